@@ -0,0 +1,16 @@
+-- 주문 저장소 (SQLite/PostgreSQL 공통 스키마)
+-- body 는 Order JSON, 나머지 컬럼은 조회용 인덱스
+CREATE TABLE IF NOT EXISTS orders (
+    id TEXT PRIMARY KEY,
+    client_order_id TEXT,
+    symbol TEXT NOT NULL,
+    status TEXT NOT NULL,
+    status_updated_at BIGINT NOT NULL,
+    created_at BIGINT NOT NULL,
+    body TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS orders_client_order_id_idx ON orders (client_order_id);
+CREATE INDEX IF NOT EXISTS orders_symbol_idx ON orders (symbol);
+CREATE INDEX IF NOT EXISTS orders_status_idx ON orders (status);
+CREATE INDEX IF NOT EXISTS orders_created_at_idx ON orders (created_at);
@@ -1,5 +1,7 @@
 "use client";
 import { useEffect, useState } from 'react';
+import { subscribeChannel } from '@/src/lib/ws';
+import { toNumber } from '@/src/lib/decimal';
 
 "use client";
 export default function MarketPage() {
@@ -16,7 +18,7 @@ export default function MarketPage() {
         const json = await res.json();
         if (alive) {
           setData(json);
-          const p = Number(json.close || json.price);
+          const p = toNumber(json.close ?? json.price);
           const t = Number(json.timestamp || Date.now());
           if (p) setSeries(s => [...s.slice(-180), { t, p }]);
         }
@@ -29,23 +31,10 @@ export default function MarketPage() {
 
   useEffect(() => {
     // WebSocket live updates (fallback to polling already active)
-    let alive = true;
-    let ws: WebSocket | null = null;
-    try {
-      const wsBase = base.replace(/^http/, 'ws');
-      ws = new WebSocket(`${wsBase}/ws/prices/${encodeURIComponent(symbol)}`);
-      ws.onmessage = (evt) => {
-        if (!alive) return;
-        try {
-          const msg = JSON.parse(evt.data as string);
-          if (msg && typeof msg.price === 'number') {
-            const t = Date.now();
-            setSeries(s => [...s.slice(-180), { t, p: msg.price }]);
-          }
-        } catch {}
-      };
-    } catch {}
-    return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+    return subscribeChannel('prices', (md) => {
+      const p = toNumber(md?.close);
+      if (p) setSeries(s => [...s.slice(-180), { t: Date.now(), p }]);
+    }, symbol, base);
   }, [symbol, base]);
 
   return (
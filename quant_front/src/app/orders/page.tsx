@@ -1,5 +1,6 @@
 "use client";
 import { useEffect, useState } from 'react';
+import { subscribeChannel } from '@/src/lib/ws';
 
 async function fetchJSON(url: string) {
   const r = await fetch(url, { cache: 'no-store' });
@@ -33,20 +34,7 @@ export default function OrdersPage() {
 
   useEffect(() => {
     // WebSocket live updates
-    let alive = true;
-    let ws: WebSocket | null = null;
-    try {
-      const wsBase = base.replace(/^http/, 'ws');
-      ws = new WebSocket(`${wsBase}/ws/orders`);
-      ws.onmessage = (evt) => {
-        if (!alive) return;
-        try {
-          const list = JSON.parse(evt.data as string);
-          if (Array.isArray(list)) setOrders(list);
-        } catch {}
-      };
-    } catch {}
-    return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+    return subscribeChannel('orders', (list) => { if (Array.isArray(list)) setOrders(list); }, undefined, base);
   }, [base]);
 
   return (
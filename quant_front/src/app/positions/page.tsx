@@ -1,5 +1,6 @@
 "use client";
 import { useEffect, useState } from 'react';
+import { subscribeChannel } from '@/src/lib/ws';
 
 export default function PositionsPage() {
   const base = process.env.NEXT_PUBLIC_AXUM_BASE || 'http://localhost:4000';
@@ -16,20 +17,7 @@ export default function PositionsPage() {
 
   useEffect(() => {
     // WebSocket live updates
-    let alive = true;
-    let ws: WebSocket | null = null;
-    try {
-      const wsBase = base.replace(/^http/, 'ws');
-      ws = new WebSocket(`${wsBase}/ws/positions`);
-      ws.onmessage = (evt) => {
-        if (!alive) return;
-        try {
-          const list = JSON.parse(evt.data as string);
-          if (Array.isArray(list)) setRows(list);
-        } catch {}
-      };
-    } catch {}
-    return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+    return subscribeChannel('positions', (list) => { if (Array.isArray(list)) setRows(list); }, undefined, base);
   }, [base]);
 
   return (
@@ -1,26 +1,14 @@
 "use client";
 import Link from 'next/link';
 import { useEffect, useState } from 'react';
+import { subscribeChannel } from '@/src/lib/ws';
 
 export default function StrategiesLive() {
   const base = process.env.NEXT_PUBLIC_AXUM_BASE || 'http://localhost:4000';
   const [items, setItems] = useState<[string, boolean][]>([]);
 
   useEffect(() => {
-    let alive = true;
-    let ws: WebSocket | null = null;
-    try {
-      const wsBase = base.replace(/^http/, 'ws');
-      ws = new WebSocket(`${wsBase}/ws/strategies`);
-      ws.onmessage = (evt) => {
-        if (!alive) return;
-        try {
-          const list = JSON.parse(evt.data as string);
-          if (Array.isArray(list)) setItems(list);
-        } catch {}
-      };
-    } catch {}
-    return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+    return subscribeChannel('strategies', (list) => { if (Array.isArray(list)) setItems(list); }, undefined, base);
   }, [base]);
 
   async function toggle(name: string, active: boolean) {
@@ -1,5 +1,7 @@
 "use client";
 import { useEffect, useMemo, useState } from 'react';
+import { subscribeChannel } from '@/src/lib/ws';
+import { toNumber } from '@/src/lib/decimal';
 
 function guessSymbolFromName(name: string): string {
   const parts = name.split(/[-_]/);
@@ -19,42 +21,20 @@ export default function StrategyLiveInfo({ name }: { name: string }) {
   const [series, setSeries] = useState<{ t: number; p: number }[]>([]);
 
   useEffect(() => {
-    let alive = true;
-    const wsBase = base.replace(/^http/, 'ws');
-    const wsO = new WebSocket(`${wsBase}/ws/orders`);
-    const wsP = new WebSocket(`${wsBase}/ws/positions`);
-    wsO.onmessage = (evt) => {
-      if (!alive) return;
-      try { const list = JSON.parse(evt.data as string); if (Array.isArray(list)) setOrders(list); } catch {}
-    };
-    wsP.onmessage = (evt) => {
-      if (!alive) return;
-      try { const list = JSON.parse(evt.data as string); if (Array.isArray(list)) setPositions(list); } catch {}
-    };
-    return () => { alive = false; try { wsO.close(); wsP.close(); } catch {} };
+    const closeOrders = subscribeChannel('orders', (list) => { if (Array.isArray(list)) setOrders(list); }, undefined, base);
+    const closePositions = subscribeChannel('positions', (list) => { if (Array.isArray(list)) setPositions(list); }, undefined, base);
+    return () => { closeOrders(); closePositions(); };
   }, [base]);
 
   useEffect(() => {
     // Live price via WS
     if (!symbol) return;
-    let alive = true;
-    let ws: WebSocket | null = null;
-    try {
-      const wsBase = base.replace(/^http/, 'ws');
-      ws = new WebSocket(`${wsBase}/ws/prices/${encodeURIComponent(symbol)}`);
-      ws.onmessage = (evt) => {
-        if (!alive) return;
-        try {
-          const msg = JSON.parse(evt.data as string);
-          if (msg && typeof msg.price === 'number') {
-            setPrice(msg.price);
-            const t = Date.now();
-            setSeries(s => [...s.slice(-180), { t, p: msg.price }]);
-          }
-        } catch {}
-      };
-    } catch {}
-    return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+    return subscribeChannel('prices', (md) => {
+      const p = toNumber(md?.close);
+      if (!p) return;
+      setPrice(p);
+      setSeries(s => [...s.slice(-180), { t: Date.now(), p }]);
+    }, symbol, base);
   }, [base, symbol]);
 
   const filteredOrders = useMemo(() => {
@@ -0,0 +1,10 @@
+// Prices, quantities and fees arrive as decimal strings ("50000.1") to keep them exact;
+// indicator values are plain numbers. Returns null for missing or non-numeric values
+export function toNumber(value: unknown): number | null {
+  if (typeof value === 'number') return Number.isFinite(value) ? value : null;
+  if (typeof value === 'string' && value.trim() !== '') {
+    const n = Number(value);
+    return Number.isFinite(n) ? n : null;
+  }
+  return null;
+}
@@ -0,0 +1,23 @@
+import { AXUM_BASE } from './config';
+
+export type WsChannel = 'prices' | 'orders' | 'positions' | 'strategies' | 'order_events';
+
+// Subscribe to one channel of the multiplexed /ws endpoint; returns a function that closes the socket
+export function subscribeChannel(channel: WsChannel, onData: (data: any) => void, symbol?: string, base: string = AXUM_BASE): () => void {
+  let alive = true;
+  let ws: WebSocket | null = null;
+  try {
+    ws = new WebSocket(`${base.replace(/^http/, 'ws')}/ws`);
+    ws.onopen = () => {
+      try { ws?.send(JSON.stringify({ op: 'subscribe', channel, symbol })); } catch {}
+    };
+    ws.onmessage = (evt) => {
+      if (!alive) return;
+      try {
+        const msg = JSON.parse(evt.data as string);
+        if (msg && msg.channel === channel && msg.data !== undefined) onData(msg.data);
+      } catch {}
+    };
+  } catch {}
+  return () => { alive = false; if (ws) { try { ws.close(); } catch {} } };
+}
@@ -14,6 +14,7 @@ use crate::strategies::combined::CombinedStrategy;
 use crate::core::strategy_manager::StrategyManager;
 use serde::{Deserialize, Serialize};
 use crate::order_core::manager::OrderManager;
+use crate::models::decimal::Decimal;
 use crate::models::order::{Order, OrderId, OrderSide, OrderType};
 
 /// 전략 목록 조회 핸들러
@@ -62,8 +63,21 @@ pub async fn create_ta_strategy(
       
       TechnicalStrategy::macd(req.symbol, fast_period, slow_period, signal_period)
     },
+    "bollinger" => {
+      let period = req.params["period"].as_u64().unwrap_or(20) as usize;
+      let std_dev = req.params["std_dev"].as_f64().unwrap_or(2.0);
+      
+      TechnicalStrategy::bollinger(req.symbol, period, std_dev)
+    },
+    "ichimoku" => {
+      let tenkan_period = req.params["tenkan_period"].as_u64().unwrap_or(9) as usize;
+      let kijun_period = req.params["kijun_period"].as_u64().unwrap_or(26) as usize;
+      let senkou_b_period = req.params["senkou_b_period"].as_u64().unwrap_or(52) as usize;
+      
+      TechnicalStrategy::ichimoku(req.symbol, tenkan_period, kijun_period, senkou_b_period)
+    },
     "multi_indicator" => {
-      TechnicalStrategy::multi_indicator(req.symbol)
+      TechnicalStrategy::multi_indicator(req.symbol, None)
     },
     "rsi_twap" => {
       let period = req.params["period"].as_u64().unwrap_or(14) as usize;
@@ -254,6 +268,10 @@ pub struct IndicatorQuery {
   pub signal_period: Option<usize>,
   pub overbought: Option<f64>,
   pub oversold: Option<f64>,
+  pub std_dev: Option<f64>,
+  pub tenkan_period: Option<usize>,
+  pub kijun_period: Option<usize>,
+  pub senkou_b_period: Option<usize>,
   pub limit: Option<usize>,
 }
 
@@ -348,6 +366,21 @@ pub async fn calculate_indicators(
       let period = query.period.unwrap_or(14);
       Box::new(crate::indicators::volume::VolumeWeightedAveragePrice::new(period))
     },
+    "bollinger" => {
+      let period = query.period.unwrap_or(20);
+      let std_dev = query.std_dev.unwrap_or(2.0);
+      Box::new(crate::indicators::volatility::BollingerBands::new(period, std_dev))
+    },
+    "atr" => {
+      let period = query.period.unwrap_or(14);
+      Box::new(crate::indicators::volatility::AverageTrueRange::new(period))
+    },
+    "ichimoku" => {
+      let tenkan_period = query.tenkan_period.unwrap_or(9);
+      let kijun_period = query.kijun_period.unwrap_or(26);
+      let senkou_b_period = query.senkou_b_period.unwrap_or(52);
+      Box::new(crate::indicators::trend::Ichimoku::new(tenkan_period, kijun_period, senkou_b_period))
+    },
     _ => {
       let error_response = serde_json::json!({
                 "error": format!("Unknown indicator type: {}", query.indicator_type),
@@ -362,7 +395,7 @@ pub async fn calculate_indicators(
   
   for candle in &historical_data {
     // 인디케이터 업데이트
-    if let Err(e) = indicator.update(candle.close, Some(candle.volume)) {
+    if let Err(e) = indicator.update_ohlc(candle.open_f64(), candle.high_f64(), candle.low_f64(), candle.close_f64(), Some(candle.volume_f64())) {
       continue; // 업데이트 실패 시 스킵
     }
     
@@ -370,14 +403,14 @@ pub async fn calculate_indicators(
     if indicator.is_ready() {
       if let Ok(result) = indicator.calculate() {
         values.push(IndicatorValue {
-          timestamp: candle.timestamp,
+          timestamp: candle.timestamp.as_millis(),
           value: result.value,
         });
         
         // 신호 처리
         for signal in &result.signals {
           all_signals.push(SignalInfo {
-            timestamp: candle.timestamp,
+            timestamp: candle.timestamp.as_millis(),
             name: signal.name.clone(),
             strength: signal.strength,
             message: signal.message.clone(),
@@ -447,6 +480,19 @@ pub async fn calculate_backtest_performance(
       
       TechnicalStrategy::macd(req.symbol.clone(), fast_period, slow_period, signal_period)
     },
+    "bollinger" => {
+      let period = req.params["period"].as_u64().unwrap_or(20) as usize;
+      let std_dev = req.params["std_dev"].as_f64().unwrap_or(2.0);
+      
+      TechnicalStrategy::bollinger(req.symbol.clone(), period, std_dev)
+    },
+    "ichimoku" => {
+      let tenkan_period = req.params["tenkan_period"].as_u64().unwrap_or(9) as usize;
+      let kijun_period = req.params["kijun_period"].as_u64().unwrap_or(26) as usize;
+      let senkou_b_period = req.params["senkou_b_period"].as_u64().unwrap_or(52) as usize;
+      
+      TechnicalStrategy::ichimoku(req.symbol.clone(), tenkan_period, kijun_period, senkou_b_period)
+    },
     _ => {
       let error_response = serde_json::json!({
                 "error": format!("Unknown strategy type: {}", req.strategy_type),
@@ -487,6 +533,11 @@ pub async fn calculate_backtest_performance(
                                 "max_drawdown": result.max_drawdown(),
                                 "profit_factor": result.profit_factor(),
                                 "car": result.car(),
+                                "sortino_ratio": result.sortino_ratio(),
+                                "calmar_ratio": result.calmar_ratio(),
+                                "market_exposure": result.market_exposure,
+                                "avg_holding_hours": result.average_holding_hours(),
+                                "excursions": result.excursion_stats(),
                             });
               
               Ok(with_status(json(&response), StatusCode::OK))
@@ -578,8 +629,8 @@ pub struct CreateOrderRequest {
   pub symbol: String,
   pub side: OrderSide,
   pub order_type: OrderType,
-  pub quantity: f64,
-  pub price: Option<f64>,
+  pub quantity: Decimal,
+  pub price: Option<Decimal>,
 }
 
 #[derive(Debug, Serialize)]
@@ -593,7 +644,7 @@ pub async fn create_order(
   order_manager: Arc<RwLock<OrderManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
   // Build order
-  let price = req.price.unwrap_or(0.0);
+  let price = req.price.unwrap_or_default();
   let order = Order::new(req.symbol, req.side, req.order_type, req.quantity, price);
 
   let id_res = {
@@ -639,7 +690,7 @@ pub async fn cancel_order(
 
 // VWAP endpoints
 #[derive(Debug, Deserialize)]
-pub struct CreateVwapRequest { pub symbol: String, pub side: OrderSide, pub target_quantity: f64, pub execution_interval_ms: i64, pub vwap_window: Option<usize> }
+pub struct CreateVwapRequest { pub symbol: String, pub side: OrderSide, pub target_quantity: Decimal, pub execution_interval_ms: i64, pub vwap_window: Option<usize> }
 
 pub async fn create_vwap_order(
   req: CreateVwapRequest,
@@ -669,7 +720,7 @@ pub async fn get_vwap_status(
 ) -> Result<impl Reply, warp::Rejection> {
   let manager = strategy_manager.read().await;
   match manager.get_strategy_status(&id) {
-    Ok((name, active)) => Ok(with_status(json(&serde_json::json!({"name": name, "active": active})), StatusCode::OK)),
+    Ok(status) => Ok(with_status(json(&status), StatusCode::OK)),
     Err(e) => Ok(with_status(json(&serde_json::json!({"error": format!("{}", e)})), StatusCode::NOT_FOUND))
   }
 }
@@ -687,7 +738,7 @@ pub async fn cancel_vwap_order(
 
 // Iceberg endpoints
 #[derive(Debug, Deserialize)]
-pub struct CreateIcebergRequest { pub symbol: String, pub side: OrderSide, pub total_quantity: f64, pub display_size: f64 }
+pub struct CreateIcebergRequest { pub symbol: String, pub side: OrderSide, pub total_quantity: Decimal, pub display_size: Decimal }
 
 pub async fn create_iceberg_order(
   req: CreateIcebergRequest,
@@ -696,7 +747,7 @@ pub async fn create_iceberg_order(
   strategy_manager: Arc<RwLock<StrategyManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
   // 지정가는 현재가 기반 외부에서 설정해야 하지만, 여기서는 표시수량 기반으로 즉시 등록
-  let limit_price = 0.0; // 실제 연결 시 현재가로 대체
+  let limit_price = Decimal::ZERO; // 실제 연결 시 현재가로 대체
   let strategy = crate::strategies::iceberg::IcebergStrategy::new(
     req.symbol.clone(), req.side, req.total_quantity, limit_price, req.display_size
   );
@@ -715,7 +766,7 @@ pub async fn get_iceberg_status(
 ) -> Result<impl Reply, warp::Rejection> {
   let manager = strategy_manager.read().await;
   match manager.get_strategy_status(&id) {
-    Ok((name, active)) => Ok(with_status(json(&serde_json::json!({"name": name, "active": active})), StatusCode::OK)),
+    Ok(status) => Ok(with_status(json(&status), StatusCode::OK)),
     Err(e) => Ok(with_status(json(&serde_json::json!({"error": format!("{}", e)})), StatusCode::NOT_FOUND))
   }
 }
@@ -733,7 +784,7 @@ pub async fn cancel_iceberg_order(
 
 // Trailing stop endpoints
 #[derive(Debug, Deserialize)]
-pub struct CreateTrailingStopRequest { pub symbol: String, pub side: OrderSide, pub quantity: f64, pub trailing_delta: f64 }
+pub struct CreateTrailingStopRequest { pub symbol: String, pub side: OrderSide, pub quantity: Decimal, pub trailing_delta: f64 }
 
 pub async fn create_trailing_stop(
   req: CreateTrailingStopRequest,
@@ -759,7 +810,7 @@ pub async fn get_trailing_stop_status(
 ) -> Result<impl Reply, warp::Rejection> {
   let manager = strategy_manager.read().await;
   match manager.get_strategy_status(&id) {
-    Ok((name, active)) => Ok(with_status(json(&serde_json::json!({"name": name, "active": active})), StatusCode::OK)),
+    Ok(status) => Ok(with_status(json(&status), StatusCode::OK)),
     Err(e) => Ok(with_status(json(&serde_json::json!({"error": format!("{}", e)})), StatusCode::NOT_FOUND))
   }
 }
@@ -0,0 +1,460 @@
+//! 백테스트 공유 계좌
+//!
+//! 모든 심볼이 하나의 현금(호가 통화) 잔고를 나눠 쓰는 모의 계좌. 주문은 `MockExchange` 와 같은
+//! 체결 모델(`FillModel`)로 캔들 기준 체결하고 (지연 후 도달, 지정가는 가격이 뚫고 지나갈 때 메이커,
+//! 캔들 거래량 비례 부분 체결), 심볼별 포지션을 평균 단가로 관리하며 청산 손익, 수수료, 자산 곡선을
+//! 기록한다. 레버리지는 없으며 롱/숏 포지션의
+//! 총 노출이 자산을 넘는 주문은 거부된다.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::exchange::fill_model::{FillModel, SimulatedFill};
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::fee::{FeeAttribution, FeeLedger};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+use crate::models::timestamp::Timestamp;
+use crate::models::trade::Trade;
+
+const QTY_EPSILON: f64 = 1e-12;
+
+/// 자산 곡선 기록 간격 (낙폭은 매 캔들 기준으로 별도 계산)
+const EQUITY_SAMPLE_MS: i64 = 60 * 60 * 1000;
+
+/// 자산 곡선 한 점
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub equity: f64,
+}
+
+/// 포지션 청산 한 건 (진입 평균가 대비 청산 손익, 수수료 제외)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosedTrade {
+    pub symbol: String,
+    /// 청산된 포지션 방향 (매수 = 롱)
+    pub side: OrderSide,
+    pub quantity: f64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub pnl: f64,
+    pub closed_at: Timestamp,
+    /// 청산 체결 ID
+    pub exit_trade_id: String,
+    /// 포지션 진입 시각 (보유 시간 계산용)
+    #[serde(default)]
+    pub opened_at: Timestamp,
+    /// 보유 중 최대 역행폭 (진입가 대비 비율)
+    #[serde(default)]
+    pub mae: f64,
+    /// 보유 중 최대 순행폭 (진입가 대비 비율)
+    #[serde(default)]
+    pub mfe: f64,
+}
+
+/// 심볼별 결과
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolResult {
+    pub symbol: String,
+    pub trades: usize,
+    /// 체결 명목 금액 합
+    pub volume: f64,
+    pub fees: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    /// 최종 포지션 (음수 = 숏)
+    pub position: f64,
+    pub average_price: f64,
+    pub last_price: f64,
+    /// 실현 + 미실현 - 수수료
+    pub net_pnl: f64,
+    /// 자산 부족(총 노출 > 자산)으로 거부된 주문
+    pub rejected_orders: usize,
+    /// 종료 시 미체결 주문
+    pub open_orders: usize,
+}
+
+/// 보유 포지션의 진입 시각과 보유 중 가격 범위
+struct OpenPosition {
+    opened_at: Timestamp,
+    high: f64,
+    low: f64,
+}
+
+/// 대기 주문 - 부분 체결 잔량과 호가창 도달 여부
+struct PendingOrder {
+    order: Order,
+    remaining: Decimal,
+    submitted_at: i64,
+    arrived: bool,
+}
+
+/// 공유 현금 계좌
+pub struct BacktestAccount {
+    quote_asset: String,
+    initial_cash: f64,
+    cash: f64,
+    fill_model: FillModel,
+    /// 수수료 체계 + 30일 거래대금 (등급별 요율로 체결 수수료 재계산)
+    fees: FeeLedger,
+    symbols: BTreeMap<String, SymbolResult>,
+    pending: Vec<PendingOrder>,
+    open_positions: BTreeMap<String, OpenPosition>,
+    trades: Vec<Trade>,
+    closed_trades: Vec<ClosedTrade>,
+    fees_paid: f64,
+    equity_curve: Vec<EquityPoint>,
+    peak_equity: f64,
+    max_drawdown: f64,
+    /// 포지션 보유 시간 / 전체 평가 구간 (ms)
+    exposed_ms: i64,
+    first_mark_ms: Option<i64>,
+    last_mark_ms: Option<i64>,
+    exposed_since_last_mark: bool,
+}
+
+impl BacktestAccount {
+    pub fn new(quote_asset: impl Into<String>, initial_cash: f64, fee_rate: f64, slippage: f64) -> Self {
+        Self::with_fill_model(quote_asset, initial_cash, FillModel::flat(fee_rate, slippage))
+    }
+
+    pub fn with_fill_model(quote_asset: impl Into<String>, initial_cash: f64, fill_model: FillModel) -> Self {
+        BacktestAccount {
+            quote_asset: quote_asset.into(),
+            initial_cash,
+            cash: initial_cash,
+            fees: FeeLedger::new(fill_model.schedule()),
+            fill_model,
+            symbols: BTreeMap::new(),
+            pending: Vec::new(),
+            open_positions: BTreeMap::new(),
+            trades: Vec::new(),
+            closed_trades: Vec::new(),
+            fees_paid: 0.0,
+            equity_curve: Vec::new(),
+            peak_equity: initial_cash,
+            max_drawdown: 0.0,
+            exposed_ms: 0,
+            first_mark_ms: None,
+            last_mark_ms: None,
+            exposed_since_last_mark: false,
+        }
+    }
+
+    pub fn quote_asset(&self) -> &str {
+        &self.quote_asset
+    }
+
+    pub fn initial_cash(&self) -> f64 {
+        self.initial_cash
+    }
+
+    pub fn cash(&self) -> f64 {
+        self.cash
+    }
+
+    /// 현금 + 보유 포지션 평가액
+    pub fn equity(&self) -> f64 {
+        self.cash + self.symbols.values().map(|s| s.position * s.last_price).sum::<f64>()
+    }
+
+    fn symbol_mut(&mut self, symbol: &str) -> &mut SymbolResult {
+        self.symbols.entry(symbol.to_string()).or_insert_with(|| SymbolResult { symbol: symbol.to_string(), ..SymbolResult::default() })
+    }
+
+    /// 새 주문 - 지연이 없으면 현재 캔들 종가로 바로 도달 (시장가/즉시 체결 가능한 지정가는 테이커 체결),
+    /// 잔량과 나머지 주문은 대기
+    pub fn submit(&mut self, mut order: Order, bar: &MarketData) {
+        if order.order_type == OrderType::StopLimit && order.price <= Decimal::ZERO {
+            // 지정가 없는 스탑 지정가는 스탑 시장가로 처리
+            order.order_type = OrderType::StopLoss;
+        }
+        let mut entry = PendingOrder { remaining: order.quantity, submitted_at: bar.timestamp.as_millis(), arrived: false, order };
+        if self.fill_model.latency_ms <= 0 {
+            entry.arrived = true;
+            if let Some(fill) = self.fill_model.on_arrival(&entry.order, entry.remaining, bar.close, bar) {
+                if !self.fill(&entry.order, &fill, bar.timestamp) {
+                    return;
+                }
+                entry.remaining -= fill.quantity;
+            }
+        }
+        if entry.remaining > Decimal::ZERO {
+            self.pending.push(entry);
+        }
+    }
+
+    /// 새 캔들 반영 - 해당 심볼의 대기 주문 도달/체결 확인 후 평가가 갱신
+    pub fn on_bar(&mut self, bar: &MarketData) {
+        // 이미 보유 중인 포지션의 가격 범위는 체결 전에 반영
+        if let Some(open) = self.open_positions.get_mut(&bar.symbol) {
+            open.high = open.high.max(bar.high_f64());
+            open.low = open.low.min(bar.low_f64());
+        }
+        let mut remaining = Vec::new();
+        for mut entry in std::mem::take(&mut self.pending) {
+            if entry.order.symbol != bar.symbol {
+                remaining.push(entry);
+                continue;
+            }
+            let mut fill = None;
+            if !entry.arrived {
+                if !self.fill_model.has_arrived(entry.submitted_at, bar.timestamp.as_millis()) {
+                    remaining.push(entry);
+                    continue;
+                }
+                // 지연 후 도달한 주문은 이 캔들 시가 기준으로 먼저 체결
+                entry.arrived = true;
+                fill = self.fill_model.on_arrival(&entry.order, entry.remaining, bar.open, bar);
+            }
+            if entry.order.order_type == OrderType::StopLimit {
+                let stop = entry.order.stop_price.unwrap_or(entry.order.price);
+                let hit = match entry.order.side {
+                    OrderSide::Buy => bar.high >= stop,
+                    OrderSide::Sell => bar.low <= stop,
+                };
+                if hit {
+                    // 발동 후에는 지정가 주문으로 다음 캔들부터 대기
+                    entry.order.order_type = OrderType::Limit;
+                    entry.order.stop_price = None;
+                }
+                remaining.push(entry);
+                continue;
+            }
+            if let Some(fill) = fill.or_else(|| self.fill_model.on_bar(&entry.order, entry.remaining, bar)) {
+                if !self.fill(&entry.order, &fill, bar.timestamp) {
+                    continue;
+                }
+                entry.remaining -= fill.quantity;
+                if entry.order.order_type == OrderType::StopLoss {
+                    // 발동된 스탑의 잔량은 시장가로 이어서 체결
+                    entry.order.order_type = OrderType::Market;
+                }
+            }
+            if entry.remaining > Decimal::ZERO {
+                remaining.push(entry);
+            }
+        }
+        self.pending = remaining;
+        self.mark(bar);
+    }
+
+    /// 평가가 갱신 및 자산/낙폭 기록
+    pub fn mark(&mut self, bar: &MarketData) {
+        if bar.close <= Decimal::ZERO {
+            return;
+        }
+        self.symbol_mut(&bar.symbol).last_price = bar.close_f64();
+        let now = bar.timestamp.as_millis();
+        if let Some(last) = self.last_mark_ms {
+            if self.exposed_since_last_mark && now > last {
+                self.exposed_ms += now - last;
+            }
+        }
+        self.first_mark_ms.get_or_insert(now);
+        self.last_mark_ms = Some(self.last_mark_ms.map_or(now, |last| last.max(now)));
+        self.exposed_since_last_mark = !self.open_positions.is_empty();
+        let equity = self.equity();
+        self.peak_equity = self.peak_equity.max(equity);
+        if self.peak_equity > 0.0 {
+            self.max_drawdown = self.max_drawdown.max((self.peak_equity - equity) / self.peak_equity);
+        }
+        let time = bar.timestamp.to_datetime();
+        let due = self.equity_curve.last().is_none_or(|p| (time - p.timestamp).num_milliseconds() >= EQUITY_SAMPLE_MS);
+        if due {
+            self.equity_curve.push(EquityPoint { timestamp: time, equity });
+        }
+    }
+
+    /// 보유 포지션 평가액 절대값 합 (롱 + 숏)
+    fn gross_exposure(&self) -> f64 {
+        self.symbols.values().map(|s| (s.position * s.last_price).abs()).sum()
+    }
+
+    /// 체결 - 레버리지 없음: 포지션을 늘리는 주문은 체결 후 총 노출이 자산을 넘으면 거부 (false 반환).
+    /// 체결 기록은 Decimal 그대로 남기고 손익/자산 통계는 f64 로 계산
+    fn fill(&mut self, order: &Order, fill: &SimulatedFill, timestamp: Timestamp) -> bool {
+        let (price, quantity) = (to_f64(fill.price), to_f64(fill.quantity));
+        if quantity <= 0.0 || price <= 0.0 {
+            return false;
+        }
+        let notional = quantity * price;
+        let fee = self.fees.fee(&order.symbol, fill.liquidity, notional, timestamp.as_millis());
+        let signed = match order.side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+        let position = self.symbols.get(&order.symbol).map(|s| s.position).unwrap_or(0.0);
+        let after = position + signed;
+        if after.abs() > position.abs() + QTY_EPSILON {
+            let current = self.symbols.get(&order.symbol).map(|s| (s.position * s.last_price).abs()).unwrap_or(0.0);
+            let exposure = self.gross_exposure() - current + after.abs() * price;
+            let equity = self.equity() - fee;
+            if exposure > equity {
+                log::debug!("backtest order {} rejected: exposure {:.2} > equity {:.2}", order.id, exposure, equity);
+                self.symbol_mut(&order.symbol).rejected_orders += 1;
+                return false;
+            }
+        }
+
+        let trade = Trade::new(format!("bt-{}", self.trades.len() + 1), order.symbol.clone(), fill.price, fill.quantity, timestamp, order.id.clone(), order.side.clone());
+        self.fees.charge(&order.symbol, fill.liquidity, notional, timestamp.as_millis());
+        self.cash -= signed * price + fee;
+        self.fees_paid += fee;
+
+        let (opened_at, high, low) = self.open_positions.get(&order.symbol)
+            .map_or((timestamp, price, price), |open| (open.opened_at, open.high.max(price), open.low.min(price)));
+        let state = self.symbol_mut(&order.symbol);
+        state.trades += 1;
+        state.volume += notional;
+        state.fees += fee;
+        state.last_price = price;
+        let mut closed = None;
+        let opened = state.position.abs() < QTY_EPSILON;
+        let mut flipped = false;
+        if opened || state.position.signum() == signed.signum() {
+            let total = state.position.abs() + quantity;
+            state.average_price = (state.average_price * state.position.abs() + notional) / total;
+            state.position += signed;
+        } else {
+            let closed_quantity = quantity.min(state.position.abs());
+            let pnl = state.position.signum() * (price - state.average_price) * closed_quantity;
+            state.realized_pnl += pnl;
+            let entry = state.average_price;
+            let (adverse, favorable) = if state.position > 0.0 { (entry - low, high - entry) } else { (high - entry, entry - low) };
+            closed = Some(ClosedTrade {
+                symbol: order.symbol.clone(),
+                side: if state.position > 0.0 { OrderSide::Buy } else { OrderSide::Sell },
+                quantity: closed_quantity,
+                entry_price: state.average_price,
+                exit_price: price,
+                pnl,
+                closed_at: timestamp,
+                exit_trade_id: trade.id.clone(),
+                opened_at,
+                mae: adverse.max(0.0) / entry,
+                mfe: favorable.max(0.0) / entry,
+            });
+            state.position += signed;
+            if state.position.abs() < QTY_EPSILON {
+                state.position = 0.0;
+                state.average_price = 0.0;
+            } else if state.position.signum() == signed.signum() {
+                // 반대 방향으로 넘어가면 남은 수량은 체결가로 새로 진입
+                state.average_price = price;
+                flipped = true;
+            }
+        }
+        if state.position == 0.0 {
+            self.open_positions.remove(&order.symbol);
+        } else if opened || flipped {
+            self.open_positions.insert(order.symbol.clone(), OpenPosition { opened_at: timestamp, high: price, low: price });
+        }
+        self.closed_trades.extend(closed);
+        self.trades.push(trade);
+        true
+    }
+
+    pub fn trades(&self) -> &[Trade] {
+        &self.trades
+    }
+
+    pub fn closed_trades(&self) -> &[ClosedTrade] {
+        &self.closed_trades
+    }
+
+    pub fn fees_paid(&self) -> f64 {
+        self.fees_paid
+    }
+
+    /// 메이커/테이커, 수수료 등급별 수수료
+    pub fn fee_attribution(&self) -> &FeeAttribution {
+        self.fees.attribution()
+    }
+
+    /// 포지션을 하나라도 보유한 시간 비율 (0~1)
+    pub fn market_exposure(&self) -> f64 {
+        match (self.first_mark_ms, self.last_mark_ms) {
+            (Some(first), Some(last)) if last > first => self.exposed_ms as f64 / (last - first) as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// 캔들 단위 최대 낙폭 (비율)
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    /// 자산 곡선 (마지막 평가 시점 포함)
+    pub fn equity_curve(&self, end: DateTime<Utc>) -> Vec<EquityPoint> {
+        let mut curve = self.equity_curve.clone();
+        if curve.last().is_some_and(|p| p.timestamp < end) {
+            curve.push(EquityPoint { timestamp: end, equity: self.equity() });
+        }
+        curve
+    }
+
+    /// 심볼별 결과 (미실현 손익/미체결 주문 포함)
+    pub fn symbol_results(&self) -> BTreeMap<String, SymbolResult> {
+        self.symbols.iter().map(|(symbol, state)| {
+            let mut result = state.clone();
+            result.unrealized_pnl = state.position * (state.last_price - state.average_price);
+            result.net_pnl = result.realized_pnl + result.unrealized_pnl - result.fees;
+            result.open_orders = self.pending.iter().filter(|p| &p.order.symbol == symbol).count();
+            (symbol.clone(), result)
+        }).collect()
+    }
+
+    /// 최종 잔고 (호가 통화 현금 + 심볼별 보유 수량, 기초 자산 이름 기준)
+    pub fn balances(&self) -> std::collections::HashMap<String, f64> {
+        let mut balances = std::collections::HashMap::new();
+        balances.insert(self.quote_asset.clone(), self.cash);
+        for (symbol, state) in &self.symbols {
+            if state.position != 0.0 {
+                let asset = symbol.strip_suffix(self.quote_asset.as_str()).filter(|a| !a.is_empty()).unwrap_or(symbol);
+                *balances.entry(asset.to_string()).or_insert(0.0) += state.position;
+            }
+        }
+        balances
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    fn bar(symbol: &str, ts: i64, open: f64, high: f64, low: f64, close: f64) -> MarketData {
+        MarketData::new(symbol, ts, to_decimal(open), to_decimal(high), to_decimal(low), to_decimal(close), dec!(10))
+    }
+
+    #[test]
+    fn test_shared_cash_across_symbols() {
+        let mut account = BacktestAccount::new("USDT", 10_000.0, 0.001, 0.0);
+        account.submit(Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(0.1), dec!(0)), &bar("BTCUSDT", 0, 50_000.0, 50_000.0, 50_000.0, 50_000.0));
+        // BTC 5,000 보유 중 ETH 3개(6,000)를 더하면 총 노출이 자산을 넘음
+        account.submit(Order::new("ETHUSDT", OrderSide::Buy, OrderType::Market, dec!(3), dec!(0)), &bar("ETHUSDT", 0, 2_000.0, 2_000.0, 2_000.0, 2_000.0));
+        account.submit(Order::new("ETHUSDT", OrderSide::Buy, OrderType::Limit, dec!(2), dec!(1_900)), &bar("ETHUSDT", 0, 2_000.0, 2_000.0, 2_000.0, 2_000.0));
+        assert_eq!(account.trades().len(), 1);
+
+        // 지정가는 저가가 닿은 캔들에서 지정가로 체결
+        account.on_bar(&bar("ETHUSDT", 60_000, 2_000.0, 2_010.0, 1_890.0, 1_950.0));
+        account.submit(Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, dec!(0.1), dec!(0)), &bar("BTCUSDT", 60_000, 51_000.0, 51_000.0, 51_000.0, 51_000.0));
+        account.on_bar(&bar("BTCUSDT", 60_000, 51_000.0, 51_000.0, 51_000.0, 51_000.0));
+
+        let results = account.symbol_results();
+        let (btc, eth) = (&results["BTCUSDT"], &results["ETHUSDT"]);
+        assert_eq!((btc.trades, btc.position, btc.realized_pnl), (2, 0.0, 100.0));
+        assert_eq!((eth.trades, eth.rejected_orders, eth.position), (1, 1, 2.0));
+        assert!((eth.unrealized_pnl - 100.0).abs() < 1e-9);
+        assert_eq!(account.closed_trades().len(), 1);
+
+        let fees = 5.0 + 5.1 + 3.8;
+        assert!((account.fees_paid() - fees).abs() < 1e-9);
+        assert!((account.equity() - (10_000.0 + 100.0 + 100.0 - fees)).abs() < 1e-9);
+        assert!((account.balances()["ETH"] - 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,249 @@
+//! 벤치마크 비교
+//!
+//! 같은 초기 자산을 벤치마크(거래 심볼 매수 후 보유, 또는 사용자 가격/지수 시계열)에 넣어 두었을 때의 자산 곡선을
+//! 전략 자산 곡선 시각에 맞춰(as-of) 만들고, 초과 수익, 알파/베타, 벤치마크 대비 상대 낙폭을 구한다.
+//! 수익률은 샤프 비율과 같은 UTC 일별 기준이다.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::market_data::external::ExternalSeries;
+use crate::models::decimal::to_f64;
+use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
+use super::account::EquityPoint;
+use super::performance::PerformanceMetrics;
+
+/// 벤치마크 이름과 시간순 가격
+type BenchmarkPrices = (String, Vec<(DateTime<Utc>, f64)>);
+
+/// 벤치마크 지정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BenchmarkSpec {
+    /// 시작 시점에 전액 매수 후 보유 (심볼 생략 시 단일 심볼 백테스트의 그 심볼, 다중 심볼이면 비교 생략)
+    BuyAndHold {
+        #[serde(default)]
+        symbol: Option<String>,
+    },
+    /// `timestamp,value` 형식 가격/지수 CSV
+    Series { name: String, path: PathBuf },
+}
+
+impl Default for BenchmarkSpec {
+    fn default() -> Self {
+        BenchmarkSpec::BuyAndHold { symbol: None }
+    }
+}
+
+impl BenchmarkSpec {
+    pub fn buy_and_hold(symbol: impl Into<String>) -> Self {
+        BenchmarkSpec::BuyAndHold { symbol: Some(symbol.into()) }
+    }
+
+    pub fn series(name: impl Into<String>, path: PathBuf) -> Self {
+        BenchmarkSpec::Series { name: name.into(), path }
+    }
+
+    /// 벤치마크 데이터 파일 (재현성 매니페스트 해시 대상)
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            BenchmarkSpec::Series { path, .. } => Some(path),
+            BenchmarkSpec::BuyAndHold { .. } => None,
+        }
+    }
+
+    /// 기간 내 벤치마크 가격 (이름, 시간순 가격) - 비교할 심볼을 정할 수 없으면 None
+    pub fn prices(
+        &self,
+        market_data: &HashMap<String, Vec<MarketData>>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<BenchmarkPrices>, TradingError> {
+        match self {
+            BenchmarkSpec::BuyAndHold { symbol } => {
+                let symbol = match symbol {
+                    Some(symbol) => symbol.clone(),
+                    None if market_data.len() == 1 => market_data.keys().next().cloned().unwrap_or_default(),
+                    None => return Ok(None),
+                };
+                let Some(series) = market_data.get(&symbol) else {
+                    log::warn!("benchmark symbol {} has no backtest data", symbol);
+                    return Ok(None);
+                };
+                let prices = series.iter()
+                    .map(|d| (d.timestamp.to_datetime(), to_f64(d.close)))
+                    .filter(|(t, _)| *t >= start && *t <= end)
+                    .collect();
+                Ok(Some((format!("{} 매수 후 보유", symbol), prices)))
+            }
+            BenchmarkSpec::Series { name, path } => {
+                let series = ExternalSeries::load_csv(name.as_str(), path, 0, usize::MAX)?;
+                let prices = series.range(Timestamp::from(start), Timestamp::from(end)).into_iter()
+                    .map(|(t, v)| (t.to_datetime(), v))
+                    .collect();
+                Ok(Some((name.clone(), prices)))
+            }
+        }
+    }
+}
+
+/// 벤치마크 비교 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub name: String,
+    pub initial_value: f64,
+    /// 벤치마크에 넣어 두었을 때의 최종 자산가치
+    pub final_value: f64,
+    pub benchmark_return: f64,
+    pub strategy_return: f64,
+    /// 전략 수익률 - 벤치마크 수익률
+    pub excess_return: f64,
+    /// 연환산 알파 (일별 수익률 회귀 절편 x 252)
+    pub alpha: f64,
+    pub beta: f64,
+    pub correlation: f64,
+    /// 벤치마크 자체의 최대 낙폭
+    pub max_drawdown: f64,
+    /// 전략/벤치마크 자산 비율의 최대 낙폭 (벤치마크 대비 가장 크게 뒤처진 폭)
+    pub relative_max_drawdown: f64,
+    /// 전략 자산 곡선 시각에 맞춘 벤치마크 자산 곡선
+    pub equity_curve: Vec<EquityPoint>,
+}
+
+/// 값 시계열의 최대 낙폭 (비율)
+fn max_drawdown(values: impl Iterator<Item = f64>) -> f64 {
+    let (mut peak, mut max_drawdown) = (f64::MIN, 0.0_f64);
+    for value in values {
+        peak = peak.max(value);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - value) / peak);
+        }
+    }
+    max_drawdown
+}
+
+impl BenchmarkReport {
+    /// 시간순 벤치마크 가격으로 비교 - 전략 자산 곡선이 비었거나 유효한 가격이 없으면 None
+    pub fn compute(name: impl Into<String>, strategy_curve: &[EquityPoint], initial_value: f64, prices: &[(DateTime<Utc>, f64)]) -> Option<Self> {
+        let last = strategy_curve.last()?;
+        let base = prices.first().map(|(_, p)| *p).filter(|p| *p > 0.0)?;
+        if initial_value <= 0.0 {
+            return None;
+        }
+
+        // 첫 가격 이전 시각은 첫 가격으로 평가 (시작 시점 매수)
+        let equity_curve: Vec<EquityPoint> = strategy_curve.iter().map(|point| {
+            let count = prices.partition_point(|(t, _)| *t <= point.timestamp);
+            let price = count.checked_sub(1).map_or(base, |i| prices[i].1);
+            EquityPoint { timestamp: point.timestamp, equity: initial_value * price / base }
+        }).collect();
+        let final_value = equity_curve.last().map_or(initial_value, |p| p.equity);
+
+        let strategy = PerformanceMetrics::daily_returns_from_equity(strategy_curve, initial_value);
+        let benchmark = PerformanceMetrics::daily_returns_from_equity(&equity_curve, initial_value);
+        let n = strategy.len().min(benchmark.len()) as f64;
+        let (alpha, beta, correlation) = if n < 2.0 {
+            (0.0, 0.0, 0.0)
+        } else {
+            let mean = |r: &[f64]| r.iter().sum::<f64>() / n;
+            let (ms, mb) = (mean(&strategy), mean(&benchmark));
+            let cov = strategy.iter().zip(&benchmark).map(|(s, b)| (s - ms) * (b - mb)).sum::<f64>() / n;
+            let var_s = strategy.iter().map(|s| (s - ms).powi(2)).sum::<f64>() / n;
+            let var_b = benchmark.iter().map(|b| (b - mb).powi(2)).sum::<f64>() / n;
+            let beta = if var_b > 0.0 { cov / var_b } else { 0.0 };
+            let correlation = if var_s > 0.0 && var_b > 0.0 { cov / (var_s.sqrt() * var_b.sqrt()) } else { 0.0 };
+            ((ms - beta * mb) * 252.0, beta, correlation)
+        };
+
+        let strategy_return = last.equity / initial_value - 1.0;
+        let benchmark_return = final_value / initial_value - 1.0;
+        Some(BenchmarkReport {
+            name: name.into(),
+            initial_value,
+            final_value,
+            benchmark_return,
+            strategy_return,
+            excess_return: strategy_return - benchmark_return,
+            alpha,
+            beta,
+            correlation,
+            max_drawdown: max_drawdown(equity_curve.iter().map(|p| p.equity)),
+            relative_max_drawdown: max_drawdown(strategy_curve.iter().zip(&equity_curve).map(|(s, b)| s.equity / b.equity)),
+            equity_curve,
+        })
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        summary.push_str(&format!("벤치마크: {} (최종 ${:.2})\n", self.name, self.final_value));
+        summary.push_str(&format!(
+            "수익률: 전략 {:.2}%, 벤치마크 {:.2}%, 초과 {:.2}%\n",
+            self.strategy_return * 100.0, self.benchmark_return * 100.0, self.excess_return * 100.0
+        ));
+        summary.push_str(&format!("알파(연환산): {:.2}%, 베타: {:.3}, 상관계수: {:.3}\n", self.alpha * 100.0, self.beta, self.correlation));
+        summary.push_str(&format!(
+            "최대 낙폭: 벤치마크 {:.2}%, 벤치마크 대비 상대 {:.2}%\n",
+            self.max_drawdown * 100.0, self.relative_max_drawdown * 100.0
+        ));
+        summary
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use crate::models::decimal::dec;
+
+    #[test]
+    fn test_benchmark_report() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let moves = [0.02, -0.01, 0.03, -0.02, 0.01];
+        let (mut price, mut equity) = (100.0, 1_000.0);
+        let mut prices = vec![(start, price)];
+        let mut curve = vec![EquityPoint { timestamp: start, equity }];
+        // 전략은 매일 벤치마크 수익률의 2배 -> 베타 2, 알파 0
+        for (i, r) in moves.iter().enumerate() {
+            let t = start + Duration::days(i as i64 + 1);
+            price *= 1.0 + r;
+            equity *= 1.0 + 2.0 * r;
+            prices.push((t, price));
+            curve.push(EquityPoint { timestamp: t, equity });
+        }
+
+        let report = BenchmarkReport::compute("BTC", &curve, 1_000.0, &prices).unwrap();
+        assert!((report.beta - 2.0).abs() < 1e-9);
+        assert!(report.alpha.abs() < 1e-9);
+        assert!((report.correlation - 1.0).abs() < 1e-9);
+        assert!((report.final_value - 1_000.0 * price / 100.0).abs() < 1e-9);
+        assert!((report.excess_return - (equity / 1_000.0 - price / 100.0)).abs() < 1e-12);
+        assert_eq!(report.equity_curve.len(), curve.len());
+        // 벤치마크 -2% 일에 전략은 -4% -> 상대 낙폭 발생
+        assert!(report.relative_max_drawdown > 0.0);
+        assert!(report.max_drawdown > 0.0 && report.max_drawdown < report.relative_max_drawdown + 0.05);
+
+        assert!(BenchmarkReport::compute("BTC", &curve, 1_000.0, &[]).is_none());
+        assert!(BenchmarkReport::compute("BTC", &[], 1_000.0, &prices).is_none());
+
+        // 심볼 미지정 매수 후 보유는 단일 심볼일 때만
+        let bar = |symbol: &str| MarketData::new(symbol, start, dec!(100), dec!(100), dec!(100), dec!(100), dec!(1));
+        let one = HashMap::from([("BTCUSDT".to_string(), vec![bar("BTCUSDT")])]);
+        let (name, _) = BenchmarkSpec::default().prices(&one, start, start).unwrap().unwrap();
+        assert!(name.starts_with("BTCUSDT"));
+        let mut two = one.clone();
+        two.insert("ETHUSDT".into(), vec![bar("ETHUSDT")]);
+        assert!(BenchmarkSpec::default().prices(&two, start, start).unwrap().is_none());
+    }
+}
@@ -1,8 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use chrono::{DateTime, Utc};
 
 use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::market_data::aggregator::parse_timeframe;
+use crate::models::decimal::Decimal;
 use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
 
 pub trait HistoricalDataProvider {
     fn available_symbols(&self) -> Vec<String>;
@@ -46,10 +51,11 @@ impl HistoricalDataProvider for CsvDataProvider {
             .from_path(&self.path)
             .map_err(|e| TradingError::IoError(e.into()))?;
 
+        let (start, end) = (Timestamp::from(start_time), Timestamp::from(end_time));
         let mut result = Vec::new();
         for rec in rdr.deserialize() {
             let row: CsvRow = rec.map_err(|e| TradingError::ParseError(e.to_string()))?;
-            if row.timestamp >= start_time.timestamp_millis() && row.timestamp <= end_time.timestamp_millis() {
+            if row.timestamp >= start && row.timestamp <= end {
                 result.push(MarketData {
                     symbol: row.symbol,
                     timestamp: row.timestamp,
@@ -65,13 +71,177 @@ impl HistoricalDataProvider for CsvDataProvider {
     }
 }
 
+/// 거래소 캔들(klines) 다운로더 - 기간을 페이지 단위로 나눠 받고, 요청 제한(429/418)과 일시 오류는
+/// 지수 백오프로 재시도한 뒤 `CsvDataProvider` 형식으로 저장
+pub struct ExchangeDataDownloader<'a> {
+    exchange: &'a dyn Exchange,
+    /// 요청당 캔들 수 (Binance 현물 최대 1000, 선물 최대 1500)
+    page_size: usize,
+    /// 페이지 사이 대기 시간
+    page_delay: Duration,
+    max_retries: u32,
+    /// 첫 재시도 대기 시간 (재시도마다 두 배)
+    retry_backoff: Duration,
+}
+
+impl<'a> ExchangeDataDownloader<'a> {
+    pub fn new(exchange: &'a dyn Exchange) -> Self {
+        Self {
+            exchange,
+            page_size: 1000,
+            page_delay: Duration::from_millis(250),
+            max_retries: 5,
+            retry_backoff: Duration::from_secs(1),
+        }
+    }
+
+    pub fn page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    pub fn page_delay(mut self, delay: Duration) -> Self {
+        self.page_delay = delay;
+        self
+    }
+
+    pub fn retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// [start, end) 구간 캔들을 시간순으로 수집 (중복 시각 제거)
+    pub async fn download(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<MarketData>, TradingError> {
+        let interval_ms = parse_timeframe(interval)?;
+        let end = end_time.timestamp_millis();
+        let mut cursor = start_time.timestamp_millis();
+        let mut candles: Vec<MarketData> = Vec::new();
+
+        while cursor < end {
+            let mut page = self.fetch_page(symbol, interval, cursor, end - 1).await?;
+            page.retain(|c| c.timestamp.as_millis() >= cursor && c.timestamp.as_millis() < end);
+            page.sort_by_key(|c| c.timestamp);
+            let Some(last) = page.last().map(|c| c.timestamp.as_millis()) else { break };
+
+            candles.extend(page);
+            log::info!("{} {} 캔들 {}개 수집 (~{})", symbol, interval, candles.len(), Timestamp::from_millis(last).to_rfc3339());
+            cursor = last + interval_ms;
+            if cursor < end && !self.page_delay.is_zero() {
+                tokio::time::sleep(self.page_delay).await;
+            }
+        }
+
+        candles.dedup_by_key(|c| c.timestamp);
+        Ok(candles)
+    }
+
+    /// 다운로드 후 CSV 저장, 저장한 캔들 수 반환
+    pub async fn download_to_csv(
+        &self,
+        path: &Path,
+        symbol: &str,
+        interval: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<usize, TradingError> {
+        let candles = self.download(symbol, interval, start_time, end_time).await?;
+        if candles.is_empty() {
+            return Err(TradingError::DataNotFound(format!("No klines for {} {} in range", symbol, interval)));
+        }
+        write_csv(path, &candles)?;
+        Ok(candles.len())
+    }
+
+    async fn fetch_page(&self, symbol: &str, interval: &str, start: i64, end: i64) -> Result<Vec<MarketData>, TradingError> {
+        let mut attempt = 0;
+        loop {
+            match self.exchange.get_historical_data(symbol, interval, start, Some(end), Some(self.page_size)).await {
+                Ok(page) => return Ok(page),
+                Err(e) if attempt < self.max_retries && e.is_retryable() => {
+                    let wait = e.retry_after().unwrap_or_default().max(self.retry_backoff * 2u32.pow(attempt));
+                    log::warn!("klines 요청 실패, {:?} 후 재시도 ({}/{}): {}", wait, attempt + 1, self.max_retries, e);
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// 백테스트 CSV 형식(symbol,timestamp,open,high,low,close,volume)으로 저장 (시각은 epoch ms)
+pub fn write_csv(path: &Path, candles: &[MarketData]) -> Result<(), TradingError> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut writer = csv::Writer::from_path(path).map_err(|e| TradingError::IoError(e.into()))?;
+    writer.write_record(["symbol", "timestamp", "open", "high", "low", "close", "volume"])
+        .map_err(|e| TradingError::IoError(e.into()))?;
+    for c in candles {
+        writer.write_record([
+            c.symbol.clone(),
+            c.timestamp.as_millis().to_string(),
+            c.open.to_string(),
+            c.high.to_string(),
+            c.low.to_string(),
+            c.close.to_string(),
+            c.volume.to_string(),
+        ]).map_err(|e| TradingError::IoError(e.into()))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
 #[derive(serde::Deserialize)]
 struct CsvRow {
     symbol: String,
-    timestamp: i64,
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
-    volume: f64,
+    // epoch ms 또는 RFC3339
+    timestamp: Timestamp,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use chrono::TimeZone;
+    use crate::config::Config;
+    use crate::exchange::mocks::MockExchange;
+
+    #[tokio::test]
+    async fn test_paginated_download_round_trips_through_csv() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut exchange = MockExchange::empty(Config::default());
+        for i in 0..25 {
+            let price = 100.0 + i as f64;
+            let timestamp = Timestamp::from_millis(start.timestamp_millis() + i * 60_000);
+            exchange.push_market_data(MarketData { symbol: "BTCUSDT".into(), timestamp, open: to_decimal(price), high: to_decimal(price), low: to_decimal(price), close: to_decimal(price), volume: Decimal::ONE }).unwrap();
+        }
+
+        let downloader = ExchangeDataDownloader::new(&exchange).page_size(10).page_delay(Duration::ZERO);
+        let end = start + chrono::Duration::minutes(20);
+        let candles = downloader.download("BTCUSDT", "1m", start, end).await.unwrap();
+        assert_eq!(candles.len(), 20);
+        assert!(candles.windows(2).all(|w| w[1].timestamp.as_millis() - w[0].timestamp.as_millis() == 60_000));
+
+        let path = std::env::temp_dir().join(format!("xquant-download-{}.csv", uuid::Uuid::new_v4()));
+        write_csv(&path, &candles).unwrap();
+        let loaded = CsvDataProvider::new(path.clone(), ',').unwrap().load_data("BTCUSDT", start, end).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.len(), 20);
+        assert_eq!(loaded[19].close, dec!(119));
+        assert!(!TradingError::InvalidSymbol("Invalid symbol.".into()).is_retryable());
+        assert!(TradingError::RateLimited { retry_after: None }.is_retryable());
+    }
 }
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+
+/// 장애 종류
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisruptionKind {
+    /// 거래소 장애 - 시세 수신 불가 + 주문 거부
+    ExchangeOutage,
+    /// 거래 정지 - 해당 심볼 체결/시세 없음 + 주문 거부
+    TradingHalt,
+    /// 데이터 누락 - 시세만 끊기고 주문은 가능
+    DataGap,
+}
+
+/// 장애 구간 (종료 시각 미포함)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisruptionWindow {
+    pub kind: DisruptionKind,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// 대상 심볼 (비어 있으면 전체 심볼)
+    #[serde(default)]
+    pub symbols: Vec<String>,
+}
+
+impl DisruptionWindow {
+    fn applies(&self, symbol: &str, time: DateTime<Utc>) -> bool {
+        time >= self.start
+            && time < self.end
+            && (self.symbols.is_empty() || self.symbols.iter().any(|s| s == symbol))
+    }
+}
+
+/// 장애 시뮬레이션 집계
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DisruptionStats {
+    pub windows: usize,
+    pub dropped_bars: usize,
+    pub rejected_orders: usize,
+}
+
+/// 백테스트 장애 스케줄
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DisruptionSchedule {
+    windows: Vec<DisruptionWindow>,
+}
+
+impl DisruptionSchedule {
+    pub fn new(windows: Vec<DisruptionWindow>) -> Self {
+        DisruptionSchedule { windows }
+    }
+
+    pub fn add(&mut self, window: DisruptionWindow) {
+        self.windows.push(window);
+    }
+
+    pub fn windows(&self) -> &[DisruptionWindow] {
+        &self.windows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// 해당 시점에 시세가 전달되는지
+    pub fn is_data_available(&self, symbol: &str, time: DateTime<Utc>) -> bool {
+        !self.windows.iter().any(|w| w.applies(symbol, time))
+    }
+
+    /// 해당 시점 주문이 거부되면 사유 반환
+    pub fn order_rejection(&self, symbol: &str, time: DateTime<Utc>) -> Option<String> {
+        self.windows.iter()
+            .filter(|w| w.kind != DisruptionKind::DataGap)
+            .find(|w| w.applies(symbol, time))
+            .map(|w| match w.kind {
+                DisruptionKind::TradingHalt => format!("{} trading halted until {}", symbol, w.end),
+                _ => format!("exchange unavailable until {}", w.end),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disruption_schedule() {
+        let t = |m: i64| DateTime::<Utc>::from_timestamp_millis(m * 60_000).unwrap();
+        let schedule = DisruptionSchedule::new(vec![
+            DisruptionWindow { kind: DisruptionKind::ExchangeOutage, start: t(10), end: t(20), symbols: Vec::new() },
+            DisruptionWindow { kind: DisruptionKind::TradingHalt, start: t(30), end: t(40), symbols: vec!["ETHUSDT".into()] },
+            DisruptionWindow { kind: DisruptionKind::DataGap, start: t(50), end: t(60), symbols: Vec::new() },
+        ]);
+
+        assert!(schedule.is_data_available("BTCUSDT", t(5)));
+        assert!(!schedule.is_data_available("BTCUSDT", t(10)));
+        assert!(schedule.order_rejection("BTCUSDT", t(15)).is_some());
+        assert!(schedule.is_data_available("BTCUSDT", t(20)));
+
+        assert!(schedule.order_rejection("BTCUSDT", t(35)).is_none());
+        assert!(schedule.order_rejection("ETHUSDT", t(35)).is_some());
+
+        assert!(!schedule.is_data_available("BTCUSDT", t(55)));
+        assert!(schedule.order_rejection("BTCUSDT", t(55)).is_none());
+    }
+}
@@ -1,18 +1,53 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use chrono::{DateTime, Duration, Utc};
 use tokio::sync::RwLock;
 
 use crate::error::TradingError;
 use crate::models::market_data::MarketData;
-use crate::models::order::{Order, OrderStatus};
+use crate::models::timestamp::Timestamp;
 use crate::models::trade::Trade;
 use crate::core::strategy_manager::StrategyManager;
 use crate::exchange::traits::Exchange;
+use crate::exchange::fill_model::FillModel;
 use crate::exchange::mocks::MockExchange;
+use crate::market_data::aggregator::CandleAggregator;
+use crate::market_data::external::ExternalSeriesStore;
 use crate::strategies::Strategy;
+use super::account::BacktestAccount;
 use super::result::BacktestResult;
 use super::data_provider::HistoricalDataProvider;
+use super::manifest::ReproducibilityManifest;
+use super::disruption::{DisruptionSchedule, DisruptionStats};
+use super::tick::{TickDataProvider, TradeTick};
+use super::benchmark::{BenchmarkReport, BenchmarkSpec};
+
+/// 백테스트 진행률 (처리한 봉 / 전체 봉) - 실행 중 다른 태스크에서 조회
+#[derive(Debug, Clone, Default)]
+pub struct BacktestProgress {
+    processed: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+}
+
+impl BacktestProgress {
+    pub fn processed(&self) -> usize {
+        self.processed.load(Ordering::Relaxed)
+    }
+
+    /// 타임라인 구성 전에는 0
+    pub fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    /// 0.0 ~ 1.0
+    pub fn fraction(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => (self.processed() as f64 / total as f64).min(1.0),
+        }
+    }
+}
 
 /// 백테스트 엔진 - 전략 백테스팅을 위한 코어 컴포넌트
 pub struct BacktestEngine {
@@ -26,7 +61,24 @@ pub struct BacktestEngine {
     initial_balance: HashMap<String, f64>,
     fee_rate: f64,
     slippage: f64,
-    data_provider: Option<super::data_provider::CsvDataProvider>,
+    /// 체결 모델 (없으면 fee_rate/slippage 단일 요율)
+    fill_model: Option<FillModel>,
+    /// 데이터 제공자 (심볼 지정 시 해당 심볼로 조회)
+    data_providers: Vec<(Option<String>, Box<dyn HistoricalDataProvider + Send + Sync>)>,
+    /// 틱 데이터 (심볼별, 해당 심볼의 캔들 대신 사용)
+    ticks: HashMap<String, Vec<TradeTick>>,
+    tick_providers: Vec<(String, Box<dyn TickDataProvider + Send + Sync>)>,
+    /// 틱 재생 시 전략에 전달할 캔들 주기 (ms, 없으면 틱마다 전달)
+    tick_bar_ms: Option<i64>,
+    /// 공유 현금 계좌의 호가 통화
+    quote_asset: String,
+    manifest: Option<ReproducibilityManifest>,
+    disruptions: DisruptionSchedule,
+    disruption_stats: DisruptionStats,
+    external: Option<ExternalSeriesStore>,
+    /// 벤치마크 (없으면 비교 생략)
+    benchmark: Option<BenchmarkSpec>,
+    progress: BacktestProgress,
 }
 
 impl BacktestEngine {
@@ -54,7 +106,18 @@ impl BacktestEngine {
             initial_balance,
             fee_rate,
             slippage,
-            data_provider: None,
+            fill_model: None,
+            data_providers: Vec::new(),
+            ticks: HashMap::new(),
+            tick_providers: Vec::new(),
+            tick_bar_ms: None,
+            quote_asset: "USDT".to_string(),
+            manifest: None,
+            disruptions: DisruptionSchedule::default(),
+            disruption_stats: DisruptionStats::default(),
+            external: None,
+            benchmark: None,
+            progress: BacktestProgress::default(),
         }
     }
     
@@ -63,9 +126,69 @@ impl BacktestEngine {
         self.market_data.insert(symbol.to_string(), data);
     }
     
-    /// 데이터 제공자 설정
+    /// 데이터 제공자 설정 (파일 안의 모든 심볼 사용)
     pub fn set_data_provider(&mut self, provider: super::data_provider::CsvDataProvider) {
-        self.data_provider = Some(provider);
+        self.data_providers.push((None, Box::new(provider)));
+    }
+    
+    /// 심볼별 데이터 제공자 추가
+    pub fn add_symbol_provider(&mut self, symbol: impl Into<String>, provider: Box<dyn HistoricalDataProvider + Send + Sync>) {
+        self.data_providers.push((Some(symbol.into()), provider));
+    }
+    
+    /// 틱 데이터 직접 추가 (해당 심볼은 틱 단위로 재생)
+    pub fn add_tick_data(&mut self, symbol: &str, ticks: Vec<TradeTick>) {
+        self.ticks.insert(symbol.to_string(), ticks);
+    }
+    
+    /// 심볼별 틱 데이터 제공자 추가
+    pub fn add_tick_provider(&mut self, symbol: impl Into<String>, provider: Box<dyn TickDataProvider + Send + Sync>) {
+        self.tick_providers.push((symbol.into(), provider));
+    }
+    
+    /// 틱 재생 심볼의 전략 입력 캔들 주기 - 체결은 틱마다 평가하고 전략은 완성된 캔들만 받음
+    pub fn set_tick_bar_interval(&mut self, interval_ms: i64) {
+        self.tick_bar_ms = Some(interval_ms).filter(|ms| *ms > 0);
+    }
+    
+    /// 시세 데이터 또는 데이터 제공자가 설정되었는지
+    pub fn has_data_source(&self) -> bool {
+        !self.market_data.is_empty() || !self.data_providers.is_empty() || !self.ticks.is_empty() || !self.tick_providers.is_empty()
+    }
+    
+    /// 공유 현금 계좌의 호가 통화 설정 (초기 잔고 중 이 통화만 현금으로 사용)
+    pub fn set_quote_asset(&mut self, asset: impl Into<String>) {
+        self.quote_asset = asset.into();
+    }
+    
+    /// 체결 모델 설정 (지연, 거래량 비례 부분 체결, 메이커/테이커 수수료)
+    pub fn set_fill_model(&mut self, fill_model: FillModel) {
+        self.fill_model = Some(fill_model);
+    }
+    
+    /// 재현성 매니페스트 설정 (결과에 포함됨)
+    pub fn set_manifest(&mut self, manifest: ReproducibilityManifest) {
+        self.manifest = Some(manifest);
+    }
+    
+    /// 거래소 장애/거래 정지 스케줄 설정
+    pub fn set_disruptions(&mut self, disruptions: DisruptionSchedule) {
+        self.disruptions = disruptions;
+    }
+    
+    /// 외부 시계열 설정 - 각 캔들 시각 기준 값이 전략에 전달됨
+    pub fn set_external_series(&mut self, store: ExternalSeriesStore) {
+        self.external = Some(store);
+    }
+    
+    /// 벤치마크 설정 - 결과에 초과 수익, 알파/베타, 상대 낙폭 포함
+    pub fn set_benchmark(&mut self, benchmark: BenchmarkSpec) {
+        self.benchmark = Some(benchmark);
+    }
+    
+    /// 진행률 핸들 (복제본이 같은 카운터를 공유)
+    pub fn progress(&self) -> BacktestProgress {
+        self.progress.clone()
     }
     
     /// 전략 추가
@@ -76,69 +199,132 @@ impl BacktestEngine {
     /// 백테스트 실행
     pub async fn run(&mut self) -> Result<BacktestResult, TradingError> {
         // 데이터 로드 확인
+        if !self.has_data_source() {
+            return Err(TradingError::InsufficientData);
+        }
         if self.market_data.is_empty() {
-            if let Some(provider) = &self.data_provider {
-                // 데이터 제공자를 통해 시장 데이터 로드
-                let symbols = provider.available_symbols();
+            // 데이터 제공자를 통해 시장 데이터 로드 - 행의 심볼 기준으로 묶음 (한 파일에 여러 심볼 가능)
+            for (symbol, provider) in &self.data_providers {
+                let symbols = symbol.iter().cloned().collect::<Vec<_>>();
+                let symbols = if symbols.is_empty() { provider.available_symbols() } else { symbols };
                 for symbol in symbols {
-                    let data = provider.load_data(&symbol, self.start_time, self.end_time)?;
-                    self.market_data.insert(symbol.clone(), data);
+                    for data in provider.load_data(&symbol, self.start_time, self.end_time)? {
+                        self.market_data.entry(data.symbol.clone()).or_default().push(data);
+                    }
                 }
-            } else {
-                return Err(TradingError::InsufficientData);
             }
         }
+        // 틱 데이터가 있는 심볼은 캔들 대신 체결 한 건씩 점 캔들로 재생
+        for (symbol, provider) in &self.tick_providers {
+            let ticks = provider.load_ticks(symbol, self.start_time, self.end_time)?;
+            self.ticks.entry(symbol.clone()).or_default().extend(ticks);
+        }
+        self.tick_providers.clear();
+        let tick_symbols: HashSet<String> = self.ticks.keys().cloned().collect();
+        for (symbol, ticks) in &self.ticks {
+            if self.market_data.contains_key(symbol) {
+                log::warn!("backtest candles for {} replaced by tick data", symbol);
+            }
+            self.market_data.insert(symbol.clone(), ticks.iter().map(TradeTick::to_market_data).collect());
+        }
+        // 정렬은 안정 정렬이라 같은 시각 틱의 순서는 유지됨
+        for series in self.market_data.values_mut() {
+            series.sort_by_key(|d| d.timestamp);
+        }
+        let mut tick_bars: HashMap<String, CandleAggregator> = match self.tick_bar_ms {
+            Some(ms) => tick_symbols.iter().map(|s| (s.clone(), CandleAggregator::new(ms))).collect(),
+            None => HashMap::new(),
+        };
         
-        // 시간 기준으로 정렬된 데이터 인덱스 만들기
-        let mut timeline: Vec<(i64, String)> = Vec::new();
-        
+        // 시간순 타임라인 (시작-종료 시간 내, 같은 시각은 심볼 이름순)
+        let (start, end) = (Timestamp::from(self.start_time), Timestamp::from(self.end_time));
+        let mut filtered_timeline: Vec<(Timestamp, String, usize)> = Vec::new();
         for (symbol, data_series) in &self.market_data {
-            for data in data_series {
-                timeline.push((data.timestamp, symbol.clone()));
+            for (index, data) in data_series.iter().enumerate() {
+                if data.timestamp >= start && data.timestamp <= end {
+                    filtered_timeline.push((data.timestamp, symbol.clone(), index));
+                }
             }
         }
+        filtered_timeline.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        self.progress.processed.store(0, Ordering::Relaxed);
+        self.progress.total.store(filtered_timeline.len(), Ordering::Relaxed);
         
-        // 시간순 정렬
-        timeline.sort_by(|a, b| a.0.cmp(&b.0));
-        
-        // 필터링된 타임라인 (시작-종료 시간 내)
-        let filtered_timeline: Vec<_> = timeline
-          .into_iter()
-          .filter(|(time, _)| *time >= self.start_time.timestamp_millis() && *time <= self.end_time.timestamp_millis())
-          .collect();
-        
-        // 초기 포트폴리오 가치 계산
-        let initial_value = 0.0; // MockExchange 포트폴리오 값 계산은 별도 구현 대상
+        // 모든 심볼이 하나의 현금 잔고를 공유
+        let initial_cash = self.initial_balance.get(&self.quote_asset).copied().unwrap_or(0.0);
+        for asset in self.initial_balance.keys().filter(|a| **a != self.quote_asset) {
+            log::warn!("backtest initial balance {} ignored: only {} is used as cash", asset, self.quote_asset);
+        }
+        let fill_model = self.fill_model.clone().unwrap_or_else(|| FillModel::flat(self.fee_rate, self.slippage));
+        let mut account = BacktestAccount::with_fill_model(self.quote_asset.clone(), initial_cash, fill_model);
+        let initial_value = account.initial_cash();
         
         // 시간에 따라 시뮬레이션 실행
         let mut current_time = self.start_time;
+        self.disruption_stats = DisruptionStats {
+            windows: self.disruptions.windows().len(),
+            ..Default::default()
+        };
         
-        for (time_ms, symbol) in filtered_timeline {
-            current_time = DateTime::<Utc>::from_timestamp_millis(time_ms).unwrap_or(self.start_time);
+        for (time, symbol, index) in filtered_timeline {
+            current_time = time.to_datetime();
+            self.progress.processed.fetch_add(1, Ordering::Relaxed);
             
-            // 현재 시장 데이터 가져오기
-            if let Some(data) = self.get_market_data(&symbol, current_time)? {
+            // 장애 구간의 시세는 전략에 전달하지 않음 (데이터 공백)
+            if !self.disruptions.is_data_available(&symbol, current_time) {
+                self.disruption_stats.dropped_bars += 1;
+                continue;
+            }
+            
+            // 현재 시장 데이터 - 대기 주문 체결과 평가가 갱신 후 전략에 전달
+            if let Some(data) = self.market_data.get(&symbol).and_then(|series| series.get(index)).cloned() {
+                account.on_bar(&data);
+                
+                // 틱 재생 + 캔들 주기 지정 시 전략은 완성된 캔들만 받고, 주문은 다음 구간 첫 틱 가격에서 처리
+                let strategy_bar = match tick_bars.get_mut(&symbol) {
+                    Some(aggregator) => aggregator.push(&data),
+                    None => Some(data.clone()),
+                };
+                let Some(strategy_bar) = strategy_bar else { continue };
+                
+                // 외부 시계열은 해당 캔들 시각까지 관측된 값만 전달
+                if let Some(external) = &self.external {
+                    self.strategy_manager.update_external_all(&external.inputs_at(data.timestamp));
+                }
+                
                 // 모든 전략 업데이트
-                self.strategy_manager.update_all(&data)?;
+                self.strategy_manager.update_all(&strategy_bar)?;
                 
                 // 주문 생성 및 처리
                 let orders = self.strategy_manager.get_all_orders()?;
                 for order in orders {
-                    let _ = self.process_order(order, current_time)?;
+                    if let Some(reason) = self.disruptions.order_rejection(&order.symbol, current_time) {
+                        log::debug!("백테스트 주문 거부: {}", reason);
+                        self.disruption_stats.rejected_orders += 1;
+                        continue;
+                    }
+                    // 다른 심볼 주문은 그 심볼의 마지막 시세로 처리 (아직 시세가 없으면 다음 캔들까지 대기)
+                    let bar = if order.symbol == data.symbol { Some(data.clone()) } else { self.get_market_data(&order.symbol, current_time)? };
+                    match bar {
+                        Some(bar) => account.submit(order, &bar),
+                        None => log::debug!("백테스트 주문 {} 건너뜀: {} 시세 없음", order.id, order.symbol),
+                    }
                 }
-                
-                // 미결제 주문 처리
-                let _ = self.process_pending_orders(current_time)?;
-                
-                // 거래소 상태 갱신 생략 (모의 환경)
             }
         }
         
         // 최종 결과 생성
-        let final_balance = HashMap::new();
-        let final_value = 0.0;
-        let trades = Vec::new();
-        let fee_paid = 0.0;
+        let final_balance = account.balances();
+        let final_value = account.equity();
+        let trades = account.trades().to_vec();
+        let fee_paid = account.fees_paid();
+        
+        let equity_curve = account.equity_curve(current_time);
+        let benchmark = match &self.benchmark {
+            Some(spec) => spec.prices(&self.market_data, self.start_time, current_time)?
+                .and_then(|(name, prices)| BenchmarkReport::compute(name, &equity_curve, initial_value, &prices)),
+            None => None,
+        };
         
         let profit = final_value - initial_value;
         let profit_percentage = if initial_value > 0.0 {
@@ -160,34 +346,28 @@ impl BacktestEngine {
             profit_percentage,
             trades,
             fee_paid,
+            fee_attribution: account.fee_attribution().clone(),
             symbols: self.market_data.keys().cloned().collect(),
+            manifest: self.manifest.clone(),
+            disruptions: self.disruption_stats.clone(),
+            symbol_results: account.symbol_results(),
+            closed_trades: account.closed_trades().to_vec(),
+            equity_curve,
+            portfolio_max_drawdown: account.max_drawdown(),
+            market_exposure: account.market_exposure(),
+            monte_carlo: None,
+            benchmark,
         })
     }
     
     // 특정 시간의 시장 데이터 가져오기
     fn get_market_data(&self, symbol: &str, time: DateTime<Utc>) -> Result<Option<MarketData>, TradingError> {
         if let Some(data_series) = self.market_data.get(symbol) {
-            // 정확한 시간 또는 가장 가까운 이전 데이터 찾기
-            let matching_data = data_series.iter()
-              .filter(|data| data.timestamp <= time.timestamp_millis())
-              .max_by_key(|data| data.timestamp)
-              .cloned();
-            
-            Ok(matching_data)
+            // 정확한 시간 또는 가장 가까운 이전 데이터 찾기 (시간순 정렬 상태)
+            let count = data_series.partition_point(|data| data.timestamp <= Timestamp::from(time));
+            Ok(count.checked_sub(1).map(|i| data_series[i].clone()))
         } else {
             Ok(None)
         }
     }
-    
-    // 주문 처리
-    fn process_order(&mut self, _order: Order, _time: DateTime<Utc>) -> Result<(), TradingError> {
-        // Stub: exchange order processing not implemented in MockExchange for backtest
-        Ok(())
-    }
-    
-    // 미결제 주문 처리
-    fn process_pending_orders(&mut self, _time: DateTime<Utc>) -> Result<(), TradingError> {
-        // Stub
-        Ok(())
-    }
 }
\ No newline at end of file
@@ -0,0 +1,495 @@
+//! API 백테스트 작업 관리
+//!
+//! `POST /backtests` 요청을 작업으로 큐에 넣고 `max_concurrent` 개까지 블로킹 스레드에서 실행한다.
+//! 실행 중에는 엔진 진행률을, 끝나면 전체 `BacktestResult` 를 조회할 수 있다.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::config::{BacktestJobsConfig, StrategySpec};
+use crate::error::TradingError;
+use crate::exchange::fill_model::{FillModel, SlippageModel};
+use crate::models::fee::FeeSchedule;
+use crate::strategies::build_strategy;
+use super::benchmark::BenchmarkSpec;
+use super::engine::BacktestProgress;
+use super::optimizer::{ParamAxis, ParameterSweep, SweepGrid, SweepMetric};
+use super::result::BacktestResult;
+use super::scenario::{BacktestScenario, BacktestScenarioBuilder};
+
+fn default_initial_balance() -> f64 { 10_000.0 }
+fn default_quote_asset() -> String { "USDT".to_string() }
+fn default_sweep_metric() -> SweepMetric { SweepMetric::Sharpe }
+
+/// 한 번의 스윕 요청이 실행할 수 있는 최대 조합 수
+const MAX_SWEEP_CELLS: usize = 400;
+
+/// 백테스트 작업 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestJobRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub strategies: Vec<StrategySpec>,
+    /// 모든 심볼이 들어 있는 CSV (없고 `symbol_data_files` 도 없으면 심볼별 `./data/{SYMBOL}-1m.csv`)
+    #[serde(default)]
+    pub data_file: Option<PathBuf>,
+    #[serde(default)]
+    pub symbol_data_files: BTreeMap<String, PathBuf>,
+    /// 기간 (ms) - 없으면 `last_days`
+    #[serde(default)]
+    pub start_time: Option<i64>,
+    #[serde(default)]
+    pub end_time: Option<i64>,
+    #[serde(default)]
+    pub last_days: Option<i64>,
+    #[serde(default = "default_initial_balance")]
+    pub initial_balance: f64,
+    #[serde(default = "default_quote_asset")]
+    pub quote_asset: String,
+    #[serde(default)]
+    pub fee_rate: Option<f64>,
+    #[serde(default)]
+    pub slippage: Option<f64>,
+    #[serde(default)]
+    pub fill_model: Option<FillModel>,
+    #[serde(default)]
+    pub slippage_model: Option<SlippageModel>,
+    #[serde(default)]
+    pub fee_schedule: Option<FeeSchedule>,
+    /// 벤치마크 (없으면 단일 심볼 매수 후 보유)
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkSpec>,
+}
+
+impl BacktestJobRequest {
+    /// 파일을 읽지 않는 요청 검증 (제출 시 바로 거부)
+    pub fn validate(&self) -> Result<(), TradingError> {
+        if self.strategies.is_empty() {
+            return Err(TradingError::InvalidParameter("at least one strategy is required".to_string()));
+        }
+        for spec in &self.strategies {
+            spec.validate().map_err(|e| TradingError::InvalidParameter(format!("{}: {}", spec.symbol(), e)))?;
+        }
+        if self.initial_balance <= 0.0 {
+            return Err(TradingError::InvalidParameter("initial_balance must be positive".to_string()));
+        }
+        self.period().map(|_| ())
+    }
+
+    fn period(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), TradingError> {
+        let at = |ms: i64| DateTime::<Utc>::from_timestamp_millis(ms).ok_or_else(|| TradingError::InvalidParameter(format!("invalid timestamp {}", ms)));
+        let (start, end) = match (self.start_time, self.end_time, self.last_days) {
+            (Some(start), Some(end), _) => (at(start)?, at(end)?),
+            (None, None, Some(days)) if days > 0 => {
+                let end = Utc::now();
+                (end - chrono::Duration::days(days), end)
+            }
+            _ => return Err(TradingError::InvalidParameter("start_time and end_time, or last_days, are required".to_string())),
+        };
+        if start >= end {
+            return Err(TradingError::InvalidParameter("start_time must be before end_time".to_string()));
+        }
+        Ok((start, end))
+    }
+
+    fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| {
+            let symbols: Vec<&str> = self.strategies.iter().map(|s| s.symbol()).collect();
+            format!("API backtest {}", symbols.join(","))
+        })
+    }
+
+    /// 시나리오 구성 (데이터 파일을 읽으므로 블로킹 스레드에서 호출)
+    pub fn scenario(&self) -> Result<BacktestScenario, TradingError> {
+        let (start, end) = self.period()?;
+        let mut builder = BacktestScenarioBuilder::new(self.display_name())
+            .description(format!("{} strategies via API", self.strategies.len()))
+            .period(start, end)
+            .quote_asset(self.quote_asset.clone())
+            .initial_balance(self.quote_asset.clone(), self.initial_balance);
+        if let Some(fee_rate) = self.fee_rate {
+            builder = builder.fee_rate(fee_rate);
+        }
+        if let Some(slippage) = self.slippage {
+            builder = builder.slippage(slippage);
+        }
+        if let Some(fill_model) = &self.fill_model {
+            builder = builder.fill_model(fill_model.clone());
+        }
+        if let Some(slippage_model) = &self.slippage_model {
+            builder = builder.slippage_model(slippage_model.clone());
+        }
+        if let Some(fee_schedule) = &self.fee_schedule {
+            builder = builder.fee_schedule(fee_schedule.clone());
+        }
+        if let Some(benchmark) = &self.benchmark {
+            builder = builder.benchmark(benchmark.clone());
+        }
+
+        match &self.data_file {
+            Some(path) => builder = builder.data_file(path.clone()),
+            None if self.symbol_data_files.is_empty() => {
+                let mut symbols: Vec<&str> = self.strategies.iter().map(|s| s.symbol()).collect();
+                symbols.sort();
+                symbols.dedup();
+                for symbol in symbols {
+                    builder = builder.symbol_data_file(symbol, PathBuf::from(format!("./data/{}-1m.csv", symbol)));
+                }
+            }
+            None => {}
+        }
+        for (symbol, path) in &self.symbol_data_files {
+            builder = builder.symbol_data_file(symbol.clone(), path.clone());
+        }
+        for spec in &self.strategies {
+            builder = builder.strategy(build_strategy(spec)?);
+        }
+        builder.build()
+    }
+}
+
+/// 파라미터 민감도 스윕 요청 - 기준 백테스트 요청의 한 전략에서 두 숫자 파라미터를 격자로 바꿔 실행
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestSweepRequest {
+    #[serde(flatten)]
+    pub base: BacktestJobRequest,
+    /// 파라미터를 바꿀 전략 (`strategies` 인덱스)
+    #[serde(default)]
+    pub strategy: usize,
+    /// 열 축 (예: fast_period)
+    pub x: ParamAxis,
+    /// 행 축 (예: slow_period)
+    pub y: ParamAxis,
+    /// 안정성 판정 지표
+    #[serde(default = "default_sweep_metric")]
+    pub metric: SweepMetric,
+}
+
+impl BacktestSweepRequest {
+    pub fn validate(&self) -> Result<(), TradingError> {
+        self.base.validate()?;
+        let spec = self.base.strategies.get(self.strategy)
+            .ok_or_else(|| TradingError::InvalidParameter(format!("strategy index {} out of range", self.strategy)))?;
+        let cells = self.x.values.len() * self.y.values.len();
+        if cells == 0 || cells > MAX_SWEEP_CELLS {
+            return Err(TradingError::InvalidParameter(format!("sweep grid must have 1..={} cells, got {}", MAX_SWEEP_CELLS, cells)));
+        }
+        // 축 이름이 전략의 숫자 파라미터인지 첫 값으로 확인
+        for axis in [&self.x, &self.y] {
+            spec.with_numeric_param(&axis.name, axis.values[0]).map_err(TradingError::InvalidParameter)?;
+        }
+        Ok(())
+    }
+
+    /// (x, y) 조합의 요청 - 범위를 벗어난 조합(예: fast >= slow)은 오류로 빈 칸 처리
+    fn request_at(&self, x: f64, y: f64) -> Result<BacktestJobRequest, TradingError> {
+        let mut request = self.base.clone();
+        let spec = &request.strategies[self.strategy];
+        let spec = spec.with_numeric_param(&self.x.name, x)
+            .and_then(|spec| spec.with_numeric_param(&self.y.name, y))
+            .map_err(TradingError::InvalidParameter)?;
+        request.strategies[self.strategy] = spec;
+        request.name = Some(format!("{} {}={} {}={}", self.base.display_name(), self.x.name, x, self.y.name, y));
+        Ok(request)
+    }
+
+    /// 스윕 실행 (모든 조합을 차례로 백테스트)
+    pub async fn run(&self) -> Result<SweepGrid, TradingError> {
+        self.validate()?;
+        ParameterSweep::new(self.x.clone(), self.y.clone())
+            .run(|x, y| self.request_at(x, y)?.scenario())
+            .await
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BacktestJobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl BacktestJobState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, BacktestJobState::Completed | BacktestJobState::Failed)
+    }
+}
+
+/// 완료된 작업의 요약 지표
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestJobSummary {
+    pub profit: f64,
+    pub profit_percentage: f64,
+    pub trade_count: usize,
+    pub win_rate: f64,
+    pub max_drawdown: f64,
+    pub sharpe_ratio: f64,
+}
+
+/// 작업 상태 (GET /backtests/:id)
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestJobStatus {
+    pub id: String,
+    pub name: String,
+    pub state: BacktestJobState,
+    /// 0.0 ~ 1.0
+    pub progress: f64,
+    pub bars_processed: usize,
+    pub bars_total: usize,
+    pub submitted_at: i64,
+    pub started_at: Option<i64>,
+    pub finished_at: Option<i64>,
+    pub error: Option<String>,
+    pub summary: Option<BacktestJobSummary>,
+}
+
+struct Job {
+    name: String,
+    state: BacktestJobState,
+    progress: BacktestProgress,
+    submitted_at: i64,
+    started_at: Option<i64>,
+    finished_at: Option<i64>,
+    error: Option<String>,
+    result: Option<Arc<BacktestResult>>,
+}
+
+impl Job {
+    fn status(&self, id: &str) -> BacktestJobStatus {
+        let progress = if self.state == BacktestJobState::Completed { 1.0 } else { self.progress.fraction() };
+        BacktestJobStatus {
+            id: id.to_string(),
+            name: self.name.clone(),
+            state: self.state,
+            progress,
+            bars_processed: self.progress.processed(),
+            bars_total: self.progress.total(),
+            submitted_at: self.submitted_at,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            error: self.error.clone(),
+            summary: self.result.as_ref().map(|r| BacktestJobSummary {
+                profit: r.profit,
+                profit_percentage: r.profit_percentage,
+                trade_count: r.trade_count(),
+                win_rate: r.win_rate(),
+                max_drawdown: r.max_drawdown(),
+                sharpe_ratio: r.sharpe_ratio(),
+            }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct JobTable {
+    jobs: HashMap<String, Job>,
+    /// 제출 순서 (보관 한도 초과 시 오래된 완료 작업부터 삭제)
+    order: VecDeque<String>,
+}
+
+/// 백테스트 작업 관리자 - HTTP 핸들러와 실행 태스크가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct BacktestJobManager {
+    table: Arc<Mutex<JobTable>>,
+    permits: Arc<Semaphore>,
+    max_retained: usize,
+}
+
+impl BacktestJobManager {
+    pub fn new(config: &BacktestJobsConfig) -> Self {
+        BacktestJobManager {
+            table: Arc::new(Mutex::new(JobTable::default())),
+            permits: Arc::new(Semaphore::new(config.max_concurrent.max(1))),
+            max_retained: config.max_retained.max(1),
+        }
+    }
+
+    fn table(&self) -> std::sync::MutexGuard<'_, JobTable> {
+        self.table.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn now() -> i64 {
+        Utc::now().timestamp_millis()
+    }
+
+    /// 작업 등록 후 실행 태스크 시작 - 동시 실행 한도를 넘으면 대기열에서 차례를 기다림
+    pub fn submit(&self, request: BacktestJobRequest) -> Result<BacktestJobStatus, TradingError> {
+        request.validate()?;
+        let id = Uuid::new_v4().to_string();
+        let status = {
+            let mut table = self.table();
+            let job = Job {
+                name: request.display_name(),
+                state: BacktestJobState::Queued,
+                progress: BacktestProgress::default(),
+                submitted_at: Self::now(),
+                started_at: None,
+                finished_at: None,
+                error: None,
+                result: None,
+            };
+            let status = job.status(&id);
+            table.jobs.insert(id.clone(), job);
+            table.order.push_back(id.clone());
+            status
+        };
+
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let Ok(_permit) = manager.permits.clone().acquire_owned().await else { return };
+            manager.update(&id, |job| {
+                job.state = BacktestJobState::Running;
+                job.started_at = Some(Self::now());
+            });
+            // 엔진은 CPU 를 오래 쓰므로 블로킹 스레드에서 실행 (진행률 핸들은 실행 전에 등록)
+            let runner = manager.clone();
+            let job_id = id.clone();
+            let handle = tokio::runtime::Handle::current();
+            let outcome = tokio::task::spawn_blocking(move || {
+                let mut scenario = request.scenario()?;
+                let progress = scenario.progress();
+                runner.update(&job_id, |job| job.progress = progress);
+                handle.block_on(scenario.run())
+            })
+            .await
+            .unwrap_or_else(|e| Err(TradingError::ExecutionError(format!("backtest task panicked: {}", e))));
+
+            manager.update(&id, |job| {
+                job.finished_at = Some(Self::now());
+                match outcome {
+                    Ok(result) => {
+                        job.state = BacktestJobState::Completed;
+                        job.result = Some(Arc::new(result));
+                    }
+                    Err(e) => {
+                        log::warn!("backtest job {} failed: {}", job.name, e);
+                        job.state = BacktestJobState::Failed;
+                        job.error = Some(e.to_string());
+                    }
+                }
+            });
+            manager.prune();
+        });
+
+        Ok(status)
+    }
+
+    fn update(&self, id: &str, f: impl FnOnce(&mut Job)) {
+        if let Some(job) = self.table().jobs.get_mut(id) {
+            f(job);
+        }
+    }
+
+    /// 보관 한도를 넘는 오래된 완료 작업 삭제 (대기/실행 중 작업은 유지)
+    fn prune(&self) {
+        let mut table = self.table();
+        let JobTable { jobs, order } = &mut *table;
+        let mut excess = order.len().saturating_sub(self.max_retained);
+        order.retain(|id| {
+            if excess > 0 && jobs.get(id).is_none_or(|job| job.state.is_finished()) {
+                jobs.remove(id);
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    /// 파라미터 스윕을 동시 실행 한도 안에서 블로킹 스레드로 실행하고 격자 반환
+    pub async fn sweep(&self, request: BacktestSweepRequest) -> Result<SweepGrid, TradingError> {
+        request.validate()?;
+        let _permit = self.permits.clone().acquire_owned().await
+            .map_err(|e| TradingError::ExecutionError(e.to_string()))?;
+        let handle = tokio::runtime::Handle::current();
+        tokio::task::spawn_blocking(move || handle.block_on(request.run()))
+            .await
+            .unwrap_or_else(|e| Err(TradingError::ExecutionError(format!("sweep task panicked: {}", e))))
+    }
+
+    pub fn status(&self, id: &str) -> Option<BacktestJobStatus> {
+        self.table().jobs.get(id).map(|job| job.status(id))
+    }
+
+    /// 최근 제출 순
+    pub fn list(&self) -> Vec<BacktestJobStatus> {
+        let table = self.table();
+        table.order.iter().rev().filter_map(|id| table.jobs.get(id).map(|job| job.status(id))).collect()
+    }
+
+    /// 완료된 작업의 전체 결과 - 없는 작업은 `DataNotFound`, 미완료/실패는 `ExecutionError`
+    pub fn result(&self, id: &str) -> Result<Arc<BacktestResult>, TradingError> {
+        let table = self.table();
+        let job = table.jobs.get(id).ok_or_else(|| TradingError::DataNotFound(format!("backtest job {}", id)))?;
+        match (&job.result, job.state) {
+            (Some(result), _) => Ok(result.clone()),
+            (None, BacktestJobState::Failed) => Err(TradingError::ExecutionError(job.error.clone().unwrap_or_default())),
+            (None, state) => Err(TradingError::ExecutionError(format!("backtest job is {:?}", state).to_lowercase())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn request(start_time: i64, end_time: i64) -> BacktestJobRequest {
+        serde_json::from_value(serde_json::json!({
+            "strategies": [{"type": "rsi", "symbol": "BTCUSDT", "period": 14, "oversold": 30.0, "overbought": 70.0}],
+            "symbol_data_files": {"BTCUSDT": "data/samples/BTCUSDT-1m.csv"},
+            "start_time": start_time,
+            "end_time": end_time,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_job_lifecycle() {
+        let manager = BacktestJobManager::new(&BacktestJobsConfig { max_concurrent: 1, max_retained: 1 });
+
+        // 기간이 잘못된 요청은 제출 시 거부
+        assert!(manager.submit(request(1_704_153_540_000, 1_704_067_200_000)).is_err());
+
+        let queued = manager.submit(request(1_704_067_200_000, 1_704_153_540_000)).unwrap();
+        assert_eq!(queued.state, BacktestJobState::Queued);
+        assert!(manager.result(&queued.id).is_err());
+
+        let mut status = manager.status(&queued.id).unwrap();
+        for _ in 0..200 {
+            if status.state.is_finished() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            status = manager.status(&queued.id).unwrap();
+        }
+        assert_eq!(status.state, BacktestJobState::Completed, "{:?}", status.error);
+        assert_eq!(status.progress, 1.0);
+        assert_eq!(status.bars_processed, status.bars_total);
+        assert!(status.bars_total > 1000);
+        assert_eq!(manager.result(&queued.id).unwrap().symbols, vec!["BTCUSDT".to_string()]);
+
+        // 없는 파일은 실행 단계에서 실패로 기록되고, 보관 한도 1 이라 이전 작업은 정리된다
+        let mut missing = request(1_704_067_200_000, 1_704_153_540_000);
+        missing.symbol_data_files.insert("BTCUSDT".to_string(), PathBuf::from("data/missing.csv"));
+        let failed = manager.submit(missing).unwrap();
+        for _ in 0..200 {
+            if manager.status(&failed.id).is_some_and(|s| s.state.is_finished()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let failed = manager.status(&failed.id).unwrap();
+        assert_eq!(failed.state, BacktestJobState::Failed);
+        assert!(failed.error.is_some());
+        assert!(manager.status(&queued.id).is_none());
+        assert_eq!(manager.list().len(), 1);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::TradingError;
+use crate::exchange::fill_model::FillModel;
+use super::disruption::DisruptionWindow;
+use super::benchmark::BenchmarkSpec;
+
+/// 시나리오 설정 스냅샷 - 동일 조건 재실행을 위한 입력값 기록
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScenarioSnapshot {
+    pub name: String,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub data_file: Option<PathBuf>,
+    /// 심볼별 데이터 파일 (다중 심볼 시나리오)
+    #[serde(default)]
+    pub symbol_data_files: BTreeMap<String, PathBuf>,
+    #[serde(default = "default_quote_asset")]
+    pub quote_asset: String,
+    pub symbols: Vec<String>,
+    pub initial_balance: HashMap<String, f64>,
+    pub fee_rate: f64,
+    pub slippage: f64,
+    /// 체결 모델 (지정한 경우)
+    #[serde(default)]
+    pub fill_model: Option<FillModel>,
+    pub strategies: Vec<String>,
+    pub csv_delimiter: char,
+    #[serde(default)]
+    pub disruptions: Vec<DisruptionWindow>,
+    /// 외부 시계열 이름별 CSV 파일
+    #[serde(default)]
+    pub external_series: BTreeMap<String, PathBuf>,
+    /// 심볼별 체결 틱 CSV (틱 재생 심볼)
+    #[serde(default)]
+    pub tick_data_files: BTreeMap<String, PathBuf>,
+    /// 틱 재생 시 전략 입력 캔들 주기
+    #[serde(default)]
+    pub tick_bar_timeframe: Option<String>,
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkSpec>,
+}
+
+fn default_quote_asset() -> String { "USDT".to_string() }
+
+/// 데이터 파일 해시
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct DataFileHash {
+    pub path: PathBuf,
+    pub sha256: String,
+    pub bytes: u64,
+}
+
+/// 재현성 매니페스트 - 백테스트 결과와 함께 저장되어 몇 달 뒤에도 동일 결과를 재현할 수 있게 한다
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReproducibilityManifest {
+    pub crate_version: String,
+    pub git_commit: Option<String>,
+    pub scenario: ScenarioSnapshot,
+    pub data_files: Vec<DataFileHash>,
+    pub rng_seeds: BTreeMap<String, u64>,
+    pub feature_flags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ReproducibilityManifest {
+    /// 현재 빌드/환경 정보로 매니페스트 생성 (데이터 파일 해시 포함)
+    pub fn capture(scenario: ScenarioSnapshot, rng_seeds: BTreeMap<String, u64>) -> Result<Self, TradingError> {
+        let mut data_files = Vec::new();
+        if let Some(path) = scenario.data_file.as_ref().filter(|p| p.exists()) {
+            data_files.push(hash_file(path)?);
+        }
+        for path in scenario.symbol_data_files.values().chain(scenario.tick_data_files.values()).chain(scenario.external_series.values()).filter(|p| p.exists()) {
+            data_files.push(hash_file(path)?);
+        }
+        if let Some(path) = scenario.benchmark.as_ref().and_then(|b| b.path()).filter(|p| p.exists()) {
+            data_files.push(hash_file(path)?);
+        }
+
+        Ok(ReproducibilityManifest {
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: detect_git_commit(),
+            scenario,
+            data_files,
+            rng_seeds,
+            feature_flags: active_feature_flags(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// 현재 데이터 파일이 기록 당시와 동일한지 확인
+    pub fn verify_data_files(&self) -> Result<bool, TradingError> {
+        for recorded in &self.data_files {
+            if hash_file(&recorded.path)? != *recorded {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 보고서용 요약 문자열
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        summary.push_str(&format!("버전: {} (commit: {})\n", self.crate_version, self.git_commit.as_deref().unwrap_or("unknown")));
+        for file in &self.data_files {
+            summary.push_str(&format!("데이터: {} ({} bytes, sha256 {})\n", file.path.display(), file.bytes, file.sha256));
+        }
+        if !self.rng_seeds.is_empty() {
+            let seeds: Vec<String> = self.rng_seeds.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            summary.push_str(&format!("RNG 시드: {}\n", seeds.join(", ")));
+        }
+        summary.push_str(&format!("빌드 플래그: {}\n", self.feature_flags.join(", ")));
+        summary
+    }
+}
+
+/// 파일 SHA-256 해시 계산
+pub fn hash_file(path: &Path) -> Result<DataFileHash, TradingError> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+    let mut bytes = 0u64;
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        bytes += n as u64;
+    }
+
+    Ok(DataFileHash {
+        path: path.to_path_buf(),
+        sha256: hex::encode(hasher.finalize()),
+        bytes,
+    })
+}
+
+/// 빌드 시 GIT_COMMIT 환경변수, 없으면 실행 시 git 조회
+fn detect_git_commit() -> Option<String> {
+    if let Some(commit) = option_env!("GIT_COMMIT") {
+        return Some(commit.to_string());
+    }
+
+    std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// 결과에 영향을 줄 수 있는 빌드 플래그
+fn active_feature_flags() -> Vec<String> {
+    let mut flags = Vec::new();
+    if cfg!(debug_assertions) {
+        flags.push("debug_assertions".to_string());
+    } else {
+        flags.push("release".to_string());
+    }
+    flags.push(format!("target_os={}", std::env::consts::OS));
+    flags.push(format!("target_arch={}", std::env::consts::ARCH));
+    flags
+}
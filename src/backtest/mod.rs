@@ -1,11 +1,31 @@
+pub mod account;
 pub mod engine;
 pub mod result;
 pub mod scenario;
 pub mod performance;
 pub mod data_provider;
+pub mod manifest;
+pub mod disruption;
+pub mod optimizer;
+pub mod synthetic;
+pub mod report;
+pub mod tick;
+pub mod jobs;
+pub mod monte_carlo;
+pub mod benchmark;
 
-pub use engine::BacktestEngine;
+pub use account::{BacktestAccount, ClosedTrade, EquityPoint, SymbolResult};
+pub use engine::{BacktestEngine, BacktestProgress};
 pub use result::BacktestResult;
 pub use scenario::{BacktestScenario, BacktestScenarioBuilder};
 pub use performance::PerformanceMetrics;
-pub use data_provider::HistoricalDataProvider;
\ No newline at end of file
+pub use data_provider::HistoricalDataProvider;
+pub use manifest::ReproducibilityManifest;
+pub use disruption::{DisruptionKind, DisruptionSchedule, DisruptionWindow};
+pub use optimizer::{ParamAxis, ParameterSweep, SweepGrid, SweepMetric, SearchMethod, WalkForwardConfig, WalkForwardOptimizer, WalkForwardReport};
+pub use report::{ReportFormat, ReportMetrics};
+pub use tick::{CsvTickProvider, TickDataProvider, TradeTick};
+pub use synthetic::{MarketRegime, SyntheticDataConfig, SyntheticDataGenerator};
+pub use monte_carlo::{Distribution, MonteCarloConfig, MonteCarloReport, ResampleMethod};
+pub use benchmark::{BenchmarkReport, BenchmarkSpec};
+pub use jobs::{BacktestJobManager, BacktestJobRequest, BacktestJobState, BacktestJobStatus, BacktestSweepRequest};
@@ -0,0 +1,341 @@
+//! 몬테카를로 강건성 분석
+//!
+//! 청산 손익 순서를 섞거나(shuffle) 복원 추출(bootstrap)하여 N 개의 가상 자산 경로를 만들고, 최종 자산가치/
+//! 최대 낙폭/파산 확률 분포를 구한다. 선택적으로 청산마다 수수료율과 슬리피지를 흔들어 비용 가정에 대한
+//! 민감도도 반영한다. 실제 결과가 분포의 어디쯤인지 보고 백테스트 성과가 운인지 판단하기 위한 것.
+//!
+//! 청산 손익 외의 차이(수수료, 미청산 포지션 평가손익)는 청산 건수로 균등 배분하므로, 섞기만 하면 최종
+//! 자산가치는 실제와 같고 경로(낙폭)만 달라진다.
+
+use std::fmt;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use super::result::BacktestResult;
+
+/// 청산 순서 재표본 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResampleMethod {
+    /// 같은 청산들의 순서만 무작위로 섞음 (최종 손익 불변, 경로 위험 평가)
+    Shuffle,
+    /// 청산을 복원 추출 (최종 손익 분포까지 평가)
+    Bootstrap,
+}
+
+impl ResampleMethod {
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        match s.to_ascii_lowercase().as_str() {
+            "shuffle" => Ok(ResampleMethod::Shuffle),
+            "bootstrap" => Ok(ResampleMethod::Bootstrap),
+            other => Err(TradingError::InvalidParameter(format!("Unsupported resample method: {} (shuffle|bootstrap)", other))),
+        }
+    }
+}
+
+/// 몬테카를로 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+    pub iterations: usize,
+    pub method: ResampleMethod,
+    pub seed: u64,
+    /// 청산마다 왕복 명목 금액에 더하는 수수료율 변동폭 (±, 0 이면 고정)
+    pub fee_jitter: f64,
+    /// 청산마다 왕복 명목 금액에 더하는 추가 슬리피지 최대치 (항상 불리한 방향)
+    pub slippage_jitter: f64,
+    /// 초기 자산가치 대비 이 비율 이상 잃으면 파산으로 집계 (0~1)
+    pub ruin_threshold: f64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        MonteCarloConfig {
+            iterations: 1_000,
+            method: ResampleMethod::Shuffle,
+            seed: 42,
+            fee_jitter: 0.0,
+            slippage_jitter: 0.0,
+            ruin_threshold: 0.5,
+        }
+    }
+}
+
+impl MonteCarloConfig {
+    pub fn validate(&self) -> Result<(), TradingError> {
+        if self.iterations == 0 {
+            return Err(TradingError::InvalidParameter("iterations must be positive".to_string()));
+        }
+        if self.fee_jitter < 0.0 || self.slippage_jitter < 0.0 {
+            return Err(TradingError::InvalidParameter("fee_jitter and slippage_jitter must not be negative".to_string()));
+        }
+        if !(self.ruin_threshold > 0.0 && self.ruin_threshold <= 1.0) {
+            return Err(TradingError::InvalidParameter("ruin_threshold must be in (0, 1]".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// 표본 분포 요약
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    pub fn from_samples(samples: &[f64]) -> Self {
+        if samples.is_empty() {
+            return Distribution::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = sorted.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Distribution {
+            mean,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            p5: percentile(&sorted, 0.05),
+            p25: percentile(&sorted, 0.25),
+            median: percentile(&sorted, 0.5),
+            p75: percentile(&sorted, 0.75),
+            p95: percentile(&sorted, 0.95),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// 정렬된 표본의 선형 보간 분위수
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+}
+
+/// 몬테카를로 분석 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    pub config: MonteCarloConfig,
+    /// 재표본한 청산 수
+    pub trades: usize,
+    pub initial_value: f64,
+    pub actual_final_value: f64,
+    /// 실제 청산 순서의 최대 낙폭 (청산 단위, 비율)
+    pub actual_max_drawdown: f64,
+    pub final_value: Distribution,
+    pub max_drawdown: Distribution,
+    /// 파산 기준 이상 손실을 본 경로 비율
+    pub risk_of_ruin: f64,
+    /// 최종 자산가치가 초기값보다 낮은 경로 비율
+    pub probability_of_loss: f64,
+    /// 실제 최종 자산가치 이하인 경로 비율 (높을수록 실제 결과가 운 좋은 쪽)
+    pub actual_final_percentile: f64,
+    /// 실제 최대 낙폭 이상인 경로 비율 (낮으면 실제 순서가 유리했음)
+    pub actual_drawdown_percentile: f64,
+}
+
+/// 한 경로의 최종 자산가치, 최대 낙폭, 파산 여부
+fn simulate_path(initial: f64, pnls: impl Iterator<Item = f64>, ruin_level: f64) -> (f64, f64, bool) {
+    let (mut equity, mut peak, mut max_drawdown, mut ruined) = (initial, initial, 0.0_f64, false);
+    for pnl in pnls {
+        equity += pnl;
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_drawdown = max_drawdown.max((peak - equity) / peak);
+        }
+        ruined |= equity <= ruin_level;
+    }
+    (equity, max_drawdown, ruined)
+}
+
+/// 청산 내역으로 몬테카를로 분석 실행 (청산이 없으면 오류)
+pub fn run(result: &BacktestResult, config: &MonteCarloConfig) -> Result<MonteCarloReport, TradingError> {
+    config.validate()?;
+    let trades = &result.closed_trades;
+    if trades.is_empty() {
+        return Err(TradingError::InvalidParameter("Monte Carlo analysis needs at least one closed trade".to_string()));
+    }
+
+    let initial = result.initial_value;
+    let ruin_level = initial * (1.0 - config.ruin_threshold);
+    let drag = (result.final_value - initial - trades.iter().map(|t| t.pnl).sum::<f64>()) / trades.len() as f64;
+    let net: Vec<f64> = trades.iter().map(|t| t.pnl + drag).collect();
+    let notional: Vec<f64> = trades.iter().map(|t| t.quantity * (t.entry_price + t.exit_price)).collect();
+    let (actual_final, actual_drawdown, _) = simulate_path(initial, net.iter().copied(), ruin_level);
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut order: Vec<usize> = (0..net.len()).collect();
+    let mut finals = Vec::with_capacity(config.iterations);
+    let mut drawdowns = Vec::with_capacity(config.iterations);
+    let mut ruined = 0usize;
+    for _ in 0..config.iterations {
+        match config.method {
+            ResampleMethod::Shuffle => order.shuffle(&mut rng),
+            ResampleMethod::Bootstrap => order.iter_mut().for_each(|i| *i = rng.gen_range(0..net.len())),
+        }
+        let pnls: Vec<f64> = order.iter().map(|&i| {
+            let mut cost_rate = 0.0;
+            if config.fee_jitter > 0.0 {
+                cost_rate += rng.gen_range(-config.fee_jitter..=config.fee_jitter);
+            }
+            if config.slippage_jitter > 0.0 {
+                cost_rate += rng.gen_range(0.0..=config.slippage_jitter);
+            }
+            net[i] - notional[i] * cost_rate
+        }).collect();
+        let (final_value, max_drawdown, is_ruined) = simulate_path(initial, pnls.into_iter(), ruin_level);
+        finals.push(final_value);
+        drawdowns.push(max_drawdown);
+        ruined += is_ruined as usize;
+    }
+
+    let n = config.iterations as f64;
+    let share = |samples: &[f64], pred: &dyn Fn(f64) -> bool| samples.iter().filter(|v| pred(**v)).count() as f64 / n;
+    Ok(MonteCarloReport {
+        config: config.clone(),
+        trades: net.len(),
+        initial_value: initial,
+        actual_final_value: actual_final,
+        actual_max_drawdown: actual_drawdown,
+        final_value: Distribution::from_samples(&finals),
+        max_drawdown: Distribution::from_samples(&drawdowns),
+        risk_of_ruin: ruined as f64 / n,
+        probability_of_loss: share(&finals, &|v| v < initial),
+        actual_final_percentile: share(&finals, &|v| v <= actual_final + 1e-9),
+        actual_drawdown_percentile: share(&drawdowns, &|v| v >= actual_drawdown - 1e-12),
+    })
+}
+
+impl MonteCarloReport {
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        summary.push_str(&format!(
+            "방식: {:?}, 반복: {}, 청산 {}건, 시드: {}\n",
+            self.config.method, self.config.iterations, self.trades, self.config.seed
+        ));
+        if self.config.fee_jitter > 0.0 || self.config.slippage_jitter > 0.0 {
+            summary.push_str(&format!(
+                "비용 변동: 수수료율 ±{:.4}%, 추가 슬리피지 최대 {:.4}%\n",
+                self.config.fee_jitter * 100.0, self.config.slippage_jitter * 100.0
+            ));
+        }
+        let d = &self.final_value;
+        summary.push_str(&format!(
+            "최종 자산가치: 실제 ${:.2} (분위 {:.1}%), 중앙 ${:.2}, 5% ${:.2}, 95% ${:.2}\n",
+            self.actual_final_value, self.actual_final_percentile * 100.0, d.median, d.p5, d.p95
+        ));
+        let d = &self.max_drawdown;
+        summary.push_str(&format!(
+            "최대 낙폭: 실제 {:.2}% (이상 경로 {:.1}%), 중앙 {:.2}%, 95% {:.2}%, 최대 {:.2}%\n",
+            self.actual_max_drawdown * 100.0, self.actual_drawdown_percentile * 100.0, d.median * 100.0, d.p95 * 100.0, d.max * 100.0
+        ));
+        summary.push_str(&format!(
+            "손실 확률: {:.2}%, 파산 확률(-{:.0}%): {:.2}%\n",
+            self.probability_of_loss * 100.0, self.config.ruin_threshold * 100.0, self.risk_of_ruin * 100.0
+        ));
+        summary
+    }
+}
+
+impl fmt::Display for MonteCarloReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.summary())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+    use chrono::{TimeZone, Utc};
+    use crate::backtest::account::ClosedTrade;
+    use crate::models::order::OrderSide;
+
+    fn closed(pnl: f64) -> ClosedTrade {
+        ClosedTrade {
+            symbol: "BTCUSDT".into(),
+            side: OrderSide::Buy,
+            quantity: 1.0,
+            entry_price: 100.0,
+            exit_price: 100.0 + pnl,
+            pnl,
+            closed_at: Default::default(),
+            exit_trade_id: String::new(),
+            opened_at: Default::default(),
+            mae: 0.0,
+            mfe: 0.0,
+        }
+    }
+
+    fn backtest_result(pnls: &[f64], fees: f64) -> BacktestResult {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let final_value = 1_000.0 + pnls.iter().sum::<f64>() - fees;
+        BacktestResult {
+            name: "mc".into(),
+            description: String::new(),
+            start_time: start,
+            end_time: start,
+            initial_balance: HashMap::new(),
+            final_balance: HashMap::new(),
+            initial_value: 1_000.0,
+            final_value,
+            profit: final_value - 1_000.0,
+            profit_percentage: 0.0,
+            trades: Vec::new(),
+            fee_paid: fees,
+            fee_attribution: Default::default(),
+            symbols: Vec::new(),
+            manifest: None,
+            disruptions: Default::default(),
+            symbol_results: BTreeMap::new(),
+            closed_trades: pnls.iter().map(|p| closed(*p)).collect(),
+            equity_curve: Vec::new(),
+            portfolio_max_drawdown: 0.0,
+            market_exposure: 0.0,
+            monte_carlo: None,
+            benchmark: None,
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        // 섞기만 하면 최종값은 같고 경로별 낙폭만 달라짐
+        let result = backtest_result(&[100.0, 100.0, 100.0, -150.0, -150.0, -150.0], 10.0);
+        let config = MonteCarloConfig { iterations: 500, ..Default::default() };
+        let report = run(&result, &config).unwrap();
+        assert_eq!(report.trades, 6);
+        assert!((report.actual_final_value - result.final_value).abs() < 1e-9);
+        assert!((report.final_value.min - result.final_value).abs() < 1e-6);
+        assert!((report.final_value.max - result.final_value).abs() < 1e-6);
+        assert!(report.max_drawdown.max > report.max_drawdown.min);
+        assert!(report.actual_final_percentile == 1.0);
+        assert_eq!(report.probability_of_loss, 1.0);
+        assert_eq!(report.risk_of_ruin, 0.0);
+
+        // 같은 시드면 같은 분포
+        let again = run(&result, &config).unwrap();
+        assert_eq!(report.max_drawdown, again.max_drawdown);
+
+        // 복원 추출 + 비용 변동은 최종값 분포를 넓힘, 파산 기준을 낮추면 파산 경로 발생
+        let bootstrap = MonteCarloConfig { method: ResampleMethod::Bootstrap, slippage_jitter: 0.01, ruin_threshold: 0.3, ..config.clone() };
+        let report = run(&result, &bootstrap).unwrap();
+        assert!(report.final_value.max > report.final_value.min);
+        assert!(report.final_value.p95 >= report.final_value.median && report.final_value.median >= report.final_value.p5);
+        assert!(report.risk_of_ruin > 0.0);
+
+        assert!(run(&backtest_result(&[], 0.0), &config).is_err());
+        assert!(run(&result, &MonteCarloConfig { iterations: 0, ..Default::default() }).is_err());
+    }
+}
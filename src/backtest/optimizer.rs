@@ -0,0 +1,691 @@
+//! 파라미터 최적화 - 2차원 스윕(히트맵)과 워크포워드 최적화
+//!
+//! 스윕은 두 파라미터의 모든 조합으로 백테스트를 돌려 샤프 비율/수익/최대 낙폭 격자를 만들고, 각 칸을
+//! 이웃 칸과 비교하여 안정적인 고원(plateau)인지 주변과 동떨어진 돌출값(spike)인지 판정한다.
+//! 최고값 하나보다 주변까지 고르게 좋은 영역을 고르도록 돕기 위한 것.
+//!
+//! 워크포워드는 기간을 구간 표본(in-sample)/검증 표본(out-of-sample) 창으로 나눠, 각 창의 구간 표본에서
+//! 고른 최적 파라미터를 바로 다음 검증 구간에 적용한다. 검증 구간 성과만 모아 과최적화 여부를 본다.
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+
+use chrono::{DateTime, Duration, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use super::result::BacktestResult;
+use super::scenario::BacktestScenario;
+
+/// 스윕 축 - 파라미터 이름과 값 목록
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParamAxis {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+impl ParamAxis {
+    pub fn new(name: impl Into<String>, values: Vec<f64>) -> Self {
+        ParamAxis { name: name.into(), values }
+    }
+
+    /// start 부터 end 까지(포함) step 간격
+    pub fn range(name: impl Into<String>, start: f64, end: f64, step: f64) -> Result<Self, TradingError> {
+        if step <= 0.0 || end < start {
+            return Err(TradingError::InvalidParameter(format!("Invalid sweep range: {}..={} step {}", start, end, step)));
+        }
+        let count = ((end - start) / step + 1e-9).floor() as usize + 1;
+        Ok(Self::new(name, (0..count).map(|i| start + step * i as f64).collect()))
+    }
+
+    /// `name=start:end:step` 범위 또는 `name=v1,v2,...` 목록 파싱 (CLI 축 지정)
+    pub fn parse(spec: &str) -> Result<Self, TradingError> {
+        let invalid = || TradingError::InvalidParameter(format!("Invalid sweep axis '{}' (name=start:end:step or name=v1,v2,...)", spec));
+        let (name, values) = spec.split_once('=').ok_or_else(invalid)?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        let numbers = |sep: char| -> Result<Vec<f64>, TradingError> {
+            values.split(sep).map(|v| v.trim().parse::<f64>().map_err(|_| invalid())).collect()
+        };
+        if values.contains(':') {
+            match numbers(':')?.as_slice() {
+                [start, end, step] => Self::range(name, *start, *end, *step),
+                _ => Err(invalid()),
+            }
+        } else {
+            Ok(Self::new(name, numbers(',')?))
+        }
+    }
+}
+
+/// 히트맵 지표
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepMetric {
+    Sharpe,
+    Profit,
+    MaxDrawdown,
+}
+
+impl SweepMetric {
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        match s.to_ascii_lowercase().as_str() {
+            "sharpe" => Ok(SweepMetric::Sharpe),
+            "profit" => Ok(SweepMetric::Profit),
+            "max_drawdown" | "drawdown" => Ok(SweepMetric::MaxDrawdown),
+            other => Err(TradingError::InvalidParameter(format!("Unknown sweep metric: {}", other))),
+        }
+    }
+
+    /// 클수록 좋은 점수로 변환 (낙폭은 부호 반전)
+    fn score(&self, value: f64) -> f64 {
+        match self {
+            SweepMetric::MaxDrawdown => -value,
+            _ => value,
+        }
+    }
+}
+
+/// 한 조합의 백테스트 결과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepCell {
+    pub x: f64,
+    pub y: f64,
+    pub sharpe: f64,
+    pub profit: f64,
+    pub profit_percentage: f64,
+    pub max_drawdown: f64,
+    pub trades: usize,
+}
+
+impl SweepCell {
+    pub fn from_result(x: f64, y: f64, result: &BacktestResult) -> Self {
+        SweepCell {
+            x,
+            y,
+            sharpe: result.sharpe_ratio(),
+            profit: result.profit,
+            profit_percentage: result.profit_percentage,
+            max_drawdown: result.max_drawdown(),
+            trades: result.trade_count(),
+        }
+    }
+
+    pub fn metric(&self, metric: SweepMetric) -> f64 {
+        match metric {
+            SweepMetric::Sharpe => self.sharpe,
+            SweepMetric::Profit => self.profit,
+            SweepMetric::MaxDrawdown => self.max_drawdown,
+        }
+    }
+}
+
+/// 칸별 안정성 판정
+#[derive(Debug, Clone, Serialize)]
+pub struct CellStability {
+    pub x: f64,
+    pub y: f64,
+    pub value: f64,
+    /// 자신 포함 3x3 이웃 평균 (클수록 좋은 점수 기준)
+    pub neighborhood_score: f64,
+    /// 이웃 중 최악 점수
+    pub neighborhood_min: f64,
+    /// 이웃 평균보다 격자 표준편차 이상 튀는 고립된 최고점
+    pub spike: bool,
+}
+
+/// 2차원 스윕 결과 격자 - cells[y_index][x_index], 실패한 조합은 None
+#[derive(Debug, Clone, Serialize)]
+pub struct SweepGrid {
+    pub x: ParamAxis,
+    pub y: ParamAxis,
+    pub cells: Vec<Vec<Option<SweepCell>>>,
+}
+
+impl SweepGrid {
+    /// 지표 행렬 (행: y, 열: x) - 히트맵 입력
+    pub fn matrix(&self, metric: SweepMetric) -> Vec<Vec<Option<f64>>> {
+        self.cells.iter()
+            .map(|row| row.iter().map(|c| c.as_ref().map(|c| c.metric(metric))).collect())
+            .collect()
+    }
+
+    /// 칸별 안정성 - 각 칸 점수를 3x3 이웃과 비교
+    pub fn stability(&self, metric: SweepMetric) -> Vec<CellStability> {
+        let scores: Vec<f64> = self.cells.iter().flatten().flatten().map(|c| metric.score(c.metric(metric))).collect();
+        if scores.is_empty() {
+            return Vec::new();
+        }
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        let std = (scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / scores.len() as f64).sqrt();
+
+        let mut out = Vec::new();
+        for (yi, row) in self.cells.iter().enumerate() {
+            for (xi, cell) in row.iter().enumerate() {
+                let Some(cell) = cell else { continue };
+                let score = metric.score(cell.metric(metric));
+                let mut neighbors = Vec::new();
+                for ny in yi.saturating_sub(1)..=(yi + 1).min(self.cells.len() - 1) {
+                    for nx in xi.saturating_sub(1)..=(xi + 1).min(row.len() - 1) {
+                        if (ny, nx) == (yi, xi) {
+                            continue;
+                        }
+                        if let Some(n) = &self.cells[ny][nx] {
+                            neighbors.push(metric.score(n.metric(metric)));
+                        }
+                    }
+                }
+                let neighbor_mean = if neighbors.is_empty() { score } else { neighbors.iter().sum::<f64>() / neighbors.len() as f64 };
+                out.push(CellStability {
+                    x: cell.x,
+                    y: cell.y,
+                    value: cell.metric(metric),
+                    neighborhood_score: (neighbor_mean * neighbors.len() as f64 + score) / (neighbors.len() + 1) as f64,
+                    neighborhood_min: neighbors.iter().copied().fold(score, f64::min),
+                    spike: std > 0.0 && score - neighbor_mean > std,
+                });
+            }
+        }
+        out
+    }
+
+    /// 지표 단독 최고 조합
+    pub fn best_raw(&self, metric: SweepMetric) -> Option<&SweepCell> {
+        self.cells.iter().flatten().flatten()
+            .max_by(|a, b| metric.score(a.metric(metric)).total_cmp(&metric.score(b.metric(metric))))
+    }
+
+    /// 이웃 평균이 가장 좋은 조합 (돌출값 제외)
+    pub fn best_robust(&self, metric: SweepMetric) -> Option<CellStability> {
+        self.stability(metric).into_iter()
+            .filter(|s| !s.spike)
+            .max_by(|a, b| a.neighborhood_score.total_cmp(&b.neighborhood_score))
+    }
+
+    /// 히트맵용 JSON (축, 지표별 행렬, 안정성 판정)
+    pub fn to_heatmap_json(&self, metric: SweepMetric) -> serde_json::Value {
+        serde_json::json!({
+            "x": self.x,
+            "y": self.y,
+            "metrics": {
+                "sharpe": self.matrix(SweepMetric::Sharpe),
+                "profit": self.matrix(SweepMetric::Profit),
+                "max_drawdown": self.matrix(SweepMetric::MaxDrawdown),
+            },
+            "stability_metric": metric,
+            "stability": self.stability(metric),
+            "best_raw": self.best_raw(metric),
+            "best_robust": self.best_robust(metric),
+        })
+    }
+
+    /// 칸당 한 행의 CSV (x, y, 지표, 안정성)
+    pub fn export_csv<W: Write>(&self, metric: SweepMetric, writer: W) -> Result<(), TradingError> {
+        let stability = self.stability(metric);
+        let mut wtr = csv::Writer::from_writer(writer);
+        wtr.write_record([self.x.name.as_str(), self.y.name.as_str(), "sharpe", "profit", "profit_percentage", "max_drawdown", "trades", "neighborhood_score", "spike"])
+            .map_err(|e| TradingError::ParseError(e.to_string()))?;
+        for cell in self.cells.iter().flatten().flatten() {
+            let s = stability.iter().find(|s| s.x == cell.x && s.y == cell.y);
+            wtr.write_record([
+                cell.x.to_string(),
+                cell.y.to_string(),
+                cell.sharpe.to_string(),
+                cell.profit.to_string(),
+                cell.profit_percentage.to_string(),
+                cell.max_drawdown.to_string(),
+                cell.trades.to_string(),
+                s.map(|s| s.neighborhood_score.to_string()).unwrap_or_default(),
+                s.map(|s| s.spike.to_string()).unwrap_or_default(),
+            ]).map_err(|e| TradingError::ParseError(e.to_string()))?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// 2차원 파라미터 스윕
+pub struct ParameterSweep {
+    x: ParamAxis,
+    y: ParamAxis,
+}
+
+impl ParameterSweep {
+    pub fn new(x: ParamAxis, y: ParamAxis) -> Self {
+        ParameterSweep { x, y }
+    }
+
+    /// 모든 (x, y) 조합에 대해 시나리오를 만들어 실행 - 생성/실행에 실패한 조합은 빈 칸으로 남김
+    pub async fn run<F>(&self, mut build: F) -> Result<SweepGrid, TradingError>
+    where
+        F: FnMut(f64, f64) -> Result<BacktestScenario, TradingError>,
+    {
+        let mut cells = Vec::with_capacity(self.y.values.len());
+        for &y in &self.y.values {
+            let mut row = Vec::with_capacity(self.x.values.len());
+            for &x in &self.x.values {
+                let result = match build(x, y) {
+                    Ok(mut scenario) => scenario.run().await,
+                    Err(e) => Err(e),
+                };
+                row.push(match result {
+                    Ok(result) => Some(SweepCell::from_result(x, y, &result)),
+                    Err(e) => {
+                        log::warn!("sweep {}={} {}={} skipped: {}", self.x.name, x, self.y.name, y, e);
+                        None
+                    }
+                });
+            }
+            cells.push(row);
+        }
+        Ok(SweepGrid { x: self.x.clone(), y: self.y.clone(), cells })
+    }
+}
+
+/// 파라미터 이름 -> 값
+pub type ParamSet = BTreeMap<String, f64>;
+
+/// 후보 파라미터 탐색 방식
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SearchMethod {
+    /// 모든 축 값의 조합
+    Grid,
+    /// 격자 점 중 무작위 표본 (중복 없이 최대 samples 개)
+    Random { samples: usize, seed: u64 },
+}
+
+impl SearchMethod {
+    /// 탐색할 파라미터 조합 목록
+    pub fn candidates(&self, axes: &[ParamAxis]) -> Vec<ParamSet> {
+        let total: usize = axes.iter().map(|a| a.values.len()).product();
+        if axes.is_empty() || total == 0 {
+            return Vec::new();
+        }
+        // 격자 번호 -> 축별 값 (첫 축이 가장 빠르게 변함)
+        let point = |mut index: usize| -> ParamSet {
+            axes.iter().map(|axis| {
+                let value = axis.values[index % axis.values.len()];
+                index /= axis.values.len();
+                (axis.name.clone(), value)
+            }).collect()
+        };
+        match *self {
+            SearchMethod::Grid => (0..total).map(point).collect(),
+            SearchMethod::Random { samples, seed } => {
+                let mut rng = StdRng::seed_from_u64(seed);
+                let mut seen = HashSet::new();
+                let mut out = Vec::new();
+                while out.len() < samples.min(total) {
+                    let index = rng.gen_range(0..total);
+                    if seen.insert(index) {
+                        out.push(point(index));
+                    }
+                }
+                out
+            }
+        }
+    }
+}
+
+/// 창 경계 (구간 표본 시작, 검증 시작 = 구간 표본 끝, 검증 끝)
+pub type WindowBounds = (DateTime<Utc>, DateTime<Utc>, DateTime<Utc>);
+
+/// 워크포워드 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// 구간 표본(최적화) 길이 (일)
+    pub in_sample_days: i64,
+    /// 검증 표본 길이 (일)
+    pub out_of_sample_days: i64,
+    /// 창 이동 간격 (일, 없으면 검증 길이만큼 - 검증 구간이 겹치지 않음)
+    pub step_days: Option<i64>,
+    /// true 면 구간 표본 시작을 고정하고 끝만 늘림 (확장 창)
+    pub anchored: bool,
+    /// 최적 파라미터 선택 지표
+    pub metric: SweepMetric,
+    pub search: SearchMethod,
+}
+
+impl Default for WalkForwardConfig {
+    fn default() -> Self {
+        WalkForwardConfig {
+            in_sample_days: 14,
+            out_of_sample_days: 7,
+            step_days: None,
+            anchored: false,
+            metric: SweepMetric::Sharpe,
+            search: SearchMethod::Grid,
+        }
+    }
+}
+
+impl WalkForwardConfig {
+    /// 기간을 (구간 표본 시작, 검증 시작, 검증 끝) 창으로 분할 - 검증 구간이 기간을 넘는 창은 제외
+    pub fn windows(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<WindowBounds>, TradingError> {
+        let step = self.step_days.unwrap_or(self.out_of_sample_days);
+        if self.in_sample_days <= 0 || self.out_of_sample_days <= 0 || step <= 0 {
+            return Err(TradingError::InvalidParameter(format!(
+                "Invalid walk-forward windows: in-sample {}d, out-of-sample {}d, step {}d", self.in_sample_days, self.out_of_sample_days, step
+            )));
+        }
+        let mut windows = Vec::new();
+        let mut is_start = start;
+        loop {
+            let oos_start = if self.anchored {
+                start + Duration::days(self.in_sample_days + step * windows.len() as i64)
+            } else {
+                is_start + Duration::days(self.in_sample_days)
+            };
+            let oos_end = oos_start + Duration::days(self.out_of_sample_days);
+            if oos_end > end {
+                break;
+            }
+            windows.push((if self.anchored { start } else { is_start }, oos_start, oos_end));
+            is_start += Duration::days(step);
+        }
+        if windows.is_empty() {
+            return Err(TradingError::InvalidParameter(format!(
+                "Period {} ~ {} is shorter than one walk-forward window", start, end
+            )));
+        }
+        Ok(windows)
+    }
+}
+
+/// 한 번의 백테스트 성과
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunMetrics {
+    pub sharpe: f64,
+    pub profit: f64,
+    pub profit_percentage: f64,
+    pub max_drawdown: f64,
+    pub trades: usize,
+}
+
+impl RunMetrics {
+    pub fn from_result(result: &BacktestResult) -> Self {
+        RunMetrics {
+            sharpe: result.sharpe_ratio(),
+            profit: result.profit,
+            profit_percentage: result.profit_percentage,
+            max_drawdown: result.max_drawdown(),
+            trades: result.trade_count(),
+        }
+    }
+
+    pub fn metric(&self, metric: SweepMetric) -> f64 {
+        match metric {
+            SweepMetric::Sharpe => self.sharpe,
+            SweepMetric::Profit => self.profit,
+            SweepMetric::MaxDrawdown => self.max_drawdown,
+        }
+    }
+}
+
+/// 워크포워드 창 하나의 결과
+#[derive(Debug, Clone, Serialize)]
+pub struct WalkForwardWindow {
+    pub index: usize,
+    pub in_sample_start: DateTime<Utc>,
+    pub in_sample_end: DateTime<Utc>,
+    pub out_of_sample_start: DateTime<Utc>,
+    pub out_of_sample_end: DateTime<Utc>,
+    /// 구간 표본에서 평가한 후보 수 (실패 제외)
+    pub candidates: usize,
+    pub best_params: ParamSet,
+    pub in_sample: RunMetrics,
+    /// 검증 구간 실행 실패 시 None
+    pub out_of_sample: Option<RunMetrics>,
+}
+
+/// 검증 구간 성과 집계
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OutOfSampleSummary {
+    pub windows: usize,
+    pub profitable_windows: usize,
+    pub total_profit: f64,
+    /// 창별 수익률을 이어 붙인 누적 수익률 (%)
+    pub compounded_return_pct: f64,
+    pub mean_sharpe: f64,
+    pub worst_drawdown: f64,
+    pub trades: usize,
+    /// 검증 평균 수익률 / 구간 표본 평균 수익률 (워크포워드 효율, 구간 표본 수익이 0 이하이면 None)
+    pub efficiency: Option<f64>,
+}
+
+impl OutOfSampleSummary {
+    pub fn from_windows(windows: &[WalkForwardWindow]) -> Self {
+        let oos: Vec<&RunMetrics> = windows.iter().filter_map(|w| w.out_of_sample.as_ref()).collect();
+        if oos.is_empty() {
+            return Self::default();
+        }
+        let n = oos.len() as f64;
+        let growth = oos.iter().fold(1.0, |acc, m| acc * (1.0 + m.profit_percentage / 100.0));
+        let is_mean = windows.iter().filter(|w| w.out_of_sample.is_some()).map(|w| w.in_sample.profit_percentage).sum::<f64>() / n;
+        let oos_mean = oos.iter().map(|m| m.profit_percentage).sum::<f64>() / n;
+        OutOfSampleSummary {
+            windows: oos.len(),
+            profitable_windows: oos.iter().filter(|m| m.profit > 0.0).count(),
+            total_profit: oos.iter().map(|m| m.profit).sum(),
+            compounded_return_pct: (growth - 1.0) * 100.0,
+            mean_sharpe: oos.iter().map(|m| m.sharpe).sum::<f64>() / n,
+            worst_drawdown: oos.iter().map(|m| m.max_drawdown).fold(0.0, f64::max),
+            trades: oos.iter().map(|m| m.trades).sum(),
+            efficiency: (is_mean > 0.0).then(|| oos_mean / is_mean),
+        }
+    }
+}
+
+/// 워크포워드 최적화 보고서
+#[derive(Debug, Clone, Serialize)]
+pub struct WalkForwardReport {
+    pub axes: Vec<ParamAxis>,
+    pub config: WalkForwardConfig,
+    pub windows: Vec<WalkForwardWindow>,
+    pub out_of_sample: OutOfSampleSummary,
+}
+
+impl WalkForwardReport {
+    /// 창당 한 행의 CSV (기간, 최적 파라미터, 구간 표본/검증 지표)
+    pub fn export_csv<W: Write>(&self, writer: W) -> Result<(), TradingError> {
+        let mut wtr = csv::Writer::from_writer(writer);
+        let mut header = vec!["window".to_string(), "in_sample_start".into(), "out_of_sample_start".into(), "out_of_sample_end".into()];
+        header.extend(self.axes.iter().map(|a| a.name.clone()));
+        header.extend(["is_metric", "is_profit", "oos_metric", "oos_profit", "oos_profit_percentage", "oos_max_drawdown", "oos_trades"].map(String::from));
+        wtr.write_record(&header).map_err(|e| TradingError::ParseError(e.to_string()))?;
+        let metric = self.config.metric;
+        for w in &self.windows {
+            let mut row = vec![w.index.to_string(), w.in_sample_start.to_rfc3339(), w.out_of_sample_start.to_rfc3339(), w.out_of_sample_end.to_rfc3339()];
+            row.extend(self.axes.iter().map(|a| w.best_params.get(&a.name).map(|v| v.to_string()).unwrap_or_default()));
+            row.push(w.in_sample.metric(metric).to_string());
+            row.push(w.in_sample.profit.to_string());
+            match &w.out_of_sample {
+                Some(m) => row.extend([m.metric(metric).to_string(), m.profit.to_string(), m.profit_percentage.to_string(), m.max_drawdown.to_string(), m.trades.to_string()]),
+                None => row.extend(std::iter::repeat_n(String::new(), 5)),
+            }
+            wtr.write_record(&row).map_err(|e| TradingError::ParseError(e.to_string()))?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+/// 워크포워드 최적화기
+pub struct WalkForwardOptimizer {
+    axes: Vec<ParamAxis>,
+    config: WalkForwardConfig,
+}
+
+impl WalkForwardOptimizer {
+    pub fn new(axes: Vec<ParamAxis>, config: WalkForwardConfig) -> Self {
+        WalkForwardOptimizer { axes, config }
+    }
+
+    /// 창마다 구간 표본에서 모든 후보를 실행해 최적 파라미터를 고르고 검증 구간에 적용.
+    /// build(파라미터, 시작, 끝) 로 시나리오를 만들며, 생성/실행에 실패한 후보는 건너뜀
+    pub async fn run<F>(&self, start: DateTime<Utc>, end: DateTime<Utc>, mut build: F) -> Result<WalkForwardReport, TradingError>
+    where
+        F: FnMut(&ParamSet, DateTime<Utc>, DateTime<Utc>) -> Result<BacktestScenario, TradingError>,
+    {
+        let candidates = self.config.search.candidates(&self.axes);
+        if candidates.is_empty() {
+            return Err(TradingError::InvalidParameter("No parameter candidates to optimize".into()));
+        }
+        let metric = self.config.metric;
+        let mut windows = Vec::new();
+        for (index, (is_start, oos_start, oos_end)) in self.config.windows(start, end)?.into_iter().enumerate() {
+            let mut best: Option<(&ParamSet, RunMetrics)> = None;
+            let mut evaluated = 0;
+            for params in &candidates {
+                match Self::run_one(&mut build, params, is_start, oos_start).await {
+                    Ok(metrics) => {
+                        evaluated += 1;
+                        if best.as_ref().is_none_or(|(_, b)| metric.score(metrics.metric(metric)) > metric.score(b.metric(metric))) {
+                            best = Some((params, metrics));
+                        }
+                    }
+                    Err(e) => log::warn!("walk-forward window {} candidate {:?} skipped: {}", index, params, e),
+                }
+            }
+            let Some((params, in_sample)) = best else {
+                log::warn!("walk-forward window {}: no candidate completed, skipping", index);
+                continue;
+            };
+            let out_of_sample = match Self::run_one(&mut build, params, oos_start, oos_end).await {
+                Ok(metrics) => Some(metrics),
+                Err(e) => {
+                    log::warn!("walk-forward window {} out-of-sample run failed: {}", index, e);
+                    None
+                }
+            };
+            log::info!("walk-forward window {}: best {:?} ({:?} {:.4} in-sample)", index, params, metric, in_sample.metric(metric));
+            windows.push(WalkForwardWindow {
+                index,
+                in_sample_start: is_start,
+                in_sample_end: oos_start,
+                out_of_sample_start: oos_start,
+                out_of_sample_end: oos_end,
+                candidates: evaluated,
+                best_params: params.clone(),
+                in_sample,
+                out_of_sample,
+            });
+        }
+        Ok(WalkForwardReport {
+            axes: self.axes.clone(),
+            config: self.config.clone(),
+            out_of_sample: OutOfSampleSummary::from_windows(&windows),
+            windows,
+        })
+    }
+
+    async fn run_one<F>(build: &mut F, params: &ParamSet, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<RunMetrics, TradingError>
+    where
+        F: FnMut(&ParamSet, DateTime<Utc>, DateTime<Utc>) -> Result<BacktestScenario, TradingError>,
+    {
+        let mut scenario = build(params, start, end)?;
+        Ok(RunMetrics::from_result(&scenario.run().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(x: f64, y: f64, sharpe: f64) -> Option<SweepCell> {
+        Some(SweepCell { x, y, sharpe, profit: sharpe * 100.0, profit_percentage: sharpe, max_drawdown: 0.1, trades: 10 })
+    }
+
+    #[test]
+    fn test_sweep_grid_plateau_vs_spike() {
+        let x = ParamAxis::range("period", 10.0, 40.0, 10.0).unwrap();
+        assert_eq!(x.values, vec![10.0, 20.0, 30.0, 40.0]);
+        assert_eq!(ParamAxis::parse("period=10:40:10").unwrap().values, x.values);
+        let y = ParamAxis::parse("threshold=20, 30,40").unwrap();
+        assert_eq!((y.name.as_str(), y.values.as_slice()), ("threshold", [20.0, 30.0, 40.0].as_slice()));
+        assert!(ParamAxis::parse("period=10:40").is_err());
+        assert!(ParamAxis::parse("=1,2").is_err());
+
+        // (40, 20) 은 주변이 나쁜 고립 최고점, 왼쪽 아래는 고르게 좋은 고원
+        let grid = SweepGrid {
+            cells: vec![
+                vec![cell(10.0, 20.0, 1.5), cell(20.0, 20.0, 1.6), cell(30.0, 20.0, 0.0), cell(40.0, 20.0, 3.0)],
+                vec![cell(10.0, 30.0, 1.4), cell(20.0, 30.0, 1.5), cell(30.0, 30.0, -0.5), None],
+                vec![cell(10.0, 40.0, 1.2), cell(20.0, 40.0, 1.3), cell(30.0, 40.0, -1.0), cell(40.0, 40.0, -1.0)],
+            ],
+            x,
+            y,
+        };
+
+        assert_eq!(grid.matrix(SweepMetric::Sharpe)[1], vec![Some(1.4), Some(1.5), Some(-0.5), None]);
+        assert_eq!(grid.best_raw(SweepMetric::Sharpe).unwrap().x, 40.0);
+
+        let stability = grid.stability(SweepMetric::Sharpe);
+        assert!(stability.iter().find(|s| s.x == 40.0 && s.y == 20.0).unwrap().spike);
+        let robust = grid.best_robust(SweepMetric::Sharpe).unwrap();
+        assert_eq!((robust.x, robust.y), (10.0, 20.0));
+        assert!(robust.neighborhood_min >= 1.4);
+
+        let json = grid.to_heatmap_json(SweepMetric::Sharpe);
+        assert_eq!(json["metrics"]["sharpe"][0][3], 3.0);
+        assert!(json["metrics"]["sharpe"][1][3].is_null());
+
+        let mut csv = Vec::new();
+        grid.export_csv(SweepMetric::Sharpe, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("period,threshold,sharpe,profit"));
+        assert_eq!(csv.lines().count(), 12);
+    }
+
+    #[test]
+    fn test_walk_forward_windows_and_summary() {
+        let axes = vec![ParamAxis::range("rsi_period", 10.0, 20.0, 5.0).unwrap(), ParamAxis::new("oversold", vec![25.0, 30.0])];
+        let grid = SearchMethod::Grid.candidates(&axes);
+        assert_eq!(grid.len(), 6);
+        assert_eq!(grid[1], ParamSet::from([("rsi_period".to_string(), 15.0), ("oversold".to_string(), 25.0)]));
+        let random = SearchMethod::Random { samples: 4, seed: 7 }.candidates(&axes);
+        assert_eq!(random.len(), 4);
+        assert_eq!(random, SearchMethod::Random { samples: 4, seed: 7 }.candidates(&axes));
+        assert_eq!(SearchMethod::Random { samples: 50, seed: 7 }.candidates(&axes).len(), 6);
+
+        // 30일 기간, 구간 표본 10일 + 검증 5일, 5일씩 이동 -> 4개 창
+        let start = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let end = start + Duration::days(30);
+        let config = WalkForwardConfig { in_sample_days: 10, out_of_sample_days: 5, ..WalkForwardConfig::default() };
+        let windows = config.windows(start, end).unwrap();
+        assert_eq!(windows.len(), 4);
+        assert_eq!(windows[3], (start + Duration::days(15), start + Duration::days(25), end));
+        let anchored = WalkForwardConfig { anchored: true, ..config.clone() }.windows(start, end).unwrap();
+        assert!(anchored.iter().all(|w| w.0 == start));
+        assert_eq!(anchored[1].1, start + Duration::days(15));
+        assert!(WalkForwardConfig { in_sample_days: 40, ..config }.windows(start, end).is_err());
+
+        let metrics = |profit: f64, drawdown: f64| RunMetrics { sharpe: profit / 100.0, profit, profit_percentage: profit / 100.0, max_drawdown: drawdown, trades: 3 };
+        let window = |index: usize, is_profit: f64, oos: Option<RunMetrics>| WalkForwardWindow {
+            index,
+            in_sample_start: start,
+            in_sample_end: start,
+            out_of_sample_start: start,
+            out_of_sample_end: end,
+            candidates: 6,
+            best_params: grid[0].clone(),
+            in_sample: metrics(is_profit, 0.01),
+            out_of_sample: oos,
+        };
+        let summary = OutOfSampleSummary::from_windows(&[
+            window(0, 200.0, Some(metrics(100.0, 0.02))),
+            window(1, 200.0, Some(metrics(-50.0, 0.05))),
+            window(2, 500.0, None),
+        ]);
+        assert_eq!((summary.windows, summary.profitable_windows, summary.trades), (2, 1, 6));
+        assert_eq!(summary.total_profit, 50.0);
+        assert!((summary.compounded_return_pct - ((1.01 * 0.995) - 1.0) * 100.0).abs() < 1e-9);
+        assert_eq!(summary.worst_drawdown, 0.05);
+        assert!((summary.efficiency.unwrap() - 0.125).abs() < 1e-9);
+    }
+}
@@ -5,10 +5,26 @@
 * description: 
 **/
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 
+use crate::models::decimal::to_f64;
 use crate::models::trade::Trade;
+use super::account::{ClosedTrade, EquityPoint};
+
+/// 청산 건별 최대 역행폭(MAE) / 최대 순행폭(MFE) 통계 (진입가 대비 비율)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExcursionStats {
+  pub avg_mae: f64,
+  pub avg_mfe: f64,
+  pub max_mae: f64,
+  pub max_mfe: f64,
+  /// 이익 청산의 평균 MAE (손절 폭 설정 기준)
+  pub winners_avg_mae: f64,
+  /// 손실 청산의 평균 MFE (놓친 이익)
+  pub losers_avg_mfe: f64,
+}
 
 /// 성능 지표 계산 유틸리티
 pub struct PerformanceMetrics;
@@ -58,7 +74,7 @@ impl PerformanceMetrics {
     let mut current_equity = initial_capital;
     
     for trade in trades {
-      current_equity += trade.price * trade.quantity; // placeholder PnL approximation
+      current_equity += to_f64(trade.value()); // placeholder PnL approximation
       equity_curve.push(current_equity);
     }
     
@@ -81,13 +97,14 @@ impl PerformanceMetrics {
   /// 수익 대 위험 비율 계산
   pub fn calculate_profit_factor(trades: &[Trade]) -> f64 {
     let gross_profit: f64 = trades.iter()
-      .filter(|t| t.price * t.quantity > 0.0)
-      .map(|t| t.price * t.quantity)
+      .map(|t| to_f64(t.value()))
+      .filter(|v| *v > 0.0)
       .sum();
     
     let gross_loss: f64 = trades.iter()
-      .filter(|t| t.price * t.quantity < 0.0)
-      .map(|t| (t.price * t.quantity).abs())
+      .map(|t| to_f64(t.value()))
+      .filter(|v| *v < 0.0)
+      .map(f64::abs)
       .sum();
     
     if gross_loss == 0.0 {
@@ -97,6 +114,93 @@ impl PerformanceMetrics {
     gross_profit / gross_loss
   }
   
+  /// 자산 곡선 기반 샤프 비율 (UTC 일자별 마지막 자산으로 일별 수익률 계산)
+  pub fn sharpe_from_equity(curve: &[EquityPoint], initial_capital: f64) -> f64 {
+    let daily_returns = Self::daily_returns_from_equity(curve, initial_capital);
+    if daily_returns.len() < 2 {
+      return 0.0;
+    }
+    
+    let mean_return = daily_returns.iter().sum::<f64>() / daily_returns.len() as f64;
+    let variance = daily_returns.iter()
+      .map(|r| (r - mean_return).powi(2))
+      .sum::<f64>() / daily_returns.len() as f64;
+    let std_dev = variance.sqrt();
+    if std_dev == 0.0 {
+      return 0.0;
+    }
+    
+    (mean_return / std_dev) * (252.0_f64).sqrt()
+  }
+  
+  /// 자산 곡선 기반 소르티노 비율 (목표 수익률 0 미만의 하방 편차만 위험으로 사용)
+  pub fn sortino_from_equity(curve: &[EquityPoint], initial_capital: f64) -> f64 {
+    let daily_returns = Self::daily_returns_from_equity(curve, initial_capital);
+    if daily_returns.len() < 2 {
+      return 0.0;
+    }
+    
+    let mean_return = daily_returns.iter().sum::<f64>() / daily_returns.len() as f64;
+    let downside = daily_returns.iter()
+      .map(|r| r.min(0.0).powi(2))
+      .sum::<f64>() / daily_returns.len() as f64;
+    let downside_dev = downside.sqrt();
+    if downside_dev == 0.0 {
+      return 0.0;
+    }
+    
+    (mean_return / downside_dev) * (252.0_f64).sqrt()
+  }
+  
+  /// 칼마 비율 (연간 복합 수익률 / 최대 낙폭)
+  pub fn calmar_ratio(annual_return: f64, max_drawdown: f64) -> f64 {
+    if max_drawdown <= 0.0 {
+      return 0.0;
+    }
+    annual_return / max_drawdown
+  }
+  
+  /// 청산 건별 평균 보유 시간 (ms)
+  pub fn average_holding_ms(closed: &[ClosedTrade]) -> f64 {
+    if closed.is_empty() {
+      return 0.0;
+    }
+    closed.iter()
+      .map(|t| (t.closed_at.as_millis() - t.opened_at.as_millis()) as f64)
+      .sum::<f64>() / closed.len() as f64
+  }
+  
+  /// 청산 건별 MAE/MFE 통계
+  pub fn excursion_stats(closed: &[ClosedTrade]) -> ExcursionStats {
+    let mean = |values: Vec<f64>| if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 };
+    ExcursionStats {
+      avg_mae: mean(closed.iter().map(|t| t.mae).collect()),
+      avg_mfe: mean(closed.iter().map(|t| t.mfe).collect()),
+      max_mae: closed.iter().map(|t| t.mae).fold(0.0, f64::max),
+      max_mfe: closed.iter().map(|t| t.mfe).fold(0.0, f64::max),
+      winners_avg_mae: mean(closed.iter().filter(|t| t.pnl > 0.0).map(|t| t.mae).collect()),
+      losers_avg_mfe: mean(closed.iter().filter(|t| t.pnl < 0.0).map(|t| t.mfe).collect()),
+    }
+  }
+  
+  /// UTC 일자별 마지막 자산 기준 일별 수익률
+  pub(crate) fn daily_returns_from_equity(curve: &[EquityPoint], initial_capital: f64) -> Vec<f64> {
+    let mut daily_close: BTreeMap<String, f64> = BTreeMap::new();
+    for point in curve {
+      daily_close.insert(point.timestamp.format("%Y-%m-%d").to_string(), point.equity);
+    }
+    
+    let mut previous = initial_capital;
+    let mut daily_returns = Vec::new();
+    for equity in daily_close.values() {
+      if previous > 0.0 {
+        daily_returns.push(equity / previous - 1.0);
+      }
+      previous = *equity;
+    }
+    daily_returns
+  }
+  
   /// 일별 수익률 계산
   fn calculate_daily_returns(trades: &[Trade], initial_capital: f64) -> Vec<f64> {
     if trades.is_empty() {
@@ -107,12 +211,9 @@ impl PerformanceMetrics {
     let mut daily_pnl: HashMap<String, f64> = HashMap::new();
     
     for trade in trades {
-      let date = chrono::NaiveDateTime::from_timestamp_millis(trade.timestamp)
-        .unwrap_or_else(|| chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap())
-        .date()
-        .format("%Y-%m-%d")
-        .to_string();
-      *daily_pnl.entry(date).or_default() += trade.price * trade.quantity;
+      // UTC 일자 기준
+      let date = trade.timestamp.to_datetime().format("%Y-%m-%d").to_string();
+      *daily_pnl.entry(date).or_default() += to_f64(trade.value());
     }
     
     // 일별 수익률 계산
@@ -131,4 +232,33 @@ impl PerformanceMetrics {
     
     daily_returns
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use chrono::{Duration, TimeZone};
+  use crate::models::order::OrderSide;
+  
+  #[test]
+  fn test_sortino_and_excursions() {
+    let day = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+    let curve: Vec<EquityPoint> = [1_020.0, 1_010.0, 1_040.0, 1_030.0].iter().enumerate()
+      .map(|(i, equity)| EquityPoint { timestamp: day + Duration::days(i as i64), equity: *equity })
+      .collect();
+    // 상승일만 있는 변동성은 소르티노에 반영되지 않음 -> 샤프보다 큼
+    let sortino = PerformanceMetrics::sortino_from_equity(&curve, 1_000.0);
+    assert!(sortino > PerformanceMetrics::sharpe_from_equity(&curve, 1_000.0));
+    assert_eq!(PerformanceMetrics::calmar_ratio(0.3, 0.15), 2.0);
+    assert_eq!(PerformanceMetrics::calmar_ratio(0.3, 0.0), 0.0);
+    
+    let closed = |pnl: f64, mae: f64, mfe: f64, hours: i64| ClosedTrade {
+      symbol: "BTCUSDT".into(), side: OrderSide::Buy, quantity: 1.0, entry_price: 100.0, exit_price: 100.0 + pnl, pnl,
+      closed_at: (hours * 3_600_000).into(), exit_trade_id: "bt-1".into(), opened_at: 0.into(), mae, mfe,
+    };
+    let trades = [closed(5.0, 0.01, 0.06, 2), closed(-3.0, 0.04, 0.02, 4)];
+    let stats = PerformanceMetrics::excursion_stats(&trades);
+    assert_eq!((stats.max_mae, stats.max_mfe, stats.winners_avg_mae, stats.losers_avg_mfe), (0.04, 0.06, 0.01, 0.02));
+    assert_eq!(PerformanceMetrics::average_holding_ms(&trades), 3.0 * 3_600_000.0);
+  }
+}
@@ -0,0 +1,296 @@
+//! 백테스트 보고서 내보내기
+//!
+//! 실행 결과를 외부 파일 없이 열리는 단일 파일로 저장한다. JSON 은 계산된 지표와 전체 결과를 함께
+//! 담고, HTML 은 요약/지표/심볼별/청산 내역 표와 인라인 SVG 자산 곡선 차트로 구성된다.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::TradingError;
+use super::account::EquityPoint;
+use super::performance::ExcursionStats;
+use super::result::BacktestResult;
+
+/// HTML 보고서에 표시할 최대 청산 건수 (최근 순)
+const MAX_HTML_TRADES: usize = 1_000;
+
+/// 보고서 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Html,
+}
+
+impl ReportFormat {
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(ReportFormat::Json),
+            "html" | "htm" => Ok(ReportFormat::Html),
+            other => Err(TradingError::InvalidParameter(format!("Unsupported report format: {}", other))),
+        }
+    }
+
+    /// 확장자로 형식 판별
+    pub fn from_path(path: &Path) -> Result<Self, TradingError> {
+        Self::parse(path.extension().and_then(|e| e.to_str()).unwrap_or(""))
+    }
+}
+
+/// 결과에서 계산한 성과 지표
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportMetrics {
+    pub trade_count: usize,
+    pub closed_trades: usize,
+    pub win_rate: f64,
+    pub average_profit_per_trade: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub calmar_ratio: f64,
+    pub max_drawdown: f64,
+    /// 손실 청산이 없으면 null
+    pub profit_factor: Option<f64>,
+    pub car: f64,
+    pub market_exposure: f64,
+    pub average_holding_hours: f64,
+    pub excursions: ExcursionStats,
+}
+
+impl ReportMetrics {
+    pub fn from_result(result: &BacktestResult) -> Self {
+        let profit_factor = result.profit_factor();
+        ReportMetrics {
+            trade_count: result.trade_count(),
+            closed_trades: result.closed_trades.len(),
+            win_rate: result.win_rate(),
+            average_profit_per_trade: result.average_profit_per_trade(),
+            sharpe_ratio: result.sharpe_ratio(),
+            sortino_ratio: result.sortino_ratio(),
+            calmar_ratio: result.calmar_ratio(),
+            max_drawdown: result.max_drawdown(),
+            profit_factor: profit_factor.is_finite().then_some(profit_factor),
+            car: result.car(),
+            market_exposure: result.market_exposure,
+            average_holding_hours: result.average_holding_hours(),
+            excursions: result.excursion_stats(),
+        }
+    }
+}
+
+/// JSON 보고서 본문
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    metrics: ReportMetrics,
+    result: &'a BacktestResult,
+}
+
+pub fn render_json(result: &BacktestResult) -> Result<String, TradingError> {
+    let report = JsonReport { metrics: ReportMetrics::from_result(result), result };
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// 자산 곡선 SVG (점이 2개 미만이면 빈 문자열, 벤치마크가 있으면 회색 선으로 함께 표시)
+fn equity_svg(result: &BacktestResult) -> String {
+    let curve = &result.equity_curve;
+    if curve.len() < 2 {
+        return String::new();
+    }
+    let benchmark = result.benchmark.as_ref().map_or(&[][..], |b| b.equity_curve.as_slice());
+    let (width, height, pad) = (900.0, 260.0, 30.0);
+    let (min, max) = curve.iter().chain(benchmark).fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.equity), hi.max(p.equity)));
+    let span = if max > min { max - min } else { 1.0 };
+    let start = curve[0].timestamp.timestamp_millis() as f64;
+    let duration = (curve[curve.len() - 1].timestamp.timestamp_millis() as f64 - start).max(1.0);
+    let points = |curve: &[EquityPoint]| -> String {
+        curve.iter().map(|p| {
+            let x = pad + (p.timestamp.timestamp_millis() as f64 - start) / duration * (width - 2.0 * pad);
+            let y = height - pad - (p.equity - min) / span * (height - 2.0 * pad);
+            format!("{:.1},{:.1}", x, y)
+        }).collect::<Vec<_>>().join(" ")
+    };
+    let benchmark_line = if benchmark.len() < 2 {
+        String::new()
+    } else {
+        format!("<polyline fill=\"none\" stroke=\"#8c959f\" stroke-width=\"1\" stroke-dasharray=\"4 3\" points=\"{}\"/>", points(benchmark))
+    };
+    let color = if curve[curve.len() - 1].equity >= curve[0].equity { "#1a7f37" } else { "#cf222e" };
+    format!(
+        "<svg viewBox=\"0 0 {w} {h}\" width=\"100%\" role=\"img\" aria-label=\"equity curve\">\
+<rect width=\"{w}\" height=\"{h}\" fill=\"#fafafa\" stroke=\"#ddd\"/>\
+<text x=\"{pad}\" y=\"18\" font-size=\"12\">{max:.2}</text><text x=\"{pad}\" y=\"{bottom}\" font-size=\"12\">{min:.2}</text>\
+{benchmark_line}<polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"1.5\" points=\"{points}\"/></svg>",
+        w = width, h = height, pad = pad, bottom = height - 8.0, max = max, min = min, color = color,
+        benchmark_line = benchmark_line, points = points(curve)
+    )
+}
+
+/// 단일 HTML 페이지 (외부 스크립트/스타일 없음)
+pub fn render_html(result: &BacktestResult) -> String {
+    let metrics = ReportMetrics::from_result(result);
+    let mut html = String::new();
+    let row = |html: &mut String, label: &str, value: String| {
+        let _ = write!(html, "<tr><th>{}</th><td>{}</td></tr>", label, escape(&value));
+    };
+
+    let _ = write!(html, "<!DOCTYPE html><html lang=\"ko\"><head><meta charset=\"utf-8\"><title>{}</title>", escape(&result.name));
+    html.push_str("<style>body{font-family:sans-serif;margin:2em;color:#222}table{border-collapse:collapse;margin:1em 0}\
+th,td{border:1px solid #ddd;padding:4px 10px;text-align:right}th{background:#f4f4f4;text-align:left}\
+.neg{color:#cf222e}.pos{color:#1a7f37}</style></head><body>");
+    let _ = write!(html, "<h1>{}</h1><p>{}</p>", escape(&result.name), escape(&result.description));
+
+    html.push_str("<h2>요약</h2><table>");
+    row(&mut html, "기간", format!("{} ~ {}", result.start_time, result.end_time));
+    row(&mut html, "심볼", result.symbols.join(", "));
+    row(&mut html, "초기 자산가치", format!("{:.2}", result.initial_value));
+    row(&mut html, "최종 자산가치", format!("{:.2}", result.final_value));
+    row(&mut html, "순이익", format!("{:.2} ({:.2}%)", result.profit, result.profit_percentage));
+    row(&mut html, "지불 수수료", format!("{:.2}", result.fee_paid));
+    if result.fee_attribution.fills > 0 {
+        let fees = &result.fee_attribution;
+        row(&mut html, "메이커 / 테이커 수수료", format!("{:.2} / {:.2}", fees.maker_fees, fees.taker_fees));
+        row(&mut html, "리베이트 / 실효 수수료", format!("{:.2} / {:.2}bps", fees.rebates, fees.effective_bps()));
+    }
+    html.push_str("</table>");
+
+    html.push_str("<h2>자산 곡선</h2>");
+    html.push_str(&equity_svg(result));
+
+    html.push_str("<h2>성과 지표</h2><table>");
+    row(&mut html, "체결 / 청산 수", format!("{} / {}", metrics.trade_count, metrics.closed_trades));
+    row(&mut html, "승률", format!("{:.2}%", metrics.win_rate));
+    row(&mut html, "평균 청산당 수익", format!("{:.2}", metrics.average_profit_per_trade));
+    row(&mut html, "샤프 비율", format!("{:.4}", metrics.sharpe_ratio));
+    row(&mut html, "소르티노 비율", format!("{:.4}", metrics.sortino_ratio));
+    row(&mut html, "칼마 비율", format!("{:.2}", metrics.calmar_ratio));
+    row(&mut html, "최대 손실폭", format!("{:.2}%", metrics.max_drawdown * 100.0));
+    row(&mut html, "수익/위험 비율", metrics.profit_factor.map_or("-".to_string(), |p| format!("{:.2}", p)));
+    row(&mut html, "연간 복합 수익률", format!("{:.2}%", metrics.car * 100.0));
+    row(&mut html, "시장 노출", format!("{:.2}%", metrics.market_exposure * 100.0));
+    row(&mut html, "평균 보유 시간", format!("{:.2}시간", metrics.average_holding_hours));
+    row(&mut html, "MAE 평균 / 최대", format!("{:.2}% / {:.2}%", metrics.excursions.avg_mae * 100.0, metrics.excursions.max_mae * 100.0));
+    row(&mut html, "MFE 평균 / 최대", format!("{:.2}% / {:.2}%", metrics.excursions.avg_mfe * 100.0, metrics.excursions.max_mfe * 100.0));
+    html.push_str("</table>");
+
+    if let Some(b) = &result.benchmark {
+        let _ = write!(html, "<h2>벤치마크 비교</h2><p>{} (점선)</p><table>", escape(&b.name));
+        row(&mut html, "수익률 전략 / 벤치마크", format!("{:.2}% / {:.2}%", b.strategy_return * 100.0, b.benchmark_return * 100.0));
+        row(&mut html, "초과 수익률", format!("{:.2}%", b.excess_return * 100.0));
+        row(&mut html, "알파 (연환산)", format!("{:.2}%", b.alpha * 100.0));
+        row(&mut html, "베타 / 상관계수", format!("{:.3} / {:.3}", b.beta, b.correlation));
+        row(&mut html, "벤치마크 최대 낙폭", format!("{:.2}%", b.max_drawdown * 100.0));
+        row(&mut html, "상대 최대 낙폭", format!("{:.2}%", b.relative_max_drawdown * 100.0));
+        html.push_str("</table>");
+    }
+
+    if !result.symbol_results.is_empty() {
+        html.push_str("<h2>심볼별 성과</h2><table><tr><th>심볼</th><th>체결</th><th>실현</th><th>미실현</th><th>수수료</th><th>순손익</th><th>포지션</th><th>거부</th></tr>");
+        for r in result.symbol_results.values() {
+            let _ = write!(
+                html,
+                "<tr><th>{}</th><td>{}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td><td class=\"{}\">{:.2}</td><td>{:.6}</td><td>{}</td></tr>",
+                escape(&r.symbol), r.trades, r.realized_pnl, r.unrealized_pnl, r.fees, if r.net_pnl < 0.0 { "neg" } else { "pos" }, r.net_pnl, r.position, r.rejected_orders
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    if !result.closed_trades.is_empty() {
+        let skipped = result.closed_trades.len().saturating_sub(MAX_HTML_TRADES);
+        html.push_str("<h2>청산 내역</h2>");
+        if skipped > 0 {
+            let _ = write!(html, "<p>최근 {}건만 표시 ({}건 생략, 전체는 JSON 보고서 참고)</p>", MAX_HTML_TRADES, skipped);
+        }
+        html.push_str("<table><tr><th>청산 시각</th><th>심볼</th><th>방향</th><th>수량</th><th>진입가</th><th>청산가</th><th>손익</th><th>MAE</th><th>MFE</th></tr>");
+        for t in &result.closed_trades[skipped..] {
+            let _ = write!(
+                html,
+                "<tr><th>{}</th><td>{}</td><td>{}</td><td>{:.6}</td><td>{:.2}</td><td>{:.2}</td><td class=\"{}\">{:.2}</td><td>{:.2}%</td><td>{:.2}%</td></tr>",
+                t.closed_at.to_rfc3339(), escape(&t.symbol), if t.side == crate::models::order::OrderSide::Buy { "롱" } else { "숏" },
+                t.quantity, t.entry_price, t.exit_price, if t.pnl < 0.0 { "neg" } else { "pos" }, t.pnl, t.mae * 100.0, t.mfe * 100.0
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    if let Some(mc) = &result.monte_carlo {
+        html.push_str("<h2>몬테카를로 분석</h2><table><tr><th></th><th>실제</th><th>5%</th><th>중앙</th><th>95%</th><th>최악</th></tr>");
+        let _ = write!(
+            html,
+            "<tr><th>최종 자산가치</th><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td></tr>",
+            mc.actual_final_value, mc.final_value.p5, mc.final_value.median, mc.final_value.p95, mc.final_value.min
+        );
+        let _ = write!(
+            html,
+            "<tr><th>최대 낙폭</th><td>{:.2}%</td><td>{:.2}%</td><td>{:.2}%</td><td>{:.2}%</td><td>{:.2}%</td></tr></table>",
+            mc.actual_max_drawdown * 100.0, mc.max_drawdown.p5 * 100.0, mc.max_drawdown.median * 100.0, mc.max_drawdown.p95 * 100.0, mc.max_drawdown.max * 100.0
+        );
+        html.push_str("<table>");
+        row(&mut html, "방식 / 반복", format!("{:?} / {}", mc.config.method, mc.config.iterations));
+        row(&mut html, "손실 확률", format!("{:.2}%", mc.probability_of_loss * 100.0));
+        row(&mut html, "파산 확률", format!("{:.2}% (-{:.0}%)", mc.risk_of_ruin * 100.0, mc.config.ruin_threshold * 100.0));
+        row(&mut html, "실제 최종값 분위", format!("{:.1}%", mc.actual_final_percentile * 100.0));
+        html.push_str("</table>");
+    }
+
+    if let Some(manifest) = &result.manifest {
+        let _ = write!(html, "<h2>재현성 정보</h2><pre>{}</pre>", escape(&manifest.summary()));
+    }
+    html.push_str("</body></html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+    use chrono::{Duration, TimeZone, Utc};
+    use crate::backtest::account::EquityPoint;
+
+    #[test]
+    fn test_html_and_json_reports() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let result = BacktestResult {
+            name: "RSI <test>".into(),
+            description: String::new(),
+            start_time: start,
+            end_time: start + Duration::days(2),
+            initial_balance: HashMap::from([("USDT".to_string(), 1_000.0)]),
+            final_balance: HashMap::new(),
+            initial_value: 1_000.0,
+            final_value: 1_050.0,
+            profit: 50.0,
+            profit_percentage: 5.0,
+            trades: Vec::new(),
+            fee_paid: 1.0,
+            fee_attribution: Default::default(),
+            symbols: vec!["BTCUSDT".into()],
+            manifest: None,
+            disruptions: Default::default(),
+            symbol_results: BTreeMap::new(),
+            closed_trades: Vec::new(),
+            equity_curve: vec![
+                EquityPoint { timestamp: start, equity: 1_000.0 },
+                EquityPoint { timestamp: start + Duration::days(2), equity: 1_050.0 },
+            ],
+            portfolio_max_drawdown: 0.0,
+            market_exposure: 0.5,
+            monte_carlo: None,
+            benchmark: None,
+        };
+
+        let html = render_html(&result);
+        assert!(html.contains("<title>RSI &lt;test&gt;</title>"));
+        assert!(html.contains("<polyline"));
+
+        let json: serde_json::Value = serde_json::from_str(&render_json(&result).unwrap()).unwrap();
+        assert_eq!(json["metrics"]["market_exposure"], 0.5);
+        assert_eq!(json["metrics"]["closed_trades"], 0);
+        assert_eq!(json["result"]["final_value"], 1_050.0);
+        assert_eq!(ReportFormat::from_path(Path::new("out/run.HTML")).unwrap(), ReportFormat::Html);
+    }
+}
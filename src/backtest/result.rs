@@ -1,10 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
+use std::path::Path;
 use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 
+use crate::models::fee::FeeAttribution;
 use crate::models::trade::Trade;
-use super::performance::PerformanceMetrics;
+use super::account::{ClosedTrade, EquityPoint, SymbolResult};
+use super::performance::{ExcursionStats, PerformanceMetrics};
+use super::manifest::ReproducibilityManifest;
+use super::monte_carlo::{self, MonteCarloConfig, MonteCarloReport};
+use super::benchmark::BenchmarkReport;
+use super::disruption::DisruptionStats;
+use super::report::{self, ReportFormat};
+use crate::core::pnl_buckets::TimeBucketReport;
+use crate::error::TradingError;
 
 /// 백테스트 결과 - 백테스트 실행 결과를 저장하고 분석
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -21,7 +31,37 @@ pub struct BacktestResult {
     pub profit_percentage: f64,
     pub trades: Vec<Trade>,
     pub fee_paid: f64,
+    /// 메이커/테이커, 수수료 등급별 수수료
+    #[serde(default)]
+    pub fee_attribution: FeeAttribution,
     pub symbols: Vec<String>,
+    /// 재현성 매니페스트 (버전, 커밋, 시나리오 설정, 데이터 해시, 시드)
+    #[serde(default)]
+    pub manifest: Option<ReproducibilityManifest>,
+    /// 장애 시뮬레이션 집계 (누락 봉, 거부 주문)
+    #[serde(default)]
+    pub disruptions: DisruptionStats,
+    /// 심볼별 체결/손익
+    #[serde(default)]
+    pub symbol_results: BTreeMap<String, SymbolResult>,
+    /// 포지션 청산 내역 (승률/손익비 계산 기준)
+    #[serde(default)]
+    pub closed_trades: Vec<ClosedTrade>,
+    /// 포트폴리오 자산 곡선 (1시간 간격 + 종료 시점)
+    #[serde(default)]
+    pub equity_curve: Vec<EquityPoint>,
+    /// 캔들 단위 포트폴리오 최대 낙폭 (비율)
+    #[serde(default)]
+    pub portfolio_max_drawdown: f64,
+    /// 포지션을 보유한 시간 비율 (0~1)
+    #[serde(default)]
+    pub market_exposure: f64,
+    /// 몬테카를로 강건성 분석 (`with_monte_carlo` 로 추가)
+    #[serde(default)]
+    pub monte_carlo: Option<MonteCarloReport>,
+    /// 벤치마크 비교 (매수 후 보유 또는 사용자 시계열)
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkReport>,
 }
 
 impl BacktestResult {
@@ -30,71 +70,102 @@ impl BacktestResult {
         self.trades.len()
     }
     
-    /// 승리 거래 수 반환
+    /// 승리 거래 수 반환 (이익 청산)
     pub fn winning_trades(&self) -> usize {
-        self.trades.iter()
-          .filter(|t| t.price * t.quantity > 0.0)
+        self.closed_trades.iter()
+          .filter(|t| t.pnl > 0.0)
           .count()
     }
     
-    /// 패배 거래 수 반환
+    /// 패배 거래 수 반환 (손실 청산)
     pub fn losing_trades(&self) -> usize {
-        self.trades.iter()
-          .filter(|t| t.price * t.quantity < 0.0)
+        self.closed_trades.iter()
+          .filter(|t| t.pnl < 0.0)
           .count()
     }
     
     /// 승률 계산
     pub fn win_rate(&self) -> f64 {
-        if self.trades.is_empty() {
+        if self.closed_trades.is_empty() {
             return 0.0;
         }
         
-        (self.winning_trades() as f64 / self.trades.len() as f64) * 100.0
+        (self.winning_trades() as f64 / self.closed_trades.len() as f64) * 100.0
     }
     
-    /// 평균 거래당 수익 계산
+    /// 평균 청산당 수익 계산
     pub fn average_profit_per_trade(&self) -> f64 {
-        if self.trades.is_empty() {
+        if self.closed_trades.is_empty() {
             return 0.0;
         }
         
-        let total_pnl: f64 = self.trades.iter()
-          .map(|t| t.price * t.quantity)
+        let total_pnl: f64 = self.closed_trades.iter()
+          .map(|t| t.pnl)
           .sum();
         
-        total_pnl / self.trades.len() as f64
+        total_pnl / self.closed_trades.len() as f64
     }
     
-    /// 최대 수익 거래 찾기
-    pub fn max_profit_trade(&self) -> Option<(&Trade, f64)> {
-        self.trades.iter()
-          .filter(|t| t.price * t.quantity > 0.0)
-          .map(|t| (t, t.price * t.quantity))
-          .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    /// 최대 수익 청산 찾기
+    pub fn max_profit_trade(&self) -> Option<(&ClosedTrade, f64)> {
+        self.closed_trades.iter()
+          .filter(|t| t.pnl > 0.0)
+          .map(|t| (t, t.pnl))
+          .max_by(|a, b| a.1.total_cmp(&b.1))
     }
     
-    /// 최대 손실 거래 찾기
-    pub fn max_loss_trade(&self) -> Option<(&Trade, f64)> {
-        self.trades.iter()
-          .filter(|t| t.price * t.quantity < 0.0)
-          .map(|t| (t, t.price * t.quantity))
-          .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    /// 최대 손실 청산 찾기
+    pub fn max_loss_trade(&self) -> Option<(&ClosedTrade, f64)> {
+        self.closed_trades.iter()
+          .filter(|t| t.pnl < 0.0)
+          .map(|t| (t, t.pnl))
+          .min_by(|a, b| a.1.total_cmp(&b.1))
     }
     
-    /// 샤프 비율 계산
+    /// 샤프 비율 계산 (자산 곡선의 일별 수익률 기준)
     pub fn sharpe_ratio(&self) -> f64 {
-        PerformanceMetrics::calculate_sharpe_ratio(&self.trades, self.initial_value)
+        if self.equity_curve.is_empty() {
+            return PerformanceMetrics::calculate_sharpe_ratio(&self.trades, self.initial_value);
+        }
+        PerformanceMetrics::sharpe_from_equity(&self.equity_curve, self.initial_value)
     }
     
     /// 최대 손실폭 계산
     pub fn max_drawdown(&self) -> f64 {
-        PerformanceMetrics::calculate_max_drawdown(&self.trades, self.initial_value)
+        if self.equity_curve.is_empty() {
+            return PerformanceMetrics::calculate_max_drawdown(&self.trades, self.initial_value);
+        }
+        self.portfolio_max_drawdown
+    }
+    
+    /// 소르티노 비율 (자산 곡선의 일별 하방 편차 기준)
+    pub fn sortino_ratio(&self) -> f64 {
+        PerformanceMetrics::sortino_from_equity(&self.equity_curve, self.initial_value)
+    }
+    
+    /// 칼마 비율 (연간 복합 수익률 / 최대 낙폭)
+    pub fn calmar_ratio(&self) -> f64 {
+        PerformanceMetrics::calmar_ratio(self.car(), self.max_drawdown())
+    }
+    
+    /// 청산 건별 평균 보유 시간 (시간)
+    pub fn average_holding_hours(&self) -> f64 {
+        PerformanceMetrics::average_holding_ms(&self.closed_trades) / 3_600_000.0
     }
     
-    /// 수익 대 위험 비율 계산
+    /// 청산 건별 MAE/MFE 통계
+    pub fn excursion_stats(&self) -> ExcursionStats {
+        PerformanceMetrics::excursion_stats(&self.closed_trades)
+    }
+    
+    /// 수익 대 위험 비율 계산 (총 이익 청산 / 총 손실 청산)
     pub fn profit_factor(&self) -> f64 {
-        PerformanceMetrics::calculate_profit_factor(&self.trades)
+        let gross_profit: f64 = self.closed_trades.iter().filter(|t| t.pnl > 0.0).map(|t| t.pnl).sum();
+        let gross_loss: f64 = self.closed_trades.iter().filter(|t| t.pnl < 0.0).map(|t| -t.pnl).sum();
+        if gross_loss == 0.0 {
+            return if gross_profit > 0.0 { f64::INFINITY } else { 0.0 };
+        }
+        gross_profit / gross_loss
     }
     
     /// 연간 복합 수익률 계산
@@ -109,6 +180,29 @@ impl BacktestResult {
         ((self.final_value / self.initial_value).powf(1.0 / years)) - 1.0
     }
     
+    /// 지정 타임존 기준 시간대/요일별 실현 손익
+    pub fn time_buckets(&self, timezone: &str) -> Result<TimeBucketReport, TradingError> {
+        TimeBucketReport::from_trades(&self.trades, timezone)
+    }
+    
+    /// 청산 순서 재표본 몬테카를로 분석을 실행하여 결과(요약/보고서)에 포함
+    pub fn with_monte_carlo(&mut self, config: &MonteCarloConfig) -> Result<&MonteCarloReport, TradingError> {
+        Ok(self.monte_carlo.insert(monte_carlo::run(self, config)?))
+    }
+    
+    /// 단일 파일 보고서로 저장 (JSON: 지표 + 전체 결과, HTML: 표와 인라인 자산 곡선 차트)
+    pub fn export(&self, path: &Path, format: ReportFormat) -> Result<(), TradingError> {
+        let body = match format {
+            ReportFormat::Json => report::render_json(self)?,
+            ReportFormat::Html => report::render_html(self),
+        };
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, body)?;
+        Ok(())
+    }
+    
     /// 결과 요약 문자열 생성
     pub fn summary(&self) -> String {
         let mut summary = String::new();
@@ -123,6 +217,13 @@ impl BacktestResult {
         summary.push_str(&format!("최종 자산가치: ${:.2}\n", self.final_value));
         summary.push_str(&format!("순이익: ${:.2} ({:.2}%)\n", self.profit, self.profit_percentage));
         summary.push_str(&format!("지불 수수료: ${:.2}\n", self.fee_paid));
+        if self.fee_attribution.fills > 0 {
+            let fees = &self.fee_attribution;
+            summary.push_str(&format!(
+                "  메이커 ${:.2} / 테이커 ${:.2} (리베이트 ${:.2}, 메이커 비중 {:.1}%, 실효 {:.2}bps)\n",
+                fees.maker_fees, fees.taker_fees, fees.rebates, fees.maker_share() * 100.0, fees.effective_bps()
+            ));
+        }
         summary.push_str("\n");
         
         summary.push_str(&format!("총 거래 수: {}\n", self.trade_count()));
@@ -136,6 +237,56 @@ impl BacktestResult {
         summary.push_str(&format!("최대 손실폭: {:.2}%\n", self.max_drawdown() * 100.0));
         summary.push_str(&format!("수익/위험 비율: {:.2}\n", self.profit_factor()));
         summary.push_str(&format!("연간 복합 수익률: {:.2}%\n", self.car() * 100.0));
+        summary.push_str(&format!("소르티노 비율: {:.4}\n", self.sortino_ratio()));
+        summary.push_str(&format!("칼마 비율: {:.2}\n", self.calmar_ratio()));
+        summary.push_str(&format!("시장 노출: {:.2}%\n", self.market_exposure * 100.0));
+        summary.push_str(&format!("평균 보유 시간: {:.2}시간\n", self.average_holding_hours()));
+        if !self.closed_trades.is_empty() {
+            let excursions = self.excursion_stats();
+            summary.push_str(&format!(
+                "MAE 평균/최대: {:.2}% / {:.2}%, MFE 평균/최대: {:.2}% / {:.2}%\n",
+                excursions.avg_mae * 100.0, excursions.max_mae * 100.0, excursions.avg_mfe * 100.0, excursions.max_mfe * 100.0
+            ));
+        }
+        
+        if !self.trades.is_empty() {
+            if let Ok(buckets) = self.time_buckets("UTC") {
+                summary.push_str("\n===== 시간대별 성과 =====\n");
+                summary.push_str(&buckets.summary());
+            }
+        }
+        
+        if let Some(benchmark) = &self.benchmark {
+            summary.push_str("\n===== 벤치마크 비교 =====\n");
+            summary.push_str(&benchmark.summary());
+        }
+        
+        if self.symbol_results.len() > 1 {
+            summary.push_str("\n===== 심볼별 성과 =====\n");
+            for r in self.symbol_results.values() {
+                summary.push_str(&format!(
+                    "{}: 거래 {}회, 실현 ${:.2}, 미실현 ${:.2}, 수수료 ${:.2}, 순손익 ${:.2} (포지션 {:.6})\n",
+                    r.symbol, r.trades, r.realized_pnl, r.unrealized_pnl, r.fees, r.net_pnl, r.position
+                ));
+            }
+        }
+        
+        if self.disruptions.windows > 0 {
+            summary.push_str("\n===== 장애 시뮬레이션 =====\n");
+            summary.push_str(&format!("장애 구간: {}\n", self.disruptions.windows));
+            summary.push_str(&format!("누락된 시세: {}\n", self.disruptions.dropped_bars));
+            summary.push_str(&format!("거부된 주문: {}\n", self.disruptions.rejected_orders));
+        }
+        
+        if let Some(monte_carlo) = &self.monte_carlo {
+            summary.push_str("\n===== 몬테카를로 분석 =====\n");
+            summary.push_str(&monte_carlo.summary());
+        }
+        
+        if let Some(manifest) = &self.manifest {
+            summary.push_str("\n===== 재현성 정보 =====\n");
+            summary.push_str(&manifest.summary());
+        }
         
         summary
     }
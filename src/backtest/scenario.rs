@@ -1,14 +1,25 @@
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
 
+use crate::config::StrategySpec;
 use crate::error::TradingError;
+use crate::exchange::fill_model::{FillModel, SlippageModel};
+use crate::models::fee::FeeSchedule;
 use crate::models::market_data::MarketData;
-use crate::strategies::Strategy;
-use super::engine::BacktestEngine;
+use crate::models::timestamp::Timestamp;
+use crate::strategies::{build_strategy, Strategy};
+use crate::market_data::aggregator::parse_timeframe;
+use crate::market_data::external::{ExternalSeries, ExternalSeriesStore};
+use super::engine::{BacktestEngine, BacktestProgress};
 use super::result::BacktestResult;
 use super::data_provider::{HistoricalDataProvider, CsvDataProvider};
+use super::manifest::{ReproducibilityManifest, ScenarioSnapshot};
+use super::disruption::{DisruptionKind, DisruptionSchedule, DisruptionWindow};
+use super::tick::CsvTickProvider;
+use super::benchmark::BenchmarkSpec;
 
 /// 백테스트 시나리오 - 백테스트를 실행하기 위한 모든 설정 및 매개변수 포함
 pub struct BacktestScenario {
@@ -41,6 +52,11 @@ impl BacktestScenario {
         &self.description
     }
     
+    /// 진행률 핸들 (실행 전에 받아 두면 실행 중 조회 가능)
+    pub fn progress(&self) -> BacktestProgress {
+        self.engine.progress()
+    }
+    
     /// 백테스트 실행
     pub async fn run(&mut self) -> Result<BacktestResult, TradingError> {
         self.engine.run().await
@@ -54,12 +70,23 @@ pub struct BacktestScenarioBuilder {
     start_time: Option<DateTime<Utc>>,
     end_time: Option<DateTime<Utc>>,
     data_file: Option<PathBuf>,
+    symbol_data_files: BTreeMap<String, PathBuf>,
+    quote_asset: String,
     symbols: Vec<String>,
     initial_balance: HashMap<String, f64>,
     fee_rate: f64,
     slippage: f64,
+    fill_model: Option<FillModel>,
+    slippage_model: Option<SlippageModel>,
+    fee_schedule: Option<FeeSchedule>,
     strategies: Vec<Box<dyn Strategy>>,
     csv_delimiter: char,
+    rng_seeds: BTreeMap<String, u64>,
+    disruptions: Vec<DisruptionWindow>,
+    external_series: BTreeMap<String, PathBuf>,
+    tick_data_files: BTreeMap<String, PathBuf>,
+    tick_bar_timeframe: Option<String>,
+    benchmark: Option<BenchmarkSpec>,
 }
 
 impl BacktestScenarioBuilder {
@@ -71,12 +98,23 @@ impl BacktestScenarioBuilder {
             start_time: None,
             end_time: None,
             data_file: None,
+            symbol_data_files: BTreeMap::new(),
+            quote_asset: "USDT".to_string(),
             symbols: Vec::new(),
             initial_balance: HashMap::new(),
             fee_rate: 0.001, // 기본 수수료율 0.1%
             slippage: 0.0005, // 기본 슬리피지 0.05%
+            fill_model: None,
+            slippage_model: None,
+            fee_schedule: None,
             strategies: Vec::new(),
             csv_delimiter: ',',
+            rng_seeds: BTreeMap::new(),
+            disruptions: Vec::new(),
+            external_series: BTreeMap::new(),
+            tick_data_files: BTreeMap::new(),
+            tick_bar_timeframe: None,
+            benchmark: Some(BenchmarkSpec::default()), // 단일 심볼이면 그 심볼 매수 후 보유
         }
     }
     
@@ -106,6 +144,22 @@ impl BacktestScenarioBuilder {
         self
     }
     
+    /// 심볼별 데이터 파일 추가 (다중 심볼 포트폴리오 백테스트, 모든 심볼이 현금 잔고 공유)
+    pub fn symbol_data_file(mut self, symbol: impl Into<String>, path: PathBuf) -> Self {
+        let symbol = symbol.into();
+        if !self.symbols.contains(&symbol) {
+            self.symbols.push(symbol.clone());
+        }
+        self.symbol_data_files.insert(symbol, path);
+        self
+    }
+    
+    /// 공유 현금 계좌의 호가 통화 (기본 USDT, 초기 잔고 중 이 통화만 현금으로 사용)
+    pub fn quote_asset(mut self, asset: impl Into<String>) -> Self {
+        self.quote_asset = asset.into();
+        self
+    }
+    
     /// 심볼 추가
     pub fn symbol(mut self, symbol: impl Into<String>) -> Self {
         self.symbols.push(symbol.into());
@@ -130,6 +184,24 @@ impl BacktestScenarioBuilder {
         self
     }
     
+    /// 체결 모델 설정 - 지정 시 fee_rate/slippage 대신 사용
+    pub fn fill_model(mut self, fill_model: FillModel) -> Self {
+        self.fill_model = Some(fill_model);
+        self
+    }
+    
+    /// 주문 크기/캔들 범위 기반 슬리피지 모델 설정 - 지정 시 고정 슬리피지 비율 대신 사용
+    pub fn slippage_model(mut self, slippage_model: SlippageModel) -> Self {
+        self.slippage_model = Some(slippage_model);
+        self
+    }
+    
+    /// 메이커/테이커 수수료 체계 설정 (심볼별 요율, 30일 거래대금 등급) - 지정 시 fee_rate 대신 사용
+    pub fn fee_schedule(mut self, fee_schedule: FeeSchedule) -> Self {
+        self.fee_schedule = Some(fee_schedule);
+        self
+    }
+    
     /// 전략 추가
     pub fn strategy(mut self, strategy: Box<dyn Strategy>) -> Self {
         self.strategies.push(strategy);
@@ -142,6 +214,64 @@ impl BacktestScenarioBuilder {
         self
     }
     
+    /// 난수 시드 기록 (재현성 매니페스트에 포함)
+    pub fn rng_seed(mut self, name: impl Into<String>, seed: u64) -> Self {
+        self.rng_seeds.insert(name.into(), seed);
+        self
+    }
+    
+    /// 거래소 전체 장애 구간 추가 (시세 공백 + 주문 거부)
+    pub fn exchange_outage(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.disruptions.push(DisruptionWindow { kind: DisruptionKind::ExchangeOutage, start, end, symbols: Vec::new() });
+        self
+    }
+    
+    /// 심볼 거래 정지 구간 추가
+    pub fn trading_halt(mut self, symbol: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.disruptions.push(DisruptionWindow { kind: DisruptionKind::TradingHalt, start, end, symbols: vec![symbol.into()] });
+        self
+    }
+    
+    /// 시세 누락 구간 추가 (주문은 가능)
+    pub fn data_gap(mut self, symbol: impl Into<String>, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.disruptions.push(DisruptionWindow { kind: DisruptionKind::DataGap, start, end, symbols: vec![symbol.into()] });
+        self
+    }
+    
+    /// 외부 시계열 CSV 추가 (`timestamp,value` 형식, 전략에는 `name` 으로 전달)
+    pub fn external_series(mut self, name: impl Into<String>, path: PathBuf) -> Self {
+        self.external_series.insert(name.into(), path);
+        self
+    }
+    
+    /// 심볼별 체결 틱 CSV 추가 (`timestamp,price,quantity`) - 해당 심볼은 캔들 대신 틱 단위로 재생
+    pub fn tick_data_file(mut self, symbol: impl Into<String>, path: PathBuf) -> Self {
+        let symbol = symbol.into();
+        if !self.symbols.contains(&symbol) {
+            self.symbols.push(symbol.clone());
+        }
+        self.tick_data_files.insert(symbol, path);
+        self
+    }
+    
+    /// 틱 재생 심볼의 전략 입력 캔들 주기 (예: "1m") - 미지정 시 전략도 틱마다 갱신
+    pub fn tick_bar_timeframe(mut self, timeframe: impl Into<String>) -> Self {
+        self.tick_bar_timeframe = Some(timeframe.into());
+        self
+    }
+    
+    /// 벤치마크 설정 (기본: 단일 심볼 백테스트의 매수 후 보유)
+    pub fn benchmark(mut self, benchmark: BenchmarkSpec) -> Self {
+        self.benchmark = Some(benchmark);
+        self
+    }
+    
+    /// 벤치마크 비교 생략
+    pub fn without_benchmark(mut self) -> Self {
+        self.benchmark = None;
+        self
+    }
+    
     /// 시나리오 빌드
     pub fn build(self) -> Result<BacktestScenario, TradingError> {
         // 필수 파라미터 검증
@@ -155,10 +285,54 @@ impl BacktestScenarioBuilder {
             return Err(TradingError::InvalidParameter("시작 시간은 종료 시간보다 이전이어야 합니다".into()));
         }
         
+        if let Some(w) = self.disruptions.iter().find(|w| w.start >= w.end) {
+            return Err(TradingError::InvalidParameter(format!("장애 구간의 시작이 종료보다 이전이어야 합니다: {:?}", w.kind)));
+        }
+        
         if self.strategies.is_empty() {
             return Err(TradingError::InvalidParameter("최소 하나의 전략이 필요합니다".into()));
         }
         
+        if let Some(fee_schedule) = &self.fee_schedule {
+            fee_schedule.validate().map_err(TradingError::InvalidParameter)?;
+        }
+        
+        // 슬리피지 모델과 수수료 체계는 체결 모델(없으면 fee_rate/slippage 단일 요율 모델)에 얹음
+        let fill_model = if self.slippage_model.is_some() || self.fee_schedule.is_some() {
+            let base = self.fill_model.unwrap_or_else(|| FillModel::flat(self.fee_rate, self.slippage));
+            Some(FillModel {
+                slippage_model: self.slippage_model.or(base.slippage_model.clone()),
+                fee_schedule: self.fee_schedule.or(base.fee_schedule.clone()),
+                ..base
+            })
+        } else {
+            self.fill_model
+        };
+        
+        // 재현성 매니페스트 생성
+        let snapshot = ScenarioSnapshot {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            start_time,
+            end_time,
+            data_file: self.data_file.clone(),
+            symbol_data_files: self.symbol_data_files.clone(),
+            quote_asset: self.quote_asset.clone(),
+            symbols: self.symbols.clone(),
+            initial_balance: self.initial_balance.clone(),
+            fee_rate: self.fee_rate,
+            slippage: self.slippage,
+            fill_model: fill_model.clone(),
+            strategies: self.strategies.iter().map(|s| s.name().to_string()).collect(),
+            csv_delimiter: self.csv_delimiter,
+            disruptions: self.disruptions.clone(),
+            external_series: self.external_series.clone(),
+            tick_data_files: self.tick_data_files.clone(),
+            tick_bar_timeframe: self.tick_bar_timeframe.clone(),
+            benchmark: self.benchmark.clone(),
+        };
+        let manifest = ReproducibilityManifest::capture(snapshot, self.rng_seeds.clone())?;
+        
         // 백테스트 엔진 생성
         let mut engine = BacktestEngine::new(
             self.name.clone(),
@@ -170,6 +344,25 @@ impl BacktestScenarioBuilder {
             self.slippage,
         );
         
+        engine.set_manifest(manifest);
+        engine.set_quote_asset(self.quote_asset.clone());
+        if let Some(fill_model) = fill_model {
+            engine.set_fill_model(fill_model);
+        }
+        engine.set_disruptions(DisruptionSchedule::new(self.disruptions));
+        if let Some(benchmark) = self.benchmark {
+            engine.set_benchmark(benchmark);
+        }
+        
+        // 외부 시계열 로드 - 캔들 주기를 알 수 없으므로 관측 시각 그대로 두어 미래 값 참조를 피함
+        if !self.external_series.is_empty() {
+            let store = ExternalSeriesStore::default();
+            for (name, path) in &self.external_series {
+                store.insert_series(ExternalSeries::load_csv(name.as_str(), path, 0, usize::MAX)?);
+            }
+            engine.set_external_series(store);
+        }
+        
         // 데이터 제공자 설정
         if let Some(data_file) = self.data_file {
             let provider = CsvDataProvider::new(
@@ -178,7 +371,17 @@ impl BacktestScenarioBuilder {
             )?;
             
             engine.set_data_provider(provider);
-        } else if !self.symbols.is_empty() {
+        }
+        for (symbol, path) in self.symbol_data_files {
+            engine.add_symbol_provider(symbol, Box::new(CsvDataProvider::new(path, self.csv_delimiter)?));
+        }
+        for (symbol, path) in self.tick_data_files {
+            engine.add_tick_provider(symbol, Box::new(CsvTickProvider::new(path, self.csv_delimiter)?));
+        }
+        if let Some(timeframe) = &self.tick_bar_timeframe {
+            engine.set_tick_bar_interval(parse_timeframe(timeframe)?);
+        }
+        if !engine.has_data_source() && !self.symbols.is_empty() {
             // 심볼만 지정된 경우 기본 데이터 제공자 필요
             return Err(TradingError::InvalidParameter("데이터 파일 또는 데이터 제공자가 필요합니다".into()));
         }
@@ -197,4 +400,250 @@ impl BacktestScenarioBuilder {
         
         Ok(scenario)
     }
-}
\ No newline at end of file
+}
+
+fn default_scenario_quote_asset() -> String { "USDT".to_string() }
+
+/// 시나리오 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioFormat {
+    Toml,
+    Yaml,
+    Json,
+}
+
+impl ScenarioFormat {
+    /// 확장자로 형식 판별 (toml, yaml/yml, json)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(ScenarioFormat::Toml),
+            "yaml" | "yml" => Some(ScenarioFormat::Yaml),
+            "json" => Some(ScenarioFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// 파일로 정의하는 백테스트 시나리오 - 재컴파일 없이 전략/데이터/수수료/기간/초기 잔고 지정
+///
+/// 전략은 설정 파일의 `strategies` 와 같은 `type` 태그 형식을 쓰고, 기간은 `start`/`end`(epoch ms 또는 RFC3339)
+/// 또는 `last_days` 로 지정한다. 상대 경로는 현재 작업 디렉터리 기준이다.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ScenarioFile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub strategies: Vec<StrategySpec>,
+    /// 모든 심볼이 들어 있는 캔들 CSV
+    #[serde(default)]
+    pub data_file: Option<PathBuf>,
+    /// 심볼별 캔들 CSV (공유 현금 잔고 포트폴리오 백테스트)
+    #[serde(default)]
+    pub symbol_data_files: BTreeMap<String, PathBuf>,
+    /// 심볼별 체결 틱 CSV
+    #[serde(default)]
+    pub tick_data_files: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    pub tick_bar_timeframe: Option<String>,
+    #[serde(default)]
+    pub external_series: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    pub csv_delimiter: Option<char>,
+    #[serde(default)]
+    pub start: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+    #[serde(default)]
+    pub last_days: Option<i64>,
+    #[serde(default = "default_scenario_quote_asset")]
+    pub quote_asset: String,
+    /// 자산별 초기 잔고 (없으면 호가 통화 10,000)
+    #[serde(default)]
+    pub initial_balances: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub fee_rate: Option<f64>,
+    #[serde(default)]
+    pub slippage: Option<f64>,
+    #[serde(default)]
+    pub fill_model: Option<FillModel>,
+    /// 크기 비례 슬리피지 (`fill_model.slippage_model` 로도 지정 가능)
+    #[serde(default)]
+    pub slippage_model: Option<SlippageModel>,
+    /// 메이커/테이커 수수료 체계 (`fill_model.fee_schedule` 로도 지정 가능)
+    #[serde(default)]
+    pub fee_schedule: Option<FeeSchedule>,
+    /// 재현성 매니페스트에 기록할 난수 시드
+    #[serde(default)]
+    pub seeds: BTreeMap<String, u64>,
+    /// 벤치마크 (없으면 단일 심볼 매수 후 보유)
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkSpec>,
+}
+
+impl ScenarioFile {
+    /// 시나리오 파일 로드 (형식은 확장자로 판별)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TradingError> {
+        let path = path.as_ref();
+        let format = ScenarioFormat::from_path(path).ok_or_else(|| {
+            TradingError::InvalidParameter(format!("시나리오 파일 확장자는 toml, yaml, yml, json 중 하나여야 합니다: {}", path.display()))
+        })?;
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| TradingError::DataNotFound(format!("{}: {}", path.display(), e)))?;
+        Self::parse(&text, format)
+            .map_err(|e| TradingError::ParseError(format!("{}: {}", path.display(), e)))
+    }
+    
+    /// 시나리오 텍스트 파싱
+    pub fn parse(text: &str, format: ScenarioFormat) -> Result<Self, TradingError> {
+        let parsed = match format {
+            ScenarioFormat::Toml => toml::from_str(text).map_err(|e| e.to_string()),
+            ScenarioFormat::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+            ScenarioFormat::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+        };
+        parsed.map_err(TradingError::ParseError)
+    }
+    
+    fn period(&self) -> Result<(DateTime<Utc>, DateTime<Utc>), TradingError> {
+        match (&self.start, &self.end, self.last_days) {
+            (Some(start), Some(end), None) => Ok((Timestamp::parse(start)?.to_datetime(), Timestamp::parse(end)?.to_datetime())),
+            (None, None, Some(days)) if days > 0 => {
+                let end = Utc::now();
+                Ok((end - Duration::days(days), end))
+            }
+            _ => Err(TradingError::InvalidParameter("start 와 end, 또는 양수 last_days 중 하나만 지정해야 합니다".into())),
+        }
+    }
+    
+    /// 시나리오 구성 (전략 파라미터 검증 및 데이터 파일 로드)
+    pub fn build(&self) -> Result<BacktestScenario, TradingError> {
+        if self.data_file.is_none() && self.symbol_data_files.is_empty() && self.tick_data_files.is_empty() {
+            return Err(TradingError::InvalidParameter("data_file, symbol_data_files, tick_data_files 중 하나가 필요합니다".into()));
+        }
+        let (start, end) = self.period()?;
+        let mut builder = BacktestScenarioBuilder::new(self.name.clone())
+            .description(self.description.clone())
+            .period(start, end)
+            .quote_asset(self.quote_asset.clone());
+        
+        if self.initial_balances.is_empty() {
+            builder = builder.initial_balance(self.quote_asset.clone(), 10_000.0);
+        }
+        for (asset, amount) in &self.initial_balances {
+            builder = builder.initial_balance(asset.clone(), *amount);
+        }
+        if let Some(fee_rate) = self.fee_rate {
+            builder = builder.fee_rate(fee_rate);
+        }
+        if let Some(slippage) = self.slippage {
+            builder = builder.slippage(slippage);
+        }
+        if let Some(fill_model) = &self.fill_model {
+            builder = builder.fill_model(fill_model.clone());
+        }
+        if let Some(slippage_model) = &self.slippage_model {
+            builder = builder.slippage_model(slippage_model.clone());
+        }
+        if let Some(fee_schedule) = &self.fee_schedule {
+            builder = builder.fee_schedule(fee_schedule.clone());
+        }
+        if let Some(delimiter) = self.csv_delimiter {
+            builder = builder.csv_delimiter(delimiter);
+        }
+        
+        // 데이터 소스
+        if let Some(path) = &self.data_file {
+            builder = builder.data_file(path.clone());
+        }
+        for (symbol, path) in &self.symbol_data_files {
+            builder = builder.symbol_data_file(symbol.clone(), path.clone());
+        }
+        for (symbol, path) in &self.tick_data_files {
+            builder = builder.tick_data_file(symbol.clone(), path.clone());
+        }
+        if let Some(timeframe) = &self.tick_bar_timeframe {
+            builder = builder.tick_bar_timeframe(timeframe.clone());
+        }
+        for (name, path) in &self.external_series {
+            builder = builder.external_series(name.clone(), path.clone());
+        }
+        for (name, seed) in &self.seeds {
+            builder = builder.rng_seed(name.clone(), *seed);
+        }
+        if let Some(benchmark) = &self.benchmark {
+            builder = builder.benchmark(benchmark.clone());
+        }
+        
+        for spec in &self.strategies {
+            builder = builder.strategy(build_strategy(spec)?);
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+name = "RSI 샘플"
+description = "파일 시나리오 테스트"
+data_file = "data/samples/BTCUSDT-1m.csv"
+start = "2024-01-01T00:00:00Z"
+end = "1704153540000"
+fee_rate = 0.002
+
+[initial_balances]
+USDT = 50000.0
+
+[[strategies]]
+type = "rsi"
+symbol = "BTCUSDT"
+period = 14
+oversold = 30.0
+overbought = 70.0
+"#;
+
+    const YAML: &str = r#"
+name: RSI 샘플
+description: 파일 시나리오 테스트
+data_file: data/samples/BTCUSDT-1m.csv
+start: "2024-01-01T00:00:00Z"
+end: "1704153540000"
+fee_rate: 0.002
+initial_balances:
+  USDT: 50000.0
+strategies:
+  - type: rsi
+    symbol: BTCUSDT
+    period: 14
+    oversold: 30.0
+    overbought: 70.0
+"#;
+
+    #[tokio::test]
+    async fn test_scenario_file() {
+        let toml = ScenarioFile::parse(TOML, ScenarioFormat::Toml).unwrap();
+        let yaml = ScenarioFile::parse(YAML, ScenarioFormat::Yaml).unwrap();
+        assert_eq!(toml.strategies, yaml.strategies);
+        assert_eq!(toml.initial_balances, yaml.initial_balances);
+        assert_eq!(toml.period().unwrap(), yaml.period().unwrap());
+        assert_eq!(toml.quote_asset, "USDT");
+        assert_eq!(ScenarioFormat::from_path(Path::new("my.YML")), Some(ScenarioFormat::Yaml));
+        assert_eq!(ScenarioFormat::from_path(Path::new("my.csv")), None);
+
+        // 기간과 last_days 를 함께 지정하면 거부
+        let mut both = toml.clone();
+        both.last_days = Some(7);
+        assert!(both.build().is_err());
+
+        // 알 수 없는 전략 타입은 파싱 오류
+        assert!(ScenarioFile::parse(&TOML.replace("\"rsi\"", "\"nope\""), ScenarioFormat::Toml).is_err());
+
+        let mut scenario = yaml.build().unwrap();
+        assert_eq!(scenario.name(), "RSI 샘플");
+        let result = scenario.run().await.unwrap();
+        // 단일 심볼 시나리오는 기본으로 매수 후 보유와 비교
+        let benchmark = result.benchmark.expect("buy & hold benchmark");
+        assert_eq!(benchmark.equity_curve.len(), result.equity_curve.len());
+    }
+}
@@ -0,0 +1,254 @@
+use std::path::Path;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::to_decimal;
+use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
+
+// 연환산 기준 밀리초
+const MS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0 * 1000.0;
+// 캔들 하나를 구성하는 내부 경로 스텝 수 (고가/저가 생성용)
+const STEPS_PER_CANDLE: usize = 4;
+
+/// 시장 국면 - 연환산 드리프트/변동성과 평균 지속 캔들 수
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketRegime {
+    pub name: String,
+    pub drift: f64,
+    pub volatility: f64,
+    pub mean_duration: usize,
+    /// 기준 거래량 배수
+    pub volume_factor: f64,
+}
+
+impl MarketRegime {
+    pub fn new(name: impl Into<String>, drift: f64, volatility: f64, mean_duration: usize, volume_factor: f64) -> Self {
+        MarketRegime { name: name.into(), drift, volatility, mean_duration: mean_duration.max(1), volume_factor }
+    }
+
+    /// 기본 국면 집합: 상승 / 하락 / 횡보 / 고변동
+    pub fn defaults() -> Vec<MarketRegime> {
+        vec![
+            MarketRegime::new("bull", 0.8, 0.45, 1_440, 1.2),
+            MarketRegime::new("bear", -0.7, 0.6, 1_080, 1.4),
+            MarketRegime::new("sideways", 0.0, 0.25, 2_160, 0.8),
+            MarketRegime::new("high_vol", 0.0, 1.2, 360, 2.0),
+        ]
+    }
+}
+
+/// 합성 데이터 생성 설정
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyntheticDataConfig {
+    pub symbol: String,
+    /// 첫 캔들 시각 (ms)
+    pub start_time: i64,
+    pub interval_ms: i64,
+    pub candles: usize,
+    pub initial_price: f64,
+    /// 캔들당 평균 거래량 (국면 배수 적용 전)
+    pub base_volume: f64,
+    pub seed: u64,
+    pub regimes: Vec<MarketRegime>,
+}
+
+impl SyntheticDataConfig {
+    pub fn new(symbol: impl Into<String>, start_time: i64, interval_ms: i64, candles: usize) -> Self {
+        SyntheticDataConfig {
+            symbol: symbol.into(),
+            start_time,
+            interval_ms,
+            candles,
+            initial_price: 30_000.0,
+            base_volume: 10.0,
+            seed: 42,
+            regimes: MarketRegime::defaults(),
+        }
+    }
+
+    pub fn initial_price(mut self, price: f64) -> Self {
+        self.initial_price = price;
+        self
+    }
+
+    pub fn base_volume(mut self, volume: f64) -> Self {
+        self.base_volume = volume;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn regimes(mut self, regimes: Vec<MarketRegime>) -> Self {
+        self.regimes = regimes;
+        self
+    }
+
+    fn validate(&self) -> Result<(), TradingError> {
+        if self.interval_ms <= 0 || self.candles == 0 {
+            return Err(TradingError::InvalidParameter("interval_ms와 candles는 양수여야 합니다".into()));
+        }
+        if self.initial_price <= 0.0 || self.base_volume <= 0.0 {
+            return Err(TradingError::InvalidParameter("initial_price와 base_volume은 양수여야 합니다".into()));
+        }
+        if self.regimes.is_empty() || self.regimes.iter().any(|r| r.volatility < 0.0 || r.volume_factor <= 0.0) {
+            return Err(TradingError::InvalidParameter("유효한 시장 국면이 최소 하나 필요합니다".into()));
+        }
+        Ok(())
+    }
+}
+
+/// 국면 전환 기하 브라운 운동(GBM) 기반 캔들 생성기 - 같은 설정/시드면 항상 같은 데이터
+pub struct SyntheticDataGenerator {
+    config: SyntheticDataConfig,
+}
+
+impl SyntheticDataGenerator {
+    pub fn new(config: SyntheticDataConfig) -> Result<Self, TradingError> {
+        config.validate()?;
+        Ok(SyntheticDataGenerator { config })
+    }
+
+    /// 캔들 생성과 함께 각 캔들의 국면 이름 반환
+    pub fn generate_with_regimes(&self) -> (Vec<MarketData>, Vec<String>) {
+        let cfg = &self.config;
+        let mut rng = StdRng::seed_from_u64(cfg.seed);
+        let dt = cfg.interval_ms as f64 / MS_PER_YEAR / STEPS_PER_CANDLE as f64;
+
+        let mut candles = Vec::with_capacity(cfg.candles);
+        let mut labels = Vec::with_capacity(cfg.candles);
+        let mut regime = 0;
+        let mut price = cfg.initial_price;
+
+        for i in 0..cfg.candles {
+            // 국면 전환: 평균 지속 기간의 역수 확률로 다른 국면으로 이동
+            let current = &cfg.regimes[regime];
+            if cfg.regimes.len() > 1 && rng.gen::<f64>() < 1.0 / current.mean_duration as f64 {
+                let next = rng.gen_range(0..cfg.regimes.len() - 1);
+                regime = if next >= regime { next + 1 } else { next };
+            }
+            let current = &cfg.regimes[regime];
+
+            let open = price;
+            let (mut high, mut low) = (open, open);
+            for _ in 0..STEPS_PER_CANDLE {
+                let z = standard_normal(&mut rng);
+                let sigma = current.volatility;
+                price *= ((current.drift - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z).exp();
+                high = high.max(price);
+                low = low.min(price);
+            }
+            // 캔들 내부 꼬리: 경로 밖 극값을 변동성 비례로 약간 확장
+            let wick = current.volatility * (dt * STEPS_PER_CANDLE as f64).sqrt();
+            high *= 1.0 + wick * rng.gen::<f64>() * 0.5;
+            low *= 1.0 - wick * rng.gen::<f64>() * 0.5;
+
+            // 거래량: 국면 배수 × 가격 변동 크기 × 로그정규 잡음
+            let move_size = ((price / open).ln().abs() / wick.max(f64::EPSILON)).min(5.0);
+            let noise = (0.4 * standard_normal(&mut rng)).exp();
+            let volume = cfg.base_volume * current.volume_factor * (0.5 + move_size) * noise;
+
+            candles.push(MarketData {
+                symbol: cfg.symbol.clone(),
+                timestamp: Timestamp::from_millis(cfg.start_time + i as i64 * cfg.interval_ms),
+                open: to_decimal(open),
+                high: to_decimal(high),
+                low: to_decimal(low),
+                close: to_decimal(price),
+                volume: to_decimal(volume),
+            });
+            labels.push(current.name.clone());
+        }
+
+        (candles, labels)
+    }
+
+    pub fn generate(&self) -> Vec<MarketData> {
+        self.generate_with_regimes().0
+    }
+
+    /// 백테스트 CSV 형식(symbol,timestamp,open,high,low,close,volume)으로 저장
+    pub fn write_csv(&self, path: &Path) -> Result<usize, TradingError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = csv::Writer::from_path(path).map_err(|e| TradingError::IoError(e.into()))?;
+        writer.write_record(["symbol", "timestamp", "open", "high", "low", "close", "volume"])
+            .map_err(|e| TradingError::IoError(e.into()))?;
+
+        let candles = self.generate();
+        for c in &candles {
+            writer.write_record([
+                c.symbol.clone(),
+                c.timestamp.as_millis().to_string(),
+                format!("{:.2}", c.open),
+                format!("{:.2}", c.high),
+                format!("{:.2}", c.low),
+                format!("{:.2}", c.close),
+                format!("{:.4}", c.volume),
+            ]).map_err(|e| TradingError::IoError(e.into()))?;
+        }
+        writer.flush()?;
+        Ok(candles.len())
+    }
+}
+
+/// 데이터 파일이 없으면 종료 시각이 현재인 최근 `days`일치 합성 1분봉 생성 (신규 사용자용)
+/// 파일을 새로 만들었으면 true
+pub fn ensure_data_file(path: &Path, symbol: &str, days: i64, seed: u64) -> Result<bool, TradingError> {
+    if path.exists() {
+        return Ok(false);
+    }
+
+    let interval_ms = 60_000;
+    let candles = (days.max(1) * 24 * 60) as usize;
+    let end = chrono::Utc::now().timestamp_millis() / interval_ms * interval_ms;
+    let start = end - candles as i64 * interval_ms;
+    let config = SyntheticDataConfig::new(symbol, start, interval_ms, candles).seed(seed);
+    SyntheticDataGenerator::new(config)?.write_csv(path)?;
+    Ok(true)
+}
+
+// Box-Muller 변환
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::Decimal;
+
+    #[test]
+    fn test_generation_is_deterministic_and_consistent() {
+        let config = SyntheticDataConfig::new("BTCUSDT", 1_704_067_200_000, 60_000, 5_000).seed(7);
+        let generator = SyntheticDataGenerator::new(config.clone()).unwrap();
+        let (candles, regimes) = generator.generate_with_regimes();
+
+        assert_eq!(candles.len(), 5_000);
+        let closes = |data: &[MarketData]| data.iter().map(|c| c.close).collect::<Vec<_>>();
+        assert_eq!(closes(&candles), closes(&SyntheticDataGenerator::new(config.clone()).unwrap().generate()));
+        assert_ne!(closes(&candles), closes(&SyntheticDataGenerator::new(config.seed(8)).unwrap().generate()));
+
+        for pair in candles.windows(2) {
+            assert_eq!(pair[1].timestamp - pair[0].timestamp, 60_000);
+            assert_eq!(pair[1].open, pair[0].close);
+        }
+        assert!(candles.iter().all(|c| c.low <= c.open.min(c.close) && c.high >= c.open.max(c.close) && c.low > Decimal::ZERO && c.volume > Decimal::ZERO));
+
+        // 5000캔들이면 국면이 한 번 이상 바뀜
+        let mut names: Vec<&String> = regimes.iter().collect();
+        names.dedup();
+        assert!(names.len() > 1);
+
+        assert!(SyntheticDataGenerator::new(SyntheticDataConfig::new("BTCUSDT", 0, 60_000, 10).regimes(vec![])).is_err());
+    }
+}
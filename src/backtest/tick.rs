@@ -0,0 +1,145 @@
+//! 틱(체결) 단위 백테스트 데이터
+//!
+//! 체결 한 건을 시가=고가=저가=종가인 점 캔들로 바꿔 엔진에 흘려보내면 대기 주문은 실제 체결 순서대로
+//! 한 가격씩 평가된다. 캔들 안의 고가/저가 순서를 알 수 없어 생기는 체결 왜곡(같은 봉에서 손절과 익절이
+//! 모두 닿는 경우 등)이 없어지고, 체결 수량이 거래량 비례 부분 체결 한도가 된다.
+
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::Decimal;
+use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
+
+/// 체결 틱 한 건
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeTick {
+    pub symbol: String,
+    pub timestamp: Timestamp,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+impl TradeTick {
+    /// 점 캔들 (거래량 = 체결 수량)
+    pub fn to_market_data(&self) -> MarketData {
+        MarketData {
+            symbol: self.symbol.clone(),
+            timestamp: self.timestamp,
+            open: self.price,
+            high: self.price,
+            low: self.price,
+            close: self.price,
+            volume: self.quantity,
+        }
+    }
+}
+
+pub trait TickDataProvider {
+    /// [start, end] 구간 틱을 시간순으로 반환
+    fn load_ticks(
+        &self,
+        symbol: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<TradeTick>, TradingError>;
+}
+
+/// 단일 심볼 체결 CSV (`timestamp,price,quantity` 헤더, 그 외 컬럼은 무시)
+pub struct CsvTickProvider {
+    path: PathBuf,
+    delimiter: u8,
+}
+
+impl CsvTickProvider {
+    pub fn new(path: PathBuf, delimiter: char) -> Result<Self, TradingError> {
+        Ok(Self { path, delimiter: delimiter as u8 })
+    }
+}
+
+impl TickDataProvider for CsvTickProvider {
+    fn load_ticks(
+        &self,
+        symbol: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<TradeTick>, TradingError> {
+        let mut rdr = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .from_path(&self.path)
+            .map_err(|e| TradingError::IoError(e.into()))?;
+
+        let (start, end) = (Timestamp::from(start_time), Timestamp::from(end_time));
+        let mut ticks = Vec::new();
+        for rec in rdr.deserialize() {
+            let row: TickRow = rec.map_err(|e| TradingError::ParseError(e.to_string()))?;
+            if row.timestamp >= start && row.timestamp <= end {
+                ticks.push(TradeTick { symbol: symbol.to_string(), timestamp: row.timestamp, price: row.price, quantity: row.quantity });
+            }
+        }
+        // 같은 시각 체결은 파일 순서 유지
+        ticks.sort_by_key(|t| t.timestamp);
+        Ok(ticks)
+    }
+}
+
+#[derive(Deserialize)]
+struct TickRow {
+    // epoch ms 또는 RFC3339
+    timestamp: Timestamp,
+    price: Decimal,
+    quantity: Decimal,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use chrono::TimeZone;
+    use crate::backtest::engine::BacktestEngine;
+    use crate::models::order::{Order, OrderSide, OrderType};
+    use crate::strategies::Strategy;
+
+    // 첫 캔들에서 99 지정가 매수 1건, 받은 캔들 기록
+    struct LimitOnce {
+        seen: Arc<Mutex<Vec<MarketData>>>,
+        pending: Vec<Order>,
+    }
+
+    impl Strategy for LimitOnce {
+        fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+            let mut seen = self.seen.lock().unwrap();
+            if seen.is_empty() {
+                self.pending.push(Order::new(&market_data.symbol, OrderSide::Buy, OrderType::Limit, dec!(1), dec!(99)));
+            }
+            seen.push(market_data);
+            Ok(())
+        }
+        fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> { Ok(std::mem::take(&mut self.pending)) }
+        fn name(&self) -> &str { "limit-once" }
+        fn description(&self) -> &str { "" }
+    }
+
+    #[tokio::test]
+    async fn test_tick_replay_fills_at_tick_price() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let tick = |ms: i64, price: Decimal| TradeTick { symbol: "BTCUSDT".into(), timestamp: Timestamp::from_millis(start.timestamp_millis() + ms), price, quantity: dec!(2) };
+        let mut engine = BacktestEngine::new("tick".into(), String::new(), start, start + chrono::Duration::minutes(5), HashMap::from([("USDT".to_string(), 1_000.0)]), 0.0, 0.0);
+        engine.add_tick_data("BTCUSDT", vec![tick(0, dec!(100)), tick(30_000, dec!(100.5)), tick(60_000, dec!(98.9)), tick(90_000, dec!(101)), tick(120_000, dec!(101))]);
+        engine.set_tick_bar_interval(60_000);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        engine.add_strategy(Box::new(LimitOnce { seen: seen.clone(), pending: Vec::new() })).unwrap();
+
+        let result = engine.run().await.unwrap();
+        // 전략은 완성된 1분봉 2개만 받고, 주문은 다음 구간 첫 틱(98.9)에서 즉시 체결
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!((seen[0].open, seen[0].high, seen[0].close, seen[0].volume), (dec!(100), dec!(100.5), dec!(100.5), dec!(4)));
+        assert_eq!(result.trades.len(), 1);
+        assert_eq!(result.trades[0].price, dec!(98.9));
+    }
+}
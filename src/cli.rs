@@ -0,0 +1,359 @@
+//! 명령줄 인터페이스 (clap)
+//!
+//! 하위 명령이 없으면 `run` 과 같이 트레이딩 루프를 시작한다. 백테스트/데이터/주문 기록 명령은 로컬 파일로 동작하고,
+//! `orders list|cancel`, `positions`, `strategies` 는 실행 중인 인스턴스의 HTTP API(`--api`)를 호출한다.
+
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
+
+use crate::models::order::Order;
+use crate::models::position::Position;
+
+#[derive(Debug, Parser)]
+#[command(name = "xquant", version, about = "자동 트레이딩 시스템")]
+pub struct Cli {
+  /// 실행 중인 인스턴스의 HTTP API 주소
+  #[arg(long, global = true, env = "XQUANT_API", default_value = "http://127.0.0.1:4000")]
+  pub api: String,
+  #[command(subcommand)]
+  pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+  /// 실거래/페이퍼/모의 트레이딩 실행 (기본)
+  Run,
+  /// 백테스트 실행
+  Backtest(BacktestArgs),
+  /// 설정된 거래소(exchange.kind)의 공개 klines 를 백테스트 CSV 로 저장
+  Download(DownloadArgs),
+  /// 시장 데이터 생성/다운로드
+  Data {
+    #[command(subcommand)]
+    command: DataCommand,
+  },
+  /// 미체결 주문 조회/취소, 주문 기록 내보내기/가져오기/세금 보고서
+  Orders {
+    #[command(subcommand)]
+    command: OrdersCommand,
+  },
+  /// 거래소 포지션 조회
+  Positions,
+  /// 전략 조회/활성화/비활성화
+  Strategies {
+    #[command(subcommand)]
+    command: StrategiesCommand,
+  },
+}
+
+#[derive(Debug, Args)]
+pub struct BacktestArgs {
+  /// 내장 시나리오(vwap, ma, rsi, pairs) 또는 시나리오 파일 경로(.toml, .yaml, .yml, .json)
+  #[arg(long, default_value = "vwap")]
+  pub scenario: String,
+  /// 보고서 저장 경로
+  #[arg(long)]
+  pub report: Option<PathBuf>,
+  /// 보고서 형식 html|json (생략 시 확장자로 판별)
+  #[arg(long)]
+  pub format: Option<String>,
+  #[command(flatten)]
+  pub monte_carlo: MonteCarloArgs,
+  #[command(subcommand)]
+  pub command: Option<BacktestCommand>,
+}
+
+#[derive(Debug, Args)]
+pub struct MonteCarloArgs {
+  /// 몬테카를로 반복 수 (지정 시 청산 순서 재표본 분석을 요약/보고서에 포함)
+  #[arg(long = "monte-carlo")]
+  pub iterations: Option<usize>,
+  /// shuffle|bootstrap
+  #[arg(long = "mc-method", default_value = "shuffle")]
+  pub method: String,
+  #[arg(long = "mc-seed", default_value_t = 42)]
+  pub seed: u64,
+  /// 청산별 수수료율 변동폭 (±, 예: 0.0005)
+  #[arg(long = "mc-fee-jitter", default_value_t = 0.0)]
+  pub fee_jitter: f64,
+  /// 청산별 추가 슬리피지 최대치 (예: 0.001)
+  #[arg(long = "mc-slippage-jitter", default_value_t = 0.0)]
+  pub slippage_jitter: f64,
+  /// 파산 기준 손실 비율 (0~1)
+  #[arg(long = "mc-ruin", default_value_t = 0.5)]
+  pub ruin_threshold: f64,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum BacktestCommand {
+  /// 2차원 파라미터 스윕 (히트맵용 JSON/CSV) - 축 생략 시 RSI 기간 x 과매도 기준점
+  Sweep(SweepArgs),
+  /// RSI 워크포워드 최적화
+  Optimize(OptimizeArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct SweepArgs {
+  /// sharpe|profit|max_drawdown
+  #[arg(long, default_value = "sharpe")]
+  pub metric: String,
+  /// 출력 파일 접두사 (PREFIX.json, PREFIX.csv)
+  #[arg(long, default_value = "sweep_rsi")]
+  pub out: String,
+  /// 열 축 NAME=START:END:STEP 또는 NAME=V1,V2,.. (`--scenario` 시나리오 파일 전략의 숫자 파라미터)
+  #[arg(long)]
+  pub x: Option<String>,
+  /// 행 축 (`--x` 와 같은 형식)
+  #[arg(long)]
+  pub y: Option<String>,
+  /// 파라미터를 바꿀 전략 순번 (시나리오 파일 `strategies` 인덱스)
+  #[arg(long, default_value_t = 0)]
+  pub strategy: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct OptimizeArgs {
+  /// 전체 기간 (일)
+  #[arg(long, default_value_t = 30)]
+  pub days: i64,
+  /// 최적화 구간 (일)
+  #[arg(long, default_value_t = 14)]
+  pub is_days: i64,
+  /// 검증 구간 (일)
+  #[arg(long, default_value_t = 7)]
+  pub oos_days: i64,
+  /// 창 이동 간격 (일, 생략 시 검증 구간 길이)
+  #[arg(long)]
+  pub step_days: Option<i64>,
+  /// 최적화 구간 시작을 고정
+  #[arg(long)]
+  pub anchored: bool,
+  /// sharpe|profit|max_drawdown
+  #[arg(long, default_value = "sharpe")]
+  pub metric: String,
+  /// grid|random
+  #[arg(long, default_value = "grid")]
+  pub search: String,
+  /// 무작위 탐색 표본 수
+  #[arg(long, default_value_t = 10)]
+  pub samples: usize,
+  /// 무작위 탐색 시드
+  #[arg(long, default_value_t = 42)]
+  pub seed: u64,
+  /// 출력 파일 접두사 (PREFIX.json, PREFIX.csv)
+  #[arg(long, default_value = "walk_forward_rsi")]
+  pub out: String,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DataCommand {
+  /// 합성 데이터 생성 (국면 전환 GBM + 거래량 모델, 같은 시드면 같은 데이터)
+  Generate(GenerateArgs),
+  /// 거래소 klines 다운로드 (`download` 과 같음)
+  Download(DownloadArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+  #[arg(long, default_value = "BTCUSDT")]
+  pub symbol: String,
+  #[arg(long, default_value_t = 30.0)]
+  pub days: f64,
+  #[arg(long, default_value = "1m")]
+  pub interval: String,
+  /// 시작 가격
+  #[arg(long)]
+  pub price: Option<f64>,
+  #[arg(long)]
+  pub seed: Option<u64>,
+  /// 첫 캔들 시각 (epoch ms, 생략 시 현재 시각에 끝나도록 배치)
+  #[arg(long)]
+  pub start: Option<i64>,
+  /// 저장 경로 (기본 ./data/SYMBOL-INTERVAL.csv)
+  #[arg(long)]
+  pub out: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DownloadArgs {
+  #[arg(long, default_value = "BTCUSDT")]
+  pub symbol: String,
+  #[arg(long, default_value = "1m")]
+  pub interval: String,
+  /// 최근 N 일 (`--from` 이 없을 때)
+  #[arg(long, default_value_t = 30.0)]
+  pub days: f64,
+  /// 시작 시각 (epoch ms 또는 RFC3339)
+  #[arg(long)]
+  pub from: Option<String>,
+  /// 종료 시각 (epoch ms 또는 RFC3339, 생략 시 현재)
+  #[arg(long)]
+  pub to: Option<String>,
+  /// 요청당 캔들 수
+  #[arg(long)]
+  pub page_size: Option<usize>,
+  /// 저장 경로 (기본 ./data/SYMBOL-INTERVAL.csv)
+  #[arg(long)]
+  pub out: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum OrdersCommand {
+  /// 미체결 주문 조회 (실행 중인 인스턴스)
+  List,
+  /// 주문 취소 (실행 중인 인스턴스)
+  Cancel {
+    id: String,
+  },
+  /// 주문/체결 기록 내보내기
+  Export {
+    #[command(flatten)]
+    store: StoreArg,
+    /// csv|json (생략 시 출력 파일 확장자, 기본 csv)
+    #[arg(long)]
+    format: Option<String>,
+    /// 저장 경로 (생략 시 표준 출력)
+    #[arg(long)]
+    out: Option<String>,
+    /// 주문 대신 체결 내보내기
+    #[arg(long)]
+    fills: bool,
+  },
+  /// 거래소 체결 내역 가져오기 및 대사
+  Import {
+    file: String,
+    #[command(flatten)]
+    store: StoreArg,
+    /// csv|json (생략 시 확장자로 판별)
+    #[arg(long)]
+    format: Option<String>,
+    /// 불일치 보고서 저장 경로
+    #[arg(long)]
+    report: Option<String>,
+    /// 가져온 체결을 로컬 체결 기록에 병합
+    #[arg(long)]
+    merge: bool,
+  },
+  /// 실현 손익(세금) 보고서
+  TaxReport {
+    #[command(flatten)]
+    store: StoreArg,
+    /// fifo|lifo|hifo
+    #[arg(long)]
+    method: Option<String>,
+    #[arg(long)]
+    year: Option<i32>,
+    /// 처분 내역 대신 연도/자산별 요약
+    #[arg(long)]
+    summary: bool,
+    /// 저장 경로 (생략 시 표준 출력)
+    #[arg(long)]
+    out: Option<String>,
+  },
+}
+
+#[derive(Debug, Args)]
+pub struct StoreArg {
+  /// 주문 스냅샷 파일 (기본 order_snapshot_path 설정값)
+  #[arg(long)]
+  pub store: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StrategiesCommand {
+  /// 전략 목록과 활성 여부
+  List,
+  /// 전략 활성화
+  Enable {
+    name: String,
+  },
+  /// 전략 비활성화
+  Disable {
+    name: String,
+  },
+}
+
+/// 실행 중인 인스턴스의 HTTP API 클라이언트
+pub struct ApiClient {
+  base: String,
+  http: reqwest::Client,
+}
+
+impl ApiClient {
+  pub fn new(base: &str) -> Self {
+    ApiClient { base: base.trim_end_matches('/').to_string(), http: reqwest::Client::new() }
+  }
+
+  async fn send(&self, request: reqwest::RequestBuilder) -> Result<serde_json::Value, anyhow::Error> {
+    let response = request.send().await.map_err(|e| anyhow::anyhow!("{} 에 연결할 수 없습니다 (실행 중인지 확인): {}", self.base, e))?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !status.is_success() {
+      anyhow::bail!("API 오류 {}: {}", status, body);
+    }
+    Ok(if body.is_empty() { serde_json::Value::Null } else { serde_json::from_str(&body)? })
+  }
+
+  async fn get(&self, path: &str) -> Result<serde_json::Value, anyhow::Error> {
+    self.send(self.http.get(format!("{}{}", self.base, path))).await
+  }
+
+  async fn post(&self, path: &str, body: serde_json::Value) -> Result<serde_json::Value, anyhow::Error> {
+    self.send(self.http.post(format!("{}{}", self.base, path)).json(&body)).await
+  }
+
+  async fn delete(&self, path: &str) -> Result<serde_json::Value, anyhow::Error> {
+    self.send(self.http.delete(format!("{}{}", self.base, path))).await
+  }
+}
+
+pub async fn list_orders(api: &ApiClient) -> Result<(), anyhow::Error> {
+  let orders: Vec<Order> = serde_json::from_value(api.get("/orders").await?)?;
+  println!("{:<40} {:<12} {:<5} {:<10} {:>14} {:>14} EXCHANGE", "ID", "SYMBOL", "SIDE", "TYPE", "QTY", "PRICE");
+  for order in &orders {
+    println!(
+      "{:<40} {:<12} {:<5} {:<10} {:>14} {:>14} {}",
+      order.id.0, order.symbol, format!("{:?}", order.side), format!("{:?}", order.order_type),
+      order.quantity, order.price, order.exchange.as_deref().unwrap_or("-"),
+    );
+  }
+  println!("미체결 주문 {}건", orders.len());
+  Ok(())
+}
+
+pub async fn cancel_order(api: &ApiClient, id: &str) -> Result<(), anyhow::Error> {
+  api.delete(&format!("/orders/{}", id)).await?;
+  println!("주문 취소 요청 완료: {}", id);
+  Ok(())
+}
+
+pub async fn list_positions(api: &ApiClient) -> Result<(), anyhow::Error> {
+  let positions: Vec<Position> = serde_json::from_value(api.get("/positions").await?)?;
+  println!("{:<12} {:>14} {:>14} {:>14} {:>14}", "SYMBOL", "QTY", "ENTRY", "MARK", "UPNL");
+  for p in &positions {
+    println!("{:<12} {:>14} {:>14.4} {:>14.4} {:>14.4}", p.symbol, p.quantity, p.entry_price, p.current_price, p.unrealized_pnl);
+  }
+  if positions.is_empty() {
+    println!("포지션 없음");
+  }
+  Ok(())
+}
+
+pub async fn run_strategies(api: &ApiClient, command: StrategiesCommand) -> Result<(), anyhow::Error> {
+  let (name, active) = match command {
+    StrategiesCommand::List => {
+      let strategies: Vec<(String, bool)> = serde_json::from_value(api.get("/strategies").await?)?;
+      for (name, active) in &strategies {
+        println!("{:<40} {}", name, if *active { "active" } else { "inactive" });
+      }
+      println!("전략 {}개", strategies.len());
+      return Ok(());
+    }
+    StrategiesCommand::Enable { name } => (name, true),
+    StrategiesCommand::Disable { name } => (name, false),
+  };
+  let status = api.post(&format!("/strategies/{}/toggle", name), serde_json::json!({ "active": active })).await?;
+  println!("{}: {}", name, if status["active"].as_bool() == Some(true) { "active" } else { "inactive" });
+  Ok(())
+}
@@ -6,83 +6,1833 @@
 **/
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use crate::core::dead_man_switch::SafetyAction;
 use crate::error::TradingError;
+use crate::exchange::fill_model::FillModel;
+use crate::market_data::aggregator::parse_timeframe;
+use crate::models::order::OrderSide;
+use crate::order_core::routing::RoutingRule;
+use crate::signals::entry_exits::EntryExitsConfig;
+use crate::signals::signal_aggregator::AggregationConfig;
+use crate::strategies::ExecutionMode;
+
+/// Config file read on startup and by hot reload
+pub const CONFIG_PATH: &str = "config.json";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
     pub exchange: ExchangeConfig,
+    /// Additional venues traded alongside `exchange`, addressed by `name` (`Order::exchange`)
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeConfig>,
     pub logging: LoggingConfig,
     pub prediction_api: PredictionApiConfig,
     #[serde(default)]
-    pub futures: Option<FuturesDefaults>,
+    pub futures: Option<FuturesDefaults>,
+    #[serde(default)]
+    pub strategy_guard: StrategyGuardConfig,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// Alert channels (Telegram, Discord, HTTP webhook, email)
+    #[serde(default)]
+    pub notifications: Vec<NotificationConfig>,
+    #[serde(default)]
+    pub allocation: AllocationConfig,
+    #[serde(default)]
+    pub reporting: ReportingConfig,
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub dead_man_switch: DeadManSwitchConfig,
+    #[serde(default)]
+    pub routing: RoutingConfig,
+    #[serde(default)]
+    pub submission_queue: SubmissionQueueConfig,
+    #[serde(default)]
+    pub external_data: ExternalDataConfig,
+    /// Periodic funding rate / open interest snapshots for perpetual symbols
+    #[serde(default)]
+    pub funding_data: FundingDataConfig,
+    /// FIX market data session; when set it is added next to the WebSocket provider
+    #[serde(default)]
+    pub fix_market_data: Option<FixMarketDataConfig>,
+    #[serde(default)]
+    pub degraded_mode: DegradedModeConfig,
+    #[serde(default)]
+    pub portfolio: PortfolioConfig,
+    /// Portfolio-level exposure limits checked before every order
+    #[serde(default)]
+    pub risk_limits: RiskLimitsConfig,
+    /// Daily realized loss limit with trading-day rollover
+    #[serde(default)]
+    pub daily_loss: DailyLossConfig,
+    /// Reduce-only / positionSide routing from tracked positions
+    #[serde(default)]
+    pub position_routing: PositionRoutingConfig,
+    /// Futures margin ratio / liquidation distance alerts with optional de-risking
+    #[serde(default)]
+    pub margin_monitor: MarginMonitorConfig,
+    #[serde(default)]
+    pub strategy_runtime: StrategyRuntimeConfig,
+    #[serde(default)]
+    pub candle_aggregation: CandleAggregationConfig,
+    /// Streamed candles/ticks appended to daily CSV files for building backtest datasets
+    #[serde(default)]
+    pub market_data_recorder: MarketDataRecorderConfig,
+    #[serde(default)]
+    pub shutdown: ShutdownConfig,
+    #[serde(default)]
+    pub hot_reload: HotReloadConfig,
+    #[serde(default)]
+    pub backtest_jobs: BacktestJobsConfig,
+    /// Strategies deployed on startup
+    #[serde(default = "default_strategies")]
+    pub strategies: Vec<StrategyDeployment>,
+    /// JSON snapshot of the order repository, used by `orders export/import`
+    #[serde(default)]
+    pub order_snapshot_path: Option<String>,
+    /// Durable order journal; when unset orders are kept in memory only
+    #[serde(default)]
+    pub order_store_path: Option<String>,
+    /// SQL order store (`sqlite://orders.db?mode=rwc` or `postgres://...`); takes the place of `order_store_path`
+    #[serde(default)]
+    pub order_store_url: Option<String>,
+    /// JSON file holding strategy specs and runtime progress, restored on startup
+    #[serde(default)]
+    pub strategy_state_path: Option<String>,
+    /// SQLite database holding the same strategy snapshot; takes the place of `strategy_state_path`
+    #[serde(default)]
+    pub strategy_state_db: Option<String>,
+    /// JSON file holding conditional (trigger) orders, restored on startup
+    #[serde(default)]
+    pub conditional_orders_path: Option<String>,
+    /// JSON Lines trade journal (every fill with strategy, signal and slippage); memory only when unset
+    #[serde(default)]
+    pub trade_journal_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    pub api_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExchangeConfig {
+    pub name: String,
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    /// Passphrase chosen when the API key was created (OKX only)
+    #[serde(default)]
+    pub api_passphrase: Option<String>,
+    pub base_url: Option<String>,
+    pub use_mock: bool,
+    /// `live`, `mock` or `paper`; overrides `use_mock` when set
+    #[serde(default)]
+    pub mode: Option<ExchangeMode>,
+    /// Live connector used when `use_mock` is false (paper mode reads its public market data)
+    #[serde(default)]
+    pub kind: ExchangeKind,
+    #[serde(default)]
+    pub user_stream: UserStreamConfig,
+    /// Fill simulation for the mock and paper exchanges (latency, partial fills, maker/taker fees)
+    #[serde(default)]
+    pub fill_model: FillModel,
+    #[serde(default)]
+    pub paper: PaperConfig,
+    /// FIX order entry session, required when `kind` is `fix`
+    #[serde(default)]
+    pub fix: Option<FixOrderEntryConfig>,
+}
+
+impl ExchangeConfig {
+    /// Effective mode: `mode` if set, otherwise derived from `use_mock`
+    pub fn mode(&self) -> ExchangeMode {
+        self.mode.unwrap_or(if self.use_mock { ExchangeMode::Mock } else { ExchangeMode::Live })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match (&self.fix, self.kind) {
+            (Some(fix), _) => fix.validate(),
+            (None, ExchangeKind::Fix) if self.mode() == ExchangeMode::Live => Err("kind fix requires a `fix` session".to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeMode {
+    /// Real orders through the `kind` connector
+    Live,
+    /// Seeded random prices, simulated fills
+    Mock,
+    /// Live WebSocket prices, simulated fills (no API keys needed)
+    Paper,
+}
+
+/// Paper trading account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaperConfig {
+    /// Starting balances per asset
+    #[serde(default = "default_paper_balances")]
+    pub initial_balances: HashMap<String, f64>,
+    /// Symbols whose live ticker drives the simulated fills
+    #[serde(default = "default_paper_symbols")]
+    pub symbols: Vec<String>,
+}
+
+fn default_paper_balances() -> HashMap<String, f64> { HashMap::from([("USDT".to_string(), 10_000.0)]) }
+fn default_paper_symbols() -> Vec<String> { vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()] }
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        PaperConfig { initial_balances: default_paper_balances(), symbols: default_paper_symbols() }
+    }
+}
+
+/// Push order/account updates over the Binance user-data WebSocket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserStreamConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// WebSocket base; defaults per exchange kind
+    #[serde(default)]
+    pub ws_url: Option<String>,
+    /// listenKey keepalive period (Binance expires keys after 60 minutes)
+    #[serde(default = "default_listen_key_keepalive_ms")]
+    pub keepalive_interval_ms: u64,
+    /// REST order status polling cadence while the stream is active (safety net for missed events)
+    #[serde(default = "default_fallback_poll_interval_ms")]
+    pub fallback_poll_interval_ms: u64,
+}
+
+fn default_listen_key_keepalive_ms() -> u64 { 1_800_000 }
+fn default_fallback_poll_interval_ms() -> u64 { 15_000 }
+
+impl Default for UserStreamConfig {
+    fn default() -> Self {
+        UserStreamConfig {
+            enabled: false,
+            ws_url: None,
+            keepalive_interval_ms: default_listen_key_keepalive_ms(),
+            fallback_poll_interval_ms: default_fallback_poll_interval_ms(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeKind {
+    /// USDT-M futures (fapi)
+    #[default]
+    BinanceFutures,
+    /// Spot (api/v3)
+    BinanceSpot,
+    /// Bybit USDT perpetuals (V5 API, category=linear)
+    Bybit,
+    /// OKX perpetual swaps (V5 API, instType=SWAP)
+    Okx,
+    /// Kraken spot
+    Kraken,
+    /// FIX order entry; session parameters in `exchange.fix`
+    Fix,
+}
+
+impl ExchangeKind {
+    pub fn default_base_url(&self) -> &'static str {
+        match self {
+            ExchangeKind::BinanceFutures => "https://fapi.binance.com",
+            ExchangeKind::BinanceSpot => "https://api.binance.com",
+            ExchangeKind::Bybit => "https://api.bybit.com",
+            ExchangeKind::Okx => "https://www.okx.com",
+            ExchangeKind::Kraken => "https://api.kraken.com",
+            ExchangeKind::Fix => "",
+        }
+    }
+
+    pub fn default_stream_url(&self) -> &'static str {
+        match self {
+            ExchangeKind::BinanceFutures => "wss://fstream.binance.com/ws",
+            ExchangeKind::BinanceSpot => "wss://stream.binance.com:9443/ws",
+            ExchangeKind::Bybit => "wss://stream.bybit.com/v5/public/linear",
+            ExchangeKind::Okx => "wss://ws.okx.com:8443/ws/v5/public",
+            ExchangeKind::Kraken => "wss://ws.kraken.com/v2",
+            ExchangeKind::Fix => "",
+        }
+    }
+
+    /// Perpetual futures venue (leverage, margin and position mode apply)
+    pub fn is_futures(&self) -> bool {
+        matches!(self, ExchangeKind::BinanceFutures | ExchangeKind::Bybit | ExchangeKind::Okx)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub file_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictionApiConfig {
+    pub base_url: String,
+    pub timeout_ms: Option<u64>,
+    /// Per-strategy microstructure features attached to signal requests
+    #[serde(default)]
+    pub features: HashMap<String, MicrostructureFeatureConfig>,
+    /// Realized signal outcomes posted back to `/feedback`
+    #[serde(default)]
+    pub feedback: FeedbackConfig,
+    /// Signals polled from `/signals` and recorded as the `prediction.<SYMBOL>.signal` / `.confidence`
+    /// external series read by prediction strategies
+    #[serde(default)]
+    pub signal_feeds: Vec<PredictionSignalFeed>,
+}
+
+impl PredictionApiConfig {
+    /// Feature settings for a strategy, if enabled
+    pub fn feature_config(&self, strategy: &str) -> Option<&MicrostructureFeatureConfig> {
+        self.features.get(strategy).filter(|c| c.enabled)
+    }
+}
+
+/// One symbol's signal stream from the prediction service
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PredictionSignalFeed {
+    pub symbol: String,
+    #[serde(default = "default_feed_timeframe")]
+    pub timeframe: String,
+    /// Model strategy name passed to `/signals`
+    #[serde(default = "default_feed_strategy")]
+    pub strategy: String,
+    #[serde(default = "default_feed_poll_ms")]
+    pub poll_interval_ms: u64,
+}
+
+fn default_feed_timeframe() -> String { "1h".to_string() }
+fn default_feed_strategy() -> String { "trend_following".to_string() }
+fn default_feed_poll_ms() -> u64 { 60_000 }
+
+/// Execution feedback loop to the prediction service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Strategies whose outcomes are reported; empty reports every strategy
+    #[serde(default)]
+    pub strategies: Vec<String>,
+    #[serde(default = "default_feedback_interval_ms")]
+    pub interval_ms: u64,
+    /// Outcomes still open after this long are sent as-is
+    #[serde(default = "default_feedback_max_pending_ms")]
+    pub max_pending_ms: i64,
+}
+
+fn default_feedback_interval_ms() -> u64 { 60_000 }
+fn default_feedback_max_pending_ms() -> i64 { 3_600_000 }
+
+impl FeedbackConfig {
+    pub fn reports(&self, strategy: &str) -> bool {
+        self.enabled && (self.strategies.is_empty() || self.strategies.iter().any(|s| s == strategy))
+    }
+}
+
+impl Default for FeedbackConfig {
+    fn default() -> Self {
+        FeedbackConfig {
+            enabled: false,
+            strategies: Vec::new(),
+            interval_ms: default_feedback_interval_ms(),
+            max_pending_ms: default_feedback_max_pending_ms(),
+        }
+    }
+}
+
+/// Order book / trade features computed locally for the prediction service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MicrostructureFeatureConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Number of book levels included in depth features
+    #[serde(default = "default_depth_levels")]
+    pub depth_levels: usize,
+    /// Number of mid-price samples used for recent volatility
+    #[serde(default = "default_volatility_window")]
+    pub volatility_window: usize,
+    /// Trades within this window (ms) feed the trade imbalance
+    #[serde(default = "default_trade_window_ms")]
+    pub trade_window_ms: i64,
+}
+
+fn default_depth_levels() -> usize { 5 }
+fn default_volatility_window() -> usize { 50 }
+fn default_trade_window_ms() -> i64 { 60_000 }
+
+impl Default for MicrostructureFeatureConfig {
+    fn default() -> Self {
+        MicrostructureFeatureConfig {
+            enabled: false,
+            depth_levels: default_depth_levels(),
+            volatility_window: default_volatility_window(),
+            trade_window_ms: default_trade_window_ms(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FuturesDefaults {
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    #[serde(default = "default_leverage")] 
+    pub leverage: u32,
+    #[serde(default)]
+    pub isolated: bool,
+    #[serde(default)]
+    pub hedge: bool,
+}
+
+fn default_leverage() -> u32 { 20 }
+
+/// Per-strategy guardrails: auto-deactivate after losses or drawdown
+///
+/// Off by default; when disabled, fills are still tracked but never deactivate a strategy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyGuardConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Consecutive losing trades before deactivation (0 disables the check)
+    #[serde(default = "default_max_consecutive_losses")]
+    pub max_consecutive_losses: u32,
+    /// Attributed drawdown (quote currency) before deactivation (0 disables the check)
+    #[serde(default = "default_max_strategy_drawdown")]
+    pub max_drawdown: f64,
+    /// Minimum time before a tripped strategy may be re-enabled
+    #[serde(default = "default_cooloff_ms")]
+    pub cooloff_ms: i64,
+}
+
+/// Drawdown-aware capital allocation across strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationConfig {
+    /// Total capital (quote currency) distributed between strategies
+    #[serde(default = "default_total_capital")]
+    pub total_capital: f64,
+    /// Lower/upper bound of a single strategy's share (0.0 ~ 1.0)
+    #[serde(default = "default_min_weight")]
+    pub min_weight: f64,
+    #[serde(default = "default_max_weight")]
+    pub max_weight: f64,
+    /// Rebalance schedule (0 disables periodic rebalancing)
+    #[serde(default = "default_rebalance_interval_ms")]
+    pub rebalance_interval_ms: i64,
+    /// Number of recent realized trades used to score a strategy
+    #[serde(default = "default_allocation_lookback")]
+    pub lookback: usize,
+    /// How strongly drawdown (relative to budget) cuts allocation
+    #[serde(default = "default_drawdown_penalty")]
+    pub drawdown_penalty: f64,
+    /// What happens to strategy orders that would exceed the strategy's remaining budget
+    #[serde(default)]
+    pub enforcement: BudgetEnforcement,
+}
+
+/// Handling of orders that exceed a strategy's capital budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetEnforcement {
+    /// Budgets are tracked but never enforced
+    #[default]
+    Off,
+    /// Drop orders that would exceed the remaining budget
+    Block,
+    /// Shrink orders to fit the remaining budget
+    Scale,
+}
+
+fn default_total_capital() -> f64 { 10000.0 }
+fn default_min_weight() -> f64 { 0.05 }
+fn default_max_weight() -> f64 { 0.5 }
+fn default_rebalance_interval_ms() -> i64 { 86_400_000 }
+fn default_allocation_lookback() -> usize { 50 }
+fn default_drawdown_penalty() -> f64 { 1.0 }
+
+impl Default for AllocationConfig {
+    fn default() -> Self {
+        AllocationConfig {
+            total_capital: default_total_capital(),
+            min_weight: default_min_weight(),
+            max_weight: default_max_weight(),
+            rebalance_interval_ms: default_rebalance_interval_ms(),
+            lookback: default_allocation_lookback(),
+            drawdown_penalty: default_drawdown_penalty(),
+            enforcement: BudgetEnforcement::default(),
+        }
+    }
+}
+
+/// Strategy deployed automatically on startup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StrategyDeployment {
+    /// Disabled entries are validated but not deployed
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub spec: StrategySpec,
+    /// Protective exits enforced independently of the strategy's own signals
+    #[serde(default)]
+    pub protection: Option<ProtectiveStopConfig>,
+    /// Stop-loss / take-profit attached to every entry order and registered as an OCO bracket once it fills
+    /// (indicator strategies only)
+    #[serde(default)]
+    pub exits: Option<EntryExitsConfig>,
+    /// Closed-candle timeframe the strategy runs on (e.g. "1h"); ticks when unset
+    #[serde(default)]
+    pub timeframe: Option<String>,
+    /// Venue the strategy's orders go to (a name from `exchange`/`exchanges`); default exchange when unset
+    #[serde(default)]
+    pub exchange: Option<String>,
+    /// Run the strategy without sending orders; would-be orders get simulated fills against live prices
+    #[serde(default)]
+    pub shadow: bool,
+    /// Time windows the strategy runs in; always when unset
+    #[serde(default)]
+    pub schedule: Option<StrategySchedule>,
+}
+
+fn default_true() -> bool { true }
+
+/// Synthetic stop-loss overlay for a strategy's attributed positions (0 disables each limit)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProtectiveStopConfig {
+    /// Adverse move from the average entry price, in percent
+    #[serde(default)]
+    pub max_loss_pct: f64,
+    /// Maximum time a position may stay open
+    #[serde(default)]
+    pub max_holding_ms: i64,
+    /// Stop placed `atr_multiplier` ATRs away from the entry price
+    #[serde(default)]
+    pub atr_multiplier: f64,
+    #[serde(default = "default_protective_atr_period")]
+    pub atr_period: usize,
+}
+
+fn default_protective_atr_period() -> usize { 14 }
+
+impl Default for ProtectiveStopConfig {
+    fn default() -> Self {
+        ProtectiveStopConfig {
+            max_loss_pct: 0.0,
+            max_holding_ms: 0,
+            atr_multiplier: 0.0,
+            atr_period: default_protective_atr_period(),
+        }
+    }
+}
+
+impl ProtectiveStopConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_loss_pct < 0.0 || self.max_holding_ms < 0 || self.atr_multiplier < 0.0 {
+            return Err("protection limits must not be negative".to_string());
+        }
+        if self.atr_multiplier > 0.0 && self.atr_period == 0 {
+            return Err("protection atr_period must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// UTC time windows a strategy runs in; outside them it gets no updates and places no orders
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StrategySchedule {
+    /// Cron-like expressions (`minute hour day month weekday`) of the minutes the strategy may run,
+    /// e.g. `"* 14 * * *"` for 14:00-15:00; runs at any time when empty
+    #[serde(default)]
+    pub active: Vec<String>,
+    /// Expressions of minutes the strategy is paused, taking precedence over `active`
+    /// (e.g. `"* * * * sat,sun"` for weekends)
+    #[serde(default)]
+    pub blackout: Vec<String>,
+    /// Pause this many minutes either side of each funding timestamp (0 disables)
+    #[serde(default)]
+    pub funding_buffer_minutes: u32,
+    /// Funding period, aligned to 00:00 UTC
+    #[serde(default = "default_funding_interval_hours")]
+    pub funding_interval_hours: u32,
+    /// Cancel the strategy's open orders when a window closes
+    #[serde(default)]
+    pub cancel_on_close: bool,
+}
+
+fn default_funding_interval_hours() -> u32 { 8 }
+
+impl StrategySchedule {
+    pub fn validate(&self) -> Result<(), String> {
+        crate::core::strategy_scheduler::Schedule::compile(self).map(|_| ())
+    }
+}
+
+/// Typed strategy parameters, tagged by `"type"`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrategySpec {
+    MaCrossover { symbol: String, fast_period: usize, slow_period: usize },
+    Rsi { symbol: String, period: usize, oversold: f64, overbought: f64 },
+    Macd { symbol: String, fast_period: usize, slow_period: usize, signal_period: usize },
+    Bollinger { symbol: String, period: usize, std_dev: f64 },
+    Ichimoku { symbol: String, tenkan_period: usize, kijun_period: usize, senkou_b_period: usize },
+    /// `aggregation` replaces the fixed signal analyzer with weighted voting and veto rules
+    MultiIndicator { symbol: String, #[serde(default)] aggregation: Option<AggregationConfig> },
+    /// Combined specs only pass signal orders the `aggregation` of the signal leg's indicators agrees with
+    RsiTwap { symbol: String, period: usize, oversold: f64, overbought: f64, twap_minutes: u64, #[serde(default)] aggregation: Option<AggregationConfig> },
+    MacdVwap { symbol: String, fast_period: usize, slow_period: usize, signal_period: usize, participation_rate: f64, #[serde(default)] aggregation: Option<AggregationConfig> },
+    MaCrossoverIceberg { symbol: String, fast_period: usize, slow_period: usize, display_size: f64, #[serde(default)] aggregation: Option<AggregationConfig> },
+    /// `book_participation` caps each slice to that fraction of visible opposite-side depth
+    Vwap { symbol: String, side: OrderSide, quantity: f64, execution_interval_ms: i64, vwap_window: usize, #[serde(default)] execution_mode: ExecutionMode, #[serde(default)] book_participation: Option<f64> },
+    Twap { symbol: String, side: OrderSide, quantity: f64, execution_interval_ms: i64, slices: usize, #[serde(default)] execution_mode: ExecutionMode },
+    /// Front-loads slices by `urgency` (0 = even split) against expected impact; slippage tracked vs arrival price
+    ImplementationShortfall { symbol: String, side: OrderSide, quantity: f64, execution_interval_ms: i64, slices: usize, urgency: f64, #[serde(default = "default_is_volatility_bps")] volatility_bps: f64, #[serde(default = "default_is_impact_bps")] impact_bps: f64 },
+    /// Trades the z-score of the `symbol` / `pair_symbol` log price spread (hedge ratio fitted over `lookback` bars)
+    Pairs { symbol: String, pair_symbol: String, quantity: f64, lookback: usize, entry_z: f64, #[serde(default = "default_pairs_exit_z")] exit_z: f64 },
+    /// Collects perpetual funding above `entry_rate` per settlement; hedged with `spot_symbol` when set, unwound below `exit_rate`
+    FundingCarry { symbol: String, #[serde(default)] spot_symbol: Option<String>, quantity: f64, entry_rate: f64, #[serde(default)] exit_rate: f64 },
+    Iceberg { symbol: String, side: OrderSide, total_quantity: f64, limit_price: f64, display_quantity: f64, #[serde(default)] execution_mode: ExecutionMode, #[serde(default)] book_participation: Option<f64> },
+    TrailingStop { symbol: String, side: OrderSide, quantity: f64, trailing_delta: f64, #[serde(default)] activation_price: Option<f64> },
+    /// Entry signal on `entry_timeframe` candles, only taken when the `trend_timeframe` indicator agrees
+    MultiTimeframe { symbol: String, entry: IndicatorSpec, trend: IndicatorSpec, entry_timeframe: String, trend_timeframe: String },
+    /// Prediction service signals at or above `min_confidence`, only acted on when the TA `confirmation` agrees
+    PredictionTa { symbol: String, quantity: f64, #[serde(default = "default_prediction_min_confidence")] min_confidence: f64, #[serde(default)] confirmation: TaConfirmationConfig, #[serde(default)] aggregation: Option<AggregationConfig> },
+}
+
+fn default_is_volatility_bps() -> f64 { 20.0 }
+fn default_is_impact_bps() -> f64 { 10.0 }
+fn default_pairs_exit_z() -> f64 { 0.5 }
+fn default_prediction_min_confidence() -> f64 { 0.6 }
+
+/// Classical indicator checks a prediction signal must pass, e.g. buy only with RSI below 50 and price above the 200-EMA
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaConfirmationConfig {
+    #[serde(default = "default_confirmation_rsi_period")]
+    pub rsi_period: usize,
+    /// Buys need RSI below this; `None` skips the check
+    #[serde(default = "default_confirmation_rsi_mid")]
+    pub max_buy_rsi: Option<f64>,
+    /// Sells need RSI above this; `None` skips the check
+    #[serde(default = "default_confirmation_rsi_mid")]
+    pub min_sell_rsi: Option<f64>,
+    /// Buys need price above this EMA and sells below it; `None` skips the check
+    #[serde(default = "default_confirmation_ema_period")]
+    pub trend_ema_period: Option<usize>,
+}
+
+fn default_confirmation_rsi_period() -> usize { 14 }
+fn default_confirmation_rsi_mid() -> Option<f64> { Some(50.0) }
+fn default_confirmation_ema_period() -> Option<usize> { Some(200) }
+
+impl Default for TaConfirmationConfig {
+    fn default() -> Self {
+        TaConfirmationConfig {
+            rsi_period: default_confirmation_rsi_period(),
+            max_buy_rsi: default_confirmation_rsi_mid(),
+            min_sell_rsi: default_confirmation_rsi_mid(),
+            trend_ema_period: default_confirmation_ema_period(),
+        }
+    }
+}
+
+impl TaConfirmationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.rsi_period == 0 || self.trend_ema_period == Some(0) {
+            return Err("confirmation periods must be positive".to_string());
+        }
+        if [self.max_buy_rsi, self.min_sell_rsi].iter().flatten().any(|rsi| !(0.0..=100.0).contains(rsi)) {
+            return Err("confirmation RSI bounds must be in [0, 100]".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Single-indicator parameters used as one leg of a multi-timeframe strategy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "indicator", rename_all = "snake_case")]
+pub enum IndicatorSpec {
+    MaCrossover { fast_period: usize, slow_period: usize },
+    Rsi { period: usize, oversold: f64, overbought: f64 },
+    Macd { fast_period: usize, slow_period: usize, signal_period: usize },
+    Bollinger { period: usize, std_dev: f64 },
+    Ichimoku { tenkan_period: usize, kijun_period: usize, senkou_b_period: usize },
+}
+
+impl IndicatorSpec {
+    /// Equivalent single-timeframe strategy spec
+    pub fn to_spec(&self, symbol: &str) -> StrategySpec {
+        let symbol = symbol.to_string();
+        match *self {
+            IndicatorSpec::MaCrossover { fast_period, slow_period } => StrategySpec::MaCrossover { symbol, fast_period, slow_period },
+            IndicatorSpec::Rsi { period, oversold, overbought } => StrategySpec::Rsi { symbol, period, oversold, overbought },
+            IndicatorSpec::Macd { fast_period, slow_period, signal_period } => StrategySpec::Macd { symbol, fast_period, slow_period, signal_period },
+            IndicatorSpec::Bollinger { period, std_dev } => StrategySpec::Bollinger { symbol, period, std_dev },
+            IndicatorSpec::Ichimoku { tenkan_period, kijun_period, senkou_b_period } => StrategySpec::Ichimoku { symbol, tenkan_period, kijun_period, senkou_b_period },
+        }
+    }
+}
+
+impl StrategySpec {
+    pub fn symbol(&self) -> &str {
+        match self {
+            StrategySpec::MaCrossover { symbol, .. }
+            | StrategySpec::Rsi { symbol, .. }
+            | StrategySpec::Macd { symbol, .. }
+            | StrategySpec::Bollinger { symbol, .. }
+            | StrategySpec::Ichimoku { symbol, .. }
+            | StrategySpec::MultiIndicator { symbol, .. }
+            | StrategySpec::RsiTwap { symbol, .. }
+            | StrategySpec::MacdVwap { symbol, .. }
+            | StrategySpec::MaCrossoverIceberg { symbol, .. }
+            | StrategySpec::Vwap { symbol, .. }
+            | StrategySpec::Twap { symbol, .. }
+            | StrategySpec::ImplementationShortfall { symbol, .. }
+            | StrategySpec::Pairs { symbol, .. }
+            | StrategySpec::FundingCarry { symbol, .. }
+            | StrategySpec::Iceberg { symbol, .. }
+            | StrategySpec::TrailingStop { symbol, .. }
+            | StrategySpec::MultiTimeframe { symbol, .. }
+            | StrategySpec::PredictionTa { symbol, .. } => symbol,
+        }
+    }
+
+    /// Spec with the fields named in a live parameter update overlaid (keys the spec lacks, such as
+    /// position sizing, are skipped). `None` if the result no longer deserializes.
+    pub fn with_params(&self, params: &serde_json::Value) -> Option<StrategySpec> {
+        let mut value = serde_json::to_value(self).ok()?;
+        let (Some(fields), Some(params)) = (value.as_object_mut(), params.as_object()) else { return None };
+        for (key, param) in params {
+            if key == "type" || key == "symbol" {
+                continue;
+            }
+            if let Some(field) = fields.get_mut(key) {
+                *field = param.clone();
+            }
+        }
+        serde_json::from_value(value).ok()
+    }
+
+    /// Spec with one numeric parameter set, for optimizer sweeps. Integer fields (periods, slices)
+    /// take the rounded value; unknown or non-numeric fields are an error rather than ignored.
+    pub fn with_numeric_param(&self, name: &str, value: f64) -> Result<StrategySpec, String> {
+        let current = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        let param = match current.get(name) {
+            _ if name == "type" || name == "symbol" => return Err(format!("{} cannot be swept", name)),
+            Some(field) if field.is_u64() || field.is_i64() => serde_json::json!(value.round() as i64),
+            Some(field) if field.is_f64() => serde_json::json!(value),
+            Some(_) => return Err(format!("parameter {} is not numeric", name)),
+            None => return Err(format!("unknown parameter {} for this strategy", name)),
+        };
+        self.with_params(&serde_json::json!({ name: param }))
+            .ok_or_else(|| format!("{}={} is not a valid value", name, value))
+    }
+
+    /// Parameter sanity checks (run on config load)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.symbol().trim().is_empty() {
+            return Err("symbol must not be empty".to_string());
+        }
+        let positive = |name: &str, v: f64| if v > 0.0 { Ok(()) } else { Err(format!("{} must be positive", name)) };
+        let periods = |fast: usize, slow: usize| {
+            if fast == 0 || slow == 0 {
+                Err("periods must be positive".to_string())
+            } else if fast >= slow {
+                Err(format!("fast_period ({}) must be below slow_period ({})", fast, slow))
+            } else {
+                Ok(())
+            }
+        };
+        let participation = |rate: &Option<f64>| match rate {
+            Some(r) if *r <= 0.0 || *r > 1.0 => Err("book_participation must be in (0, 1]".to_string()),
+            _ => Ok(()),
+        };
+        let bands = |period: usize, oversold: f64, overbought: f64| {
+            if period == 0 {
+                Err("period must be positive".to_string())
+            } else if !(0.0..=100.0).contains(&oversold) || !(0.0..=100.0).contains(&overbought) || oversold >= overbought {
+                Err(format!("invalid RSI bands {}/{}", oversold, overbought))
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            StrategySpec::MaCrossover { fast_period, slow_period, .. } => periods(*fast_period, *slow_period),
+            StrategySpec::Rsi { period, oversold, overbought, .. } => bands(*period, *oversold, *overbought),
+            StrategySpec::Macd { fast_period, slow_period, signal_period, .. } => {
+                periods(*fast_period, *slow_period)?;
+                if *signal_period == 0 { Err("signal_period must be positive".to_string()) } else { Ok(()) }
+            }
+            StrategySpec::Bollinger { period, std_dev, .. } => {
+                if *period < 2 { return Err("period must be at least 2".to_string()); }
+                positive("std_dev", *std_dev)
+            }
+            StrategySpec::Ichimoku { tenkan_period, kijun_period, senkou_b_period, .. } => {
+                periods(*tenkan_period, *kijun_period)?;
+                if kijun_period >= senkou_b_period {
+                    Err(format!("kijun_period ({}) must be below senkou_b_period ({})", kijun_period, senkou_b_period))
+                } else {
+                    Ok(())
+                }
+            }
+            StrategySpec::MultiIndicator { aggregation, .. } => aggregation.as_ref().map_or(Ok(()), |a| a.validate()),
+            StrategySpec::RsiTwap { period, oversold, overbought, twap_minutes, aggregation, .. } => {
+                aggregation.as_ref().map_or(Ok(()), |a| a.validate())?;
+                bands(*period, *oversold, *overbought)?;
+                if *twap_minutes == 0 { Err("twap_minutes must be positive".to_string()) } else { Ok(()) }
+            }
+            StrategySpec::MacdVwap { fast_period, slow_period, signal_period, participation_rate, aggregation, .. } => {
+                aggregation.as_ref().map_or(Ok(()), |a| a.validate())?;
+                periods(*fast_period, *slow_period)?;
+                if *signal_period == 0 {
+                    return Err("signal_period must be positive".to_string());
+                }
+                if *participation_rate <= 0.0 || *participation_rate > 1.0 {
+                    return Err("participation_rate must be in (0, 1]".to_string());
+                }
+                Ok(())
+            }
+            StrategySpec::MaCrossoverIceberg { fast_period, slow_period, display_size, aggregation, .. } => {
+                aggregation.as_ref().map_or(Ok(()), |a| a.validate())?;
+                periods(*fast_period, *slow_period)?;
+                positive("display_size", *display_size)
+            }
+            StrategySpec::Vwap { quantity, execution_interval_ms, vwap_window, book_participation, .. } => {
+                positive("quantity", *quantity)?;
+                participation(book_participation)?;
+                positive("execution_interval_ms", *execution_interval_ms as f64)?;
+                if *vwap_window == 0 { Err("vwap_window must be positive".to_string()) } else { Ok(()) }
+            }
+            StrategySpec::Twap { quantity, execution_interval_ms, slices, .. } => {
+                positive("quantity", *quantity)?;
+                positive("execution_interval_ms", *execution_interval_ms as f64)?;
+                if *slices == 0 { Err("slices must be positive".to_string()) } else { Ok(()) }
+            }
+            StrategySpec::ImplementationShortfall { quantity, execution_interval_ms, slices, urgency, volatility_bps, impact_bps, .. } => {
+                positive("quantity", *quantity)?;
+                positive("execution_interval_ms", *execution_interval_ms as f64)?;
+                positive("volatility_bps", *volatility_bps)?;
+                positive("impact_bps", *impact_bps)?;
+                if *urgency < 0.0 {
+                    return Err("urgency must not be negative".to_string());
+                }
+                if *slices == 0 { Err("slices must be positive".to_string()) } else { Ok(()) }
+            }
+            StrategySpec::Pairs { symbol, pair_symbol, quantity, lookback, entry_z, exit_z } => {
+                positive("quantity", *quantity)?;
+                if pair_symbol.trim().is_empty() || pair_symbol == symbol {
+                    return Err("pair_symbol must be set and differ from symbol".to_string());
+                }
+                if *lookback < 3 {
+                    return Err("lookback must be at least 3".to_string());
+                }
+                if *exit_z < 0.0 || entry_z <= exit_z {
+                    return Err(format!("entry_z ({}) must exceed exit_z ({}) and exit_z must not be negative", entry_z, exit_z));
+                }
+                Ok(())
+            }
+            StrategySpec::FundingCarry { symbol, spot_symbol, quantity, entry_rate, exit_rate } => {
+                positive("quantity", *quantity)?;
+                positive("entry_rate", *entry_rate)?;
+                if exit_rate >= entry_rate {
+                    return Err(format!("exit_rate ({}) must be below entry_rate ({})", exit_rate, entry_rate));
+                }
+                match spot_symbol {
+                    Some(spot) if spot.trim().is_empty() || spot == symbol => Err("spot_symbol must differ from symbol".to_string()),
+                    _ => Ok(()),
+                }
+            }
+            StrategySpec::Iceberg { total_quantity, limit_price, display_quantity, book_participation, .. } => {
+                positive("total_quantity", *total_quantity)?;
+                participation(book_participation)?;
+                positive("limit_price", *limit_price)?;
+                positive("display_quantity", *display_quantity)?;
+                if display_quantity > total_quantity {
+                    return Err("display_quantity exceeds total_quantity".to_string());
+                }
+                Ok(())
+            }
+            StrategySpec::TrailingStop { quantity, trailing_delta, .. } => {
+                positive("quantity", *quantity)?;
+                positive("trailing_delta", *trailing_delta)
+            }
+            StrategySpec::MultiTimeframe { symbol, entry, trend, entry_timeframe, trend_timeframe } => {
+                entry.to_spec(symbol).validate().map_err(|e| format!("entry: {}", e))?;
+                trend.to_spec(symbol).validate().map_err(|e| format!("trend: {}", e))?;
+                let entry_ms = parse_timeframe(entry_timeframe).map_err(|e| e.to_string())?;
+                let trend_ms = parse_timeframe(trend_timeframe).map_err(|e| e.to_string())?;
+                if trend_ms <= entry_ms {
+                    Err(format!("trend_timeframe ({}) must be longer than entry_timeframe ({})", trend_timeframe, entry_timeframe))
+                } else {
+                    Ok(())
+                }
+            }
+            StrategySpec::PredictionTa { quantity, min_confidence, confirmation, aggregation, .. } => {
+                aggregation.as_ref().map_or(Ok(()), |a| a.validate())?;
+                positive("quantity", *quantity)?;
+                if !(0.0..=1.0).contains(min_confidence) {
+                    return Err("min_confidence must be in [0, 1]".to_string());
+                }
+                confirmation.validate()
+            }
+        }
+    }
+}
+
+/// Strategies deployed when the config does not list any
+fn default_strategies() -> Vec<StrategyDeployment> {
+    let deploy = |spec| StrategyDeployment { enabled: true, spec, protection: None, exits: None, timeframe: None, exchange: None, shadow: false, schedule: None };
+    vec![
+        deploy(StrategySpec::MaCrossover { symbol: "BTCUSDT".into(), fast_period: 12, slow_period: 26 }),
+        deploy(StrategySpec::Rsi { symbol: "ETHUSDT".into(), period: 14, oversold: 30.0, overbought: 70.0 }),
+        deploy(StrategySpec::Macd { symbol: "BTCUSDT".into(), fast_period: 12, slow_period: 26, signal_period: 9 }),
+        deploy(StrategySpec::MultiIndicator { symbol: "ETHUSDT".into(), aggregation: None }),
+        deploy(StrategySpec::RsiTwap { symbol: "BTCUSDT".into(), period: 14, oversold: 30.0, overbought: 70.0, twap_minutes: 60, aggregation: None }),
+        deploy(StrategySpec::MacdVwap { symbol: "ETHUSDT".into(), fast_period: 12, slow_period: 26, signal_period: 9, participation_rate: 0.1, aggregation: None }),
+    ]
+}
+
+/// Strategy watchdog: learns update/order cadence and flags anomalies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchdogConfig {
+    /// Alert when silent for longer than stall_factor x learned interval
+    #[serde(default = "default_stall_factor")]
+    pub stall_factor: f64,
+    /// Silence shorter than this never alerts
+    #[serde(default = "default_min_silence_ms")]
+    pub min_silence_ms: i64,
+    /// Alert when orders in burst_window_ms exceed burst_factor x expected
+    #[serde(default = "default_burst_factor")]
+    pub burst_factor: f64,
+    #[serde(default = "default_burst_window_ms")]
+    pub burst_window_ms: i64,
+    /// Intervals observed before the cadence is trusted
+    #[serde(default = "default_watchdog_min_samples")]
+    pub min_samples: usize,
+    /// EWMA smoothing factor for the learned interval
+    #[serde(default = "default_watchdog_alpha")]
+    pub ewma_alpha: f64,
+    #[serde(default = "default_watchdog_check_interval_ms")]
+    pub check_interval_ms: u64,
+}
+
+fn default_stall_factor() -> f64 { 10.0 }
+fn default_min_silence_ms() -> i64 { 300_000 }
+fn default_burst_factor() -> f64 { 10.0 }
+fn default_burst_window_ms() -> i64 { 60_000 }
+fn default_watchdog_min_samples() -> usize { 20 }
+fn default_watchdog_alpha() -> f64 { 0.1 }
+fn default_watchdog_check_interval_ms() -> u64 { 10_000 }
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        WatchdogConfig {
+            stall_factor: default_stall_factor(),
+            min_silence_ms: default_min_silence_ms(),
+            burst_factor: default_burst_factor(),
+            burst_window_ms: default_burst_window_ms(),
+            min_samples: default_watchdog_min_samples(),
+            ewma_alpha: default_watchdog_alpha(),
+            check_interval_ms: default_watchdog_check_interval_ms(),
+        }
+    }
+}
+
+/// Dead man's switch: safety actions when the external heartbeat stops
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadManSwitchConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Trip when no heartbeat arrives for this long
+    #[serde(default = "default_heartbeat_timeout_ms")]
+    pub timeout_ms: i64,
+    #[serde(default = "default_heartbeat_check_interval_ms")]
+    pub check_interval_ms: u64,
+    /// Executed in order: halt strategies, cancel orders, flatten positions
+    #[serde(default = "default_safety_actions")]
+    pub actions: Vec<SafetyAction>,
+}
+
+fn default_heartbeat_timeout_ms() -> i64 { 600_000 }
+fn default_heartbeat_check_interval_ms() -> u64 { 15_000 }
+fn default_safety_actions() -> Vec<SafetyAction> {
+    vec![SafetyAction::HaltStrategies, SafetyAction::CancelOrders, SafetyAction::FlattenPositions]
+}
+
+impl Default for DeadManSwitchConfig {
+    fn default() -> Self {
+        DeadManSwitchConfig {
+            enabled: false,
+            timeout_ms: default_heartbeat_timeout_ms(),
+            check_interval_ms: default_heartbeat_check_interval_ms(),
+            actions: default_safety_actions(),
+        }
+    }
+}
+
+/// Order routing rules evaluated before submission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingConfig {
+    #[serde(default)]
+    pub rules: Vec<RoutingRule>,
+    /// JSON array of rules; replaces `rules` and is re-read when modified
+    #[serde(default)]
+    pub rules_path: Option<String>,
+    #[serde(default = "default_routing_reload_interval_ms")]
+    pub reload_interval_ms: u64,
+}
+
+fn default_routing_reload_interval_ms() -> u64 { 5_000 }
+
+impl Default for RoutingConfig {
+    fn default() -> Self {
+        RoutingConfig {
+            rules: Vec::new(),
+            rules_path: None,
+            reload_interval_ms: default_routing_reload_interval_ms(),
+        }
+    }
+}
+
+/// Prioritized order submission: cancels and reduce-only orders first, then execution children, then new entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionQueueConfig {
+    /// Dispatch rate cap across all strategies (0 = unthrottled)
+    #[serde(default = "default_max_submissions_per_sec")]
+    pub max_per_second: f64,
+    /// Requests waiting longer than this are served ahead of higher priorities
+    #[serde(default = "default_starvation_ms")]
+    pub starvation_ms: u64,
+    /// Pending requests beyond this are rejected immediately, except risk-reducing ones (0 = unbounded)
+    #[serde(default = "default_max_pending_submissions")]
+    pub max_pending: usize,
+}
+
+fn default_max_submissions_per_sec() -> f64 { 10.0 }
+fn default_starvation_ms() -> u64 { 5_000 }
+fn default_max_pending_submissions() -> usize { 200 }
+
+impl Default for SubmissionQueueConfig {
+    fn default() -> Self {
+        SubmissionQueueConfig {
+            max_per_second: default_max_submissions_per_sec(),
+            starvation_ms: default_starvation_ms(),
+            max_pending: default_max_pending_submissions(),
+        }
+    }
+}
+
+/// External numeric series (sentiment, on-chain flows) exposed to strategies as auxiliary inputs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalDataConfig {
+    /// Observations are aligned to candles of this length (open time)
+    #[serde(default = "default_external_interval_ms")]
+    pub candle_interval_ms: i64,
+    /// Points retained per series
+    #[serde(default = "default_external_max_points")]
+    pub max_points: usize,
+    /// HTTP sources polled on startup (series can also be pushed via `POST /external/:name`)
+    #[serde(default)]
+    pub sources: Vec<ExternalSourceConfig>,
+}
+
+fn default_external_interval_ms() -> i64 { 60_000 }
+fn default_external_max_points() -> usize { 10_000 }
+
+impl Default for ExternalDataConfig {
+    fn default() -> Self {
+        ExternalDataConfig {
+            candle_interval_ms: default_external_interval_ms(),
+            max_points: default_external_max_points(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// Funding rate and open interest collection
+///
+/// Snapshots are kept per symbol for `GET /market/:symbol/funding` and also recorded as
+/// external series (`funding.<SYMBOL>.rate`, `funding.<SYMBOL>.next_time`, `oi.<SYMBOL>`,
+/// `oi.<SYMBOL>.change`) so strategies see them next to candles.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingDataConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Symbols to collect (empty = the strategy runtime symbols)
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    #[serde(default = "default_funding_interval_ms")]
+    pub interval_ms: u64,
+    /// Also poll open interest (REST only)
+    #[serde(default = "default_true")]
+    pub open_interest: bool,
+    /// Snapshots retained per symbol
+    #[serde(default = "default_funding_history_limit")]
+    pub history_limit: usize,
+    /// Take funding from the `markPrice` WebSocket stream when fresh (futures market stream only)
+    #[serde(default)]
+    pub mark_price_stream: bool,
+}
+
+fn default_funding_interval_ms() -> u64 { 60_000 }
+fn default_funding_history_limit() -> usize { 10_080 }
+
+impl Default for FundingDataConfig {
+    fn default() -> Self {
+        FundingDataConfig {
+            enabled: false,
+            symbols: Vec::new(),
+            interval_ms: default_funding_interval_ms(),
+            open_interest: true,
+            history_limit: default_funding_history_limit(),
+            mark_price_stream: false,
+        }
+    }
 }
 
+/// FIX session parameters (logon, heartbeats, sequence numbers) shared by FIX connectors
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ServerConfig {
+pub struct FixSessionConfig {
     pub host: String,
     pub port: u16,
-    pub api_token: Option<String>,
+    pub sender_comp_id: String,
+    pub target_comp_id: String,
+    /// BeginString (8), e.g. `FIX.4.2`, `FIX.4.4`
+    #[serde(default = "default_fix_begin_string")]
+    pub begin_string: String,
+    /// HeartBtInt (108) in seconds
+    #[serde(default = "default_fix_heartbeat_secs")]
+    pub heartbeat_secs: u64,
+    /// Send ResetSeqNumFlag=Y on every logon and restart both sequence numbers at 1
+    #[serde(default = "default_true")]
+    pub reset_on_logon: bool,
+    /// Username (553) / Password (554) sent on logon
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Venue-specific logon fields by tag, e.g. `{"1137": "9"}`
+    #[serde(default)]
+    pub logon_fields: HashMap<u32, String>,
+    #[serde(default = "default_fix_logon_timeout_ms")]
+    pub logon_timeout_ms: u64,
+    /// Delay before reconnecting after the session drops
+    #[serde(default = "default_fix_reconnect_delay_ms")]
+    pub reconnect_delay_ms: u64,
+}
+
+fn default_fix_begin_string() -> String { "FIX.4.4".to_string() }
+fn default_fix_heartbeat_secs() -> u64 { 30 }
+fn default_fix_logon_timeout_ms() -> u64 { 10_000 }
+fn default_fix_reconnect_delay_ms() -> u64 { 5_000 }
+
+impl FixSessionConfig {
+    pub fn new(host: impl Into<String>, port: u16, sender_comp_id: impl Into<String>, target_comp_id: impl Into<String>) -> Self {
+        FixSessionConfig {
+            host: host.into(),
+            port,
+            sender_comp_id: sender_comp_id.into(),
+            target_comp_id: target_comp_id.into(),
+            begin_string: default_fix_begin_string(),
+            heartbeat_secs: default_fix_heartbeat_secs(),
+            reset_on_logon: true,
+            username: None,
+            password: None,
+            logon_fields: HashMap::new(),
+            logon_timeout_ms: default_fix_logon_timeout_ms(),
+            reconnect_delay_ms: default_fix_reconnect_delay_ms(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.host.trim().is_empty() || self.port == 0 {
+            return Err("host and port are required".to_string());
+        }
+        if self.sender_comp_id.is_empty() || self.target_comp_id.is_empty() {
+            return Err("sender_comp_id and target_comp_id are required".to_string());
+        }
+        if self.heartbeat_secs == 0 || self.logon_timeout_ms == 0 {
+            return Err("heartbeat_secs and logon_timeout_ms must be positive".to_string());
+        }
+        Ok(())
+    }
 }
 
+/// FIX market data feed: MarketDataRequest (V) per subscribed symbol, snapshots (W) and
+/// incremental refreshes (X) published to the market data stream as ticks and order books
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExchangeConfig {
-    pub name: String,
-    pub api_key: Option<String>,
-    pub api_secret: Option<String>,
-    pub base_url: Option<String>,
-    pub use_mock: bool,
+pub struct FixMarketDataConfig {
+    #[serde(flatten)]
+    pub session: FixSessionConfig,
+    /// MarketDepth (264); 0 requests the full book
+    #[serde(default = "default_fix_market_depth")]
+    pub market_depth: u32,
+    /// MDEntryType (269) values requested: `0` bid, `1` offer, `2` trade
+    #[serde(default = "default_fix_entry_types")]
+    pub entry_types: Vec<String>,
+    /// Request incremental refreshes (MDUpdateType=1) instead of full refreshes only
+    #[serde(default = "default_true")]
+    pub incremental: bool,
+}
+
+fn default_fix_market_depth() -> u32 { 10 }
+fn default_fix_entry_types() -> Vec<String> { vec!["0".to_string(), "1".to_string(), "2".to_string()] }
+
+impl FixMarketDataConfig {
+    pub fn new(session: FixSessionConfig) -> Self {
+        FixMarketDataConfig { session, market_depth: default_fix_market_depth(), entry_types: default_fix_entry_types(), incremental: true }
+    }
 }
 
+/// FIX order entry: NewOrderSingle (D), OrderCancelRequest (F) and OrderCancelReplaceRequest (G),
+/// with ExecutionReports (8) tracked into order status updates
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LoggingConfig {
-    pub level: String,
-    pub file_path: Option<String>,
+pub struct FixOrderEntryConfig {
+    #[serde(flatten)]
+    pub session: FixSessionConfig,
+    /// Account (1) sent on every order
+    #[serde(default)]
+    pub account: Option<String>,
+    /// HandlInst (21), required by some FIX 4.2 venues (`1` automated, no intervention)
+    #[serde(default)]
+    pub handl_inst: Option<String>,
+    /// How long submit/cancel/replace wait for the venue's ExecutionReport or OrderCancelReject
+    #[serde(default = "default_fix_ack_timeout_ms")]
+    pub ack_timeout_ms: u64,
+}
+
+fn default_fix_ack_timeout_ms() -> u64 { 5_000 }
+
+impl FixOrderEntryConfig {
+    pub fn new(session: FixSessionConfig) -> Self {
+        FixOrderEntryConfig { session, account: None, handl_inst: None, ack_timeout_ms: default_fix_ack_timeout_ms() }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.session.validate()?;
+        if self.ack_timeout_ms == 0 {
+            return Err("ack_timeout_ms must be positive".to_string());
+        }
+        Ok(())
+    }
 }
 
+/// Polled JSON endpoint for one external series
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PredictionApiConfig {
-    pub base_url: String,
-    pub timeout_ms: Option<u64>,
+pub struct ExternalSourceConfig {
+    pub name: String,
+    pub url: String,
+    /// JSON pointer to the numeric value (numeric strings accepted), e.g. `/data/0/value`
+    pub value_path: String,
+    /// JSON pointer to the observation time (epoch s/ms or RFC3339); poll time when absent
+    #[serde(default)]
+    pub timestamp_path: Option<String>,
+    #[serde(default = "default_external_poll_ms")]
+    pub poll_interval_ms: u64,
 }
 
+fn default_external_poll_ms() -> u64 { 300_000 }
+
+/// Read-only degraded mode entered when exchange writes keep failing with auth/permission errors
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FuturesDefaults {
+pub struct DegradedModeConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Consecutive auth/permission failures before orders are blocked
+    #[serde(default = "default_degraded_failure_threshold")]
+    pub failure_threshold: u32,
+    /// While degraded, one order is let through this often to detect restored access
+    #[serde(default = "default_degraded_probe_interval_ms")]
+    pub probe_interval_ms: i64,
+    /// Blocked orders kept for `GET /health/degraded`
+    #[serde(default = "default_degraded_journal_limit")]
+    pub journal_limit: usize,
+}
+
+fn default_degraded_failure_threshold() -> u32 { 3 }
+fn default_degraded_probe_interval_ms() -> i64 { 300_000 }
+fn default_degraded_journal_limit() -> usize { 1_000 }
+
+impl Default for DegradedModeConfig {
+    fn default() -> Self {
+        DegradedModeConfig {
+            enabled: true,
+            failure_threshold: default_degraded_failure_threshold(),
+            probe_interval_ms: default_degraded_probe_interval_ms(),
+            journal_limit: default_degraded_journal_limit(),
+        }
+    }
+}
+
+/// Portfolio PnL tracking built from order fills
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioConfig {
+    /// Equity before any tracked fills (quote currency)
+    #[serde(default = "default_starting_equity")]
+    pub starting_equity: f64,
+    /// Equity curve sampling period; open positions are re-priced at the same cadence
+    #[serde(default = "default_portfolio_history_interval_ms")]
+    pub history_interval_ms: i64,
+    /// Equity curve points kept for `GET /portfolio/history`
+    #[serde(default = "default_portfolio_history_limit")]
+    pub history_limit: usize,
+}
+
+fn default_starting_equity() -> f64 { 10000.0 }
+
+/// Portfolio-level exposure limits (quote currency notional at current prices)
+///
+/// Orders that would push a limit further past its cap are rejected; orders that reduce the
+/// measured exposure always pass. Changes are applied on config reload without a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RiskLimitsConfig {
+    /// Cap on gross notional across all positions
     #[serde(default)]
+    pub max_total_notional: Option<f64>,
+    /// Cap on the number of symbols with an open position
+    #[serde(default)]
+    pub max_open_positions: Option<usize>,
+    /// Symbols treated as one risk bucket (e.g. BTCUSDT + ETHUSDT)
+    #[serde(default)]
+    pub correlation_groups: Vec<CorrelationGroup>,
+}
+
+/// Correlated symbols whose combined gross notional is capped together
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CorrelationGroup {
+    pub name: String,
     pub symbols: Vec<String>,
-    #[serde(default = "default_leverage")] 
-    pub leverage: u32,
+    pub max_notional: f64,
+}
+
+impl RiskLimitsConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_total_notional.is_some() || self.max_open_positions.is_some() || !self.correlation_groups.is_empty()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_total_notional.is_some_and(|cap| cap <= 0.0) {
+            return Err("max_total_notional must be positive".to_string());
+        }
+        if self.max_open_positions == Some(0) {
+            return Err("max_open_positions must be positive".to_string());
+        }
+        for group in &self.correlation_groups {
+            if group.symbols.len() < 2 {
+                return Err(format!("correlation group {} needs at least two symbols", group.name));
+            }
+            if group.max_notional <= 0.0 {
+                return Err(format!("correlation group {} max_notional must be positive", group.name));
+            }
+        }
+        Ok(())
+    }
+}
+/// Daily realized loss accounting
+///
+/// The trading day starts at `rollover_hour` in the local time given by `utc_offset_minutes`
+/// (e.g. 540 / 9 for a 09:00 KST reset). Losses are counted per trading day and reset
+/// automatically when the day rolls over.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DailyLossConfig {
+    /// Realized loss (including fees) after which only reduce-only orders pass
     #[serde(default)]
-    pub isolated: bool,
+    pub max_daily_loss: Option<f64>,
+    /// Offset of the trading-day timezone from UTC
     #[serde(default)]
-    pub hedge: bool,
+    pub utc_offset_minutes: i32,
+    /// Local hour (0-23) at which a new trading day starts
+    #[serde(default)]
+    pub rollover_hour: u32,
+    /// File keeping the current day's totals across restarts
+    #[serde(default)]
+    pub state_path: Option<String>,
 }
 
-fn default_leverage() -> u32 { 20 }
+impl DailyLossConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_daily_loss.is_some_and(|limit| limit <= 0.0) {
+            return Err("max_daily_loss must be positive".to_string());
+        }
+        if self.rollover_hour > 23 {
+            return Err("rollover_hour must be in 0..=23".to_string());
+        }
+        if self.utc_offset_minutes.abs() > 14 * 60 {
+            return Err("utc_offset_minutes must be within +/-14h".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Position-aware order routing
+///
+/// In one-way mode orders opposing the current net position are sent reduce-only; in hedge
+/// mode (`futures.hedge`) every order gets the LONG/SHORT positionSide it opens or closes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PositionRoutingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Let a one-way order larger than the opposite position flip it instead of cutting it to a close
+    #[serde(default)]
+    pub allow_flip: bool,
+    /// Resync tracked positions from the exchange every N ms (0 = order fills only)
+    #[serde(default)]
+    pub sync_interval_ms: u64,
+}
+
+/// Futures margin monitoring
+///
+/// The margin ratio is maintenance margin / margin balance (1.0 = liquidation). Crossing
+/// `warn_ratio` or `critical_ratio` raises an alert; with `derisk` every open position is cut by
+/// `derisk_fraction` with reduce-only market orders while the ratio stays critical.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarginMonitorConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_margin_interval_ms")]
+    pub interval_ms: u64,
+    #[serde(default = "default_margin_warn_ratio")]
+    pub warn_ratio: f64,
+    #[serde(default = "default_margin_critical_ratio")]
+    pub critical_ratio: f64,
+    /// Alert when a position's mark price is within this fraction of its liquidation price
+    #[serde(default)]
+    pub liquidation_distance: Option<f64>,
+    /// Reduce positions automatically at the critical ratio
+    #[serde(default)]
+    pub derisk: bool,
+    /// Share of each position closed per de-risk step
+    #[serde(default = "default_derisk_fraction")]
+    pub derisk_fraction: f64,
+    /// Minimum time between de-risk steps, giving fills time to lower the ratio
+    #[serde(default = "default_derisk_cooldown_ms")]
+    pub derisk_cooldown_ms: i64,
+}
+
+fn default_margin_interval_ms() -> u64 { 30_000 }
+fn default_margin_warn_ratio() -> f64 { 0.5 }
+fn default_margin_critical_ratio() -> f64 { 0.8 }
+fn default_derisk_fraction() -> f64 { 0.25 }
+fn default_derisk_cooldown_ms() -> i64 { 300_000 }
+
+impl Default for MarginMonitorConfig {
+    fn default() -> Self {
+        MarginMonitorConfig {
+            enabled: false,
+            interval_ms: default_margin_interval_ms(),
+            warn_ratio: default_margin_warn_ratio(),
+            critical_ratio: default_margin_critical_ratio(),
+            liquidation_distance: None,
+            derisk: false,
+            derisk_fraction: default_derisk_fraction(),
+            derisk_cooldown_ms: default_derisk_cooldown_ms(),
+        }
+    }
+}
+
+impl MarginMonitorConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.warn_ratio > 0.0 && self.warn_ratio < self.critical_ratio && self.critical_ratio <= 1.0) {
+            return Err("ratios must satisfy 0 < warn_ratio < critical_ratio <= 1".to_string());
+        }
+        if self.liquidation_distance.is_some_and(|d| !(d > 0.0 && d < 1.0)) {
+            return Err("liquidation_distance must be in (0, 1)".to_string());
+        }
+        if !(self.derisk_fraction > 0.0 && self.derisk_fraction <= 1.0) {
+            return Err("derisk_fraction must be in (0, 1]".to_string());
+        }
+        if self.interval_ms == 0 || self.derisk_cooldown_ms < 0 {
+            return Err("interval_ms must be positive and derisk_cooldown_ms non-negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn default_portfolio_history_interval_ms() -> i64 { 60_000 }
+fn default_portfolio_history_limit() -> usize { 10_080 }
+
+impl Default for PortfolioConfig {
+    fn default() -> Self {
+        PortfolioConfig {
+            starting_equity: default_starting_equity(),
+            history_interval_ms: default_portfolio_history_interval_ms(),
+            history_limit: default_portfolio_history_limit(),
+        }
+    }
+}
+
+/// Live strategy loop: driven by WebSocket tickers, REST polling only as a fallback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyRuntimeConfig {
+    /// Poll REST market data when a symbol's stream has been silent this long
+    #[serde(default = "default_stream_timeout_ms")]
+    pub stream_timeout_ms: u64,
+    /// Minimum gap between REST order book / funding refreshes per symbol
+    #[serde(default = "default_rest_refresh_ms")]
+    pub rest_refresh_ms: u64,
+    /// Identical orders (strategy, symbol, side, reason) within this window are dropped (0 = off)
+    #[serde(default = "default_dedup_cooldown_ms")]
+    pub dedup_cooldown_ms: u64,
+}
+
+fn default_stream_timeout_ms() -> u64 { 5_000 }
+fn default_rest_refresh_ms() -> u64 { 5_000 }
+fn default_dedup_cooldown_ms() -> u64 { 5_000 }
+
+impl Default for StrategyRuntimeConfig {
+    fn default() -> Self {
+        StrategyRuntimeConfig {
+            stream_timeout_ms: default_stream_timeout_ms(),
+            rest_refresh_ms: default_rest_refresh_ms(),
+            dedup_cooldown_ms: default_dedup_cooldown_ms(),
+        }
+    }
+}
+
+/// Closed candles built from the live ticker stream and published on the market data stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandleAggregationConfig {
+    /// Timeframes built per traded symbol (e.g. "1s", "1m", "5m", "1h"); empty disables aggregation
+    #[serde(default = "default_candle_timeframes")]
+    pub timeframes: Vec<String>,
+    /// Refetch candles missing after a stream gap (e.g. a reconnect) before live candles resume
+    #[serde(default = "default_true")]
+    pub backfill: bool,
+    /// Most recent candles fetched per gap; older missing candles are skipped
+    #[serde(default = "default_max_backfill_candles")]
+    pub max_backfill_candles: usize,
+}
+
+fn default_candle_timeframes() -> Vec<String> { vec!["1m".to_string(), "5m".to_string(), "1h".to_string()] }
+fn default_max_backfill_candles() -> usize { 1000 }
+
+impl Default for CandleAggregationConfig {
+    fn default() -> Self {
+        CandleAggregationConfig { timeframes: default_candle_timeframes(), backfill: true, max_backfill_candles: default_max_backfill_candles() }
+    }
+}
+
+/// Market data recorder. Files are written per symbol and UTC day in the backtest CSV layout
+/// (`symbol,timestamp,open,high,low,close,volume`), so `CsvDataProvider` reads them directly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketDataRecorderConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Root directory; `index.json` lists every file with its time range
+    #[serde(default = "default_recorder_dir")]
+    pub dir: String,
+    /// Record closed candles of the aggregated timeframes
+    #[serde(default = "default_true")]
+    pub candles: bool,
+    /// Record every ticker update as a point candle
+    #[serde(default)]
+    pub ticks: bool,
+    /// Candle timeframes to record (empty = every aggregated timeframe)
+    #[serde(default)]
+    pub timeframes: Vec<String>,
+    /// A day's file rolls over to a new part once it grows past this size
+    #[serde(default = "default_recorder_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Days kept on disk; older days are deleted (None keeps everything)
+    #[serde(default = "default_recorder_retention_days")]
+    pub retention_days: Option<u32>,
+    #[serde(default = "default_recorder_flush_interval_ms")]
+    pub flush_interval_ms: u64,
+    /// How often past days are compacted into one file and retention is applied
+    #[serde(default = "default_recorder_maintenance_interval_ms")]
+    pub maintenance_interval_ms: u64,
+}
+
+fn default_recorder_dir() -> String { "data/recordings".to_string() }
+fn default_recorder_max_file_bytes() -> u64 { 64 * 1024 * 1024 }
+fn default_recorder_retention_days() -> Option<u32> { Some(90) }
+fn default_recorder_flush_interval_ms() -> u64 { 5_000 }
+fn default_recorder_maintenance_interval_ms() -> u64 { 3_600_000 }
+
+impl Default for MarketDataRecorderConfig {
+    fn default() -> Self {
+        MarketDataRecorderConfig {
+            enabled: false,
+            dir: default_recorder_dir(),
+            candles: true,
+            ticks: false,
+            timeframes: Vec::new(),
+            max_file_bytes: default_recorder_max_file_bytes(),
+            retention_days: default_recorder_retention_days(),
+            flush_interval_ms: default_recorder_flush_interval_ms(),
+            maintenance_interval_ms: default_recorder_maintenance_interval_ms(),
+        }
+    }
+}
+
+impl MarketDataRecorderConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.dir.trim().is_empty() {
+            return Err("dir must not be empty".to_string());
+        }
+        if self.max_file_bytes == 0 || self.flush_interval_ms == 0 || self.maintenance_interval_ms == 0 {
+            return Err("max_file_bytes, flush_interval_ms and maintenance_interval_ms must be positive".to_string());
+        }
+        if self.retention_days == Some(0) {
+            return Err("retention_days must be at least 1".to_string());
+        }
+        if let Some(tf) = self.timeframes.iter().find(|tf| parse_timeframe(tf).is_err()) {
+            return Err(format!("unknown timeframe '{}'", tf));
+        }
+        Ok(())
+    }
+}
+
+/// Cleanup on SIGINT/SIGTERM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownConfig {
+    /// Cancel every open order on exit (a removed strategy's own orders are always cancelled)
+    #[serde(default = "default_true")]
+    pub cancel_open_orders: bool,
+    /// Upper bound on the exit cleanup before the process quits anyway
+    #[serde(default = "default_shutdown_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_shutdown_timeout_ms() -> u64 { 10_000 }
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        ShutdownConfig { cancel_open_orders: true, timeout_ms: default_shutdown_timeout_ms() }
+    }
+}
+
+/// Re-read the config file when it changes and apply the sections that are safe to swap at runtime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotReloadConfig {
+    /// Watch the config file; `POST /config/reload` works either way
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default = "default_hot_reload_interval_ms")]
+    pub interval_ms: u64,
+}
+
+fn default_hot_reload_interval_ms() -> u64 { 2_000 }
+
+impl Default for HotReloadConfig {
+    fn default() -> Self {
+        HotReloadConfig { enabled: true, interval_ms: default_hot_reload_interval_ms() }
+    }
+}
+
+/// Backtests submitted over the API (`POST /backtests`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestJobsConfig {
+    /// Jobs running at the same time; the rest wait in the queue
+    #[serde(default = "default_backtest_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Finished jobs (with results) kept in memory; the oldest are dropped first
+    #[serde(default = "default_backtest_max_retained")]
+    pub max_retained: usize,
+}
+
+fn default_backtest_max_concurrent() -> usize { 2 }
+fn default_backtest_max_retained() -> usize { 50 }
+
+impl Default for BacktestJobsConfig {
+    fn default() -> Self {
+        BacktestJobsConfig { max_concurrent: default_backtest_max_concurrent(), max_retained: default_backtest_max_retained() }
+    }
+}
+
+/// Exchange connectivity telemetry: thresholds for /health/exchange and clock sync cadence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// REST p95 round trip above this marks the exchange degraded
+    #[serde(default = "default_latency_warn_ms")]
+    pub latency_warn_ms: f64,
+    /// WebSocket p95 event-to-receive lag above this marks the exchange degraded
+    #[serde(default = "default_stream_lag_warn_ms")]
+    pub stream_lag_warn_ms: f64,
+    /// Absolute local-vs-server clock offset above this marks the exchange degraded
+    #[serde(default = "default_clock_offset_warn_ms")]
+    pub clock_offset_warn_ms: i64,
+    #[serde(default = "default_clock_sync_interval_ms")]
+    pub clock_sync_interval_ms: u64,
+}
+
+fn default_latency_warn_ms() -> f64 { 1_000.0 }
+fn default_stream_lag_warn_ms() -> f64 { 2_000.0 }
+fn default_clock_offset_warn_ms() -> i64 { 1_000 }
+fn default_clock_sync_interval_ms() -> u64 { 60_000 }
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        TelemetryConfig {
+            latency_warn_ms: default_latency_warn_ms(),
+            stream_lag_warn_ms: default_stream_lag_warn_ms(),
+            clock_offset_warn_ms: default_clock_offset_warn_ms(),
+            clock_sync_interval_ms: default_clock_sync_interval_ms(),
+        }
+    }
+}
+
+/// Timezone used for hour-of-day / day-of-week PnL buckets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportingConfig {
+    /// Default UTC offset ("UTC", "+09:00", "-05:00")
+    #[serde(default = "default_reporting_timezone")]
+    pub timezone: String,
+    /// Per-strategy override of the reporting timezone
+    #[serde(default)]
+    pub strategy_timezones: HashMap<String, String>,
+}
+
+fn default_reporting_timezone() -> String { "UTC".to_string() }
+
+impl ReportingConfig {
+    pub fn timezone_for(&self, strategy: &str) -> &str {
+        self.strategy_timezones.get(strategy).map(|s| s.as_str()).unwrap_or(&self.timezone)
+    }
+}
+
+impl Default for ReportingConfig {
+    fn default() -> Self {
+        ReportingConfig {
+            timezone: default_reporting_timezone(),
+            strategy_timezones: HashMap::new(),
+        }
+    }
+}
+
+/// Outbound webhook endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// HMAC-SHA256 signing secret (X-XQuant-Signature header)
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Event names to deliver (e.g. "order_filled"); empty means all events
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_webhook_retries")]
+    pub max_retries: u32,
+}
+
+fn default_webhook_retries() -> u32 { 3 }
+
+impl WebhookConfig {
+    /// Whether this endpoint subscribes to the given event name
+    pub fn accepts(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+}
+
+/// Alert channel; `kind` selects the sink and `events` routes event types to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(flatten)]
+    pub sink: NotificationSinkConfig,
+    /// Event names to alert on (e.g. "risk_breach"); empty means all events
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_notification_retries")]
+    pub max_retries: u32,
+}
+
+fn default_notification_retries() -> u32 { 2 }
+
+impl NotificationConfig {
+    /// Whether this channel subscribes to the given event name
+    pub fn accepts(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+}
+
+/// Notification sink settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NotificationSinkConfig {
+    /// Telegram bot `sendMessage`
+    Telegram {
+        bot_token: String,
+        chat_id: String,
+        /// Bot API base URL (defaults to https://api.telegram.org)
+        #[serde(default)]
+        api_url: Option<String>,
+    },
+    /// Discord channel webhook
+    Discord { webhook_url: String },
+    /// Generic HTTP endpoint receiving a JSON alert
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+    },
+    /// Plain-text email over SMTP
+    Email {
+        smtp_host: String,
+        #[serde(default)]
+        smtp_port: Option<u16>,
+        #[serde(default)]
+        security: SmtpSecurity,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+/// SMTP connection security
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Implicit TLS (port 465)
+    Tls,
+    /// STARTTLS upgrade (port 587)
+    #[default]
+    StartTls,
+    /// Unencrypted, for local relays only
+    None,
+}
+
+fn default_max_consecutive_losses() -> u32 { 5 }
+fn default_max_strategy_drawdown() -> f64 { 1000.0 }
+fn default_cooloff_ms() -> i64 { 3_600_000 }
+
+impl Default for StrategyGuardConfig {
+    fn default() -> Self {
+        StrategyGuardConfig {
+            enabled: false,
+            max_consecutive_losses: default_max_consecutive_losses(),
+            max_drawdown: default_max_strategy_drawdown(),
+            cooloff_ms: default_cooloff_ms(),
+        }
+    }
+}
 
 impl Config {
-    /// Load configuration from a file
+    /// Load configuration from `config.json` (defaults when the file is missing)
     pub fn load() -> Result<Self, TradingError> {
-        // Try to load from config.json
-        let config_path = Path::new("config.json");
+        let config_path = Path::new(CONFIG_PATH);
 
         if config_path.exists() {
-            let mut file = File::open(config_path)
-                .map_err(|e| TradingError::ConfigError(format!("Failed to open config file: {}", e)))?;
-
-            let mut contents = String::new();
-            file.read_to_string(&mut contents)
-                .map_err(|e| TradingError::ConfigError(format!("Failed to read config file: {}", e)))?;
-
-            let mut cfg: Config = serde_json::from_str(&contents)
-                .map_err(|e| TradingError::ConfigError(format!("Failed to parse config file: {}", e)))?;
-            // environment overrides
-            cfg.apply_env_overrides();
-            Ok(cfg)
+            Self::load_from(config_path)
         } else {
             // Return default configuration
             let mut cfg = Config::default();
@@ -91,12 +1841,83 @@ impl Config {
         }
     }
 
+    /// Load, apply environment overrides and validate a config file
+    pub fn load_from(config_path: &Path) -> Result<Self, TradingError> {
+        let mut file = File::open(config_path)
+            .map_err(|e| TradingError::ConfigError(format!("Failed to open config file: {}", e)))?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)
+            .map_err(|e| TradingError::ConfigError(format!("Failed to read config file: {}", e)))?;
+
+        let mut cfg: Config = serde_json::from_str(&contents)
+            .map_err(|e| TradingError::ConfigError(format!("Failed to parse config file: {}", e)))?;
+        // environment overrides
+        cfg.apply_env_overrides();
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Validate declarative sections (strategy specs)
+    pub fn validate(&self) -> Result<(), TradingError> {
+        let venues: Vec<&str> = std::iter::once(&self.exchange).chain(&self.exchanges).map(|e| e.name.as_str()).collect();
+        for venue in std::iter::once(&self.exchange).chain(&self.exchanges) {
+            venue.validate().map_err(|e| TradingError::ConfigError(format!("exchange {}: {}", venue.name, e)))?;
+        }
+        if let Some(name) = venues.iter().enumerate().find_map(|(i, name)| venues[..i].contains(name).then_some(name)) {
+            return Err(TradingError::ConfigError(format!("duplicate exchange name '{}'", name)));
+        }
+        if self.order_store_path.is_some() && self.order_store_url.is_some() {
+            return Err(TradingError::ConfigError("order_store_path and order_store_url are mutually exclusive".to_string()));
+        }
+        if self.strategy_state_path.is_some() && self.strategy_state_db.is_some() {
+            return Err(TradingError::ConfigError("strategy_state_path and strategy_state_db are mutually exclusive".to_string()));
+        }
+        for (i, deployment) in self.strategies.iter().enumerate() {
+            if let Some(venue) = deployment.exchange.as_deref().filter(|v| !venues.contains(v)) {
+                return Err(TradingError::ConfigError(format!("strategies[{}] ({}): unknown exchange '{}'", i, deployment.spec.symbol(), venue)));
+            }
+            deployment.spec.validate()
+                .and_then(|_| deployment.protection.as_ref().map_or(Ok(()), |p| p.validate()))
+                .and_then(|_| deployment.exits.as_ref().map_or(Ok(()), |e| e.validate()))
+                .and_then(|_| deployment.schedule.as_ref().map_or(Ok(()), |s| s.validate()))
+                .and_then(|_| deployment.timeframe.as_deref().map_or(Ok(()), |tf| parse_timeframe(tf).map(|_| ()).map_err(|e| e.to_string())))
+                .map_err(|e| {
+                    TradingError::ConfigError(format!("strategies[{}] ({}): {}", i, deployment.spec.symbol(), e))
+                })?;
+        }
+        for rule in &self.routing.rules {
+            rule.validate().map_err(|e| TradingError::ConfigError(format!("routing rule {}: {}", rule.name, e)))?;
+        }
+        self.risk_limits.validate().map_err(|e| TradingError::ConfigError(format!("risk_limits: {}", e)))?;
+        self.daily_loss.validate().map_err(|e| TradingError::ConfigError(format!("daily_loss: {}", e)))?;
+        self.margin_monitor.validate().map_err(|e| TradingError::ConfigError(format!("margin_monitor: {}", e)))?;
+        self.market_data_recorder.validate().map_err(|e| TradingError::ConfigError(format!("market_data_recorder: {}", e)))?;
+        if let Some(fix) = &self.fix_market_data {
+            fix.session.validate().map_err(|e| TradingError::ConfigError(format!("fix_market_data: {}", e)))?;
+        }
+        Ok(())
+    }
+
     /// Apply environment variable overrides for sensitive/runtime fields
     fn apply_env_overrides(&mut self) {
         use std::env;
         if let Ok(v) = env::var("EXCHANGE_API_KEY") { if !v.is_empty() { self.exchange.api_key = Some(v); } }
         if let Ok(v) = env::var("EXCHANGE_API_SECRET") { if !v.is_empty() { self.exchange.api_secret = Some(v); } }
+        if let Ok(v) = env::var("EXCHANGE_API_PASSPHRASE") { if !v.is_empty() { self.exchange.api_passphrase = Some(v); } }
         if let Ok(v) = env::var("EXCHANGE_BASE_URL") { if !v.is_empty() { self.exchange.base_url = Some(v); } }
+        if let Ok(v) = env::var("EXCHANGE_KIND") {
+            match serde_json::from_value(serde_json::Value::String(v.to_lowercase())) {
+                Ok(kind) => self.exchange.kind = kind,
+                Err(_) => log::warn!("ignoring unknown EXCHANGE_KIND {}", v),
+            }
+        }
+        if let Ok(v) = env::var("EXCHANGE_MODE") {
+            match serde_json::from_value(serde_json::Value::String(v.to_lowercase())) {
+                Ok(mode) => self.exchange.mode = Some(mode),
+                Err(_) => log::warn!("ignoring unknown EXCHANGE_MODE {}", v),
+            }
+        }
         if let Ok(v) = env::var("USE_MOCK") {
             let lower = v.to_lowercase();
             if ["1","true","yes"].contains(&lower.as_str()) { self.exchange.use_mock = true; }
@@ -118,9 +1939,17 @@ impl Default for Config {
                 name: "Mock".to_string(),
                 api_key: None,
                 api_secret: None,
+                api_passphrase: None,
                 base_url: None,
                 use_mock: true,
+                mode: None,
+                kind: ExchangeKind::default(),
+                user_stream: UserStreamConfig::default(),
+                fill_model: FillModel::default(),
+                paper: PaperConfig::default(),
+                fix: None,
             },
+            exchanges: Vec::new(),
             logging: LoggingConfig {
                 level: "info".to_string(),
                 file_path: None,
@@ -128,8 +1957,78 @@ impl Default for Config {
             prediction_api: PredictionApiConfig {
                 base_url: "http://127.0.0.1:8000".to_string(),
                 timeout_ms: Some(5000),
+                features: HashMap::new(),
+                feedback: FeedbackConfig::default(),
+                signal_feeds: Vec::new(),
             },
             futures: Some(FuturesDefaults { symbols: vec!["BTCUSDT".into(), "ETHUSDT".into()], leverage: 20, isolated: false, hedge: false }),
+            strategy_guard: StrategyGuardConfig::default(),
+            webhooks: Vec::new(),
+            notifications: Vec::new(),
+            allocation: AllocationConfig::default(),
+            reporting: ReportingConfig::default(),
+            watchdog: WatchdogConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            dead_man_switch: DeadManSwitchConfig::default(),
+            routing: RoutingConfig::default(),
+            submission_queue: SubmissionQueueConfig::default(),
+            external_data: ExternalDataConfig::default(),
+            funding_data: FundingDataConfig::default(),
+            fix_market_data: None,
+            degraded_mode: DegradedModeConfig::default(),
+            portfolio: PortfolioConfig::default(),
+            risk_limits: RiskLimitsConfig::default(),
+            daily_loss: DailyLossConfig::default(),
+            position_routing: PositionRoutingConfig::default(),
+            margin_monitor: MarginMonitorConfig::default(),
+            strategy_runtime: StrategyRuntimeConfig::default(),
+            candle_aggregation: CandleAggregationConfig::default(),
+            market_data_recorder: MarketDataRecorderConfig::default(),
+            shutdown: ShutdownConfig::default(),
+            hot_reload: HotReloadConfig::default(),
+            backtest_jobs: BacktestJobsConfig::default(),
+            strategies: default_strategies(),
+            order_snapshot_path: None,
+            order_store_path: None,
+            order_store_url: None,
+            strategy_state_path: None,
+            strategy_state_db: None,
+            conditional_orders_path: None,
+            trade_journal_path: None,
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_specs() {
+        let json = r#"{"strategies": [
+            {"type": "ma_crossover", "symbol": "BTCUSDT", "fast_period": 5, "slow_period": 20},
+            {"type": "twap", "enabled": false, "symbol": "ETHUSDT", "side": "Sell", "quantity": 2.0, "execution_interval_ms": 3600000, "slices": 12}
+        ]}"#;
+        #[derive(Deserialize)]
+        struct Section { #[serde(default = "default_strategies")] strategies: Vec<StrategyDeployment> }
+
+        let section: Section = serde_json::from_str(json).unwrap();
+        assert_eq!(section.strategies.len(), 2);
+        assert!(section.strategies[0].enabled);
+        assert!(!section.strategies[1].enabled);
+        assert!(section.strategies.iter().all(|d| d.spec.validate().is_ok()));
+
+        let defaults: Section = serde_json::from_str("{}").unwrap();
+        assert_eq!(defaults.strategies.len(), 6);
+
+        let bad = StrategySpec::MaCrossover { symbol: "BTCUSDT".into(), fast_period: 30, slow_period: 10 };
+        assert!(bad.validate().is_err());
+        let mtf: StrategySpec = serde_json::from_str(r#"{"type": "multi_timeframe", "symbol": "BTCUSDT",
+            "entry": {"indicator": "rsi", "period": 14, "oversold": 30.0, "overbought": 70.0},
+            "trend": {"indicator": "ma_crossover", "fast_period": 50, "slow_period": 200},
+            "entry_timeframe": "1h", "trend_timeframe": "5m"}"#).unwrap();
+        assert!(mtf.validate().unwrap_err().contains("trend_timeframe"));
+        let mut config = Config::default();
+        config.strategies.push(StrategyDeployment { enabled: false, spec: bad, protection: None, exits: None, timeframe: None, exchange: None, shadow: false, schedule: None });
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,481 @@
+//! 전략별 자본 배분 모듈
+//!
+//! 최근 위험 조정 성과(수익 안정성, 낙폭)를 기준으로 전략 간 자본 예산을 주기적으로 재배분하고
+//! 모든 배분 변경 내역을 감사 로그로 남긴다. 운영자가 고정한 예산은 재배분에서 제외되며,
+//! 체결로 사용 중인 명목 금액을 추적해 예산을 넘는 주문을 차단하거나 축소한다.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+
+use crate::config::{AllocationConfig, BudgetEnforcement};
+use crate::error::TradingError;
+use crate::models::decimal::{to_decimal, to_f64, Decimal};
+use crate::models::order::{Order, OrderSide};
+
+/// 배분 변경 기록
+#[derive(Debug, Clone, Serialize)]
+pub struct AllocationChange {
+    pub timestamp: i64,
+    pub strategy: String,
+    pub old_budget: f64,
+    pub new_budget: f64,
+    pub reason: String,
+}
+
+/// 전략 예산 사용 현황 (/allocations 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetUsage {
+    pub strategy: String,
+    pub budget: f64,
+    /// 보유 포지션의 진입 명목 금액 합계
+    pub consumed: f64,
+    pub remaining: f64,
+    /// 운영자가 고정한 예산 (재배분 제외)
+    pub pinned: bool,
+}
+
+/// 예산 검사 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetDecision {
+    Allow,
+    /// 남은 예산에 맞춰 줄인 수량
+    Scale(Decimal),
+    Block(String),
+}
+
+/// 심볼별 순포지션 (부호 있는 수량, 평균 진입가)
+#[derive(Debug, Clone, Copy, Default)]
+struct Exposure {
+    quantity: f64,
+    avg_price: f64,
+}
+
+impl Exposure {
+    fn apply(&mut self, signed_qty: f64, price: f64) {
+        let next = self.quantity + signed_qty;
+        if self.quantity == 0.0 || self.quantity.signum() == signed_qty.signum() {
+            // 같은 방향 추가 - 평균 진입가 갱신
+            self.avg_price = (self.quantity.abs() * self.avg_price + signed_qty.abs() * price) / next.abs();
+        } else if next != 0.0 && next.signum() != self.quantity.signum() {
+            // 반대 방향으로 넘어가면 남은 수량은 체결가로 새로 진입
+            self.avg_price = price;
+        }
+        self.quantity = if next.abs() < 1e-12 { 0.0 } else { next };
+    }
+
+    fn notional(&self) -> f64 {
+        self.quantity.abs() * self.avg_price
+    }
+}
+
+fn signed(side: &OrderSide, quantity: f64) -> f64 {
+    match side {
+        OrderSide::Buy => quantity,
+        OrderSide::Sell => -quantity,
+    }
+}
+
+/// 전략별 성과 추적 상태
+#[derive(Debug, Clone, Default)]
+struct PerformanceWindow {
+    /// 최근 실현 손익 (최대 lookback 개)
+    pnls: VecDeque<f64>,
+    /// 누적 손익
+    cumulative: f64,
+    /// 누적 손익 고점
+    peak: f64,
+}
+
+impl PerformanceWindow {
+    fn drawdown(&self) -> f64 {
+        (self.peak - self.cumulative).max(0.0)
+    }
+
+    /// 손익 평균/표준편차 비율 (표본 부족 시 0)
+    fn risk_adjusted(&self) -> f64 {
+        if self.pnls.len() < 2 {
+            return 0.0;
+        }
+        let n = self.pnls.len() as f64;
+        let mean = self.pnls.iter().sum::<f64>() / n;
+        let var = self.pnls.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        if var <= 0.0 {
+            return mean.signum();
+        }
+        mean / var.sqrt()
+    }
+}
+
+/// 자본 배분기 - 전략 예산 관리 및 성과 기반 재배분
+pub struct CapitalAllocator {
+    config: AllocationConfig,
+    budgets: HashMap<String, f64>,
+    performance: HashMap<String, PerformanceWindow>,
+    last_rebalance: i64,
+    audit_trail: Vec<AllocationChange>,
+    /// 운영자가 고정한 예산
+    pinned: HashMap<String, f64>,
+    /// 전략 -> 심볼 -> 순포지션
+    exposure: HashMap<String, HashMap<String, Exposure>>,
+}
+
+impl CapitalAllocator {
+    /// 새 배분기 생성
+    pub fn new(config: AllocationConfig) -> Self {
+        CapitalAllocator {
+            config,
+            budgets: HashMap::new(),
+            performance: HashMap::new(),
+            last_rebalance: 0,
+            audit_trail: Vec::new(),
+            pinned: HashMap::new(),
+            exposure: HashMap::new(),
+        }
+    }
+
+    /// 배분 설정 변경
+    pub fn set_config(&mut self, config: AllocationConfig) {
+        self.config = config;
+    }
+
+    /// 전략 등록 - 전체 자본을 등록된 전략 수로 균등 분할
+    pub fn register(&mut self, strategy: &str, now: i64) {
+        if self.budgets.contains_key(strategy) {
+            return;
+        }
+        self.budgets.insert(strategy.to_string(), 0.0);
+        self.performance.insert(strategy.to_string(), PerformanceWindow::default());
+        let weights = self.equal_weights();
+        self.apply_weights(&weights, now, "strategy registered");
+    }
+
+    /// 전략 해제 - 남은 전략에 균등 재분배
+    pub fn unregister(&mut self, strategy: &str, now: i64) {
+        if self.budgets.remove(strategy).is_none() {
+            return;
+        }
+        self.performance.remove(strategy);
+        self.pinned.remove(strategy);
+        self.exposure.remove(strategy);
+        let weights = self.equal_weights();
+        self.apply_weights(&weights, now, "strategy removed");
+    }
+
+    /// 실현 손익 기록
+    pub fn record_pnl(&mut self, strategy: &str, pnl: f64) {
+        let lookback = self.config.lookback.max(2);
+        if let Some(window) = self.performance.get_mut(strategy) {
+            window.pnls.push_back(pnl);
+            if window.pnls.len() > lookback {
+                window.pnls.pop_front();
+            }
+            window.cumulative += pnl;
+            window.peak = window.peak.max(window.cumulative);
+        }
+    }
+
+    /// 예산 고정 - 고정 예산을 뺀 나머지 자본을 다른 전략에 균등 재분배
+    pub fn set_budget(&mut self, strategy: &str, budget: f64, now: i64) -> Result<Vec<AllocationChange>, TradingError> {
+        if !self.budgets.contains_key(strategy) {
+            return Err(TradingError::StrategyNotFound(strategy.to_string()));
+        }
+        if !budget.is_finite() || budget < 0.0 {
+            return Err(TradingError::InvalidParameter(format!("budget must be a non-negative amount, got {}", budget)));
+        }
+        let pinned_others: f64 = self.pinned.iter().filter(|(k, _)| k.as_str() != strategy).map(|(_, b)| b).sum();
+        if pinned_others + budget > self.config.total_capital + 1e-6 {
+            return Err(TradingError::InvalidParameter(format!(
+                "pinned budgets would total {:.2}, above total capital {:.2}", pinned_others + budget, self.config.total_capital
+            )));
+        }
+        self.pinned.insert(strategy.to_string(), budget);
+        let mut changes = self.set_pinned_budget(strategy, budget, now);
+        let weights = self.equal_weights();
+        changes.extend(self.apply_weights(&weights, now, "budget pinned"));
+        Ok(changes)
+    }
+
+    /// 고정 예산 해제 - 다시 자동 배분 대상으로 (해제할 고정 예산이 없으면 None)
+    pub fn clear_budget(&mut self, strategy: &str, now: i64) -> Option<Vec<AllocationChange>> {
+        self.pinned.remove(strategy)?;
+        let weights = self.equal_weights();
+        Some(self.apply_weights(&weights, now, "budget unpinned"))
+    }
+
+    fn set_pinned_budget(&mut self, strategy: &str, budget: f64, now: i64) -> Vec<AllocationChange> {
+        let old_budget = self.budgets.insert(strategy.to_string(), budget).unwrap_or(0.0);
+        if (budget - old_budget).abs() < 1e-6 {
+            return Vec::new();
+        }
+        let change = AllocationChange {
+            timestamp: now,
+            strategy: strategy.to_string(),
+            old_budget,
+            new_budget: budget,
+            reason: "budget pinned by operator".to_string(),
+        };
+        self.audit_trail.push(change.clone());
+        vec![change]
+    }
+
+    /// 체결 반영 - 전략의 심볼별 순포지션과 사용 중인 명목 금액 갱신
+    pub fn record_fill(&mut self, strategy: &str, symbol: &str, side: &OrderSide, quantity: f64, price: f64) {
+        if quantity <= 0.0 || price <= 0.0 {
+            return;
+        }
+        self.exposure.entry(strategy.to_string()).or_default()
+            .entry(symbol.to_string()).or_default()
+            .apply(signed(side, quantity), price);
+    }
+
+    /// 보유 포지션의 진입 명목 금액 합계
+    pub fn consumed(&self, strategy: &str) -> f64 {
+        self.exposure.get(strategy).map(|symbols| symbols.values().map(Exposure::notional).sum()).unwrap_or(0.0)
+    }
+
+    /// 남은 예산 (예산이 없는 전략은 None)
+    pub fn remaining(&self, strategy: &str) -> Option<f64> {
+        self.budget(strategy).map(|budget| (budget - self.consumed(strategy)).max(0.0))
+    }
+
+    /// 주문 예산 검사 - 포지션을 줄이는 부분은 항상 허용하고 늘리는 명목 금액만 남은 예산과 비교
+    pub fn check_order(&self, strategy: &str, order: &Order, price: f64) -> BudgetDecision {
+        let quantity = to_f64(order.quantity);
+        if self.config.enforcement == BudgetEnforcement::Off || price <= 0.0 || quantity <= 0.0 {
+            return BudgetDecision::Allow;
+        }
+        let Some(remaining) = self.remaining(strategy) else {
+            return BudgetDecision::Allow;
+        };
+        let current = self.exposure.get(strategy).and_then(|s| s.get(&order.symbol)).map(|e| e.quantity).unwrap_or(0.0);
+        let added_qty = ((current + signed(&order.side, quantity)).abs() - current.abs()).max(0.0);
+        if added_qty * price <= remaining + 1e-9 {
+            return BudgetDecision::Allow;
+        }
+
+        let excess_qty = added_qty - remaining / price;
+        let scaled = quantity - excess_qty;
+        let reason = format!(
+            "{} needs {:.2} of new exposure but only {:.2} of its budget remains", strategy, added_qty * price, remaining
+        );
+        match self.config.enforcement {
+            BudgetEnforcement::Scale if scaled > 1e-12 => BudgetDecision::Scale(to_decimal(scaled)),
+            _ => BudgetDecision::Block(reason),
+        }
+    }
+
+    /// 전략별 예산 사용 현황
+    pub fn usage(&self) -> Vec<BudgetUsage> {
+        let mut usage: Vec<BudgetUsage> = self.budgets.iter().map(|(name, budget)| {
+            let consumed = self.consumed(name);
+            BudgetUsage {
+                strategy: name.clone(),
+                budget: *budget,
+                consumed,
+                remaining: (budget - consumed).max(0.0),
+                pinned: self.pinned.contains_key(name),
+            }
+        }).collect();
+        usage.sort_by(|a, b| a.strategy.cmp(&b.strategy));
+        usage
+    }
+
+    /// 전략 예산 조회
+    pub fn budget(&self, strategy: &str) -> Option<f64> {
+        self.budgets.get(strategy).copied()
+    }
+
+    /// 전체 예산 조회
+    pub fn budgets(&self) -> &HashMap<String, f64> {
+        &self.budgets
+    }
+
+    /// 배분 변경 감사 로그
+    pub fn audit_trail(&self) -> &[AllocationChange] {
+        &self.audit_trail
+    }
+
+    /// 재배분 주기 도래 여부
+    pub fn should_rebalance(&self, now: i64) -> bool {
+        self.config.rebalance_interval_ms > 0
+            && !self.budgets.is_empty()
+            && now - self.last_rebalance >= self.config.rebalance_interval_ms
+    }
+
+    /// 성과 기반 재배분 - 변경된 항목 반환
+    pub fn rebalance(&mut self, now: i64) -> Vec<AllocationChange> {
+        self.last_rebalance = now;
+        if self.budgets.is_empty() {
+            return Vec::new();
+        }
+
+        // 점수 = (1 + 위험조정수익) x (1 - 낙폭비율 x 페널티)
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for (name, budget) in self.budgets.iter().filter(|(k, _)| !self.pinned.contains_key(*k)) {
+            let window = self.performance.get(name).cloned().unwrap_or_default();
+            let base = (1.0 + window.risk_adjusted()).max(0.05);
+            let dd_ratio = if *budget > 0.0 { (window.drawdown() / budget).min(1.0) } else { 0.0 };
+            let penalty = (1.0 - dd_ratio * self.config.drawdown_penalty).max(0.05);
+            scores.insert(name.clone(), base * penalty);
+        }
+
+        if scores.is_empty() {
+            return Vec::new();
+        }
+        let weights = self.bounded_weights(&scores);
+        self.apply_weights(&weights, now, "performance rebalance")
+    }
+
+    /// 고정 예산을 제외한 전략에 균등 비중
+    fn equal_weights(&self) -> HashMap<String, f64> {
+        let auto: Vec<&String> = self.budgets.keys().filter(|k| !self.pinned.contains_key(*k)).collect();
+        let n = auto.len().max(1) as f64;
+        auto.into_iter().map(|k| (k.clone(), 1.0 / n)).collect()
+    }
+
+    /// 자동 배분 대상 자본 (전체 자본 - 고정 예산)
+    fn auto_capital(&self) -> f64 {
+        (self.config.total_capital - self.pinned.values().sum::<f64>()).max(0.0)
+    }
+
+    /// 점수를 비중으로 정규화하고 [min_weight, max_weight] 범위로 제한
+    /// (범위를 벗어난 전략은 경계값으로 고정하고 나머지 비중을 다시 점수 비례로 분배)
+    fn bounded_weights(&self, scores: &HashMap<String, f64>) -> HashMap<String, f64> {
+        let n = scores.len() as f64;
+        let min_w = self.config.min_weight.min(1.0 / n);
+        let max_w = self.config.max_weight.max(1.0 / n);
+        let mut fixed: HashMap<String, f64> = HashMap::new();
+
+        loop {
+            let remaining = 1.0 - fixed.values().sum::<f64>();
+            let free: Vec<(&String, f64)> = scores.iter()
+                .filter(|(k, _)| !fixed.contains_key(*k))
+                .map(|(k, s)| (k, *s))
+                .collect();
+            if free.is_empty() {
+                return fixed;
+            }
+
+            let free_total: f64 = free.iter().map(|(_, s)| s).sum();
+            let weights: Vec<(&String, f64)> = free.iter()
+                .map(|(k, s)| (*k, if free_total > 0.0 { remaining * s / free_total } else { remaining / free.len() as f64 }))
+                .collect();
+
+            // 상한 초과분을 먼저 고정하고, 없을 때만 하한 미달분을 고정 (동시 고정 시 합계가 1에서 벗어남)
+            let over: Vec<&String> = weights.iter().filter(|(_, w)| *w > max_w).map(|(k, _)| *k).collect();
+            let under: Vec<&String> = weights.iter().filter(|(_, w)| *w < min_w).map(|(k, _)| *k).collect();
+            if !over.is_empty() {
+                fixed.extend(over.into_iter().map(|k| (k.clone(), max_w)));
+            } else if !under.is_empty() {
+                fixed.extend(under.into_iter().map(|k| (k.clone(), min_w)));
+            } else {
+                fixed.extend(weights.into_iter().map(|(k, w)| (k.clone(), w)));
+                return fixed;
+            }
+        }
+    }
+
+    fn apply_weights(&mut self, weights: &HashMap<String, f64>, now: i64, reason: &str) -> Vec<AllocationChange> {
+        let mut changes = Vec::new();
+        for (name, weight) in weights {
+            let new_budget = self.auto_capital() * weight;
+            let old_budget = self.budgets.get(name).copied().unwrap_or(0.0);
+            if (new_budget - old_budget).abs() < 1e-6 {
+                continue;
+            }
+            self.budgets.insert(name.clone(), new_budget);
+            changes.push(AllocationChange {
+                timestamp: now,
+                strategy: name.clone(),
+                old_budget,
+                new_budget,
+                reason: reason.to_string(),
+            });
+        }
+        self.audit_trail.extend(changes.iter().cloned());
+        changes
+    }
+}
+
+impl Default for CapitalAllocator {
+    fn default() -> Self {
+        Self::new(AllocationConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::OrderType;
+
+    #[test]
+    fn test_drawdown_aware_rebalance() {
+        let mut allocator = CapitalAllocator::new(AllocationConfig {
+            total_capital: 10000.0,
+            min_weight: 0.1,
+            max_weight: 0.8,
+            rebalance_interval_ms: 1000,
+            lookback: 20,
+            drawdown_penalty: 1.0,
+            enforcement: BudgetEnforcement::Off,
+        });
+
+        allocator.register("stable", 0);
+        allocator.register("losing", 0);
+        assert_eq!(allocator.budget("stable"), Some(5000.0));
+
+        for _ in 0..10 {
+            allocator.record_pnl("stable", 50.0);
+            allocator.record_pnl("losing", -200.0);
+        }
+
+        assert!(allocator.should_rebalance(1000));
+        let changes = allocator.rebalance(1000);
+        assert!(!changes.is_empty());
+
+        let stable = allocator.budget("stable").unwrap();
+        let losing = allocator.budget("losing").unwrap();
+        assert!(stable > losing);
+        assert!(losing >= 1000.0 - 1e-6);
+        assert!((stable + losing - 10000.0).abs() < 1e-6);
+        assert!(allocator.audit_trail().len() >= 4);
+    }
+
+    #[test]
+    fn test_pinned_budget_enforcement() {
+        let mut allocator = CapitalAllocator::new(AllocationConfig { enforcement: BudgetEnforcement::Block, ..AllocationConfig::default() });
+        allocator.register("a", 0);
+        allocator.register("b", 0);
+        allocator.register("c", 0);
+        assert!(allocator.set_budget("a", 20_000.0, 1).is_err());
+        allocator.set_budget("a", 1_000.0, 1).unwrap();
+        assert_eq!(allocator.budget("a"), Some(1_000.0));
+        assert_eq!(allocator.budget("b"), Some(4_500.0));
+        // 고정 예산은 재배분 대상이 아님
+        allocator.rebalance(2);
+        assert_eq!(allocator.budget("a"), Some(1_000.0));
+
+        // 0.015 BTC @ 50000 = 750 사용, 남은 250
+        allocator.record_fill("a", "BTCUSDT", &OrderSide::Buy, 0.015, 50_000.0);
+        assert!((allocator.remaining("a").unwrap() - 250.0).abs() < 1e-9);
+        let buy = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(0.01), dec!(0));
+        assert!(matches!(allocator.check_order("a", &buy, 50_000.0), BudgetDecision::Block(_)));
+        // 포지션 축소는 예산과 무관하게 허용, 반대 방향 초과분만 검사
+        let sell = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, dec!(0.02), dec!(0));
+        assert_eq!(allocator.check_order("a", &sell, 50_000.0), BudgetDecision::Allow);
+
+        allocator.set_config(AllocationConfig { enforcement: BudgetEnforcement::Scale, ..AllocationConfig::default() });
+        match allocator.check_order("a", &buy, 50_000.0) {
+            BudgetDecision::Scale(qty) => assert!((to_f64(qty) - 0.005).abs() < 1e-9),
+            other => panic!("expected scale, got {:?}", other),
+        }
+
+        allocator.record_fill("a", "BTCUSDT", &OrderSide::Sell, 0.015, 51_000.0);
+        assert_eq!(allocator.consumed("a"), 0.0);
+        allocator.clear_budget("a", 3).unwrap();
+        assert!((allocator.budget("a").unwrap() - 10_000.0 / 3.0).abs() < 1e-6);
+        assert!(allocator.usage().iter().all(|u| !u.pinned));
+    }
+}
@@ -0,0 +1,637 @@
+//! 조건부(트리거) 주문
+//!
+//! 가격/지표 조건을 모두 만족하면 미리 등록한 주문을 제출한다
+//! (예: "RSI(14) < 25 이고 가격 < 60000 이면 BTC 0.5 시장가 매수").
+//! 지표는 시세를 주기별 캔들로 집계해 완성된 캔들로만 계산하고, 조건은 JSON 파일에 저장해
+//! 재시작 후에도 대기 중인 조건을 이어서 감시한다.
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::indicators::{ExponentialMovingAverage, Indicator, RelativeStrengthIndex, SimpleMovingAverage};
+use crate::market_data::aggregator::{parse_timeframe, CandleAggregator};
+use crate::models::decimal::Decimal;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderId, OrderSide, OrderType};
+use crate::order_core::manager::OrderManager;
+
+/// 새 지표에 재생할 완성 캔들 보관 개수 (시작 시 과거 데이터 조회 개수이기도 함)
+const HISTORY_CANDLES: usize = 200;
+
+/// 비교 연산자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompareOp {
+    Above,
+    Below,
+}
+
+impl CompareOp {
+    fn matches(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            CompareOp::Above => lhs > rhs,
+            CompareOp::Below => lhs < rhs,
+        }
+    }
+}
+
+/// 조건에 사용할 지표
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IndicatorKind {
+    Rsi { period: usize },
+    Sma { period: usize },
+    Ema { period: usize },
+}
+
+impl IndicatorKind {
+    fn period(self) -> usize {
+        match self {
+            IndicatorKind::Rsi { period } | IndicatorKind::Sma { period } | IndicatorKind::Ema { period } => period,
+        }
+    }
+
+    fn build(self) -> Box<dyn Indicator> {
+        match self {
+            IndicatorKind::Rsi { period } => Box::new(RelativeStrengthIndex::new(period, None, None)),
+            IndicatorKind::Sma { period } => Box::new(SimpleMovingAverage::new(period)),
+            IndicatorKind::Ema { period } => Box::new(ExponentialMovingAverage::new(period)),
+        }
+    }
+}
+
+fn default_timeframe() -> String { "1m".to_string() }
+
+/// 트리거 조건 (주문 하나의 조건은 모두 만족해야 발동)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Condition {
+    /// 현재가 비교
+    Price { op: CompareOp, value: f64 },
+    /// 주기별 캔들 지표 비교 (완성된 캔들 기준)
+    Indicator {
+        indicator: IndicatorKind,
+        #[serde(default = "default_timeframe")]
+        timeframe: String,
+        op: CompareOp,
+        value: f64,
+    },
+}
+
+impl Condition {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Condition::Price { value, .. } if *value <= 0.0 => Err("price condition needs a positive value".to_string()),
+            Condition::Indicator { indicator, timeframe, .. } => {
+                if indicator.period() == 0 {
+                    return Err(format!("{:?} needs a positive period", indicator));
+                }
+                parse_timeframe(timeframe).map(|_| ()).map_err(|e| e.to_string())
+            }
+            Condition::Price { .. } => Ok(()),
+        }
+    }
+}
+
+/// 조건부 주문 등록 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalOrderRequest {
+    pub symbol: String,
+    pub conditions: Vec<Condition>,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: Decimal,
+    /// 지정가 주문 가격 (시장가는 0)
+    #[serde(default)]
+    pub price: Decimal,
+    #[serde(default)]
+    pub strategy: Option<String>,
+}
+
+impl ConditionalOrderRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.symbol.is_empty() {
+            return Err("symbol is required".to_string());
+        }
+        if self.conditions.is_empty() {
+            return Err("at least one condition is required".to_string());
+        }
+        if self.quantity <= Decimal::ZERO {
+            return Err("quantity must be positive".to_string());
+        }
+        match self.order_type {
+            OrderType::Market => {}
+            OrderType::Limit if self.price > Decimal::ZERO => {}
+            OrderType::Limit => return Err("limit order needs a positive price".to_string()),
+            ref other => return Err(format!("{:?} is not supported, use market or limit", other)),
+        }
+        self.conditions.iter().try_for_each(Condition::validate)
+    }
+
+    fn order(&self) -> Order {
+        Order::new(self.symbol.clone(), self.side.clone(), self.order_type.clone(), self.quantity, self.price)
+    }
+}
+
+/// 조건부 주문 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionalOrderStatus {
+    /// 조건 감시 중
+    Pending,
+    /// 조건 충족 후 주문 제출됨
+    Triggered,
+    /// 조건 충족 후 주문 제출 실패
+    Failed,
+    Cancelled,
+}
+
+/// 등록된 조건부 주문
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConditionalOrder {
+    pub id: String,
+    #[serde(flatten)]
+    pub request: ConditionalOrderRequest,
+    pub status: ConditionalOrderStatus,
+    /// 발동 시 제출된 주문
+    pub order_id: Option<OrderId>,
+    /// 발동 시점 가격
+    pub triggered_price: Option<f64>,
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// 심볼/주기별 캔들 집계와 지표
+struct Series {
+    aggregator: CandleAggregator,
+    history: VecDeque<MarketData>,
+    indicators: HashMap<IndicatorKind, Box<dyn Indicator>>,
+    seeded: bool,
+}
+
+impl Series {
+    fn new(aggregator: CandleAggregator) -> Self {
+        Series { aggregator, history: VecDeque::new(), indicators: HashMap::new(), seeded: false }
+    }
+
+    /// 지표 추가 - 보관 중인 캔들을 재생해 바로 계산 가능하게 함
+    fn ensure_indicator(&mut self, kind: IndicatorKind) {
+        if self.indicators.contains_key(&kind) {
+            return;
+        }
+        let mut indicator = kind.build();
+        for candle in &self.history {
+            Self::feed(indicator.as_mut(), candle);
+        }
+        self.indicators.insert(kind, indicator);
+    }
+
+    fn push(&mut self, data: &MarketData) {
+        if let Some(candle) = self.aggregator.push(data) {
+            for indicator in self.indicators.values_mut() {
+                Self::feed(indicator.as_mut(), &candle);
+            }
+            self.history.push_back(candle);
+            if self.history.len() > HISTORY_CANDLES {
+                self.history.pop_front();
+            }
+        }
+    }
+
+    fn feed(indicator: &mut dyn Indicator, candle: &MarketData) {
+        if let Err(e) = indicator.update_ohlc(candle.open_f64(), candle.high_f64(), candle.low_f64(), candle.close_f64(), Some(candle.volume_f64())) {
+            log::debug!("{} update failed: {}", indicator.name(), e);
+        }
+    }
+
+    fn value(&self, kind: IndicatorKind) -> Option<f64> {
+        let indicator = self.indicators.get(&kind)?;
+        if !indicator.is_ready() {
+            return None;
+        }
+        indicator.calculate().ok().map(|r| r.value)
+    }
+}
+
+/// 조건 평가 엔진 - 입출력 없이 시세를 받아 발동할 주문을 결정
+#[derive(Default)]
+pub struct ConditionEngine {
+    orders: HashMap<String, ConditionalOrder>,
+    series: HashMap<(String, String), Series>,
+    prices: HashMap<String, f64>,
+}
+
+impl ConditionEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 등록 (복원 포함) - 지표 조건에 필요한 캔들 시리즈 준비
+    pub fn insert(&mut self, order: ConditionalOrder) -> Result<(), TradingError> {
+        if order.status == ConditionalOrderStatus::Pending {
+            for condition in &order.request.conditions {
+                if let Condition::Indicator { indicator, timeframe, .. } = condition {
+                    let key = (order.request.symbol.clone(), timeframe.clone());
+                    let series = match self.series.entry(key) {
+                        std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                        std::collections::hash_map::Entry::Vacant(entry) => {
+                            entry.insert(Series::new(CandleAggregator::from_timeframe(timeframe)?))
+                        }
+                    };
+                    series.ensure_indicator(*indicator);
+                }
+            }
+        }
+        self.orders.insert(order.id.clone(), order);
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ConditionalOrder> {
+        self.orders.get(id)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut ConditionalOrder> {
+        self.orders.get_mut(id)
+    }
+
+    pub fn orders(&self) -> impl Iterator<Item = &ConditionalOrder> {
+        self.orders.values()
+    }
+
+    /// 감시 중인 조건이 있는 심볼
+    pub fn pending_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = self.orders.values()
+            .filter(|o| o.status == ConditionalOrderStatus::Pending)
+            .map(|o| o.request.symbol.clone())
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+
+    /// 과거 캔들로 아직 채우지 않은 (심볼, 주기)
+    pub fn unseeded(&self, symbol: &str) -> Vec<String> {
+        self.series.iter()
+            .filter(|((s, _), series)| s == symbol && !series.seeded)
+            .map(|((_, timeframe), _)| timeframe.clone())
+            .collect()
+    }
+
+    /// 과거 캔들로 지표 초기화 (조회 실패 시에도 호출해 재시도하지 않도록 함)
+    pub fn seed(&mut self, symbol: &str, timeframe: &str, candles: &[MarketData]) {
+        if let Some(series) = self.series.get_mut(&(symbol.to_string(), timeframe.to_string())) {
+            for candle in candles {
+                series.push(candle);
+            }
+            series.seeded = true;
+        }
+    }
+
+    /// 조건 하나의 충족 여부 (지표가 준비되지 않았으면 불충족)
+    fn check(&self, symbol: &str, condition: &Condition) -> bool {
+        match condition {
+            Condition::Price { op, value } => self.prices.get(symbol).is_some_and(|price| op.matches(*price, *value)),
+            Condition::Indicator { indicator, timeframe, op, value } => self.series
+                .get(&(symbol.to_string(), timeframe.clone()))
+                .and_then(|series| series.value(*indicator))
+                .is_some_and(|current| op.matches(current, *value)),
+        }
+    }
+
+    /// 시세 반영 후 조건을 모두 만족한 주문을 발동 상태로 바꾸고 제출할 주문 반환
+    pub fn on_market_data(&mut self, data: &MarketData, now: i64) -> Vec<(String, Option<String>, Order)> {
+        self.prices.insert(data.symbol.clone(), data.close_f64());
+        for ((symbol, _), series) in self.series.iter_mut() {
+            if *symbol == data.symbol {
+                series.push(data);
+            }
+        }
+
+        let ready: Vec<String> = self.orders.values()
+            .filter(|o| o.status == ConditionalOrderStatus::Pending && o.request.symbol == data.symbol)
+            .filter(|o| o.request.conditions.iter().all(|c| self.check(&data.symbol, c)))
+            .map(|o| o.id.clone())
+            .collect();
+
+        let mut fired = Vec::new();
+        for id in ready {
+            let Some(order) = self.orders.get_mut(&id) else { continue };
+            // 제출 전에 상태를 바꿔 다음 시세에서 중복 발동하지 않도록 함
+            order.status = ConditionalOrderStatus::Triggered;
+            order.triggered_price = Some(data.close_f64());
+            order.updated_at = now;
+            fired.push((id, order.request.strategy.clone(), order.request.order()));
+        }
+        self.prune();
+        fired
+    }
+
+    /// 제출 결과 기록
+    pub fn mark_submitted(&mut self, id: &str, result: Result<OrderId, TradingError>, now: i64) {
+        let Some(order) = self.orders.get_mut(id) else { return };
+        match result {
+            Ok(order_id) => order.order_id = Some(order_id),
+            Err(e) => {
+                order.status = ConditionalOrderStatus::Failed;
+                order.error = Some(e.to_string());
+            }
+        }
+        order.updated_at = now;
+    }
+
+    /// 감시 중인 조건이 더 이상 참조하지 않는 캔들 시리즈 정리
+    fn prune(&mut self) {
+        let orders = &self.orders;
+        self.series.retain(|(symbol, timeframe), _| {
+            orders.values().any(|o| {
+                o.status == ConditionalOrderStatus::Pending
+                    && o.request.symbol == *symbol
+                    && o.request.conditions.iter().any(|c| matches!(c, Condition::Indicator { timeframe: tf, .. } if tf == timeframe))
+            })
+        });
+    }
+}
+
+/// 조건부 주문 저장소 (JSON 파일, 전체 스냅샷 교체)
+pub struct JsonFileConditionalOrderStore {
+    path: PathBuf,
+}
+
+impl JsonFileConditionalOrderStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        JsonFileConditionalOrderStore { path: path.into() }
+    }
+
+    /// 파일이 없으면 빈 목록 (최초 실행)
+    pub fn load_all(&self) -> Result<Vec<ConditionalOrder>, TradingError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut contents = String::new();
+        File::open(&self.path)?.read_to_string(&mut contents)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// 임시 파일에 쓴 뒤 교체 (쓰기 도중 종료되어도 기존 스냅샷 보존)
+    pub fn save_all(&self, orders: &[ConditionalOrder]) -> Result<(), TradingError> {
+        let tmp = self.path.with_extension("tmp");
+        File::create(&tmp)?.write_all(serde_json::to_string_pretty(orders)?.as_bytes())?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// 조건부 주문 관리자 - HTTP 핸들러와 감시 태스크가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct ConditionalOrderManager {
+    order_manager: Arc<RwLock<OrderManager>>,
+    engine: Arc<Mutex<ConditionEngine>>,
+    store: Option<Arc<JsonFileConditionalOrderStore>>,
+}
+
+impl ConditionalOrderManager {
+    pub fn new(order_manager: Arc<RwLock<OrderManager>>) -> Self {
+        ConditionalOrderManager {
+            order_manager,
+            engine: Arc::new(Mutex::new(ConditionEngine::new())),
+            store: None,
+        }
+    }
+
+    /// 저장소 설정 - 저장된 조건부 주문을 복원하고 이후 변경마다 저장
+    pub fn with_store(mut self, store: JsonFileConditionalOrderStore) -> Result<Self, TradingError> {
+        {
+            let mut engine = self.engine();
+            for order in store.load_all()? {
+                engine.insert(order)?;
+            }
+        }
+        self.store = Some(Arc::new(store));
+        Ok(self)
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    fn engine(&self) -> std::sync::MutexGuard<'_, ConditionEngine> {
+        self.engine.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn persist(&self, engine: &ConditionEngine) {
+        if let Some(store) = &self.store {
+            let orders: Vec<ConditionalOrder> = engine.orders().cloned().collect();
+            if let Err(e) = store.save_all(&orders) {
+                log::error!("conditional order store save failed: {}", e);
+            }
+        }
+    }
+
+    /// 조건부 주문 등록
+    pub fn create(&self, request: ConditionalOrderRequest) -> Result<ConditionalOrder, TradingError> {
+        request.validate().map_err(TradingError::InvalidParameter)?;
+        let now = Self::now();
+        let order = ConditionalOrder {
+            id: format!("cond-{}", Uuid::new_v4()),
+            request,
+            status: ConditionalOrderStatus::Pending,
+            order_id: None,
+            triggered_price: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let mut engine = self.engine();
+        engine.insert(order.clone())?;
+        self.persist(&engine);
+        log::info!("conditional order {} registered for {}", order.id, order.request.symbol);
+        Ok(order)
+    }
+
+    /// 대기 중인 조건부 주문 취소 (이미 발동/종료된 주문은 그대로 반환)
+    pub fn cancel(&self, id: &str) -> Result<ConditionalOrder, TradingError> {
+        let mut engine = self.engine();
+        let order = engine.get_mut(id).ok_or_else(|| TradingError::DataNotFound(format!("conditional order {}", id)))?;
+        if order.status != ConditionalOrderStatus::Pending {
+            return Ok(order.clone());
+        }
+        order.status = ConditionalOrderStatus::Cancelled;
+        order.updated_at = Self::now();
+        let order = order.clone();
+        engine.prune();
+        self.persist(&engine);
+        Ok(order)
+    }
+
+    pub fn get(&self, id: &str) -> Option<ConditionalOrder> {
+        self.engine().get(id).cloned()
+    }
+
+    /// 전체 조건부 주문 (최신순)
+    pub fn list(&self) -> Vec<ConditionalOrder> {
+        let mut orders: Vec<ConditionalOrder> = self.engine().orders().cloned().collect();
+        orders.sort_by_key(|o| std::cmp::Reverse(o.created_at));
+        orders
+    }
+
+    /// 시세 반영 - 발동한 주문을 제출 큐로 제출
+    pub async fn on_market_data(&self, data: &MarketData) {
+        let fired = {
+            let mut engine = self.engine();
+            let fired = engine.on_market_data(data, Self::now());
+            if !fired.is_empty() {
+                self.persist(&engine);
+            }
+            fired
+        };
+        if fired.is_empty() {
+            return;
+        }
+
+        let queue = self.order_manager.read().await.submission_queue();
+        for (id, strategy, order) in fired {
+            let result = queue.submit(strategy, order).await;
+            match &result {
+                Ok(order_id) => log::info!("conditional order {} triggered at {}: {}", id, data.close, order_id),
+                Err(e) => log::error!("conditional order {} triggered but submission failed: {}", id, e),
+            }
+            let mut engine = self.engine();
+            engine.mark_submitted(&id, result, Self::now());
+            self.persist(&engine);
+        }
+    }
+
+    /// 지표 조건의 캔들 시리즈를 과거 데이터로 채움
+    async fn seed(&self, exchange: &Arc<RwLock<dyn Exchange>>, symbol: &str) {
+        let timeframes = self.engine().unseeded(symbol);
+        for timeframe in timeframes {
+            let Ok(timeframe_ms) = parse_timeframe(&timeframe) else { continue };
+            let start = Self::now() - timeframe_ms * HISTORY_CANDLES as i64;
+            let history = exchange.read().await.get_historical_data(symbol, &timeframe, start, None, Some(HISTORY_CANDLES)).await;
+            let candles = history.unwrap_or_else(|e| {
+                log::warn!("history for {} {} unavailable, indicators warm up from live data: {}", symbol, timeframe, e);
+                Vec::new()
+            });
+            self.engine().seed(symbol, &timeframe, &candles);
+        }
+    }
+
+    /// 감시 태스크 시작 - 대기 중인 조건이 있는 심볼의 시세를 주기적으로 조회해 평가
+    pub fn start(&self, exchange: Arc<RwLock<dyn Exchange>>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let symbols = manager.engine().pending_symbols();
+                for symbol in symbols {
+                    manager.seed(&exchange, &symbol).await;
+                    let market_data = exchange.read().await.get_market_data(&symbol).await;
+                    match market_data {
+                        Ok(data) => manager.on_market_data(&data).await,
+                        Err(e) => log::warn!("conditional orders: market data fetch failed for {}: {}", symbol, e),
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    fn candle(minute: i64, price: f64) -> MarketData {
+        MarketData::new("BTCUSDT", minute * 60_000, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_rsi_and_price_trigger() {
+        let request: ConditionalOrderRequest = serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT", "side": "Buy", "order_type": "Market", "quantity": 0.5,
+            "conditions": [
+                {"type": "indicator", "indicator": {"kind": "rsi", "period": 14}, "op": "below", "value": 25.0},
+                {"type": "price", "op": "below", "value": 60000.0}
+            ]
+        })).unwrap();
+        assert!(request.validate().is_ok());
+        let order = ConditionalOrder {
+            id: "cond-1".to_string(),
+            request,
+            status: ConditionalOrderStatus::Pending,
+            order_id: None,
+            triggered_price: None,
+            error: None,
+            created_at: 0,
+            updated_at: 0,
+        };
+
+        // 과거 캔들은 상승 추세 -> RSI 높음
+        let mut engine = ConditionEngine::new();
+        engine.insert(order.clone()).unwrap();
+        assert_eq!(engine.unseeded("BTCUSDT"), vec!["1m".to_string()]);
+        let history: Vec<MarketData> = (0..20).map(|m| candle(m, 61_000.0 + m as f64 * 100.0)).collect();
+        engine.seed("BTCUSDT", "1m", &history);
+        assert!(engine.unseeded("BTCUSDT").is_empty());
+
+        // 급락 중: 가격 조건은 곧 충족되지만 RSI 는 완성된 캔들로만 갱신
+        let mut fired = Vec::new();
+        for (i, minute) in (20..40).enumerate() {
+            fired = engine.on_market_data(&candle(minute, 62_000.0 - i as f64 * 400.0), minute * 60_000);
+            if !fired.is_empty() {
+                break;
+            }
+        }
+        assert_eq!(fired.len(), 1);
+        let (id, _, submitted) = &fired[0];
+        assert_eq!((submitted.side.clone(), submitted.order_type.clone(), submitted.quantity), (OrderSide::Buy, OrderType::Market, dec!(0.5)));
+        assert!(engine.get(id).unwrap().triggered_price.unwrap() < 60_000.0);
+        assert_eq!(engine.get(id).unwrap().status, ConditionalOrderStatus::Triggered);
+
+        // 발동 후에는 다시 발동하지 않음
+        assert!(engine.on_market_data(&candle(41, 50_000.0), 41 * 60_000).is_empty());
+        engine.mark_submitted(id, Ok(OrderId("42".into())), 41 * 60_000);
+
+        // 저장 후 복원
+        let path = std::env::temp_dir().join(format!("xquant_conditional_orders_{}.json", std::process::id()));
+        let store = JsonFileConditionalOrderStore::new(&path);
+        store.save_all(&engine.orders().cloned().chain(std::iter::once(ConditionalOrder { id: "cond-2".into(), ..order })).collect::<Vec<_>>()).unwrap();
+        let mut restored = ConditionEngine::new();
+        for order in store.load_all().unwrap() {
+            restored.insert(order).unwrap();
+        }
+        std::fs::remove_file(&path).ok();
+        assert_eq!(restored.get("cond-1").unwrap().order_id, Some(OrderId("42".into())));
+        assert_eq!(restored.pending_symbols(), vec!["BTCUSDT".to_string()]);
+    }
+
+    #[test]
+    fn test_request_validation() {
+        let mut request = ConditionalOrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            conditions: vec![Condition::Price { op: CompareOp::Above, value: 70_000.0 }],
+            side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            quantity: dec!(1),
+            price: dec!(0),
+            strategy: None,
+        };
+        assert!(request.validate().is_err());
+        request.price = dec!(70_100);
+        assert!(request.validate().is_ok());
+        request.conditions.push(Condition::Indicator { indicator: IndicatorKind::Ema { period: 20 }, timeframe: "7x".into(), op: CompareOp::Above, value: 1.0 });
+        assert!(request.validate().is_err());
+    }
+}
@@ -0,0 +1,429 @@
+//! 설정 핫 리로드 모듈
+//!
+//! 설정 파일이 바뀌거나 `POST /config/reload` 가 호출되면 파일을 다시 읽어 기존 설정과 섹션 단위로
+//! 비교하고, 실행 중 교체해도 안전한 섹션(전략 가드/자본 배분/리포트/워치독 한도, 인라인 라우팅 규칙,
+//! 선물 레버리지, 전략 배포 목록, 포트폴리오 노출 한도, 일일 손실 한도)만 적용한다. 거래소 연결, 저장소 경로처럼 재시작이 필요한 섹션은
+//! 적용하지 않고 보고만 한다.
+//!
+//! 배포 목록에 새 심볼이 생기면 시세 구독과 전략 런타임 시작을 요청하고, 명세가 바뀐 실행 전략
+//! (TWAP/VWAP/IS/아이스버그)은 다시 만들되 실행한 수량 등 진행 상태와 미체결 분할 주문을 이어받는다.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use tokio::sync::{mpsc, RwLock};
+
+use crate::config::{Config, ExchangeMode, StrategyDeployment};
+use crate::core::daily_loss::DailyLossTracker;
+use crate::core::risk_manager::{ExposureLimits, SharedExposureLimits};
+use crate::core::strategy_manager::StrategyManager;
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::order_core::manager::OrderManager;
+use crate::order_core::routing::OrderRouter;
+
+// 실행 중 적용하는 섹션 (나머지 섹션 변경은 재시작 필요)
+const HOT_SECTIONS: [&str; 9] = ["strategy_guard", "allocation", "reporting", "watchdog", "routing", "futures", "strategies", "risk_limits", "daily_loss"];
+
+/// 리로드 결과 (POST /config/reload 응답)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReloadReport {
+    /// 적용한 섹션
+    pub applied: Vec<String>,
+    /// 바뀌었지만 재시작해야 반영되는 섹션
+    pub restart_required: Vec<String>,
+    /// 전략 배포 변경 내역 (예: "added RSI 14")
+    pub strategy_changes: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ReloadReport {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+/// 바뀐 최상위 섹션을 (실행 중 적용 가능, 재시작 필요) 로 분류
+pub fn diff_sections(old: &Config, new: &Config) -> Result<(Vec<String>, Vec<String>), TradingError> {
+    let old = serde_json::to_value(old)?;
+    let new = serde_json::to_value(new)?;
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Ok((Vec::new(), Vec::new()));
+    };
+    let mut hot = Vec::new();
+    let mut restart = Vec::new();
+    for (key, value) in new {
+        if old.get(key) == Some(value) {
+            continue;
+        }
+        if HOT_SECTIONS.contains(&key.as_str()) {
+            hot.push(key.clone());
+        } else {
+            restart.push(key.clone());
+        }
+    }
+    Ok((hot, restart))
+}
+
+/// 전략 배포 목록 변경 계획 (전략 이름 기준, 활성 항목만)
+#[derive(Debug, Default, PartialEq)]
+pub struct DeploymentPlan {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// 전략 파라미터가 바뀌어 다시 생성해야 하는 전략
+    pub replaced: Vec<String>,
+    /// 타임프레임/거래소/보호 청산만 바뀐 전략
+    pub updated: Vec<String>,
+}
+
+fn deployments_by_name(deployments: &[StrategyDeployment]) -> Result<HashMap<String, &StrategyDeployment>, TradingError> {
+    let mut by_name = HashMap::new();
+    for deployment in deployments.iter().filter(|d| d.enabled) {
+        let strategy = crate::strategies::build_strategy(&deployment.spec)?;
+        by_name.insert(strategy.name().to_string(), deployment);
+    }
+    Ok(by_name)
+}
+
+pub fn plan_deployments(old: &[StrategyDeployment], new: &[StrategyDeployment]) -> Result<DeploymentPlan, TradingError> {
+    let old = deployments_by_name(old)?;
+    let new = deployments_by_name(new)?;
+    let mut plan = DeploymentPlan::default();
+    for (name, deployment) in &new {
+        match old.get(name) {
+            None => plan.added.push(name.clone()),
+            Some(prev) if prev.spec != deployment.spec => plan.replaced.push(name.clone()),
+            Some(prev) => {
+                if prev.timeframe != deployment.timeframe || prev.exchange != deployment.exchange || prev.protection != deployment.protection || prev.exits != deployment.exits || prev.shadow != deployment.shadow || prev.schedule != deployment.schedule {
+                    plan.updated.push(name.clone());
+                }
+            }
+        }
+    }
+    plan.removed = old.keys().filter(|name| !new.contains_key(*name)).cloned().collect();
+    for list in [&mut plan.added, &mut plan.removed, &mut plan.replaced, &mut plan.updated] {
+        list.sort();
+    }
+    Ok(plan)
+}
+
+/// 설정 리로더 - 파일 감시 태스크와 HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct ConfigReloader {
+    path: PathBuf,
+    // 리로드가 겹치지 않도록 적용이 끝날 때까지 잠금 유지
+    current: Arc<tokio::sync::Mutex<Config>>,
+    loaded_modified: Arc<Mutex<Option<SystemTime>>>,
+    strategy_manager: Arc<RwLock<StrategyManager>>,
+    order_manager: Arc<RwLock<OrderManager>>,
+    exchange: Arc<RwLock<dyn Exchange>>,
+    router: OrderRouter,
+    // 시세 스트림/전략 런타임이 도는 심볼
+    streamed_symbols: Arc<Mutex<Vec<String>>>,
+    // 새 심볼의 시세 구독/런타임 시작 요청 (없으면 새 심볼은 재시작해야 시세를 받음)
+    symbol_feed: Option<mpsc::UnboundedSender<String>>,
+    // 주문 검증기가 쓰는 노출 한도
+    exposure_limits: Option<SharedExposureLimits>,
+    // 주문 검증기/포트폴리오 추적기가 쓰는 일일 손실 추적기
+    daily_loss: Option<DailyLossTracker>,
+}
+
+impl ConfigReloader {
+    pub fn new(
+        path: impl Into<PathBuf>,
+        config: Config,
+        strategy_manager: Arc<RwLock<StrategyManager>>,
+        order_manager: Arc<RwLock<OrderManager>>,
+        exchange: Arc<RwLock<dyn Exchange>>,
+        router: OrderRouter,
+        streamed_symbols: Vec<String>,
+    ) -> Self {
+        let path = path.into();
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        ConfigReloader {
+            path,
+            current: Arc::new(tokio::sync::Mutex::new(config)),
+            loaded_modified: Arc::new(Mutex::new(modified)),
+            strategy_manager,
+            order_manager,
+            exchange,
+            router,
+            streamed_symbols: Arc::new(Mutex::new(streamed_symbols)),
+            symbol_feed: None,
+            exposure_limits: None,
+            daily_loss: None,
+        }
+    }
+
+    /// 새 심볼 요청을 받을 채널 연결
+    pub fn with_symbol_feed(mut self, feed: mpsc::UnboundedSender<String>) -> Self {
+        self.symbol_feed = Some(feed);
+        self
+    }
+
+    /// 리로드 시 교체할 노출 한도 핸들 연결
+    pub fn with_exposure_limits(mut self, limits: SharedExposureLimits) -> Self {
+        self.exposure_limits = Some(limits);
+        self
+    }
+
+    /// 리로드 시 한도/거래일 경계를 바꿀 일일 손실 추적기 연결
+    pub fn with_daily_loss(mut self, daily_loss: DailyLossTracker) -> Self {
+        self.daily_loss = Some(daily_loss);
+        self
+    }
+
+    /// 설정 파일이 수정되었으면 리로드 - 리로드했으면 결과 반환
+    pub async fn reload_if_changed(&self) -> Result<Option<ReloadReport>, TradingError> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        {
+            let mut loaded = self.loaded_modified.lock().unwrap_or_else(|e| e.into_inner());
+            if *loaded == Some(modified) {
+                return Ok(None);
+            }
+            // 잘못된 파일은 다음 수정 전까지 다시 시도하지 않음
+            *loaded = Some(modified);
+        }
+        self.reload().await.map(Some)
+    }
+
+    /// 설정 파일을 다시 읽어 안전한 변경만 적용 (읽기/검증 실패 시 기존 설정 유지)
+    pub async fn reload(&self) -> Result<ReloadReport, TradingError> {
+        let new = Config::load_from(&self.path)?;
+        let mut current = self.current.lock().await;
+        let (hot, restart) = diff_sections(&current, &new)?;
+        let mut report = ReloadReport { restart_required: restart, ..ReloadReport::default() };
+
+        for section in hot {
+            match self.apply_section(&section, &current, &new, &mut report).await {
+                Ok(()) => report.applied.push(section),
+                Err(e) => report.warnings.push(format!("{}: {}", section, e)),
+            }
+        }
+        if !report.restart_required.is_empty() {
+            log::warn!("config reload: restart required for {}", report.restart_required.join(", "));
+        }
+        if !report.is_empty() {
+            log::info!("config reloaded from {}: applied [{}]", self.path.display(), report.applied.join(", "));
+        }
+        *current = new;
+        Ok(report)
+    }
+
+    async fn apply_section(&self, section: &str, old: &Config, new: &Config, report: &mut ReloadReport) -> Result<(), TradingError> {
+        match section {
+            "strategy_guard" => self.strategy_manager.write().await.set_guard_config(new.strategy_guard.clone()),
+            "allocation" => self.strategy_manager.write().await.set_allocation_config(new.allocation.clone()),
+            "reporting" => self.strategy_manager.write().await.set_reporting_config(new.reporting.clone())?,
+            "watchdog" => self.strategy_manager.write().await.set_watchdog_config(new.watchdog.clone()),
+            "routing" => {
+                if old.routing.rules_path != new.routing.rules_path || old.routing.reload_interval_ms != new.routing.reload_interval_ms {
+                    report.restart_required.push("routing.rules_path".to_string());
+                }
+                // 규칙 파일을 쓰면 파일 감시가 규칙을 관리
+                if new.routing.rules_path.is_none() {
+                    self.router.set_rules(new.routing.rules.clone())?;
+                }
+            }
+            "futures" => self.apply_futures(new).await?,
+            "strategies" => self.apply_strategies(&old.strategies, &new.strategies, report).await?,
+            "risk_limits" => self.apply_risk_limits(new)?,
+            "daily_loss" => {
+                // 상태 파일 경로는 시작할 때만 읽음
+                if old.daily_loss.state_path != new.daily_loss.state_path {
+                    report.restart_required.push("daily_loss.state_path".to_string());
+                }
+                let daily_loss = self.daily_loss.as_ref()
+                    .ok_or_else(|| TradingError::ConfigError("daily loss tracker is not shared with the order manager".to_string()))?;
+                daily_loss.apply_config(&new.daily_loss, chrono::Utc::now().timestamp_millis())?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // 노출 한도 교체 (검증 실패 시 기존 한도 유지)
+    fn apply_risk_limits(&self, new: &Config) -> Result<(), TradingError> {
+        let shared = self.exposure_limits.as_ref()
+            .ok_or_else(|| TradingError::ConfigError("exposure limits are not shared with the order manager".to_string()))?;
+        let limits = ExposureLimits::new(new.risk_limits.clone())?;
+        *shared.write().unwrap_or_else(|e| e.into_inner()) = limits;
+        Ok(())
+    }
+
+    // 선물 레버리지/마진 모드/포지션 모드 재적용 (실거래 선물 거래소만)
+    async fn apply_futures(&self, new: &Config) -> Result<(), TradingError> {
+        let Some(futures) = &new.futures else { return Ok(()) };
+        if !new.exchange.kind.is_futures() || new.exchange.mode() != ExchangeMode::Live {
+            return Ok(());
+        }
+        let mut exchange = self.exchange.write().await;
+        exchange.set_futures_position_mode(futures.hedge).await?;
+        for symbol in &futures.symbols {
+            exchange.set_futures_margin_mode(symbol, futures.isolated).await?;
+            exchange.set_futures_leverage(symbol, futures.leverage).await?;
+        }
+        Ok(())
+    }
+
+    async fn apply_strategies(&self, old: &[StrategyDeployment], new: &[StrategyDeployment], report: &mut ReloadReport) -> Result<(), TradingError> {
+        let plan = plan_deployments(old, new)?;
+        let deployments = deployments_by_name(new)?;
+
+        // 다시 만드는 실행 전략의 진행 상태 (처음부터 다시 실행하지 않도록)
+        let mut carried = HashMap::new();
+        {
+            let manager = self.strategy_manager.read().await;
+            for name in plan.replaced.iter().filter(|name| manager.is_execution_strategy(name)) {
+                if let Some(state) = manager.export_strategy_state(name) {
+                    carried.insert(name.clone(), state);
+                }
+            }
+        }
+
+        // 제거/재생성되는 전략의 미체결 주문은 먼저 취소 (진행을 이어받는 실행 전략의 분할 주문은 유지)
+        for name in plan.removed.iter().chain(&plan.replaced) {
+            match self.strategy_manager.write().await.remove_strategy(name) {
+                Ok(()) => {}
+                Err(TradingError::StrategyNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+            if carried.contains_key(name) {
+                continue;
+            }
+            if let Err(e) = self.order_manager.read().await.cancel_strategy_orders(name).await {
+                report.warnings.push(format!("{} order cleanup failed: {}", name, e));
+            }
+            if plan.removed.contains(name) {
+                report.strategy_changes.push(format!("removed {}", name));
+            }
+        }
+
+        let mut manager = self.strategy_manager.write().await;
+        for name in plan.added.iter().chain(&plan.replaced).chain(&plan.updated) {
+            let deployment = deployments[name];
+            if !plan.updated.contains(name) {
+                manager.add_strategy_spec(deployment.spec.clone())?;
+                let verb = if plan.added.contains(name) { "added" } else { "replaced" };
+                report.strategy_changes.push(format!("{} {}", verb, name));
+                if let Some(state) = carried.get(name) {
+                    manager.restore_strategy_state(name, state)?;
+                    report.strategy_changes.push(format!("carried execution progress of {}", name));
+                }
+            } else {
+                report.strategy_changes.push(format!("updated {}", name));
+            }
+            manager.set_strategy_timeframe(name, deployment.timeframe.as_deref())?;
+            manager.set_strategy_exchange(name, deployment.exchange.as_deref())?;
+            manager.set_strategy_shadow(name, deployment.shadow)?;
+            manager.set_schedule(name, deployment.schedule.clone())?;
+            manager.set_protection(name, deployment.protection.clone())?;
+            manager.set_entry_exits(name, deployment.exits.clone())?;
+            self.ensure_symbol_stream(name, deployment.spec.symbol(), report);
+        }
+        Ok(())
+    }
+
+    // 새 심볼이면 시세 구독/전략 런타임 시작 요청
+    fn ensure_symbol_stream(&self, name: &str, symbol: &str, report: &mut ReloadReport) {
+        let mut streamed = self.streamed_symbols.lock().unwrap_or_else(|e| e.into_inner());
+        if streamed.iter().any(|s| s == symbol) {
+            return;
+        }
+        match self.symbol_feed.as_ref().map(|feed| feed.send(symbol.to_string())) {
+            Some(Ok(())) => {
+                streamed.push(symbol.to_string());
+                report.strategy_changes.push(format!("subscribed {}", symbol));
+            }
+            _ => report.warnings.push(format!("{}: no market data stream for {} until restart", name, symbol)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployments(json: &str) -> Vec<StrategyDeployment> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_diff_and_deployment_plan() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.strategy_guard.max_consecutive_losses = old.strategy_guard.max_consecutive_losses + 1;
+        new.server.port += 1;
+        new.risk_limits.max_open_positions = Some(3);
+        let (hot, restart) = diff_sections(&old, &new).unwrap();
+        assert_eq!(hot, vec!["risk_limits", "strategy_guard"]);
+        assert_eq!(restart, vec!["server"]);
+
+        let old = deployments(r#"[
+            {"type": "rsi", "symbol": "BTCUSDT", "period": 14, "oversold": 30, "overbought": 70},
+            {"type": "ma_crossover", "symbol": "BTCUSDT", "fast_period": 5, "slow_period": 20},
+            {"type": "bollinger", "symbol": "ETHUSDT", "period": 20, "std_dev": 2.0}
+        ]"#);
+        let new = deployments(r#"[
+            {"type": "rsi", "symbol": "BTCUSDT", "period": 14, "oversold": 25, "overbought": 70},
+            {"type": "ma_crossover", "symbol": "BTCUSDT", "fast_period": 5, "slow_period": 20, "timeframe": "1h"},
+            {"type": "macd", "symbol": "ETHUSDT", "fast_period": 12, "slow_period": 26, "signal_period": 9}
+        ]"#);
+        let plan = plan_deployments(&old, &new).unwrap();
+        assert_eq!(plan.added, vec!["MACD 12/26/9"]);
+        assert_eq!(plan.removed, vec!["Bollinger 20/2"]);
+        assert_eq!(plan.replaced, vec!["RSI 14"]);
+        assert_eq!(plan.updated, vec!["MA Crossover 5/20"]);
+        assert!(plan_deployments(&new, &new).unwrap() == DeploymentPlan::default());
+    }
+
+    #[tokio::test]
+    async fn test_reload_carries_execution_progress_and_feeds_new_symbols() {
+        use crate::exchange::mocks::MockExchange;
+        use crate::order_core::repository::InMemoryOrderRepository;
+
+        let exchange: Arc<RwLock<dyn Exchange>> = Arc::new(RwLock::new(MockExchange::new(Config::default())));
+        let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+        let order_manager = Arc::new(RwLock::new(OrderManager::new(exchange.clone(), repository)));
+        let strategy_manager = Arc::new(RwLock::new(StrategyManager::new()));
+        let (feed, mut symbols) = mpsc::unbounded_channel();
+        let reloader = ConfigReloader::new("missing.json", Config::default(), strategy_manager.clone(), order_manager, exchange, OrderRouter::default(), vec!["BTCUSDT".to_string()])
+            .with_symbol_feed(feed);
+
+        let old = deployments(r#"[{"type": "twap", "symbol": "BTCUSDT", "side": "Buy", "quantity": 1.0, "execution_interval_ms": 60000, "slices": 10}]"#);
+        let new = deployments(r#"[
+            {"type": "twap", "symbol": "BTCUSDT", "side": "Buy", "quantity": 1.0, "execution_interval_ms": 120000, "slices": 10},
+            {"type": "rsi", "symbol": "ETHUSDT", "period": 14, "oversold": 30, "overbought": 70}
+        ]"#);
+        reloader.apply_strategies(&[], &old, &mut ReloadReport::default()).await.unwrap();
+        let name = "TWAP-BTCUSDT";
+        let mut state = strategy_manager.read().await.export_strategy_state(name).unwrap();
+        state["executed_quantity"] = serde_json::json!("0.4");
+        strategy_manager.write().await.restore_strategy_state(name, &state).unwrap();
+
+        let mut report = ReloadReport::default();
+        reloader.apply_strategies(&old, &new, &mut report).await.unwrap();
+        // 간격이 바뀐 TWAP 은 다시 만들어도 실행한 수량을 이어받음
+        let carried = strategy_manager.read().await.export_strategy_state(name).unwrap();
+        assert_eq!(carried["executed_quantity"], serde_json::json!("0.4"));
+        // 새 심볼은 시세 구독/런타임 시작 요청 (이미 도는 심볼은 요청하지 않음)
+        assert_eq!(symbols.try_recv().unwrap(), "ETHUSDT");
+        assert!(symbols.try_recv().is_err());
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert!(report.strategy_changes.contains(&"subscribed ETHUSDT".to_string()));
+
+        // 노출 한도는 검증기와 공유한 핸들을 교체
+        let shared: SharedExposureLimits = Arc::new(std::sync::RwLock::new(ExposureLimits::new(Default::default()).unwrap()));
+        let reloader = reloader.with_exposure_limits(shared.clone());
+        let mut new = Config::default();
+        new.risk_limits.max_open_positions = Some(3);
+        reloader.apply_section("risk_limits", &Config::default(), &new, &mut ReloadReport::default()).await.unwrap();
+        assert_eq!(shared.read().unwrap().config().max_open_positions, Some(3));
+        new.risk_limits.max_open_positions = Some(0);
+        assert!(reloader.apply_section("risk_limits", &Config::default(), &new, &mut ReloadReport::default()).await.is_err());
+        assert_eq!(shared.read().unwrap().config().max_open_positions, Some(3));
+    }
+}
@@ -0,0 +1,307 @@
+//! 거래일 단위 일일 손실 집계
+//!
+//! 거래일은 설정한 UTC 오프셋의 현지 시각 기준 `rollover_hour` 시에 바뀐다 (예: KST 09시 = 오프셋 +540분, 9시).
+//! 실현 손실(수수료 포함)을 거래일마다 누적하고, 한도에 닿으면 포지션을 줄이는 주문 외의 신규 주문을 거부한다.
+//! 날짜가 바뀌면 조회/기록 시점에 자동으로 초기화되며, 상태 파일을 지정하면 재시작 후에도 당일 집계를 이어간다.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use chrono::{Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::DailyLossConfig;
+use crate::error::TradingError;
+use crate::models::order::Order;
+use crate::order_core::validator::OrderValidator;
+
+/// 거래일 경계 계산
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TradingDayClock {
+    utc_offset_minutes: i32,
+    rollover_hour: u32,
+}
+
+impl TradingDayClock {
+    pub fn new(utc_offset_minutes: i32, rollover_hour: u32) -> Self {
+        TradingDayClock { utc_offset_minutes, rollover_hour: rollover_hour.min(23) }
+    }
+
+    // 현지 시각에서 롤오버 시각만큼 당긴 시각 (이 값의 날짜가 거래일)
+    fn shift_ms(&self) -> i64 {
+        self.utc_offset_minutes as i64 * 60_000 - self.rollover_hour as i64 * 3_600_000
+    }
+
+    pub fn trading_day(&self, now_ms: i64) -> NaiveDate {
+        Utc.timestamp_millis_opt(now_ms + self.shift_ms()).single().unwrap_or_default().date_naive()
+    }
+
+    /// 다음 거래일이 시작되는 시각 (epoch ms)
+    pub fn next_rollover(&self, now_ms: i64) -> i64 {
+        let next = self.trading_day(now_ms) + Duration::days(1);
+        next.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp_millis() - self.shift_ms()
+    }
+}
+
+/// 일일 손실 현황 (`GET /risk/daily` 응답)
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DailyLossStatus {
+    pub trading_day: NaiveDate,
+    /// 당일 실현 손익 합계 (수수료 차감)
+    pub realized_pnl: f64,
+    /// 당일 손실 체결 누적액 (이익으로 상쇄하지 않음)
+    pub loss: f64,
+    pub limit: Option<f64>,
+    pub remaining: Option<f64>,
+    pub breached: bool,
+    pub next_reset_at: i64,
+}
+
+/// 재시작 후 이어서 집계할 당일 상태
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedDay {
+    trading_day: NaiveDate,
+    realized_pnl: f64,
+    loss: f64,
+}
+
+/// 거래일 단위 손실 집계 (입출력 없음)
+#[derive(Debug, Clone)]
+pub struct DailyLoss {
+    clock: TradingDayClock,
+    limit: Option<f64>,
+    trading_day: NaiveDate,
+    realized_pnl: f64,
+    loss: f64,
+}
+
+impl DailyLoss {
+    pub fn new(clock: TradingDayClock, limit: Option<f64>, now_ms: i64) -> Self {
+        DailyLoss { clock, limit, trading_day: clock.trading_day(now_ms), realized_pnl: 0.0, loss: 0.0 }
+    }
+
+    pub fn set_clock(&mut self, clock: TradingDayClock, now_ms: i64) {
+        self.clock = clock;
+        self.roll(now_ms);
+    }
+
+    /// 거래일이 바뀌었으면 초기화 - 바뀌었으면 true
+    pub fn roll(&mut self, now_ms: i64) -> bool {
+        let today = self.clock.trading_day(now_ms);
+        if today == self.trading_day {
+            return false;
+        }
+        log::info!("trading day {} closed: realized {:.2}, loss {:.2}", self.trading_day, self.realized_pnl, self.loss);
+        self.trading_day = today;
+        self.realized_pnl = 0.0;
+        self.loss = 0.0;
+        true
+    }
+
+    /// 실현 손익 기록 (음수만 손실로 누적)
+    pub fn record(&mut self, amount: f64, now_ms: i64) {
+        self.roll(now_ms);
+        self.realized_pnl += amount;
+        if amount < 0.0 {
+            self.loss += amount.abs();
+        }
+    }
+
+    /// 당일 집계 수동 초기화
+    pub fn reset(&mut self, now_ms: i64) {
+        self.trading_day = self.clock.trading_day(now_ms);
+        self.realized_pnl = 0.0;
+        self.loss = 0.0;
+    }
+
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+
+    pub fn is_breached(&mut self, now_ms: i64) -> bool {
+        self.roll(now_ms);
+        self.limit.is_some_and(|limit| self.loss >= limit)
+    }
+
+    pub fn status(&mut self, now_ms: i64) -> DailyLossStatus {
+        self.roll(now_ms);
+        DailyLossStatus {
+            trading_day: self.trading_day,
+            realized_pnl: self.realized_pnl,
+            loss: self.loss,
+            limit: self.limit,
+            remaining: self.limit.map(|limit| (limit - self.loss).max(0.0)),
+            breached: self.limit.is_some_and(|limit| self.loss >= limit),
+            next_reset_at: self.clock.next_rollover(now_ms),
+        }
+    }
+
+    fn persisted(&self) -> PersistedDay {
+        PersistedDay { trading_day: self.trading_day, realized_pnl: self.realized_pnl, loss: self.loss }
+    }
+
+    // 저장된 날이 현재 거래일이면 이어서 집계
+    fn restore(&mut self, day: PersistedDay) {
+        if day.trading_day == self.trading_day {
+            self.realized_pnl = day.realized_pnl;
+            self.loss = day.loss;
+        }
+    }
+}
+
+/// 일일 손실 추적기 - 포트폴리오 추적기(체결 기록), 주문 관리자(검증), HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct DailyLossTracker {
+    state: Arc<Mutex<DailyLoss>>,
+    path: Option<PathBuf>,
+}
+
+impl DailyLossTracker {
+    /// `state_path` 에 당일 집계가 있으면 복원
+    pub fn new(config: &DailyLossConfig, now_ms: i64) -> Result<Self, TradingError> {
+        let mut daily = DailyLoss::new(TradingDayClock::new(config.utc_offset_minutes, config.rollover_hour), config.max_daily_loss, now_ms);
+        let path = config.state_path.as_ref().map(PathBuf::from);
+        if let Some(path) = path.as_ref().filter(|p| p.exists()) {
+            let mut contents = String::new();
+            File::open(path)?.read_to_string(&mut contents)?;
+            let day: PersistedDay = serde_json::from_str(&contents)?;
+            daily.restore(day);
+            log::info!("daily loss restored for {}: loss {:.2}", daily.trading_day, daily.loss);
+        }
+        Ok(DailyLossTracker { state: Arc::new(Mutex::new(daily)), path })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, DailyLoss> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // 임시 파일에 쓴 뒤 교체
+    fn save(&self, daily: &DailyLoss) {
+        let Some(path) = &self.path else { return };
+        let tmp = path.with_extension("tmp");
+        let written = serde_json::to_string(&daily.persisted())
+            .map_err(std::io::Error::other)
+            .and_then(|json| File::create(&tmp)?.write_all(json.as_bytes()))
+            .and_then(|_| std::fs::rename(&tmp, path));
+        if let Err(e) = written {
+            log::warn!("daily loss state save failed ({}): {}", path.display(), e);
+        }
+    }
+
+    pub fn record_pnl(&self, amount: f64, now_ms: i64) {
+        let mut daily = self.lock();
+        let was_breached = daily.is_breached(now_ms);
+        daily.record(amount, now_ms);
+        if !was_breached && daily.is_breached(now_ms) {
+            log::error!("daily loss limit reached: {:.2} >= {:.2}, new positions blocked until {}", daily.loss, daily.limit.unwrap_or_default(), daily.clock.next_rollover(now_ms));
+        }
+        self.save(&daily);
+    }
+
+    /// 거래일 경계 확인 (주기 태스크) - 초기화되었으면 true
+    pub fn roll(&self, now_ms: i64) -> bool {
+        let mut daily = self.lock();
+        let rolled = daily.roll(now_ms);
+        if rolled {
+            self.save(&daily);
+        }
+        rolled
+    }
+
+    pub fn reset(&self, now_ms: i64) -> DailyLossStatus {
+        let mut daily = self.lock();
+        daily.reset(now_ms);
+        self.save(&daily);
+        daily.status(now_ms)
+    }
+
+    pub fn status(&self, now_ms: i64) -> DailyLossStatus {
+        self.lock().status(now_ms)
+    }
+
+    /// 설정 리로드 - 한도와 거래일 경계 교체 (당일 집계는 유지, 바뀐 경계로 거래일이 달라지면 초기화)
+    pub fn apply_config(&self, config: &DailyLossConfig, now_ms: i64) -> Result<(), TradingError> {
+        config.validate().map_err(TradingError::ConfigError)?;
+        let mut daily = self.lock();
+        daily.limit = config.max_daily_loss;
+        daily.set_clock(TradingDayClock::new(config.utc_offset_minutes, config.rollover_hour), now_ms);
+        self.save(&daily);
+        Ok(())
+    }
+
+    /// 한도 도달 후에는 reduce-only 주문만 통과
+    pub fn check(&self, order: &Order, now_ms: i64) -> Result<(), TradingError> {
+        let mut daily = self.lock();
+        if order.reduce_only == Some(true) || !daily.is_breached(now_ms) {
+            return Ok(());
+        }
+        Err(TradingError::RiskLimitExceeded(format!(
+            "daily loss {:.2} reached limit {:.2} for {}", daily.loss, daily.limit.unwrap_or_default(), daily.trading_day
+        )))
+    }
+}
+
+impl OrderValidator for DailyLossTracker {
+    fn validate(&self, order: &Order) -> Result<(), TradingError> {
+        self.check(order, Utc::now().timestamp_millis())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+
+    fn at(s: &str) -> i64 {
+        chrono::DateTime::parse_from_rfc3339(s).unwrap().timestamp_millis()
+    }
+
+    #[test]
+    fn test_trading_day_rollover_and_persistence() {
+        // KST 09:00 롤오버 = UTC 00:00
+        let clock = TradingDayClock::new(540, 9);
+        assert_eq!(clock.trading_day(at("2024-03-01T23:59:00Z")).to_string(), "2024-03-01");
+        assert_eq!(clock.trading_day(at("2024-03-02T00:00:00Z")).to_string(), "2024-03-02");
+        assert_eq!(clock.next_rollover(at("2024-03-01T12:00:00Z")), at("2024-03-02T00:00:00Z"));
+        // UTC 17시 롤오버 (뉴욕 장 마감 기준 등)
+        let ny = TradingDayClock::new(0, 17);
+        assert_eq!(ny.trading_day(at("2024-03-01T16:59:00Z")).to_string(), "2024-02-29");
+        assert_eq!(ny.next_rollover(at("2024-03-01T18:00:00Z")), at("2024-03-02T17:00:00Z"));
+
+        let path = std::env::temp_dir().join(format!("daily-loss-{}.json", uuid::Uuid::new_v4()));
+        let config = DailyLossConfig { max_daily_loss: Some(100.0), utc_offset_minutes: 0, rollover_hour: 0, state_path: Some(path.display().to_string()) };
+        let now = at("2024-03-01T10:00:00Z");
+        let tracker = DailyLossTracker::new(&config, now).unwrap();
+        tracker.record_pnl(-70.0, now);
+        tracker.record_pnl(50.0, now);
+        let status = tracker.status(now);
+        assert_eq!((status.realized_pnl, status.loss, status.remaining, status.breached), (-20.0, 70.0, Some(30.0), false));
+        tracker.record_pnl(-30.0, now);
+
+        // 한도 도달: 신규 주문 거부, reduce-only 는 통과
+        assert!(tracker.status(now).breached);
+        let mut order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(1), dec!(50_000));
+        assert!(matches!(tracker.check(&order, now), Err(TradingError::RiskLimitExceeded(_))));
+        order.reduce_only = Some(true);
+        assert!(tracker.check(&order, now).is_ok());
+
+        // 재시작해도 같은 거래일이면 이어서, 다음 날이면 초기화
+        let restored = DailyLossTracker::new(&config, at("2024-03-01T23:00:00Z")).unwrap();
+        assert_eq!(restored.status(at("2024-03-01T23:00:00Z")).loss, 100.0);
+        assert!(restored.roll(at("2024-03-02T00:00:01Z")));
+        assert!(!restored.status(at("2024-03-02T00:00:01Z")).breached);
+        let next_day = DailyLossTracker::new(&config, at("2024-03-02T01:00:00Z")).unwrap();
+        assert_eq!(next_day.status(at("2024-03-02T01:00:00Z")).loss, 0.0);
+
+        // 설정 리로드로 한도를 올리면 당일 집계는 그대로 두고 신규 주문 재개
+        restored.record_pnl(-100.0, at("2024-03-02T01:00:00Z"));
+        restored.apply_config(&DailyLossConfig { max_daily_loss: Some(150.0), ..config.clone() }, at("2024-03-02T02:00:00Z")).unwrap();
+        let status = restored.status(at("2024-03-02T02:00:00Z"));
+        assert_eq!((status.loss, status.breached), (100.0, false));
+        assert!(restored.apply_config(&DailyLossConfig { max_daily_loss: Some(0.0), ..config.clone() }, at("2024-03-02T02:00:00Z")).is_err());
+        std::fs::remove_file(&path).ok();
+    }
+}
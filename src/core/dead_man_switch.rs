@@ -0,0 +1,202 @@
+//! 데드맨 스위치 모듈
+//!
+//! 외부 모니터가 주기적으로 하트비트(`POST /heartbeat`)를 보내야 하며, 설정된 시간 동안 하트비트가
+//! 끊기면 (운영자/VPS 장애) 전략 중지, 미체결 주문 취소, 포지션 청산 등 안전 조치를 실행한다.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::config::DeadManSwitchConfig;
+use crate::core::strategy_manager::StrategyManager;
+use crate::order_core::manager::OrderManager;
+
+/// 하트비트 중단 시 실행할 안전 조치
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyAction {
+    /// 모든 전략 비활성화
+    HaltStrategies,
+    /// 거래소 미체결 주문 전체 취소
+    CancelOrders,
+    /// 보유 포지션을 reduce-only 시장가로 청산
+    FlattenPositions,
+}
+
+/// 스위치 상태 (/heartbeat 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadManStatus {
+    pub enabled: bool,
+    pub timeout_ms: i64,
+    pub last_heartbeat: Option<i64>,
+    pub last_source: Option<String>,
+    /// 안전 조치 실행까지 남은 시간 (발동 후에는 0)
+    pub remaining_ms: i64,
+    pub tripped_at: Option<i64>,
+}
+
+/// 안전 조치 실행 결과
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SafetyReport {
+    pub halted_strategies: Vec<String>,
+    pub cancelled_orders: Vec<String>,
+    pub flattened_positions: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// 데드맨 스위치
+pub struct DeadManSwitch {
+    config: DeadManSwitchConfig,
+    // 하트비트가 한 번도 없으면 시작 시각부터 계산
+    armed_at: i64,
+    last_heartbeat: Option<i64>,
+    last_source: Option<String>,
+    tripped_at: Option<i64>,
+}
+
+impl DeadManSwitch {
+    pub fn new(config: DeadManSwitchConfig, now: i64) -> Self {
+        DeadManSwitch {
+            config,
+            armed_at: now,
+            last_heartbeat: None,
+            last_source: None,
+            tripped_at: None,
+        }
+    }
+
+    pub fn config(&self) -> &DeadManSwitchConfig {
+        &self.config
+    }
+
+    /// 하트비트 수신 - 발동 상태였다면 재무장
+    pub fn heartbeat(&mut self, now: i64, source: Option<String>) {
+        if self.tripped_at.take().is_some() {
+            log::info!("dead man's switch re-armed by heartbeat from {}", source.as_deref().unwrap_or("unknown"));
+        }
+        self.last_heartbeat = Some(now);
+        self.last_source = source;
+    }
+
+    fn deadline(&self) -> i64 {
+        self.last_heartbeat.unwrap_or(self.armed_at) + self.config.timeout_ms
+    }
+
+    /// 시간 초과 확인 - 새로 발동한 경우에만 true (다음 하트비트까지 재발동하지 않음)
+    pub fn check(&mut self, now: i64) -> bool {
+        if !self.config.enabled || self.tripped_at.is_some() || now < self.deadline() {
+            return false;
+        }
+        self.tripped_at = Some(now);
+        true
+    }
+
+    pub fn status(&self, now: i64) -> DeadManStatus {
+        DeadManStatus {
+            enabled: self.config.enabled,
+            timeout_ms: self.config.timeout_ms,
+            last_heartbeat: self.last_heartbeat,
+            last_source: self.last_source.clone(),
+            remaining_ms: if self.tripped_at.is_some() { 0 } else { (self.deadline() - now).max(0) },
+            tripped_at: self.tripped_at,
+        }
+    }
+}
+
+/// 안전 조치 실행 - 전략 중지를 먼저 하여 취소/청산 도중 새 주문이 나가지 않게 함.
+/// 취소/청산은 주문 관리자를 거쳐 등록된 모든 거래소에 실행 (저장소 상태와 주문 이벤트도 갱신)
+pub async fn execute_safety_actions(
+    actions: &[SafetyAction],
+    order_manager: &Arc<RwLock<OrderManager>>,
+    strategy_manager: &Arc<RwLock<StrategyManager>>,
+) -> SafetyReport {
+    let mut report = SafetyReport::default();
+
+    if actions.contains(&SafetyAction::HaltStrategies) {
+        let mut manager = strategy_manager.write().await;
+        for (name, active) in manager.list_strategies() {
+            if !active {
+                continue;
+            }
+            match manager.set_strategy_active(&name, false) {
+                Ok(()) => report.halted_strategies.push(name),
+                Err(e) => report.errors.push(format!("halt {}: {}", name, e)),
+            }
+        }
+    }
+
+    if actions.contains(&SafetyAction::CancelOrders) {
+        let (cancelled, errors) = order_manager.read().await.cancel_all_open_orders().await;
+        report.cancelled_orders.extend(cancelled.into_iter().map(|id| id.0));
+        report.errors.extend(errors);
+    }
+
+    if actions.contains(&SafetyAction::FlattenPositions) {
+        let (flattened, errors) = order_manager.read().await.flatten_all_positions().await;
+        report.flattened_positions.extend(flattened);
+        report.errors.extend(errors);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::config::{Config, StrategySpec};
+    use crate::exchange::mocks::MockExchange;
+    use crate::exchange::registry::ExchangeRegistry;
+    use crate::exchange::traits::Exchange;
+    use crate::models::order::{Order, OrderSide, OrderType};
+    use crate::order_core::repository::InMemoryOrderRepository;
+
+    #[tokio::test]
+    async fn test_dead_man_switch_trips_and_rearms() {
+        let mut switch = DeadManSwitch::new(DeadManSwitchConfig {
+            enabled: true,
+            timeout_ms: 60_000,
+            check_interval_ms: 1_000,
+            actions: vec![SafetyAction::HaltStrategies, SafetyAction::CancelOrders],
+        }, 0);
+        switch.heartbeat(30_000, Some("uptime-monitor".to_string()));
+        assert!(!switch.check(80_000));
+        assert_eq!(switch.status(80_000).remaining_ms, 10_000);
+
+        // 하트비트 중단 -> 1회만 발동
+        assert!(switch.check(90_000));
+        assert!(!switch.check(120_000));
+        assert_eq!(switch.status(120_000).tripped_at, Some(90_000));
+        switch.heartbeat(130_000, None);
+        assert!(switch.status(130_000).tripped_at.is_none());
+
+        // 기본 거래소와 추가 거래소에 하나씩 미체결 주문
+        let venue = || {
+            let mut mock = MockExchange::new(Config::default());
+            mock.set_price("BTCUSDT", dec!(50_000)).unwrap();
+            Arc::new(RwLock::new(mock)) as Arc<RwLock<dyn Exchange>>
+        };
+        let (primary, sandbox) = (venue(), venue());
+        let mut registry = ExchangeRegistry::new("binance", primary.clone());
+        registry.register("sandbox", sandbox.clone()).unwrap();
+        let mut order_manager = OrderManager::new(primary.clone(), Arc::new(RwLock::new(InMemoryOrderRepository::new())));
+        order_manager.set_exchange_registry(registry);
+        let order_manager = Arc::new(RwLock::new(order_manager));
+        let open = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(40_000));
+        order_manager.read().await.create_order(open.clone()).await.unwrap();
+        sandbox.write().await.submit_order(open).await.unwrap();
+        let manager = Arc::new(RwLock::new(StrategyManager::new()));
+        manager.write().await.add_strategy_spec(StrategySpec::MultiIndicator { symbol: "BTCUSDT".into(), aggregation: None }).unwrap();
+
+        let report = execute_safety_actions(&switch.config().actions, &order_manager, &manager).await;
+        assert_eq!(report.halted_strategies.len(), 1);
+        assert_eq!(report.cancelled_orders.len(), 2);
+        assert!(report.errors.is_empty());
+        for exchange in [&primary, &sandbox] {
+            assert!(exchange.read().await.get_open_orders().await.unwrap().is_empty());
+        }
+        assert!(order_manager.read().await.get_open_orders().await.unwrap().is_empty());
+        assert!(manager.read().await.list_strategies().iter().all(|(_, active)| !active));
+    }
+}
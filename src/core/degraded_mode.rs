@@ -0,0 +1,273 @@
+//! 읽기 전용(degraded) 모드 모듈
+//!
+//! API 키의 거래 권한이 사라지거나 거래소가 인증 오류를 계속 반환하면 매 주문마다 실패 로그만
+//! 쌓이게 된다. 연속된 쓰기 권한 오류를 감지하면 읽기 전용 모드로 전환하여 주문 제출을 막고
+//! (전략 계산은 계속, 막힌 주문은 기록), 일정 간격으로 주문 하나만 통과시켜 권한 복구를 확인한다.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::config::DegradedModeConfig;
+use crate::error::TradingError;
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderSide, OrderType};
+
+// 쓰기 권한 상실로 보는 거래소 오류 (Binance 코드 / HTTP 상태 / 메시지)
+const WRITE_ACCESS_MARKERS: [&str; 7] = [
+    "-2015", // Invalid API-key, IP, or permissions for action
+    "-2014", // API-key format invalid
+    "-1022", // Signature for this request is not valid
+    "-2008", // Invalid Api-Key ID
+    "unauthorized", // HTTP 401
+    "forbidden",    // HTTP 403
+    "permission",
+];
+
+/// 쓰기 권한 상실로 볼 수 있는 오류인지 판별
+pub fn is_write_access_error(err: &TradingError) -> bool {
+    match err {
+        TradingError::ExchangeError(msg) | TradingError::ExecutionError(msg) => {
+            let msg = msg.to_lowercase();
+            WRITE_ACCESS_MARKERS.iter().any(|marker| msg.contains(marker))
+        }
+        TradingError::ExchangeRejected { code, msg } => {
+            let msg = format!("{} {}", code, msg.to_lowercase());
+            WRITE_ACCESS_MARKERS.iter().any(|marker| msg.contains(marker))
+        }
+        _ => false,
+    }
+}
+
+/// 읽기 전용 모드 상태 (/health 응답)
+#[derive(Debug, Clone, Serialize)]
+pub struct DegradedStatus {
+    pub enabled: bool,
+    pub degraded: bool,
+    pub since: Option<i64>,
+    /// 전환 원인이 된 마지막 오류
+    pub reason: Option<String>,
+    pub consecutive_failures: u32,
+    /// 읽기 전용 모드에서 막힌 주문 수 (누적)
+    pub blocked_orders: u64,
+    pub last_probe_at: Option<i64>,
+    /// 다음 복구 확인 주문을 통과시킬 시각
+    pub next_probe_at: Option<i64>,
+}
+
+/// 읽기 전용 모드에서 막힌 주문 기록
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockedOrder {
+    pub at: i64,
+    pub strategy: Option<String>,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub client_order_id: Option<String>,
+}
+
+/// 모드 전환 알림
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum DegradedEvent {
+    Entered { status: DegradedStatus },
+    Recovered { status: DegradedStatus },
+}
+
+#[derive(Default)]
+struct MonitorState {
+    consecutive_failures: u32,
+    since: Option<i64>,
+    reason: Option<String>,
+    last_probe_at: Option<i64>,
+    blocked_total: u64,
+    journal: VecDeque<BlockedOrder>,
+}
+
+/// 쓰기 권한 감시자 - 주문 관리자와 HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct WriteAccessMonitor {
+    config: DegradedModeConfig,
+    state: Arc<Mutex<MonitorState>>,
+    events: broadcast::Sender<DegradedEvent>,
+}
+
+impl WriteAccessMonitor {
+    pub fn new(config: DegradedModeConfig) -> Self {
+        WriteAccessMonitor {
+            config,
+            state: Arc::new(Mutex::new(MonitorState::default())),
+            events: broadcast::channel(16).0,
+        }
+    }
+
+    /// 모드 전환 알림 구독
+    pub fn subscribe(&self) -> broadcast::Receiver<DegradedEvent> {
+        self.events.subscribe()
+    }
+
+    /// 주문 제출 가능 여부 - 읽기 전용 모드면 복구 확인 시점의 주문 하나만 통과
+    pub fn check(&self, now: i64) -> Result<(), TradingError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !self.config.enabled || state.since.is_none() {
+            return Ok(());
+        }
+        if now >= self.next_probe_at(&state) {
+            state.last_probe_at = Some(now);
+            log::info!("degraded mode: letting one order through to probe write access");
+            return Ok(());
+        }
+        Err(TradingError::DegradedMode(state.reason.clone().unwrap_or_else(|| "exchange write access lost".to_string())))
+    }
+
+    fn next_probe_at(&self, state: &MonitorState) -> i64 {
+        state.last_probe_at.or(state.since).unwrap_or_default() + self.config.probe_interval_ms
+    }
+
+    /// 막힌 주문 기록 (최근 `journal_limit` 건 보관)
+    pub fn record_blocked(&self, strategy: Option<&str>, order: &Order, now: i64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.blocked_total += 1;
+        state.journal.push_back(BlockedOrder {
+            at: now,
+            strategy: strategy.map(str::to_string),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            quantity: order.quantity,
+            price: order.price,
+            client_order_id: order.client_order_id.clone(),
+        });
+        while state.journal.len() > self.config.journal_limit {
+            state.journal.pop_front();
+        }
+    }
+
+    /// 거래소 쓰기 요청 결과 반영 - 성공이면 복구, 권한 오류가 임계치만큼 이어지면 전환
+    pub fn record_result<T>(&self, result: &Result<T, TradingError>, now: i64) {
+        if !self.config.enabled {
+            return;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match result {
+            Ok(_) => {
+                state.consecutive_failures = 0;
+                if let Some(since) = state.since.take() {
+                    state.reason = None;
+                    state.last_probe_at = None;
+                    log::info!("exchange write access restored after {}ms; leaving degraded mode", now - since);
+                    let _ = self.events.send(DegradedEvent::Recovered { status: self.status_of(&state) });
+                }
+            }
+            Err(e) if is_write_access_error(e) => {
+                state.consecutive_failures += 1;
+                state.reason = Some(e.to_string());
+                if state.since.is_none() && state.consecutive_failures >= self.config.failure_threshold {
+                    state.since = Some(now);
+                    log::error!("{} consecutive write access failures; entering read-only degraded mode: {}", state.consecutive_failures, e);
+                    let _ = self.events.send(DegradedEvent::Entered { status: self.status_of(&state) });
+                }
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// 운영자 수동 해제 (키 교체 후 등)
+    pub fn reset(&self) -> DegradedStatus {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures = 0;
+        state.reason = None;
+        state.last_probe_at = None;
+        if state.since.take().is_some() {
+            log::info!("degraded mode cleared manually");
+            let _ = self.events.send(DegradedEvent::Recovered { status: self.status_of(&state) });
+        }
+        self.status_of(&state)
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        self.config.enabled && self.state.lock().unwrap_or_else(|e| e.into_inner()).since.is_some()
+    }
+
+    pub fn status(&self) -> DegradedStatus {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        self.status_of(&state)
+    }
+
+    fn status_of(&self, state: &MonitorState) -> DegradedStatus {
+        DegradedStatus {
+            enabled: self.config.enabled,
+            degraded: state.since.is_some(),
+            since: state.since,
+            reason: state.reason.clone(),
+            consecutive_failures: state.consecutive_failures,
+            blocked_orders: state.blocked_total,
+            last_probe_at: state.last_probe_at,
+            next_probe_at: state.since.map(|_| self.next_probe_at(state)),
+        }
+    }
+
+    /// 최근 막힌 주문 (최신순)
+    pub fn blocked_orders(&self) -> Vec<BlockedOrder> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.journal.iter().rev().cloned().collect()
+    }
+}
+
+impl Default for WriteAccessMonitor {
+    fn default() -> Self {
+        WriteAccessMonitor::new(DegradedModeConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    fn auth_error() -> Result<(), TradingError> {
+        Err(TradingError::ExchangeError("order failed: 401 Unauthorized {\"code\":-2015,\"msg\":\"Invalid API-key, IP, or permissions for action.\"}".into()))
+    }
+
+    #[test]
+    fn test_degraded_mode_transitions() {
+        let monitor = WriteAccessMonitor::new(DegradedModeConfig { failure_threshold: 3, probe_interval_ms: 60_000, ..Default::default() });
+        let mut events = monitor.subscribe();
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(1), dec!(0));
+
+        // 일반 오류는 세지 않고, 성공하면 연속 횟수 초기화
+        monitor.record_result(&auth_error(), 0);
+        monitor.record_result::<()>(&Err(TradingError::InsufficientBalance), 0);
+        monitor.record_result(&Ok(()), 0);
+        assert_eq!(monitor.status().consecutive_failures, 0);
+
+        for now in 1..=3 {
+            assert!(monitor.check(now).is_ok());
+            monitor.record_result(&auth_error(), now);
+        }
+        assert!(monitor.is_degraded());
+        assert!(matches!(events.try_recv().unwrap(), DegradedEvent::Entered { .. }));
+
+        // 읽기 전용: 주문 차단 및 기록
+        assert!(matches!(monitor.check(10_000), Err(TradingError::DegradedMode(_))));
+        monitor.record_blocked(Some("rsi"), &order, 10_000);
+        assert_eq!(monitor.blocked_orders()[0].strategy.as_deref(), Some("rsi"));
+
+        // 복구 확인 주문은 간격마다 하나만 통과, 실패하면 계속 읽기 전용
+        assert!(monitor.check(60_003).is_ok());
+        assert!(monitor.check(60_004).is_err());
+        monitor.record_result(&auth_error(), 60_004);
+        assert_eq!(monitor.status().next_probe_at, Some(120_003));
+
+        assert!(monitor.check(120_003).is_ok());
+        monitor.record_result(&Ok(()), 120_010);
+        let status = monitor.status();
+        assert!(!status.degraded && status.blocked_orders == 1);
+        assert!(matches!(events.try_recv().unwrap(), DegradedEvent::Recovered { .. }));
+        assert!(!is_write_access_error(&TradingError::ExchangeError("order failed: 429 Too Many Requests".into())));
+    }
+}
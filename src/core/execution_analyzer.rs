@@ -6,9 +6,13 @@
 **/
 
 use std::collections::HashMap;
+use std::path::Path;
 
+use crate::core::trade_journal::{TradeJournal, TradeRecord};
+use crate::error::TradingError;
+use crate::models::decimal::to_f64;
 use crate::models::market_data::MarketData;
-use crate::models::order::{Order, OrderSide};
+use crate::models::order::{Order, OrderId, OrderSide};
 use crate::models::trade::Trade;
 
 /// 실행 성능 분석기
@@ -27,6 +31,8 @@ pub struct ExecutionAnalyzer {
   slippage: f64,
   /// 시장 영향 (%)
   impact: f64,
+  /// 도착 가격 (실행 결정 시점 가격, Implementation Shortfall 기준)
+  arrival_price: Option<f64>,
 }
 
 impl ExecutionAnalyzer {
@@ -40,9 +46,47 @@ impl ExecutionAnalyzer {
       twap: 0.0,
       slippage: 0.0,
       impact: 0.0,
+      arrival_price: None,
     }
   }
   
+  /// 도착 가격 설정 - 이후 체결을 이 가격 대비 비용으로 평가
+  pub fn set_arrival_price(&mut self, price: f64) {
+    if price > 0.0 {
+      self.arrival_price = Some(price);
+    }
+  }
+  
+  pub fn arrival_price(&self) -> Option<f64> {
+    self.arrival_price
+  }
+  
+  /// 평균 체결가의 기준가 대비 비용 (bps, 불리한 방향이 양수)
+  fn cost_bps(&self, benchmark: f64) -> Option<f64> {
+    if benchmark <= 0.0 {
+      return None;
+    }
+    let (mut value, mut quantity) = (0.0, 0.0);
+    for trade in &self.trades {
+      value += to_f64(trade.value());
+      quantity += to_f64(trade.quantity);
+    }
+    let side = &self.trades.first()?.side;
+    if quantity <= 0.0 {
+      return None;
+    }
+    let diff = (value / quantity - benchmark) / benchmark * 10_000.0;
+    Some(match side {
+      OrderSide::Buy => diff,
+      OrderSide::Sell => -diff,
+    })
+  }
+  
+  /// 도착 가격 대비 실현 슬리피지 (Implementation Shortfall, bps)
+  pub fn arrival_slippage_bps(&self) -> Option<f64> {
+    self.cost_bps(self.arrival_price?)
+  }
+  
   /// 분석을 위한 거래 추가
   pub fn add_trade(&mut self, trade: Trade) {
     if trade.symbol == self.symbol {
@@ -51,6 +95,36 @@ impl ExecutionAnalyzer {
     }
   }
   
+  /// 체결 기록으로 사후 분석 - 대상 심볼의 체결을 추가하고, 도착 가격이 없으면 첫 결정 가격 사용.
+  /// 추가한 체결 수 반환
+  pub fn add_journal_records(&mut self, records: &[TradeRecord]) -> usize {
+    let records: Vec<&TradeRecord> = records.iter().filter(|r| r.symbol == self.symbol).collect();
+    if self.arrival_price.is_none() {
+      if let Some(price) = records.iter().find_map(|r| r.decision_price) {
+        self.set_arrival_price(price);
+      }
+    }
+    for record in &records {
+      self.trades.push(Trade::new(
+        record.id.clone(),
+        record.symbol.clone(),
+        record.price,
+        record.quantity,
+        record.timestamp,
+        OrderId(record.order_id.clone()),
+        record.side.clone(),
+      ));
+    }
+    self.calculate_metrics();
+    records.len()
+  }
+  
+  /// 트레이드 저널 파일 불러오기
+  pub fn load_journal(&mut self, path: impl AsRef<Path>) -> Result<usize, TradingError> {
+    let records = TradeJournal::read_file(path.as_ref())?;
+    Ok(self.add_journal_records(&records))
+  }
+  
   /// 분석을 위한 시장 데이터 추가
   pub fn add_market_data(&mut self, data: MarketData) {
     if data.symbol == self.symbol {
@@ -77,9 +151,8 @@ impl ExecutionAnalyzer {
     let mut total_volume = 0.0;
     
     for trade in &self.trades {
-      let value = trade.price * trade.quantity;
-      total_value += value;
-      total_volume += trade.quantity;
+      total_value += to_f64(trade.value());
+      total_volume += to_f64(trade.quantity);
     }
     
     if total_volume > 0.0 {
@@ -96,7 +169,7 @@ impl ExecutionAnalyzer {
     let mut total = 0.0;
     
     for data in &self.market_data {
-      total += data.close;
+      total += data.close_f64();
     }
     
     self.twap = total / self.market_data.len() as f64;
@@ -112,13 +185,14 @@ impl ExecutionAnalyzer {
     let mut total_volume = 0.0;
     
     for trade in &self.trades {
+      let price = to_f64(trade.price);
       let slippage = match trade.side {
-        OrderSide::Buy => (trade.price - self.vwap) / self.vwap * 100.0,
-        OrderSide::Sell => (self.vwap - trade.price) / self.vwap * 100.0,
+        OrderSide::Buy => (price - self.vwap) / self.vwap * 100.0,
+        OrderSide::Sell => (self.vwap - price) / self.vwap * 100.0,
       };
       
-      total_slippage += slippage * trade.quantity;
-      total_volume += trade.quantity;
+      total_slippage += slippage * to_f64(trade.quantity);
+      total_volume += to_f64(trade.quantity);
     }
     
     if total_volume > 0.0 {
@@ -133,15 +207,15 @@ impl ExecutionAnalyzer {
     }
     
     // 거래 전후 가격 가져오기
-    let start_price = self.market_data.first().unwrap().close;
-    let end_price = self.market_data.last().unwrap().close;
+    let start_price = self.market_data.first().unwrap().close_f64();
+    let end_price = self.market_data.last().unwrap().close_f64();
     
     // 총 거래량 계산
     let mut total_volume = 0.0;
     let mut is_buy = false;
     
     for trade in &self.trades {
-      total_volume += trade.quantity;
+      total_volume += to_f64(trade.quantity);
       is_buy = trade.side == OrderSide::Buy;
     }
     
@@ -174,8 +248,8 @@ impl ExecutionAnalyzer {
     let mut total_quantity = 0.0;
     
     for trade in &self.trades {
-      total_value += trade.price * trade.quantity;
-      total_quantity += trade.quantity;
+      total_value += to_f64(trade.value());
+      total_quantity += to_f64(trade.quantity);
     }
     
     let avg_price = if total_quantity > 0.0 {
@@ -189,6 +263,17 @@ impl ExecutionAnalyzer {
     report.insert("total_value".to_string(), total_value);
     report.insert("trade_count".to_string(), self.trades.len() as f64);
     
+    // 벤치마크별 비용 비교 (IS 와 VWAP/TWAP 실행 비교용)
+    if let Some(arrival) = self.arrival_price {
+      report.insert("arrival_price".to_string(), arrival);
+    }
+    if let Some(bps) = self.arrival_slippage_bps() {
+      report.insert("arrival_slippage_bps".to_string(), bps);
+    }
+    if let Some(bps) = self.cost_bps(self.twap) {
+      report.insert("twap_slippage_bps".to_string(), bps);
+    }
+    
     report
   }
 }
@@ -196,6 +281,7 @@ impl ExecutionAnalyzer {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::models::decimal::dec;
   use crate::models::order::OrderId;
   
   #[test]
@@ -205,34 +291,35 @@ mod tests {
     // 시장 데이터 추가
     let md1 = MarketData {
       symbol: "BTCUSDT".to_string(),
-      timestamp: 1000,
-      open: 50000.0,
-      high: 50100.0,
-      low: 49900.0,
-      close: 50000.0,
-      volume: 10.0,
+      timestamp: 1000.into(),
+      open: dec!(50000),
+      high: dec!(50100),
+      low: dec!(49900),
+      close: dec!(50000),
+      volume: dec!(10),
     };
     
     let md2 = MarketData {
       symbol: "BTCUSDT".to_string(),
-      timestamp: 2000,
-      open: 50000.0,
-      high: 50200.0,
-      low: 49950.0,
-      close: 50100.0,
-      volume: 15.0,
+      timestamp: 2000.into(),
+      open: dec!(50000),
+      high: dec!(50200),
+      low: dec!(49950),
+      close: dec!(50100),
+      volume: dec!(15),
     };
     
     analyzer.add_market_data(md1);
     analyzer.add_market_data(md2);
+    analyzer.set_arrival_price(50000.0);
     
     // 거래 내역 추가
     let trade1 = Trade {
       id: "trade1".to_string(),
       symbol: "BTCUSDT".to_string(),
-      price: 50050.0,
-      quantity: 0.5,
-      timestamp: 1500,
+      price: dec!(50050),
+      quantity: dec!(0.5),
+      timestamp: 1500.into(),
       order_id: OrderId("order1".to_string()),
       side: OrderSide::Buy,
     };
@@ -240,9 +327,9 @@ mod tests {
     let trade2 = Trade {
       id: "trade2".to_string(),
       symbol: "BTCUSDT".to_string(),
-      price: 50080.0,
-      quantity: 0.3,
-      timestamp: 1800,
+      price: dec!(50080),
+      quantity: dec!(0.3),
+      timestamp: 1800.into(),
       order_id: OrderId("order2".to_string()),
       side: OrderSide::Buy,
     };
@@ -264,5 +351,9 @@ mod tests {
     assert!(report["vwap"] > 0.0);
     assert_eq!(report["trade_count"], 2.0);
     assert_eq!(report["total_quantity"], 0.8);
+    
+    // 평균 체결가 50061.25 -> 도착가 50000 대비 12.25bps 비용
+    assert!((report["arrival_slippage_bps"] - 12.25).abs() < 1e-6);
+    assert!(report["twap_slippage_bps"] < report["arrival_slippage_bps"]);
   }
 }
\ No newline at end of file
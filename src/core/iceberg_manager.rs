@@ -11,6 +11,7 @@ use tokio::time::{interval, Duration};
 
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
 use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
 
 /// Iceberg 주문 관리자
@@ -22,13 +23,13 @@ pub struct IcebergManager {
   /// 주문 방향 (매수/매도)
   side: OrderSide,
   /// 총 주문 수량
-  total_quantity: f64,
+  total_quantity: Decimal,
   /// 지정가 가격
-  limit_price: f64,
+  limit_price: Decimal,
   /// 노출할 부분 수량
-  display_quantity: f64,
+  display_quantity: Decimal,
   /// 이미 실행한 수량
-  executed_quantity: f64,
+  executed_quantity: Decimal,
   /// 실행 중 여부
   is_active: bool,
   /// 현재 활성 주문 ID
@@ -43,9 +44,9 @@ impl IcebergManager {
     exchange: Arc<RwLock<dyn Exchange>>,
     symbol: impl Into<String>,
     side: OrderSide,
-    total_quantity: f64,
-    limit_price: f64,
-    display_quantity: f64,
+    total_quantity: Decimal,
+    limit_price: Decimal,
+    display_quantity: Decimal,
   ) -> Self {
     let display_quantity = display_quantity.min(total_quantity);
     
@@ -56,7 +57,7 @@ impl IcebergManager {
       total_quantity,
       limit_price,
       display_quantity,
-      executed_quantity: 0.0,
+      executed_quantity: Decimal::ZERO,
       is_active: false,
       current_order_id: None,
       execution_lock: Arc::new(Mutex::new(()))
@@ -70,7 +71,7 @@ impl IcebergManager {
     }
     
     self.is_active = true;
-    self.executed_quantity = 0.0;
+    self.executed_quantity = Decimal::ZERO;
     self.current_order_id = None;
     
     // 첫 번째 노출 부분 제출
@@ -88,7 +89,7 @@ impl IcebergManager {
     // 자신에 대한 약한 참조 사용하여 메모리 누수 방지
     let is_active = Arc::new(RwLock::new(true));
     let is_active_clone = is_active.clone();
-    let executed_quantity = Arc::new(RwLock::new(0.0));
+    let executed_quantity = Arc::new(RwLock::new(Decimal::ZERO));
     let executed_quantity_clone = executed_quantity.clone();
     let current_order_id = Arc::new(RwLock::new(None));
     let current_order_id_clone = current_order_id.clone();
@@ -123,7 +124,7 @@ impl IcebergManager {
                 drop(exchange); // 락 해제
                 
                 let remaining = total_quantity - *executed_quantity_clone.read().await;
-                if remaining > 0.0 {
+                if remaining > Decimal::ZERO {
                   let next_quantity = display_quantity.min(remaining);
                   
                   let mut exchange = exchange_clone.write().await;
@@ -145,7 +146,7 @@ impl IcebergManager {
                 drop(exchange); // 락 해제
                 
                 let remaining = total_quantity - *executed_quantity_clone.read().await;
-                if remaining > 0.0 {
+                if remaining > Decimal::ZERO {
                   let next_quantity = display_quantity.min(remaining);
                   
                   let mut exchange = exchange_clone.write().await;
@@ -192,7 +193,7 @@ impl IcebergManager {
   }
   
   /// Iceberg 실행 상태 조회
-  pub fn status(&self) -> (bool, f64, f64) {
+  pub fn status(&self) -> (bool, Decimal, Decimal) {
     (self.is_active, self.executed_quantity, self.total_quantity)
   }
   
@@ -200,7 +201,7 @@ impl IcebergManager {
   async fn submit_visible_portion(&mut self) -> Result<(), TradingError> {
     let remaining_quantity = self.total_quantity - self.executed_quantity;
     
-    if remaining_quantity <= 0.0 {
+    if remaining_quantity <= Decimal::ZERO {
       self.is_active = false;
       return Ok(());
     }
@@ -225,7 +226,7 @@ impl IcebergManager {
   }
   
   /// 향후 주문 제출을 위한 지정가 업데이트
-  pub async fn update_price(&mut self, new_price: f64) -> Result<(), TradingError> {
+  pub async fn update_price(&mut self, new_price: Decimal) -> Result<(), TradingError> {
     self.limit_price = new_price;
     
     // 활성 상태면 현재 주문 취소 후 새 가격으로 재제출
@@ -247,6 +248,7 @@ mod tests {
   use super::*;
   use crate::exchange::mocks::MockExchange;
   use crate::config::Config;
+  use crate::models::decimal::dec;
   
   #[tokio::test]
   async fn test_iceberg_manager() {
@@ -259,9 +261,9 @@ mod tests {
       exchange.clone(),
       "BTCUSDT",
       OrderSide::Buy,
-      10.0,
-      50000.0,
-      1.0,
+      dec!(10),
+      dec!(50000),
+      dec!(1),
     );
     
     // 실행 시작
@@ -0,0 +1,251 @@
+/**
+* filename : is_splitter
+* author : HAMA
+* date: 2025. 5. 8.
+* description:
+**/
+
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{interval, Duration};
+
+use crate::core::execution_analyzer::ExecutionAnalyzer;
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::trade::Trade;
+
+// sinh 오버플로 방지용 궤적 곡률 상한
+const MAX_KAPPA_HORIZON: f64 = 20.0;
+
+/// Implementation Shortfall 실행 스케줄 (Almgren-Chriss 최적 궤적)
+///
+/// 분할별 실행 비율(합계 1.0)을 반환. `urgency` 는 위험 회피 계수로 0 이면 균등 분할(TWAP)과
+/// 같고 클수록 앞쪽 분할에 몰린다. `volatility_bps` 는 분할 구간당 가격 변동성,
+/// `impact_bps` 는 전체 수량을 한 분할에 냈을 때 예상 시장 충격으로, 충격이 클수록 고르게 분산된다.
+pub fn shortfall_schedule(num_slices: usize, urgency: f64, volatility_bps: f64, impact_bps: f64) -> Vec<f64> {
+  if num_slices == 0 {
+    return Vec::new();
+  }
+  let n = num_slices as f64;
+  // 분할당 궤적 곡률: kappa^2 = urgency * sigma^2 / eta
+  let kappa = if urgency > 0.0 && volatility_bps > 0.0 && impact_bps > 0.0 {
+    (urgency * volatility_bps * volatility_bps / impact_bps).sqrt().min(MAX_KAPPA_HORIZON / n)
+  } else {
+    0.0
+  };
+  if kappa <= f64::EPSILON {
+    return vec![1.0 / n; num_slices];
+  }
+
+  // 남은 보유 비율 x_j = sinh(kappa (N - j)) / sinh(kappa N), 분할 j 실행량 = x_j - x_{j+1}
+  let holding = |j: usize| (kappa * (n - j as f64)).sinh() / (kappa * n).sinh();
+  (0..num_slices).map(|j| holding(j) - holding(j + 1)).collect()
+}
+
+/// Implementation Shortfall 기반 주문 분할기 - 도착 가격 대비 비용을 추적
+pub struct ImplementationShortfallSplitter {
+  /// 거래소 인스턴스
+  exchange: Arc<RwLock<dyn Exchange>>,
+  /// 거래 심볼
+  symbol: String,
+  /// 주문 방향 (매수/매도)
+  side: OrderSide,
+  /// 총 주문 수량
+  total_quantity: Decimal,
+  /// 실행 기간 (밀리초)
+  execution_interval: i64,
+  /// 분할 수
+  num_slices: usize,
+  /// 긴급도 (위험 회피 계수)
+  urgency: f64,
+  /// 분할 구간당 변동성 (bps)
+  volatility_bps: f64,
+  /// 예상 시장 충격 (bps)
+  impact_bps: f64,
+  /// 이미 실행한 수량
+  executed_quantity: Decimal,
+  /// 실행 중 여부
+  is_active: bool,
+  /// 생성된 하위 주문 ID 목록
+  child_orders: Vec<OrderId>,
+  /// 도착 가격 대비 실행 성과
+  analyzer: ExecutionAnalyzer,
+}
+
+impl ImplementationShortfallSplitter {
+  /// 새 IS 분할기 생성
+  pub fn new(
+    exchange: Arc<RwLock<dyn Exchange>>,
+    symbol: impl Into<String>,
+    side: OrderSide,
+    total_quantity: Decimal,
+    execution_interval: i64,
+    num_slices: usize,
+    urgency: f64,
+  ) -> Self {
+    let symbol = symbol.into();
+    ImplementationShortfallSplitter {
+      exchange,
+      analyzer: ExecutionAnalyzer::new(symbol.clone()),
+      symbol,
+      side,
+      total_quantity,
+      execution_interval,
+      num_slices: num_slices.max(1),
+      urgency,
+      volatility_bps: 20.0,
+      impact_bps: 10.0,
+      executed_quantity: Decimal::ZERO,
+      is_active: false,
+      child_orders: Vec::new(),
+    }
+  }
+
+  /// 시장 모형 설정 (분할 구간당 변동성, 예상 시장 충격)
+  pub fn with_market_model(mut self, volatility_bps: f64, impact_bps: f64) -> Self {
+    self.volatility_bps = volatility_bps;
+    self.impact_bps = impact_bps;
+    self
+  }
+
+  /// IS 실행 시작 - 시작 시점 가격을 도착 가격으로 기록
+  pub async fn start(&mut self) -> Result<(), TradingError> {
+    if self.is_active {
+      return Err(TradingError::AlreadyRunning("IS execution already running".to_string()));
+    }
+
+    let arrival = self.exchange.read().await.get_market_data(&self.symbol).await?;
+    self.analyzer = ExecutionAnalyzer::new(self.symbol.clone());
+    self.analyzer.set_arrival_price(arrival.close_f64());
+    self.analyzer.add_market_data(arrival);
+
+    self.is_active = true;
+    self.executed_quantity = Decimal::ZERO;
+    self.child_orders.clear();
+
+    let schedule = shortfall_schedule(self.num_slices, self.urgency, self.volatility_bps, self.impact_bps);
+    let time_between_slices = (self.execution_interval / self.num_slices as i64).max(1);
+    let mut interval_timer = interval(Duration::from_millis(time_between_slices as u64));
+    let mut remaining_quantity = self.total_quantity;
+
+    for (i, fraction) in schedule.iter().enumerate() {
+      interval_timer.tick().await;
+
+      if !self.is_active {
+        break;
+      }
+
+      // 마지막 분할은 남은 수량 전부 (반올림 오차 처리)
+      let quantity = if i == self.num_slices - 1 {
+        remaining_quantity
+      } else {
+        (self.total_quantity * to_decimal(*fraction)).min(remaining_quantity)
+      };
+
+      if quantity > Decimal::ZERO {
+        match self.create_child_order(quantity).await {
+          Ok(order_id) => {
+            self.child_orders.push(order_id);
+            remaining_quantity -= quantity;
+            self.executed_quantity += quantity;
+          },
+          Err(e) => {
+            log::error!("Failed to create IS child order: {}", e);
+          }
+        }
+      }
+
+      if remaining_quantity <= Decimal::ZERO {
+        break;
+      }
+    }
+
+    self.is_active = false;
+    if let Some(bps) = self.analyzer.arrival_slippage_bps() {
+      log::info!("IS execution {} finished: {:.2}bps vs arrival", self.symbol, bps);
+    }
+    Ok(())
+  }
+
+  /// IS 실행 중지 및 모든 활성 주문 취소
+  pub async fn stop(&mut self) -> Result<(), TradingError> {
+    if !self.is_active {
+      return Ok(());
+    }
+
+    self.is_active = false;
+
+    for order_id in &self.child_orders {
+      let mut exchange = self.exchange.write().await;
+      let status = exchange.get_order_status(order_id).await?;
+
+      if status == OrderStatus::New || status == OrderStatus::PartiallyFilled {
+        let _ = exchange.cancel_order(order_id).await;
+      }
+    }
+
+    Ok(())
+  }
+
+  /// IS 실행 상태 조회
+  pub fn status(&self) -> (bool, Decimal, Decimal) {
+    (self.is_active, self.executed_quantity, self.total_quantity)
+  }
+
+  /// 도착 가격 대비 실행 성과 (VWAP/TWAP 분할기와 비교용)
+  pub fn analyzer(&self) -> &ExecutionAnalyzer {
+    &self.analyzer
+  }
+
+  /// 하위 주문 생성 - 시장가 체결은 현재가로 성과에 기록
+  async fn create_child_order(&mut self, quantity: Decimal) -> Result<OrderId, TradingError> {
+    let mut exchange = self.exchange.write().await;
+
+    let market_data = exchange.get_market_data(&self.symbol).await?;
+    let price = market_data.close;
+    let timestamp = market_data.timestamp;
+
+    let order = Order::new(
+      self.symbol.clone(),
+      self.side.clone(),
+      OrderType::Market,
+      quantity,
+      price,
+    );
+
+    let order_id = exchange.submit_order(order).await?;
+    self.analyzer.add_market_data(market_data);
+    self.analyzer.add_trade(Trade::new(order_id.0.clone(), self.symbol.clone(), price, quantity, timestamp, order_id.clone(), self.side.clone()));
+    Ok(order_id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_shortfall_schedule() {
+    // 긴급도 0 이면 균등 분할
+    let uniform = shortfall_schedule(4, 0.0, 20.0, 10.0);
+    assert!(uniform.iter().all(|f| (f - 0.25).abs() < 1e-12));
+
+    // 긴급도가 높을수록 앞쪽에 몰림, 합계는 항상 1
+    let moderate = shortfall_schedule(5, 0.05, 20.0, 10.0);
+    let urgent = shortfall_schedule(5, 1.0, 20.0, 10.0);
+    for schedule in [&moderate, &urgent] {
+      assert!((schedule.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+      assert!(schedule.windows(2).all(|w| w[0] > w[1]));
+    }
+    assert!(urgent[0] > moderate[0]);
+
+    // 예상 시장 충격이 크면 더 고르게 분산
+    let high_impact = shortfall_schedule(5, 1.0, 20.0, 1_000.0);
+    assert!(high_impact[0] < urgent[0]);
+
+    // 극단적 긴급도에서도 유한한 값
+    assert!(shortfall_schedule(50, 1e9, 100.0, 0.1).iter().all(|f| f.is_finite() && *f >= 0.0));
+  }
+}
@@ -0,0 +1,114 @@
+//! 전체 거래 중지(킬 스위치) 모듈
+//!
+//! 운영자가 `POST /trading/halt` 로 거래를 멈추면 주문 관리자가 모든 신규 주문을 거부한다
+//! (전략 계산과 주문 취소는 계속). 필요하면 미체결 주문 취소와 포지션 청산을 함께 실행하고,
+//! `POST /trading/resume` 으로 해제하기 전까지 유지된다.
+
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::error::TradingError;
+
+/// 거래 중지 상태 (/trading/status 응답)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HaltStatus {
+    pub halted: bool,
+    pub since: Option<i64>,
+    pub reason: Option<String>,
+    /// 중지 요청 주체 (API 호출자 등)
+    pub source: Option<String>,
+    /// 중지 중 거부된 주문 수
+    pub rejected_orders: u64,
+    pub last_resumed_at: Option<i64>,
+}
+
+/// 킬 스위치 - 주문 관리자와 HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone, Default)]
+pub struct KillSwitch {
+    state: Arc<Mutex<HaltStatus>>,
+}
+
+impl KillSwitch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 거래 중지 - 이미 중지 상태면 사유만 갱신
+    pub fn halt(&self, reason: Option<String>, source: Option<String>, now: i64) -> HaltStatus {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.halted {
+            state.halted = true;
+            state.since = Some(now);
+            state.rejected_orders = 0;
+        }
+        log::error!("trading halted by {}: {}", source.as_deref().unwrap_or("unknown"), reason.as_deref().unwrap_or("no reason given"));
+        state.reason = reason;
+        state.source = source;
+        state.clone()
+    }
+
+    /// 거래 재개
+    pub fn resume(&self, now: i64) -> HaltStatus {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.halted {
+            log::info!("trading resumed after {}ms ({} orders rejected while halted)", now - state.since.unwrap_or(now), state.rejected_orders);
+            state.halted = false;
+            state.since = None;
+            state.reason = None;
+            state.source = None;
+            state.last_resumed_at = Some(now);
+        }
+        state.clone()
+    }
+
+    /// 주문 제출 가능 여부 - 중지 중이면 거부 수를 세고 오류 반환
+    pub fn check(&self) -> Result<(), TradingError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !state.halted {
+            return Ok(());
+        }
+        state.rejected_orders += 1;
+        Err(TradingError::TradingHalted(state.reason.clone().unwrap_or_else(|| "trading halted by operator".to_string())))
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).halted
+    }
+
+    pub fn status(&self) -> HaltStatus {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::exchange::mocks::MockExchange;
+    use crate::models::order::{Order, OrderSide, OrderType};
+    use crate::order_core::manager::OrderManager;
+    use crate::order_core::repository::InMemoryOrderRepository;
+    use tokio::sync::RwLock;
+
+    #[tokio::test]
+    async fn test_halt_blocks_orders_until_resume() {
+        let exchange = Arc::new(RwLock::new(MockExchange::new(crate::config::Config::default())));
+        exchange.write().await.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+        let mut manager = OrderManager::new(exchange, repository);
+        let switch = KillSwitch::new();
+        manager.set_kill_switch(switch.clone());
+        let order = || Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(0.01), dec!(0));
+
+        let status = switch.halt(Some("exchange incident".into()), Some("ops".into()), 1_000);
+        assert!(status.halted);
+        assert!(matches!(manager.create_order(order()).await, Err(TradingError::TradingHalted(reason)) if reason == "exchange incident"));
+        assert_eq!(switch.status().rejected_orders, 1);
+
+        let status = switch.resume(5_000);
+        assert!(!status.halted);
+        assert_eq!(status.last_resumed_at, Some(5_000));
+        assert!(manager.create_order(order()).await.is_ok());
+    }
+}
@@ -0,0 +1,282 @@
+//! 실시간 WebSocket 피드 허브
+//!
+//! 클라이언트마다 거래소를 폴링하지 않도록 시세/미체결 주문/포지션/전략 상태를 한 곳에서 받아
+//! 공유 채널로 내보낸다. 시세는 `MarketDataStream` 채널을 심볼별로 한 번만 구독하고(스트림이 조용하면
+//! REST 로 대체), 주문과 포지션은 주문 이벤트가 올 때와 재동기화 주기에만 조회한다.
+//! 구독자가 없는 채널은 조회하지 않는다.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, watch, RwLock};
+
+use crate::core::strategy_manager::{StrategyManager, StrategyStatus};
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::market_data::stream::MarketDataStream;
+use crate::models::market_data::MarketData;
+use crate::models::order::Order;
+use crate::models::position::Position;
+use crate::order_core::events::OrderLifecycleEvent;
+use crate::order_core::manager::OrderManager;
+
+/// 스트림 시세가 이 시간 동안 없으면 REST 로 한 번 조회
+const PRICE_STREAM_TIMEOUT: Duration = Duration::from_secs(1);
+/// 주문 이벤트가 없어도 미체결 주문을 다시 조회하는 주기 (수동 주문 등 외부 변경 반영)
+const ORDER_RESYNC: Duration = Duration::from_secs(30);
+/// 포지션 재조회 주기 (체결 이벤트가 오면 즉시 조회)
+const POSITION_RESYNC: Duration = Duration::from_secs(15);
+/// 전략 목록/상태 변경 확인 주기 (메모리 조회)
+const STRATEGY_CHECK: Duration = Duration::from_secs(1);
+
+/// 실시간 피드 허브 - HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct LiveFeed {
+    exchange: Arc<RwLock<dyn Exchange>>,
+    market_stream: Arc<RwLock<MarketDataStream>>,
+    prices: Arc<Mutex<HashMap<String, broadcast::Sender<MarketData>>>>,
+    orders: watch::Sender<Option<Vec<Order>>>,
+    positions: watch::Sender<Option<Vec<Position>>>,
+    strategies: watch::Sender<Option<Vec<StrategyStatus>>>,
+}
+
+impl LiveFeed {
+    /// 주문/포지션/전략 갱신 태스크 시작 (시세 중계는 심볼별 첫 구독 시 시작)
+    pub fn start(
+        exchange: Arc<RwLock<dyn Exchange>>,
+        market_stream: Arc<RwLock<MarketDataStream>>,
+        order_manager: Arc<RwLock<OrderManager>>,
+        strategy_manager: Arc<RwLock<StrategyManager>>,
+    ) -> Self {
+        let feed = LiveFeed {
+            exchange: exchange.clone(),
+            market_stream,
+            prices: Arc::new(Mutex::new(HashMap::new())),
+            orders: watch::channel(None).0,
+            positions: watch::channel(None).0,
+            strategies: watch::channel(None).0,
+        };
+
+        let om = order_manager.clone();
+        spawn_snapshot(feed.orders.clone(), order_manager.clone(), |_| true, ORDER_RESYNC, move || {
+            let om = om.clone();
+            async move { om.read().await.get_open_orders().await }
+        });
+        let ex = exchange.clone();
+        spawn_snapshot(feed.positions.clone(), order_manager.clone(), |event| event.update.is_fill(), POSITION_RESYNC, move || {
+            let ex = ex.clone();
+            async move { ex.read().await.get_positions().await }
+        });
+
+        let strategies = feed.strategies.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(STRATEGY_CHECK);
+            loop {
+                ticker.tick().await;
+                if strategies.is_closed() {
+                    if strategies.borrow().is_some() {
+                        strategies.send_replace(None);
+                    }
+                    continue;
+                }
+                let list = strategy_manager.read().await.strategy_statuses();
+                strategies.send_if_modified(|current| {
+                    let changed = current.as_ref() != Some(&list);
+                    if changed {
+                        *current = Some(list);
+                    }
+                    changed
+                });
+            }
+        });
+
+        feed
+    }
+
+    /// 심볼 시세 구독 - 심볼당 중계 태스크 하나를 모든 구독자가 공유
+    pub async fn subscribe_prices(&self, symbol: &str) -> broadcast::Receiver<MarketData> {
+        let symbol = symbol.to_uppercase();
+        let (sender, receiver) = {
+            let mut prices = self.prices.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(sender) = prices.get(&symbol) {
+                return sender.subscribe();
+            }
+            let (sender, receiver) = broadcast::channel(256);
+            prices.insert(symbol.clone(), sender.clone());
+            (sender, receiver)
+        };
+        let ticks = self.market_stream.write().await.get_or_create_channel(&symbol).subscribe();
+        self.spawn_price_relay(symbol, sender, ticks);
+        receiver
+    }
+
+    fn spawn_price_relay(&self, symbol: String, sender: broadcast::Sender<MarketData>, ticks: broadcast::Receiver<MarketData>) {
+        let (prices, exchange) = (self.prices.clone(), self.exchange.clone());
+        tokio::spawn(async move {
+            let mut ticks = Some(ticks);
+            loop {
+                // 마지막 구독자가 떠나면 중계 종료 (구독은 같은 잠금 안에서 이뤄지므로 경합 없음)
+                {
+                    let mut prices = prices.lock().unwrap_or_else(|e| e.into_inner());
+                    if sender.receiver_count() == 0 {
+                        prices.remove(&symbol);
+                        return;
+                    }
+                }
+                let pushed = match ticks.as_mut() {
+                    Some(rx) => match tokio::time::timeout(PRICE_STREAM_TIMEOUT, rx.recv()).await {
+                        Ok(Ok(data)) => Some(data),
+                        Ok(Err(RecvError::Lagged(_))) => continue,
+                        Ok(Err(RecvError::Closed)) => {
+                            ticks = None;
+                            None
+                        }
+                        Err(_) => None,
+                    },
+                    None => {
+                        tokio::time::sleep(PRICE_STREAM_TIMEOUT).await;
+                        None
+                    }
+                };
+                let data = match pushed {
+                    Some(data) => data,
+                    None => match exchange.read().await.get_market_data(&symbol).await {
+                        Ok(data) => data,
+                        Err(e) => {
+                            log::debug!("live feed price refresh failed for {}: {}", symbol, e);
+                            continue;
+                        }
+                    },
+                };
+                let _ = sender.send(data);
+            }
+        });
+    }
+
+    /// 미체결 주문 목록 (첫 값이 준비되기 전에는 None)
+    pub fn subscribe_orders(&self) -> watch::Receiver<Option<Vec<Order>>> {
+        self.orders.subscribe()
+    }
+
+    pub fn subscribe_positions(&self) -> watch::Receiver<Option<Vec<Position>>> {
+        self.positions.subscribe()
+    }
+
+    /// 전략별 활성 여부와 실행 상태 (상태가 바뀔 때만, 최대 초당 한 번)
+    pub fn subscribe_strategies(&self) -> watch::Receiver<Option<Vec<StrategyStatus>>> {
+        self.strategies.subscribe()
+    }
+}
+
+/// 스냅샷 갱신 태스크 - `trigger` 에 맞는 주문 이벤트가 오거나 `resync` 가 지나면 구독자가 있을 때만 조회.
+/// 구독자가 모두 떠나면 값을 비워 다음 구독자가 오래된 스냅샷을 받지 않게 한다.
+fn spawn_snapshot<T, F, Fut>(
+    sender: watch::Sender<Option<Vec<T>>>,
+    order_manager: Arc<RwLock<OrderManager>>,
+    trigger: fn(&OrderLifecycleEvent) -> bool,
+    resync: Duration,
+    fetch: F,
+) where
+    T: Serialize + Send + Sync + 'static,
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Vec<T>, TradingError>> + Send,
+{
+    tokio::spawn(async move {
+        let mut events = order_manager.read().await.subscribe_to_order_events();
+        let mut ticker = tokio::time::interval(Duration::from_millis(200));
+        let mut dirty = true;
+        let mut refreshed_at: Option<Instant> = None;
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => dirty |= trigger(&event),
+                    Err(RecvError::Lagged(_)) => dirty = true,
+                    Err(RecvError::Closed) => return,
+                },
+                _ = ticker.tick() => {
+                    dirty |= refreshed_at.is_none_or(|at| at.elapsed() >= resync);
+                }
+            }
+            if sender.is_closed() {
+                if sender.borrow().is_some() {
+                    sender.send_replace(None);
+                }
+                dirty = true;
+                continue;
+            }
+            if !dirty {
+                continue;
+            }
+            // 몰려온 이벤트는 한 번의 조회로 처리
+            while events.try_recv().is_ok() {}
+            refreshed_at = Some(Instant::now());
+            match fetch().await {
+                Ok(list) => {
+                    dirty = false;
+                    let value = serde_json::to_value(&list).ok();
+                    sender.send_if_modified(|current| {
+                        let changed = current.is_none() || current.as_ref().and_then(|c| serde_json::to_value(c).ok()) != value;
+                        if changed {
+                            *current = Some(list);
+                        }
+                        changed
+                    });
+                }
+                Err(e) => log::debug!("live feed snapshot refresh failed: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::mocks::MockExchange;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+    use crate::order_core::repository::InMemoryOrderRepository;
+
+    #[tokio::test]
+    async fn test_shared_price_and_order_feeds() {
+        let mut mock = MockExchange::new(crate::config::Config::default());
+        mock.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let exchange: Arc<RwLock<dyn Exchange>> = Arc::new(RwLock::new(mock));
+        let market_stream = Arc::new(RwLock::new(MarketDataStream::new(100)));
+        let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+        let order_manager = Arc::new(RwLock::new(OrderManager::new(exchange.clone(), repository)));
+        let strategy_manager = Arc::new(RwLock::new(StrategyManager::new()));
+        let feed = LiveFeed::start(exchange.clone(), market_stream.clone(), order_manager.clone(), strategy_manager);
+
+        // 두 구독자가 같은 중계 채널을 공유하고, 스트림 푸시가 그대로 전달된다
+        let mut a = feed.subscribe_prices("BTCUSDT").await;
+        let mut b = feed.subscribe_prices("btcusdt").await;
+        assert_eq!(feed.prices.lock().unwrap().len(), 1);
+        let pushed = exchange.read().await.get_market_data("BTCUSDT").await.unwrap();
+        market_stream.write().await.publish(pushed.clone()).unwrap();
+        assert_eq!(a.recv().await.unwrap().close, pushed.close);
+        assert_eq!(b.recv().await.unwrap().close, pushed.close);
+
+        // 첫 스냅샷 이후 주문 접수 이벤트로 미체결 목록이 다시 조회된다
+        let mut orders = feed.subscribe_orders();
+        tokio::time::timeout(Duration::from_secs(5), orders.wait_for(|o| o.is_some())).await.unwrap().unwrap();
+        let before = orders.borrow_and_update().as_ref().map(Vec::len).unwrap();
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.01), dec!(40_000));
+        order_manager.read().await.create_order(order).await.unwrap();
+        let after = tokio::time::timeout(Duration::from_secs(5), orders.wait_for(|o| o.as_ref().is_some_and(|o| o.len() > before))).await;
+        assert!(after.is_ok());
+
+        // 구독자가 모두 떠나면 시세 중계가 정리된다
+        drop((a, b));
+        for _ in 0..50 {
+            if feed.prices.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(feed.prices.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,264 @@
+//! 선물 증거금 / 청산 감시 모듈
+//!
+//! 주기적으로 거래소의 증거금 잔고와 유지 증거금, 포지션별 청산 가격을 조회해 증거금 비율
+//! (유지 증거금 / 증거금 잔고) 이 경고/위험 기준을 넘으면 알림을 보낸다. `derisk` 가 켜져 있으면
+//! 위험 구간에 머무는 동안 쿨다운마다 모든 포지션을 `derisk_fraction` 만큼 reduce-only 시장가로 줄인다.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::{broadcast, RwLock};
+
+use crate::config::MarginMonitorConfig;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::margin::{MarginAccount, PositionMargin};
+use crate::models::order::{Order, OrderSide, OrderType};
+use crate::order_core::manager::OrderManager;
+use crate::order_core::submission_queue::SubmissionPriority;
+
+/// 증거금 위험 단계
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum MarginLevel {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// 증거금 감시 상태 (`GET /risk/margin`)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MarginStatus {
+    pub enabled: bool,
+    pub level: MarginLevel,
+    pub margin_ratio: Option<f64>,
+    pub account: Option<MarginAccount>,
+    pub positions: Vec<PositionMargin>,
+    pub checked_at: Option<i64>,
+    /// 마지막 조회 실패 사유 (성공하면 지워짐)
+    pub last_error: Option<String>,
+    pub last_derisk_at: Option<i64>,
+}
+
+/// 증거금 알림
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum MarginEvent {
+    /// 위험 단계 변경 (복구 포함)
+    LevelChanged { from: MarginLevel, to: MarginLevel, margin_ratio: f64 },
+    /// 마크 가격이 청산 가격에 `liquidation_distance` 이내로 접근
+    LiquidationNear { symbol: String, position_side: Option<String>, mark_price: f64, liquidation_price: f64, distance: f64 },
+    /// 위험 구간 자동 축소 주문 제출
+    Derisked { symbol: String, side: OrderSide, quantity: Decimal, margin_ratio: f64 },
+}
+
+// (심볼, 헤지 모드 포지션 쪽)
+type PositionKey = (String, Option<String>);
+
+/// 증거금 감시자 - 감시 태스크와 HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct MarginMonitor {
+    config: MarginMonitorConfig,
+    state: Arc<Mutex<MarginStatus>>,
+    /// 청산 근접 알림을 이미 보낸 포지션 (멀어지면 다시 알림)
+    near_liquidation: Arc<Mutex<HashSet<PositionKey>>>,
+    events: broadcast::Sender<MarginEvent>,
+}
+
+impl MarginMonitor {
+    pub fn new(config: MarginMonitorConfig) -> Self {
+        MarginMonitor {
+            state: Arc::new(Mutex::new(MarginStatus { enabled: config.enabled, ..MarginStatus::default() })),
+            config,
+            near_liquidation: Arc::new(Mutex::new(HashSet::new())),
+            events: broadcast::channel(64).0,
+        }
+    }
+
+    /// 알림 구독
+    pub fn subscribe(&self) -> broadcast::Receiver<MarginEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn status(&self) -> MarginStatus {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn level_for(&self, ratio: f64) -> MarginLevel {
+        if ratio >= self.config.critical_ratio {
+            MarginLevel::Critical
+        } else if ratio >= self.config.warn_ratio {
+            MarginLevel::Warning
+        } else {
+            MarginLevel::Normal
+        }
+    }
+
+    /// 조회 결과 반영 - 단계 변경/청산 근접 알림을 보내고 제출할 축소 주문을 돌려준다
+    pub fn evaluate(&self, account: MarginAccount, positions: Vec<PositionMargin>, now: i64) -> Vec<Order> {
+        let ratio = account.margin_ratio();
+        let level = self.level_for(ratio);
+        self.check_liquidation_distance(&positions);
+
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if level != state.level {
+            log::warn!("margin ratio {:.3}: {:?} -> {:?}", ratio, state.level, level);
+            let _ = self.events.send(MarginEvent::LevelChanged { from: state.level, to: level, margin_ratio: ratio });
+        }
+        let derisk = level == MarginLevel::Critical
+            && self.config.derisk
+            && state.last_derisk_at.is_none_or(|at| now - at >= self.config.derisk_cooldown_ms);
+        let orders = if derisk {
+            state.last_derisk_at = Some(now);
+            positions.iter().filter_map(|p| self.derisk_order(p)).collect()
+        } else {
+            Vec::new()
+        };
+        *state = MarginStatus {
+            enabled: self.config.enabled,
+            level,
+            margin_ratio: Some(ratio),
+            account: Some(account),
+            positions,
+            checked_at: Some(now),
+            last_error: None,
+            last_derisk_at: state.last_derisk_at,
+        };
+        orders
+    }
+
+    fn check_liquidation_distance(&self, positions: &[PositionMargin]) {
+        let Some(threshold) = self.config.liquidation_distance else {
+            return;
+        };
+        let mut near = self.near_liquidation.lock().unwrap_or_else(|e| e.into_inner());
+        let mut still_near = HashSet::new();
+        for position in positions {
+            let (Some(distance), Some(liquidation_price)) = (position.liquidation_distance(), position.liquidation_price) else {
+                continue;
+            };
+            if distance > threshold {
+                continue;
+            }
+            let key = (position.symbol.clone(), position.position_side.clone());
+            if !near.contains(&key) {
+                log::warn!("{} mark {} is {:.2}% from liquidation at {}", position.symbol, position.mark_price, distance * 100.0, liquidation_price);
+                let _ = self.events.send(MarginEvent::LiquidationNear {
+                    symbol: position.symbol.clone(),
+                    position_side: position.position_side.clone(),
+                    mark_price: position.mark_price,
+                    liquidation_price,
+                    distance,
+                });
+            }
+            still_near.insert(key);
+        }
+        *near = still_near;
+    }
+
+    // 포지션 일부를 닫는 시장가 주문 (헤지 모드는 positionSide, 단방향은 reduce-only)
+    fn derisk_order(&self, position: &PositionMargin) -> Option<Order> {
+        let quantity = to_decimal(position.quantity.abs() * self.config.derisk_fraction);
+        if quantity <= Decimal::ZERO {
+            return None;
+        }
+        let side = if position.quantity > 0.0 { OrderSide::Sell } else { OrderSide::Buy };
+        let order = Order::new(position.symbol.clone(), side, OrderType::Market, quantity, Decimal::ZERO);
+        Some(match position.position_side.as_deref() {
+            Some(position_side @ ("LONG" | "SHORT")) => order.with_position_side(position_side),
+            _ => order.with_reduce_only(true),
+        })
+    }
+
+    fn record_error(&self, error: String) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).last_error = Some(error);
+    }
+
+    /// 감시 태스크 시작 - 위험 구간 축소 주문은 제출 큐의 위험 감소 우선순위로 제출
+    /// (큐가 가득 차도 받아들여지고 대기 중인 진입 주문보다 먼저 나감)
+    pub fn start(&self, exchange: Arc<RwLock<dyn Exchange>>, order_manager: Arc<RwLock<OrderManager>>) -> tokio::task::JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let queue = order_manager.read().await.submission_queue();
+            let mut ticker = tokio::time::interval(Duration::from_millis(monitor.config.interval_ms));
+            loop {
+                ticker.tick().await;
+                let (account, positions) = {
+                    let ex = exchange.read().await;
+                    (ex.get_margin_account().await, ex.get_position_margins().await)
+                };
+                let (account, positions) = match (account, positions) {
+                    (Ok(account), Ok(positions)) => (account, positions),
+                    (Err(e), _) | (_, Err(e)) => {
+                        log::warn!("margin monitor fetch failed: {}", e);
+                        monitor.record_error(e.to_string());
+                        continue;
+                    }
+                };
+                let ratio = account.margin_ratio();
+                let orders = monitor.evaluate(account, positions, chrono::Utc::now().timestamp_millis());
+                for order in orders {
+                    let (symbol, side, quantity) = (order.symbol.clone(), order.side.clone(), order.quantity);
+                    match queue.submit_with_priority(SubmissionPriority::RiskReducing, None, order).await {
+                        Ok(_) => {
+                            log::warn!("margin ratio {:.3} critical: reduced {} by {:?} {}", ratio, symbol, side, quantity);
+                            let _ = monitor.events.send(MarginEvent::Derisked { symbol, side, quantity, margin_ratio: ratio });
+                        }
+                        Err(e) => log::error!("margin de-risk order for {} failed: {}", symbol, e),
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    fn account(maintenance_margin: f64) -> MarginAccount {
+        MarginAccount { margin_balance: 1_000.0, maintenance_margin, timestamp: 0 }
+    }
+
+    fn position(symbol: &str, quantity: f64, position_side: Option<&str>, liquidation_price: f64) -> PositionMargin {
+        PositionMargin {
+            symbol: symbol.to_string(),
+            position_side: position_side.map(str::to_string),
+            quantity,
+            mark_price: 100.0,
+            liquidation_price: Some(liquidation_price),
+        }
+    }
+
+    #[test]
+    fn test_margin_levels_and_derisk() {
+        let config = MarginMonitorConfig { enabled: true, derisk: true, liquidation_distance: Some(0.05), ..MarginMonitorConfig::default() };
+        let monitor = MarginMonitor::new(config);
+        let mut events = monitor.subscribe();
+        let positions = vec![position("BTCUSDT", 2.0, None, 70.0), position("ETHUSDT", -4.0, Some("SHORT"), 104.0)];
+
+        assert!(monitor.evaluate(account(100.0), positions.clone(), 0).is_empty());
+        // ETH 숏은 청산가까지 4% - 한 번만 알림
+        assert!(matches!(events.try_recv().unwrap(), MarginEvent::LiquidationNear { ref symbol, .. } if symbol == "ETHUSDT"));
+        assert!(monitor.evaluate(account(600.0), positions.clone(), 1_000).is_empty());
+        assert!(matches!(events.try_recv().unwrap(), MarginEvent::LevelChanged { to: MarginLevel::Warning, .. }));
+
+        // 위험 구간: 포지션마다 25% 축소, 쿨다운 동안은 다시 줄이지 않음
+        let orders = monitor.evaluate(account(850.0), positions.clone(), 2_000);
+        assert!(matches!(events.try_recv().unwrap(), MarginEvent::LevelChanged { to: MarginLevel::Critical, .. }));
+        assert_eq!(orders.len(), 2);
+        assert_eq!((&orders[0].side, orders[0].quantity, orders[0].reduce_only), (&OrderSide::Sell, dec!(0.5), Some(true)));
+        assert_eq!((&orders[1].side, orders[1].quantity, orders[1].position_side.as_deref()), (&OrderSide::Buy, dec!(1), Some("SHORT")));
+        assert!(monitor.evaluate(account(850.0), positions.clone(), 3_000).is_empty());
+        assert_eq!(monitor.evaluate(account(850.0), positions.clone(), 2_000 + 300_000).len(), 2);
+
+        assert!(monitor.evaluate(account(100.0), positions, 400_000).is_empty());
+        assert!(matches!(events.try_recv().unwrap(), MarginEvent::LevelChanged { to: MarginLevel::Normal, .. }));
+        assert!(events.try_recv().is_err());
+        assert_eq!(monitor.status().margin_ratio, Some(0.1));
+    }
+}
@@ -4,6 +4,27 @@ pub mod vwap_splitter;
 pub mod iceberg_manager;
 pub mod trailing_stop_manager;
 pub mod twap_splitter;
+pub mod is_splitter;
 pub mod risk_manager;
+pub mod daily_loss;
+pub mod margin_monitor;
 pub mod execution_analyzer;
 pub mod strategy_manager;
+pub mod strategy_guard;
+pub mod strategy_scheduler;
+pub mod protective_stop;
+pub mod capital_allocator;
+pub mod pnl_buckets;
+pub mod strategy_watchdog;
+pub mod strategy_store;
+pub mod dead_man_switch;
+pub mod degraded_mode;
+pub mod kill_switch;
+pub mod oco_manager;
+pub mod conditional_orders;
+pub mod portfolio;
+pub mod trade_journal;
+pub mod config_reload;
+pub mod live_feed;
+pub mod shadow;
+pub mod signal_dedup;
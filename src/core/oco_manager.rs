@@ -0,0 +1,248 @@
+//! OCO(one-cancels-other) 브래킷 관리자
+//!
+//! 브래킷의 진입 주문을 제출하고, 주문 이벤트(사용자 데이터 스트림)와 주기적인 상태 조회로
+//! 구성 주문의 체결을 감시한다. 진입이 체결되면 익절/손절을 내고, 한쪽이 체결되면 다른 쪽을 취소.
+//! 청산 주문과 취소는 위험 축소 우선순위로 제출 큐에 넣는다.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::error::TradingError;
+use crate::models::order::{OrderId, OrderStatus};
+use crate::order_core::bracket::{Bracket, BracketAction, BracketOrder, BracketStatus};
+use crate::order_core::manager::OrderManager;
+use crate::order_core::submission_queue::{SubmissionPriority, SubmissionQueue};
+
+/// 브래킷 주문 관리자 - HTTP 핸들러와 감시 태스크가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct OcoManager {
+    order_manager: Arc<RwLock<OrderManager>>,
+    brackets: Arc<Mutex<HashMap<String, Bracket>>>,
+    poll_interval: Duration,
+}
+
+impl OcoManager {
+    pub fn new(order_manager: Arc<RwLock<OrderManager>>) -> Self {
+        OcoManager {
+            order_manager,
+            brackets: Arc::new(Mutex::new(HashMap::new())),
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// 상태 조회 주기 (사용자 데이터 스트림이 없으면 이 주기로만 체결 감지)
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    /// 브래킷 생성 - 진입 주문 제출 후 현재 상태 반환
+    pub async fn submit(&self, strategy: Option<String>, order: BracketOrder) -> Result<Bracket, TradingError> {
+        let queue = self.order_manager.read().await.submission_queue();
+        let priority = SubmissionPriority::classify(&order.entry);
+        self.enqueue(&queue, priority, strategy, order)?.await
+    }
+
+    /// 진입 주문을 지금 제출 큐에 넣고(제출 순서 확정), 제출 결과 대기와 브래킷 등록은 반환된 future 에서
+    pub fn enqueue(
+        &self,
+        queue: &SubmissionQueue,
+        priority: SubmissionPriority,
+        strategy: Option<String>,
+        order: BracketOrder,
+    ) -> Result<impl Future<Output = Result<Bracket, TradingError>> + Send + 'static, TradingError> {
+        order.validate().map_err(TradingError::InvalidParameter)?;
+        let bracket = Bracket::new(strategy.clone(), order, Self::now());
+        let pending = queue.enqueue_submit(priority, strategy, bracket.order.entry.clone())?;
+        let manager = self.clone();
+        Ok(async move {
+            let entry_id = pending.wait().await?;
+            manager.register(bracket, entry_id).await
+        })
+    }
+
+    // 진입 주문이 접수된 브래킷 등록
+    async fn register(&self, mut bracket: Bracket, entry_id: OrderId) -> Result<Bracket, TradingError> {
+        log::info!("bracket {} entry submitted: {}", bracket.id, entry_id);
+        bracket.entry_id = Some(entry_id.clone());
+        let id = bracket.id.clone();
+        self.brackets.lock().unwrap_or_else(|e| e.into_inner()).insert(id.clone(), bracket);
+
+        // 시장가 진입은 이미 체결됐을 수 있으므로 바로 확인
+        self.refresh(&entry_id).await;
+        self.get(&id).ok_or(TradingError::DataNotFound(id))
+    }
+
+    /// 브래킷 취소 - 남은 구성 주문 모두 취소
+    pub async fn cancel(&self, id: &str) -> Result<Bracket, TradingError> {
+        let open = {
+            let mut brackets = self.brackets.lock().unwrap_or_else(|e| e.into_inner());
+            let bracket = brackets.get_mut(id).ok_or_else(|| TradingError::DataNotFound(format!("bracket {}", id)))?;
+            if bracket.status.is_terminal() {
+                return Ok(bracket.clone());
+            }
+            let open = bracket.open_order_ids();
+            bracket.status = BracketStatus::Cancelled;
+            bracket.updated_at = Self::now();
+            open
+        };
+        for order_id in open {
+            self.cancel_order(id, order_id).await;
+        }
+        self.get(id).ok_or_else(|| TradingError::DataNotFound(id.to_string()))
+    }
+
+    pub fn get(&self, id: &str) -> Option<Bracket> {
+        self.brackets.lock().unwrap_or_else(|e| e.into_inner()).get(id).cloned()
+    }
+
+    /// 전체 브래킷 (최신순)
+    pub fn list(&self) -> Vec<Bracket> {
+        let mut brackets: Vec<Bracket> = self.brackets.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+        brackets.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+        brackets
+    }
+
+    /// 주문 상태 변화 반영 - 해당 주문이 속한 브래킷의 후속 주문 제출/취소
+    pub async fn on_order_status(&self, order_id: &OrderId, status: OrderStatus) {
+        let (id, actions) = {
+            let mut brackets = self.brackets.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(bracket) = brackets.values_mut().find(|b| !b.status.is_terminal() && b.contains(order_id)) else {
+                return;
+            };
+            (bracket.id.clone(), bracket.on_order_status(order_id, &status, Self::now()))
+        };
+
+        for action in actions {
+            match action {
+                BracketAction::PlaceExits => self.place_exits(&id).await,
+                BracketAction::Cancel(sibling) => {
+                    log::info!("bracket {}: {} {:?}, cancelling {}", id, order_id, status, sibling);
+                    self.cancel_order(&id, sibling).await;
+                }
+            }
+        }
+    }
+
+    /// 진행 중인 모든 브래킷의 미완료 주문 상태 조회
+    pub async fn poll(&self) {
+        let open: Vec<OrderId> = {
+            let brackets = self.brackets.lock().unwrap_or_else(|e| e.into_inner());
+            brackets.values().flat_map(|b| b.open_order_ids()).collect()
+        };
+        for order_id in open {
+            self.refresh(&order_id).await;
+        }
+    }
+
+    async fn refresh(&self, order_id: &OrderId) {
+        let status = self.order_manager.read().await.get_order_status(order_id).await;
+        match status {
+            Ok(status) => self.on_order_status(order_id, status).await,
+            Err(e) => log::debug!("bracket order {} status unavailable: {}", order_id, e),
+        }
+    }
+
+    async fn place_exits(&self, id: &str) {
+        let Some(bracket) = self.get(id) else { return };
+        let queue = self.order_manager.read().await.submission_queue();
+        let strategy = bracket.strategy.clone();
+        let take_profit = queue.submit_with_priority(SubmissionPriority::RiskReducing, strategy.clone(), bracket.order.take_profit_order()).await;
+        let stop_loss = queue.submit_with_priority(SubmissionPriority::RiskReducing, strategy, bracket.order.stop_loss_order()).await;
+
+        let cleanup = {
+            let mut brackets = self.brackets.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(bracket) = brackets.get_mut(id) else { return };
+            bracket.updated_at = Self::now();
+            match (take_profit, stop_loss) {
+                (Ok(tp), Ok(sl)) => {
+                    log::info!("bracket {} active: take profit {}, stop loss {}", id, tp, sl);
+                    bracket.take_profit_id = Some(tp);
+                    bracket.stop_loss_id = Some(sl);
+                    None
+                }
+                // 한쪽만 들어가면 OCO 가 성립하지 않으므로 들어간 쪽도 취소하고 실패 처리
+                (tp, sl) => {
+                    let error = tp.as_ref().err().or(sl.as_ref().err()).map(|e| e.to_string());
+                    log::error!("bracket {} exit orders failed, position left unprotected: {:?}", id, error);
+                    bracket.status = BracketStatus::Failed;
+                    bracket.error = error;
+                    tp.ok().or(sl.ok())
+                }
+            }
+        };
+        if let Some(order_id) = cleanup {
+            self.cancel_order(id, order_id).await;
+        }
+    }
+
+    async fn cancel_order(&self, id: &str, order_id: OrderId) {
+        let queue = self.order_manager.read().await.submission_queue();
+        if let Err(e) = queue.cancel(order_id.clone()).await {
+            log::warn!("bracket {}: cancel {} failed: {}", id, order_id, e);
+        }
+    }
+
+    /// 감시 태스크 시작 - 주문 이벤트를 즉시 반영하고, 주기적으로 상태를 조회해 누락 보완
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut events = manager.order_manager.read().await.subscribe_to_order_events();
+            let mut ticker = tokio::time::interval(manager.poll_interval);
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(event) => manager.on_order_status(&event.update.order_id, event.event.status()).await,
+                        Err(RecvError::Lagged(n)) => log::warn!("bracket monitor lagged {} order updates", n),
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = ticker.tick() => manager.poll().await,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::exchange::mocks::MockExchange;
+    use crate::exchange::traits::Exchange;
+    use crate::models::order::{Order, OrderSide, OrderType};
+    use crate::order_core::repository::InMemoryOrderRepository;
+
+    #[tokio::test]
+    async fn test_oco_cancels_sibling() {
+        let exchange = Arc::new(RwLock::new(MockExchange::new(crate::config::Config::default())));
+        exchange.write().await.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+        let order_manager = Arc::new(RwLock::new(OrderManager::new(exchange.clone(), repository)));
+        OrderManager::start_submission_worker(order_manager.clone());
+        let oco = OcoManager::new(order_manager);
+
+        // 시장가 진입은 즉시 체결 -> 익절/손절 제출
+        let entry = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(0.01), dec!(0));
+        let bracket = oco.submit(Some("manual".into()), BracketOrder::new(entry, dec!(51_000), dec!(49_000))).await.unwrap();
+        assert_eq!(bracket.status, BracketStatus::Active);
+        let (tp, sl) = (bracket.take_profit_id.clone().unwrap(), bracket.stop_loss_id.clone().unwrap());
+
+        // 익절가 도달 -> 손절 주문 취소
+        exchange.write().await.set_price("BTCUSDT", dec!(51_500)).unwrap();
+        oco.poll().await;
+        let bracket = oco.get(&bracket.id).unwrap();
+        assert_eq!(bracket.status, BracketStatus::TakeProfitFilled);
+        let ex = exchange.read().await;
+        assert_eq!(ex.get_order_status(&tp).await.unwrap(), OrderStatus::Filled);
+        assert_eq!(ex.get_order_status(&sl).await.unwrap(), OrderStatus::Cancelled);
+    }
+}
@@ -0,0 +1,203 @@
+//! 시간대별 손익 집계 모듈
+//!
+//! 실현 손익을 설정된 타임존 기준 시간(0~23시)과 요일(월~일) 버킷으로 집계하여
+//! 전략이 실제로 수익을 내는 시간대를 파악하고 세션 스케줄 설정에 활용할 수 있게 한다.
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, FixedOffset, TimeZone, Timelike};
+use serde::Serialize;
+
+use crate::error::TradingError;
+use crate::models::order::OrderSide;
+use crate::models::trade::Trade;
+use crate::models::decimal::to_f64;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// "UTC", "+09:00", "-0530" 형식의 타임존 오프셋 파싱
+pub fn parse_utc_offset(tz: &str) -> Result<FixedOffset, TradingError> {
+    let tz = tz.trim();
+    if tz.is_empty() || tz.eq_ignore_ascii_case("UTC") || tz.eq_ignore_ascii_case("Z") {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+    }
+
+    let invalid = || TradingError::InvalidParameter(format!("Invalid timezone offset: {}", tz));
+    let (sign, rest) = if let Some(rest) = tz.strip_prefix('+') {
+        (1, rest)
+    } else if let Some(rest) = tz.strip_prefix('-') {
+        (-1, rest)
+    } else {
+        return Err(invalid());
+    };
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().map_err(|_| invalid())?, 0),
+        4 => (
+            digits[..2].parse::<i32>().map_err(|_| invalid())?,
+            digits[2..].parse::<i32>().map_err(|_| invalid())?,
+        ),
+        _ => return Err(invalid()),
+    };
+    if hours > 14 || minutes >= 60 {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// 버킷 통계
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BucketStats {
+    pub label: String,
+    pub trades: usize,
+    pub wins: usize,
+    pub losses: usize,
+    pub pnl: f64,
+}
+
+impl BucketStats {
+    fn labeled(label: impl Into<String>) -> Self {
+        BucketStats { label: label.into(), ..Default::default() }
+    }
+
+    fn record(&mut self, pnl: f64) {
+        self.trades += 1;
+        self.pnl += pnl;
+        if pnl > 0.0 {
+            self.wins += 1;
+        } else if pnl < 0.0 {
+            self.losses += 1;
+        }
+    }
+
+    /// 승률 (%)
+    pub fn win_rate(&self) -> f64 {
+        if self.trades == 0 {
+            return 0.0;
+        }
+        self.wins as f64 / self.trades as f64 * 100.0
+    }
+}
+
+/// 시간대/요일별 손익 리포트
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeBucketReport {
+    pub timezone: String,
+    #[serde(skip)]
+    offset: FixedOffset,
+    pub by_hour: Vec<BucketStats>,
+    pub by_weekday: Vec<BucketStats>,
+}
+
+impl TimeBucketReport {
+    /// 타임존 문자열로 리포트 생성
+    pub fn new(timezone: &str) -> Result<Self, TradingError> {
+        let offset = parse_utc_offset(timezone)?;
+        Ok(TimeBucketReport {
+            timezone: if timezone.trim().is_empty() { "UTC".to_string() } else { timezone.trim().to_string() },
+            offset,
+            by_hour: (0..24).map(|h| BucketStats::labeled(format!("{:02}:00", h))).collect(),
+            by_weekday: WEEKDAYS.iter().map(|d| BucketStats::labeled(*d)).collect(),
+        })
+    }
+
+    /// 실현 손익 기록 (timestamp: ms)
+    pub fn record(&mut self, timestamp: i64, pnl: f64) {
+        let Some(local) = self.offset.timestamp_millis_opt(timestamp).single() else {
+            return;
+        };
+        self.by_hour[local.hour() as usize].record(pnl);
+        self.by_weekday[local.weekday().num_days_from_monday() as usize].record(pnl);
+    }
+
+    /// 체결 목록에서 심볼별 평균단가 기준 실현 손익을 계산하여 리포트 생성
+    pub fn from_trades(trades: &[Trade], timezone: &str) -> Result<Self, TradingError> {
+        let mut report = Self::new(timezone)?;
+        let mut positions: HashMap<&str, (f64, f64)> = HashMap::new(); // (순수량, 평균단가)
+
+        let mut sorted: Vec<&Trade> = trades.iter().collect();
+        sorted.sort_by_key(|t| t.timestamp);
+        for trade in sorted {
+            let (price, quantity) = (to_f64(trade.price), to_f64(trade.quantity));
+            let signed = match trade.side {
+                OrderSide::Buy => quantity,
+                OrderSide::Sell => -quantity,
+            };
+            let (qty, avg) = positions.entry(trade.symbol.as_str()).or_insert((0.0, 0.0));
+            if *qty == 0.0 || qty.signum() == signed.signum() {
+                let total = qty.abs() + quantity;
+                *avg = (*avg * qty.abs() + price * quantity) / total;
+                *qty += signed;
+                continue;
+            }
+
+            let closed = quantity.min(qty.abs());
+            report.record(trade.timestamp.as_millis(), qty.signum() * (price - *avg) * closed);
+            *qty += signed;
+            if qty.abs() < f64::EPSILON {
+                *qty = 0.0;
+                *avg = 0.0;
+            } else if qty.signum() == signed.signum() {
+                *avg = price;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// 손익 기준 상위 시간대
+    pub fn best_hours(&self, n: usize) -> Vec<&BucketStats> {
+        let mut hours: Vec<&BucketStats> = self.by_hour.iter().filter(|b| b.trades > 0).collect();
+        hours.sort_by(|a, b| b.pnl.partial_cmp(&a.pnl).unwrap_or(std::cmp::Ordering::Equal));
+        hours.truncate(n);
+        hours
+    }
+
+    /// 보고서용 요약 문자열 (거래가 있는 버킷만)
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        summary.push_str(&format!("시간대별 손익 ({})\n", self.timezone));
+        for b in self.by_hour.iter().filter(|b| b.trades > 0) {
+            summary.push_str(&format!("  {} 거래 {:>4} 손익 {:>12.2} 승률 {:>6.2}%\n", b.label, b.trades, b.pnl, b.win_rate()));
+        }
+        summary.push_str("요일별 손익\n");
+        for b in self.by_weekday.iter().filter(|b| b.trades > 0) {
+            summary.push_str(&format!("  {}   거래 {:>4} 손익 {:>12.2} 승률 {:>6.2}%\n", b.label, b.trades, b.pnl, b.win_rate()));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::OrderId;
+
+    #[test]
+    fn test_time_buckets() {
+        assert_eq!(parse_utc_offset("+09:00").unwrap().local_minus_utc(), 9 * 3600);
+        assert_eq!(parse_utc_offset("-0530").unwrap().local_minus_utc(), -(5 * 3600 + 30 * 60));
+        assert!(parse_utc_offset("KST").is_err());
+
+        // 2024-01-01 00:30 UTC (월요일) = 09:30 KST
+        let ts = 1_704_069_000_000;
+        let mut report = TimeBucketReport::new("+09:00").unwrap();
+        report.record(ts, 10.0);
+        assert_eq!(report.by_hour[9].trades, 1);
+        assert_eq!(report.by_weekday[0].pnl, 10.0);
+
+        let trades = vec![
+            Trade::new("1", "BTCUSDT", dec!(100), dec!(1), ts, OrderId("a".into()), OrderSide::Buy),
+            Trade::new("2", "BTCUSDT", dec!(110), dec!(1), ts + 3_600_000, OrderId("b".into()), OrderSide::Sell),
+        ];
+        let report = TimeBucketReport::from_trades(&trades, "UTC").unwrap();
+        assert_eq!(report.by_hour[1].pnl, 10.0);
+        assert_eq!(report.by_hour[1].wins, 1);
+        assert_eq!(report.best_hours(1)[0].label, "01:00");
+    }
+}
@@ -0,0 +1,378 @@
+//! 포트폴리오/손익 추적 모듈
+//!
+//! 주문 관리자의 체결 이벤트와 시세를 받아 심볼별 순포지션(평균 진입가), 실현/미실현 손익,
+//! 노출 금액을 계산하고 일정 간격으로 자산(equity) 곡선을 기록한다.
+//! 같은 주문의 체결이 사용자 데이터 스트림과 상태 폴링으로 중복 전달될 수 있어
+//! 주문별 누적 체결 수량으로 새 체결분만 반영한다.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::config::PortfolioConfig;
+use crate::core::daily_loss::DailyLossTracker;
+use crate::exchange::traits::Exchange;
+use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{OrderId, OrderSide, OrderUpdate};
+use crate::order_core::manager::OrderManager;
+
+/// 심볼별 포지션
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PortfolioPosition {
+    pub symbol: String,
+    /// 부호 있는 순수량 (음수면 숏)
+    pub quantity: f64,
+    pub avg_entry_price: f64,
+    pub realized_pnl: f64,
+    /// 누적 수수료 (호가 통화)
+    pub fees: f64,
+    pub last_price: f64,
+    pub unrealized_pnl: f64,
+    /// 현재가 기준 부호 있는 노출 금액
+    pub exposure: f64,
+    pub updated_at: i64,
+}
+
+impl PortfolioPosition {
+    fn apply_fill(&mut self, signed_qty: f64, price: f64) {
+        let next = self.quantity + signed_qty;
+        if self.quantity == 0.0 || self.quantity.signum() == signed_qty.signum() {
+            self.avg_entry_price = (self.quantity.abs() * self.avg_entry_price + signed_qty.abs() * price) / next.abs();
+        } else {
+            // 반대 방향 체결 - 줄어든 수량만큼 손익 실현
+            let closed = signed_qty.abs().min(self.quantity.abs());
+            self.realized_pnl += closed * (price - self.avg_entry_price) * self.quantity.signum();
+            if next != 0.0 && next.signum() != self.quantity.signum() {
+                self.avg_entry_price = price;
+            }
+        }
+        self.quantity = if next.abs() < 1e-12 { 0.0 } else { next };
+        if self.quantity == 0.0 {
+            self.avg_entry_price = 0.0;
+        }
+        self.mark(if self.last_price > 0.0 { self.last_price } else { price });
+    }
+
+    fn mark(&mut self, price: f64) {
+        self.last_price = price;
+        self.unrealized_pnl = self.quantity * (price - self.avg_entry_price);
+        self.exposure = self.quantity * price;
+    }
+}
+
+/// 포트폴리오 요약 (GET /portfolio)
+#[derive(Debug, Clone, Serialize)]
+pub struct PortfolioSnapshot {
+    pub timestamp: i64,
+    pub starting_equity: f64,
+    pub equity: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub fees: f64,
+    /// 롱/숏 노출 절대값 합계
+    pub gross_exposure: f64,
+    /// 부호 있는 노출 합계
+    pub net_exposure: f64,
+    pub positions: Vec<PortfolioPosition>,
+}
+
+/// 자산 곡선 표본 (GET /portfolio/history)
+#[derive(Debug, Clone, Serialize)]
+pub struct EquityPoint {
+    pub timestamp: i64,
+    pub equity: f64,
+    pub realized_pnl: f64,
+    pub unrealized_pnl: f64,
+    pub gross_exposure: f64,
+}
+
+/// 포트폴리오 상태 - 입출력 없이 체결/시세만 반영
+pub struct Portfolio {
+    config: PortfolioConfig,
+    positions: HashMap<String, PortfolioPosition>,
+    /// 주문별 반영된 누적 체결 수량
+    filled: HashMap<OrderId, Decimal>,
+    history: VecDeque<EquityPoint>,
+}
+
+impl Portfolio {
+    pub fn new(config: PortfolioConfig) -> Self {
+        Portfolio {
+            config,
+            positions: HashMap::new(),
+            filled: HashMap::new(),
+            history: VecDeque::new(),
+        }
+    }
+
+    /// 주문 이벤트 반영 - 새로 체결된 수량이 있으면 true
+    pub fn apply_update(&mut self, update: &OrderUpdate) -> bool {
+        if !update.is_fill() || update.last_fill_price <= Decimal::ZERO {
+            return false;
+        }
+        // 누적 수량이 있으면 이미 반영한 부분을 빼고, 없으면 이벤트의 체결 수량 그대로 사용
+        let seen = self.filled.get(&update.order_id).copied().unwrap_or_default();
+        let quantity = update.new_fill_quantity(seen);
+        if quantity <= Decimal::ZERO {
+            return false;
+        }
+        self.filled.insert(update.order_id.clone(), seen + quantity);
+
+        let signed_qty = match update.side {
+            OrderSide::Buy => to_f64(quantity),
+            OrderSide::Sell => -to_f64(quantity),
+        };
+        let position = self.positions.entry(update.symbol.clone()).or_insert_with(|| PortfolioPosition {
+            symbol: update.symbol.clone(),
+            ..PortfolioPosition::default()
+        });
+        position.apply_fill(signed_qty, to_f64(update.last_fill_price));
+        // 호가 통화 수수료만 손익에 반영 (BNB 등 다른 자산 수수료는 제외)
+        if update.fee_asset.as_deref().is_none_or(|asset| update.symbol.ends_with(asset)) {
+            position.fees += to_f64(update.fee);
+        }
+        position.updated_at = update.timestamp;
+        true
+    }
+
+    /// 시세 반영 (보유하지 않은 심볼은 무시)
+    pub fn update_price(&mut self, symbol: &str, price: f64, now: i64) {
+        if price <= 0.0 {
+            return;
+        }
+        if let Some(position) = self.positions.get_mut(symbol) {
+            position.mark(price);
+            position.updated_at = now;
+        }
+    }
+
+    /// 심볼의 수수료 차감 실현 손익
+    pub fn realized_net(&self, symbol: &str) -> f64 {
+        self.positions.get(symbol).map_or(0.0, |p| p.realized_pnl - p.fees)
+    }
+
+    /// 보유 중인 심볼
+    pub fn open_symbols(&self) -> Vec<String> {
+        self.positions.values().filter(|p| p.quantity != 0.0).map(|p| p.symbol.clone()).collect()
+    }
+
+    pub fn snapshot(&self, now: i64) -> PortfolioSnapshot {
+        let mut positions: Vec<PortfolioPosition> = self.positions.values().cloned().collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        let realized_pnl: f64 = positions.iter().map(|p| p.realized_pnl).sum();
+        let unrealized_pnl: f64 = positions.iter().map(|p| p.unrealized_pnl).sum();
+        let fees: f64 = positions.iter().map(|p| p.fees).sum();
+        PortfolioSnapshot {
+            timestamp: now,
+            starting_equity: self.config.starting_equity,
+            equity: self.config.starting_equity + realized_pnl + unrealized_pnl - fees,
+            realized_pnl,
+            unrealized_pnl,
+            fees,
+            gross_exposure: positions.iter().map(|p| p.exposure.abs()).sum(),
+            net_exposure: positions.iter().map(|p| p.exposure).sum(),
+            positions,
+        }
+    }
+
+    /// 자산 곡선 표본 기록 (최근 `history_limit` 개 보관)
+    pub fn record_equity(&mut self, now: i64) -> EquityPoint {
+        let snapshot = self.snapshot(now);
+        let point = EquityPoint {
+            timestamp: now,
+            equity: snapshot.equity,
+            realized_pnl: snapshot.realized_pnl,
+            unrealized_pnl: snapshot.unrealized_pnl,
+            gross_exposure: snapshot.gross_exposure,
+        };
+        self.history.push_back(point.clone());
+        while self.history.len() > self.config.history_limit.max(1) {
+            self.history.pop_front();
+        }
+        point
+    }
+
+    /// 자산 곡선 (`since` 이후, 최근 `limit` 개)
+    pub fn history(&self, since: Option<i64>, limit: Option<usize>) -> Vec<EquityPoint> {
+        let points: Vec<EquityPoint> = self.history.iter()
+            .filter(|p| since.is_none_or(|since| p.timestamp >= since))
+            .cloned()
+            .collect();
+        let skip = limit.map(|limit| points.len().saturating_sub(limit)).unwrap_or(0);
+        points.into_iter().skip(skip).collect()
+    }
+}
+
+/// 포트폴리오 추적기 - HTTP 핸들러와 감시 태스크가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct PortfolioTracker {
+    portfolio: Arc<Mutex<Portfolio>>,
+    history_interval: Duration,
+    /// 체결마다 실현 손익 변화분을 넘길 일일 손실 추적기
+    daily_loss: Option<DailyLossTracker>,
+}
+
+impl PortfolioTracker {
+    pub fn new(config: PortfolioConfig) -> Self {
+        let history_interval = Duration::from_millis(config.history_interval_ms.max(1_000) as u64);
+        PortfolioTracker {
+            portfolio: Arc::new(Mutex::new(Portfolio::new(config))),
+            history_interval,
+            daily_loss: None,
+        }
+    }
+
+    pub fn with_daily_loss(mut self, tracker: DailyLossTracker) -> Self {
+        self.daily_loss = Some(tracker);
+        self
+    }
+
+    fn portfolio(&self) -> std::sync::MutexGuard<'_, Portfolio> {
+        self.portfolio.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    pub fn apply_update(&self, update: &OrderUpdate) -> bool {
+        let mut portfolio = self.portfolio();
+        let before = portfolio.realized_net(&update.symbol);
+        if !portfolio.apply_update(update) {
+            return false;
+        }
+        let realized = portfolio.realized_net(&update.symbol) - before;
+        drop(portfolio);
+        if let Some(daily) = self.daily_loss.as_ref().filter(|_| realized != 0.0) {
+            let now = if update.timestamp > 0 { update.timestamp } else { Self::now() };
+            daily.record_pnl(realized, now);
+        }
+        true
+    }
+
+    /// 시세 스트림 반영
+    pub fn on_market_data(&self, data: &MarketData) {
+        self.portfolio().update_price(&data.symbol, data.close_f64(), data.timestamp.as_millis());
+    }
+
+    pub fn snapshot(&self) -> PortfolioSnapshot {
+        self.portfolio().snapshot(Self::now())
+    }
+
+    pub fn history(&self, since: Option<i64>, limit: Option<usize>) -> Vec<EquityPoint> {
+        self.portfolio().history(since, limit)
+    }
+
+    /// 감시 태스크 시작 - 체결 이벤트를 반영하고, 보유 심볼은 시세 스트림의 최신가로 매초 평가.
+    /// 자산 곡선 기록 시점에는 스트림에 없는 심볼을 거래소 현재가로 보완
+    pub fn start(
+        &self,
+        order_manager: Arc<RwLock<OrderManager>>,
+        exchange: Arc<RwLock<dyn Exchange>>,
+        market_stream: Arc<RwLock<MarketDataStream>>,
+    ) -> tokio::task::JoinHandle<()> {
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            let mut events = order_manager.read().await.subscribe_to_order_events();
+            let mut price_ticker = tokio::time::interval(Duration::from_secs(1));
+            let mut ticker = tokio::time::interval(tracker.history_interval);
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(event) => {
+                            let update = &event.update;
+                            if tracker.apply_update(update) {
+                                log::debug!("portfolio fill: {} {:?} {} @ {}", update.symbol, update.side, update.last_fill_quantity, update.last_fill_price);
+                            }
+                        }
+                        Err(RecvError::Lagged(n)) => log::warn!("portfolio tracker lagged {} order updates, fills may be missing", n),
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = price_ticker.tick() => {
+                        let symbols = tracker.portfolio().open_symbols();
+                        let stream = market_stream.read().await;
+                        for data in symbols.iter().filter_map(|symbol| stream.get_latest_data(symbol)) {
+                            tracker.on_market_data(&data);
+                        }
+                    }
+                    _ = ticker.tick() => {
+                        let symbols = tracker.portfolio().open_symbols();
+                        let streamed: Vec<String> = {
+                            let stream = market_stream.read().await;
+                            symbols.iter().filter(|s| stream.get_latest_data(s).is_some()).cloned().collect()
+                        };
+                        for symbol in symbols.into_iter().filter(|s| !streamed.contains(s)) {
+                            let market_data = exchange.read().await.get_market_data(&symbol).await;
+                            match market_data {
+                                Ok(data) => tracker.portfolio().update_price(&symbol, data.close_f64(), Self::now()),
+                                Err(e) => log::debug!("portfolio price refresh failed for {}: {}", symbol, e),
+                            }
+                        }
+                        tracker.portfolio().record_equity(Self::now());
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::to_decimal;
+    use crate::models::order::OrderStatus;
+
+    fn fill(order_id: &str, side: OrderSide, price: f64, quantity: f64, cumulative: f64, fee: f64) -> OrderUpdate {
+        OrderUpdate {
+            order_id: OrderId(order_id.to_string()),
+            client_order_id: None,
+            symbol: "BTCUSDT".to_string(),
+            side,
+            status: OrderStatus::Filled,
+            last_fill_price: to_decimal(price),
+            last_fill_quantity: to_decimal(quantity),
+            cumulative_quantity: to_decimal(cumulative),
+            fee: to_decimal(fee),
+            fee_asset: Some("USDT".to_string()),
+            liquidity: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_realized_and_unrealized_pnl() {
+        let mut portfolio = Portfolio::new(PortfolioConfig { starting_equity: 10_000.0, ..PortfolioConfig::default() });
+        assert!(portfolio.apply_update(&fill("1", OrderSide::Buy, 100.0, 1.0, 1.0, 0.1)));
+        assert!(portfolio.apply_update(&fill("2", OrderSide::Buy, 110.0, 1.0, 1.0, 0.1)));
+        // 같은 체결이 폴링으로 다시 들어와도 무시
+        assert!(!portfolio.apply_update(&fill("2", OrderSide::Buy, 110.0, 1.0, 1.0, 0.0)));
+
+        portfolio.update_price("BTCUSDT", 120.0, 1);
+        let snapshot = portfolio.snapshot(1);
+        assert_eq!(snapshot.positions[0].avg_entry_price, 105.0);
+        assert_eq!(snapshot.unrealized_pnl, 30.0);
+        assert_eq!(snapshot.gross_exposure, 240.0);
+
+        // 3 매도: 2 청산(실현 +30), 1 숏 진입 @120
+        assert!(portfolio.apply_update(&fill("3", OrderSide::Sell, 120.0, 3.0, 3.0, 0.0)));
+        portfolio.update_price("BTCUSDT", 100.0, 2);
+        let snapshot = portfolio.snapshot(2);
+        let position = &snapshot.positions[0];
+        assert_eq!((position.quantity, position.avg_entry_price), (-1.0, 120.0));
+        assert_eq!(snapshot.realized_pnl, 30.0);
+        assert_eq!(snapshot.unrealized_pnl, 20.0);
+        assert_eq!(snapshot.net_exposure, -100.0);
+        assert!((snapshot.equity - (10_000.0 + 50.0 - 0.2)).abs() < 1e-9);
+
+        portfolio.record_equity(2);
+        portfolio.record_equity(3);
+        assert_eq!(portfolio.history(Some(3), None).len(), 1);
+        assert_eq!(portfolio.history(None, Some(1))[0].timestamp, 3);
+    }
+}
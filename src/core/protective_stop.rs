@@ -0,0 +1,262 @@
+//! 합성 손절(보호 청산) 모듈
+//!
+//! 청산 로직이 없는 시그널 전략을 위해 전략별로 귀속된 포지션을 감시하고,
+//! 최대 손실률 / 최대 보유 시간 / ATR 손절선에 닿으면 전략 로직과 무관하게
+//! reduce-only 시장가 청산 주문을 만든다.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::config::ProtectiveStopConfig;
+use crate::indicators::{Indicator, volatility::AverageTrueRange};
+use crate::models::decimal::to_decimal;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+
+/// 청산 주문을 낸 뒤 체결 기록이 없으면 다시 내기까지 대기 시간
+const EXIT_RETRY_MS: i64 = 30_000;
+
+/// 보호 청산 사유
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectiveExitReason {
+    MaxLoss,
+    MaxHolding,
+    AtrStop,
+}
+
+impl ProtectiveExitReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtectiveExitReason::MaxLoss => "max_loss",
+            ProtectiveExitReason::MaxHolding => "max_holding",
+            ProtectiveExitReason::AtrStop => "atr_stop",
+        }
+    }
+}
+
+/// 전략 / 심볼별 귀속 포지션
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProtectedPosition {
+    /// 순 포지션 수량 (매수 +, 매도 -)
+    pub net_qty: f64,
+    pub avg_price: f64,
+    /// 포지션 진입 시각 (ms)
+    pub opened_at: i64,
+    /// 진입 시점 ATR 기준 손절가
+    pub atr_stop: Option<f64>,
+    /// 청산 주문 제출 시각 (체결 기록 전까지 중복 청산 방지)
+    pub exit_pending_since: Option<i64>,
+}
+
+/// 발동한 보호 청산
+#[derive(Debug, Clone)]
+pub struct ProtectiveExit {
+    pub strategy: String,
+    pub reason: ProtectiveExitReason,
+    pub order: Order,
+}
+
+/// 보호 청산 관리자 - 설정이 있는 전략의 포지션만 추적
+#[derive(Default)]
+pub struct ProtectiveStopManager {
+    configs: HashMap<String, ProtectiveStopConfig>,
+    positions: HashMap<(String, String), ProtectedPosition>,
+    // 심볼별 ATR (기간별로 분리)
+    atrs: HashMap<(String, usize), AverageTrueRange>,
+}
+
+impl ProtectiveStopManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 전략 보호 설정 (None 이면 해제)
+    pub fn set_config(&mut self, strategy: &str, config: Option<ProtectiveStopConfig>) {
+        match config {
+            Some(config) => { self.configs.insert(strategy.to_string(), config); }
+            None => self.remove(strategy),
+        }
+    }
+
+    pub fn config(&self, strategy: &str) -> Option<&ProtectiveStopConfig> {
+        self.configs.get(strategy)
+    }
+
+    /// 전략 체결 기록 - 포지션 평균가/진입 시각 갱신
+    pub fn record_fill(&mut self, strategy: &str, symbol: &str, side: &OrderSide, quantity: f64, price: f64, now: i64) {
+        let Some(config) = self.configs.get(strategy) else { return };
+        if quantity <= 0.0 || price <= 0.0 {
+            return;
+        }
+        let atr = (config.atr_multiplier > 0.0)
+            .then(|| self.atrs.get(&(symbol.to_string(), config.atr_period)))
+            .flatten()
+            .and_then(|atr| atr.value());
+        let atr_multiplier = config.atr_multiplier;
+
+        let position = self.positions.entry((strategy.to_string(), symbol.to_string())).or_default();
+        let signed_qty = match side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+        position.exit_pending_since = None;
+
+        if position.net_qty == 0.0 || position.net_qty.signum() == signed_qty.signum() {
+            // 신규 진입 또는 추가 진입
+            if position.net_qty == 0.0 {
+                position.opened_at = now;
+            }
+            let total = position.net_qty.abs() + quantity;
+            position.avg_price = (position.avg_price * position.net_qty.abs() + price * quantity) / total;
+            position.net_qty += signed_qty;
+        } else {
+            position.net_qty += signed_qty;
+            if position.net_qty.abs() < f64::EPSILON {
+                self.positions.remove(&(strategy.to_string(), symbol.to_string()));
+                return;
+            }
+            if position.net_qty.signum() == signed_qty.signum() {
+                // 포지션 반전: 남은 수량은 새 진입
+                position.avg_price = price;
+                position.opened_at = now;
+            } else {
+                // 부분 청산: 평균가/손절가 유지
+                return;
+            }
+        }
+
+        position.atr_stop = atr.map(|atr| position.avg_price - position.net_qty.signum() * atr_multiplier * atr);
+    }
+
+    /// 시세 반영 후 한도에 닿은 포지션의 청산 주문 반환
+    pub fn on_market_data(&mut self, market_data: &MarketData, now: i64) -> Vec<ProtectiveExit> {
+        let symbol = market_data.symbol.as_str();
+        for config in self.configs.values().filter(|c| c.atr_multiplier > 0.0) {
+            self.atrs.entry((symbol.to_string(), config.atr_period))
+                .or_insert_with(|| AverageTrueRange::new(config.atr_period));
+        }
+        for ((atr_symbol, _), atr) in self.atrs.iter_mut() {
+            if atr_symbol == symbol {
+                if let Err(e) = atr.update_ohlc(market_data.open_f64(), market_data.high_f64(), market_data.low_f64(), market_data.close_f64(), None) {
+                    log::debug!("protective ATR update skipped for {}: {}", symbol, e);
+                }
+            }
+        }
+
+        let price = market_data.close_f64();
+        let mut exits = Vec::new();
+        for ((strategy, position_symbol), position) in self.positions.iter_mut() {
+            if position_symbol != symbol || position.net_qty == 0.0 {
+                continue;
+            }
+            let Some(config) = self.configs.get(strategy) else { continue };
+            if position.exit_pending_since.is_some_and(|at| now - at < EXIT_RETRY_MS) {
+                continue;
+            }
+
+            // ATR 준비 전에 진입한 포지션은 준비되는 시점에 손절가 설정
+            if position.atr_stop.is_none() && config.atr_multiplier > 0.0 {
+                let atr = self.atrs.get(&(symbol.to_string(), config.atr_period)).and_then(|a| a.value());
+                position.atr_stop = atr.map(|atr| position.avg_price - position.net_qty.signum() * config.atr_multiplier * atr);
+            }
+
+            let direction = position.net_qty.signum();
+            let loss_pct = direction * (position.avg_price - price) / position.avg_price * 100.0;
+            let reason = if config.max_loss_pct > 0.0 && loss_pct >= config.max_loss_pct {
+                Some(ProtectiveExitReason::MaxLoss)
+            } else if position.atr_stop.is_some_and(|stop| direction * (price - stop) <= 0.0) {
+                Some(ProtectiveExitReason::AtrStop)
+            } else if config.max_holding_ms > 0 && now - position.opened_at >= config.max_holding_ms {
+                Some(ProtectiveExitReason::MaxHolding)
+            } else {
+                None
+            };
+
+            if let Some(reason) = reason {
+                let side = if direction > 0.0 { OrderSide::Sell } else { OrderSide::Buy };
+                let order = Order::new(symbol, side, OrderType::Market, to_decimal(position.net_qty.abs()), market_data.close)
+                    .with_reduce_only(true);
+                position.exit_pending_since = Some(now);
+                exits.push(ProtectiveExit { strategy: strategy.clone(), reason, order });
+            }
+        }
+        exits
+    }
+
+    /// 청산 주문 제출 실패 시 다음 시세에서 바로 재시도
+    pub fn exit_failed(&mut self, strategy: &str, symbol: &str) {
+        if let Some(position) = self.positions.get_mut(&(strategy.to_string(), symbol.to_string())) {
+            position.exit_pending_since = None;
+        }
+    }
+
+    /// 전략의 보호 대상 포지션 조회
+    pub fn positions(&self, strategy: &str) -> Vec<(String, ProtectedPosition)> {
+        self.positions.iter()
+            .filter(|((s, _), _)| s == strategy)
+            .map(|((_, symbol), p)| (symbol.clone(), p.clone()))
+            .collect()
+    }
+
+    /// 전략 제거 시 상태 정리
+    pub fn remove(&mut self, strategy: &str) {
+        self.configs.remove(strategy);
+        self.positions.retain(|(s, _), _| s != strategy);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    fn candle(price: f64, range: f64) -> MarketData {
+        MarketData::new("BTCUSDT", 0, to_decimal(price), to_decimal(price + range), to_decimal(price - range), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_protective_exits() {
+        let mut stops = ProtectiveStopManager::new();
+        stops.set_config("loss", Some(ProtectiveStopConfig { max_loss_pct: 5.0, ..Default::default() }));
+        stops.set_config("hold", Some(ProtectiveStopConfig { max_holding_ms: 60_000, ..Default::default() }));
+        stops.set_config("atr", Some(ProtectiveStopConfig { atr_multiplier: 2.0, atr_period: 3, ..Default::default() }));
+
+        // ATR(3) 준비: 범위 2.0 캔들
+        for _ in 0..3 {
+            assert!(stops.on_market_data(&candle(100.0, 1.0), 0).is_empty());
+        }
+
+        // 설정이 없는 전략은 추적하지 않음
+        stops.record_fill("other", "BTCUSDT", &OrderSide::Buy, 1.0, 100.0, 0);
+        stops.record_fill("loss", "BTCUSDT", &OrderSide::Buy, 1.0, 100.0, 0);
+        stops.record_fill("hold", "BTCUSDT", &OrderSide::Sell, 2.0, 100.0, 0);
+        stops.record_fill("atr", "BTCUSDT", &OrderSide::Buy, 1.0, 100.0, 0);
+        assert!(stops.positions("other").is_empty());
+        assert!((stops.positions("atr")[0].1.atr_stop.unwrap() - 96.0).abs() < 1e-9);
+
+        // 3% 하락: ATR 손절(96) 전, 손실 한도(5%) 전
+        assert!(stops.on_market_data(&candle(97.0, 0.5), 1_000).is_empty());
+
+        // 4% 하락 -> ATR 손절
+        let exits = stops.on_market_data(&candle(96.0, 0.5), 2_000);
+        assert_eq!(exits.len(), 1);
+        assert_eq!((exits[0].strategy.as_str(), &exits[0].reason), ("atr", &ProtectiveExitReason::AtrStop));
+        assert_eq!(exits[0].order.side, OrderSide::Sell);
+        assert_eq!(exits[0].order.reduce_only, Some(true));
+
+        // 청산 체결 전에는 중복 청산 없음, 5% 하락 -> 손실 한도 전략 청산
+        let exits = stops.on_market_data(&candle(95.0, 0.5), 3_000);
+        assert_eq!(exits.len(), 1);
+        assert_eq!((exits[0].strategy.as_str(), &exits[0].reason), ("loss", &ProtectiveExitReason::MaxLoss));
+        stops.record_fill("atr", "BTCUSDT", &OrderSide::Sell, 1.0, 96.0, 3_000);
+        stops.record_fill("loss", "BTCUSDT", &OrderSide::Sell, 1.0, 95.0, 3_000);
+        assert!(stops.positions("atr").is_empty() && stops.positions("loss").is_empty());
+
+        // 보유 시간 초과 -> 매도 포지션은 매수로 청산
+        let exits = stops.on_market_data(&candle(95.0, 0.5), 60_000);
+        assert_eq!(exits.len(), 1);
+        assert_eq!((exits[0].reason.clone(), exits[0].order.side.clone(), exits[0].order.quantity), (ProtectiveExitReason::MaxHolding, OrderSide::Buy, dec!(2)));
+    }
+}
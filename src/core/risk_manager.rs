@@ -1,15 +1,117 @@
 //! 리스크 관리 모듈
 //!
 //! 포지션 크기, 손실 한도 등 리스크 관리 기능 구현
+//! 포트폴리오 수준 노출 한도(총 명목가, 보유 심볼 수, 상관 그룹)는 `ExposureLimits` 가 판정하고,
+//! 실시간 주문 경로에서는 `ExposureValidator` 가 포트폴리오 추적기의 포지션으로 검사한다.
+//! 검증기의 한도는 `SharedExposureLimits` 핸들로 설정 리로드와 공유해 재시작 없이 교체한다.
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::config::RiskLimitsConfig;
+use crate::core::daily_loss::{DailyLossStatus, DailyLossTracker};
+use crate::core::portfolio::PortfolioTracker;
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::{to_f64, Decimal};
 use crate::models::order::{Order, OrderSide};
 use crate::models::position::Position;
+use crate::order_core::validator::OrderValidator;
+
+/// 보유 중으로 보지 않는 명목가 (먼지 잔량)
+const DUST_NOTIONAL: f64 = 1e-9;
+
+/// 포트폴리오 노출 한도
+///
+/// 주문 체결 후의 노출이 한도를 넘고 현재보다 커질 때만 거부하므로, 이미 한도를 넘은 상태에서도
+/// 노출을 줄이는 주문은 통과한다.
+#[derive(Debug, Clone)]
+pub struct ExposureLimits {
+    config: RiskLimitsConfig,
+}
+
+impl ExposureLimits {
+    pub fn new(config: RiskLimitsConfig) -> Result<Self, TradingError> {
+        config.validate().map_err(TradingError::ConfigError)?;
+        Ok(ExposureLimits { config })
+    }
+
+    pub fn config(&self) -> &RiskLimitsConfig {
+        &self.config
+    }
+
+    /// 주문 검사 - `exposures` 는 심볼별 부호 있는 명목가, `price` 가 없으면 명목가 한도는 건너뜀
+    pub fn check(&self, exposures: &HashMap<String, f64>, order: &Order, price: Option<f64>) -> Result<(), String> {
+        let current = exposures.get(&order.symbol).copied().unwrap_or(0.0);
+
+        // 보유하지 않은 심볼의 주문은 새 포지션
+        if let Some(max) = self.config.max_open_positions {
+            let open = exposures.values().filter(|v| v.abs() > DUST_NOTIONAL).count();
+            if current.abs() <= DUST_NOTIONAL && open >= max {
+                return Err(format!("{} open positions already at limit {}", open, max));
+            }
+        }
+
+        let Some(price) = price.filter(|p| *p > 0.0) else {
+            return Ok(());
+        };
+        let notional = to_f64(order.quantity) * price;
+        let after = current + if order.side == OrderSide::Buy { notional } else { -notional };
+        // 주문 심볼의 노출 변화만 반영한 그룹 총 명목가 (전/후)
+        let gross = |symbols: Option<&[String]>| {
+            let others: f64 = exposures.iter()
+                .filter(|(symbol, _)| **symbol != order.symbol && symbols.is_none_or(|list| list.contains(symbol)))
+                .map(|(_, exposure)| exposure.abs())
+                .sum();
+            (others + current.abs(), others + after.abs())
+        };
+
+        if let Some(cap) = self.config.max_total_notional {
+            let (before, after) = gross(None);
+            if after > cap && after > before {
+                return Err(format!("total notional {:.2} would exceed {:.2}", after, cap));
+            }
+        }
+        for group in self.config.correlation_groups.iter().filter(|g| g.symbols.contains(&order.symbol)) {
+            let (before, after) = gross(Some(&group.symbols));
+            if after > group.max_notional && after > before {
+                return Err(format!("correlation group {} notional {:.2} would exceed {:.2}", group.name, after, group.max_notional));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 노출 한도 핸들 - 주문 검증기와 설정 리로드가 공유 (리로드 시 통째로 교체)
+pub type SharedExposureLimits = Arc<std::sync::RwLock<ExposureLimits>>;
+
+/// 주문 관리자 검증기 - 포트폴리오 추적기의 현재 노출로 한도 검사
+pub struct ExposureValidator {
+    limits: SharedExposureLimits,
+    portfolio: PortfolioTracker,
+}
+
+impl ExposureValidator {
+    pub fn new(limits: SharedExposureLimits, portfolio: PortfolioTracker) -> Self {
+        ExposureValidator { limits, portfolio }
+    }
+}
+
+impl OrderValidator for ExposureValidator {
+    fn validate(&self, order: &Order) -> Result<(), TradingError> {
+        let snapshot = self.portfolio.snapshot();
+        let exposures: HashMap<String, f64> = snapshot.positions.iter().map(|p| (p.symbol.clone(), p.exposure)).collect();
+        // 시장가는 보유 포지션의 최신가로 평가
+        let price = if to_f64(order.price) > 0.0 {
+            Some(to_f64(order.price))
+        } else {
+            snapshot.positions.iter().find(|p| p.symbol == order.symbol).map(|p| p.last_price)
+        };
+        let limits = self.limits.read().unwrap_or_else(|e| e.into_inner());
+        limits.check(&exposures, order, price).map_err(TradingError::RiskLimitExceeded)
+    }
+}
 
 /// 리스크 관리자
 pub struct RiskManager {
@@ -19,12 +121,12 @@ pub struct RiskManager {
     max_position_size: HashMap<String, f64>,
     /// 최대 낙폭 비율 (%)
     max_drawdown_percent: f64,
-    /// 일일 최대 손실액
-    max_daily_loss: f64,
-    /// 당일 손실액
-    daily_loss: f64,
+    /// 거래일 단위 손실 집계 - 포트폴리오 추적기/주문 관리자와 공유 (한도와 거래일 경계는 설정 리로드로 교체)
+    daily_loss: DailyLossTracker,
     /// 현재 포지션
     positions: HashMap<String, Position>,
+    /// 포트폴리오 노출 한도
+    exposure_limits: Option<ExposureLimits>,
 }
 
 impl RiskManager {
@@ -32,18 +134,28 @@ impl RiskManager {
     pub fn new(
         exchange: Arc<RwLock<dyn Exchange>>,
         max_drawdown_percent: f64,
-        max_daily_loss: f64,
+        daily_loss: DailyLossTracker,
     ) -> Self {
         RiskManager {
             exchange,
             max_position_size: HashMap::new(),
             max_drawdown_percent,
-            max_daily_loss,
-            daily_loss: 0.0,
+            daily_loss,
             positions: HashMap::new(),
+            exposure_limits: None,
         }
     }
     
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+    
+    /// 포트폴리오 노출 한도 설정
+    pub fn set_exposure_limits(&mut self, config: RiskLimitsConfig) -> Result<(), TradingError> {
+        self.exposure_limits = if config.is_enabled() { Some(ExposureLimits::new(config)?) } else { None };
+        Ok(())
+    }
+    
     /// 특정 심볼의 최대 포지션 크기 설정
     pub fn set_max_position_size(&mut self, symbol: impl Into<String>, size: f64) {
         self.max_position_size.insert(symbol.into(), size);
@@ -58,8 +170,8 @@ impl RiskManager {
         if let Some(max_size) = self.max_position_size.get(&order.symbol) {
             let current_size = self.get_position_size(&order.symbol);
             let order_effect = match order.side {
-                OrderSide::Buy => order.quantity,
-                OrderSide::Sell => -order.quantity,
+                OrderSide::Buy => to_f64(order.quantity),
+                OrderSide::Sell => -to_f64(order.quantity),
             };
             
             let new_size = current_size + order_effect;
@@ -69,8 +181,25 @@ impl RiskManager {
             }
         }
         
+        // 총 노출 / 보유 심볼 수 / 상관 그룹 한도 확인
+        if let Some(limits) = &self.exposure_limits {
+            let exposures: HashMap<String, f64> = self.positions.iter()
+                .map(|(symbol, p)| (symbol.clone(), to_f64(p.quantity * p.current_price)))
+                .collect();
+            let price = if to_f64(order.price) > 0.0 {
+                Some(to_f64(order.price))
+            } else {
+                self.positions.get(&order.symbol).map(|p| to_f64(p.current_price))
+            };
+            if let Err(reason) = limits.check(&exposures, order, price) {
+                log::warn!("order rejected by exposure limit: {}", reason);
+                return Ok(false);
+            }
+        }
+        
         // 낙폭 및 일일 손실 한도 확인
-        if self.daily_loss >= self.max_daily_loss {
+        if let Err(e) = self.daily_loss.check(order, Self::now()) {
+            log::warn!("order rejected by daily loss limit: {}", e);
             return Ok(false);
         }
         
@@ -88,13 +217,7 @@ impl RiskManager {
         // 심볼별로 그룹화
         for order in open_orders {
             let position = positions.entry(order.symbol.clone()).or_insert_with(|| {
-                Position {
-                    symbol: order.symbol.clone(),
-                    quantity: 0.0,
-                    entry_price: 0.0,
-                    current_price: 0.0,
-                    unrealized_pnl: 0.0,
-                }
+                Position::new(order.symbol.clone(), Decimal::ZERO, Decimal::ZERO)
             });
             
             // 포지션 크기 업데이트
@@ -110,9 +233,8 @@ impl RiskManager {
             position.current_price = market_data.close;
             
             // 미실현 손익 계산
-            if position.quantity != 0.0 && position.entry_price != 0.0 {
-                let direction = position.quantity.signum();
-                position.unrealized_pnl = direction * (position.current_price - position.entry_price).abs() * position.quantity.abs();
+            if !position.entry_price.is_zero() {
+                position.calculate_pnl();
             }
         }
         
@@ -122,24 +244,37 @@ impl RiskManager {
     
     /// 심볼별 포지션 크기 조회
     pub fn get_position_size(&self, symbol: &str) -> f64 {
-        self.positions.get(symbol).map_or(0.0, |p| p.quantity)
+        self.positions.get(symbol).map_or(0.0, |p| to_f64(p.quantity))
     }
     
     /// 심볼별 미실현 손익 조회
     pub fn get_unrealized_pnl(&self, symbol: &str) -> f64 {
-        self.positions.get(symbol).map_or(0.0, |p| p.unrealized_pnl)
+        self.positions.get(symbol).map_or(0.0, |p| to_f64(p.unrealized_pnl))
     }
     
     /// 실현 손익 기록
     pub fn record_pnl(&mut self, amount: f64) {
-        if amount < 0.0 {
-            self.daily_loss += amount.abs();
-        }
+        self.daily_loss.record_pnl(amount, Self::now());
+    }
+    
+    /// 일일 손실 카운터 초기화 (거래일이 바뀌면 자동으로도 초기화됨)
+    pub fn reset_daily_loss(&mut self) -> DailyLossStatus {
+        self.daily_loss.reset(Self::now())
     }
     
-    /// 일일 손실 카운터 초기화
-    pub fn reset_daily_loss(&mut self) {
-        self.daily_loss = 0.0;
+    /// 당일 손실 현황
+    pub fn daily_loss_status(&self) -> DailyLossStatus {
+        self.daily_loss.status(Self::now())
+    }
+    
+    /// 변동성 타게팅: 목표 연변동성 대비 실현 변동성으로 포지션 명목가치 산출
+    /// (realized_vol은 indicators::volatility 추정치, 레버리지는 max_leverage로 제한)
+    pub fn volatility_target_notional(&self, capital: f64, target_vol: f64, realized_vol: f64, max_leverage: f64) -> f64 {
+        if capital <= 0.0 || target_vol <= 0.0 || realized_vol <= 0.0 {
+            return 0.0;
+        }
+        let leverage = (target_vol / realized_vol).min(max_leverage);
+        capital * leverage
     }
     
     /// 모든 현재 포지션 조회
@@ -151,8 +286,10 @@ impl RiskManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::decimal::dec;
     use crate::exchange::mocks::MockExchange;
-    use crate::config::Config;
+    use crate::config::{Config, DailyLossConfig};
+    use crate::models::decimal::Decimal;
     use crate::models::order::OrderType;
     
     #[tokio::test]
@@ -160,12 +297,16 @@ mod tests {
         // 테스트 환경 설정
         let config = Config::default();
         let exchange = Arc::new(RwLock::new(MockExchange::new(config)));
+        let daily_loss = DailyLossTracker::new(
+            &DailyLossConfig { max_daily_loss: Some(1000.0), ..DailyLossConfig::default() }, // $1000 일일 최대 손실
+            chrono::Utc::now().timestamp_millis(),
+        ).unwrap();
         
         // 리스크 관리자 생성
         let mut risk_manager = RiskManager::new(
             exchange.clone(),
             5.0,   // 5% 최대 낙폭
-            1000.0, // $1000 일일 최대 손실
+            daily_loss.clone(),
         );
         
         // 최대 포지션 크기 설정
@@ -180,8 +321,8 @@ mod tests {
             "BTCUSDT",
             OrderSide::Buy,
             OrderType::Market,
-            0.5,
-            50000.0,
+            dec!(0.5),
+            dec!(50000),
         );
         
         let check_result = risk_manager.check_order(&order).await;
@@ -192,12 +333,44 @@ mod tests {
             "BTCUSDT",
             OrderSide::Buy,
             OrderType::Market,
-            2.0,
-            50000.0,
+            dec!(2),
+            dec!(50000),
         );
         
         let large_check_result = risk_manager.check_order(&large_order).await;
         assert!(large_check_result.is_ok());
         assert!(!large_check_result.unwrap());
+        
+        // 일일 손실은 공유 추적기에 기록 - 한도 도달 후 신규 주문 거부
+        risk_manager.record_pnl(-1500.0);
+        assert!(daily_loss.status(chrono::Utc::now().timestamp_millis()).breached);
+        assert!(!risk_manager.check_order(&order).await.unwrap());
+    }
+    
+    #[test]
+    fn test_exposure_limits() {
+        use crate::config::CorrelationGroup;
+        
+        let limits = ExposureLimits::new(RiskLimitsConfig {
+            max_total_notional: Some(100_000.0),
+            max_open_positions: Some(2),
+            correlation_groups: vec![CorrelationGroup { name: "majors".into(), symbols: vec!["BTCUSDT".into(), "ETHUSDT".into()], max_notional: 60_000.0 }],
+        }).unwrap();
+        let exposures: HashMap<String, f64> = [("BTCUSDT".to_string(), 50_000.0)].into();
+        let order = |symbol: &str, side: OrderSide, quantity| Order::new(symbol, side, OrderType::Market, quantity, Decimal::ZERO);
+        
+        // ETH 는 BTC 와 같은 버킷 - 개별로는 작아도 그룹 한도 초과
+        let reason = limits.check(&exposures, &order("ETHUSDT", OrderSide::Buy, dec!(5)), Some(3_000.0)).unwrap_err();
+        assert!(reason.contains("majors"));
+        assert!(limits.check(&exposures, &order("ETHUSDT", OrderSide::Buy, dec!(3)), Some(3_000.0)).is_ok());
+        assert!(limits.check(&exposures, &order("SOLUSDT", OrderSide::Buy, dec!(300)), Some(200.0)).unwrap_err().contains("total notional"));
+        
+        // 보유 심볼 수 한도: 새 심볼은 거부, 기존 포지션 청산은 한도를 넘은 상태에서도 통과
+        let full: HashMap<String, f64> = [("BTCUSDT".to_string(), 70_000.0), ("SOLUSDT".to_string(), 1_000.0)].into();
+        assert!(limits.check(&full, &order("XRPUSDT", OrderSide::Buy, dec!(1)), Some(1.0)).unwrap_err().contains("open positions"));
+        assert!(limits.check(&full, &order("BTCUSDT", OrderSide::Sell, dec!(0.2)), Some(50_000.0)).is_ok());
+        assert!(limits.check(&full, &order("BTCUSDT", OrderSide::Buy, dec!(0.1)), None).is_ok());
+        
+        assert!(ExposureLimits::new(RiskLimitsConfig { max_open_positions: Some(0), ..RiskLimitsConfig::default() }).is_err());
     }
 }
\ No newline at end of file
@@ -0,0 +1,275 @@
+//! 섀도(드라이런) 실행 장부
+//!
+//! 섀도 모드 전략의 주문은 거래소로 보내지 않고 여기에 기록한다. 시장가는 접수 시점의 호가(없으면 현재가)로,
+//! 지정가/스톱은 이후 시세가 가격을 지날 때 체결된 것으로 보고(FillModel) 전략별 가상 포지션과 손익을 집계한다.
+//! 실제 자금을 걸기 전에 운영 시세로 전략을 검증하는 용도.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::exchange::fill_model::{FillModel, Liquidity, SimulatedFill};
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderId, OrderSide, OrderType};
+use crate::models::order_book::OrderBook;
+
+/// 전략별 보관 체결 수 (오래된 것부터 버림)
+const MAX_FILLS_PER_STRATEGY: usize = 500;
+
+/// 가상 체결 한 건
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ShadowFill {
+    pub strategy: String,
+    pub order_id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub liquidity: Liquidity,
+    pub fee: Decimal,
+    pub timestamp: i64,
+}
+
+/// 전략/심볼별 가상 포지션 (수량은 롱 +, 숏 -)
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct ShadowPosition {
+    pub symbol: String,
+    pub quantity: Decimal,
+    pub average_price: Decimal,
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub fees: Decimal,
+}
+
+impl ShadowPosition {
+    /// 체결 반영 - 반대 방향 체결은 평균가 기준으로 실현 손익을 잡고, 남는 수량은 체결가로 새로 진입
+    fn apply(&mut self, side: &OrderSide, price: Decimal, quantity: Decimal, fee: Decimal) {
+        let signed = match side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+        self.fees += fee;
+        if self.quantity.is_zero() || self.quantity.is_sign_positive() == signed.is_sign_positive() {
+            let total = self.quantity + signed;
+            self.average_price = (self.average_price * self.quantity.abs() + price * quantity) / total.abs();
+            self.quantity = total;
+            return;
+        }
+        let closed = quantity.min(self.quantity.abs());
+        let direction = if self.quantity.is_sign_positive() { Decimal::ONE } else { -Decimal::ONE };
+        self.realized_pnl += (price - self.average_price) * closed * direction;
+        self.quantity += signed;
+        if self.quantity.is_zero() {
+            self.average_price = Decimal::ZERO;
+        } else if self.quantity.is_sign_positive() != direction.is_sign_positive() {
+            self.average_price = price;
+        }
+    }
+
+    fn mark(&mut self, price: Decimal) {
+        self.unrealized_pnl = (price - self.average_price) * self.quantity;
+    }
+}
+
+/// 전략 하나의 섀도 실행 보고서
+#[derive(Debug, Clone, Serialize)]
+pub struct ShadowReport {
+    pub strategy: String,
+    pub fills: Vec<ShadowFill>,
+    pub open_orders: Vec<Order>,
+    pub positions: Vec<ShadowPosition>,
+    /// 실현 손익 합계 (수수료 차감 전)
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub fees: Decimal,
+}
+
+#[derive(Default)]
+struct ShadowState {
+    /// (전략, 미체결 주문)
+    open: Vec<(String, Order)>,
+    fills: HashMap<String, VecDeque<ShadowFill>>,
+    positions: HashMap<(String, String), ShadowPosition>,
+    last_price: HashMap<String, Decimal>,
+}
+
+impl ShadowState {
+    fn record(&mut self, strategy: &str, order: &Order, fill: &SimulatedFill, timestamp: i64) -> ShadowFill {
+        let position = self.positions.entry((strategy.to_string(), order.symbol.clone()))
+            .or_insert_with(|| ShadowPosition { symbol: order.symbol.clone(), ..ShadowPosition::default() });
+        position.apply(&order.side, fill.price, fill.quantity, fill.fee);
+        if let Some(price) = self.last_price.get(&order.symbol) {
+            position.mark(*price);
+        }
+        let shadow = ShadowFill {
+            strategy: strategy.to_string(),
+            order_id: order.id.0.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: order.order_type.clone(),
+            price: fill.price,
+            quantity: fill.quantity,
+            liquidity: fill.liquidity,
+            fee: fill.fee,
+            timestamp,
+        };
+        log::info!("[shadow] {} filled {:?} {} {} @ {} ({:?})", strategy, shadow.side, shadow.quantity, shadow.symbol, shadow.price, shadow.liquidity);
+        let fills = self.fills.entry(strategy.to_string()).or_default();
+        fills.push_back(shadow.clone());
+        if fills.len() > MAX_FILLS_PER_STRATEGY {
+            fills.pop_front();
+        }
+        shadow
+    }
+}
+
+/// 섀도 실행 장부 - 전략 실행 루프와 HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct ShadowBook {
+    fill_model: FillModel,
+    state: Arc<Mutex<ShadowState>>,
+}
+
+impl ShadowBook {
+    /// 수수료/슬리피지는 `fill_model` 을 따르고, 거래량 제한 없이 주문 수량 전체를 체결
+    pub fn new(fill_model: FillModel) -> Self {
+        ShadowBook {
+            fill_model: FillModel { max_volume_share: None, latency_ms: 0, ..fill_model },
+            state: Arc::new(Mutex::new(ShadowState::default())),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ShadowState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 섀도 주문 접수 - 즉시 체결되면 체결 반환, 아니면 시세가 가격을 지날 때까지 대기
+    pub fn submit(&self, strategy: &str, mut order: Order, market_data: &MarketData, book: Option<&OrderBook>) -> Option<ShadowFill> {
+        if order.id.0.is_empty() {
+            order.id = OrderId(format!("shadow-{}", uuid::Uuid::new_v4()));
+        }
+        // 테이커 기준가: 같은 심볼 호가창이 있으면 반대편 최우선 호가
+        let quote = book.filter(|b| b.symbol == order.symbol).and_then(|b| match order.side {
+            OrderSide::Buy => b.best_ask(),
+            OrderSide::Sell => b.best_bid(),
+        });
+        log::info!("[shadow] {} would place {:?} {:?} {} {} @ {}", strategy, order.order_type, order.side, order.quantity, order.symbol, order.price);
+        let timestamp = i64::from(market_data.timestamp);
+        let mut state = self.lock();
+        let reference = match quote {
+            Some(level) => to_decimal(level.price),
+            None if market_data.symbol == order.symbol => market_data.close,
+            None => match state.last_price.get(&order.symbol) {
+                Some(price) => *price,
+                None => {
+                    log::warn!("[shadow] {} no price for {}, order kept open", strategy, order.symbol);
+                    state.open.push((strategy.to_string(), order));
+                    return None;
+                }
+            },
+        };
+        // 체결 기준가 한 점 (거래량을 모르므로 크기 비례 슬리피지는 최소값)
+        let point = MarketData { symbol: order.symbol.clone(), open: reference, high: reference, low: reference, close: reference, volume: Decimal::ZERO, ..market_data.clone() };
+        match self.fill_model.on_arrival(&order, order.quantity, reference, &point) {
+            Some(fill) => Some(state.record(strategy, &order, &fill, timestamp)),
+            None => {
+                state.open.push((strategy.to_string(), order));
+                None
+            }
+        }
+    }
+
+    /// 시세 갱신 - 평가 손익을 갱신하고, 가격을 지난 대기 주문을 체결
+    pub fn on_market_data(&self, market_data: &MarketData) -> Vec<ShadowFill> {
+        let mut state = self.lock();
+        state.last_price.insert(market_data.symbol.clone(), market_data.close);
+        for ((_, symbol), position) in state.positions.iter_mut() {
+            if *symbol == market_data.symbol {
+                position.mark(market_data.close);
+            }
+        }
+        // 티커의 고가/저가는 24시간 기준이라 현재가 한 점으로만 판정
+        let tick = MarketData { open: market_data.close, high: market_data.close, low: market_data.close, ..market_data.clone() };
+        let timestamp = i64::from(market_data.timestamp);
+        let open = std::mem::take(&mut state.open);
+        let mut fills = Vec::new();
+        for (strategy, order) in open {
+            if order.symbol != market_data.symbol {
+                state.open.push((strategy, order));
+                continue;
+            }
+            match self.fill_model.on_bar(&order, order.quantity, &tick) {
+                Some(fill) => fills.push(state.record(&strategy, &order, &fill, timestamp)),
+                None => state.open.push((strategy, order)),
+            }
+        }
+        fills
+    }
+
+    /// 전략의 대기 주문 정리 (섀도 해제/전략 제거 시) - 정리한 수 반환
+    pub fn cancel_orders(&self, strategy: &str) -> usize {
+        let mut state = self.lock();
+        let before = state.open.len();
+        state.open.retain(|(name, _)| name != strategy);
+        before - state.open.len()
+    }
+
+    pub fn report(&self, strategy: &str) -> ShadowReport {
+        let state = self.lock();
+        let mut positions: Vec<ShadowPosition> = state.positions.iter()
+            .filter(|((name, _), _)| name == strategy)
+            .map(|(_, position)| position.clone())
+            .collect();
+        positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        ShadowReport {
+            strategy: strategy.to_string(),
+            fills: state.fills.get(strategy).map(|f| f.iter().cloned().collect()).unwrap_or_default(),
+            open_orders: state.open.iter().filter(|(name, _)| name == strategy).map(|(_, order)| order.clone()).collect(),
+            realized_pnl: positions.iter().map(|p| p.realized_pnl).sum(),
+            unrealized_pnl: positions.iter().map(|p| p.unrealized_pnl).sum(),
+            fees: positions.iter().map(|p| p.fees).sum(),
+            positions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    fn tick(close: Decimal) -> MarketData {
+        MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: dec!(40_000), high: dec!(70_000), low: dec!(30_000), close, volume: dec!(1_000) }
+    }
+
+    #[test]
+    fn test_shadow_fills_and_pnl() {
+        let shadow = ShadowBook::new(FillModel::flat(0.001, 0.0));
+        let buy = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(1), Decimal::ZERO);
+        let fill = shadow.submit("s1", buy, &tick(dec!(50_000)), None).unwrap();
+        assert_eq!((fill.price, fill.fee), (dec!(50_000), dec!(50)));
+
+        // 24시간 고가가 아닌 현재가가 지나야 체결
+        let take_profit = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Limit, dec!(1), dec!(51_000));
+        assert!(shadow.submit("s1", take_profit, &tick(dec!(50_000)), None).is_none());
+        assert!(shadow.on_market_data(&tick(dec!(50_500))).is_empty());
+        let report = shadow.report("s1");
+        assert_eq!(report.open_orders.len(), 1);
+        assert_eq!(report.unrealized_pnl, dec!(500));
+
+        let fills = shadow.on_market_data(&tick(dec!(51_200)));
+        assert_eq!((fills.len(), fills[0].price, fills[0].liquidity), (1, dec!(51_000), Liquidity::Maker));
+        let report = shadow.report("s1");
+        assert!(report.open_orders.is_empty());
+        assert_eq!((report.realized_pnl, report.unrealized_pnl, report.fees), (dec!(1_000), dec!(0), dec!(101)));
+        assert_eq!(report.positions[0].quantity, dec!(0));
+        assert!(shadow.report("s2").fills.is_empty());
+
+        let bid = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(40_000));
+        shadow.submit("s1", bid, &tick(dec!(51_200)), None);
+        assert_eq!(shadow.cancel_orders("s1"), 1);
+    }
+}
@@ -0,0 +1,144 @@
+//! 전략 신호 중복 제거
+//!
+//! 같은 전략이 같은 심볼/방향/사유로 연달아 낸 주문은 쿨다운 동안 한 번만 제출한다.
+//! 제출이 실패하면 `release` 로 기록을 지워 다음 시세에서 다시 낼 수 있게 한다.
+//! 실행 전략(TWAP/VWAP/IS/아이스버그)의 분할 주문은 부모 주문을 나눈 것이므로 대상에서 뺀다.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use crate::models::order::{Order, OrderSide};
+
+/// 중복 판별 키
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignalKey {
+    pub strategy: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    /// 신호 사유 (전략 신호는 "signal", 보호 청산은 청산 사유)
+    pub reason: String,
+}
+
+impl SignalKey {
+    pub fn new(strategy: &str, symbol: &str, side: OrderSide, reason: &str) -> Self {
+        SignalKey { strategy: strategy.to_string(), symbol: symbol.to_string(), side, reason: reason.to_string() }
+    }
+
+    /// 전략 주문의 판별 키 - 실행 전략의 분할 주문은 None (항상 제출)
+    pub fn for_order(strategy: &str, order: &Order, reason: &str, execution_strategy: bool) -> Option<Self> {
+        (!execution_strategy).then(|| SignalKey::new(strategy, &order.symbol, order.side.clone(), reason))
+    }
+}
+
+#[derive(Default)]
+struct DedupState {
+    /// 키별 마지막으로 통과시킨 시각 (ms)
+    last_admitted: HashMap<SignalKey, i64>,
+    /// 전략별 버린 중복 건수
+    dropped: BTreeMap<String, u64>,
+}
+
+/// 신호 중복 제거기 - 전략 실행 루프와 /metrics 가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct SignalDeduplicator {
+    cooldown_ms: i64,
+    state: Arc<Mutex<DedupState>>,
+}
+
+impl SignalDeduplicator {
+    /// `cooldown_ms` 가 0 이면 모든 신호 통과
+    pub fn new(cooldown_ms: u64) -> Self {
+        SignalDeduplicator { cooldown_ms: cooldown_ms as i64, state: Arc::new(Mutex::new(DedupState::default())) }
+    }
+
+    /// 통과 여부 - 쿨다운 안의 같은 키는 버리고 중복 건수 집계
+    pub fn admit(&self, key: &SignalKey, now: i64) -> bool {
+        if self.cooldown_ms <= 0 {
+            return true;
+        }
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let cooldown_ms = self.cooldown_ms;
+        state.last_admitted.retain(|_, at| now - *at < cooldown_ms);
+        if state.last_admitted.contains_key(key) {
+            *state.dropped.entry(key.strategy.clone()).or_insert(0) += 1;
+            return false;
+        }
+        state.last_admitted.insert(key.clone(), now);
+        true
+    }
+
+    /// 제출 실패 - 다음 같은 신호를 바로 통과시킴
+    pub fn release(&self, key: &SignalKey) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).last_admitted.remove(key);
+    }
+
+    /// 전략별 버린 중복 건수
+    pub fn dropped(&self) -> BTreeMap<String, u64> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).dropped.clone()
+    }
+
+    /// Prometheus 텍스트 형식 지표 (/metrics)
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::from("# TYPE xquant_signal_duplicates_dropped_total counter\n");
+        for (strategy, count) in self.dropped() {
+            out.push_str(&format!("xquant_signal_duplicates_dropped_total{{strategy=\"{}\"}} {}\n", strategy, count));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_release_and_metrics() {
+        let dedup = SignalDeduplicator::new(1_000);
+        let buy = SignalKey::new("s1", "BTCUSDT", OrderSide::Buy, "signal");
+        assert!(dedup.admit(&buy, 0));
+        assert!(!dedup.admit(&buy, 500));
+        // 전략, 방향, 사유가 다르면 별개 신호
+        assert!(dedup.admit(&SignalKey::new("s2", "BTCUSDT", OrderSide::Buy, "signal"), 500));
+        assert!(dedup.admit(&SignalKey::new("s1", "BTCUSDT", OrderSide::Sell, "signal"), 500));
+        assert!(dedup.admit(&SignalKey::new("s1", "BTCUSDT", OrderSide::Buy, "max_loss"), 500));
+        // 쿨다운이 지나면 다시 통과
+        assert!(dedup.admit(&buy, 1_000));
+        // 제출 실패 후에는 바로 재시도 가능
+        dedup.release(&buy);
+        assert!(dedup.admit(&buy, 1_100));
+
+        assert_eq!(dedup.dropped(), BTreeMap::from([("s1".to_string(), 1)]));
+        assert!(dedup.render_prometheus().contains("xquant_signal_duplicates_dropped_total{strategy=\"s1\"} 1"));
+        assert!(SignalDeduplicator::new(0).admit(&buy, 1_100));
+    }
+
+    #[test]
+    fn test_execution_slices_bypass_cooldown() {
+        use crate::models::decimal::{dec, Decimal};
+        use crate::models::market_data::MarketData;
+        use crate::strategies::twap::TwapStrategy;
+        use crate::strategies::Strategy;
+
+        // 20초 5분할 -> 4초마다 분할 주문, 모두 5초 쿨다운 안
+        let dedup = SignalDeduplicator::new(5_000);
+        let mut twap = TwapStrategy::new("BTCUSDT", OrderSide::Buy, dec!(1), 20_000, 5);
+        let mut submitted = Decimal::ZERO;
+        for i in 0..5 {
+            let now = 1_000 + i * 4_000;
+            twap.update(MarketData::new("BTCUSDT", chrono::DateTime::from_timestamp_millis(now).unwrap(), dec!(100), dec!(100), dec!(100), dec!(100), dec!(1))).unwrap();
+            for order in twap.get_orders().unwrap() {
+                let key = SignalKey::for_order("twap", &order, "signal", twap.is_execution_strategy());
+                if key.is_none_or(|key| dedup.admit(&key, now)) {
+                    submitted += order.quantity;
+                }
+            }
+        }
+        assert_eq!(submitted, dec!(1));
+        assert!(dedup.dropped().is_empty());
+
+        // 일반 전략이면 같은 분할이 중복으로 걸러짐
+        let order = Order::new("BTCUSDT", OrderSide::Buy, crate::models::order::OrderType::Market, dec!(0.2), dec!(100));
+        let key = SignalKey::for_order("ma", &order, "signal", false).unwrap();
+        assert!(dedup.admit(&key, 1_000) && !dedup.admit(&key, 5_000));
+    }
+}
@@ -0,0 +1,224 @@
+//! 전략 가드레일 모듈
+//!
+//! 연속 손실 횟수 또는 전략별 낙폭이 한도를 넘으면 전략을 자동 비활성화하고,
+//! 쿨오프 기간이 지난 뒤에만 수동 재활성화를 허용한다. 체결은 실제 주문 체결 이벤트로 기록하며,
+//! 가드가 꺼져 있으면(`enabled = false`) 손익만 추적하고 비활성화하지 않는다.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::config::StrategyGuardConfig;
+use crate::error::TradingError;
+use crate::models::order::OrderSide;
+
+/// 전략별 가드 상태 (체결 기반 손익 추적)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GuardState {
+    /// 순 포지션 수량 (매수 +, 매도 -)
+    pub net_qty: f64,
+    /// 평균 진입가
+    pub avg_price: f64,
+    /// 누적 실현 손익
+    pub realized_pnl: f64,
+    /// 실현 손익 고점
+    pub peak_pnl: f64,
+    /// 연속 손실 거래 수
+    pub consecutive_losses: u32,
+    /// 비활성화 시각 (ms)
+    pub tripped_at: Option<i64>,
+    /// 비활성화 사유
+    pub reason: Option<String>,
+}
+
+impl GuardState {
+    /// 고점 대비 낙폭
+    pub fn drawdown(&self) -> f64 {
+        (self.peak_pnl - self.realized_pnl).max(0.0)
+    }
+}
+
+/// 전략 가드 - 전략별 손실/낙폭 감시
+pub struct StrategyGuard {
+    config: StrategyGuardConfig,
+    states: HashMap<String, GuardState>,
+}
+
+impl StrategyGuard {
+    /// 새 전략 가드 생성
+    pub fn new(config: StrategyGuardConfig) -> Self {
+        StrategyGuard {
+            config,
+            states: HashMap::new(),
+        }
+    }
+
+    /// 가드 설정 변경
+    pub fn set_config(&mut self, config: StrategyGuardConfig) {
+        self.config = config;
+    }
+
+    /// 체결 기록 - 포지션을 줄이는 체결은 하나의 거래 결과로 집계한다.
+    /// 이번 체결로 가드가 발동하면 사유를 반환
+    pub fn record_fill(&mut self, strategy: &str, side: &OrderSide, quantity: f64, price: f64, now: i64) -> Option<String> {
+        if quantity <= 0.0 || price <= 0.0 {
+            return None;
+        }
+
+        let state = self.states.entry(strategy.to_string()).or_default();
+        let signed_qty = match side {
+            OrderSide::Buy => quantity,
+            OrderSide::Sell => -quantity,
+        };
+
+        // 같은 방향이면 평균 단가 갱신, 반대 방향이면 청산분 손익 실현
+        if state.net_qty == 0.0 || state.net_qty.signum() == signed_qty.signum() {
+            let total = state.net_qty.abs() + quantity;
+            state.avg_price = (state.avg_price * state.net_qty.abs() + price * quantity) / total;
+            state.net_qty += signed_qty;
+            return None;
+        }
+
+        let closed = quantity.min(state.net_qty.abs());
+        let pnl = state.net_qty.signum() * (price - state.avg_price) * closed;
+        state.net_qty += signed_qty;
+        if state.net_qty.abs() < f64::EPSILON {
+            state.net_qty = 0.0;
+            state.avg_price = 0.0;
+        } else if state.net_qty.signum() == signed_qty.signum() {
+            // 포지션 반전: 남은 수량은 새 진입가로
+            state.avg_price = price;
+        }
+
+        self.record_trade_result(strategy, pnl, now)
+    }
+
+    /// 거래 결과(실현 손익) 기록. 가드가 새로 발동하면 사유를 반환
+    pub fn record_trade_result(&mut self, strategy: &str, pnl: f64, now: i64) -> Option<String> {
+        let max_losses = self.config.max_consecutive_losses;
+        let max_drawdown = self.config.max_drawdown;
+        let state = self.states.entry(strategy.to_string()).or_default();
+
+        state.realized_pnl += pnl;
+        state.peak_pnl = state.peak_pnl.max(state.realized_pnl);
+        if pnl < 0.0 {
+            state.consecutive_losses += 1;
+        } else {
+            state.consecutive_losses = 0;
+        }
+
+        if !self.config.enabled || state.tripped_at.is_some() {
+            return None;
+        }
+
+        let reason = if max_losses > 0 && state.consecutive_losses >= max_losses {
+            Some(format!("{} consecutive losing trades", state.consecutive_losses))
+        } else if max_drawdown > 0.0 && state.drawdown() >= max_drawdown {
+            Some(format!("drawdown {:.2} exceeds limit {:.2}", state.drawdown(), max_drawdown))
+        } else {
+            None
+        };
+
+        if let Some(r) = &reason {
+            state.tripped_at = Some(now);
+            state.reason = Some(r.clone());
+        }
+        reason
+    }
+
+    /// 가드로 비활성화된 상태인지
+    pub fn is_tripped(&self, strategy: &str) -> bool {
+        self.states.get(strategy).map(|s| s.tripped_at.is_some()).unwrap_or(false)
+    }
+
+    /// 수동 재활성화 가능 여부 확인 (쿨오프 기간 중이면 오류)
+    pub fn check_reenable(&self, strategy: &str, now: i64) -> Result<(), TradingError> {
+        if let Some(tripped_at) = self.states.get(strategy).and_then(|s| s.tripped_at) {
+            let remaining = tripped_at + self.config.cooloff_ms - now;
+            if remaining > 0 {
+                return Err(TradingError::RiskLimitExceeded(format!(
+                    "Strategy '{}' is cooling off for another {}s", strategy, remaining / 1000
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 재활성화 시 연속 손실/낙폭 기준 초기화 (누적 손익은 유지)
+    pub fn reset(&mut self, strategy: &str) {
+        if let Some(state) = self.states.get_mut(strategy) {
+            state.consecutive_losses = 0;
+            state.peak_pnl = state.realized_pnl;
+            state.tripped_at = None;
+            state.reason = None;
+        }
+    }
+
+    /// 전략 상태 조회
+    pub fn state(&self, strategy: &str) -> Option<&GuardState> {
+        self.states.get(strategy)
+    }
+
+    /// 전략 제거 시 상태 정리
+    pub fn remove(&mut self, strategy: &str) {
+        self.states.remove(strategy);
+    }
+}
+
+impl Default for StrategyGuard {
+    fn default() -> Self {
+        Self::new(StrategyGuardConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategy_guard() {
+        let mut guard = StrategyGuard::new(StrategyGuardConfig {
+            enabled: true,
+            max_consecutive_losses: 2,
+            max_drawdown: 0.0,
+            cooloff_ms: 60_000,
+        });
+
+        // 손실 거래 1회: 100에 매수, 90에 매도
+        assert!(guard.record_fill("s1", &OrderSide::Buy, 1.0, 100.0, 0).is_none());
+        assert!(guard.record_fill("s1", &OrderSide::Sell, 1.0, 90.0, 1).is_none());
+        assert_eq!(guard.state("s1").unwrap().consecutive_losses, 1);
+
+        // 손실 거래 2회 -> 발동
+        guard.record_fill("s1", &OrderSide::Buy, 1.0, 100.0, 2);
+        let reason = guard.record_fill("s1", &OrderSide::Sell, 1.0, 95.0, 1_000);
+        assert!(reason.is_some());
+        assert!(guard.is_tripped("s1"));
+        assert!((guard.state("s1").unwrap().realized_pnl + 15.0).abs() < 1e-9);
+
+        // 쿨오프 중 재활성화 불가, 이후 가능
+        assert!(guard.check_reenable("s1", 30_000).is_err());
+        assert!(guard.check_reenable("s1", 61_000).is_ok());
+        guard.reset("s1");
+        assert!(!guard.is_tripped("s1"));
+
+        // 낙폭 한도
+        let mut guard = StrategyGuard::new(StrategyGuardConfig {
+            enabled: true,
+            max_consecutive_losses: 0,
+            max_drawdown: 50.0,
+            cooloff_ms: 0,
+        });
+        assert!(guard.record_trade_result("s2", 100.0, 0).is_none());
+        assert!(guard.record_trade_result("s2", -30.0, 1).is_none());
+        assert!(guard.record_trade_result("s2", 10.0, 2).is_none());
+        assert!(guard.record_trade_result("s2", -40.0, 3).is_some());
+
+        // 기본값은 꺼짐 - 손익은 추적하되 비활성화하지 않음
+        let mut guard = StrategyGuard::new(StrategyGuardConfig::default());
+        for i in 0..10 {
+            assert!(guard.record_trade_result("s3", -100.0, i).is_none());
+        }
+        assert_eq!(guard.state("s3").unwrap().consecutive_losses, 10);
+    }
+}
@@ -5,24 +5,328 @@
 * description: 
 **/
 
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+use crate::config::{AllocationConfig, ProtectiveStopConfig, ReportingConfig, StrategyGuardConfig, StrategySchedule, StrategySpec, WatchdogConfig};
+use crate::core::capital_allocator::{AllocationChange, BudgetDecision, BudgetUsage, CapitalAllocator};
+use crate::core::pnl_buckets::TimeBucketReport;
+use crate::core::protective_stop::{ProtectedPosition, ProtectiveExit, ProtectiveStopManager};
+use crate::core::strategy_guard::{GuardState, StrategyGuard};
+use crate::core::strategy_scheduler::{ScheduleTransition, StrategyScheduler};
+use crate::core::strategy_store::{StrategyRecord, StrategyStateRepository};
+use crate::core::strategy_watchdog::{StrategyWatchdog, WatchdogAlert, WatchdogState};
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::market_data::external::ExternalInputs;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::funding::FundingRate;
 use crate::models::market_data::MarketData;
-use crate::models::order::Order;
+use crate::models::order_book::OrderBook;
+use crate::models::order::{Order, OrderSide};
+use crate::signals::entry_exits::EntryExitsConfig;
 use crate::strategies::Strategy;
 
+// 전략 핸들 - 전략마다 따로 잠가 느린 전략이 다른 전략의 업데이트나 조회를 막지 않음
+pub type SharedStrategy = Arc<Mutex<Box<dyn Strategy>>>;
+
+fn lock(strategy: &SharedStrategy) -> MutexGuard<'_, Box<dyn Strategy>> {
+  strategy.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// 전략 하나에 전달할 시세 (단일 심볼 캔들 또는 다중 심볼 스냅샷)
+enum UpdateInput {
+  Single(MarketData),
+  Multi(Arc<HashMap<String, MarketData>>),
+}
+
+struct UpdateJob {
+  name: String,
+  strategy: SharedStrategy,
+  input: UpdateInput,
+}
+
+impl UpdateJob {
+  fn apply(self) -> (String, Result<(), TradingError>) {
+    let mut strategy = lock(&self.strategy);
+    let result = match &self.input {
+      UpdateInput::Single(market_data) => strategy.update(market_data.clone()),
+      UpdateInput::Multi(snapshot) => strategy.update_multi(snapshot),
+    };
+    drop(strategy);
+    (self.name, result)
+  }
+}
+
+// 시세/캔들 하나로 업데이트할 전략 묶음 - 관리자 잠금 없이 실행하고 결과는 `complete_update` 로 반영
+#[must_use]
+pub struct UpdateBatch {
+  jobs: Vec<UpdateJob>,
+}
+
+impl UpdateBatch {
+  // 전략별 블로킹 작업으로 병렬 실행 (같은 묶음의 전략은 서로 다름)
+  pub async fn run(self) -> Vec<(String, Result<(), TradingError>)> {
+    let tasks = self.jobs.into_iter().map(|job| async move {
+      let name = job.name.clone();
+      tokio::task::spawn_blocking(move || job.apply()).await
+        .unwrap_or_else(|e| (name, Err(TradingError::InvalidStrategy(format!("strategy update panicked: {}", e)))))
+    });
+    futures::future::join_all(tasks).await
+  }
+
+  // 순서대로 실행 (백테스트 - 결과 재현성)
+  pub fn run_sequential(self) -> Vec<(String, Result<(), TradingError>)> {
+    self.jobs.into_iter().map(UpdateJob::apply).collect()
+  }
+}
+
+// 전략 실행 상태 (GET /strategies/:name/status, WebSocket strategies 채널)
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct StrategyStatus {
+  pub name: String,
+  pub active: bool,
+  // 섀도 모드 - 주문을 거래소로 보내지 않고 가상 체결만 기록
+  pub shadow: bool,
+  pub status: serde_json::Value,
+}
+
 // 전략 관리자 - 여러 전략 관리 및 조정
 pub struct StrategyManager {
-  strategies: HashMap<String, Box<dyn Strategy>>,
+  strategies: HashMap<String, SharedStrategy>,
+  // 전략별 다중 심볼 목록 (업데이트 대상 선별 시 전략을 잠그지 않도록 등록/파라미터 변경 때 갱신)
+  symbols: HashMap<String, Vec<String>>,
   active_strategies: Vec<String>,
+  guard: StrategyGuard,
+  protection: ProtectiveStopManager,
+  allocator: CapitalAllocator,
+  reporting: ReportingConfig,
+  pnl_buckets: HashMap<String, TimeBucketReport>,
+  watchdog: StrategyWatchdog,
+  capabilities: Option<ExchangeCapabilities>,
+  specs: HashMap<String, StrategySpec>,
+  state_store: Option<Box<dyn StrategyStateRepository>>,
+  // 심볼별 최신 시세 (다중 심볼 전략 스냅샷)
+  latest_market_data: HashMap<String, MarketData>,
+  // 전략별 실행 타임프레임 (없으면 틱 단위 업데이트)
+  timeframes: HashMap<String, String>,
+  // 타임프레임별 심볼 최신 마감 캔들 (다중 심볼 전략 스냅샷)
+  latest_candles: HashMap<String, HashMap<String, MarketData>>,
+  // 전략별 주문 대상 거래소 (없으면 기본 거래소)
+  venues: HashMap<String, String>,
+  // 전략별 진입 주문 손절/익절
+  entry_exits: HashMap<String, EntryExitsConfig>,
+  // 섀도(드라이런) 모드 전략
+  shadow: HashSet<String>,
+  // 전략별 실행 시간대
+  scheduler: StrategyScheduler,
+  // 마지막으로 읽은 전략 상태 (업데이트 중이라 잠겨 있으면 대신 반환)
+  status_cache: Mutex<HashMap<String, serde_json::Value>>,
 }
 
 impl StrategyManager {
   pub fn new() -> Self {
     StrategyManager {
       strategies: HashMap::new(),
+      symbols: HashMap::new(),
       active_strategies: Vec::new(),
+      guard: StrategyGuard::default(),
+      protection: ProtectiveStopManager::new(),
+      allocator: CapitalAllocator::default(),
+      reporting: ReportingConfig::default(),
+      pnl_buckets: HashMap::new(),
+      watchdog: StrategyWatchdog::default(),
+      capabilities: None,
+      specs: HashMap::new(),
+      state_store: None,
+      latest_market_data: HashMap::new(),
+      timeframes: HashMap::new(),
+      latest_candles: HashMap::new(),
+      venues: HashMap::new(),
+      entry_exits: HashMap::new(),
+      shadow: HashSet::new(),
+      scheduler: StrategyScheduler::new(),
+      status_cache: Mutex::new(HashMap::new()),
+    }
+  }
+  
+  // 전략 가드레일 설정 (연속 손실/낙폭 한도, 쿨오프)
+  pub fn set_guard_config(&mut self, config: StrategyGuardConfig) {
+    self.guard.set_config(config);
+  }
+  
+  // 전략별 보호 청산(합성 손절) 설정 - None 이면 해제
+  pub fn set_protection(&mut self, name: &str, config: Option<ProtectiveStopConfig>) -> Result<(), TradingError> {
+    if !self.strategies.contains_key(name) {
+      return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
+    }
+    if let Some(config) = &config {
+      config.validate().map_err(TradingError::InvalidParameter)?;
+    }
+    self.protection.set_config(name, config);
+    self.persist_state();
+    Ok(())
+  }
+  
+  // 자본 배분 정책 설정 (총 자본, 비중 한도, 재배분 주기)
+  pub fn set_allocation_config(&mut self, config: AllocationConfig) {
+    self.allocator.set_config(config);
+  }
+  
+  // 시간대별 손익 리포트 타임존 설정 (기존 집계는 초기화)
+  pub fn set_reporting_config(&mut self, config: ReportingConfig) -> Result<(), TradingError> {
+    for tz in std::iter::once(&config.timezone).chain(config.strategy_timezones.values()) {
+      crate::core::pnl_buckets::parse_utc_offset(tz)?;
+    }
+    self.reporting = config;
+    self.pnl_buckets.clear();
+    Ok(())
+  }
+  
+  // 워치독 설정
+  pub fn set_watchdog_config(&mut self, config: WatchdogConfig) {
+    self.watchdog.set_config(config);
+  }
+  
+  // 거래소 기능 설정 - 등록된 전략과 이후 추가되는 전략의 네이티브 실행 여부 결정
+  pub fn set_capabilities(&mut self, capabilities: ExchangeCapabilities) {
+    for strategy in self.strategies.values() {
+      lock(strategy).apply_capabilities(&capabilities);
+    }
+    self.capabilities = Some(capabilities);
+  }
+  
+  // 전략 상태 저장소 설정 - 설정 명세로 추가된 전략의 설정/진행 상태를 변경 시마다 저장
+  pub fn set_state_store(&mut self, store: Box<dyn StrategyStateRepository>) {
+    self.state_store = Some(store);
+  }
+  
+  // 저장소에서 전략 복원 (이미 등록된 이름은 건너뜀) - 복원된 전략 수 반환
+  pub fn restore_strategies(&mut self) -> Result<usize, TradingError> {
+    let records = match &self.state_store {
+      Some(store) => store.load_all()?,
+      None => return Ok(0),
+    };
+    
+    let mut restored = 0;
+    for record in records {
+      if self.strategies.contains_key(&record.name) {
+        continue;
+      }
+      let mut strategy = crate::strategies::build_strategy(&record.spec)?;
+      if strategy.name() != record.name {
+        log::warn!("저장된 전략 이름 불일치: {} != {}", record.name, strategy.name());
+        continue;
+      }
+      if let Some(state) = &record.state {
+        strategy.restore_state(state)?;
+      }
+      strategy.set_active(record.active);
+      self.specs.insert(record.name.clone(), record.spec);
+      self.add_strategy(strategy)?;
+      self.protection.set_config(&record.name, record.protection);
+      if let Some(exits) = record.exits {
+        match self.strategies.get(&record.name).map(|s| lock(s).set_entry_exits(Some(exits.clone()))) {
+          Some(Ok(())) => { self.entry_exits.insert(record.name.clone(), exits); }
+          Some(Err(e)) => log::warn!("저장된 손절/익절 무시: {} ({})", record.name, e),
+          None => {}
+        }
+      }
+      if let Some(timeframe) = record.timeframe {
+        self.timeframes.insert(record.name.clone(), timeframe);
+      }
+      if let Some(venue) = record.exchange {
+        self.venues.insert(record.name.clone(), venue);
+      }
+      if record.shadow {
+        self.shadow.insert(record.name.clone());
+      }
+      if let Err(e) = self.scheduler.set(&record.name, record.schedule) {
+        log::warn!("저장된 스케줄 무시: {} ({})", record.name, e);
+      }
+      if !record.active {
+        self.active_strategies.retain(|s| s != &record.name);
+      }
+      restored += 1;
+    }
+    
+    Ok(restored)
+  }
+  
+  // 설정 명세로 전략 생성 후 추가 (상태 저장 대상) - 전략 이름 반환
+  pub fn add_strategy_spec(&mut self, spec: StrategySpec) -> Result<String, TradingError> {
+    let strategy = crate::strategies::build_strategy(&spec)?;
+    let name = strategy.name().to_string();
+    self.add_strategy(strategy)?;
+    self.specs.insert(name.clone(), spec);
+    self.persist_state();
+    Ok(name)
+  }
+  
+  // 명세로 추가된 전략의 현재 명세 (실행 중 파라미터 변경 반영)
+  pub fn strategy_spec(&self, name: &str) -> Option<&StrategySpec> {
+    self.specs.get(name)
+  }
+  
+  // 전략 진행 상태 (설정 리로드로 다시 생성할 때 이어받기용)
+  pub fn export_strategy_state(&self, name: &str) -> Option<serde_json::Value> {
+    lock(self.strategies.get(name)?).export_state()
+  }
+  
+  // export_strategy_state 로 받은 진행 상태 복원
+  pub fn restore_strategy_state(&mut self, name: &str, state: &serde_json::Value) -> Result<(), TradingError> {
+    let strategy = self.strategies.get(name)
+      .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
+    lock(strategy).restore_state(state)?;
+    self.persist_state();
+    Ok(())
+  }
+  
+  // 실행 중 파라미터 변경 - 전략 진행 상태는 유지하고, 저장 명세에도 반영해 재시작 후에도 유지
+  pub fn update_strategy_params(&mut self, name: &str, params: serde_json::Value) -> Result<(), TradingError> {
+    let strategy = self.strategies.get(name)
+      .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
+    let symbols = {
+      let mut strategy = lock(strategy);
+      strategy.update_params(params.clone())?;
+      strategy.symbols()
+    };
+    self.symbols.insert(name.to_string(), symbols);
+    
+    if let Some(spec) = self.specs.get_mut(name) {
+      match spec.with_params(&params) {
+        Some(updated) => *spec = updated,
+        None => log::warn!("{}: parameters applied but spec could not be updated", name),
+      }
+    }
+    log::info!("{} parameters updated: {}", name, params);
+    self.persist_state();
+    Ok(())
+  }
+  
+  // 전략 설정/상태 스냅샷 저장 (실패해도 실행은 계속)
+  fn persist_state(&mut self) {
+    let Some(store) = self.state_store.as_mut() else { return };
+    let now = chrono::Utc::now().timestamp_millis();
+    let records: Vec<StrategyRecord> = self.specs.iter()
+      .filter_map(|(name, spec)| {
+        let state = lock(self.strategies.get(name)?).export_state();
+        Some(StrategyRecord {
+          name: name.clone(),
+          spec: spec.clone(),
+          active: self.active_strategies.contains(name),
+          state,
+          protection: self.protection.config(name).cloned(),
+          exits: self.entry_exits.get(name).cloned(),
+          timeframe: self.timeframes.get(name).cloned(),
+          exchange: self.venues.get(name).cloned(),
+          shadow: self.shadow.contains(name),
+          schedule: self.scheduler.config(name).cloned(),
+          updated_at: now,
+        })
+      })
+      .collect();
+    if let Err(e) = store.save_all(&records) {
+      log::warn!("전략 상태 저장 실패: {}", e);
     }
   }
   
@@ -34,8 +338,15 @@ impl StrategyManager {
       return Err(TradingError::DuplicateStrategy(format!("Strategy '{}' already exists", name)));
     }
     
+    let mut strategy = strategy;
+    if let Some(capabilities) = &self.capabilities {
+      strategy.apply_capabilities(capabilities);
+    }
+    strategy.on_start()?;
     let is_active = strategy.is_active();
-    self.strategies.insert(name.clone(), strategy);
+    self.symbols.insert(name.clone(), strategy.symbols());
+    self.strategies.insert(name.clone(), Arc::new(Mutex::new(strategy)));
+    self.allocator.register(&name, chrono::Utc::now().timestamp_millis());
     
     if is_active {
       self.active_strategies.push(name);
@@ -44,24 +355,176 @@ impl StrategyManager {
     Ok(())
   }
   
+  // 타임프레임 지정 전략 추가 - 해당 타임프레임의 마감 캔들만 받음
+  pub fn add_strategy_with_timeframe(&mut self, strategy: Box<dyn Strategy>, timeframe: &str) -> Result<(), TradingError> {
+    crate::market_data::aggregator::parse_timeframe(timeframe)?;
+    let name = strategy.name().to_string();
+    self.add_strategy(strategy)?;
+    self.timeframes.insert(name, timeframe.to_string());
+    Ok(())
+  }
+  
+  // 등록된 전략의 타임프레임 변경 (None 이면 틱 단위로 복귀)
+  pub fn set_strategy_timeframe(&mut self, name: &str, timeframe: Option<&str>) -> Result<(), TradingError> {
+    if !self.strategies.contains_key(name) {
+      return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
+    }
+    match timeframe {
+      Some(timeframe) => {
+        crate::market_data::aggregator::parse_timeframe(timeframe)?;
+        self.timeframes.insert(name.to_string(), timeframe.to_string());
+      }
+      None => {
+        self.timeframes.remove(name);
+      }
+    }
+    self.persist_state();
+    Ok(())
+  }
+  
+  // 전략 타임프레임 조회
+  pub fn strategy_timeframe(&self, name: &str) -> Option<&str> {
+    self.timeframes.get(name).map(String::as_str)
+  }
+  
+  // 등록된 전략이 사용하는 타임프레임 목록 (캔들 집계 대상)
+  pub fn timeframes(&self) -> BTreeSet<String> {
+    self.timeframes.values().cloned().collect()
+  }
+  
+  // 전략 주문 대상 거래소 지정 (None 이면 기본 거래소)
+  pub fn set_strategy_exchange(&mut self, name: &str, exchange: Option<&str>) -> Result<(), TradingError> {
+    if !self.strategies.contains_key(name) {
+      return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
+    }
+    match exchange {
+      Some(exchange) => self.venues.insert(name.to_string(), exchange.to_string()),
+      None => self.venues.remove(name),
+    };
+    self.persist_state();
+    Ok(())
+  }
+  
+  pub fn strategy_exchange(&self, name: &str) -> Option<&str> {
+    self.venues.get(name).map(String::as_str)
+  }
+  
+  // 진입 주문 손절/익절 설정 - 진입 체결 후 OCO 브래킷으로 등록 (None 이면 해제)
+  pub fn set_entry_exits(&mut self, name: &str, config: Option<EntryExitsConfig>) -> Result<(), TradingError> {
+    let strategy = self.strategies.get(name)
+      .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
+    lock(strategy).set_entry_exits(config.clone())?;
+    match config {
+      Some(config) => self.entry_exits.insert(name.to_string(), config),
+      None => self.entry_exits.remove(name),
+    };
+    self.persist_state();
+    Ok(())
+  }
+  
+  pub fn entry_exits(&self, name: &str) -> Option<&EntryExitsConfig> {
+    self.entry_exits.get(name)
+  }
+  
+  // 섀도 모드 지정 - 전략은 그대로 실행하되 주문은 거래소 대신 섀도 장부로
+  pub fn set_strategy_shadow(&mut self, name: &str, shadow: bool) -> Result<(), TradingError> {
+    if !self.strategies.contains_key(name) {
+      return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
+    }
+    if shadow {
+      self.shadow.insert(name.to_string());
+    } else {
+      self.shadow.remove(name);
+    }
+    self.persist_state();
+    Ok(())
+  }
+  
+  pub fn is_shadow(&self, name: &str) -> bool {
+    self.shadow.contains(name)
+  }
+  
+  // 총 수량을 나눠 내는 실행 전략인지 (분할 주문은 신호 중복 제거 대상 아님)
+  pub fn is_execution_strategy(&self, name: &str) -> bool {
+    self.strategies.get(name).is_some_and(|s| lock(s).is_execution_strategy())
+  }
+  
+  // 실행 시간대 지정 (None 이면 항상 실행)
+  pub fn set_schedule(&mut self, name: &str, schedule: Option<StrategySchedule>) -> Result<(), TradingError> {
+    if !self.strategies.contains_key(name) {
+      return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
+    }
+    self.scheduler.set(name, schedule)?;
+    self.persist_state();
+    Ok(())
+  }
+  
+  pub fn schedule(&self, name: &str) -> Option<&StrategySchedule> {
+    self.scheduler.config(name)
+  }
+  
+  // 시간대 밖이면 사유 (스케줄 없는 전략은 항상 None)
+  pub fn schedule_closed_reason(&self, name: &str, now: i64) -> Option<String> {
+    self.scheduler.closed_reason(name, now)
+  }
+  
+  // 시간대 열림/닫힘 점검 - 런타임이 주기적으로 호출해 알림과 미체결 정리에 사용
+  pub fn check_schedules(&mut self, now: i64) -> Vec<ScheduleTransition> {
+    self.scheduler.check(now)
+  }
+  
   // 전략 제거
   pub fn remove_strategy(&mut self, name: &str) -> Result<(), TradingError> {
     if !self.strategies.contains_key(name) {
       return Err(TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)));
     }
     
-    self.strategies.remove(name);
+    if let Some(strategy) = self.strategies.remove(name) {
+      lock(&strategy).on_stop();
+    }
+    self.symbols.remove(name);
     self.active_strategies.retain(|s| s != name);
+    self.guard.remove(name);
+    self.protection.remove(name);
+    self.pnl_buckets.remove(name);
+    self.watchdog.remove(name);
+    self.timeframes.remove(name);
+    self.venues.remove(name);
+    self.status_cache.get_mut().unwrap_or_else(|e| e.into_inner()).remove(name);
+    self.entry_exits.remove(name);
+    self.shadow.remove(name);
+    self.scheduler.remove(name);
+    self.allocator.unregister(name, chrono::Utc::now().timestamp_millis());
+    if self.specs.remove(name).is_some() {
+      self.persist_state();
+    }
     
     Ok(())
   }
   
+  // 프로세스 종료 - 모든 전략 정지 후 설정/진행 상태 저장 (재시작 시 그대로 복원) - 정지한 전략 이름 반환
+  pub fn shutdown(&mut self) -> Vec<String> {
+    let mut stopped = Vec::new();
+    for (name, strategy) in &self.strategies {
+      lock(strategy).on_stop();
+      stopped.push(name.clone());
+    }
+    self.persist_state();
+    stopped
+  }
+  
   // 전략 활성화/비활성화
   pub fn set_strategy_active(&mut self, name: &str, active: bool) -> Result<(), TradingError> {
-    let strategy = self.strategies.get_mut(name)
+    let strategy = self.strategies.get(name).cloned()
       .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
     
-    strategy.set_active(active);
+    // 가드로 비활성화된 전략은 쿨오프 이후에만 재활성화
+    if active && self.guard.is_tripped(name) {
+      self.guard.check_reenable(name, chrono::Utc::now().timestamp_millis())?;
+      self.guard.reset(name);
+    }
+    
+    lock(&strategy).set_active(active);
     
     if active {
       if !self.active_strategies.contains(&name.to_string()) {
@@ -69,62 +532,536 @@ impl StrategyManager {
       }
     } else {
       self.active_strategies.retain(|s| s != name);
+      // 비활성 전략은 업데이트가 없으므로 학습된 주기 초기화
+      self.watchdog.remove(name);
     }
+    self.persist_state();
     
     Ok(())
   }
   
-  // 모든 전략 업데이트
+  // 타임프레임 미지정 전략 업데이트 (틱/폴링 시세) - 전략을 차례로 실행 (백테스트)
   pub fn update_all(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
-    for name in &self.active_strategies.clone() {
-      if let Some(strategy) = self.strategies.get_mut(name) {
-        strategy.update(market_data.clone())?;
+    let results = self.prepare_update(market_data).run_sequential();
+    self.complete_update(results).into_iter().next().map_or(Ok(()), |(_, e)| Err(e))
+  }
+  
+  // 시세를 받을 타임프레임 미지정 전략 선별 (최신 시세 스냅샷 갱신)
+  pub fn prepare_update(&mut self, market_data: &MarketData) -> UpdateBatch {
+    let now = chrono::Utc::now().timestamp_millis();
+    self.latest_market_data.insert(market_data.symbol.clone(), market_data.clone());
+    let snapshot = Arc::new(self.latest_market_data.clone());
+    self.batch(market_data, snapshot, |name, timeframes, scheduler| !timeframes.contains_key(name) && scheduler.is_open(name, now))
+  }
+  
+  // 마감 캔들을 같은 타임프레임으로 등록된 전략에만 전달 - 받을 전략 선별
+  pub fn prepare_candle(&mut self, timeframe: &str, candle: &MarketData) -> UpdateBatch {
+    let now = chrono::Utc::now().timestamp_millis();
+    let latest = self.latest_candles.entry(timeframe.to_string()).or_default();
+    latest.insert(candle.symbol.clone(), candle.clone());
+    let snapshot = Arc::new(latest.clone());
+    self.batch(candle, snapshot, |name, timeframes, scheduler| {
+      timeframes.get(name).map(String::as_str) == Some(timeframe) && scheduler.is_open(name, now)
+    })
+  }
+  
+  // 활성 전략 중 대상 전략 묶음 - 다중 심볼 전략은 대상 심볼이 갱신될 때만 전체 스냅샷으로 업데이트
+  fn batch(
+    &self,
+    market_data: &MarketData,
+    snapshot: Arc<HashMap<String, MarketData>>,
+    selected: impl Fn(&str, &HashMap<String, String>, &StrategyScheduler) -> bool,
+  ) -> UpdateBatch {
+    let jobs = self.active_strategies.iter()
+      .filter(|name| selected(name, &self.timeframes, &self.scheduler))
+      .filter_map(|name| {
+        let strategy = self.strategies.get(name)?.clone();
+        let input = match self.symbols.get(name) {
+          Some(symbols) if !symbols.is_empty() => {
+            if !symbols.contains(&market_data.symbol) {
+              return None;
+            }
+            UpdateInput::Multi(snapshot.clone())
+          }
+          _ => UpdateInput::Single(market_data.clone()),
+        };
+        Some(UpdateJob { name: name.clone(), strategy, input })
+      })
+      .collect();
+    UpdateBatch { jobs }
+  }
+  
+  // 업데이트 결과 반영 - 성공한 전략은 워치독에 기록, 실패한 전략과 오류 반환
+  // (업데이트 중에 제거된 전략의 결과는 버림)
+  pub fn complete_update(&mut self, results: Vec<(String, Result<(), TradingError>)>) -> Vec<(String, TradingError)> {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut failed = Vec::new();
+    for (name, result) in results {
+      if !self.strategies.contains_key(&name) {
+        continue;
+      }
+      match result {
+        Ok(()) => self.watchdog.record_update(&name, now),
+        Err(e) => failed.push((name, e)),
+      }
+    }
+    failed
+  }
+  
+  // 외부 시계열 값을 모든 활성 전략에 전달
+  pub fn update_external_all(&mut self, inputs: &ExternalInputs) {
+    for name in &self.active_strategies {
+      if let Some(strategy) = self.strategies.get(name) {
+        lock(strategy).update_external(inputs);
+      }
+    }
+  }
+  
+  // 펀딩 정보를 모든 활성 전략에 전달
+  pub fn update_funding_all(&mut self, funding: &FundingRate) {
+    for name in &self.active_strategies {
+      if let Some(strategy) = self.strategies.get(name) {
+        lock(strategy).update_funding(funding);
+      }
+    }
+  }
+  
+  // 호가창 갱신을 모든 활성 전략에 전달
+  pub fn update_order_book_all(&mut self, book: &OrderBook) {
+    for name in &self.active_strategies {
+      if let Some(strategy) = self.strategies.get(name) {
+        lock(strategy).update_order_book(book);
       }
     }
-    
-    Ok(())
   }
   
   // 모든 활성 전략에서 주문 수집
   pub fn get_all_orders(&mut self) -> Result<Vec<Order>, TradingError> {
     let mut all_orders = Vec::new();
+    let now = chrono::Utc::now().timestamp_millis();
     
     for name in &self.active_strategies {
-      if let Some(strategy) = self.strategies.get_mut(name) {
-        let orders = strategy.get_orders()?;
+      // 실행 시간대 밖의 전략은 주문을 내지 않음
+      if !self.scheduler.is_open(name, now) {
+        continue;
+      }
+      if let Some(strategy) = self.strategies.get(name) {
+        let orders = lock(strategy).get_orders()?;
         all_orders.extend(orders);
       }
     }
+    if !all_orders.is_empty() {
+      self.persist_state();
+    }
     
     Ok(all_orders)
   }
   
+  // 모든 활성 전략에서 주문 수집 (전략 이름 포함) - `skip` 전략(업데이트 실패)은 제외하고,
+  // 주문 생성에 실패한 전략은 기록만 하고 나머지 전략의 주문은 그대로 수집
+  pub fn get_all_orders_by_strategy(&mut self, skip: &[String]) -> Vec<(String, Order)> {
+    let mut all_orders = Vec::new();
+    let now = chrono::Utc::now().timestamp_millis();
+    
+    for name in &self.active_strategies {
+      // 실행 시간대 밖의 전략은 주문을 내지 않음
+      if !self.scheduler.is_open(name, now) || skip.contains(name) {
+        continue;
+      }
+      if let Some(strategy) = self.strategies.get(name) {
+        let orders = {
+          let mut strategy = lock(strategy);
+          // 포지션 크기를 자본 기준으로 정하는 전략은 남은 예산으로 계산
+          if let Some(remaining) = self.allocator.remaining(name) {
+            strategy.set_available_capital(remaining);
+          }
+          match strategy.get_orders() {
+            Ok(orders) => orders,
+            Err(e) => {
+              log::warn!("strategy {} order collection failed: {}", name, e);
+              continue;
+            }
+          }
+        };
+        self.watchdog.record_orders(name, orders.len(), now);
+        for mut order in orders {
+          // 가격 없는 시장가는 최신 시세로 명목 금액 계산
+          let price = if order.price > Decimal::ZERO { order.price } else { self.latest_market_data.get(&order.symbol).map(|md| md.close).unwrap_or_default() };
+          match self.allocator.check_order(name, &order, to_f64(price)) {
+            BudgetDecision::Allow => {}
+            BudgetDecision::Scale(quantity) => {
+              log::info!("예산 초과 주문 축소: {} {} {} -> {}", name, order.symbol, order.quantity, quantity);
+              order.quantity = quantity;
+            }
+            BudgetDecision::Block(reason) => {
+              log::warn!("예산 초과 주문 차단: {}", reason);
+              continue;
+            }
+          }
+          // 거래소를 정하지 않은 주문은 전략의 거래소로
+          if order.exchange.is_none() {
+            order.exchange = self.venues.get(name).cloned();
+          }
+          all_orders.push((name.clone(), order));
+        }
+      }
+    }
+    if !all_orders.is_empty() {
+      self.persist_state();
+    }
+    
+    all_orders
+  }
+  
+  // 보호 청산 점검 - 전략 활성 여부와 무관하게 한도에 닿은 포지션의 reduce-only 청산 주문 반환
+  pub fn check_protective_exits(&mut self, market_data: &MarketData) -> Vec<ProtectiveExit> {
+    let exits = self.protection.on_market_data(market_data, chrono::Utc::now().timestamp_millis());
+    for exit in &exits {
+      log::warn!("보호 청산: {} {} {:?} {} ({:?})", exit.strategy, exit.order.symbol, exit.order.side, exit.order.quantity, exit.reason);
+    }
+    exits
+  }
+  
+  // 보호 청산 주문 제출 실패 - 다음 시세에서 재시도
+  pub fn protective_exit_failed(&mut self, name: &str, symbol: &str) {
+    self.protection.exit_failed(name, symbol);
+  }
+  
+  // 전략 보호 청산 설정 조회
+  pub fn get_protection(&self, name: &str) -> Option<ProtectiveStopConfig> {
+    self.protection.config(name).cloned()
+  }
+  
+  // 전략별 보호 대상 포지션 조회
+  pub fn get_protected_positions(&self, name: &str) -> Vec<(String, ProtectedPosition)> {
+    self.protection.positions(name)
+  }
+  
+  // 전략 체결 기록 - 가드 발동 시 전략 자동 비활성화 (true 반환)
+  pub fn record_fill(&mut self, name: &str, symbol: &str, side: &OrderSide, quantity: f64, price: f64) -> bool {
+    let now = chrono::Utc::now().timestamp_millis();
+    self.protection.record_fill(name, symbol, side, quantity, price, now);
+    self.allocator.record_fill(name, symbol, side, quantity, price);
+    let realized_before = self.guard.state(name).map(|s| s.realized_pnl).unwrap_or(0.0);
+    let trip = self.guard.record_fill(name, side, quantity, price, now);
+    
+    // 실현 손익이 발생한 체결은 자본 배분 성과에 반영
+    let realized_after = self.guard.state(name).map(|s| s.realized_pnl).unwrap_or(0.0);
+    if realized_after != realized_before {
+      self.allocator.record_pnl(name, realized_after - realized_before);
+      let tz = self.reporting.timezone_for(name).to_string();
+      if !self.pnl_buckets.contains_key(name) {
+        match TimeBucketReport::new(&tz) {
+          Ok(report) => { self.pnl_buckets.insert(name.to_string(), report); }
+          Err(e) => log::warn!("손익 리포트 타임존 오류 {}: {}", name, e),
+        }
+      }
+      if let Some(report) = self.pnl_buckets.get_mut(name) {
+        report.record(now, realized_after - realized_before);
+      }
+    }
+    
+    if let Some(reason) = trip {
+      log::warn!("전략 '{}' 자동 비활성화: {}", name, reason);
+      if let Some(strategy) = self.strategies.get(name) {
+        lock(strategy).set_active(false);
+      }
+      self.active_strategies.retain(|s| s != name);
+      self.persist_state();
+      return true;
+    }
+    false
+  }
+  
+  // 재배분 주기가 도래하면 성과 기반으로 예산 재배분 (변경 내역 반환)
+  pub fn maybe_rebalance(&mut self, now: i64) -> Vec<AllocationChange> {
+    if !self.allocator.should_rebalance(now) {
+      return Vec::new();
+    }
+    let changes = self.allocator.rebalance(now);
+    for c in &changes {
+      log::info!("자본 재배분: {} {:.2} -> {:.2} ({})", c.strategy, c.old_budget, c.new_budget, c.reason);
+    }
+    changes
+  }
+  
+  // 전략별 자본 예산 조회
+  pub fn get_allocations(&self) -> HashMap<String, f64> {
+    self.allocator.budgets().clone()
+  }
+  
+  // 전략 예산 고정 (재배분 제외)
+  pub fn set_strategy_budget(&mut self, name: &str, budget: f64) -> Result<Vec<AllocationChange>, TradingError> {
+    self.allocator.set_budget(name, budget, chrono::Utc::now().timestamp_millis())
+  }
+  
+  // 전략 예산 고정 해제 - 고정된 예산이 없으면 오류
+  pub fn clear_strategy_budget(&mut self, name: &str) -> Result<Vec<AllocationChange>, TradingError> {
+    self.allocator.clear_budget(name, chrono::Utc::now().timestamp_millis())
+      .ok_or_else(|| TradingError::DataNotFound(format!("no pinned budget for '{}'", name)))
+  }
+  
+  // 전략별 예산 사용 현황 (예산, 사용 중 명목 금액, 남은 금액)
+  pub fn get_budget_usage(&self) -> Vec<BudgetUsage> {
+    self.allocator.usage()
+  }
+  
+  // 자본 배분 변경 감사 로그
+  pub fn get_allocation_audit(&self) -> Vec<AllocationChange> {
+    self.allocator.audit_trail().to_vec()
+  }
+  
+  // 전략별 시간대/요일 손익 리포트
+  pub fn get_time_buckets(&self, name: &str) -> Option<TimeBucketReport> {
+    self.pnl_buckets.get(name).cloned()
+  }
+  
+  // 워치독 점검 - 새로 발생한 이상 징후 반환
+  pub fn check_watchdog(&mut self, now: i64) -> Vec<WatchdogAlert> {
+    let alerts = self.watchdog.check(now);
+    for a in &alerts {
+      log::warn!("전략 이상 감지: {} {:?} ({})", a.strategy, a.kind, a.detail);
+    }
+    alerts
+  }
+  
+  // 전략 워치독 상태 조회
+  pub fn get_watchdog_state(&self, name: &str) -> Option<WatchdogState> {
+    self.watchdog.state(name).cloned()
+  }
+  
+  // 전략 가드 상태 조회
+  pub fn get_guard_state(&self, name: &str) -> Option<GuardState> {
+    self.guard.state(name).cloned()
+  }
+  
   // 특정 전략의 주문 가져오기
   pub fn get_orders_from_strategy(&mut self, name: &str) -> Result<Vec<Order>, TradingError> {
-    let strategy = self.strategies.get_mut(name)
+    let strategy = self.strategies.get(name)
       .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
     
-    strategy.get_orders()
+    lock(strategy).get_orders()
   }
 
-  // 전략 상태 조회
-  pub fn get_strategy_status(&self, name: &str) -> Result<(String, bool), TradingError> {
+  // 전략 상태 조회 - 활성 여부와 전략별 실행 상태 (실행 수량, 지표 값, 트리거 가격 등)
+  pub fn get_strategy_status(&self, name: &str) -> Result<StrategyStatus, TradingError> {
     let strategy = self.strategies.get(name)
       .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
-    Ok((name.to_string(), strategy.is_active()))
+    Ok(self.status_snapshot(name, strategy))
+  }
+
+  // 업데이트 중인 전략은 기다리지 않고 마지막으로 읽은 상태와 관리자의 활성 목록으로 대신
+  fn status_snapshot(&self, name: &str, strategy: &SharedStrategy) -> StrategyStatus {
+    let shadow = self.shadow.contains(name);
+    let strategy = match strategy.try_lock() {
+      Ok(strategy) => strategy,
+      Err(TryLockError::Poisoned(e)) => e.into_inner(),
+      Err(TryLockError::WouldBlock) => {
+        let cached = self.status_cache.lock().unwrap_or_else(|e| e.into_inner()).get(name).cloned();
+        return StrategyStatus { name: name.to_string(), active: self.active_strategies.iter().any(|s| s == name), shadow, status: cached.unwrap_or_default() };
+      }
+    };
+    let status = strategy.status();
+    self.status_cache.lock().unwrap_or_else(|e| e.into_inner()).insert(name.to_string(), status.clone());
+    StrategyStatus { name: name.to_string(), active: strategy.is_active(), shadow, status }
   }
 
   // 전략 상세 정보 조회 (이름, 설명, 활성여부)
   pub fn get_strategy_info(&self, name: &str) -> Result<(String, String, bool), TradingError> {
     let strategy = self.strategies.get(name)
       .ok_or_else(|| TradingError::StrategyNotFound(format!("Strategy '{}' not found", name)))?;
+    let strategy = lock(strategy);
     Ok((strategy.name().to_string(), strategy.description().to_string(), strategy.is_active()))
   }
   
-  // 사용 가능한 전략 목록
+  // 실행 전략 성과 (도착 가격/TWAP 대비 슬리피지)
+  pub fn get_execution_report(&self, name: &str) -> Option<std::collections::HashMap<String, f64>> {
+    self.strategies.get(name).and_then(|s| lock(s).execution_report())
+  }
+  
+  // 사용 가능한 전략 목록 - 업데이트 중인 전략은 기다리지 않고 관리자의 활성 목록으로 대신
   pub fn list_strategies(&self) -> Vec<(String, bool)> {
     self.strategies.iter()
-      .map(|(name, strategy)| (name.clone(), strategy.is_active()))
+      .map(|(name, strategy)| {
+        let active = match strategy.try_lock() {
+          Ok(strategy) => strategy.is_active(),
+          Err(TryLockError::Poisoned(e)) => e.into_inner().is_active(),
+          Err(TryLockError::WouldBlock) => self.active_strategies.contains(name),
+        };
+        (name.clone(), active)
+      })
       .collect()
   }
-}
\ No newline at end of file
+  
+  // 전체 전략 실행 상태 (이름순)
+  pub fn strategy_statuses(&self) -> Vec<StrategyStatus> {
+    let mut statuses: Vec<StrategyStatus> = self.strategies.iter()
+      .map(|(name, strategy)| self.status_snapshot(name, strategy))
+      .collect();
+    statuses.sort_by(|a, b| a.name.cmp(&b.name));
+    statuses
+  }
+}
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::to_decimal;
+  
+  // 받은 캔들 종가 기록
+  struct Recorder {
+    name: String,
+    seen: std::sync::Arc<std::sync::Mutex<Vec<f64>>>,
+    updated: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+  }
+  
+  impl Strategy for Recorder {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+      self.seen.lock().unwrap().push(market_data.close_f64());
+      if let Some(updated) = &self.updated {
+        let _ = updated.send(self.name.clone());
+      }
+      Ok(())
+    }
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> { Ok(Vec::new()) }
+    fn name(&self) -> &str { &self.name }
+    fn description(&self) -> &str { "" }
+  }
+  
+  #[test]
+  fn test_candles_routed_by_timeframe() {
+    let md = |close: f64| MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: to_decimal(close), high: to_decimal(close), low: to_decimal(close), close: to_decimal(close), volume: Decimal::ONE };
+    let recorder = |name: &str| {
+      let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+      (Box::new(Recorder { name: name.into(), seen: seen.clone(), updated: None }), seen)
+    };
+    let (tick, tick_seen) = recorder("tick");
+    let (hourly, hourly_seen) = recorder("hourly");
+    let (minute, minute_seen) = recorder("minute");
+    
+    let mut manager = StrategyManager::new();
+    manager.add_strategy(tick).unwrap();
+    manager.add_strategy_with_timeframe(hourly, "1h").unwrap();
+    manager.add_strategy_with_timeframe(minute, "1m").unwrap();
+    assert!(manager.add_strategy_with_timeframe(recorder("bad").0, "7x").is_err());
+    assert_eq!(manager.timeframes().into_iter().collect::<Vec<_>>(), vec!["1h", "1m"]);
+    
+    manager.update_all(&md(1.0)).unwrap();
+    for (timeframe, close) in [("1m", 2.0), ("1h", 3.0)] {
+      let results = manager.prepare_candle(timeframe, &md(close)).run_sequential();
+      assert!(manager.complete_update(results).is_empty());
+    }
+    assert_eq!(*tick_seen.lock().unwrap(), vec![1.0]);
+    assert_eq!(*minute_seen.lock().unwrap(), vec![2.0]);
+    assert_eq!(*hourly_seen.lock().unwrap(), vec![3.0]);
+    
+    // 타임프레임 해제 시 다시 틱 단위로 업데이트
+    manager.set_strategy_timeframe("minute", None).unwrap();
+    manager.update_all(&md(4.0)).unwrap();
+    assert_eq!(*minute_seen.lock().unwrap(), vec![2.0, 4.0]);
+    assert_eq!(manager.strategy_timeframe("hourly"), Some("1h"));
+  }
+  
+  #[tokio::test]
+  #[allow(clippy::await_holding_lock)]
+  async fn test_parallel_updates_and_non_blocking_listing() {
+    let md = MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: Decimal::ONE, high: Decimal::ONE, low: Decimal::ONE, close: Decimal::ONE, volume: Decimal::ONE };
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (updated_tx, mut updated) = tokio::sync::mpsc::unbounded_channel();
+    let mut manager = StrategyManager::new();
+    for name in ["a", "b"] {
+      manager.add_strategy(Box::new(Recorder { name: name.into(), seen: seen.clone(), updated: Some(updated_tx.clone()) })).unwrap();
+    }
+    
+    // "a" 가 업데이트 중(잠김)이어도 목록 조회와 "b" 업데이트는 기다리지 않음
+    let busy = manager.strategies["a"].clone();
+    let guard = lock(&busy);
+    let mut listed = manager.list_strategies();
+    listed.sort();
+    assert_eq!(listed, vec![("a".to_string(), true), ("b".to_string(), true)]);
+    let statuses = manager.strategy_statuses();
+    assert!(statuses[0].active && statuses[0].status.is_null() && manager.get_strategy_status("a").is_ok());
+    let running = tokio::spawn(manager.prepare_update(&md).run());
+    assert_eq!(updated.recv().await.unwrap(), "b");
+    assert_eq!(seen.lock().unwrap().len(), 1);
+    drop(guard);
+    
+    let results = running.await.unwrap();
+    assert_eq!((results.len(), seen.lock().unwrap().len()), (2, 2));
+    // 업데이트 중에 제거된 전략은 워치독에 다시 등록하지 않음
+    manager.remove_strategy("b").unwrap();
+    assert!(manager.complete_update(results).is_empty());
+    assert!(manager.get_watchdog_state("a").is_some() && manager.get_watchdog_state("b").is_none());
+  }
+  
+  // 시작/정지 호출 기록
+  struct Lifecycle {
+    name: String,
+    events: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+  }
+  
+  impl Strategy for Lifecycle {
+    fn update(&mut self, _market_data: MarketData) -> Result<(), TradingError> { Ok(()) }
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> { Ok(Vec::new()) }
+    fn name(&self) -> &str { &self.name }
+    fn description(&self) -> &str { "" }
+    fn on_start(&mut self) -> Result<(), TradingError> {
+      if self.name == "broken" {
+        return Err(TradingError::InvalidStrategy("start failed".into()));
+      }
+      self.events.lock().unwrap().push(format!("start {}", self.name));
+      Ok(())
+    }
+    fn on_stop(&mut self) {
+      self.events.lock().unwrap().push(format!("stop {}", self.name));
+    }
+  }
+  
+  #[test]
+  fn test_lifecycle_hooks() {
+    let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let strategy = |name: &str| Box::new(Lifecycle { name: name.into(), events: events.clone() });
+    let mut manager = StrategyManager::new();
+    manager.add_strategy(strategy("a")).unwrap();
+    manager.add_strategy(strategy("b")).unwrap();
+    // 시작에 실패한 전략은 등록되지 않음
+    assert!(manager.add_strategy(strategy("broken")).is_err());
+    assert_eq!(manager.list_strategies().len(), 2);
+    
+    manager.remove_strategy("a").unwrap();
+    assert_eq!(*events.lock().unwrap(), vec!["start a", "start b", "stop a"]);
+    
+    assert_eq!(manager.shutdown(), vec!["b".to_string()]);
+    assert_eq!(events.lock().unwrap().last().map(String::as_str), Some("stop b"));
+  }
+  
+  #[test]
+  fn test_live_parameter_update() {
+    let mut manager = StrategyManager::new();
+    let spec: StrategySpec = serde_json::from_value(serde_json::json!(
+      {"type": "rsi", "symbol": "BTCUSDT", "period": 14, "oversold": 30.0, "overbought": 70.0}
+    )).unwrap();
+    let name = manager.add_strategy_spec(spec).unwrap();
+    
+    // 임계치와 포지션 크기는 변경, 명세에는 명세에 있는 필드만 반영
+    manager.update_strategy_params(&name, serde_json::json!({"oversold": 25.0, "base_position_size": 0.5})).unwrap();
+    match manager.strategy_spec(&name) {
+      Some(StrategySpec::Rsi { oversold, period, .. }) => assert_eq!((*oversold, *period), (25.0, 14)),
+      other => panic!("unexpected spec {:?}", other),
+    }
+    
+    // 기간 변경, 잘못된 임계치, 없는 전략은 거부되고 기존 값 유지
+    assert!(manager.update_strategy_params(&name, serde_json::json!({"period": 7})).is_err());
+    assert!(manager.update_strategy_params(&name, serde_json::json!({"oversold": 80.0})).is_err());
+    assert!(matches!(manager.update_strategy_params("missing", serde_json::json!({"oversold": 20.0})), Err(TradingError::StrategyNotFound(_))));
+    assert!(matches!(manager.strategy_spec(&name), Some(StrategySpec::Rsi { oversold, .. }) if *oversold == 25.0));
+    
+    // 실행 상태: 기간이 차기 전에는 RSI 값 없음, 변경된 임계치 보고
+    manager.set_strategy_shadow(&name, true).unwrap();
+    assert!(manager.set_strategy_shadow("missing", true).is_err());
+    let status = manager.get_strategy_status(&name).unwrap();
+    assert!(status.active && status.shadow);
+    assert!(status.status["rsi"].is_null());
+    assert_eq!(status.status["oversold"], 25.0);
+    assert_eq!(manager.strategy_statuses(), vec![status]);
+  }
+}
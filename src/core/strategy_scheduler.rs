@@ -0,0 +1,289 @@
+//! 전략 실행 시간대 스케줄러
+//!
+//! 전략별로 실행 가능한 시간대와 정지 시간대(주말, 펀딩 시각 전후 등)를 cron 형식 표현식으로 정한다.
+//! 창 밖의 전략은 시세 업데이트와 주문 수집에서 제외되고, 창이 열리고 닫힐 때 런타임이 알림/주문 정리를 한다.
+//! 모든 시각은 UTC, 분 단위.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+
+use crate::config::StrategySchedule;
+use crate::error::TradingError;
+
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+/// cron 형식 표현식 (`분 시 일 월 요일`)
+///
+/// `*`, 값, 범위(`a-b`), 간격(`*/n`, `a-b/n`, `a/n`), 목록(`,`)과 요일/월 영문 약어를 지원한다.
+/// 요일은 0(또는 7)이 일요일. 일과 요일이 모두 지정되면 표준 cron 처럼 둘 중 하나만 맞아도 일치.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpr {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    day_restricted: bool,
+    weekday_restricted: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("cron expression '{}' needs 5 fields (minute hour day month weekday)", expr));
+        }
+        let field = |i: usize, min: u32, max: u32, names: &[&str]| {
+            parse_field(fields[i], min, max, names).map_err(|e| format!("cron expression '{}': {}", expr, e))
+        };
+        let mut weekdays = field(4, 0, 7, &WEEKDAYS)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+        Ok(CronExpr {
+            source: expr.to_string(),
+            minutes: field(0, 0, 59, &[])?,
+            hours: field(1, 0, 23, &[])?,
+            days: field(2, 1, 31, &[])?,
+            months: field(3, 1, 12, &MONTHS)?,
+            weekdays,
+            day_restricted: fields[2] != "*",
+            weekday_restricted: fields[4] != "*",
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn matches(&self, at: DateTime<Utc>) -> bool {
+        let has = |set: u64, value: u32| set & (1u64 << value) != 0;
+        if !has(self.minutes, at.minute()) || !has(self.hours, at.hour()) || !has(self.months, at.month()) {
+            return false;
+        }
+        let day = has(self.days, at.day());
+        let weekday = has(self.weekdays, at.weekday().num_days_from_sunday());
+        if self.day_restricted && self.weekday_restricted {
+            day || weekday
+        } else {
+            day && weekday
+        }
+    }
+}
+
+// 값 하나 - 숫자 또는 약어 (약어는 `min` 부터 번호)
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let parsed = match names.iter().position(|name| name.eq_ignore_ascii_case(value)) {
+        Some(index) => index as u32 + min,
+        None => value.parse().map_err(|_| format!("invalid value '{}'", value))?,
+    };
+    if parsed < min || parsed > max {
+        return Err(format!("value {} out of range {}-{}", parsed, min, max));
+    }
+    Ok(parsed)
+}
+
+// 필드 하나를 허용 값 비트 집합으로
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut set = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => match step.parse::<u32>() {
+                Ok(step) if step > 0 => (range, Some(step)),
+                _ => return Err(format!("invalid step '{}'", step)),
+            },
+            None => (part, None),
+        };
+        let (low, high) = if range == "*" {
+            (min, max)
+        } else if let Some((low, high)) = range.split_once('-') {
+            (parse_value(low, min, max, names)?, parse_value(high, min, max, names)?)
+        } else {
+            let value = parse_value(range, min, max, names)?;
+            (value, if step.is_some() { max } else { value })
+        };
+        if low > high {
+            return Err(format!("empty range '{}'", range));
+        }
+        for value in (low..=high).step_by(step.unwrap_or(1) as usize) {
+            set |= 1u64 << value;
+        }
+    }
+    Ok(set)
+}
+
+/// 전략 하나의 컴파일된 스케줄
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    active: Vec<CronExpr>,
+    blackout: Vec<CronExpr>,
+    funding_buffer_ms: i64,
+    funding_interval_ms: i64,
+}
+
+impl Schedule {
+    pub fn compile(config: &StrategySchedule) -> Result<Self, String> {
+        let parse_all = |exprs: &[String]| exprs.iter().map(|e| CronExpr::parse(e)).collect::<Result<Vec<_>, _>>();
+        if config.funding_buffer_minutes > 0 && config.funding_interval_hours == 0 {
+            return Err("schedule funding_interval_hours must be positive".to_string());
+        }
+        Ok(Schedule {
+            active: parse_all(&config.active)?,
+            blackout: parse_all(&config.blackout)?,
+            funding_buffer_ms: config.funding_buffer_minutes as i64 * 60_000,
+            funding_interval_ms: config.funding_interval_hours as i64 * 3_600_000,
+        })
+    }
+
+    /// 닫혀 있으면 사유, 실행 가능하면 None
+    pub fn closed_reason(&self, now_ms: i64) -> Option<String> {
+        let at = Utc.timestamp_millis_opt(now_ms).single()?;
+        if let Some(expr) = self.blackout.iter().find(|e| e.matches(at)) {
+            return Some(format!("blackout '{}'", expr.source()));
+        }
+        if !self.active.is_empty() && !self.active.iter().any(|e| e.matches(at)) {
+            return Some("outside active windows".to_string());
+        }
+        if self.funding_buffer_ms > 0 {
+            let offset = now_ms.rem_euclid(self.funding_interval_ms);
+            if offset.min(self.funding_interval_ms - offset) <= self.funding_buffer_ms {
+                return Some("funding timestamp".to_string());
+            }
+        }
+        None
+    }
+}
+
+/// 창이 열리거나 닫힌 전략
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleTransition {
+    pub strategy: String,
+    pub open: bool,
+    /// 닫힌 사유
+    pub reason: Option<String>,
+    /// 닫히면서 미체결 주문을 취소해야 하는지
+    pub cancel_orders: bool,
+}
+
+/// 전략별 스케줄 - 스케줄이 없는 전략은 항상 실행
+#[derive(Debug, Default)]
+pub struct StrategyScheduler {
+    schedules: HashMap<String, (StrategySchedule, Schedule)>,
+    // 마지막 점검에서 닫혀 있던 전략
+    closed: HashSet<String>,
+}
+
+impl StrategyScheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 스케줄 지정 (None 이면 해제)
+    pub fn set(&mut self, name: &str, config: Option<StrategySchedule>) -> Result<(), TradingError> {
+        match config {
+            Some(config) => {
+                let schedule = Schedule::compile(&config).map_err(TradingError::InvalidParameter)?;
+                self.schedules.insert(name.to_string(), (config, schedule));
+            }
+            None => {
+                self.schedules.remove(name);
+                self.closed.remove(name);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn config(&self, name: &str) -> Option<&StrategySchedule> {
+        self.schedules.get(name).map(|(config, _)| config)
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.schedules.remove(name);
+        self.closed.remove(name);
+    }
+
+    pub fn closed_reason(&self, name: &str, now_ms: i64) -> Option<String> {
+        self.schedules.get(name).and_then(|(_, schedule)| schedule.closed_reason(now_ms))
+    }
+
+    pub fn is_open(&self, name: &str, now_ms: i64) -> bool {
+        self.closed_reason(name, now_ms).is_none()
+    }
+
+    /// 창 상태 점검 - 지난 점검 이후 열리거나 닫힌 전략 반환 (이름순)
+    pub fn check(&mut self, now_ms: i64) -> Vec<ScheduleTransition> {
+        let mut transitions = Vec::new();
+        for (name, (config, schedule)) in &self.schedules {
+            let reason = schedule.closed_reason(now_ms);
+            let was_closed = self.closed.contains(name);
+            if reason.is_some() == was_closed {
+                continue;
+            }
+            if reason.is_some() {
+                self.closed.insert(name.clone());
+            } else {
+                self.closed.remove(name);
+            }
+            transitions.push(ScheduleTransition {
+                strategy: name.clone(),
+                open: reason.is_none(),
+                cancel_orders: reason.is_some() && config.cancel_on_close,
+                reason,
+            });
+        }
+        transitions.sort_by(|a, b| a.strategy.cmp(&b.strategy));
+        transitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> i64 {
+        DateTime::parse_from_rfc3339(s).unwrap().timestamp_millis()
+    }
+
+    fn schedule(active: &[&str], blackout: &[&str], funding_buffer_minutes: u32) -> StrategySchedule {
+        StrategySchedule {
+            active: active.iter().map(|s| s.to_string()).collect(),
+            blackout: blackout.iter().map(|s| s.to_string()).collect(),
+            funding_buffer_minutes,
+            funding_interval_hours: 8,
+            cancel_on_close: true,
+        }
+    }
+
+    #[test]
+    fn test_windows_and_transitions() {
+        let weekday_twap = Schedule::compile(&schedule(&["* 14 * * mon-fri"], &[], 0)).unwrap();
+        // 2024-01-05 은 금요일, 01-06 은 토요일
+        assert!(weekday_twap.closed_reason(at("2024-01-05T14:59:00Z")).is_none());
+        assert!(weekday_twap.closed_reason(at("2024-01-05T15:00:00Z")).is_some());
+        assert!(weekday_twap.closed_reason(at("2024-01-06T14:30:00Z")).is_some());
+
+        let no_weekend = Schedule::compile(&schedule(&[], &["* * * * sat,0"], 10)).unwrap();
+        assert_eq!(no_weekend.closed_reason(at("2024-01-07T12:00:00Z")), Some("blackout '* * * * sat,0'".to_string()));
+        assert_eq!(no_weekend.closed_reason(at("2024-01-05T07:55:00Z")), Some("funding timestamp".to_string()));
+        assert!(no_weekend.closed_reason(at("2024-01-05T08:11:00Z")).is_none());
+
+        assert!(CronExpr::parse("* 24 * * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+        assert!(CronExpr::parse("* * *").is_err());
+        assert!(CronExpr::parse("0,30 9-17/2 1 jan-jun 1").unwrap().matches(Utc.timestamp_millis_opt(at("2024-03-01T11:30:00Z")).unwrap()));
+
+        let mut scheduler = StrategyScheduler::new();
+        scheduler.set("twap", Some(schedule(&["* 14 * * *"], &[], 0))).unwrap();
+        assert!(scheduler.set("bad", Some(schedule(&["nope"], &[], 0))).is_err());
+        assert!(scheduler.is_open("unscheduled", at("2024-01-05T03:00:00Z")));
+        let closed = scheduler.check(at("2024-01-05T13:00:00Z"));
+        assert_eq!(closed.len(), 1);
+        assert!(!closed[0].open && closed[0].cancel_orders);
+        assert!(scheduler.check(at("2024-01-05T13:30:00Z")).is_empty());
+        let opened = scheduler.check(at("2024-01-05T14:00:00Z"));
+        assert!(opened[0].open && !opened[0].cancel_orders);
+    }
+}
@@ -0,0 +1,244 @@
+//! 전략 상태 저장소 모듈
+//!
+//! 전략 설정(StrategySpec)과 실행 진행 상태(실행 수량, 마지막 주문 시각 등)를 스냅샷으로 저장하여
+//! 재시작 후에도 전략을 이어서 실행할 수 있게 한다. JSON 파일과 SQLite 백엔드를 제공한다.
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ProtectiveStopConfig, StrategySchedule, StrategySpec};
+use crate::error::TradingError;
+use crate::signals::entry_exits::EntryExitsConfig;
+
+/// 저장 단위 - 전략 하나의 설정과 런타임 상태
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyRecord {
+    pub name: String,
+    pub spec: StrategySpec,
+    pub active: bool,
+    /// 전략별 런타임 상태 (Strategy::export_state)
+    #[serde(default)]
+    pub state: Option<serde_json::Value>,
+    /// 보호 청산 설정
+    #[serde(default)]
+    pub protection: Option<ProtectiveStopConfig>,
+    /// 진입 주문 손절/익절
+    #[serde(default)]
+    pub exits: Option<EntryExitsConfig>,
+    /// 실행 타임프레임 (없으면 틱 단위 업데이트)
+    #[serde(default)]
+    pub timeframe: Option<String>,
+    /// 주문 대상 거래소 (없으면 기본 거래소)
+    #[serde(default)]
+    pub exchange: Option<String>,
+    /// 섀도(드라이런) 모드 - 주문을 거래소로 보내지 않음
+    #[serde(default)]
+    pub shadow: bool,
+    /// 실행 시간대 (없으면 항상 실행)
+    #[serde(default)]
+    pub schedule: Option<StrategySchedule>,
+    pub updated_at: i64,
+}
+
+/// 전략 상태 저장소 인터페이스
+pub trait StrategyStateRepository: Send + Sync {
+    /// 저장된 전략 전체 로드
+    fn load_all(&self) -> Result<Vec<StrategyRecord>, TradingError>;
+
+    /// 현재 전략 전체 스냅샷 저장 (기존 내용 교체)
+    fn save_all(&mut self, records: &[StrategyRecord]) -> Result<(), TradingError>;
+}
+
+/// 메모리 기반 저장소 (테스트/백테스트용)
+#[derive(Debug, Default)]
+pub struct InMemoryStrategyStateRepository {
+    records: Vec<StrategyRecord>,
+}
+
+impl InMemoryStrategyStateRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StrategyStateRepository for InMemoryStrategyStateRepository {
+    fn load_all(&self) -> Result<Vec<StrategyRecord>, TradingError> {
+        Ok(self.records.clone())
+    }
+
+    fn save_all(&mut self, records: &[StrategyRecord]) -> Result<(), TradingError> {
+        self.records = records.to_vec();
+        Ok(())
+    }
+}
+
+/// JSON 파일 기반 저장소
+pub struct JsonFileStrategyStateRepository {
+    path: PathBuf,
+}
+
+impl JsonFileStrategyStateRepository {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        JsonFileStrategyStateRepository { path: path.into() }
+    }
+}
+
+impl StrategyStateRepository for JsonFileStrategyStateRepository {
+    /// 파일이 없으면 빈 목록 (최초 실행)
+    fn load_all(&self) -> Result<Vec<StrategyRecord>, TradingError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut contents = String::new();
+        File::open(&self.path)?.read_to_string(&mut contents)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// 임시 파일에 쓴 뒤 교체 (쓰기 도중 종료되어도 기존 스냅샷 보존)
+    fn save_all(&mut self, records: &[StrategyRecord]) -> Result<(), TradingError> {
+        let tmp = self.path.with_extension("tmp");
+        File::create(&tmp)?.write_all(serde_json::to_string_pretty(records)?.as_bytes())?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// 현재 SQLite 스키마 버전 (`PRAGMA user_version`)
+const SQLITE_SCHEMA_VERSION: i64 = 1;
+
+/// SQLite 기반 저장소 - 전략마다 한 행, 설정과 상태는 JSON 으로 저장
+pub struct SqliteStrategyStateRepository {
+    // Connection 은 Sync 가 아니므로 잠금으로 감쌈
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStrategyStateRepository {
+    /// DB 파일 열기 (없으면 생성) 후 스키마 마이그레이션
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TradingError> {
+        let conn = Connection::open(path.into())?;
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version > SQLITE_SCHEMA_VERSION {
+            return Err(TradingError::ConfigError(format!(
+                "Strategy store schema version {} is newer than supported {}", version, SQLITE_SCHEMA_VERSION
+            )));
+        }
+        if version < 1 {
+            conn.execute_batch(
+                "CREATE TABLE IF NOT EXISTS strategies (
+                    name TEXT PRIMARY KEY,
+                    active INTEGER NOT NULL,
+                    updated_at INTEGER NOT NULL,
+                    record TEXT NOT NULL
+                );
+                PRAGMA user_version = 1;",
+            )?;
+        }
+        Ok(SqliteStrategyStateRepository { conn: Mutex::new(conn) })
+    }
+}
+
+impl StrategyStateRepository for SqliteStrategyStateRepository {
+    fn load_all(&self) -> Result<Vec<StrategyRecord>, TradingError> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stmt = conn.prepare("SELECT record FROM strategies ORDER BY name")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut records = Vec::new();
+        for row in rows {
+            records.push(serde_json::from_str(&row?)?);
+        }
+        Ok(records)
+    }
+
+    /// 한 트랜잭션으로 교체 (쓰기 도중 종료되어도 기존 스냅샷 보존)
+    fn save_all(&mut self, records: &[StrategyRecord]) -> Result<(), TradingError> {
+        let conn = self.conn.get_mut().unwrap_or_else(|e| e.into_inner());
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM strategies", [])?;
+        {
+            let mut insert = tx.prepare("INSERT INTO strategies (name, active, updated_at, record) VALUES (?1, ?2, ?3, ?4)")?;
+            for record in records {
+                insert.execute(rusqlite::params![record.name, record.active, record.updated_at, serde_json::to_string(record)?])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::core::strategy_manager::StrategyManager;
+    use crate::models::market_data::MarketData;
+    use crate::models::order::OrderSide;
+    use crate::strategies::ExecutionMode;
+
+    #[test]
+    fn test_restore_strategy_progress() {
+        let path = std::env::temp_dir().join(format!("xquant_strategy_store_{}.json", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let spec = StrategySpec::Twap {
+            symbol: "BTCUSDT".to_string(),
+            side: OrderSide::Buy,
+            quantity: 1.0,
+            execution_interval_ms: 60_000,
+            slices: 4,
+            execution_mode: ExecutionMode::ClientSide,
+        };
+        let mut manager = StrategyManager::new();
+        manager.set_state_store(Box::new(JsonFileStrategyStateRepository::new(&path)));
+        let name = manager.add_strategy_spec(spec).unwrap();
+        manager.update_all(&MarketData {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: 1_000.into(),
+            open: dec!(100),
+            high: dec!(100),
+            low: dec!(100),
+            close: dec!(100),
+            volume: dec!(1),
+        }).unwrap();
+        assert_eq!(manager.get_all_orders().unwrap().len(), 1);
+
+        // 재시작: 같은 파일에서 설정과 진행 상태 복원
+        let mut restarted = StrategyManager::new();
+        restarted.set_state_store(Box::new(JsonFileStrategyStateRepository::new(&path)));
+        assert_eq!(restarted.restore_strategies().unwrap(), 1);
+        let records = JsonFileStrategyStateRepository::new(&path).load_all().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(records[0].name, name);
+        assert_eq!(records[0].state.as_ref().unwrap()["executed_quantity"], "0.25");
+        assert_eq!(records[0].state.as_ref().unwrap()["last_order_time"], 1_000);
+        assert_eq!(restarted.list_strategies(), vec![(name, true)]);
+    }
+
+    #[test]
+    fn test_sqlite_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("xquant_strategy_store_{}.db", uuid::Uuid::new_v4()));
+        let spec = StrategySpec::MultiIndicator { symbol: "BTCUSDT".into(), aggregation: None };
+        let mut manager = StrategyManager::new();
+        manager.set_state_store(Box::new(SqliteStrategyStateRepository::open(&path).unwrap()));
+        let name = manager.add_strategy_spec(spec).unwrap();
+        manager.set_strategy_active(&name, false).unwrap();
+
+        // 재시작: 스키마를 다시 만들지 않고 저장된 전략 복원
+        let mut restarted = StrategyManager::new();
+        restarted.set_state_store(Box::new(SqliteStrategyStateRepository::open(&path).unwrap()));
+        assert_eq!(restarted.restore_strategies().unwrap(), 1);
+        assert_eq!(restarted.list_strategies(), vec![(name, false)]);
+
+        // 빈 스냅샷 저장은 기존 행을 모두 지움
+        let mut store = SqliteStrategyStateRepository::open(&path).unwrap();
+        store.save_all(&[]).unwrap();
+        assert!(store.load_all().unwrap().is_empty());
+        drop(store);
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,216 @@
+//! 전략 워치독 모듈
+//!
+//! 전략별 평소 업데이트/주문 주기를 학습(EWMA)하고, 업데이트나 주문이 비정상적으로 오래 끊기거나
+//! 평소보다 훨씬 많은 주문이 몰리면 경보를 발생시킨다. 내부 태스크 패닉이나 끊어진 데이터 구독처럼
+//! 조용히 실패하는 상황을 드러내기 위한 용도이다.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+
+use crate::config::WatchdogConfig;
+
+/// 경보 종류
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchdogAlertKind {
+    /// 업데이트가 평소 주기보다 오래 없음
+    UpdateStalled,
+    /// 주문이 평소 주기보다 오래 없음
+    OrderSilence,
+    /// 짧은 시간에 평소보다 훨씬 많은 주문
+    OrderBurst,
+}
+
+/// 워치독 경보
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchdogAlert {
+    pub strategy: String,
+    pub kind: WatchdogAlertKind,
+    pub detail: String,
+    pub timestamp: i64,
+}
+
+/// 이벤트 주기 추적 (EWMA 간격)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CadenceStats {
+    pub last_at: Option<i64>,
+    pub mean_interval_ms: Option<f64>,
+    pub samples: usize,
+}
+
+impl CadenceStats {
+    fn record(&mut self, now: i64, alpha: f64) {
+        if let Some(last) = self.last_at {
+            let interval = (now - last).max(0) as f64;
+            self.mean_interval_ms = Some(match self.mean_interval_ms {
+                Some(mean) => alpha * interval + (1.0 - alpha) * mean,
+                None => interval,
+            });
+            self.samples += 1;
+        }
+        self.last_at = Some(now);
+    }
+
+    /// 학습된 주기의 factor 배 (최소 min_ms) 이상 이벤트가 없으면 경과 시간 반환
+    fn overdue(&self, now: i64, factor: f64, min_ms: i64, min_samples: usize) -> Option<i64> {
+        if self.samples < min_samples {
+            return None;
+        }
+        let (last, mean) = (self.last_at?, self.mean_interval_ms?);
+        let elapsed = now - last;
+        let limit = (mean * factor).max(min_ms as f64);
+        (elapsed as f64 > limit).then_some(elapsed)
+    }
+}
+
+/// 전략별 워치독 상태
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WatchdogState {
+    pub updates: CadenceStats,
+    pub orders: CadenceStats,
+    #[serde(skip)]
+    recent_orders: VecDeque<i64>,
+    /// 현재 발생 중인 경보 (복구될 때까지 중복 발행하지 않음)
+    pub active_alerts: Vec<WatchdogAlertKind>,
+}
+
+/// 전략 워치독
+pub struct StrategyWatchdog {
+    config: WatchdogConfig,
+    states: HashMap<String, WatchdogState>,
+}
+
+impl StrategyWatchdog {
+    pub fn new(config: WatchdogConfig) -> Self {
+        StrategyWatchdog {
+            config,
+            states: HashMap::new(),
+        }
+    }
+
+    pub fn set_config(&mut self, config: WatchdogConfig) {
+        self.config = config;
+    }
+
+    /// 전략 업데이트 성공 기록
+    pub fn record_update(&mut self, strategy: &str, now: i64) {
+        let alpha = self.config.ewma_alpha;
+        let state = self.states.entry(strategy.to_string()).or_default();
+        state.updates.record(now, alpha);
+        state.active_alerts.retain(|k| *k != WatchdogAlertKind::UpdateStalled);
+    }
+
+    /// 전략 주문 생성 기록
+    pub fn record_orders(&mut self, strategy: &str, count: usize, now: i64) {
+        if count == 0 {
+            return;
+        }
+        let alpha = self.config.ewma_alpha;
+        let window = self.config.burst_window_ms;
+        let state = self.states.entry(strategy.to_string()).or_default();
+        state.orders.record(now, alpha);
+        state.active_alerts.retain(|k| *k != WatchdogAlertKind::OrderSilence);
+        for _ in 0..count {
+            state.recent_orders.push_back(now);
+        }
+        while state.recent_orders.front().is_some_and(|t| now - *t > window) {
+            state.recent_orders.pop_front();
+        }
+    }
+
+    /// 전체 전략 점검 - 새로 발생한 경보 반환
+    pub fn check(&mut self, now: i64) -> Vec<WatchdogAlert> {
+        let cfg = &self.config;
+        let mut alerts = Vec::new();
+
+        for (name, state) in self.states.iter_mut() {
+            let mut raise = |kind: WatchdogAlertKind, detail: String, state: &mut WatchdogState| {
+                if !state.active_alerts.contains(&kind) {
+                    state.active_alerts.push(kind);
+                    alerts.push(WatchdogAlert { strategy: name.clone(), kind, detail, timestamp: now });
+                }
+            };
+
+            if let Some(elapsed) = state.updates.overdue(now, cfg.stall_factor, cfg.min_silence_ms, cfg.min_samples) {
+                raise(WatchdogAlertKind::UpdateStalled, format!("no updates for {}s", elapsed / 1000), state);
+            }
+            if let Some(elapsed) = state.orders.overdue(now, cfg.stall_factor, cfg.min_silence_ms, cfg.min_samples) {
+                raise(WatchdogAlertKind::OrderSilence, format!("no orders for {}s", elapsed / 1000), state);
+            }
+
+            // 최근 구간 주문 수가 학습된 주기 기준 기대치의 burst_factor 배를 넘으면 경보
+            while state.recent_orders.front().is_some_and(|t| now - *t > cfg.burst_window_ms) {
+                state.recent_orders.pop_front();
+            }
+            if state.orders.samples >= cfg.min_samples {
+                if let Some(mean) = state.orders.mean_interval_ms.filter(|m| *m > 0.0) {
+                    let expected = (cfg.burst_window_ms as f64 / mean).max(1.0);
+                    let recent = state.recent_orders.len() as f64;
+                    if recent > expected * cfg.burst_factor {
+                        raise(WatchdogAlertKind::OrderBurst, format!("{} orders in {}s (expected ~{:.1})", recent, cfg.burst_window_ms / 1000, expected), state);
+                    } else {
+                        state.active_alerts.retain(|k| *k != WatchdogAlertKind::OrderBurst);
+                    }
+                }
+            }
+        }
+
+        alerts
+    }
+
+    pub fn state(&self, strategy: &str) -> Option<&WatchdogState> {
+        self.states.get(strategy)
+    }
+
+    /// 전략 제거 또는 비활성화 시 학습 상태 정리
+    pub fn remove(&mut self, strategy: &str) {
+        self.states.remove(strategy);
+    }
+}
+
+impl Default for StrategyWatchdog {
+    fn default() -> Self {
+        Self::new(WatchdogConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watchdog_stall_and_burst() {
+        let mut watchdog = StrategyWatchdog::new(WatchdogConfig {
+            stall_factor: 5.0,
+            min_silence_ms: 0,
+            burst_factor: 3.0,
+            burst_window_ms: 10_000,
+            min_samples: 5,
+            ewma_alpha: 0.2,
+            check_interval_ms: 1_000,
+        });
+
+        // 1초 간격 업데이트, 10초 간격 주문 학습
+        for i in 0..=100 {
+            watchdog.record_update("s1", i * 1_000);
+            if i % 10 == 0 {
+                watchdog.record_orders("s1", 1, i * 1_000);
+            }
+        }
+        assert!(watchdog.check(100_500).is_empty());
+
+        // 업데이트 중단 -> 경보 1회만
+        let alerts = watchdog.check(110_000);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, WatchdogAlertKind::UpdateStalled);
+        assert!(watchdog.check(111_000).is_empty());
+        watchdog.record_update("s1", 112_000);
+        assert!(watchdog.state("s1").unwrap().active_alerts.is_empty());
+
+        // 주문 폭주
+        watchdog.record_orders("s1", 20, 113_000);
+        let alerts = watchdog.check(113_000);
+        assert!(alerts.iter().any(|a| a.kind == WatchdogAlertKind::OrderBurst));
+    }
+}
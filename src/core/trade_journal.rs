@@ -0,0 +1,349 @@
+//! 체결 기록(트레이드 저널)
+//!
+//! 모든 체결을 전략 이름, 신호 정보, 결정 가격 대비 슬리피지, 수수료(메이커/테이커 구분)와 함께 기록한다.
+//! 주문 제출 전에 클라이언트 주문 ID 로 주문 맥락(전략/신호/결정 가격)을 등록해 두면
+//! 주문 이벤트의 체결과 연결된다. 파일 경로가 있으면 JSON Lines 로 추가 기록하고 시작 시 다시 읽는다.
+//! `GET /trades/export` 와 `ExecutionAnalyzer::load_journal` 에서 사용.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::error::TradingError;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::fee::{FeeAttribution, Liquidity};
+use crate::models::order::{OrderSide, OrderStatus, OrderUpdate};
+use crate::order_core::manager::OrderManager;
+use crate::order_core::reconcile::FileFormat;
+
+/// 주문 제출 시점의 맥락 - 체결 기록에 그대로 옮겨짐
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OrderContext {
+    pub strategy: Option<String>,
+    /// 주문을 낸 신호 (예: "strategy", "protective_exit")
+    pub signal: Option<String>,
+    /// 주문 결정 시점 가격 (슬리피지 기준)
+    pub decision_price: Option<f64>,
+}
+
+/// 체결 한 건
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TradeRecord {
+    pub id: String,
+    pub order_id: String,
+    pub client_order_id: Option<String>,
+    pub strategy: Option<String>,
+    pub signal: Option<String>,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub fee: Decimal,
+    pub fee_asset: Option<String>,
+    /// 메이커/테이커 (거래소가 알려준 경우)
+    #[serde(default)]
+    pub liquidity: Option<Liquidity>,
+    /// 체결 금액 대비 수수료 (bps, 호가 통화 수수료일 때만)
+    #[serde(default)]
+    pub fee_bps: Option<f64>,
+    pub decision_price: Option<f64>,
+    /// 결정 가격 대비 슬리피지 (bps, 불리한 방향이 양수)
+    pub slippage_bps: Option<f64>,
+    pub timestamp: i64,
+}
+
+impl TradeRecord {
+    pub fn notional(&self) -> Decimal {
+        self.price * self.quantity
+    }
+
+    /// 수수료가 호가 통화로 부과됐는지 (자산 미표기는 호가 통화로 간주)
+    pub fn fee_in_quote(&self) -> bool {
+        self.fee_asset.as_deref().is_none_or(|asset| self.symbol.ends_with(asset))
+    }
+}
+
+/// 결정 가격 대비 체결가 슬리피지 (bps, 매수는 비싸게, 매도는 싸게 체결되면 양수)
+pub fn slippage_bps(side: &OrderSide, decision_price: f64, fill_price: f64) -> Option<f64> {
+    if decision_price <= 0.0 || fill_price <= 0.0 {
+        return None;
+    }
+    let diff = (fill_price - decision_price) / decision_price * 10_000.0;
+    Some(match side {
+        OrderSide::Buy => diff,
+        OrderSide::Sell => -diff,
+    })
+}
+
+/// 조회/내보내기 필터
+#[derive(Debug, Clone, Default)]
+pub struct TradeFilter {
+    pub strategy: Option<String>,
+    pub symbol: Option<String>,
+    /// 이 시각(ms) 이후 체결만
+    pub since: Option<i64>,
+}
+
+impl TradeFilter {
+    pub fn matches(&self, record: &TradeRecord) -> bool {
+        self.strategy.as_ref().is_none_or(|s| record.strategy.as_ref() == Some(s))
+            && self.symbol.as_ref().is_none_or(|s| &record.symbol == s)
+            && self.since.is_none_or(|since| record.timestamp >= since)
+    }
+}
+
+/// 체결 기록 내보내기 (CSV 는 한 줄에 한 체결)
+pub fn export_trades<W: Write>(records: &[TradeRecord], format: FileFormat, writer: W) -> Result<(), TradingError> {
+    match format {
+        FileFormat::Json => serde_json::to_writer_pretty(writer, records)?,
+        FileFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(writer);
+            for record in records {
+                wtr.serialize(record).map_err(|e| TradingError::ParseError(e.to_string()))?;
+            }
+            wtr.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct JournalState {
+    records: Vec<TradeRecord>,
+    /// 클라이언트 주문 ID(없으면 주문 ID) -> 주문 맥락
+    contexts: HashMap<String, OrderContext>,
+    /// 주문별 기록한 누적 체결 수량 (같은 체결의 중복 이벤트 제거)
+    filled: HashMap<String, Decimal>,
+}
+
+/// 트레이드 저널 - 주문 관리자, 감시 태스크, HTTP 핸들러가 같은 핸들을 공유
+#[derive(Clone, Default)]
+pub struct TradeJournal {
+    state: Arc<Mutex<JournalState>>,
+    path: Option<PathBuf>,
+}
+
+impl TradeJournal {
+    /// 메모리 전용 저널
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 파일 저널 열기 - 기존 기록을 읽고 이후 체결을 추가 기록
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TradingError> {
+        let path = path.into();
+        let records = if path.exists() { Self::read_file(&path)? } else { Vec::new() };
+        let mut state = JournalState::default();
+        for record in &records {
+            *state.filled.entry(record.order_id.clone()).or_default() += record.quantity;
+        }
+        log::info!("trade journal {}: {} fills loaded", path.display(), records.len());
+        state.records = records;
+        Ok(TradeJournal { state: Arc::new(Mutex::new(state)), path: Some(path) })
+    }
+
+    /// 저널 파일 읽기 - 기록 도중 종료로 잘린 줄은 건너뜀
+    pub fn read_file(path: &Path) -> Result<Vec<TradeRecord>, TradingError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut records = Vec::new();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<TradeRecord>(&line) {
+                Ok(record) => records.push(record),
+                Err(e) => log::warn!("trade journal line {} skipped: {}", i + 1, e),
+            }
+        }
+        Ok(records)
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, JournalState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 주문 맥락 등록 - 이미 등록된 값은 유지하고 비어 있는 항목만 채움
+    /// (전략 런타임이 신호와 함께 먼저 등록하고, 주문 관리자가 나머지를 보완)
+    pub fn register(&self, client_order_id: &str, context: OrderContext) {
+        let mut state = self.state();
+        let entry = state.contexts.entry(client_order_id.to_string()).or_default();
+        if entry.strategy.is_none() {
+            entry.strategy = context.strategy;
+        }
+        if entry.signal.is_none() {
+            entry.signal = context.signal;
+        }
+        if entry.decision_price.is_none() {
+            entry.decision_price = context.decision_price.filter(|p| *p > 0.0);
+        }
+    }
+
+    /// 주문 이벤트 반영 - 새 체결이면 기록 후 반환
+    pub fn record_update(&self, update: &OrderUpdate) -> Result<Option<TradeRecord>, TradingError> {
+        let key = update.client_order_id.clone().unwrap_or_else(|| update.order_id.0.clone());
+        let record = {
+            let mut state = self.state();
+            let record = Self::build_record(&mut state, &key, update);
+            if let Some(record) = &record {
+                state.records.push(record.clone());
+            }
+            // 종료된 주문의 맥락은 더 쓰이지 않음
+            if matches!(update.status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected | OrderStatus::Expired) {
+                state.contexts.remove(&key);
+            }
+            record
+        };
+        if let (Some(record), Some(path)) = (&record, &self.path) {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        Ok(record)
+    }
+
+    fn build_record(state: &mut JournalState, key: &str, update: &OrderUpdate) -> Option<TradeRecord> {
+        if !update.is_fill() || update.last_fill_price <= Decimal::ZERO {
+            return None;
+        }
+        // 누적 수량이 있으면 이미 기록한 부분을 빼고, 없으면 이벤트의 체결 수량 그대로 사용
+        let seen = state.filled.get(&update.order_id.0).copied().unwrap_or_default();
+        let quantity = if update.cumulative_quantity > Decimal::ZERO {
+            (update.cumulative_quantity - seen).min(update.last_fill_quantity)
+        } else {
+            update.last_fill_quantity
+        };
+        if quantity <= Decimal::ZERO {
+            return None;
+        }
+        state.filled.insert(update.order_id.0.clone(), seen + quantity);
+
+        let context = state.contexts.get(key).cloned().unwrap_or_default();
+        let slippage = context.decision_price.and_then(|p| slippage_bps(&update.side, p, to_f64(update.last_fill_price)));
+        let notional = update.last_fill_price * quantity;
+        let fee_in_quote = update.fee_asset.as_deref().is_none_or(|asset| update.symbol.ends_with(asset));
+        Some(TradeRecord {
+            id: format!("trade-{}", Uuid::new_v4()),
+            order_id: update.order_id.0.clone(),
+            client_order_id: update.client_order_id.clone(),
+            strategy: context.strategy,
+            signal: context.signal,
+            symbol: update.symbol.clone(),
+            side: update.side.clone(),
+            price: update.last_fill_price,
+            quantity,
+            fee: update.fee,
+            fee_asset: update.fee_asset.clone(),
+            liquidity: update.liquidity,
+            fee_bps: (fee_in_quote && notional > Decimal::ZERO).then(|| to_f64(update.fee / notional) * 10_000.0),
+            decision_price: context.decision_price,
+            slippage_bps: slippage,
+            timestamp: update.timestamp,
+        })
+    }
+
+    /// 조건에 맞는 체결 (시간순)
+    pub fn records(&self, filter: &TradeFilter) -> Vec<TradeRecord> {
+        self.state().records.iter().filter(|r| filter.matches(r)).cloned().collect()
+    }
+
+    /// 조건에 맞는 체결의 메이커/테이커별 수수료 (호가 통화 수수료만 집계)
+    pub fn fee_attribution(&self, filter: &TradeFilter) -> FeeAttribution {
+        let mut attribution = FeeAttribution::default();
+        for record in self.state().records.iter().filter(|r| filter.matches(r) && r.fee_in_quote()) {
+            attribution.add(record.liquidity, to_f64(record.notional()), to_f64(record.fee));
+        }
+        attribution
+    }
+
+    pub fn len(&self) -> usize {
+        self.state().records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 감시 태스크 시작 - 주문 이벤트(사용자 데이터 스트림 + 상태 폴링)의 체결 기록
+    pub fn start(&self, order_manager: Arc<RwLock<OrderManager>>) -> tokio::task::JoinHandle<()> {
+        let journal = self.clone();
+        tokio::spawn(async move {
+            let mut events = order_manager.read().await.subscribe_to_order_events();
+            loop {
+                match events.recv().await {
+                    Ok(event) => match journal.record_update(&event.update) {
+                        Ok(Some(record)) => log::debug!("journal fill: {} {:?} {} @ {} ({:?} bps)", record.symbol, record.side, record.quantity, record.price, record.slippage_bps),
+                        Ok(None) => {}
+                        Err(e) => log::warn!("trade journal write failed: {}", e),
+                    },
+                    Err(RecvError::Lagged(n)) => log::warn!("trade journal lagged {} order updates, fills may be missing", n),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::models::order::OrderId;
+
+    fn fill(order_id: &str, client_id: &str, side: OrderSide, price: f64, qty: f64, cumulative: f64) -> OrderUpdate {
+        OrderUpdate {
+            order_id: OrderId(order_id.into()),
+            client_order_id: Some(client_id.into()),
+            symbol: "BTCUSDT".into(),
+            side,
+            status: OrderStatus::PartiallyFilled,
+            last_fill_price: to_decimal(price),
+            last_fill_quantity: to_decimal(qty),
+            cumulative_quantity: to_decimal(cumulative),
+            fee: dec!(0.5),
+            fee_asset: Some("USDT".into()),
+            liquidity: Some(Liquidity::Taker),
+            timestamp: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_journal_records_slippage_and_persists() {
+        let path = std::env::temp_dir().join(format!("xquant-journal-{}.jsonl", Uuid::new_v4()));
+        let journal = TradeJournal::open(&path).unwrap();
+        journal.register("c1", OrderContext { strategy: None, signal: Some("strategy".into()), decision_price: Some(50_000.0) });
+        // 주문 관리자의 보완 등록은 기존 값을 덮지 않음
+        journal.register("c1", OrderContext { strategy: Some("ma".into()), signal: None, decision_price: Some(49_000.0) });
+
+        let first = journal.record_update(&fill("1", "c1", OrderSide::Buy, 50_050.0, 0.1, 0.1)).unwrap().unwrap();
+        assert_eq!(first.strategy.as_deref(), Some("ma"));
+        assert_eq!(first.signal.as_deref(), Some("strategy"));
+        assert!((first.slippage_bps.unwrap() - 10.0).abs() < 1e-9);
+        // 같은 누적 수량의 중복 이벤트는 무시
+        assert!(journal.record_update(&fill("1", "c1", OrderSide::Buy, 50_050.0, 0.1, 0.1)).unwrap().is_none());
+        journal.record_update(&fill("2", "c2", OrderSide::Sell, 49_900.0, 0.2, 0.2)).unwrap();
+
+        let reopened = TradeJournal::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        let all = reopened.records(&TradeFilter { symbol: Some("BTCUSDT".into()), ..TradeFilter::default() });
+        assert_eq!(all[1].slippage_bps, None);
+        assert_eq!(all[0].liquidity, Some(Liquidity::Taker));
+        // 0.5 USDT / 5,005 USDT
+        assert!((all[0].fee_bps.unwrap() - 0.5 / 5_005.0 * 10_000.0).abs() < 1e-9);
+        let fees = reopened.fee_attribution(&TradeFilter::default());
+        assert_eq!((fees.fills, fees.taker_fees, fees.maker_fees), (2, 1.0, 0.0));
+
+        let mut csv = Vec::new();
+        export_trades(&reopened.records(&TradeFilter { strategy: Some("ma".into()), ..TradeFilter::default() }), FileFormat::Csv, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.starts_with("id,order_id,client_order_id,strategy,signal"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
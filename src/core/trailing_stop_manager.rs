@@ -11,6 +11,7 @@ use tokio::time::{interval, Duration};
 
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
 
 /// Trailing Stop 주문 관리자
@@ -22,9 +23,9 @@ pub struct TrailingStopManager {
   /// 주문 방향 (매수/매도)
   side: OrderSide,
   /// 주문 수량
-  quantity: f64,
+  quantity: Decimal,
   /// 활성화 가격 (선택사항)
-  activation_price: Option<f64>,
+  activation_price: Option<Decimal>,
   /// 트레일링 간격 (백분율)
   trailing_delta: f64,
   /// 실행 여부
@@ -32,9 +33,9 @@ pub struct TrailingStopManager {
   /// 활성 여부
   is_active: bool,
   /// 최고 가격 (매수 추적용)
-  highest_price: f64,
+  highest_price: Decimal,
   /// 최저 가격 (매도 추적용)
-  lowest_price: f64,
+  lowest_price: Decimal,
   /// 스탑 주문 ID
   stop_order_id: Option<OrderId>,
 }
@@ -45,9 +46,9 @@ impl TrailingStopManager {
     exchange: Arc<RwLock<dyn Exchange>>,
     symbol: impl Into<String>,
     side: OrderSide,
-    quantity: f64,
+    quantity: Decimal,
     trailing_delta: f64,
-    activation_price: Option<f64>,
+    activation_price: Option<Decimal>,
   ) -> Self {
     TrailingStopManager {
       exchange,
@@ -58,8 +59,8 @@ impl TrailingStopManager {
       trailing_delta,
       executed: false,
       is_active: false,
-      highest_price: 0.0,
-      lowest_price: f64::MAX,
+      highest_price: Decimal::ZERO,
+      lowest_price: Decimal::MAX,
       stop_order_id: None,
     }
   }
@@ -139,12 +140,12 @@ impl TrailingStopManager {
           let stop_price = match side_clone {
             OrderSide::Buy => {
               // 매수 트레일링 스탑: 최고가에서 델타% 하락 시 트리거
-              let delta_amount = *highest_price.read().await * (trailing_delta / 100.0);
+              let delta_amount = *highest_price.read().await * to_decimal(trailing_delta / 100.0);
               *highest_price.read().await - delta_amount
             },
             OrderSide::Sell => {
               // 매도 트레일링 스탑: 최저가에서 델타% 상승 시 트리거
-              let delta_amount = *lowest_price.read().await * (trailing_delta / 100.0);
+              let delta_amount = *lowest_price.read().await * to_decimal(trailing_delta / 100.0);
               *lowest_price.read().await + delta_amount
             }
           };
@@ -203,14 +204,14 @@ impl TrailingStopManager {
   }
   
   /// Trailing Stop 현재 상태 조회
-  pub fn status(&self) -> (bool, bool, f64, f64) {
+  pub fn status(&self) -> (bool, bool, Decimal, Decimal) {
     let current_trigger_price = match self.side {
       OrderSide::Buy => {
-        let delta_amount = self.highest_price * (self.trailing_delta / 100.0);
+        let delta_amount = self.highest_price * to_decimal(self.trailing_delta / 100.0);
         self.highest_price - delta_amount
       },
       OrderSide::Sell => {
-        let delta_amount = self.lowest_price * (self.trailing_delta / 100.0);
+        let delta_amount = self.lowest_price * to_decimal(self.trailing_delta / 100.0);
         self.lowest_price + delta_amount
       }
     };
@@ -234,6 +235,7 @@ mod tests {
   use super::*;
   use crate::exchange::mocks::MockExchange;
   use crate::config::Config;
+  use crate::models::decimal::dec;
   
   #[tokio::test]
   async fn test_trailing_stop() {
@@ -246,7 +248,7 @@ mod tests {
       exchange.clone(),
       "BTCUSDT",
       OrderSide::Sell,
-      0.1,
+      dec!(0.1),
       2.0,  // 2% 트레일링 델타
       None, // 활성화 가격 없음 (즉시 활성화)
     );
@@ -259,8 +261,8 @@ mod tests {
     let (is_active, executed, trigger_price, quantity) = trailing_stop.status();
     assert!(is_active);
     assert!(!executed);
-    assert!(trigger_price > 0.0);
-    assert_eq!(quantity, 0.1);
+    assert!(trigger_price > Decimal::ZERO);
+    assert_eq!(quantity, dec!(0.1));
     
     // 중지
     let stop_result = trailing_stop.stop().await;
@@ -11,6 +11,7 @@ use tokio::time::{interval, Duration};
 
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
 use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
 
 /// TWAP 기반 주문 분할기
@@ -22,13 +23,13 @@ pub struct TwapSplitter {
   /// 주문 방향 (매수/매도)
   side: OrderSide,
   /// 총 주문 수량
-  total_quantity: f64,
+  total_quantity: Decimal,
   /// 실행 간격 (밀리초)
   execution_interval: i64,
   /// 분할 수
   num_slices: usize,
   /// 이미 실행한 수량
-  executed_quantity: f64,
+  executed_quantity: Decimal,
   /// 실행 중 여부
   is_active: bool,
   /// 생성된 하위 주문 ID 목록
@@ -41,7 +42,7 @@ impl TwapSplitter {
     exchange: Arc<RwLock<dyn Exchange>>,
     symbol: impl Into<String>,
     side: OrderSide,
-    total_quantity: f64,
+    total_quantity: Decimal,
     execution_interval: i64,
     num_slices: usize,
   ) -> Self {
@@ -52,7 +53,7 @@ impl TwapSplitter {
       total_quantity,
       execution_interval,
       num_slices,
-      executed_quantity: 0.0,
+      executed_quantity: Decimal::ZERO,
       is_active: false,
       child_orders: Vec::new(),
     }
@@ -65,11 +66,11 @@ impl TwapSplitter {
     }
     
     self.is_active = true;
-    self.executed_quantity = 0.0;
+    self.executed_quantity = Decimal::ZERO;
     self.child_orders.clear();
     
     // 분할 크기 계산
-    let slice_quantity = self.total_quantity / self.num_slices as f64;
+    let slice_quantity = self.total_quantity / Decimal::from(self.num_slices);
     let time_between_slices = self.execution_interval / self.num_slices as i64;
     
     // 분할 실행을 위한 타이머 생성
@@ -91,7 +92,7 @@ impl TwapSplitter {
         slice_quantity.min(remaining_quantity)
       };
       
-      if adjusted_quantity > 0.0 {
+      if adjusted_quantity > Decimal::ZERO {
         // 하위 주문 생성 및 제출
         let order_result = self.create_child_order(adjusted_quantity).await;
         
@@ -108,7 +109,7 @@ impl TwapSplitter {
       }
       
       // 총 수량 완료 여부 확인
-      if remaining_quantity <= 0.0 {
+      if remaining_quantity <= Decimal::ZERO {
         break;
       }
     }
@@ -139,12 +140,12 @@ impl TwapSplitter {
   }
   
   /// TWAP 실행 상태 조회
-  pub fn status(&self) -> (bool, f64, f64) {
+  pub fn status(&self) -> (bool, Decimal, Decimal) {
     (self.is_active, self.executed_quantity, self.total_quantity)
   }
   
   /// 하위 주문 생성
-  async fn create_child_order(&self, quantity: f64) -> Result<OrderId, TradingError> {
+  async fn create_child_order(&self, quantity: Decimal) -> Result<OrderId, TradingError> {
     let mut exchange = self.exchange.write().await;
     
     // 현재 시장 데이터로 가격 조회
@@ -168,6 +169,7 @@ mod tests {
   use super::*;
   use crate::exchange::mocks::MockExchange;
   use crate::config::Config;
+  use crate::models::decimal::dec;
   
   #[tokio::test]
   async fn test_twap_splitter() {
@@ -180,7 +182,7 @@ mod tests {
       exchange.clone(),
       "BTCUSDT",
       OrderSide::Buy,
-      1.0,
+      dec!(1),
       3600000,  // 1시간 (밀리초)
       5,        // 5개 분할
     );
@@ -192,8 +194,8 @@ mod tests {
     // 상태 확인
     let (is_active, executed, total) = twap.status();
     assert!(!is_active); // 테스트에서는 즉시 완료됨
-    assert!(executed > 0.0);
-    assert_eq!(total, 1.0);
+    assert!(executed > Decimal::ZERO);
+    assert_eq!(total, dec!(1));
     
     // 하위 주문 수 확인
     assert!(twap.child_orders.len() <= 5);
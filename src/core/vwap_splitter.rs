@@ -8,6 +8,7 @@ use tokio::time::{interval, Duration};
 
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
 use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
 
@@ -20,13 +21,13 @@ pub struct VwapSplitter {
   /// 주문 방향 (매수/매도)
   side: OrderSide,
   /// 총 주문 수량
-  total_quantity: f64,
+  total_quantity: Decimal,
   /// 실행 간격 (밀리초)
   execution_interval: i64,
   /// 목표 거래량 비율 (%)
   target_percentage: Option<f64>,
   /// 이미 실행한 수량
-  executed_quantity: f64,
+  executed_quantity: Decimal,
   /// 실행 중 여부
   is_active: bool,
   /// 생성된 하위 주문 ID 목록
@@ -39,7 +40,7 @@ impl VwapSplitter {
     exchange: Arc<RwLock<dyn Exchange>>,
     symbol: impl Into<String>,
     side: OrderSide,
-    total_quantity: f64,
+    total_quantity: Decimal,
     execution_interval: i64,
     target_percentage: Option<f64>,
   ) -> Self {
@@ -50,7 +51,7 @@ impl VwapSplitter {
       total_quantity,
       execution_interval,
       target_percentage,
-      executed_quantity: 0.0,
+      executed_quantity: Decimal::ZERO,
       is_active: false,
       child_orders: Vec::new(),
     }
@@ -63,7 +64,7 @@ impl VwapSplitter {
     }
     
     self.is_active = true;
-    self.executed_quantity = 0.0;
+    self.executed_quantity = Decimal::ZERO;
     self.child_orders.clear();
     
     // 시간 구간 계산
@@ -95,7 +96,7 @@ impl VwapSplitter {
       
       // 거래량 프로필 기반으로 분할 수량 계산
       let volume_ratio = volume_profile[i];
-      let slice_quantity = self.total_quantity * volume_ratio;
+      let slice_quantity = self.total_quantity * to_decimal(volume_ratio);
       
       // 남은 수량 기준으로 조정
       let adjusted_quantity = if i == num_slices - 1 {
@@ -104,7 +105,7 @@ impl VwapSplitter {
         slice_quantity.min(remaining_quantity)
       };
       
-      if adjusted_quantity > 0.0 {
+      if adjusted_quantity > Decimal::ZERO {
         // 하위 주문 생성 및 제출
         let order_result = self.create_child_order(adjusted_quantity).await;
         
@@ -121,7 +122,7 @@ impl VwapSplitter {
       }
       
       // 총 수량 완료 여부 확인
-      if remaining_quantity <= 0.0 {
+      if remaining_quantity <= Decimal::ZERO {
         break;
       }
     }
@@ -152,7 +153,7 @@ impl VwapSplitter {
   }
   
   /// VWAP 실행 상태 조회
-  pub fn status(&self) -> (bool, f64, f64) {
+  pub fn status(&self) -> (bool, Decimal, Decimal) {
     (self.is_active, self.executed_quantity, self.total_quantity)
   }
   
@@ -185,7 +186,7 @@ impl VwapSplitter {
     }
     
     // 총 거래량 계산
-    let total_volume: f64 = data.iter().map(|d| d.volume).sum();
+    let total_volume: f64 = data.iter().map(|d| d.volume_f64()).sum();
     
     if total_volume == 0.0 {
       let num_slices = 10;
@@ -200,7 +201,7 @@ impl VwapSplitter {
       let start_idx = i * period_duration;
       let end_idx = ((i + 1) * period_duration).min(data.len());
       
-      let period_volume: f64 = data[start_idx..end_idx].iter().map(|d| d.volume).sum();
+      let period_volume: f64 = data[start_idx..end_idx].iter().map(|d| d.volume_f64()).sum();
       volume_profile.push(period_volume / total_volume);
     }
     
@@ -208,7 +209,7 @@ impl VwapSplitter {
   }
   
   /// 하위 주문 생성
-  async fn create_child_order(&self, quantity: f64) -> Result<OrderId, TradingError> {
+  async fn create_child_order(&self, quantity: Decimal) -> Result<OrderId, TradingError> {
     let mut exchange = self.exchange.write().await;
     
     // 현재 시장 데이터로 가격 조회
@@ -232,6 +233,7 @@ mod tests {
   use super::*;
   use crate::exchange::mocks::MockExchange;
   use crate::config::Config;
+  use crate::models::decimal::dec;
   
   #[tokio::test]
   async fn test_vwap_splitter() {
@@ -244,7 +246,7 @@ mod tests {
       exchange.clone(),
       "BTCUSDT",
       OrderSide::Buy,
-      1.0,
+      dec!(1),
       3600000,  // 1시간 (밀리초)
       Some(10.0),
     );
@@ -256,7 +258,7 @@ mod tests {
     // 상태 확인
     let (is_active, executed, total) = vwap.status();
     assert!(!is_active); // 테스트에서는 즉시 완료됨
-    assert!(executed > 0.0);
-    assert_eq!(total, 1.0);
+    assert!(executed > Decimal::ZERO);
+    assert_eq!(total, dec!(1));
   }
 }
\ No newline at end of file
@@ -5,6 +5,8 @@
 * description: 
 **/
 
+use std::time::Duration;
+
 use thiserror::Error;
 
 use crate::models::order::OrderId;
@@ -41,6 +43,9 @@ pub enum TradingError {
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
 
+    #[error("Database error: {0}")]
+    DatabaseError(String),
+
     #[error("Channel not found: {0}")]
     ChannelNotFound(String),
 
@@ -62,6 +67,25 @@ pub enum TradingError {
     #[error("Insufficient balance")]
     InsufficientBalance,
 
+    /// 거래소 요청 제한 (HTTP 429/418, Binance -1003). `retry_after`는 Retry-After 헤더 값
+    #[error("Rate limited{}", .retry_after.map(|d| format!(" (retry after {}ms)", d.as_millis())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+
+    #[error("Invalid symbol: {0}")]
+    InvalidSymbol(String),
+
+    /// 연결 실패, 응답 시간 초과 등 요청이 거래소에 닿았는지 알 수 없는 오류
+    #[error("Network timeout: {0}")]
+    NetworkTimeout(String),
+
+    /// 거래소 서버 오류 (HTTP 5xx)
+    #[error("Exchange unavailable: {0}")]
+    ExchangeUnavailable(String),
+
+    /// 거래소가 오류 코드와 함께 거부한 요청
+    #[error("Exchange rejected ({code}): {msg}")]
+    ExchangeRejected { code: i64, msg: String },
+
     #[error("Duplicate strategy: {0}")]
     DuplicateStrategy(String),
 
@@ -71,6 +95,16 @@ pub enum TradingError {
     #[error("Risk limit exceeded: {0}")]
     RiskLimitExceeded(String),
 
+    #[error("Degraded mode, exchange writes blocked: {0}")]
+    DegradedMode(String),
+
+    #[error("Trading halted: {0}")]
+    TradingHalted(String),
+
+    /// 주문 제출 큐가 가득 차 새 요청을 받지 않음 (위험 감소 요청은 예외)
+    #[error("Submission queue full: {0}")]
+    QueueFull(String),
+
     #[error("Parse error: {0}")]
     ParseError(String),
 
@@ -85,4 +119,66 @@ pub enum TradingError {
 
     #[error("Unknown error: {0}")]
     Unknown(String),
+}
+
+impl From<sqlx::Error> for TradingError {
+    fn from(e: sqlx::Error) -> Self {
+        TradingError::DatabaseError(e.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for TradingError {
+    fn from(e: rusqlite::Error) -> Self {
+        TradingError::DatabaseError(e.to_string())
+    }
+}
+
+// Binance -1021: 요청 timestamp가 recvWindow를 벗어남 (시간 동기화 후 재시도)
+pub const BINANCE_TIMESTAMP_OUT_OF_WINDOW: i64 = -1021;
+
+impl TradingError {
+    /// 같은 요청을 다시 보내면 성공할 수 있는 오류인지 (요청 제한, 네트워크 오류, 거래소 장애, 시간 오차)
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TradingError::RateLimited { .. } | TradingError::NetworkTimeout(_) | TradingError::ExchangeUnavailable(_) => true,
+            TradingError::ExchangeRejected { code, .. } => *code == BINANCE_TIMESTAMP_OUT_OF_WINDOW,
+            _ => false,
+        }
+    }
+
+    /// 거래소가 지정한 재시도 대기 시간
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            TradingError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+
+    /// 거래소 오류 코드 (ExchangeRejected 일 때)
+    pub fn exchange_code(&self) -> Option<i64> {
+        match self {
+            TradingError::ExchangeRejected { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retryable_classification() {
+        let limited = TradingError::RateLimited { retry_after: Some(Duration::from_secs(2)) };
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(2)));
+        assert_eq!(limited.to_string(), "Rate limited (retry after 2000ms)");
+        assert_eq!(TradingError::RateLimited { retry_after: None }.to_string(), "Rate limited");
+
+        assert!(TradingError::NetworkTimeout("GET /api/v3/klines".into()).is_retryable());
+        assert!(TradingError::ExchangeRejected { code: -1021, msg: "Timestamp outside recvWindow".into() }.is_retryable());
+        assert!(!TradingError::ExchangeRejected { code: -1013, msg: "Filter failure: LOT_SIZE".into() }.is_retryable());
+        assert!(!TradingError::InsufficientBalance.is_retryable());
+        assert!(!TradingError::InvalidSymbol("FOOBAR".into()).is_retryable());
+    }
 }
\ No newline at end of file
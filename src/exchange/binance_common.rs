@@ -0,0 +1,396 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use rust_decimal::prelude::FromPrimitive;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::TradingError;
+use crate::exchange::rate_limiter::{WeightRateLimiter, BINANCE_SPOT_WEIGHT_PER_MINUTE};
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::models::decimal::Decimal;
+use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// REST plumbing shared by the Binance spot and futures connectors:
+/// HMAC signing, request weight limiting, server time offset and latency telemetry
+pub(crate) struct BinanceRestClient {
+  pub base_url: String,
+  pub api_key: String,
+  api_secret: String,
+  http: reqwest::Client,
+  pub recv_window_ms: u64,
+  /// request weight budget, kept in sync with X-MBX-USED-WEIGHT-1M
+  pub limiter: WeightRateLimiter,
+  time_offset_ms: AtomicI64,
+  pub telemetry: ExchangeTelemetry,
+  /// orderId -> symbol; Binance order endpoints need both
+  order_symbols: Mutex<HashMap<String, String>>,
+}
+
+impl BinanceRestClient {
+  pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    BinanceRestClient {
+      base_url: base_url.into(),
+      api_key: api_key.into(),
+      api_secret: api_secret.into(),
+      http: reqwest::Client::new(),
+      recv_window_ms: 5000,
+      limiter: WeightRateLimiter::per_minute(BINANCE_SPOT_WEIGHT_PER_MINUTE),
+      time_offset_ms: AtomicI64::new(0),
+      telemetry: ExchangeTelemetry::default(),
+      order_symbols: Mutex::new(HashMap::new()),
+    }
+  }
+
+  pub fn timestamp_ms() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
+  }
+
+  pub fn ts_with_offset(&self) -> i64 { Self::timestamp_ms() + self.time_offset_ms.load(Ordering::SeqCst) }
+
+  pub fn sign(&self, query: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes()).unwrap();
+    mac.update(query.as_bytes());
+    let result = mac.finalize().into_bytes();
+    hex::encode(result)
+  }
+
+  /// Per-minute weight budget (spot 6000, USDT-M futures 2400)
+  pub fn with_weight_limit(mut self, weight_per_minute: u32) -> Self {
+    self.limiter = WeightRateLimiter::per_minute(weight_per_minute);
+    self
+  }
+
+  /// Wait for the request weight of `path` (with its query) to fit in the budget
+  pub async fn throttle(&self, method: &reqwest::Method, path: &str, query: &str) {
+    self.limiter.acquire(request_weight(method, path, query)).await;
+  }
+
+  /// Send a request, record its round trip under `endpoint` and feed the weight headers to the limiter
+  pub async fn send_timed(&self, endpoint: &str, req: reqwest::RequestBuilder) -> Result<reqwest::Response, reqwest::Error> {
+    let started = std::time::Instant::now();
+    let res = req.send().await;
+    if let Ok(r) = &res {
+      self.limiter.observe_response(r.status().as_u16(), r.headers(), std::time::Instant::now());
+    }
+    let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
+    self.telemetry.record_request(endpoint, started.elapsed().as_secs_f64() * 1000.0, ok);
+    res
+  }
+
+  pub fn get(&self, url: String) -> reqwest::RequestBuilder { self.http.get(url) }
+
+  pub fn post(&self, url: String) -> reqwest::RequestBuilder { self.http.post(url).header("X-MBX-APIKEY", &self.api_key) }
+
+  /// API-key-only request (no signature), used by the listenKey endpoints
+  pub async fn api_key_request(&self, method: reqwest::Method, path: &str, query: &str) -> Result<serde_json::Value, TradingError> {
+    let url = if query.is_empty() { format!("{}{}", self.base_url, path) } else { format!("{}{}?{}", self.base_url, path, query) };
+    let endpoint = format!("{} {}", method, path);
+    self.throttle(&method, path, query).await;
+    let res = self.send_timed(&endpoint, self.http.request(method, url).header("X-MBX-APIKEY", &self.api_key)).await
+      .map_err(|e| transport_error(&endpoint, e))?;
+    read_json(&endpoint, res).await
+  }
+
+  /// Unsigned GET against a market data endpoint
+  pub async fn public_get(&self, path: &str, query: &str) -> Result<serde_json::Value, TradingError> {
+    let url = if query.is_empty() { format!("{}{}", self.base_url, path) } else { format!("{}{}?{}", self.base_url, path, query) };
+    let endpoint = format!("GET {}", path);
+    self.throttle(&reqwest::Method::GET, path, query).await;
+    let res = self.send_timed(&endpoint, self.http.get(url)).await
+      .map_err(|e| transport_error(&endpoint, e))?;
+    read_json(&endpoint, res).await
+  }
+
+  /// Signed request; returns the JSON body or a typed error (OrderNotFound when `order_id` is unknown)
+  pub async fn signed_request(&self, method: reqwest::Method, path: &str, params: &[String], order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    let mut query = params.to_vec();
+    query.push(format!("timestamp={}", self.ts_with_offset()));
+    query.push(format!("recvWindow={}", self.recv_window_ms));
+    let query = query.join("&");
+    let url = format!("{}{}?{}&signature={}", self.base_url, path, query, self.sign(&query));
+    let endpoint = format!("{} {}", method, path);
+    self.throttle(&method, path, &query).await;
+    let res = self.send_timed(&endpoint, self.http.request(method, url).header("X-MBX-APIKEY", &self.api_key)).await
+      .map_err(|e| transport_error(&endpoint, e))?;
+    match (read_json(&endpoint, res).await, order_id) {
+      // -2011 unknown order (cancel), -2013 order does not exist (query)
+      (Err(TradingError::ExchangeRejected { code: -2011 | -2013, .. }), Some(id)) => Err(TradingError::OrderNotFound(id.clone())),
+      (res, _) => res,
+    }
+  }
+
+  /// Measure the server clock offset from a `{"serverTime": ..}` endpoint
+  pub async fn sync_time(&self, path: &str) -> Result<(), TradingError> {
+    let url = format!("{}{}", self.base_url, path);
+    let endpoint = format!("GET {}", path);
+    self.throttle(&reqwest::Method::GET, path, "").await;
+    let sent_at = Self::timestamp_ms();
+    let res = self.send_timed(&endpoint, self.http.get(url)).await
+      .map_err(|e| transport_error(&endpoint, e))?;
+    let v = read_json(&endpoint, res).await?;
+    if let Some(server_ts) = v.get("serverTime").and_then(|t| t.as_i64()) {
+      // server time is stamped roughly mid-flight: compare against the local midpoint
+      let received_at = Self::timestamp_ms();
+      let rtt = (received_at - sent_at).max(0);
+      let offset = server_ts - (sent_at + rtt / 2);
+      self.time_offset_ms.store(offset, Ordering::SeqCst);
+      self.telemetry.record_clock_offset(offset, rtt as f64, received_at);
+      Ok(())
+    } else {
+      Err(TradingError::ParseError("serverTime missing".into()))
+    }
+  }
+
+  pub fn remember_symbol(&self, order_id: &str, symbol: &str) {
+    self.order_symbols.lock().unwrap_or_else(|e| e.into_inner()).insert(order_id.to_string(), symbol.to_string());
+  }
+
+  pub fn symbol_for(&self, order_id: &OrderId) -> Result<String, TradingError> {
+    self.order_symbols.lock().unwrap_or_else(|e| e.into_inner())
+      .get(&order_id.0)
+      .cloned()
+      .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))
+  }
+}
+
+/// Request weight of a Binance REST call (spot /api/v3, USDT-M futures /fapi)
+pub(crate) fn request_weight(method: &reqwest::Method, path: &str, query: &str) -> u32 {
+  let param = |name: &str| query.split('&').find_map(|kv| kv.strip_prefix(name).and_then(|v| v.strip_prefix('=')));
+  let limit = param("limit").and_then(|l| l.parse::<u32>().ok());
+  let has_symbol = param("symbol").is_some();
+  match path {
+    "/api/v3/exchangeInfo" | "/api/v3/account" => 20,
+    "/api/v3/order" if method == reqwest::Method::GET => 4,
+    "/api/v3/openOrders" => if has_symbol { 6 } else { 80 },
+    "/fapi/v1/openOrders" => if has_symbol { 1 } else { 40 },
+    "/api/v3/ticker/bookTicker" | "/fapi/v1/ticker/bookTicker" => if has_symbol { 2 } else { 5 },
+    "/api/v3/depth" => match limit.unwrap_or(100) { 0..=100 => 5, 101..=500 => 25, 501..=1000 => 50, _ => 250 },
+    "/fapi/v1/depth" => match limit.unwrap_or(500) { 0..=50 => 2, 51..=100 => 5, 101..=500 => 10, _ => 20 },
+    "/api/v3/klines" | "/api/v3/userDataStream" => 2,
+    "/fapi/v1/klines" => match limit.unwrap_or(500) { 0..=99 => 1, 100..=499 => 2, 500..=1000 => 5, _ => 10 },
+    "/fapi/v2/positionRisk" => 5,
+    _ => 1,
+  }
+}
+
+/// Map a transport failure: timeouts and connection errors are retryable, anything else is not
+pub(crate) fn transport_error(endpoint: &str, e: reqwest::Error) -> TradingError {
+  if e.is_timeout() || e.is_connect() || e.is_request() {
+    TradingError::NetworkTimeout(format!("{} http error: {}", endpoint, e))
+  } else {
+    TradingError::ExchangeError(format!("{} http error: {}", endpoint, e))
+  }
+}
+
+/// JSON body of a successful response, or the typed error of a failed one
+pub(crate) async fn read_json(endpoint: &str, res: reqwest::Response) -> Result<serde_json::Value, TradingError> {
+  let status = res.status();
+  let retry_after = res.headers().get(reqwest::header::RETRY_AFTER)
+    .and_then(|v| v.to_str().ok())
+    .and_then(|v| v.trim().parse::<u64>().ok())
+    .map(Duration::from_secs);
+  let body = res.text().await.map_err(|e| transport_error(endpoint, e))?;
+  if !status.is_success() {
+    return Err(binance_error(endpoint, status, &body, retry_after));
+  }
+  serde_json::from_str(&body).map_err(|e| TradingError::ExchangeError(format!("{} parse error: {}", endpoint, e)))
+}
+
+/// Classify a failed Binance response (`{"code": -1121, "msg": ".."}` body when the API answered)
+pub(crate) fn binance_error(endpoint: &str, status: reqwest::StatusCode, body: &str, retry_after: Option<Duration>) -> TradingError {
+  let json = serde_json::from_str::<serde_json::Value>(body).ok();
+  let code = json.as_ref().and_then(|j| j.get("code")).and_then(|c| c.as_i64());
+  let msg = json.as_ref().and_then(|j| j.get("msg")).and_then(|m| m.as_str()).unwrap_or(body).to_string();
+  match code {
+    // 429 request weight exceeded, 418 IP auto-banned; -1003 too many requests, -1015 too many new orders
+    _ if status.as_u16() == 429 || status.as_u16() == 418 => TradingError::RateLimited { retry_after },
+    Some(-1003 | -1015) => TradingError::RateLimited { retry_after },
+    // -2018 balance / -2019 margin insufficient; -2010 is a generic new-order rejection
+    Some(-2018 | -2019) => TradingError::InsufficientBalance,
+    Some(-2010) if msg.to_lowercase().contains("insufficient balance") => TradingError::InsufficientBalance,
+    Some(-1121) => TradingError::InvalidSymbol(msg),
+    // -1001 internal disconnect, -1007 backend timeout (order state unknown)
+    Some(-1001 | -1007) => TradingError::NetworkTimeout(format!("{}: {}", endpoint, msg)),
+    Some(code) => TradingError::ExchangeRejected { code, msg },
+    None if status.is_server_error() => TradingError::ExchangeUnavailable(format!("{} failed: {} {}", endpoint, status, body)),
+    None => TradingError::ExchangeError(format!("{} failed: {} {}", endpoint, status, body)),
+  }
+}
+
+pub(crate) fn parse_f64(v: Option<&serde_json::Value>) -> f64 {
+  v.and_then(|x| x.as_str().and_then(|s| s.parse().ok()).or_else(|| x.as_f64())).unwrap_or(0.0)
+}
+
+/// Exact decimal of a numeric string (or JSON number); 0 when missing or malformed
+pub(crate) fn parse_decimal(v: Option<&serde_json::Value>) -> Decimal {
+  v.and_then(|x| match x {
+    serde_json::Value::String(s) => Decimal::from_str(s).or_else(|_| Decimal::from_scientific(s)).ok(),
+    serde_json::Value::Number(n) => n.as_i64().map(Decimal::from).or_else(|| n.as_f64().and_then(Decimal::from_f64)),
+    _ => None,
+  }).unwrap_or_default()
+}
+
+/// Binance order id is numeric in JSON; keep it as its decimal string
+pub(crate) fn parse_order_id(v: &serde_json::Value) -> Option<OrderId> {
+  v.get("orderId").and_then(|id| id.as_i64().map(|n| n.to_string()).or_else(|| id.as_str().map(str::to_string))).map(OrderId)
+}
+
+pub(crate) fn parse_order_status(status: &str) -> OrderStatus {
+  match status {
+    "NEW" => OrderStatus::New,
+    "PARTIALLY_FILLED" => OrderStatus::PartiallyFilled,
+    "FILLED" => OrderStatus::Filled,
+    "CANCELED" => OrderStatus::Cancelled,
+    "REJECTED" => OrderStatus::Rejected,
+    // EXPIRED, EXPIRED_IN_MATCH (self-trade prevention)
+    _ => OrderStatus::Expired,
+  }
+}
+
+/// Map a Binance order object (openOrders / order query, spot or futures) to an Order
+pub(crate) fn parse_order(v: &serde_json::Value) -> Option<Order> {
+  let id = parse_order_id(v)?;
+  let symbol = v.get("symbol")?.as_str()?;
+  let side = match v.get("side")?.as_str()? { "BUY" => OrderSide::Buy, _ => OrderSide::Sell };
+  let order_type = match v.get("type").or_else(|| v.get("origType")).and_then(|t| t.as_str()).unwrap_or("") {
+    "LIMIT" | "LIMIT_MAKER" => OrderType::Limit,
+    "STOP" | "STOP_LOSS_LIMIT" | "TAKE_PROFIT_LIMIT" => OrderType::StopLimit,
+    "STOP_MARKET" | "STOP_LOSS" | "TAKE_PROFIT" | "TAKE_PROFIT_MARKET" => OrderType::StopLoss,
+    "TRAILING_STOP_MARKET" => OrderType::TrailingStop,
+    _ => OrderType::Market,
+  };
+  let mut order = Order::new(symbol, side, order_type, parse_decimal(v.get("origQty")), parse_decimal(v.get("price")));
+  order.id = id;
+  order.client_order_id = v.get("clientOrderId").and_then(|c| c.as_str()).map(str::to_string);
+  order.created_at = v.get("time").and_then(|t| t.as_i64()).map(Timestamp::from_millis).unwrap_or(order.created_at);
+  if let Some(tif) = v.get("timeInForce").and_then(|t| t.as_str()) { order.time_in_force = tif.to_string(); }
+  let stop = parse_decimal(v.get("stopPrice"));
+  if stop > Decimal::ZERO { order.stop_price = Some(stop); }
+  if order.order_type == OrderType::TrailingStop {
+    order.trailing_delta = Some(parse_f64(v.get("priceRate")));
+    let activation = parse_decimal(v.get("activatePrice"));
+    if activation > Decimal::ZERO { order.stop_price = Some(activation); }
+  }
+  // spot: trailingDelta in BIPS on STOP_LOSS / TAKE_PROFIT types
+  if let Some(bips) = v.get("trailingDelta").and_then(|d| d.as_f64()) {
+    order.order_type = OrderType::TrailingStop;
+    order.trailing_delta = Some(bips / 100.0);
+  }
+  let qty = parse_decimal(v.get("icebergQty"));
+  if qty > Decimal::ZERO { order.iceberg_qty = Some(qty); }
+  order.reduce_only = v.get("reduceOnly").and_then(|r| r.as_bool());
+  order.position_side = v.get("positionSide").and_then(|p| p.as_str()).map(str::to_string);
+  Some(order)
+}
+
+/// Map a kline array `[openTime, open, high, low, close, volume, ...]` to a candle
+pub(crate) fn parse_kline(symbol: &str, v: &serde_json::Value) -> Option<MarketData> {
+  let k = v.as_array()?;
+  Some(MarketData {
+    symbol: symbol.to_string(),
+    timestamp: Timestamp::from_millis(k.first()?.as_i64()?),
+    open: parse_decimal(k.get(1)),
+    high: parse_decimal(k.get(2)),
+    low: parse_decimal(k.get(3)),
+    close: parse_decimal(k.get(4)),
+    volume: parse_decimal(k.get(5)),
+  })
+}
+
+/// Mid of a bookTicker response (falls back to whichever side is quoted)
+pub(crate) fn book_ticker_mid(json: &serde_json::Value) -> Decimal {
+  let bid = parse_decimal(json.get("bidPrice"));
+  let ask = json.get("askPrice").map(|a| parse_decimal(Some(a))).unwrap_or(bid);
+  if bid > Decimal::ZERO && ask > Decimal::ZERO { (bid + ask) / Decimal::TWO } else { bid.max(ask) }
+}
+
+/// Binance only accepts fixed depth limits; pick the next one up
+pub(crate) fn depth_limit(depth: usize, limits: &[usize]) -> usize {
+  limits.iter().copied().find(|l| *l >= depth).unwrap_or_else(|| limits.last().copied().unwrap_or(depth))
+}
+
+/// Entry of `symbol` in an exchangeInfo response (fapi ignores the symbol parameter and lists every contract)
+pub(crate) fn exchange_info_symbol<'a>(info: &'a serde_json::Value, symbol: &str) -> Option<&'a serde_json::Value> {
+  info.get("symbols").and_then(|s| s.as_array())?
+    .iter()
+    .find(|s| s.get("symbol").and_then(|n| n.as_str()) == Some(symbol))
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SymbolFilters {
+  pub tick_size: Decimal,
+  pub step_size: Decimal,
+  pub min_qty: Decimal,
+  pub min_notional: Decimal,
+}
+
+impl SymbolFilters {
+  /// Read PRICE_FILTER / LOT_SIZE / (MIN_)NOTIONAL from an exchangeInfo symbol entry
+  pub fn from_exchange_info(symbol: &serde_json::Value) -> Self {
+    let mut filters = SymbolFilters::default();
+    for f in symbol.get("filters").and_then(|f| f.as_array()).into_iter().flatten() {
+      match f.get("filterType").and_then(|x| x.as_str()).unwrap_or("") {
+        "PRICE_FILTER" => {
+          filters.tick_size = parse_decimal(f.get("tickSize"));
+        }
+        "LOT_SIZE" => {
+          filters.step_size = parse_decimal(f.get("stepSize"));
+          filters.min_qty = parse_decimal(f.get("minQty"));
+        }
+        "MIN_NOTIONAL" | "NOTIONAL" => {
+          filters.min_notional = parse_decimal(f.get("notional").or_else(|| f.get("minNotional")));
+        }
+        _ => {}
+      }
+    }
+    filters
+  }
+
+  pub fn symbol_info(&self, symbol: &str) -> SymbolInfo {
+    SymbolInfo {
+      symbol: symbol.to_string(),
+      tick_size: self.tick_size,
+      step_size: self.step_size,
+      min_qty: self.min_qty,
+      min_notional: self.min_notional,
+    }
+  }
+
+  /// Round price/quantity onto the symbol grid and bump the price up to min notional if needed
+  pub fn normalize(&self, order: &mut Order) {
+    if order.price > Decimal::ZERO && self.tick_size > Decimal::ZERO {
+      order.price = floor_to_step(order.price, self.tick_size);
+    }
+    if self.step_size > Decimal::ZERO {
+      // qty step floor
+      order.quantity = floor_to_step(order.quantity, self.step_size);
+      if order.quantity < self.min_qty && self.min_qty > Decimal::ZERO {
+        order.quantity = self.min_qty;
+      }
+    }
+    // min notional guard: if not met and we can adjust price, bump price minimally
+    if self.min_notional > Decimal::ZERO && order.quantity > Decimal::ZERO && order.quantity * order.price < self.min_notional {
+      let mut new_price = self.min_notional / order.quantity;
+      if self.tick_size > Decimal::ZERO { new_price = ceil_to_step(new_price, self.tick_size); }
+      order.price = new_price;
+    }
+  }
+}
+
+pub(crate) fn floor_to_step(value: Decimal, step: Decimal) -> Decimal {
+  if step <= Decimal::ZERO { return value; }
+  (value / step).floor() * step
+}
+
+pub(crate) fn ceil_to_step(value: Decimal, step: Decimal) -> Decimal {
+  if step <= Decimal::ZERO { return value; }
+  (value / step).ceil() * step
+}
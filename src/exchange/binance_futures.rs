@@ -1,138 +1,58 @@
 use async_trait::async_trait;
-use hmac::{Hmac, Mac};
-use sha2::Sha256;
-use std::time::{SystemTime, UNIX_EPOCH};
-use std::sync::atomic::{AtomicI64, Ordering};
 use std::collections::HashMap;
 
 use crate::error::TradingError;
+use crate::exchange::binance_common::{book_ticker_mid, depth_limit, parse_decimal, parse_kline, parse_order, parse_order_id, parse_order_status, exchange_info_symbol, read_json, transport_error, BinanceRestClient, SymbolFilters};
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::rate_limiter::BINANCE_FUTURES_WEIGHT_PER_MINUTE;
+use crate::exchange::telemetry::ExchangeTelemetry;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::models::funding::{FundingRate, OpenInterest};
+use crate::models::margin::{MarginAccount, PositionMargin};
 use crate::models::market_data::MarketData;
+use crate::models::order_book::{levels_from_json, OrderBook};
 use crate::models::position::Position;
-use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::order::{Order, OrderFill, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
 use crate::models::trade::Trade;
 
-type HmacSha256 = Hmac<Sha256>;
-
 /// Binance USDT-M Futures REST connector (minimal subset)
 pub struct BinanceFuturesExchange {
-  pub base_url: String,
-  pub api_key: String,
-  pub api_secret: String,
-  pub http: reqwest::Client,
-  pub recv_window_ms: u64,
-  pub min_interval_ms: u64,
-  pub last_request_ms: AtomicI64,
-  pub time_offset_ms: AtomicI64,
+  rest: BinanceRestClient,
   symbol_filters: HashMap<String, SymbolFilters>,
 }
 
 impl BinanceFuturesExchange {
   pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
     BinanceFuturesExchange {
-      base_url: base_url.into(),
-      api_key: api_key.into(),
-      api_secret: api_secret.into(),
-      http: reqwest::Client::new(),
-      recv_window_ms: 5000,
-      min_interval_ms: 50,
-      last_request_ms: AtomicI64::new(0),
-      time_offset_ms: AtomicI64::new(0),
+      rest: BinanceRestClient::new(base_url, api_key, api_secret).with_weight_limit(BINANCE_FUTURES_WEIGHT_PER_MINUTE),
       symbol_filters: HashMap::new(),
     }
   }
 
-  fn timestamp_ms() -> i64 {
-    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64
-  }
-
-  fn sign(&self, query: &str) -> String {
-    let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes()).unwrap();
-    mac.update(query.as_bytes());
-    let result = mac.finalize().into_bytes();
-    hex::encode(result)
+  /// Record REST latency and clock samples into a shared telemetry handle
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    self.rest.telemetry = telemetry;
+    self
   }
-
-  async fn throttle(&self) {
-    let now = Self::timestamp_ms();
-    let last = self.last_request_ms.load(Ordering::SeqCst);
-    let elapsed = (now - last) as u64;
-    if elapsed < self.min_interval_ms {
-      let sleep_ms = self.min_interval_ms - elapsed;
-      tokio::time::sleep(std::time::Duration::from_millis(sleep_ms)).await;
-    }
-    self.last_request_ms.store(Self::timestamp_ms(), Ordering::SeqCst);
-  }
-
-  fn ts_with_offset(&self) -> i64 { Self::timestamp_ms() + self.time_offset_ms.load(Ordering::SeqCst) }
-}
-#[derive(Debug, Clone, Default)]
-struct SymbolFilters {
-  tick_size: f64,
-  step_size: f64,
-  min_qty: f64,
-  min_notional: f64,
-}
-
-fn floor_to_step(value: f64, step: f64) -> f64 {
-  if step <= 0.0 { return value; }
-  (value / step).floor() * step
-}
-
-fn ceil_to_step(value: f64, step: f64) -> f64 {
-  if step <= 0.0 { return value; }
-  (value / step).ceil() * step
 }
 
 impl BinanceFuturesExchange {
   async fn ensure_filters(&mut self, symbol: &str) -> Result<(), TradingError> {
     if self.symbol_filters.contains_key(symbol) { return Ok(()); }
-    let url = format!("{}/fapi/v1/exchangeInfo?symbol={}", self.base_url, symbol);
-    self.throttle().await;
-    let res = self.http.get(url).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("exchangeInfo http error: {}", e)))?;
-    if !res.status().is_success() {
-      return Err(TradingError::ExchangeError(format!("exchangeInfo failed: {}", res.status())));
-    }
-    let v = res.json::<serde_json::Value>().await
-      .map_err(|e| TradingError::ExchangeError(format!("exchangeInfo parse error: {}", e)))?;
-    let mut filters = SymbolFilters::default();
-    if let Some(arr) = v.get("symbols").and_then(|s| s.as_array()).and_then(|a| a.get(0)).and_then(|s| s.get("filters")).and_then(|f| f.as_array()) {
-      for f in arr {
-        if let Some(ft) = f.get("filterType").and_then(|x| x.as_str()) {
-          match ft {
-            "PRICE_FILTER" => {
-              filters.tick_size = f.get("tickSize").and_then(|x| x.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-            }
-            "LOT_SIZE" => {
-              filters.step_size = f.get("stepSize").and_then(|x| x.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-              filters.min_qty = f.get("minQty").and_then(|x| x.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-            }
-            "MIN_NOTIONAL" => {
-              filters.min_notional = f.get("notional").or_else(|| f.get("minNotional")).and_then(|x| x.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-            }
-            _ => {}
-          }
-        }
-      }
-    }
+    let v = self.rest.public_get("/fapi/v1/exchangeInfo", &format!("symbol={}", symbol)).await?;
+    let filters = exchange_info_symbol(&v, symbol)
+      .map(SymbolFilters::from_exchange_info)
+      .unwrap_or_default();
     self.symbol_filters.insert(symbol.to_string(), filters);
     Ok(())
   }
 
-  async fn get_mid_price(&self, symbol: &str) -> Result<f64, TradingError> {
-    // reuse bookTicker logic
-    let url = format!("{}/fapi/v1/ticker/bookTicker?symbol={}", self.base_url, symbol);
-    self.throttle().await;
-    let res = self.http.get(url).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("bookTicker http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("bookTicker failed: {}", res.status()))); }
-    let json = res.json::<serde_json::Value>().await
-      .map_err(|e| TradingError::ExchangeError(format!("bookTicker parse error: {}", e)))?;
-    let bid = json.get("bidPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-    let ask = json.get("askPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(bid);
-    let mid = if bid > 0.0 && ask > 0.0 { (bid + ask) / 2.0 } else { bid.max(ask) };
-    if mid <= 0.0 { return Err(TradingError::DataNotFound(format!("mid price {}", symbol))); }
+  async fn get_mid_price(&self, symbol: &str) -> Result<Decimal, TradingError> {
+    let json = self.rest.public_get("/fapi/v1/ticker/bookTicker", &format!("symbol={}", symbol)).await?;
+    let mid = book_ticker_mid(&json);
+    if mid <= Decimal::ZERO { return Err(TradingError::DataNotFound(format!("mid price {}", symbol))); }
     Ok(mid)
   }
 }
@@ -143,36 +63,18 @@ impl Exchange for BinanceFuturesExchange {
     // Load symbol filters and normalize price/qty
     self.ensure_filters(&order.symbol).await?;
     let mut normalized_order = order.clone();
-    let filters = self.symbol_filters.get(&order.symbol).cloned().unwrap_or_default();
-    if normalized_order.order_type == OrderType::Limit && filters.tick_size > 0.0 {
-      normalized_order.price = floor_to_step(normalized_order.price, filters.tick_size);
-    }
-    if filters.step_size > 0.0 {
-      // qty step floor
-      normalized_order.quantity = floor_to_step(normalized_order.quantity, filters.step_size);
-      if normalized_order.quantity < filters.min_qty && filters.min_qty > 0.0 {
-        normalized_order.quantity = filters.min_qty;
-      }
-    }
-    // min notional guard: if not met and we can adjust price, bump price minimally
-    if filters.min_notional > 0.0 {
-      let notional = normalized_order.quantity * normalized_order.price;
-      if notional < filters.min_notional {
-        let need = filters.min_notional / normalized_order.quantity;
-        let mut new_price = need;
-        if filters.tick_size > 0.0 { new_price = ceil_to_step(new_price, filters.tick_size); }
-        normalized_order.price = new_price;
-      }
+    if let Some(filters) = self.symbol_filters.get(&order.symbol) {
+      filters.normalize(&mut normalized_order);
     }
     // Build Binance Futures order params by type
     let side = match normalized_order.side { OrderSide::Buy => "BUY", OrderSide::Sell => "SELL" };
-    let ts = self.ts_with_offset();
+    let ts = self.rest.ts_with_offset();
     let mut params = vec![
       format!("symbol={}", normalized_order.symbol),
       format!("side={}", side),
       format!("quantity={}", normalized_order.quantity),
       format!("timestamp={}", ts),
-      format!("recvWindow={}", self.recv_window_ms),
+      format!("recvWindow={}", self.rest.recv_window_ms),
     ];
 
     match normalized_order.order_type {
@@ -187,7 +89,7 @@ impl Exchange for BinanceFuturesExchange {
       }
       OrderType::StopLoss | OrderType::StopLimit => {
         // Use STOP (limit) if price provided, else STOP_MARKET
-        if normalized_order.price > 0.0 {
+        if normalized_order.price > Decimal::ZERO {
           params.push("type=STOP".to_string());
           params.push(format!("price={}", normalized_order.price));
           params.push(format!("timeInForce={}", normalized_order.time_in_force));
@@ -195,7 +97,7 @@ impl Exchange for BinanceFuturesExchange {
           params.push("type=STOP_MARKET".to_string());
         }
         let stop = normalized_order.stop_price.unwrap_or(normalized_order.price);
-        if stop > 0.0 { params.push(format!("stopPrice={}", stop)); }
+        if stop > Decimal::ZERO { params.push(format!("stopPrice={}", stop)); }
       }
       OrderType::TrailingStop => {
         // Binance requires callbackRate in percent (0.1-5.0)
@@ -209,22 +111,29 @@ impl Exchange for BinanceFuturesExchange {
     // futures flags: reduceOnly, positionSide
     if let Some(ro) = normalized_order.reduce_only { if ro { params.push("reduceOnly=true".to_string()); } }
     if let Some(ps) = &normalized_order.position_side { params.push(format!("positionSide={}", ps)); }
+    if let Some(cid) = &normalized_order.client_order_id { params.push(format!("newClientOrderId={}", cid)); }
     let query = params.join("&");
-    let signature = self.sign(&query);
-    let url = format!("{}/fapi/v1/order?{}&signature={}", self.base_url, query, signature);
-    self.throttle().await;
-    let res = self.http
-      .post(url)
-      .header("X-MBX-APIKEY", &self.api_key)
-      .send().await
-      .map_err(|e| TradingError::ExchangeError(format!("submit_order http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("submit_order failed: {}", res.status()))); }
-    // In real code parse orderId
-    Ok(OrderId(format!("binfut-{}", ts)))
+    let signature = self.rest.sign(&query);
+    let url = format!("{}/fapi/v1/order?{}&signature={}", self.rest.base_url, query, signature);
+    self.rest.throttle(&reqwest::Method::POST, "/fapi/v1/order", &query).await;
+    let res = self.rest.send_timed("POST /fapi/v1/order", self.rest.post(url))
+      .await
+      .map_err(|e| transport_error("POST /fapi/v1/order", e))?;
+    // typed exchange error (e.g. -1021 timestamp, rate limit) so OrderManager can react to it
+    let body = read_json("POST /fapi/v1/order", res).await?;
+    let order_id = parse_order_id(&body)
+      .ok_or_else(|| TradingError::ParseError(format!("orderId missing in {}", body)))?;
+    if let Some(s) = body.get("status").and_then(|s| s.as_str()) {
+      log::debug!("binance futures order {} ({}) accepted: {}", order_id, body.get("clientOrderId").and_then(|c| c.as_str()).unwrap_or("-"), s);
+    }
+    self.rest.remember_symbol(&order_id.0, &normalized_order.symbol);
+    Ok(order_id)
   }
 
-  async fn cancel_order(&mut self, _order_id: &OrderId) -> Result<(), TradingError> {
-    // Not implemented
+  async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    self.rest.signed_request(reqwest::Method::DELETE, "/fapi/v1/order", &params, Some(order_id)).await?;
     Ok(())
   }
 
@@ -232,34 +141,55 @@ impl Exchange for BinanceFuturesExchange {
     Err(TradingError::ExchangeError("modify not supported in connector".to_string()))
   }
 
-  async fn get_order_status(&self, _order_id: &OrderId) -> Result<OrderStatus, TradingError> {
-    Ok(OrderStatus::New)
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    let body = self.rest.signed_request(reqwest::Method::GET, "/fapi/v1/order", &params, Some(order_id)).await?;
+    let status = body.get("status").and_then(|s| s.as_str())
+      .ok_or_else(|| TradingError::ParseError(format!("status missing in {}", body)))?;
+    Ok(parse_order_status(status))
+  }
+
+  async fn get_order_fill(&self, order_id: &OrderId) -> Result<Option<OrderFill>, TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    let body = self.rest.signed_request(reqwest::Method::GET, "/fapi/v1/order", &params, Some(order_id)).await?;
+    let executed = parse_decimal(body.get("executedQty"));
+    if executed <= Decimal::ZERO {
+      return Ok(None);
+    }
+    Ok(Some(OrderFill { executed_quantity: executed, average_price: parse_decimal(body.get("avgPrice")) }))
   }
 
   async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
-    Ok(Vec::new())
+    // without symbol: all symbols (weight 40)
+    let body = self.rest.signed_request(reqwest::Method::GET, "/fapi/v1/openOrders", &[], None).await?;
+    let orders: Vec<Order> = body.as_array().map(|list| list.iter().filter_map(parse_order).collect()).unwrap_or_default();
+    // orders placed before a restart become cancellable again
+    for order in &orders {
+      self.rest.remember_symbol(&order.id.0, &order.symbol);
+    }
+    Ok(orders)
   }
 
   async fn get_positions(&self) -> Result<Vec<Position>, TradingError> {
     // GET /fapi/v2/positionRisk requires API key/secret
-    let ts = self.ts_with_offset();
-    let q = format!("timestamp={}&recvWindow={}", ts, self.recv_window_ms);
-    let url = format!("{}/fapi/v2/positionRisk?{}&signature={}", self.base_url, q, self.sign(&q));
-    self.throttle().await;
-    let res = self.http.get(url)
-      .header("X-MBX-APIKEY", &self.api_key)
-      .send().await
-      .map_err(|e| TradingError::ExchangeError(format!("positions http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("positions failed: {}", res.status()))); }
-    let arr = res.json::<serde_json::Value>().await
-      .map_err(|e| TradingError::ExchangeError(format!("positions parse error: {}", e)))?;
+    let ts = self.rest.ts_with_offset();
+    let q = format!("timestamp={}&recvWindow={}", ts, self.rest.recv_window_ms);
+    let url = format!("{}/fapi/v2/positionRisk?{}&signature={}", self.rest.base_url, q, self.rest.sign(&q));
+    self.rest.throttle(&reqwest::Method::GET, "/fapi/v2/positionRisk", &q).await;
+    let res = self.rest.send_timed("GET /fapi/v2/positionRisk", self.rest.get(url)
+      .header("X-MBX-APIKEY", &self.rest.api_key))
+      .await
+      .map_err(|e| transport_error("GET /fapi/v2/positionRisk", e))?;
+    let arr = read_json("GET /fapi/v2/positionRisk", res).await?;
     let mut out = Vec::new();
     if let Some(list) = arr.as_array() {
       for p in list {
         let symbol = p.get("symbol").and_then(|v| v.as_str()).unwrap_or("");
-        let pos_amt = p.get("positionAmt").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-        let entry_price = p.get("entryPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-        let mark_price = p.get("markPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(entry_price);
+        let pos_amt = parse_decimal(p.get("positionAmt"));
+        let entry_price = parse_decimal(p.get("entryPrice"));
+        let mark_price = Some(parse_decimal(p.get("markPrice"))).filter(|m| *m > Decimal::ZERO).unwrap_or(entry_price);
         if symbol.is_empty() { continue; }
         let mut pos = Position::new(symbol.to_string(), pos_amt, entry_price);
         pos.update_price(mark_price);
@@ -269,86 +199,180 @@ impl Exchange for BinanceFuturesExchange {
     Ok(out)
   }
 
+  async fn get_margin_account(&self) -> Result<MarginAccount, TradingError> {
+    let json = self.rest.signed_request(reqwest::Method::GET, "/fapi/v2/account", &[], None).await?;
+    MarginAccount::from_binance_account(&json, self.rest.ts_with_offset())
+      .ok_or_else(|| TradingError::ParseError(format!("account parse error: {}", json)))
+  }
+
+  async fn get_position_margins(&self) -> Result<Vec<PositionMargin>, TradingError> {
+    // positionRisk lists every symbol; flat ones are dropped by the parser
+    let json = self.rest.signed_request(reqwest::Method::GET, "/fapi/v2/positionRisk", &[], None).await?;
+    Ok(json.as_array().map(|list| list.iter().filter_map(PositionMargin::from_binance_position_risk).collect()).unwrap_or_default())
+  }
+
   async fn get_recent_trades(&self, _symbol: &str, _limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
     Ok(Vec::new())
   }
 
   async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
     // Prefer book ticker for current price snapshot
-    let url = format!("{}/fapi/v1/ticker/bookTicker?symbol={}", self.base_url, symbol);
-    self.throttle().await;
-    let res = self.http.get(url)
-      .send().await
-      .map_err(|e| TradingError::ExchangeError(format!("market_data http error: {}", e)))?;
-    let status = res.status();
-    let json = res.json::<serde_json::Value>().await
-      .map_err(|e| TradingError::ExchangeError(format!("market_data parse error: {}", e)))?;
-    if !status.is_success() { return Err(TradingError::ExchangeError(format!("market_data failed: {}", status))); }
-    let bid = json.get("bidPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
-    let ask = json.get("askPrice").and_then(|v| v.as_str()).and_then(|s| s.parse::<f64>().ok()).unwrap_or(bid);
-    let close = if bid > 0.0 && ask > 0.0 { (bid + ask) / 2.0 } else { bid.max(ask) };
+    let json = self.rest.public_get("/fapi/v1/ticker/bookTicker", &format!("symbol={}", symbol)).await?;
+    let close = book_ticker_mid(&json);
     let high = close;
     let low = close;
-    let volume = 0.0;
-    Ok(MarketData { symbol: symbol.to_string(), timestamp: self.ts_with_offset(), open: close, high, low, close, volume })
+    let volume = Decimal::ZERO;
+    Ok(MarketData { symbol: symbol.to_string(), timestamp: self.rest.ts_with_offset().into(), open: close, high, low, close, volume })
   }
 
-  async fn get_historical_data(&self, _symbol: &str, _interval: &str, _start_time: i64, _end_time: Option<i64>, _limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
-    Ok(Vec::new())
+  async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+    // Binance only accepts fixed depth limits; request the next one up and trim
+    let limit = depth_limit(depth, &[5, 10, 20, 50, 100, 500, 1000]);
+    let json = self.rest.public_get("/fapi/v1/depth", &format!("symbol={}&limit={}", symbol, limit)).await?;
+    let timestamp = json.get("E").and_then(|v| v.as_i64()).unwrap_or_else(|| self.rest.ts_with_offset());
+    let mut book = OrderBook::new(symbol, timestamp, levels_from_json(&json["bids"]), levels_from_json(&json["asks"]));
+    book.truncate(depth);
+    Ok(book)
+  }
+
+  async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+    // premiumIndex: mark/index price plus lastFundingRate (the rate applied at nextFundingTime)
+    let json = self.rest.public_get("/fapi/v1/premiumIndex", &format!("symbol={}", symbol)).await?;
+    FundingRate::from_premium_index(&json)
+      .ok_or_else(|| TradingError::ParseError(format!("premiumIndex parse error: {}", json)))
+  }
+
+  async fn get_open_interest(&self, symbol: &str) -> Result<OpenInterest, TradingError> {
+    let json = self.rest.public_get("/fapi/v1/openInterest", &format!("symbol={}", symbol)).await?;
+    OpenInterest::from_binance(&json)
+      .ok_or_else(|| TradingError::ParseError(format!("openInterest parse error: {}", json)))
   }
 
-  async fn get_balance(&self, _asset: &str) -> Result<f64, TradingError> { Ok(0.0) }
+  /// Klines oldest first; `limit` is capped at 1500 by the exchange
+  async fn get_historical_data(&self, symbol: &str, interval: &str, start_time: i64, end_time: Option<i64>, limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
+    let mut query = format!("symbol={}&interval={}&startTime={}", symbol, interval, start_time);
+    if let Some(end) = end_time { query.push_str(&format!("&endTime={}", end)); }
+    query.push_str(&format!("&limit={}", limit.unwrap_or(500).min(1500)));
+    let json = self.rest.public_get("/fapi/v1/klines", &query).await?;
+    Ok(json.as_array().map(|rows| rows.iter().filter_map(|k| parse_kline(symbol, k)).collect()).unwrap_or_default())
+  }
+
+  async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+    let v = self.rest.public_get("/fapi/v1/exchangeInfo", &format!("symbol={}", symbol)).await?;
+    exchange_info_symbol(&v, symbol)
+      .map(|s| SymbolFilters::from_exchange_info(s).symbol_info(symbol))
+      .ok_or_else(|| TradingError::DataNotFound(format!("symbol info {}", symbol)))
+  }
+
+  async fn get_balance(&self, _asset: &str) -> Result<Decimal, TradingError> { Ok(Decimal::ZERO) }
 
   async fn set_futures_leverage(&mut self, symbol: &str, leverage: u32) -> Result<(), TradingError> {
-    let ts = self.ts_with_offset();
-    let q = format!("symbol={}&leverage={}&timestamp={}&recvWindow={}", symbol, leverage, ts, self.recv_window_ms);
-    let url = format!("{}/fapi/v1/leverage?{}&signature={}", self.base_url, q, self.sign(&q));
-    self.throttle().await;
-    let res = self.http.post(url).header("X-MBX-APIKEY", &self.api_key).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("set leverage http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("set leverage failed: {}", res.status()))); }
-    Ok(())
+    let ts = self.rest.ts_with_offset();
+    let q = format!("symbol={}&leverage={}&timestamp={}&recvWindow={}", symbol, leverage, ts, self.rest.recv_window_ms);
+    let url = format!("{}/fapi/v1/leverage?{}&signature={}", self.rest.base_url, q, self.rest.sign(&q));
+    self.rest.throttle(&reqwest::Method::POST, "/fapi/v1/leverage", &q).await;
+    let res = self.rest.send_timed("POST /fapi/v1/leverage", self.rest.post(url)).await
+      .map_err(|e| transport_error("POST /fapi/v1/leverage", e))?;
+    read_json("POST /fapi/v1/leverage", res).await.map(|_| ())
   }
 
   async fn set_futures_position_mode(&mut self, hedge: bool) -> Result<(), TradingError> {
-    let ts = self.ts_with_offset();
-    let q = format!("dualSidePosition={}&timestamp={}&recvWindow={}", if hedge {"true"} else {"false"}, ts, self.recv_window_ms);
-    let url = format!("{}/fapi/v1/positionSide/dual?{}&signature={}", self.base_url, q, self.sign(&q));
-    self.throttle().await;
-    let res = self.http.post(url).header("X-MBX-APIKEY", &self.api_key).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("set position mode http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("set position mode failed: {}", res.status()))); }
-    Ok(())
+    let ts = self.rest.ts_with_offset();
+    let q = format!("dualSidePosition={}&timestamp={}&recvWindow={}", if hedge {"true"} else {"false"}, ts, self.rest.recv_window_ms);
+    let url = format!("{}/fapi/v1/positionSide/dual?{}&signature={}", self.rest.base_url, q, self.rest.sign(&q));
+    self.rest.throttle(&reqwest::Method::POST, "/fapi/v1/positionSide/dual", &q).await;
+    let res = self.rest.send_timed("POST /fapi/v1/positionSide/dual", self.rest.post(url)).await
+      .map_err(|e| transport_error("POST /fapi/v1/positionSide/dual", e))?;
+    read_json("POST /fapi/v1/positionSide/dual", res).await.map(|_| ())
   }
 
   async fn set_futures_margin_mode(&mut self, symbol: &str, isolated: bool) -> Result<(), TradingError> {
     // NOTE: Binance uses marginType=ISOLATED|CROSSED
-    let ts = self.ts_with_offset();
-    let q = format!("symbol={}&marginType={}&timestamp={}&recvWindow={}", symbol, if isolated {"ISOLATED"} else {"CROSSED"}, ts, self.recv_window_ms);
-    let url = format!("{}/fapi/v1/marginType?{}&signature={}", self.base_url, q, self.sign(&q));
-    self.throttle().await;
-    let res = self.http.post(url).header("X-MBX-APIKEY", &self.api_key).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("set margin mode http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("set margin mode failed: {}", res.status()))); }
-    Ok(())
+    let ts = self.rest.ts_with_offset();
+    let q = format!("symbol={}&marginType={}&timestamp={}&recvWindow={}", symbol, if isolated {"ISOLATED"} else {"CROSSED"}, ts, self.rest.recv_window_ms);
+    let url = format!("{}/fapi/v1/marginType?{}&signature={}", self.rest.base_url, q, self.rest.sign(&q));
+    self.rest.throttle(&reqwest::Method::POST, "/fapi/v1/marginType", &q).await;
+    let res = self.rest.send_timed("POST /fapi/v1/marginType", self.rest.post(url)).await
+      .map_err(|e| transport_error("POST /fapi/v1/marginType", e))?;
+    read_json("POST /fapi/v1/marginType", res).await.map(|_| ())
   }
 
-  async fn sync_time(&mut self) -> Result<(), TradingError> {
-    // GET /fapi/v1/time
-    let url = format!("{}/fapi/v1/time", self.base_url);
-    self.throttle().await;
-    let res = self.http.get(url).send().await
-      .map_err(|e| TradingError::ExchangeError(format!("time http error: {}", e)))?;
-    if !res.status().is_success() { return Err(TradingError::ExchangeError(format!("time failed: {}", res.status()))); }
-    let v = res.json::<serde_json::Value>().await
-      .map_err(|e| TradingError::ExchangeError(format!("time parse error: {}", e)))?;
-    if let Some(server_ts) = v.get("serverTime").and_then(|t| t.as_i64()) {
-      let local = Self::timestamp_ms();
-      let offset = server_ts - local;
-      self.time_offset_ms.store(offset, Ordering::SeqCst);
-      Ok(())
-    } else {
-      Err(TradingError::ParseError("serverTime missing".into()))
+  fn capabilities(&self) -> ExchangeCapabilities {
+    // USDT-M futures: no icebergQty, VWAP/TWAP are client-side (sent as MARKET slices)
+    ExchangeCapabilities {
+      order_types: vec![OrderType::Market, OrderType::Limit, OrderType::StopLoss, OrderType::StopLimit, OrderType::TrailingStop],
+      time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string(), "GTX".to_string()],
+      hedge_mode: true,
+      batch_orders: true,
+      native_iceberg: false,
+      native_twap: false,
+      native_vwap: false,
     }
   }
+
+  async fn sync_time(&mut self) -> Result<(), TradingError> {
+    self.rest.sync_time("/fapi/v1/time").await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+
+  #[test]
+  fn test_parse_order_response() {
+    let body = serde_json::json!({
+      "orderId": 8389765501234_i64, "symbol": "BTCUSDT", "status": "PARTIALLY_FILLED",
+      "clientOrderId": "c-42", "price": "60000.10", "origQty": "0.010", "executedQty": "0.004",
+      "timeInForce": "GTC", "type": "LIMIT", "side": "SELL", "stopPrice": "0",
+      "reduceOnly": true, "positionSide": "BOTH", "time": 1_700_000_000_000_i64
+    });
+    assert_eq!(parse_order_id(&body), Some(OrderId("8389765501234".to_string())));
+    assert_eq!(parse_order_status(body["status"].as_str().unwrap()), OrderStatus::PartiallyFilled);
+    assert_eq!(parse_order_status("CANCELED"), OrderStatus::Cancelled);
+
+    let order = parse_order(&body).unwrap();
+    assert_eq!(order.id.0, "8389765501234");
+    assert_eq!(order.client_order_id.as_deref(), Some("c-42"));
+    assert_eq!(order.side, OrderSide::Sell);
+    assert_eq!(order.order_type, OrderType::Limit);
+    assert_eq!((order.quantity, order.price), (dec!(0.01), dec!(60000.1)));
+    assert_eq!(order.stop_price, None);
+    assert_eq!(order.reduce_only, Some(true));
+    assert_eq!(order.created_at.as_millis(), 1_700_000_000_000);
+
+    // cancel/status need the symbol an order was placed on
+    let exchange = BinanceFuturesExchange::new("http://localhost", "k", "s");
+    assert!(matches!(exchange.rest.symbol_for(&order.id), Err(TradingError::OrderNotFound(_))));
+    exchange.rest.remember_symbol(&order.id.0, &order.symbol);
+    assert_eq!(exchange.rest.symbol_for(&order.id).unwrap(), "BTCUSDT");
+  }
+
+  #[test]
+  fn test_binance_error_mapping() {
+    use crate::exchange::binance_common::binance_error;
+    use reqwest::StatusCode;
+    use std::time::Duration;
+
+    let ep = "POST /fapi/v1/order";
+    let err = binance_error(ep, StatusCode::TOO_MANY_REQUESTS, r#"{"code":-1003,"msg":"Too many requests."}"#, Some(Duration::from_secs(3)));
+    assert!(matches!(err, TradingError::RateLimited { retry_after: Some(d) } if d == Duration::from_secs(3)));
+    assert!(err.is_retryable());
+    let err = binance_error(ep, StatusCode::BAD_REQUEST, r#"{"code":-2019,"msg":"Margin is insufficient."}"#, None);
+    assert!(matches!(err, TradingError::InsufficientBalance));
+    let err = binance_error(ep, StatusCode::BAD_REQUEST, r#"{"code":-2010,"msg":"Account has insufficient balance for requested action."}"#, None);
+    assert!(matches!(err, TradingError::InsufficientBalance));
+    let err = binance_error(ep, StatusCode::BAD_REQUEST, r#"{"code":-1121,"msg":"Invalid symbol."}"#, None);
+    assert!(matches!(err, TradingError::InvalidSymbol(_)));
+    let err = binance_error(ep, StatusCode::BAD_REQUEST, r#"{"code":-1021,"msg":"Timestamp for this request is outside of the recvWindow."}"#, None);
+    assert_eq!(err.exchange_code(), Some(-1021));
+    assert!(err.is_retryable());
+    let err = binance_error(ep, StatusCode::BAD_REQUEST, r#"{"code":-4164,"msg":"Order's notional must be no smaller than 5"}"#, None);
+    assert!(matches!(err, TradingError::ExchangeRejected { code: -4164, .. }));
+    assert!(!err.is_retryable());
+    let err = binance_error(ep, StatusCode::BAD_GATEWAY, "<html>502 Bad Gateway</html>", None);
+    assert!(matches!(err, TradingError::ExchangeUnavailable(_)));
+    assert!(err.is_retryable());
+  }
 }
@@ -0,0 +1,270 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::error::TradingError;
+use crate::exchange::binance_common::{book_ticker_mid, depth_limit, parse_decimal, parse_kline, parse_order, parse_order_id, parse_order_status, exchange_info_symbol, BinanceRestClient, SymbolFilters};
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::models::market_data::MarketData;
+use crate::models::order_book::{levels_from_json, OrderBook};
+use crate::models::order::{Order, OrderFill, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
+use crate::models::trade::Trade;
+
+/// Binance symbols have no separator: "BTC/USDT", "btc-usdt" -> "BTCUSDT"
+pub fn spot_symbol(symbol: &str) -> String {
+  symbol.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_ascii_uppercase()
+}
+
+/// Binance Spot REST connector (orders, klines, depth, account balances)
+pub struct BinanceSpotExchange {
+  rest: BinanceRestClient,
+  symbol_filters: HashMap<String, SymbolFilters>,
+}
+
+impl BinanceSpotExchange {
+  pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    BinanceSpotExchange {
+      rest: BinanceRestClient::new(base_url, api_key, api_secret),
+      symbol_filters: HashMap::new(),
+    }
+  }
+
+  /// Record REST latency and clock samples into a shared telemetry handle
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    self.rest.telemetry = telemetry;
+    self
+  }
+
+  async fn ensure_filters(&mut self, symbol: &str) -> Result<(), TradingError> {
+    if self.symbol_filters.contains_key(symbol) { return Ok(()); }
+    let v = self.rest.public_get("/api/v3/exchangeInfo", &format!("symbol={}", symbol)).await?;
+    let filters = exchange_info_symbol(&v, symbol)
+      .map(SymbolFilters::from_exchange_info)
+      .unwrap_or_default();
+    self.symbol_filters.insert(symbol.to_string(), filters);
+    Ok(())
+  }
+
+  /// Free + locked amount per asset from GET /api/v3/account
+  async fn account_balances(&self) -> Result<HashMap<String, Decimal>, TradingError> {
+    let body = self.rest.signed_request(reqwest::Method::GET, "/api/v3/account", &[], None).await?;
+    Ok(parse_balances(&body))
+  }
+}
+
+fn parse_balances(account: &serde_json::Value) -> HashMap<String, Decimal> {
+  account.get("balances").and_then(|b| b.as_array()).into_iter().flatten()
+    .filter_map(|b| {
+      let asset = b.get("asset")?.as_str()?;
+      Some((asset.to_string(), parse_decimal(b.get("free")) + parse_decimal(b.get("locked"))))
+    })
+    .collect()
+}
+
+/// Spot order params by type (symbol/side/quantity are added by the caller)
+fn order_type_params(order: &Order) -> Vec<String> {
+  let mut params = Vec::new();
+  match order.order_type {
+    OrderType::Market | OrderType::VWAP | OrderType::TWAP => {
+      params.push("type=MARKET".to_string());
+    }
+    OrderType::Limit | OrderType::Iceberg => {
+      params.push("type=LIMIT".to_string());
+      params.push(format!("price={}", order.price));
+      // icebergQty requires GTC
+      let tif = if order.iceberg_qty.is_some() { "GTC" } else { order.time_in_force.as_str() };
+      params.push(format!("timeInForce={}", tif));
+      if let Some(ice) = order.iceberg_qty { params.push(format!("icebergQty={}", ice)); }
+    }
+    OrderType::StopLoss | OrderType::StopLimit => {
+      // STOP_LOSS_LIMIT if price provided, else STOP_LOSS (market on trigger)
+      if order.price > Decimal::ZERO {
+        params.push("type=STOP_LOSS_LIMIT".to_string());
+        params.push(format!("price={}", order.price));
+        params.push(format!("timeInForce={}", order.time_in_force));
+      } else {
+        params.push("type=STOP_LOSS".to_string());
+      }
+      let stop = order.stop_price.unwrap_or(order.price);
+      if stop > Decimal::ZERO { params.push(format!("stopPrice={}", stop)); }
+    }
+    OrderType::TrailingStop => {
+      // Spot trails via trailingDelta in BIPS (10-2000) on STOP_LOSS; stop_price is the activation price
+      params.push("type=STOP_LOSS".to_string());
+      let bips = (order.trailing_delta.unwrap_or(0.5) * 100.0).round().clamp(10.0, 2000.0);
+      params.push(format!("trailingDelta={}", bips));
+      if let Some(act) = order.stop_price { params.push(format!("stopPrice={}", act)); }
+    }
+  }
+  params
+}
+
+#[async_trait]
+impl Exchange for BinanceSpotExchange {
+  async fn submit_order(&mut self, order: Order) -> Result<OrderId, TradingError> {
+    let mut normalized_order = order;
+    normalized_order.symbol = spot_symbol(&normalized_order.symbol);
+    self.ensure_filters(&normalized_order.symbol).await?;
+    if let Some(filters) = self.symbol_filters.get(&normalized_order.symbol) {
+      filters.normalize(&mut normalized_order);
+    }
+    let side = match normalized_order.side { OrderSide::Buy => "BUY", OrderSide::Sell => "SELL" };
+    let mut params = vec![
+      format!("symbol={}", normalized_order.symbol),
+      format!("side={}", side),
+      format!("quantity={}", normalized_order.quantity),
+    ];
+    params.extend(order_type_params(&normalized_order));
+    if let Some(cid) = &normalized_order.client_order_id { params.push(format!("newClientOrderId={}", cid)); }
+    params.push("newOrderRespType=ACK".to_string());
+
+    let body = self.rest.signed_request(reqwest::Method::POST, "/api/v3/order", &params, None).await?;
+    let order_id = parse_order_id(&body)
+      .ok_or_else(|| TradingError::ParseError(format!("orderId missing in {}", body)))?;
+    self.rest.remember_symbol(&order_id.0, &normalized_order.symbol);
+    Ok(order_id)
+  }
+
+  async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    self.rest.signed_request(reqwest::Method::DELETE, "/api/v3/order", &params, Some(order_id)).await?;
+    Ok(())
+  }
+
+  async fn modify_order(&mut self, _order_id: &OrderId, _order: Order) -> Result<OrderId, TradingError> {
+    Err(TradingError::ExchangeError("modify not supported in connector".to_string()))
+  }
+
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    let body = self.rest.signed_request(reqwest::Method::GET, "/api/v3/order", &params, Some(order_id)).await?;
+    let status = body.get("status").and_then(|s| s.as_str())
+      .ok_or_else(|| TradingError::ParseError(format!("status missing in {}", body)))?;
+    Ok(parse_order_status(status))
+  }
+
+  /// Average price from the cumulative quote quantity
+  async fn get_order_fill(&self, order_id: &OrderId) -> Result<Option<OrderFill>, TradingError> {
+    let symbol = self.rest.symbol_for(order_id)?;
+    let params = [format!("symbol={}", symbol), format!("orderId={}", order_id.0)];
+    let body = self.rest.signed_request(reqwest::Method::GET, "/api/v3/order", &params, Some(order_id)).await?;
+    let executed = parse_decimal(body.get("executedQty"));
+    if executed <= Decimal::ZERO {
+      return Ok(None);
+    }
+    let quote = parse_decimal(body.get("cummulativeQuoteQty"));
+    Ok(Some(OrderFill { executed_quantity: executed, average_price: quote / executed }))
+  }
+
+  async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+    // without symbol: all symbols (weight 80)
+    let body = self.rest.signed_request(reqwest::Method::GET, "/api/v3/openOrders", &[], None).await?;
+    let orders: Vec<Order> = body.as_array().map(|list| list.iter().filter_map(parse_order).collect()).unwrap_or_default();
+    for order in &orders {
+      self.rest.remember_symbol(&order.id.0, &order.symbol);
+    }
+    Ok(orders)
+  }
+
+  async fn get_recent_trades(&self, _symbol: &str, _limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+    Ok(Vec::new())
+  }
+
+  async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+    let symbol = spot_symbol(symbol);
+    let json = self.rest.public_get("/api/v3/ticker/bookTicker", &format!("symbol={}", symbol)).await?;
+    let close = book_ticker_mid(&json);
+    Ok(MarketData { symbol, timestamp: self.rest.ts_with_offset().into(), open: close, high: close, low: close, close, volume: Decimal::ZERO })
+  }
+
+  async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+    let symbol = spot_symbol(symbol);
+    let limit = depth_limit(depth, &[5, 10, 20, 50, 100, 500, 1000, 5000]);
+    let json = self.rest.public_get("/api/v3/depth", &format!("symbol={}&limit={}", symbol, limit)).await?;
+    // spot depth has no event time
+    let mut book = OrderBook::new(symbol, self.rest.ts_with_offset(), levels_from_json(&json["bids"]), levels_from_json(&json["asks"]));
+    book.truncate(depth);
+    Ok(book)
+  }
+
+  /// Klines oldest first; `limit` is capped at 1000 by the exchange
+  async fn get_historical_data(&self, symbol: &str, interval: &str, start_time: i64, end_time: Option<i64>, limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
+    let symbol = spot_symbol(symbol);
+    let mut query = format!("symbol={}&interval={}&startTime={}", symbol, interval, start_time);
+    if let Some(end) = end_time { query.push_str(&format!("&endTime={}", end)); }
+    query.push_str(&format!("&limit={}", limit.unwrap_or(500).min(1000)));
+    let json = self.rest.public_get("/api/v3/klines", &query).await?;
+    Ok(json.as_array().map(|rows| rows.iter().filter_map(|k| parse_kline(&symbol, k)).collect()).unwrap_or_default())
+  }
+
+  async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+    let spot = spot_symbol(symbol);
+    let v = self.rest.public_get("/api/v3/exchangeInfo", &format!("symbol={}", spot)).await?;
+    exchange_info_symbol(&v, &spot)
+      .map(|s| SymbolFilters::from_exchange_info(s).symbol_info(symbol))
+      .ok_or_else(|| TradingError::DataNotFound(format!("symbol info {}", symbol)))
+  }
+
+  async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+    Ok(self.account_balances().await?.get(&asset.to_ascii_uppercase()).copied().unwrap_or_default())
+  }
+
+  fn capabilities(&self) -> ExchangeCapabilities {
+    // Spot: native icebergQty, no hedge mode, VWAP/TWAP are client-side
+    ExchangeCapabilities {
+      order_types: vec![OrderType::Market, OrderType::Limit, OrderType::StopLoss, OrderType::StopLimit, OrderType::TrailingStop, OrderType::Iceberg],
+      time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string()],
+      hedge_mode: false,
+      batch_orders: false,
+      native_iceberg: true,
+      native_twap: false,
+      native_vwap: false,
+    }
+  }
+
+  async fn sync_time(&mut self) -> Result<(), TradingError> {
+    self.rest.sync_time("/api/v3/time").await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+
+  #[test]
+  fn test_spot_symbols_params_and_parsing() {
+    assert_eq!(spot_symbol("BTC/USDT"), "BTCUSDT");
+    assert_eq!(spot_symbol("eth-usdt"), "ETHUSDT");
+
+    let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Iceberg, dec!(1), dec!(60_000)).with_iceberg_qty(dec!(0.1));
+    assert_eq!(order_type_params(&order), vec!["type=LIMIT", "price=60000", "timeInForce=GTC", "icebergQty=0.1"]);
+    let trailing = Order::new("BTCUSDT", OrderSide::Sell, OrderType::TrailingStop, dec!(1), dec!(0)).with_trailing_delta(1.5);
+    assert_eq!(order_type_params(&trailing), vec!["type=STOP_LOSS", "trailingDelta=150"]);
+
+    let account = serde_json::json!({"balances": [
+      {"asset": "BTC", "free": "0.50000000", "locked": "0.25000000"},
+      {"asset": "USDT", "free": "1000.00", "locked": "0.00"}
+    ]});
+    let balances = parse_balances(&account);
+    assert_eq!(balances["BTC"], dec!(0.75));
+    assert_eq!(balances["USDT"], dec!(1000));
+
+    let kline = serde_json::json!([1_700_000_000_000_i64, "100.0", "110.0", "95.0", "105.0", "12.5", 1_700_000_059_999_i64]);
+    let candle = parse_kline("BTCUSDT", &kline).unwrap();
+    assert_eq!((candle.timestamp.as_millis(), candle.high, candle.close, candle.volume), (1_700_000_000_000, dec!(110), dec!(105), dec!(12.5)));
+
+    let open = serde_json::json!({
+      "orderId": 28, "symbol": "BTCUSDT", "status": "NEW", "type": "STOP_LOSS", "side": "SELL",
+      "price": "0.0", "origQty": "1.0", "stopPrice": "0.0", "trailingDelta": 150, "timeInForce": "GTC"
+    });
+    let parsed = parse_order(&open).unwrap();
+    assert_eq!(parsed.order_type, OrderType::TrailingStop);
+    assert_eq!(parsed.trailing_delta, Some(1.5));
+  }
+}
@@ -0,0 +1,299 @@
+use futures_util::StreamExt;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+
+use crate::config::ExchangeKind;
+use crate::error::TradingError;
+use crate::exchange::binance_common::{parse_decimal, parse_f64, parse_order_id, parse_order_status, BinanceRestClient};
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::models::decimal::Decimal;
+use crate::models::fee::Liquidity;
+use crate::models::order::{OrderSide, OrderUpdate};
+use crate::models::position::Position;
+
+/// Event pushed by the user-data stream
+#[derive(Debug, Clone)]
+pub enum UserDataEvent {
+  /// executionReport (spot) / ORDER_TRADE_UPDATE (futures)
+  Order(OrderUpdate),
+  /// ACCOUNT_UPDATE position leg (futures); hedge-mode legs carry `SYMBOL:LONG` / `SYMBOL:SHORT` as symbol
+  Position(Position),
+  /// Wallet balance change (spot: free + locked, futures: wallet balance)
+  Balance { asset: String, balance: f64 },
+  /// The listenKey is gone; the stream reconnects with a fresh key
+  ListenKeyExpired,
+}
+
+/// Binance user-data stream: creates and keeps alive a listenKey, parses order and account
+/// events and fans them out on a broadcast channel while caching the latest positions/balances
+#[derive(Clone)]
+pub struct BinanceUserDataStream {
+  rest: Arc<BinanceRestClient>,
+  kind: ExchangeKind,
+  ws_url: String,
+  keepalive_interval: Duration,
+  reconnect_interval: Duration,
+  events: broadcast::Sender<UserDataEvent>,
+  positions: Arc<RwLock<HashMap<String, Position>>>,
+  balances: Arc<RwLock<HashMap<String, f64>>>,
+}
+
+impl BinanceUserDataStream {
+  pub fn new(kind: ExchangeKind, base_url: impl Into<String>, ws_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+    let (events, _) = broadcast::channel(1024);
+    BinanceUserDataStream {
+      // listenKey endpoints only need the API key header
+      rest: Arc::new(BinanceRestClient::new(base_url, api_key, "")),
+      kind,
+      ws_url: ws_url.into().trim_end_matches('/').to_string(),
+      keepalive_interval: Duration::from_secs(30 * 60),
+      reconnect_interval: Duration::from_secs(5),
+      events,
+      positions: Arc::new(RwLock::new(HashMap::new())),
+      balances: Arc::new(RwLock::new(HashMap::new())),
+    }
+  }
+
+  pub fn with_keepalive_interval(mut self, interval: Duration) -> Self {
+    self.keepalive_interval = interval;
+    self
+  }
+
+  /// Share the connector telemetry so listenKey calls and event lag show up in /health/exchange
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    if let Some(rest) = Arc::get_mut(&mut self.rest) {
+      rest.telemetry = telemetry;
+    }
+    self
+  }
+
+  pub fn subscribe(&self) -> broadcast::Receiver<UserDataEvent> {
+    self.events.subscribe()
+  }
+
+  /// Latest positions keyed by symbol (hedge-mode legs as `SYMBOL:LONG` / `SYMBOL:SHORT`)
+  pub async fn positions(&self) -> HashMap<String, Position> {
+    self.positions.read().await.clone()
+  }
+
+  pub async fn balances(&self) -> HashMap<String, f64> {
+    self.balances.read().await.clone()
+  }
+
+  /// Connector name used for connection telemetry and alerts
+  fn connector_name(&self) -> String {
+    format!("user_stream:{:?}", self.kind)
+  }
+
+  fn listen_key_path(&self) -> Result<&'static str, TradingError> {
+    match self.kind {
+      ExchangeKind::BinanceFutures => Ok("/fapi/v1/listenKey"),
+      ExchangeKind::BinanceSpot => Ok("/api/v3/userDataStream"),
+      ExchangeKind::Bybit | ExchangeKind::Okx | ExchangeKind::Kraken | ExchangeKind::Fix => Err(TradingError::InvalidParameter("listenKey user stream is Binance only".to_string())),
+    }
+  }
+
+  async fn create_listen_key(&self) -> Result<String, TradingError> {
+    let body = self.rest.api_key_request(reqwest::Method::POST, self.listen_key_path()?, "").await?;
+    body.get("listenKey").and_then(Value::as_str).map(str::to_string)
+      .ok_or_else(|| TradingError::ExchangeError(format!("listenKey missing in response: {}", body)))
+  }
+
+  async fn keepalive(&self, listen_key: &str) -> Result<(), TradingError> {
+    self.rest.api_key_request(reqwest::Method::PUT, self.listen_key_path()?, &format!("listenKey={}", listen_key)).await.map(|_| ())
+  }
+
+  /// Run the stream in the background, reconnecting with a new listenKey on expiry or error
+  pub fn start(&self) -> JoinHandle<()> {
+    let stream = self.clone();
+    tokio::spawn(async move {
+      loop {
+        let connector = stream.connector_name();
+        match stream.run_session().await {
+          Ok(()) => log::info!("user data stream: listenKey expired, reconnecting"),
+          Err(e) => {
+            log::warn!("user data stream error: {}", e);
+            stream.rest.telemetry.record_connection(&connector, false, &e.to_string());
+          }
+        }
+        tokio::time::sleep(stream.reconnect_interval).await;
+      }
+    })
+  }
+
+  async fn run_session(&self) -> Result<(), TradingError> {
+    let listen_key = self.create_listen_key().await?;
+    let (ws, _) = connect_async(format!("{}/{}", self.ws_url, listen_key)).await
+      .map_err(|e| TradingError::ExchangeError(format!("user data stream connect failed: {}", e)))?;
+    let (_write, mut read) = ws.split();
+    log::info!("user data stream connected ({:?})", self.kind);
+    self.rest.telemetry.record_connection(&self.connector_name(), true, "listenKey session");
+
+    let mut keepalive = tokio::time::interval(self.keepalive_interval);
+    keepalive.tick().await;
+    loop {
+      tokio::select! {
+        _ = keepalive.tick() => {
+          if let Err(e) = self.keepalive(&listen_key).await {
+            log::warn!("listenKey keepalive failed: {}", e);
+          }
+        }
+        msg = read.next() => {
+          let text = match msg {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(_))) | None => return Err(TradingError::ExchangeError("user data stream closed".into())),
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(TradingError::ExchangeError(format!("user data stream error: {}", e))),
+          };
+          let Ok(json) = serde_json::from_str::<Value>(&text) else { continue };
+          if let Some(event_ts) = json.get("E").and_then(Value::as_i64) {
+            self.rest.telemetry.record_stream_lag("userData", event_ts, chrono::Utc::now().timestamp_millis());
+          }
+          for event in parse_user_events(&json) {
+            let expired = matches!(event, UserDataEvent::ListenKeyExpired);
+            self.apply(&event).await;
+            let _ = self.events.send(event);
+            if expired {
+              return Ok(());
+            }
+          }
+        }
+      }
+    }
+  }
+
+  async fn apply(&self, event: &UserDataEvent) {
+    match event {
+      UserDataEvent::Position(position) => {
+        let mut positions = self.positions.write().await;
+        if position.quantity.is_zero() {
+          positions.remove(&position.symbol);
+        } else {
+          positions.insert(position.symbol.clone(), position.clone());
+        }
+      }
+      UserDataEvent::Balance { asset, balance } => {
+        self.balances.write().await.insert(asset.clone(), *balance);
+      }
+      UserDataEvent::Order(_) | UserDataEvent::ListenKeyExpired => {}
+    }
+  }
+}
+
+/// Parse one user-data message into events (spot and futures payloads)
+pub fn parse_user_events(json: &Value) -> Vec<UserDataEvent> {
+  match json.get("e").and_then(Value::as_str).unwrap_or("") {
+    "executionReport" => parse_order_update(json).map(UserDataEvent::Order).into_iter().collect(),
+    "ORDER_TRADE_UPDATE" => json.get("o").and_then(parse_order_update).map(UserDataEvent::Order).into_iter().collect(),
+    "ACCOUNT_UPDATE" => {
+      let Some(account) = json.get("a") else { return Vec::new() };
+      let mut events: Vec<UserDataEvent> = account.get("B").and_then(Value::as_array).into_iter().flatten()
+        .filter_map(|b| Some(UserDataEvent::Balance { asset: b.get("a")?.as_str()?.to_string(), balance: parse_f64(b.get("wb")) }))
+        .collect();
+      events.extend(account.get("P").and_then(Value::as_array).into_iter().flatten().filter_map(parse_position).map(UserDataEvent::Position));
+      events
+    }
+    "outboundAccountPosition" => json.get("B").and_then(Value::as_array).into_iter().flatten()
+      .filter_map(|b| Some(UserDataEvent::Balance {
+        asset: b.get("a")?.as_str()?.to_string(),
+        balance: parse_f64(b.get("f")) + parse_f64(b.get("l")),
+      }))
+      .collect(),
+    "listenKeyExpired" => vec![UserDataEvent::ListenKeyExpired],
+    _ => Vec::new(),
+  }
+}
+
+// executionReport / ORDER_TRADE_UPDATE.o share field names: i, c, s, S, X, L, l, z, n, N, m, T
+fn parse_order_update(v: &Value) -> Option<OrderUpdate> {
+  let order_id = parse_order_id(&serde_json::json!({ "orderId": v.get("i")? }))?;
+  Some(OrderUpdate {
+    order_id,
+    client_order_id: v.get("c").and_then(Value::as_str).filter(|c| !c.is_empty()).map(str::to_string),
+    symbol: v.get("s")?.as_str()?.to_string(),
+    side: match v.get("S")?.as_str()? { "BUY" => OrderSide::Buy, _ => OrderSide::Sell },
+    status: parse_order_status(v.get("X")?.as_str()?),
+    last_fill_price: parse_decimal(v.get("L")),
+    last_fill_quantity: parse_decimal(v.get("l")),
+    cumulative_quantity: parse_decimal(v.get("z")),
+    fee: parse_decimal(v.get("n")),
+    fee_asset: v.get("N").and_then(Value::as_str).map(str::to_string),
+    liquidity: v.get("m").and_then(Value::as_bool).filter(|_| parse_decimal(v.get("l")) > Decimal::ZERO)
+      .map(|maker| if maker { Liquidity::Maker } else { Liquidity::Taker }),
+    timestamp: v.get("T").or_else(|| v.get("E")).and_then(Value::as_i64).unwrap_or_else(|| chrono::Utc::now().timestamp_millis()),
+  })
+}
+
+// ACCOUNT_UPDATE.a.P leg: s, pa (signed amount), ep (entry), up (unrealized pnl), ps (position side)
+fn parse_position(p: &Value) -> Option<Position> {
+  let symbol = p.get("s")?.as_str()?;
+  let quantity = parse_decimal(p.get("pa"));
+  let entry_price = parse_decimal(p.get("ep"));
+  let symbol = match p.get("ps").and_then(Value::as_str) {
+    Some(side @ ("LONG" | "SHORT")) => format!("{}:{}", symbol, side),
+    _ => symbol.to_string(),
+  };
+  let mut position = Position::new(symbol, quantity, entry_price);
+  position.unrealized_pnl = parse_decimal(p.get("up"));
+  if !quantity.is_zero() {
+    position.current_price = entry_price + position.unrealized_pnl / quantity;
+  }
+  Some(position)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+  use crate::models::order::{OrderId, OrderStatus};
+
+  #[tokio::test]
+  async fn test_parse_user_events() {
+    let futures_fill = serde_json::json!({
+      "e": "ORDER_TRADE_UPDATE", "E": 1700000000100i64, "T": 1700000000090i64,
+      "o": {"s": "BTCUSDT", "c": "strat-1", "S": "BUY", "o": "LIMIT", "X": "PARTIALLY_FILLED", "i": 8886774,
+            "l": "0.010", "z": "0.015", "L": "42000.5", "n": "0.168", "N": "USDT", "T": 1700000000090i64}
+    });
+    let events = parse_user_events(&futures_fill);
+    let UserDataEvent::Order(update) = &events[0] else { panic!("expected order update") };
+    assert_eq!(update.order_id, OrderId("8886774".into()));
+    assert_eq!(update.client_order_id.as_deref(), Some("strat-1"));
+    assert_eq!(update.status, OrderStatus::PartiallyFilled);
+    assert!(update.is_fill());
+    assert_eq!((update.last_fill_price, update.cumulative_quantity), (dec!(42000.5), dec!(0.015)));
+
+    let spot_new = serde_json::json!({
+      "e": "executionReport", "E": 1, "s": "ETHUSDT", "c": "abc", "S": "SELL", "X": "NEW", "i": 12,
+      "l": "0.00000000", "z": "0.00000000", "L": "0.00000000", "n": "0", "N": null, "T": 2
+    });
+    let UserDataEvent::Order(update) = &parse_user_events(&spot_new)[0] else { panic!("expected order update") };
+    assert_eq!((update.side.clone(), update.status.clone(), update.is_fill()), (OrderSide::Sell, OrderStatus::New, false));
+
+    let account = serde_json::json!({
+      "e": "ACCOUNT_UPDATE", "E": 3,
+      "a": {"m": "ORDER", "B": [{"a": "USDT", "wb": "1000.5", "cw": "1000.5"}],
+            "P": [{"s": "BTCUSDT", "pa": "-0.5", "ep": "40000", "up": "-100", "ps": "BOTH"},
+                  {"s": "ETHUSDT", "pa": "0", "ep": "0", "up": "0", "ps": "LONG"}]}
+    });
+    let stream = BinanceUserDataStream::new(ExchangeKind::BinanceFutures, "http://localhost", "ws://localhost", "key");
+    let events = parse_user_events(&account);
+    assert_eq!(events.len(), 3);
+    for event in &events {
+      stream.apply(event).await;
+    }
+    let positions = stream.positions().await;
+    assert_eq!(positions.len(), 1);
+    let btc = &positions["BTCUSDT"];
+    assert_eq!((btc.quantity, btc.current_price, btc.unrealized_pnl), (dec!(-0.5), dec!(40200), dec!(-100)));
+    assert_eq!(stream.balances().await["USDT"], 1000.5);
+
+    let spot_balance = serde_json::json!({"e": "outboundAccountPosition", "E": 4, "B": [{"a": "BTC", "f": "1.5", "l": "0.5"}]});
+    assert!(matches!(&parse_user_events(&spot_balance)[0], UserDataEvent::Balance { asset, balance } if asset == "BTC" && *balance == 2.0));
+    assert!(matches!(parse_user_events(&serde_json::json!({"e": "listenKeyExpired", "E": 5}))[0], UserDataEvent::ListenKeyExpired));
+  }
+}
@@ -0,0 +1,537 @@
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+use crate::error::TradingError;
+use crate::exchange::binance_common::{parse_decimal, parse_f64, SymbolFilters};
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::models::funding::FundingRate;
+use crate::models::market_data::MarketData;
+use crate::models::order_book::{levels_from_json, OrderBook};
+use crate::models::position::Position;
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
+use crate::models::timestamp::Timestamp;
+use crate::models::trade::Trade;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Contract category of USDT-margined perpetuals in the V5 API
+const CATEGORY: &str = "linear";
+/// retCode for an unknown order or one that already finished
+const ORDER_NOT_EXISTS: i64 = 110001;
+/// retCodes returned when a leverage/position/margin setting is already in place
+const NOT_MODIFIED: [i64; 3] = [110043, 110025, 110026];
+
+/// Bybit V5 REST connector for USDT perpetual futures (category=linear)
+pub struct BybitExchange {
+  base_url: String,
+  api_key: String,
+  api_secret: String,
+  http: reqwest::Client,
+  recv_window_ms: u64,
+  min_interval_ms: u64,
+  last_request_ms: AtomicI64,
+  time_offset_ms: AtomicI64,
+  telemetry: ExchangeTelemetry,
+  /// orderId -> symbol; V5 order endpoints need both
+  order_symbols: Mutex<HashMap<String, String>>,
+  symbol_filters: HashMap<String, SymbolFilters>,
+  /// last leverage per symbol, re-sent when switching margin mode
+  leverage: HashMap<String, u32>,
+}
+
+impl BybitExchange {
+  pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    BybitExchange {
+      base_url: base_url.into(),
+      api_key: api_key.into(),
+      api_secret: api_secret.into(),
+      http: reqwest::Client::new(),
+      recv_window_ms: 5000,
+      min_interval_ms: 50,
+      last_request_ms: AtomicI64::new(0),
+      time_offset_ms: AtomicI64::new(0),
+      telemetry: ExchangeTelemetry::default(),
+      order_symbols: Mutex::new(HashMap::new()),
+      symbol_filters: HashMap::new(),
+      leverage: HashMap::new(),
+    }
+  }
+
+  /// Record REST latency and clock samples into a shared telemetry handle
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    self.telemetry = telemetry;
+    self
+  }
+
+  fn ts_with_offset(&self) -> i64 {
+    chrono::Utc::now().timestamp_millis() + self.time_offset_ms.load(Ordering::SeqCst)
+  }
+
+  /// V5 signature: hex HMAC-SHA256 of `timestamp + apiKey + recvWindow + payload`,
+  /// where payload is the query string (GET) or the JSON body (POST)
+  fn sign(&self, timestamp: i64, payload: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes()).unwrap();
+    mac.update(format!("{}{}{}{}", timestamp, self.api_key, self.recv_window_ms, payload).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+  }
+
+  async fn throttle(&self) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let elapsed = (now - self.last_request_ms.load(Ordering::SeqCst)).max(0) as u64;
+    if elapsed < self.min_interval_ms {
+      tokio::time::sleep(std::time::Duration::from_millis(self.min_interval_ms - elapsed)).await;
+    }
+    self.last_request_ms.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+  }
+
+  /// Send a request and unwrap the `{retCode, retMsg, result}` envelope.
+  /// Unknown orders map to OrderNotFound when `order_id` is given
+  async fn send(&self, endpoint: &str, req: reqwest::RequestBuilder, order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    self.throttle().await;
+    let started = std::time::Instant::now();
+    let res = req.send().await;
+    let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
+    self.telemetry.record_request(endpoint, started.elapsed().as_secs_f64() * 1000.0, ok);
+    let res = res.map_err(|e| TradingError::ExchangeError(format!("{} http error: {}", endpoint, e)))?;
+    let status = res.status();
+    let body = res.json::<serde_json::Value>().await
+      .map_err(|e| TradingError::ExchangeError(format!("{} parse error: {}", endpoint, e)))?;
+    let code = body.get("retCode").and_then(|c| c.as_i64()).unwrap_or(-1);
+    match (code, order_id) {
+      (0, _) if status.is_success() => Ok(body.get("result").cloned().unwrap_or(serde_json::Value::Null)),
+      (ORDER_NOT_EXISTS, Some(id)) => Err(TradingError::OrderNotFound(id.clone())),
+      _ => Err(TradingError::ExchangeError(format!("{} failed: {} {}", endpoint, status, body))),
+    }
+  }
+
+  async fn public_get(&self, path: &str, query: &str) -> Result<serde_json::Value, TradingError> {
+    let url = format!("{}{}?{}", self.base_url, path, query);
+    self.send(&format!("GET {}", path), self.http.get(url), None).await
+  }
+
+  async fn signed_get(&self, path: &str, query: &str, order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    let ts = self.ts_with_offset();
+    let req = self.http.get(format!("{}{}?{}", self.base_url, path, query));
+    self.send(&format!("GET {}", path), self.auth_headers(req, ts, query), order_id).await
+  }
+
+  async fn signed_post(&self, path: &str, body: &serde_json::Value, order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    let ts = self.ts_with_offset();
+    let payload = body.to_string();
+    let req = self.http.post(format!("{}{}", self.base_url, path))
+      .header("Content-Type", "application/json")
+      .body(payload.clone());
+    self.send(&format!("POST {}", path), self.auth_headers(req, ts, &payload), order_id).await
+  }
+
+  /// Settings calls succeed when the value is already set
+  async fn signed_post_idempotent(&self, path: &str, body: &serde_json::Value) -> Result<(), TradingError> {
+    match self.signed_post(path, body, None).await {
+      Err(TradingError::ExchangeError(msg)) if NOT_MODIFIED.iter().any(|code| msg.contains(&format!("\"retCode\":{}", code))) => Ok(()),
+      other => other.map(|_| ()),
+    }
+  }
+
+  fn auth_headers(&self, req: reqwest::RequestBuilder, ts: i64, payload: &str) -> reqwest::RequestBuilder {
+    req.header("X-BAPI-API-KEY", &self.api_key)
+      .header("X-BAPI-TIMESTAMP", ts.to_string())
+      .header("X-BAPI-RECV-WINDOW", self.recv_window_ms.to_string())
+      .header("X-BAPI-SIGN", self.sign(ts, payload))
+  }
+
+  fn remember_symbol(&self, order_id: &str, symbol: &str) {
+    self.order_symbols.lock().unwrap_or_else(|e| e.into_inner()).insert(order_id.to_string(), symbol.to_string());
+  }
+
+  fn symbol_for(&self, order_id: &OrderId) -> Result<String, TradingError> {
+    self.order_symbols.lock().unwrap_or_else(|e| e.into_inner())
+      .get(&order_id.0)
+      .cloned()
+      .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))
+  }
+
+  async fn ensure_filters(&mut self, symbol: &str) -> Result<(), TradingError> {
+    if self.symbol_filters.contains_key(symbol) { return Ok(()); }
+    let result = self.public_get("/v5/market/instruments-info", &format!("category={}&symbol={}", CATEGORY, symbol)).await?;
+    let filters = first(&result).map(instrument_filters).unwrap_or_default();
+    self.symbol_filters.insert(symbol.to_string(), filters);
+    Ok(())
+  }
+
+  async fn ticker(&self, symbol: &str) -> Result<serde_json::Value, TradingError> {
+    let result = self.public_get("/v5/market/tickers", &format!("category={}&symbol={}", CATEGORY, symbol)).await?;
+    first(&result).cloned().ok_or_else(|| TradingError::DataNotFound(format!("ticker {}", symbol)))
+  }
+}
+
+/// First entry of a V5 `result.list`
+fn first(result: &serde_json::Value) -> Option<&serde_json::Value> {
+  result.get("list").and_then(|l| l.as_array()).and_then(|l| l.first())
+}
+
+fn parse_ms(v: Option<&serde_json::Value>) -> Option<i64> {
+  v.and_then(|x| x.as_str().and_then(|s| s.parse().ok()).or_else(|| x.as_i64()))
+}
+
+/// Kline interval: "1m" -> "1", "4h" -> "240", "1d" -> "D", "1w" -> "W", "1M" -> "M"
+pub fn bybit_interval(interval: &str) -> Result<String, TradingError> {
+  let (num, unit) = interval.split_at(interval.len().saturating_sub(1));
+  let n: u32 = num.parse().map_err(|_| TradingError::InvalidParameter(format!("Invalid interval: {}", interval)))?;
+  let mapped = match (unit, n) {
+    ("m", 1 | 3 | 5 | 15 | 30) => n.to_string(),
+    ("h", 1 | 2 | 4 | 6 | 12) => (n * 60).to_string(),
+    ("d", 1) => "D".to_string(),
+    ("w", 1) => "W".to_string(),
+    ("M", 1) => "M".to_string(),
+    _ => return Err(TradingError::InvalidParameter(format!("Interval not supported by Bybit: {}", interval))),
+  };
+  Ok(mapped)
+}
+
+pub(crate) fn parse_order_status(status: &str) -> OrderStatus {
+  match status {
+    // Untriggered: conditional order waiting for its trigger price
+    "New" | "Untriggered" | "Triggered" => OrderStatus::New,
+    "PartiallyFilled" => OrderStatus::PartiallyFilled,
+    "Filled" => OrderStatus::Filled,
+    "Cancelled" | "PartiallyFilledCanceled" | "Deactivated" => OrderStatus::Cancelled,
+    "Rejected" => OrderStatus::Rejected,
+    _ => OrderStatus::Expired,
+  }
+}
+
+/// Map a V5 order object (realtime / history) to an Order
+pub(crate) fn parse_order(v: &serde_json::Value) -> Option<Order> {
+  let side = match v.get("side")?.as_str()? { "Buy" => OrderSide::Buy, _ => OrderSide::Sell };
+  let trigger = parse_decimal(v.get("triggerPrice"));
+  let order_type = match (v.get("orderType")?.as_str()?, trigger > Decimal::ZERO) {
+    ("Limit", true) => OrderType::StopLimit,
+    ("Market", true) => OrderType::StopLoss,
+    ("Limit", false) => OrderType::Limit,
+    _ => OrderType::Market,
+  };
+  let mut order = Order::new(v.get("symbol")?.as_str()?, side, order_type, parse_decimal(v.get("qty")), parse_decimal(v.get("price")));
+  order.id = OrderId(v.get("orderId")?.as_str()?.to_string());
+  order.client_order_id = v.get("orderLinkId").and_then(|c| c.as_str()).filter(|c| !c.is_empty()).map(str::to_string);
+  order.created_at = parse_ms(v.get("createdTime")).map(Timestamp::from_millis).unwrap_or(order.created_at);
+  if let Some(tif) = v.get("timeInForce").and_then(|t| t.as_str()) {
+    order.time_in_force = if tif == "PostOnly" { "GTX".to_string() } else { tif.to_string() };
+  }
+  if trigger > Decimal::ZERO { order.stop_price = Some(trigger); }
+  order.reduce_only = v.get("reduceOnly").and_then(|r| r.as_bool());
+  order.position_side = match v.get("positionIdx").and_then(|p| p.as_i64()) {
+    Some(1) => Some("LONG".to_string()),
+    Some(2) => Some("SHORT".to_string()),
+    _ => None,
+  };
+  Some(order)
+}
+
+/// Map a kline row `[startTime, open, high, low, close, volume, turnover]` (numeric strings)
+pub(crate) fn parse_kline(symbol: &str, v: &serde_json::Value) -> Option<MarketData> {
+  let k = v.as_array()?;
+  Some(MarketData {
+    symbol: symbol.to_string(),
+    timestamp: Timestamp::from_millis(parse_ms(k.first())?),
+    open: parse_decimal(k.get(1)),
+    high: parse_decimal(k.get(2)),
+    low: parse_decimal(k.get(3)),
+    close: parse_decimal(k.get(4)),
+    volume: parse_decimal(k.get(5)),
+  })
+}
+
+/// Tick size / qty step / minimums from an instruments-info entry
+fn instrument_filters(v: &serde_json::Value) -> SymbolFilters {
+  let lot = v.get("lotSizeFilter");
+  SymbolFilters {
+    tick_size: parse_decimal(v.get("priceFilter").and_then(|p| p.get("tickSize"))),
+    step_size: parse_decimal(lot.and_then(|l| l.get("qtyStep"))),
+    min_qty: parse_decimal(lot.and_then(|l| l.get("minOrderQty"))),
+    min_notional: parse_decimal(lot.and_then(|l| l.get("minNotionalValue"))),
+  }
+}
+
+/// One-way mode uses positionIdx 0; hedge mode 1 (long) / 2 (short)
+fn position_idx(position_side: Option<&str>) -> u8 {
+  match position_side {
+    Some("LONG") => 1,
+    Some("SHORT") => 2,
+    _ => 0,
+  }
+}
+
+/// V5 create-order body for an already normalized order
+pub(crate) fn order_body(order: &Order) -> serde_json::Value {
+  let side = match order.side { OrderSide::Buy => "Buy", OrderSide::Sell => "Sell" };
+  let mut body = serde_json::json!({
+    "category": CATEGORY,
+    "symbol": order.symbol,
+    "side": side,
+    "qty": order.quantity.to_string(),
+    "positionIdx": position_idx(order.position_side.as_deref()),
+  });
+  let limit = match order.order_type {
+    OrderType::Limit | OrderType::Iceberg | OrderType::StopLimit => true,
+    OrderType::StopLoss => order.price > Decimal::ZERO,
+    _ => false,
+  };
+  body["orderType"] = serde_json::json!(if limit { "Limit" } else { "Market" });
+  if limit {
+    body["price"] = serde_json::json!(order.price.to_string());
+    body["timeInForce"] = serde_json::json!(match order.time_in_force.as_str() { "GTX" => "PostOnly", tif => tif });
+  }
+  if matches!(order.order_type, OrderType::StopLoss | OrderType::StopLimit) {
+    if let Some(stop) = order.stop_price.filter(|s| *s > Decimal::ZERO) {
+      body["triggerPrice"] = serde_json::json!(stop.to_string());
+      // stop buys trigger on a rise, stop sells on a fall
+      body["triggerDirection"] = serde_json::json!(if order.side == OrderSide::Buy { 1 } else { 2 });
+    }
+  }
+  if order.reduce_only == Some(true) { body["reduceOnly"] = serde_json::json!(true); }
+  if let Some(cid) = &order.client_order_id { body["orderLinkId"] = serde_json::json!(cid); }
+  body
+}
+
+#[async_trait]
+impl Exchange for BybitExchange {
+  async fn submit_order(&mut self, order: Order) -> Result<OrderId, TradingError> {
+    self.ensure_filters(&order.symbol).await?;
+    let mut normalized = order;
+    if let Some(filters) = self.symbol_filters.get(&normalized.symbol) {
+      filters.normalize(&mut normalized);
+    }
+    let result = self.signed_post("/v5/order/create", &order_body(&normalized), None).await?;
+    let order_id = result.get("orderId").and_then(|id| id.as_str()).map(|id| OrderId(id.to_string()))
+      .ok_or_else(|| TradingError::ParseError(format!("orderId missing in {}", result)))?;
+    self.remember_symbol(&order_id.0, &normalized.symbol);
+    Ok(order_id)
+  }
+
+  async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+    let symbol = self.symbol_for(order_id)?;
+    let body = serde_json::json!({"category": CATEGORY, "symbol": symbol, "orderId": order_id.0});
+    self.signed_post("/v5/order/cancel", &body, Some(order_id)).await?;
+    Ok(())
+  }
+
+  /// Amends price/quantity in place; the order keeps its id
+  async fn modify_order(&mut self, order_id: &OrderId, order: Order) -> Result<OrderId, TradingError> {
+    let symbol = self.symbol_for(order_id)?;
+    let mut body = serde_json::json!({"category": CATEGORY, "symbol": symbol, "orderId": order_id.0, "qty": order.quantity.to_string()});
+    if order.price > Decimal::ZERO { body["price"] = serde_json::json!(order.price.to_string()); }
+    if let Some(stop) = order.stop_price { body["triggerPrice"] = serde_json::json!(stop.to_string()); }
+    self.signed_post("/v5/order/amend", &body, Some(order_id)).await?;
+    Ok(order_id.clone())
+  }
+
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+    let symbol = self.symbol_for(order_id)?;
+    let query = format!("category={}&symbol={}&orderId={}", CATEGORY, symbol, order_id.0);
+    // realtime covers open and recently closed orders; older ones are only in history
+    let mut result = self.signed_get("/v5/order/realtime", &query, Some(order_id)).await?;
+    if first(&result).is_none() {
+      result = self.signed_get("/v5/order/history", &query, Some(order_id)).await?;
+    }
+    let status = first(&result).and_then(|o| o.get("orderStatus")).and_then(|s| s.as_str())
+      .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))?;
+    Ok(parse_order_status(status))
+  }
+
+  async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+    // openOnly=0 (default) with settleCoin returns every active linear order
+    let result = self.signed_get("/v5/order/realtime", &format!("category={}&settleCoin=USDT", CATEGORY), None).await?;
+    let orders: Vec<Order> = result.get("list").and_then(|l| l.as_array())
+      .map(|list| list.iter().filter_map(parse_order).collect())
+      .unwrap_or_default();
+    for order in &orders {
+      self.remember_symbol(&order.id.0, &order.symbol);
+    }
+    Ok(orders)
+  }
+
+  async fn get_positions(&self) -> Result<Vec<Position>, TradingError> {
+    let result = self.signed_get("/v5/position/list", &format!("category={}&settleCoin=USDT", CATEGORY), None).await?;
+    let mut out = Vec::new();
+    for p in result.get("list").and_then(|l| l.as_array()).into_iter().flatten() {
+      let symbol = p.get("symbol").and_then(|s| s.as_str()).unwrap_or("");
+      let size = parse_decimal(p.get("size"));
+      if symbol.is_empty() || size.is_zero() { continue; }
+      let quantity = if p.get("side").and_then(|s| s.as_str()) == Some("Sell") { -size } else { size };
+      let entry_price = parse_decimal(p.get("avgPrice"));
+      let mut pos = Position::new(symbol.to_string(), quantity, entry_price);
+      pos.update_price(Some(parse_decimal(p.get("markPrice"))).filter(|m| *m > Decimal::ZERO).unwrap_or(entry_price));
+      out.push(pos);
+    }
+    Ok(out)
+  }
+
+  async fn get_recent_trades(&self, symbol: &str, limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+    let query = format!("category={}&symbol={}&limit={}", CATEGORY, symbol, limit.unwrap_or(60).min(1000));
+    let result = self.public_get("/v5/market/recent-trade", &query).await?;
+    Ok(result.get("list").and_then(|l| l.as_array()).into_iter().flatten()
+      .filter_map(|t| {
+        let side = match t.get("side")?.as_str()? { "Buy" => OrderSide::Buy, _ => OrderSide::Sell };
+        Some(Trade::new(t.get("execId")?.as_str()?, symbol, parse_decimal(t.get("price")), parse_decimal(t.get("size")), Timestamp::from_millis(parse_ms(t.get("time"))?), OrderId(String::new()), side))
+      })
+      .collect())
+  }
+
+  async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+    let t = self.ticker(symbol).await?;
+    let (bid, ask) = (parse_decimal(t.get("bid1Price")), parse_decimal(t.get("ask1Price")));
+    let close = if bid > Decimal::ZERO && ask > Decimal::ZERO { (bid + ask) / Decimal::TWO } else { parse_decimal(t.get("lastPrice")) };
+    if close <= Decimal::ZERO { return Err(TradingError::DataNotFound(format!("price {}", symbol))); }
+    Ok(MarketData { symbol: symbol.to_string(), timestamp: self.ts_with_offset().into(), open: close, high: close, low: close, close, volume: Decimal::ZERO })
+  }
+
+  async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+    let query = format!("category={}&symbol={}&limit={}", CATEGORY, symbol, depth.clamp(1, 500));
+    let result = self.public_get("/v5/market/orderbook", &query).await?;
+    let timestamp = result.get("ts").and_then(|v| v.as_i64()).unwrap_or_else(|| self.ts_with_offset());
+    let mut book = OrderBook::new(symbol, timestamp, levels_from_json(&result["b"]), levels_from_json(&result["a"]));
+    book.truncate(depth);
+    Ok(book)
+  }
+
+  async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+    let t = self.ticker(symbol).await?;
+    Ok(FundingRate {
+      symbol: symbol.to_string(),
+      mark_price: parse_f64(t.get("markPrice")),
+      index_price: parse_f64(t.get("indexPrice")),
+      funding_rate: parse_f64(t.get("fundingRate")),
+      next_funding_time: parse_ms(t.get("nextFundingTime")).unwrap_or_default(),
+      timestamp: self.ts_with_offset(),
+    })
+  }
+
+  /// Klines oldest first; `limit` is capped at 1000 by the exchange
+  async fn get_historical_data(&self, symbol: &str, interval: &str, start_time: i64, end_time: Option<i64>, limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
+    let mut query = format!("category={}&symbol={}&interval={}&start={}", CATEGORY, symbol, bybit_interval(interval)?, start_time);
+    if let Some(end) = end_time { query.push_str(&format!("&end={}", end)); }
+    query.push_str(&format!("&limit={}", limit.unwrap_or(200).min(1000)));
+    let result = self.public_get("/v5/market/kline", &query).await?;
+    // Bybit lists newest first
+    let mut candles: Vec<MarketData> = result.get("list").and_then(|l| l.as_array())
+      .map(|rows| rows.iter().filter_map(|k| parse_kline(symbol, k)).collect())
+      .unwrap_or_default();
+    candles.reverse();
+    Ok(candles)
+  }
+
+  async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+    let result = self.public_get("/v5/market/instruments-info", &format!("category={}&symbol={}", CATEGORY, symbol)).await?;
+    first(&result)
+      .map(|v| instrument_filters(v).symbol_info(symbol))
+      .ok_or_else(|| TradingError::DataNotFound(format!("symbol info {}", symbol)))
+  }
+
+  /// Wallet balance of `asset` in the unified trading account
+  async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+    let result = self.signed_get("/v5/account/wallet-balance", &format!("accountType=UNIFIED&coin={}", asset), None).await?;
+    Ok(first(&result)
+      .and_then(|account| account.get("coin")).and_then(|c| c.as_array())
+      .and_then(|coins| coins.iter().find(|c| c.get("coin").and_then(|n| n.as_str()) == Some(asset)))
+      .map(|c| parse_decimal(c.get("walletBalance")))
+      .unwrap_or_default())
+  }
+
+  async fn set_futures_leverage(&mut self, symbol: &str, leverage: u32) -> Result<(), TradingError> {
+    let body = serde_json::json!({"category": CATEGORY, "symbol": symbol, "buyLeverage": leverage.to_string(), "sellLeverage": leverage.to_string()});
+    self.signed_post_idempotent("/v5/position/set-leverage", &body).await?;
+    self.leverage.insert(symbol.to_string(), leverage);
+    Ok(())
+  }
+
+  /// mode 0 = merged single (one-way), 3 = both sides (hedge)
+  async fn set_futures_position_mode(&mut self, hedge: bool) -> Result<(), TradingError> {
+    let body = serde_json::json!({"category": CATEGORY, "coin": "USDT", "mode": if hedge { 3 } else { 0 }});
+    self.signed_post_idempotent("/v5/position/switch-mode", &body).await
+  }
+
+  /// tradeMode 0 = cross, 1 = isolated; the call must repeat the current leverage
+  async fn set_futures_margin_mode(&mut self, symbol: &str, isolated: bool) -> Result<(), TradingError> {
+    let leverage = self.leverage.get(symbol).copied().unwrap_or(10).to_string();
+    let body = serde_json::json!({"category": CATEGORY, "symbol": symbol, "tradeMode": if isolated { 1 } else { 0 }, "buyLeverage": leverage, "sellLeverage": leverage});
+    self.signed_post_idempotent("/v5/position/switch-isolated", &body).await
+  }
+
+  fn capabilities(&self) -> ExchangeCapabilities {
+    // conditional orders via triggerPrice; no iceberg, trailing stops are position-level only
+    ExchangeCapabilities {
+      order_types: vec![OrderType::Market, OrderType::Limit, OrderType::StopLoss, OrderType::StopLimit],
+      time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string(), "GTX".to_string()],
+      hedge_mode: true,
+      batch_orders: true,
+      native_iceberg: false,
+      native_twap: false,
+      native_vwap: false,
+    }
+  }
+
+  async fn sync_time(&mut self) -> Result<(), TradingError> {
+    let sent_at = chrono::Utc::now().timestamp_millis();
+    let result = self.public_get("/v5/market/time", "").await?;
+    let server_ms = result.get("timeNano").and_then(|t| t.as_str()).and_then(|t| t.parse::<i128>().ok()).map(|ns| (ns / 1_000_000) as i64)
+      .ok_or_else(|| TradingError::ParseError(format!("timeNano missing in {}", result)))?;
+    let received_at = chrono::Utc::now().timestamp_millis();
+    let rtt = (received_at - sent_at).max(0);
+    let offset = server_ms - (sent_at + rtt / 2);
+    self.time_offset_ms.store(offset, Ordering::SeqCst);
+    self.telemetry.record_clock_offset(offset, rtt as f64, received_at);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+
+  #[test]
+  fn test_order_mapping_and_signing() {
+    let order = Order::new("BTCUSDT", OrderSide::Sell, OrderType::StopLimit, dec!(0.01), dec!(59_000))
+      .with_stop_price(dec!(59_500))
+      .with_time_in_force("GTX")
+      .with_position_side("SHORT")
+      .with_client_order_id("c-7");
+    let body = order_body(&order);
+    assert_eq!(body["orderType"], "Limit");
+    assert_eq!(body["timeInForce"], "PostOnly");
+    assert_eq!(body["triggerPrice"], "59500");
+    assert_eq!(body["triggerDirection"], 2);
+    assert_eq!(body["positionIdx"], 2);
+    assert_eq!(body["orderLinkId"], "c-7");
+
+    let v = serde_json::json!({
+      "orderId": "1321003749386327552", "orderLinkId": "c-7", "symbol": "BTCUSDT", "side": "Sell",
+      "orderType": "Limit", "price": "59000", "qty": "0.01", "triggerPrice": "59500", "timeInForce": "PostOnly",
+      "orderStatus": "Untriggered", "reduceOnly": false, "positionIdx": 2, "createdTime": "1700000000000"
+    });
+    let parsed = parse_order(&v).unwrap();
+    assert_eq!(parsed.id.0, "1321003749386327552");
+    assert_eq!(parsed.order_type, OrderType::StopLimit);
+    assert_eq!((parsed.quantity, parsed.price, parsed.stop_price), (dec!(0.01), dec!(59_000), Some(dec!(59_500))));
+    assert_eq!(parsed.time_in_force, "GTX");
+    assert_eq!(parsed.position_side.as_deref(), Some("SHORT"));
+    assert_eq!(parse_order_status(v["orderStatus"].as_str().unwrap()), OrderStatus::New);
+    assert_eq!(parse_order_status("PartiallyFilledCanceled"), OrderStatus::Cancelled);
+
+    assert_eq!(bybit_interval("4h").unwrap(), "240");
+    assert_eq!(bybit_interval("1d").unwrap(), "D");
+    assert!(bybit_interval("7m").is_err());
+
+    // signature covers timestamp, key, recv window and payload
+    let exchange = BybitExchange::new("http://localhost", "key", "secret");
+    assert_eq!(exchange.sign(1_700_000_000_000, "category=linear"), "e6c3e971c517d999338172674f1c633b9016addf8f8c632372232076767b4c07");
+  }
+}
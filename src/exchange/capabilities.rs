@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::order::{Order, OrderType};
+
+/// Describes which order features a venue supports natively.
+/// Callers use it to pre-check orders and to substitute client-side behavior
+/// (e.g. a plain limit order driven by IcebergStrategy when native icebergs are unavailable).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExchangeCapabilities {
+    pub order_types: Vec<OrderType>,
+    pub time_in_force: Vec<String>,
+    pub hedge_mode: bool,
+    pub batch_orders: bool,
+    pub native_iceberg: bool,
+    /// Venue runs TWAP/VWAP parent orders server-side (algo endpoints)
+    #[serde(default)]
+    pub native_twap: bool,
+    #[serde(default)]
+    pub native_vwap: bool,
+}
+
+impl Default for ExchangeCapabilities {
+    /// Permissive descriptor: everything is accepted, as simulators do
+    fn default() -> Self {
+        ExchangeCapabilities {
+            order_types: vec![
+                OrderType::Market,
+                OrderType::Limit,
+                OrderType::StopLoss,
+                OrderType::StopLimit,
+                OrderType::TrailingStop,
+                OrderType::Iceberg,
+                OrderType::VWAP,
+                OrderType::TWAP,
+            ],
+            time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string()],
+            hedge_mode: true,
+            batch_orders: true,
+            native_iceberg: true,
+            native_twap: true,
+            native_vwap: true,
+        }
+    }
+}
+
+impl ExchangeCapabilities {
+    pub fn supports_order_type(&self, order_type: &OrderType) -> bool {
+        self.order_types.contains(order_type)
+    }
+
+    pub fn supports_time_in_force(&self, tif: &str) -> bool {
+        self.time_in_force.iter().any(|t| t.eq_ignore_ascii_case(tif))
+    }
+
+    /// Whether an execution algorithm can be delegated to the venue instead of split client-side
+    pub fn supports_native(&self, order_type: &OrderType) -> bool {
+        let native = match order_type {
+            OrderType::Iceberg => self.native_iceberg,
+            OrderType::TWAP => self.native_twap,
+            OrderType::VWAP => self.native_vwap,
+            _ => false,
+        };
+        native && self.supports_order_type(order_type)
+    }
+
+    /// Rewrite an order into a form the venue accepts, when a client-side equivalent exists.
+    /// - Iceberg without native support becomes a plain Limit (slicing is left to IcebergStrategy)
+    /// - VWAP/TWAP without native support become Market child orders
+    pub fn substitute(&self, mut order: Order) -> Order {
+        match order.order_type {
+            OrderType::Iceberg if !self.native_iceberg || !self.supports_order_type(&OrderType::Iceberg) => {
+                order.order_type = OrderType::Limit;
+                order.iceberg_qty = None;
+            }
+            OrderType::VWAP | OrderType::TWAP if !self.supports_order_type(&order.order_type) => {
+                order.order_type = OrderType::Market;
+            }
+            _ => {}
+        }
+        order
+    }
+
+    /// Fail fast with a descriptive error instead of an opaque exchange rejection
+    pub fn check_order(&self, order: &Order) -> Result<(), TradingError> {
+        if !self.supports_order_type(&order.order_type) {
+            return Err(TradingError::InvalidParameter(format!(
+                "Order type {:?} not supported by exchange", order.order_type
+            )));
+        }
+
+        let needs_tif = matches!(order.order_type, OrderType::Limit | OrderType::StopLimit | OrderType::Iceberg);
+        if needs_tif && !self.supports_time_in_force(&order.time_in_force) {
+            return Err(TradingError::InvalidParameter(format!(
+                "Time in force {} not supported by exchange", order.time_in_force
+            )));
+        }
+
+        if matches!(order.position_side.as_deref(), Some(side) if side != "BOTH") && !self.hedge_mode {
+            return Err(TradingError::InvalidParameter(
+                "Hedge mode position side not supported by exchange".to_string()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::OrderSide;
+
+    #[test]
+    fn test_substitute_and_check() {
+        let caps = ExchangeCapabilities {
+            order_types: vec![OrderType::Market, OrderType::Limit],
+            time_in_force: vec!["GTC".to_string()],
+            hedge_mode: false,
+            batch_orders: false,
+            native_iceberg: false,
+            native_twap: false,
+            native_vwap: false,
+        };
+        assert!(!caps.supports_native(&OrderType::Iceberg));
+        assert!(ExchangeCapabilities::default().supports_native(&OrderType::TWAP));
+
+        let iceberg = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(50000)).with_iceberg_qty(dec!(0.1));
+        let substituted = caps.substitute(iceberg);
+        assert_eq!(substituted.order_type, OrderType::Limit);
+        assert!(substituted.iceberg_qty.is_none());
+        assert!(caps.check_order(&substituted).is_ok());
+
+        let twap = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, dec!(1), dec!(0)).with_twap_params(60_000);
+        assert_eq!(caps.substitute(twap).order_type, OrderType::Market);
+
+        let ioc = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(50000)).with_time_in_force("IOC");
+        assert!(caps.check_order(&ioc).is_err());
+
+        let stop = Order::new("BTCUSDT", OrderSide::Sell, OrderType::StopLoss, dec!(1), dec!(0));
+        assert!(caps.check_order(&stop).is_err());
+
+        let hedged = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(1), dec!(0)).with_position_side("LONG");
+        assert!(caps.check_order(&hedged).is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use crate::error::TradingError;
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
 use crate::models::market_data::MarketData;
 use crate::models::order::{Order, OrderId, OrderStatus};
 use crate::models::trade::Trade;
@@ -36,10 +37,10 @@ impl Exchange for DryRunExchange {
   async fn get_recent_trades(&self, _symbol: &str, _limit: Option<usize>) -> Result<Vec<Trade>, TradingError> { Ok(vec![]) }
 
   async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
-    Ok(MarketData { symbol: symbol.to_string(), timestamp: Self::ts(), open: 0.0, high: 0.0, low: 0.0, close: 0.0, volume: 0.0 })
+    Ok(MarketData { symbol: symbol.to_string(), timestamp: Self::ts().into(), open: Decimal::ZERO, high: Decimal::ZERO, low: Decimal::ZERO, close: Decimal::ZERO, volume: Decimal::ZERO })
   }
 
   async fn get_historical_data(&self, _symbol: &str, _interval: &str, _start_time: i64, _end_time: Option<i64>, _limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> { Ok(vec![]) }
 
-  async fn get_balance(&self, _asset: &str) -> Result<f64, TradingError> { Ok(0.0) }
+  async fn get_balance(&self, _asset: &str) -> Result<Decimal, TradingError> { Ok(Decimal::ZERO) }
 }
@@ -0,0 +1,269 @@
+//! Fill simulation shared by `MockExchange` and the backtest engine
+//!
+//! Orders only reach the book after a configurable latency. Market orders and marketable limits
+//! then take liquidity at the prevailing price plus slippage, while resting limits wait until the
+//! market trades through their price and fill as maker at the limit. Each fill is capped at a share
+//! of the candle volume, so large orders stay partially filled across several candles. Maker and
+//! taker fees are charged separately, optionally from a [`FeeSchedule`] with per-symbol rates and
+//! 30-day volume tiers (the tier itself is tracked by the caller's `FeeLedger`). Taker slippage is either a flat fraction or a
+//! [`SlippageModel`] that scales with the candle range and the order's share of candle volume.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::decimal::{to_decimal, to_f64, Decimal};
+pub use crate::models::fee::Liquidity;
+use crate::models::fee::FeeSchedule;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+
+/// One simulated execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedFill {
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub liquidity: Liquidity,
+    /// Fee in quote currency
+    pub fee: Decimal,
+}
+
+/// How far taker fills trade from the reference price, as a function of the order and its candle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlippageModel {
+    /// Constant impact in basis points regardless of size
+    FixedBps { bps: f64 },
+    /// A share of the candle's high-low range, used as a spread proxy (0.5 = cross half of it)
+    Spread {
+        share: f64,
+        #[serde(default)]
+        min_bps: f64,
+    },
+    /// Square-root market impact `coefficient * (range / close) * sqrt(quantity / volume)`: four
+    /// times the size pays twice the impact. Candles without volume fall back to `min_bps`
+    SquareRoot {
+        coefficient: f64,
+        #[serde(default)]
+        min_bps: f64,
+        #[serde(default)]
+        max_bps: Option<f64>,
+    },
+}
+
+impl SlippageModel {
+    /// Slippage as a fraction of price for taking `quantity` during `bar`
+    pub fn fraction(&self, quantity: Decimal, bar: &MarketData) -> f64 {
+        let close = to_f64(bar.close);
+        let range = if close > 0.0 { (to_f64(bar.high) - to_f64(bar.low)).max(0.0) / close } else { 0.0 };
+        match self {
+            SlippageModel::FixedBps { bps } => bps / 10_000.0,
+            SlippageModel::Spread { share, min_bps } => (range * share).max(min_bps / 10_000.0),
+            SlippageModel::SquareRoot { coefficient, min_bps, max_bps } => {
+                let volume = to_f64(bar.volume);
+                let participation = if volume > 0.0 { to_f64(quantity) / volume } else { 0.0 };
+                let impact = (coefficient * range * participation.sqrt()).max(min_bps / 10_000.0);
+                max_bps.map_or(impact, |max| impact.min(max / 10_000.0))
+            }
+        }
+    }
+}
+
+/// Fill simulation parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FillModel {
+    /// Fee rate for resting orders that add liquidity
+    pub maker_fee: f64,
+    /// Fee rate for market orders and marketable limits
+    pub taker_fee: f64,
+    /// Per-symbol and volume-tier rates; replaces `maker_fee`/`taker_fee` when set
+    pub fee_schedule: Option<FeeSchedule>,
+    /// Price impact on taker fills (fraction of price, against the order)
+    pub slippage: f64,
+    /// Size-aware slippage; `None` applies the flat `slippage` fraction
+    pub slippage_model: Option<SlippageModel>,
+    /// Delay between submission and the order reaching the book (ms)
+    pub latency_ms: i64,
+    /// Largest share of a candle's volume one order can fill; `None` fills the whole remainder
+    pub max_volume_share: Option<f64>,
+    /// Resting limits need the price to trade through them; `false` also fills on a touch
+    pub strict_limit_cross: bool,
+}
+
+impl Default for FillModel {
+    fn default() -> Self {
+        FillModel {
+            maker_fee: 0.0,
+            taker_fee: 0.0,
+            fee_schedule: None,
+            slippage: 0.0,
+            slippage_model: None,
+            latency_ms: 0,
+            max_volume_share: None,
+            strict_limit_cross: true,
+        }
+    }
+}
+
+impl FillModel {
+    /// Single fee rate for both sides, no latency and no volume cap (the classic backtest setup)
+    pub fn flat(fee_rate: f64, slippage: f64) -> Self {
+        FillModel { maker_fee: fee_rate, taker_fee: fee_rate, slippage, ..FillModel::default() }
+    }
+
+    /// Whether an order submitted at `submitted_at` has reached the book by `now`
+    pub fn has_arrived(&self, submitted_at: i64, now: i64) -> bool {
+        now - submitted_at >= self.latency_ms
+    }
+
+    /// Fee schedule in effect, the flat maker/taker rates when none is configured
+    pub fn schedule(&self) -> FeeSchedule {
+        self.fee_schedule.clone().unwrap_or_else(|| FeeSchedule::flat(self.maker_fee, self.taker_fee))
+    }
+
+    /// Fee for a fill at the base tier; callers tracking 30-day volume re-price through a `FeeLedger`
+    pub fn fee(&self, symbol: &str, notional: Decimal, liquidity: Liquidity) -> Decimal {
+        let rate = match &self.fee_schedule {
+            Some(schedule) => schedule.rates(symbol, 0.0).rate(liquidity),
+            None => match liquidity {
+                Liquidity::Maker => self.maker_fee,
+                Liquidity::Taker => self.taker_fee,
+            },
+        };
+        notional * to_decimal(rate)
+    }
+
+    /// Taker price after slippage for `quantity` taken during `bar`
+    pub fn taker_price(&self, side: &OrderSide, reference: Decimal, quantity: Decimal, bar: &MarketData) -> Decimal {
+        let slippage = to_decimal(match &self.slippage_model {
+            Some(model) => model.fraction(quantity, bar),
+            None => self.slippage,
+        });
+        match side {
+            OrderSide::Buy => reference * (Decimal::ONE + slippage),
+            OrderSide::Sell => reference * (Decimal::ONE - slippage),
+        }
+    }
+
+    /// Quantity fillable against a candle with `volume`
+    pub fn fillable(&self, remaining: Decimal, volume: Decimal) -> Decimal {
+        match self.max_volume_share {
+            Some(share) => remaining.min(volume.max(Decimal::ZERO) * to_decimal(share.max(0.0))),
+            None => remaining,
+        }
+    }
+
+    fn is_limit(order: &Order) -> bool {
+        matches!(order.order_type, OrderType::Limit | OrderType::Iceberg) && order.price > Decimal::ZERO
+    }
+
+    fn take(&self, order: &Order, price: Decimal, quantity: Decimal, liquidity: Liquidity) -> Option<SimulatedFill> {
+        if quantity <= Decimal::ZERO || price <= Decimal::ZERO {
+            return None;
+        }
+        Some(SimulatedFill { price, quantity, liquidity, fee: self.fee(&order.symbol, price * quantity, liquidity) })
+    }
+
+    /// Fill for an order reaching the book during `bar` while the market trades at `reference`:
+    /// market orders and marketable limits take liquidity (a limit never pays past its price),
+    /// anything else rests
+    pub fn on_arrival(&self, order: &Order, remaining: Decimal, reference: Decimal, bar: &MarketData) -> Option<SimulatedFill> {
+        let quantity = self.fillable(remaining, bar.volume);
+        if Self::is_limit(order) {
+            let marketable = match order.side {
+                OrderSide::Buy => reference <= order.price,
+                OrderSide::Sell => reference >= order.price,
+            };
+            if !marketable {
+                return None;
+            }
+            let price = match order.side {
+                OrderSide::Buy => self.taker_price(&order.side, reference, quantity, bar).min(order.price),
+                OrderSide::Sell => self.taker_price(&order.side, reference, quantity, bar).max(order.price),
+            };
+            return self.take(order, price, quantity, Liquidity::Taker);
+        }
+        match order.order_type {
+            OrderType::StopLoss | OrderType::StopLimit => None,
+            _ => self.take(order, self.taker_price(&order.side, reference, quantity, bar), quantity, Liquidity::Taker),
+        }
+    }
+
+    /// Fill for an order already on the book while `bar` trades: limits fill as maker at their
+    /// price once crossed, stops trigger into a taker fill (from the open on a gap), and leftover
+    /// market quantity takes the open
+    pub fn on_bar(&self, order: &Order, remaining: Decimal, bar: &MarketData) -> Option<SimulatedFill> {
+        let quantity = self.fillable(remaining, bar.volume);
+        if Self::is_limit(order) {
+            let crossed = match (&order.side, self.strict_limit_cross) {
+                (OrderSide::Buy, true) => bar.low < order.price,
+                (OrderSide::Buy, false) => bar.low <= order.price,
+                (OrderSide::Sell, true) => bar.high > order.price,
+                (OrderSide::Sell, false) => bar.high >= order.price,
+            };
+            return if crossed { self.take(order, order.price, quantity, Liquidity::Maker) } else { None };
+        }
+        match (&order.order_type, order.stop_price) {
+            (OrderType::StopLoss, stop) => {
+                let stop = stop.unwrap_or(order.price);
+                let base = match order.side {
+                    OrderSide::Buy if bar.high >= stop => stop.max(bar.open),
+                    OrderSide::Sell if bar.low <= stop => stop.min(bar.open),
+                    _ => return None,
+                };
+                self.take(order, self.taker_price(&order.side, base, quantity, bar), quantity, Liquidity::Taker)
+            }
+            (OrderType::StopLimit, _) => None,
+            _ => self.take(order, self.taker_price(&order.side, bar.open, quantity, bar), quantity, Liquidity::Taker),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    #[test]
+    fn test_limit_cross_volume_cap_and_fees() {
+        let model = FillModel { maker_fee: 0.0002, taker_fee: 0.0005, max_volume_share: Some(0.1), ..FillModel::default() };
+        let bar = |low: Decimal| MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: dec!(50_000), high: dec!(50_100), low, close: dec!(50_000), volume: dec!(20) };
+        let bid = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(5), dec!(49_000));
+
+        assert_eq!(model.on_arrival(&bid, dec!(5), dec!(50_000), &bar(dec!(49_900))), None);
+        // a touch is not a cross
+        assert_eq!(model.on_bar(&bid, dec!(5), &bar(dec!(49_000))), None);
+        let fill = model.on_bar(&bid, dec!(5), &bar(dec!(48_900))).unwrap();
+        assert_eq!((fill.price, fill.quantity, fill.liquidity), (dec!(49_000), dec!(2), Liquidity::Maker));
+        assert_eq!(fill.fee, dec!(19.6));
+
+        // marketable limit takes liquidity but never pays past its price
+        let slipped = FillModel { slippage: 0.01, ..model.clone() };
+        let fill = slipped.on_arrival(&Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(50_200)), dec!(1), dec!(50_000), &bar(dec!(49_900))).unwrap();
+        assert_eq!((fill.price, fill.liquidity), (dec!(50_200), Liquidity::Taker));
+        assert!(model.has_arrived(1_000, 1_000));
+        assert!(!FillModel { latency_ms: 250, ..model }.has_arrived(1_000, 1_200));
+    }
+
+    #[test]
+    fn test_slippage_models() {
+        // 1% range, 100 BTC traded in the candle
+        let bar = MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: dec!(50_000), high: dec!(50_250), low: dec!(49_750), close: dec!(50_000), volume: dec!(100) };
+        let buy = |model: SlippageModel, quantity: Decimal| {
+            let fill_model = FillModel { slippage: 0.5, slippage_model: Some(model), ..FillModel::default() };
+            let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, quantity, Decimal::ZERO);
+            fill_model.on_arrival(&order, quantity, dec!(50_000), &bar).unwrap().price
+        };
+
+        // the model replaces the flat fraction
+        assert_eq!(buy(SlippageModel::FixedBps { bps: 10.0 }, dec!(1)), dec!(50_050));
+        assert_eq!(buy(SlippageModel::Spread { share: 0.5, min_bps: 0.0 }, dec!(1)), dec!(50_250));
+
+        // square-root impact: 4x the size pays 2x the slippage
+        let impact = SlippageModel::SquareRoot { coefficient: 1.0, min_bps: 0.0, max_bps: None };
+        assert_eq!(buy(impact.clone(), dec!(1)), dec!(50_050));
+        assert_eq!(buy(impact, dec!(4)), dec!(50_100));
+        let capped = SlippageModel::SquareRoot { coefficient: 1.0, min_bps: 1.0, max_bps: Some(15.0) };
+        assert_eq!(buy(capped.clone(), dec!(25)), dec!(50_075));
+        assert_eq!(capped.fraction(dec!(25), &MarketData { volume: Decimal::ZERO, ..bar.clone() }), 0.0001);
+    }
+}
@@ -0,0 +1,618 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, oneshot, RwLock};
+use tokio::task::JoinHandle;
+
+use crate::config::FixOrderEntryConfig;
+use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::traits::Exchange;
+use crate::market_data::fix_session::{
+    msg_type, parse_utc_timestamp, sending_time, tags, FixConnector, FixMessage, FixSession, FixSessionEvent, TcpConnector,
+};
+use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::Decimal;
+use crate::models::fee::Liquidity;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType, OrderUpdate};
+use crate::models::trade::Trade;
+
+fn fix_side(side: &OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "1",
+        OrderSide::Sell => "2",
+    }
+}
+
+/// OrdType (40); execution algorithms must be substituted client-side before reaching a FIX venue
+fn fix_ord_type(order_type: &OrderType) -> Result<&'static str, TradingError> {
+    match order_type {
+        OrderType::Market => Ok("1"),
+        OrderType::Limit => Ok("2"),
+        OrderType::StopLoss => Ok("3"),
+        OrderType::StopLimit => Ok("4"),
+        other => Err(TradingError::InvalidParameter(format!("order type {:?} not supported over FIX", other))),
+    }
+}
+
+/// TimeInForce (59)
+fn fix_time_in_force(tif: &str) -> Result<&'static str, TradingError> {
+    match tif.to_ascii_uppercase().as_str() {
+        "DAY" => Ok("0"),
+        "GTC" => Ok("1"),
+        "IOC" => Ok("3"),
+        "FOK" => Ok("4"),
+        other => Err(TradingError::InvalidParameter(format!("time in force {} not supported over FIX", other))),
+    }
+}
+
+/// OrdStatus (39) -> OrderStatus; pending states (5 replaced, 6 pending cancel, E pending replace) keep the previous status
+fn order_status(ord_status: &str) -> Option<OrderStatus> {
+    match ord_status {
+        "0" | "A" => Some(OrderStatus::New),
+        "1" => Some(OrderStatus::PartiallyFilled),
+        "2" | "3" => Some(OrderStatus::Filled),
+        "4" => Some(OrderStatus::Cancelled),
+        "8" => Some(OrderStatus::Rejected),
+        "C" => Some(OrderStatus::Expired),
+        _ => None,
+    }
+}
+
+fn is_open(status: &OrderStatus) -> bool {
+    matches!(status, OrderStatus::New | OrderStatus::PartiallyFilled)
+}
+
+/// Venue reject carried by an ExecutionReport or OrderCancelReject
+fn rejection(message: &FixMessage, reason_tag: u32) -> TradingError {
+    TradingError::ExchangeRejected {
+        code: message.get(reason_tag).and_then(|r| r.parse().ok()).unwrap_or(0),
+        msg: message.get(tags::TEXT).unwrap_or("rejected by venue").to_string(),
+    }
+}
+
+/// Local view of one order sent over FIX
+struct FixOrder {
+    order: Order,
+    /// ClOrdID of the latest accepted request in the cancel/replace chain
+    cl_ord_id: String,
+    /// OrderID (37) assigned by the venue
+    venue_order_id: Option<String>,
+    status: OrderStatus,
+    cum_qty: Decimal,
+    fills: Vec<Trade>,
+}
+
+/// Orders keyed by their original ClOrdID (which is also the `OrderId` handed to callers)
+#[derive(Default)]
+struct FixOrderState {
+    orders: HashMap<String, FixOrder>,
+    /// Every ClOrdID of a cancel/replace chain -> original ClOrdID
+    chains: HashMap<String, String>,
+    /// ClOrdID -> caller waiting for the venue's answer to that request
+    waiters: HashMap<String, oneshot::Sender<FixMessage>>,
+}
+
+impl FixOrderState {
+    fn insert(&mut self, order: Order) {
+        let key = order.id.0.clone();
+        self.chains.insert(key.clone(), key.clone());
+        self.orders.insert(key.clone(), FixOrder {
+            order,
+            cl_ord_id: key,
+            venue_order_id: None,
+            status: OrderStatus::New,
+            cum_qty: Decimal::ZERO,
+            fills: Vec::new(),
+        });
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.orders.remove(key);
+        self.chains.retain(|_, original| original != key);
+    }
+
+    fn resolve(&self, message: &FixMessage) -> Option<String> {
+        [tags::CL_ORD_ID, tags::ORIG_CL_ORD_ID].iter()
+            .find_map(|tag| message.get(*tag).and_then(|id| self.chains.get(id)))
+            .cloned()
+    }
+
+    /// Apply an ExecutionReport to the local order; returns the push update for the order manager
+    fn apply_report(&mut self, report: &FixMessage) -> Option<OrderUpdate> {
+        let key = self.resolve(report)?;
+        let entry = self.orders.get_mut(&key)?;
+        if let Some(id) = report.get(tags::ORDER_ID).filter(|id| *id != "NONE") {
+            entry.venue_order_id = Some(id.to_string());
+        }
+        let exec_type = report.get(tags::EXEC_TYPE).unwrap_or_default();
+        if matches!(exec_type, "4" | "5") {
+            if let Some(cl_ord_id) = report.get(tags::CL_ORD_ID) {
+                entry.cl_ord_id = cl_ord_id.to_string();
+            }
+        }
+        if exec_type == "5" {
+            if let Some(quantity) = report.get_decimal(tags::ORDER_QTY) {
+                entry.order.quantity = quantity;
+            }
+            if let Some(price) = report.get_decimal(tags::PRICE) {
+                entry.order.price = price;
+            }
+        }
+        if let Some(status) = report.get(tags::ORD_STATUS).and_then(order_status) {
+            entry.status = status;
+        }
+
+        let timestamp = report.get(tags::TRANSACT_TIME).and_then(parse_utc_timestamp)
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+        let last_qty = report.get_decimal(tags::LAST_QTY).unwrap_or_default();
+        let last_px = report.get_decimal(tags::LAST_PX).unwrap_or_default();
+        if last_qty > Decimal::ZERO {
+            let exec_id = report.get(tags::EXEC_ID).map(str::to_string).unwrap_or_else(|| format!("{}-{}", key, entry.fills.len() + 1));
+            entry.fills.push(Trade::new(
+                exec_id,
+                entry.order.symbol.clone(),
+                last_px,
+                last_qty,
+                timestamp,
+                OrderId(key.clone()),
+                entry.order.side.clone(),
+            ));
+        }
+        entry.cum_qty = report.get_decimal(tags::CUM_QTY).unwrap_or(entry.cum_qty + last_qty);
+
+        Some(OrderUpdate {
+            order_id: OrderId(key),
+            client_order_id: entry.order.client_order_id.clone(),
+            symbol: entry.order.symbol.clone(),
+            side: entry.order.side.clone(),
+            status: entry.status.clone(),
+            last_fill_price: last_px,
+            last_fill_quantity: last_qty,
+            cumulative_quantity: entry.cum_qty,
+            fee: report.get_decimal(tags::COMMISSION).unwrap_or_default(),
+            fee_asset: None,
+            liquidity: match report.get(tags::LAST_LIQUIDITY_IND) {
+                Some("1") => Some(Liquidity::Maker),
+                Some("2") => Some(Liquidity::Taker),
+                _ => None,
+            },
+            timestamp,
+        })
+    }
+
+    /// Hand a reply to the caller waiting on its ClOrdID
+    fn notify(&mut self, message: &FixMessage) {
+        if let Some(waiter) = message.get(tags::CL_ORD_ID).and_then(|id| self.waiters.remove(id)) {
+            let _ = waiter.send(message.clone());
+        }
+    }
+}
+
+/// FIX order entry connector for venues without a REST trading API.
+///
+/// Orders go out as NewOrderSingle (D), cancels as OrderCancelRequest (F) and modifications as
+/// OrderCancelReplaceRequest (G) over a `FixSession`. Each request waits up to `ack_timeout_ms` for the
+/// venue's ExecutionReport (8) or OrderCancelReject (9); later ExecutionReports update the local order
+/// state and are pushed through `order_updates`. The `OrderId` returned by `submit_order` is the
+/// original ClOrdID and stays stable across cancel/replace chains. After every logon an
+/// OrderStatusRequest (H) is sent for each open order to catch up on missed executions.
+pub struct FixExchange {
+    config: FixOrderEntryConfig,
+    session: FixSession,
+    state: Arc<Mutex<FixOrderState>>,
+    updates: broadcast::Sender<OrderUpdate>,
+    market: Option<Arc<RwLock<MarketDataStream>>>,
+    id_prefix: String,
+    next_id: AtomicU64,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl FixExchange {
+    pub fn new(config: FixOrderEntryConfig) -> Self {
+        let session = FixSession::new(config.session.clone());
+        FixExchange {
+            config,
+            session,
+            state: Arc::new(Mutex::new(FixOrderState::default())),
+            updates: broadcast::channel(1024).0,
+            market: None,
+            id_prefix: format!("xq{}", chrono::Utc::now().timestamp_millis()),
+            next_id: AtomicU64::new(1),
+            tasks: Vec::new(),
+        }
+    }
+
+    /// Serve `get_market_data` from a live market data stream (FIX order entry sessions carry no prices)
+    pub fn with_market_stream(mut self, stream: Arc<RwLock<MarketDataStream>>) -> Self {
+        self.market = Some(stream);
+        self
+    }
+
+    /// Connect over TCP to the configured host and port
+    pub fn connect(&mut self) {
+        let connector = Arc::new(TcpConnector::new(&self.config.session.host, self.config.session.port));
+        self.start(connector);
+    }
+
+    /// Start the session and the ExecutionReport handler over a custom transport
+    pub fn start(&mut self, connector: Arc<dyn FixConnector>) {
+        let mut events = self.session.subscribe();
+        let session = self.session.clone();
+        let state = self.state.clone();
+        let updates = self.updates.clone();
+        self.tasks.push(tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(FixSessionEvent::Message(message)) => {
+                        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                        if message.msg_type == msg_type::EXECUTION_REPORT {
+                            if let Some(update) = state.apply_report(&message) {
+                                let _ = updates.send(update);
+                            }
+                        }
+                        if matches!(message.msg_type.as_str(), msg_type::EXECUTION_REPORT | msg_type::ORDER_CANCEL_REJECT) {
+                            state.notify(&message);
+                        }
+                    }
+                    Ok(FixSessionEvent::LoggedOn) => {
+                        let requests: Vec<FixMessage> = {
+                            let state = state.lock().unwrap_or_else(|e| e.into_inner());
+                            state.orders.values().filter(|o| is_open(&o.status)).map(status_request).collect()
+                        };
+                        for request in requests {
+                            if let Err(e) = session.send(request) {
+                                log::warn!("FIX order status request failed: {}", e);
+                            }
+                        }
+                    }
+                    Ok(FixSessionEvent::LoggedOut { reason }) => log::warn!("FIX order entry session logged out: {}", reason),
+                    Err(RecvError::Lagged(n)) => log::warn!("FIX order entry events lagged: {} dropped", n),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        }));
+        self.tasks.push(self.session.start(connector));
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        self.session.is_logged_on()
+    }
+
+    fn state(&self) -> MutexGuard<'_, FixOrderState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn next_cl_ord_id(&self) -> String {
+        format!("{}-{}", self.id_prefix, self.next_id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Send a request and wait for the ExecutionReport/OrderCancelReject carrying its ClOrdID
+    async fn request(&self, cl_ord_id: &str, message: FixMessage) -> Result<FixMessage, TradingError> {
+        let (tx, rx) = oneshot::channel();
+        self.state().waiters.insert(cl_ord_id.to_string(), tx);
+        if let Err(e) = self.session.send(message) {
+            self.state().waiters.remove(cl_ord_id);
+            return Err(e);
+        }
+        match tokio::time::timeout(Duration::from_millis(self.config.ack_timeout_ms), rx).await {
+            Ok(Ok(reply)) => Ok(reply),
+            _ => {
+                self.state().waiters.remove(cl_ord_id);
+                Err(TradingError::NetworkTimeout(format!("no FIX answer to {} within {}ms", cl_ord_id, self.config.ack_timeout_ms)))
+            }
+        }
+    }
+
+    fn order_message(&self, message_type: &str, cl_ord_id: &str, order: &Order) -> Result<FixMessage, TradingError> {
+        let mut message = FixMessage::new(message_type).with(tags::CL_ORD_ID, cl_ord_id);
+        if let Some(account) = &self.config.account {
+            message.push(tags::ACCOUNT, account);
+        }
+        if let Some(handl_inst) = &self.config.handl_inst {
+            message.push(tags::HANDL_INST, handl_inst);
+        }
+        message.push(tags::SYMBOL, &order.symbol);
+        message.push(tags::SIDE, fix_side(&order.side));
+        message.push(tags::TRANSACT_TIME, sending_time());
+        message.push(tags::ORDER_QTY, order.quantity);
+        message.push(tags::ORD_TYPE, fix_ord_type(&order.order_type)?);
+        if matches!(order.order_type, OrderType::Limit | OrderType::StopLimit) {
+            message.push(tags::PRICE, order.price);
+        }
+        if matches!(order.order_type, OrderType::StopLoss | OrderType::StopLimit) {
+            let stop_price = order.stop_price.ok_or_else(|| TradingError::InvalidParameter("stop order without stop price".to_string()))?;
+            message.push(tags::STOP_PX, stop_price);
+        }
+        message.push(tags::TIME_IN_FORCE, fix_time_in_force(&order.time_in_force)?);
+        Ok(message)
+    }
+
+    /// (original ClOrdID, current ClOrdID, venue OrderID, order) of an open order
+    fn open_order(&self, order_id: &OrderId) -> Result<(String, Option<String>, Order), TradingError> {
+        let state = self.state();
+        let entry = state.orders.get(&order_id.0).ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))?;
+        if !is_open(&entry.status) {
+            return Err(TradingError::InvalidParameter(format!("order {} is {:?}", order_id, entry.status)));
+        }
+        Ok((entry.cl_ord_id.clone(), entry.venue_order_id.clone(), entry.order.clone()))
+    }
+}
+
+impl Drop for FixExchange {
+    fn drop(&mut self) {
+        self.session.stop();
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+fn status_request(entry: &FixOrder) -> FixMessage {
+    let mut message = FixMessage::new(msg_type::ORDER_STATUS_REQUEST).with(tags::CL_ORD_ID, &entry.cl_ord_id);
+    if let Some(id) = &entry.venue_order_id {
+        message.push(tags::ORDER_ID, id);
+    }
+    message.with(tags::SYMBOL, &entry.order.symbol).with(tags::SIDE, fix_side(&entry.order.side))
+}
+
+#[async_trait]
+impl Exchange for FixExchange {
+    async fn submit_order(&mut self, mut order: Order) -> Result<OrderId, TradingError> {
+        let cl_ord_id = order.client_order_id.clone().unwrap_or_else(|| self.next_cl_ord_id());
+        if self.state().chains.contains_key(&cl_ord_id) {
+            return Err(TradingError::InvalidParameter(format!("duplicate ClOrdID {}", cl_ord_id)));
+        }
+        let message = self.order_message(msg_type::NEW_ORDER_SINGLE, &cl_ord_id, &order)?;
+        order.id = OrderId(cl_ord_id.clone());
+        self.state().insert(order);
+
+        let reply = self.request(&cl_ord_id, message).await;
+        match reply {
+            Ok(report) if report.get(tags::EXEC_TYPE) == Some("8") || report.get(tags::ORD_STATUS) == Some("8") => {
+                self.state().remove(&cl_ord_id);
+                Err(rejection(&report, tags::ORD_REJ_REASON))
+            }
+            Ok(_) => Ok(OrderId(cl_ord_id)),
+            // keep a late order so a delayed ExecutionReport can still fill in its state
+            Err(TradingError::NetworkTimeout(msg)) => Err(TradingError::NetworkTimeout(msg)),
+            Err(e) => {
+                self.state().remove(&cl_ord_id);
+                Err(e)
+            }
+        }
+    }
+
+    async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+        let (orig_cl_ord_id, venue_order_id, order) = self.open_order(order_id)?;
+        let cl_ord_id = self.next_cl_ord_id();
+        let mut message = FixMessage::new(msg_type::ORDER_CANCEL_REQUEST)
+            .with(tags::ORIG_CL_ORD_ID, &orig_cl_ord_id)
+            .with(tags::CL_ORD_ID, &cl_ord_id);
+        if let Some(id) = venue_order_id {
+            message.push(tags::ORDER_ID, id);
+        }
+        let message = message
+            .with(tags::SYMBOL, &order.symbol)
+            .with(tags::SIDE, fix_side(&order.side))
+            .with(tags::TRANSACT_TIME, sending_time())
+            .with(tags::ORDER_QTY, order.quantity);
+        self.state().chains.insert(cl_ord_id.clone(), order_id.0.clone());
+
+        let reply = self.request(&cl_ord_id, message).await?;
+        if reply.msg_type == msg_type::ORDER_CANCEL_REJECT {
+            return Err(rejection(&reply, tags::CXL_REJ_REASON));
+        }
+        Ok(())
+    }
+
+    async fn modify_order(&mut self, order_id: &OrderId, order: Order) -> Result<OrderId, TradingError> {
+        let (orig_cl_ord_id, venue_order_id, current) = self.open_order(order_id)?;
+        if order.symbol != current.symbol || order.side != current.side {
+            return Err(TradingError::InvalidParameter("cancel/replace cannot change symbol or side".to_string()));
+        }
+        let cl_ord_id = self.next_cl_ord_id();
+        let mut message = self.order_message(msg_type::ORDER_CANCEL_REPLACE_REQUEST, &cl_ord_id, &order)?
+            .with(tags::ORIG_CL_ORD_ID, &orig_cl_ord_id);
+        if let Some(id) = venue_order_id {
+            message.push(tags::ORDER_ID, id);
+        }
+        self.state().chains.insert(cl_ord_id.clone(), order_id.0.clone());
+
+        let reply = self.request(&cl_ord_id, message).await?;
+        if reply.msg_type == msg_type::ORDER_CANCEL_REJECT {
+            return Err(rejection(&reply, tags::CXL_REJ_REASON));
+        }
+        Ok(order_id.clone())
+    }
+
+    async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+        self.state().orders.get(&order_id.0)
+            .map(|o| o.status.clone())
+            .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+        Ok(self.state().orders.values().filter(|o| is_open(&o.status)).map(|o| o.order.clone()).collect())
+    }
+
+    /// Fills of orders sent through this session, newest last
+    async fn get_recent_trades(&self, symbol: &str, limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+        let state = self.state();
+        let mut trades: Vec<Trade> = state.orders.values()
+            .filter(|o| o.order.symbol == symbol)
+            .flat_map(|o| o.fills.iter().cloned())
+            .collect();
+        trades.sort_by_key(|t| t.timestamp);
+        let skip = limit.map_or(0, |limit| trades.len().saturating_sub(limit));
+        Ok(trades.split_off(skip))
+    }
+
+    async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+        match &self.market {
+            Some(stream) => stream.read().await.get_latest_data(symbol)
+                .ok_or_else(|| TradingError::DataNotFound(format!("no market data for {}", symbol))),
+            None => Err(TradingError::ExchangeError(format!("market data not supported over FIX order entry ({})", symbol))),
+        }
+    }
+
+    async fn get_historical_data(
+        &self,
+        symbol: &str,
+        _interval: &str,
+        _start_time: i64,
+        _end_time: Option<i64>,
+        _limit: Option<usize>,
+    ) -> Result<Vec<MarketData>, TradingError> {
+        Err(TradingError::ExchangeError(format!("historical data not supported over FIX order entry ({})", symbol)))
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+        Err(TradingError::ExchangeError(format!("balance not supported over FIX order entry ({})", asset)))
+    }
+
+    fn order_updates(&self) -> Option<broadcast::Receiver<OrderUpdate>> {
+        Some(self.updates.subscribe())
+    }
+
+    fn capabilities(&self) -> ExchangeCapabilities {
+        ExchangeCapabilities {
+            order_types: vec![OrderType::Market, OrderType::Limit, OrderType::StopLoss, OrderType::StopLimit],
+            time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string(), "DAY".to_string()],
+            hedge_mode: false,
+            batch_orders: false,
+            native_iceberg: false,
+            native_twap: false,
+            native_vwap: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::FixSessionConfig;
+    use crate::models::decimal::to_decimal;
+    use crate::market_data::fix_session::FixStream;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    struct DuplexConnector(Mutex<Option<DuplexStream>>);
+
+    #[async_trait]
+    impl FixConnector for DuplexConnector {
+        async fn connect(&self) -> Result<Box<dyn FixStream>, TradingError> {
+            let stream = self.0.lock().unwrap().take().ok_or(TradingError::NotConnected)?;
+            Ok(Box::new(stream))
+        }
+    }
+
+    // acceptor side answering orders with ExecutionReports
+    struct Venue {
+        stream: DuplexStream,
+        buf: Vec<u8>,
+        seq: u64,
+    }
+
+    impl Venue {
+        async fn recv(&mut self) -> FixMessage {
+            loop {
+                if let Some(frame) = FixMessage::take_frame(&mut self.buf).unwrap() {
+                    return FixMessage::decode(&frame).unwrap();
+                }
+                let mut chunk = [0u8; 4096];
+                let n = tokio::time::timeout(Duration::from_secs(2), self.stream.read(&mut chunk)).await.unwrap().unwrap();
+                self.buf.extend_from_slice(&chunk[..n]);
+            }
+        }
+
+        async fn send(&mut self, message: FixMessage) {
+            self.seq += 1;
+            let frame = message.encode("FIX.4.4", "VENUE", "XQ", self.seq, &sending_time());
+            self.stream.write_all(frame.as_bytes()).await.unwrap();
+        }
+    }
+
+    fn report(cl_ord_id: &str, exec_type: &str, ord_status: &str) -> FixMessage {
+        FixMessage::new(msg_type::EXECUTION_REPORT)
+            .with(tags::ORDER_ID, "V1")
+            .with(tags::CL_ORD_ID, cl_ord_id)
+            .with(tags::EXEC_TYPE, exec_type)
+            .with(tags::ORD_STATUS, ord_status)
+    }
+
+    #[tokio::test]
+    async fn test_submit_fill_replace_and_cancel() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let mut exchange = FixExchange::new(FixOrderEntryConfig::new(FixSessionConfig::new("localhost", 9878, "XQ", "VENUE")));
+        let mut updates = exchange.order_updates().unwrap();
+        exchange.start(Arc::new(DuplexConnector(Mutex::new(Some(client)))));
+        let mut venue = Venue { stream: server, buf: Vec::new(), seq: 0 };
+        venue.recv().await;
+        venue.send(FixMessage::new(msg_type::LOGON).with(tags::HEART_BT_INT, 30)).await;
+        while !exchange.is_logged_on() {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, to_decimal(2.0), to_decimal(100.0)).with_client_order_id("c1");
+        let (order_id, new_order) = tokio::join!(exchange.submit_order(order), async {
+            let new_order = venue.recv().await;
+            venue.send(report("c1", "0", "0")).await;
+            new_order
+        });
+        let order_id = order_id.unwrap();
+        assert_eq!(order_id.0, "c1");
+        assert_eq!(
+            (new_order.msg_type.as_str(), new_order.get(tags::SIDE), new_order.get(tags::ORD_TYPE), new_order.get(tags::PRICE), new_order.get(tags::TIME_IN_FORCE)),
+            ("D", Some("1"), Some("2"), Some("100"), Some("1")),
+        );
+        assert_eq!(updates.recv().await.unwrap().status, OrderStatus::New);
+
+        // partial fill: pushed as an update and kept as a trade
+        venue.send(report("c1", "F", "1").with(tags::LAST_QTY, "0.5").with(tags::LAST_PX, "99.5").with(tags::CUM_QTY, "0.5").with(tags::EXEC_ID, "e1")).await;
+        let fill = updates.recv().await.unwrap();
+        assert_eq!((fill.status, fill.last_fill_quantity, fill.cumulative_quantity), (OrderStatus::PartiallyFilled, to_decimal(0.5), to_decimal(0.5)));
+        assert_eq!(exchange.get_recent_trades("BTCUSDT", None).await.unwrap()[0].id, "e1");
+
+        // replace: OrderCancelReject is an error, an accepted replace keeps the OrderId
+        let replacement = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, to_decimal(2.0), to_decimal(101.0));
+        let (rejected, _) = tokio::join!(exchange.modify_order(&order_id, replacement.clone()), async {
+            let replace = venue.recv().await;
+            let reject = FixMessage::new(msg_type::ORDER_CANCEL_REJECT).with(tags::CL_ORD_ID, replace.get(tags::CL_ORD_ID).unwrap()).with(tags::TEXT, "too late");
+            venue.send(reject).await;
+        });
+        assert!(matches!(rejected, Err(TradingError::ExchangeRejected { msg, .. }) if msg == "too late"));
+        let (replaced, replace) = tokio::join!(exchange.modify_order(&order_id, replacement), async {
+            let replace = venue.recv().await;
+            venue.send(report(replace.get(tags::CL_ORD_ID).unwrap(), "5", "1").with(tags::ORIG_CL_ORD_ID, "c1").with(tags::PRICE, "101")).await;
+            replace
+        });
+        assert_eq!(replaced.unwrap(), order_id);
+        assert_eq!((replace.msg_type.as_str(), replace.get(tags::ORIG_CL_ORD_ID), replace.get(tags::ORDER_ID)), ("G", Some("c1"), Some("V1")));
+        assert_eq!(exchange.get_open_orders().await.unwrap()[0].price, to_decimal(101.0));
+
+        // cancel refers to the replaced ClOrdID
+        let (cancelled, cancel) = tokio::join!(exchange.cancel_order(&order_id), async {
+            let cancel = venue.recv().await;
+            venue.send(report(cancel.get(tags::CL_ORD_ID).unwrap(), "4", "4")).await;
+            cancel
+        });
+        cancelled.unwrap();
+        assert_eq!((cancel.msg_type.as_str(), cancel.get(tags::ORIG_CL_ORD_ID)), ("F", replace.get(tags::CL_ORD_ID)));
+        assert_eq!(exchange.get_order_status(&order_id).await.unwrap(), OrderStatus::Cancelled);
+        assert!(exchange.get_open_orders().await.unwrap().is_empty());
+
+        // venue reject
+        let order = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, to_decimal(1.0), Decimal::ZERO);
+        let (rejected, _) = tokio::join!(exchange.submit_order(order), async {
+            let new_order = venue.recv().await;
+            let reject = report(new_order.get(tags::CL_ORD_ID).unwrap(), "8", "8").with(tags::ORD_REJ_REASON, "3").with(tags::TEXT, "exceeds limit");
+            venue.send(reject).await;
+        });
+        assert!(matches!(rejected, Err(TradingError::ExchangeRejected { code: 3, .. })));
+    }
+}
@@ -0,0 +1,503 @@
+use async_trait::async_trait;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+use crate::error::TradingError;
+use crate::exchange::binance_common::{floor_to_step, parse_decimal, parse_f64};
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::models::fee::FeeTier;
+use crate::models::market_data::MarketData;
+use crate::models::order_book::{levels_from_json, OrderBook};
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
+use crate::models::timestamp::Timestamp;
+use crate::models::trade::Trade;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// Error prefix for an unknown order (cancel/amend/query)
+const UNKNOWN_ORDER: &str = "EOrder:Unknown order";
+/// OHLC serves at most this many of the most recent candles
+const MAX_CANDLES: usize = 720;
+
+/// "BTCUSDT" / "BTC/USDT" -> "XBTUSDT" (Kraken calls bitcoin XBT)
+pub fn kraken_pair(symbol: &str) -> String {
+  let pair = symbol.replace('/', "").to_ascii_uppercase();
+  match pair.strip_prefix("BTC") {
+    Some(quote) => format!("XBT{}", quote),
+    None => pair,
+  }
+}
+
+/// "XBTUSDT" -> "BTCUSDT" (the symbol form used by strategies and orders)
+pub fn kraken_symbol(pair: &str) -> String {
+  match pair.strip_prefix("XBT") {
+    Some(quote) => format!("BTC{}", quote),
+    None => pair.to_string(),
+  }
+}
+
+/// OHLC interval in minutes: "1m" -> 1, "4h" -> 240, "1d" -> 1440, "1w" -> 10080
+pub fn kraken_interval(interval: &str) -> Result<u32, TradingError> {
+  let (num, unit) = interval.split_at(interval.len().saturating_sub(1));
+  let n: u32 = num.parse().map_err(|_| TradingError::InvalidParameter(format!("Invalid interval: {}", interval)))?;
+  let minutes = match (unit, n) {
+    ("m", 1 | 5 | 15 | 30) => n,
+    ("h", 1 | 4) => n * 60,
+    ("d", 1) => 1440,
+    ("w", 1) => 10080,
+    _ => return Err(TradingError::InvalidParameter(format!("Interval not supported by Kraken: {}", interval))),
+  };
+  Ok(minutes)
+}
+
+/// Balance keys an asset may appear under ("BTC" -> XXBT/XBT, "USD" -> ZUSD)
+fn balance_keys(asset: &str) -> Vec<String> {
+  let asset = asset.to_ascii_uppercase();
+  let native = match asset.as_str() { "BTC" => "XBT".to_string(), "DOGE" => "XDG".to_string(), _ => asset.clone() };
+  vec![asset, format!("X{}", native), format!("Z{}", native), native]
+}
+
+/// Public results are keyed by Kraken's canonical pair name ("XXBTZUSD"); take the single entry
+fn pair_entry(result: &serde_json::Value) -> Option<&serde_json::Value> {
+  result.as_object()?.iter().find(|(k, _)| k.as_str() != "last").map(|(_, v)| v)
+}
+
+/// Price/volume grid of a pair from AssetPairs
+#[derive(Debug, Clone)]
+pub(crate) struct PairFilters {
+  tick_size: Decimal,
+  lot_decimals: u32,
+  order_min: Decimal,
+  /// minimum order cost in the quote currency
+  cost_min: Decimal,
+}
+
+impl PairFilters {
+  fn from_json(v: &serde_json::Value) -> Self {
+    let pair_decimals = v.get("pair_decimals").and_then(|d| d.as_u64()).unwrap_or(8) as u32;
+    PairFilters {
+      tick_size: Some(parse_decimal(v.get("tick_size"))).filter(|t| *t > Decimal::ZERO).unwrap_or(Decimal::new(1, pair_decimals)),
+      lot_decimals: v.get("lot_decimals").and_then(|d| d.as_u64()).unwrap_or(8) as u32,
+      order_min: parse_decimal(v.get("ordermin")),
+      cost_min: parse_decimal(v.get("costmin")),
+    }
+  }
+
+  fn lot_size(&self) -> Decimal {
+    Decimal::new(1, self.lot_decimals)
+  }
+
+  fn volume(&self, quantity: Decimal) -> String {
+    floor_to_step(quantity, self.lot_size()).max(self.order_min).to_string()
+  }
+
+  fn price(&self, price: Decimal) -> String {
+    floor_to_step(price, self.tick_size).to_string()
+  }
+}
+
+/// Kraken REST connector for spot pairs
+pub struct KrakenExchange {
+  base_url: String,
+  api_key: String,
+  api_secret: String,
+  http: reqwest::Client,
+  min_interval_ms: u64,
+  last_request_ms: AtomicI64,
+  last_nonce: AtomicI64,
+  time_offset_ms: AtomicI64,
+  telemetry: ExchangeTelemetry,
+  pair_filters: Mutex<HashMap<String, PairFilters>>,
+}
+
+impl KrakenExchange {
+  pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>) -> Self {
+    KrakenExchange {
+      base_url: base_url.into(),
+      api_key: api_key.into(),
+      api_secret: api_secret.into(),
+      http: reqwest::Client::new(),
+      // public endpoints allow ~1 request/s; private calls draw from a decaying counter
+      min_interval_ms: 200,
+      last_request_ms: AtomicI64::new(0),
+      last_nonce: AtomicI64::new(0),
+      time_offset_ms: AtomicI64::new(0),
+      telemetry: ExchangeTelemetry::default(),
+      pair_filters: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Record REST latency and clock samples into a shared telemetry handle
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    self.telemetry = telemetry;
+    self
+  }
+
+  fn ts_with_offset(&self) -> i64 {
+    chrono::Utc::now().timestamp_millis() + self.time_offset_ms.load(Ordering::SeqCst)
+  }
+
+  /// Strictly increasing nonce (ms clock, bumped when two calls share a millisecond)
+  fn next_nonce(&self) -> i64 {
+    let now = chrono::Utc::now().timestamp_millis();
+    let prev = self.last_nonce.fetch_max(now, Ordering::SeqCst);
+    if prev >= now { self.last_nonce.fetch_add(1, Ordering::SeqCst) + 1 } else { now }
+  }
+
+  /// API-Sign: base64 HMAC-SHA512 keyed with the base64-decoded secret over
+  /// `uri_path + SHA256(nonce + post_data)`
+  fn sign(&self, path: &str, nonce: i64, post_data: &str) -> Result<String, TradingError> {
+    let secret = base64::engine::general_purpose::STANDARD.decode(&self.api_secret)
+      .map_err(|e| TradingError::InvalidParameter(format!("Kraken API secret is not base64: {}", e)))?;
+    let digest = Sha256::digest(format!("{}{}", nonce, post_data).as_bytes());
+    let mut mac = HmacSha512::new_from_slice(&secret).unwrap();
+    mac.update(path.as_bytes());
+    mac.update(&digest);
+    Ok(base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes()))
+  }
+
+  async fn throttle(&self) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let elapsed = (now - self.last_request_ms.load(Ordering::SeqCst)).max(0) as u64;
+    if elapsed < self.min_interval_ms {
+      tokio::time::sleep(std::time::Duration::from_millis(self.min_interval_ms - elapsed)).await;
+    }
+    self.last_request_ms.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+  }
+
+  /// Send a request and unwrap the `{error: [...], result}` envelope.
+  /// Unknown orders map to OrderNotFound when `order_id` is given
+  async fn send(&self, endpoint: &str, req: reqwest::RequestBuilder, order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    self.throttle().await;
+    let started = std::time::Instant::now();
+    let res = req.send().await;
+    let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
+    self.telemetry.record_request(endpoint, started.elapsed().as_secs_f64() * 1000.0, ok);
+    let res = res.map_err(|e| TradingError::ExchangeError(format!("{} http error: {}", endpoint, e)))?;
+    let status = res.status();
+    let body = res.json::<serde_json::Value>().await
+      .map_err(|e| TradingError::ExchangeError(format!("{} parse error: {}", endpoint, e)))?;
+    let errors: Vec<&str> = body.get("error").and_then(|e| e.as_array()).into_iter().flatten().filter_map(|e| e.as_str()).collect();
+    match (errors.first(), order_id) {
+      (None, _) if status.is_success() => Ok(body.get("result").cloned().unwrap_or(serde_json::Value::Null)),
+      (Some(e), Some(id)) if e.starts_with(UNKNOWN_ORDER) => Err(TradingError::OrderNotFound(id.clone())),
+      _ => Err(TradingError::ExchangeError(format!("{} failed: {} {}", endpoint, status, body))),
+    }
+  }
+
+  async fn public_get(&self, method: &str, query: &str) -> Result<serde_json::Value, TradingError> {
+    let path = format!("/0/public/{}", method);
+    let url = format!("{}{}?{}", self.base_url, path, query);
+    self.send(&format!("GET {}", path), self.http.get(url), None).await
+  }
+
+  /// Private calls are form-encoded POSTs with the nonce as the first field
+  async fn private(&self, method: &str, params: &[(&str, String)], order_id: Option<&OrderId>) -> Result<serde_json::Value, TradingError> {
+    let path = format!("/0/private/{}", method);
+    let nonce = self.next_nonce();
+    let mut post_data = format!("nonce={}", nonce);
+    for (k, v) in params {
+      post_data.push_str(&format!("&{}={}", k, urlencoding(v)));
+    }
+    let req = self.http.post(format!("{}{}", self.base_url, path))
+      .header("API-Key", &self.api_key)
+      .header("API-Sign", self.sign(&path, nonce, &post_data)?)
+      .header("Content-Type", "application/x-www-form-urlencoded")
+      .body(post_data);
+    self.send(&format!("POST {}", path), req, order_id).await
+  }
+
+  async fn filters(&self, pair: &str) -> Result<PairFilters, TradingError> {
+    if let Some(filters) = self.pair_filters.lock().unwrap_or_else(|e| e.into_inner()).get(pair) {
+      return Ok(filters.clone());
+    }
+    let result = self.public_get("AssetPairs", &format!("pair={}", pair)).await?;
+    let filters = pair_entry(&result).map(PairFilters::from_json)
+      .ok_or_else(|| TradingError::DataNotFound(format!("asset pair {}", pair)))?;
+    self.pair_filters.lock().unwrap_or_else(|e| e.into_inner()).insert(pair.to_string(), filters.clone());
+    Ok(filters)
+  }
+
+  async fn query_order(&self, order_id: &OrderId) -> Result<serde_json::Value, TradingError> {
+    let result = self.private("QueryOrders", &[("txid", order_id.0.clone())], Some(order_id)).await?;
+    result.get(&order_id.0).cloned().ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))
+  }
+}
+
+/// Minimal form encoding for parameter values (pairs, decimals, ids)
+fn urlencoding(value: &str) -> String {
+  value.chars().map(|c| match c {
+    'A'..='Z' | 'a'..='z' | '0'..='9' | '-' | '_' | '.' | '~' => c.to_string(),
+    _ => format!("%{:02X}", c as u32),
+  }).collect()
+}
+
+/// Open orders with some volume executed are partially filled; `closed` means fully filled
+pub(crate) fn parse_order_status(v: &serde_json::Value) -> OrderStatus {
+  match v.get("status").and_then(|s| s.as_str()).unwrap_or("") {
+    "pending" => OrderStatus::New,
+    "open" if parse_f64(v.get("vol_exec")) > 0.0 => OrderStatus::PartiallyFilled,
+    "open" => OrderStatus::New,
+    "closed" => OrderStatus::Filled,
+    "canceled" => OrderStatus::Cancelled,
+    _ => OrderStatus::Expired,
+  }
+}
+
+/// Map an order entry (OpenOrders / QueryOrders) keyed by its txid
+pub(crate) fn parse_order(txid: &str, v: &serde_json::Value) -> Option<Order> {
+  let descr = v.get("descr")?;
+  let side = match descr.get("type")?.as_str()? { "buy" => OrderSide::Buy, _ => OrderSide::Sell };
+  let order_type = match descr.get("ordertype")?.as_str()? {
+    "limit" => OrderType::Limit,
+    "stop-loss" => OrderType::StopLoss,
+    "stop-loss-limit" => OrderType::StopLimit,
+    "trailing-stop" => OrderType::TrailingStop,
+    _ => OrderType::Market,
+  };
+  let oflags = v.get("oflags").and_then(|f| f.as_str()).unwrap_or("");
+  let mut order = Order::new(kraken_symbol(descr.get("pair")?.as_str()?), side, order_type.clone(), parse_decimal(v.get("vol")), Decimal::ZERO);
+  order.id = OrderId(txid.to_string());
+  // price is the limit price, or the trigger price of stop orders (limit in price2)
+  match order_type {
+    OrderType::StopLoss => order.stop_price = Some(parse_decimal(descr.get("price"))),
+    OrderType::StopLimit => {
+      order.stop_price = Some(parse_decimal(descr.get("price")));
+      order.price = parse_decimal(descr.get("price2"));
+    }
+    _ => order.price = parse_decimal(descr.get("price")),
+  }
+  if oflags.split(',').any(|f| f == "post") { order.time_in_force = "GTX".to_string(); }
+  order.client_order_id = v.get("cl_ord_id").and_then(|c| c.as_str()).map(str::to_string);
+  // opentm is in (fractional) seconds
+  if let Some(opened) = v.get("opentm").and_then(|t| t.as_f64()) { order.created_at = Timestamp::from_millis((opened * 1000.0) as i64); }
+  Some(order)
+}
+
+/// OHLC row `[time(s), open, high, low, close, vwap, volume, count]`
+pub(crate) fn parse_ohlc(symbol: &str, v: &serde_json::Value) -> Option<MarketData> {
+  let k = v.as_array()?;
+  Some(MarketData {
+    symbol: symbol.to_string(),
+    timestamp: Timestamp::from_millis(k.first()?.as_i64()? * 1000),
+    open: parse_decimal(k.get(1)),
+    high: parse_decimal(k.get(2)),
+    low: parse_decimal(k.get(3)),
+    close: parse_decimal(k.get(4)),
+    volume: parse_decimal(k.get(6)),
+  })
+}
+
+/// AddOrder fields after the pair; prices and volume already on the pair grid
+pub(crate) fn order_params(order: &Order, filters: &PairFilters) -> Vec<(&'static str, String)> {
+  let side = match order.side { OrderSide::Buy => "buy", OrderSide::Sell => "sell" };
+  let mut params = vec![("type", side.to_string()), ("volume", filters.volume(order.quantity))];
+  let stop = order.stop_price.filter(|s| *s > Decimal::ZERO);
+  let (ordertype, price, price2) = match (&order.order_type, stop) {
+    (OrderType::Limit | OrderType::Iceberg, _) if order.price > Decimal::ZERO => ("limit", Some(order.price), None),
+    (OrderType::StopLoss, Some(stop)) => ("stop-loss", Some(stop), None),
+    (OrderType::StopLimit, Some(stop)) if order.price > Decimal::ZERO => ("stop-loss-limit", Some(stop), Some(order.price)),
+    _ => ("market", None, None),
+  };
+  params.push(("ordertype", ordertype.to_string()));
+  if let Some(price) = price { params.push(("price", filters.price(price))); }
+  if let Some(price2) = price2 { params.push(("price2", filters.price(price2))); }
+  if ordertype == "limit" {
+    match order.time_in_force.as_str() {
+      "GTX" => params.push(("oflags", "post".to_string())),
+      "IOC" => params.push(("timeinforce", "IOC".to_string())),
+      _ => {}
+    }
+  }
+  if let Some(cid) = &order.client_order_id { params.push(("cl_ord_id", cid.clone())); }
+  params
+}
+
+#[async_trait]
+impl Exchange for KrakenExchange {
+  async fn submit_order(&mut self, order: Order) -> Result<OrderId, TradingError> {
+    let pair = kraken_pair(&order.symbol);
+    let filters = self.filters(&pair).await?;
+    let mut params = vec![("pair", pair)];
+    params.extend(order_params(&order, &filters));
+    let result = self.private("AddOrder", &params, None).await?;
+    result.get("txid").and_then(|t| t.as_array()).and_then(|t| t.first()).and_then(|id| id.as_str())
+      .map(|id| OrderId(id.to_string()))
+      .ok_or_else(|| TradingError::ParseError(format!("txid missing in {}", result)))
+  }
+
+  async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+    self.private("CancelOrder", &[("txid", order_id.0.clone())], Some(order_id)).await?;
+    Ok(())
+  }
+
+  /// AmendOrder changes quantity/price in place; the order keeps its txid
+  async fn modify_order(&mut self, order_id: &OrderId, order: Order) -> Result<OrderId, TradingError> {
+    let filters = self.filters(&kraken_pair(&order.symbol)).await?;
+    let mut params = vec![("txid", order_id.0.clone()), ("order_qty", filters.volume(order.quantity))];
+    if order.price > Decimal::ZERO { params.push(("limit_price", filters.price(order.price))); }
+    if let Some(stop) = order.stop_price.filter(|s| *s > Decimal::ZERO) { params.push(("trigger_price", filters.price(stop))); }
+    self.private("AmendOrder", &params, Some(order_id)).await?;
+    Ok(order_id.clone())
+  }
+
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+    Ok(parse_order_status(&self.query_order(order_id).await?))
+  }
+
+  async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+    let result = self.private("OpenOrders", &[], None).await?;
+    Ok(result.get("open").and_then(|o| o.as_object())
+      .map(|open| open.iter().filter_map(|(txid, v)| parse_order(txid, v)).collect())
+      .unwrap_or_default())
+  }
+
+  async fn get_recent_trades(&self, symbol: &str, limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+    let result = self.public_get("Trades", &format!("pair={}&count={}", kraken_pair(symbol), limit.unwrap_or(100).min(1000))).await?;
+    // rows are [price, volume, time(s), side b/s, type, misc, trade_id]
+    Ok(pair_entry(&result).and_then(|t| t.as_array()).into_iter().flatten()
+      .filter_map(|t| {
+        let side = match t.get(3)?.as_str()? { "b" => OrderSide::Buy, _ => OrderSide::Sell };
+        let ts = (t.get(2)?.as_f64()? * 1000.0) as i64;
+        Some(Trade::new(t.get(6)?.to_string(), symbol, parse_decimal(t.get(0)), parse_decimal(t.get(1)), ts, OrderId(String::new()), side))
+      })
+      .collect())
+  }
+
+  async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+    let result = self.public_get("Ticker", &format!("pair={}", kraken_pair(symbol))).await?;
+    let t = pair_entry(&result).ok_or_else(|| TradingError::DataNotFound(format!("ticker {}", symbol)))?;
+    // a/b/c are [price, ...] arrays: best ask, best bid, last trade
+    let first = |key: &str| parse_decimal(t.get(key).and_then(|v| v.get(0)));
+    let (bid, ask) = (first("b"), first("a"));
+    let close = if bid > Decimal::ZERO && ask > Decimal::ZERO { (bid + ask) / Decimal::TWO } else { first("c") };
+    if close <= Decimal::ZERO { return Err(TradingError::DataNotFound(format!("price {}", symbol))); }
+    Ok(MarketData { symbol: symbol.to_string(), timestamp: self.ts_with_offset().into(), open: close, high: close, low: close, close, volume: Decimal::ZERO })
+  }
+
+  async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+    let result = self.public_get("Depth", &format!("pair={}&count={}", kraken_pair(symbol), depth.clamp(1, 500))).await?;
+    let book = pair_entry(&result).ok_or_else(|| TradingError::DataNotFound(format!("order book {}", symbol)))?;
+    let mut book = OrderBook::new(symbol, self.ts_with_offset(), levels_from_json(&book["bids"]), levels_from_json(&book["asks"]));
+    book.truncate(depth);
+    Ok(book)
+  }
+
+  /// Candles oldest first from `start_time`. Kraken only keeps the latest 720 candles per
+  /// interval, so older ranges come back empty
+  async fn get_historical_data(&self, symbol: &str, interval: &str, start_time: i64, end_time: Option<i64>, limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
+    // `since` is exclusive and in seconds
+    let query = format!("pair={}&interval={}&since={}", kraken_pair(symbol), kraken_interval(interval)?, start_time / 1000 - 1);
+    let result = self.public_get("OHLC", &query).await?;
+    let end = end_time.unwrap_or(i64::MAX);
+    Ok(pair_entry(&result).and_then(|rows| rows.as_array()).into_iter().flatten()
+      .filter_map(|k| parse_ohlc(symbol, k))
+      .filter(|c| c.timestamp.as_millis() >= start_time && c.timestamp.as_millis() <= end)
+      .take(limit.unwrap_or(MAX_CANDLES))
+      .collect())
+  }
+
+  async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+    let filters = self.filters(&kraken_pair(symbol)).await?;
+    Ok(SymbolInfo {
+      symbol: symbol.to_string(),
+      tick_size: filters.tick_size,
+      step_size: filters.lot_size(),
+      min_qty: filters.order_min,
+      min_notional: filters.cost_min,
+    })
+  }
+
+  async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+    let result = self.private("Balance", &[], None).await?;
+    Ok(balance_keys(asset).iter().find_map(|key| result.get(key)).map(|b| parse_decimal(Some(b))).unwrap_or_default())
+  }
+
+  /// Current fee tier from TradeVolume; Kraken reports percentages
+  async fn get_fee_tier(&self, symbol: &str) -> Result<FeeTier, TradingError> {
+    let result = self.private("TradeVolume", &[("pair", kraken_pair(symbol))], None).await?;
+    let fee = |key: &str| result.get(key).and_then(pair_entry).map(|f| parse_f64(f.get("fee")) / 100.0);
+    let taker_fee = fee("fees").ok_or_else(|| TradingError::DataNotFound(format!("fee tier {}", symbol)))?;
+    Ok(FeeTier {
+      symbol: symbol.to_string(),
+      maker_fee: fee("fees_maker").unwrap_or(taker_fee),
+      taker_fee,
+      thirty_day_volume: result.get("volume").map(|v| parse_f64(Some(v))),
+    })
+  }
+
+  fn capabilities(&self) -> ExchangeCapabilities {
+    // FOK is not offered on spot; trailing stops take relative offsets we don't map
+    ExchangeCapabilities {
+      order_types: vec![OrderType::Market, OrderType::Limit, OrderType::StopLoss, OrderType::StopLimit],
+      time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "GTX".to_string()],
+      hedge_mode: false,
+      batch_orders: false,
+      native_iceberg: false,
+      native_twap: false,
+      native_vwap: false,
+    }
+  }
+
+  async fn sync_time(&mut self) -> Result<(), TradingError> {
+    let sent_at = chrono::Utc::now().timestamp_millis();
+    let result = self.public_get("Time", "").await?;
+    let server_ms = result.get("unixtime").and_then(|t| t.as_i64()).map(|s| s * 1000)
+      .ok_or_else(|| TradingError::ParseError(format!("unixtime missing in {}", result)))?;
+    let received_at = chrono::Utc::now().timestamp_millis();
+    let rtt = (received_at - sent_at).max(0);
+    // second resolution: only worth correcting when the clock is off by more than that
+    let offset = server_ms - (sent_at + rtt / 2);
+    self.time_offset_ms.store(if offset.abs() > 1000 { offset } else { 0 }, Ordering::SeqCst);
+    self.telemetry.record_clock_offset(offset, rtt as f64, received_at);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+
+  #[test]
+  fn test_order_mapping_and_signing() {
+    assert_eq!(kraken_pair("BTC/USDT"), "XBTUSDT");
+    assert_eq!(kraken_pair("ethusd"), "ETHUSD");
+    assert_eq!(kraken_symbol("XBTUSDT"), "BTCUSDT");
+    assert_eq!(kraken_interval("4h").unwrap(), 240);
+    assert!(kraken_interval("3m").is_err());
+    assert_eq!(balance_keys("BTC")[1], "XXBT");
+
+    let filters = PairFilters::from_json(&serde_json::json!({"pair_decimals": 1, "lot_decimals": 8, "ordermin": "0.0001", "tick_size": "0.1"}));
+    let order = Order::new("BTCUSDT", OrderSide::Sell, OrderType::StopLimit, dec!(0.123456789), dec!(59_000.07))
+      .with_stop_price(dec!(59_500))
+      .with_client_order_id("c-7");
+    let params: HashMap<_, _> = order_params(&order, &filters).into_iter().collect();
+    assert_eq!(params["ordertype"], "stop-loss-limit");
+    assert_eq!((params["price"].as_str(), params["price2"].as_str()), ("59500.0", "59000.0"));
+    assert_eq!(params["volume"], "0.12345678");
+    assert_eq!(params["cl_ord_id"], "c-7");
+
+    let v = serde_json::json!({
+      "status": "open", "vol": "0.5", "vol_exec": "0.1", "opentm": 1700000000.25, "oflags": "fciq,post", "cl_ord_id": "c-8",
+      "descr": {"pair": "XBTUSDT", "type": "buy", "ordertype": "limit", "price": "58000.0", "price2": "0"}
+    });
+    let parsed = parse_order("OQCLML-BW3P3-BUCMWZ", &v).unwrap();
+    assert_eq!((parsed.symbol.as_str(), parsed.quantity, parsed.price), ("BTCUSDT", dec!(0.5), dec!(58_000)));
+    assert_eq!(parsed.time_in_force, "GTX");
+    assert_eq!(parsed.created_at.as_millis(), 1_700_000_000_250);
+    assert_eq!(parse_order_status(&v), OrderStatus::PartiallyFilled);
+
+    let exchange = KrakenExchange::new("http://localhost", "key", "c2VjcmV0");
+    assert_eq!(exchange.sign("/0/private/Balance", 1_700_000_000_000, "nonce=1700000000000").unwrap(), "/xbwWEpes5nsvtwSckvMTeFE2hi+XrkND8ZtAy3heGzUb2ol5PfOfzq13ngg5s+0rU75nNKyZyvhKWhlVLQKvw==");
+    assert!(KrakenExchange::new("http://localhost", "key", "not base64!").sign("/0/private/Balance", 1, "nonce=1").is_err());
+  }
+}
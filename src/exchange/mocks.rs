@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -9,42 +9,230 @@ use uuid::Uuid;
 
 use crate::config::Config;
 use crate::error::TradingError;
+use crate::exchange::fill_model::{FillModel, SimulatedFill};
 use crate::exchange::traits::Exchange;
+use crate::models::decimal::{dec, to_decimal, to_f64, Decimal};
+use crate::models::fee::{FeeAttribution, FeeLedger, FeeTier};
+use crate::models::funding::FundingRate;
 use crate::models::market_data::MarketData;
-use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::order_book::{OrderBook, PriceLevel};
+use crate::models::order::{Order, OrderFill, OrderId, OrderSide, OrderStatus};
 use crate::models::trade::Trade;
 
+/// Candles kept per symbol (long paper-trading sessions push one per ticker update)
+const MAX_PRICE_HISTORY: usize = 10_000;
+
 /// A mock implementation of the Exchange trait for testing and development
 pub struct MockExchange {
     config: Config,
     orders: HashMap<OrderId, (Order, OrderStatus)>,
     market_data: HashMap<String, Vec<MarketData>>,
-    balances: HashMap<String, f64>,
+    balances: HashMap<String, Decimal>,
     trades: HashMap<String, Vec<Trade>>,
     order_id_counter: u64,
+    /// Simulated clock (ms); `None` follows the wall clock
+    clock_ms: Option<i64>,
+    /// Resting orders older than this expire when the clock moves
+    order_ttl_ms: Option<i64>,
+    /// Funding rates published with `set_funding_rate`
+    funding_rates: HashMap<String, FundingRate>,
+    /// Fill simulation (latency, volume-capped partial fills, maker/taker fees)
+    fill_model: FillModel,
+    /// Fee schedule of the fill model plus the rolling 30-day volume that picks the tier
+    fees: FeeLedger,
+    /// Orders still travelling to the book (latency not yet elapsed)
+    in_flight: HashSet<OrderId>,
 }
 
 impl MockExchange {
     pub fn new(config: Config) -> Self {
-        let mut exchange = Self {
+        let mut exchange = Self::empty(config);
+
+        // Initialize with some test data
+        exchange.initialize_test_data();
+        exchange
+    }
+
+    /// No balances and no seeded price history (prices arrive through `push_market_data`)
+    pub fn empty(config: Config) -> Self {
+        let fill_model = config.exchange.fill_model.clone();
+        let fees = FeeLedger::new(fill_model.schedule());
+        Self {
             config,
             orders: HashMap::new(),
             market_data: HashMap::new(),
             balances: HashMap::new(),
             trades: HashMap::new(),
             order_id_counter: 0,
+            clock_ms: None,
+            order_ttl_ms: None,
+            funding_rates: HashMap::new(),
+            fill_model,
+            fees,
+            in_flight: HashSet::new(),
+        }
+    }
+
+    /// Expire resting orders `ttl_ms` after submission (checked whenever the clock moves)
+    pub fn with_order_ttl(mut self, ttl_ms: i64) -> Self {
+        self.order_ttl_ms = Some(ttl_ms);
+        self
+    }
+
+    /// Replace the fill model taken from `exchange.fill_model` in the config
+    pub fn with_fill_model(mut self, fill_model: FillModel) -> Self {
+        self.fees = FeeLedger::new(fill_model.schedule());
+        self.fill_model = fill_model;
+        self
+    }
+
+    /// Fees charged so far, split by maker/taker and tier
+    pub fn fee_attribution(&self) -> &FeeAttribution {
+        self.fees.attribution()
+    }
+
+    /// Current exchange time: the simulated clock once it has been set, otherwise wall time
+    pub fn now(&self) -> i64 {
+        self.clock_ms.unwrap_or_else(|| Utc::now().timestamp_millis())
+    }
+
+    /// Freeze the clock at `timestamp` (ms); time only moves through `advance_time` afterwards
+    pub fn set_time(&mut self, timestamp: i64) {
+        self.clock_ms = Some(timestamp);
+        self.expire_orders();
+    }
+
+    /// Move the simulated clock forward and expire resting orders past their TTL
+    pub fn advance_time(&mut self, ms: i64) {
+        self.set_time(self.now() + ms);
+    }
+
+    /// Publish a new latest price at the current clock time and match resting orders against it
+    pub fn set_price(&mut self, symbol: &str, price: Decimal) -> Result<(), TradingError> {
+        let volume = self.market_data.get(symbol).and_then(|data| data.first()).map(|d| d.volume).unwrap_or_default();
+        self.push_market_data(MarketData {
+            symbol: symbol.to_string(),
+            timestamp: self.now().into(),
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume,
+        })
+    }
+
+    /// Publish a candle as the latest market data and match open orders against it
+    pub fn push_market_data(&mut self, candle: MarketData) -> Result<(), TradingError> {
+        let symbol = candle.symbol.clone();
+        // Newest candle first, matching the seeded history
+        let data = self.market_data.entry(symbol.clone()).or_default();
+        data.insert(0, candle);
+        data.truncate(MAX_PRICE_HISTORY);
+        self.match_orders(&symbol)
+    }
+
+    pub fn set_balance(&mut self, asset: &str, amount: Decimal) {
+        self.balances.insert(asset.to_string(), amount);
+    }
+
+    /// Publish a predicted funding rate; mark price follows the latest candle
+    pub fn set_funding_rate(&mut self, symbol: &str, funding_rate: f64, next_funding_time: i64) {
+        let mark_price = self.get_latest_market_data(symbol).map(|d| d.close_f64()).unwrap_or_default();
+        let timestamp = self.now();
+        self.funding_rates.insert(symbol.to_string(), FundingRate {
+            symbol: symbol.to_string(),
+            mark_price,
+            index_price: mark_price,
+            funding_rate,
+            next_funding_time,
+            timestamp,
+        });
+    }
+
+    fn is_open(status: &OrderStatus) -> bool {
+        *status == OrderStatus::New || *status == OrderStatus::PartiallyFilled
+    }
+
+    fn filled_quantity(&self, order: &Order) -> Decimal {
+        self.trades
+            .get(&order.symbol)
+            .map(|trades| trades.iter().filter(|t| t.order_id == order.id).map(|t| t.quantity).sum())
+            .unwrap_or_default()
+    }
+
+    // Matches open orders against the latest candle: in-flight orders whose latency has elapsed
+    // arrive at its price, resting ones fill when the candle crosses them
+    fn match_orders(&mut self, symbol: &str) -> Result<(), TradingError> {
+        let candle = self.get_latest_market_data(symbol)?;
+        let now = self.now();
+        let mut open: Vec<Order> = self
+            .orders
+            .values()
+            .filter(|(order, status)| order.symbol == symbol && Self::is_open(status))
+            .map(|(order, _)| order.clone())
+            .collect();
+        // Deterministic fill order regardless of HashMap iteration
+        open.sort_by_key(|order| (order.created_at, order.id.0.clone()));
+
+        for order in open {
+            let remaining = order.quantity - self.filled_quantity(&order);
+            if remaining <= Decimal::ZERO {
+                continue;
+            }
+            let fill = if self.in_flight.contains(&order.id) {
+                if !self.fill_model.has_arrived(order.created_at.as_millis(), now) {
+                    continue;
+                }
+                self.in_flight.remove(&order.id);
+                self.fill_model.on_arrival(&order, remaining, candle.close, &candle)
+            } else {
+                self.fill_model.on_bar(&order, remaining, &candle)
+            };
+            if let Some(fill) = fill {
+                self.apply_fill(&order, fill)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Records a simulated fill and moves the order to Filled / PartiallyFilled
+    fn apply_fill(&mut self, order: &Order, fill: SimulatedFill) -> Result<(), TradingError> {
+        let now = self.now();
+        let fee = self.fees.charge(&order.symbol, fill.liquidity, to_f64(fill.price * fill.quantity), now);
+        let trade = Trade {
+            id: Uuid::new_v4().to_string(),
+            symbol: order.symbol.clone(),
+            price: fill.price,
+            quantity: fill.quantity,
+            timestamp: now.into(),
+            order_id: order.id.clone(),
+            side: order.side.clone(),
         };
+        self.update_balances(&trade, to_decimal(fee))?;
+        self.trades.entry(order.symbol.clone()).or_default().push(trade);
 
-        // Initialize with some test data
-        exchange.initialize_test_data();
-        exchange
+        let filled = self.filled_quantity(order);
+        if let Some((_, status)) = self.orders.get_mut(&order.id) {
+            *status = if filled >= order.quantity { OrderStatus::Filled } else { OrderStatus::PartiallyFilled };
+        }
+        Ok(())
+    }
+
+    fn expire_orders(&mut self) {
+        let Some(ttl) = self.order_ttl_ms else { return };
+        let now = self.now();
+        for (order, status) in self.orders.values_mut() {
+            if Self::is_open(status) && now - order.created_at.as_millis() >= ttl {
+                *status = OrderStatus::Expired;
+            }
+        }
     }
 
     fn initialize_test_data(&mut self) {
         // Add some initial balances
-        self.balances.insert("BTC".to_string(), 10.0);
-        self.balances.insert("ETH".to_string(), 100.0);
-        self.balances.insert("USDT".to_string(), 50000.0);
+        self.balances.insert("BTC".to_string(), dec!(10));
+        self.balances.insert("ETH".to_string(), dec!(100));
+        self.balances.insert("USDT".to_string(), dec!(50000));
 
         // Create some mock market data for BTC/USDT
         let symbol = "BTCUSDT".to_string();
@@ -61,12 +249,12 @@ impl MockExchange {
 
             market_data.push(MarketData {
                 symbol: symbol.clone(),
-                timestamp: timestamp.timestamp_millis(),
-                open: last_price * (1.0 - 0.001),
-                high: last_price * (1.0 + 0.002),
-                low: last_price * (1.0 - 0.002),
-                close: last_price,
-                volume,
+                timestamp: timestamp.into(),
+                open: to_decimal(last_price * (1.0 - 0.001)).round_dp(2),
+                high: to_decimal(last_price * (1.0 + 0.002)).round_dp(2),
+                low: to_decimal(last_price * (1.0 - 0.002)).round_dp(2),
+                close: to_decimal(last_price).round_dp(2),
+                volume: to_decimal(volume).round_dp(4),
             });
         }
 
@@ -86,12 +274,12 @@ impl MockExchange {
 
             market_data.push(MarketData {
                 symbol: symbol.clone(),
-                timestamp: timestamp.timestamp_millis(),
-                open: last_price * (1.0 - 0.001),
-                high: last_price * (1.0 + 0.002),
-                low: last_price * (1.0 - 0.002),
-                close: last_price,
-                volume,
+                timestamp: timestamp.into(),
+                open: to_decimal(last_price * (1.0 - 0.001)).round_dp(2),
+                high: to_decimal(last_price * (1.0 + 0.002)).round_dp(2),
+                low: to_decimal(last_price * (1.0 - 0.002)).round_dp(2),
+                close: to_decimal(last_price).round_dp(2),
+                volume: to_decimal(volume).round_dp(4),
             });
         }
 
@@ -103,72 +291,7 @@ impl MockExchange {
         OrderId(format!("mock-{}", self.order_id_counter))
     }
 
-    // Simulates execution of an order
-    fn process_order(&mut self, order: &Order) -> Result<(), TradingError> {
-        // Simple simulation - immediate fill for market orders, partial fills for limit orders
-        let symbol = &order.symbol;
-        let latest_market_data = self.get_latest_market_data(symbol)?;
-
-        match order.order_type {
-            OrderType::Market => {
-                // Create a trade for the market order
-                let trade = Trade {
-                    id: Uuid::new_v4().to_string(),
-                    symbol: symbol.clone(),
-                    price: latest_market_data.close,
-                    quantity: order.quantity,
-                    timestamp: Utc::now().timestamp_millis(),
-                    order_id: order.id.clone(),
-                    side: order.side.clone(),
-                };
-
-                // Update balances based on the trade
-                self.update_balances(&trade)?;
-
-                // Store the trade
-                self.trades
-                    .entry(symbol.clone())
-                    .or_insert_with(Vec::new)
-                    .push(trade);
-            },
-            OrderType::Limit => {
-                // For simplicity, partially fill limit orders
-                if (order.side == OrderSide::Buy && order.price >= latest_market_data.close) ||
-                    (order.side == OrderSide::Sell && order.price <= latest_market_data.close) {
-                    // Partial fill (50%)
-                    let filled_quantity = order.quantity * 0.5;
-                    let trade = Trade {
-                        id: Uuid::new_v4().to_string(),
-                        symbol: symbol.clone(),
-                        price: if order.side == OrderSide::Buy {
-                            order.price
-                        } else {
-                            latest_market_data.close
-                        },
-                        quantity: filled_quantity,
-                        timestamp: Utc::now().timestamp_millis(),
-                        order_id: order.id.clone(),
-                        side: order.side.clone(),
-                    };
-
-                    // Update balances
-                    self.update_balances(&trade)?;
-
-                    // Store the trade
-                    self.trades
-                        .entry(symbol.clone())
-                        .or_insert_with(Vec::new)
-                        .push(trade);
-                }
-            },
-            // Handle other order types as needed
-            _ => {},
-        }
-
-        Ok(())
-    }
-
-    fn update_balances(&mut self, trade: &Trade) -> Result<(), TradingError> {
+    fn update_balances(&mut self, trade: &Trade, fee: Decimal) -> Result<(), TradingError> {
         // Extract the base and quote currencies from the symbol
         // Assuming symbols are in the format BTCUSDT, ETHUSDT, etc.
         let base_asset = &trade.symbol[0..3];
@@ -177,15 +300,16 @@ impl MockExchange {
         match trade.side {
             OrderSide::Buy => {
                 // Increase base asset, decrease quote asset
-                *self.balances.entry(base_asset.to_string()).or_insert(0.0) += trade.quantity;
-                *self.balances.entry(quote_asset.to_string()).or_insert(0.0) -= trade.quantity * trade.price;
+                *self.balances.entry(base_asset.to_string()).or_default() += trade.quantity;
+                *self.balances.entry(quote_asset.to_string()).or_default() -= trade.quantity * trade.price;
             },
             OrderSide::Sell => {
                 // Decrease base asset, increase quote asset
-                *self.balances.entry(base_asset.to_string()).or_insert(0.0) -= trade.quantity;
-                *self.balances.entry(quote_asset.to_string()).or_insert(0.0) += trade.quantity * trade.price;
+                *self.balances.entry(base_asset.to_string()).or_default() -= trade.quantity;
+                *self.balances.entry(quote_asset.to_string()).or_default() += trade.quantity * trade.price;
             },
         }
+        *self.balances.entry(quote_asset.to_string()).or_default() -= fee;
 
         Ok(())
     }
@@ -206,18 +330,17 @@ impl Exchange for MockExchange {
     async fn submit_order(&mut self, mut order: Order) -> Result<OrderId, TradingError> {
         let order_id = self.generate_order_id();
         order.id = order_id.clone();
-
-        // Process the order (execution simulation)
-        self.process_order(&order)?;
-
-        // Store the order with its status
-        let status = if order.order_type == OrderType::Market {
-            OrderStatus::Filled
-        } else {
-            OrderStatus::PartiallyFilled
-        };
-
-        self.orders.insert(order_id.clone(), (order, status));
+        order.created_at = self.now().into();
+        let latest = self.get_latest_market_data(&order.symbol)?;
+        self.orders.insert(order_id.clone(), (order.clone(), OrderStatus::New));
+
+        // Without latency the order reaches the book at the latest price right away; otherwise it
+        // waits for the first price update after the latency has elapsed
+        if self.fill_model.latency_ms > 0 {
+            self.in_flight.insert(order_id.clone());
+        } else if let Some(fill) = self.fill_model.on_arrival(&order, order.quantity, latest.close, &latest) {
+            self.apply_fill(&order, fill)?;
+        }
 
         Ok(order_id)
     }
@@ -256,13 +379,24 @@ impl Exchange for MockExchange {
         }
     }
 
+    async fn get_order_fill(&self, order_id: &OrderId) -> Result<Option<OrderFill>, TradingError> {
+        let (order, _) = self.orders.get(order_id).ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))?;
+        let trades: Vec<&Trade> = self.trades.get(&order.symbol)
+            .map(|trades| trades.iter().filter(|t| &t.order_id == order_id).collect())
+            .unwrap_or_default();
+        let executed: Decimal = trades.iter().map(|t| t.quantity).sum();
+        if executed <= Decimal::ZERO {
+            return Ok(None);
+        }
+        let notional: Decimal = trades.iter().map(|t| t.price * t.quantity).sum();
+        Ok(Some(OrderFill { executed_quantity: executed, average_price: notional / executed }))
+    }
+
     async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
         let open_orders = self
             .orders
             .iter()
-            .filter(|(_, (_, status))| {
-                *status == OrderStatus::New || *status == OrderStatus::PartiallyFilled
-            })
+            .filter(|(_, (_, status))| Self::is_open(status))
             .map(|(_, (order, _))| order.clone())
             .collect();
 
@@ -297,13 +431,15 @@ impl Exchange for MockExchange {
         end_time: Option<i64>,
         limit: Option<usize>,
     ) -> Result<Vec<MarketData>, TradingError> {
-        let end_time = end_time.unwrap_or(Utc::now().timestamp_millis());
+        let end_time = end_time.unwrap_or_else(|| self.now());
         let limit = limit.unwrap_or(1000);
 
         if let Some(data) = self.market_data.get(symbol) {
+            // Stored newest first; return oldest first like the exchange klines endpoints
             let filtered_data = data
                 .iter()
-                .filter(|d| d.timestamp >= start_time && d.timestamp <= end_time)
+                .rev()
+                .filter(|d| d.timestamp.as_millis() >= start_time && d.timestamp.as_millis() <= end_time)
                 .take(limit)
                 .cloned()
                 .collect();
@@ -314,11 +450,117 @@ impl Exchange for MockExchange {
         }
     }
 
-    async fn get_balance(&self, asset: &str) -> Result<f64, TradingError> {
+    /// Synthetic book around the latest close: 1bp ticks, size growing away from the touch
+    async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+        let latest = self.get_latest_market_data(symbol)?;
+        let close = latest.close_f64();
+        let tick = close * 0.0001;
+        let level = |i: usize, sign: f64| PriceLevel::new(close + sign * tick * (i as f64 + 0.5), 1.0 + i as f64);
+        Ok(OrderBook::new(
+            symbol,
+            latest.timestamp.as_millis(),
+            (0..depth).map(|i| level(i, -1.0)).collect(),
+            (0..depth).map(|i| level(i, 1.0)).collect(),
+        ))
+    }
+
+    async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+        self.funding_rates.get(symbol).cloned()
+            .ok_or_else(|| TradingError::DataNotFound(format!("no funding rate for {}", symbol)))
+    }
+
+    /// Fee rates of the configured schedule at the current 30-day volume
+    async fn get_fee_tier(&self, symbol: &str) -> Result<FeeTier, TradingError> {
+        Ok(self.fees.fee_tier(symbol, self.now()))
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
         if let Some(balance) = self.balances.get(asset) {
             Ok(*balance)
         } else {
-            Ok(0.0) // Asset not found, return zero balance
+            Ok(Decimal::ZERO) // Asset not found, return zero balance
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::models::order::OrderType;
+    use crate::strategies::trailing_stop::TrailingStopStrategy;
+    use crate::strategies::Strategy;
+
+    #[tokio::test]
+    async fn test_time_travel_fills_and_expiry() {
+        let mut exchange = MockExchange::new(Config::default()).with_order_ttl(60_000);
+        exchange.set_time(1_000_000);
+        exchange.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        assert_eq!(exchange.get_market_data("BTCUSDT").await.unwrap().timestamp.as_millis(), 1_000_000);
+
+        let resting = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(49_000));
+        let resting = exchange.submit_order(resting).await.unwrap();
+        let stale = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Limit, dec!(1), dec!(55_000));
+        let stale = exchange.submit_order(stale).await.unwrap();
+
+        // price crosses the bid -> fully filled at the limit price
+        exchange.advance_time(30_000);
+        exchange.set_price("BTCUSDT", dec!(48_500)).unwrap();
+        assert_eq!(exchange.get_order_status(&resting).await.unwrap(), OrderStatus::Filled);
+        let trades = exchange.get_recent_trades("BTCUSDT", None).await.unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!((trades[0].price, trades[0].quantity, trades[0].timestamp.as_millis()), (dec!(49_000), dec!(1), 1_030_000));
+
+        // untouched ask expires once its TTL has elapsed on the simulated clock
+        exchange.advance_time(29_999);
+        assert_eq!(exchange.get_order_status(&stale).await.unwrap(), OrderStatus::New);
+        exchange.advance_time(1);
+        assert_eq!(exchange.get_order_status(&stale).await.unwrap(), OrderStatus::Expired);
+        assert!(exchange.get_open_orders().await.unwrap().is_empty());
+
+        // trailing stop driven by scripted prices instead of live ticks
+        let mut strategy = TrailingStopStrategy::new("BTCUSDT", OrderSide::Buy, dec!(0.5), 2.0, None);
+        strategy.set_entry_price(dec!(48_500));
+        for price in [49_000.0, 50_000.0, 49_500.0, 48_900.0] {
+            exchange.advance_time(1_000);
+            exchange.set_price("BTCUSDT", to_decimal(price)).unwrap();
+            strategy.update(exchange.get_market_data("BTCUSDT").await.unwrap()).unwrap();
         }
+        let orders = strategy.get_orders().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(exchange.now(), 1_064_000);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_latency_and_volume_capped_fills() {
+        let fill_model = FillModel { taker_fee: 0.001, latency_ms: 500, max_volume_share: Some(0.5), ..FillModel::default() };
+        let mut exchange = MockExchange::new(Config::default()).with_fill_model(fill_model);
+        exchange.set_time(1_000_000);
+        exchange.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let volume = exchange.get_market_data("BTCUSDT").await.unwrap().volume;
+        let usdt = exchange.get_balance("USDT").await.unwrap();
+
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, volume * dec!(0.8), dec!(0));
+        let order = exchange.submit_order(order).await.unwrap();
+        // still travelling to the book
+        exchange.advance_time(400);
+        exchange.set_price("BTCUSDT", dec!(50_100)).unwrap();
+        assert_eq!(exchange.get_order_status(&order).await.unwrap(), OrderStatus::New);
+
+        // arrives at the next price, half the candle volume per update
+        exchange.advance_time(100);
+        exchange.set_price("BTCUSDT", dec!(50_200)).unwrap();
+        assert_eq!(exchange.get_order_status(&order).await.unwrap(), OrderStatus::PartiallyFilled);
+        exchange.set_price("BTCUSDT", dec!(50_300)).unwrap();
+        assert_eq!(exchange.get_order_status(&order).await.unwrap(), OrderStatus::Filled);
+
+        let trades = exchange.get_recent_trades("BTCUSDT", None).await.unwrap();
+        let prices: Vec<Decimal> = trades.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![dec!(50_200), dec!(50_300)]);
+        let cost: Decimal = trades.iter().map(|t| t.value() * dec!(1.001)).sum();
+        assert_eq!(usdt - exchange.get_balance("USDT").await.unwrap(), cost);
+        let tier = exchange.get_fee_tier("BTCUSDT").await.unwrap();
+        // rolling volume is summed in f64 and the candle volume is random
+        assert!((tier.thirty_day_volume.unwrap() - to_f64(trades.iter().map(|t| t.value()).sum())).abs() < 1e-6);
+        assert_eq!(exchange.fee_attribution().fills, 2);
+    }
+}
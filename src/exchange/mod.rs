@@ -1,4 +1,18 @@
 pub mod mocks;
+pub mod fill_model;
 pub mod traits; 
+pub mod binance_common;
 pub mod binance_futures;
-pub mod dry_run;
\ No newline at end of file
+pub mod binance_spot;
+pub mod bybit;
+pub mod okx;
+pub mod kraken;
+pub mod fix;
+pub mod dry_run;
+pub mod capabilities;
+pub mod telemetry;
+pub mod rate_limiter;
+pub mod binance_user_stream;
+pub mod paper;
+pub mod registry;
+
@@ -0,0 +1,574 @@
+use async_trait::async_trait;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+use crate::error::TradingError;
+use crate::exchange::binance_common::{floor_to_step, parse_decimal, parse_f64};
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::traits::Exchange;
+use crate::market_data::aggregator::parse_timeframe;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::funding::FundingRate;
+use crate::models::market_data::MarketData;
+use crate::models::order_book::{OrderBook, PriceLevel};
+use crate::models::position::Position;
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+use crate::models::symbol_info::SymbolInfo;
+use crate::models::timestamp::Timestamp;
+use crate::models::trade::Trade;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// sCode/code for an order that does not exist (cancel/amend/query)
+const ORDER_NOT_EXISTS: &str = "51603";
+/// history-candles page size limit
+const MAX_CANDLES: usize = 100;
+
+/// "BTCUSDT" -> "BTC-USDT-SWAP"; instrument ids ("ETH-USDT-SWAP") pass through
+pub fn okx_inst_id(symbol: &str) -> String {
+  if symbol.contains('-') {
+    return symbol.to_ascii_uppercase();
+  }
+  let symbol = symbol.to_ascii_uppercase();
+  for quote in ["USDT", "USDC", "USD"] {
+    if let Some(base) = symbol.strip_suffix(quote).filter(|b| !b.is_empty()) {
+      return format!("{}-{}-SWAP", base, quote);
+    }
+  }
+  symbol
+}
+
+/// "BTC-USDT-SWAP" -> "BTCUSDT" (the symbol form used by strategies and orders)
+pub fn okx_symbol(inst_id: &str) -> String {
+  inst_id.trim_end_matches("-SWAP").replace('-', "")
+}
+
+/// Candle bar: minutes stay lower case, hours and above are upper case ("1h" -> "1H", "1d" -> "1Dutc")
+pub fn okx_bar(interval: &str) -> Result<String, TradingError> {
+  let (num, unit) = interval.split_at(interval.len().saturating_sub(1));
+  let n: u32 = num.parse().map_err(|_| TradingError::InvalidParameter(format!("Invalid interval: {}", interval)))?;
+  let bar = match (unit, n) {
+    ("m", 1 | 3 | 5 | 15 | 30) => format!("{}m", n),
+    ("h", 1 | 2 | 4) => format!("{}H", n),
+    // UTC-aligned variants match Binance/Bybit day boundaries
+    ("h", 6 | 12) => format!("{}Hutc", n),
+    ("d", 1) => "1Dutc".to_string(),
+    ("w", 1) => "1Wutc".to_string(),
+    _ => return Err(TradingError::InvalidParameter(format!("Interval not supported by OKX: {}", interval))),
+  };
+  Ok(bar)
+}
+
+/// Contract spec of a swap: orders, positions and book sizes are in contracts of `ct_val` base units
+#[derive(Debug, Clone)]
+pub(crate) struct Instrument {
+  ct_val: Decimal,
+  tick_size: Decimal,
+  lot_size: Decimal,
+  min_size: Decimal,
+}
+
+impl Instrument {
+  fn from_json(v: &serde_json::Value) -> Self {
+    Instrument {
+      ct_val: Some(parse_decimal(v.get("ctVal"))).filter(|c| *c > Decimal::ZERO).unwrap_or(Decimal::ONE),
+      tick_size: parse_decimal(v.get("tickSz")),
+      lot_size: parse_decimal(v.get("lotSz")),
+      min_size: parse_decimal(v.get("minSz")),
+    }
+  }
+
+  /// Base quantity -> contracts on the lot grid (at least the minimum size)
+  fn contracts(&self, quantity: Decimal) -> String {
+    floor_to_step(quantity / self.ct_val, self.lot_size).max(self.min_size).to_string()
+  }
+
+  fn price(&self, price: Decimal) -> String {
+    floor_to_step(price, self.tick_size).to_string()
+  }
+}
+
+/// OKX V5 REST connector for USDT perpetual swaps (instType=SWAP)
+pub struct OkxExchange {
+  base_url: String,
+  api_key: String,
+  api_secret: String,
+  passphrase: String,
+  http: reqwest::Client,
+  min_interval_ms: u64,
+  last_request_ms: AtomicI64,
+  time_offset_ms: AtomicI64,
+  telemetry: ExchangeTelemetry,
+  /// ordId -> instId; order endpoints need both
+  order_instruments: Mutex<HashMap<String, String>>,
+  instruments: Mutex<HashMap<String, Instrument>>,
+  /// margin mode per instrument ("cross" | "isolated"), sent as tdMode on every order
+  margin_modes: HashMap<String, &'static str>,
+  /// long/short position mode: orders must carry posSide
+  hedge: bool,
+}
+
+impl OkxExchange {
+  pub fn new(base_url: impl Into<String>, api_key: impl Into<String>, api_secret: impl Into<String>, passphrase: impl Into<String>) -> Self {
+    OkxExchange {
+      base_url: base_url.into(),
+      api_key: api_key.into(),
+      api_secret: api_secret.into(),
+      passphrase: passphrase.into(),
+      http: reqwest::Client::new(),
+      min_interval_ms: 50,
+      last_request_ms: AtomicI64::new(0),
+      time_offset_ms: AtomicI64::new(0),
+      telemetry: ExchangeTelemetry::default(),
+      order_instruments: Mutex::new(HashMap::new()),
+      instruments: Mutex::new(HashMap::new()),
+      margin_modes: HashMap::new(),
+      hedge: false,
+    }
+  }
+
+  /// Record REST latency and clock samples into a shared telemetry handle
+  pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+    self.telemetry = telemetry;
+    self
+  }
+
+  fn ts_with_offset(&self) -> i64 {
+    chrono::Utc::now().timestamp_millis() + self.time_offset_ms.load(Ordering::SeqCst)
+  }
+
+  /// Signature: base64 HMAC-SHA256 of `timestamp + METHOD + requestPath(+query) + body`
+  fn sign(&self, timestamp: &str, method: &str, path: &str, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes()).unwrap();
+    mac.update(format!("{}{}{}{}", timestamp, method, path, body).as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(mac.finalize().into_bytes())
+  }
+
+  async fn throttle(&self) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let elapsed = (now - self.last_request_ms.load(Ordering::SeqCst)).max(0) as u64;
+    if elapsed < self.min_interval_ms {
+      tokio::time::sleep(std::time::Duration::from_millis(self.min_interval_ms - elapsed)).await;
+    }
+    self.last_request_ms.store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+  }
+
+  /// Send a request and unwrap the `{code, msg, data}` envelope, including the per-order
+  /// `sCode` of trade endpoints. Unknown orders map to OrderNotFound when `order_id` is given
+  async fn send(&self, endpoint: &str, req: reqwest::RequestBuilder, order_id: Option<&OrderId>) -> Result<Vec<serde_json::Value>, TradingError> {
+    self.throttle().await;
+    let started = std::time::Instant::now();
+    let res = req.send().await;
+    let ok = res.as_ref().map(|r| r.status().is_success()).unwrap_or(false);
+    self.telemetry.record_request(endpoint, started.elapsed().as_secs_f64() * 1000.0, ok);
+    let res = res.map_err(|e| TradingError::ExchangeError(format!("{} http error: {}", endpoint, e)))?;
+    let status = res.status();
+    let body = res.json::<serde_json::Value>().await
+      .map_err(|e| TradingError::ExchangeError(format!("{} parse error: {}", endpoint, e)))?;
+    let data = body.get("data").and_then(|d| d.as_array()).cloned().unwrap_or_default();
+    let code = body.get("code").and_then(|c| c.as_str()).unwrap_or("-1");
+    let s_code = data.first().and_then(|d| d.get("sCode")).and_then(|c| c.as_str()).unwrap_or("0");
+    match (code, s_code, order_id) {
+      ("0", "0", _) if status.is_success() => Ok(data),
+      (ORDER_NOT_EXISTS, _, Some(id)) | (_, ORDER_NOT_EXISTS, Some(id)) => Err(TradingError::OrderNotFound(id.clone())),
+      _ => Err(TradingError::ExchangeError(format!("{} failed: {} {}", endpoint, status, body))),
+    }
+  }
+
+  async fn public_get(&self, path: &str, query: &str) -> Result<Vec<serde_json::Value>, TradingError> {
+    let url = format!("{}{}?{}", self.base_url, path, query);
+    self.send(&format!("GET {}", path), self.http.get(url), None).await
+  }
+
+  async fn signed(&self, method: reqwest::Method, path: &str, query: &str, body: Option<&serde_json::Value>, order_id: Option<&OrderId>) -> Result<Vec<serde_json::Value>, TradingError> {
+    let request_path = if query.is_empty() { path.to_string() } else { format!("{}?{}", path, query) };
+    let payload = body.map(|b| b.to_string()).unwrap_or_default();
+    let timestamp = Timestamp::from_millis(self.ts_with_offset()).to_datetime().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string();
+    let mut req = self.http.request(method.clone(), format!("{}{}", self.base_url, request_path))
+      .header("OK-ACCESS-KEY", &self.api_key)
+      .header("OK-ACCESS-SIGN", self.sign(&timestamp, method.as_str(), &request_path, &payload))
+      .header("OK-ACCESS-TIMESTAMP", timestamp)
+      .header("OK-ACCESS-PASSPHRASE", &self.passphrase);
+    if body.is_some() {
+      req = req.header("Content-Type", "application/json").body(payload);
+    }
+    self.send(&format!("{} {}", method, path), req, order_id).await
+  }
+
+  async fn instrument(&self, inst_id: &str) -> Result<Instrument, TradingError> {
+    if let Some(inst) = self.instruments.lock().unwrap_or_else(|e| e.into_inner()).get(inst_id) {
+      return Ok(inst.clone());
+    }
+    let data = self.public_get("/api/v5/public/instruments", &format!("instType=SWAP&instId={}", inst_id)).await?;
+    let inst = data.first().map(Instrument::from_json)
+      .ok_or_else(|| TradingError::DataNotFound(format!("instrument {}", inst_id)))?;
+    self.instruments.lock().unwrap_or_else(|e| e.into_inner()).insert(inst_id.to_string(), inst.clone());
+    Ok(inst)
+  }
+
+  fn remember_instrument(&self, order_id: &str, inst_id: &str) {
+    self.order_instruments.lock().unwrap_or_else(|e| e.into_inner()).insert(order_id.to_string(), inst_id.to_string());
+  }
+
+  fn instrument_for(&self, order_id: &OrderId) -> Result<String, TradingError> {
+    self.order_instruments.lock().unwrap_or_else(|e| e.into_inner())
+      .get(&order_id.0)
+      .cloned()
+      .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))
+  }
+
+  fn margin_mode(&self, inst_id: &str) -> &'static str {
+    self.margin_modes.get(inst_id).copied().unwrap_or("cross")
+  }
+
+  /// Orders in base units; sizes converted from contracts with the cached contract value
+  async fn parse_orders(&self, data: &[serde_json::Value]) -> Vec<Order> {
+    let mut orders = Vec::new();
+    for v in data {
+      let Some(inst_id) = v.get("instId").and_then(|i| i.as_str()) else { continue };
+      let ct_val = self.instrument(inst_id).await.map(|i| i.ct_val).unwrap_or(Decimal::ONE);
+      if let Some(order) = parse_order(v, ct_val) {
+        self.remember_instrument(&order.id.0, inst_id);
+        orders.push(order);
+      }
+    }
+    orders
+  }
+}
+
+fn parse_ms(v: Option<&serde_json::Value>) -> Option<i64> {
+  v.and_then(|x| x.as_str()).and_then(|s| s.parse().ok())
+}
+
+pub(crate) fn parse_order_status(state: &str) -> OrderStatus {
+  match state {
+    "live" => OrderStatus::New,
+    "partially_filled" => OrderStatus::PartiallyFilled,
+    "filled" => OrderStatus::Filled,
+    "canceled" | "mmp_canceled" => OrderStatus::Cancelled,
+    _ => OrderStatus::Expired,
+  }
+}
+
+/// Map an order object (order / orders-pending) to an Order; `ct_val` converts contracts to base units
+pub(crate) fn parse_order(v: &serde_json::Value, ct_val: Decimal) -> Option<Order> {
+  let side = match v.get("side")?.as_str()? { "buy" => OrderSide::Buy, _ => OrderSide::Sell };
+  let (order_type, tif) = match v.get("ordType")?.as_str()? {
+    "market" | "optimal_limit_ioc" => (OrderType::Market, "GTC"),
+    "post_only" => (OrderType::Limit, "GTX"),
+    "ioc" => (OrderType::Limit, "IOC"),
+    "fok" => (OrderType::Limit, "FOK"),
+    _ => (OrderType::Limit, "GTC"),
+  };
+  let inst_id = v.get("instId")?.as_str()?;
+  let mut order = Order::new(okx_symbol(inst_id), side, order_type, parse_decimal(v.get("sz")) * ct_val, parse_decimal(v.get("px")));
+  order.id = OrderId(v.get("ordId")?.as_str()?.to_string());
+  order.client_order_id = v.get("clOrdId").and_then(|c| c.as_str()).filter(|c| !c.is_empty()).map(str::to_string);
+  order.time_in_force = tif.to_string();
+  if let Some(created) = parse_ms(v.get("cTime")) { order.created_at = Timestamp::from_millis(created); }
+  order.reduce_only = v.get("reduceOnly").and_then(|r| r.as_str()).map(|r| r == "true");
+  order.position_side = match v.get("posSide").and_then(|p| p.as_str()) {
+    Some("long") => Some("LONG".to_string()),
+    Some("short") => Some("SHORT".to_string()),
+    _ => None,
+  };
+  Some(order)
+}
+
+/// Map a candle row `[ts, o, h, l, c, vol(contracts), volCcy(base), volCcyQuote, confirm]`
+pub(crate) fn parse_candle(symbol: &str, v: &serde_json::Value) -> Option<MarketData> {
+  let k = v.as_array()?;
+  Some(MarketData {
+    symbol: symbol.to_string(),
+    timestamp: Timestamp::from_millis(parse_ms(k.first())?),
+    open: parse_decimal(k.get(1)),
+    high: parse_decimal(k.get(2)),
+    low: parse_decimal(k.get(3)),
+    close: parse_decimal(k.get(4)),
+    volume: parse_decimal(k.get(6)),
+  })
+}
+
+/// Create-order body; `ord_type` is derived from the order type and time in force
+pub(crate) fn order_body(order: &Order, inst: &Instrument, inst_id: &str, td_mode: &str, hedge: bool) -> serde_json::Value {
+  let side = match order.side { OrderSide::Buy => "buy", OrderSide::Sell => "sell" };
+  let market = !matches!(order.order_type, OrderType::Limit | OrderType::Iceberg | OrderType::StopLimit) || order.price <= Decimal::ZERO;
+  let ord_type = match (market, order.time_in_force.as_str()) {
+    (true, _) => "market",
+    (false, "GTX") => "post_only",
+    (false, "IOC") => "ioc",
+    (false, "FOK") => "fok",
+    _ => "limit",
+  };
+  let mut body = serde_json::json!({
+    "instId": inst_id,
+    "tdMode": td_mode,
+    "side": side,
+    "ordType": ord_type,
+    "sz": inst.contracts(order.quantity),
+  });
+  if !market { body["px"] = serde_json::json!(inst.price(order.price)); }
+  if hedge {
+    // long/short mode: opening buys and closing sells act on the long side
+    let pos_side = match (order.position_side.as_deref(), &order.side, order.reduce_only == Some(true)) {
+      (Some("LONG"), _, _) => "long",
+      (Some("SHORT"), _, _) => "short",
+      (_, OrderSide::Buy, false) | (_, OrderSide::Sell, true) => "long",
+      _ => "short",
+    };
+    body["posSide"] = serde_json::json!(pos_side);
+  } else if order.reduce_only == Some(true) {
+    body["reduceOnly"] = serde_json::json!(true);
+  }
+  // clOrdId: up to 32 alphanumerics
+  if let Some(cid) = &order.client_order_id {
+    let cid: String = cid.chars().filter(|c| c.is_ascii_alphanumeric()).take(32).collect();
+    if !cid.is_empty() { body["clOrdId"] = serde_json::json!(cid); }
+  }
+  body
+}
+
+#[async_trait]
+impl Exchange for OkxExchange {
+  async fn submit_order(&mut self, order: Order) -> Result<OrderId, TradingError> {
+    let inst_id = okx_inst_id(&order.symbol);
+    let inst = self.instrument(&inst_id).await?;
+    let body = order_body(&order, &inst, &inst_id, self.margin_mode(&inst_id), self.hedge);
+    let data = self.signed(reqwest::Method::POST, "/api/v5/trade/order", "", Some(&body), None).await?;
+    let order_id = data.first().and_then(|d| d.get("ordId")).and_then(|id| id.as_str()).map(|id| OrderId(id.to_string()))
+      .ok_or_else(|| TradingError::ParseError(format!("ordId missing in {:?}", data)))?;
+    self.remember_instrument(&order_id.0, &inst_id);
+    Ok(order_id)
+  }
+
+  async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+    let inst_id = self.instrument_for(order_id)?;
+    let body = serde_json::json!({"instId": inst_id, "ordId": order_id.0});
+    self.signed(reqwest::Method::POST, "/api/v5/trade/cancel-order", "", Some(&body), Some(order_id)).await?;
+    Ok(())
+  }
+
+  /// Amends size/price in place; the order keeps its id
+  async fn modify_order(&mut self, order_id: &OrderId, order: Order) -> Result<OrderId, TradingError> {
+    let inst_id = self.instrument_for(order_id)?;
+    let inst = self.instrument(&inst_id).await?;
+    let mut body = serde_json::json!({"instId": inst_id, "ordId": order_id.0, "newSz": inst.contracts(order.quantity)});
+    if order.price > Decimal::ZERO { body["newPx"] = serde_json::json!(inst.price(order.price)); }
+    self.signed(reqwest::Method::POST, "/api/v5/trade/amend-order", "", Some(&body), Some(order_id)).await?;
+    Ok(order_id.clone())
+  }
+
+  async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+    let inst_id = self.instrument_for(order_id)?;
+    let data = self.signed(reqwest::Method::GET, "/api/v5/trade/order", &format!("instId={}&ordId={}", inst_id, order_id.0), None, Some(order_id)).await?;
+    let state = data.first().and_then(|o| o.get("state")).and_then(|s| s.as_str())
+      .ok_or_else(|| TradingError::OrderNotFound(order_id.clone()))?;
+    Ok(parse_order_status(state))
+  }
+
+  async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+    let data = self.signed(reqwest::Method::GET, "/api/v5/trade/orders-pending", "instType=SWAP", None, None).await?;
+    Ok(self.parse_orders(&data).await)
+  }
+
+  async fn get_positions(&self) -> Result<Vec<Position>, TradingError> {
+    let data = self.signed(reqwest::Method::GET, "/api/v5/account/positions", "instType=SWAP", None, None).await?;
+    let mut out = Vec::new();
+    for p in &data {
+      let Some(inst_id) = p.get("instId").and_then(|i| i.as_str()) else { continue };
+      let contracts = parse_decimal(p.get("pos"));
+      if contracts.is_zero() { continue; }
+      // net mode: signed pos; long/short mode: unsigned pos per side
+      let signed = if p.get("posSide").and_then(|s| s.as_str()) == Some("short") { -contracts.abs() } else { contracts };
+      let ct_val = self.instrument(inst_id).await.map(|i| i.ct_val).unwrap_or(Decimal::ONE);
+      let entry_price = parse_decimal(p.get("avgPx"));
+      let mut pos = Position::new(okx_symbol(inst_id), signed * ct_val, entry_price);
+      pos.update_price(Some(parse_decimal(p.get("markPx"))).filter(|m| *m > Decimal::ZERO).unwrap_or(entry_price));
+      out.push(pos);
+    }
+    Ok(out)
+  }
+
+  async fn get_recent_trades(&self, symbol: &str, limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+    let inst_id = okx_inst_id(symbol);
+    let ct_val = self.instrument(&inst_id).await?.ct_val;
+    let data = self.public_get("/api/v5/market/trades", &format!("instId={}&limit={}", inst_id, limit.unwrap_or(100).min(500))).await?;
+    Ok(data.iter()
+      .filter_map(|t| {
+        let side = match t.get("side")?.as_str()? { "buy" => OrderSide::Buy, _ => OrderSide::Sell };
+        let ts = parse_ms(t.get("ts"))?;
+        Some(Trade::new(t.get("tradeId")?.as_str()?, symbol, parse_decimal(t.get("px")), parse_decimal(t.get("sz")) * ct_val, ts, OrderId(String::new()), side))
+      })
+      .collect())
+  }
+
+  async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+    let data = self.public_get("/api/v5/market/ticker", &format!("instId={}", okx_inst_id(symbol))).await?;
+    let t = data.first().ok_or_else(|| TradingError::DataNotFound(format!("ticker {}", symbol)))?;
+    let (bid, ask) = (parse_decimal(t.get("bidPx")), parse_decimal(t.get("askPx")));
+    let close = if bid > Decimal::ZERO && ask > Decimal::ZERO { (bid + ask) / Decimal::TWO } else { parse_decimal(t.get("last")) };
+    if close <= Decimal::ZERO { return Err(TradingError::DataNotFound(format!("price {}", symbol))); }
+    Ok(MarketData { symbol: symbol.to_string(), timestamp: self.ts_with_offset().into(), open: close, high: close, low: close, close, volume: Decimal::ZERO })
+  }
+
+  async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+    let inst_id = okx_inst_id(symbol);
+    let ct_val = to_f64(self.instrument(&inst_id).await?.ct_val);
+    let data = self.public_get("/api/v5/market/books", &format!("instId={}&sz={}", inst_id, depth.clamp(1, 400))).await?;
+    let book = data.first().ok_or_else(|| TradingError::DataNotFound(format!("order book {}", symbol)))?;
+    // levels are [price, contracts, deprecated, order count]
+    let levels = |side: &str| -> Vec<PriceLevel> {
+      book.get(side).and_then(|l| l.as_array()).into_iter().flatten()
+        .filter_map(|l| Some(PriceLevel::new(l.get(0)?.as_str()?.parse().ok()?, parse_f64(l.get(1)) * ct_val)))
+        .collect()
+    };
+    let timestamp = parse_ms(book.get("ts")).unwrap_or_else(|| self.ts_with_offset());
+    let mut book = OrderBook::new(symbol, timestamp, levels("bids"), levels("asks"));
+    book.truncate(depth);
+    Ok(book)
+  }
+
+  async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+    let inst_id = okx_inst_id(symbol);
+    let funding = self.public_get("/api/v5/public/funding-rate", &format!("instId={}", inst_id)).await?;
+    let mark = self.public_get("/api/v5/public/mark-price", &format!("instType=SWAP&instId={}", inst_id)).await?;
+    let f = funding.first().ok_or_else(|| TradingError::DataNotFound(format!("funding rate {}", symbol)))?;
+    Ok(FundingRate {
+      symbol: symbol.to_string(),
+      mark_price: mark.first().map(|m| parse_f64(m.get("markPx"))).unwrap_or_default(),
+      index_price: 0.0,
+      funding_rate: parse_f64(f.get("fundingRate")),
+      next_funding_time: parse_ms(f.get("fundingTime")).unwrap_or_default(),
+      timestamp: self.ts_with_offset(),
+    })
+  }
+
+  /// Candles oldest first from `start_time`, at most 100 per call (history-candles page size)
+  async fn get_historical_data(&self, symbol: &str, interval: &str, start_time: i64, end_time: Option<i64>, limit: Option<usize>) -> Result<Vec<MarketData>, TradingError> {
+    let bar = okx_bar(interval)?;
+    let limit = limit.unwrap_or(MAX_CANDLES).clamp(1, MAX_CANDLES);
+    // `after` returns candles older than it and `before` newer than it, newest first:
+    // bound the window so the page starts at `start_time`
+    let window_end = start_time + parse_timeframe(interval)? * limit as i64;
+    let after = end_time.map_or(window_end, |end| end.min(window_end - 1) + 1);
+    let query = format!("instId={}&bar={}&before={}&after={}&limit={}", okx_inst_id(symbol), bar, start_time - 1, after, limit);
+    let data = self.public_get("/api/v5/market/history-candles", &query).await?;
+    let mut candles: Vec<MarketData> = data.iter().filter_map(|k| parse_candle(symbol, k)).collect();
+    candles.reverse();
+    Ok(candles)
+  }
+
+  /// Contract grid converted to base units (sizes are sent in contracts of `ctVal`)
+  async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+    let inst = self.instrument(&okx_inst_id(symbol)).await?;
+    Ok(SymbolInfo {
+      symbol: symbol.to_string(),
+      tick_size: inst.tick_size,
+      step_size: inst.lot_size * inst.ct_val,
+      min_qty: inst.min_size * inst.ct_val,
+      min_notional: Decimal::ZERO,
+    })
+  }
+
+  async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+    let data = self.signed(reqwest::Method::GET, "/api/v5/account/balance", &format!("ccy={}", asset), None, None).await?;
+    Ok(data.first()
+      .and_then(|account| account.get("details")).and_then(|d| d.as_array())
+      .and_then(|details| details.iter().find(|d| d.get("ccy").and_then(|c| c.as_str()) == Some(asset)))
+      .map(|d| parse_decimal(d.get("cashBal")))
+      .unwrap_or_default())
+  }
+
+  async fn set_futures_leverage(&mut self, symbol: &str, leverage: u32) -> Result<(), TradingError> {
+    let inst_id = okx_inst_id(symbol);
+    let body = serde_json::json!({"instId": inst_id, "lever": leverage.to_string(), "mgnMode": self.margin_mode(&inst_id)});
+    self.signed(reqwest::Method::POST, "/api/v5/account/set-leverage", "", Some(&body), None).await?;
+    Ok(())
+  }
+
+  async fn set_futures_position_mode(&mut self, hedge: bool) -> Result<(), TradingError> {
+    let body = serde_json::json!({"posMode": if hedge { "long_short_mode" } else { "net_mode" }});
+    self.signed(reqwest::Method::POST, "/api/v5/account/set-position-mode", "", Some(&body), None).await?;
+    self.hedge = hedge;
+    Ok(())
+  }
+
+  /// OKX picks the margin mode per order (tdMode); remember it for later orders and leverage calls
+  async fn set_futures_margin_mode(&mut self, symbol: &str, isolated: bool) -> Result<(), TradingError> {
+    self.margin_modes.insert(okx_inst_id(symbol), if isolated { "isolated" } else { "cross" });
+    Ok(())
+  }
+
+  fn capabilities(&self) -> ExchangeCapabilities {
+    // stop/trailing orders live on the separate algo endpoints and are not wired up
+    ExchangeCapabilities {
+      order_types: vec![OrderType::Market, OrderType::Limit],
+      time_in_force: vec!["GTC".to_string(), "IOC".to_string(), "FOK".to_string(), "GTX".to_string()],
+      hedge_mode: true,
+      batch_orders: true,
+      native_iceberg: false,
+      native_twap: false,
+      native_vwap: false,
+    }
+  }
+
+  async fn sync_time(&mut self) -> Result<(), TradingError> {
+    let sent_at = chrono::Utc::now().timestamp_millis();
+    let data = self.public_get("/api/v5/public/time", "").await?;
+    let server_ms = data.first().and_then(|t| parse_ms(t.get("ts")))
+      .ok_or_else(|| TradingError::ParseError(format!("ts missing in {:?}", data)))?;
+    let received_at = chrono::Utc::now().timestamp_millis();
+    let rtt = (received_at - sent_at).max(0);
+    let offset = server_ms - (sent_at + rtt / 2);
+    self.time_offset_ms.store(offset, Ordering::SeqCst);
+    self.telemetry.record_clock_offset(offset, rtt as f64, received_at);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::dec;
+
+  #[test]
+  fn test_contract_sizing_and_signing() {
+    assert_eq!(okx_inst_id("BTCUSDT"), "BTC-USDT-SWAP");
+    assert_eq!(okx_inst_id("eth-usdt-swap"), "ETH-USDT-SWAP");
+    assert_eq!(okx_symbol("BTC-USDT-SWAP"), "BTCUSDT");
+    assert_eq!(okx_bar("4h").unwrap(), "4H");
+    assert!(okx_bar("7m").is_err());
+
+    // BTC-USDT-SWAP: 0.01 BTC per contract
+    let inst = Instrument::from_json(&serde_json::json!({"ctVal": "0.01", "tickSz": "0.1", "lotSz": "1", "minSz": "1"}));
+    let order = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Limit, dec!(0.257), dec!(60_000.07))
+      .with_time_in_force("GTX")
+      .with_reduce_only(true)
+      .with_client_order_id("2f1c-77ab");
+    let body = order_body(&order, &inst, "BTC-USDT-SWAP", "cross", false);
+    assert_eq!(body["sz"], "25");
+    assert_eq!((body["ordType"].as_str(), body["px"].as_str()), (Some("post_only"), Some("60000.0")));
+    assert_eq!(body["reduceOnly"], true);
+    assert_eq!(body["clOrdId"], "2f1c77ab");
+    // hedge mode: a reducing sell closes the long side
+    assert_eq!(order_body(&order, &inst, "BTC-USDT-SWAP", "cross", true)["posSide"], "long");
+
+    let v = serde_json::json!({
+      "instId": "BTC-USDT-SWAP", "ordId": "680800019749904384", "clOrdId": "", "side": "buy", "ordType": "limit",
+      "sz": "3", "px": "59000", "state": "partially_filled", "posSide": "net", "reduceOnly": "false", "cTime": "1700000000000"
+    });
+    let parsed = parse_order(&v, inst.ct_val).unwrap();
+    assert_eq!((parsed.symbol.as_str(), parsed.quantity, parsed.price), ("BTCUSDT", dec!(0.03), dec!(59_000)));
+    assert_eq!(parsed.client_order_id, None);
+    assert_eq!(parsed.created_at.as_millis(), 1_700_000_000_000);
+    assert_eq!(parse_order_status(v["state"].as_str().unwrap()), OrderStatus::PartiallyFilled);
+
+    let exchange = OkxExchange::new("http://localhost", "key", "secret", "pass");
+    assert_eq!(exchange.sign("2020-12-08T09:08:57.715Z", "GET", "/api/v5/account/balance?ccy=BTC", ""), "wpDvCwYCprcMQsQkxWJiWy+YADoQE4ep+OEKKLimMoY=");
+  }
+}
@@ -0,0 +1,190 @@
+//! Paper trading exchange
+//!
+//! Live ticker updates from the WebSocket market stream drive a local `MockExchange`, which simulates
+//! fills with the configured `FillModel`. Strategies run against real prices without API keys and
+//! without risking funds. Historical candles, order books and funding rates come from the public
+//! endpoints of the live connector when one is given.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::config::Config;
+use crate::error::TradingError;
+use crate::exchange::mocks::MockExchange;
+use crate::exchange::traits::Exchange;
+use crate::market_data::aggregator::ticker_point;
+use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::fee::FeeTier;
+use crate::models::funding::FundingRate;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderFill, OrderId, OrderStatus};
+use crate::models::order_book::OrderBook;
+use crate::models::symbol_info::SymbolInfo;
+use crate::models::trade::Trade;
+
+/// Simulated account fed by live prices
+pub struct PaperExchange {
+    sim: MockExchange,
+    /// Public market data source (no credentials needed)
+    market: Option<Box<dyn Exchange>>,
+    /// Last rolling 24h volume per symbol; the positive change between tickers is the traded volume
+    last_volume: HashMap<String, Decimal>,
+}
+
+impl PaperExchange {
+    pub fn new(config: &Config, market: Option<Box<dyn Exchange>>) -> Self {
+        let mut sim = MockExchange::empty(config.clone());
+        for (asset, amount) in &config.exchange.paper.initial_balances {
+            sim.set_balance(asset, to_decimal(*amount));
+        }
+        PaperExchange { sim, market, last_volume: HashMap::new() }
+    }
+
+    /// Apply one live ticker: the last price becomes a point candle and open orders are matched
+    pub fn on_ticker(&mut self, ticker: &MarketData) -> Result<(), TradingError> {
+        let previous = self.last_volume.insert(ticker.symbol.clone(), ticker.volume);
+        self.sim.push_market_data(ticker_point(ticker, previous))
+    }
+
+    /// Forward live tickers for `symbols` from the market stream into the paper exchange
+    pub fn spawn_feed(exchange: Arc<RwLock<PaperExchange>>, stream: Arc<RwLock<MarketDataStream>>, symbols: Vec<String>) {
+        for symbol in symbols {
+            let exchange = exchange.clone();
+            let stream = stream.clone();
+            tokio::spawn(async move {
+                let mut tickers = stream.write().await.get_or_create_channel(&symbol).subscribe();
+                loop {
+                    match tickers.recv().await {
+                        Ok(ticker) => {
+                            if let Err(e) = exchange.write().await.on_ticker(&ticker) {
+                                log::warn!("paper ticker {} not applied: {}", symbol, e);
+                            }
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => log::debug!("paper feed {} lagged: {} tickers skipped", symbol, n),
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for PaperExchange {
+    async fn submit_order(&mut self, order: Order) -> Result<OrderId, TradingError> {
+        self.sim.submit_order(order).await
+    }
+
+    async fn cancel_order(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+        self.sim.cancel_order(order_id).await
+    }
+
+    async fn modify_order(&mut self, order_id: &OrderId, order: Order) -> Result<OrderId, TradingError> {
+        self.sim.modify_order(order_id, order).await
+    }
+
+    async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
+        self.sim.get_order_status(order_id).await
+    }
+
+    async fn get_order_fill(&self, order_id: &OrderId) -> Result<Option<OrderFill>, TradingError> {
+        self.sim.get_order_fill(order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
+        self.sim.get_open_orders().await
+    }
+
+    async fn get_recent_trades(&self, symbol: &str, limit: Option<usize>) -> Result<Vec<Trade>, TradingError> {
+        self.sim.get_recent_trades(symbol, limit).await
+    }
+
+    /// Latest live ticker; before the first one arrives, the public REST price
+    async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
+        match (self.sim.get_market_data(symbol).await, &self.market) {
+            (Ok(data), _) => Ok(data),
+            (Err(_), Some(market)) => market.get_market_data(symbol).await,
+            (Err(e), None) => Err(e),
+        }
+    }
+
+    async fn get_historical_data(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time: i64,
+        end_time: Option<i64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<MarketData>, TradingError> {
+        match &self.market {
+            Some(market) => market.get_historical_data(symbol, interval, start_time, end_time, limit).await,
+            None => self.sim.get_historical_data(symbol, interval, start_time, end_time, limit).await,
+        }
+    }
+
+    async fn get_order_book(&self, symbol: &str, depth: usize) -> Result<OrderBook, TradingError> {
+        match &self.market {
+            Some(market) => market.get_order_book(symbol, depth).await,
+            None => self.sim.get_order_book(symbol, depth).await,
+        }
+    }
+
+    // 실거래소 규칙으로 검증해야 실전 전환 시 거부되는 주문을 미리 확인 가능
+    async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+        match &self.market {
+            Some(market) => market.get_symbol_info(symbol).await,
+            None => self.sim.get_symbol_info(symbol).await,
+        }
+    }
+
+    // 체결은 시뮬레이터가 하므로 수수료도 시뮬레이터 기준
+    async fn get_fee_tier(&self, symbol: &str) -> Result<FeeTier, TradingError> {
+        self.sim.get_fee_tier(symbol).await
+    }
+
+    async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+        match &self.market {
+            Some(market) => market.get_funding_rate(symbol).await,
+            None => self.sim.get_funding_rate(symbol).await,
+        }
+    }
+
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError> {
+        self.sim.get_balance(asset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::models::order::{OrderSide, OrderType};
+
+    fn ticker(price: f64, volume_24h: f64) -> MarketData {
+        MarketData { symbol: "BTCUSDT".into(), timestamp: 0.into(), open: dec!(48_000), high: dec!(52_000), low: dec!(47_000), close: to_decimal(price), volume: to_decimal(volume_24h) }
+    }
+
+    #[tokio::test]
+    async fn test_live_tickers_drive_simulated_fills() {
+        let mut exchange = PaperExchange::new(&Config::default(), None);
+        assert_eq!(exchange.get_balance("USDT").await.unwrap(), dec!(10_000));
+        assert!(exchange.get_market_data("BTCUSDT").await.is_err());
+
+        exchange.on_ticker(&ticker(50_000.0, 1_000.0)).unwrap();
+        let bid = exchange.submit_order(Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(49_500))).await.unwrap();
+        // the 24h high/low is not the price path: only the last price counts
+        exchange.on_ticker(&ticker(49_600.0, 1_002.0)).unwrap();
+        assert_eq!(exchange.get_order_status(&bid).await.unwrap(), OrderStatus::New);
+
+        exchange.on_ticker(&ticker(49_400.0, 1_005.0)).unwrap();
+        assert_eq!(exchange.get_order_status(&bid).await.unwrap(), OrderStatus::Filled);
+        assert_eq!(exchange.get_balance("BTC").await.unwrap(), dec!(0.1));
+        assert_eq!(exchange.get_balance("USDT").await.unwrap(), dec!(10_000) - dec!(4_950));
+        let latest = exchange.get_market_data("BTCUSDT").await.unwrap();
+        assert_eq!((latest.high, latest.low, latest.volume), (dec!(49_400), dec!(49_400), dec!(3)));
+    }
+}
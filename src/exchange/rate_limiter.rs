@@ -0,0 +1,161 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+/// Binance spot request weight per minute (per IP)
+pub const BINANCE_SPOT_WEIGHT_PER_MINUTE: u32 = 6000;
+/// Binance USDT-M futures request weight per minute (per IP)
+pub const BINANCE_FUTURES_WEIGHT_PER_MINUTE: u32 = 2400;
+
+/// Pause after a 429 that carries no Retry-After header
+const DEFAULT_429_BACKOFF: Duration = Duration::from_secs(1);
+/// Pause after a 418 (IP ban) without Retry-After; Binance bans start at two minutes
+const DEFAULT_418_BACKOFF: Duration = Duration::from_secs(120);
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    blocked_until: Option<Instant>,
+}
+
+/// Weight-aware token bucket shared by every request of one connector.
+///
+/// Tokens refill continuously at `capacity / window`. The exchange's own count
+/// (`X-MBX-USED-WEIGHT-1M`) pulls the bucket down when other clients on the same
+/// IP spend weight, and 418/429 responses block all requests until Retry-After.
+#[derive(Debug)]
+pub struct WeightRateLimiter {
+    capacity: f64,
+    window: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+impl WeightRateLimiter {
+    pub fn new(capacity: u32, window: Duration) -> Self {
+        WeightRateLimiter {
+            capacity: capacity as f64,
+            window,
+            bucket: Mutex::new(Bucket { tokens: capacity as f64, updated: Instant::now(), blocked_until: None }),
+        }
+    }
+
+    pub fn per_minute(capacity: u32) -> Self {
+        Self::new(capacity, Duration::from_secs(60))
+    }
+
+    /// Wait until `weight` tokens are available, then spend them
+    pub async fn acquire(&self, weight: u32) {
+        while let Some(wait) = self.try_acquire(weight, Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Spend `weight` if possible; otherwise return how long to wait before trying again
+    pub fn try_acquire(&self, weight: u32, now: Instant) -> Option<Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(until) = bucket.blocked_until {
+            if now < until {
+                return Some(until - now);
+            }
+            bucket.blocked_until = None;
+        }
+        self.refill(&mut bucket, now);
+        // a request heavier than the whole bucket only has to wait for a full bucket
+        let weight = (weight as f64).min(self.capacity);
+        if bucket.tokens >= weight {
+            bucket.tokens -= weight;
+            return None;
+        }
+        let missing = weight - bucket.tokens;
+        Some(Duration::from_secs_f64(missing / self.refill_per_sec()))
+    }
+
+    /// Align the bucket with the exchange's used weight and honor 418/429 back-off
+    pub fn observe_response(&self, status: u16, headers: &HeaderMap, now: Instant) {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).and_then(|v| v.trim().parse::<u64>().ok());
+        if let Some(used) = header("x-mbx-used-weight-1m").or_else(|| header("x-mbx-used-weight")) {
+            self.observe_used_weight(used as u32, now);
+        }
+        let default_backoff = match status {
+            429 => DEFAULT_429_BACKOFF,
+            418 => DEFAULT_418_BACKOFF,
+            _ => return,
+        };
+        let retry_after = header("retry-after").map(Duration::from_secs).unwrap_or(default_backoff);
+        log::warn!("rate limited ({}), pausing requests for {:?}", status, retry_after);
+        self.block_for(retry_after, now);
+    }
+
+    /// Exchange-reported weight used in the current window; never raises the local bucket
+    pub fn observe_used_weight(&self, used: u32, now: Instant) {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut bucket, now);
+        let remaining = (self.capacity - used as f64).max(0.0);
+        bucket.tokens = bucket.tokens.min(remaining);
+    }
+
+    /// Block every request until `now + duration` and drain the bucket
+    pub fn block_for(&self, duration: Duration, now: Instant) {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let until = now + duration;
+        bucket.blocked_until = Some(bucket.blocked_until.map_or(until, |b| b.max(until)));
+        bucket.tokens = 0.0;
+        bucket.updated = until;
+    }
+
+    /// Tokens currently available (for telemetry)
+    pub fn available(&self) -> f64 {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        self.refill(&mut bucket, Instant::now());
+        bucket.tokens
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.capacity / self.window.as_secs_f64().max(f64::EPSILON)
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        if now <= bucket.updated {
+            return;
+        }
+        let elapsed = (now - bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.refill_per_sec()).min(self.capacity);
+        bucket.updated = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_weight_bucket_and_headers() {
+        let limiter = WeightRateLimiter::per_minute(1200);
+        let t0 = Instant::now();
+
+        // 1200 weight per minute refills 20 per second
+        assert_eq!(limiter.try_acquire(1000, t0), None);
+        let wait = limiter.try_acquire(400, t0).unwrap();
+        assert!((wait.as_secs_f64() - 10.0).abs() < 1e-6);
+        assert_eq!(limiter.try_acquire(400, t0 + Duration::from_secs(10)), None);
+
+        // the exchange saw more weight than we spent locally (other clients on the IP)
+        let limiter = WeightRateLimiter::per_minute(1200);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mbx-used-weight-1m", HeaderValue::from_static("1190"));
+        limiter.observe_response(200, &headers, t0);
+        assert_eq!(limiter.try_acquire(10, t0), None);
+        assert!(limiter.try_acquire(1, t0).is_some());
+
+        // 429 with Retry-After blocks everything until it elapses
+        headers.insert("retry-after", HeaderValue::from_static("5"));
+        limiter.observe_response(429, &headers, t0);
+        let wait = limiter.try_acquire(1, t0 + Duration::from_secs(1)).unwrap();
+        assert_eq!(wait, Duration::from_secs(4));
+        assert!(limiter.try_acquire(1, t0 + Duration::from_secs(5)).is_some());
+        assert_eq!(limiter.try_acquire(1, t0 + Duration::from_secs(6)), None);
+    }
+}
@@ -0,0 +1,86 @@
+//! Named exchange connections.
+//!
+//! Lets one process trade several venues at once (e.g. Binance futures next to a mock
+//! or paper account). Orders pick a venue through `Order::exchange`; orders without one
+//! go to the default exchange.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+
+/// Exchanges keyed by their configured name, with one default venue.
+#[derive(Clone)]
+pub struct ExchangeRegistry {
+    default: String,
+    exchanges: BTreeMap<String, Arc<RwLock<dyn Exchange>>>,
+}
+
+impl ExchangeRegistry {
+    /// Registry holding only the default exchange.
+    pub fn new(default_name: impl Into<String>, exchange: Arc<RwLock<dyn Exchange>>) -> Self {
+        let default = default_name.into();
+        ExchangeRegistry { exchanges: BTreeMap::from([(default.clone(), exchange)]), default }
+    }
+
+    /// Adds another venue; names must be unique.
+    pub fn register(&mut self, name: impl Into<String>, exchange: Arc<RwLock<dyn Exchange>>) -> Result<(), TradingError> {
+        let name = name.into();
+        if self.exchanges.contains_key(&name) {
+            return Err(TradingError::InvalidParameter(format!("Exchange '{}' already registered", name)));
+        }
+        self.exchanges.insert(name, exchange);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<RwLock<dyn Exchange>>> {
+        self.exchanges.get(name).cloned()
+    }
+
+    /// Venue for an order: the named exchange, or the default when `name` is `None`.
+    pub fn resolve(&self, name: Option<&str>) -> Result<Arc<RwLock<dyn Exchange>>, TradingError> {
+        let name = name.unwrap_or(&self.default);
+        self.get(name).ok_or_else(|| TradingError::InvalidParameter(format!("Unknown exchange '{}'", name)))
+    }
+
+    pub fn default_name(&self) -> &str {
+        &self.default
+    }
+
+    pub fn default_exchange(&self) -> Arc<RwLock<dyn Exchange>> {
+        self.exchanges[&self.default].clone()
+    }
+
+    /// Registered names in sorted order.
+    pub fn names(&self) -> Vec<String> {
+        self.exchanges.keys().cloned().collect()
+    }
+
+    /// Iterates over `(name, exchange)` pairs in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Arc<RwLock<dyn Exchange>>)> {
+        self.exchanges.iter().map(|(name, exchange)| (name.as_str(), exchange))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::exchange::mocks::MockExchange;
+
+    #[test]
+    fn test_resolve_named_and_default_venues() {
+        let primary: Arc<RwLock<dyn Exchange>> = Arc::new(RwLock::new(MockExchange::new(Config::default())));
+        let secondary: Arc<RwLock<dyn Exchange>> = Arc::new(RwLock::new(MockExchange::new(Config::default())));
+        let mut registry = ExchangeRegistry::new("binance", primary.clone());
+        registry.register("sandbox", secondary.clone()).unwrap();
+        assert!(registry.register("sandbox", secondary.clone()).is_err());
+
+        assert!(Arc::ptr_eq(&registry.resolve(None).unwrap(), &primary));
+        assert!(Arc::ptr_eq(&registry.resolve(Some("sandbox")).unwrap(), &secondary));
+        assert!(registry.resolve(Some("kraken")).is_err());
+        assert_eq!(registry.names(), vec!["binance", "sandbox"]);
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::config::TelemetryConfig;
+
+/// Samples kept per endpoint/stream for percentile estimates
+const LATENCY_WINDOW: usize = 256;
+/// Clock offset samples kept for drift estimation
+const CLOCK_WINDOW: usize = 32;
+
+/// Rolling latency window for one REST endpoint or WebSocket stream
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    samples: VecDeque<f64>,
+    count: u64,
+    errors: u64,
+    last_ms: f64,
+    max_ms: f64,
+    last_at: i64,
+}
+
+impl LatencyWindow {
+    fn record(&mut self, value_ms: f64, at: i64) {
+        self.samples.push_back(value_ms);
+        if self.samples.len() > LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+        self.count += 1;
+        self.last_ms = value_ms;
+        self.max_ms = self.max_ms.max(value_ms);
+        self.last_at = at;
+    }
+
+    fn stats(&self) -> LatencyStats {
+        let mut sorted: Vec<f64> = self.samples.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let percentile = |p: f64| -> f64 {
+            if sorted.is_empty() {
+                return 0.0;
+            }
+            let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+            sorted[idx]
+        };
+        LatencyStats {
+            count: self.count,
+            errors: self.errors,
+            last_ms: self.last_ms,
+            mean_ms: if sorted.is_empty() { 0.0 } else { sorted.iter().sum::<f64>() / sorted.len() as f64 },
+            p50_ms: percentile(0.5),
+            p95_ms: percentile(0.95),
+            max_ms: self.max_ms,
+            last_at: self.last_at,
+        }
+    }
+}
+
+/// Latency summary over the recent window
+#[derive(Debug, Clone, Serialize)]
+pub struct LatencyStats {
+    pub count: u64,
+    pub errors: u64,
+    pub last_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    pub last_at: i64,
+}
+
+/// Local-vs-server clock offset and how fast it moves
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ClockDriftStats {
+    /// server time - local time (ms), corrected for half the round trip
+    pub offset_ms: Option<i64>,
+    pub rtt_ms: Option<f64>,
+    /// Slope of the offset over the sample window
+    pub drift_ms_per_hour: Option<f64>,
+    pub samples: usize,
+    pub last_sync_at: Option<i64>,
+}
+
+/// Health snapshot served on /health/exchange
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetrySnapshot {
+    /// "ok" or "degraded"
+    pub status: &'static str,
+    pub issues: Vec<String>,
+    pub endpoints: BTreeMap<String, LatencyStats>,
+    pub streams: BTreeMap<String, LatencyStats>,
+    pub clock: ClockDriftStats,
+    /// WebSocket connector -> currently connected
+    pub connections: BTreeMap<String, bool>,
+}
+
+/// Connect/disconnect transition of a WebSocket connector
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectionEvent {
+    pub connector: String,
+    pub connected: bool,
+    pub detail: String,
+    pub at: i64,
+}
+
+#[derive(Debug, Default)]
+struct TelemetryState {
+    endpoints: HashMap<String, LatencyWindow>,
+    streams: HashMap<String, LatencyWindow>,
+    clock: VecDeque<(i64, i64, f64)>, // (measured_at, offset_ms, rtt_ms)
+    connections: HashMap<String, bool>,
+}
+
+/// Shared handle for per-endpoint latency, stream lag, clock drift and connector state.
+/// Cheap to clone; the REST connector and WebSocket provider record into the same instance.
+#[derive(Clone)]
+pub struct ExchangeTelemetry {
+    config: TelemetryConfig,
+    state: Arc<Mutex<TelemetryState>>,
+    connection_events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl Default for ExchangeTelemetry {
+    fn default() -> Self {
+        Self::new(TelemetryConfig::default())
+    }
+}
+
+impl ExchangeTelemetry {
+    pub fn new(config: TelemetryConfig) -> Self {
+        let (connection_events, _) = broadcast::channel(64);
+        ExchangeTelemetry {
+            config,
+            state: Arc::new(Mutex::new(TelemetryState::default())),
+            connection_events,
+        }
+    }
+
+    pub fn config(&self) -> &TelemetryConfig {
+        &self.config
+    }
+
+    fn now_ms() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    /// Record one REST round trip (`endpoint` is "METHOD /path" without query)
+    pub fn record_request(&self, endpoint: &str, elapsed_ms: f64, ok: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let window = state.endpoints.entry(endpoint.to_string()).or_default();
+        window.record(elapsed_ms, Self::now_ms());
+        if !ok {
+            window.errors += 1;
+        }
+    }
+
+    /// Record WebSocket message lag (receive time - exchange event time)
+    pub fn record_stream_lag(&self, stream: &str, event_time_ms: i64, received_at_ms: i64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let lag = (received_at_ms - event_time_ms) as f64;
+        state.streams.entry(stream.to_string()).or_default().record(lag, received_at_ms);
+    }
+
+    /// Record a server time sample
+    pub fn record_clock_offset(&self, offset_ms: i64, rtt_ms: f64, measured_at_ms: i64) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.clock.push_back((measured_at_ms, offset_ms, rtt_ms));
+        if state.clock.len() > CLOCK_WINDOW {
+            state.clock.pop_front();
+        }
+    }
+
+    /// Record a connector's connection state; transitions are broadcast to subscribers.
+    /// The first successful connect is not an event, repeated failures while down are collapsed.
+    /// Returns true when the state changed.
+    pub fn record_connection(&self, connector: &str, connected: bool, detail: &str) -> bool {
+        let previous = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            state.connections.insert(connector.to_string(), connected)
+        };
+        let changed = match previous {
+            None => !connected,
+            Some(was) => was != connected,
+        };
+        if changed {
+            let _ = self.connection_events.send(ConnectionEvent {
+                connector: connector.to_string(),
+                connected,
+                detail: detail.to_string(),
+                at: Self::now_ms(),
+            });
+        }
+        changed
+    }
+
+    pub fn subscribe_connections(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.connection_events.subscribe()
+    }
+
+    fn clock_stats(clock: &VecDeque<(i64, i64, f64)>) -> ClockDriftStats {
+        let Some(&(last_at, offset, rtt)) = clock.back() else {
+            return ClockDriftStats::default();
+        };
+        let drift_ms_per_hour = clock.front()
+            .filter(|(first_at, ..)| last_at > *first_at)
+            .map(|&(first_at, first_offset, _)| (offset - first_offset) as f64 / (last_at - first_at) as f64 * 3_600_000.0);
+        ClockDriftStats {
+            offset_ms: Some(offset),
+            rtt_ms: Some(rtt),
+            drift_ms_per_hour,
+            samples: clock.len(),
+            last_sync_at: Some(last_at),
+        }
+    }
+
+    /// Current stats with a degraded/ok verdict against configured thresholds
+    pub fn snapshot(&self) -> TelemetrySnapshot {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let cfg = &self.config;
+        let mut issues = Vec::new();
+
+        let endpoints: BTreeMap<String, LatencyStats> = state.endpoints.iter().map(|(k, w)| (k.clone(), w.stats())).collect();
+        for (name, s) in &endpoints {
+            if s.p95_ms > cfg.latency_warn_ms {
+                issues.push(format!("{} p95 latency {:.0}ms > {:.0}ms", name, s.p95_ms, cfg.latency_warn_ms));
+            }
+        }
+        let streams: BTreeMap<String, LatencyStats> = state.streams.iter().map(|(k, w)| (k.clone(), w.stats())).collect();
+        for (name, s) in &streams {
+            if s.p95_ms > cfg.stream_lag_warn_ms {
+                issues.push(format!("{} p95 lag {:.0}ms > {:.0}ms", name, s.p95_ms, cfg.stream_lag_warn_ms));
+            }
+        }
+        let clock = Self::clock_stats(&state.clock);
+        if let Some(offset) = clock.offset_ms.filter(|o| o.abs() > cfg.clock_offset_warn_ms) {
+            issues.push(format!("clock offset {}ms > {}ms", offset, cfg.clock_offset_warn_ms));
+        }
+        let connections: BTreeMap<String, bool> = state.connections.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        for (name, _) in connections.iter().filter(|(_, connected)| !**connected) {
+            issues.push(format!("{} disconnected", name));
+        }
+
+        TelemetrySnapshot {
+            status: if issues.is_empty() { "ok" } else { "degraded" },
+            issues,
+            endpoints,
+            streams,
+            clock,
+            connections,
+        }
+    }
+
+    /// Prometheus text exposition of the snapshot
+    pub fn render_prometheus(&self) -> String {
+        let snap = self.snapshot();
+        let mut out = String::new();
+        out.push_str("# TYPE xquant_exchange_request_latency_ms gauge\n");
+        for (endpoint, s) in &snap.endpoints {
+            for (q, v) in [("0.5", s.p50_ms), ("0.95", s.p95_ms)] {
+                out.push_str(&format!("xquant_exchange_request_latency_ms{{endpoint=\"{}\",quantile=\"{}\"}} {}\n", endpoint, q, v));
+            }
+        }
+        out.push_str("# TYPE xquant_exchange_requests_total counter\n");
+        for (endpoint, s) in &snap.endpoints {
+            out.push_str(&format!("xquant_exchange_requests_total{{endpoint=\"{}\"}} {}\n", endpoint, s.count));
+            out.push_str(&format!("xquant_exchange_request_errors_total{{endpoint=\"{}\"}} {}\n", endpoint, s.errors));
+        }
+        out.push_str("# TYPE xquant_exchange_stream_lag_ms gauge\n");
+        for (stream, s) in &snap.streams {
+            for (q, v) in [("0.5", s.p50_ms), ("0.95", s.p95_ms)] {
+                out.push_str(&format!("xquant_exchange_stream_lag_ms{{stream=\"{}\",quantile=\"{}\"}} {}\n", stream, q, v));
+            }
+        }
+        out.push_str("# TYPE xquant_exchange_clock_offset_ms gauge\n");
+        if let Some(offset) = snap.clock.offset_ms {
+            out.push_str(&format!("xquant_exchange_clock_offset_ms {}\n", offset));
+        }
+        if let Some(drift) = snap.clock.drift_ms_per_hour {
+            out.push_str(&format!("xquant_exchange_clock_drift_ms_per_hour {}\n", drift));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_lag_and_drift() {
+        let telemetry = ExchangeTelemetry::new(TelemetryConfig {
+            latency_warn_ms: 200.0,
+            stream_lag_warn_ms: 500.0,
+            clock_offset_warn_ms: 1_000,
+            clock_sync_interval_ms: 60_000,
+        });
+        for ms in [10.0, 20.0, 30.0, 40.0] {
+            telemetry.record_request("GET /fapi/v1/time", ms, true);
+        }
+        telemetry.record_request("POST /fapi/v1/order", 50.0, false);
+        telemetry.record_stream_lag("btcusdt@ticker", 1_000, 1_120);
+        telemetry.record_clock_offset(-10, 8.0, 0);
+        telemetry.record_clock_offset(-40, 8.0, 1_800_000);
+
+        let snap = telemetry.snapshot();
+        assert_eq!(snap.status, "ok");
+        let time = &snap.endpoints["GET /fapi/v1/time"];
+        assert_eq!(time.count, 4);
+        assert_eq!(time.max_ms, 40.0);
+        assert_eq!(snap.endpoints["POST /fapi/v1/order"].errors, 1);
+        assert_eq!(snap.streams["btcusdt@ticker"].last_ms, 120.0);
+        assert_eq!(snap.clock.offset_ms, Some(-40));
+        assert_eq!(snap.clock.drift_ms_per_hour, Some(-60.0));
+
+        // sustained slow responses flip the verdict
+        for _ in 0..10 {
+            telemetry.record_request("GET /fapi/v1/time", 900.0, true);
+        }
+        let snap = telemetry.snapshot();
+        assert_eq!(snap.status, "degraded");
+
+        // only connection transitions are broadcast; the first connect is not one
+        let mut events = telemetry.subscribe_connections();
+        assert!(!telemetry.record_connection("market_stream", true, "wss://stream"));
+        assert!(telemetry.record_connection("market_stream", false, "reset"));
+        assert!(!telemetry.record_connection("market_stream", false, "connect refused"));
+        assert!(telemetry.record_connection("market_stream", true, "wss://stream"));
+        assert_eq!(events.try_recv().unwrap().detail, "reset");
+        assert!(events.try_recv().unwrap().connected);
+        assert!(events.try_recv().is_err());
+        assert!(telemetry.render_prometheus().contains("xquant_exchange_clock_offset_ms -40"));
+    }
+}
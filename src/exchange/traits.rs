@@ -1,10 +1,18 @@
 use async_trait::async_trait;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::models::decimal::Decimal;
+use crate::models::fee::FeeTier;
+use crate::models::funding::{FundingRate, OpenInterest};
+use crate::models::margin::{MarginAccount, PositionMargin};
 use crate::models::market_data::MarketData;
+use crate::models::order_book::OrderBook;
 use crate::models::position::Position;
-use crate::models::order::{Order, OrderId, OrderStatus, OrderType};
+use crate::models::order::{Order, OrderFill, OrderId, OrderStatus, OrderType, OrderUpdate};
+use crate::models::symbol_info::SymbolInfo;
 use crate::models::trade::Trade;
 
 /// The `Exchange` trait defines the interface for interacting with trading exchanges.
@@ -23,6 +31,10 @@ pub trait Exchange: Send + Sync {
     /// Get the status of an order
     async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError>;
 
+    /// Get the executed quantity and average fill price of an order.
+    /// Default none, callers fall back to the order's own price
+    async fn get_order_fill(&self, _order_id: &OrderId) -> Result<Option<OrderFill>, TradingError> { Ok(None) }
+
     /// Get all open orders
     async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError>;
 
@@ -32,7 +44,7 @@ pub trait Exchange: Send + Sync {
     /// Get current market data for a symbol
     async fn get_market_data(&self, symbol: &str) -> Result<MarketData, TradingError>;
 
-    /// Get historical market data for a symbol
+    /// Get historical market data for a symbol, oldest first, at most `limit` candles from `start_time`
     async fn get_historical_data(
         &self,
         symbol: &str,
@@ -42,8 +54,38 @@ pub trait Exchange: Send + Sync {
         limit: Option<usize>,
     ) -> Result<Vec<MarketData>, TradingError>;
 
+    /// Get an order book snapshot with up to `depth` levels per side.
+    /// Default errors so callers can fall back to candle-only behaviour
+    async fn get_order_book(&self, symbol: &str, _depth: usize) -> Result<OrderBook, TradingError> {
+        Err(TradingError::ExchangeError(format!("order book not supported for {}", symbol)))
+    }
+
+    /// Get mark/index price and the predicted funding rate of a perpetual contract.
+    /// Default errors for venues without perpetuals
+    async fn get_funding_rate(&self, symbol: &str) -> Result<FundingRate, TradingError> {
+        Err(TradingError::ExchangeError(format!("funding rate not supported for {}", symbol)))
+    }
+
+    /// Get the open interest of a perpetual contract.
+    /// Default errors for venues without perpetuals
+    async fn get_open_interest(&self, symbol: &str) -> Result<OpenInterest, TradingError> {
+        Err(TradingError::ExchangeError(format!("open interest not supported for {}", symbol)))
+    }
+
+    /// Get trading rules of a symbol (tick size, step size, min qty, min notional).
+    /// Default errors so orders go out unvalidated
+    async fn get_symbol_info(&self, symbol: &str) -> Result<SymbolInfo, TradingError> {
+        Err(TradingError::ExchangeError(format!("symbol info not supported for {}", symbol)))
+    }
+
+    /// Get the account's maker/taker fee rates for a symbol.
+    /// Default errors for venues that don't report a fee tier
+    async fn get_fee_tier(&self, symbol: &str) -> Result<FeeTier, TradingError> {
+        Err(TradingError::ExchangeError(format!("fee tier not supported for {}", symbol)))
+    }
+
     /// Get account balance
-    async fn get_balance(&self, asset: &str) -> Result<f64, TradingError>;
+    async fn get_balance(&self, asset: &str) -> Result<Decimal, TradingError>;
 
     /// Optional: sync server time for signed requests (default no-op)
     async fn sync_time(&mut self) -> Result<(), TradingError> { Ok(()) }
@@ -59,4 +101,23 @@ pub trait Exchange: Send + Sync {
 
     /// Optional: get current positions snapshot. Default empty list
     async fn get_positions(&self) -> Result<Vec<Position>, TradingError> { Ok(Vec::new()) }
+
+    /// Get the futures account's margin balance and total maintenance margin.
+    /// Default errors for venues without a margin account
+    async fn get_margin_account(&self) -> Result<MarginAccount, TradingError> {
+        Err(TradingError::ExchangeError("margin account not supported".to_string()))
+    }
+
+    /// Get mark and liquidation price of every open position.
+    /// Default errors for venues without a margin account
+    async fn get_position_margins(&self) -> Result<Vec<PositionMargin>, TradingError> {
+        Err(TradingError::ExchangeError("position margins not supported".to_string()))
+    }
+
+    /// Optional: push-style order updates for connectors that receive executions on their own session
+    /// (e.g. FIX ExecutionReports). Default none, orders are tracked by polling
+    fn order_updates(&self) -> Option<broadcast::Receiver<OrderUpdate>> { None }
+
+    /// Optional: describe natively supported order features. Default accepts everything
+    fn capabilities(&self) -> ExchangeCapabilities { ExchangeCapabilities::default() }
 }
\ No newline at end of file
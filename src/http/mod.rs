@@ -1,40 +1,114 @@
-use axum::{routing::{get, post}, Router, extract::{Path, State}, response::IntoResponse};
-use axum::extract::ws::{WebSocketUpgrade, Message, WebSocket};
+use axum::{routing::{get, post, put}, Router, extract::{Path, State}, response::IntoResponse};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tower_http::cors::{CorsLayer, Any};
 
-use crate::core::strategy_manager::StrategyManager;
+use crate::backtest::{BacktestJobManager, BacktestJobRequest, BacktestJobStatus, BacktestResult, BacktestSweepRequest};
+use crate::core::dead_man_switch::DeadManSwitch;
+use crate::core::oco_manager::OcoManager;
+use crate::core::portfolio::PortfolioTracker;
+use crate::core::daily_loss::{DailyLossStatus, DailyLossTracker};
+use crate::core::margin_monitor::{MarginMonitor, MarginStatus};
+use crate::core::conditional_orders::{ConditionalOrder, ConditionalOrderManager, ConditionalOrderRequest};
+use crate::core::config_reload::{ConfigReloader, ReloadReport};
+use crate::core::live_feed::LiveFeed;
+use crate::core::shadow::{ShadowBook, ShadowReport};
+use crate::core::signal_dedup::SignalDeduplicator;
+use crate::core::strategy_manager::{StrategyManager, StrategyStatus};
+use crate::core::trade_journal::{TradeFilter, TradeJournal};
+use crate::exchange::telemetry::ExchangeTelemetry;
 use crate::exchange::traits::Exchange;
-use crate::strategies::Strategy;
+use crate::market_data::external::{ExternalPoint, ExternalSeriesStore};
+use crate::market_data::funding::{FundingSnapshot, FundingStore};
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::timestamp::Timestamp;
 use crate::order_core::manager::OrderManager;
 use crate::core::risk_manager::RiskManager;
 use crate::models::order::{Order, OrderSide, OrderType, OrderId};
+use crate::webhooks::{WebhookDispatcher, WebhookEventKind};
+
+mod ws;
 
 #[derive(Clone)]
 pub struct AppState {
   pub exchange: Arc<RwLock<dyn Exchange>>, 
   pub strategy_manager: Arc<RwLock<StrategyManager>>, 
-  // Note: OrderManager is in main runtime; for API calls we recreate lightweight paths via exchange+repo if needed.
+  pub webhooks: WebhookDispatcher,
+  pub telemetry: ExchangeTelemetry,
+  // API 주문도 전략 주문과 같은 검증/저장/상태 알림 경로를 거치도록 공유
+  pub order_manager: Arc<RwLock<OrderManager>>,
+  pub dead_man: Arc<RwLock<DeadManSwitch>>,
+  // 외부 시계열 (폴링 + 웹훅 수신)
+  pub external: ExternalSeriesStore,
+  // 브래킷(진입 + 익절/손절 OCO) 주문
+  pub oco: OcoManager,
+  // 가격/지표 조건부 주문
+  pub conditional: ConditionalOrderManager,
+  // 포지션/손익/자산 곡선
+  pub portfolio: PortfolioTracker,
+  // 거래일 단위 일일 손실 집계 (포트폴리오 추적기/주문 검증과 공유)
+  pub daily_loss: DailyLossTracker,
+  // 선물 증거금 비율/청산 가격 감시
+  pub margin: MarginMonitor,
+  // 펀딩비/미결제약정 스냅샷 (수집 태스크와 공유)
+  pub funding: FundingStore,
+  // 체결 기록 (전략/신호/슬리피지)
+  pub journal: TradeJournal,
+  // 설정 파일 재로드 (파일 감시 태스크와 공유)
+  pub config_reload: ConfigReloader,
+  // WebSocket 시세/주문/포지션/전략 공유 피드
+  pub live_feed: LiveFeed,
+  // API 백테스트 작업 (비동기 실행 + 진행률/결과 조회)
+  pub backtests: BacktestJobManager,
+  // 섀도 모드 전략 가상 체결 장부 (전략 실행 루프와 공유)
+  pub shadow: ShadowBook,
+  // 전략 신호 중복 제거 (전략 실행 루프와 공유, 버린 건수는 /metrics)
+  pub signal_dedup: SignalDeduplicator,
 }
 
 #[derive(Debug, Serialize)]
-struct Health { status: &'static str }
+struct Health { status: &'static str, degraded: crate::core::degraded_mode::DegradedStatus }
 
 pub fn build_router(state: AppState) -> Router {
   let cors = CorsLayer::new().allow_origin(Any).allow_headers(Any).allow_methods(Any);
 
   Router::new()
-    .route("/health", get(|| async { axum::Json(Health { status: "ok" }) }))
+    .route("/health", get(health))
+    .route("/health/degraded", get(degraded_details))
+    .route("/health/degraded/reset", post(reset_degraded))
+    .route("/health/exchange", get(exchange_health))
+    .route("/metrics", get(metrics))
+    .route("/heartbeat", get(heartbeat_status).post(heartbeat))
+    .route("/trading/status", get(trading_status))
+    .route("/trading/halt", post(halt_trading))
+    .route("/trading/resume", post(resume_trading))
+    .route("/config/reload", post(reload_config))
     .route("/strategies", get(list_strategies))
     .route("/strategies/ta", post(create_ta_strategy))
     .route("/strategies/vwap", post(create_vwap_strategy))
     .route("/strategies/twap", post(create_twap_strategy))
     .route("/strategies/iceberg", post(create_iceberg_strategy))
     .route("/strategies/trailing", post(create_trailing_strategy))
+    .route("/strategies/is", post(create_is_strategy))
+    .route("/strategies/pairs", post(create_pairs_strategy))
+    .route("/strategies/funding_carry", post(create_funding_carry_strategy))
     .route("/strategies/:name/toggle", post(toggle_strategy))
-    .route("/strategies/:name", get(get_strategy_info).delete(delete_strategy))
+    .route("/strategies/:name/status", get(get_strategy_status))
+    .route("/strategies/:name/shadow", get(get_strategy_shadow))
+    .route("/strategies/:name/schedule", get(get_strategy_schedule).put(set_strategy_schedule))
+    .route("/strategies/:name/protection", put(set_strategy_protection))
+    .route("/strategies/:name/exits", put(set_strategy_exits))
+    .route("/strategies/:name", get(get_strategy_info).patch(update_strategy_params).delete(delete_strategy))
+    .route("/allocations", get(get_allocations))
+    .route("/portfolio", get(get_portfolio))
+    .route("/portfolio/history", get(get_portfolio_history))
+    .route("/risk/daily", get(get_daily_loss))
+    .route("/risk/daily/reset", post(reset_daily_loss))
+    .route("/risk/margin", get(get_margin_status))
+    .route("/trades", get(get_trades))
+    .route("/trades/export", get(export_trades))
+    .route("/allocations/:name", get(get_strategy_allocation).put(set_strategy_budget).delete(clear_strategy_budget))
     // futures settings
     .route("/futures/position_mode", post(set_position_mode))
     .route("/futures/margin_mode", post(set_margin_mode))
@@ -42,18 +116,122 @@ pub fn build_router(state: AppState) -> Router {
     .route("/futures/settings", post(apply_futures_settings))
     // market data
     .route("/market/:symbol", get(get_market_snapshot))
+    .route("/market/:symbol/funding", get(get_funding_rate))
+    .route("/market/:symbol/fees", get(get_fee_tier))
     .route("/positions", get(get_positions))
     // orders
-    .route("/orders", post(create_order))
+    .route("/orders", get(list_orders).post(create_order))
+    .route("/orders/queue", get(get_submission_queue))
     .route("/orders/:id", get(get_order_status).delete(cancel_order))
-    .route("/ws/prices/:symbol", get(ws_prices))
-    .route("/ws/orders", get(ws_orders))
-    .route("/ws/positions", get(ws_positions))
-    .route("/ws/strategies", get(ws_strategies))
+    .route("/brackets", get(list_brackets).post(create_bracket))
+    .route("/brackets/:id", get(get_bracket).delete(cancel_bracket))
+    .route("/conditions", get(list_conditions).post(create_condition))
+    .route("/conditions/:id", get(get_condition).delete(cancel_condition))
+    .route("/external", get(list_external_series))
+    .route("/external/:name", get(get_external_series).post(push_external_series))
+    .route("/backtests", get(list_backtests).post(submit_backtest))
+    .route("/backtests/sweep", post(run_backtest_sweep))
+    .route("/backtests/:id", get(get_backtest))
+    .route("/backtests/:id/result", get(get_backtest_result))
+    .route("/ws", get(ws::ws_handler))
     .with_state(state)
     .layer(cors)
 }
 
+// 읽기 전용 모드면 status 가 "degraded"
+async fn health(State(state): State<AppState>) -> axum::Json<Health> {
+  let degraded = state.order_manager.read().await.write_access().status();
+  axum::Json(Health { status: if degraded.degraded { "degraded" } else { "ok" }, degraded })
+}
+
+// 읽기 전용 모드 상태와 차단된 주문 (최신순)
+async fn degraded_details(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  let monitor = state.order_manager.read().await.write_access();
+  axum::Json(serde_json::json!({ "status": monitor.status(), "blocked_orders": monitor.blocked_orders() }))
+}
+
+// API 키 교체 등으로 권한을 복구한 뒤 수동 해제
+async fn reset_degraded(State(state): State<AppState>) -> axum::Json<crate::core::degraded_mode::DegradedStatus> {
+  axum::Json(state.order_manager.read().await.write_access().reset())
+}
+
+// 거래소 연결 상태: 엔드포인트별 지연, WS 메시지 지연, 서버 시각 오차
+async fn exchange_health(State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let snapshot = state.telemetry.snapshot();
+  serde_json::to_value(snapshot).map(axum::Json).map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// Prometheus 텍스트 형식 지표
+async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+  let mut body = state.telemetry.render_prometheus();
+  body.push_str(&state.order_manager.read().await.submission_queue().render_prometheus());
+  body.push_str(&state.signal_dedup.render_prometheus());
+  ([(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")], body)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HeartbeatReq { source: Option<String> }
+
+// 데드맨 스위치 하트비트: 외부 모니터가 timeout_ms 이내로 호출해야 함
+async fn heartbeat(State(state): State<AppState>, body: Option<axum::Json<HeartbeatReq>>) -> axum::Json<crate::core::dead_man_switch::DeadManStatus> {
+  let now = chrono::Utc::now().timestamp_millis();
+  let source = body.and_then(|axum::Json(req)| req.source);
+  let mut switch = state.dead_man.write().await;
+  switch.heartbeat(now, source);
+  axum::Json(switch.status(now))
+}
+
+async fn heartbeat_status(State(state): State<AppState>) -> axum::Json<crate::core::dead_man_switch::DeadManStatus> {
+  axum::Json(state.dead_man.read().await.status(chrono::Utc::now().timestamp_millis()))
+}
+
+// 설정 파일 다시 읽기 - 실행 중 적용 가능한 섹션만 반영 (읽기/검증 실패 시 기존 설정 유지)
+async fn reload_config(State(state): State<AppState>) -> Result<axum::Json<ReloadReport>, axum::http::StatusCode> {
+  state.config_reload.reload().await.map(axum::Json).map_err(|e| {
+    log::warn!("config reload rejected: {}", e);
+    axum::http::StatusCode::UNPROCESSABLE_ENTITY
+  })
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HaltReq {
+  reason: Option<String>,
+  source: Option<String>,
+  /// 거래소 미체결 주문 전체 취소
+  #[serde(default)]
+  cancel_orders: bool,
+  /// 보유 포지션을 reduce-only 시장가로 청산
+  #[serde(default)]
+  flatten_positions: bool,
+}
+
+// 킬 스위치: 신규 주문을 즉시 막고, 요청 시 미체결 취소/포지션 청산까지 실행
+async fn halt_trading(State(state): State<AppState>, body: Option<axum::Json<HaltReq>>) -> axum::Json<serde_json::Value> {
+  use crate::core::dead_man_switch::{execute_safety_actions, SafetyAction};
+  let req = body.map(|axum::Json(req)| req).unwrap_or_default();
+  let status = state.order_manager.read().await.kill_switch().halt(req.reason, req.source, chrono::Utc::now().timestamp_millis());
+  let mut actions = Vec::new();
+  if req.cancel_orders { actions.push(SafetyAction::CancelOrders); }
+  if req.flatten_positions { actions.push(SafetyAction::FlattenPositions); }
+  let report = execute_safety_actions(&actions, &state.order_manager, &state.strategy_manager).await;
+  if !report.errors.is_empty() {
+    log::error!("trading halt safety actions incomplete: {:?}", report.errors);
+  }
+  let body = serde_json::json!({"status": status, "report": report});
+  state.webhooks.emit(WebhookEventKind::TradingHalted, body.clone());
+  axum::Json(body)
+}
+
+async fn resume_trading(State(state): State<AppState>) -> axum::Json<crate::core::kill_switch::HaltStatus> {
+  let status = state.order_manager.read().await.kill_switch().resume(chrono::Utc::now().timestamp_millis());
+  state.webhooks.emit(WebhookEventKind::TradingResumed, serde_json::json!(status));
+  axum::Json(status)
+}
+
+async fn trading_status(State(state): State<AppState>) -> axum::Json<crate::core::kill_switch::HaltStatus> {
+  axum::Json(state.order_manager.read().await.kill_switch().status())
+}
+
 async fn list_strategies(State(state): State<AppState>) -> Result<axum::Json<Vec<(String, bool)>>, axum::http::StatusCode> {
   let mgr = state.strategy_manager.read().await;
   Ok(axum::Json(mgr.list_strategies()))
@@ -67,105 +245,454 @@ struct CreateReq {
 }
 
 async fn create_ta_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<CreateReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
-  use crate::strategies::technical::TechnicalStrategy;
+  use crate::config::StrategySpec;
 
-  let strategy_result = match req.strategy_type.as_str() {
+  // 명세로 생성하여 전략 상태 저장소에 설정이 보존되도록 함
+  let spec = match req.strategy_type.as_str() {
     "ma_crossover" => {
       let fast = req.params.get("fast_period").and_then(|v| v.as_u64()).unwrap_or(12) as usize;
       let slow = req.params.get("slow_period").and_then(|v| v.as_u64()).unwrap_or(26) as usize;
-      TechnicalStrategy::ma_crossover(req.symbol.clone(), fast, slow)
+      StrategySpec::MaCrossover { symbol: req.symbol.clone(), fast_period: fast, slow_period: slow }
     }
     , "rsi" => {
       let period = req.params.get("period").and_then(|v| v.as_u64()).unwrap_or(14) as usize;
       let oversold = req.params.get("oversold").and_then(|v| v.as_f64()).unwrap_or(30.0);
       let overbought = req.params.get("overbought").and_then(|v| v.as_f64()).unwrap_or(70.0);
-      TechnicalStrategy::rsi(req.symbol.clone(), period, oversold, overbought)
+      StrategySpec::Rsi { symbol: req.symbol.clone(), period, oversold, overbought }
+    }
+    , "bollinger" => {
+      let period = req.params.get("period").and_then(|v| v.as_u64()).unwrap_or(20) as usize;
+      let std_dev = req.params.get("std_dev").and_then(|v| v.as_f64()).unwrap_or(2.0);
+      StrategySpec::Bollinger { symbol: req.symbol.clone(), period, std_dev }
+    }
+    , "ichimoku" => {
+      let tenkan = req.params.get("tenkan_period").and_then(|v| v.as_u64()).unwrap_or(9) as usize;
+      let kijun = req.params.get("kijun_period").and_then(|v| v.as_u64()).unwrap_or(26) as usize;
+      let senkou_b = req.params.get("senkou_b_period").and_then(|v| v.as_u64()).unwrap_or(52) as usize;
+      StrategySpec::Ichimoku { symbol: req.symbol.clone(), tenkan_period: tenkan, kijun_period: kijun, senkou_b_period: senkou_b }
     }
-    , _ => Err(crate::error::TradingError::InvalidStrategy("unknown".into()))
+    , "multi_timeframe" => {
+      // params: {"entry": {"indicator": "rsi", ...}, "trend": {"indicator": "ma_crossover", ...}, "entry_timeframe": "5m", "trend_timeframe": "1h"}
+      let leg = |key: &str| req.params.get(key).cloned().and_then(|v| serde_json::from_value(v).ok());
+      let (Some(entry), Some(trend)) = (leg("entry"), leg("trend")) else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+      };
+      let timeframe = |key: &str, default: &str| req.params.get(key).and_then(|v| v.as_str()).unwrap_or(default).to_string();
+      StrategySpec::MultiTimeframe {
+        symbol: req.symbol.clone(),
+        entry,
+        trend,
+        entry_timeframe: timeframe("entry_timeframe", "5m"),
+        trend_timeframe: timeframe("trend_timeframe", "1h"),
+      }
+    }
+    , "prediction_ta" => {
+      // params: {"quantity": 0.01, "min_confidence": 0.6, "confirmation": {"max_buy_rsi": 50, "trend_ema_period": 200}}
+      let Some(quantity) = req.params.get("quantity").and_then(|v| v.as_f64()) else {
+        return Err(axum::http::StatusCode::BAD_REQUEST);
+      };
+      let confirmation = match req.params.get("confirmation") {
+        Some(v) => serde_json::from_value(v.clone()).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?,
+        None => Default::default(),
+      };
+      StrategySpec::PredictionTa {
+        symbol: req.symbol.clone(),
+        quantity,
+        min_confidence: req.params.get("min_confidence").and_then(|v| v.as_f64()).unwrap_or(0.6),
+        confirmation,
+        aggregation: None,
+      }
+    }
+    , _ => return Err(axum::http::StatusCode::BAD_REQUEST)
   };
 
-  match strategy_result {
-    Ok(strategy) => {
-      let name = Strategy::name(&strategy).to_string();
-      let mut mgr = state.strategy_manager.write().await;
-      if let Err(_) = mgr.add_strategy(Box::new(strategy)) { return Err(axum::http::StatusCode::BAD_REQUEST); }
-      Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": name})))
-    }
-    Err(_) => Err(axum::http::StatusCode::BAD_REQUEST)
-  }
+  let mut mgr = state.strategy_manager.write().await;
+  let name = mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": name})))
 }
 
 #[derive(Debug, Deserialize)]
-struct VwapReq { symbol: String, side: String, quantity: f64, window: i64, participation: Option<f64> }
+struct VwapReq { symbol: String, side: String, quantity: f64, window: i64, participation: Option<f64>, #[serde(default)] execution_mode: crate::strategies::ExecutionMode, book_participation: Option<f64> }
 async fn create_vwap_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<VwapReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
-  use crate::strategies::vwap::VwapStrategy;
+  use crate::config::StrategySpec;
   use crate::models::order::OrderSide;
   let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST)};
-  let s = VwapStrategy::new(&req.symbol, side, req.quantity, req.window as i64, (req.participation.unwrap_or(0.1)*100.0) as usize);
+  let spec = StrategySpec::Vwap {
+    symbol: req.symbol.clone(),
+    side,
+    quantity: req.quantity,
+    execution_interval_ms: req.window,
+    vwap_window: (req.participation.unwrap_or(0.1)*100.0) as usize,
+    execution_mode: req.execution_mode,
+    book_participation: req.book_participation,
+  };
   let mut mgr = state.strategy_manager.write().await;
-  mgr.add_strategy(Box::new(s)).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
   Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("VWAP-{}", req.symbol)})))
 }
 
 #[derive(Debug, Deserialize)]
-struct TwapReq { symbol: String, side: String, quantity: f64, window: i64 }
+struct TwapReq { symbol: String, side: String, quantity: f64, window: i64, liquidity_weights: Option<Vec<f64>>, dead_hours: Option<Vec<u32>>, #[serde(default)] execution_mode: crate::strategies::ExecutionMode }
 async fn create_twap_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<TwapReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
   use crate::strategies::twap::TwapStrategy;
   use crate::models::order::OrderSide;
   let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST)};
-  let s = TwapStrategy::new(&req.symbol, side, req.quantity, req.window as i64, 5);
+  // 유동성 곡선이 없으면 명세로 생성 (전략 상태 저장 대상)
+  if req.liquidity_weights.is_none() && req.dead_hours.is_none() {
+    let spec = crate::config::StrategySpec::Twap {
+      symbol: req.symbol.clone(),
+      side,
+      quantity: req.quantity,
+      execution_interval_ms: req.window,
+      slices: 5,
+      execution_mode: req.execution_mode,
+    };
+    let mut mgr = state.strategy_manager.write().await;
+    mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    return Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("TWAP-{}", req.symbol)})));
+  }
+  // 유동성 곡선/비거래 시간대가 지정되면 가중 분할 (명세로 표현되지 않아 상태 저장 제외)
+  use crate::strategies::twap::LiquidityProfile;
+  let profile = match &req.liquidity_weights {
+    Some(w) => LiquidityProfile::from_weights(w).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?,
+    None => LiquidityProfile::uniform(),
+  };
+  let s = TwapStrategy::new(&req.symbol, side, to_decimal(req.quantity), req.window as i64, 5)
+    .with_execution_mode(req.execution_mode)
+    .with_liquidity_profile(profile.with_dead_hours(req.dead_hours.as_deref().unwrap_or(&[])));
   let mut mgr = state.strategy_manager.write().await;
   mgr.add_strategy(Box::new(s)).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
   Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("TWAP-{}", req.symbol)})))
 }
 
 #[derive(Debug, Deserialize)]
-struct IcebergReq { symbol: String, side: String, total_qty: f64, visible_qty: f64, price: f64 }
+struct IcebergReq { symbol: String, side: String, total_qty: f64, visible_qty: f64, price: f64, #[serde(default)] execution_mode: crate::strategies::ExecutionMode, book_participation: Option<f64> }
 async fn create_iceberg_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<IcebergReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
-  use crate::strategies::iceberg::IcebergStrategy;
+  use crate::config::StrategySpec;
   use crate::models::order::OrderSide;
   let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST)};
-  let s = IcebergStrategy::new(req.symbol.clone(), side, req.total_qty, req.visible_qty, req.price);
+  let spec = StrategySpec::Iceberg {
+    symbol: req.symbol.clone(),
+    side,
+    total_quantity: req.total_qty,
+    limit_price: req.visible_qty,
+    display_quantity: req.price,
+    execution_mode: req.execution_mode,
+    book_participation: req.book_participation,
+  };
   let mut mgr = state.strategy_manager.write().await;
-  mgr.add_strategy(Box::new(s)).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
   Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("ICEBERG-{}", req.symbol)})))
 }
 
+#[derive(Debug, Deserialize)]
+struct IsReq { symbol: String, side: String, quantity: f64, window: i64, slices: Option<usize>, urgency: f64, volatility_bps: Option<f64>, impact_bps: Option<f64> }
+async fn create_is_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<IsReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  use crate::config::StrategySpec;
+  use crate::models::order::OrderSide;
+  let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST)};
+  let spec = StrategySpec::ImplementationShortfall {
+    symbol: req.symbol.clone(),
+    side,
+    quantity: req.quantity,
+    execution_interval_ms: req.window,
+    slices: req.slices.unwrap_or(5),
+    urgency: req.urgency,
+    volatility_bps: req.volatility_bps.unwrap_or(20.0),
+    impact_bps: req.impact_bps.unwrap_or(10.0),
+  };
+  let mut mgr = state.strategy_manager.write().await;
+  mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("IS-{}", req.symbol)})))
+}
+
+#[derive(Debug, Deserialize)]
+struct PairsReq { symbol: String, pair_symbol: String, quantity: f64, lookback: Option<usize>, entry_z: Option<f64>, exit_z: Option<f64> }
+async fn create_pairs_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<PairsReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  use crate::config::StrategySpec;
+  let spec = StrategySpec::Pairs {
+    symbol: req.symbol,
+    pair_symbol: req.pair_symbol,
+    quantity: req.quantity,
+    lookback: req.lookback.unwrap_or(60),
+    entry_z: req.entry_z.unwrap_or(2.0),
+    exit_z: req.exit_z.unwrap_or(0.5),
+  };
+  let mut mgr = state.strategy_manager.write().await;
+  let name = mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": name})))
+}
+
+#[derive(Debug, Deserialize)]
+struct FundingCarryReq { symbol: String, spot_symbol: Option<String>, quantity: f64, entry_rate: f64, exit_rate: Option<f64> }
+async fn create_funding_carry_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<FundingCarryReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  use crate::config::StrategySpec;
+  let spec = StrategySpec::FundingCarry {
+    symbol: req.symbol,
+    spot_symbol: req.spot_symbol,
+    quantity: req.quantity,
+    entry_rate: req.entry_rate,
+    exit_rate: req.exit_rate.unwrap_or(0.0),
+  };
+  let mut mgr = state.strategy_manager.write().await;
+  let name = mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": name})))
+}
+
 #[derive(Debug, Deserialize)]
 struct TrailingReq { symbol: String, side: String, qty: f64, callback: f64, activation: Option<f64> }
 async fn create_trailing_strategy(State(state): State<AppState>, axum::Json(req): axum::Json<TrailingReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
-  use crate::strategies::trailing_stop::TrailingStopStrategy;
+  use crate::config::StrategySpec;
   use crate::models::order::OrderSide;
   let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST)};
-  let mut s = TrailingStopStrategy::new(req.symbol.clone(), side, req.qty, req.callback, req.activation);
+  let spec = StrategySpec::TrailingStop {
+    symbol: req.symbol.clone(),
+    side,
+    quantity: req.qty,
+    trailing_delta: req.callback,
+    activation_price: req.activation,
+  };
   let mut mgr = state.strategy_manager.write().await;
-  mgr.add_strategy(Box::new(s)).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  mgr.add_strategy_spec(spec).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
   Ok(axum::Json(serde_json::json!({"status":"success","strategy_name": format!("TRAIL-{}", req.symbol)})))
 }
 
+// active / shadow 중 지정한 값만 변경 (둘 다 없으면 422)
 #[derive(Debug, Deserialize)]
-struct ToggleReq { active: bool }
+struct ToggleReq {
+  #[serde(default)]
+  active: Option<bool>,
+  #[serde(default)]
+  shadow: Option<bool>,
+}
 
 async fn toggle_strategy(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<ToggleReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  if body.active.is_none() && body.shadow.is_none() {
+    log::warn!("toggle {}: neither active nor shadow given", name);
+    return Err(axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+  }
   let mut mgr = state.strategy_manager.write().await;
-  mgr.set_strategy_active(&name, body.active).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
-  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"active":body.active})))
+  if let Some(shadow) = body.shadow {
+    mgr.set_strategy_shadow(&name, shadow).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+    // 실거래로 전환하면 남은 가상 주문은 버림
+    if !shadow {
+      state.shadow.cancel_orders(&name);
+    }
+    log::info!("strategy {} shadow mode {}", name, if shadow { "on" } else { "off" });
+  }
+  if let Some(active) = body.active {
+    mgr.set_strategy_active(&name, active).map_err(|e| match e {
+      crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+      _ => axum::http::StatusCode::CONFLICT,
+    })?;
+  }
+  let status = mgr.get_strategy_status(&name).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+  state.webhooks.emit(WebhookEventKind::StrategyToggled, serde_json::json!({"strategy": name, "active": status.active, "shadow": status.shadow, "reason": "api"}));
+  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"active":status.active,"shadow":status.shadow})))
 }
 
-async fn delete_strategy(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+// 실행 시간대 조회 - 현재 창이 열려 있는지와 닫힌 사유 포함
+async fn get_strategy_schedule(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mgr = state.strategy_manager.read().await;
+  if mgr.get_strategy_status(&name).is_err() {
+    return Err(axum::http::StatusCode::NOT_FOUND);
+  }
+  let closed = mgr.schedule_closed_reason(&name, chrono::Utc::now().timestamp_millis());
+  Ok(axum::Json(serde_json::json!({"name": name, "schedule": mgr.schedule(&name), "open": closed.is_none(), "closed_reason": closed})))
+}
+
+// 실행 시간대 변경 (body 가 null 이면 해제)
+async fn set_strategy_schedule(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<Option<crate::config::StrategySchedule>>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mut mgr = state.strategy_manager.write().await;
+  mgr.set_schedule(&name, body.clone()).map_err(|e| match e {
+    crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    e => {
+      log::warn!("invalid schedule for {}: {}", name, e);
+      axum::http::StatusCode::UNPROCESSABLE_ENTITY
+    }
+  })?;
+  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"schedule":body})))
+}
+
+// 섀도 모드 가상 체결/포지션/손익
+async fn get_strategy_shadow(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<ShadowReport>, axum::http::StatusCode> {
+  if state.strategy_manager.read().await.get_strategy_status(&name).is_err() {
+    return Err(axum::http::StatusCode::NOT_FOUND);
+  }
+  Ok(axum::Json(state.shadow.report(&name)))
+}
+
+// 보호 청산 설정 변경 (body 가 null 이면 해제)
+async fn set_strategy_protection(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<Option<crate::config::ProtectiveStopConfig>>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
   let mut mgr = state.strategy_manager.write().await;
-  mgr.remove_strategy(&name).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
-  Ok(axum::Json(serde_json::json!({"status":"ok","deleted":name})))
+  mgr.set_protection(&name, body.clone()).map_err(|e| match e {
+    crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    _ => axum::http::StatusCode::BAD_REQUEST,
+  })?;
+  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"protection":body})))
+}
+
+// 진입 주문 손절/익절 설정 (null 이면 해제) - 지표 신호 전략만 지원
+async fn set_strategy_exits(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<Option<crate::signals::entry_exits::EntryExitsConfig>>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mut mgr = state.strategy_manager.write().await;
+  mgr.set_entry_exits(&name, body.clone()).map_err(|e| match e {
+    crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    e => {
+      log::warn!("strategy {} exits rejected: {}", name, e);
+      axum::http::StatusCode::UNPROCESSABLE_ENTITY
+    }
+  })?;
+  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"exits":body})))
+}
+
+async fn delete_strategy(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  state.strategy_manager.write().await.remove_strategy(&name).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+  state.shadow.cancel_orders(&name);
+  // 제거된 전략이 남긴 미체결 주문 취소
+  let cancelled = match state.order_manager.read().await.cancel_strategy_orders(&name).await {
+    Ok(n) => n,
+    Err(e) => {
+      log::warn!("strategy {} order cleanup failed: {}", name, e);
+      0
+    }
+  };
+  Ok(axum::Json(serde_json::json!({"status":"ok","deleted":name,"cancelled_orders":cancelled})))
 }
 
 async fn get_strategy_info(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
   let mgr = state.strategy_manager.read().await;
   match mgr.get_strategy_info(&name) {
-    Ok((n, desc, active)) => Ok(axum::Json(serde_json::json!({"name":n, "description": desc, "active":active}))),
+    Ok((n, desc, active)) => {
+      let guard = mgr.get_guard_state(&name);
+      let time_buckets = mgr.get_time_buckets(&name);
+      let watchdog = mgr.get_watchdog_state(&name);
+      let protection = serde_json::json!({"config": mgr.get_protection(&name), "positions": mgr.get_protected_positions(&name)});
+      let execution = mgr.get_execution_report(&name);
+      let spec = mgr.strategy_spec(&name);
+      Ok(axum::Json(serde_json::json!({"name":n, "description": desc, "active":active, "spec": spec, "guard": guard, "time_buckets": time_buckets, "watchdog": watchdog, "protection": protection, "execution": execution})))
+    }
     Err(_) => Err(axum::http::StatusCode::NOT_FOUND)
   }
 }
 
+// 전략 실행 상태 (TWAP/VWAP 실행 수량, RSI 값, 트레일링 트리거 가격 등)
+async fn get_strategy_status(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<StrategyStatus>, axum::http::StatusCode> {
+  state.strategy_manager.read().await.get_strategy_status(&name).map(axum::Json).map_err(|_| axum::http::StatusCode::NOT_FOUND)
+}
+
+// 실행 중 파라미터 변경 (임계치, 참여율, 트레일링 간격 등) - 전략을 다시 만들지 않아 진행 상태 유지
+async fn update_strategy_params(Path(name): Path<String>, State(state): State<AppState>, axum::Json(params): axum::Json<serde_json::Value>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mut mgr = state.strategy_manager.write().await;
+  mgr.update_strategy_params(&name, params.clone()).map_err(|e| match e {
+    crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    e => {
+      log::warn!("API parameter update for {} rejected: {}", name, e);
+      axum::http::StatusCode::UNPROCESSABLE_ENTITY
+    }
+  })?;
+  Ok(axum::Json(serde_json::json!({"status":"ok","name":name,"params":params,"spec":mgr.strategy_spec(&name)})))
+}
+
+async fn get_allocations(State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mgr = state.strategy_manager.read().await;
+  Ok(axum::Json(serde_json::json!({"budgets": mgr.get_allocations(), "usage": mgr.get_budget_usage(), "audit": mgr.get_allocation_audit()})))
+}
+
+// =============== Portfolio ===============
+async fn get_portfolio(State(state): State<AppState>) -> axum::Json<crate::core::portfolio::PortfolioSnapshot> {
+  axum::Json(state.portfolio.snapshot())
+}
+
+#[derive(Debug, Deserialize)]
+struct PortfolioHistoryQuery { since: Option<i64>, limit: Option<usize> }
+
+// 자산 곡선 (since: ms 이후, limit: 최근 N개)
+async fn get_portfolio_history(State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<PortfolioHistoryQuery>) -> axum::Json<serde_json::Value> {
+  axum::Json(serde_json::json!({"history": state.portfolio.history(q.since, q.limit)}))
+}
+
+// =============== Daily loss ===============
+// 당일 손실 vs 한도, 다음 초기화 시각
+async fn get_daily_loss(State(state): State<AppState>) -> axum::Json<DailyLossStatus> {
+  axum::Json(state.daily_loss.status(chrono::Utc::now().timestamp_millis()))
+}
+
+// 당일 집계 수동 초기화 (한도 도달 후 운영자 판단으로 재개)
+async fn reset_daily_loss(State(state): State<AppState>) -> axum::Json<DailyLossStatus> {
+  log::warn!("daily loss counter reset via API");
+  axum::Json(state.daily_loss.reset(chrono::Utc::now().timestamp_millis()))
+}
+
+// =============== Margin ===============
+// 증거금 비율, 위험 단계, 포지션별 청산 가격 (마지막 조회 기준)
+async fn get_margin_status(State(state): State<AppState>) -> axum::Json<MarginStatus> {
+  axum::Json(state.margin.status())
+}
+
+// =============== Trade journal ===============
+#[derive(Debug, Deserialize)]
+struct TradesQuery {
+  format: Option<String>,
+  limit: Option<usize>,
+  strategy: Option<String>,
+  symbol: Option<String>,
+  since: Option<i64>,
+}
+
+impl TradesQuery {
+  // 쿼리 문자열 flatten 은 숫자 파싱이 안 되므로 필드를 직접 옮김
+  fn filter(&self) -> TradeFilter {
+    TradeFilter { strategy: self.strategy.clone(), symbol: self.symbol.clone(), since: self.since }
+  }
+}
+
+// 체결 기록 조회 (strategy/symbol/since 필터, limit: 최근 N건)
+async fn get_trades(State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<TradesQuery>) -> axum::Json<serde_json::Value> {
+  let filter = q.filter();
+  let mut trades = state.journal.records(&filter);
+  if let Some(limit) = q.limit {
+    trades.drain(..trades.len().saturating_sub(limit));
+  }
+  axum::Json(serde_json::json!({"trades": trades, "fees": state.journal.fee_attribution(&filter)}))
+}
+
+// 체결 기록 내보내기 (format=csv|json, 기본 csv)
+async fn export_trades(State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<TradesQuery>) -> Result<impl IntoResponse, axum::http::StatusCode> {
+  let format = crate::order_core::reconcile::FileFormat::parse(q.format.as_deref().unwrap_or("csv")).map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  let mut body = Vec::new();
+  crate::core::trade_journal::export_trades(&state.journal.records(&q.filter()), format, &mut body).map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+  let content_type = match format {
+    crate::order_core::reconcile::FileFormat::Csv => "text/csv",
+    crate::order_core::reconcile::FileFormat::Json => "application/json",
+  };
+  Ok(([(axum::http::header::CONTENT_TYPE, content_type)], body))
+}
+
+async fn get_strategy_allocation(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<crate::core::capital_allocator::BudgetUsage>, axum::http::StatusCode> {
+  let mgr = state.strategy_manager.read().await;
+  mgr.get_budget_usage().into_iter().find(|u| u.strategy == name).map(axum::Json).ok_or(axum::http::StatusCode::NOT_FOUND)
+}
+
+#[derive(Debug, Deserialize)]
+struct SetBudgetReq { budget: f64 }
+
+// 전략 예산 고정 - 나머지 자본은 고정되지 않은 전략에 균등 재분배
+async fn set_strategy_budget(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<SetBudgetReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mut mgr = state.strategy_manager.write().await;
+  let changes = mgr.set_strategy_budget(&name, body.budget).map_err(|e| match e {
+    crate::error::TradingError::StrategyNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    _ => axum::http::StatusCode::BAD_REQUEST,
+  })?;
+  Ok(axum::Json(serde_json::json!({"status": "ok", "name": name, "changes": changes})))
+}
+
+// 예산 고정 해제 - 다시 자동 배분 대상으로
+async fn clear_strategy_budget(Path(name): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let mut mgr = state.strategy_manager.write().await;
+  let changes = mgr.clear_strategy_budget(&name).map_err(|_| axum::http::StatusCode::NOT_FOUND)?;
+  Ok(axum::Json(serde_json::json!({"status": "ok", "name": name, "changes": changes})))
+}
+
 // =============== Futures settings ===============
 #[derive(Debug, Deserialize)]
 struct SetPositionModeRequest { hedge: bool }
@@ -189,6 +716,10 @@ struct FuturesSettingsResponse { applied: serde_json::Value }
 async fn set_position_mode(State(state): State<AppState>, axum::Json(req): axum::Json<SetPositionModeRequest>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
   let mut ex = state.exchange.write().await;
   ex.set_futures_position_mode(req.hedge).await.map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  drop(ex);
+  if let Some(router) = state.order_manager.read().await.position_router() {
+    router.set_hedge_mode(req.hedge);
+  }
   Ok(axum::Json(serde_json::json!({"status":"ok","hedge":req.hedge})))
 }
 
@@ -210,6 +741,10 @@ async fn apply_futures_settings(State(state): State<AppState>, axum::Json(req):
     if let Some(pm) = &req.position_mode {
       let mut ex = state.exchange.write().await;
       ex.set_futures_position_mode(pm.hedge).await.map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+      drop(ex);
+      if let Some(router) = state.order_manager.read().await.position_router() {
+        router.set_hedge_mode(pm.hedge);
+      }
       applied["position_mode"] = serde_json::json!({"hedge": pm.hedge});
     }
   }
@@ -238,75 +773,34 @@ async fn get_market_snapshot(Path(symbol): Path<String>, State(state): State<App
   }
 }
 
-async fn ws_prices(ws: WebSocketUpgrade, Path(symbol): Path<String>, State(state): State<AppState>) -> impl IntoResponse {
-  ws.on_upgrade(move |socket| price_stream(socket, symbol, state))
-}
-
-async fn price_stream(mut socket: WebSocket, symbol: String, state: AppState) {
-  loop {
-    // simple 1s polling push
-    let price = {
-      let ex = state.exchange.read().await;
-      ex.get_market_data(&symbol).await.ok().map(|md| md.close)
-    };
-    if let Some(p) = price { let _ = socket.send(Message::Text(format!("{{\"symbol\":\"{}\",\"price\":{}}}", symbol, p))).await; }
-    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
-  }
-}
-
-async fn ws_orders(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-  ws.on_upgrade(move |socket| orders_stream(socket, state))
-}
-
-async fn orders_stream(mut socket: WebSocket, state: AppState) {
-  loop {
-    let data = {
-      let ex = state.exchange.read().await;
-      ex.get_open_orders().await.ok()
-    };
-    if let Some(list) = data {
-      if let Ok(text) = serde_json::to_string(&list) {
-        let _ = socket.send(Message::Text(text)).await;
-      }
-    }
-    tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
-  }
-}
+#[derive(Debug, Deserialize)]
+struct FundingQuery { limit: Option<usize> }
 
-async fn ws_positions(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-  ws.on_upgrade(move |socket| positions_stream(socket, state))
+#[derive(Debug, Serialize)]
+struct FundingResponse {
+  #[serde(flatten)]
+  latest: FundingSnapshot,
+  history: Vec<FundingSnapshot>,
 }
 
-async fn positions_stream(mut socket: WebSocket, state: AppState) {
-  loop {
-    let data = {
+// 최신 펀딩/미결제약정 + 수집된 스냅샷 (limit: 최근 N개, 기본 100) - 수집 전이면 거래소에서 바로 조회
+async fn get_funding_rate(Path(symbol): Path<String>, State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<FundingQuery>) -> Result<axum::Json<FundingResponse>, axum::http::StatusCode> {
+  let latest = match state.funding.latest(&symbol) {
+    Some(latest) => latest,
+    None => {
       let ex = state.exchange.read().await;
-      ex.get_positions().await.ok()
-    };
-    if let Some(list) = data {
-      if let Ok(text) = serde_json::to_string(&list) {
-        let _ = socket.send(Message::Text(text)).await;
-      }
+      let funding = ex.get_funding_rate(&symbol).await.map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+      let open_interest = ex.get_open_interest(&symbol).await.ok().map(|oi| oi.open_interest);
+      FundingSnapshot { funding, open_interest, open_interest_change: None }
     }
-    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
-  }
-}
-
-async fn ws_strategies(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-  ws.on_upgrade(move |socket| strategies_stream(socket, state))
+  };
+  let history = state.funding.history(&symbol, q.limit.unwrap_or(100));
+  Ok(axum::Json(FundingResponse { latest, history }))
 }
 
-async fn strategies_stream(mut socket: WebSocket, state: AppState) {
-  loop {
-    let snapshot = {
-      let mgr = state.strategy_manager.read().await;
-      mgr.list_strategies()
-    };
-    if let Ok(text) = serde_json::to_string(&snapshot) {
-      let _ = socket.send(Message::Text(text)).await;
-    }
-    tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
-  }
+async fn get_fee_tier(Path(symbol): Path<String>, State(state): State<AppState>) -> Result<axum::Json<crate::models::fee::FeeTier>, axum::http::StatusCode> {
+  let ex = state.exchange.read().await;
+  ex.get_fee_tier(&symbol).await.map(axum::Json).map_err(|_| axum::http::StatusCode::BAD_REQUEST)
 }
 
 #[derive(Debug, Deserialize)]
@@ -314,10 +808,27 @@ struct CreateOrderReq {
   symbol: String,
   side: String,
   order_type: String,
-  quantity: f64,
-  price: Option<f64>,
+  quantity: Decimal,
+  price: Option<Decimal>,
   reduce_only: Option<bool>,
   position_side: Option<String>,
+  client_order_id: Option<String>,
+  // 거래소 등록부 이름 (없으면 기본 거래소)
+  exchange: Option<String>,
+}
+
+// 주문 관리자 오류 -> HTTP 상태 코드
+fn order_error_status(e: &crate::error::TradingError) -> axum::http::StatusCode {
+  use crate::error::TradingError;
+  match e {
+    TradingError::OrderNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    TradingError::DegradedMode(_) | TradingError::TradingHalted(_) | TradingError::ExchangeUnavailable(_) => axum::http::StatusCode::SERVICE_UNAVAILABLE,
+    TradingError::RateLimited { .. } | TradingError::QueueFull(_) => axum::http::StatusCode::TOO_MANY_REQUESTS,
+    TradingError::NetworkTimeout(_) => axum::http::StatusCode::GATEWAY_TIMEOUT,
+    TradingError::InvalidParameter(_) | TradingError::RiskLimitExceeded(_) | TradingError::InsufficientBalance
+    | TradingError::InvalidSymbol(_) | TradingError::ExchangeRejected { .. } => axum::http::StatusCode::UNPROCESSABLE_ENTITY,
+    _ => axum::http::StatusCode::BAD_REQUEST,
+  }
 }
 
 async fn create_order(State(state): State<AppState>, axum::Json(req): axum::Json<CreateOrderReq>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
@@ -327,31 +838,195 @@ async fn create_order(State(state): State<AppState>, axum::Json(req): axum::Json
     "limit" => OrderType::Limit,
     "stop" | "stoploss" => OrderType::StopLoss,
     _ => OrderType::Market,
-  }, req.quantity, req.price.unwrap_or(0.0));
+  }, req.quantity, req.price.unwrap_or_default());
   if let Some(ro) = req.reduce_only { order = order.with_reduce_only(ro); }
   if let Some(ps) = req.position_side { order = order.with_position_side(ps); }
+  if let Some(exchange) = req.exchange { order = order.with_exchange(exchange); }
+  let client_order_id = req.client_order_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+  order = order.with_client_order_id(client_order_id.clone());
 
-  // Minimal submit path via Exchange directly
-  let oid = {
-    let mut ex = state.exchange.write().await;
-    ex.submit_order(order).await.map_err(|_| axum::http::StatusCode::BAD_REQUEST)?
-  };
-  Ok(axum::Json(serde_json::json!({"status":"ok","order_id": oid.0})))
+  // 검증기 -> 저장소 -> 거래소 제출 (재시도 포함)
+  let queue = state.order_manager.read().await.submission_queue();
+  let oid = queue.submit(None, order).await.map_err(|e| {
+    log::warn!("API order rejected: {}", e);
+    order_error_status(&e)
+  })?;
+  Ok(axum::Json(serde_json::json!({"status":"ok","order_id": oid.0, "client_order_id": client_order_id})))
+}
+
+// 미체결 주문 (등록된 모든 거래소)
+async fn list_orders(State(state): State<AppState>) -> Result<axum::Json<Vec<Order>>, axum::http::StatusCode> {
+  let orders = state.order_manager.read().await.get_open_orders().await.map_err(|e| order_error_status(&e))?;
+  Ok(axum::Json(orders))
 }
 
 async fn cancel_order(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
-  let order_id = OrderId(id);
-  let mut ex = state.exchange.write().await;
-  ex.cancel_order(&order_id).await.map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+  let queue = state.order_manager.read().await.submission_queue();
+  queue.cancel(OrderId(id)).await.map_err(|e| order_error_status(&e))?;
   Ok(axum::Json(serde_json::json!({"status":"ok","cancelled":true})))
 }
 
+#[derive(Debug, Deserialize)]
+struct CreateBracketReq {
+  symbol: String,
+  side: String,
+  quantity: Decimal,
+  /// 지정가 진입 가격 (없으면 시장가)
+  price: Option<Decimal>,
+  take_profit: Decimal,
+  stop_loss: Decimal,
+  strategy: Option<String>,
+}
+
+async fn create_bracket(State(state): State<AppState>, axum::Json(req): axum::Json<CreateBracketReq>) -> Result<axum::Json<crate::order_core::bracket::Bracket>, axum::http::StatusCode> {
+  use crate::order_core::bracket::BracketOrder;
+  let side = match req.side.to_lowercase().as_str() { "buy" => OrderSide::Buy, "sell" => OrderSide::Sell, _ => return Err(axum::http::StatusCode::BAD_REQUEST) };
+  let entry = match req.price {
+    Some(price) => Order::new(req.symbol, side, OrderType::Limit, req.quantity, price),
+    None => Order::new(req.symbol, side, OrderType::Market, req.quantity, Decimal::ZERO),
+  };
+  let bracket = state.oco.submit(req.strategy, BracketOrder::new(entry, req.take_profit, req.stop_loss)).await.map_err(|e| {
+    log::warn!("API bracket rejected: {}", e);
+    order_error_status(&e)
+  })?;
+  Ok(axum::Json(bracket))
+}
+
+async fn list_brackets(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  axum::Json(serde_json::json!({"brackets": state.oco.list()}))
+}
+
+async fn get_bracket(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<crate::order_core::bracket::Bracket>, axum::http::StatusCode> {
+  state.oco.get(&id).map(axum::Json).ok_or(axum::http::StatusCode::NOT_FOUND)
+}
+
+async fn cancel_bracket(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<crate::order_core::bracket::Bracket>, axum::http::StatusCode> {
+  state.oco.cancel(&id).await.map(axum::Json).map_err(|e| match e {
+    crate::error::TradingError::DataNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    e => order_error_status(&e),
+  })
+}
+
+// =============== Conditional orders ===============
+async fn create_condition(State(state): State<AppState>, axum::Json(req): axum::Json<ConditionalOrderRequest>) -> Result<axum::Json<ConditionalOrder>, axum::http::StatusCode> {
+  state.conditional.create(req).map(axum::Json).map_err(|e| {
+    log::warn!("API conditional order rejected: {}", e);
+    axum::http::StatusCode::BAD_REQUEST
+  })
+}
+
+async fn list_conditions(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  axum::Json(serde_json::json!({"conditions": state.conditional.list()}))
+}
+
+async fn get_condition(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<ConditionalOrder>, axum::http::StatusCode> {
+  state.conditional.get(&id).map(axum::Json).ok_or(axum::http::StatusCode::NOT_FOUND)
+}
+
+async fn cancel_condition(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<ConditionalOrder>, axum::http::StatusCode> {
+  state.conditional.cancel(&id).map(axum::Json).map_err(|_| axum::http::StatusCode::NOT_FOUND)
+}
+
+// =============== Backtests ===============
+// 작업을 큐에 넣고 바로 202 반환 - 진행률은 GET /backtests/:id 로 확인
+async fn submit_backtest(State(state): State<AppState>, axum::Json(req): axum::Json<BacktestJobRequest>) -> Result<(axum::http::StatusCode, axum::Json<BacktestJobStatus>), axum::http::StatusCode> {
+  state.backtests.submit(req).map(|status| (axum::http::StatusCode::ACCEPTED, axum::Json(status))).map_err(|e| {
+    log::warn!("API backtest rejected: {}", e);
+    axum::http::StatusCode::UNPROCESSABLE_ENTITY
+  })
+}
+
+async fn list_backtests(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  axum::Json(serde_json::json!({"backtests": state.backtests.list()}))
+}
+
+async fn get_backtest(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<BacktestJobStatus>, axum::http::StatusCode> {
+  state.backtests.status(&id).map(axum::Json).ok_or(axum::http::StatusCode::NOT_FOUND)
+}
+
+#[derive(Debug, Deserialize)]
+struct SweepQuery { format: Option<String> }
+
+// 2차원 파라미터 스윕을 끝까지 실행하고 히트맵 JSON(기본) 또는 칸별 CSV(format=csv) 반환
+async fn run_backtest_sweep(State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<SweepQuery>, axum::Json(req): axum::Json<BacktestSweepRequest>) -> Result<impl IntoResponse, (axum::http::StatusCode, String)> {
+  let metric = req.metric;
+  let grid = state.backtests.sweep(req).await.map_err(|e| {
+    log::warn!("API sweep rejected: {}", e);
+    (axum::http::StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+  })?;
+  match q.format.as_deref() {
+    Some("csv") => {
+      let mut body = Vec::new();
+      grid.export_csv(metric, &mut body).map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+      Ok(([(axum::http::header::CONTENT_TYPE, "text/csv")], body))
+    }
+    _ => Ok(([(axum::http::header::CONTENT_TYPE, "application/json")], grid.to_heatmap_json(metric).to_string().into_bytes())),
+  }
+}
+
+// 완료 전이거나 실패한 작업은 409 (실패 사유는 작업 상태의 error)
+async fn get_backtest_result(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<BacktestResult>, axum::http::StatusCode> {
+  state.backtests.result(&id).map(|result| axum::Json(result.as_ref().clone())).map_err(|e| match e {
+    crate::error::TradingError::DataNotFound(_) => axum::http::StatusCode::NOT_FOUND,
+    _ => axum::http::StatusCode::CONFLICT,
+  })
+}
+
+// 제출 큐 우선순위별 대기/처리 지표
+async fn get_submission_queue(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  let queue = state.order_manager.read().await.submission_queue();
+  axum::Json(serde_json::json!({
+    "pending": queue.pending(), "capacity": queue.capacity(), "max_depth": queue.max_depth(), "priorities": queue.metrics(),
+  }))
+}
+
+// =============== External series ===============
+async fn list_external_series(State(state): State<AppState>) -> axum::Json<serde_json::Value> {
+  axum::Json(serde_json::json!({"series": state.external.summaries()}))
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalRangeQuery { from: Option<Timestamp>, to: Option<Timestamp> }
+
+async fn get_external_series(Path(name): Path<String>, State(state): State<AppState>, axum::extract::Query(q): axum::extract::Query<ExternalRangeQuery>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let from = q.from.unwrap_or_default();
+  let to = q.to.unwrap_or_else(Timestamp::now);
+  let points = state.external.range(&name, from, to).ok_or(axum::http::StatusCode::NOT_FOUND)?;
+  let points: Vec<ExternalPoint> = points.into_iter().map(|(timestamp, value)| ExternalPoint { timestamp, value }).collect();
+  Ok(axum::Json(serde_json::json!({"name": name, "points": points})))
+}
+
+// 웹훅 수신: {"value": 42} (시각 생략 시 수신 시각), {"timestamp": ..., "value": ...} 또는 그 배열
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ExternalPush {
+  Many(Vec<ExternalPushPoint>),
+  One(ExternalPushPoint),
+}
+
+#[derive(Debug, Deserialize)]
+struct ExternalPushPoint { timestamp: Option<Timestamp>, value: f64 }
+
+async fn push_external_series(Path(name): Path<String>, State(state): State<AppState>, axum::Json(body): axum::Json<ExternalPush>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
+  let points = match body {
+    ExternalPush::Many(points) => points,
+    ExternalPush::One(point) => vec![point],
+  };
+  if points.iter().any(|p| !p.value.is_finite()) {
+    return Err(axum::http::StatusCode::BAD_REQUEST);
+  }
+  for point in &points {
+    state.external.record(&name, point.timestamp.unwrap_or_else(Timestamp::now), point.value);
+  }
+  Ok(axum::Json(serde_json::json!({"status": "ok", "name": name, "recorded": points.len()})))
+}
+
 async fn get_order_status(Path(id): Path<String>, State(state): State<AppState>) -> Result<axum::Json<serde_json::Value>, axum::http::StatusCode> {
   let order_id = OrderId(id);
-  let ex = state.exchange.read().await;
-  match ex.get_order_status(&order_id).await {
+  let om = state.order_manager.read().await;
+  match om.get_order_status(&order_id).await {
     Ok(status) => Ok(axum::Json(serde_json::json!({"status": format!("{:?}", status)}))),
-    Err(_) => Err(axum::http::StatusCode::BAD_REQUEST)
+    Err(e) => Err(order_error_status(&e))
   }
 }
 
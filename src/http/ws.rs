@@ -0,0 +1,259 @@
+//! 단일 `/ws` 엔드포인트 - 구독 메시지로 채널을 골라 한 소켓에 다중화
+//!
+//! 클라이언트 메시지: `{"op":"subscribe","channel":"prices","symbol":"BTCUSDT"}`,
+//! `{"op":"unsubscribe",...}`, `{"op":"ping"}`. 서버는 `{"channel","symbol","data"}` 로 데이터를 보내고
+//! 구독 결과는 `{"type":"subscribed"|"unsubscribed"|"error",...}` 로 알린다.
+//! 서버는 주기적으로 WebSocket Ping 을 보내고, 클라이언트가 일정 시간 아무것도 보내지 않으면 연결을 닫는다.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+
+use super::AppState;
+
+/// 서버 Ping 주기
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// 이 시간 동안 클라이언트 메시지(Pong 포함)가 없으면 연결 종료
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(90);
+/// 소켓으로 나가기 전 대기할 수 있는 메시지 수 (느린 클라이언트는 시세부터 밀림)
+const OUTBOUND_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+enum Channel {
+  Prices,
+  Orders,
+  Positions,
+  Strategies,
+  /// 주문 생명주기 이벤트 (생성/접수/체결/취소/거부) - 스냅샷 없이 발생할 때마다
+  OrderEvents,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum ClientMessage {
+  Subscribe {
+    channel: Channel,
+    #[serde(default)]
+    symbol: Option<String>,
+  },
+  Unsubscribe {
+    channel: Channel,
+    #[serde(default)]
+    symbol: Option<String>,
+  },
+  Ping,
+}
+
+type SubscriptionKey = (Channel, Option<String>);
+
+pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
+  ws.on_upgrade(move |socket| run_session(socket, state))
+}
+
+async fn run_session(mut socket: WebSocket, state: AppState) {
+  let (out_tx, mut out_rx) = mpsc::channel::<String>(OUTBOUND_BUFFER);
+  let mut subscriptions: HashMap<SubscriptionKey, JoinHandle<()>> = HashMap::new();
+  let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+  heartbeat.tick().await;
+  let mut last_seen = Instant::now();
+
+  loop {
+    tokio::select! {
+      text = out_rx.recv() => {
+        let Some(text) = text else { break };
+        if socket.send(Message::Text(text)).await.is_err() { break; }
+      }
+      msg = socket.recv() => {
+        let msg = match msg {
+          Some(Ok(msg)) => msg,
+          _ => break,
+        };
+        last_seen = Instant::now();
+        let reply = match msg {
+          Message::Text(text) => handle_message(&text, &mut subscriptions, |key| spawn_forwarder(key, &state, out_tx.clone())).await,
+          Message::Close(_) => break,
+          // Ping 에는 axum 이 자동으로 Pong 응답, Pong 은 생존 확인만
+          _ => None,
+        };
+        if let Some(reply) = reply {
+          if socket.send(Message::Text(reply.to_string())).await.is_err() { break; }
+        }
+      }
+      _ = heartbeat.tick() => {
+        if last_seen.elapsed() >= CLIENT_TIMEOUT {
+          log::debug!("ws client idle for {:?}, closing", last_seen.elapsed());
+          let _ = socket.send(Message::Close(None)).await;
+          break;
+        }
+        if socket.send(Message::Ping(Vec::new())).await.is_err() { break; }
+      }
+    }
+  }
+
+  for (_, task) in subscriptions.drain() {
+    task.abort();
+  }
+}
+
+// 클라이언트 메시지 처리 - 즉시 보낼 응답 반환 (새 구독이면 `spawn` 으로 중계 태스크 시작)
+async fn handle_message<F, Fut>(
+  text: &str,
+  subscriptions: &mut HashMap<SubscriptionKey, JoinHandle<()>>,
+  spawn: F,
+) -> Option<serde_json::Value>
+where
+  F: FnOnce(SubscriptionKey) -> Fut,
+  Fut: Future<Output = JoinHandle<()>>,
+{
+  let message = match serde_json::from_str::<ClientMessage>(text) {
+    Ok(message) => message,
+    Err(e) => return Some(serde_json::json!({"type": "error", "message": format!("invalid message: {}", e)})),
+  };
+  match message {
+    ClientMessage::Ping => Some(serde_json::json!({"type": "pong", "timestamp": chrono::Utc::now().timestamp_millis()})),
+    ClientMessage::Subscribe { channel, symbol } => {
+      let key = match subscription_key(channel, symbol) {
+        Ok(key) => key,
+        Err(message) => return Some(serde_json::json!({"type": "error", "message": message})),
+      };
+      if !subscriptions.contains_key(&key) {
+        let task = spawn(key.clone()).await;
+        subscriptions.insert(key.clone(), task);
+      }
+      Some(serde_json::json!({"type": "subscribed", "channel": key.0, "symbol": key.1}))
+    }
+    ClientMessage::Unsubscribe { channel, symbol } => {
+      let key = match subscription_key(channel, symbol) {
+        Ok(key) => key,
+        Err(message) => return Some(serde_json::json!({"type": "error", "message": message})),
+      };
+      match subscriptions.remove(&key) {
+        Some(task) => {
+          task.abort();
+          Some(serde_json::json!({"type": "unsubscribed", "channel": key.0, "symbol": key.1}))
+        }
+        None => Some(serde_json::json!({"type": "error", "message": "not subscribed", "channel": key.0, "symbol": key.1})),
+      }
+    }
+  }
+}
+
+// 시세 채널만 심볼 필요 (대문자로 통일), 나머지 채널은 심볼 무시
+fn subscription_key(channel: Channel, symbol: Option<String>) -> Result<SubscriptionKey, String> {
+  match channel {
+    Channel::Prices => match symbol.filter(|s| !s.trim().is_empty()) {
+      Some(symbol) => Ok((channel, Some(symbol.trim().to_uppercase()))),
+      None => Err("prices channel requires a symbol".to_string()),
+    },
+    _ => Ok((channel, None)),
+  }
+}
+
+// 공유 피드 -> 이 연결의 송신 큐
+async fn spawn_forwarder(key: SubscriptionKey, state: &AppState, out: mpsc::Sender<String>) -> JoinHandle<()> {
+  let (channel, symbol) = key;
+  match channel {
+    Channel::Prices => {
+      let symbol = symbol.unwrap_or_default();
+      let mut prices = state.live_feed.subscribe_prices(&symbol).await;
+      tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+          match prices.recv().await {
+            Ok(data) => {
+              let text = serde_json::json!({"channel": channel, "symbol": symbol, "data": data}).to_string();
+              // 송신 큐가 가득 차면 이번 시세는 버림 (다음 시세가 곧 옴)
+              if let Err(mpsc::error::TrySendError::Closed(_)) = out.try_send(text) { return; }
+            }
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+          }
+        }
+      })
+    }
+    Channel::Orders => tokio::spawn(forward_snapshots(channel, state.live_feed.subscribe_orders(), out)),
+    Channel::Positions => tokio::spawn(forward_snapshots(channel, state.live_feed.subscribe_positions(), out)),
+    Channel::Strategies => tokio::spawn(forward_snapshots(channel, state.live_feed.subscribe_strategies(), out)),
+    Channel::OrderEvents => {
+      let mut events = state.order_manager.read().await.subscribe_to_order_events();
+      tokio::spawn(async move {
+        use tokio::sync::broadcast::error::RecvError;
+        loop {
+          match events.recv().await {
+            Ok(event) => {
+              let text = serde_json::json!({"channel": channel, "data": event}).to_string();
+              if out.send(text).await.is_err() { return; }
+            }
+            Err(RecvError::Lagged(n)) => log::debug!("ws order events lagged {}", n),
+            Err(RecvError::Closed) => return,
+          }
+        }
+      })
+    }
+  }
+}
+
+// 스냅샷 채널: 구독 직후 현재 목록, 이후 바뀔 때마다 전체 목록
+async fn forward_snapshots<T: Serialize>(channel: Channel, mut snapshots: watch::Receiver<Option<T>>, out: mpsc::Sender<String>) {
+  snapshots.mark_changed();
+  while snapshots.changed().await.is_ok() {
+    let text = snapshots.borrow_and_update().as_ref()
+      .map(|data| serde_json::json!({"channel": channel, "data": data}).to_string());
+    if let Some(text) = text {
+      if out.send(text).await.is_err() { return; }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 중계 태스크 대신 끝나지 않는 태스크, 시작 횟수만 셈
+  async fn send(text: &str, subscriptions: &mut HashMap<SubscriptionKey, JoinHandle<()>>, spawned: &mut usize) -> serde_json::Value {
+    handle_message(text, subscriptions, |_| {
+      *spawned += 1;
+      async { tokio::spawn(std::future::pending::<()>()) }
+    }).await.unwrap()
+  }
+
+  #[tokio::test]
+  async fn test_subscribe_unsubscribe_and_unknown_channel() {
+    let (mut subscriptions, mut spawned) = (HashMap::new(), 0);
+
+    // 심볼은 대문자로 통일, 같은 구독을 반복해도 중계 태스크는 하나
+    let reply = send(r#"{"op":"subscribe","channel":"prices","symbol":"btcusdt"}"#, &mut subscriptions, &mut spawned).await;
+    assert_eq!(reply, serde_json::json!({"type": "subscribed", "channel": "prices", "symbol": "BTCUSDT"}));
+    send(r#"{"op":"subscribe","channel":"prices","symbol":"BTCUSDT"}"#, &mut subscriptions, &mut spawned).await;
+    // 시세 외 채널은 심볼 무시
+    let reply = send(r#"{"op":"subscribe","channel":"orders","symbol":"ETHUSDT"}"#, &mut subscriptions, &mut spawned).await;
+    assert_eq!(reply["symbol"], serde_json::Value::Null);
+    assert_eq!((spawned, subscriptions.len()), (2, 2));
+
+    let reply = send(r#"{"op":"unsubscribe","channel":"prices","symbol":"BTCUSDT"}"#, &mut subscriptions, &mut spawned).await;
+    assert_eq!(reply["type"], "unsubscribed");
+    assert_eq!(subscriptions.len(), 1);
+    let reply = send(r#"{"op":"unsubscribe","channel":"prices","symbol":"BTCUSDT"}"#, &mut subscriptions, &mut spawned).await;
+    assert_eq!((reply["type"].as_str(), reply["message"].as_str()), (Some("error"), Some("not subscribed")));
+
+    // 알 수 없는 채널/연산, 심볼 없는 시세 구독은 오류 응답만 하고 구독하지 않음
+    for text in [
+      r#"{"op":"subscribe","channel":"trades"}"#,
+      r#"{"op":"publish","channel":"orders"}"#,
+      r#"{"op":"subscribe","channel":"prices"}"#,
+      "not json",
+    ] {
+      assert_eq!(send(text, &mut subscriptions, &mut spawned).await["type"], "error", "{}", text);
+    }
+    assert_eq!((spawned, subscriptions.len()), (2, 1));
+    assert_eq!(send(r#"{"op":"ping"}"#, &mut subscriptions, &mut spawned).await["type"], "pong");
+  }
+}
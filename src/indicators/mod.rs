@@ -8,12 +8,14 @@ pub mod moving_averages;
 pub mod oscillators;
 pub mod trend;
 pub mod volume;
+pub mod volatility;
 pub mod utils;
 
 pub use moving_averages::*;
 pub use oscillators::*;
 pub use trend::*;
 pub use volume::*;
+pub use volatility::*;
 pub use utils::*;
 
 use std::fmt::Debug;
@@ -37,6 +39,11 @@ pub trait Indicator: Debug + Send + Sync {
   // 새로운 데이터로 지표 업데이트
   fn update(&mut self, price: f64, volume: Option<f64>) -> Result<(), crate::error::TradingError>;
   
+  // 캔들(OHLC) 데이터로 지표 업데이트 - 기본은 종가만 사용
+  fn update_ohlc(&mut self, _open: f64, _high: f64, _low: f64, close: f64, volume: Option<f64>) -> Result<(), crate::error::TradingError> {
+    self.update(close, volume)
+  }
+  
   // 현재 지표 값 반환
   fn calculate(&self) -> Result<IndicatorResult, crate::error::TradingError>;
   
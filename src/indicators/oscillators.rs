@@ -42,6 +42,12 @@ impl RelativeStrengthIndex {
   pub fn period(&self) -> usize {
     self.period
   }
+  
+  // 과매수/과매도 임계치 변경 (누적된 가격 이력은 유지)
+  pub fn set_thresholds(&mut self, overbought: f64, oversold: f64) {
+    self.overbought_threshold = overbought;
+    self.oversold_threshold = oversold;
+  }
 }
 
 impl Indicator for RelativeStrengthIndex {
@@ -136,4 +136,206 @@ impl Indicator for MACD {
     self.signal_ema.reset();
     self.histogram_values.clear();
   }
-}
\ No newline at end of file
+}
+
+// 일목균형표 각 선의 현재 값
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IchimokuLines {
+  pub tenkan: f64,
+  pub kijun: f64,
+  // 현재 캔들에서 계산되어 displacement 만큼 앞에 그려지는 선행스팬
+  pub senkou_a: f64,
+  pub senkou_b: f64,
+  // 현재 종가 (displacement 만큼 뒤에 그려짐)
+  pub chikou: f64,
+  // 현재 캔들 위치의 구름 (displacement 전에 계산된 선행스팬)
+  pub cloud_top: f64,
+  pub cloud_bottom: f64,
+}
+
+// 일목균형표(Ichimoku Kinko Hyo): 전환선/기준선/선행스팬 A·B/후행스팬
+#[derive(Debug)]
+pub struct Ichimoku {
+  name: String,
+  tenkan_period: usize,
+  kijun_period: usize,
+  senkou_b_period: usize,
+  displacement: usize,
+  highs: VecDeque<f64>,
+  lows: VecDeque<f64>,
+  // 후행스팬 비교용 종가 이력 (displacement + 1)
+  closes: VecDeque<f64>,
+  // 선행스팬 (A, B) 이력 - 맨 앞이 현재 캔들 위치의 구름
+  spans: VecDeque<(f64, f64)>,
+  current: Option<IchimokuLines>,
+  previous: Option<IchimokuLines>,
+}
+
+impl Ichimoku {
+  pub fn new(tenkan_period: usize, kijun_period: usize, senkou_b_period: usize) -> Self {
+    let tenkan_period = tenkan_period.max(1);
+    let kijun_period = kijun_period.max(1);
+    let senkou_b_period = senkou_b_period.max(1);
+    let window = tenkan_period.max(kijun_period).max(senkou_b_period);
+    Ichimoku {
+      name: format!("ICHIMOKU-{}-{}-{}", tenkan_period, kijun_period, senkou_b_period),
+      tenkan_period,
+      kijun_period,
+      senkou_b_period,
+      displacement: kijun_period,
+      highs: VecDeque::with_capacity(window),
+      lows: VecDeque::with_capacity(window),
+      closes: VecDeque::with_capacity(kijun_period + 1),
+      spans: VecDeque::with_capacity(kijun_period + 1),
+      current: None,
+      previous: None,
+    }
+  }
+
+  // 선행/후행 이동 폭 (기본 기준선 기간)
+  pub fn with_displacement(mut self, displacement: usize) -> Self {
+    self.displacement = displacement.max(1);
+    self
+  }
+
+  pub fn lines(&self) -> Option<IchimokuLines> {
+    self.current
+  }
+
+  // 최근 period 개 캔들의 (최고가 + 최저가) / 2
+  fn midpoint(&self, period: usize) -> Option<f64> {
+    if self.highs.len() < period {
+      return None;
+    }
+    let skip = self.highs.len() - period;
+    let high = self.highs.iter().skip(skip).cloned().fold(f64::MIN, f64::max);
+    let low = self.lows.iter().skip(skip).cloned().fold(f64::MAX, f64::min);
+    Some((high + low) / 2.0)
+  }
+}
+
+impl Indicator for Ichimoku {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  // 종가만 주어지면 고가 = 저가 = 종가로 간주
+  fn update(&mut self, price: f64, volume: Option<f64>) -> Result<(), TradingError> {
+    self.update_ohlc(price, price, price, price, volume)
+  }
+
+  fn update_ohlc(&mut self, _open: f64, high: f64, low: f64, close: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if low <= 0.0 || high < low {
+      return Err(TradingError::InvalidParameter("Invalid high/low range".to_string()));
+    }
+
+    let window = self.tenkan_period.max(self.kijun_period).max(self.senkou_b_period);
+    self.highs.push_back(high);
+    self.lows.push_back(low);
+    if self.highs.len() > window {
+      self.highs.pop_front();
+      self.lows.pop_front();
+    }
+    self.closes.push_back(close);
+    if self.closes.len() > self.displacement + 1 {
+      self.closes.pop_front();
+    }
+
+    let (Some(tenkan), Some(kijun), Some(senkou_b)) = (
+      self.midpoint(self.tenkan_period),
+      self.midpoint(self.kijun_period),
+      self.midpoint(self.senkou_b_period),
+    ) else {
+      return Ok(());
+    };
+    let senkou_a = (tenkan + kijun) / 2.0;
+
+    self.spans.push_back((senkou_a, senkou_b));
+    if self.spans.len() > self.displacement + 1 {
+      self.spans.pop_front();
+    }
+
+    // displacement 전에 계산된 구름이 있어야 현재 위치 판단 가능
+    if self.spans.len() > self.displacement {
+      let (span_a, span_b) = self.spans[0];
+      self.previous = self.current;
+      self.current = Some(IchimokuLines {
+        tenkan,
+        kijun,
+        senkou_a,
+        senkou_b,
+        chikou: close,
+        cloud_top: span_a.max(span_b),
+        cloud_bottom: span_a.min(span_b),
+      });
+    }
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    let Some(lines) = self.current else {
+      return Err(TradingError::InsufficientData);
+    };
+
+    let mut signals = Vec::new();
+    let price = lines.chikou;
+    // 후행스팬이 displacement 전 종가보다 위/아래인지 (추세 확인)
+    let chikou_bias = match self.closes.front() {
+      Some(past) if self.closes.len() > self.displacement && price > *past => 1.0,
+      Some(past) if self.closes.len() > self.displacement && price < *past => -1.0,
+      _ => 0.0,
+    };
+
+    if let Some(prev) = self.previous {
+      // 구름 돌파: 직전 종가가 구름 상단 이하 -> 현재 종가가 구름 위
+      if prev.chikou <= prev.cloud_top && price > lines.cloud_top {
+        signals.push(IndicatorSignal {
+          name: "Ichimoku Cloud Breakout Up".to_string(),
+          strength: if chikou_bias > 0.0 { 0.8 } else { 0.6 },
+          message: format!("Price {:.2} broke above cloud top {:.2}", price, lines.cloud_top),
+        });
+      } else if prev.chikou >= prev.cloud_bottom && price < lines.cloud_bottom {
+        signals.push(IndicatorSignal {
+          name: "Ichimoku Cloud Breakout Down".to_string(),
+          strength: if chikou_bias < 0.0 { -0.8 } else { -0.6 },
+          message: format!("Price {:.2} broke below cloud bottom {:.2}", price, lines.cloud_bottom),
+        });
+      }
+
+      // 전환선/기준선 교차 - 구름 위 골든크로스, 구름 아래 데드크로스가 가장 강함
+      let position = if price > lines.cloud_top { 1.0 } else if price < lines.cloud_bottom { -1.0 } else { 0.0 };
+      if prev.tenkan <= prev.kijun && lines.tenkan > lines.kijun {
+        signals.push(IndicatorSignal {
+          name: "Ichimoku TK Bullish Cross".to_string(),
+          strength: 0.4 + 0.2 * position,
+          message: format!("Tenkan {:.2} crossed above Kijun {:.2}", lines.tenkan, lines.kijun),
+        });
+      } else if prev.tenkan >= prev.kijun && lines.tenkan < lines.kijun {
+        signals.push(IndicatorSignal {
+          name: "Ichimoku TK Bearish Cross".to_string(),
+          strength: -0.4 + 0.2 * position,
+          message: format!("Tenkan {:.2} crossed below Kijun {:.2}", lines.tenkan, lines.kijun),
+        });
+      }
+    }
+
+    Ok(IndicatorResult {
+      value: lines.kijun, // 기준선을 주요 값으로 반환
+      signals,
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.current.is_some()
+  }
+
+  fn reset(&mut self) {
+    self.highs.clear();
+    self.lows.clear();
+    self.closes.clear();
+    self.spans.clear();
+    self.current = None;
+    self.previous = None;
+  }
+}
@@ -27,7 +27,11 @@ pub fn update_indicators_with_market_data(
   indicators: &mut [Box<dyn Indicator>],
   market_data: &MarketData
 ) -> Result<(), TradingError> {
-  update_indicators(indicators, market_data.close, Some(market_data.volume))
+  for indicator in indicators.iter_mut() {
+    indicator.update_ohlc(market_data.open_f64(), market_data.high_f64(), market_data.low_f64(), market_data.close_f64(), Some(market_data.volume_f64()))?;
+  }
+  
+  Ok(())
 }
 
 // 지표 초기화
@@ -0,0 +1,768 @@
+/**
+* filename : volatility
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 실현 변동성(EWMA/Parkinson/Garman-Klass), ATR, 볼린저 밴드 및 롤링 상관계수 지표
+**/
+
+use std::collections::VecDeque;
+use crate::error::TradingError;
+use crate::models::market_data::MarketData;
+use super::{Indicator, IndicatorResult, IndicatorSignal};
+
+// 1분봉 기준 연환산 계수
+pub const MINUTES_PER_YEAR: f64 = 525_600.0;
+
+// EWMA(RiskMetrics) 변동성: var_t = λ·var_{t-1} + (1-λ)·r_t²
+#[derive(Debug)]
+pub struct EwmaVolatility {
+  name: String,
+  lambda: f64,
+  annualization: f64,
+  min_samples: usize,
+  samples: usize,
+  variance: Option<f64>,
+  prev_price: Option<f64>,
+}
+
+impl EwmaVolatility {
+  pub fn new(lambda: f64, annualization: f64) -> Self {
+    EwmaVolatility {
+      name: format!("EWMA-VOL-{}", lambda),
+      lambda,
+      annualization,
+      min_samples: 10,
+      samples: 0,
+      variance: None,
+      prev_price: None,
+    }
+  }
+}
+
+impl Indicator for EwmaVolatility {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn update(&mut self, price: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if price <= 0.0 {
+      return Err(TradingError::InvalidParameter("Price must be positive".to_string()));
+    }
+
+    if let Some(prev) = self.prev_price {
+      let r = (price / prev).ln();
+      self.variance = Some(match self.variance {
+        Some(v) => self.lambda * v + (1.0 - self.lambda) * r * r,
+        None => r * r,
+      });
+      self.samples += 1;
+    }
+    self.prev_price = Some(price);
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    if !self.is_ready() {
+      return Err(TradingError::InsufficientData);
+    }
+
+    let variance = self.variance.unwrap_or(0.0);
+    Ok(IndicatorResult {
+      value: (variance * self.annualization).sqrt(),
+      signals: Vec::new(),
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.samples >= self.min_samples
+  }
+
+  fn reset(&mut self) {
+    self.samples = 0;
+    self.variance = None;
+    self.prev_price = None;
+  }
+}
+
+// Parkinson 변동성: 고가/저가 범위 기반 추정
+#[derive(Debug)]
+pub struct ParkinsonVolatility {
+  name: String,
+  period: usize,
+  annualization: f64,
+  terms: VecDeque<f64>,
+}
+
+impl ParkinsonVolatility {
+  pub fn new(period: usize, annualization: f64) -> Self {
+    let period = period.max(1);
+    ParkinsonVolatility {
+      name: format!("PARKINSON-VOL-{}", period),
+      period,
+      annualization,
+      terms: VecDeque::with_capacity(period),
+    }
+  }
+}
+
+impl Indicator for ParkinsonVolatility {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  // 종가만 주어지면 범위가 0이므로 OHLC 업데이트 필요
+  fn update(&mut self, price: f64, volume: Option<f64>) -> Result<(), TradingError> {
+    self.update_ohlc(price, price, price, price, volume)
+  }
+
+  fn update_ohlc(&mut self, _open: f64, high: f64, low: f64, _close: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if low <= 0.0 || high < low {
+      return Err(TradingError::InvalidParameter("Invalid high/low range".to_string()));
+    }
+
+    let hl = (high / low).ln();
+    self.terms.push_back(hl * hl);
+    if self.terms.len() > self.period {
+      self.terms.pop_front();
+    }
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    if !self.is_ready() {
+      return Err(TradingError::InsufficientData);
+    }
+
+    let mean = self.terms.iter().sum::<f64>() / self.terms.len() as f64;
+    let variance = mean / (4.0 * std::f64::consts::LN_2);
+    Ok(IndicatorResult {
+      value: (variance * self.annualization).sqrt(),
+      signals: Vec::new(),
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.terms.len() >= self.period
+  }
+
+  fn reset(&mut self) {
+    self.terms.clear();
+  }
+}
+
+// Garman-Klass 변동성: 시가/고가/저가/종가 기반 추정
+#[derive(Debug)]
+pub struct GarmanKlassVolatility {
+  name: String,
+  period: usize,
+  annualization: f64,
+  terms: VecDeque<f64>,
+}
+
+impl GarmanKlassVolatility {
+  pub fn new(period: usize, annualization: f64) -> Self {
+    let period = period.max(1);
+    GarmanKlassVolatility {
+      name: format!("GK-VOL-{}", period),
+      period,
+      annualization,
+      terms: VecDeque::with_capacity(period),
+    }
+  }
+}
+
+impl Indicator for GarmanKlassVolatility {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn update(&mut self, price: f64, volume: Option<f64>) -> Result<(), TradingError> {
+    self.update_ohlc(price, price, price, price, volume)
+  }
+
+  fn update_ohlc(&mut self, open: f64, high: f64, low: f64, close: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if open <= 0.0 || low <= 0.0 || high < low {
+      return Err(TradingError::InvalidParameter("Invalid OHLC values".to_string()));
+    }
+
+    let hl = (high / low).ln();
+    let co = (close / open).ln();
+    let term = 0.5 * hl * hl - (2.0 * std::f64::consts::LN_2 - 1.0) * co * co;
+    self.terms.push_back(term);
+    if self.terms.len() > self.period {
+      self.terms.pop_front();
+    }
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    if !self.is_ready() {
+      return Err(TradingError::InsufficientData);
+    }
+
+    let variance = (self.terms.iter().sum::<f64>() / self.terms.len() as f64).max(0.0);
+    Ok(IndicatorResult {
+      value: (variance * self.annualization).sqrt(),
+      signals: Vec::new(),
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.terms.len() >= self.period
+  }
+
+  fn reset(&mut self) {
+    self.terms.clear();
+  }
+}
+
+// 두 심볼 수익률 간 롤링 상관계수 (피어슨)
+#[derive(Debug)]
+pub struct RollingCorrelation {
+  name: String,
+  period: usize,
+  symbol_a: String,
+  symbol_b: String,
+  pending_a: Option<f64>,
+  pending_b: Option<f64>,
+  prev_a: Option<f64>,
+  prev_b: Option<f64>,
+  returns: VecDeque<(f64, f64)>,
+}
+
+impl RollingCorrelation {
+  pub fn new(symbol_a: impl Into<String>, symbol_b: impl Into<String>, period: usize) -> Self {
+    let symbol_a = symbol_a.into();
+    let symbol_b = symbol_b.into();
+    // 수익률이 두 개 이상이어야 분산이 정의됨
+    let period = period.max(2);
+    RollingCorrelation {
+      name: format!("CORR-{}-{}-{}", symbol_a, symbol_b, period),
+      period,
+      symbol_a,
+      symbol_b,
+      pending_a: None,
+      pending_b: None,
+      prev_a: None,
+      prev_b: None,
+      returns: VecDeque::with_capacity(period),
+    }
+  }
+
+  // 같은 시점의 두 가격을 함께 반영
+  pub fn update_pair(&mut self, price_a: f64, price_b: f64) -> Result<(), TradingError> {
+    if price_a <= 0.0 || price_b <= 0.0 {
+      return Err(TradingError::InvalidParameter("Price must be positive".to_string()));
+    }
+
+    if let (Some(pa), Some(pb)) = (self.prev_a, self.prev_b) {
+      self.returns.push_back(((price_a / pa).ln(), (price_b / pb).ln()));
+      if self.returns.len() > self.period {
+        self.returns.pop_front();
+      }
+    }
+    self.prev_a = Some(price_a);
+    self.prev_b = Some(price_b);
+
+    Ok(())
+  }
+
+  // 심볼별 스트림에서 들어오는 데이터 반영 - 양쪽 모두 새 값이 있을 때 한 쌍으로 기록
+  pub fn update_market_data(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if market_data.symbol == self.symbol_a {
+      self.pending_a = Some(market_data.close_f64());
+    } else if market_data.symbol == self.symbol_b {
+      self.pending_b = Some(market_data.close_f64());
+    } else {
+      return Ok(());
+    }
+
+    if let (Some(a), Some(b)) = (self.pending_a, self.pending_b) {
+      self.pending_a = None;
+      self.pending_b = None;
+      self.update_pair(a, b)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl Indicator for RollingCorrelation {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  // 단일 가격 스트림으로는 상관계수를 계산할 수 없음
+  fn update(&mut self, _price: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    Err(TradingError::InvalidParameter(
+      "RollingCorrelation requires update_pair or update_market_data".to_string()
+    ))
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    if !self.is_ready() {
+      return Err(TradingError::InsufficientData);
+    }
+
+    let n = self.returns.len() as f64;
+    let mean_a = self.returns.iter().map(|(a, _)| a).sum::<f64>() / n;
+    let mean_b = self.returns.iter().map(|(_, b)| b).sum::<f64>() / n;
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for (a, b) in &self.returns {
+      cov += (a - mean_a) * (b - mean_b);
+      var_a += (a - mean_a).powi(2);
+      var_b += (b - mean_b).powi(2);
+    }
+
+    if var_a == 0.0 || var_b == 0.0 {
+      return Err(TradingError::CalculationError("Zero variance in correlation window".to_string()));
+    }
+
+    Ok(IndicatorResult {
+      value: cov / (var_a.sqrt() * var_b.sqrt()),
+      signals: Vec::new(),
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.returns.len() >= self.period
+  }
+
+  fn reset(&mut self) {
+    self.pending_a = None;
+    self.pending_b = None;
+    self.prev_a = None;
+    self.prev_b = None;
+    self.returns.clear();
+  }
+}
+
+// ATR(Average True Range): 고가/저가/전일 종가 기반 True Range 의 Wilder 평활 평균
+#[derive(Debug)]
+pub struct AverageTrueRange {
+  name: String,
+  period: usize,
+  prev_close: Option<f64>,
+  // 초기 평균 계산용 True Range
+  warmup: Vec<f64>,
+  atr: Option<f64>,
+}
+
+impl AverageTrueRange {
+  pub fn new(period: usize) -> Self {
+    AverageTrueRange {
+      name: format!("ATR-{}", period),
+      period: period.max(1),
+      prev_close: None,
+      warmup: Vec::with_capacity(period),
+      atr: None,
+    }
+  }
+
+  // 현재 ATR (준비되지 않았으면 None)
+  pub fn value(&self) -> Option<f64> {
+    self.atr
+  }
+}
+
+impl Indicator for AverageTrueRange {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  // 종가만 주어지면 True Range 는 종가 간 변화폭
+  fn update(&mut self, price: f64, volume: Option<f64>) -> Result<(), TradingError> {
+    self.update_ohlc(price, price, price, price, volume)
+  }
+
+  fn update_ohlc(&mut self, _open: f64, high: f64, low: f64, close: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if low <= 0.0 || high < low || close < low || close > high {
+      return Err(TradingError::InvalidParameter("Invalid high/low/close values".to_string()));
+    }
+
+    let true_range = match self.prev_close {
+      Some(prev) => (high - low).max((high - prev).abs()).max((low - prev).abs()),
+      None => high - low,
+    };
+    self.prev_close = Some(close);
+
+    self.atr = match self.atr {
+      Some(atr) => Some((atr * (self.period - 1) as f64 + true_range) / self.period as f64),
+      None => {
+        self.warmup.push(true_range);
+        if self.warmup.len() >= self.period {
+          let initial = self.warmup.iter().sum::<f64>() / self.period as f64;
+          self.warmup.clear();
+          Some(initial)
+        } else {
+          None
+        }
+      }
+    };
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    let atr = self.atr.ok_or(TradingError::InsufficientData)?;
+    Ok(IndicatorResult {
+      value: atr,
+      signals: Vec::new(),
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.atr.is_some()
+  }
+
+  fn reset(&mut self) {
+    self.prev_close = None;
+    self.warmup.clear();
+    self.atr = None;
+  }
+}
+
+// 볼린저 밴드: 중심선 = SMA(period), 상/하단 = 중심선 ± k·표준편차
+// 밴드 폭이 최근 squeeze_lookback 구간 최저 수준이면 스퀴즈, 스퀴즈 직후 밴드 이탈은 돌파 신호
+#[derive(Debug)]
+pub struct BollingerBands {
+  name: String,
+  period: usize,
+  std_dev_multiplier: f64,
+  squeeze_lookback: usize,
+  prices: VecDeque<f64>,
+  // 최근 밴드 폭(bandwidth) 이력
+  bandwidths: VecDeque<f64>,
+  last_price: Option<f64>,
+  // 직전 캔들까지 스퀴즈 상태였는지
+  was_squeezed: bool,
+  squeezed: bool,
+}
+
+impl BollingerBands {
+  pub fn new(period: usize, std_dev_multiplier: f64) -> Self {
+    BollingerBands {
+      name: format!("BB-{}-{}", period, std_dev_multiplier),
+      period,
+      std_dev_multiplier,
+      squeeze_lookback: period * 6,
+      prices: VecDeque::with_capacity(period),
+      bandwidths: VecDeque::with_capacity(period * 6),
+      last_price: None,
+      was_squeezed: false,
+      squeezed: false,
+    }
+  }
+
+  // 스퀴즈 판정에 사용할 밴드 폭 이력 길이 (기본 period × 6)
+  pub fn with_squeeze_lookback(mut self, lookback: usize) -> Self {
+    self.squeeze_lookback = lookback.max(2);
+    self
+  }
+
+  // (상단, 중심선, 하단)
+  pub fn bands(&self) -> Option<(f64, f64, f64)> {
+    if self.prices.len() < self.period || self.period == 0 {
+      return None;
+    }
+
+    let n = self.prices.len() as f64;
+    let middle = self.prices.iter().sum::<f64>() / n;
+    let variance = self.prices.iter().map(|p| (p - middle).powi(2)).sum::<f64>() / n;
+    let offset = self.std_dev_multiplier * variance.sqrt();
+    Some((middle + offset, middle, middle - offset))
+  }
+
+  // 밴드 폭 = (상단 - 하단) / 중심선
+  pub fn bandwidth(&self) -> Option<f64> {
+    self.bands()
+      .filter(|(_, middle, _)| *middle != 0.0)
+      .map(|(upper, middle, lower)| (upper - lower) / middle)
+  }
+
+  // %B = (가격 - 하단) / (상단 - 하단)
+  pub fn percent_b(&self) -> Option<f64> {
+    let (upper, _, lower) = self.bands()?;
+    let price = self.last_price?;
+    if upper == lower {
+      return Some(0.5);
+    }
+    Some((price - lower) / (upper - lower))
+  }
+
+  pub fn is_squeezed(&self) -> bool {
+    self.squeezed
+  }
+}
+
+impl Indicator for BollingerBands {
+  fn name(&self) -> &str {
+    &self.name
+  }
+
+  fn update(&mut self, price: f64, _volume: Option<f64>) -> Result<(), TradingError> {
+    if price <= 0.0 {
+      return Err(TradingError::InvalidParameter("Price must be positive".to_string()));
+    }
+
+    self.prices.push_back(price);
+    if self.prices.len() > self.period {
+      self.prices.pop_front();
+    }
+    self.last_price = Some(price);
+
+    self.was_squeezed = self.squeezed;
+    if let Some(bandwidth) = self.bandwidth() {
+      // 이력이 충분히 쌓인 뒤에만 스퀴즈 판정
+      self.squeezed = self.bandwidths.len() + 1 >= self.squeeze_lookback
+        && self.bandwidths.iter().all(|b| bandwidth <= *b);
+      self.bandwidths.push_back(bandwidth);
+      if self.bandwidths.len() > self.squeeze_lookback {
+        self.bandwidths.pop_front();
+      }
+    }
+
+    Ok(())
+  }
+
+  fn calculate(&self) -> Result<IndicatorResult, TradingError> {
+    let (Some((upper, middle, lower)), Some(price)) = (self.bands(), self.last_price) else {
+      return Err(TradingError::InsufficientData);
+    };
+
+    let mut signals = Vec::new();
+    let width = (upper - lower).max(f64::EPSILON);
+
+    if self.was_squeezed && price > upper {
+      // 스퀴즈 이후 상단 돌파: 추세 추종 매수
+      signals.push(IndicatorSignal {
+        name: "BB Breakout Up".to_string(),
+        strength: (0.6 + (price - upper) / width).min(1.0),
+        message: format!("Price {:.2} broke above upper band {:.2} after squeeze", price, upper),
+      });
+    } else if self.was_squeezed && price < lower {
+      signals.push(IndicatorSignal {
+        name: "BB Breakout Down".to_string(),
+        strength: -(0.6 + (lower - price) / width).min(1.0),
+        message: format!("Price {:.2} broke below lower band {:.2} after squeeze", price, lower),
+      });
+    } else if price >= upper {
+      // 스퀴즈 없는 밴드 터치: 평균 회귀
+      signals.push(IndicatorSignal {
+        name: "BB Upper Touch".to_string(),
+        strength: -(0.3 + (price - upper) / width).min(0.8),
+        message: format!("Price {:.2} touched upper band {:.2}", price, upper),
+      });
+    } else if price <= lower {
+      signals.push(IndicatorSignal {
+        name: "BB Lower Touch".to_string(),
+        strength: (0.3 + (lower - price) / width).min(0.8),
+        message: format!("Price {:.2} touched lower band {:.2}", price, lower),
+      });
+    }
+
+    if self.squeezed {
+      // 방향 정보가 없는 상태 알림
+      signals.push(IndicatorSignal {
+        name: "BB Squeeze".to_string(),
+        strength: 0.0,
+        message: format!("Bandwidth {:.4} at {}-bar low", width / middle, self.squeeze_lookback),
+      });
+    }
+
+    Ok(IndicatorResult {
+      value: middle,
+      signals,
+    })
+  }
+
+  fn is_ready(&self) -> bool {
+    self.prices.len() >= self.period
+  }
+
+  fn reset(&mut self) {
+    self.prices.clear();
+    self.bandwidths.clear();
+    self.last_price = None;
+    self.was_squeezed = false;
+    self.squeezed = false;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+  }
+
+  // (open, high, low, close)
+  const BARS: [(f64, f64, f64, f64); 4] = [
+    (100.0, 102.0, 99.0, 101.0),
+    (101.0, 104.0, 100.0, 103.0),
+    (103.0, 103.5, 101.0, 102.0),
+    (102.0, 106.0, 101.5, 105.0),
+  ];
+
+  #[test]
+  fn test_ewma_volatility_known_values() {
+    let mut ewma = EwmaVolatility::new(0.94, 252.0);
+    assert!(matches!(ewma.calculate(), Err(TradingError::InsufficientData)));
+
+    // 수익률 10개가 쌓여야 준비됨
+    let prices = [100.0, 101.0, 99.5, 102.0, 103.0, 101.0, 100.0, 104.0, 105.0, 103.0, 106.0];
+    for price in &prices[..10] {
+      ewma.update(*price, None).unwrap();
+    }
+    assert!(!ewma.is_ready());
+    ewma.update(prices[10], None).unwrap();
+    assert_close(ewma.calculate().unwrap().value, 0.259011668449171);
+
+    assert!(ewma.update(0.0, None).is_err());
+    ewma.reset();
+    for _ in 0..11 {
+      ewma.update(100.0, None).unwrap();
+    }
+    assert_close(ewma.calculate().unwrap().value, 0.0);
+  }
+
+  #[test]
+  fn test_range_volatility_known_values() {
+    let mut parkinson = ParkinsonVolatility::new(3, 1.0);
+    let mut garman_klass = GarmanKlassVolatility::new(3, 1.0);
+    for (open, high, low, close) in &BARS[..3] {
+      parkinson.update_ohlc(*open, *high, *low, *close, None).unwrap();
+      garman_klass.update_ohlc(*open, *high, *low, *close, None).unwrap();
+    }
+    assert_close(parkinson.calculate().unwrap().value, 0.019077690543114);
+    assert_close(garman_klass.calculate().unwrap().value, 0.020737385578759);
+
+    // 창을 넘으면 가장 오래된 봉이 빠짐
+    let (open, high, low, close) = BARS[3];
+    parkinson.update_ohlc(open, high, low, close, None).unwrap();
+    garman_klass.update_ohlc(open, high, low, close, None).unwrap();
+    assert_close(parkinson.calculate().unwrap().value, 0.021978599597683);
+    assert_close(garman_klass.calculate().unwrap().value, 0.022353933825638);
+
+    assert!(parkinson.update_ohlc(100.0, 99.0, 101.0, 100.0, None).is_err());
+    assert!(garman_klass.update_ohlc(0.0, 101.0, 99.0, 100.0, None).is_err());
+  }
+
+  #[test]
+  fn test_range_volatility_short_and_zero_windows() {
+    // 창이 차기 전에는 계산하지 않음
+    let mut parkinson = ParkinsonVolatility::new(3, 1.0);
+    parkinson.update_ohlc(100.0, 102.0, 99.0, 101.0, None).unwrap();
+    assert!(matches!(parkinson.calculate(), Err(TradingError::InsufficientData)));
+
+    // 길이 0 은 1 로 취급 (빈 창의 0/0 방지) - 마지막 봉만으로 계산
+    let mut parkinson = ParkinsonVolatility::new(0, 1.0);
+    let mut garman_klass = GarmanKlassVolatility::new(0, 1.0);
+    assert!(!parkinson.is_ready() && !garman_klass.is_ready());
+    for (open, high, low, close) in &BARS[..2] {
+      parkinson.update_ohlc(*open, *high, *low, *close, None).unwrap();
+      garman_klass.update_ohlc(*open, *high, *low, *close, None).unwrap();
+    }
+    assert_close(parkinson.calculate().unwrap().value, (104.0f64 / 100.0).ln() / (4.0 * std::f64::consts::LN_2).sqrt());
+    let term = 0.5 * (104.0f64 / 100.0).ln().powi(2) - (2.0 * std::f64::consts::LN_2 - 1.0) * (103.0f64 / 101.0).ln().powi(2);
+    assert_close(garman_klass.calculate().unwrap().value, term.sqrt());
+
+    // 종가만 주면 범위가 0
+    let mut flat = GarmanKlassVolatility::new(1, 1.0);
+    flat.update(100.0, None).unwrap();
+    assert_close(flat.calculate().unwrap().value, 0.0);
+  }
+
+  #[test]
+  fn test_rolling_correlation_known_values() {
+    let a = [100.0, 101.0, 103.0, 102.0, 105.0, 107.0];
+    let b = [50.0, 50.6, 51.2, 51.3, 52.5, 53.1];
+    let mut correlation = RollingCorrelation::new("A", "B", 5);
+    assert!(correlation.update(100.0, None).is_err());
+    for (price_a, price_b) in a.iter().zip(&b) {
+      correlation.update_pair(*price_a, *price_b).unwrap();
+    }
+    assert_close(correlation.calculate().unwrap().value, 0.912738888212220);
+
+    // 같은 수익률은 1, 반대 수익률은 -1 (심볼별 스트림은 양쪽이 모두 도착하면 한 쌍으로 기록)
+    let md = |symbol: &str, close: f64| {
+      let close = crate::models::decimal::to_decimal(close);
+      MarketData { symbol: symbol.into(), timestamp: 0.into(), open: close, high: close, low: close, close, volume: close }
+    };
+    let mut same = RollingCorrelation::new("A", "B", 3);
+    let mut opposite = RollingCorrelation::new("A", "B", 3);
+    for price in [100.0, 102.0, 101.0, 104.0] {
+      for data in [md("A", price), md("C", 1.0), md("B", price * 2.0)] {
+        same.update_market_data(&data).unwrap();
+      }
+      opposite.update_pair(price, 10_000.0 / price).unwrap();
+    }
+    assert_close(same.calculate().unwrap().value, 1.0);
+    assert_close(opposite.calculate().unwrap().value, -1.0);
+  }
+
+  #[test]
+  fn test_rolling_correlation_short_and_degenerate_windows() {
+    // 길이 0/1 은 2 로 취급 - 수익률 하나로는 준비되지 않음
+    let mut correlation = RollingCorrelation::new("A", "B", 0);
+    correlation.update_pair(100.0, 50.0).unwrap();
+    correlation.update_pair(101.0, 51.0).unwrap();
+    assert!(matches!(correlation.calculate(), Err(TradingError::InsufficientData)));
+    correlation.update_pair(103.0, 51.5).unwrap();
+    assert!(correlation.calculate().unwrap().value.abs() <= 1.0);
+
+    // 한쪽 가격이 변하지 않으면 상관계수를 정의할 수 없음
+    let mut flat = RollingCorrelation::new("A", "B", 2);
+    for price in [100.0, 101.0, 103.0] {
+      flat.update_pair(price, 50.0).unwrap();
+    }
+    assert!(matches!(flat.calculate(), Err(TradingError::CalculationError(_))));
+    assert!(flat.update_pair(0.0, 50.0).is_err());
+  }
+
+  #[test]
+  fn test_atr_matches_reference_series() {
+    // (high, low, close) - 9번째 봉은 전일 종가 대비 갭으로 True Range 가 고가-저가보다 큼
+    let bars = [
+      (48.70, 47.79, 48.16), (48.72, 48.14, 48.61), (48.90, 48.39, 48.75), (48.87, 48.37, 48.63), (48.82, 48.24, 48.74),
+      (49.05, 48.64, 48.64), (49.20, 48.94, 49.03), (49.35, 48.86, 49.07), (49.92, 49.30, 49.82), (50.19, 49.87, 49.91),
+    ];
+    // 첫 5봉 True Range 의 단순 평균 이후 Wilder 평활
+    let expected = [None, None, None, None, Some(0.616), Some(0.5748), Some(0.57184), Some(0.555472), Some(0.6143776), Some(0.56550208)];
+    let mut atr = AverageTrueRange::new(5);
+    for ((high, low, close), expected) in bars.iter().zip(expected) {
+      atr.update_ohlc(*close, *high, *low, *close, None).unwrap();
+      assert_eq!(atr.is_ready(), expected.is_some());
+      if let Some(expected) = expected {
+        assert_close(atr.calculate().unwrap().value, expected);
+      }
+    }
+
+    assert!(atr.update_ohlc(50.0, 50.5, 49.5, 51.0, None).is_err());
+    atr.reset();
+    assert!(matches!(atr.calculate(), Err(TradingError::InsufficientData)));
+  }
+
+  #[test]
+  fn test_atr_short_windows() {
+    // 길이 0 은 1 로 취급 - 매 봉의 True Range
+    let mut atr = AverageTrueRange::new(0);
+    atr.update_ohlc(100.0, 102.0, 99.0, 101.0, None).unwrap();
+    assert_close(atr.value().unwrap(), 3.0);
+    atr.update_ohlc(101.0, 104.0, 103.0, 103.5, None).unwrap();
+    assert_close(atr.value().unwrap(), 3.0);
+
+    // 종가만 주면 종가 간 변화폭 (첫 봉은 0) - (0 + 3) / 2 이후 (1.5 + 2) / 2
+    let mut atr = AverageTrueRange::new(2);
+    for price in [100.0, 103.0, 101.0] {
+      atr.update(price, None).unwrap();
+    }
+    assert_close(atr.value().unwrap(), 1.75);
+  }
+}
@@ -16,6 +16,9 @@ pub mod utils;
 pub mod trading_bots;
 // Python 예측 시스템 통신 모듈
 pub mod prediction_client;
+// 외부 연동 웹훅
+pub mod webhooks;
+pub mod notifications;
 // TA 모듈 공개
 pub mod indicators;
 pub mod signals;
@@ -7,6 +7,7 @@
 
 mod api;
 mod backtest;
+mod cli;
 mod config;
 mod prediction_client;
 mod core;
@@ -22,35 +23,85 @@ mod utils;
 mod indicators;
 mod signals;
 mod trading_bots;
+mod webhooks;
+mod notifications;
 
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 // use warp::Filter; // Warp 제거 예정: Axum 단일화
 use chrono::{Utc, Duration};
+use clap::Parser;
 
 // use crate::api::routes; // Warp 라우트 사용 중지
-use crate::backtest::scenario::BacktestScenarioBuilder;
+use crate::backtest::scenario::{BacktestScenarioBuilder, ScenarioFile, ScenarioFormat};
+use crate::backtest::optimizer::{ParamAxis, ParameterSweep, SearchMethod, SweepMetric, WalkForwardConfig, WalkForwardOptimizer};
+use crate::backtest::data_provider::ExchangeDataDownloader;
+use crate::backtest::report::ReportFormat;
+use crate::backtest::monte_carlo::{MonteCarloConfig, ResampleMethod};
+use crate::backtest::synthetic::{self, SyntheticDataConfig, SyntheticDataGenerator};
+use crate::backtest::BacktestJobManager;
 use crate::http::{build_router, AppState};
-use crate::config::Config;
+use crate::cli::{ApiClient, BacktestArgs, BacktestCommand, Cli, Command, DataCommand, DownloadArgs, GenerateArgs, OptimizeArgs, OrdersCommand, SweepArgs};
+use crate::config::{Config, ExchangeKind, ExchangeMode, CONFIG_PATH};
 use crate::exchange::mocks::MockExchange;
+use crate::exchange::paper::PaperExchange;
+use crate::exchange::registry::ExchangeRegistry;
+use crate::market_data::aggregator::CandleAggregationService;
+use crate::market_data::external::{self, ExternalSeriesStore};
+use crate::market_data::backfill;
+use crate::market_data::recorder::{self, MarketDataRecorder};
+use crate::market_data::funding::{self, FundingStore};
 use crate::market_data::provider::MarketDataManager;
 use crate::market_data::stream::MarketDataStream;
+use crate::market_data::fix::FixProvider;
 use crate::market_data::websocket::WebSocketProvider;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::binance_user_stream::{BinanceUserDataStream, UserDataEvent};
+use crate::order_core::bracket::BracketOrder;
+use crate::order_core::events::OrderEvent;
 use crate::order_core::manager::OrderManager;
-use crate::order_core::repository::InMemoryOrderRepository;
+use crate::order_core::position_router::PositionRouter;
+use crate::order_core::reconcile::{self, FileFormat, OrderSnapshot};
+use crate::order_core::file_repository::FileOrderRepository;
+use crate::order_core::sql_repository::SqlOrderRepository;
+use crate::order_core::repository::{InMemoryOrderRepository, OrderRepository};
+use crate::order_core::routing::OrderRouter;
+use crate::order_core::submission_queue::{SubmissionPriority, SubmissionQueue};
+use crate::order_core::tax_lots::{LotMethod, TaxLotLedger};
+use crate::order_core::validator::CapabilityOrderValidator;
 use crate::strategies::vwap::VwapStrategy;
 use crate::utils::logging;
-use crate::models::order::OrderSide;
+use crate::models::decimal::{dec, to_f64, Decimal};
+use crate::models::order::{OrderId, OrderSide, OrderType};
+use crate::models::timestamp::Timestamp;
 // 새로 추가된 TA 관련 임포트
 use crate::strategies::technical::TechnicalStrategy;
-use crate::strategies::combined::CombinedStrategy;
+use crate::strategies::pairs::PairsTradingStrategy;
+use crate::core::dead_man_switch::{execute_safety_actions, DeadManSwitch};
+use crate::core::degraded_mode::{DegradedEvent, WriteAccessMonitor};
+use crate::core::oco_manager::OcoManager;
+use crate::core::portfolio::PortfolioTracker;
+use crate::core::daily_loss::DailyLossTracker;
+use crate::core::margin_monitor::MarginMonitor;
+use crate::core::risk_manager::{ExposureLimits, ExposureValidator, SharedExposureLimits};
+use crate::core::trade_journal::{OrderContext, TradeJournal};
+use crate::core::conditional_orders::{ConditionalOrderManager, JsonFileConditionalOrderStore};
+use crate::core::config_reload::ConfigReloader;
+use crate::core::live_feed::LiveFeed;
+use crate::core::shadow::ShadowBook;
+use crate::core::signal_dedup::{SignalDeduplicator, SignalKey};
 use crate::core::strategy_manager::StrategyManager;
+use crate::core::strategy_store::{JsonFileStrategyStateRepository, SqliteStrategyStateRepository, StrategyStateRepository};
 use crate::exchange::traits::Exchange;
-use crate::prediction_client::{PredictionClient, SignalRequest};
+use crate::prediction_client::{FeedbackBatch, FeedbackCollector, PredictionClient, SignalOutcome, SignalRequest};
+use crate::webhooks::{WebhookDispatcher, WebhookEventKind};
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
+  // 명령줄 인수 확인 (--help 는 설정 로드 전에 처리)
+  let cli = Cli::parse();
+  
   // 로깅 초기화
   logging::init()?;
   log::info!("자동 트레이딩 시스템 시작...");
@@ -59,13 +110,18 @@ async fn main() -> Result<(), anyhow::Error> {
   let config = Config::load()?;
   log::info!("설정 로드 완료");
   
-  // 명령줄 인수 확인
-  let args: Vec<String> = std::env::args().collect();
-  
-  if args.len() > 1 && args[1] == "backtest" {
-    run_backtest().await?;
-  } else {
-    run_live_trading(config).await?;
+  let api = ApiClient::new(&cli.api);
+  match cli.command.unwrap_or(Command::Run) {
+    Command::Run => run_live_trading(config).await?,
+    Command::Backtest(args) => run_backtest(args).await?,
+    Command::Download(args) => run_data_download(&config, args).await?,
+    Command::Data { command: DataCommand::Generate(args) } => run_data_generate(args)?,
+    Command::Data { command: DataCommand::Download(args) } => run_data_download(&config, args).await?,
+    Command::Orders { command: OrdersCommand::List } => cli::list_orders(&api).await?,
+    Command::Orders { command: OrdersCommand::Cancel { id } } => cli::cancel_order(&api, &id).await?,
+    Command::Orders { command } => run_orders_cli(&config, command)?,
+    Command::Positions => cli::list_positions(&api).await?,
+    Command::Strategies { command } => cli::run_strategies(&api, command).await?,
   }
   
   Ok(())
@@ -75,56 +131,226 @@ async fn run_live_trading(config: Config) -> Result<(), anyhow::Error> {
   // 시장 데이터 스트림 생성
   let market_stream = Arc::new(RwLock::new(MarketDataStream::new(1000)));
   
+  // 거래소 연결 텔레메트리 (REST 지연, WS 메시지 지연, 서버 시각 오차)
+  let telemetry = ExchangeTelemetry::new(config.telemetry.clone());
+  
   // WebSocket 제공자 생성
-  let ws_provider = Arc::new(RwLock::new(WebSocketProvider::new(
+  let mut ws_provider = WebSocketProvider::new(
     "wss://stream.binance.com:9443/ws",
     market_stream.clone(),
-  )));
+  ).with_telemetry(telemetry.clone());
+  if config.funding_data.enabled && config.funding_data.mark_price_stream {
+    ws_provider = ws_provider.with_mark_price();
+  }
+  let ws_provider = Arc::new(RwLock::new(ws_provider));
   
   // 시장 데이터 관리자 생성
   let mut market_manager = MarketDataManager::new();
   market_manager.add_provider(ws_provider.clone());
+  // FIX 시장 데이터 세션 (기관용 피드, WebSocket 과 같은 스트림에 발행)
+  if let Some(fix) = &config.fix_market_data {
+    log::info!("FIX market data session {}:{} ({} -> {})", fix.session.host, fix.session.port, fix.session.sender_comp_id, fix.session.target_comp_id);
+    market_manager.add_provider(Arc::new(RwLock::new(FixProvider::new(fix.clone(), market_stream.clone()))));
+  }
   
   // 모든 제공자 연결(실패해도 경고 후 지속)
   if let Err(e) = market_manager.connect_all().await {
     log::warn!("market providers connect failed: {} — running with mocks only", e);
   }
   
-  // 거래소 인스턴스 생성 (실거래/모의 선택)
-  let exchange: Arc<RwLock<dyn Exchange>> = if !config.exchange.use_mock {
-    let base = config.exchange.base_url.clone().unwrap_or("https://fapi.binance.com".to_string());
-    let key = config.exchange.api_key.clone().unwrap_or_default();
-    let sec = config.exchange.api_secret.clone().unwrap_or_default();
-    Arc::new(RwLock::new(crate::exchange::binance_futures::BinanceFuturesExchange::new(base, key, sec)))
-  } else {
-    Arc::new(RwLock::new(MockExchange::new(config.clone())))
-  };
-  log::info!("거래소 초기화 완료 (mock: {})", config.exchange.use_mock);
+  // 거래소 인스턴스 생성 (실거래/모의/페이퍼 선택)
+  let mode = config.exchange.mode();
+  let kind = config.exchange.kind;
+  let exchange = build_exchange(&config, &config.exchange, &telemetry, &mut market_manager, &market_stream).await;
+  log::info!("거래소 초기화 완료 (mode: {:?}, kind: {:?})", mode, kind);
+  // 추가 거래소 (주문/전략이 이름으로 선택, 나머지 구성 요소는 기본 거래소 사용)
+  let mut exchanges = ExchangeRegistry::new(config.exchange.name.clone(), exchange.clone());
+  // 자체 세션으로 체결을 받는 거래소(FIX)의 주문 업데이트
+  let mut pushed_updates: Vec<_> = exchange.read().await.order_updates().into_iter().collect();
+  for venue in &config.exchanges {
+    let venue_exchange = build_exchange(&config, venue, &telemetry, &mut market_manager, &market_stream).await;
+    pushed_updates.extend(venue_exchange.read().await.order_updates());
+    if venue.mode() == ExchangeMode::Live {
+      if let Err(e) = venue_exchange.write().await.sync_time().await { log::warn!("time sync failed ({}): {}", venue.name, e); }
+    }
+    exchanges.register(venue.name.clone(), venue_exchange)?;
+    log::info!("추가 거래소 등록: {} (mode: {:?}, kind: {:?})", venue.name, venue.mode(), venue.kind);
+  }
 
   // 선물 기본 설정(실거래 사용 시): 레버리지/포지션모드/마진모드 적용
-  if !config.exchange.use_mock {
+  if mode == ExchangeMode::Live {
     // 서버 시간 동기화
     {
       let mut ex = exchange.write().await;
       if let Err(e) = ex.sync_time().await { log::warn!("time sync failed: {}", e); }
     }
-    // 설정 기반 기본값 적용
-    let (symbols, lev, iso, hedge) = if let Some(f) = &config.futures {
-      (if f.symbols.is_empty() { vec!["BTCUSDT".into()] } else { f.symbols.clone() }, f.leverage, f.isolated, f.hedge)
-    } else { (vec!["BTCUSDT".into(), "ETHUSDT".into()], 20, false, false) };
-    if let Err(e) = init_futures_defaults(exchange.clone(), symbols, lev, iso, hedge).await {
-      log::warn!("futures defaults init failed: {}", e);
+    // 주기적 재동기화로 시각 오차/드리프트 감시
+    {
+      let ex = exchange.clone();
+      let telemetry = telemetry.clone();
+      let interval_ms = config.telemetry.clock_sync_interval_ms.max(1_000);
+      tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+        ticker.tick().await;
+        loop {
+          ticker.tick().await;
+          if let Err(e) = ex.write().await.sync_time().await {
+            log::warn!("time sync failed: {}", e);
+            continue;
+          }
+          let clock = telemetry.snapshot().clock;
+          if clock.offset_ms.is_some_and(|o| o.abs() > telemetry.config().clock_offset_warn_ms) {
+            log::warn!("exchange clock offset {:?}ms (drift {:?}ms/h)", clock.offset_ms, clock.drift_ms_per_hour);
+          }
+        }
+      });
+    }
+    // 설정 기반 기본값 적용 (현물은 레버리지/포지션 모드 없음)
+    if config.exchange.kind.is_futures() {
+      let (symbols, lev, iso, hedge) = if let Some(f) = &config.futures {
+        (if f.symbols.is_empty() { vec!["BTCUSDT".into()] } else { f.symbols.clone() }, f.leverage, f.isolated, f.hedge)
+      } else { (vec!["BTCUSDT".into(), "ETHUSDT".into()], 20, false, false) };
+      if let Err(e) = init_futures_defaults(exchange.clone(), symbols, lev, iso, hedge).await {
+        log::warn!("futures defaults init failed: {}", e);
+      }
     }
   }
   
-  // 주문 저장소 생성
-  let order_repo = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+  // 주문 저장소 생성 (SQL URL 또는 파일 저널이 설정되면 재시작 후에도 유지)
+  let order_repo: Arc<RwLock<dyn OrderRepository>> = match (&config.order_store_url, &config.order_store_path) {
+    (Some(url), _) => Arc::new(RwLock::new(SqlOrderRepository::connect(url).await?)),
+    (None, Some(path)) => Arc::new(RwLock::new(FileOrderRepository::open(path)?)),
+    (None, None) => Arc::new(RwLock::new(InMemoryOrderRepository::new())),
+  };
+  
+  // 주문 저장소 스냅샷 주기 저장 (orders export/import 에서 사용)
+  if let Some(path) = config.order_snapshot_path.clone() {
+    let repo = order_repo.clone();
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_secs(30));
+      loop {
+        ticker.tick().await;
+        let orders = match repo.read().await.find_all().await {
+          Ok(orders) => orders,
+          Err(e) => { log::warn!("order snapshot failed: {}", e); continue; }
+        };
+        // 가져오기로 추가된 체결 기록은 유지
+        let fills = OrderSnapshot::load(std::path::Path::new(&path)).map(|s| s.fills).unwrap_or_default();
+        if let Err(e) = OrderSnapshot::new(orders, fills).save(std::path::Path::new(&path)) {
+          log::warn!("order snapshot save failed: {}", e);
+        }
+      }
+    });
+  }
   
   // 주문 관리자 생성
   let order_manager = Arc::new(RwLock::new(OrderManager::new(
     exchange.clone(),
     order_repo.clone(),
   )));
+  order_manager.write().await.set_exchange_registry(exchanges);
+  for mut updates in pushed_updates {
+    let manager = order_manager.clone();
+    tokio::spawn(async move {
+      loop {
+        match updates.recv().await {
+          Ok(update) => {
+            if update.is_fill() {
+              log::info!("fill {} {:?} {} @ {} ({:?})", update.symbol, update.side, update.last_fill_quantity, update.last_fill_price, update.status);
+            }
+            if let Err(e) = manager.read().await.apply_order_update(&update).await {
+              log::warn!("order update {} apply failed: {}", update.order_id, e);
+            }
+          }
+          Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => log::warn!("order updates lagged: {} dropped (polling will catch up)", n),
+          Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+      }
+    });
+  }
+  // 거래소 기능 기반 사전 검증 (미지원 주문 유형/TIF)
+  {
+    let capabilities = exchange.read().await.capabilities();
+    order_manager.write().await.add_validator(Box::new(CapabilityOrderValidator::new(capabilities)));
+  }
+  
+  // 우선순위 제출 큐: 동시에 몰린 주문을 취소/축소 -> 실행 자식 -> 신규 진입 순으로 처리
+  order_manager.write().await.set_submission_queue(SubmissionQueue::new(config.submission_queue.clone()));
+  OrderManager::start_submission_worker(order_manager.clone());
+  
+  // 쓰기 권한 감시: 인증/권한 오류가 이어지면 읽기 전용 모드로 전환 (전략 계산은 계속)
+  let write_access = WriteAccessMonitor::new(config.degraded_mode.clone());
+  order_manager.write().await.set_write_access_monitor(write_access.clone());
+  
+  // 체결 기록: 모든 체결을 전략/신호/슬리피지와 함께 저장 (경로 없으면 메모리에만)
+  let journal = match config.trade_journal_path.clone() {
+    Some(path) => TradeJournal::open(path)?,
+    None => TradeJournal::new(),
+  };
+  order_manager.write().await.set_trade_journal(journal.clone());
+  journal.start(order_manager.clone());
+  
+  // 주문 라우팅 규칙 (규칙 파일은 수정 시 재로드)
+  let router = OrderRouter::from_config(&config.routing)?;
+  order_manager.write().await.set_router(router.clone());
+  if config.routing.rules_path.is_some() {
+    let interval_ms = config.routing.reload_interval_ms.max(100);
+    let router = router.clone();
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+      loop {
+        ticker.tick().await;
+        if let Err(e) = router.reload_if_changed() {
+          log::warn!("routing rules reload failed (keeping previous rules): {}", e);
+        }
+      }
+    });
+  }
+  // 포지션 인지 라우팅 (청산 주문 reduce-only / 헤지 모드 positionSide)
+  if config.position_routing.enabled {
+    let hedge = config.exchange.kind.is_futures() && config.futures.as_ref().is_some_and(|f| f.hedge);
+    let position_router = PositionRouter::new(&config.position_routing, hedge);
+    order_manager.write().await.set_position_router(position_router.clone());
+    let sync_interval = (config.position_routing.sync_interval_ms > 0).then(|| std::time::Duration::from_millis(config.position_routing.sync_interval_ms));
+    position_router.start(order_manager.clone(), exchange.clone(), sync_interval);
+    log::info!("position-aware routing enabled (hedge mode: {})", hedge);
+  }
+  
+  // 사용자 데이터 스트림 (체결/포지션 푸시) - 사용 시 REST 폴링은 안전망으로만 유지
+  let user_stream_config = &config.exchange.user_stream;
+  if user_stream_config.enabled && mode == ExchangeMode::Live && matches!(kind, ExchangeKind::Bybit | ExchangeKind::Okx | ExchangeKind::Kraken) {
+    log::warn!("user data stream is Binance only; {:?} orders are tracked by REST polling", kind);
+  } else if user_stream_config.enabled && mode == ExchangeMode::Live && kind != ExchangeKind::Fix {
+    let kind = config.exchange.kind;
+    let base = config.exchange.base_url.clone().unwrap_or(kind.default_base_url().to_string());
+    let ws_url = user_stream_config.ws_url.clone().unwrap_or(kind.default_stream_url().to_string());
+    let stream = BinanceUserDataStream::new(kind, base, ws_url, config.exchange.api_key.clone().unwrap_or_default())
+      .with_keepalive_interval(std::time::Duration::from_millis(user_stream_config.keepalive_interval_ms.max(60_000)))
+      .with_telemetry(telemetry.clone());
+    order_manager.write().await.set_poll_interval(std::time::Duration::from_millis(user_stream_config.fallback_poll_interval_ms.max(1_000)));
+    
+    let mut events = stream.subscribe();
+    let manager = order_manager.clone();
+    tokio::spawn(async move {
+      loop {
+        match events.recv().await {
+          Ok(UserDataEvent::Order(update)) => {
+            if update.is_fill() {
+              log::info!("fill {} {:?} {} @ {} ({:?})", update.symbol, update.side, update.last_fill_quantity, update.last_fill_price, update.status);
+            }
+            if let Err(e) = manager.read().await.apply_order_update(&update).await {
+              log::warn!("order update {} apply failed: {}", update.order_id, e);
+            }
+          }
+          Ok(UserDataEvent::Position(position)) => log::debug!("position update {} {} @ {}", position.symbol, position.quantity, position.entry_price),
+          Ok(_) => {}
+          Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => log::warn!("user data events lagged: {} dropped (REST polling will catch up)", n),
+          Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+      }
+    });
+    stream.start();
+  }
   
   // 주문 상태 감시 시작
   {
@@ -132,8 +358,63 @@ async fn run_live_trading(config: Config) -> Result<(), anyhow::Error> {
     manager.start_order_monitoring().await?;
   }
   
+  // OCO 브래킷 감시 (주문 이벤트 즉시 반영, 주문 상태 폴링 주기로 누락 보완)
+  let oco = OcoManager::new(order_manager.clone()).with_poll_interval(order_manager.read().await.poll_interval());
+  oco.start();
+  
+  // 조건부(트리거) 주문: 저장소가 설정되면 대기 중인 조건 복원 후 시세 감시
+  let conditional = match config.conditional_orders_path.clone() {
+    Some(path) => ConditionalOrderManager::new(order_manager.clone()).with_store(JsonFileConditionalOrderStore::new(path))?,
+    None => ConditionalOrderManager::new(order_manager.clone()),
+  };
+  conditional.start(exchange.clone(), std::time::Duration::from_secs(1));
+  
+  // 포트폴리오/손익 추적 (주문 체결 이벤트 + 시세 스트림)
+  // 거래일 단위 일일 손실 (체결 손익은 포트폴리오 추적기가 기록, 한도 도달 시 reduce-only 만 허용)
+  // 설정 리로드로 한도를 켤 수 있으므로 한도가 없어도 검증기 등록 (한도 없으면 모두 통과)
+  let daily_loss = DailyLossTracker::new(&config.daily_loss, chrono::Utc::now().timestamp_millis())?;
+  order_manager.write().await.add_validator(Box::new(daily_loss.clone()));
+  if config.daily_loss.max_daily_loss.is_some() {
+    log::info!("daily loss limit enabled: {:?}", config.daily_loss);
+  }
+  {
+    let daily_loss = daily_loss.clone();
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_secs(60));
+      loop {
+        ticker.tick().await;
+        daily_loss.roll(chrono::Utc::now().timestamp_millis());
+      }
+    });
+  }
+  let portfolio = PortfolioTracker::new(config.portfolio.clone()).with_daily_loss(daily_loss.clone());
+  portfolio.start(order_manager.clone(), exchange.clone(), market_stream.clone());
+  // 포트폴리오 노출 한도 (총 명목가 / 보유 심볼 수 / 상관 그룹) - 설정 리로드로 교체하므로 한도가 없어도 등록
+  let exposure_limits: SharedExposureLimits = Arc::new(std::sync::RwLock::new(ExposureLimits::new(config.risk_limits.clone())?));
+  order_manager.write().await.add_validator(Box::new(ExposureValidator::new(exposure_limits.clone(), portfolio.clone())));
+  if config.risk_limits.is_enabled() {
+    log::info!("exposure limits enabled: {:?}", config.risk_limits);
+  }
+  
+  // 아웃바운드 웹훅/알림 채널 발송기 및 포지션 변화, 커넥터 연결 상태 감시
+  let webhooks = WebhookDispatcher::spawn(config.webhooks.clone())
+    .with_notifier(notifications::Notifier::spawn(config.notifications.clone()));
+  webhooks::spawn_position_watcher(exchange.clone(), webhooks.clone(), std::time::Duration::from_secs(5));
+  webhooks::spawn_connection_watcher(&telemetry, webhooks.clone());
+  
+  // 외부 데이터 시계열 (설정된 소스 폴링 + /external 웹훅 수신)
+  let external = ExternalSeriesStore::from_config(&config.external_data);
+  external::spawn_pollers(external.clone(), config.external_data.sources.clone());
+  // 예측 서비스 신호 (예측 전략이 외부 시계열로 읽음)
+  prediction_client::spawn_signal_feeds(config.prediction_api.base_url.clone(), external.clone(), config.prediction_api.signal_feeds.clone());
+  
   // 전략 매니저 생성 (신규)
   let strategy_manager = Arc::new(RwLock::new(StrategyManager::new()));
+  strategy_manager.write().await.set_guard_config(config.strategy_guard.clone());
+  strategy_manager.write().await.set_allocation_config(config.allocation.clone());
+  strategy_manager.write().await.set_reporting_config(config.reporting.clone())?;
+  strategy_manager.write().await.set_watchdog_config(config.watchdog.clone());
+  strategy_manager.write().await.set_capabilities(exchange.read().await.capabilities());
   log::info!("전략 매니저 초기화 완료");
   
   // 예측 API 헬스체크 및 샘플 호출
@@ -146,32 +427,292 @@ async fn run_live_trading(config: Config) -> Result<(), anyhow::Error> {
     }
 
     // 샘플 시그널 1회 호출(실패해도 무시)
-    let sample_req = SignalRequest {
-      symbol: "BTC/USDT".to_string(),
-      timeframe: "1h".to_string(),
-      strategy: "trend_following".to_string(),
-      lookback: 100,
-    };
+    let sample_req = SignalRequest::new("BTC/USDT", "1h", "trend_following", 100);
     match pred.get_signals(sample_req).await {
       Ok(sig) => log::info!("Sample signal: {} (conf {:.2})", sig.signal, sig.confidence),
       Err(e) => log::warn!("Sample signal fetch failed: {}", e),
     }
   }
 
-  // 기본 기술적 분석 전략 추가 (예시)
-  setup_technical_strategies(strategy_manager.clone(), exchange.clone(), market_stream.clone()).await?;
-  log::info!("기술적 분석 전략 초기화 완료");
+  // 설정 기반 전략 자동 배포
+  // 전략 상태 저장소가 설정되면 이전 실행의 전략/진행 상태 복원
+  let state_store: Option<Box<dyn StrategyStateRepository>> = match (&config.strategy_state_db, &config.strategy_state_path) {
+    (Some(db), _) => Some(Box::new(SqliteStrategyStateRepository::open(db)?)),
+    (None, Some(path)) => Some(Box::new(JsonFileStrategyStateRepository::new(path))),
+    (None, None) => None,
+  };
+  if let Some(store) = state_store {
+    let mut manager = strategy_manager.write().await;
+    manager.set_state_store(store);
+    let restored = manager.restore_strategies()?;
+    log::info!("저장된 전략 {}개 복원", restored);
+  }
+  deploy_configured_strategies(strategy_manager.clone(), &config.strategies).await?;
+  log::info!("전략 배포 완료: {}개", config.strategies.iter().filter(|d| d.enabled).count());
+  
+  // 전략 워치독: 업데이트/주문 주기 이상 감지
+  {
+    let sm = strategy_manager.clone();
+    let hooks = webhooks.clone();
+    let interval = std::time::Duration::from_millis(config.watchdog.check_interval_ms.max(1000));
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(interval);
+      loop {
+        ticker.tick().await;
+        let alerts = sm.write().await.check_watchdog(chrono::Utc::now().timestamp_millis());
+        for alert in alerts {
+          hooks.emit(WebhookEventKind::StrategyAnomaly, serde_json::to_value(&alert).unwrap_or_default());
+        }
+      }
+    });
+  }
+  
+  // 전략 실행 시간대: 창이 열리고 닫힐 때 알림, 닫히면 설정에 따라 미체결 주문 취소
+  {
+    let sm = strategy_manager.clone();
+    let om = order_manager.clone();
+    let hooks = webhooks.clone();
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_secs(15));
+      loop {
+        ticker.tick().await;
+        let transitions = sm.write().await.check_schedules(chrono::Utc::now().timestamp_millis());
+        for transition in transitions {
+          log::info!("strategy {} schedule window {} ({})", transition.strategy, if transition.open { "opened" } else { "closed" }, transition.reason.as_deref().unwrap_or("active"));
+          if transition.cancel_orders {
+            if let Err(e) = om.read().await.cancel_strategy_orders(&transition.strategy).await {
+              log::warn!("strategy {} order cleanup on window close failed: {}", transition.strategy, e);
+            }
+          }
+          hooks.emit(WebhookEventKind::StrategyToggled, serde_json::json!({
+            "strategy": transition.strategy, "window_open": transition.open, "reason": transition.reason.unwrap_or_else(|| "schedule".to_string()),
+          }));
+        }
+      }
+    });
+  }
+  
+  // 데드맨 스위치: 하트비트가 끊기면 안전 조치 실행
+  let dead_man = Arc::new(RwLock::new(DeadManSwitch::new(config.dead_man_switch.clone(), chrono::Utc::now().timestamp_millis())));
+  if config.dead_man_switch.enabled {
+    let switch = dead_man.clone();
+    let sm = strategy_manager.clone();
+    let om = order_manager.clone();
+    let hooks = webhooks.clone();
+    let actions = config.dead_man_switch.actions.clone();
+    let interval = std::time::Duration::from_millis(config.dead_man_switch.check_interval_ms.max(1000));
+    log::info!("데드맨 스위치 활성화: {}ms 내 하트비트 필요", config.dead_man_switch.timeout_ms);
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(interval);
+      loop {
+        ticker.tick().await;
+        let now = chrono::Utc::now().timestamp_millis();
+        let status = {
+          let mut switch = switch.write().await;
+          if !switch.check(now) {
+            continue;
+          }
+          switch.status(now)
+        };
+        log::error!("heartbeat lost (last {:?}), executing safety actions {:?}", status.last_heartbeat, actions);
+        let report = execute_safety_actions(&actions, &om, &sm).await;
+        hooks.emit(WebhookEventKind::DeadManTriggered, serde_json::json!({"status": status, "report": report}));
+      }
+    });
+  }
+  
+  // 읽기 전용 모드 전환/복구 알림
+  {
+    let mut events = write_access.subscribe();
+    let hooks = webhooks.clone();
+    tokio::spawn(async move {
+      loop {
+        let event = match events.recv().await {
+          Ok(event) => event,
+          Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+          Err(_) => break,
+        };
+        let kind = match &event {
+          DegradedEvent::Entered { .. } => WebhookEventKind::DegradedModeEntered,
+          DegradedEvent::Recovered { .. } => WebhookEventKind::DegradedModeRecovered,
+        };
+        hooks.emit(kind, serde_json::json!(event));
+      }
+    });
+  }
+
+  // 선물 증거금 비율/청산 근접 감시 (위험 구간 자동 축소는 설정 시)
+  let margin = MarginMonitor::new(config.margin_monitor.clone());
+  if config.margin_monitor.enabled {
+    let mut events = margin.subscribe();
+    let hooks = webhooks.clone();
+    tokio::spawn(async move {
+      loop {
+        match events.recv().await {
+          Ok(event) => hooks.emit(WebhookEventKind::MarginAlert, serde_json::json!(event)),
+          Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+          Err(_) => break,
+        }
+      }
+    });
+    margin.start(exchange.clone(), order_manager.clone());
+    log::info!("margin monitor enabled: {:?}", config.margin_monitor);
+  }
+  
+  // 예측 서비스 피드백: 시그널별 실제 체결/손익을 주기적으로 /feedback 에 전송
+  let feedback = FeedbackCollector::new(config.prediction_api.feedback.clone());
+  if feedback.config().enabled {
+    let mut events = order_manager.read().await.subscribe_to_order_events();
+    let collector = feedback.clone();
+    tokio::spawn(async move {
+      loop {
+        match events.recv().await {
+          Ok(event) => collector.apply_order_update(&event.update),
+          Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+          Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+        }
+      }
+    });
+    
+    let collector = feedback.clone();
+    let client = PredictionClient::new(config.prediction_api.base_url.clone());
+    let interval_ms = config.prediction_api.feedback.interval_ms.max(1_000);
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+      loop {
+        ticker.tick().await;
+        let outcomes = collector.take_ready(chrono::Utc::now().timestamp_millis());
+        if outcomes.is_empty() {
+          continue;
+        }
+        let batch = FeedbackBatch { sent_at: chrono::Utc::now(), outcomes };
+        match client.send_feedback(&batch).await {
+          Ok(()) => log::debug!("feedback sent: {} outcomes", batch.outcomes.len()),
+          Err(e) => {
+            log::warn!("feedback send failed (will retry): {}", e);
+            collector.requeue(batch.outcomes);
+          }
+        }
+      }
+    });
+  }
   
-  // 전략 실행 런타임 시작: 거래소 시세 폴링 → 전략 업데이트 → 주문 제출
+  // 전략 실행 런타임 시작: 시세 스트림 푸시(없으면 REST 폴링) → 전략 업데이트 → 주문 제출
+  // 모의 거래소는 자체 가상 시세를 쓰므로 실시간 스트림 대신 거래소 시세를 폴링
+  let runtime_symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+  let runtime_stream = if mode == ExchangeMode::Mock {
+    None
+  } else {
+    for symbol in &runtime_symbols {
+      if let Err(e) = market_manager.subscribe_all(symbol).await {
+        log::warn!("ticker subscribe {} failed, strategies poll REST: {}", symbol, e);
+      }
+    }
+    Some(market_stream.clone())
+  };
+  // 티커 스트림에서 주기별 완성 캔들 생성 (`MarketDataStream` 캔들 채널로 발행)
+  // 설정 타임프레임 + 타임프레임 지정 전략이 사용하는 타임프레임
+  let strategy_timeframes = strategy_manager.read().await.timeframes();
+  let mut candle_timeframes = config.candle_aggregation.timeframes.clone();
+  candle_timeframes.extend(strategy_timeframes.iter().filter(|tf| !config.candle_aggregation.timeframes.contains(tf)).cloned());
+  let mut candle_service = CandleAggregationService::new(market_stream.clone());
+  if runtime_stream.is_none() && !strategy_timeframes.is_empty() {
+    log::warn!("no ticker stream in mock mode: strategies on {:?} candles will not be updated", strategy_timeframes);
+  }
+  if runtime_stream.is_some() && !candle_timeframes.is_empty() {
+    // 재연결 등으로 빠진 캔들은 REST 로 채운 뒤 실시간 캔들 재개 (지표 상태 보존)
+    if config.candle_aggregation.backfill {
+      backfill::spawn_backfill(market_stream.clone(), exchange.clone(), config.candle_aggregation.max_backfill_candles).await;
+    }
+    for symbol in &runtime_symbols {
+      if let Err(e) = candle_service.start(symbol, &candle_timeframes).await {
+        log::warn!("candle aggregation {} not started: {}", symbol, e);
+      }
+    }
+  }
+  // 실시간 캔들/틱을 일자별 CSV 로 기록 (백테스트 데이터셋, `RecordedDataProvider` 로 조회)
+  if config.market_data_recorder.enabled {
+    match MarketDataRecorder::new(config.market_data_recorder.clone()) {
+      Ok(recorder) => {
+        log::info!("recording market data for {:?} to {}", runtime_symbols, config.market_data_recorder.dir);
+        recorder::spawn_recorder(recorder, market_stream.clone(), &runtime_symbols, &candle_timeframes).await;
+      }
+      Err(e) => log::error!("market data recorder not started: {}", e),
+    }
+  }
+  // 펀딩비/미결제약정 스냅샷 (전략에는 외부 시계열로 전달, GET /market/:symbol/funding)
+  let funding_store = FundingStore::new(config.funding_data.history_limit).with_external(external.clone());
+  if config.funding_data.enabled {
+    let symbols = if config.funding_data.symbols.is_empty() { runtime_symbols.clone() } else { config.funding_data.symbols.clone() };
+    log::info!("funding/open interest collection for {:?} every {}ms", symbols, config.funding_data.interval_ms);
+    funding::spawn_collector(exchange.clone(), Some(market_stream.clone()), funding_store.clone(), symbols, config.funding_data.clone());
+  }
+  // 섀도 모드 전략의 가상 체결 장부 (수수료/슬리피지는 거래소 체결 모델 설정)
+  let shadow = ShadowBook::new(config.exchange.fill_model.clone());
+  // 같은 전략의 같은 신호가 쿨다운 안에 반복되면 한 번만 제출 (버린 건수는 /metrics)
+  let signal_dedup = SignalDeduplicator::new(config.strategy_runtime.dedup_cooldown_ms);
+  // 설정 리로드로 새 심볼 전략이 배포되면 시세 구독 + 캔들 집계 후 전략 런타임에 심볼 추가
+  let streaming = runtime_stream.is_some();
+  let (runtime_symbol_tx, runtime_symbol_rx) = tokio::sync::mpsc::unbounded_channel();
+  let (reload_symbol_tx, mut reload_symbol_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+  {
+    let candle_timeframes = candle_timeframes.clone();
+    tokio::spawn(async move {
+      while let Some(symbol) = reload_symbol_rx.recv().await {
+        if streaming {
+          if let Err(e) = market_manager.subscribe_all(&symbol).await {
+            log::warn!("ticker subscribe {} failed, strategies poll REST: {}", symbol, e);
+          }
+          if !candle_timeframes.is_empty() {
+            if let Err(e) = candle_service.start(&symbol, &candle_timeframes).await {
+              log::warn!("candle aggregation {} not started: {}", symbol, e);
+            }
+          }
+        }
+        let _ = runtime_symbol_tx.send(symbol);
+      }
+    });
+  }
   start_strategy_runtime(
     strategy_manager.clone(),
     order_manager.clone(),
     exchange.clone(),
-    vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+    runtime_stream,
+    runtime_symbols.clone(),
+    config.strategy_runtime.clone(),
+    webhooks.clone(),
+    feedback,
+    external.clone(),
+    shadow.clone(),
+    oco.clone(),
+    signal_dedup.clone(),
+    runtime_symbol_rx,
   );
   
+  // 설정 핫 리로드: 파일 수정 감시 + POST /config/reload
+  let config_reload = ConfigReloader::new(CONFIG_PATH, config.clone(), strategy_manager.clone(), order_manager.clone(), exchange.clone(), router, runtime_symbols)
+    .with_symbol_feed(reload_symbol_tx)
+    .with_exposure_limits(exposure_limits)
+    .with_daily_loss(daily_loss.clone());
+  if config.hot_reload.enabled && std::path::Path::new(CONFIG_PATH).exists() {
+    let reloader = config_reload.clone();
+    let interval_ms = config.hot_reload.interval_ms.max(500);
+    tokio::spawn(async move {
+      let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+      loop {
+        ticker.tick().await;
+        if let Err(e) = reloader.reload_if_changed().await {
+          log::warn!("config reload failed (keeping previous config): {}", e);
+        }
+      }
+    });
+  }
+  
+  // WebSocket 클라이언트가 공유하는 실시간 피드 (거래소 조회는 클라이언트 수와 무관하게 한 번)
+  let live_feed = LiveFeed::start(exchange.clone(), market_stream.clone(), order_manager.clone(), strategy_manager.clone());
+  
   // Axum 서버 시작
-  let axum_state = AppState { exchange: exchange.clone(), strategy_manager: strategy_manager.clone() };
+  let axum_state = AppState { exchange: exchange.clone(), strategy_manager: strategy_manager.clone(), webhooks: webhooks.clone(), telemetry: telemetry.clone(), order_manager: order_manager.clone(), dead_man: dead_man.clone(), external: external.clone(), oco: oco.clone(), conditional: conditional.clone(), portfolio: portfolio.clone(), daily_loss: daily_loss.clone(), margin, funding: funding_store, journal: journal.clone(), config_reload, live_feed, backtests: BacktestJobManager::new(&config.backtest_jobs), shadow, signal_dedup };
   let axum_router = build_router(axum_state);
   let axum_addr = std::net::SocketAddr::from(([127,0,0,1], 4000));
   log::info!("Axum 서버 시작: http://127.0.0.1:4000/");
@@ -179,73 +720,172 @@ async fn run_live_trading(config: Config) -> Result<(), anyhow::Error> {
     let listener = tokio::net::TcpListener::bind(axum_addr).await.unwrap();
     axum::serve(listener, axum_router.into_make_service()).await.unwrap();
   });
-  let _ = tokio::join!(axum_task);
+  tokio::select! {
+    res = axum_task => {
+      if let Err(e) = res { log::error!("Axum 서버 종료: {}", e); }
+    }
+    _ = shutdown_signal() => log::info!("종료 신호 수신 - 정리 후 종료"),
+  }
+  
+  // 정리 단계가 거래소 응답을 기다리며 멈추지 않도록 제한 시간 적용
+  let cleanup = graceful_shutdown(strategy_manager, order_manager, order_repo, &config);
+  if tokio::time::timeout(std::time::Duration::from_millis(config.shutdown.timeout_ms), cleanup).await.is_err() {
+    log::warn!("종료 정리 시간 초과 ({}ms)", config.shutdown.timeout_ms);
+  }
   
   Ok(())
 }
 
-// TA 전략 설정 함수 (신규)
-async fn setup_technical_strategies(
+// SIGINT(Ctrl-C) 또는 SIGTERM 대기
+async fn shutdown_signal() {
+  #[cfg(unix)]
+  {
+    let mut term = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+      Ok(term) => term,
+      Err(e) => {
+        log::warn!("SIGTERM 핸들러 등록 실패: {}", e);
+        let _ = tokio::signal::ctrl_c().await;
+        return;
+      }
+    };
+    tokio::select! {
+      _ = tokio::signal::ctrl_c() => {}
+      _ = term.recv() => {}
+    }
+  }
+  #[cfg(not(unix))]
+  {
+    let _ = tokio::signal::ctrl_c().await;
+  }
+}
+
+// 종료 정리: 전략 정지(상태 저장) -> 미체결 주문 취소 -> 주문 스냅샷 저장
+async fn graceful_shutdown(
   strategy_manager: Arc<RwLock<StrategyManager>>,
-  exchange: Arc<RwLock<dyn Exchange>>,
-  market_stream: Arc<RwLock<MarketDataStream>>,
+  order_manager: Arc<RwLock<OrderManager>>,
+  order_repo: Arc<RwLock<dyn OrderRepository>>,
+  config: &Config,
+) {
+  let stopped = strategy_manager.write().await.shutdown();
+  log::info!("전략 정지: {}개", stopped.len());
+  
+  if config.shutdown.cancel_open_orders {
+    let (cancelled, errors) = order_manager.read().await.cancel_all_open_orders().await;
+    for e in errors {
+      log::warn!("shutdown cancel failed: {}", e);
+    }
+    log::info!("미체결 주문 취소: {}건", cancelled.len());
+  }
+  
+  if let Some(path) = &config.order_snapshot_path {
+    let path = std::path::Path::new(path);
+    match order_repo.read().await.find_all().await {
+      Ok(orders) => {
+        let fills = OrderSnapshot::load(path).map(|s| s.fills).unwrap_or_default();
+        if let Err(e) = OrderSnapshot::new(orders, fills).save(path) {
+          log::warn!("order snapshot save failed: {}", e);
+        }
+      }
+      Err(e) => log::warn!("order snapshot failed: {}", e),
+    }
+  }
+}
+
+// 거래소 설정 하나로 거래소 인스턴스 생성 (실거래/모의/페이퍼)
+// 모의/페이퍼 거래소는 전체 설정을 받으므로 `venue` 를 기본 거래소 자리에 넣은 설정으로 생성
+async fn build_exchange(
+  config: &Config,
+  venue: &crate::config::ExchangeConfig,
+  telemetry: &ExchangeTelemetry,
+  market_manager: &mut MarketDataManager,
+  market_stream: &Arc<RwLock<MarketDataStream>>,
+) -> Arc<RwLock<dyn Exchange>> {
+  let config = Config { exchange: venue.clone(), ..config.clone() };
+  let mode = config.exchange.mode();
+  let kind = config.exchange.kind;
+  let base = config.exchange.base_url.clone().unwrap_or(kind.default_base_url().to_string());
+  match mode {
+    ExchangeMode::Live => {
+      let key = config.exchange.api_key.clone().unwrap_or_default();
+      let sec = config.exchange.api_secret.clone().unwrap_or_default();
+      match kind {
+        ExchangeKind::BinanceFutures => Arc::new(RwLock::new(crate::exchange::binance_futures::BinanceFuturesExchange::new(base, key, sec).with_telemetry(telemetry.clone()))),
+        ExchangeKind::BinanceSpot => Arc::new(RwLock::new(crate::exchange::binance_spot::BinanceSpotExchange::new(base, key, sec).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Bybit => Arc::new(RwLock::new(crate::exchange::bybit::BybitExchange::new(base, key, sec).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Okx => {
+          let passphrase = config.exchange.api_passphrase.clone().unwrap_or_default();
+          Arc::new(RwLock::new(crate::exchange::okx::OkxExchange::new(base, key, sec, passphrase).with_telemetry(telemetry.clone())))
+        }
+        ExchangeKind::Kraken => Arc::new(RwLock::new(crate::exchange::kraken::KrakenExchange::new(base, key, sec).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Fix => {
+          let fix = config.exchange.fix.clone().expect("exchange.fix is checked by Config::validate");
+          let mut fix_exchange = crate::exchange::fix::FixExchange::new(fix).with_market_stream(market_stream.clone());
+          fix_exchange.connect();
+          Arc::new(RwLock::new(fix_exchange))
+        }
+      }
+    }
+    ExchangeMode::Mock => Arc::new(RwLock::new(MockExchange::new(config))),
+    ExchangeMode::Paper => {
+      // 공개 시세(캔들/호가/펀딩)만 사용하므로 API 키 없이 연결 (FIX 주문 세션은 시세가 없어 스트림 틱만 사용)
+      let market: Option<Box<dyn Exchange>> = match kind {
+        ExchangeKind::BinanceFutures => Some(Box::new(crate::exchange::binance_futures::BinanceFuturesExchange::new(base, String::new(), String::new()).with_telemetry(telemetry.clone()))),
+        ExchangeKind::BinanceSpot => Some(Box::new(crate::exchange::binance_spot::BinanceSpotExchange::new(base, String::new(), String::new()).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Bybit => Some(Box::new(crate::exchange::bybit::BybitExchange::new(base, String::new(), String::new()).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Okx => Some(Box::new(crate::exchange::okx::OkxExchange::new(base, String::new(), String::new(), String::new()).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Kraken => Some(Box::new(crate::exchange::kraken::KrakenExchange::new(base, String::new(), String::new()).with_telemetry(telemetry.clone()))),
+        ExchangeKind::Fix => None,
+      };
+      let paper = Arc::new(RwLock::new(PaperExchange::new(&config, market)));
+      let symbols = config.exchange.paper.symbols.clone();
+      for symbol in &symbols {
+        if let Err(e) = market_manager.subscribe_all(symbol).await {
+          log::warn!("paper ticker subscribe {} failed: {}", symbol, e);
+        }
+      }
+      PaperExchange::spawn_feed(paper.clone(), market_stream.clone(), symbols);
+      paper
+    }
+  }
+}
+
+// 설정에 선언된 전략 배포 (config.strategies)
+async fn deploy_configured_strategies(
+  strategy_manager: Arc<RwLock<StrategyManager>>,
+  deployments: &[crate::config::StrategyDeployment],
 ) -> Result<(), anyhow::Error> {
-  // 1. 이동평균 크로스오버 전략
-  let ma_strategy = TechnicalStrategy::ma_crossover(
-    "BTCUSDT".to_string(),
-    12,  // 빠른 이동평균 기간
-    26,  // 느린 이동평균 기간
-  )?;
-  
-  // 2. RSI 전략
-  let rsi_strategy = TechnicalStrategy::rsi(
-    "ETHUSDT".to_string(),
-    14,    // RSI 기간
-    30.0,  // 과매도 기준점
-    70.0,  // 과매수 기준점
-  )?;
-  
-  // 3. MACD 전략
-  let macd_strategy = TechnicalStrategy::macd(
-    "BTCUSDT".to_string(),
-    12,  // 빠른 EMA
-    26,  // 느린 EMA
-    9,   // 시그널 EMA
-  )?;
-  
-  // 4. 복합 지표 전략
-  let multi_strategy = TechnicalStrategy::multi_indicator(
-    "ETHUSDT".to_string(),
-  )?;
-  
-  // 5. RSI 신호 + TWAP 실행의 복합 전략
-  let combined_strategy = CombinedStrategy::rsi_twap(
-    "BTCUSDT".to_string(),
-    14,      // RSI 기간
-    30.0,    // 과매도 기준점
-    70.0,    // 과매수 기준점
-    60,      // 60분(TWAP 분할 기간)
-  )?;
-  
-  // 6. MACD 신호 + VWAP 실행의 복합 전략
-  let macd_vwap_strategy = CombinedStrategy::macd_vwap(
-    "ETHUSDT".to_string(),
-    12,    // 빠른 EMA
-    26,    // 느린 EMA
-    9,     // 시그널 EMA
-    0.1,   // 거래량 참여율 10%
-  )?;
-  
-  // 전략 매니저에 전략 추가
   let mut manager = strategy_manager.write().await;
-  manager.add_strategy(Box::new(ma_strategy))?;
-  manager.add_strategy(Box::new(rsi_strategy))?;
-  manager.add_strategy(Box::new(macd_strategy))?;
-  manager.add_strategy(Box::new(multi_strategy))?;
-  manager.add_strategy(Box::new(combined_strategy))?;
-  manager.add_strategy(Box::new(macd_vwap_strategy))?;
-  
-  // 시장 데이터 스트림 연결은 추후 구현 예정
+  let restored: Vec<String> = manager.list_strategies().into_iter().map(|(name, _)| name).collect();
+  for deployment in deployments.iter().filter(|d| d.enabled) {
+    let strategy = crate::strategies::build_strategy(&deployment.spec)?;
+    // 저장소에서 복원된 전략은 저장된 진행 상태를 유지
+    if restored.iter().any(|name| name == strategy.name()) {
+      log::info!("전략 복원 유지: {} ({})", strategy.name(), deployment.spec.symbol());
+    } else {
+      log::info!("전략 배포: {} ({})", strategy.name(), deployment.spec.symbol());
+      manager.add_strategy_spec(deployment.spec.clone())?;
+    }
+    // 설정 파일의 타임프레임이 저장된 값보다 우선
+    if deployment.timeframe.is_some() {
+      manager.set_strategy_timeframe(strategy.name(), deployment.timeframe.as_deref())?;
+    }
+    if deployment.exchange.is_some() {
+      manager.set_strategy_exchange(strategy.name(), deployment.exchange.as_deref())?;
+    }
+    if deployment.shadow {
+      manager.set_strategy_shadow(strategy.name(), true)?;
+    }
+    if deployment.schedule.is_some() {
+      manager.set_schedule(strategy.name(), deployment.schedule.clone())?;
+    }
+    // 설정 파일의 보호 청산 설정이 저장된 설정보다 우선
+    if deployment.protection.is_some() {
+      manager.set_protection(strategy.name(), deployment.protection.clone())?;
+    }
+    if deployment.exits.is_some() {
+      manager.set_entry_exits(strategy.name(), deployment.exits.clone())?;
+    }
+  }
   
   Ok(())
 }
@@ -274,131 +914,557 @@ async fn init_futures_defaults(
   Ok(())
 }
 
-// 실시간 전략 실행 루프: 심볼별로 거래소 시세를 폴링하여 전략을 업데이트하고 주문을 제출한다
+// 실시간 전략 실행 루프: 심볼별로 시세 스트림(WebSocket 티커)을 받아 전략을 업데이트하고 주문을 제출한다
+// 스트림이 없거나(`market_stream` 없음 = 모의 거래소) `stream_timeout_ms` 동안 조용하면 거래소 REST 시세를 폴링
+// 타임프레임 지정 전략은 시세 대신 캔들 집계 서비스가 발행한 마감 캔들로만 업데이트
+#[allow(clippy::too_many_arguments)]
 fn start_strategy_runtime(
   strategy_manager: Arc<RwLock<StrategyManager>>,
   order_manager: Arc<RwLock<OrderManager>>,
   exchange: Arc<RwLock<dyn Exchange>>,
+  market_stream: Option<Arc<RwLock<MarketDataStream>>>,
   symbols: Vec<String>,
+  runtime: crate::config::StrategyRuntimeConfig,
+  webhooks: WebhookDispatcher,
+  feedback: FeedbackCollector,
+  external: ExternalSeriesStore,
+  shadow: ShadowBook,
+  oco: OcoManager,
+  dedup: SignalDeduplicator,
+  mut added_symbols: tokio::sync::mpsc::UnboundedReceiver<String>,
 ) {
+  use tokio::sync::broadcast::error::RecvError;
+  let stream_timeout = std::time::Duration::from_millis(runtime.stream_timeout_ms.max(100));
+  let rest_refresh = std::time::Duration::from_millis(runtime.rest_refresh_ms);
+  
+  // 전략 주문의 실제 체결(부분 체결 포함)을 전략 가드/보호 청산/자본 배분에 기록
+  {
+    let (sm, om, hooks, feedback) = (strategy_manager.clone(), order_manager.clone(), webhooks.clone(), feedback.clone());
+    tokio::spawn(async move {
+      let mut events = om.read().await.subscribe_to_order_events();
+      // 주문별 반영한 누적 체결 수량 (같은 체결을 스트림과 폴링이 모두 알려도 한 번만 기록)
+      let mut filled: HashMap<OrderId, Decimal> = HashMap::new();
+      loop {
+        let event = match events.recv().await {
+          Ok(event) => event,
+          Err(RecvError::Lagged(n)) => {
+            log::warn!("strategy fill feed lagged {} order events, guard may miss fills", n);
+            continue;
+          }
+          Err(RecvError::Closed) => break,
+        };
+        let (OrderEvent::PartiallyFilled { price, .. } | OrderEvent::Filled { price, .. }) = event.event else { continue };
+        let Some(strategy_name) = event.strategy else { continue };
+        let update = event.update;
+        let seen = filled.remove(&update.order_id).unwrap_or_default();
+        let qty = update.new_fill_quantity(seen);
+        if !event.event.is_terminal() {
+          filled.insert(update.order_id.clone(), seen + qty);
+        }
+        if qty <= Decimal::ZERO || price <= Decimal::ZERO {
+          continue;
+        }
+        let (realized, tripped, reason) = {
+          let mut manager = sm.write().await;
+          let realized_before = manager.get_guard_state(&strategy_name).map(|g| g.realized_pnl).unwrap_or(0.0);
+          let tripped = manager.record_fill(&strategy_name, &update.symbol, &update.side, to_f64(qty), to_f64(price));
+          let state = manager.get_guard_state(&strategy_name);
+          (state.as_ref().map(|g| g.realized_pnl).unwrap_or(0.0) - realized_before, tripped, state.and_then(|g| g.reason))
+        };
+        hooks.emit(WebhookEventKind::OrderFilled, serde_json::json!({
+          "order_id": update.order_id.0, "strategy": strategy_name, "symbol": update.symbol,
+          "side": update.side, "quantity": qty, "price": price,
+        }));
+        feedback.record_realized_pnl(&update.order_id.0, realized);
+        if tripped {
+          hooks.emit(WebhookEventKind::RiskBreach, serde_json::json!({"strategy": strategy_name, "reason": reason}));
+          hooks.emit(WebhookEventKind::StrategyToggled, serde_json::json!({"strategy": strategy_name, "active": false, "reason": reason}));
+        }
+      }
+    });
+  }
+  
   // 심볼별 태스크 생성
-  for symbol in symbols {
+  let spawn_symbol = move |symbol: String| {
     let sm = strategy_manager.clone();
     let om = order_manager.clone();
     let ex = exchange.clone();
+    let market_stream = market_stream.clone();
+    let hooks = webhooks.clone();
+    let feedback = feedback.clone();
+    let external = external.clone();
+    let shadow = shadow.clone();
+    let oco = oco.clone();
+    let dedup = dedup.clone();
     tokio::spawn(async move {
-      let mut ticker = tokio::time::interval(std::time::Duration::from_millis(1000));
+      let mut ticks = match &market_stream {
+        Some(stream) => Some(stream.write().await.get_or_create_channel(&symbol).subscribe()),
+        None => None,
+      };
+      // 전략 타임프레임별 마감 캔들 수신기 (집계 중인 타임프레임만)
+      let mut candles = Vec::new();
+      if let Some(stream) = &market_stream {
+        let timeframes = sm.read().await.timeframes();
+        let stream = stream.read().await;
+        for timeframe in timeframes {
+          match stream.get_candle_receiver(&symbol, &timeframe) {
+            Ok(rx) => candles.push((timeframe, rx)),
+            Err(e) => log::warn!("no {} candles for {}: {}", timeframe, symbol, e),
+          }
+        }
+      }
+      let mut polling = false;
+      let mut last_rest_refresh: Option<std::time::Instant> = None;
       loop {
-        ticker.tick().await;
-        // 최신 시장 데이터 조회
-        let md_res = {
-          let exr = ex.read().await;
-          exr.get_market_data(&symbol).await
+        // 스트림 푸시를 기다리고, 시간 초과/스트림 없음이면 REST 폴링으로 대체
+        let pushed = match ticks.as_mut() {
+          Some(rx) => match tokio::time::timeout(stream_timeout, rx.recv()).await {
+            Ok(Ok(data)) => Some(data),
+            Ok(Err(RecvError::Lagged(n))) => {
+              log::debug!("strategy runtime {} lagged: {} tickers skipped", symbol, n);
+              continue;
+            }
+            Ok(Err(RecvError::Closed)) => {
+              log::warn!("market stream for {} closed, falling back to REST polling", symbol);
+              ticks = None;
+              None
+            }
+            Err(_) => None,
+          },
+          None => {
+            tokio::time::sleep(std::time::Duration::from_millis(1000)).await;
+            None
+          }
         };
-        let market_data = match md_res {
-          Ok(md) => md,
-          Err(e) => {
-            log::warn!("market data fetch failed for {}: {}", symbol, e);
-            continue;
+        let market_data = match pushed {
+          Some(data) => {
+            if polling {
+              log::info!("market stream for {} resumed", symbol);
+              polling = false;
+            }
+            data
+          }
+          None => {
+            if ticks.is_some() && !polling {
+              log::warn!("no streamed ticker for {} in {:?}, polling REST", symbol, stream_timeout);
+              polling = true;
+            }
+            let md_res = {
+              let exr = ex.read().await;
+              exr.get_market_data(&symbol).await
+            };
+            match md_res {
+              Ok(md) => md,
+              Err(e) => {
+                log::warn!("market data fetch failed for {}: {}", symbol, e);
+                continue;
+              }
+            }
           }
         };
-        // 전략 업데이트 및 주문 수집
-        let orders = {
+        // 섀도 주문 가상 체결/평가
+        shadow.on_market_data(&market_data);
+        // 호가창: 스트림 최신 호가 우선, 없으면 REST (미지원 거래소는 캔들만 사용)
+        let streamed_book = match &market_stream {
+          Some(stream) => stream.read().await.get_latest_order_book(&symbol),
+          None => None,
+        };
+        // REST 보조 조회(호가창/펀딩)는 `rest_refresh_ms` 마다 한 번만
+        let refresh_due = last_rest_refresh.is_none_or(|at| at.elapsed() >= rest_refresh);
+        let book = match streamed_book {
+          Some(book) => Some(book),
+          None if refresh_due => {
+            let exr = ex.read().await;
+            exr.get_order_book(&symbol, 20).await.map_err(|e| log::debug!("order book unavailable for {}: {}", symbol, e)).ok()
+          }
+          None => None,
+        };
+        // 펀딩 정보 (무기한 선물만)
+        let funding = if refresh_due {
+          last_rest_refresh = Some(std::time::Instant::now());
+          let exr = ex.read().await;
+          exr.get_funding_rate(&symbol).await.map_err(|e| log::debug!("funding rate unavailable for {}: {}", symbol, e)).ok()
+        } else {
+          None
+        };
+        // 업데이트할 전략 선별 (관리자 잠금은 선별/반영 동안만)
+        let batches = {
           let mut manager = sm.write().await;
-          if let Err(e) = manager.update_all(&market_data) {
-            log::warn!("strategy update failed: {}", e);
-            Vec::new()
-          } else {
-            match manager.get_all_orders() {
-              Ok(os) => os,
-              Err(e) => {
-                log::warn!("collect orders failed: {}", e);
-                Vec::new()
+          if let Some(book) = &book {
+            manager.update_order_book_all(book);
+          }
+          if let Some(funding) = &funding {
+            manager.update_funding_all(funding);
+          }
+          manager.maybe_rebalance(chrono::Utc::now().timestamp_millis());
+          // 외부 시계열(심리 지수 등) 보조 입력
+          if !external.is_empty() {
+            manager.update_external_all(&external.inputs_at(market_data.timestamp));
+          }
+          // 이번 주기까지 마감된 캔들을 해당 타임프레임 전략에 전달
+          let mut batches = vec![manager.prepare_update(&market_data)];
+          for (timeframe, rx) in candles.iter_mut() {
+            loop {
+              match rx.try_recv() {
+                Ok(candle) => batches.push(manager.prepare_candle(timeframe, &candle)),
+                Err(tokio::sync::broadcast::error::TryRecvError::Lagged(n)) => {
+                  log::warn!("strategy runtime {} lagged: {} {} candles skipped", symbol, n, timeframe);
+                }
+                Err(_) => break,
               }
             }
           }
+          batches
+        };
+        // 전략 업데이트 - 묶음 안의 전략은 병렬, 묶음(틱, 캔들)은 도착 순서대로
+        let mut results = Vec::new();
+        for batch in batches {
+          results.extend(batch.run().await);
+        }
+        // 결과 반영 및 주문 수집
+        let orders = {
+          let mut manager = sm.write().await;
+          // 업데이트에 실패한 전략만 이번 주기 주문 수집에서 제외
+          let failed: Vec<String> = manager.complete_update(results).into_iter()
+            .map(|(name, e)| {
+              log::warn!("strategy {} update failed: {}", name, e);
+              name
+            })
+            .collect();
+          let mut orders: Vec<_> = manager.get_all_orders_by_strategy(&failed).into_iter().map(|(name, order)| {
+            let execution = manager.is_execution_strategy(&name);
+            (name, order, None, execution)
+          }).collect();
+          // 보호 청산은 전략 주문보다 먼저 제출
+          let exits = manager.check_protective_exits(&market_data);
+          orders.splice(0..0, exits.into_iter().map(|exit| (exit.strategy, exit.order, Some(exit.reason), false)));
+          orders
         };
         // 주문 제출
-        for order in orders {
-          let submit_res = {
-            let om_read = om.read().await;
-            om_read.create_order(order).await
-          };
-          if let Err(e) = submit_res {
-            log::warn!("order submit failed: {}", e);
+        for (strategy_name, mut order, exit_reason, execution_slice) in orders {
+          let protective_exit = exit_reason.is_some();
+          // 실행 전략의 분할 주문은 중복 판별에서 제외 (이미 전략의 실행 수량에 반영됨)
+          let dedup_key = SignalKey::for_order(&strategy_name, &order, exit_reason.as_ref().map_or("signal", |r| r.as_str()), execution_slice);
+          if let Some(key) = dedup_key.as_ref().filter(|key| !dedup.admit(key, chrono::Utc::now().timestamp_millis())) {
+            log::debug!("duplicate signal dropped: {} {} {:?} ({})", strategy_name, order.symbol, order.side, key.reason);
+            continue;
+          }
+          // 섀도 모드: 거래소로 보내지 않고 현재 시세 기준 가상 체결만 기록
+          if sm.read().await.is_shadow(&strategy_name) {
+            hooks.emit(WebhookEventKind::StrategySignal, serde_json::json!({
+              "strategy": strategy_name, "symbol": order.symbol, "side": order.side,
+              "quantity": to_f64(order.quantity), "price": market_data.close, "protective_exit": protective_exit, "shadow": true,
+            }));
+            shadow.submit(&strategy_name, order, &market_data, book.as_ref());
+            continue;
           }
+          // 체결 기록용 신호/결정 가격 등록 (클라이언트 ID 로 체결과 연결)
+          if let Some(journal) = om.read().await.trade_journal() {
+            let client_id = order.client_order_id.get_or_insert_with(|| uuid::Uuid::new_v4().to_string()).clone();
+            let signal = if protective_exit { "protective_exit" } else { "strategy" };
+            journal.register(&client_id, OrderContext { strategy: Some(strategy_name.clone()), signal: Some(signal.to_string()), decision_price: Some(market_data.close_f64()) });
+          }
+          let (side, quantity) = (order.side.clone(), to_f64(order.quantity));
+          let order_symbol = order.symbol.clone();
+          let signal = SignalOutcome::new(strategy_name.as_str(), order_symbol.as_str(), side.clone(), quantity, market_data.close_f64(), chrono::Utc::now().timestamp_millis());
+          hooks.emit(WebhookEventKind::StrategySignal, serde_json::json!({
+            "strategy": strategy_name, "symbol": order_symbol, "side": side,
+            "quantity": quantity, "price": market_data.close, "protective_exit": protective_exit,
+          }));
+          // 제출 순서는 여기서 큐에 넣으며 확정하고, 결과 처리는 별도 태스크에서 (다음 시세 처리를 막지 않음)
+          // 보호 청산은 reduce-only 여부와 무관하게 위험 감소 우선순위
+          let priority = if protective_exit { SubmissionPriority::RiskReducing } else { SubmissionPriority::classify(&order) };
+          let queue = om.read().await.submission_queue();
+          let submission: futures::future::BoxFuture<'static, Result<OrderId, crate::error::TradingError>> = match order.attached_exits.take() {
+            // 손절/익절이 붙은 진입은 브래킷으로 제출 (진입 체결 후 OCO 등록)
+            Some(exits) => match oco.enqueue(&queue, priority, Some(strategy_name.clone()), BracketOrder::new(order, exits.take_profit, exits.stop_loss)) {
+              Ok(bracket) => Box::pin(async move {
+                bracket.await
+                  .and_then(|bracket| bracket.entry_id.ok_or_else(|| crate::error::TradingError::ExecutionError(format!("bracket {} has no entry order", bracket.id))))
+              }),
+              Err(e) => Box::pin(std::future::ready(Err(e))),
+            },
+            None => match queue.enqueue_submit(priority, Some(strategy_name.clone()), order) {
+              Ok(pending) => Box::pin(pending.wait()),
+              Err(e) => Box::pin(std::future::ready(Err(e))),
+            },
+          };
+          let (sm, hooks, feedback, dedup) = (sm.clone(), hooks.clone(), feedback.clone(), dedup.clone());
+          tokio::spawn(async move {
+            let submit_res = submission.await;
+            if let (Err(_), Some(key)) = (&submit_res, &dedup_key) {
+              dedup.release(key);
+            }
+            if protective_exit && submit_res.is_err() {
+              sm.write().await.protective_exit_failed(&strategy_name, &order_symbol);
+            }
+            match submit_res {
+              // 체결은 주문 이벤트 버스의 실제 체결로 기록 (위 체결 피드)
+              Ok(order_id) => feedback.record_signal(&order_id.0, signal),
+              Err(crate::error::TradingError::RiskLimitExceeded(reason)) => {
+                log::warn!("order rejected by risk limit: {}", reason);
+                feedback.record_rejection(signal, &reason);
+                hooks.emit(WebhookEventKind::RiskBreach, serde_json::json!({"strategy": strategy_name, "symbol": order_symbol, "reason": reason}));
+              }
+              // 읽기 전용 모드: 차단 내역은 감시자에 기록되므로 매 주기 경고하지 않음
+              Err(crate::error::TradingError::DegradedMode(reason)) => {
+                log::debug!("order blocked in degraded mode: {}", reason);
+                feedback.record_rejection(signal, "degraded mode");
+              }
+              // 거래 중지: 중지 상태는 /trading/status 에서 확인
+              Err(crate::error::TradingError::TradingHalted(reason)) => {
+                log::debug!("order blocked, trading halted: {}", reason);
+                feedback.record_rejection(signal, "trading halted");
+              }
+              // 큐 포화: 신호 폭주 시 신규 진입은 버림 (보호 청산은 거부되지 않음)
+              Err(crate::error::TradingError::QueueFull(reason)) => {
+                log::warn!("order dropped, submission queue full: {}", reason);
+                feedback.record_rejection(signal, "submission queue full");
+              }
+              Err(e) => log::warn!("order submit failed: {}", e),
+            }
+          });
         }
       }
     });
+  };
+  for symbol in symbols {
+    spawn_symbol(symbol);
   }
+  // 설정 리로드로 추가된 전략의 새 심볼도 같은 방식으로 실행
+  tokio::spawn(async move {
+    while let Some(symbol) = added_symbols.recv().await {
+      log::info!("strategy runtime started for {}", symbol);
+      spawn_symbol(symbol);
+    }
+  });
 }
 
-async fn run_backtest() -> Result<(), anyhow::Error> {
-  log::info!("백테스트 모드 시작...");
+// 주문 기록 CLI - 내보내기/가져오기/세금 보고서 (주문 스냅샷 파일 기준, 실행 중인 인스턴스 불필요)
+fn run_orders_cli(config: &Config, command: OrdersCommand) -> Result<(), anyhow::Error> {
+  let store_path = |store: Option<String>| {
+    std::path::PathBuf::from(store.or_else(|| config.order_snapshot_path.clone()).unwrap_or_else(|| "orders_snapshot.json".to_string()))
+  };
   
-  // 기본 백테스트 시나리오 생성
-  let basic_scenario = BacktestScenarioBuilder::new("VWAP 전략 테스트")
-    .description("BTCUSDT에 대한 VWAP 기반 매수 전략 테스트")
-    .data_file("./data/BTCUSDT-1m.csv".into())
-    .last_days(30)  // 최근 30일
-    .initial_balance("USDT", 10000.0)
-    .fee_rate(0.001)  // 0.1% 수수료
-    .slippage(0.0005)  // 0.05% 슬리피지
-    .strategy(Box::new(VwapStrategy::new(
-      "BTCUSDT",
-      OrderSide::Buy,
-      1.0,  // 1 BTC 매수
-      86400000,  // 24시간(밀리초) 동안 실행
-      100,  // 100개 캔들의 VWAP 윈도우
-    )))
-    .build()?;
-  
-  // TA 전략 백테스트 시나리오 생성 (신규)
-  let ta_scenario = BacktestScenarioBuilder::new("MA 크로스오버 전략 테스트")
-    .description("BTCUSDT에 대한 이동평균 크로스오버 전략 테스트")
-    .data_file("./data/BTCUSDT-1m.csv".into())
-    .last_days(30)  // 최근 30일
-    .initial_balance("USDT", 10000.0)
-    .fee_rate(0.001)  // 0.1% 수수료
-    .slippage(0.0005)  // 0.05% 슬리피지
-    .strategy(Box::new(TechnicalStrategy::ma_crossover(
-      "BTCUSDT".to_string(),
-      12,  // 빠른 이동평균 기간
-      26,  // 느린 이동평균 기간
-    )?))
-    .build()?;
-  
-  // 추가 TA 백테스트 시나리오 생성
-  let rsi_scenario = BacktestScenarioBuilder::new("RSI 전략 테스트")
-    .description("BTCUSDT에 대한 RSI 기반 전략 테스트")
-    .data_file("./data/BTCUSDT-1m.csv".into())
-    .last_days(30)  // 최근 30일
-    .initial_balance("USDT", 10000.0)
-    .fee_rate(0.001)  // 0.1% 수수료
-    .slippage(0.0005)  // 0.05% 슬리피지
-    .strategy(Box::new(TechnicalStrategy::rsi(
-      "BTCUSDT".to_string(),
-      14,    // RSI 기간
-      30.0,  // 과매도 기준점
-      70.0,  // 과매수 기준점
-    )?))
-    .build()?;
-  
-  // 명령줄 인수 확인 - 어떤 백테스트를 실행할지 결정
-  let args: Vec<String> = std::env::args().collect();
-  let mut scenario = if args.len() > 2 {
-    match args[2].as_str() {
-      "ma" => ta_scenario,
-      "rsi" => rsi_scenario,
-      _ => basic_scenario,
+  match command {
+    OrdersCommand::Export { store, format, out, fills } => {
+      let snapshot = OrderSnapshot::load(&store_path(store.store))?;
+      let format = match (format, &out) {
+        (Some(f), _) => FileFormat::parse(&f)?,
+        (None, Some(o)) => FileFormat::from_path(std::path::Path::new(o)).unwrap_or(FileFormat::Csv),
+        (None, None) => FileFormat::Csv,
+      };
+      let writer: Box<dyn std::io::Write> = match &out {
+        Some(o) => Box::new(std::fs::File::create(o)?),
+        None => Box::new(std::io::stdout()),
+      };
+      if fills {
+        reconcile::export_fills(&snapshot.fills, format, writer)?;
+      } else {
+        reconcile::export_orders(&snapshot.orders, format, writer)?;
+      }
+      log::info!("주문 내보내기 완료: 주문 {}건, 체결 {}건", snapshot.orders.len(), snapshot.fills.len());
+    }
+    OrdersCommand::Import { file, store, format, report, merge } => {
+      let store_path = store_path(store.store);
+      let format = match format {
+        Some(f) => FileFormat::parse(&f)?,
+        None => FileFormat::from_path(std::path::Path::new(&file))?,
+      };
+      let fills = reconcile::import_exchange_fills(std::fs::File::open(&file)?, format)?;
+      let mut snapshot = if store_path.exists() { OrderSnapshot::load(&store_path)? } else { OrderSnapshot::default() };
+      
+      let reconciliation = reconcile::reconcile(&snapshot, &fills);
+      println!("{}", reconciliation.summary());
+      if let Some(path) = report {
+        std::fs::write(&path, serde_json::to_string_pretty(&reconciliation)?)?;
+        log::info!("불일치 보고서 저장: {}", path);
+      }
+      
+      // 거래소 체결 내역을 로컬 체결 기록으로 병합 (데이터 유실 복구)
+      if merge {
+        for fill in fills {
+          if !snapshot.fills.contains(&fill) {
+            snapshot.fills.push(fill);
+          }
+        }
+        snapshot.save(&store_path)?;
+        log::info!("체결 기록 병합 완료: {}건", snapshot.fills.len());
+      }
+    }
+    OrdersCommand::TaxReport { store, method, year, summary, out } => {
+      let snapshot = OrderSnapshot::load(&store_path(store.store))?;
+      let method = method.map(|m| LotMethod::parse(&m)).transpose()?.unwrap_or_default();
+      let mut ledger = TaxLotLedger::from_fills(&snapshot.fills, method);
+      if let Some(year) = year {
+        ledger.retain_year(year);
+      }
+      let writer: Box<dyn std::io::Write> = match out {
+        Some(o) => Box::new(std::fs::File::create(o)?),
+        None => Box::new(std::io::stdout()),
+      };
+      if summary {
+        ledger.export_summary_csv(writer)?;
+      } else {
+        ledger.export_realized_csv(writer)?;
+      }
+      log::info!("실현 손익 보고서 생성 완료: 처분 {}건, 미처분 취득 단위 {}건", ledger.realized().len(), ledger.open_lots().len());
     }
+    OrdersCommand::List | OrdersCommand::Cancel { .. } => unreachable!("handled over the HTTP API"),
+  }
+  
+  Ok(())
+}
+
+// 합성 데이터 생성 (국면 전환 GBM + 거래량 모델, 같은 시드면 같은 데이터)
+fn run_data_generate(args: GenerateArgs) -> Result<(), anyhow::Error> {
+  let symbol = args.symbol.to_uppercase();
+  let interval_ms = crate::market_data::aggregator::parse_timeframe(&args.interval)?;
+  let candles = ((args.days * 86_400_000.0) / interval_ms as f64).ceil().max(1.0) as usize;
+  // 시작 시각 미지정 시 현재 시각에 끝나도록 배치 (`last_days` 백테스트와 바로 호환)
+  let start = args.start.unwrap_or_else(|| Utc::now().timestamp_millis() / interval_ms * interval_ms - candles as i64 * interval_ms);
+  
+  let mut config = SyntheticDataConfig::new(&symbol, start, interval_ms, candles);
+  if let Some(price) = args.price {
+    config = config.initial_price(price);
+  }
+  if let Some(seed) = args.seed {
+    config = config.seed(seed);
+  }
+  let out = args.out.unwrap_or_else(|| format!("./data/{}-{}.csv", symbol, args.interval));
+  
+  let written = SyntheticDataGenerator::new(config)?.write_csv(std::path::Path::new(&out))?;
+  log::info!("합성 데이터 {}개 캔들 저장: {}", written, out);
+  Ok(())
+}
+
+// 거래소 klines 다운로드 (API 키 불필요, TIME 은 epoch ms 또는 RFC3339, 종료 시각 미지정 시 현재)
+async fn run_data_download(config: &Config, args: DownloadArgs) -> Result<(), anyhow::Error> {
+  let symbol = args.symbol.to_uppercase();
+  let interval = args.interval;
+  let end = match args.to {
+    Some(t) => Timestamp::parse(&t)?.to_datetime(),
+    None => Utc::now(),
+  };
+  let start = match args.from {
+    Some(t) => Timestamp::parse(&t)?.to_datetime(),
+    None => end - chrono::Duration::milliseconds((args.days * 86_400_000.0) as i64),
+  };
+  let out = args.out.unwrap_or_else(|| format!("./data/{}-{}.csv", symbol, interval));
+  
+  let kind = config.exchange.kind;
+  let base = config.exchange.base_url.clone().unwrap_or(kind.default_base_url().to_string());
+  let exchange: Box<dyn Exchange> = match kind {
+    ExchangeKind::BinanceFutures => Box::new(crate::exchange::binance_futures::BinanceFuturesExchange::new(base, String::new(), String::new())),
+    ExchangeKind::BinanceSpot => Box::new(crate::exchange::binance_spot::BinanceSpotExchange::new(base, String::new(), String::new())),
+    ExchangeKind::Bybit => Box::new(crate::exchange::bybit::BybitExchange::new(base, String::new(), String::new())),
+    ExchangeKind::Okx => Box::new(crate::exchange::okx::OkxExchange::new(base, String::new(), String::new(), String::new())),
+    ExchangeKind::Kraken => Box::new(crate::exchange::kraken::KrakenExchange::new(base, String::new(), String::new())),
+    ExchangeKind::Fix => anyhow::bail!("FIX order entry has no historical candles; download with a REST exchange kind"),
+  };
+  let mut downloader = ExchangeDataDownloader::new(exchange.as_ref());
+  if let Some(size) = args.page_size {
+    downloader = downloader.page_size(size);
+  }
+  
+  let written = downloader.download_to_csv(std::path::Path::new(&out), &symbol, &interval, start, end).await?;
+  log::info!("{} {} 캔들 {}개 저장 ({} ~ {}): {}", symbol, interval, written, start, end, out);
+  Ok(())
+}
+
+/// 내장 백테스트 시나리오
+fn builtin_scenario(name: &str) -> Result<crate::backtest::BacktestScenario, anyhow::Error> {
+  // 데이터 파일이 없는 신규 환경: 최근 30일치 합성 데이터로 대체
+  let data_path = std::path::Path::new("./data/BTCUSDT-1m.csv");
+  if synthetic::ensure_data_file(data_path, "BTCUSDT", 30, 42)? {
+    log::warn!("{} 가 없어 합성 데이터(seed=42)를 생성했습니다. 실제 데이터로 교체하세요", data_path.display());
+  }
+  
+  let scenario = match name {
+    // 기본 백테스트 시나리오
+    "vwap" => BacktestScenarioBuilder::new("VWAP 전략 테스트")
+      .description("BTCUSDT에 대한 VWAP 기반 매수 전략 테스트")
+      .data_file("./data/BTCUSDT-1m.csv".into())
+      .last_days(30)  // 최근 30일
+      .initial_balance("USDT", 10000.0)
+      .fee_rate(0.001)  // 0.1% 수수료
+      .slippage(0.0005)  // 0.05% 슬리피지
+      .strategy(Box::new(VwapStrategy::new(
+        "BTCUSDT",
+        OrderSide::Buy,
+        dec!(1),  // 1 BTC 매수
+        86400000,  // 24시간(밀리초) 동안 실행
+        100,  // 100개 캔들의 VWAP 윈도우
+      )))
+      .build()?,
+    // TA 전략 백테스트 시나리오
+    "ma" => BacktestScenarioBuilder::new("MA 크로스오버 전략 테스트")
+      .description("BTCUSDT에 대한 이동평균 크로스오버 전략 테스트")
+      .data_file("./data/BTCUSDT-1m.csv".into())
+      .last_days(30)  // 최근 30일
+      .initial_balance("USDT", 10000.0)
+      .fee_rate(0.001)  // 0.1% 수수료
+      .slippage(0.0005)  // 0.05% 슬리피지
+      .strategy(Box::new(TechnicalStrategy::ma_crossover(
+        "BTCUSDT".to_string(),
+        12,  // 빠른 이동평균 기간
+        26,  // 느린 이동평균 기간
+      )?))
+      .build()?,
+    // 추가 TA 백테스트 시나리오
+    "rsi" => BacktestScenarioBuilder::new("RSI 전략 테스트")
+      .description("BTCUSDT에 대한 RSI 기반 전략 테스트")
+      .data_file("./data/BTCUSDT-1m.csv".into())
+      .last_days(30)  // 최근 30일
+      .initial_balance("USDT", 100000.0)  // RSI 봇은 신호당 1 BTC 주문
+      .fee_rate(0.001)  // 0.1% 수수료
+      .slippage(0.0005)  // 0.05% 슬리피지
+      .strategy(Box::new(TechnicalStrategy::rsi(
+        "BTCUSDT".to_string(),
+        14,    // RSI 기간
+        30.0,  // 과매도 기준점
+        70.0,  // 과매수 기준점
+      )?))
+      .build()?,
+    "pairs" => pairs_scenario()?,
+    other => anyhow::bail!("unknown scenario: {} (vwap|ma|rsi|pairs 또는 .toml/.yaml/.json 파일)", other),
+  };
+  Ok(scenario)
+}
+
+async fn run_backtest(args: BacktestArgs) -> Result<(), anyhow::Error> {
+  match args.command {
+    Some(BacktestCommand::Sweep(sweep)) => return run_sweep(&args.scenario, sweep).await,
+    Some(BacktestCommand::Optimize(optimize)) => return run_rsi_walk_forward(optimize).await,
+    None => {}
+  }
+  log::info!("백테스트 모드 시작...");
+  
+  // 시나리오 파일(toml/yaml/json) 또는 내장 시나리오
+  let scenario_path = std::path::Path::new(&args.scenario);
+  let mut scenario = if ScenarioFormat::from_path(scenario_path).is_some() {
+    ScenarioFile::load(scenario_path)?.build()?
   } else {
-    basic_scenario
+    builtin_scenario(&args.scenario)?
   };
   
   // 백테스트 실행
   log::info!("백테스트 실행 중: {}", scenario.name());
-  let result = scenario.run().await?;
+  let mut result = scenario.run().await?;
+  
+  // 몬테카를로 강건성 분석: --monte-carlo N (요약과 보고서에 포함)
+  if let Some(iterations) = args.monte_carlo.iterations {
+    let mc = &args.monte_carlo;
+    let config = MonteCarloConfig {
+      iterations,
+      method: ResampleMethod::parse(&mc.method)?,
+      seed: mc.seed,
+      fee_jitter: mc.fee_jitter,
+      slippage_jitter: mc.slippage_jitter,
+      ruin_threshold: mc.ruin_threshold,
+    };
+    result.with_monte_carlo(&config)?;
+  }
   
   // 결과 출력
   println!("\n{}", result.summary());
@@ -410,11 +1476,11 @@ async fn run_backtest() -> Result<(), anyhow::Error> {
   println!("평균 거래당 수익: {:.2} USDT", result.average_profit_per_trade());
   
   if let Some((trade, profit)) = result.max_profit_trade() {
-    println!("최대 수익 거래: {:.2} USDT ({})", profit, trade.id);
+    println!("최대 수익 거래: {:.2} USDT ({} {})", profit, trade.symbol, trade.exit_trade_id);
   }
   
   if let Some((trade, loss)) = result.max_loss_trade() {
-    println!("최대 손실 거래: {:.2} USDT ({})", loss, trade.id);
+    println!("최대 손실 거래: {:.2} USDT ({} {})", loss, trade.symbol, trade.exit_trade_id);
   }
   
   println!("\n=== 최종 잔고 ===");
@@ -423,7 +1489,7 @@ async fn run_backtest() -> Result<(), anyhow::Error> {
   }
   
   // TA 관련 추가 분석 출력 (신규)
-  if scenario.name().contains("MA") || scenario.name().contains("RSI") {
+  if scenario.name().contains("MA") || scenario.name().contains("RSI") || scenario.name().contains("페어") {
     println!("\n=== TA 전략 성능 지표 ===");
     println!("샤프 비율: {:.4}", result.sharpe_ratio());
     println!("최대 손실폭: {:.2}%", result.max_drawdown() * 100.0);
@@ -431,5 +1497,146 @@ async fn run_backtest() -> Result<(), anyhow::Error> {
     println!("CAR (연간 복합 수익률): {:.2}%", result.car() * 100.0);
   }
   
+  // 보고서 저장: --report PATH [--format html|json] (형식 생략 시 확장자로 판별)
+  if let Some(path) = &args.report {
+    let format = match &args.format {
+      Some(f) => ReportFormat::parse(f)?,
+      None => ReportFormat::from_path(path)?,
+    };
+    result.export(path, format)?;
+    println!("\n보고서 저장: {}", path.display());
+  }
+  
   Ok(())
-}
\ No newline at end of file
+}
+
+// BTC/ETH 페어 전략 포트폴리오 백테스트 - 심볼별 데이터 파일, 현금 잔고 공유
+fn pairs_scenario() -> Result<crate::backtest::BacktestScenario, anyhow::Error> {
+  let eth_path = std::path::Path::new("./data/ETHUSDT-1m.csv");
+  if synthetic::ensure_data_file(eth_path, "ETHUSDT", 30, 7)? {
+    log::warn!("{} 가 없어 합성 데이터(seed=7)를 생성했습니다. 실제 데이터로 교체하세요", eth_path.display());
+  }
+  Ok(BacktestScenarioBuilder::new("BTC/ETH 페어 전략 테스트")
+    .description("BTCUSDT/ETHUSDT 스프레드 평균회귀 포트폴리오 테스트")
+    .symbol_data_file("BTCUSDT", "./data/BTCUSDT-1m.csv".into())
+    .symbol_data_file("ETHUSDT", eth_path.to_path_buf())
+    .last_days(30)
+    .initial_balance("USDT", 10000.0)
+    .fee_rate(0.001)
+    .slippage(0.0005)
+    .strategy(Box::new(PairsTradingStrategy::new("BTCUSDT", "ETHUSDT", dec!(0.01), 120, 2.0, 0.5)?))
+    .build()?)
+}
+
+// 2차원 파라미터 스윕 - 히트맵용 JSON/CSV 출력
+// --x/--y 를 주면 --scenario 시나리오 파일의 전략 파라미터를, 생략하면 RSI 기간 x 과매도 기준점(과매수 = 100 - 과매도)을 스윕
+async fn run_sweep(scenario: &str, args: SweepArgs) -> Result<(), anyhow::Error> {
+  let metric = SweepMetric::parse(&args.metric)?;
+  let prefix = &args.out;
+  
+  let grid = match (&args.x, &args.y) {
+    (Some(x), Some(y)) => {
+      let path = std::path::Path::new(scenario);
+      if ScenarioFormat::from_path(path).is_none() {
+        anyhow::bail!("--x/--y 스윕에는 시나리오 파일이 필요합니다 (--scenario my.yaml)");
+      }
+      let base = ScenarioFile::load(path)?;
+      let spec = base.strategies.get(args.strategy).cloned()
+        .ok_or_else(|| anyhow::anyhow!("시나리오에 {}번 전략이 없습니다", args.strategy))?;
+      let (x, y) = (ParamAxis::parse(x)?, ParamAxis::parse(y)?);
+      // 축 이름 오타는 모든 칸이 빈 칸이 되기 전에 바로 거부
+      for axis in [&x, &y] {
+        spec.with_numeric_param(&axis.name, axis.values[0]).map_err(|e| anyhow::anyhow!(e))?;
+      }
+      ParameterSweep::new(x.clone(), y.clone()).run(|xv, yv| {
+        let mut file = base.clone();
+        file.strategies[args.strategy] = spec.with_numeric_param(&x.name, xv)
+          .and_then(|s| s.with_numeric_param(&y.name, yv))
+          .map_err(crate::error::TradingError::InvalidParameter)?;
+        file.name = format!("{} {}={} {}={}", base.name, x.name, xv, y.name, yv);
+        file.build()
+      }).await?
+    }
+    (None, None) => {
+      ParameterSweep::new(
+        ParamAxis::range("rsi_period", 7.0, 28.0, 7.0)?,
+        ParamAxis::range("oversold", 20.0, 40.0, 5.0)?,
+      ).run(|period, oversold| {
+        BacktestScenarioBuilder::new(format!("RSI 스윕 {}/{}", period, oversold))
+          .data_file("./data/BTCUSDT-1m.csv".into())
+          .last_days(30)
+          .initial_balance("USDT", 100000.0)
+          .fee_rate(0.001)
+          .slippage(0.0005)
+          .strategy(Box::new(TechnicalStrategy::rsi("BTCUSDT".to_string(), period as usize, oversold, 100.0 - oversold)?))
+          .build()
+      }).await?
+    }
+    _ => anyhow::bail!("--x 와 --y 는 함께 지정해야 합니다"),
+  };
+  
+  std::fs::write(format!("{}.json", prefix), serde_json::to_string_pretty(&grid.to_heatmap_json(metric))?)?;
+  grid.export_csv(metric, std::fs::File::create(format!("{}.csv", prefix))?)?;
+  
+  let (x_name, y_name) = (&grid.x.name, &grid.y.name);
+  if let Some(best) = grid.best_raw(metric) {
+    println!("최고값: {}={} {}={} ({:?} {:.4})", x_name, best.x, y_name, best.y, metric, best.metric(metric));
+  }
+  if let Some(robust) = grid.best_robust(metric) {
+    println!("안정 구간: {}={} {}={} (이웃 평균 {:.4}, 이웃 최저 {:.4})", x_name, robust.x, y_name, robust.y, robust.neighborhood_score, robust.neighborhood_min);
+  }
+  let spikes = grid.stability(metric).into_iter().filter(|s| s.spike).count();
+  println!("고립 돌출값 {}개 - {}.json / {}.csv 저장", spikes, prefix, prefix);
+  Ok(())
+}
+
+// RSI 워크포워드 최적화 (기간 10..20 x 과매도 20..35, 과매수 = 100 - 과매도) - 창별 최적 파라미터와 검증 구간 성과
+async fn run_rsi_walk_forward(args: OptimizeArgs) -> Result<(), anyhow::Error> {
+  let days = args.days;
+  let search = match args.search.as_str() {
+    "grid" => SearchMethod::Grid,
+    "random" => SearchMethod::Random { samples: args.samples, seed: args.seed },
+    other => anyhow::bail!("unknown search method: {} (grid|random)", other),
+  };
+  let config = WalkForwardConfig {
+    in_sample_days: args.is_days,
+    out_of_sample_days: args.oos_days,
+    step_days: args.step_days,
+    anchored: args.anchored,
+    metric: SweepMetric::parse(&args.metric)?,
+    search,
+  };
+  let prefix = args.out;
+  
+  let optimizer = WalkForwardOptimizer::new(
+    vec![ParamAxis::range("rsi_period", 10.0, 20.0, 2.0)?, ParamAxis::range("oversold", 20.0, 35.0, 5.0)?],
+    config,
+  );
+  let end = chrono::Utc::now();
+  let report = optimizer.run(end - chrono::Duration::days(days), end, |params, start, end| {
+    let (period, oversold) = (params["rsi_period"], params["oversold"]);
+    BacktestScenarioBuilder::new(format!("RSI 워크포워드 {}/{}", period, oversold))
+      .data_file("./data/BTCUSDT-1m.csv".into())
+      .period(start, end)
+      .initial_balance("USDT", 100000.0)
+      .fee_rate(0.001)
+      .slippage(0.0005)
+      .strategy(Box::new(TechnicalStrategy::rsi("BTCUSDT".to_string(), period as usize, oversold, 100.0 - oversold)?))
+      .build()
+  }).await?;
+  
+  std::fs::write(format!("{}.json", prefix), serde_json::to_string_pretty(&report)?)?;
+  report.export_csv(std::fs::File::create(format!("{}.csv", prefix))?)?;
+  
+  for w in &report.windows {
+    let oos = w.out_of_sample.as_ref().map(|m| format!("{:.2} USDT ({:.2}%)", m.profit, m.profit_percentage)).unwrap_or_else(|| "실패".to_string());
+    println!("창 {} [{} ~ {}]: rsi_period={} oversold={} -> 검증 {}", w.index, w.out_of_sample_start.format("%m-%d"), w.out_of_sample_end.format("%m-%d"), w.best_params["rsi_period"], w.best_params["oversold"], oos);
+  }
+  let summary = &report.out_of_sample;
+  println!("검증 구간 {}개 중 수익 {}개, 누적 수익률 {:.2}%, 평균 샤프 {:.4}, 최대 낙폭 {:.2}%", summary.windows, summary.profitable_windows, summary.compounded_return_pct, summary.mean_sharpe, summary.worst_drawdown * 100.0);
+  if let Some(efficiency) = summary.efficiency {
+    println!("워크포워드 효율 (검증/구간 표본 수익률): {:.2}", efficiency);
+  }
+  println!("{}.json / {}.csv 저장", prefix, prefix);
+  Ok(())
+}
@@ -0,0 +1,278 @@
+//! 캔들 집계 모듈
+//!
+//! 틱 또는 하위 주기 캔들을 상위 주기(예: 5m, 1h) 캔들로 합친다. 구간 경계는 타임스탬프(ms)를
+//! 주기로 나눈 값으로 정하며, 다음 구간의 데이터가 들어오는 시점에 이전 캔들이 완성된다.
+//! `CandleAggregationService` 는 실시간 티커 스트림을 주기별 완성 캔들로 바꿔 `MarketDataStream` 캔들
+//! 채널에 발행하고, 거래가 없어도 구간이 끝나면 캔들을 마감한다.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+use crate::error::TradingError;
+use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::Decimal;
+use crate::models::market_data::MarketData;
+use crate::models::timestamp::Timestamp;
+
+/// 구간 종료 후 늦게 도착하는 틱을 기다리는 시간 (ms)
+const CLOSE_GRACE_MS: i64 = 500;
+
+/// 주기 문자열("30s", "1m", "5m", "1h", "4h", "1d", "1w")을 밀리초로 변환
+pub fn parse_timeframe(timeframe: &str) -> Result<i64, TradingError> {
+    let timeframe = timeframe.trim();
+    let invalid = || TradingError::InvalidParameter(format!("Invalid timeframe: {}", timeframe));
+    if timeframe.len() < 2 {
+        return Err(invalid());
+    }
+    let (count, unit) = timeframe.split_at(timeframe.len() - 1);
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    let unit_ms = match unit {
+        "s" => 1_000,
+        "m" => 60_000,
+        "h" => 3_600_000,
+        "d" => 86_400_000,
+        "w" => 604_800_000,
+        _ => return Err(invalid()),
+    };
+    if count <= 0 {
+        return Err(invalid());
+    }
+    Ok(count * unit_ms)
+}
+
+/// 단일 심볼 캔들 집계기
+#[derive(Debug, Clone)]
+pub struct CandleAggregator {
+    timeframe_ms: i64,
+    current: Option<MarketData>,
+    /// 마지막으로 마감한 구간 시작 시각 (이후 도착한 같은 구간 데이터는 무시)
+    last_closed: Option<Timestamp>,
+}
+
+impl CandleAggregator {
+    pub fn new(timeframe_ms: i64) -> Self {
+        CandleAggregator {
+            timeframe_ms,
+            current: None,
+            last_closed: None,
+        }
+    }
+
+    /// 주기 문자열로 생성
+    pub fn from_timeframe(timeframe: &str) -> Result<Self, TradingError> {
+        Ok(Self::new(parse_timeframe(timeframe)?))
+    }
+
+    pub fn timeframe_ms(&self) -> i64 {
+        self.timeframe_ms
+    }
+
+    /// 구간 시작 시각
+    fn bucket_start(&self, timestamp: Timestamp) -> Timestamp {
+        Timestamp::from_millis(timestamp.as_millis().div_euclid(self.timeframe_ms) * self.timeframe_ms)
+    }
+
+    /// 데이터 추가 - 새 구간으로 넘어가면 완성된 이전 캔들 반환
+    pub fn push(&mut self, data: &MarketData) -> Option<MarketData> {
+        let start = self.bucket_start(data.timestamp);
+        match self.current.as_mut() {
+            Some(candle) if candle.timestamp == start => {
+                candle.high = candle.high.max(data.high);
+                candle.low = candle.low.min(data.low);
+                candle.close = data.close;
+                candle.volume += data.volume;
+                None
+            }
+            // 이전 구간보다 늦게 도착한 데이터는 무시
+            Some(candle) if start < candle.timestamp => None,
+            None if self.last_closed.is_some_and(|closed| start <= closed) => None,
+            _ => {
+                let mut candle = data.clone();
+                candle.timestamp = start;
+                let completed = self.current.replace(candle);
+                if let Some(completed) = &completed {
+                    self.last_closed = Some(completed.timestamp);
+                }
+                completed
+            }
+        }
+    }
+
+    /// 구간이 끝난(`now_ms` >= 구간 종료) 진행 중 캔들을 마감 - 다음 틱을 기다리지 않고 캔들 완성
+    pub fn close_due(&mut self, now_ms: i64) -> Option<MarketData> {
+        let due = self.current.as_ref().is_some_and(|c| now_ms >= c.timestamp.as_millis() + self.timeframe_ms);
+        if !due {
+            return None;
+        }
+        let completed = self.current.take();
+        self.last_closed = completed.as_ref().map(|c| c.timestamp);
+        completed
+    }
+
+    /// 진행 중인 (미완성) 캔들
+    pub fn current(&self) -> Option<&MarketData> {
+        self.current.as_ref()
+    }
+}
+
+/// 24시간 롤링 티커를 최신가 점 캔들로 변환 (거래량 = 직전 티커 대비 24시간 거래량 증가분)
+pub fn ticker_point(ticker: &MarketData, previous_volume: Option<Decimal>) -> MarketData {
+    MarketData {
+        open: ticker.close,
+        high: ticker.close,
+        low: ticker.close,
+        volume: previous_volume.map(|v| (ticker.volume - v).max(Decimal::ZERO)).unwrap_or_default(),
+        ..ticker.clone()
+    }
+}
+
+/// 티커 스트림 캔들 집계 서비스 - 심볼별 태스크가 티커를 받아 주기별 완성 캔들을
+/// `MarketDataStream::publish_candle` 로 발행 (`get_candle_receiver(symbol, timeframe)` 로 구독)
+pub struct CandleAggregationService {
+    stream: Arc<RwLock<MarketDataStream>>,
+    tasks: HashMap<String, (Vec<String>, JoinHandle<()>)>,
+}
+
+impl CandleAggregationService {
+    pub fn new(stream: Arc<RwLock<MarketDataStream>>) -> Self {
+        CandleAggregationService { stream, tasks: HashMap::new() }
+    }
+
+    /// 심볼 집계 시작 (예: ["1s", "1m", "5m", "1h"])
+    pub async fn start(&mut self, symbol: &str, timeframes: &[String]) -> Result<(), TradingError> {
+        if self.tasks.contains_key(symbol) {
+            return Err(TradingError::AlreadyRunning(format!("Aggregation for {} already running", symbol)));
+        }
+        let mut aggregators = Vec::new();
+        for timeframe in timeframes {
+            if !aggregators.iter().any(|(tf, _): &(String, CandleAggregator)| tf == timeframe) {
+                aggregators.push((timeframe.clone(), CandleAggregator::from_timeframe(timeframe)?));
+            }
+        }
+        if aggregators.is_empty() {
+            return Err(TradingError::InvalidParameter(format!("No timeframes to aggregate for {}", symbol)));
+        }
+
+        let mut receiver = {
+            let mut stream = self.stream.write().await;
+            for (timeframe, _) in &aggregators {
+                stream.get_or_create_candle_channel(symbol, timeframe);
+            }
+            stream.get_or_create_channel(symbol).subscribe()
+        };
+        let stream = self.stream.clone();
+        let name = symbol.to_string();
+        let task = tokio::spawn(async move {
+            use tokio::sync::broadcast::error::RecvError;
+            let mut last_volume: Option<Decimal> = None;
+            let mut close_timer = tokio::time::interval(std::time::Duration::from_millis(250));
+            loop {
+                let completed: Vec<(String, MarketData)> = tokio::select! {
+                    received = receiver.recv() => match received {
+                        Ok(ticker) => {
+                            let point = ticker_point(&ticker, last_volume);
+                            last_volume = Some(ticker.volume);
+                            aggregators.iter_mut().filter_map(|(tf, agg)| agg.push(&point).map(|c| (tf.clone(), c))).collect()
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            log::debug!("candle aggregation {} lagged: {} tickers skipped", name, n);
+                            continue;
+                        }
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = close_timer.tick() => {
+                        let now = chrono::Utc::now().timestamp_millis() - CLOSE_GRACE_MS;
+                        aggregators.iter_mut().filter_map(|(tf, agg)| agg.close_due(now).map(|c| (tf.clone(), c))).collect()
+                    }
+                };
+                if completed.is_empty() {
+                    continue;
+                }
+                let mut stream = stream.write().await;
+                for (timeframe, candle) in completed {
+                    stream.publish_candle(&timeframe, candle);
+                }
+            }
+        });
+        self.tasks.insert(symbol.to_string(), (timeframes.to_vec(), task));
+        Ok(())
+    }
+
+    /// 심볼 집계 중지
+    pub fn stop(&mut self, symbol: &str) -> Result<(), TradingError> {
+        match self.tasks.remove(symbol) {
+            Some((_, task)) => {
+                task.abort();
+                Ok(())
+            }
+            None => Err(TradingError::TaskNotFound(format!("Aggregation task for {} not found", symbol))),
+        }
+    }
+
+    /// 집계 중인 심볼별 주기
+    pub fn timeframes(&self, symbol: &str) -> Option<&[String]> {
+        self.tasks.get(symbol).map(|(timeframes, _)| timeframes.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    fn tick(timestamp: i64, price: f64) -> MarketData {
+        MarketData::new("BTCUSDT", timestamp, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_candle_aggregation() {
+        assert_eq!(parse_timeframe("5m").unwrap(), 300_000);
+        assert_eq!(parse_timeframe("4h").unwrap(), 14_400_000);
+        assert!(parse_timeframe("0m").is_err());
+        assert!(parse_timeframe("5x").is_err());
+
+        let mut agg = CandleAggregator::from_timeframe("1m").unwrap();
+        assert!(agg.push(&tick(1_000, 100.0)).is_none());
+        assert!(agg.push(&tick(20_000, 105.0)).is_none());
+        assert!(agg.push(&tick(59_000, 98.0)).is_none());
+
+        let candle = agg.push(&tick(61_000, 101.0)).unwrap();
+        assert_eq!(candle.timestamp.as_millis(), 0);
+        assert_eq!((candle.open, candle.high, candle.low, candle.close), (dec!(100), dec!(105), dec!(98), dec!(98)));
+        assert_eq!(candle.volume, dec!(3));
+
+        // 늦게 도착한 이전 구간 데이터는 무시
+        assert!(agg.push(&tick(30_000, 200.0)).is_none());
+        assert_eq!(agg.current().unwrap().high, dec!(101));
+
+        // 거래가 없어도 구간이 끝나면 마감, 마감된 구간의 늦은 틱은 무시
+        assert!(agg.close_due(119_999).is_none());
+        assert_eq!(agg.close_due(120_000).unwrap().close, dec!(101));
+        assert!(agg.push(&tick(110_000, 300.0)).is_none());
+        assert!(agg.current().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_service_publishes_closed_candles_from_tickers() {
+        let stream = Arc::new(RwLock::new(MarketDataStream::new(16)));
+        let mut service = CandleAggregationService::new(stream.clone());
+        service.start("BTCUSDT", &["1d".to_string()]).await.unwrap();
+        assert!(service.start("BTCUSDT", &["1m".to_string()]).await.is_err());
+        let mut candles = stream.read().await.get_candle_receiver("BTCUSDT", "1d").unwrap();
+
+        // 24시간 롤링 티커: 가격은 최신가, 거래량은 누적 증가분
+        let now = chrono::Utc::now().timestamp_millis();
+        let ticker = |ts: i64, close: f64, volume_24h: f64| MarketData::new("BTCUSDT", ts, dec!(90), dec!(200), dec!(50), to_decimal(close), to_decimal(volume_24h));
+        for data in [ticker(now, 100.0, 1_000.0), ticker(now + 1, 104.0, 1_003.0), ticker(now + 86_400_000, 103.0, 1_010.0)] {
+            stream.write().await.publish(data).unwrap();
+            tokio::task::yield_now().await;
+        }
+        let candle = tokio::time::timeout(std::time::Duration::from_secs(2), candles.recv()).await.unwrap().unwrap();
+        assert_eq!((candle.open, candle.high, candle.low, candle.close, candle.volume), (dec!(100), dec!(104), dec!(100), dec!(104), dec!(3)));
+        assert_eq!(stream.read().await.get_latest_candle("BTCUSDT", "1d").unwrap().close, dec!(104));
+        service.stop("BTCUSDT").unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::exchange::traits::Exchange;
+use crate::market_data::stream::{CandleGap, MarketDataStream};
+use crate::models::market_data::MarketData;
+
+/// 빠진 캔들 조회 - 끊김이 `max_candles` 보다 길면 최근 `max_candles` 개만 채움
+pub async fn fetch_gap(exchange: &dyn Exchange, gap: &CandleGap, max_candles: usize) -> Result<Vec<MarketData>, crate::error::TradingError> {
+    let start = gap.from.max(gap.to - max_candles as i64 * gap.interval_ms);
+    exchange.get_historical_data(&gap.symbol, &gap.interval, start, Some(gap.to - 1), Some(max_candles)).await
+}
+
+/// 캔들 끊김 보충 태스크 시작 - 재연결 등으로 빠진 캔들을 REST 로 받아 순서대로 발행한 뒤 실시간 캔들을 이어 보냄
+pub async fn spawn_backfill(
+    stream: Arc<RwLock<MarketDataStream>>,
+    exchange: Arc<RwLock<dyn Exchange>>,
+    max_candles: usize,
+) -> tokio::task::JoinHandle<()> {
+    let mut gaps = stream.write().await.enable_backfill();
+    let max_candles = max_candles.max(1);
+    tokio::spawn(async move {
+        loop {
+            let gap = match gaps.recv().await {
+                Ok(gap) => gap,
+                Err(RecvError::Lagged(n)) => {
+                    log::warn!("candle backfill lagged {} gaps", n);
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let fetched = fetch_gap(&*exchange.read().await, &gap, max_candles).await;
+            let candles = fetched.unwrap_or_else(|e| {
+                log::warn!("{} {} backfill failed, resuming live candles with a hole: {}", gap.symbol, gap.interval, e);
+                Vec::new()
+            });
+            let filled = stream.write().await.complete_backfill(&gap, candles);
+            log::info!("{} {} backfilled {}/{} missing candles", gap.symbol, gap.interval, filled, gap.missing());
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::exchange::mocks::MockExchange;
+    use crate::models::decimal::to_decimal;
+
+    fn candle(minute: i64) -> MarketData {
+        let price = to_decimal(100.0 + minute as f64);
+        MarketData::new("BTCUSDT", minute * 60_000, price, price, price, price, to_decimal(1.0))
+    }
+
+    #[tokio::test]
+    async fn test_gap_is_backfilled_before_live_candles() {
+        let mut mock = MockExchange::empty(Config::default());
+        for minute in 0..10 {
+            mock.push_market_data(candle(minute)).unwrap();
+        }
+        let exchange: Arc<RwLock<dyn Exchange>> = Arc::new(RwLock::new(mock));
+        let stream = Arc::new(RwLock::new(MarketDataStream::new(100)));
+        let mut candles = stream.write().await.get_or_create_candle_channel("BTCUSDT", "1m").subscribe();
+        let _task = spawn_backfill(stream.clone(), exchange, 1000).await;
+
+        {
+            let mut s = stream.write().await;
+            s.publish_candle("1m", candle(1));
+            s.publish_candle("1m", candle(2));
+            // 재연결 후 3~5 분 캔들이 빠짐 - 6, 7 분은 보충이 끝날 때까지 보류
+            s.publish_candle("1m", candle(6));
+            s.publish_candle("1m", candle(7));
+            s.publish_candle("1m", candle(7));
+        }
+        let mut received = Vec::new();
+        while received.len() < 7 {
+            let c = tokio::time::timeout(std::time::Duration::from_secs(1), candles.recv()).await.unwrap().unwrap();
+            received.push(c.timestamp.as_millis() / 60_000);
+        }
+        assert_eq!(received, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert!(candles.try_recv().is_err());
+    }
+}
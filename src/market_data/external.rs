@@ -0,0 +1,288 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ExternalDataConfig, ExternalSourceConfig};
+use crate::error::TradingError;
+use crate::models::timestamp::Timestamp;
+
+/// 외부 수치 시계열 (심리 지수, 온체인 흐름 등)
+///
+/// 관측값은 관측 시각이 속한 캔들(시작 시각 기준)에 정렬하여 저장하고, 같은 캔들의
+/// 마지막 값만 남긴다. 조회는 해당 시각 이전의 가장 최근 값(as-of)이라 백테스트에서
+/// 미래 값을 참조하지 않는다.
+#[derive(Debug, Clone)]
+pub struct ExternalSeries {
+    name: String,
+    interval_ms: i64,
+    max_points: usize,
+    points: BTreeMap<i64, f64>,
+}
+
+impl ExternalSeries {
+    pub fn new(name: impl Into<String>, interval_ms: i64, max_points: usize) -> Self {
+        ExternalSeries {
+            name: name.into(),
+            interval_ms,
+            max_points: max_points.max(1),
+            points: BTreeMap::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 관측값 저장 - 정렬된 캔들 시각 반환
+    pub fn insert(&mut self, timestamp: Timestamp, value: f64) -> Timestamp {
+        let millis = timestamp.as_millis();
+        let aligned = if self.interval_ms > 0 { millis.div_euclid(self.interval_ms) * self.interval_ms } else { millis };
+        self.points.insert(aligned, value);
+        while self.points.len() > self.max_points {
+            self.points.pop_first();
+        }
+        Timestamp::from_millis(aligned)
+    }
+
+    /// 주어진 시각 기준 가장 최근 값
+    pub fn value_at(&self, timestamp: Timestamp) -> Option<f64> {
+        self.points.range(..=timestamp.as_millis()).next_back().map(|(_, v)| *v)
+    }
+
+    pub fn latest(&self) -> Option<(Timestamp, f64)> {
+        self.points.iter().next_back().map(|(t, v)| (Timestamp::from_millis(*t), *v))
+    }
+
+    /// 기간 내 관측값 (지표 계산 등에 사용)
+    pub fn range(&self, from: Timestamp, to: Timestamp) -> Vec<(Timestamp, f64)> {
+        self.points.range(from.as_millis()..=to.as_millis())
+            .map(|(t, v)| (Timestamp::from_millis(*t), *v))
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// `timestamp,value` 형식 CSV 로드 (timestamp 는 epoch ms 또는 RFC3339)
+    pub fn load_csv(name: impl Into<String>, path: &Path, interval_ms: i64, max_points: usize) -> Result<Self, TradingError> {
+        let mut series = ExternalSeries::new(name, interval_ms, max_points);
+        let mut rdr = csv::Reader::from_path(path).map_err(|e| TradingError::IoError(e.into()))?;
+        for rec in rdr.deserialize() {
+            let row: ExternalPoint = rec.map_err(|e| TradingError::ParseError(format!("{}: {}", path.display(), e)))?;
+            series.insert(row.timestamp, row.value);
+        }
+        Ok(series)
+    }
+}
+
+/// 외부 관측값 하나 (웹훅 수신 / CSV 행)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ExternalPoint {
+    pub timestamp: Timestamp,
+    pub value: f64,
+}
+
+/// 특정 시각 기준 외부 시계열 값 묶음 - 전략에 보조 입력으로 전달
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ExternalInputs {
+    pub timestamp: Timestamp,
+    pub values: HashMap<String, f64>,
+}
+
+impl ExternalInputs {
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.values.get(name).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// 시계열 요약 (API 응답용)
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalSeriesSummary {
+    pub name: String,
+    pub points: usize,
+    pub latest_timestamp: Option<Timestamp>,
+    pub latest_value: Option<f64>,
+}
+
+/// 이름별 외부 시계열 저장소 - 실시간(폴링/웹훅)과 백테스트(CSV)에서 공유
+#[derive(Clone, Default)]
+pub struct ExternalSeriesStore {
+    series: Arc<RwLock<HashMap<String, ExternalSeries>>>,
+    interval_ms: i64,
+    max_points: usize,
+}
+
+impl ExternalSeriesStore {
+    pub fn new(interval_ms: i64, max_points: usize) -> Self {
+        ExternalSeriesStore {
+            series: Arc::new(RwLock::new(HashMap::new())),
+            interval_ms,
+            max_points,
+        }
+    }
+
+    pub fn from_config(config: &ExternalDataConfig) -> Self {
+        Self::new(config.candle_interval_ms, config.max_points)
+    }
+
+    /// 관측값 기록 (없는 시계열은 생성)
+    pub fn record(&self, name: &str, timestamp: Timestamp, value: f64) -> Timestamp {
+        let mut series = self.series.write().unwrap_or_else(|e| e.into_inner());
+        series.entry(name.to_string())
+            .or_insert_with(|| ExternalSeries::new(name, self.interval_ms, self.max_points))
+            .insert(timestamp, value)
+    }
+
+    /// 미리 구성한 시계열 등록 (같은 이름은 교체)
+    pub fn insert_series(&self, series: ExternalSeries) {
+        let mut all = self.series.write().unwrap_or_else(|e| e.into_inner());
+        all.insert(series.name().to_string(), series);
+    }
+
+    pub fn value_at(&self, name: &str, timestamp: Timestamp) -> Option<f64> {
+        let series = self.series.read().unwrap_or_else(|e| e.into_inner());
+        series.get(name).and_then(|s| s.value_at(timestamp))
+    }
+
+    /// 주어진 시각까지 관측된 모든 시계열 값
+    pub fn inputs_at(&self, timestamp: Timestamp) -> ExternalInputs {
+        let series = self.series.read().unwrap_or_else(|e| e.into_inner());
+        let values = series.iter()
+            .filter_map(|(name, s)| s.value_at(timestamp).map(|v| (name.clone(), v)))
+            .collect();
+        ExternalInputs { timestamp, values }
+    }
+
+    pub fn range(&self, name: &str, from: Timestamp, to: Timestamp) -> Option<Vec<(Timestamp, f64)>> {
+        let series = self.series.read().unwrap_or_else(|e| e.into_inner());
+        series.get(name).map(|s| s.range(from, to))
+    }
+
+    pub fn summaries(&self) -> Vec<ExternalSeriesSummary> {
+        let series = self.series.read().unwrap_or_else(|e| e.into_inner());
+        let mut summaries: Vec<_> = series.values()
+            .map(|s| {
+                let latest = s.latest();
+                ExternalSeriesSummary {
+                    name: s.name().to_string(),
+                    points: s.len(),
+                    latest_timestamp: latest.map(|(t, _)| t),
+                    latest_value: latest.map(|(_, v)| v),
+                }
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        summaries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.read().unwrap_or_else(|e| e.into_inner()).is_empty()
+    }
+}
+
+/// 설정된 HTTP 소스를 주기적으로 조회하여 저장소에 기록
+pub fn spawn_pollers(store: ExternalSeriesStore, sources: Vec<ExternalSourceConfig>) {
+    for source in sources {
+        let store = store.clone();
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            let mut ticker = tokio::time::interval(Duration::from_millis(source.poll_interval_ms.max(1_000)));
+            loop {
+                ticker.tick().await;
+                match poll_source(&client, &source).await {
+                    Ok(point) => {
+                        store.record(&source.name, point.timestamp, point.value);
+                        log::debug!("external series {} = {} @ {}", source.name, point.value, point.timestamp);
+                    }
+                    Err(e) => log::warn!("external series {} poll failed: {}", source.name, e),
+                }
+            }
+        });
+    }
+}
+
+async fn poll_source(client: &reqwest::Client, source: &ExternalSourceConfig) -> Result<ExternalPoint, TradingError> {
+    let body: serde_json::Value = client.get(&source.url)
+        .timeout(Duration::from_secs(10))
+        .send().await
+        .map_err(|e| TradingError::DataNotFound(format!("{}: {}", source.url, e)))?
+        .error_for_status()
+        .map_err(|e| TradingError::DataNotFound(format!("{}: {}", source.url, e)))?
+        .json().await
+        .map_err(|e| TradingError::ParseError(e.to_string()))?;
+    extract_point(&body, source)
+}
+
+/// JSON 응답에서 값/시각 추출 (JSON pointer, 숫자 문자열 허용)
+/// 시각이 초 단위로 보이면(10^11 미만) 밀리초로 변환, 시각 경로가 없으면 현재 시각
+pub fn extract_point(body: &serde_json::Value, source: &ExternalSourceConfig) -> Result<ExternalPoint, TradingError> {
+    let number = |v: &serde_json::Value| v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok()));
+    let value = body.pointer(&source.value_path)
+        .and_then(number)
+        .ok_or_else(|| TradingError::ParseError(format!("no numeric value at '{}'", source.value_path)))?;
+
+    let timestamp = match &source.timestamp_path {
+        Some(path) => {
+            let raw = body.pointer(path)
+                .ok_or_else(|| TradingError::ParseError(format!("no timestamp at '{}'", path)))?;
+            let ts = match number(raw) {
+                Some(n) => Timestamp::from_millis(n as i64),
+                None => Timestamp::parse(raw.as_str().unwrap_or_default())?,
+            };
+            if ts.as_millis().abs() < 100_000_000_000 { Timestamp::from_millis(ts.as_millis() * 1000) } else { ts }
+        }
+        None => Timestamp::now(),
+    };
+
+    Ok(ExternalPoint { timestamp, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_series_alignment() {
+        let store = ExternalSeriesStore::new(60_000, 3);
+        // 같은 캔들 안의 관측값은 마지막 값으로 덮어씀
+        assert_eq!(store.record("fear_greed", Timestamp::from_millis(60_500), 20.0).as_millis(), 60_000);
+        store.record("fear_greed", Timestamp::from_millis(119_999), 25.0);
+        store.record("fear_greed", Timestamp::from_millis(180_000), 40.0);
+
+        // as-of 조회: 첫 관측 이전에는 값 없음, 비어 있는 캔들은 직전 값 유지
+        assert_eq!(store.value_at("fear_greed", Timestamp::from_millis(0)), None);
+        assert_eq!(store.value_at("fear_greed", Timestamp::from_millis(60_000)), Some(25.0));
+        assert_eq!(store.value_at("fear_greed", Timestamp::from_millis(120_000)), Some(25.0));
+        assert_eq!(store.inputs_at(Timestamp::from_millis(200_000)).get("fear_greed"), Some(40.0));
+
+        // 최대 보관 개수 초과 시 오래된 값부터 삭제
+        store.record("fear_greed", Timestamp::from_millis(240_000), 50.0);
+        store.record("fear_greed", Timestamp::from_millis(300_000), 60.0);
+        assert_eq!(store.summaries()[0].points, 3);
+        assert_eq!(store.value_at("fear_greed", Timestamp::from_millis(60_000)), None);
+
+        // 폴링 응답 파싱: 숫자 문자열 값, 초 단위 시각
+        let source = ExternalSourceConfig {
+            name: "fear_greed".into(),
+            url: String::new(),
+            value_path: "/data/0/value".into(),
+            timestamp_path: Some("/data/0/timestamp".into()),
+            poll_interval_ms: 60_000,
+        };
+        let body = serde_json::json!({"data": [{"value": "27", "timestamp": "1704067200"}]});
+        assert_eq!(extract_point(&body, &source).unwrap(), ExternalPoint { timestamp: Timestamp::from_millis(1_704_067_200_000), value: 27.0 });
+        assert!(extract_point(&serde_json::json!({"data": []}), &source).is_err());
+    }
+}
@@ -1,105 +1,222 @@
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock, Mutex};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, RwLock};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::task::JoinHandle;
-use tokio::time::{interval, Duration};
 use async_trait::async_trait;
 
+use crate::config::FixMarketDataConfig;
+use crate::market_data::fix_session::{msg_type, tags, FixConnector, FixMessage, FixSession, FixSessionEvent, TcpConnector};
 use crate::market_data::provider::MarketDataProvider;
 use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::to_decimal;
 use crate::models::market_data::MarketData;
+use crate::models::order_book::{OrderBook, PriceLevel};
 use crate::error::TradingError;
 
 /// FIX 프로토콜 기반 시장 데이터 제공자
+///
+/// 로그온할 때마다 구독 심볼의 MarketDataRequest(V) 를 다시 보내고, 스냅샷(W)/증분(X) 으로 호가창을 유지해
+/// `MarketDataStream` 에 호가창과 틱을 발행한다. 틱 거래량은 세션 시작 이후 누적 체결 수량
+/// (WebSocket 티커의 누적 거래량과 같은 의미라 캔들 집계가 증가분으로 계산)이다.
 pub struct FixProvider {
-    host: String,
-    port: u16,
-    sender_comp_id: String,
-    target_comp_id: String,
+    config: FixMarketDataConfig,
+    connector: Arc<dyn FixConnector>,
     stream: Arc<RwLock<MarketDataStream>>,
-    subscriptions: HashMap<String, String>,
+    /// 심볼 -> MDReqID
+    subscriptions: Arc<Mutex<HashMap<String, String>>>,
+    session: Option<FixSession>,
     connected: bool,
     fix_task: Option<JoinHandle<()>>,
-    session_id: String,
+    feed_task: Option<JoinHandle<()>>,
+}
+
+/// 심볼별 호가창/누적 거래량 (스냅샷과 증분 갱신 반영)
+#[derive(Default)]
+struct FixBookState {
+    books: HashMap<String, OrderBook>,
+    volumes: HashMap<String, f64>,
+}
+
+// MDEntry 한 건 (0 매수 호가, 1 매도 호가, 2 체결)
+struct MdEntry {
+    symbol: Option<String>,
+    entry_type: String,
+    price: f64,
+    size: f64,
+    delete: bool,
+}
+
+fn md_entries(message: &FixMessage, delimiter_tag: u32) -> Vec<MdEntry> {
+    message.groups(tags::NO_MD_ENTRIES, delimiter_tag).into_iter().filter_map(|group| {
+        let get = |tag: u32| group.iter().find(|(t, _)| *t == tag).map(|(_, v)| v.as_str());
+        Some(MdEntry {
+            symbol: get(tags::SYMBOL).map(str::to_string),
+            entry_type: get(tags::MD_ENTRY_TYPE)?.to_string(),
+            price: get(tags::MD_ENTRY_PX)?.parse().ok()?,
+            size: get(tags::MD_ENTRY_SIZE).and_then(|s| s.parse().ok()).unwrap_or(0.0),
+            delete: get(tags::MD_UPDATE_ACTION) == Some("2"),
+        })
+    }).collect()
+}
+
+impl FixBookState {
+    /// 스냅샷(W) / 증분(X) 반영 - 바뀐 심볼의 (호가창, 틱) 반환
+    fn apply(&mut self, message: &FixMessage, req_symbols: &HashMap<String, String>, depth: usize, now: i64) -> Vec<(OrderBook, Option<MarketData>)> {
+        let snapshot = message.msg_type == msg_type::MARKET_DATA_SNAPSHOT;
+        let default_symbol = message.get(tags::SYMBOL).map(str::to_string)
+            .or_else(|| message.get(tags::MD_REQ_ID).and_then(|id| req_symbols.get(id).cloned()));
+        let entries = md_entries(message, if snapshot { tags::MD_ENTRY_TYPE } else { tags::MD_UPDATE_ACTION });
+
+        let mut touched: Vec<String> = Vec::new();
+        let mut trades: HashMap<String, f64> = HashMap::new();
+        let mut updates: HashMap<String, (Vec<PriceLevel>, Vec<PriceLevel>)> = HashMap::new();
+        let mut symbol = default_symbol;
+        for entry in entries {
+            if entry.symbol.is_some() {
+                symbol = entry.symbol.clone();
+            }
+            let Some(symbol) = symbol.clone() else { continue };
+            if !touched.contains(&symbol) {
+                touched.push(symbol.clone());
+            }
+            let level = PriceLevel::new(entry.price, if entry.delete { 0.0 } else { entry.size });
+            match entry.entry_type.as_str() {
+                "0" => updates.entry(symbol).or_default().0.push(level),
+                "1" => updates.entry(symbol).or_default().1.push(level),
+                "2" if !entry.delete => {
+                    *self.volumes.entry(symbol.clone()).or_default() += entry.size;
+                    trades.insert(symbol, entry.price);
+                }
+                _ => {}
+            }
+        }
+
+        touched.into_iter().map(|symbol| {
+            let (bids, asks) = updates.remove(&symbol).unwrap_or_default();
+            let book = self.books.entry(symbol.clone()).or_insert_with(|| OrderBook::new(symbol.clone(), now, Vec::new(), Vec::new()));
+            if snapshot {
+                *book = OrderBook::new(symbol.clone(), now, bids, asks);
+            } else {
+                book.apply_update(now, &bids, &asks);
+            }
+            if depth > 0 {
+                book.truncate(depth);
+            }
+            // 체결이 없으면 중간가 틱
+            let tick = trades.get(&symbol).copied().or_else(|| book.mid_price()).map(|price| {
+                let price = to_decimal(price);
+                let volume = to_decimal(self.volumes.get(&symbol).copied().unwrap_or_default());
+                MarketData::new(symbol.clone(), now, price, price, price, price, volume)
+            });
+            (book.clone(), tick)
+        }).collect()
+    }
 }
 
 impl FixProvider {
-    pub fn new(
-        host: impl Into<String>,
-        port: u16,
-        sender_comp_id: impl Into<String>,
-        target_comp_id: impl Into<String>,
-        stream: Arc<RwLock<MarketDataStream>>,
-    ) -> Self {
-        let sender = sender_comp_id.into();
-        let target = target_comp_id.into();
-        
+    pub fn new(config: FixMarketDataConfig, stream: Arc<RwLock<MarketDataStream>>) -> Self {
+        let connector = Arc::new(TcpConnector::new(&config.session.host, config.session.port));
         FixProvider {
-            host: host.into(),
-            port,
-            sender_comp_id: sender.clone(),
-            target_comp_id: target.clone(),
+            config,
+            connector,
             stream,
-            subscriptions: HashMap::new(),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+            session: None,
             connected: false,
             fix_task: None,
-            session_id: format!("FIX.4.4:{}:{}", sender, target),
+            feed_task: None,
+        }
+    }
+
+    /// 전송 계층 교체 (TLS 터널, 테스트용 스트림 등)
+    pub fn with_connector(mut self, connector: Arc<dyn FixConnector>) -> Self {
+        self.connector = connector;
+        self
+    }
+
+    fn market_data_request(config: &FixMarketDataConfig, req_id: &str, symbol: &str, subscribe: bool) -> FixMessage {
+        let mut request = FixMessage::new(msg_type::MARKET_DATA_REQUEST)
+            .with(tags::MD_REQ_ID, req_id)
+            // 1: 스냅샷 + 갱신 구독, 2: 구독 해제
+            .with(tags::SUBSCRIPTION_REQUEST_TYPE, if subscribe { 1 } else { 2 })
+            .with(tags::MARKET_DEPTH, config.market_depth);
+        if subscribe {
+            request.push(tags::MD_UPDATE_TYPE, if config.incremental { 1 } else { 0 });
+        }
+        request.push(tags::NO_MD_ENTRY_TYPES, config.entry_types.len());
+        for entry_type in &config.entry_types {
+            request.push(tags::MD_ENTRY_TYPE, entry_type);
         }
+        request.with(tags::NO_RELATED_SYM, 1).with(tags::SYMBOL, symbol)
     }
-    
-    // FIX 연결 시작 (실제 구현은 FIX 라이브러리에 따라 다름)
+
+    // FIX 세션 시작 + 수신 메시지를 스트림으로 발행하는 태스크
     async fn start_fix_session(&mut self) -> Result<(), TradingError> {
-        // 여기서는 FIX 세션 시뮬레이션만 구현
-        // 실제로는 FIX 라이브러리를 사용하여 구현해야 함
-        
-        let stream_clone = self.stream.clone();
-        let host = self.host.clone();
-        let port = self.port;
-        let session_id = self.session_id.clone();
-        let subscriptions_clone = self.subscriptions.clone();
-        
-        let fix_task = tokio::spawn(async move {
-            log::info!("Starting FIX session: {}", session_id);
-            
-            // 로그온 과정 시뮬레이션
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            
-            // 시장 데이터 요청 시뮬레이션
-            for (symbol, _) in &subscriptions_clone {
-                log::info!("Subscribing to {} via FIX", symbol);
-                // 실제로는 FIX 요청 메시지 전송
-            }
-            
-            // 데이터 수신 시뮬레이션
-            let mut interval_timer = interval(Duration::from_millis(100));
+        let session = FixSession::new(self.config.session.clone());
+        let mut events = session.subscribe();
+        self.fix_task = Some(session.start(self.connector.clone()));
+
+        let stream = self.stream.clone();
+        let subscriptions = self.subscriptions.clone();
+        let config = self.config.clone();
+        let feed_session = session.clone();
+        let feed_task = tokio::spawn(async move {
+            let mut state = FixBookState::default();
             loop {
-                interval_timer.tick().await;
-                
-                for (symbol, _) in &subscriptions_clone {
-                    // 시뮬레이션된 시장 데이터 생성
-                    let now = chrono::Utc::now();
-                    let price = 50000.0 + (rand::random::<f64>() * 1000.0 - 500.0);
-                    
-                    let market_data = MarketData {
-                        symbol: symbol.clone(),
-                        timestamp: now.timestamp_millis(),
-                        open: price - 10.0,
-                        high: price + 20.0,
-                        low: price - 20.0,
-                        close: price,
-                        volume: rand::random::<f64>() * 10.0,
-                    };
-                    
-                    // 데이터 스트림에 발행
-                    let mut stream = stream_clone.write().await;
-                    let _ = stream.publish(market_data);
+                let message = match events.recv().await {
+                    Ok(FixSessionEvent::LoggedOn) => {
+                        // 새 세션에는 이전 구독이 없으므로 다시 요청
+                        let requests: Vec<(String, String)> = subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+                            .iter().map(|(s, id)| (s.clone(), id.clone())).collect();
+                        for (symbol, req_id) in requests {
+                            log::info!("Subscribing to {} via FIX", symbol);
+                            if let Err(e) = feed_session.send(Self::market_data_request(&config, &req_id, &symbol, true)) {
+                                log::warn!("FIX market data request for {} failed: {}", symbol, e);
+                            }
+                        }
+                        continue;
+                    }
+                    Ok(FixSessionEvent::LoggedOut { reason }) => {
+                        log::warn!("FIX market data session logged out: {}", reason);
+                        continue;
+                    }
+                    Ok(FixSessionEvent::Message(message)) => message,
+                    Err(RecvError::Lagged(n)) => {
+                        log::warn!("FIX market data feed lagged {} messages", n);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                match message.msg_type.as_str() {
+                    msg_type::MARKET_DATA_SNAPSHOT | msg_type::MARKET_DATA_INCREMENTAL => {
+                        let req_symbols: HashMap<String, String> = subscriptions.lock().unwrap_or_else(|e| e.into_inner())
+                            .iter().map(|(s, id)| (id.clone(), s.clone())).collect();
+                        let now = chrono::Utc::now().timestamp_millis();
+                        let updates = state.apply(&message, &req_symbols, config.market_depth as usize, now);
+                        let mut stream = stream.write().await;
+                        for (book, tick) in updates {
+                            stream.publish_order_book(book);
+                            if let Some(tick) = tick {
+                                let _ = stream.publish(tick);
+                            }
+                        }
+                    }
+                    msg_type::MARKET_DATA_REQUEST_REJECT => log::warn!(
+                        "FIX market data request {} rejected (reason {}): {}",
+                        message.get(tags::MD_REQ_ID).unwrap_or("?"),
+                        message.get(tags::MD_REQ_REJ_REASON).unwrap_or("?"),
+                        message.get(tags::TEXT).unwrap_or(""),
+                    ),
+                    other => log::debug!("FIX market data session ignored MsgType {}", other),
                 }
             }
         });
-        
-        self.fix_task = Some(fix_task);
+
+        self.session = Some(session);
+        self.feed_task = Some(feed_task);
         self.connected = true;
-        
+
         Ok(())
     }
 }
@@ -111,15 +228,21 @@ impl MarketDataProvider for FixProvider {
             return Err(TradingError::NotConnected);
         }
 
-        if self.subscriptions.contains_key(symbol) {
-            return Ok(());
-        }
-
-        // 구독 ID 생성
-        let sub_id = format!("fix_sub_{}", rand::random::<u64>());
+        let req_id = {
+            let mut subscriptions = self.subscriptions.lock().unwrap_or_else(|e| e.into_inner());
+            if subscriptions.contains_key(symbol) {
+                return Ok(());
+            }
+            // 구독 ID 생성
+            let req_id = format!("fix_sub_{}", rand::random::<u64>());
+            subscriptions.insert(symbol.to_string(), req_id.clone());
+            req_id
+        };
 
-        // 실제 FIX 구독 메시지는 start_fix_session에서 처리
-        self.subscriptions.insert(symbol.to_string(), sub_id);
+        // 로그온 전이면 로그온 직후 요청
+        if let Some(session) = self.session.as_ref().filter(|s| s.is_logged_on()) {
+            session.send(Self::market_data_request(&self.config, &req_id, symbol, true))?;
+        }
 
         Ok(())
     }
@@ -129,15 +252,16 @@ impl MarketDataProvider for FixProvider {
             return Err(TradingError::NotConnected);
         }
 
-        if let Some(_) = self.subscriptions.remove(symbol) {
-            // 실제 FIX 구독 해제 메시지 전송
+        let removed = self.subscriptions.lock().unwrap_or_else(|e| e.into_inner()).remove(symbol);
+        if let (Some(req_id), Some(session)) = (removed, self.session.as_ref().filter(|s| s.is_logged_on())) {
+            session.send(Self::market_data_request(&self.config, &req_id, symbol, false))?;
         }
 
         Ok(())
     }
 
     fn get_receiver(&self, symbol: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
-        if !self.subscriptions.contains_key(symbol) {
+        if !self.subscriptions.lock().unwrap_or_else(|e| e.into_inner()).contains_key(symbol) {
             return Err(TradingError::NotSubscribed(symbol.to_string()));
         }
 
@@ -159,8 +283,13 @@ impl MarketDataProvider for FixProvider {
         }
     }
 
+    async fn get_current_order_book(&self, symbol: &str) -> Result<OrderBook, TradingError> {
+        self.stream.read().await.get_latest_order_book(symbol)
+            .ok_or_else(|| TradingError::DataNotFound(format!("order book for {}", symbol)))
+    }
+
     async fn is_connected(&self) -> bool {
-        self.connected
+        self.connected && self.session.as_ref().is_some_and(|s| s.is_logged_on())
     }
 
     async fn connect(&mut self) -> Result<(), TradingError> {
@@ -176,13 +305,50 @@ impl MarketDataProvider for FixProvider {
             return Ok(());
         }
 
-        if let Some(task) = self.fix_task.take() {
+        // 세션 태스크는 로그아웃을 보내고 스스로 종료
+        if let Some(session) = self.session.take() {
+            session.stop();
+        }
+        self.fix_task.take();
+        if let Some(task) = self.feed_task.take() {
             task.abort();
         }
 
         self.connected = false;
-        self.subscriptions.clear();
+        self.subscriptions.lock().unwrap_or_else(|e| e.into_inner()).clear();
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_and_incremental_refresh() {
+        let mut state = FixBookState::default();
+        let req_symbols = HashMap::from([("md-1".to_string(), "BTCUSDT".to_string())]);
+        let snapshot = FixMessage::new(msg_type::MARKET_DATA_SNAPSHOT)
+            .with(tags::MD_REQ_ID, "md-1")
+            .with(tags::NO_MD_ENTRIES, 3)
+            .with(tags::MD_ENTRY_TYPE, 0).with(tags::MD_ENTRY_PX, "99").with(tags::MD_ENTRY_SIZE, "2")
+            .with(tags::MD_ENTRY_TYPE, 1).with(tags::MD_ENTRY_PX, "101").with(tags::MD_ENTRY_SIZE, "1")
+            .with(tags::MD_ENTRY_TYPE, 2).with(tags::MD_ENTRY_PX, "100.5").with(tags::MD_ENTRY_SIZE, "0.3");
+        let updates = state.apply(&snapshot, &req_symbols, 10, 1_000);
+        let (book, tick) = &updates[0];
+        assert_eq!((book.symbol.as_str(), book.best_bid().unwrap().price, book.best_ask().unwrap().price), ("BTCUSDT", 99.0, 101.0));
+        assert_eq!(tick.as_ref().map(|t| (t.close_f64(), t.volume)), Some((100.5, to_decimal(0.3))));
+
+        // 증분: 99 매수 호가 삭제, 100 새 호가, 체결 누적 거래량
+        let incremental = FixMessage::new(msg_type::MARKET_DATA_INCREMENTAL)
+            .with(tags::NO_MD_ENTRIES, 3)
+            .with(tags::MD_UPDATE_ACTION, 2).with(tags::MD_ENTRY_TYPE, 0).with(tags::SYMBOL, "BTCUSDT").with(tags::MD_ENTRY_PX, "99")
+            .with(tags::MD_UPDATE_ACTION, 0).with(tags::MD_ENTRY_TYPE, 0).with(tags::MD_ENTRY_PX, "100").with(tags::MD_ENTRY_SIZE, "5")
+            .with(tags::MD_UPDATE_ACTION, 0).with(tags::MD_ENTRY_TYPE, 2).with(tags::MD_ENTRY_PX, "100.8").with(tags::MD_ENTRY_SIZE, "0.2");
+        let updates = state.apply(&incremental, &req_symbols, 10, 2_000);
+        let (book, tick) = &updates[0];
+        assert_eq!((book.bids.len(), book.best_bid().unwrap().price, book.timestamp), (1, 100.0, 2_000));
+        assert_eq!(tick.as_ref().map(|t| (t.close_f64(), t.volume)), Some((100.8, to_decimal(0.5))));
+    }
+}
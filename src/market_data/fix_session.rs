@@ -0,0 +1,688 @@
+//! FIX 세션 계층
+//!
+//! 태그=값 메시지 인코딩/디코딩(BodyLength, CheckSum 검증)과 세션 관리(로그온, 하트비트/TestRequest,
+//! 송수신 시퀀스 번호, ResendRequest 에 대한 GapFill, 로그아웃, 재연결)를 담당한다.
+//! 전송 계층은 `FixConnector` 로 바꿔 끼울 수 있고(기본 TCP), 애플리케이션 메시지는 `FixSession::send` 로 보내고
+//! `subscribe` 로 받는다. 시장 데이터 제공자(`market_data::fix`)와 FIX 주문 게이트웨이가 함께 사용한다.
+//!
+//! 보낸 애플리케이션 메시지는 저장하지 않으므로 상대의 ResendRequest 에는 항상 SequenceReset-GapFill 로 답한다.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{broadcast, mpsc, watch};
+
+use crate::config::FixSessionConfig;
+use crate::error::TradingError;
+use crate::models::decimal::Decimal;
+
+/// 필드 구분자
+pub const SOH: char = '\x01';
+
+/// 자주 쓰는 태그 번호
+pub mod tags {
+    pub const ACCOUNT: u32 = 1;
+    pub const AVG_PX: u32 = 6;
+    pub const BEGIN_SEQ_NO: u32 = 7;
+    pub const BEGIN_STRING: u32 = 8;
+    pub const BODY_LENGTH: u32 = 9;
+    pub const CHECKSUM: u32 = 10;
+    pub const CL_ORD_ID: u32 = 11;
+    pub const COMMISSION: u32 = 12;
+    pub const CUM_QTY: u32 = 14;
+    pub const END_SEQ_NO: u32 = 16;
+    pub const EXEC_ID: u32 = 17;
+    pub const HANDL_INST: u32 = 21;
+    pub const LAST_PX: u32 = 31;
+    pub const LAST_QTY: u32 = 32;
+    pub const MSG_SEQ_NUM: u32 = 34;
+    pub const MSG_TYPE: u32 = 35;
+    pub const NEW_SEQ_NO: u32 = 36;
+    pub const ORDER_ID: u32 = 37;
+    pub const ORDER_QTY: u32 = 38;
+    pub const ORD_STATUS: u32 = 39;
+    pub const ORD_TYPE: u32 = 40;
+    pub const ORIG_CL_ORD_ID: u32 = 41;
+    pub const POSS_DUP_FLAG: u32 = 43;
+    pub const PRICE: u32 = 44;
+    pub const REF_SEQ_NUM: u32 = 45;
+    pub const SENDER_COMP_ID: u32 = 49;
+    pub const SENDING_TIME: u32 = 52;
+    pub const SIDE: u32 = 54;
+    pub const SYMBOL: u32 = 55;
+    pub const TARGET_COMP_ID: u32 = 56;
+    pub const TEXT: u32 = 58;
+    pub const TIME_IN_FORCE: u32 = 59;
+    pub const TRANSACT_TIME: u32 = 60;
+    pub const ENCRYPT_METHOD: u32 = 98;
+    pub const STOP_PX: u32 = 99;
+    pub const CXL_REJ_REASON: u32 = 102;
+    pub const ORD_REJ_REASON: u32 = 103;
+    pub const HEART_BT_INT: u32 = 108;
+    pub const TEST_REQ_ID: u32 = 112;
+    pub const GAP_FILL_FLAG: u32 = 123;
+    pub const RESET_SEQ_NUM_FLAG: u32 = 141;
+    pub const NO_RELATED_SYM: u32 = 146;
+    pub const EXEC_TYPE: u32 = 150;
+    pub const LEAVES_QTY: u32 = 151;
+    pub const MD_REQ_ID: u32 = 262;
+    pub const SUBSCRIPTION_REQUEST_TYPE: u32 = 263;
+    pub const MARKET_DEPTH: u32 = 264;
+    pub const MD_UPDATE_TYPE: u32 = 265;
+    pub const NO_MD_ENTRY_TYPES: u32 = 267;
+    pub const NO_MD_ENTRIES: u32 = 268;
+    pub const MD_ENTRY_TYPE: u32 = 269;
+    pub const MD_ENTRY_PX: u32 = 270;
+    pub const MD_ENTRY_SIZE: u32 = 271;
+    pub const MD_UPDATE_ACTION: u32 = 279;
+    pub const MD_REQ_REJ_REASON: u32 = 281;
+    pub const USERNAME: u32 = 553;
+    pub const PASSWORD: u32 = 554;
+    pub const LAST_LIQUIDITY_IND: u32 = 851;
+}
+
+/// 메시지 종류 (MsgType, 35)
+pub mod msg_type {
+    pub const HEARTBEAT: &str = "0";
+    pub const TEST_REQUEST: &str = "1";
+    pub const RESEND_REQUEST: &str = "2";
+    pub const REJECT: &str = "3";
+    pub const SEQUENCE_RESET: &str = "4";
+    pub const LOGOUT: &str = "5";
+    pub const EXECUTION_REPORT: &str = "8";
+    pub const ORDER_CANCEL_REJECT: &str = "9";
+    pub const LOGON: &str = "A";
+    pub const NEW_ORDER_SINGLE: &str = "D";
+    pub const ORDER_CANCEL_REQUEST: &str = "F";
+    pub const ORDER_CANCEL_REPLACE_REQUEST: &str = "G";
+    pub const ORDER_STATUS_REQUEST: &str = "H";
+    pub const MARKET_DATA_REQUEST: &str = "V";
+    pub const MARKET_DATA_SNAPSHOT: &str = "W";
+    pub const MARKET_DATA_INCREMENTAL: &str = "X";
+    pub const MARKET_DATA_REQUEST_REJECT: &str = "Y";
+}
+
+// 세션 계층이 채우는 표준 헤더/트레일러 태그
+const SESSION_TAGS: [u32; 8] = [
+    tags::BEGIN_STRING, tags::BODY_LENGTH, tags::MSG_TYPE, tags::SENDER_COMP_ID,
+    tags::TARGET_COMP_ID, tags::MSG_SEQ_NUM, tags::SENDING_TIME, tags::CHECKSUM,
+];
+
+/// FIX 메시지 (MsgType + 순서가 유지되는 태그=값 필드)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FixMessage {
+    pub msg_type: String,
+    /// 수신 메시지는 헤더 필드(34, 49, 52 ...)도 포함, 송신 메시지는 본문 필드만
+    pub fields: Vec<(u32, String)>,
+}
+
+impl FixMessage {
+    pub fn new(msg_type: impl Into<String>) -> Self {
+        FixMessage { msg_type: msg_type.into(), fields: Vec::new() }
+    }
+
+    pub fn with(mut self, tag: u32, value: impl ToString) -> Self {
+        self.push(tag, value);
+        self
+    }
+
+    pub fn push(&mut self, tag: u32, value: impl ToString) {
+        self.fields.push((tag, value.to_string()));
+    }
+
+    /// 태그의 첫 값
+    pub fn get(&self, tag: u32) -> Option<&str> {
+        self.fields.iter().find(|(t, _)| *t == tag).map(|(_, v)| v.as_str())
+    }
+
+    /// 가격/수량 태그 값 (문자열 그대로 정확히 변환)
+    pub fn get_decimal(&self, tag: u32) -> Option<Decimal> {
+        self.get(tag).and_then(|v| v.parse().ok())
+    }
+
+    pub fn seq_num(&self) -> Option<u64> {
+        self.get(tags::MSG_SEQ_NUM).and_then(|v| v.parse().ok())
+    }
+
+    fn flag(&self, tag: u32) -> bool {
+        self.get(tag) == Some("Y")
+    }
+
+    /// 반복 그룹 - `count_tag` 뒤의 필드를 `delimiter_tag` 가 나올 때마다 새 항목으로 나눔
+    /// (마지막 항목은 메시지 끝까지 포함)
+    pub fn groups(&self, count_tag: u32, delimiter_tag: u32) -> Vec<Vec<(u32, String)>> {
+        let Some(start) = self.fields.iter().position(|(t, _)| *t == count_tag) else {
+            return Vec::new();
+        };
+        let mut groups: Vec<Vec<(u32, String)>> = Vec::new();
+        for field in &self.fields[start + 1..] {
+            if field.0 == delimiter_tag {
+                groups.push(Vec::new());
+            }
+            if let Some(group) = groups.last_mut() {
+                group.push(field.clone());
+            }
+        }
+        groups
+    }
+
+    /// 헤더/트레일러를 붙여 전송 형식으로 인코딩
+    pub fn encode(&self, begin_string: &str, sender: &str, target: &str, seq: u64, sending_time: &str) -> String {
+        let mut body = format!(
+            "{}={}{SOH}{}={}{SOH}{}={}{SOH}{}={}{SOH}{}={}{SOH}",
+            tags::MSG_TYPE, self.msg_type, tags::SENDER_COMP_ID, sender, tags::TARGET_COMP_ID, target,
+            tags::MSG_SEQ_NUM, seq, tags::SENDING_TIME, sending_time,
+        );
+        for (tag, value) in self.fields.iter().filter(|(t, _)| !SESSION_TAGS.contains(t)) {
+            body.push_str(&format!("{}={}{SOH}", tag, value));
+        }
+        let head = format!("{}={}{SOH}{}={}{SOH}", tags::BEGIN_STRING, begin_string, tags::BODY_LENGTH, body.len());
+        let checksum = head.bytes().chain(body.bytes()).fold(0u32, |sum, b| sum + b as u32) % 256;
+        format!("{}{}{}={:03}{SOH}", head, body, tags::CHECKSUM, checksum)
+    }
+
+    /// 완전한 프레임 하나를 디코딩 (BodyLength / CheckSum 검증)
+    pub fn decode(frame: &[u8]) -> Result<FixMessage, TradingError> {
+        let text = std::str::from_utf8(frame).map_err(|e| TradingError::ParseError(format!("FIX frame is not UTF-8: {}", e)))?;
+        let checksum_at = text.rfind(&format!("{SOH}{}=", tags::CHECKSUM))
+            .ok_or_else(|| TradingError::ParseError("FIX frame has no CheckSum".to_string()))? + 1;
+        let expected = frame[..checksum_at].iter().fold(0u32, |sum, b| sum + *b as u32) % 256;
+        let declared: u32 = text[checksum_at + 3..].trim_end_matches(SOH).parse()
+            .map_err(|_| TradingError::ParseError("FIX CheckSum is not numeric".to_string()))?;
+        if expected != declared {
+            return Err(TradingError::ParseError(format!("FIX CheckSum mismatch: {} != {}", declared, expected)));
+        }
+
+        let mut message = FixMessage::default();
+        for field in text[..checksum_at].split(SOH).filter(|f| !f.is_empty()) {
+            let (tag, value) = field.split_once('=')
+                .ok_or_else(|| TradingError::ParseError(format!("malformed FIX field '{}'", field)))?;
+            let tag: u32 = tag.parse().map_err(|_| TradingError::ParseError(format!("malformed FIX tag '{}'", tag)))?;
+            match tag {
+                tags::BEGIN_STRING | tags::BODY_LENGTH => {}
+                tags::MSG_TYPE => message.msg_type = value.to_string(),
+                _ => message.push(tag, value),
+            }
+        }
+        if message.msg_type.is_empty() {
+            return Err(TradingError::ParseError("FIX message has no MsgType".to_string()));
+        }
+        Ok(message)
+    }
+
+    /// 버퍼 앞의 완전한 프레임 하나를 떼어냄 (아직 덜 받았으면 None)
+    pub fn take_frame(buf: &mut Vec<u8>) -> Result<Option<Vec<u8>>, TradingError> {
+        let Some(start) = buf.windows(2).position(|w| w == b"8=") else {
+            return Ok(None);
+        };
+        buf.drain(..start);
+        let Some(begin_end) = buf.iter().position(|b| *b == SOH as u8) else {
+            return Ok(None);
+        };
+        let Some(length_end) = buf[begin_end + 1..].iter().position(|b| *b == SOH as u8).map(|i| begin_end + 1 + i) else {
+            return Ok(None);
+        };
+        let length_field = String::from_utf8_lossy(&buf[begin_end + 1..length_end]).to_string();
+        let Some(body_length) = length_field.strip_prefix("9=").and_then(|l| l.parse::<usize>().ok()) else {
+            // 다음 프레임부터 다시 찾도록 깨진 시작 부분 버림
+            buf.drain(..begin_end + 1);
+            return Err(TradingError::ParseError(format!("bad FIX BodyLength field '{}'", length_field)));
+        };
+        // 본문 뒤 "10=NNN<SOH>"
+        let total = length_end + 1 + body_length + 7;
+        if buf.len() < total {
+            return Ok(None);
+        }
+        Ok(Some(buf.drain(..total).collect()))
+    }
+}
+
+/// FIX 전송 스트림 (TCP, TLS 터널, 테스트용 메모리 스트림 등)
+pub trait FixStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> FixStream for T {}
+
+/// 세션마다 새 전송 스트림을 여는 연결기 (재연결 시 다시 호출)
+#[async_trait]
+pub trait FixConnector: Send + Sync {
+    async fn connect(&self) -> Result<Box<dyn FixStream>, TradingError>;
+}
+
+/// 평문 TCP 연결기
+pub struct TcpConnector {
+    addr: String,
+}
+
+impl TcpConnector {
+    pub fn new(host: &str, port: u16) -> Self {
+        TcpConnector { addr: format!("{}:{}", host, port) }
+    }
+}
+
+#[async_trait]
+impl FixConnector for TcpConnector {
+    async fn connect(&self) -> Result<Box<dyn FixStream>, TradingError> {
+        let stream = tokio::net::TcpStream::connect(&self.addr).await
+            .map_err(|e| TradingError::NetworkTimeout(format!("FIX connect {} failed: {}", self.addr, e)))?;
+        stream.set_nodelay(true)?;
+        Ok(Box::new(stream))
+    }
+}
+
+/// 세션 이벤트
+#[derive(Debug, Clone)]
+pub enum FixSessionEvent {
+    /// 로그온 완료 - 구독/주문 상태 요청을 다시 보낼 시점
+    LoggedOn,
+    LoggedOut { reason: String },
+    /// 애플리케이션 메시지 (세션 계층 메시지는 내부에서 처리하고 Reject 는 함께 전달)
+    Message(FixMessage),
+}
+
+struct SeqNums {
+    /// 다음에 보낼 번호
+    outgoing: u64,
+    /// 다음에 받을 번호
+    incoming: u64,
+}
+
+/// FIX 세션 - 세션 태스크와 사용하는 쪽이 같은 핸들을 공유
+#[derive(Clone)]
+pub struct FixSession {
+    config: FixSessionConfig,
+    seq: Arc<Mutex<SeqNums>>,
+    logged_on: Arc<AtomicBool>,
+    events: broadcast::Sender<FixSessionEvent>,
+    outbound: mpsc::UnboundedSender<FixMessage>,
+    outbound_rx: Arc<Mutex<Option<mpsc::UnboundedReceiver<FixMessage>>>>,
+    stop: Arc<watch::Sender<bool>>,
+}
+
+/// 현재 시각 (UTCTimestamp, SendingTime/TransactTime 형식)
+pub fn sending_time() -> String {
+    chrono::Utc::now().format("%Y%m%d-%H:%M:%S%.3f").to_string()
+}
+
+/// UTCTimestamp(`20250101-00:00:00[.sss]`) -> epoch ms
+pub fn parse_utc_timestamp(value: &str) -> Option<i64> {
+    chrono::NaiveDateTime::parse_from_str(value, "%Y%m%d-%H:%M:%S%.f").ok().map(|t| t.and_utc().timestamp_millis())
+}
+
+impl FixSession {
+    pub fn new(config: FixSessionConfig) -> Self {
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+        FixSession {
+            config,
+            seq: Arc::new(Mutex::new(SeqNums { outgoing: 1, incoming: 1 })),
+            logged_on: Arc::new(AtomicBool::new(false)),
+            events: broadcast::channel(1024).0,
+            outbound,
+            outbound_rx: Arc::new(Mutex::new(Some(outbound_rx))),
+            stop: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    pub fn config(&self) -> &FixSessionConfig {
+        &self.config
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<FixSessionEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn is_logged_on(&self) -> bool {
+        self.logged_on.load(Ordering::SeqCst)
+    }
+
+    /// (다음 송신 번호, 다음 수신 번호)
+    pub fn seq_nums(&self) -> (u64, u64) {
+        let seq = self.seq.lock().unwrap_or_else(|e| e.into_inner());
+        (seq.outgoing, seq.incoming)
+    }
+
+    /// 애플리케이션 메시지 전송 (로그온 전에는 NotConnected)
+    pub fn send(&self, message: FixMessage) -> Result<(), TradingError> {
+        if !self.is_logged_on() {
+            return Err(TradingError::NotConnected);
+        }
+        self.outbound.send(message).map_err(|_| TradingError::NotConnected)
+    }
+
+    /// 로그아웃 후 세션 태스크 종료 (재연결하지 않음)
+    pub fn stop(&self) {
+        let _ = self.stop.send(true);
+    }
+
+    /// 세션 태스크 시작 - 끊기면 `reconnect_delay_ms` 뒤 다시 연결하고 로그온
+    pub fn start(&self, connector: Arc<dyn FixConnector>) -> tokio::task::JoinHandle<()> {
+        let session = self.clone();
+        let outbound_rx = self.outbound_rx.lock().unwrap_or_else(|e| e.into_inner()).take();
+        tokio::spawn(async move {
+            let Some(mut outbound_rx) = outbound_rx else {
+                log::error!("FIX session {} already started", session.config.sender_comp_id);
+                return;
+            };
+            let mut stop = session.stop.subscribe();
+            while !*stop.borrow() {
+                let reason = match connector.connect().await {
+                    Ok(stream) => session.run_connection(stream, &mut outbound_rx, &mut stop).await,
+                    Err(e) => e.to_string(),
+                };
+                if session.logged_on.swap(false, Ordering::SeqCst) {
+                    let _ = session.events.send(FixSessionEvent::LoggedOut { reason: reason.clone() });
+                }
+                if *stop.borrow() {
+                    break;
+                }
+                log::warn!("FIX session {}->{} down ({}), reconnecting in {}ms",
+                    session.config.sender_comp_id, session.config.target_comp_id, reason, session.config.reconnect_delay_ms);
+                tokio::select! {
+                    _ = tokio::time::sleep(Duration::from_millis(session.config.reconnect_delay_ms)) => {}
+                    _ = stop.changed() => {}
+                }
+            }
+        })
+    }
+
+    async fn write<W: AsyncWrite + Unpin>(&self, writer: &mut W, message: &FixMessage, seq_override: Option<u64>) -> Result<(), TradingError> {
+        let seq = {
+            let mut seq = self.seq.lock().unwrap_or_else(|e| e.into_inner());
+            match seq_override {
+                Some(n) => n,
+                None => {
+                    seq.outgoing += 1;
+                    seq.outgoing - 1
+                }
+            }
+        };
+        let frame = message.encode(&self.config.begin_string, &self.config.sender_comp_id, &self.config.target_comp_id, seq, &sending_time());
+        log::trace!("FIX out: {}", frame.replace(SOH, "|"));
+        writer.write_all(frame.as_bytes()).await?;
+        writer.flush().await?;
+        Ok(())
+    }
+
+    fn logon_message(&self) -> FixMessage {
+        let mut logon = FixMessage::new(msg_type::LOGON)
+            .with(tags::ENCRYPT_METHOD, 0)
+            .with(tags::HEART_BT_INT, self.config.heartbeat_secs);
+        if self.config.reset_on_logon {
+            logon.push(tags::RESET_SEQ_NUM_FLAG, "Y");
+        }
+        if let Some(username) = &self.config.username {
+            logon.push(tags::USERNAME, username);
+        }
+        if let Some(password) = &self.config.password {
+            logon.push(tags::PASSWORD, password);
+        }
+        let mut extra: Vec<_> = self.config.logon_fields.iter().collect();
+        extra.sort();
+        for (tag, value) in extra {
+            logon.push(*tag, value);
+        }
+        logon
+    }
+
+    // 연결 하나의 수명 - 끊긴 사유를 돌려줌
+    async fn run_connection(
+        &self,
+        stream: Box<dyn FixStream>,
+        outbound_rx: &mut mpsc::UnboundedReceiver<FixMessage>,
+        stop: &mut watch::Receiver<bool>,
+    ) -> String {
+        let (mut reader, mut writer) = tokio::io::split(stream);
+        let mut buf = Vec::with_capacity(8192);
+        if self.config.reset_on_logon {
+            *self.seq.lock().unwrap_or_else(|e| e.into_inner()) = SeqNums { outgoing: 1, incoming: 1 };
+        }
+        if let Err(e) = self.write(&mut writer, &self.logon_message(), None).await {
+            return format!("logon send failed: {}", e);
+        }
+
+        // 로그온 응답 대기
+        let logon_timeout = Duration::from_millis(self.config.logon_timeout_ms);
+        let response = match tokio::time::timeout(logon_timeout, read_message(&mut reader, &mut buf)).await {
+            Ok(Ok(message)) => message,
+            Ok(Err(e)) => return e.to_string(),
+            Err(_) => return "logon timed out".to_string(),
+        };
+        match response.msg_type.as_str() {
+            msg_type::LOGON => {}
+            msg_type::LOGOUT => return format!("logon rejected: {}", response.get(tags::TEXT).unwrap_or("")),
+            other => return format!("expected Logon, got MsgType {}", other),
+        }
+        if let Some(reason) = self.on_incoming(&mut writer, response).await {
+            return reason;
+        }
+        // 끊겨 있던 동안 쌓인 메시지는 버림 (send 는 로그온 중에만 받음)
+        while outbound_rx.try_recv().is_ok() {}
+        self.logged_on.store(true, Ordering::SeqCst);
+        log::info!("FIX session {}->{} logged on", self.config.sender_comp_id, self.config.target_comp_id);
+        let _ = self.events.send(FixSessionEvent::LoggedOn);
+
+        let heartbeat = Duration::from_secs(self.config.heartbeat_secs);
+        let (mut last_sent, mut last_received) = (Instant::now(), Instant::now());
+        let mut test_request_pending = false;
+        let mut timer = tokio::time::interval(Duration::from_millis(250).min(heartbeat));
+        loop {
+            tokio::select! {
+                read = read_message(&mut reader, &mut buf) => {
+                    let message = match read {
+                        Ok(message) => message,
+                        Err(e) => return e.to_string(),
+                    };
+                    last_received = Instant::now();
+                    test_request_pending = false;
+                    if let Some(reason) = self.on_incoming(&mut writer, message).await {
+                        return reason;
+                    }
+                }
+                Some(message) = outbound_rx.recv() => {
+                    if let Err(e) = self.write(&mut writer, &message, None).await {
+                        return format!("send failed: {}", e);
+                    }
+                    last_sent = Instant::now();
+                }
+                _ = timer.tick() => {
+                    if last_received.elapsed() >= heartbeat * 2 {
+                        return "heartbeat timeout".to_string();
+                    }
+                    if !test_request_pending && last_received.elapsed() >= heartbeat + heartbeat / 5 {
+                        let test = FixMessage::new(msg_type::TEST_REQUEST).with(tags::TEST_REQ_ID, chrono::Utc::now().timestamp_millis());
+                        if self.write(&mut writer, &test, None).await.is_err() {
+                            return "test request send failed".to_string();
+                        }
+                        test_request_pending = true;
+                        last_sent = Instant::now();
+                    } else if last_sent.elapsed() >= heartbeat {
+                        if self.write(&mut writer, &FixMessage::new(msg_type::HEARTBEAT), None).await.is_err() {
+                            return "heartbeat send failed".to_string();
+                        }
+                        last_sent = Instant::now();
+                    }
+                }
+                _ = stop.changed() => {
+                    let _ = self.write(&mut writer, &FixMessage::new(msg_type::LOGOUT), None).await;
+                    return "logout requested".to_string();
+                }
+            }
+        }
+    }
+
+    // 수신 메시지 처리 - 시퀀스 검사 후 세션 메시지는 직접 응답, 나머지는 구독자에게 전달
+    async fn on_incoming<W: AsyncWrite + Unpin>(&self, writer: &mut W, message: FixMessage) -> Option<String> {
+        log::trace!("FIX in: {} {:?}", message.msg_type, message.fields);
+        let seq = message.seq_num().unwrap_or_default();
+        if message.msg_type == msg_type::SEQUENCE_RESET {
+            if let Some(next) = message.get(tags::NEW_SEQ_NO).and_then(|v| v.parse().ok()) {
+                self.seq.lock().unwrap_or_else(|e| e.into_inner()).incoming = next;
+            }
+            return None;
+        }
+        if message.msg_type == msg_type::LOGON && message.flag(tags::RESET_SEQ_NUM_FLAG) {
+            self.seq.lock().unwrap_or_else(|e| e.into_inner()).incoming = 1;
+        }
+
+        let expected = self.seq.lock().unwrap_or_else(|e| e.into_inner()).incoming;
+        if seq < expected && !message.flag(tags::POSS_DUP_FLAG) {
+            let text = format!("MsgSeqNum too low, expecting {} but received {}", expected, seq);
+            let _ = self.write(writer, &FixMessage::new(msg_type::LOGOUT).with(tags::TEXT, &text), None).await;
+            return Some(text);
+        }
+        if seq > expected {
+            log::warn!("FIX sequence gap: expected {}, received {}; requesting resend", expected, seq);
+            let resend = FixMessage::new(msg_type::RESEND_REQUEST).with(tags::BEGIN_SEQ_NO, expected).with(tags::END_SEQ_NO, 0);
+            if self.write(writer, &resend, None).await.is_err() {
+                return Some("resend request send failed".to_string());
+            }
+        }
+        if seq >= expected {
+            self.seq.lock().unwrap_or_else(|e| e.into_inner()).incoming = seq + 1;
+        }
+
+        match message.msg_type.as_str() {
+            msg_type::HEARTBEAT | msg_type::LOGON => {}
+            msg_type::TEST_REQUEST => {
+                let mut heartbeat = FixMessage::new(msg_type::HEARTBEAT);
+                if let Some(id) = message.get(tags::TEST_REQ_ID) {
+                    heartbeat.push(tags::TEST_REQ_ID, id);
+                }
+                if self.write(writer, &heartbeat, None).await.is_err() {
+                    return Some("heartbeat send failed".to_string());
+                }
+            }
+            msg_type::RESEND_REQUEST => {
+                // 저장한 메시지가 없으므로 요청 구간 전체를 GapFill 로 건너뜀
+                let begin = message.get(tags::BEGIN_SEQ_NO).and_then(|v| v.parse().ok()).unwrap_or(1);
+                let next = self.seq_nums().0;
+                let gap_fill = FixMessage::new(msg_type::SEQUENCE_RESET)
+                    .with(tags::POSS_DUP_FLAG, "Y")
+                    .with(tags::GAP_FILL_FLAG, "Y")
+                    .with(tags::NEW_SEQ_NO, next);
+                if self.write(writer, &gap_fill, Some(begin)).await.is_err() {
+                    return Some("gap fill send failed".to_string());
+                }
+            }
+            msg_type::LOGOUT => {
+                let reason = format!("logout: {}", message.get(tags::TEXT).unwrap_or(""));
+                let _ = self.write(writer, &FixMessage::new(msg_type::LOGOUT), None).await;
+                return Some(reason);
+            }
+            msg_type::REJECT => {
+                log::warn!("FIX session reject for seq {}: {}", message.get(tags::REF_SEQ_NUM).unwrap_or("?"), message.get(tags::TEXT).unwrap_or(""));
+                let _ = self.events.send(FixSessionEvent::Message(message));
+            }
+            _ => {
+                let _ = self.events.send(FixSessionEvent::Message(message));
+            }
+        }
+        None
+    }
+}
+
+async fn read_message<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut Vec<u8>) -> Result<FixMessage, TradingError> {
+    loop {
+        if let Some(frame) = FixMessage::take_frame(buf)? {
+            return FixMessage::decode(&frame);
+        }
+        let mut chunk = [0u8; 4096];
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(TradingError::NetworkTimeout("FIX connection closed by peer".to_string()));
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::DuplexStream;
+
+    // 한 번만 연결되는 메모리 전송
+    struct DuplexConnector(Mutex<Option<DuplexStream>>);
+
+    #[async_trait]
+    impl FixConnector for DuplexConnector {
+        async fn connect(&self) -> Result<Box<dyn FixStream>, TradingError> {
+            let stream = self.0.lock().unwrap().take().ok_or(TradingError::NotConnected)?;
+            Ok(Box::new(stream))
+        }
+    }
+
+    struct Acceptor {
+        stream: DuplexStream,
+        buf: Vec<u8>,
+        seq: u64,
+    }
+
+    impl Acceptor {
+        async fn recv(&mut self) -> FixMessage {
+            tokio::time::timeout(Duration::from_secs(2), read_message(&mut self.stream, &mut self.buf)).await.unwrap().unwrap()
+        }
+
+        async fn send(&mut self, message: FixMessage) {
+            self.seq += 1;
+            let frame = message.encode("FIX.4.4", "VENUE", "XQ", self.seq, &sending_time());
+            self.stream.write_all(frame.as_bytes()).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_encode_decode_round_trip() {
+        let message = FixMessage::new(msg_type::MARKET_DATA_REQUEST).with(tags::MD_REQ_ID, "1").with(tags::SYMBOL, "BTCUSDT");
+        let frame = message.encode("FIX.4.4", "XQ", "VENUE", 7, "20250101-00:00:00.000");
+        assert!(frame.starts_with("8=FIX.4.4\x019="));
+        let mut buf = format!("garbage{}{}", frame, &frame[..10]).into_bytes();
+        let decoded = FixMessage::decode(&FixMessage::take_frame(&mut buf).unwrap().unwrap()).unwrap();
+        assert_eq!((decoded.msg_type.as_str(), decoded.seq_num(), decoded.get(tags::SYMBOL)), ("V", Some(7), Some("BTCUSDT")));
+        assert_eq!(buf, &frame.as_bytes()[..10]);
+        assert!(FixMessage::take_frame(&mut buf).unwrap().is_none());
+        assert!(FixMessage::decode(frame.replace("10=", "10=9").as_bytes()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_session_logon_heartbeat_and_sequence_gap() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let mut config = FixSessionConfig::new("localhost", 9878, "XQ", "VENUE");
+        config.username = Some("user".to_string());
+        let session = FixSession::new(config);
+        let mut events = session.subscribe();
+        let task = session.start(Arc::new(DuplexConnector(Mutex::new(Some(client)))));
+        let mut venue = Acceptor { stream: server, buf: Vec::new(), seq: 0 };
+
+        let logon = venue.recv().await;
+        assert_eq!((logon.msg_type.as_str(), logon.get(tags::RESET_SEQ_NUM_FLAG), logon.get(tags::USERNAME)), ("A", Some("Y"), Some("user")));
+        assert!(session.send(FixMessage::new(msg_type::MARKET_DATA_REQUEST)).is_err());
+        venue.send(FixMessage::new(msg_type::LOGON).with(tags::HEART_BT_INT, 30)).await;
+        assert!(matches!(events.recv().await.unwrap(), FixSessionEvent::LoggedOn));
+
+        // TestRequest 에는 같은 ID 의 Heartbeat
+        venue.send(FixMessage::new(msg_type::TEST_REQUEST).with(tags::TEST_REQ_ID, "ping")).await;
+        let heartbeat = venue.recv().await;
+        assert_eq!((heartbeat.msg_type.as_str(), heartbeat.get(tags::TEST_REQ_ID), heartbeat.seq_num()), ("0", Some("ping"), Some(2)));
+
+        // 번호가 건너뛰면 ResendRequest 후 메시지는 전달
+        venue.seq += 2;
+        venue.send(FixMessage::new(msg_type::MARKET_DATA_SNAPSHOT).with(tags::SYMBOL, "BTCUSDT")).await;
+        let resend = venue.recv().await;
+        assert_eq!((resend.msg_type.as_str(), resend.get(tags::BEGIN_SEQ_NO)), ("2", Some("3")));
+        assert!(matches!(events.recv().await.unwrap(), FixSessionEvent::Message(m) if m.msg_type == "W"));
+        assert_eq!(session.seq_nums(), (4, 6));
+
+        // 상대 ResendRequest 는 GapFill 로 응답
+        venue.send(FixMessage::new(msg_type::RESEND_REQUEST).with(tags::BEGIN_SEQ_NO, 2).with(tags::END_SEQ_NO, 0)).await;
+        let gap_fill = venue.recv().await;
+        assert_eq!((gap_fill.msg_type.as_str(), gap_fill.seq_num(), gap_fill.get(tags::NEW_SEQ_NO)), ("4", Some(2), Some("4")));
+
+        session.send(FixMessage::new(msg_type::MARKET_DATA_REQUEST).with(tags::MD_REQ_ID, "md-1")).unwrap();
+        assert_eq!(venue.recv().await.seq_num(), Some(4));
+        session.stop();
+        assert_eq!(venue.recv().await.msg_type, msg_type::LOGOUT);
+        assert!(matches!(events.recv().await.unwrap(), FixSessionEvent::LoggedOut { .. }));
+        task.await.unwrap();
+    }
+}
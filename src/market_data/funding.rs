@@ -0,0 +1,228 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::FundingDataConfig;
+use crate::exchange::traits::Exchange;
+use crate::market_data::external::{ExternalInputs, ExternalSeriesStore};
+use crate::market_data::stream::MarketDataStream;
+use crate::models::funding::FundingRate;
+use crate::models::timestamp::Timestamp;
+
+/// 펀딩비 시계열 이름 (정산 1회 기준 비율)
+pub fn funding_rate_series(symbol: &str) -> String {
+    format!("funding.{}.rate", symbol)
+}
+
+/// 다음 펀딩 정산 시각 시계열 이름 (ms)
+pub fn next_funding_series(symbol: &str) -> String {
+    format!("funding.{}.next_time", symbol)
+}
+
+/// 미결제약정 시계열 이름
+pub fn open_interest_series(symbol: &str) -> String {
+    format!("oi.{}", symbol)
+}
+
+/// 직전 스냅샷 대비 미결제약정 변화율 시계열 이름
+pub fn open_interest_change_series(symbol: &str) -> String {
+    format!("oi.{}.change", symbol)
+}
+
+/// 펀딩/미결제약정 스냅샷
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FundingSnapshot {
+    #[serde(flatten)]
+    pub funding: FundingRate,
+    pub open_interest: Option<f64>,
+    /// 직전 스냅샷 대비 미결제약정 변화율 (0.1 = 10% 증가)
+    pub open_interest_change: Option<f64>,
+}
+
+/// 심볼별 펀딩/미결제약정 스냅샷 저장소 - 수집 태스크와 HTTP 핸들러가 공유
+///
+/// 외부 시계열 저장소를 연결하면 같은 값을 캔들 시각에 맞춰 기록해 전략 보조 입력으로 넘긴다.
+#[derive(Clone, Default)]
+pub struct FundingStore {
+    snapshots: Arc<RwLock<HashMap<String, VecDeque<FundingSnapshot>>>>,
+    history_limit: usize,
+    external: Option<ExternalSeriesStore>,
+}
+
+impl FundingStore {
+    pub fn new(history_limit: usize) -> Self {
+        FundingStore {
+            snapshots: Arc::new(RwLock::new(HashMap::new())),
+            history_limit: history_limit.max(1),
+            external: None,
+        }
+    }
+
+    /// 전략 보조 입력용 외부 시계열 저장소 연결
+    pub fn with_external(mut self, external: ExternalSeriesStore) -> Self {
+        self.external = Some(external);
+        self
+    }
+
+    /// 스냅샷 기록 - 미결제약정 변화율을 채워 돌려준다
+    pub fn record(&self, funding: FundingRate, open_interest: Option<f64>) -> FundingSnapshot {
+        let mut snapshots = self.snapshots.write().unwrap_or_else(|e| e.into_inner());
+        let history = snapshots.entry(funding.symbol.clone()).or_default();
+        let previous_oi = history.iter().rev().find_map(|s| s.open_interest);
+        let open_interest_change = match (previous_oi, open_interest) {
+            (Some(previous), Some(current)) if previous > 0.0 => Some(current / previous - 1.0),
+            _ => None,
+        };
+        let snapshot = FundingSnapshot { funding, open_interest, open_interest_change };
+        history.push_back(snapshot.clone());
+        while history.len() > self.history_limit {
+            history.pop_front();
+        }
+        drop(snapshots);
+
+        if let Some(external) = &self.external {
+            let symbol = &snapshot.funding.symbol;
+            let at = Timestamp::from_millis(snapshot.funding.timestamp);
+            external.record(&funding_rate_series(symbol), at, snapshot.funding.funding_rate);
+            if snapshot.funding.next_funding_time > 0 {
+                external.record(&next_funding_series(symbol), at, snapshot.funding.next_funding_time as f64);
+            }
+            if let Some(oi) = snapshot.open_interest {
+                external.record(&open_interest_series(symbol), at, oi);
+            }
+            if let Some(change) = snapshot.open_interest_change {
+                external.record(&open_interest_change_series(symbol), at, change);
+            }
+        }
+        snapshot
+    }
+
+    pub fn latest(&self, symbol: &str) -> Option<FundingSnapshot> {
+        let snapshots = self.snapshots.read().unwrap_or_else(|e| e.into_inner());
+        snapshots.get(symbol).and_then(|h| h.back().cloned())
+    }
+
+    /// 최근 `limit` 개 스냅샷 (오래된 순)
+    pub fn history(&self, symbol: &str, limit: usize) -> Vec<FundingSnapshot> {
+        let snapshots = self.snapshots.read().unwrap_or_else(|e| e.into_inner());
+        snapshots.get(symbol)
+            .map(|h| h.iter().skip(h.len().saturating_sub(limit)).cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// 전략이 받은 외부 입력에서 꺼낸 펀딩/미결제약정 값 - 정산 전후 진입 회피, 미결제약정 급증 필터에 사용
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FundingView {
+    pub funding_rate: Option<f64>,
+    pub next_funding_time: Option<i64>,
+    pub open_interest: Option<f64>,
+    pub open_interest_change: Option<f64>,
+}
+
+impl FundingView {
+    pub fn from_inputs(inputs: &ExternalInputs, symbol: &str) -> Self {
+        FundingView {
+            funding_rate: inputs.get(&funding_rate_series(symbol)),
+            next_funding_time: inputs.get(&next_funding_series(symbol)).map(|t| t as i64),
+            open_interest: inputs.get(&open_interest_series(symbol)),
+            open_interest_change: inputs.get(&open_interest_change_series(symbol)),
+        }
+    }
+
+    /// 다음 정산까지 `window_ms` 이내인지 (정산 시각을 모르면 false)
+    pub fn near_funding(&self, now: i64, window_ms: i64) -> bool {
+        self.next_funding_time.is_some_and(|next| next >= now && next - now <= window_ms)
+    }
+
+    /// 미결제약정 변화율이 `threshold` 이상 (증가/감소 모두)
+    pub fn open_interest_spike(&self, threshold: f64) -> bool {
+        self.open_interest_change.is_some_and(|change| change.abs() >= threshold)
+    }
+}
+
+/// 설정된 심볼의 펀딩/미결제약정을 주기적으로 수집 (마크 가격 스트림 값이 최신이면 REST 대신 사용)
+pub fn spawn_collector(
+    exchange: Arc<tokio::sync::RwLock<dyn Exchange>>,
+    stream: Option<Arc<tokio::sync::RwLock<MarketDataStream>>>,
+    store: FundingStore,
+    symbols: Vec<String>,
+    config: FundingDataConfig,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let interval_ms = config.interval_ms.max(1_000);
+        let mut ticker = tokio::time::interval(Duration::from_millis(interval_ms));
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now().timestamp_millis();
+            for symbol in &symbols {
+                let streamed = match (&stream, config.mark_price_stream) {
+                    (Some(stream), true) => stream.read().await.get_latest_funding(symbol)
+                        .filter(|f| now - f.timestamp <= interval_ms as i64),
+                    _ => None,
+                };
+                let ex = exchange.read().await;
+                let funding = match streamed {
+                    Some(funding) => funding,
+                    None => match ex.get_funding_rate(symbol).await {
+                        Ok(funding) => funding,
+                        Err(e) => {
+                            log::debug!("funding rate unavailable for {}: {}", symbol, e);
+                            continue;
+                        }
+                    },
+                };
+                let open_interest = if config.open_interest {
+                    ex.get_open_interest(symbol).await
+                        .map_err(|e| log::debug!("open interest unavailable for {}: {}", symbol, e))
+                        .ok()
+                        .map(|oi| oi.open_interest)
+                } else {
+                    None
+                };
+                drop(ex);
+                let funding = FundingRate { timestamp: if funding.timestamp > 0 { funding.timestamp } else { now }, ..funding };
+                store.record(funding, open_interest);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funding(rate: f64, timestamp: i64) -> FundingRate {
+        FundingRate {
+            symbol: "BTCUSDT".to_string(),
+            mark_price: 60_000.0,
+            index_price: 60_000.0,
+            funding_rate: rate,
+            next_funding_time: 28_800_000,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_funding_snapshots_feed_strategy_inputs() {
+        let external = ExternalSeriesStore::new(60_000, 100);
+        let store = FundingStore::new(2).with_external(external.clone());
+        assert_eq!(store.record(funding(0.0001, 60_000), Some(1_000.0)).open_interest_change, None);
+        store.record(funding(0.0002, 120_000), None);
+        // 미결제약정이 빠진 스냅샷은 건너뛰고 직전 값과 비교
+        let spike = store.record(funding(0.0003, 180_000), Some(1_250.0));
+        assert_eq!(spike.open_interest_change, Some(0.25));
+        assert_eq!(store.history("BTCUSDT", 10).len(), 2);
+        assert_eq!(store.latest("BTCUSDT").unwrap().funding.funding_rate, 0.0003);
+
+        // 캔들 시각 기준 as-of 값으로 전략에 전달
+        let view = FundingView::from_inputs(&external.inputs_at(Timestamp::from_millis(150_000)), "BTCUSDT");
+        assert_eq!((view.funding_rate, view.open_interest, view.open_interest_change), (Some(0.0002), Some(1_000.0), None));
+        let view = FundingView::from_inputs(&external.inputs_at(Timestamp::from_millis(180_000)), "BTCUSDT");
+        assert!(view.open_interest_spike(0.2) && !view.open_interest_spike(0.3));
+        assert!(view.near_funding(28_800_000 - 300_000, 600_000));
+        assert!(!view.near_funding(0, 600_000));
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::MicrostructureFeatureConfig;
+use crate::models::decimal::to_f64;
+use crate::models::order::OrderSide;
+use crate::models::order_book::OrderBook;
+use crate::models::trade::Trade;
+
+/// 호가창 스냅샷 (가격, 수량) - bids 는 높은 가격순, asks 는 낮은 가격순
+#[derive(Debug, Clone, Default)]
+pub struct BookSnapshot {
+    pub timestamp: i64,
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl From<&OrderBook> for BookSnapshot {
+    fn from(book: &OrderBook) -> Self {
+        BookSnapshot {
+            timestamp: book.timestamp,
+            bids: book.bids.iter().map(|l| (l.price, l.quantity)).collect(),
+            asks: book.asks.iter().map(|l| (l.price, l.quantity)).collect(),
+        }
+    }
+}
+
+/// 예측 모델에 전달하는 시장 미시구조 피처
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MicrostructureFeatures {
+    pub timestamp: i64,
+    pub mid_price: f64,
+    pub spread: f64,
+    pub spread_bps: f64,
+    /// 상위 N호가 수량 불균형 (bid - ask) / (bid + ask), -1.0 ~ 1.0
+    pub book_imbalance: f64,
+    /// 레벨별 누적 호가 수량 (1 ~ N호가)
+    pub bid_depth: Vec<f64>,
+    pub ask_depth: Vec<f64>,
+    /// 최근 체결의 매수/매도 수량 불균형
+    pub trade_imbalance: f64,
+    pub trade_count: usize,
+    /// 최근 중간가 로그수익률 표준편차 (비연환산)
+    pub realized_volatility: f64,
+}
+
+#[derive(Debug, Default)]
+struct SymbolState {
+    book: Option<BookSnapshot>,
+    trades: VecDeque<(i64, f64, f64, OrderSide)>,
+    mids: VecDeque<f64>,
+}
+
+/// 심볼별 호가/체결 스트림을 받아 미시구조 피처를 계산
+#[derive(Debug, Default)]
+pub struct MicrostructureTracker {
+    symbols: HashMap<String, SymbolState>,
+    max_history: usize,
+}
+
+impl MicrostructureTracker {
+    pub fn new(max_history: usize) -> Self {
+        MicrostructureTracker {
+            symbols: HashMap::new(),
+            max_history: max_history.max(2),
+        }
+    }
+
+    /// 호가창 갱신
+    pub fn on_book(&mut self, symbol: &str, snapshot: BookSnapshot) {
+        let max_history = self.max_history;
+        let state = self.symbols.entry(symbol.to_string()).or_default();
+        if let (Some((bid, _)), Some((ask, _))) = (snapshot.bids.first(), snapshot.asks.first()) {
+            state.mids.push_back((bid + ask) / 2.0);
+            if state.mids.len() > max_history {
+                state.mids.pop_front();
+            }
+        }
+        state.book = Some(snapshot);
+    }
+
+    /// 체결 갱신
+    pub fn on_trade(&mut self, trade: &Trade) {
+        let max_history = self.max_history;
+        let state = self.symbols.entry(trade.symbol.clone()).or_default();
+        state.trades.push_back((trade.timestamp.as_millis(), to_f64(trade.price), to_f64(trade.quantity), trade.side.clone()));
+        if state.trades.len() > max_history {
+            state.trades.pop_front();
+        }
+    }
+
+    /// 설정에 따라 피처 계산 (호가 데이터가 없으면 None)
+    pub fn features(&self, symbol: &str, config: &MicrostructureFeatureConfig) -> Option<MicrostructureFeatures> {
+        let state = self.symbols.get(symbol)?;
+        let book = state.book.as_ref()?;
+        let (best_bid, _) = *book.bids.first()?;
+        let (best_ask, _) = *book.asks.first()?;
+
+        let mid_price = (best_bid + best_ask) / 2.0;
+        let spread = best_ask - best_bid;
+        let spread_bps = if mid_price > 0.0 { spread / mid_price * 10_000.0 } else { 0.0 };
+
+        let levels = config.depth_levels.max(1);
+        let bid_depth = cumulative_depth(&book.bids, levels);
+        let ask_depth = cumulative_depth(&book.asks, levels);
+        let bid_total = bid_depth.last().copied().unwrap_or(0.0);
+        let ask_total = ask_depth.last().copied().unwrap_or(0.0);
+        let book_imbalance = imbalance(bid_total, ask_total);
+
+        // 체결 불균형은 최신 호가 시각 기준 trade_window_ms 이내 체결만 사용
+        let since = book.timestamp - config.trade_window_ms;
+        let (mut buy_qty, mut sell_qty, mut trade_count) = (0.0, 0.0, 0);
+        for (_, _, qty, side) in state.trades.iter().filter(|(ts, ..)| *ts >= since) {
+            match side {
+                OrderSide::Buy => buy_qty += qty,
+                OrderSide::Sell => sell_qty += qty,
+            }
+            trade_count += 1;
+        }
+
+        let window = config.volatility_window.max(2);
+        let mids: Vec<f64> = state.mids.iter().rev().take(window + 1).rev().copied().collect();
+        let realized_volatility = log_return_std(&mids);
+
+        Some(MicrostructureFeatures {
+            timestamp: book.timestamp,
+            mid_price,
+            spread,
+            spread_bps,
+            book_imbalance,
+            bid_depth,
+            ask_depth,
+            trade_imbalance: imbalance(buy_qty, sell_qty),
+            trade_count,
+            realized_volatility,
+        })
+    }
+}
+
+fn cumulative_depth(levels: &[(f64, f64)], n: usize) -> Vec<f64> {
+    let mut total = 0.0;
+    levels.iter().take(n).map(|(_, qty)| { total += qty; total }).collect()
+}
+
+fn imbalance(a: f64, b: f64) -> f64 {
+    if a + b > 0.0 { (a - b) / (a + b) } else { 0.0 }
+}
+
+fn log_return_std(prices: &[f64]) -> f64 {
+    let returns: Vec<f64> = prices.windows(2)
+        .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+        .map(|w| (w[1] / w[0]).ln())
+        .collect();
+    if returns.len() < 2 {
+        return 0.0;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::OrderId;
+
+    #[test]
+    fn test_microstructure_features() {
+        let mut tracker = MicrostructureTracker::new(100);
+        let config = MicrostructureFeatureConfig { enabled: true, depth_levels: 2, volatility_window: 10, trade_window_ms: 1_000 };
+        assert!(tracker.features("BTCUSDT", &config).is_none());
+
+        tracker.on_book("BTCUSDT", BookSnapshot {
+            timestamp: 1_000,
+            bids: vec![(99.0, 3.0), (98.0, 1.0), (97.0, 10.0)],
+            asks: vec![(101.0, 1.0), (102.0, 1.0)],
+        });
+        tracker.on_trade(&Trade::new("1", "BTCUSDT", dec!(100), dec!(3), 900, OrderId("o".to_string()), OrderSide::Buy));
+        tracker.on_trade(&Trade::new("2", "BTCUSDT", dec!(100), dec!(1), 950, OrderId("o".to_string()), OrderSide::Sell));
+        // 윈도우 밖 체결은 제외
+        tracker.on_trade(&Trade::new("3", "BTCUSDT", dec!(100), dec!(50), -1, OrderId("o".to_string()), OrderSide::Sell));
+
+        let f = tracker.features("BTCUSDT", &config).unwrap();
+        assert_eq!(f.mid_price, 100.0);
+        assert_eq!(f.spread, 2.0);
+        assert_eq!(f.bid_depth, vec![3.0, 4.0]);
+        assert_eq!(f.ask_depth, vec![1.0, 2.0]);
+        assert!((f.book_imbalance - 2.0 / 6.0).abs() < 1e-9);
+        assert!((f.trade_imbalance - 0.5).abs() < 1e-9);
+        assert_eq!(f.trade_count, 2);
+    }
+}
@@ -2,3 +2,10 @@ pub mod provider;
 pub mod stream;
 pub mod websocket;
 pub mod fix;
+pub mod fix_session;
+pub mod microstructure;
+pub mod aggregator;
+pub mod external;
+pub mod funding;
+pub mod backfill;
+pub mod recorder;
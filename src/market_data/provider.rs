@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use tokio::sync::{broadcast, RwLock};
 
 use crate::models::market_data::MarketData;
+use crate::models::order_book::OrderBook;
 use crate::error::TradingError;
 
 /// 시장 데이터 제공자 인터페이스
@@ -17,9 +18,29 @@ pub trait MarketDataProvider: Send + Sync {
     /// 시장 데이터 수신 채널 얻기
     fn get_receiver(&self, symbol: &str) -> Result<broadcast::Receiver<MarketData>, TradingError>;
 
+    /// 주기별 캔들(kline) 구독 - interval 예: "1m", "5m", "1h" (캔들 스트림이 없는 제공자는 InvalidParameter)
+    async fn subscribe_interval(&mut self, symbol: &str, interval: &str) -> Result<(), TradingError> {
+        Err(TradingError::InvalidParameter(format!("candle stream not supported: {}@{}", symbol, interval)))
+    }
+
+    /// 주기별 캔들 구독 해제
+    async fn unsubscribe_interval(&mut self, _symbol: &str, _interval: &str) -> Result<(), TradingError> {
+        Ok(())
+    }
+
+    /// 완성된(마감) 캔들 수신 채널 얻기
+    fn get_candle_receiver(&self, symbol: &str, interval: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
+        Err(TradingError::NotSubscribed(format!("{}@{}", symbol, interval)))
+    }
+
     /// 현재 시장 데이터 조회
     async fn get_current_data(&self, symbol: &str) -> Result<MarketData, TradingError>;
 
+    /// 현재 호가창 조회 (호가 스트림이 없는 제공자는 DataNotFound)
+    async fn get_current_order_book(&self, symbol: &str) -> Result<OrderBook, TradingError> {
+        Err(TradingError::DataNotFound(format!("order book for {}", symbol)))
+    }
+
     /// 제공자 연결 상태 확인
     async fn is_connected(&self) -> bool;
 
@@ -75,6 +96,19 @@ impl MarketDataManager {
         Ok(())
     }
 
+    /// 캔들 스트림을 지원하는 제공자에 주기별 캔들 구독 (하나 이상 성공해야 함)
+    pub async fn subscribe_interval_all(&mut self, symbol: &str, interval: &str) -> Result<(), TradingError> {
+        let mut last_error = TradingError::NoAvailableProvider;
+        let mut subscribed = false;
+        for provider in &self.providers {
+            match provider.write().await.subscribe_interval(symbol, interval).await {
+                Ok(()) => subscribed = true,
+                Err(e) => last_error = e,
+            }
+        }
+        if subscribed { Ok(()) } else { Err(last_error) }
+    }
+
     /// 모든 제공자 연결 시작
     pub async fn connect_all(&mut self) -> Result<(), TradingError> {
         for provider in &self.providers {
@@ -113,6 +147,18 @@ impl MarketDataManager {
         Err(TradingError::NoAvailableProvider)
     }
 
+    /// 완성된 캔들 수신기 얻기 (해당 주기를 구독한 첫 번째 제공자 사용)
+    pub async fn get_candle_receiver(&self, symbol: &str, interval: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
+        for provider in &self.providers {
+            let provider = provider.read().await;
+            if let Ok(receiver) = provider.get_candle_receiver(symbol, interval) {
+                return Ok(receiver);
+            }
+        }
+
+        Err(TradingError::NotSubscribed(format!("{}@{}", symbol, interval)))
+    }
+
     /// 현재 시장 데이터 얻기 (첫 번째 가용 제공자 사용)
     pub async fn get_current_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
         for provider in &self.providers {
@@ -0,0 +1,524 @@
+//! 시장 데이터 기록기
+//!
+//! 스트림으로 받은 캔들/틱을 심볼·UTC 일자별 CSV 파일에 이어 써서 실시간 데이터로 백테스트 데이터셋을 만든다.
+//! 파일은 백테스트 CSV 형식(symbol,timestamp,open,high,low,close,volume)이라 `CsvDataProvider` 로 바로 읽을 수 있다.
+//!
+//! - 경로: `{dir}/candles/{interval}/{SYMBOL}/{YYYY-MM-DD}.csv`, `{dir}/ticks/{SYMBOL}/{YYYY-MM-DD}.csv`
+//! - 하루 파일이 `max_file_bytes` 를 넘으면 `{date}.1.csv`, `{date}.2.csv` ... 조각으로 나눠 쓴다.
+//! - 정리 주기마다 지난 날짜의 조각을 시간순으로 합치고(캔들은 같은 시각 중복 제거) `retention_days` 가 지난 날짜는 지운다.
+//! - `index.json` 에 파일별 시각 범위를 기록해 구간 조회 시 겹치는 파일만 읽는다.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::backtest::data_provider::{CsvDataProvider, HistoricalDataProvider};
+use crate::config::MarketDataRecorderConfig;
+use crate::error::TradingError;
+use crate::market_data::stream::MarketDataStream;
+use crate::models::market_data::MarketData;
+
+const INDEX_FILE: &str = "index.json";
+const CSV_HEADER: [&str; 7] = ["symbol", "timestamp", "open", "high", "low", "close", "volume"];
+
+/// 기록 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordKind {
+    Candle,
+    Tick,
+}
+
+/// 기록 파일 한 개의 색인 항목
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedFile {
+    pub kind: RecordKind,
+    pub symbol: String,
+    /// 캔들 주기 (틱은 None)
+    pub interval: Option<String>,
+    /// UTC 일자 (YYYY-MM-DD)
+    pub date: String,
+    pub part: u32,
+    /// 기록 디렉터리 기준 상대 경로
+    pub path: PathBuf,
+    pub first_timestamp: i64,
+    pub last_timestamp: i64,
+    pub rows: u64,
+}
+
+impl RecordedFile {
+    fn series(&self) -> SeriesKey {
+        (self.kind, self.symbol.clone(), self.interval.clone())
+    }
+}
+
+/// 기록 파일 색인 (`index.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordingIndex {
+    pub files: Vec<RecordedFile>,
+}
+
+impl RecordingIndex {
+    /// 색인 읽기 (없으면 빈 색인)
+    pub fn load(dir: &Path) -> Result<Self, TradingError> {
+        match std::fs::read_to_string(dir.join(INDEX_FILE)) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(RecordingIndex::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn save(&self, dir: &Path) -> Result<(), TradingError> {
+        let tmp = dir.join(format!("{}.tmp", INDEX_FILE));
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(tmp, dir.join(INDEX_FILE))?;
+        Ok(())
+    }
+
+    /// [start, end] (epoch ms) 와 겹치는 파일 (시각순)
+    pub fn query(&self, kind: RecordKind, symbol: &str, interval: Option<&str>, start: i64, end: i64) -> Vec<&RecordedFile> {
+        let mut files: Vec<&RecordedFile> = self.files.iter()
+            .filter(|f| f.kind == kind && f.symbol == symbol && f.interval.as_deref() == interval)
+            .filter(|f| f.rows > 0 && f.first_timestamp <= end && f.last_timestamp >= start)
+            .collect();
+        files.sort_by_key(|f| (f.first_timestamp, f.part));
+        files
+    }
+
+    pub fn symbols(&self, kind: RecordKind, interval: Option<&str>) -> Vec<String> {
+        let mut symbols: Vec<String> = self.files.iter()
+            .filter(|f| f.kind == kind && f.interval.as_deref() == interval)
+            .map(|f| f.symbol.clone())
+            .collect();
+        symbols.sort();
+        symbols.dedup();
+        symbols
+    }
+}
+
+// (종류, 심볼, 캔들 주기)
+type SeriesKey = (RecordKind, String, Option<String>);
+
+struct OpenFile {
+    /// 색인에서 이 파일 항목의 위치
+    entry: usize,
+    writer: csv::Writer<File>,
+    bytes: u64,
+}
+
+struct RecorderState {
+    index: RecordingIndex,
+    open: HashMap<SeriesKey, OpenFile>,
+    dirty: bool,
+}
+
+/// 시장 데이터 기록기 - 스트림 구독 태스크들이 같은 핸들을 공유
+#[derive(Clone)]
+pub struct MarketDataRecorder {
+    dir: PathBuf,
+    config: MarketDataRecorderConfig,
+    state: Arc<Mutex<RecorderState>>,
+}
+
+fn utc_date(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(timestamp).unwrap_or_default().format("%Y-%m-%d").to_string()
+}
+
+fn io_error(e: csv::Error) -> TradingError {
+    TradingError::IoError(e.into())
+}
+
+impl MarketDataRecorder {
+    pub fn new(config: MarketDataRecorderConfig) -> Result<Self, TradingError> {
+        let dir = PathBuf::from(&config.dir);
+        std::fs::create_dir_all(&dir)?;
+        let index = RecordingIndex::load(&dir)?;
+        Ok(MarketDataRecorder {
+            dir,
+            config,
+            state: Arc::new(Mutex::new(RecorderState { index, open: HashMap::new(), dirty: false })),
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RecorderState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn relative_path(kind: RecordKind, symbol: &str, interval: Option<&str>, date: &str, part: u32) -> PathBuf {
+        let base = match (kind, interval) {
+            (RecordKind::Candle, Some(interval)) => PathBuf::from("candles").join(interval).join(symbol),
+            _ => PathBuf::from("ticks").join(symbol),
+        };
+        let name = if part == 0 { format!("{}.csv", date) } else { format!("{}.{}.csv", date, part) };
+        base.join(name)
+    }
+
+    /// 캔들/틱 한 건 기록 (파일 쓰기는 버퍼링되고 `flush` 주기마다 디스크에 반영)
+    pub fn record(&self, kind: RecordKind, interval: Option<&str>, data: &MarketData) -> Result<(), TradingError> {
+        let key: SeriesKey = (kind, data.symbol.clone(), interval.map(str::to_string));
+        let timestamp = data.timestamp.as_millis();
+        let date = utc_date(timestamp);
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        let reusable = state.open.get(&key)
+            .is_some_and(|f| state.index.files[f.entry].date == date && f.bytes < self.config.max_file_bytes);
+        if !reusable {
+            if let Some(mut previous) = state.open.remove(&key) {
+                previous.writer.flush()?;
+            }
+            let opened = self.open_file(&mut state.index, &key, &date)?;
+            state.open.insert(key.clone(), opened);
+        }
+
+        let file = state.open.get_mut(&key).expect("recorder file opened above");
+        let row = [
+            data.symbol.clone(),
+            timestamp.to_string(),
+            data.open.to_string(),
+            data.high.to_string(),
+            data.low.to_string(),
+            data.close.to_string(),
+            data.volume.to_string(),
+        ];
+        file.writer.write_record(&row).map_err(io_error)?;
+        file.bytes += row.iter().map(|f| f.len() as u64 + 1).sum::<u64>();
+
+        let entry = &mut state.index.files[file.entry];
+        if entry.rows == 0 {
+            entry.first_timestamp = timestamp;
+            entry.last_timestamp = timestamp;
+        }
+        entry.first_timestamp = entry.first_timestamp.min(timestamp);
+        entry.last_timestamp = entry.last_timestamp.max(timestamp);
+        entry.rows += 1;
+        state.dirty = true;
+        Ok(())
+    }
+
+    // 해당 일자의 마지막 조각을 이어 쓰거나, 가득 찼으면 새 조각 생성
+    fn open_file(&self, index: &mut RecordingIndex, key: &SeriesKey, date: &str) -> Result<OpenFile, TradingError> {
+        let last = index.files.iter().enumerate()
+            .filter(|(_, f)| f.series() == *key && f.date == date)
+            .max_by_key(|(_, f)| f.part)
+            .map(|(i, f)| (i, f.part));
+        if let Some((entry, _)) = last {
+            let path = self.dir.join(&index.files[entry].path);
+            let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            if bytes > 0 && bytes < self.config.max_file_bytes {
+                let file = OpenOptions::new().append(true).open(&path)?;
+                return Ok(OpenFile { entry, writer: csv::WriterBuilder::new().has_headers(false).from_writer(file), bytes });
+            }
+        }
+
+        let part = last.map(|(_, part)| part + 1).unwrap_or(0);
+        let (kind, symbol, interval) = key;
+        let relative = Self::relative_path(*kind, symbol, interval.as_deref(), date, part);
+        let path = self.dir.join(&relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut writer = csv::Writer::from_writer(File::create(&path)?);
+        writer.write_record(CSV_HEADER).map_err(io_error)?;
+        index.files.push(RecordedFile {
+            kind: *kind,
+            symbol: symbol.clone(),
+            interval: interval.clone(),
+            date: date.to_string(),
+            part,
+            path: relative,
+            first_timestamp: 0,
+            last_timestamp: 0,
+            rows: 0,
+        });
+        Ok(OpenFile { entry: index.files.len() - 1, writer, bytes: CSV_HEADER.join(",").len() as u64 + 1 })
+    }
+
+    /// 버퍼를 디스크에 쓰고 색인 저장
+    pub fn flush(&self) -> Result<(), TradingError> {
+        let mut state = self.lock();
+        for file in state.open.values_mut() {
+            file.writer.flush()?;
+        }
+        if state.dirty {
+            state.index.save(&self.dir)?;
+            state.dirty = false;
+        }
+        Ok(())
+    }
+
+    /// 지난 날짜 조각 병합 + 보관 기간 지난 날짜 삭제
+    pub fn maintain(&self, today: NaiveDate) -> Result<(), TradingError> {
+        let today_str = today.format("%Y-%m-%d").to_string();
+        let mut state = self.lock();
+        // 지난 날짜 파일은 닫고 나서 정리 (색인 위치가 바뀌므로 열린 파일은 모두 닫음)
+        for (_, mut file) in state.open.drain() {
+            file.writer.flush()?;
+        }
+
+        if let Some(days) = self.config.retention_days {
+            let cutoff = (today - chrono::Duration::days(days as i64)).format("%Y-%m-%d").to_string();
+            let (expired, kept): (Vec<RecordedFile>, Vec<RecordedFile>) = std::mem::take(&mut state.index.files)
+                .into_iter()
+                .partition(|f| f.date < cutoff);
+            for file in &expired {
+                if let Err(e) = std::fs::remove_file(self.dir.join(&file.path)) {
+                    log::warn!("recorder could not delete {}: {}", file.path.display(), e);
+                }
+            }
+            if !expired.is_empty() {
+                log::info!("recorder deleted {} files older than {}", expired.len(), cutoff);
+            }
+            state.index.files = kept;
+        }
+
+        let mut groups: HashMap<(SeriesKey, String), Vec<RecordedFile>> = HashMap::new();
+        let mut files = Vec::new();
+        for file in std::mem::take(&mut state.index.files) {
+            if file.date < today_str {
+                groups.entry((file.series(), file.date.clone())).or_default().push(file);
+            } else {
+                files.push(file);
+            }
+        }
+        for (_, mut parts) in groups {
+            if parts.len() > 1 {
+                parts.sort_by_key(|f| f.part);
+                match self.compact(&parts) {
+                    Ok(merged) => parts = vec![merged],
+                    Err(e) => log::warn!("recorder could not compact {} {}: {}", parts[0].symbol, parts[0].date, e),
+                }
+            }
+            files.extend(parts);
+        }
+        files.sort_by(|a, b| (&a.symbol, &a.date, a.part).cmp(&(&b.symbol, &b.date, b.part)));
+        state.index.files = files;
+        state.index.save(&self.dir)?;
+        state.dirty = false;
+        Ok(())
+    }
+
+    // 하루 조각들을 시각순 단일 파일로 병합 (캔들은 같은 시각 중 나중 기록 유지)
+    fn compact(&self, parts: &[RecordedFile]) -> Result<RecordedFile, TradingError> {
+        let mut rows: Vec<(i64, csv::StringRecord)> = Vec::new();
+        for part in parts {
+            let mut reader = csv::Reader::from_path(self.dir.join(&part.path)).map_err(io_error)?;
+            for record in reader.records() {
+                let record = record.map_err(|e| TradingError::ParseError(e.to_string()))?;
+                let timestamp = record.get(1).and_then(|t| t.parse().ok())
+                    .ok_or_else(|| TradingError::ParseError(format!("bad timestamp in {}", part.path.display())))?;
+                rows.push((timestamp, record));
+            }
+        }
+        rows.sort_by_key(|(timestamp, _)| *timestamp);
+        if parts[0].kind == RecordKind::Candle {
+            rows.reverse();
+            rows.dedup_by_key(|(timestamp, _)| *timestamp);
+            rows.reverse();
+        }
+
+        let first = &parts[0];
+        let relative = Self::relative_path(first.kind, &first.symbol, first.interval.as_deref(), &first.date, 0);
+        let tmp = self.dir.join(relative.with_extension("csv.tmp"));
+        let mut writer = csv::Writer::from_path(&tmp).map_err(io_error)?;
+        writer.write_record(CSV_HEADER).map_err(io_error)?;
+        for (_, record) in &rows {
+            writer.write_record(record).map_err(io_error)?;
+        }
+        writer.flush()?;
+        drop(writer);
+        for part in parts.iter().filter(|p| p.part > 0) {
+            std::fs::remove_file(self.dir.join(&part.path))?;
+        }
+        std::fs::rename(&tmp, self.dir.join(&relative))?;
+
+        Ok(RecordedFile {
+            part: 0,
+            path: relative,
+            first_timestamp: rows.first().map(|(t, _)| *t).unwrap_or_default(),
+            last_timestamp: rows.last().map(|(t, _)| *t).unwrap_or_default(),
+            rows: rows.len() as u64,
+            ..first.clone()
+        })
+    }
+
+    pub fn index(&self) -> RecordingIndex {
+        self.lock().index.clone()
+    }
+
+    /// 기록된 캔들/틱 구간 조회 (색인으로 겹치는 파일만 읽음, 시각순)
+    pub fn load(
+        &self,
+        kind: RecordKind,
+        symbol: &str,
+        interval: Option<&str>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<MarketData>, TradingError> {
+        self.flush()?;
+        RecordedDataProvider::new(&self.dir, kind, interval)?.load_data(symbol, start_time, end_time)
+    }
+}
+
+/// 기록 디렉터리를 백테스트 데이터 공급자로 사용
+pub struct RecordedDataProvider {
+    dir: PathBuf,
+    index: RecordingIndex,
+    kind: RecordKind,
+    interval: Option<String>,
+}
+
+impl RecordedDataProvider {
+    pub fn new(dir: impl AsRef<Path>, kind: RecordKind, interval: Option<&str>) -> Result<Self, TradingError> {
+        let dir = dir.as_ref().to_path_buf();
+        let index = RecordingIndex::load(&dir)?;
+        Ok(RecordedDataProvider { dir, index, kind, interval: interval.map(str::to_string) })
+    }
+}
+
+impl HistoricalDataProvider for RecordedDataProvider {
+    fn available_symbols(&self) -> Vec<String> {
+        self.index.symbols(self.kind, self.interval.as_deref())
+    }
+
+    fn load_data(
+        &self,
+        symbol: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<MarketData>, TradingError> {
+        let (start, end) = (start_time.timestamp_millis(), end_time.timestamp_millis());
+        let mut data = Vec::new();
+        for file in self.index.query(self.kind, symbol, self.interval.as_deref(), start, end) {
+            data.extend(CsvDataProvider::new(self.dir.join(&file.path), ',')?.load_data(symbol, start_time, end_time)?);
+        }
+        data.sort_by_key(|d| d.timestamp);
+        Ok(data)
+    }
+}
+
+/// 기록 태스크 시작 - 심볼별 틱 채널과 캔들 채널을 구독해 기록하고 주기적으로 flush / 정리
+pub async fn spawn_recorder(
+    recorder: MarketDataRecorder,
+    stream: Arc<RwLock<MarketDataStream>>,
+    symbols: &[String],
+    timeframes: &[String],
+) -> Vec<tokio::task::JoinHandle<()>> {
+    let config = recorder.config.clone();
+    let mut receivers = Vec::new();
+    {
+        let mut stream = stream.write().await;
+        for symbol in symbols {
+            if config.ticks {
+                receivers.push((RecordKind::Tick, None, stream.get_or_create_channel(symbol).subscribe()));
+            }
+            if config.candles {
+                for tf in timeframes.iter().filter(|tf| config.timeframes.is_empty() || config.timeframes.contains(tf)) {
+                    receivers.push((RecordKind::Candle, Some(tf.clone()), stream.get_or_create_candle_channel(symbol, tf).subscribe()));
+                }
+            }
+        }
+    }
+
+    let mut handles: Vec<_> = receivers.into_iter().map(|(kind, interval, mut rx)| {
+        let recorder = recorder.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(data) => {
+                        if let Err(e) = recorder.record(kind, interval.as_deref(), &data) {
+                            log::warn!("recorder write for {} failed: {}", data.symbol, e);
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => log::warn!("recorder lagged, {} {:?} updates not recorded", n, kind),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }).collect();
+
+    handles.push(tokio::spawn(async move {
+        let mut flush = tokio::time::interval(Duration::from_millis(config.flush_interval_ms));
+        let mut maintenance = tokio::time::interval(Duration::from_millis(config.maintenance_interval_ms));
+        loop {
+            tokio::select! {
+                _ = flush.tick() => {
+                    if let Err(e) = recorder.flush() {
+                        log::warn!("recorder flush failed: {}", e);
+                    }
+                }
+                _ = maintenance.tick() => {
+                    if let Err(e) = recorder.maintain(Utc::now().date_naive()) {
+                        log::warn!("recorder maintenance failed: {}", e);
+                    }
+                }
+            }
+        }
+    }));
+    handles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::to_decimal;
+
+    const DAY: i64 = 86_400_000;
+
+    fn candle(timestamp: i64, close: f64) -> MarketData {
+        let price = to_decimal(close);
+        MarketData::new("BTCUSDT", timestamp, price, price, price, price, to_decimal(1.0))
+    }
+
+    #[test]
+    fn test_record_rotate_compact_and_query() {
+        let dir = std::env::temp_dir().join(format!("xquant-recorder-{}", uuid::Uuid::new_v4()));
+        let config = MarketDataRecorderConfig {
+            enabled: true,
+            dir: dir.to_string_lossy().to_string(),
+            max_file_bytes: 200,
+            retention_days: Some(2),
+            ..MarketDataRecorderConfig::default()
+        };
+        let recorder = MarketDataRecorder::new(config).unwrap();
+        // 1일차 40개 (작은 파일 크기로 여러 조각), 2일차에 재연결로 같은 캔들 중복 기록
+        for i in 0..40 {
+            recorder.record(RecordKind::Candle, Some("1m"), &candle(DAY + i * 60_000, 100.0 + i as f64)).unwrap();
+        }
+        recorder.record(RecordKind::Candle, Some("1m"), &candle(2 * DAY, 1.0)).unwrap();
+        recorder.record(RecordKind::Candle, Some("1m"), &candle(DAY + 39 * 60_000, 139.0)).unwrap();
+        recorder.record(RecordKind::Tick, None, &candle(2 * DAY + 5, 2.0)).unwrap();
+        recorder.flush().unwrap();
+        let day1 = |index: &RecordingIndex| index.files.iter().filter(|f| f.date == "1970-01-02" && f.kind == RecordKind::Candle).count();
+        assert!(day1(&recorder.index()) > 1);
+
+        let all = |r: &MarketDataRecorder| r.load(RecordKind::Candle, "BTCUSDT", Some("1m"), DateTime::UNIX_EPOCH, Utc::now()).unwrap();
+        assert_eq!(all(&recorder).len(), 42);
+
+        // 3일차 정리: 지난 1일차 조각은 하나로 병합(중복 제거), 당일인 2일차는 그대로
+        recorder.maintain(NaiveDate::from_ymd_opt(1970, 1, 3).unwrap()).unwrap();
+        let index = RecordingIndex::load(&dir).unwrap();
+        assert_eq!(day1(&index), 1);
+        let candles = all(&recorder);
+        assert_eq!(candles.len(), 41);
+        assert!(candles.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        let range = recorder.load(RecordKind::Candle, "BTCUSDT", Some("1m"),
+            DateTime::from_timestamp_millis(2 * DAY).unwrap(), DateTime::from_timestamp_millis(3 * DAY).unwrap()).unwrap();
+        assert_eq!(range.len(), 1);
+        let provider = RecordedDataProvider::new(&dir, RecordKind::Tick, None).unwrap();
+        assert_eq!(provider.available_symbols(), vec!["BTCUSDT".to_string()]);
+
+        // 보관 기간(2일) 지난 1일차 삭제
+        recorder.maintain(NaiveDate::from_ymd_opt(1970, 1, 5).unwrap()).unwrap();
+        assert_eq!(all(&recorder).len(), 1);
+        assert!(!dir.join("candles/1m/BTCUSDT/1970-01-02.csv").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,17 +1,49 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock, Mutex};
-use tokio::task::JoinHandle;
 
+use crate::market_data::aggregator::parse_timeframe;
+use crate::models::funding::FundingRate;
 use crate::models::market_data::MarketData;
+use crate::models::order_book::OrderBook;
 use crate::error::TradingError;
 
+/// 캔들 연속성 끊김 - `from` 부터 `to` 직전까지의 캔들(시작 시각 기준)이 빠짐
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleGap {
+    pub symbol: String,
+    pub interval: String,
+    pub interval_ms: i64,
+    pub from: i64,
+    pub to: i64,
+}
+
+impl CandleGap {
+    /// 빠진 캔들 수
+    pub fn missing(&self) -> i64 {
+        (self.to - self.from) / self.interval_ms
+    }
+}
+
 /// 시장 데이터 스트림 처리기
 pub struct MarketDataStream {
     channels: HashMap<String, broadcast::Sender<MarketData>>,
     latest_data: HashMap<String, MarketData>,
+    book_channels: HashMap<String, broadcast::Sender<OrderBook>>,
+    latest_books: HashMap<String, OrderBook>,
+    /// "SYMBOL@interval" -> 완성된 캔들 채널
+    candle_channels: HashMap<String, broadcast::Sender<MarketData>>,
+    latest_candles: HashMap<String, MarketData>,
+    /// 마크 가격 스트림의 최신 펀딩 정보
+    latest_funding: HashMap<String, FundingRate>,
+    /// 캔들 채널별 마지막으로 발행한 캔들 시작 시각 (연속성 검사)
+    last_candle_open: HashMap<String, i64>,
+    /// 끊김 보충 사용 여부 - 켜져 있으면 끊김 이후 캔들은 보충이 끝날 때까지 보류
+    backfill: bool,
+    /// 보충 대기 중인 끊김과 그동안 들어온 캔들
+    pending_backfills: HashMap<String, (CandleGap, Vec<MarketData>)>,
+    gaps: broadcast::Sender<CandleGap>,
     buffer_size: usize,
-    aggregation_tasks: HashMap<String, JoinHandle<()>>,
 }
 
 impl MarketDataStream {
@@ -19,11 +51,28 @@ impl MarketDataStream {
         MarketDataStream {
             channels: HashMap::new(),
             latest_data: HashMap::new(),
+            book_channels: HashMap::new(),
+            latest_books: HashMap::new(),
+            candle_channels: HashMap::new(),
+            latest_candles: HashMap::new(),
+            latest_funding: HashMap::new(),
+            last_candle_open: HashMap::new(),
+            backfill: false,
+            pending_backfills: HashMap::new(),
+            gaps: broadcast::channel(64).0,
             buffer_size,
-            aggregation_tasks: HashMap::new(),
         }
     }
 
+    /// 펀딩 정보 갱신 (마크 가격 스트림)
+    pub fn publish_funding(&mut self, funding: FundingRate) {
+        self.latest_funding.insert(funding.symbol.clone(), funding);
+    }
+
+    pub fn get_latest_funding(&self, symbol: &str) -> Option<FundingRate> {
+        self.latest_funding.get(symbol).cloned()
+    }
+
     /// 심볼 채널 생성 또는 가져오기
     pub fn get_or_create_channel(&mut self, symbol: &str) -> broadcast::Sender<MarketData> {
         if let Some(sender) = self.channels.get(symbol) {
@@ -65,28 +114,117 @@ impl MarketDataStream {
         }
     }
 
-    /// 집계 작업 시작 (예: 1분봉 생성)
-    pub fn start_aggregation(&mut self, symbol: &str, interval: u64) -> Result<(), TradingError> {
-        if self.aggregation_tasks.contains_key(symbol) {
-            return Err(TradingError::AlreadyRunning(format!("Aggregation for {} already running", symbol)));
+    /// 심볼 호가창 채널 생성 또는 가져오기
+    pub fn get_or_create_book_channel(&mut self, symbol: &str) -> broadcast::Sender<OrderBook> {
+        self.book_channels
+            .entry(symbol.to_string())
+            .or_insert_with(|| broadcast::channel(self.buffer_size).0)
+            .clone()
+    }
+
+    /// 호가창 갱신 및 브로드캐스트 (구독자가 없어도 최신 호가는 보관)
+    pub fn publish_order_book(&mut self, book: OrderBook) {
+        if let Some(sender) = self.book_channels.get(&book.symbol) {
+            let _ = sender.send(book.clone());
         }
+        self.latest_books.insert(book.symbol.clone(), book);
+    }
+
+    /// 최신 호가창 조회
+    pub fn get_latest_order_book(&self, symbol: &str) -> Option<OrderBook> {
+        self.latest_books.get(symbol).cloned()
+    }
 
-        let receiver = self.get_receiver(symbol)?;
-        let task = tokio::spawn(async move {
-            // 집계 로직 구현 (생략)
-        });
+    /// 호가창 수신기 얻기
+    pub fn get_book_receiver(&mut self, symbol: &str) -> broadcast::Receiver<OrderBook> {
+        self.get_or_create_book_channel(symbol).subscribe()
+    }
 
-        self.aggregation_tasks.insert(symbol.to_string(), task);
-        Ok(())
+    /// 캔들 채널 키
+    pub fn candle_key(symbol: &str, interval: &str) -> String {
+        format!("{}@{}", symbol.to_uppercase(), interval)
     }
 
-    /// 집계 작업 중지
-    pub fn stop_aggregation(&mut self, symbol: &str) -> Result<(), TradingError> {
-        if let Some(task) = self.aggregation_tasks.remove(symbol) {
-            task.abort();
-            Ok(())
-        } else {
-            Err(TradingError::TaskNotFound(format!("Aggregation task for {} not found", symbol)))
+    /// 심볼/주기 캔들 채널 생성 또는 가져오기
+    pub fn get_or_create_candle_channel(&mut self, symbol: &str, interval: &str) -> broadcast::Sender<MarketData> {
+        self.candle_channels
+            .entry(Self::candle_key(symbol, interval))
+            .or_insert_with(|| broadcast::channel(self.buffer_size).0)
+            .clone()
+    }
+
+    /// 완성된 캔들 브로드캐스트 (최신 캔들은 구독자가 없어도 보관)
+    ///
+    /// 이미 발행한 시각 이전/같은 캔들은 버리고, 시작 시각이 한 주기 이상 건너뛰면 끊김으로 보고
+    /// 보충이 켜져 있으면 빠진 캔들을 채울 때까지 이후 캔들을 보류한다 (`complete_backfill`).
+    pub fn publish_candle(&mut self, interval: &str, candle: MarketData) {
+        let key = Self::candle_key(&candle.symbol, interval);
+        if let Some((_, held)) = self.pending_backfills.get_mut(&key) {
+            held.push(candle);
+            return;
+        }
+        let open = candle.timestamp.as_millis();
+        if let (Some(last), Ok(interval_ms)) = (self.last_candle_open.get(&key).copied(), parse_timeframe(interval)) {
+            if open <= last {
+                log::debug!("{} candle {} already published, dropped", key, open);
+                return;
+            }
+            if open > last + interval_ms {
+                let gap = CandleGap { symbol: candle.symbol.clone(), interval: interval.to_string(), interval_ms, from: last + interval_ms, to: open };
+                log::warn!("{} candle gap: {} missing before {}", key, gap.missing(), open);
+                if self.backfill {
+                    self.pending_backfills.insert(key, (gap.clone(), vec![candle]));
+                    let _ = self.gaps.send(gap);
+                    return;
+                }
+            }
+        }
+        self.emit_candle(key, candle);
+    }
+
+    fn emit_candle(&mut self, key: String, candle: MarketData) {
+        self.last_candle_open.insert(key.clone(), candle.timestamp.as_millis());
+        if let Some(sender) = self.candle_channels.get(&key) {
+            let _ = sender.send(candle.clone());
+        }
+        self.latest_candles.insert(key, candle);
+    }
+
+    /// 끊김 보충 사용 - 보충 태스크가 `subscribe_gaps` 로 끊김을 받아 `complete_backfill` 로 채움
+    pub fn enable_backfill(&mut self) -> broadcast::Receiver<CandleGap> {
+        self.backfill = true;
+        self.gaps.subscribe()
+    }
+
+    /// 빠진 캔들을 순서대로 발행한 뒤 보류한 캔들을 이어서 발행 (보충 실패 시 빈 목록으로 호출해 보류만 해제)
+    pub fn complete_backfill(&mut self, gap: &CandleGap, mut candles: Vec<MarketData>) -> usize {
+        let key = Self::candle_key(&gap.symbol, &gap.interval);
+        let Some((_, held)) = self.pending_backfills.remove(&key) else {
+            return 0;
+        };
+        candles.sort_by_key(|c| c.timestamp.as_millis());
+        candles.dedup_by_key(|c| c.timestamp.as_millis());
+        let mut filled = 0;
+        for candle in candles.into_iter().filter(|c| (gap.from..gap.to).contains(&c.timestamp.as_millis())) {
+            self.emit_candle(key.clone(), candle);
+            filled += 1;
+        }
+        for candle in held {
+            self.publish_candle(&gap.interval, candle);
         }
+        filled
+    }
+
+    /// 가장 최근에 완성된 캔들
+    pub fn get_latest_candle(&self, symbol: &str, interval: &str) -> Option<MarketData> {
+        self.latest_candles.get(&Self::candle_key(symbol, interval)).cloned()
+    }
+
+    /// 캔들 수신기 얻기
+    pub fn get_candle_receiver(&self, symbol: &str, interval: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
+        let key = Self::candle_key(symbol, interval);
+        self.candle_channels.get(&key)
+            .map(|sender| sender.subscribe())
+            .ok_or(TradingError::ChannelNotFound(key))
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::BTreeSet;
 use std::sync::Arc;
-use tokio::sync::{broadcast, RwLock, Mutex};
+use tokio::sync::{broadcast, mpsc, RwLock, Mutex};
 use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
 use async_trait::async_trait;
@@ -11,17 +11,53 @@ use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
 use crate::market_data::provider::MarketDataProvider;
 use crate::market_data::stream::MarketDataStream;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::funding::FundingRate;
 use crate::models::market_data::MarketData;
+use crate::models::order_book::{levels_from_json, OrderBook};
 use crate::error::TradingError;
+use crate::exchange::telemetry::ExchangeTelemetry;
+
+/// 연결 상태 텔레메트리/알림에 쓰이는 커넥터 이름
+pub const MARKET_STREAM_CONNECTOR: &str = "market_stream";
 
 /// WebSocket 기반 시장 데이터 제공자
 pub struct WebSocketProvider {
     url: String,
     stream: Arc<RwLock<MarketDataStream>>,
-    subscriptions: HashMap<String, String>,  // 심볼 -> 구독 ID
+    /// 티커(+호가창) 구독 심볼 - 연결 후 추가된 심볼도 재연결 시 다시 구독
+    subscriptions: Arc<std::sync::Mutex<BTreeSet<String>>>,
     connected: bool,
     ws_task: Option<JoinHandle<()>>,
     reconnect_interval: Duration,
+    telemetry: ExchangeTelemetry,
+    /// 호가창 구독 단계 수 (None 이면 티커만 구독)
+    depth_levels: Option<usize>,
+    /// 마크 가격/펀딩 스트림(`{symbol}@markPrice@1s`) 함께 구독 (선물 스트림 전용)
+    mark_price: bool,
+    /// 캔들 스트림 이름 (`btcusdt@kline_1m`) - 재연결 시 다시 구독
+    kline_streams: Arc<std::sync::Mutex<BTreeSet<String>>>,
+    /// 연결 중 추가 구독 메시지를 WebSocket 태스크로 전달
+    command_tx: Option<mpsc::UnboundedSender<SubscriptionRequest>>,
+}
+
+/// 바이낸스 kline 스트림이 지원하는 주기
+const KLINE_INTERVALS: [&str; 16] = ["1s", "1m", "3m", "5m", "15m", "30m", "1h", "2h", "4h", "6h", "8h", "12h", "1d", "3d", "1w", "1M"];
+
+fn kline_stream_name(symbol: &str, interval: &str) -> String {
+    format!("{}@kline_{}", symbol.to_lowercase(), interval)
+}
+
+// 심볼 구독 스트림 이름 (티커, 설정 시 부분 호가창 / 마크 가격)
+fn symbol_stream_names(symbol: &str, depth_levels: Option<usize>, mark_price: bool) -> Vec<String> {
+    let mut params = vec![format!("{}@ticker", symbol.to_lowercase())];
+    if let Some(levels) = depth_levels {
+        params.push(format!("{}@depth{}@100ms", symbol.to_lowercase(), levels));
+    }
+    if mark_price {
+        params.push(format!("{}@markPrice@1s", symbol.to_lowercase()));
+    }
+    params
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -36,44 +72,119 @@ impl WebSocketProvider {
         WebSocketProvider {
             url: url.into(),
             stream,
-            subscriptions: HashMap::new(),
+            subscriptions: Arc::new(std::sync::Mutex::new(BTreeSet::new())),
             connected: false,
             ws_task: None,
             reconnect_interval: Duration::from_secs(5),
+            telemetry: ExchangeTelemetry::default(),
+            depth_levels: None,
+            mark_price: false,
+            kline_streams: Arc::new(std::sync::Mutex::new(BTreeSet::new())),
+            command_tx: None,
         }
     }
 
+    /// 부분 호가창 스트림(`{symbol}@depth{levels}@100ms`) 함께 구독 - 거래소 허용 단계(5/10/20)로 올림
+    pub fn with_depth(mut self, levels: usize) -> Self {
+        self.depth_levels = Some([5, 10, 20].into_iter().find(|l| *l >= levels).unwrap_or(20));
+        self
+    }
+
+    /// 마크 가격 스트림 구독 - 펀딩비/다음 정산 시각을 REST 조회 없이 갱신
+    pub fn with_mark_price(mut self) -> Self {
+        self.mark_price = true;
+        self
+    }
+
+    /// 메시지 지연(이벤트 시각 대비 수신 시각) 기록용 텔레메트리 공유
+    pub fn with_telemetry(mut self, telemetry: ExchangeTelemetry) -> Self {
+        self.telemetry = telemetry;
+        self
+    }
+
     async fn start_websocket(&mut self) -> Result<(), TradingError> {
         let url = self.url.clone();
         let stream_clone = self.stream.clone();
         let subscriptions_clone = self.subscriptions.clone();
+        let telemetry = self.telemetry.clone();
+        let depth_levels = self.depth_levels;
+        let mark_price = self.mark_price;
+        let kline_streams = self.kline_streams.clone();
+        let (command_tx, mut command_rx) = mpsc::unbounded_channel::<SubscriptionRequest>();
+        self.command_tx = Some(command_tx);
 
         let ws_task = tokio::spawn(async move {
             loop {
                 match connect_async(&url).await {
                     Ok((ws_stream, _)) => {
                         let (mut write, mut read) = ws_stream.split();
+                        telemetry.record_connection(MARKET_STREAM_CONNECTOR, true, &url);
 
                         // 기존 구독 재설정
-                        for (symbol, _) in &subscriptions_clone {
+                        let symbols: Vec<String> = subscriptions_clone.lock().map(|s| s.iter().cloned().collect()).unwrap_or_default();
+                        for symbol in &symbols {
+                            let params = symbol_stream_names(symbol, depth_levels, mark_price);
                             let sub_msg = SubscriptionRequest {
                                 method: "SUBSCRIBE".to_string(),
-                                params: vec![format!("{}@ticker", symbol.to_lowercase())],
+                                params,
                                 id: rand::random::<u64>(),
                             };
 
                             let msg = serde_json::to_string(&sub_msg).unwrap();
                             let _ = write.send(Message::Text(msg)).await;
                         }
+                        let klines: Vec<String> = kline_streams.lock().map(|s| s.iter().cloned().collect()).unwrap_or_default();
+                        if !klines.is_empty() {
+                            let sub_msg = SubscriptionRequest { method: "SUBSCRIBE".to_string(), params: klines, id: rand::random::<u64>() };
+                            let _ = write.send(Message::Text(serde_json::to_string(&sub_msg).unwrap())).await;
+                        }
 
-                        // 메시지 처리 루프
-                        while let Some(msg_result) = read.next().await {
+                        // 메시지 처리 루프 (연결 중 추가된 구독 요청도 함께 처리)
+                        loop {
+                            let msg_result = tokio::select! {
+                                command = command_rx.recv() => {
+                                    if let Some(request) = command {
+                                        let _ = write.send(Message::Text(serde_json::to_string(&request).unwrap())).await;
+                                    }
+                                    continue;
+                                }
+                                msg = read.next() => match msg {
+                                    Some(msg) => msg,
+                                    None => {
+                                        telemetry.record_connection(MARKET_STREAM_CONNECTOR, false, "stream closed");
+                                        break;
+                                    }
+                                },
+                            };
                             match msg_result {
                                 Ok(msg) => {
                                     if let Message::Text(text) = msg {
                                         // 메시지 파싱 및 처리 (예시 - 실제 구현은 거래소별 포맷에 맞게 조정 필요)
                                         if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                            if let Some(data) = parse_market_data(json) {
+                                            // 결합 스트림 형식({"stream", "data"})이면 본문만 사용
+                                            let json = match json.get("data") {
+                                                Some(data) if json.get("stream").is_some() => data.clone(),
+                                                _ => json,
+                                            };
+                                            if let Some((interval, candle, closed)) = parse_kline(&json) {
+                                                let received_at = chrono::Utc::now().timestamp_millis();
+                                                let event_time = json.get("E").and_then(Value::as_i64).unwrap_or(received_at);
+                                                telemetry.record_stream_lag(&kline_stream_name(&candle.symbol, &interval), event_time, received_at);
+                                                // 진행 중인 캔들은 버리고 마감된 캔들만 전달
+                                                if closed {
+                                                    stream_clone.write().await.publish_candle(&interval, candle);
+                                                }
+                                            } else if let Some(funding) = parse_mark_price(&json) {
+                                                let received_at = chrono::Utc::now().timestamp_millis();
+                                                telemetry.record_stream_lag(&format!("{}@markPrice", funding.symbol.to_lowercase()), funding.timestamp, received_at);
+                                                stream_clone.write().await.publish_funding(funding);
+                                            } else if let Some(book) = parse_order_book(&json) {
+                                                let received_at = chrono::Utc::now().timestamp_millis();
+                                                telemetry.record_stream_lag(&format!("{}@depth", book.symbol.to_lowercase()), book.timestamp, received_at);
+                                                stream_clone.write().await.publish_order_book(book);
+                                            } else if let Some(data) = parse_market_data(json) {
+                                                let received_at = chrono::Utc::now().timestamp_millis();
+                                                telemetry.record_stream_lag(&format!("{}@ticker", data.symbol.to_lowercase()), data.timestamp.as_millis(), received_at);
                                                 let mut stream = stream_clone.write().await;
                                                 let _ = stream.publish(data);
                                             }
@@ -82,6 +193,7 @@ impl WebSocketProvider {
                                 },
                                 Err(e) => {
                                     log::error!("WebSocket error: {}", e);
+                                    telemetry.record_connection(MARKET_STREAM_CONNECTOR, false, &e.to_string());
                                     break;
                                 }
                             }
@@ -89,6 +201,7 @@ impl WebSocketProvider {
                     },
                     Err(e) => {
                         log::error!("Failed to connect to WebSocket: {}", e);
+                        telemetry.record_connection(MARKET_STREAM_CONNECTOR, false, &e.to_string());
                     }
                 }
 
@@ -111,16 +224,16 @@ impl MarketDataProvider for WebSocketProvider {
             return Err(TradingError::NotConnected);
         }
 
-        if self.subscriptions.contains_key(symbol) {
-            return Ok(());
+        // 수신기가 구독 직후부터 받을 수 있도록 채널을 먼저 생성
+        self.stream.write().await.get_or_create_channel(symbol);
+        let added = self.subscriptions.lock().map(|mut s| s.insert(symbol.to_string())).unwrap_or(false);
+        if added {
+            // 연결 중이면 바로 구독, 아니면 재연결 시 구독 목록에서 다시 보냄
+            if let Some(tx) = &self.command_tx {
+                let _ = tx.send(SubscriptionRequest { method: "SUBSCRIBE".to_string(), params: symbol_stream_names(symbol, self.depth_levels, self.mark_price), id: rand::random::<u64>() });
+            }
         }
 
-        // 구독 ID 생성
-        let sub_id = format!("sub_{}", rand::random::<u64>());
-
-        // 실제 WebSocket 구독 메시지 전송은 start_websocket 내에서 처리
-        self.subscriptions.insert(symbol.to_string(), sub_id.clone());
-
         Ok(())
     }
 
@@ -129,15 +242,19 @@ impl MarketDataProvider for WebSocketProvider {
             return Err(TradingError::NotConnected);
         }
 
-        if let Some(sub_id) = self.subscriptions.remove(symbol) {
-            // 실제 구독 해제 메시지는 start_websocket 내에서 처리
+        let removed = self.subscriptions.lock().map(|mut s| s.remove(symbol)).unwrap_or(false);
+        if removed {
+            if let Some(tx) = &self.command_tx {
+                let _ = tx.send(SubscriptionRequest { method: "UNSUBSCRIBE".to_string(), params: symbol_stream_names(symbol, self.depth_levels, self.mark_price), id: rand::random::<u64>() });
+            }
         }
 
         Ok(())
     }
 
     fn get_receiver(&self, symbol: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
-        if !self.subscriptions.contains_key(symbol) {
+        let subscribed = self.subscriptions.lock().map(|s| s.contains(symbol)).unwrap_or(false);
+        if !subscribed {
             return Err(TradingError::NotSubscribed(symbol.to_string()));
         }
 
@@ -149,6 +266,50 @@ impl MarketDataProvider for WebSocketProvider {
         stream.get_receiver(symbol)
     }
 
+    async fn subscribe_interval(&mut self, symbol: &str, interval: &str) -> Result<(), TradingError> {
+        if !self.connected {
+            return Err(TradingError::NotConnected);
+        }
+        if !KLINE_INTERVALS.contains(&interval) {
+            return Err(TradingError::InvalidParameter(format!("Unsupported kline interval: {}", interval)));
+        }
+
+        let name = kline_stream_name(symbol, interval);
+        self.stream.write().await.get_or_create_candle_channel(symbol, interval);
+        let added = self.kline_streams.lock().map(|mut s| s.insert(name.clone())).unwrap_or(false);
+        if added {
+            if let Some(tx) = &self.command_tx {
+                let _ = tx.send(SubscriptionRequest { method: "SUBSCRIBE".to_string(), params: vec![name], id: rand::random::<u64>() });
+            }
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe_interval(&mut self, symbol: &str, interval: &str) -> Result<(), TradingError> {
+        let name = kline_stream_name(symbol, interval);
+        let removed = self.kline_streams.lock().map(|mut s| s.remove(&name)).unwrap_or(false);
+        if removed {
+            if let Some(tx) = &self.command_tx {
+                let _ = tx.send(SubscriptionRequest { method: "UNSUBSCRIBE".to_string(), params: vec![name], id: rand::random::<u64>() });
+            }
+        }
+        Ok(())
+    }
+
+    fn get_candle_receiver(&self, symbol: &str, interval: &str) -> Result<broadcast::Receiver<MarketData>, TradingError> {
+        let subscribed = self.kline_streams.lock().map(|s| s.contains(&kline_stream_name(symbol, interval))).unwrap_or(false);
+        if !subscribed {
+            return Err(TradingError::NotSubscribed(format!("{}@{}", symbol, interval)));
+        }
+
+        let stream = match self.stream.try_read() {
+            Ok(guard) => guard,
+            Err(_) => return Err(TradingError::LockError),
+        };
+
+        stream.get_candle_receiver(symbol, interval)
+    }
+
     async fn get_current_data(&self, symbol: &str) -> Result<MarketData, TradingError> {
         let stream = self.stream.read().await;
 
@@ -159,6 +320,12 @@ impl MarketDataProvider for WebSocketProvider {
         }
     }
 
+    async fn get_current_order_book(&self, symbol: &str) -> Result<OrderBook, TradingError> {
+        self.stream.read().await
+            .get_latest_order_book(symbol)
+            .ok_or_else(|| TradingError::DataNotFound(format!("order book for {}", symbol)))
+    }
+
     async fn is_connected(&self) -> bool {
         self.connected
     }
@@ -181,27 +348,72 @@ impl MarketDataProvider for WebSocketProvider {
         }
 
         self.connected = false;
-        self.subscriptions.clear();
+        self.command_tx = None;
+        if let Ok(mut symbols) = self.subscriptions.lock() {
+            symbols.clear();
+        }
+        if let Ok(mut streams) = self.kline_streams.lock() {
+            streams.clear();
+        }
 
         Ok(())
     }
 }
 
+// 캔들 메시지 파싱 (kline: s, k{t, i, o, h, l, c, v, x}) -> (주기, 캔들, 마감 여부), 캔들 시각은 시작 시각
+fn parse_kline(json: &Value) -> Option<(String, MarketData, bool)> {
+    if json.get("e").and_then(Value::as_str) != Some("kline") {
+        return None;
+    }
+    let k = json.get("k")?;
+    let num = |key: &str| k.get(key).and_then(|v| v.as_str().and_then(|s| s.parse::<Decimal>().ok()).or_else(|| v.as_f64().map(to_decimal)));
+    let symbol = k.get("s").or_else(|| json.get("s")).and_then(Value::as_str)?;
+    let candle = MarketData::new(symbol, k.get("t")?.as_i64()?, num("o")?, num("h")?, num("l")?, num("c")?, num("v").unwrap_or_default());
+    let closed = k.get("x").and_then(Value::as_bool).unwrap_or(false);
+    Some((k.get("i")?.as_str()?.to_string(), candle, closed))
+}
+
+// 마크 가격 메시지 파싱 (바이낸스 선물 markPriceUpdate: s, p, i, r, T, E)
+fn parse_mark_price(json: &Value) -> Option<FundingRate> {
+    if json.get("e").and_then(Value::as_str) != Some("markPriceUpdate") {
+        return None;
+    }
+    let num = |key: &str| json.get(key).and_then(|v| v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64()));
+    Some(FundingRate {
+        symbol: json.get("s")?.as_str()?.to_string(),
+        mark_price: num("p")?,
+        index_price: num("i").unwrap_or_default(),
+        funding_rate: num("r")?,
+        next_funding_time: json.get("T").and_then(Value::as_i64).unwrap_or_default(),
+        timestamp: json.get("E").and_then(Value::as_i64).unwrap_or_default(),
+    })
+}
+
+// 부분 호가창 메시지 파싱 (바이낸스 선물 depthUpdate: s, E, b, a)
+fn parse_order_book(json: &Value) -> Option<OrderBook> {
+    let symbol = json.get("s").and_then(Value::as_str)?;
+    // 현물 티커의 b/a 는 최우선 호가 문자열이므로 배열일 때만 호가창으로 처리
+    let bids = json.get("b").filter(|v| v.is_array())?;
+    let asks = json.get("a").filter(|v| v.is_array())?;
+    let timestamp = json.get("E").and_then(Value::as_i64).unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    Some(OrderBook::new(symbol, timestamp, levels_from_json(bids), levels_from_json(asks)))
+}
+
 // WebSocket 메시지를 MarketData로 파싱하는 함수 (거래소별 포맷에 맞게 구현 필요)
 fn parse_market_data(json: Value) -> Option<MarketData> {
     // 예: 바이낸스 형식의 메시지 파싱
     if let (Some(symbol), Some(close), Some(high), Some(low), Some(open), Some(volume), Some(time)) = (
         json.get("s").and_then(Value::as_str),
-        json.get("c").and_then(Value::as_str).and_then(|s| s.parse::<f64>().ok()),
-        json.get("h").and_then(Value::as_str).and_then(|s| s.parse::<f64>().ok()),
-        json.get("l").and_then(Value::as_str).and_then(|s| s.parse::<f64>().ok()),
-        json.get("o").and_then(Value::as_str).and_then(|s| s.parse::<f64>().ok()),
-        json.get("v").and_then(Value::as_str).and_then(|s| s.parse::<f64>().ok()),
+        json.get("c").and_then(Value::as_str).and_then(|s| s.parse::<Decimal>().ok()),
+        json.get("h").and_then(Value::as_str).and_then(|s| s.parse::<Decimal>().ok()),
+        json.get("l").and_then(Value::as_str).and_then(|s| s.parse::<Decimal>().ok()),
+        json.get("o").and_then(Value::as_str).and_then(|s| s.parse::<Decimal>().ok()),
+        json.get("v").and_then(Value::as_str).and_then(|s| s.parse::<Decimal>().ok()),
         json.get("E").and_then(Value::as_i64),
     ) {
         Some(MarketData {
             symbol: symbol.to_string(),
-            timestamp: time,
+            timestamp: time.into(),
             open,
             high,
             low,
@@ -211,4 +423,45 @@ fn parse_market_data(json: Value) -> Option<MarketData> {
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    #[tokio::test]
+    async fn test_kline_stream_candles() {
+        let message = |closed: bool, close: &str| serde_json::json!({
+            "e": "kline", "E": 1_700_000_060_100i64, "s": "BTCUSDT",
+            "k": {"t": 1_700_000_000_000i64, "T": 1_700_000_059_999i64, "s": "BTCUSDT", "i": "1m",
+                  "o": "100.0", "h": "105.0", "l": "99.0", "c": close, "v": "12.5", "x": closed}
+        });
+        let (interval, candle, closed) = parse_kline(&message(false, "101.0")).unwrap();
+        assert_eq!((interval.as_str(), closed, candle.close), ("1m", false, dec!(101)));
+        assert!(parse_order_book(&message(true, "1")).is_none());
+        assert!(parse_kline(&serde_json::json!({"e": "24hrTicker", "s": "BTCUSDT"})).is_none());
+
+        let stream = Arc::new(RwLock::new(MarketDataStream::new(16)));
+        let mut provider = WebSocketProvider::new("ws://127.0.0.1:1", stream.clone());
+        assert!(matches!(provider.subscribe_interval("BTCUSDT", "1m").await, Err(TradingError::NotConnected)));
+        provider.connected = true;
+        assert!(provider.subscribe_interval("BTCUSDT", "7m").await.is_err());
+        provider.subscribe_interval("BTCUSDT", "1m").await.unwrap();
+        let mut receiver = provider.get_candle_receiver("BTCUSDT", "1m").unwrap();
+        assert!(provider.get_candle_receiver("BTCUSDT", "5m").is_err());
+
+        let (interval, candle, _) = parse_kline(&message(true, "104.0")).unwrap();
+        stream.write().await.publish_candle(&interval, candle);
+        let received = receiver.try_recv().unwrap();
+        assert_eq!((received.timestamp.as_millis(), received.close, received.volume), (1_700_000_000_000, dec!(104), dec!(12.5)));
+        assert_eq!(stream.read().await.get_latest_candle("btcusdt", "1m").unwrap().high, dec!(105));
+
+        // 연결 후 추가한 티커 구독도 바로 수신 가능
+        assert!(provider.get_receiver("ETHUSDT").is_err());
+        provider.subscribe("ETHUSDT").await.unwrap();
+        let mut tickers = provider.get_receiver("ETHUSDT").unwrap();
+        stream.write().await.publish(MarketData::new("ETHUSDT", 1_700_000_000_000, dec!(1), dec!(1), dec!(1), dec!(2_000), dec!(5))).unwrap();
+        assert_eq!(tickers.try_recv().unwrap().close, dec!(2_000));
+    }
+}
@@ -0,0 +1,46 @@
+//! 금액/수량 계산용 Decimal 도우미
+//!
+//! 주문/체결/시세/잔고의 가격과 수량은 `Decimal` 로 다뤄 반올림·비교 오차(잔량이 정확히 0 이 되지 않는 문제 등)를 없앤다.
+//! 지표처럼 실수 연산이 필요한 곳은 `to_f64` 로 바꿔 계산하고 주문을 만들 때 `to_decimal` 로 되돌린다.
+//! 직렬화는 문자열로 해서 저장 파일과 API 응답이 값을 그대로 보존하고(실수로 쓰면 자릿수가 깨짐),
+//! 역직렬화는 숫자/문자열 모두 받아 이전에 숫자로 저장한 파일도 읽는다.
+
+pub use rust_decimal::Decimal;
+pub use rust_decimal_macros::dec;
+
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+
+/// f64 → Decimal (NaN/무한대는 0)
+pub fn to_decimal(value: f64) -> Decimal {
+    Decimal::from_f64(value).unwrap_or_default()
+}
+
+/// Decimal → f64 (지표/통계 계산용)
+pub fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_round_trip_is_exact() {
+        assert_eq!(to_decimal(0.1) + to_decimal(0.2), dec!(0.3));
+        assert_eq!(to_decimal(f64::NAN), Decimal::ZERO);
+        assert_eq!(to_f64(dec!(60000.5)), 60000.5);
+
+        // 분할 체결 잔량이 정확히 0 이 된다
+        let mut remaining = dec!(1);
+        for _ in 0..10 {
+            remaining -= dec!(0.1);
+        }
+        assert!(remaining.is_zero());
+
+        // 직렬화는 문자열, 역직렬화는 숫자/문자열 모두 허용
+        assert_eq!(serde_json::to_string(&dec!(0.1)).unwrap(), "\"0.1\"");
+        assert_eq!(serde_json::to_string(&(dec!(0.1) + dec!(0.2))).unwrap(), "\"0.3\"");
+        assert_eq!(serde_json::from_str::<Decimal>("\"1.5\"").unwrap(), dec!(1.5));
+        assert_eq!(serde_json::from_str::<Decimal>("0.1").unwrap(), dec!(0.1));
+    }
+}
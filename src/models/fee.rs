@@ -0,0 +1,267 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// 30일 거래대금 집계 기간 (ms)
+const THIRTY_DAYS_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+/// 계정 수수료 등급 - 심볼별 메이커/테이커 수수료율 (0.001 = 0.1%)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeeTier {
+    pub symbol: String,
+    pub maker_fee: f64,
+    pub taker_fee: f64,
+    /// 등급 산정 기준 30일 거래대금 (거래소가 알려주지 않으면 None)
+    pub thirty_day_volume: Option<f64>,
+}
+
+/// 체결이 호가창의 어느 쪽이었는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+/// 메이커/테이커 수수료 (bps, 음수 = 리베이트)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+}
+
+impl FeeRates {
+    /// 비율 (0.001 = 0.1%)
+    pub fn rate(&self, liquidity: Liquidity) -> f64 {
+        let bps = match liquidity {
+            Liquidity::Maker => self.maker_bps,
+            Liquidity::Taker => self.taker_bps,
+        };
+        bps / 10_000.0
+    }
+}
+
+/// 30일 거래대금 등급
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeTier {
+    /// 등급 이름 (예: "VIP1")
+    pub name: String,
+    /// 등급 적용 최소 30일 거래대금 (호가 통화)
+    pub min_volume: f64,
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+}
+
+/// 수수료 체계 - 기본 요율, 30일 거래대금 등급, 심볼별 덮어쓰기 (심볼 요율이 등급보다 우선)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeeSchedule {
+    pub maker_bps: f64,
+    pub taker_bps: f64,
+    pub symbols: BTreeMap<String, FeeRates>,
+    pub tiers: Vec<VolumeTier>,
+}
+
+impl FeeSchedule {
+    /// 등급/심볼 구분 없는 단일 요율 (비율 단위)
+    pub fn flat(maker_fee: f64, taker_fee: f64) -> Self {
+        FeeSchedule { maker_bps: maker_fee * 10_000.0, taker_bps: taker_fee * 10_000.0, ..FeeSchedule::default() }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tiers.iter().any(|t| t.min_volume.is_nan() || t.min_volume < 0.0) {
+            return Err("fee tier min_volume must not be negative".to_string());
+        }
+        let mut names: Vec<&str> = self.tiers.iter().map(|t| t.name.as_str()).collect();
+        names.sort_unstable();
+        if names.windows(2).any(|w| w[0] == w[1]) {
+            return Err("fee tier names must be unique".to_string());
+        }
+        Ok(())
+    }
+
+    /// 30일 거래대금으로 도달한 최고 등급
+    pub fn tier(&self, thirty_day_volume: f64) -> Option<&VolumeTier> {
+        self.tiers.iter()
+            .filter(|t| thirty_day_volume >= t.min_volume)
+            .max_by(|a, b| a.min_volume.total_cmp(&b.min_volume))
+    }
+
+    /// 심볼과 30일 거래대금에 적용되는 요율
+    pub fn rates(&self, symbol: &str, thirty_day_volume: f64) -> FeeRates {
+        if let Some(rates) = self.symbols.get(symbol) {
+            return *rates;
+        }
+        match self.tier(thirty_day_volume) {
+            Some(tier) => FeeRates { maker_bps: tier.maker_bps, taker_bps: tier.taker_bps },
+            None => FeeRates { maker_bps: self.maker_bps, taker_bps: self.taker_bps },
+        }
+    }
+}
+
+/// 수수료 귀속 집계 - 메이커/테이커별 수수료와 체결 금액
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeAttribution {
+    pub maker_fees: f64,
+    pub taker_fees: f64,
+    /// 메이커/테이커 구분을 알 수 없는 체결의 수수료 (거래소가 알려주지 않은 경우)
+    pub unclassified_fees: f64,
+    /// 음수 요율로 받은 리베이트 (양수, 수수료 합계에는 차감되어 있음)
+    pub rebates: f64,
+    pub maker_notional: f64,
+    pub taker_notional: f64,
+    pub unclassified_notional: f64,
+    pub fills: usize,
+    /// 적용 요율별 수수료 - 등급 이름, 심볼 덮어쓰기는 심볼, 기본 요율은 "base"
+    #[serde(default)]
+    pub by_tier: BTreeMap<String, f64>,
+}
+
+impl FeeAttribution {
+    pub fn add(&mut self, liquidity: Option<Liquidity>, notional: f64, fee: f64) {
+        match liquidity {
+            Some(Liquidity::Maker) => {
+                self.maker_fees += fee;
+                self.maker_notional += notional;
+            }
+            Some(Liquidity::Taker) => {
+                self.taker_fees += fee;
+                self.taker_notional += notional;
+            }
+            None => {
+                self.unclassified_fees += fee;
+                self.unclassified_notional += notional;
+            }
+        }
+        if fee < 0.0 {
+            self.rebates -= fee;
+        }
+        self.fills += 1;
+    }
+
+    pub fn total_fees(&self) -> f64 {
+        self.maker_fees + self.taker_fees + self.unclassified_fees
+    }
+
+    /// 체결 금액 대비 실효 수수료 (bps)
+    pub fn effective_bps(&self) -> f64 {
+        let notional = self.maker_notional + self.taker_notional + self.unclassified_notional;
+        if notional > 0.0 { self.total_fees() / notional * 10_000.0 } else { 0.0 }
+    }
+
+    /// 메이커 체결 비율 (구분된 체결 금액 기준)
+    pub fn maker_share(&self) -> f64 {
+        let classified = self.maker_notional + self.taker_notional;
+        if classified > 0.0 { self.maker_notional / classified } else { 0.0 }
+    }
+}
+
+/// 수수료 원장 - 수수료 체계와 최근 30일 체결 금액으로 체결마다 요율을 정하고 귀속을 집계
+/// (모의 거래소와 백테스트 계좌가 각각 보유)
+#[derive(Debug, Clone, Default)]
+pub struct FeeLedger {
+    schedule: FeeSchedule,
+    /// (체결 시각 ms, 체결 금액) - 30일 지난 항목은 다음 체결 때 제거
+    window: VecDeque<(i64, f64)>,
+    attribution: FeeAttribution,
+}
+
+impl FeeLedger {
+    pub fn new(schedule: FeeSchedule) -> Self {
+        FeeLedger { schedule, ..FeeLedger::default() }
+    }
+
+    pub fn schedule(&self) -> &FeeSchedule {
+        &self.schedule
+    }
+
+    /// `now` 기준 최근 30일 체결 금액
+    pub fn thirty_day_volume(&self, now: i64) -> f64 {
+        self.window.iter()
+            .filter(|(timestamp, _)| now - timestamp < THIRTY_DAYS_MS)
+            .map(|(_, notional)| notional)
+            .sum()
+    }
+
+    /// 체결 전 수수료 (기록하지 않음)
+    pub fn fee(&self, symbol: &str, liquidity: Liquidity, notional: f64, now: i64) -> f64 {
+        notional * self.schedule.rates(symbol, self.thirty_day_volume(now)).rate(liquidity)
+    }
+
+    /// 체결 기록 - 수수료를 반환하고 30일 거래대금과 귀속 집계에 반영
+    pub fn charge(&mut self, symbol: &str, liquidity: Liquidity, notional: f64, now: i64) -> f64 {
+        while self.window.front().is_some_and(|(timestamp, _)| now - timestamp >= THIRTY_DAYS_MS) {
+            self.window.pop_front();
+        }
+        let volume = self.thirty_day_volume(now);
+        let tier = match self.schedule.symbols.contains_key(symbol) {
+            true => symbol.to_string(),
+            false => self.schedule.tier(volume).map_or_else(|| "base".to_string(), |t| t.name.clone()),
+        };
+        let fee = notional * self.schedule.rates(symbol, volume).rate(liquidity);
+        self.window.push_back((now, notional));
+        self.attribution.add(Some(liquidity), notional, fee);
+        *self.attribution.by_tier.entry(tier).or_insert(0.0) += fee;
+        fee
+    }
+
+    /// 현재 등급 (`Exchange::get_fee_tier` 응답)
+    pub fn fee_tier(&self, symbol: &str, now: i64) -> FeeTier {
+        let volume = self.thirty_day_volume(now);
+        let rates = self.schedule.rates(symbol, volume);
+        FeeTier {
+            symbol: symbol.to_string(),
+            maker_fee: rates.rate(Liquidity::Maker),
+            taker_fee: rates.rate(Liquidity::Taker),
+            thirty_day_volume: Some(volume),
+        }
+    }
+
+    pub fn attribution(&self) -> &FeeAttribution {
+        &self.attribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_tiers_overrides_and_rolling_volume() {
+        let schedule = FeeSchedule {
+            maker_bps: 2.0,
+            taker_bps: 5.0,
+            symbols: BTreeMap::from([("BNBUSDT".to_string(), FeeRates { maker_bps: 0.0, taker_bps: 1.0 })]),
+            tiers: vec![
+                VolumeTier { name: "VIP2".into(), min_volume: 5_000_000.0, maker_bps: -0.5, taker_bps: 3.0 },
+                VolumeTier { name: "VIP1".into(), min_volume: 1_000_000.0, maker_bps: 1.0, taker_bps: 4.0 },
+            ],
+        };
+        assert!(schedule.validate().is_ok());
+        assert_eq!(schedule.rates("BTCUSDT", 0.0), FeeRates { maker_bps: 2.0, taker_bps: 5.0 });
+        assert_eq!(schedule.rates("BTCUSDT", 2_000_000.0).taker_bps, 4.0);
+        assert_eq!(schedule.rates("BTCUSDT", 9_000_000.0).maker_bps, -0.5);
+        // 심볼 덮어쓰기는 등급보다 우선
+        assert_eq!(schedule.rates("BNBUSDT", 9_000_000.0).taker_bps, 1.0);
+
+        let day = 24 * 60 * 60 * 1000;
+        let mut ledger = FeeLedger::new(schedule);
+        assert!((ledger.charge("BTCUSDT", Liquidity::Taker, 1_000_000.0, 0) - 500.0).abs() < 1e-9);
+        // 30일 거래대금 100만 -> VIP1
+        assert!((ledger.charge("BTCUSDT", Liquidity::Taker, 100_000.0, day) - 40.0).abs() < 1e-9);
+        assert_eq!(ledger.fee_tier("BTCUSDT", day).thirty_day_volume, Some(1_100_000.0));
+        // 첫 체결이 30일 창을 벗어나면 기본 요율로 복귀
+        assert!((ledger.fee("BTCUSDT", Liquidity::Maker, 100_000.0, 30 * day) - 20.0).abs() < 1e-9);
+
+        let mut vip2 = FeeLedger::new(ledger.schedule().clone());
+        vip2.charge("BTCUSDT", Liquidity::Taker, 5_000_000.0, 0);
+        let rebate = vip2.charge("BTCUSDT", Liquidity::Maker, 100_000.0, 1);
+        assert!((rebate + 5.0).abs() < 1e-9);
+        let attribution = vip2.attribution();
+        assert!((attribution.rebates - 5.0).abs() < 1e-9);
+        assert!((attribution.total_fees() - 2_495.0).abs() < 1e-9);
+        assert_eq!(attribution.by_tier.keys().collect::<Vec<_>>(), ["VIP2", "base"]);
+
+        assert!(FeeSchedule { tiers: vec![VolumeTier { name: "A".into(), min_volume: -1.0, maker_bps: 0.0, taker_bps: 0.0 }], ..FeeSchedule::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+/// 무기한 선물 펀딩 정보 (마크/인덱스 가격, 다음 정산 예상 펀딩비)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FundingRate {
+    pub symbol: String,
+    pub mark_price: f64,
+    pub index_price: f64,
+    /// 다음 정산에 적용될 예상 펀딩비 (양수면 롱이 숏에 지급)
+    pub funding_rate: f64,
+    /// 다음 펀딩 정산 시각 (ms)
+    pub next_funding_time: i64,
+    pub timestamp: i64,
+}
+
+impl FundingRate {
+    /// Binance `GET /fapi/v1/premiumIndex` 응답 파싱 (숫자 문자열 허용)
+    pub fn from_premium_index(value: &serde_json::Value) -> Option<Self> {
+        let num = |key: &str| {
+            let v = value.get(key)?;
+            v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        };
+        Some(FundingRate {
+            symbol: value.get("symbol")?.as_str()?.to_string(),
+            mark_price: num("markPrice")?,
+            index_price: num("indexPrice").unwrap_or_default(),
+            funding_rate: num("lastFundingRate")?,
+            next_funding_time: value.get("nextFundingTime").and_then(|v| v.as_i64()).unwrap_or_default(),
+            timestamp: value.get("time").and_then(|v| v.as_i64()).unwrap_or_default(),
+        })
+    }
+
+    /// 마크 가격의 인덱스 대비 프리미엄 (비율)
+    pub fn premium(&self) -> Option<f64> {
+        (self.index_price > 0.0).then(|| (self.mark_price - self.index_price) / self.index_price)
+    }
+
+    /// 다음 정산까지 남은 시간 (ms, 지났으면 0)
+    pub fn time_to_funding(&self, now: i64) -> i64 {
+        (self.next_funding_time - now).max(0)
+    }
+}
+
+/// 무기한 선물 미결제약정 (계약 수량 기준)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct OpenInterest {
+    pub symbol: String,
+    pub open_interest: f64,
+    pub timestamp: i64,
+}
+
+impl OpenInterest {
+    /// Binance `GET /fapi/v1/openInterest` 응답 파싱
+    pub fn from_binance(value: &serde_json::Value) -> Option<Self> {
+        let v = value.get("openInterest")?;
+        Some(OpenInterest {
+            symbol: value.get("symbol")?.as_str()?.to_string(),
+            open_interest: v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))?,
+            timestamp: value.get("time").and_then(|v| v.as_i64()).unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_premium_index() {
+        let body = serde_json::json!({
+            "symbol": "BTCUSDT", "markPrice": "60010.00000000", "indexPrice": "60000.00000000",
+            "estimatedSettlePrice": "60005.1", "lastFundingRate": "0.00010000", "interestRate": "0.00010000",
+            "nextFundingTime": 1_700_028_800_000_i64, "time": 1_700_000_000_000_i64
+        });
+        let funding = FundingRate::from_premium_index(&body).unwrap();
+        assert_eq!(funding.symbol, "BTCUSDT");
+        assert_eq!((funding.mark_price, funding.funding_rate), (60010.0, 0.0001));
+        assert!((funding.premium().unwrap() - 10.0 / 60000.0).abs() < 1e-12);
+        assert_eq!(funding.time_to_funding(1_700_000_000_000), 28_800_000);
+        assert!(FundingRate::from_premium_index(&serde_json::json!({"symbol": "BTCUSDT"})).is_none());
+
+        let oi = OpenInterest::from_binance(&serde_json::json!({"openInterest": "10659.509", "symbol": "BTCUSDT", "time": 1_700_000_000_000_i64})).unwrap();
+        assert_eq!((oi.open_interest, oi.timestamp), (10659.509, 1_700_000_000_000));
+    }
+}
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+// Binance 숫자 문자열 / 숫자 필드 파싱
+fn num(value: &serde_json::Value, key: &str) -> Option<f64> {
+    let v = value.get(key)?;
+    v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// 선물 계정 증거금 요약
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MarginAccount {
+    /// 증거금 잔고 (지갑 잔고 + 미실현 손익)
+    pub margin_balance: f64,
+    /// 유지 증거금 합계
+    pub maintenance_margin: f64,
+    pub timestamp: i64,
+}
+
+impl MarginAccount {
+    /// Binance `GET /fapi/v2/account` 응답 파싱
+    pub fn from_binance_account(value: &serde_json::Value, timestamp: i64) -> Option<Self> {
+        Some(MarginAccount {
+            margin_balance: num(value, "totalMarginBalance")?,
+            maintenance_margin: num(value, "totalMaintMargin")?,
+            timestamp: value.get("updateTime").and_then(|v| v.as_i64()).filter(|t| *t > 0).unwrap_or(timestamp),
+        })
+    }
+
+    /// 증거금 비율 (유지 증거금 / 증거금 잔고, 1.0 이면 청산)
+    pub fn margin_ratio(&self) -> f64 {
+        if self.margin_balance > 0.0 {
+            self.maintenance_margin / self.margin_balance
+        } else if self.maintenance_margin > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 포지션별 증거금/청산 정보
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PositionMargin {
+    pub symbol: String,
+    /// 헤지 모드 포지션 쪽 ("LONG" / "SHORT"), 단방향 모드는 None
+    pub position_side: Option<String>,
+    /// 포지션 수량 (롱 +, 숏 -)
+    pub quantity: f64,
+    pub mark_price: f64,
+    /// 청산 가격 (거래소가 계산하지 않으면 None)
+    pub liquidation_price: Option<f64>,
+}
+
+impl PositionMargin {
+    /// Binance `GET /fapi/v2/positionRisk` 항목 파싱 (수량 0 인 항목은 None)
+    pub fn from_binance_position_risk(value: &serde_json::Value) -> Option<Self> {
+        let quantity = num(value, "positionAmt")?;
+        if quantity == 0.0 {
+            return None;
+        }
+        let position_side = value.get("positionSide").and_then(|v| v.as_str()).filter(|s| *s != "BOTH");
+        Some(PositionMargin {
+            symbol: value.get("symbol")?.as_str()?.to_string(),
+            position_side: position_side.map(str::to_string),
+            quantity,
+            mark_price: num(value, "markPrice")?,
+            liquidation_price: num(value, "liquidationPrice").filter(|p| *p > 0.0),
+        })
+    }
+
+    /// 마크 가격에서 청산 가격까지 남은 거리 (마크 가격 대비 비율)
+    pub fn liquidation_distance(&self) -> Option<f64> {
+        let liquidation = self.liquidation_price?;
+        (self.mark_price > 0.0).then(|| (self.mark_price - liquidation).abs() / self.mark_price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_binance_margin() {
+        let account = serde_json::json!({
+            "totalMarginBalance": "1000.00000000", "totalMaintMargin": "250.00000000",
+            "totalWalletBalance": "1100.0", "updateTime": 0
+        });
+        let account = MarginAccount::from_binance_account(&account, 42).unwrap();
+        assert_eq!((account.margin_ratio(), account.timestamp), (0.25, 42));
+        assert_eq!(MarginAccount { margin_balance: 0.0, maintenance_margin: 1.0, timestamp: 0 }.margin_ratio(), f64::INFINITY);
+
+        let risk = serde_json::json!([
+            {"symbol": "BTCUSDT", "positionAmt": "-0.500", "markPrice": "60000.0", "liquidationPrice": "66000.0", "positionSide": "BOTH"},
+            {"symbol": "ETHUSDT", "positionAmt": "0.000", "markPrice": "3000.0", "liquidationPrice": "0", "positionSide": "LONG"}
+        ]);
+        let positions: Vec<PositionMargin> = risk.as_array().unwrap().iter().filter_map(PositionMargin::from_binance_position_risk).collect();
+        assert_eq!(positions.len(), 1);
+        assert_eq!((positions[0].quantity, positions[0].position_side.as_deref()), (-0.5, None));
+        assert!((positions[0].liquidation_distance().unwrap() - 0.1).abs() < 1e-12);
+    }
+}
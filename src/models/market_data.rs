@@ -1,29 +1,32 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::timestamp::Timestamp;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketData {
     pub symbol: String,
-    pub timestamp: i64,
-    pub open: f64,
-    pub high: f64,
-    pub low: f64,
-    pub close: f64,
-    pub volume: f64,
+    pub timestamp: Timestamp,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
 }
 
 impl MarketData {
     pub fn new(
         symbol: impl Into<String>,
-        timestamp: i64,
-        open: f64,
-        high: f64,
-        low: f64,
-        close: f64,
-        volume: f64,
+        timestamp: impl Into<Timestamp>,
+        open: Decimal,
+        high: Decimal,
+        low: Decimal,
+        close: Decimal,
+        volume: Decimal,
     ) -> Self {
         MarketData {
             symbol: symbol.into(),
-            timestamp,
+            timestamp: timestamp.into(),
             open,
             high,
             low,
@@ -32,8 +35,15 @@ impl MarketData {
         }
     }
 
-    pub fn vwap(&self) -> f64 {
-        let typical_price = (self.high + self.low + self.close) / 3.0;
+    pub fn vwap(&self) -> Decimal {
+        let typical_price = (self.high + self.low + self.close) / Decimal::from(3);
         typical_price  // In a real implementation, you'd multiply by volume and divide by total volume
     }
+
+    // 지표 계산용 f64 값
+    pub fn open_f64(&self) -> f64 { to_f64(self.open) }
+    pub fn high_f64(&self) -> f64 { to_f64(self.high) }
+    pub fn low_f64(&self) -> f64 { to_f64(self.low) }
+    pub fn close_f64(&self) -> f64 { to_f64(self.close) }
+    pub fn volume_f64(&self) -> f64 { to_f64(self.volume) }
 }
\ No newline at end of file
@@ -2,3 +2,10 @@ pub mod market_data;
 pub mod order;
 pub mod position;
 pub mod trade;
+pub mod order_book;
+pub mod timestamp;
+pub mod funding;
+pub mod fee;
+pub mod margin;
+pub mod symbol_info;
+pub mod decimal;
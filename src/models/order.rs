@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::models::decimal::Decimal;
+use crate::models::fee::Liquidity;
+use crate::models::timestamp::Timestamp;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, Hash,PartialEq)]
 pub struct OrderId(pub String);
 
@@ -10,7 +14,7 @@ impl fmt::Display for OrderId {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum OrderSide {
     Buy,
     Sell,
@@ -44,21 +48,32 @@ pub struct Order {
     pub symbol: String,
     pub side: OrderSide,
     pub order_type: OrderType,
-    pub quantity: f64,
-    pub price: f64,
-    pub stop_price: Option<f64>,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub stop_price: Option<Decimal>,
     pub time_in_force: String,
-    pub created_at: i64,
+    pub created_at: Timestamp,
     pub client_order_id: Option<String>,
 
     // Advanced order parameters
-    pub iceberg_qty: Option<f64>,           // For Iceberg orders
+    pub iceberg_qty: Option<Decimal>,          // For Iceberg orders
     pub trailing_delta: Option<f64>,        // For Trailing Stop orders
     pub execution_interval: Option<i64>,    // For TWAP/VWAP orders
     pub target_percentage: Option<f64>,     // For participation rate
     // Futures-specific parameters
     pub reduce_only: Option<bool>,          // Reduce-only flag
     pub position_side: Option<String>,      // "BOTH"|"LONG"|"SHORT"
+    // Venue name in the exchange registry (default exchange when None)
+    pub exchange: Option<String>,
+    // Take-profit / stop-loss to register as an OCO bracket once this entry fills
+    pub attached_exits: Option<AttachedExits>,
+}
+
+/// Protective exit prices attached to an entry order
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct AttachedExits {
+    pub take_profit: Decimal,
+    pub stop_loss: Decimal,
 }
 
 impl Order {
@@ -66,8 +81,8 @@ impl Order {
         symbol: impl Into<String>,
         side: OrderSide,
         order_type: OrderType,
-        quantity: f64,
-        price: f64,
+        quantity: Decimal,
+        price: Decimal,
     ) -> Self {
         Order {
             id: OrderId("".to_string()),  // This will be set by the exchange
@@ -78,7 +93,7 @@ impl Order {
             price,
             stop_price: None,
             time_in_force: "GTC".to_string(),  // Good Till Cancelled
-            created_at: chrono::Utc::now().timestamp_millis(),
+            created_at: Timestamp::now(),
             client_order_id: None,
             iceberg_qty: None,
             trailing_delta: None,
@@ -86,10 +101,12 @@ impl Order {
             target_percentage: None,
             reduce_only: None,
             position_side: None,
+            exchange: None,
+            attached_exits: None,
         }
     }
 
-    pub fn with_stop_price(mut self, stop_price: f64) -> Self {
+    pub fn with_stop_price(mut self, stop_price: Decimal) -> Self {
         self.stop_price = Some(stop_price);
         self
     }
@@ -104,7 +121,7 @@ impl Order {
         self
     }
 
-    pub fn with_iceberg_qty(mut self, iceberg_qty: f64) -> Self {
+    pub fn with_iceberg_qty(mut self, iceberg_qty: Decimal) -> Self {
         self.iceberg_qty = Some(iceberg_qty);
         self.order_type = OrderType::Iceberg;
         self
@@ -134,8 +151,59 @@ impl Order {
         self
     }
 
+    pub fn with_exchange(mut self, exchange: impl Into<String>) -> Self {
+        self.exchange = Some(exchange.into());
+        self
+    }
+
     pub fn with_position_side(mut self, side: impl Into<String>) -> Self {
         self.position_side = Some(side.into());
         self
     }
-}
\ No newline at end of file
+
+    pub fn with_attached_exits(mut self, take_profit: Decimal, stop_loss: Decimal) -> Self {
+        self.attached_exits = Some(AttachedExits { take_profit, stop_loss });
+        self
+    }
+}
+/// Push-style order event (user-data stream execution report)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrderUpdate {
+    pub order_id: OrderId,
+    pub client_order_id: Option<String>,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub status: OrderStatus,
+    /// Price and quantity of the fill carried by this event (0 when not a trade)
+    pub last_fill_price: Decimal,
+    pub last_fill_quantity: Decimal,
+    pub cumulative_quantity: Decimal,
+    pub fee: Decimal,
+    pub fee_asset: Option<String>,
+    /// Maker/taker side of the fill when the venue reports it
+    pub liquidity: Option<Liquidity>,
+    pub timestamp: i64,
+}
+
+impl OrderUpdate {
+    pub fn is_fill(&self) -> bool {
+        self.last_fill_quantity > Decimal::ZERO
+    }
+
+    /// Quantity this report adds on top of `seen` already recorded for the order: the cumulative
+    /// quantity dedups repeated reports when present, otherwise the fill quantity is taken as is
+    pub fn new_fill_quantity(&self, seen: Decimal) -> Decimal {
+        if self.cumulative_quantity > Decimal::ZERO {
+            (self.cumulative_quantity - seen).min(self.last_fill_quantity).max(Decimal::ZERO)
+        } else {
+            self.last_fill_quantity
+        }
+    }
+}
+
+/// Executed quantity and average fill price of an order as reported by the venue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderFill {
+    pub executed_quantity: Decimal,
+    pub average_price: Decimal,
+}
@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::order::OrderSide;
+
+/// 호가 한 단계
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub quantity: f64,
+}
+
+impl PriceLevel {
+    pub fn new(price: f64, quantity: f64) -> Self {
+        PriceLevel { price, quantity }
+    }
+}
+
+/// `[[price, quantity], ...]` 형식 JSON 배열 파싱 (문자열/숫자 모두 허용)
+pub fn levels_from_json(value: &serde_json::Value) -> Vec<PriceLevel> {
+    let num = |v: &serde_json::Value| v.as_f64().or_else(|| v.as_str().and_then(|s| s.parse().ok()));
+    value.as_array()
+        .map(|levels| levels.iter()
+            .filter_map(|level| Some(PriceLevel::new(num(level.get(0)?)?, num(level.get(1)?)?)))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// 호가창 - bids 는 높은 가격순, asks 는 낮은 가격순
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub symbol: String,
+    pub timestamp: i64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+impl OrderBook {
+    /// 정렬되지 않은 호가로 생성 (수량 0 레벨 제거)
+    pub fn new(symbol: impl Into<String>, timestamp: i64, bids: Vec<PriceLevel>, asks: Vec<PriceLevel>) -> Self {
+        let mut book = OrderBook {
+            symbol: symbol.into(),
+            timestamp,
+            bids,
+            asks,
+        };
+        book.normalize();
+        book
+    }
+
+    fn normalize(&mut self) {
+        self.bids.retain(|l| l.quantity > 0.0);
+        self.asks.retain(|l| l.quantity > 0.0);
+        self.bids.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap_or(std::cmp::Ordering::Equal));
+        self.asks.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids.first().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks.first().copied()
+    }
+
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()?.price + self.best_ask()?.price) / 2.0)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// 주문 방향 기준으로 체결 상대가 되는 호가 (매수 -> asks, 매도 -> bids)
+    pub fn opposite_levels(&self, side: &OrderSide) -> &[PriceLevel] {
+        match side {
+            OrderSide::Buy => &self.asks,
+            OrderSide::Sell => &self.bids,
+        }
+    }
+
+    /// 상위 levels 단계의 상대 호가 수량 합
+    pub fn depth(&self, side: &OrderSide, levels: usize) -> f64 {
+        self.opposite_levels(side).iter().take(levels).map(|l| l.quantity).sum()
+    }
+
+    /// quantity 를 시장가로 소화할 때의 평균 체결가 (호가 부족 시 None)
+    pub fn depth_weighted_price(&self, side: &OrderSide, quantity: f64) -> Option<f64> {
+        if quantity <= 0.0 {
+            return None;
+        }
+        let mut remaining = quantity;
+        let mut notional = 0.0;
+        for level in self.opposite_levels(side) {
+            let take = remaining.min(level.quantity);
+            notional += take * level.price;
+            remaining -= take;
+            if remaining <= 0.0 {
+                return Some(notional / quantity);
+            }
+        }
+        None
+    }
+
+    /// 최우선 호가 대비 max_slippage_bps 이내에서 소화 가능한 수량
+    pub fn liquidity_within(&self, side: &OrderSide, max_slippage_bps: f64) -> f64 {
+        let levels = self.opposite_levels(side);
+        let Some(best) = levels.first() else { return 0.0 };
+        let limit = match side {
+            OrderSide::Buy => best.price * (1.0 + max_slippage_bps / 10_000.0),
+            OrderSide::Sell => best.price * (1.0 - max_slippage_bps / 10_000.0),
+        };
+        levels.iter()
+            .take_while(|l| match side {
+                OrderSide::Buy => l.price <= limit,
+                OrderSide::Sell => l.price >= limit,
+            })
+            .map(|l| l.quantity)
+            .sum()
+    }
+
+    /// 증분 갱신 적용 (수량 0 은 해당 가격 레벨 삭제)
+    pub fn apply_update(&mut self, timestamp: i64, bids: &[PriceLevel], asks: &[PriceLevel]) {
+        fn merge(levels: &mut Vec<PriceLevel>, updates: &[PriceLevel]) {
+            for update in updates {
+                match levels.iter_mut().find(|l| l.price == update.price) {
+                    Some(level) => level.quantity = update.quantity,
+                    None => levels.push(*update),
+                }
+            }
+        }
+        merge(&mut self.bids, bids);
+        merge(&mut self.asks, asks);
+        self.timestamp = timestamp;
+        self.normalize();
+    }
+
+    /// 상위 levels 단계만 남김
+    pub fn truncate(&mut self, levels: usize) {
+        self.bids.truncate(levels);
+        self.asks.truncate(levels);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_book_depth() {
+        let mut book = OrderBook::new(
+            "BTCUSDT",
+            1_000,
+            vec![PriceLevel::new(99.0, 2.0), PriceLevel::new(100.0, 1.0)],
+            vec![PriceLevel::new(102.0, 3.0), PriceLevel::new(101.0, 1.0), PriceLevel::new(103.0, 0.0)],
+        );
+        assert_eq!(book.best_bid().unwrap().price, 100.0);
+        assert_eq!(book.best_ask().unwrap().price, 101.0);
+        assert_eq!(book.mid_price(), Some(100.5));
+        assert_eq!(book.asks.len(), 2);
+
+        // 1 @ 101 + 1 @ 102
+        assert_eq!(book.depth_weighted_price(&OrderSide::Buy, 2.0), Some(101.5));
+        assert_eq!(book.depth_weighted_price(&OrderSide::Buy, 5.0), None);
+        assert_eq!(book.depth(&OrderSide::Sell, 1), 1.0);
+        assert_eq!(book.liquidity_within(&OrderSide::Buy, 50.0), 1.0);
+
+        book.apply_update(2_000, &[PriceLevel::new(100.0, 0.0)], &[PriceLevel::new(100.5, 4.0)]);
+        assert_eq!(book.best_bid().unwrap().price, 99.0);
+        assert_eq!(book.best_ask().unwrap(), PriceLevel::new(100.5, 4.0));
+        assert_eq!(book.timestamp, 2_000);
+
+        let levels = levels_from_json(&serde_json::json!([["100.5", "2.0"], [101, 1.5]]));
+        assert_eq!(levels, vec![PriceLevel::new(100.5, 2.0), PriceLevel::new(101.0, 1.5)]);
+    }
+}
@@ -1,44 +1,46 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::decimal::Decimal;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     pub symbol: String,
-    pub quantity: f64,
-    pub entry_price: f64,
-    pub current_price: f64,
-    pub unrealized_pnl: f64,
+    pub quantity: Decimal,
+    pub entry_price: Decimal,
+    pub current_price: Decimal,
+    pub unrealized_pnl: Decimal,
 }
 
 impl Position {
-    pub fn new(symbol: impl Into<String>, quantity: f64, entry_price: f64) -> Self {
+    pub fn new(symbol: impl Into<String>, quantity: Decimal, entry_price: Decimal) -> Self {
         Position {
             symbol: symbol.into(),
             quantity,
             entry_price,
             current_price: entry_price,
-            unrealized_pnl: 0.0,
+            unrealized_pnl: Decimal::ZERO,
         }
     }
 
     pub fn is_long(&self) -> bool {
-        self.quantity > 0.0
+        self.quantity > Decimal::ZERO
     }
 
     pub fn is_short(&self) -> bool {
-        self.quantity < 0.0
+        self.quantity < Decimal::ZERO
     }
 
-    pub fn update_price(&mut self, new_price: f64) {
+    pub fn update_price(&mut self, new_price: Decimal) {
         self.current_price = new_price;
         self.calculate_pnl();
     }
 
     pub fn calculate_pnl(&mut self) {
-        if self.quantity != 0.0 {
-            let direction = self.quantity.signum();
-            self.unrealized_pnl = direction * (self.current_price - self.entry_price).abs() * self.quantity.abs();
+        if !self.quantity.is_zero() {
+            let pnl = (self.current_price - self.entry_price).abs() * self.quantity.abs();
+            self.unrealized_pnl = if self.quantity.is_sign_negative() { -pnl } else { pnl };
         } else {
-            self.unrealized_pnl = 0.0;
+            self.unrealized_pnl = Decimal::ZERO;
         }
     }
-}
\ No newline at end of file
+}
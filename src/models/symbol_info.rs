@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::decimal::Decimal;
+use crate::models::order::OrderSide;
+
+/// 거래소 심볼 규칙 - 호가 단위, 수량 단위, 최소 수량, 최소 주문 금액 (0 이면 제한 없음)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    pub tick_size: Decimal,
+    pub step_size: Decimal,
+    pub min_qty: Decimal,
+    pub min_notional: Decimal,
+}
+
+impl SymbolInfo {
+    /// 호가 단위로 반올림 - 매수는 내림, 매도는 올림 (지정가보다 불리하게 체결되지 않도록)
+    pub fn round_price(&self, price: Decimal, side: &OrderSide) -> Decimal {
+        if self.tick_size <= Decimal::ZERO {
+            return price;
+        }
+        let steps = price / self.tick_size;
+        let steps = match side {
+            OrderSide::Buy => steps.floor(),
+            OrderSide::Sell => steps.ceil(),
+        };
+        (steps * self.tick_size).normalize()
+    }
+
+    /// 수량 단위로 내림 (주문 수량을 늘리지 않음)
+    pub fn round_quantity(&self, quantity: Decimal) -> Decimal {
+        if self.step_size <= Decimal::ZERO {
+            return quantity;
+        }
+        ((quantity / self.step_size).floor() * self.step_size).normalize()
+    }
+}
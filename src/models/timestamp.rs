@@ -0,0 +1,167 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::TradingError;
+
+/// UTC 기준 epoch 밀리초 시각
+///
+/// 직렬화는 항상 epoch ms 정수로 하고, 역직렬화는 epoch ms(정수/숫자 문자열)와
+/// RFC3339 문자열(`2024-01-01T00:00:00Z`, 오프셋 포함 시 UTC 로 변환)을 모두 받음
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub const fn from_millis(millis: i64) -> Self {
+        Timestamp(millis)
+    }
+
+    pub fn now() -> Self {
+        Timestamp(Utc::now().timestamp_millis())
+    }
+
+    pub const fn as_millis(self) -> i64 {
+        self.0
+    }
+
+    /// 범위를 벗어난 값은 epoch(1970-01-01) 으로 대체
+    pub fn to_datetime(self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.0).single().unwrap_or_default()
+    }
+
+    pub fn to_rfc3339(self) -> String {
+        self.to_datetime().to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+
+    /// epoch ms 숫자 또는 RFC3339 문자열 파싱
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        let s = s.trim();
+        if let Ok(millis) = s.parse::<i64>() {
+            return Ok(Timestamp(millis));
+        }
+        DateTime::parse_from_rfc3339(s)
+            .map(|t| Timestamp(t.with_timezone(&Utc).timestamp_millis()))
+            .map_err(|e| TradingError::ParseError(format!("Invalid timestamp '{}': {}", s, e)))
+    }
+}
+
+impl From<i64> for Timestamp {
+    fn from(millis: i64) -> Self {
+        Timestamp(millis)
+    }
+}
+
+impl From<Timestamp> for i64 {
+    fn from(ts: Timestamp) -> Self {
+        ts.0
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for Timestamp {
+    fn from(t: DateTime<Tz>) -> Self {
+        Timestamp(t.timestamp_millis())
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(ts: Timestamp) -> Self {
+        ts.to_datetime()
+    }
+}
+
+/// 밀리초 더하기
+impl Add<i64> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, millis: i64) -> Timestamp {
+        Timestamp(self.0 + millis)
+    }
+}
+
+/// 밀리초 빼기
+impl Sub<i64> for Timestamp {
+    type Output = Timestamp;
+
+    fn sub(self, millis: i64) -> Timestamp {
+        Timestamp(self.0 - millis)
+    }
+}
+
+/// 두 시각 간 간격 (밀리초)
+impl Sub for Timestamp {
+    type Output = i64;
+
+    fn sub(self, other: Timestamp) -> i64 {
+        self.0 - other.0
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_rfc3339())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimestampVisitor;
+
+        impl<'de> Visitor<'de> for TimestampVisitor {
+            type Value = Timestamp;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("epoch milliseconds or an RFC3339 string")
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Timestamp, E> {
+                Ok(Timestamp(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Timestamp, E> {
+                i64::try_from(v).map(Timestamp).map_err(|_| E::custom("timestamp out of range"))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Timestamp, E> {
+                Ok(Timestamp(v as i64))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Timestamp, E> {
+                Timestamp::parse(v).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_formats() {
+        let ts = Timestamp::from_millis(1_704_067_200_000);
+        assert_eq!(ts.to_rfc3339(), "2024-01-01T00:00:00.000Z");
+        assert_eq!(Timestamp::parse("2024-01-01T09:00:00+09:00").unwrap(), ts);
+        assert_eq!(Timestamp::parse("1704067200000").unwrap(), ts);
+        assert!(Timestamp::parse("yesterday").is_err());
+
+        // 역직렬화는 정수/문자열/RFC3339 모두 허용, 직렬화는 epoch ms
+        for input in ["1704067200000", "\"1704067200000\"", "\"2024-01-01T00:00:00Z\""] {
+            assert_eq!(serde_json::from_str::<Timestamp>(input).unwrap(), ts);
+        }
+        assert_eq!(serde_json::to_string(&ts).unwrap(), "1704067200000");
+
+        assert_eq!((ts + 60_000) - ts, 60_000);
+        assert_eq!(Timestamp::from(ts.to_datetime()), ts);
+    }
+}
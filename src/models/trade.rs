@@ -1,14 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::decimal::Decimal;
 use crate::models::order::{OrderId, OrderSide};
+use crate::models::timestamp::Timestamp;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub id: String,
     pub symbol: String,
-    pub price: f64,
-    pub quantity: f64,
-    pub timestamp: i64,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub timestamp: Timestamp,
     pub order_id: OrderId,
     pub side: OrderSide,
 }
@@ -17,9 +19,9 @@ impl Trade {
     pub fn new(
         id: impl Into<String>,
         symbol: impl Into<String>,
-        price: f64,
-        quantity: f64,
-        timestamp: i64,
+        price: Decimal,
+        quantity: Decimal,
+        timestamp: impl Into<Timestamp>,
         order_id: OrderId,
         side: OrderSide,
     ) -> Self {
@@ -28,13 +30,13 @@ impl Trade {
             symbol: symbol.into(),
             price,
             quantity,
-            timestamp,
+            timestamp: timestamp.into(),
             order_id,
             side,
         }
     }
 
-    pub fn value(&self) -> f64 {
+    pub fn value(&self) -> Decimal {
         self.price * self.quantity
     }
 }
\ No newline at end of file
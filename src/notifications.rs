@@ -0,0 +1,435 @@
+/**
+* filename : notifications
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 알림 채널 (Telegram / Discord / HTTP 웹훅 / 이메일) - 이벤트 종류별 라우팅
+**/
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::config::{NotificationConfig, NotificationSinkConfig, SmtpSecurity};
+use crate::error::TradingError;
+use crate::webhooks::{WebhookEvent, WebhookEventKind};
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+/// Discord 메시지 최대 길이
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// 채널로 보내는 사람이 읽는 알림
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub event: WebhookEventKind,
+    pub title: String,
+    /// "key: value" 줄로 정리한 본문
+    pub text: String,
+    pub timestamp: i64,
+    pub data: serde_json::Value,
+}
+
+impl Alert {
+    /// 제목과 본문을 합친 메시지 (채팅 채널용)
+    pub fn message(&self) -> String {
+        if self.text.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n{}", self.title, self.text)
+        }
+    }
+}
+
+fn event_title(kind: WebhookEventKind) -> &'static str {
+    match kind {
+        WebhookEventKind::OrderFilled => "Order filled",
+        WebhookEventKind::PositionOpened => "Position opened",
+        WebhookEventKind::PositionClosed => "Position closed",
+        WebhookEventKind::RiskBreach => "Risk limit breached",
+        WebhookEventKind::StrategyToggled => "Strategy toggled",
+        WebhookEventKind::StrategyAnomaly => "Strategy anomaly",
+        WebhookEventKind::StrategySignal => "Strategy signal",
+        WebhookEventKind::DeadManTriggered => "Dead man's switch triggered",
+        WebhookEventKind::DegradedModeEntered => "Degraded mode entered",
+        WebhookEventKind::DegradedModeRecovered => "Degraded mode recovered",
+        WebhookEventKind::TradingHalted => "Trading halted",
+        WebhookEventKind::TradingResumed => "Trading resumed",
+        WebhookEventKind::ConnectorDisconnected => "Connector disconnected",
+        WebhookEventKind::ConnectorReconnected => "Connector reconnected",
+        WebhookEventKind::MarginAlert => "Margin alert",
+    }
+}
+
+/// 이벤트를 알림으로 변환 (제목에 전략/심볼을 붙이고 나머지 필드는 본문으로)
+pub fn format_alert(event: &WebhookEvent) -> Alert {
+    let mut title = format!("[xQuant] {}", event_title(event.event));
+    let subject: Vec<String> = ["strategy", "symbol", "connector"]
+        .iter()
+        .filter_map(|key| event.data.get(*key).and_then(|v| v.as_str()).map(str::to_string))
+        .collect();
+    if !subject.is_empty() {
+        title.push_str(&format!(" - {}", subject.join(" ")));
+    }
+
+    let text = match &event.data {
+        serde_json::Value::Object(fields) => {
+            let mut keys: Vec<&String> = fields.keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .filter(|key| !fields[*key].is_null())
+                .map(|key| match &fields[key] {
+                    serde_json::Value::String(s) => format!("{}: {}", key, s),
+                    other => format!("{}: {}", key, other),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    };
+
+    Alert {
+        event: event.event,
+        title,
+        text,
+        timestamp: event.timestamp,
+        data: event.data.clone(),
+    }
+}
+
+/// 알림 전송 채널
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// 로그에 표시할 채널 이름
+    fn name(&self) -> String;
+
+    async fn send(&self, alert: &Alert) -> Result<(), TradingError>;
+}
+
+/// HTTP 요청 전송 (`label` 은 오류 메시지에 표시)
+async fn send_request(label: &str, request: reqwest::RequestBuilder) -> Result<(), TradingError> {
+    let res = request.send().await.map_err(|e| TradingError::ExecutionError(format!("{}: {}", label, e)))?;
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        let status = res.status();
+        let body = res.text().await.unwrap_or_default();
+        Err(TradingError::ExecutionError(format!("{} responded {}: {}", label, status, body)))
+    }
+}
+
+/// Telegram 봇 sendMessage
+pub struct TelegramSink {
+    http: reqwest::Client,
+    api_url: String,
+    bot_token: String,
+    chat_id: String,
+}
+
+#[async_trait]
+impl NotificationSink for TelegramSink {
+    fn name(&self) -> String {
+        format!("telegram:{}", self.chat_id)
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), TradingError> {
+        let url = format!("{}/bot{}/sendMessage", self.api_url.trim_end_matches('/'), self.bot_token);
+        let request = self.http.post(&url).json(&serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": alert.message(),
+            "disable_web_page_preview": true,
+        }));
+        // 토큰이 로그에 남지 않도록 URL 대신 채널 이름 사용
+        send_request(&self.name(), request).await
+    }
+}
+
+/// Discord 채널 웹훅
+pub struct DiscordSink {
+    http: reqwest::Client,
+    webhook_url: String,
+}
+
+#[async_trait]
+impl NotificationSink for DiscordSink {
+    fn name(&self) -> String {
+        "discord".to_string()
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), TradingError> {
+        let content: String = alert.message().chars().take(DISCORD_MAX_CHARS).collect();
+        let request = self.http.post(&self.webhook_url).json(&serde_json::json!({ "content": content }));
+        send_request(&self.name(), request).await
+    }
+}
+
+/// 범용 HTTP 웹훅 (알림 JSON 그대로 전송)
+pub struct HttpWebhookSink {
+    http: reqwest::Client,
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+#[async_trait]
+impl NotificationSink for HttpWebhookSink {
+    fn name(&self) -> String {
+        format!("webhook:{}", self.url)
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), TradingError> {
+        let mut request = self.http.post(&self.url).json(alert);
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        send_request(&self.url, request).await
+    }
+}
+
+/// SMTP 이메일
+pub struct EmailSink {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailSink {
+    pub fn new(
+        smtp_host: &str,
+        smtp_port: Option<u16>,
+        security: SmtpSecurity,
+        credentials: Option<(String, String)>,
+        from: &str,
+        to: &[String],
+    ) -> Result<Self, TradingError> {
+        let smtp_error = |e: lettre::transport::smtp::Error| TradingError::InvalidParameter(format!("smtp {}: {}", smtp_host, e));
+        let mut builder = match security {
+            SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host).map_err(smtp_error)?,
+            SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp_host).map_err(smtp_error)?,
+            SmtpSecurity::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host),
+        };
+        if let Some(port) = smtp_port {
+            builder = builder.port(port);
+        }
+        if let Some((username, password)) = credentials {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        let parse = |address: &str| address.parse::<Mailbox>().map_err(|e| TradingError::InvalidParameter(format!("email address {}: {}", address, e)));
+        let to = to.iter().map(|a| parse(a)).collect::<Result<Vec<_>, _>>()?;
+        if to.is_empty() {
+            return Err(TradingError::InvalidParameter("email notification needs at least one recipient".to_string()));
+        }
+        Ok(EmailSink { transport: builder.build(), from: parse(from)?, to })
+    }
+}
+
+#[async_trait]
+impl NotificationSink for EmailSink {
+    fn name(&self) -> String {
+        format!("email:{}", self.to.iter().map(|m| m.email.to_string()).collect::<Vec<_>>().join(","))
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), TradingError> {
+        let mut message = Message::builder().from(self.from.clone()).subject(alert.title.clone()).header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            message = message.to(to.clone());
+        }
+        let message = message.body(alert.text.clone()).map_err(|e| TradingError::InvalidParameter(format!("email build failed: {}", e)))?;
+        self.transport.send(message).await.map(|_| ()).map_err(|e| TradingError::ExecutionError(format!("{}: {}", self.name(), e)))
+    }
+}
+
+/// 설정으로 채널 생성
+pub fn build_sink(config: &NotificationSinkConfig, http: &reqwest::Client) -> Result<Arc<dyn NotificationSink>, TradingError> {
+    Ok(match config {
+        NotificationSinkConfig::Telegram { bot_token, chat_id, api_url } => Arc::new(TelegramSink {
+            http: http.clone(),
+            api_url: api_url.clone().unwrap_or_else(|| TELEGRAM_API_URL.to_string()),
+            bot_token: bot_token.clone(),
+            chat_id: chat_id.clone(),
+        }),
+        NotificationSinkConfig::Discord { webhook_url } => Arc::new(DiscordSink { http: http.clone(), webhook_url: webhook_url.clone() }),
+        NotificationSinkConfig::Webhook { url, headers } => Arc::new(HttpWebhookSink {
+            http: http.clone(),
+            url: url.clone(),
+            headers: headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }),
+        NotificationSinkConfig::Email { smtp_host, smtp_port, security, username, password, from, to } => {
+            let credentials = username.clone().map(|u| (u, password.clone().unwrap_or_default()));
+            Arc::new(EmailSink::new(smtp_host, *smtp_port, *security, credentials, from, to)?)
+        }
+    })
+}
+
+/// 채널과 라우팅 규칙
+#[derive(Clone)]
+struct Route {
+    sink: Arc<dyn NotificationSink>,
+    events: Vec<String>,
+    max_retries: u32,
+}
+
+impl Route {
+    fn accepts(&self, event: &str) -> bool {
+        self.events.is_empty() || self.events.iter().any(|e| e == event)
+    }
+}
+
+/// 알림 발송기 - 이벤트 버스(`WebhookDispatcher`)에서 받은 이벤트를 채널별로 전달
+#[derive(Clone)]
+pub struct Notifier {
+    sender: Option<mpsc::UnboundedSender<WebhookEvent>>,
+}
+
+impl Notifier {
+    /// 채널 없이 동작하는 발송기 (모든 이벤트 무시)
+    pub fn disabled() -> Self {
+        Notifier { sender: None }
+    }
+
+    /// 설정된 채널로 전송하는 워커 시작 (생성에 실패한 채널은 경고 후 제외)
+    pub fn spawn(channels: Vec<NotificationConfig>) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        let routes: Vec<Route> = channels
+            .iter()
+            .filter_map(|channel| match build_sink(&channel.sink, &http) {
+                Ok(sink) => Some(Route { sink, events: channel.events.clone(), max_retries: channel.max_retries }),
+                Err(e) => {
+                    log::warn!("notification channel disabled: {}", e);
+                    None
+                }
+            })
+            .collect();
+        Self::with_sinks(routes)
+    }
+
+    fn with_sinks(routes: Vec<Route>) -> Self {
+        if routes.is_empty() {
+            return Self::disabled();
+        }
+
+        let (sender, mut receiver) = mpsc::unbounded_channel::<WebhookEvent>();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                let alert = Arc::new(format_alert(&event));
+                for route in routes.iter().filter(|r| r.accepts(event.event.as_str())) {
+                    // 느린 채널이 다른 채널을 막지 않도록 개별 태스크로 전송
+                    let route = route.clone();
+                    let alert = alert.clone();
+                    tokio::spawn(async move {
+                        deliver(&route, &alert).await;
+                    });
+                }
+            }
+        });
+
+        Notifier { sender: Some(sender) }
+    }
+
+    pub fn notify(&self, event: &WebhookEvent) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(event.clone());
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some()
+    }
+}
+
+/// 단일 채널 전송 (지수 백오프 재시도)
+async fn deliver(route: &Route, alert: &Alert) {
+    let mut attempt = 0u32;
+    loop {
+        match route.sink.send(alert).await {
+            Ok(()) => return,
+            Err(e) => log::warn!("notification {} failed: {} (attempt {})", route.sink.name(), e, attempt + 1),
+        }
+        if attempt >= route.max_retries {
+            log::error!("notification {} dropped after {} attempts", route.sink.name(), attempt + 1);
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(1000u64.saturating_mul(2u64.pow(attempt)))).await;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct RecordingSink {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl NotificationSink for RecordingSink {
+        fn name(&self) -> String {
+            "recording".to_string()
+        }
+
+        async fn send(&self, alert: &Alert) -> Result<(), TradingError> {
+            self.sent.lock().unwrap().push(alert.title.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_format_and_route_alerts() {
+        let event = WebhookEvent {
+            event: WebhookEventKind::OrderFilled,
+            timestamp: 1700000000000,
+            data: serde_json::json!({"strategy": "RSI 14", "symbol": "BTCUSDT", "side": "Buy", "quantity": 0.5, "reason": null}),
+        };
+        let alert = format_alert(&event);
+        assert_eq!(alert.title, "[xQuant] Order filled - RSI 14 BTCUSDT");
+        assert_eq!(alert.text, "quantity: 0.5\nside: Buy\nstrategy: RSI 14\nsymbol: BTCUSDT");
+
+        let config: NotificationConfig = serde_json::from_value(serde_json::json!({
+            "kind": "telegram", "bot_token": "t", "chat_id": "42", "events": ["risk_breach"]
+        })).unwrap();
+        assert!(matches!(config.sink, NotificationSinkConfig::Telegram { .. }));
+        assert!(config.accepts("risk_breach"));
+        assert!(!config.accepts("order_filled"));
+        assert_eq!(config.max_retries, 2);
+
+        // 리스크 채널은 risk_breach 만, 전체 채널은 모든 이벤트를 받는다
+        let risk = Arc::new(Mutex::new(Vec::new()));
+        let all = Arc::new(Mutex::new(Vec::new()));
+        let notifier = Notifier::with_sinks(vec![
+            Route { sink: Arc::new(RecordingSink { sent: risk.clone() }), events: vec!["risk_breach".to_string()], max_retries: 0 },
+            Route { sink: Arc::new(RecordingSink { sent: all.clone() }), events: Vec::new(), max_retries: 0 },
+        ]);
+        notifier.notify(&event);
+        notifier.notify(&WebhookEvent {
+            event: WebhookEventKind::ConnectorDisconnected,
+            timestamp: 1700000000001,
+            data: serde_json::json!({"connector": "market_stream", "detail": "stream closed"}),
+        });
+        notifier.notify(&WebhookEvent { event: WebhookEventKind::RiskBreach, timestamp: 1700000000002, data: serde_json::json!({"reason": "drawdown"}) });
+        for _ in 0..50 {
+            if all.lock().unwrap().len() == 3 && risk.lock().unwrap().len() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut all = all.lock().unwrap().clone();
+        all.sort();
+        assert_eq!(all, vec![
+            "[xQuant] Connector disconnected - market_stream".to_string(),
+            "[xQuant] Order filled - RSI 14 BTCUSDT".to_string(),
+            "[xQuant] Risk limit breached".to_string(),
+        ]);
+        assert_eq!(risk.lock().unwrap().clone(), vec!["[xQuant] Risk limit breached".to_string()]);
+    }
+}
@@ -0,0 +1,218 @@
+//! 브래킷 주문 (진입 + 익절 + 손절)
+//!
+//! 진입 주문이 전량 체결되면 반대 방향의 익절 지정가와 손절 스탑 주문을 함께 내고,
+//! 둘 중 하나가 체결되면 나머지를 취소한다(OCO). 상태 전이는 주문 상태만 보고 결정하며
+//! 실제 제출/취소는 `core::oco_manager::OcoManager` 가 수행한다.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderId, OrderSide, OrderStatus, OrderType};
+
+/// 브래킷 주문 요청
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BracketOrder {
+    /// 진입 주문 (시장가 또는 지정가)
+    pub entry: Order,
+    /// 익절 지정가
+    pub take_profit: Decimal,
+    /// 손절 발동가 (발동 시 시장가 청산)
+    pub stop_loss: Decimal,
+}
+
+impl BracketOrder {
+    pub fn new(entry: Order, take_profit: Decimal, stop_loss: Decimal) -> Self {
+        BracketOrder { entry, take_profit, stop_loss }
+    }
+
+    /// 가격 관계 검증 - 매수 진입이면 손절 < 진입가 < 익절 (매도는 반대)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.entry.quantity <= Decimal::ZERO {
+            return Err("entry quantity must be positive".to_string());
+        }
+        if self.take_profit <= Decimal::ZERO || self.stop_loss <= Decimal::ZERO {
+            return Err("take_profit and stop_loss must be positive".to_string());
+        }
+        if !matches!(self.entry.order_type, OrderType::Market | OrderType::Limit) {
+            return Err(format!("{:?} entry is not supported, use market or limit", self.entry.order_type));
+        }
+        let (low, high) = match self.entry.side {
+            OrderSide::Buy => (self.stop_loss, self.take_profit),
+            OrderSide::Sell => (self.take_profit, self.stop_loss),
+        };
+        if low >= high {
+            return Err(format!("{:?} bracket needs stop_loss on the losing side of take_profit", self.entry.side));
+        }
+        let entry_price = self.entry.price;
+        if entry_price > Decimal::ZERO && (entry_price <= low || entry_price >= high) {
+            return Err(format!("entry price {} must lie between {} and {}", entry_price, low, high));
+        }
+        Ok(())
+    }
+
+    fn exit_side(&self) -> OrderSide {
+        match self.entry.side {
+            OrderSide::Buy => OrderSide::Sell,
+            OrderSide::Sell => OrderSide::Buy,
+        }
+    }
+
+    /// 익절 지정가 주문
+    pub fn take_profit_order(&self) -> Order {
+        Order::new(self.entry.symbol.clone(), self.exit_side(), OrderType::Limit, self.entry.quantity, self.take_profit)
+    }
+
+    /// 손절 스탑 주문 (가격 0 -> 발동 시 시장가)
+    pub fn stop_loss_order(&self) -> Order {
+        let mut order = Order::new(self.entry.symbol.clone(), self.exit_side(), OrderType::StopLoss, self.entry.quantity, Decimal::ZERO);
+        order.stop_price = Some(self.stop_loss);
+        order
+    }
+}
+
+/// 브래킷 진행 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BracketStatus {
+    /// 진입 주문 체결 대기
+    PendingEntry,
+    /// 익절/손절 주문 대기 중
+    Active,
+    TakeProfitFilled,
+    StopLossFilled,
+    Cancelled,
+    /// 진입 거부/만료 또는 청산 주문 제출 실패
+    Failed,
+}
+
+impl BracketStatus {
+    pub fn is_terminal(self) -> bool {
+        !matches!(self, BracketStatus::PendingEntry | BracketStatus::Active)
+    }
+}
+
+/// 상태 전이에 따라 수행할 거래소 요청
+#[derive(Debug, Clone, PartialEq)]
+pub enum BracketAction {
+    /// 익절/손절 주문 제출
+    PlaceExits,
+    /// 반대쪽 주문 취소
+    Cancel(OrderId),
+}
+
+/// 진행 중인 브래킷
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bracket {
+    pub id: String,
+    pub strategy: Option<String>,
+    pub order: BracketOrder,
+    pub status: BracketStatus,
+    pub entry_id: Option<OrderId>,
+    pub take_profit_id: Option<OrderId>,
+    pub stop_loss_id: Option<OrderId>,
+    /// 실패 사유
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+impl Bracket {
+    pub fn new(strategy: Option<String>, order: BracketOrder, now: i64) -> Self {
+        Bracket {
+            id: format!("bracket-{}", Uuid::new_v4()),
+            strategy,
+            order,
+            status: BracketStatus::PendingEntry,
+            entry_id: None,
+            take_profit_id: None,
+            stop_loss_id: None,
+            error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// 상태를 확인해야 하는 미완료 주문
+    pub fn open_order_ids(&self) -> Vec<OrderId> {
+        match self.status {
+            BracketStatus::PendingEntry => self.entry_id.iter().cloned().collect(),
+            BracketStatus::Active => self.take_profit_id.iter().chain(self.stop_loss_id.iter()).cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn contains(&self, order_id: &OrderId) -> bool {
+        [&self.entry_id, &self.take_profit_id, &self.stop_loss_id].iter().any(|id| id.as_ref() == Some(order_id))
+    }
+
+    /// 구성 주문의 상태 변화 반영 - 필요한 거래소 요청 반환
+    pub fn on_order_status(&mut self, order_id: &OrderId, status: &OrderStatus, now: i64) -> Vec<BracketAction> {
+        let is_entry = self.entry_id.as_ref() == Some(order_id);
+        let is_take_profit = self.take_profit_id.as_ref() == Some(order_id);
+        let is_stop_loss = self.stop_loss_id.as_ref() == Some(order_id);
+        let dead = matches!(status, OrderStatus::Cancelled | OrderStatus::Rejected | OrderStatus::Expired);
+
+        let actions = match self.status {
+            // 부분 체결에서는 청산 수량이 맞지 않으므로 전량 체결 후에만 청산 주문
+            BracketStatus::PendingEntry if is_entry && *status == OrderStatus::Filled => {
+                self.status = BracketStatus::Active;
+                vec![BracketAction::PlaceExits]
+            }
+            BracketStatus::PendingEntry if is_entry && dead => {
+                self.status = if *status == OrderStatus::Cancelled { BracketStatus::Cancelled } else { BracketStatus::Failed };
+                Vec::new()
+            }
+            BracketStatus::Active if *status == OrderStatus::Filled && (is_take_profit || is_stop_loss) => {
+                let sibling = if is_take_profit { &self.stop_loss_id } else { &self.take_profit_id };
+                let actions = sibling.iter().cloned().map(BracketAction::Cancel).collect();
+                self.status = if is_take_profit { BracketStatus::TakeProfitFilled } else { BracketStatus::StopLossFilled };
+                actions
+            }
+            // 한쪽이 외부에서 취소/거부되면 보호가 깨지므로 나머지도 취소
+            BracketStatus::Active if dead && (is_take_profit || is_stop_loss) => {
+                let sibling = if is_take_profit { &self.stop_loss_id } else { &self.take_profit_id };
+                let actions = sibling.iter().cloned().map(BracketAction::Cancel).collect();
+                self.status = BracketStatus::Cancelled;
+                self.error = Some(format!("exit order {} ended as {:?}", order_id, status));
+                actions
+            }
+            _ => return Vec::new(),
+        };
+        self.updated_at = now;
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    #[test]
+    fn test_bracket_transitions() {
+        let entry = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(50_000));
+        assert!(BracketOrder::new(entry.clone(), dec!(49_000), dec!(51_000)).validate().is_err());
+        assert!(BracketOrder::new(entry.clone(), dec!(52_000), dec!(50_500)).validate().is_err());
+        let spec = BracketOrder::new(entry, dec!(52_000), dec!(49_000));
+        assert!(spec.validate().is_ok());
+        assert_eq!((spec.take_profit_order().side, spec.take_profit_order().price), (OrderSide::Sell, dec!(52_000)));
+        assert_eq!(spec.stop_loss_order().stop_price, Some(dec!(49_000)));
+
+        let mut bracket = Bracket::new(None, spec, 0);
+        let (entry_id, tp, sl) = (OrderId("1".into()), OrderId("2".into()), OrderId("3".into()));
+        bracket.entry_id = Some(entry_id.clone());
+        assert!(bracket.on_order_status(&entry_id, &OrderStatus::PartiallyFilled, 1).is_empty());
+        assert_eq!(bracket.on_order_status(&entry_id, &OrderStatus::Filled, 2), vec![BracketAction::PlaceExits]);
+        assert_eq!(bracket.status, BracketStatus::Active);
+
+        bracket.take_profit_id = Some(tp.clone());
+        bracket.stop_loss_id = Some(sl.clone());
+        assert_eq!(bracket.open_order_ids(), vec![tp.clone(), sl.clone()]);
+        // 손절 체결 -> 익절 취소, 이후 이벤트는 무시
+        assert_eq!(bracket.on_order_status(&sl, &OrderStatus::Filled, 3), vec![BracketAction::Cancel(tp.clone())]);
+        assert_eq!(bracket.status, BracketStatus::StopLossFilled);
+        assert!(bracket.on_order_status(&tp, &OrderStatus::Cancelled, 4).is_empty());
+        assert!(bracket.open_order_ids().is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+//! 주문 생명주기 이벤트
+//!
+//! 주문 관리자가 생성/접수/체결/취소/거부를 형식화된 `OrderEvent` 로 하나의 브로드캐스트 버스에 발행한다.
+//! 포트폴리오 추적기, 체결 기록, 브래킷 관리자, WebSocket 피드 등은 모두 같은 버스를 구독한다.
+//! 같은 종료(취소/체결/거부/만료)를 취소 응답, 사용자 데이터 스트림, 상태 폴링이 각각 알려도
+//! 버스는 주문마다 종료 이벤트를 한 번만 내보낸다.
+//! 전략이 낸 주문은 버스가 주인 전략을 기억해 이벤트에 붙이므로, 구독자는 실제 체결을 전략별로 집계할 수 있다.
+//!
+//! `update.order_id` 는 거래소 접수 전(생성, 접수 전 거부)에는 내부 주문 ID, 접수 후에는 거래소 주문 ID 다.
+//! 주문을 처음부터 끝까지 잇는 키는 `update.client_order_id` 로, 주문 관리자가 낸 주문의 모든 이벤트에 실린다.
+//! 클라이언트 ID 가 빠졌거나 다른 값(바이낸스 취소 보고의 취소 요청 ID 등)으로 온 거래소 보고는
+//! 버스가 앞서 본 거래소 주문 ID 로 원래 클라이언트 ID 를 채운다.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderId, OrderStatus, OrderUpdate};
+
+/// 버스 용량 (느린 구독자는 Lagged 로 알림 받음)
+pub const ORDER_EVENT_CAPACITY: usize = 1024;
+
+/// 종료 이벤트를 기억하는 최근 주문 수 (넘으면 오래된 주문부터 잊음)
+const TERMINAL_HISTORY: usize = 10_000;
+
+/// 주문 상태 변화
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderEvent {
+    /// 검증을 통과해 저장소에 기록됨 (거래소 제출 전)
+    Created,
+    /// 거래소가 접수함
+    Acked,
+    /// 일부 체결 - 이번 체결 수량/가격
+    PartiallyFilled { qty: Decimal, price: Decimal },
+    /// 전량 체결 - 마지막 체결 수량/가격
+    Filled { qty: Decimal, price: Decimal },
+    Cancelled,
+    Rejected { reason: String },
+    /// 유효 기간 만료 (IOC/FOK 미체결분 등)
+    Expired,
+}
+
+impl OrderEvent {
+    /// 거래소 상태 보고(사용자 데이터 스트림, 상태 폴링)에서 이벤트 생성
+    pub fn from_update(update: &OrderUpdate) -> Self {
+        match update.status {
+            OrderStatus::New => OrderEvent::Acked,
+            OrderStatus::PartiallyFilled => OrderEvent::PartiallyFilled { qty: update.last_fill_quantity, price: update.last_fill_price },
+            OrderStatus::Filled => OrderEvent::Filled { qty: update.last_fill_quantity, price: update.last_fill_price },
+            OrderStatus::Cancelled => OrderEvent::Cancelled,
+            OrderStatus::Rejected => OrderEvent::Rejected { reason: "rejected by exchange".to_string() },
+            OrderStatus::Expired => OrderEvent::Expired,
+        }
+    }
+
+    /// 이벤트 후 주문 상태
+    pub fn status(&self) -> OrderStatus {
+        match self {
+            OrderEvent::Created | OrderEvent::Acked => OrderStatus::New,
+            OrderEvent::PartiallyFilled { .. } => OrderStatus::PartiallyFilled,
+            OrderEvent::Filled { .. } => OrderStatus::Filled,
+            OrderEvent::Cancelled => OrderStatus::Cancelled,
+            OrderEvent::Rejected { .. } => OrderStatus::Rejected,
+            OrderEvent::Expired => OrderStatus::Expired,
+        }
+    }
+
+    /// 더 이상 상태가 바뀌지 않는 이벤트인지
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderEvent::Filled { .. } | OrderEvent::Cancelled | OrderEvent::Rejected { .. } | OrderEvent::Expired)
+    }
+}
+
+/// 버스로 나가는 이벤트 - 형식화된 이벤트와 그 시점의 주문 보고 (식별자, 체결 수수료/메이커·테이커 등)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderLifecycleEvent {
+    pub event: OrderEvent,
+    pub update: OrderUpdate,
+    /// 주문을 낸 전략 (발행 시 버스가 채움)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<String>,
+}
+
+impl OrderLifecycleEvent {
+    pub fn from_update(update: OrderUpdate) -> Self {
+        OrderLifecycleEvent { event: OrderEvent::from_update(&update), update, strategy: None }
+    }
+
+    /// 체결 없는 상태 변화 (생성/접수/취소/거부)
+    pub fn for_order(order: &Order, order_id: &OrderId, event: OrderEvent) -> Self {
+        let update = OrderUpdate {
+            order_id: order_id.clone(),
+            client_order_id: order.client_order_id.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            status: event.status(),
+            last_fill_price: Decimal::ZERO,
+            last_fill_quantity: Decimal::ZERO,
+            cumulative_quantity: Decimal::ZERO,
+            fee: Decimal::ZERO,
+            fee_asset: None,
+            liquidity: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        OrderLifecycleEvent { event, update, strategy: None }
+    }
+}
+
+#[derive(Default)]
+struct OrderRecords {
+    /// 종료 이벤트를 낸 주문 (최근 TERMINAL_HISTORY 건)
+    terminal_ids: HashSet<OrderId>,
+    terminal_order: VecDeque<OrderId>,
+    /// 미종결 전략 주문의 주인 전략 (클라이언트 ID 기준)
+    owners: HashMap<String, String>,
+    /// 미종결 주문의 현재 주문 ID -> 클라이언트 ID
+    client_ids: HashMap<OrderId, String>,
+    /// 미종결 주문의 지금까지 발행된 누적 체결 수량
+    filled: HashMap<OrderId, Decimal>,
+}
+
+/// 주문 이벤트 버스 - 종료 이벤트를 낸 주문의 이후 이벤트는 버림
+#[derive(Clone)]
+pub struct OrderEventBus {
+    sender: broadcast::Sender<OrderLifecycleEvent>,
+    records: Arc<Mutex<OrderRecords>>,
+}
+
+impl OrderEventBus {
+    pub fn new(capacity: usize) -> Self {
+        OrderEventBus { sender: broadcast::channel(capacity).0, records: Arc::new(Mutex::new(OrderRecords::default())) }
+    }
+
+    fn records(&self) -> std::sync::MutexGuard<'_, OrderRecords> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 발행 - 이미 종료된 주문이면 버리고 false, 클라이언트 ID 와 주인 전략을 채움
+    pub fn publish(&self, mut event: OrderLifecycleEvent) -> bool {
+        {
+            let mut records = self.records();
+            let order_id = &event.update.order_id;
+            if records.terminal_ids.contains(order_id) {
+                return false;
+            }
+            // 생성 이벤트의 내부 주문 ID 는 접수 후 바뀌므로 기억하지 않음
+            match records.client_ids.get(order_id) {
+                Some(client_id) => event.update.client_order_id = Some(client_id.clone()),
+                None => {
+                    if let Some(client_id) = event.update.client_order_id.as_ref().filter(|_| event.event != OrderEvent::Created) {
+                        records.client_ids.insert(order_id.clone(), client_id.clone());
+                    }
+                }
+            }
+            if event.strategy.is_none() {
+                event.strategy = event.update.client_order_id.as_ref().and_then(|id| records.owners.get(id)).cloned();
+            }
+            if event.update.is_fill() {
+                let seen = records.filled.get(order_id).copied().unwrap_or_default();
+                let quantity = event.update.new_fill_quantity(seen);
+                records.filled.insert(order_id.clone(), seen + quantity);
+            }
+            if event.event.is_terminal() {
+                records.client_ids.remove(order_id);
+                if let Some(client_id) = &event.update.client_order_id {
+                    records.owners.remove(client_id);
+                }
+                records.filled.remove(order_id);
+                records.terminal_ids.insert(order_id.clone());
+                records.terminal_order.push_back(order_id.clone());
+                if records.terminal_order.len() > TERMINAL_HISTORY {
+                    if let Some(oldest) = records.terminal_order.pop_front() {
+                        records.terminal_ids.remove(&oldest);
+                    }
+                }
+            }
+        }
+        let _ = self.sender.send(event);
+        true
+    }
+
+    /// 종료 이벤트를 이미 낸 주문인지
+    pub fn is_terminal(&self, order_id: &OrderId) -> bool {
+        self.records().terminal_ids.contains(order_id)
+    }
+
+    /// 미종결 주문의 지금까지 발행된 누적 체결 수량
+    pub fn filled_quantity(&self, order_id: &OrderId) -> Decimal {
+        self.records().filled.get(order_id).copied().unwrap_or_default()
+    }
+
+    /// 전략 주문 등록 (생성 이벤트 발행 전) - 종료될 때까지 이벤트에 전략 이름을 붙임
+    pub fn assign_strategy(&self, client_order_id: &str, strategy: &str) {
+        self.records().owners.insert(client_order_id.to_string(), strategy.to_string());
+    }
+
+    /// 전략의 미종결 주문 (현재 주문 ID)
+    pub fn strategy_orders(&self, strategy: &str) -> Vec<OrderId> {
+        let records = self.records();
+        records.client_ids.iter()
+            .filter(|(_, client_id)| records.owners.get(*client_id).is_some_and(|owner| owner == strategy))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// 전략 주문 등록 해제 (`None` 이면 전체)
+    pub fn release_strategy_orders(&self, order_id: Option<&OrderId>) {
+        let mut records = self.records();
+        match order_id {
+            Some(order_id) => {
+                if let Some(client_id) = records.client_ids.get(order_id).cloned() {
+                    records.owners.remove(&client_id);
+                }
+            }
+            None => records.owners.clear(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<OrderLifecycleEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for OrderEventBus {
+    fn default() -> Self {
+        OrderEventBus::new(ORDER_EVENT_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+
+    fn drain(rx: &mut broadcast::Receiver<OrderLifecycleEvent>) -> Vec<OrderLifecycleEvent> {
+        std::iter::from_fn(|| rx.try_recv().ok()).collect()
+    }
+
+    #[test]
+    fn test_owner_attribution_and_client_id_across_order_ids() {
+        let bus = OrderEventBus::default();
+        let mut rx = bus.subscribe();
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100)).with_client_order_id("c1");
+        let exchange_id = OrderId("ex-1".to_string());
+
+        // 생성은 내부 ID, 접수 이후는 거래소 ID
+        bus.assign_strategy("c1", "grid");
+        bus.publish(OrderLifecycleEvent::for_order(&order, &order.id, OrderEvent::Created));
+        bus.publish(OrderLifecycleEvent::for_order(&order, &exchange_id, OrderEvent::Acked));
+        assert_eq!(bus.strategy_orders("grid"), vec![exchange_id.clone()]);
+
+        // 클라이언트 ID 없는 체결 보고, 취소 요청 ID 가 실린 취소 보고
+        let mut fill = OrderLifecycleEvent::for_order(&order, &exchange_id, OrderEvent::PartiallyFilled { qty: dec!(0.4), price: dec!(100) });
+        fill.update.client_order_id = None;
+        bus.publish(fill);
+        let mut cancel = OrderLifecycleEvent::for_order(&order, &exchange_id, OrderEvent::Cancelled);
+        cancel.update.client_order_id = Some("cancel-req".to_string());
+        bus.publish(cancel);
+
+        let received = drain(&mut rx);
+        assert_eq!(received.len(), 4);
+        assert!(received.iter().all(|e| e.strategy.as_deref() == Some("grid") && e.update.client_order_id.as_deref() == Some("c1")));
+        assert!(bus.strategy_orders("grid").is_empty());
+
+        // 접수 전 거부도 주인 전략 등록을 정리
+        let rejected = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, dec!(1), dec!(0)).with_client_order_id("c2");
+        bus.assign_strategy("c2", "grid");
+        bus.publish(OrderLifecycleEvent::for_order(&rejected, &rejected.id, OrderEvent::Rejected { reason: "insufficient balance".to_string() }));
+        assert_eq!(drain(&mut rx)[0].strategy.as_deref(), Some("grid"));
+        assert!(bus.records().owners.is_empty() && bus.records().client_ids.is_empty());
+    }
+
+    #[test]
+    fn test_terminal_event_published_once() {
+        let bus = OrderEventBus::default();
+        let mut rx = bus.subscribe();
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100)).with_client_order_id("c1");
+        let exchange_id = OrderId("ex-1".to_string());
+        let fill = |qty, cumulative, event| {
+            let mut lifecycle = OrderLifecycleEvent::for_order(&order, &exchange_id, event);
+            lifecycle.update.last_fill_quantity = qty;
+            lifecycle.update.cumulative_quantity = cumulative;
+            lifecycle
+        };
+
+        assert!(bus.publish(fill(dec!(0.4), dec!(0.4), OrderEvent::PartiallyFilled { qty: dec!(0.4), price: dec!(100) })));
+        assert_eq!(bus.filled_quantity(&exchange_id), dec!(0.4));
+        // 사용자 데이터 스트림의 전량 체결 후 폴링의 같은 체결, 뒤늦은 취소 보고는 버림
+        assert!(bus.publish(fill(dec!(0.6), dec!(1), OrderEvent::Filled { qty: dec!(0.6), price: dec!(100) })));
+        assert!(!bus.publish(fill(dec!(0.6), dec!(1), OrderEvent::Filled { qty: dec!(0.6), price: dec!(100) })));
+        assert!(!bus.publish(OrderLifecycleEvent::for_order(&order, &exchange_id, OrderEvent::Cancelled)));
+
+        assert_eq!(drain(&mut rx).len(), 2);
+        assert!(bus.is_terminal(&exchange_id));
+        assert_eq!(bus.filled_quantity(&exchange_id), Decimal::ZERO);
+    }
+}
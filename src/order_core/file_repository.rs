@@ -0,0 +1,363 @@
+//! 파일 기반 영속 주문 저장소
+//!
+//! 모든 변경을 JSON Lines 저널에 추가 기록하고, 시작 시 저널을 재생하여 메모리 인덱스를 복원한다.
+//! 첫 줄은 스키마 버전 헤더이며, 이전 형식(orders export 스냅샷) 파일은 열 때 현재 스키마로 마이그레이션된다.
+//! 실행 중 기록(추가, fsync, 압축)은 블로킹 스레드에서 수행해 비동기 작업자 스레드를 막지 않는다.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::order::{Order, OrderId, OrderStatus};
+use crate::order_core::reconcile::OrderSnapshot;
+use crate::order_core::repository::OrderRepository;
+
+/// 현재 저널 스키마 버전
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalHeader {
+    schema_version: u32,
+}
+
+/// 저널 항목 (한 줄에 하나)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalEntry {
+    Upsert { order: Box<Order>, previous_id: Option<String> },
+    Status { order_id: String, status: OrderStatus, updated_at: i64 },
+    Delete { order_id: String },
+}
+
+/// 저장된 주문과 마지막으로 기록된 상태
+#[derive(Debug, Clone)]
+struct StoredOrder {
+    order: Order,
+    status: OrderStatus,
+    status_updated_at: i64,
+}
+
+/// JSON Lines 저널 기반 주문 저장소
+pub struct FileOrderRepository {
+    path: PathBuf,
+    orders: HashMap<String, StoredOrder>,      // OrderId.0 -> 주문
+    client_id_index: HashMap<String, String>,  // client_order_id -> OrderId.0
+    journal_entries: usize,
+}
+
+impl FileOrderRepository {
+    /// 저장소 열기 - 파일이 없으면 생성, 이전 스키마는 마이그레이션 후 재작성
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, TradingError> {
+        let path = path.into();
+        let mut repo = FileOrderRepository {
+            path,
+            orders: HashMap::new(),
+            client_id_index: HashMap::new(),
+            journal_entries: 0,
+        };
+
+        if !repo.path.exists() {
+            repo.compact()?;
+            return Ok(repo);
+        }
+
+        let mut reader = BufReader::new(File::open(&repo.path)?);
+        let mut first_line = String::new();
+        reader.read_line(&mut first_line)?;
+        match serde_json::from_str::<JournalHeader>(first_line.trim()) {
+            Ok(header) if header.schema_version == SCHEMA_VERSION => {
+                for (i, line) in reader.lines().enumerate() {
+                    let line = line?;
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    // 기록 도중 종료로 잘린 마지막 줄은 건너뜀
+                    match serde_json::from_str::<JournalEntry>(&line) {
+                        Ok(entry) => repo.apply(entry),
+                        Err(e) => log::warn!("order journal line {} skipped: {}", i + 2, e),
+                    }
+                    repo.journal_entries += 1;
+                }
+            }
+            Ok(header) => {
+                return Err(TradingError::ConfigError(format!(
+                    "Order store schema version {} is newer than supported {}", header.schema_version, SCHEMA_VERSION
+                )));
+            }
+            Err(_) => {
+                // 버전 0: orders export 스냅샷(JSON 문서)
+                let mut contents = first_line;
+                reader.read_to_string(&mut contents)?;
+                let snapshot: OrderSnapshot = serde_json::from_str(&contents)?;
+                log::info!("migrating order store {} to schema v{} ({} orders)", repo.path.display(), SCHEMA_VERSION, snapshot.orders.len());
+                for order in snapshot.orders {
+                    repo.apply(JournalEntry::Upsert { order: Box::new(order), previous_id: None });
+                }
+                repo.compact()?;
+            }
+        }
+
+        Ok(repo)
+    }
+
+    /// 마지막으로 기록된 주문 상태
+    pub fn status_of(&self, order_id: &OrderId) -> Option<OrderStatus> {
+        self.orders.get(&order_id.0).map(|s| s.status.clone())
+    }
+
+    /// 저널을 현재 상태 스냅샷으로 재작성 (임시 파일 교체)
+    pub fn compact(&mut self) -> Result<(), TradingError> {
+        write_snapshot(&self.path, &self.snapshot_lines()?)?;
+        self.journal_entries = self.orders.len();
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // 헤더 + 주문별 현재 상태 저널 줄
+    fn snapshot_lines(&self) -> Result<Vec<String>, TradingError> {
+        let mut lines = vec![serde_json::to_string(&JournalHeader { schema_version: SCHEMA_VERSION })?];
+        let mut stored: Vec<&StoredOrder> = self.orders.values().collect();
+        stored.sort_by_key(|s| s.order.created_at);
+        for s in &stored {
+            lines.push(serde_json::to_string(&JournalEntry::Upsert { order: Box::new(s.order.clone()), previous_id: None })?);
+            if s.status != OrderStatus::New {
+                lines.push(serde_json::to_string(&JournalEntry::Status {
+                    order_id: s.order.id.0.clone(),
+                    status: s.status.clone(),
+                    updated_at: s.status_updated_at,
+                })?);
+            }
+        }
+        Ok(lines)
+    }
+
+    // 저널에 기록한 뒤 메모리 상태에 반영 (압축 스냅샷에 이번 변경이 포함되도록 반영 후 압축)
+    async fn record(&mut self, entry: JournalEntry) -> Result<(), TradingError> {
+        let (path, line) = (self.path.clone(), serde_json::to_string(&entry)?);
+        run_blocking(move || append_line(&path, &line)).await?;
+        self.journal_entries += 1;
+        self.apply(entry);
+
+        // 저널이 주문 수에 비해 과도하게 길어지면 압축
+        if self.journal_entries > 1_000 && self.journal_entries > self.orders.len() * 4 {
+            let (path, lines) = (self.path.clone(), self.snapshot_lines()?);
+            run_blocking(move || write_snapshot(&path, &lines)).await?;
+            self.journal_entries = self.orders.len();
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, entry: JournalEntry) {
+        match entry {
+            JournalEntry::Upsert { order, previous_id } => {
+                let order = *order;
+                let previous = previous_id.and_then(|id| self.orders.remove(&id));
+                let (status, status_updated_at) = previous
+                    .or_else(|| self.orders.get(&order.id.0).cloned())
+                    .map(|s| (s.status, s.status_updated_at))
+                    .unwrap_or((OrderStatus::New, order.created_at.as_millis()));
+                if let Some(client_id) = &order.client_order_id {
+                    self.client_id_index.insert(client_id.clone(), order.id.0.clone());
+                }
+                self.orders.insert(order.id.0.clone(), StoredOrder { order, status, status_updated_at });
+            }
+            JournalEntry::Status { order_id, status, updated_at } => {
+                if let Some(stored) = self.orders.get_mut(&order_id) {
+                    stored.status = status;
+                    stored.status_updated_at = updated_at;
+                }
+            }
+            JournalEntry::Delete { order_id } => {
+                if let Some(stored) = self.orders.remove(&order_id) {
+                    if let Some(client_id) = stored.order.client_order_id {
+                        self.client_id_index.remove(&client_id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 저널 한 줄 추가 후 디스크 동기화
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    file.sync_data()
+}
+
+// 임시 파일에 쓴 뒤 교체
+fn write_snapshot(path: &Path, lines: &[String]) -> std::io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    {
+        let mut file = File::create(&tmp)?;
+        for line in lines {
+            writeln!(file, "{}", line)?;
+        }
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp, path)
+}
+
+// 파일 기록은 블로킹 스레드에서
+async fn run_blocking(write: impl FnOnce() -> std::io::Result<()> + Send + 'static) -> Result<(), TradingError> {
+    tokio::task::spawn_blocking(write).await
+        .unwrap_or_else(|e| Err(std::io::Error::other(format!("order journal write panicked: {}", e))))?;
+    Ok(())
+}
+
+#[async_trait]
+impl OrderRepository for FileOrderRepository {
+    async fn save(&mut self, order: &Order) -> Result<(), TradingError> {
+        let entry = JournalEntry::Upsert { order: Box::new(order.clone()), previous_id: None };
+        self.record(entry).await?;
+        Ok(())
+    }
+
+    /// 거래소 주문 ID 가 새로 부여된 경우 클라이언트 ID 로 기존 주문을 찾아 키를 교체
+    async fn update(&mut self, order: &Order) -> Result<(), TradingError> {
+        let previous_id = if self.orders.contains_key(&order.id.0) {
+            None
+        } else {
+            let key = order.client_order_id.as_ref()
+                .and_then(|c| self.client_id_index.get(c))
+                .cloned()
+                .ok_or_else(|| TradingError::OrderNotFound(order.id.clone()))?;
+            Some(key)
+        };
+
+        let entry = JournalEntry::Upsert { order: Box::new(order.clone()), previous_id };
+        self.record(entry).await?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, order_id: &OrderId) -> Result<Option<Order>, TradingError> {
+        Ok(self.orders.get(&order_id.0).map(|s| s.order.clone()))
+    }
+
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Order>, TradingError> {
+        Ok(self.client_id_index.get(client_id)
+            .and_then(|id| self.orders.get(id))
+            .map(|s| s.order.clone()))
+    }
+
+    async fn find_by_status(&self, statuses: &[OrderStatus]) -> Result<Vec<Order>, TradingError> {
+        Ok(self.orders.values()
+            .filter(|s| statuses.contains(&s.status))
+            .map(|s| s.order.clone())
+            .collect())
+    }
+
+    async fn find_by_symbol(&self, symbol: &str) -> Result<Vec<Order>, TradingError> {
+        Ok(self.orders.values()
+            .filter(|s| s.order.symbol == symbol)
+            .map(|s| s.order.clone())
+            .collect())
+    }
+
+    async fn find_all(&self) -> Result<Vec<Order>, TradingError> {
+        Ok(self.orders.values().map(|s| s.order.clone()).collect())
+    }
+
+    async fn delete(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+        if !self.orders.contains_key(&order_id.0) {
+            return Ok(());
+        }
+        let entry = JournalEntry::Delete { order_id: order_id.0.clone() };
+        self.record(entry).await?;
+        Ok(())
+    }
+
+    async fn update_status(&mut self, order_id: &OrderId, status: OrderStatus) -> Result<(), TradingError> {
+        match self.orders.get(&order_id.0) {
+            None => return Err(TradingError::OrderNotFound(order_id.clone())),
+            Some(stored) if stored.status == status => return Ok(()),
+            Some(_) => {}
+        }
+        let entry = JournalEntry::Status {
+            order_id: order_id.0.clone(),
+            status,
+            updated_at: chrono::Utc::now().timestamp_millis(),
+        };
+        self.record(entry).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+
+    #[tokio::test]
+    async fn test_file_repository_persists_and_migrates() {
+        let path = std::env::temp_dir().join(format!("xquant_order_store_{}.jsonl", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        {
+            let mut repo = FileOrderRepository::open(&path).unwrap();
+            let mut order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100)).with_client_order_id("c1");
+            order.created_at = 1_000.into();
+            repo.save(&order).await.unwrap();
+            // 거래소 ID 부여 후 키 교체
+            order.id = OrderId("ex-1".to_string());
+            repo.update(&order).await.unwrap();
+            repo.update_status(&order.id, OrderStatus::Filled).await.unwrap();
+
+            let mut other = Order::new("ETHUSDT", OrderSide::Sell, OrderType::Market, dec!(2), dec!(0));
+            other.id = OrderId("ex-2".to_string());
+            other.created_at = 5_000.into();
+            repo.save(&other).await.unwrap();
+        }
+
+        // 재시작 후 복원
+        let repo = FileOrderRepository::open(&path).unwrap();
+        assert_eq!(repo.find_all().await.unwrap().len(), 2);
+        assert_eq!(repo.find_by_client_id("c1").await.unwrap().unwrap().id.0, "ex-1");
+        assert_eq!(repo.status_of(&OrderId("ex-1".to_string())), Some(OrderStatus::Filled));
+        assert_eq!(repo.find_by_status(&[OrderStatus::New]).await.unwrap()[0].symbol, "ETHUSDT");
+        assert_eq!(repo.find_by_time_range(0.into(), 2_000.into()).await.unwrap().len(), 1);
+        assert_eq!(repo.find_by_symbol("BTCUSDT").await.unwrap().len(), 1);
+
+        // 이전 형식 스냅샷 마이그레이션
+        let orders = repo.find_all().await.unwrap();
+        OrderSnapshot::new(orders, Vec::new()).save(&path).unwrap();
+        let migrated = FileOrderRepository::open(&path).unwrap();
+        assert_eq!(migrated.find_all().await.unwrap().len(), 2);
+        let header = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(header.starts_with("{\"schema_version\":1}"));
+    }
+
+    #[tokio::test]
+    async fn test_compaction_keeps_triggering_write() {
+        let path = std::env::temp_dir().join(format!("xquant_order_compact_{}.jsonl", uuid::Uuid::new_v4()));
+        let mut repo = FileOrderRepository::open(&path).unwrap();
+        let mut order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100));
+        order.id = OrderId("ex-1".to_string());
+        repo.save(&order).await.unwrap();
+        // 상태를 번갈아 기록해 저널을 1,000 줄로 채운 뒤, 압축을 일으키는 저장도 재시작 후 남아야 함
+        let mut last = OrderStatus::New;
+        for i in 0..999 {
+            last = if i % 2 == 0 { OrderStatus::PartiallyFilled } else { OrderStatus::New };
+            repo.update_status(&order.id, last.clone()).await.unwrap();
+        }
+        let mut other = order.clone();
+        other.id = OrderId("ex-2".to_string());
+        repo.save(&other).await.unwrap();
+        assert_eq!(repo.journal_entries, 2);
+
+        let reopened = FileOrderRepository::open(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(reopened.status_of(&order.id), Some(last));
+        assert!(reopened.find_by_id(&other.id).await.unwrap().is_some());
+    }
+}
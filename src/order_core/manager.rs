@@ -1,20 +1,47 @@
-use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::{broadcast, RwLock, Mutex};
 use uuid::Uuid;
 
-use crate::error::TradingError;
+use crate::core::degraded_mode::{self, WriteAccessMonitor};
+use crate::core::kill_switch::KillSwitch;
+use crate::core::trade_journal::{OrderContext, TradeJournal};
+use crate::core::twap_splitter::TwapSplitter;
+use crate::core::vwap_splitter::VwapSplitter;
+use crate::error::{TradingError, BINANCE_TIMESTAMP_OUT_OF_WINDOW};
+use crate::exchange::registry::ExchangeRegistry;
 use crate::exchange::traits::Exchange;
-use crate::models::order::{Order, OrderId, OrderStatus, OrderType, OrderSide};
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::order::{Order, OrderId, OrderStatus, OrderType, OrderSide, OrderUpdate};
+use crate::order_core::events::{OrderEvent, OrderEventBus, OrderLifecycleEvent};
+use crate::order_core::position_router::PositionRouter;
 use crate::order_core::repository::OrderRepository;
-use crate::order_core::validator::OrderValidator;
+use crate::order_core::routing::{OrderRouter, RoutingAction, RoutingContext};
+use crate::order_core::submission_queue::{SubmissionKind, SubmissionQueue};
+use crate::order_core::validator::{OrderValidator, SymbolRulesValidator};
 
 /// 주문 관리자 - 주문 생명주기 관리
 pub struct OrderManager {
-    exchange: Arc<RwLock<dyn Exchange>>,
+    /// 주문 대상 거래소 (`Order::exchange` 로 선택, 없으면 기본 거래소)
+    exchanges: ExchangeRegistry,
     repository: Arc<RwLock<dyn OrderRepository>>,
     validators: Vec<Box<dyn OrderValidator>>,
-    status_channels: HashMap<String, broadcast::Sender<OrderStatus>>,
+    /// 거래소 심볼 규칙 (호가/수량 단위, 최소 수량/주문 금액) - 제출 전 반올림 및 검증
+    symbol_rules: SymbolRulesValidator,
+    router: OrderRouter,
+    /// 현재 포지션 기준 reduceOnly / positionSide 지정 (없으면 주문 그대로 제출)
+    position_router: Option<PositionRouter>,
+    /// 주문 생명주기 이벤트 버스 (생성/접수/체결/취소/거부, 종료 이벤트는 주문마다 한 번)
+    order_events: OrderEventBus,
+    /// 주문 상태 폴링 주기
+    poll_interval: tokio::time::Duration,
+    /// 우선순위 제출 큐 (start_submission_worker 가 처리)
+    submission_queue: SubmissionQueue,
+    /// 쓰기 권한 상실 감지 (읽기 전용 모드에서는 주문 차단)
+    write_access: WriteAccessMonitor,
+    /// 운영자 거래 중지 (중지 중에는 모든 신규 주문 거부)
+    kill_switch: KillSwitch,
+    /// 체결 기록 (주문 제출 시 전략/결정 가격 등록)
+    trade_journal: Option<TradeJournal>,
 }
 
 impl OrderManager {
@@ -23,20 +50,177 @@ impl OrderManager {
         repository: Arc<RwLock<dyn OrderRepository>>,
     ) -> Self {
         OrderManager {
-            exchange,
+            exchanges: ExchangeRegistry::new("default", exchange),
             repository,
             validators: Vec::new(),
-            status_channels: HashMap::new(),
+            symbol_rules: SymbolRulesValidator::new(),
+            router: OrderRouter::default(),
+            position_router: None,
+            order_events: OrderEventBus::default(),
+            poll_interval: tokio::time::Duration::from_secs(1),
+            submission_queue: SubmissionQueue::default(),
+            write_access: WriteAccessMonitor::default(),
+            kill_switch: KillSwitch::default(),
+            trade_journal: None,
         }
     }
 
+    /// 다중 거래소 등록부 교체 - 기본 거래소는 `new` 에 넘긴 거래소와 같아야 함
+    pub fn set_exchange_registry(&mut self, registry: ExchangeRegistry) {
+        self.exchanges = registry;
+    }
+
+    pub fn exchange_registry(&self) -> &ExchangeRegistry {
+        &self.exchanges
+    }
+
+    /// 저장된 주문이 제출된 거래소 (주문을 모르면 기본 거래소)
+    async fn exchange_for(&self, order_id: &OrderId) -> Result<Arc<RwLock<dyn Exchange>>, TradingError> {
+        let venue = self.repository.read().await.find_by_id(order_id).await?.and_then(|o| o.exchange);
+        self.exchanges.resolve(venue.as_deref())
+    }
+
+    /// 주문 상태 폴링 주기 설정 (사용자 데이터 스트림 사용 시 안전망 용도로 늘림)
+    pub fn set_poll_interval(&mut self, interval: tokio::time::Duration) {
+        self.poll_interval = interval;
+    }
+
+    pub fn poll_interval(&self) -> tokio::time::Duration {
+        self.poll_interval
+    }
+
+    /// 제출 큐 교체 (처리 태스크 시작 전에 설정)
+    pub fn set_submission_queue(&mut self, queue: SubmissionQueue) {
+        self.submission_queue = queue;
+    }
+
+    /// 제출 큐 핸들 - 관리자 잠금을 잡지 않은 채로 제출 결과를 기다릴 때 사용
+    pub fn submission_queue(&self) -> SubmissionQueue {
+        self.submission_queue.clone()
+    }
+
+    /// 쓰기 권한 감시자 교체 (HTTP 상태 조회와 같은 핸들 공유)
+    pub fn set_write_access_monitor(&mut self, monitor: WriteAccessMonitor) {
+        self.write_access = monitor;
+    }
+
+    pub fn write_access(&self) -> WriteAccessMonitor {
+        self.write_access.clone()
+    }
+
+    /// 킬 스위치 교체 (HTTP 중지/재개와 같은 핸들 공유)
+    pub fn set_kill_switch(&mut self, kill_switch: KillSwitch) {
+        self.kill_switch = kill_switch;
+    }
+
+    pub fn kill_switch(&self) -> KillSwitch {
+        self.kill_switch.clone()
+    }
+
+    /// 체결 기록 연결 - 이후 제출하는 주문의 맥락을 저널에 등록
+    pub fn set_trade_journal(&mut self, journal: TradeJournal) {
+        self.trade_journal = Some(journal);
+    }
+
+    pub fn trade_journal(&self) -> Option<TradeJournal> {
+        self.trade_journal.clone()
+    }
+
+    /// 제출 큐 처리 태스크 시작 - 처리량 상한을 지키며 우선순위 순서대로 하나씩 제출/취소
+    pub fn start_submission_worker(manager: Arc<RwLock<OrderManager>>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let queue = manager.read().await.submission_queue();
+            let mut last_dispatch: Option<tokio::time::Instant> = None;
+            loop {
+                // 간격을 먼저 기다린 뒤 꺼내야 그 사이 들어온 상위 요청이 먼저 나감
+                if let (Some(min_interval), Some(last)) = (queue.min_interval(), last_dispatch) {
+                    tokio::time::sleep_until(last + min_interval).await;
+                }
+                let item = queue.next().await;
+                last_dispatch = Some(tokio::time::Instant::now());
+
+                let (priority, enqueued_at) = (item.priority, item.enqueued_at());
+                let manager = manager.read().await;
+                match item.kind {
+                    SubmissionKind::Submit { strategy, order, reply } => {
+                        let result = manager.create_order_for(strategy.as_deref(), *order).await;
+                        queue.record_completion(priority, enqueued_at);
+                        let _ = reply.send(result);
+                    }
+                    SubmissionKind::Cancel { order_id, reply } => {
+                        let result = manager.cancel_order(&order_id).await;
+                        queue.record_completion(priority, enqueued_at);
+                        let _ = reply.send(result);
+                    }
+                }
+            }
+        })
+    }
+
     /// 주문 검증기 추가
     pub fn add_validator(&mut self, validator: Box<dyn OrderValidator>) {
         self.validators.push(validator);
     }
 
+    /// 거래소별 심볼 규칙 캐시
+    pub fn symbol_rules(&self) -> &SymbolRulesValidator {
+        &self.symbol_rules
+    }
+
+    /// 주문 라우팅 규칙 설정 (같은 핸들을 재로드 태스크와 공유)
+    pub fn set_router(&mut self, router: OrderRouter) {
+        self.router = router;
+    }
+
+    /// 포지션 인지 라우팅 설정 (같은 핸들을 체결 감시 태스크/HTTP 포지션 모드 변경과 공유)
+    pub fn set_position_router(&mut self, router: PositionRouter) {
+        self.position_router = Some(router);
+    }
+
+    pub fn position_router(&self) -> Option<&PositionRouter> {
+        self.position_router.as_ref()
+    }
+
     /// 주문 생성 및 제출 (간단 재시도 포함)
-    pub async fn create_order(&self, mut order: Order) -> Result<OrderId, TradingError> {
+    pub async fn create_order(&self, order: Order) -> Result<OrderId, TradingError> {
+        self.create_order_for(None, order).await
+    }
+
+    /// 전략 이름과 함께 주문 생성 - 라우팅 규칙의 전략 조건에 사용
+    pub async fn create_order_for(&self, strategy: Option<&str>, order: Order) -> Result<OrderId, TradingError> {
+        // 거래 중지 중에는 라우팅/분할 실행 전에 거부 (reduce-only 청산은 허용)
+        if order.reduce_only != Some(true) {
+            self.kill_switch.check()?;
+        }
+        let exchange = self.exchanges.resolve(order.exchange.as_deref())?;
+
+        // 라우팅 규칙 적용 (가격 없는 시장가는 현재가로 명목가 계산)
+        let reference_price = if order.price <= Decimal::ZERO && self.router.needs_reference_price() {
+            exchange.read().await.get_market_data(&order.symbol).await.ok().map(|md| md.close)
+        } else {
+            None
+        };
+        let ctx = RoutingContext { strategy, now_ms: chrono::Utc::now().timestamp_millis(), reference_price };
+        let routed = self.router.route(order, &ctx)?;
+
+        // 거래소 미지원 기능은 클라이언트 측 대체 (예: 네이티브 아이스버그 미지원 시 지정가)
+        let capabilities = exchange.read().await.capabilities();
+        if let Some(algo) = &routed.algo {
+            if !capabilities.supports_native(&routed.order.order_type) {
+                return self.start_algo(exchange, routed.order, algo).await;
+            }
+        }
+        let mut order = capabilities.substitute(routed.order);
+
+        // 현재 포지션 기준 청산 주문은 reduce-only, 헤지 모드는 positionSide 지정 (의도치 않은 반전 방지)
+        if let Some(position_router) = &self.position_router {
+            order = position_router.route(order)?;
+        }
+
+        // 거래소 심볼 규칙에 맞춰 가격/수량 반올림 (최소 수량/주문 금액 미달은 거부)
+        let venue = order.exchange.clone().unwrap_or_else(|| self.exchanges.default_name().to_string());
+        let mut order = self.symbol_rules.apply(&venue, &exchange, order, reference_price).await?;
+
         // 주문 검증
         for validator in &self.validators {
             validator.validate(&order)?;
@@ -47,11 +231,39 @@ impl OrderManager {
             order.client_order_id = Some(Uuid::new_v4().to_string());
         }
 
+        // 체결 기록용 맥락 등록 (결정 가격: 지정가 또는 현재가)
+        if let (Some(journal), Some(client_id)) = (&self.trade_journal, &order.client_order_id) {
+            let decision_price = if order.price > Decimal::ZERO {
+                Some(order.price)
+            } else if reference_price.is_some() {
+                reference_price
+            } else {
+                exchange.read().await.get_market_data(&order.symbol).await.ok().map(|md| md.close)
+            };
+            journal.register(client_id, OrderContext { strategy: strategy.map(str::to_string), signal: None, decision_price: decision_price.map(to_f64) });
+        }
+
+        // 읽기 전용 모드: 거래소로 보내지 않고 차단 기록만 남김
+        let now = chrono::Utc::now().timestamp_millis();
+        if let Err(e) = self.write_access.check(now) {
+            self.write_access.record_blocked(strategy, &order, now);
+            return Err(e);
+        }
+
         // 주문 저장소에 임시 저장
         {
             let mut repo = self.repository.write().await;
             repo.save(&order).await?;
         }
+        if let (Some(strategy), Some(client_id)) = (strategy, &order.client_order_id) {
+            self.order_events.assign_strategy(client_id, strategy);
+        }
+        self.publish(OrderLifecycleEvent::for_order(&order, &order.id, OrderEvent::Created));
+
+        // 체결을 반영할 포지션 등록 (제출 전에 해야 빠른 체결 이벤트를 놓치지 않음)
+        if let Some(position_router) = &self.position_router {
+            position_router.track(&order);
+        }
 
         // 주문 제출 (재시도)
         let mut last_err: Option<TradingError> = None;
@@ -61,25 +273,38 @@ impl OrderManager {
 
         while attempt <= max_retries {
             let submit_res = {
-                let mut exchange = self.exchange.write().await;
+                let mut exchange = exchange.write().await;
                 exchange.submit_order(order.clone()).await
             };
 
             match submit_res {
                 Ok(oid) => { order_id = Some(oid); break; }
+                Err(e) if degraded_mode::is_write_access_error(&e) => {
+                    // 권한/인증 오류는 재시도해도 같은 결과
+                    last_err = Some(e);
+                    break;
+                }
+                Err(e) if !e.is_retryable() && !matches!(e, TradingError::ExchangeError(_)) => {
+                    // 잔고 부족, 잘못된 심볼, 거래소 거부 등은 재시도해도 같은 결과
+                    // (아직 타입이 없는 커넥터의 ExchangeError 는 기존처럼 재시도)
+                    last_err = Some(e);
+                    break;
+                }
                 Err(e) => {
                     // Special handling for common exchange errors: time drift, rate limit
-                    let err_str = format!("{}", e);
-                    if err_str.contains("-1021") || err_str.to_lowercase().contains("timestamp") {
+                    let untyped = match &e { TradingError::ExchangeError(msg) => msg.to_lowercase(), _ => String::new() };
+                    if e.exchange_code() == Some(BINANCE_TIMESTAMP_OUT_OF_WINDOW) || untyped.contains("timestamp") {
                         // sync time and retry quickly
                         {
-                            let mut ex = self.exchange.write().await;
+                            let mut ex = exchange.write().await;
                             let _ = ex.sync_time().await;
                         }
                     }
                     // simple backoff (aggressive if rate limit)
                     let mut backoff_ms = 200u64.saturating_mul(2u64.pow(attempt));
-                    if err_str.contains("429") || err_str.to_lowercase().contains("too many") {
+                    if let Some(wait) = e.retry_after() {
+                        backoff_ms = backoff_ms.max(wait.as_millis() as u64);
+                    } else if matches!(e, TradingError::RateLimited { .. }) || untyped.contains("429") || untyped.contains("too many") {
                         backoff_ms = backoff_ms.max(1000);
                     }
 
@@ -92,7 +317,15 @@ impl OrderManager {
             }
         }
 
-        let order_id = match order_id { Some(id) => id, None => return Err(last_err.unwrap_or(TradingError::Unknown("submit failed".into()))) };
+        let submit_res = order_id.ok_or_else(|| last_err.unwrap_or(TradingError::Unknown("submit failed".into())));
+        self.write_access.record_result(&submit_res, chrono::Utc::now().timestamp_millis());
+        if let (Err(_), Some(position_router), Some(client_id)) = (&submit_res, &self.position_router, &order.client_order_id) {
+            position_router.forget(client_id);
+        }
+        if let Err(e) = &submit_res {
+            self.publish(OrderLifecycleEvent::for_order(&order, &order.id, OrderEvent::Rejected { reason: e.to_string() }));
+        }
+        let order_id = submit_res?;
 
         // 주문 ID 업데이트
         {
@@ -101,49 +334,166 @@ impl OrderManager {
             updated_order.id = order_id.clone();
             repo.update(&updated_order).await?;
         }
+        self.publish(OrderLifecycleEvent::for_order(&order, &order_id, OrderEvent::Acked));
 
         Ok(order_id)
     }
 
+    /// 라우팅 규칙으로 분할 실행이 지정된 주문을 클라이언트 측 분할기로 실행 - 부모 주문 ID 반환
+    async fn start_algo(&self, exchange: Arc<RwLock<dyn Exchange>>, mut parent: Order, algo: &RoutingAction) -> Result<OrderId, TradingError> {
+        for validator in &self.validators {
+            validator.validate(&parent)?;
+        }
+        parent.id = OrderId(format!("algo-{}", Uuid::new_v4()));
+        if parent.client_order_id.is_none() {
+            parent.client_order_id = Some(parent.id.0.clone());
+        }
+        self.repository.write().await.save(&parent).await?;
+
+        let (repository, algo) = (self.repository.clone(), algo.clone());
+        let parent_id = parent.id.clone();
+        tokio::spawn(async move {
+            let (symbol, side, quantity) = (parent.symbol.clone(), parent.side.clone(), parent.quantity);
+            let result = match algo {
+                RoutingAction::Twap { duration_ms, slices } => {
+                    let mut splitter = TwapSplitter::new(exchange, symbol, side, quantity, duration_ms, slices);
+                    splitter.start().await.map(|_| splitter.status().1)
+                }
+                RoutingAction::Vwap { duration_ms, participation_rate } => {
+                    let mut splitter = VwapSplitter::new(exchange, symbol, side, quantity, duration_ms, participation_rate);
+                    splitter.start().await.map(|_| splitter.status().1)
+                }
+                _ => Ok(Decimal::ZERO),
+            };
+            let status = match result {
+                Ok(executed) if executed >= quantity => OrderStatus::Filled,
+                Ok(executed) if executed > Decimal::ZERO => OrderStatus::PartiallyFilled,
+                Ok(_) => OrderStatus::Expired,
+                Err(e) => {
+                    log::warn!("routed algo order {} failed: {}", parent.id, e);
+                    OrderStatus::Rejected
+                }
+            };
+            if let Err(e) = repository.write().await.update_status(&parent.id, status).await {
+                log::warn!("algo order status persist failed {}: {}", parent.id, e);
+            }
+        });
+
+        Ok(parent_id)
+    }
+
     /// 주문 취소
     pub async fn cancel_order(&self, order_id: &OrderId) -> Result<(), TradingError> {
-        // 주문 존재 여부 확인
-        {
+        // 주문 존재 여부 확인 (제출된 거래소로 취소)
+        let venue = {
             let repo = self.repository.read().await;
-            if repo.find_by_id(order_id).await?.is_none() {
-                return Err(TradingError::OrderNotFound(order_id.clone()));
+            match repo.find_by_id(order_id).await? {
+                Some(order) => order.exchange,
+                None => return Err(TradingError::OrderNotFound(order_id.clone())),
             }
-        }
+        };
+        let exchange = self.exchanges.resolve(venue.as_deref())?;
 
-        // 주문 취소 요청
+        // 주문 취소 요청 (읽기 전용 모드에서도 시도 - 성공하면 권한 복구로 판단)
         {
-            let mut exchange = self.exchange.write().await;
-            exchange.cancel_order(order_id).await?;
+            let mut exchange = exchange.write().await;
+            let cancel_res = exchange.cancel_order(order_id).await;
+            self.write_access.record_result(&cancel_res, chrono::Utc::now().timestamp_millis());
+            cancel_res?;
         }
 
         // 주문 상태 업데이트
         {
             let mut repo = self.repository.write().await;
             if let Some(mut order) = repo.find_by_id(order_id).await? {
-                // 상태 변경
-                let status = OrderStatus::Cancelled;
-
-                // 상태 채널 알림
-                if let Some(client_id) = order.client_order_id.as_ref() {
-                    if let Some(sender) = self.status_channels.get(client_id) {
-                        let _ = sender.send(status.clone());
-                    }
-                }
-
                 // 주문 업데이트
                 order.id = order_id.clone();
                 repo.update(&order).await?;
+                repo.update_status(order_id, OrderStatus::Cancelled).await?;
+                self.publish(OrderLifecycleEvent::for_order(&order, order_id, OrderEvent::Cancelled));
             }
         }
 
         Ok(())
     }
 
+    /// 전략이 낸 미체결 주문 취소 (전략 제거 시) - 취소한 주문 수 반환
+    pub async fn cancel_strategy_orders(&self, strategy: &str) -> Result<usize, TradingError> {
+        let ids = self.order_events.strategy_orders(strategy);
+        if ids.is_empty() {
+            return Ok(0);
+        }
+        let open: std::collections::HashSet<OrderId> = self.get_open_orders().await?.into_iter().map(|o| o.id).collect();
+        let mut cancelled = 0;
+        for id in ids {
+            if open.contains(&id) {
+                match self.cancel_order(&id).await {
+                    Ok(()) => cancelled += 1,
+                    Err(e) => log::warn!("strategy {} order {} cancel failed: {}", strategy, id.0, e),
+                }
+            }
+            self.order_events.release_strategy_orders(Some(&id));
+        }
+        Ok(cancelled)
+    }
+
+    /// 모든 거래소의 미체결 주문 취소 (프로세스 종료, 거래 중지/데드맨 스위치) - 취소한 주문과 실패 내역 반환
+    pub async fn cancel_all_open_orders(&self) -> (Vec<OrderId>, Vec<String>) {
+        let (mut cancelled, mut errors) = (Vec::new(), Vec::new());
+        for (name, exchange) in self.exchanges.iter() {
+            let open = match exchange.read().await.get_open_orders().await {
+                Ok(open) => open,
+                Err(e) => {
+                    errors.push(format!("{} open orders: {}", name, e));
+                    continue;
+                }
+            };
+            for order in open {
+                let cancel_res = exchange.write().await.cancel_order(&order.id).await;
+                self.write_access.record_result(&cancel_res, chrono::Utc::now().timestamp_millis());
+                match cancel_res {
+                    Ok(()) => {
+                        // 이 프로세스가 낸 주문이면 저장소 상태를 갱신하고 취소 이벤트 발행
+                        let mut repo = self.repository.write().await;
+                        if let Ok(Some(stored)) = repo.find_by_id(&order.id).await {
+                            let _ = repo.update_status(&order.id, OrderStatus::Cancelled).await;
+                            self.publish(OrderLifecycleEvent::for_order(&stored, &order.id, OrderEvent::Cancelled));
+                        }
+                        cancelled.push(order.id);
+                    }
+                    Err(e) => errors.push(format!("{} cancel {}: {}", name, order.id.0, e)),
+                }
+            }
+        }
+        self.order_events.release_strategy_orders(None);
+        (cancelled, errors)
+    }
+
+    /// 모든 거래소의 보유 포지션을 reduce-only 시장가로 청산 - 청산한 `거래소:심볼` 과 실패 내역 반환
+    pub async fn flatten_all_positions(&self) -> (Vec<String>, Vec<String>) {
+        let (mut flattened, mut errors) = (Vec::new(), Vec::new());
+        for (name, exchange) in self.exchanges.iter() {
+            let positions = match exchange.read().await.get_positions().await {
+                Ok(positions) => positions,
+                Err(e) => {
+                    errors.push(format!("{} positions: {}", name, e));
+                    continue;
+                }
+            };
+            for position in positions.into_iter().filter(|p| !p.quantity.is_zero()) {
+                let side = if position.is_long() { OrderSide::Sell } else { OrderSide::Buy };
+                let order = Order::new(position.symbol.clone(), side, OrderType::Market, position.quantity.abs(), Decimal::ZERO)
+                    .with_reduce_only(true)
+                    .with_exchange(name);
+                match self.create_order(order).await {
+                    Ok(_) => flattened.push(format!("{}:{}", name, position.symbol)),
+                    Err(e) => errors.push(format!("{} flatten {}: {}", name, position.symbol, e)),
+                }
+            }
+        }
+        (flattened, errors)
+    }
+
     /// 주문 수정
     pub async fn modify_order(&self, order_id: &OrderId, new_params: Order) -> Result<OrderId, TradingError> {
         // 주문 존재 여부 확인
@@ -156,9 +506,16 @@ impl OrderManager {
             }
         };
 
-        // 주문 수정 요청
+        // 주문 수정 요청 (원 주문과 같은 거래소)
+        let mut new_params = new_params;
+        if new_params.exchange.is_none() {
+            new_params.exchange = original_order.exchange.clone();
+        }
+        let exchange = self.exchanges.resolve(original_order.exchange.as_deref())?;
+        let venue = original_order.exchange.clone().unwrap_or_else(|| self.exchanges.default_name().to_string());
+        let new_params = self.symbol_rules.apply(&venue, &exchange, new_params, None).await?;
         let new_order_id = {
-            let mut exchange = self.exchange.write().await;
+            let mut exchange = exchange.write().await;
             exchange.modify_order(order_id, new_params.clone()).await?
         };
 
@@ -167,20 +524,14 @@ impl OrderManager {
             let mut repo = self.repository.write().await;
 
             // 원래 주문 취소 상태로 변경
-            let mut cancelled_order = original_order.clone();
-            let status = OrderStatus::Cancelled;
-
-            // 상태 채널 알림
-            if let Some(client_id) = cancelled_order.client_order_id.as_ref() {
-                if let Some(sender) = self.status_channels.get(client_id) {
-                    let _ = sender.send(status.clone());
-                }
-            }
+            repo.update_status(&original_order.id, OrderStatus::Cancelled).await?;
+            self.publish(OrderLifecycleEvent::for_order(&original_order, &original_order.id, OrderEvent::Cancelled));
 
             // 새 주문 저장
             let mut new_order = new_params.clone();
             new_order.id = new_order_id.clone();
             repo.save(&new_order).await?;
+            self.publish(OrderLifecycleEvent::for_order(&new_order, &new_order_id, OrderEvent::Acked));
         }
 
         Ok(new_order_id)
@@ -190,122 +541,280 @@ impl OrderManager {
     pub async fn get_order_status(&self, order_id: &OrderId) -> Result<OrderStatus, TradingError> {
         // 거래소에서 최신 상태 확인
         let exchange_status = {
-            let exchange = self.exchange.read().await;
+            let exchange = self.exchange_for(order_id).await?;
+            let exchange = exchange.read().await;
             exchange.get_order_status(order_id).await?
         };
 
-        // 주문 저장소 업데이트
+        // 주문 저장소 업데이트 (상태 변화 알림은 사용자 데이터 스트림과 상태 폴링이 발행)
         {
             let mut repo = self.repository.write().await;
-            if let Some(mut order) = repo.find_by_id(order_id).await? {
-                // 상태 채널 알림
-                if let Some(client_id) = order.client_order_id.as_ref() {
-                    if let Some(sender) = self.status_channels.get(client_id) {
-                        let _ = sender.send(exchange_status.clone());
-                    }
-                }
+            if repo.find_by_id(order_id).await?.is_some() {
+                repo.update_status(order_id, exchange_status.clone()).await?;
             }
         }
 
         Ok(exchange_status)
     }
 
-    /// 미체결 주문 조회
+    /// 미체결 주문 조회 - 등록된 모든 거래소 (주문에 거래소 이름 표시)
     pub async fn get_open_orders(&self) -> Result<Vec<Order>, TradingError> {
-        let exchange = self.exchange.read().await;
-        exchange.get_open_orders().await
+        let mut orders = Vec::new();
+        for (name, exchange) in self.exchanges.iter() {
+            let open = exchange.read().await.get_open_orders().await?;
+            orders.extend(open.into_iter().map(|mut order| {
+                order.exchange.get_or_insert_with(|| name.to_string());
+                order
+            }));
+        }
+        Ok(orders)
     }
 
-    /// 주문 상태 변경 알림 구독
-    pub fn subscribe_to_status_updates(&mut self, client_order_id: &str) -> broadcast::Receiver<OrderStatus> {
-        if let Some(sender) = self.status_channels.get(client_order_id) {
-            sender.subscribe()
-        } else {
-            let (sender, receiver) = broadcast::channel(100);
-            self.status_channels.insert(client_order_id.to_string(), sender);
-            receiver
+    fn publish(&self, event: OrderLifecycleEvent) {
+        self.order_events.publish(event);
+    }
+
+    /// 주문 생명주기 이벤트 구독 (모든 주문)
+    pub fn subscribe_to_order_events(&self) -> broadcast::Receiver<OrderLifecycleEvent> {
+        self.order_events.subscribe()
+    }
+
+    /// 푸시된 주문 보고 반영 - 저장소 상태 갱신 후 이벤트 버스로 발행
+    pub async fn apply_order_update(&self, update: &OrderUpdate) -> Result<(), TradingError> {
+        let mut update = update.clone();
+        {
+            let mut repo = self.repository.write().await;
+            // 이 프로세스가 만들지 않은 주문(수동 주문 등)은 저장하지 않고 알림만 전달
+            if let Some(order) = repo.find_by_id(&update.order_id).await? {
+                repo.update_status(&update.order_id, update.status.clone()).await?;
+                if update.client_order_id.is_none() {
+                    update.client_order_id = order.client_order_id;
+                }
+            }
         }
+
+        self.publish(OrderLifecycleEvent::from_update(update));
+        Ok(())
     }
 
     /// 주문 상태 감시 시작
     pub async fn start_order_monitoring(&self) -> Result<(), TradingError> {
-        let exchange_clone = self.exchange.clone();
-        let repository_clone = self.repository.clone();
-        let status_channels_clone = self.status_channels.clone();
+        let exchanges = self.exchanges.clone();
+        let repository = self.repository.clone();
+        let order_events = self.order_events.clone();
+        let poll_interval = self.poll_interval;
 
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
-
+            let mut interval = tokio::time::interval(poll_interval);
             loop {
                 interval.tick().await;
-
-                // 미체결 주문 가져오기
-                let repo = repository_clone.read().await;
-                let open_orders = match repo.find_by_status(&[OrderStatus::New, OrderStatus::PartiallyFilled]).await {
-                    Ok(orders) => orders,
-                    Err(_) => continue,
-                };
-
-                // 각 주문 상태 확인
-                for order in open_orders {
-                    let exchange = exchange_clone.read().await;
-                    if let Ok(status) = exchange.get_order_status(&order.id).await {
-                        drop(exchange);
-
-                        // 상태가 변경된 경우 업데이트
-                        let mut repo = repository_clone.write().await;
-                        let mut updated_order = order.clone();
-                        updated_order.id = order.id.clone();
-
-                        // 상태 채널 알림
-                        if let Some(client_id) = order.client_order_id.as_ref() {
-                            if let Some(sender) = status_channels_clone.get(client_id) {
-                                let _ = sender.send(status.clone());
-                            }
-                        }
-                    }
-                }
+                Self::poll_order_statuses(&exchanges, &repository, &order_events).await;
             }
         });
 
         Ok(())
     }
+
+    /// 미체결 주문 상태 한 번 조회 - 폴링으로 감지한 종료 상태도 이벤트로 알림
+    /// (취소 응답이나 사용자 데이터 스트림이 이미 알린 종료는 버스가 걸러냄,
+    /// 상태를 저장하지 않는 저장소는 종료된 주문도 계속 조회됨)
+    async fn poll_order_statuses(exchanges: &ExchangeRegistry, repository: &Arc<RwLock<dyn OrderRepository>>, order_events: &OrderEventBus) {
+        // 읽기 잠금은 조회 직후 해제 (아래 상태 갱신에서 쓰기 잠금 필요)
+        let open_orders = match repository.read().await.find_by_status(&[OrderStatus::New, OrderStatus::PartiallyFilled]).await {
+            Ok(orders) => orders,
+            Err(_) => return,
+        };
+
+        for order in open_orders {
+            let Ok(exchange) = exchanges.resolve(order.exchange.as_deref()) else { continue };
+            let exchange = exchange.read().await;
+            let Ok(status) = exchange.get_order_status(&order.id).await else { continue };
+            // 체결 수량을 모르는 일부 체결은 사용자 데이터 스트림에 맡김
+            let event = match status {
+                _ if order_events.is_terminal(&order.id) => None,
+                // 전량 체결은 이미 발행된 부분 체결을 뺀 나머지를 거래소 평균 체결가로
+                // (평균가를 모르면 주문 가격, 가격 없는 시장가는 현재가)
+                OrderStatus::Filled => {
+                    let fill = exchange.get_order_fill(&order.id).await.ok().flatten();
+                    let executed = fill.map_or(order.quantity, |f| f.executed_quantity);
+                    let price = match fill {
+                        Some(f) if f.average_price > Decimal::ZERO => Some(f.average_price),
+                        _ if order.price > Decimal::ZERO => Some(order.price),
+                        _ => exchange.get_market_data(&order.symbol).await.ok().map(|md| md.close),
+                    };
+                    let remaining = (executed - order_events.filled_quantity(&order.id)).max(Decimal::ZERO);
+                    price.map(|price| OrderEvent::Filled { qty: remaining, price }).map(|event| (event, executed))
+                }
+                OrderStatus::Cancelled => Some((OrderEvent::Cancelled, Decimal::ZERO)),
+                OrderStatus::Rejected => Some((OrderEvent::Rejected { reason: "rejected by exchange".to_string() }, Decimal::ZERO)),
+                OrderStatus::Expired => Some((OrderEvent::Expired, Decimal::ZERO)),
+                OrderStatus::New | OrderStatus::PartiallyFilled => None,
+            };
+            drop(exchange);
+            if let Some((event, executed)) = event {
+                let mut lifecycle = OrderLifecycleEvent::for_order(&order, &order.id, event);
+                if let OrderEvent::Filled { qty, price } = lifecycle.event {
+                    lifecycle.update.last_fill_price = price;
+                    lifecycle.update.last_fill_quantity = qty;
+                    lifecycle.update.cumulative_quantity = executed;
+                }
+                order_events.publish(lifecycle);
+            }
+
+            let mut repo = repository.write().await;
+            if let Err(e) = repo.update_status(&order.id, status).await {
+                log::warn!("order status persist failed {}: {}", order.id, e);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::decimal::dec;
     use crate::exchange::mocks::MockExchange;
+    use crate::models::symbol_info::SymbolInfo;
     use crate::order_core::repository::InMemoryOrderRepository;
 
     #[tokio::test]
     async fn test_order_lifecycle() {
         // 테스트 환경 설정
         let config = crate::config::Config::default();
-        let exchange = Arc::new(RwLock::new(MockExchange::new(config)));
+        let mut exchange = MockExchange::new(config);
+        exchange.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let exchange = Arc::new(RwLock::new(exchange));
         let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
         let manager = OrderManager::new(exchange, repository);
+        let mut events = manager.subscribe_to_order_events();
 
         // 주문 생성
         let order = Order::new(
             "BTCUSDT",
             OrderSide::Buy,
             OrderType::Limit,
-            0.1,
-            50000.0,
+            dec!(0.1),
+            dec!(40000),  // 시세(50,000) 아래 - 체결되지 않고 대기
         );
 
-        let order_id = manager.create_order(order).await.unwrap();
+        let order_id = manager.create_order_for(Some("grid"), order).await.unwrap();
 
         // 주문 상태 확인
         let status = manager.get_order_status(&order_id).await.unwrap();
         assert!(status == OrderStatus::PartiallyFilled || status == OrderStatus::New);
 
+        // 거래소 체결 보고는 형식화된 체결 이벤트로
+        let fill = OrderUpdate {
+            status: OrderStatus::PartiallyFilled,
+            last_fill_price: dec!(40_000),
+            last_fill_quantity: dec!(0.05),
+            ..OrderLifecycleEvent::for_order(&Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(40000)), &order_id, OrderEvent::Acked).update
+        };
+        manager.apply_order_update(&fill).await.unwrap();
+
         // 주문 취소
         manager.cancel_order(&order_id).await.unwrap();
 
         // 취소 확인
         let status = manager.get_order_status(&order_id).await.unwrap();
         assert_eq!(status, OrderStatus::Cancelled);
+
+        // 생성 -> 접수 -> 일부 체결 -> 취소 이벤트 (모든 이벤트에 같은 클라이언트 ID 와 주문을 낸 전략이 붙음)
+        let received: Vec<OrderLifecycleEvent> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+        assert_eq!(received.iter().map(|e| e.event.clone()).collect::<Vec<_>>(), vec![
+            OrderEvent::Created,
+            OrderEvent::Acked,
+            OrderEvent::PartiallyFilled { qty: dec!(0.05), price: dec!(40_000) },
+            OrderEvent::Cancelled,
+        ]);
+        assert_eq!(received.iter().map(|e| e.strategy.as_deref()).collect::<Vec<_>>(), vec![Some("grid"); 4]);
+        assert!(received[0].update.client_order_id.is_some() && received.iter().all(|e| e.update.client_order_id == received[0].update.client_order_id));
+        assert!(manager.order_events.strategy_orders("grid").is_empty());
+
+        // 폴링이 같은 취소를 다시 감지해도, 뒤늦은 취소 보고가 와도 다시 알리지 않음
+        OrderManager::poll_order_statuses(&manager.exchanges, &manager.repository, &manager.order_events).await;
+        manager.apply_order_update(&OrderUpdate { status: OrderStatus::Cancelled, ..fill }).await.unwrap();
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_polled_fill_reports_remaining_quantity() {
+        let mut exchange = MockExchange::new(crate::config::Config::default());
+        exchange.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let exchange = Arc::new(RwLock::new(exchange));
+        let manager = OrderManager::new(exchange.clone(), Arc::new(RwLock::new(InMemoryOrderRepository::new())));
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(40_000));
+        let order_id = manager.create_order(order.clone()).await.unwrap();
+
+        // 사용자 데이터 스트림이 일부 체결을 먼저 알림
+        let partial = OrderUpdate {
+            status: OrderStatus::PartiallyFilled,
+            last_fill_price: dec!(40_000),
+            last_fill_quantity: dec!(0.04),
+            cumulative_quantity: dec!(0.04),
+            ..OrderLifecycleEvent::for_order(&order, &order_id, OrderEvent::Acked).update
+        };
+        manager.apply_order_update(&partial).await.unwrap();
+        let mut events = manager.subscribe_to_order_events();
+
+        // 폴링으로 전량 체결 감지 - 나머지 수량만 거래소 평균 체결가로
+        exchange.write().await.set_price("BTCUSDT", dec!(39_000)).unwrap();
+        let fill = exchange.read().await.get_order_fill(&order_id).await.unwrap().unwrap();
+        OrderManager::poll_order_statuses(&manager.exchanges, &manager.repository, &manager.order_events).await;
+        let filled = events.try_recv().unwrap();
+        assert_eq!(filled.event, OrderEvent::Filled { qty: dec!(0.06), price: fill.average_price });
+        assert_eq!(filled.update.cumulative_quantity, dec!(0.1));
+        assert_eq!(filled.update.new_fill_quantity(dec!(0.04)), dec!(0.06));
+    }
+
+    #[tokio::test]
+    async fn test_orders_routed_to_named_exchange() {
+        let mut primary = MockExchange::new(crate::config::Config::default());
+        primary.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let mut sandbox = MockExchange::new(crate::config::Config::default());
+        sandbox.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let (primary, sandbox) = (Arc::new(RwLock::new(primary)), Arc::new(RwLock::new(sandbox)));
+        let mut manager = OrderManager::new(primary.clone(), Arc::new(RwLock::new(InMemoryOrderRepository::new())));
+        let mut registry = ExchangeRegistry::new("binance", primary.clone());
+        registry.register("sandbox", sandbox.clone()).unwrap();
+        manager.set_exchange_registry(registry);
+
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(40_000)).with_exchange("sandbox");
+        let order_id = manager.create_order(order).await.unwrap();
+        assert_eq!(sandbox.read().await.get_open_orders().await.unwrap().len(), 1);
+        assert!(primary.read().await.get_open_orders().await.unwrap().is_empty());
+        assert_eq!(manager.get_open_orders().await.unwrap()[0].exchange.as_deref(), Some("sandbox"));
+
+        // 취소도 제출한 거래소로
+        manager.cancel_order(&order_id).await.unwrap();
+        assert!(sandbox.read().await.get_open_orders().await.unwrap().is_empty());
+
+        let unknown = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.1), dec!(40_000)).with_exchange("kraken");
+        assert!(manager.create_order(unknown).await.is_err());
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_orders_rounded_to_symbol_rules() {
+        let mut exchange = MockExchange::new(crate::config::Config::default());
+        exchange.set_price("BTCUSDT", dec!(50_000)).unwrap();
+        let repository = Arc::new(RwLock::new(InMemoryOrderRepository::new()));
+        let manager = OrderManager::new(Arc::new(RwLock::new(exchange)), repository.clone());
+        let rules = SymbolInfo { symbol: "BTCUSDT".into(), tick_size: dec!(0.1), step_size: dec!(0.001), min_qty: dec!(0.001), min_notional: dec!(100) };
+        manager.symbol_rules().insert("default", rules);
+
+        // 매수 지정가는 호가 단위로 내림, 수량은 수량 단위로 내림
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.12345), dec!(40_000.07));
+        let order_id = manager.create_order(order).await.unwrap();
+        let stored = repository.read().await.find_by_id(&order_id).await.unwrap().unwrap();
+        assert_eq!((stored.quantity, stored.price), (dec!(0.123), dec!(40_000)));
+
+        // 수량 단위 내림 후 최소 수량 미달, 최소 주문 금액 미달은 제출 전 거부
+        assert!(manager.create_order(Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.0009), dec!(40_000))).await.is_err());
+        assert!(manager.create_order(Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(0.002), dec!(40_000))).await.is_err());
+        assert_eq!(repository.read().await.find_all().await.unwrap().len(), 1);
+
+        // 매도 지정가는 올림
+        let info = manager.symbol_rules().get("default", "BTCUSDT").unwrap();
+        assert_eq!(info.round_price(dec!(60_000.01), &OrderSide::Sell), dec!(60_000.1));
+    }
+}
@@ -1,3 +1,12 @@
 pub mod manager;
 pub mod repository;
-pub mod validator;
\ No newline at end of file
+pub mod validator;
+pub mod reconcile;
+pub mod file_repository;
+pub mod routing;
+pub mod tax_lots;
+pub mod submission_queue;
+pub mod bracket;
+pub mod position_router;
+pub mod events;
+pub mod sql_repository;
@@ -0,0 +1,344 @@
+//! 포지션 인지 주문 라우팅 (넷팅 / 헤지 모드)
+//!
+//! 현재 포지션과 선물 포지션 모드를 보고 주문의 reduceOnly / positionSide 를 정한다.
+//! - 단방향(넷팅) 모드: 보유 포지션 반대 방향 주문은 reduce-only 로 표시하고, 포지션보다 큰 주문은
+//!   `allow_flip` 이 아니면 포지션 크기로 줄여 의도치 않은 반전을 막는다.
+//! - 헤지 모드: 매수는 LONG, 매도는 SHORT 로 열고, reduce-only 주문은 반대 쪽 포지션을 닫는 positionSide 로
+//!   바꾼다 (헤지 모드 거래소는 reduceOnly 대신 positionSide 로 청산을 구분).
+//!
+//! 포지션은 주문 체결 이벤트로 추적하고, `sync_interval_ms` 가 있으면 거래소 포지션으로 주기적으로 맞춘다.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::RwLock;
+
+use crate::config::PositionRoutingConfig;
+use crate::error::TradingError;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderSide, OrderStatus, OrderUpdate};
+use crate::models::position::Position;
+use crate::order_core::manager::OrderManager;
+
+/// 심볼별 롱/숏 포지션 (단방향 모드에서는 둘 중 하나만 0 이 아님)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct PositionLegs {
+    pub long: Decimal,
+    pub short: Decimal,
+}
+
+impl PositionLegs {
+    /// 순 포지션 (롱 +, 숏 -)
+    pub fn net(&self) -> Decimal {
+        self.long - self.short
+    }
+
+    fn leg_mut(&mut self, leg: Leg) -> &mut Decimal {
+        match leg {
+            Leg::Long => &mut self.long,
+            Leg::Short => &mut self.short,
+        }
+    }
+
+    // 단방향 모드 체결: 반대 포지션부터 줄이고 남으면 반대로 진입
+    fn apply_net(&mut self, signed_qty: Decimal) {
+        let net = self.net() + signed_qty;
+        *self = PositionLegs { long: net.max(Decimal::ZERO), short: (-net).max(Decimal::ZERO) };
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Leg {
+    Long,
+    Short,
+}
+
+impl Leg {
+    fn parse(position_side: Option<&str>) -> Option<Leg> {
+        match position_side? {
+            side if side.eq_ignore_ascii_case("LONG") => Some(Leg::Long),
+            side if side.eq_ignore_ascii_case("SHORT") => Some(Leg::Short),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Leg::Long => "LONG",
+            Leg::Short => "SHORT",
+        }
+    }
+
+    // 이 방향 주문이 해당 포지션을 여는지
+    fn opened_by(side: &OrderSide) -> Leg {
+        match side {
+            OrderSide::Buy => Leg::Long,
+            OrderSide::Sell => Leg::Short,
+        }
+    }
+}
+
+struct RouterState {
+    hedge: bool,
+    positions: HashMap<String, PositionLegs>,
+    /// 제출한 주문의 클라이언트 ID -> 헤지 모드 포지션 쪽 (체결 반영용, 단방향은 None)
+    pending: HashMap<String, Option<Leg>>,
+}
+
+/// 포지션 인지 라우터 - 주문 관리자와 체결 감시 태스크가 같은 핸들을 공유
+#[derive(Clone)]
+pub struct PositionRouter {
+    state: Arc<Mutex<RouterState>>,
+    allow_flip: bool,
+}
+
+impl PositionRouter {
+    pub fn new(config: &PositionRoutingConfig, hedge: bool) -> Self {
+        PositionRouter {
+            state: Arc::new(Mutex::new(RouterState { hedge, positions: HashMap::new(), pending: HashMap::new() })),
+            allow_flip: config.allow_flip,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RouterState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 거래소 포지션 모드 변경 반영 (`POST /futures/position_mode`)
+    pub fn set_hedge_mode(&self, hedge: bool) {
+        self.lock().hedge = hedge;
+    }
+
+    pub fn hedge_mode(&self) -> bool {
+        self.lock().hedge
+    }
+
+    pub fn position(&self, symbol: &str) -> PositionLegs {
+        self.lock().positions.get(symbol).copied().unwrap_or_default()
+    }
+
+    /// 주문에 reduceOnly / positionSide 지정 (청산할 포지션이 없는 reduce-only 주문은 거부)
+    pub fn route(&self, mut order: Order) -> Result<Order, TradingError> {
+        let state = self.lock();
+        let legs = state.positions.get(&order.symbol).copied().unwrap_or_default();
+        if state.hedge {
+            self.route_hedge(&mut order, legs)?;
+        } else {
+            self.route_net(&mut order, legs)?;
+        }
+        Ok(order)
+    }
+
+    /// 제출 직전 주문 등록 - 체결을 어느 포지션에 반영할지 클라이언트 ID 로 기억
+    pub fn track(&self, order: &Order) {
+        if let Some(client_id) = &order.client_order_id {
+            let leg = Leg::parse(order.position_side.as_deref());
+            self.lock().pending.insert(client_id.clone(), leg);
+        }
+    }
+
+    fn route_net(&self, order: &mut Order, legs: PositionLegs) -> Result<(), TradingError> {
+        let net = legs.net();
+        let opposes = match order.side {
+            OrderSide::Buy => net < Decimal::ZERO,
+            OrderSide::Sell => net > Decimal::ZERO,
+        };
+        if !opposes {
+            if order.reduce_only == Some(true) {
+                return Err(TradingError::InvalidParameter(format!(
+                    "reduce-only {:?} {} has no opposite position to reduce (net {})", order.side, order.symbol, net
+                )));
+            }
+            return Ok(());
+        }
+        if order.quantity <= net.abs() {
+            order.reduce_only = Some(true);
+        } else if !self.allow_flip {
+            log::info!("{} {:?} {} would flip net position {}, cut to a reduce-only close", order.symbol, order.side, order.quantity, net);
+            order.quantity = net.abs();
+            order.reduce_only = Some(true);
+        }
+        Ok(())
+    }
+
+    fn route_hedge(&self, order: &mut Order, legs: PositionLegs) -> Result<(), TradingError> {
+        let explicit = Leg::parse(order.position_side.as_deref());
+        let opens = Leg::opened_by(&order.side);
+        let leg = match explicit {
+            Some(leg) => leg,
+            // 청산 주문은 주문 방향이 여는 쪽의 반대 포지션을 닫음
+            None if order.reduce_only == Some(true) => if opens == Leg::Long { Leg::Short } else { Leg::Long },
+            None => opens,
+        };
+        if leg != opens {
+            let held = match leg {
+                Leg::Long => legs.long,
+                Leg::Short => legs.short,
+            };
+            if held <= Decimal::ZERO {
+                return Err(TradingError::InvalidParameter(format!(
+                    "{:?} {} closes the {} position, which is empty", order.side, order.symbol, leg.as_str()
+                )));
+            }
+            if order.quantity > held {
+                order.quantity = held;
+            }
+        }
+        order.position_side = Some(leg.as_str().to_string());
+        // 헤지 모드 거래소는 reduceOnly 를 받지 않음 (positionSide 로 청산 구분)
+        order.reduce_only = None;
+        Ok(())
+    }
+
+    /// 체결 이벤트 반영
+    pub fn on_order_update(&self, update: &OrderUpdate) {
+        let mut state = self.lock();
+        let leg = update.client_order_id.as_ref().and_then(|id| state.pending.get(id).copied());
+        if update.is_fill() {
+            let hedge = state.hedge;
+            let legs = state.positions.entry(update.symbol.clone()).or_default();
+            let signed = match update.side {
+                OrderSide::Buy => update.last_fill_quantity,
+                OrderSide::Sell => -update.last_fill_quantity,
+            };
+            // 라우팅하지 않은 주문은 현재 모드의 기본 규칙으로 추정 (헤지: 주문 방향이 여는 쪽)
+            match leg.unwrap_or_else(|| hedge.then(|| Leg::opened_by(&update.side))) {
+                Some(leg) => {
+                    let opens = Leg::opened_by(&update.side) == leg;
+                    let held = legs.leg_mut(leg);
+                    *held = if opens { *held + update.last_fill_quantity } else { (*held - update.last_fill_quantity).max(Decimal::ZERO) };
+                }
+                None => legs.apply_net(signed),
+            }
+        }
+        if matches!(update.status, OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected | OrderStatus::Expired) {
+            if let Some(id) = &update.client_order_id {
+                state.pending.remove(id);
+            }
+        }
+    }
+
+    /// 제출에 실패한 주문 정리
+    pub fn forget(&self, client_order_id: &str) {
+        self.lock().pending.remove(client_order_id);
+    }
+
+    /// 거래소 포지션으로 교체 (수량 부호로 롱/숏 구분, 헤지 모드는 심볼당 두 개까지)
+    pub fn sync(&self, positions: &[Position]) {
+        let mut synced: HashMap<String, PositionLegs> = HashMap::new();
+        for position in positions {
+            let legs = synced.entry(position.symbol.clone()).or_default();
+            if position.quantity > Decimal::ZERO {
+                legs.long += position.quantity;
+            } else {
+                legs.short += -position.quantity;
+            }
+        }
+        self.lock().positions = synced;
+    }
+
+    /// 체결 감시 태스크 시작 (sync_interval 이 있으면 거래소 포지션으로 주기 동기화)
+    pub fn start(
+        &self,
+        order_manager: Arc<RwLock<OrderManager>>,
+        exchange: Arc<RwLock<dyn Exchange>>,
+        sync_interval: Option<Duration>,
+    ) -> tokio::task::JoinHandle<()> {
+        let router = self.clone();
+        tokio::spawn(async move {
+            let mut events = order_manager.read().await.subscribe_to_order_events();
+            let mut ticker = tokio::time::interval(sync_interval.unwrap_or(Duration::from_secs(3600)));
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(event) => router.on_order_update(&event.update),
+                        Err(RecvError::Lagged(n)) => log::warn!("position router lagged {} order updates, positions may be stale", n),
+                        Err(RecvError::Closed) => break,
+                    },
+                    _ = ticker.tick(), if sync_interval.is_some() => {
+                        let positions = exchange.read().await.get_positions().await;
+                        match positions {
+                            Ok(positions) => router.sync(&positions),
+                            Err(e) => log::debug!("position router sync failed: {}", e),
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::models::order::{OrderId, OrderType};
+
+    fn order(side: OrderSide, quantity: f64, client_id: &str) -> Order {
+        Order::new("BTCUSDT", side, OrderType::Market, to_decimal(quantity), dec!(0)).with_client_order_id(client_id)
+    }
+
+    fn routed(router: &PositionRouter, order: Order) -> Order {
+        let order = router.route(order).unwrap();
+        router.track(&order);
+        order
+    }
+
+    fn fill(order: &Order, quantity: f64) -> OrderUpdate {
+        OrderUpdate {
+            order_id: OrderId(format!("ex-{}", order.client_order_id.as_deref().unwrap_or(""))),
+            client_order_id: order.client_order_id.clone(),
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            status: OrderStatus::Filled,
+            last_fill_price: dec!(50_000),
+            last_fill_quantity: to_decimal(quantity),
+            cumulative_quantity: to_decimal(quantity),
+            fee: Decimal::ZERO,
+            fee_asset: None,
+            liquidity: None,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_netting_and_hedge_routing() {
+        // 단방향: 반대 주문은 reduce-only, 포지션보다 크면 청산 수량으로 축소
+        let router = PositionRouter::new(&PositionRoutingConfig::default(), false);
+        let entry = routed(&router, order(OrderSide::Buy, 1.0, "a"));
+        assert_eq!((entry.reduce_only, entry.position_side.as_deref()), (None, None));
+        router.on_order_update(&fill(&entry, 1.0));
+        let close = routed(&router, order(OrderSide::Sell, 0.4, "b"));
+        assert_eq!(close.reduce_only, Some(true));
+        let flip = routed(&router, order(OrderSide::Sell, 3.0, "c"));
+        assert_eq!((flip.quantity, flip.reduce_only), (dec!(1), Some(true)));
+        router.on_order_update(&fill(&flip, 1.0));
+        assert_eq!(router.position("BTCUSDT"), PositionLegs::default());
+        assert!(router.route(order(OrderSide::Sell, 1.0, "d").with_reduce_only(true)).is_err());
+        // 반전 허용 시 수량 유지
+        let flipping = PositionRouter::new(&PositionRoutingConfig { allow_flip: true, ..PositionRoutingConfig::default() }, false);
+        flipping.sync(&[Position::new("BTCUSDT", dec!(1), dec!(50_000))]);
+        let flip = routed(&flipping, order(OrderSide::Sell, 3.0, "e"));
+        assert_eq!((flip.quantity, flip.reduce_only), (dec!(3), None));
+        flipping.on_order_update(&fill(&flip, 3.0));
+        assert_eq!(flipping.position("BTCUSDT").net(), dec!(-2));
+
+        // 헤지: 롱/숏을 따로 열고, reduce-only 는 positionSide 로 청산
+        let hedge = PositionRouter::new(&PositionRoutingConfig::default(), true);
+        let long = routed(&hedge, order(OrderSide::Buy, 1.0, "f"));
+        let short = routed(&hedge, order(OrderSide::Sell, 2.0, "g"));
+        assert_eq!((long.position_side.as_deref(), short.position_side.as_deref()), (Some("LONG"), Some("SHORT")));
+        hedge.on_order_update(&fill(&long, 1.0));
+        hedge.on_order_update(&fill(&short, 2.0));
+        assert_eq!(hedge.position("BTCUSDT"), PositionLegs { long: dec!(1), short: dec!(2) });
+        let close_long = routed(&hedge, order(OrderSide::Sell, 5.0, "h").with_reduce_only(true));
+        assert_eq!((close_long.position_side.as_deref(), close_long.reduce_only, close_long.quantity), (Some("LONG"), None, dec!(1)));
+        hedge.on_order_update(&fill(&close_long, 1.0));
+        assert_eq!(hedge.position("BTCUSDT"), PositionLegs { long: dec!(0), short: dec!(2) });
+        assert!(hedge.route(order(OrderSide::Sell, 1.0, "i").with_position_side("LONG")).is_err());
+    }
+}
@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderSide, OrderType};
+
+
+/// 내보내기/가져오기 파일 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    Json,
+}
+
+impl FileFormat {
+    /// 문자열 또는 확장자로 형식 판별
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(FileFormat::Csv),
+            "json" => Ok(FileFormat::Json),
+            other => Err(TradingError::InvalidParameter(format!("Unsupported format: {}", other))),
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Result<Self, TradingError> {
+        Self::parse(path.extension().and_then(|e| e.to_str()).unwrap_or(""))
+    }
+}
+
+/// 체결 기록 (로컬 저장 또는 거래소 거래내역에서 가져온 값)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FillRecord {
+    pub order_id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    #[serde(default)]
+    pub fee: Decimal,
+    pub timestamp: i64,
+}
+
+/// 주문/체결 스냅샷 파일 - 주문 저장소 덤프 및 복구 단위
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderSnapshot {
+    pub saved_at: i64,
+    pub orders: Vec<Order>,
+    #[serde(default)]
+    pub fills: Vec<FillRecord>,
+}
+
+impl OrderSnapshot {
+    pub fn new(orders: Vec<Order>, fills: Vec<FillRecord>) -> Self {
+        OrderSnapshot {
+            saved_at: Utc::now().timestamp_millis(),
+            orders,
+            fills,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, TradingError> {
+        let mut contents = String::new();
+        File::open(path)?.read_to_string(&mut contents)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// 임시 파일에 쓴 뒤 교체 (쓰기 도중 종료되어도 기존 스냅샷 보존)
+    pub fn save(&self, path: &Path) -> Result<(), TradingError> {
+        let tmp = path.with_extension("tmp");
+        File::create(&tmp)?.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// 주문 목록 내보내기
+pub fn export_orders<W: Write>(orders: &[Order], format: FileFormat, writer: W) -> Result<(), TradingError> {
+    match format {
+        FileFormat::Json => serde_json::to_writer_pretty(writer, orders)?,
+        FileFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(writer);
+            for order in orders {
+                wtr.serialize(order).map_err(|e| TradingError::ParseError(e.to_string()))?;
+            }
+            wtr.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// 체결 목록 내보내기
+pub fn export_fills<W: Write>(fills: &[FillRecord], format: FileFormat, writer: W) -> Result<(), TradingError> {
+    match format {
+        FileFormat::Json => serde_json::to_writer_pretty(writer, fills)?,
+        FileFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(writer);
+            for fill in fills {
+                wtr.serialize(fill).map_err(|e| TradingError::ParseError(e.to_string()))?;
+            }
+            wtr.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// 내보낸 주문 파일 다시 읽기
+pub fn import_orders<R: Read>(reader: R, format: FileFormat) -> Result<Vec<Order>, TradingError> {
+    match format {
+        FileFormat::Json => Ok(serde_json::from_reader(reader)?),
+        FileFormat::Csv => csv::Reader::from_reader(reader)
+            .deserialize()
+            .map(|r| r.map_err(|e| TradingError::ParseError(e.to_string())))
+            .collect(),
+    }
+}
+
+/// 거래소 거래내역 파일 가져오기.
+/// CSV 는 헤더 이름이 거래소마다 달라 흔히 쓰이는 별칭을 인식한다
+/// (예: Binance "Date(UTC),Pair,Side,Price,Executed,Amount,Fee").
+pub fn import_exchange_fills<R: Read>(reader: R, format: FileFormat) -> Result<Vec<FillRecord>, TradingError> {
+    if format == FileFormat::Json {
+        return Ok(serde_json::from_reader(reader)?);
+    }
+
+    let mut rdr = csv::Reader::from_reader(reader);
+    let headers: Vec<String> = rdr.headers()
+        .map_err(|e| TradingError::ParseError(e.to_string()))?
+        .iter()
+        .map(|h| h.trim().to_ascii_lowercase())
+        .collect();
+    let column = |aliases: &[&str]| headers.iter().position(|h| aliases.contains(&h.as_str()));
+
+    let missing = |name: &str| TradingError::ParseError(format!("Missing column: {}", name));
+    let order_col = column(&["order_id", "orderid", "order id", "order no"]).ok_or_else(|| missing("order_id"))?;
+    let symbol_col = column(&["symbol", "pair", "market"]).ok_or_else(|| missing("symbol"))?;
+    let side_col = column(&["side", "type"]).ok_or_else(|| missing("side"))?;
+    let price_col = column(&["price", "avg price", "avg_price"]).ok_or_else(|| missing("price"))?;
+    let qty_col = column(&["quantity", "qty", "executed", "filled"]).ok_or_else(|| missing("quantity"))?;
+    let time_col = column(&["timestamp", "time", "date(utc)", "date", "datetime"]).ok_or_else(|| missing("timestamp"))?;
+    let fee_col = column(&["fee", "commission"]);
+
+    let mut fills = Vec::new();
+    for (line, record) in rdr.records().enumerate() {
+        let record = record.map_err(|e| TradingError::ParseError(e.to_string()))?;
+        let field = |i: usize| record.get(i).unwrap_or("").trim();
+        let bad = |what: &str| TradingError::ParseError(format!("Row {}: invalid {}", line + 2, what));
+
+        let side = match field(side_col).to_ascii_lowercase().as_str() {
+            "buy" | "b" => OrderSide::Buy,
+            "sell" | "s" => OrderSide::Sell,
+            _ => return Err(bad("side")),
+        };
+        fills.push(FillRecord {
+            order_id: field(order_col).to_string(),
+            symbol: field(symbol_col).replace(['/', '-', '_'], "").to_ascii_uppercase(),
+            side,
+            price: parse_amount(field(price_col)).ok_or_else(|| bad("price"))?,
+            quantity: parse_amount(field(qty_col)).ok_or_else(|| bad("quantity"))?,
+            fee: fee_col.and_then(|i| parse_amount(field(i))).unwrap_or_default(),
+            timestamp: parse_timestamp(field(time_col)).ok_or_else(|| bad("timestamp"))?,
+        });
+    }
+    Ok(fills)
+}
+
+/// "0.015BTC", "1,234.5" 같은 값에서 숫자 부분만 파싱
+fn parse_amount(s: &str) -> Option<Decimal> {
+    let numeric: String = s.chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '-' || *c == ',')
+        .filter(|c| *c != ',')
+        .collect();
+    numeric.parse().ok()
+}
+
+/// 밀리초/초 타임스탬프 또는 "YYYY-MM-DD HH:MM:SS" (UTC)
+fn parse_timestamp(s: &str) -> Option<i64> {
+    if let Ok(n) = s.parse::<i64>() {
+        return Some(if n < 100_000_000_000 { n * 1000 } else { n });
+    }
+    ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.fZ"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(s, fmt).ok())
+        .map(|dt| dt.and_utc().timestamp_millis())
+}
+
+/// 불일치 유형
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    /// 거래소 체결에 대응하는 로컬 주문 없음
+    UnknownOrder,
+    /// 로컬 주문과 체결의 심볼/방향 불일치
+    SymbolMismatch,
+    SideMismatch,
+    /// 주문 수량보다 많이 체결됨
+    Overfill,
+    /// 지정가보다 불리한 가격으로 체결됨
+    PriceViolation,
+    /// 로컬에 기록된 체결이 거래소 내역에 없음
+    MissingOnExchange,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Discrepancy {
+    pub kind: DiscrepancyKind,
+    pub order_id: String,
+    pub detail: String,
+}
+
+/// 대사(reconciliation) 결과
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconciliationReport {
+    pub local_orders: usize,
+    pub exchange_fills: usize,
+    pub matched_orders: usize,
+    /// 거래소 체결이 없는 로컬 주문 (미체결/취소 가능성 - 정보용)
+    pub unfilled_orders: Vec<String>,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl ReconciliationReport {
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = String::new();
+        summary.push_str(&format!("로컬 주문: {} / 거래소 체결: {} / 매칭 주문: {}\n", self.local_orders, self.exchange_fills, self.matched_orders));
+        summary.push_str(&format!("체결 없는 주문: {}\n", self.unfilled_orders.len()));
+        summary.push_str(&format!("불일치: {}\n", self.discrepancies.len()));
+        for d in &self.discrepancies {
+            summary.push_str(&format!("  [{:?}] {} - {}\n", d.kind, d.order_id, d.detail));
+        }
+        summary
+    }
+}
+
+/// 로컬 주문/체결 기록과 거래소 체결 내역 대사
+pub fn reconcile(snapshot: &OrderSnapshot, exchange_fills: &[FillRecord]) -> ReconciliationReport {
+    let mut report = ReconciliationReport {
+        local_orders: snapshot.orders.len(),
+        exchange_fills: exchange_fills.len(),
+        ..Default::default()
+    };
+
+    // 거래소 주문 ID 또는 클라이언트 주문 ID 로 로컬 주문 조회
+    let mut index: HashMap<&str, &Order> = HashMap::new();
+    for order in &snapshot.orders {
+        index.insert(order.id.0.as_str(), order);
+        if let Some(cid) = &order.client_order_id {
+            index.insert(cid.as_str(), order);
+        }
+    }
+
+    let mut by_order: HashMap<&str, Vec<&FillRecord>> = HashMap::new();
+    for fill in exchange_fills {
+        match index.get(fill.order_id.as_str()) {
+            Some(order) => by_order.entry(order.id.0.as_str()).or_default().push(fill),
+            None => report.discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::UnknownOrder,
+                order_id: fill.order_id.clone(),
+                detail: format!("{} {:?} {} @ {}", fill.symbol, fill.side, fill.quantity, fill.price),
+            }),
+        }
+    }
+
+    for order in &snapshot.orders {
+        let Some(fills) = by_order.get(order.id.0.as_str()) else {
+            report.unfilled_orders.push(order.id.0.clone());
+            continue;
+        };
+        report.matched_orders += 1;
+        let id = order.id.0.clone();
+
+        for fill in fills {
+            if fill.symbol != order.symbol {
+                report.discrepancies.push(Discrepancy {
+                    kind: DiscrepancyKind::SymbolMismatch,
+                    order_id: id.clone(),
+                    detail: format!("local {} / exchange {}", order.symbol, fill.symbol),
+                });
+            }
+            if fill.side != order.side {
+                report.discrepancies.push(Discrepancy {
+                    kind: DiscrepancyKind::SideMismatch,
+                    order_id: id.clone(),
+                    detail: format!("local {:?} / exchange {:?}", order.side, fill.side),
+                });
+            }
+            let worse = match order.side {
+                OrderSide::Buy => fill.price > order.price,
+                OrderSide::Sell => fill.price < order.price,
+            };
+            if order.order_type == OrderType::Limit && worse {
+                report.discrepancies.push(Discrepancy {
+                    kind: DiscrepancyKind::PriceViolation,
+                    order_id: id.clone(),
+                    detail: format!("limit {} / filled {}", order.price, fill.price),
+                });
+            }
+        }
+
+        let filled: Decimal = fills.iter().map(|f| f.quantity).sum();
+        if filled > order.quantity {
+            report.discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::Overfill,
+                order_id: id.clone(),
+                detail: format!("ordered {} / filled {}", order.quantity, filled),
+            });
+        }
+    }
+
+    // 로컬에 기록된 체결 중 거래소 내역에 없는 것
+    for local in &snapshot.fills {
+        let found = exchange_fills.iter().any(|f| {
+            f.order_id == local.order_id
+                && f.quantity == local.quantity
+                && f.price == local.price
+        });
+        if !found {
+            report.discrepancies.push(Discrepancy {
+                kind: DiscrepancyKind::MissingOnExchange,
+                order_id: local.order_id.clone(),
+                detail: format!("{} {:?} {} @ {}", local.symbol, local.side, local.quantity, local.price),
+            });
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::OrderId;
+
+    #[test]
+    fn test_reconcile_exchange_history() {
+        let csv_data = "Date(UTC),Order ID,Pair,Side,Price,Executed,Fee\n\
+                        2024-01-01 00:00:00,1001,BTC/USDT,BUY,100.5,0.6BTC,0.01\n\
+                        2024-01-01 00:00:01,1001,BTC/USDT,BUY,100.0,0.6BTC,0.01\n\
+                        2024-01-01 00:00:02,9999,ETHUSDT,SELL,50,1,0\n";
+        let fills = import_exchange_fills(csv_data.as_bytes(), FileFormat::Csv).unwrap();
+        assert_eq!(fills.len(), 3);
+        assert_eq!(fills[0].symbol, "BTCUSDT");
+        assert_eq!(fills[0].quantity, dec!(0.6));
+        assert_eq!(fills[0].timestamp, 1_704_067_200_000);
+
+        let mut order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100));
+        order.id = OrderId("1001".to_string());
+        let open = {
+            let mut o = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Limit, dec!(1), dec!(120));
+            o.id = OrderId("1002".to_string());
+            o
+        };
+        let snapshot = OrderSnapshot::new(vec![order, open], Vec::new());
+
+        let report = reconcile(&snapshot, &fills);
+        assert_eq!(report.matched_orders, 1);
+        assert_eq!(report.unfilled_orders, vec!["1002".to_string()]);
+        let kinds: Vec<DiscrepancyKind> = report.discrepancies.iter().map(|d| d.kind.clone()).collect();
+        assert!(kinds.contains(&DiscrepancyKind::UnknownOrder));
+        assert!(kinds.contains(&DiscrepancyKind::PriceViolation));
+        assert!(kinds.contains(&DiscrepancyKind::Overfill));
+
+        // CSV 내보내기 후 다시 읽기
+        let mut buf = Vec::new();
+        export_orders(&snapshot.orders, FileFormat::Csv, &mut buf).unwrap();
+        let restored = import_orders(buf.as_slice(), FileFormat::Csv).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored[0].id, OrderId("1001".to_string()));
+    }
+}
@@ -5,6 +5,7 @@ use async_trait::async_trait;
 
 use crate::error::TradingError;
 use crate::models::order::{Order, OrderId, OrderStatus};
+use crate::models::timestamp::Timestamp;
 
 /// 주문 저장소 인터페이스
 #[async_trait]
@@ -32,6 +33,21 @@ pub trait OrderRepository: Send + Sync {
 
     /// 주문 삭제
     async fn delete(&mut self, order_id: &OrderId) -> Result<(), TradingError>;
+
+    /// 생성 시각 구간 [from, to] 주문 찾기
+    async fn find_by_time_range(&self, from: Timestamp, to: Timestamp) -> Result<Vec<Order>, TradingError> {
+        let mut orders: Vec<Order> = self.find_all().await?
+            .into_iter()
+            .filter(|o| o.created_at >= from && o.created_at <= to)
+            .collect();
+        orders.sort_by_key(|o| o.created_at);
+        Ok(orders)
+    }
+
+    /// 주문 상태 기록 (상태를 보관하지 않는 저장소는 무시)
+    async fn update_status(&mut self, _order_id: &OrderId, _status: OrderStatus) -> Result<(), TradingError> {
+        Ok(())
+    }
 }
 
 /// 메모리 기반 주문 저장소 구현
@@ -70,7 +86,12 @@ impl OrderRepository for InMemoryOrderRepository {
         let id_key = order.id.0.clone();
 
         if !self.orders.contains_key(&id_key) {
-            return Err(TradingError::OrderNotFound(order.id.clone()));
+            // 제출 전 임시 저장된 주문은 클라이언트 ID 로 찾아 거래소 ID 로 다시 등록
+            let previous = order.client_order_id.as_ref()
+                .and_then(|c| self.client_id_index.get(c))
+                .cloned()
+                .ok_or_else(|| TradingError::OrderNotFound(order.id.clone()))?;
+            self.orders.remove(&previous);
         }
 
         // 주문 업데이트
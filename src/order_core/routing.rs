@@ -0,0 +1,348 @@
+//! 주문 라우팅 규칙 엔진
+//!
+//! 주문 제출 직전에 설정된 규칙을 순서대로 평가하여 주문을 재작성하거나 다른 실행 경로로 보낸다.
+//! 예: 명목가 5만 달러 초과 주문은 VWAP 으로, 00:00–01:00 UTC 의 BTC 주문은 post-only 로,
+//! 특정 전략은 reduce-only 로만 거래. 규칙 파일(JSON 배열)이 바뀌면 재시작 없이 다시 읽는다.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+use chrono::{TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::RoutingConfig;
+use crate::error::TradingError;
+use crate::models::decimal::Decimal;
+use crate::models::order::{Order, OrderSide, OrderType};
+
+/// 규칙 적용 조건 - 지정된 항목은 모두 만족해야 함 (비어 있으면 모든 주문)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleCondition {
+    /// 심볼 목록, 끝의 `*` 는 접두사 일치 (예: "BTC*")
+    #[serde(default)]
+    pub symbols: Vec<String>,
+    /// 주문을 낸 전략 이름
+    #[serde(default)]
+    pub strategies: Vec<String>,
+    #[serde(default)]
+    pub side: Option<OrderSide>,
+    #[serde(default)]
+    pub order_types: Vec<OrderType>,
+    /// 명목가(수량 x 가격) 하한/상한
+    #[serde(default)]
+    pub min_notional: Option<Decimal>,
+    #[serde(default)]
+    pub max_notional: Option<Decimal>,
+    /// UTC 시간대 "HH:MM-HH:MM" (자정을 넘는 구간 허용)
+    #[serde(default)]
+    pub utc_window: Option<String>,
+}
+
+/// 규칙이 일치했을 때의 조치
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RoutingAction {
+    /// VWAP 분할 실행으로 전환
+    Vwap {
+        duration_ms: i64,
+        #[serde(default)]
+        participation_rate: Option<f64>,
+    },
+    /// TWAP 분할 실행으로 전환
+    Twap {
+        duration_ms: i64,
+        #[serde(default = "default_twap_slices")]
+        slices: usize,
+    },
+    /// 메이커 전용 (시장가는 기준가 지정가로 전환, TIF=GTX)
+    PostOnly,
+    /// 포지션 축소 주문만 허용
+    ReduceOnly,
+    /// 주문 거부
+    Reject { reason: String },
+}
+
+fn default_twap_slices() -> usize { 10 }
+
+/// 라우팅 규칙 한 건
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutingRule {
+    pub name: String,
+    #[serde(default)]
+    pub when: RuleCondition,
+    pub then: RoutingAction,
+}
+
+impl RoutingRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(window) = &self.when.utc_window {
+            parse_utc_window(window)?;
+        }
+        match &self.then {
+            RoutingAction::Vwap { duration_ms, participation_rate } => {
+                if *duration_ms <= 0 {
+                    return Err("duration_ms must be positive".to_string());
+                }
+                if participation_rate.is_some_and(|r| r <= 0.0 || r > 1.0) {
+                    return Err("participation_rate must be in (0, 1]".to_string());
+                }
+            }
+            RoutingAction::Twap { duration_ms, slices } if *duration_ms <= 0 || *slices == 0 => {
+                return Err("duration_ms and slices must be positive".to_string());
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 조건 일치 여부 (명목가 조건은 가격을 알 수 없으면 불일치)
+    fn matches(&self, order: &Order, ctx: &RoutingContext) -> bool {
+        let when = &self.when;
+        if !when.symbols.is_empty() && !when.symbols.iter().any(|s| symbol_matches(s, &order.symbol)) {
+            return false;
+        }
+        if !when.strategies.is_empty() && !ctx.strategy.is_some_and(|s| when.strategies.iter().any(|w| w == s)) {
+            return false;
+        }
+        if when.side.as_ref().is_some_and(|side| *side != order.side) {
+            return false;
+        }
+        if !when.order_types.is_empty() && !when.order_types.contains(&order.order_type) {
+            return false;
+        }
+        if when.min_notional.is_some() || when.max_notional.is_some() {
+            let Some(notional) = notional(order, ctx) else { return false };
+            if when.min_notional.is_some_and(|min| notional < min) || when.max_notional.is_some_and(|max| notional > max) {
+                return false;
+            }
+        }
+        if let Some(window) = &when.utc_window {
+            let Ok((start, end)) = parse_utc_window(window) else { return false };
+            let minute = Utc.timestamp_millis_opt(ctx.now_ms).single()
+                .map(|t| t.hour() * 60 + t.minute())
+                .unwrap_or(0);
+            let inside = if start <= end { minute >= start && minute < end } else { minute >= start || minute < end };
+            if !inside {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn apply(&self, mut order: Order, ctx: &RoutingContext) -> Result<Order, TradingError> {
+        match &self.then {
+            RoutingAction::Vwap { duration_ms, participation_rate } => {
+                order = order.with_vwap_params(*duration_ms, *participation_rate);
+            }
+            RoutingAction::Twap { duration_ms, .. } => {
+                order = order.with_twap_params(*duration_ms);
+            }
+            RoutingAction::PostOnly => {
+                if order.order_type == OrderType::Market {
+                    let price = if order.price > Decimal::ZERO { Some(order.price) } else { ctx.reference_price };
+                    let Some(price) = price else {
+                        return Err(TradingError::InvalidParameter(format!(
+                            "routing rule {}: post-only needs a price for market order", self.name
+                        )));
+                    };
+                    order.order_type = OrderType::Limit;
+                    order.price = price;
+                }
+                if matches!(order.order_type, OrderType::Limit | OrderType::Iceberg) {
+                    order.time_in_force = "GTX".to_string();
+                }
+            }
+            RoutingAction::ReduceOnly => {
+                order.reduce_only = Some(true);
+            }
+            RoutingAction::Reject { reason } => {
+                return Err(TradingError::RiskLimitExceeded(format!("routing rule {}: {}", self.name, reason)));
+            }
+        }
+        Ok(order)
+    }
+}
+
+fn symbol_matches(pattern: &str, symbol: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => symbol.to_ascii_uppercase().starts_with(&prefix.to_ascii_uppercase()),
+        None => pattern.eq_ignore_ascii_case(symbol),
+    }
+}
+
+fn notional(order: &Order, ctx: &RoutingContext) -> Option<Decimal> {
+    let price = if order.price > Decimal::ZERO { Some(order.price) } else { ctx.reference_price };
+    price.map(|p| p * order.quantity)
+}
+
+/// "HH:MM-HH:MM" -> (시작 분, 종료 분)
+fn parse_utc_window(window: &str) -> Result<(u32, u32), String> {
+    let minutes = |hm: &str| -> Option<u32> {
+        let (h, m) = hm.trim().split_once(':')?;
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        (h <= 24 && m < 60 && h * 60 + m <= 1440).then_some(h * 60 + m)
+    };
+    window.split_once('-')
+        .and_then(|(start, end)| Some((minutes(start)?, minutes(end)?)))
+        .ok_or_else(|| format!("invalid utc_window {}", window))
+}
+
+/// 규칙 평가 문맥
+#[derive(Debug, Clone, Default)]
+pub struct RoutingContext<'a> {
+    pub strategy: Option<&'a str>,
+    pub now_ms: i64,
+    /// 가격 없는 시장가 주문의 명목가 계산용 현재가
+    pub reference_price: Option<Decimal>,
+}
+
+/// 라우팅 결과
+#[derive(Debug, Clone)]
+pub struct RoutedOrder {
+    pub order: Order,
+    /// 적용된 규칙 이름 (평가 순서)
+    pub applied: Vec<String>,
+    /// 분할 실행(VWAP/TWAP)으로 전환한 마지막 조치
+    pub algo: Option<RoutingAction>,
+}
+
+/// 규칙 엔진 핸들 - 복제해도 같은 규칙 집합을 공유하므로 재로드 태스크와 주문 관리자가 함께 사용
+#[derive(Clone, Default)]
+pub struct OrderRouter {
+    rules: Arc<RwLock<Vec<RoutingRule>>>,
+    path: Option<PathBuf>,
+    loaded_modified: Arc<Mutex<Option<SystemTime>>>,
+}
+
+impl OrderRouter {
+    pub fn new(rules: Vec<RoutingRule>) -> Result<Self, TradingError> {
+        let router = OrderRouter::default();
+        router.set_rules(rules)?;
+        Ok(router)
+    }
+
+    /// 설정의 인라인 규칙 + 규칙 파일 (파일이 있으면 파일이 우선)
+    pub fn from_config(config: &RoutingConfig) -> Result<Self, TradingError> {
+        let mut router = Self::new(config.rules.clone())?;
+        if let Some(path) = &config.rules_path {
+            router.path = Some(PathBuf::from(path));
+            router.reload_if_changed()?;
+        }
+        Ok(router)
+    }
+
+    /// 규칙 교체 (검증 실패 시 기존 규칙 유지)
+    pub fn set_rules(&self, rules: Vec<RoutingRule>) -> Result<(), TradingError> {
+        for rule in &rules {
+            rule.validate().map_err(|e| TradingError::ConfigError(format!("routing rule {}: {}", rule.name, e)))?;
+        }
+        *self.rules.write().unwrap_or_else(|e| e.into_inner()) = rules;
+        Ok(())
+    }
+
+    pub fn rules(&self) -> Vec<RoutingRule> {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// 명목가 조건이 있어 시장가 주문의 현재가가 필요한지
+    pub fn needs_reference_price(&self) -> bool {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).iter()
+            .any(|r| r.when.min_notional.is_some() || r.when.max_notional.is_some() || r.then == RoutingAction::PostOnly)
+    }
+
+    /// 규칙 파일이 수정되었으면 다시 읽음 - 재로드했으면 true
+    pub fn reload_if_changed(&self) -> Result<bool, TradingError> {
+        let Some(path) = &self.path else { return Ok(false) };
+        let modified = std::fs::metadata(path)?.modified()?;
+        let mut loaded = self.loaded_modified.lock().unwrap_or_else(|e| e.into_inner());
+        if *loaded == Some(modified) {
+            return Ok(false);
+        }
+        let rules: Vec<RoutingRule> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let count = rules.len();
+        // 잘못된 파일은 다음 수정 전까지 다시 시도하지 않음
+        *loaded = Some(modified);
+        self.set_rules(rules)?;
+        log::info!("routing rules loaded from {} ({} rules)", path.display(), count);
+        Ok(true)
+    }
+
+    /// 규칙을 순서대로 평가하여 일치하는 모든 조치를 적용
+    pub fn route(&self, order: Order, ctx: &RoutingContext) -> Result<RoutedOrder, TradingError> {
+        let rules = self.rules.read().unwrap_or_else(|e| e.into_inner()).clone();
+        let mut routed = RoutedOrder { order, applied: Vec::new(), algo: None };
+        for rule in &rules {
+            if !rule.matches(&routed.order, ctx) {
+                continue;
+            }
+            log::info!(
+                "routing rule {} applied to {:?} {} {} ({:?}, strategy {})",
+                rule.name, routed.order.side, routed.order.quantity, routed.order.symbol, rule.then, ctx.strategy.unwrap_or("-")
+            );
+            routed.order = rule.apply(routed.order, ctx)?;
+            routed.applied.push(rule.name.clone());
+            if matches!(rule.then, RoutingAction::Vwap { .. } | RoutingAction::Twap { .. }) {
+                routed.algo = Some(rule.then.clone());
+            }
+        }
+        Ok(routed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+
+    #[test]
+    fn test_routing_rules() {
+        let rules: Vec<RoutingRule> = serde_json::from_str(r#"[
+            {"name": "large-to-vwap", "when": {"min_notional": 50000}, "then": {"action": "vwap", "duration_ms": 600000}},
+            {"name": "btc-night-post-only", "when": {"symbols": ["BTC*"], "utc_window": "00:00-01:00"}, "then": {"action": "post_only"}},
+            {"name": "hedge-reduce-only", "when": {"strategies": ["hedge"]}, "then": {"action": "reduce_only"}},
+            {"name": "no-doge", "when": {"symbols": ["DOGEUSDT"]}, "then": {"action": "reject", "reason": "blocked"}}
+        ]"#).unwrap();
+        let router = OrderRouter::new(rules).unwrap();
+        assert!(router.needs_reference_price());
+
+        // 00:30 UTC, 가격 없는 시장가 1 BTC @ 60k 기준가 -> VWAP (이후 규칙도 계속 평가, post-only 는 지정가에만 영향)
+        let ctx = RoutingContext { strategy: None, now_ms: 30 * 60_000, reference_price: Some(dec!(60_000)) };
+        let order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Market, dec!(1), dec!(0));
+        let routed = router.route(order, &ctx).unwrap();
+        assert_eq!(routed.applied, vec!["large-to-vwap", "btc-night-post-only"]);
+        assert_eq!(routed.order.order_type, OrderType::VWAP);
+        assert_eq!(routed.order.execution_interval, Some(600_000));
+        assert!(matches!(routed.algo, Some(RoutingAction::Vwap { duration_ms: 600_000, .. })));
+
+        // 소액 시장가는 기준가 지정가 post-only 로
+        let small = Order::new("BTCUSDT", OrderSide::Sell, OrderType::Market, dec!(0.1), dec!(0));
+        let routed = router.route(small.clone(), &ctx).unwrap();
+        assert_eq!(routed.applied, vec!["btc-night-post-only"]);
+        assert_eq!((routed.order.order_type.clone(), routed.order.price, routed.order.time_in_force.as_str()), (OrderType::Limit, dec!(60_000), "GTX"));
+
+        // 시간대 밖 + 전략 조건
+        let later = RoutingContext { strategy: Some("hedge"), now_ms: 2 * 3_600_000, reference_price: Some(dec!(60_000)) };
+        let routed = router.route(small, &later).unwrap();
+        assert_eq!(routed.applied, vec!["hedge-reduce-only"]);
+        assert_eq!(routed.order.reduce_only, Some(true));
+
+        let doge = Order::new("DOGEUSDT", OrderSide::Buy, OrderType::Limit, dec!(10), dec!(0.1));
+        assert!(matches!(router.route(doge, &later), Err(TradingError::RiskLimitExceeded(_))));
+
+        // 자정을 넘는 구간, 잘못된 규칙은 기존 규칙 유지
+        assert_eq!(parse_utc_window("23:00-01:00").unwrap(), (1380, 60));
+        let bad = RoutingRule { name: "bad".into(), when: RuleCondition::default(), then: RoutingAction::Twap { duration_ms: 0, slices: 5 } };
+        assert!(router.set_rules(vec![bad]).is_err());
+        assert_eq!(router.rules().len(), 4);
+
+        // 규칙 파일 재로드
+        let path = std::env::temp_dir().join(format!("xquant_routing_{}.json", std::process::id()));
+        std::fs::write(&path, r#"[{"name": "all-reduce-only", "then": {"action": "reduce_only"}}]"#).unwrap();
+        let config = RoutingConfig { rules: Vec::new(), rules_path: Some(path.to_string_lossy().into_owned()), reload_interval_ms: 1_000 };
+        let router = OrderRouter::from_config(&config).unwrap();
+        assert_eq!(router.rules()[0].name, "all-reduce-only");
+        assert!(!router.reload_if_changed().unwrap());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,240 @@
+//! SQL 주문 저장소 (SQLite / PostgreSQL)
+//!
+//! sqlx `Any` 드라이버로 연결 URL(`sqlite://...`, `postgres://...`)에 따라 백엔드를 고른다.
+//! 연결 시 `migrations/` 의 스키마 마이그레이션을 적용하며, 주문 본문은 JSON 으로 저장하고
+//! 상태/심볼/생성 시각/클라이언트 ID 는 인덱스 컬럼으로 두어 DB 에서 바로 조회한다.
+
+use async_trait::async_trait;
+use sqlx::any::{AnyPoolOptions, AnyRow};
+use sqlx::{AnyPool, Row};
+
+use crate::error::TradingError;
+use crate::models::order::{Order, OrderId, OrderStatus};
+use crate::models::timestamp::Timestamp;
+use crate::order_core::repository::OrderRepository;
+
+/// sqlx 기반 주문 저장소
+pub struct SqlOrderRepository {
+    pool: AnyPool,
+}
+
+impl SqlOrderRepository {
+    /// URL 로 연결하고 마이그레이션 적용
+    pub async fn connect(url: &str) -> Result<Self, TradingError> {
+        let pool = connect_pool(url).await?;
+        Ok(SqlOrderRepository { pool })
+    }
+
+    /// 마지막으로 기록된 주문 상태
+    pub async fn status_of(&self, order_id: &OrderId) -> Result<Option<OrderStatus>, TradingError> {
+        let row = sqlx::query("SELECT status FROM orders WHERE id = $1")
+            .bind(order_id.0.clone())
+            .fetch_optional(&self.pool)
+            .await?;
+        row.map(|r| parse_status(&r.try_get::<String, _>("status")?)).transpose()
+    }
+}
+
+/// 연결 풀 생성 후 스키마 마이그레이션 적용 (SQL 저장소 공용)
+pub(crate) async fn connect_pool(url: &str) -> Result<AnyPool, TradingError> {
+    sqlx::any::install_default_drivers();
+    // 메모리 SQLite 는 연결마다 별도 DB 이므로 단일 연결로 제한
+    let max_connections = if url.contains(":memory:") { 1 } else { 5 };
+    let pool = AnyPoolOptions::new().max_connections(max_connections).connect(url).await?;
+
+    let mut migrator = sqlx::migrate!();
+    // 같은 DB 를 공유하는 다른 저장소가 더 새로운 마이그레이션을 적용했을 수 있음
+    migrator.set_ignore_missing(true);
+    migrator.run(&pool).await.map_err(|e| TradingError::DatabaseError(e.to_string()))?;
+    Ok(pool)
+}
+
+fn status_name(status: &OrderStatus) -> String {
+    format!("{:?}", status)
+}
+
+fn parse_status(name: &str) -> Result<OrderStatus, TradingError> {
+    Ok(serde_json::from_value(serde_json::Value::String(name.to_string()))?)
+}
+
+fn order_from_row(row: &AnyRow) -> Result<Order, TradingError> {
+    Ok(serde_json::from_str(&row.try_get::<String, _>("body")?)?)
+}
+
+#[async_trait]
+impl OrderRepository for SqlOrderRepository {
+    /// 이미 있는 주문이면 본문만 갱신하고 기록된 상태는 유지
+    async fn save(&mut self, order: &Order) -> Result<(), TradingError> {
+        sqlx::query(
+            "INSERT INTO orders (id, client_order_id, symbol, status, status_updated_at, created_at, body) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             ON CONFLICT (id) DO UPDATE SET client_order_id = excluded.client_order_id, \
+             symbol = excluded.symbol, created_at = excluded.created_at, body = excluded.body",
+        )
+        .bind(order.id.0.clone())
+        .bind(order.client_order_id.clone())
+        .bind(order.symbol.clone())
+        .bind(status_name(&OrderStatus::New))
+        .bind(order.created_at.as_millis())
+        .bind(order.created_at.as_millis())
+        .bind(serde_json::to_string(order)?)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 거래소 주문 ID 가 새로 부여된 경우 클라이언트 ID 로 기존 주문을 찾아 키를 교체
+    async fn update(&mut self, order: &Order) -> Result<(), TradingError> {
+        let body = serde_json::to_string(order)?;
+        let updated = sqlx::query(
+            "UPDATE orders SET client_order_id = $1, symbol = $2, created_at = $3, body = $4 WHERE id = $5",
+        )
+        .bind(order.client_order_id.clone())
+        .bind(order.symbol.clone())
+        .bind(order.created_at.as_millis())
+        .bind(body.clone())
+        .bind(order.id.0.clone())
+        .execute(&self.pool)
+        .await?;
+        if updated.rows_affected() > 0 {
+            return Ok(());
+        }
+
+        let client_id = order.client_order_id.clone()
+            .ok_or_else(|| TradingError::OrderNotFound(order.id.clone()))?;
+        let rekeyed = sqlx::query(
+            "UPDATE orders SET id = $1, symbol = $2, created_at = $3, body = $4 WHERE client_order_id = $5",
+        )
+        .bind(order.id.0.clone())
+        .bind(order.symbol.clone())
+        .bind(order.created_at.as_millis())
+        .bind(body)
+        .bind(client_id)
+        .execute(&self.pool)
+        .await?;
+        if rekeyed.rows_affected() == 0 {
+            return Err(TradingError::OrderNotFound(order.id.clone()));
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, order_id: &OrderId) -> Result<Option<Order>, TradingError> {
+        let row = sqlx::query("SELECT body FROM orders WHERE id = $1")
+            .bind(order_id.0.clone())
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(order_from_row).transpose()
+    }
+
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Order>, TradingError> {
+        let row = sqlx::query("SELECT body FROM orders WHERE client_order_id = $1")
+            .bind(client_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(order_from_row).transpose()
+    }
+
+    async fn find_by_status(&self, statuses: &[OrderStatus]) -> Result<Vec<Order>, TradingError> {
+        if statuses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let placeholders: Vec<String> = (1..=statuses.len()).map(|i| format!("${}", i)).collect();
+        let sql = format!("SELECT body FROM orders WHERE status IN ({})", placeholders.join(", "));
+        let mut query = sqlx::query(&sql);
+        for status in statuses {
+            query = query.bind(status_name(status));
+        }
+        let rows = query.fetch_all(&self.pool).await?;
+        rows.iter().map(order_from_row).collect()
+    }
+
+    async fn find_by_symbol(&self, symbol: &str) -> Result<Vec<Order>, TradingError> {
+        let rows = sqlx::query("SELECT body FROM orders WHERE symbol = $1")
+            .bind(symbol.to_string())
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(order_from_row).collect()
+    }
+
+    async fn find_by_time_range(&self, from: Timestamp, to: Timestamp) -> Result<Vec<Order>, TradingError> {
+        let rows = sqlx::query("SELECT body FROM orders WHERE created_at >= $1 AND created_at <= $2 ORDER BY created_at")
+            .bind(from.as_millis())
+            .bind(to.as_millis())
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter().map(order_from_row).collect()
+    }
+
+    async fn find_all(&self) -> Result<Vec<Order>, TradingError> {
+        let rows = sqlx::query("SELECT body FROM orders").fetch_all(&self.pool).await?;
+        rows.iter().map(order_from_row).collect()
+    }
+
+    async fn delete(&mut self, order_id: &OrderId) -> Result<(), TradingError> {
+        sqlx::query("DELETE FROM orders WHERE id = $1")
+            .bind(order_id.0.clone())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_status(&mut self, order_id: &OrderId, status: OrderStatus) -> Result<(), TradingError> {
+        let result = sqlx::query("UPDATE orders SET status = $1, status_updated_at = $2 WHERE id = $3")
+            .bind(status_name(&status))
+            .bind(chrono::Utc::now().timestamp_millis())
+            .bind(order_id.0.clone())
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(TradingError::OrderNotFound(order_id.clone()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+
+    #[tokio::test]
+    async fn test_sqlite_repository_persists_and_queries() {
+        let path = std::env::temp_dir().join(format!("xquant_orders_{}.db", uuid::Uuid::new_v4()));
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+
+        {
+            let mut repo = SqlOrderRepository::connect(&url).await.unwrap();
+            let mut order = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100)).with_client_order_id("c1");
+            order.created_at = 1_000.into();
+            repo.save(&order).await.unwrap();
+            // 거래소 ID 부여 후 키 교체
+            order.id = OrderId("ex-1".to_string());
+            repo.update(&order).await.unwrap();
+            repo.update_status(&order.id, OrderStatus::Filled).await.unwrap();
+            // 재저장해도 기록된 상태 유지
+            repo.save(&order).await.unwrap();
+
+            let mut other = Order::new("ETHUSDT", OrderSide::Sell, OrderType::Market, dec!(2), dec!(0));
+            other.id = OrderId("ex-2".to_string());
+            other.created_at = 5_000.into();
+            repo.save(&other).await.unwrap();
+            assert!(matches!(repo.update_status(&OrderId("missing".to_string()), OrderStatus::Filled).await, Err(TradingError::OrderNotFound(_))));
+        }
+
+        // 재연결 후 조회 (마이그레이션 재적용은 무시됨)
+        let mut repo = SqlOrderRepository::connect(&url).await.unwrap();
+        assert_eq!(repo.find_all().await.unwrap().len(), 2);
+        assert_eq!(repo.find_by_client_id("c1").await.unwrap().unwrap().id.0, "ex-1");
+        assert_eq!(repo.status_of(&OrderId("ex-1".to_string())).await.unwrap(), Some(OrderStatus::Filled));
+        assert_eq!(repo.find_by_status(&[OrderStatus::New]).await.unwrap()[0].symbol, "ETHUSDT");
+        assert_eq!(repo.find_by_status(&[OrderStatus::New, OrderStatus::Filled]).await.unwrap().len(), 2);
+        assert_eq!(repo.find_by_time_range(0.into(), 2_000.into()).await.unwrap().len(), 1);
+        assert_eq!(repo.find_by_symbol("BTCUSDT").await.unwrap().len(), 1);
+
+        repo.delete(&OrderId("ex-2".to_string())).await.unwrap();
+        assert_eq!(repo.find_all().await.unwrap().len(), 1);
+        drop(repo);
+        std::fs::remove_file(&path).ok();
+    }
+}
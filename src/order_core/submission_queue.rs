@@ -0,0 +1,376 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tokio::sync::{oneshot, Notify};
+
+use crate::config::SubmissionQueueConfig;
+use crate::error::TradingError;
+use crate::models::order::{Order, OrderId};
+
+/// 주문 제출 우선순위 (앞쪽이 먼저 처리)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionPriority {
+    /// 취소 / reduce-only 등 위험을 줄이는 요청
+    RiskReducing,
+    /// 분할 실행 자식 주문 등 시간에 민감한 요청
+    Execution,
+    /// 신규 진입
+    Entry,
+}
+
+impl SubmissionPriority {
+    pub const ALL: [SubmissionPriority; 3] = [SubmissionPriority::RiskReducing, SubmissionPriority::Execution, SubmissionPriority::Entry];
+
+    /// 주문 속성으로 우선순위 분류
+    pub fn classify(order: &Order) -> Self {
+        if order.reduce_only == Some(true) {
+            SubmissionPriority::RiskReducing
+        } else if order.execution_interval.is_some()
+            || order.iceberg_qty.is_some()
+            || matches!(order.time_in_force.as_str(), "IOC" | "FOK")
+        {
+            SubmissionPriority::Execution
+        } else {
+            SubmissionPriority::Entry
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SubmissionPriority::RiskReducing => "risk_reducing",
+            SubmissionPriority::Execution => "execution",
+            SubmissionPriority::Entry => "entry",
+        }
+    }
+}
+
+/// 큐에 들어간 요청 - 처리 결과는 reply 로 호출자에게 전달
+pub enum SubmissionKind {
+    Submit { strategy: Option<String>, order: Box<Order>, reply: oneshot::Sender<Result<OrderId, TradingError>> },
+    Cancel { order_id: OrderId, reply: oneshot::Sender<Result<(), TradingError>> },
+}
+
+pub struct QueuedSubmission {
+    pub priority: SubmissionPriority,
+    pub kind: SubmissionKind,
+    enqueued_at: Instant,
+}
+
+impl QueuedSubmission {
+    /// 큐에 들어간 시각 (처리 완료 시 `SubmissionQueue::record_completion` 에 전달)
+    pub fn enqueued_at(&self) -> Instant {
+        self.enqueued_at
+    }
+}
+
+/// 큐에 넣은 주문 제출 요청 - 넣는 순서는 즉시 확정되고 결과는 나중에 기다림
+pub struct PendingSubmission {
+    rx: oneshot::Receiver<Result<OrderId, TradingError>>,
+}
+
+impl PendingSubmission {
+    pub async fn wait(self) -> Result<OrderId, TradingError> {
+        self.rx.await.map_err(|_| TradingError::ExecutionError("submission queue dropped the request".to_string()))?
+    }
+}
+
+/// 우선순위별 처리 지표
+#[derive(Debug, Clone, Serialize)]
+pub struct PriorityMetrics {
+    pub priority: SubmissionPriority,
+    pub enqueued: u64,
+    pub dispatched: u64,
+    /// 대기 시간 초과로 상위 우선순위보다 먼저 처리된 건수
+    pub promoted: u64,
+    pub pending: usize,
+    /// 큐가 가득 차 즉시 거부된 건수
+    pub rejected: u64,
+    pub avg_wait_ms: f64,
+    pub max_wait_ms: u64,
+    /// 큐에 들어간 시점부터 거래소 응답까지
+    pub completed: u64,
+    pub avg_latency_ms: f64,
+    pub max_latency_ms: u64,
+}
+
+impl PriorityMetrics {
+    fn new(priority: SubmissionPriority) -> Self {
+        PriorityMetrics {
+            priority,
+            enqueued: 0,
+            dispatched: 0,
+            promoted: 0,
+            pending: 0,
+            rejected: 0,
+            avg_wait_ms: 0.0,
+            max_wait_ms: 0,
+            completed: 0,
+            avg_latency_ms: 0.0,
+            max_latency_ms: 0,
+        }
+    }
+}
+
+struct QueueState {
+    lanes: [VecDeque<QueuedSubmission>; 3],
+    metrics: [PriorityMetrics; 3],
+    /// 대기 건수 최고치
+    max_depth: usize,
+}
+
+/// 우선순위 주문 제출 큐 (여러 전략이 동시에 주문을 낼 때 처리 순서 결정)
+///
+/// 위험 감소 -> 실행 자식 -> 신규 진입 순으로 꺼내되, `starvation_ms` 이상 기다린
+/// 요청은 우선순위와 무관하게 가장 오래된 것부터 처리. 대기 건수가 `max_pending` 에 이르면
+/// 위험 감소 요청 외에는 바로 `QueueFull` 로 거부해 신호 폭주가 지연으로 쌓이지 않게 함
+#[derive(Clone)]
+pub struct SubmissionQueue {
+    state: Arc<Mutex<QueueState>>,
+    notify: Arc<Notify>,
+    config: SubmissionQueueConfig,
+}
+
+impl SubmissionQueue {
+    pub fn new(config: SubmissionQueueConfig) -> Self {
+        SubmissionQueue {
+            state: Arc::new(Mutex::new(QueueState {
+                lanes: Default::default(),
+                metrics: SubmissionPriority::ALL.map(PriorityMetrics::new),
+                max_depth: 0,
+            })),
+            notify: Arc::new(Notify::new()),
+            config,
+        }
+    }
+
+    /// 주문 제출 요청 - 처리 순서가 올 때까지 대기 후 결과 반환
+    pub async fn submit(&self, strategy: Option<String>, order: Order) -> Result<OrderId, TradingError> {
+        let priority = SubmissionPriority::classify(&order);
+        self.submit_with_priority(priority, strategy, order).await
+    }
+
+    pub async fn submit_with_priority(&self, priority: SubmissionPriority, strategy: Option<String>, order: Order) -> Result<OrderId, TradingError> {
+        self.enqueue_submit(priority, strategy, order)?.wait().await
+    }
+
+    /// 주문 제출 요청을 큐에 넣기만 함 (결과를 기다리지 않는 호출자용, 큐가 가득 차면 즉시 오류)
+    pub fn enqueue_submit(&self, priority: SubmissionPriority, strategy: Option<String>, order: Order) -> Result<PendingSubmission, TradingError> {
+        let (reply, rx) = oneshot::channel();
+        self.enqueue(priority, SubmissionKind::Submit { strategy, order: Box::new(order), reply })?;
+        Ok(PendingSubmission { rx })
+    }
+
+    /// 주문 취소 요청 (항상 최우선)
+    pub async fn cancel(&self, order_id: OrderId) -> Result<(), TradingError> {
+        let (reply, rx) = oneshot::channel();
+        self.enqueue(SubmissionPriority::RiskReducing, SubmissionKind::Cancel { order_id, reply })?;
+        rx.await.map_err(|_| TradingError::ExecutionError("submission queue dropped the request".to_string()))?
+    }
+
+    pub fn enqueue(&self, priority: SubmissionPriority, kind: SubmissionKind) -> Result<(), TradingError> {
+        {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let depth: usize = state.lanes.iter().map(|lane| lane.len()).sum();
+            if self.config.max_pending > 0 && depth >= self.config.max_pending && priority != SubmissionPriority::RiskReducing {
+                state.metrics[priority.index()].rejected += 1;
+                return Err(TradingError::QueueFull(format!("{} requests pending", depth)));
+            }
+            state.lanes[priority.index()].push_back(QueuedSubmission { priority, kind, enqueued_at: Instant::now() });
+            state.metrics[priority.index()].enqueued += 1;
+            state.max_depth = state.max_depth.max(depth + 1);
+        }
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// 처리 완료 기록 - 큐 진입부터 거래소 응답까지의 지연
+    pub fn record_completion(&self, priority: SubmissionPriority, enqueued_at: Instant) {
+        let latency_ms = enqueued_at.elapsed().as_millis() as u64;
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let metrics = &mut state.metrics[priority.index()];
+        metrics.avg_latency_ms = (metrics.avg_latency_ms * metrics.completed as f64 + latency_ms as f64) / (metrics.completed + 1) as f64;
+        metrics.completed += 1;
+        metrics.max_latency_ms = metrics.max_latency_ms.max(latency_ms);
+    }
+
+    /// 다음 처리 대상 (없으면 None)
+    pub fn pop(&self) -> Option<QueuedSubmission> {
+        self.pop_at(Instant::now())
+    }
+
+    fn pop_at(&self, now: Instant) -> Option<QueuedSubmission> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let highest = state.lanes.iter().position(|lane| !lane.is_empty())?;
+        let starvation = Duration::from_millis(self.config.starvation_ms);
+        let lane = state.lanes.iter()
+            .enumerate()
+            .filter_map(|(i, lane)| lane.front().map(|item| (i, item.enqueued_at)))
+            .filter(|(_, at)| now.saturating_duration_since(*at) >= starvation)
+            .min_by_key(|(_, at)| *at)
+            .map(|(i, _)| i)
+            .unwrap_or(highest);
+
+        let item = state.lanes[lane].pop_front()?;
+        let wait_ms = now.saturating_duration_since(item.enqueued_at).as_millis() as u64;
+        let metrics = &mut state.metrics[lane];
+        metrics.avg_wait_ms = (metrics.avg_wait_ms * metrics.dispatched as f64 + wait_ms as f64) / (metrics.dispatched + 1) as f64;
+        metrics.dispatched += 1;
+        metrics.max_wait_ms = metrics.max_wait_ms.max(wait_ms);
+        if lane != highest {
+            metrics.promoted += 1;
+        }
+        Some(item)
+    }
+
+    /// 요청이 들어올 때까지 대기 후 꺼냄
+    pub async fn next(&self) -> QueuedSubmission {
+        loop {
+            if let Some(item) = self.pop() {
+                return item;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// 처리 간 최소 간격 (처리량 상한이 없으면 None)
+    pub fn min_interval(&self) -> Option<Duration> {
+        (self.config.max_per_second > 0.0).then(|| Duration::from_secs_f64(1.0 / self.config.max_per_second))
+    }
+
+    pub fn pending(&self) -> usize {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.lanes.iter().map(|lane| lane.len()).sum()
+    }
+
+    /// 대기 건수 상한 (0 = 무제한)
+    pub fn capacity(&self) -> usize {
+        self.config.max_pending
+    }
+
+    /// 지금까지의 대기 건수 최고치
+    pub fn max_depth(&self) -> usize {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).max_depth
+    }
+
+    pub fn metrics(&self) -> Vec<PriorityMetrics> {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.metrics.iter()
+            .zip(state.lanes.iter())
+            .map(|(m, lane)| PriorityMetrics { pending: lane.len(), ..m.clone() })
+            .collect()
+    }
+
+    /// Prometheus 텍스트 형식 큐 지표 (/metrics)
+    pub fn render_prometheus(&self) -> String {
+        let metrics = self.metrics();
+        let mut out = String::new();
+        out.push_str("# TYPE xquant_submission_queue_depth gauge\n");
+        for m in &metrics {
+            out.push_str(&format!("xquant_submission_queue_depth{{priority=\"{}\"}} {}\n", m.priority.name(), m.pending));
+        }
+        out.push_str(&format!("xquant_submission_queue_max_depth {}\n", self.max_depth()));
+        out.push_str("# TYPE xquant_submission_queue_rejected_total counter\n");
+        for m in &metrics {
+            out.push_str(&format!("xquant_submission_queue_rejected_total{{priority=\"{}\"}} {}\n", m.priority.name(), m.rejected));
+        }
+        out.push_str("# TYPE xquant_submission_queue_latency_ms gauge\n");
+        for m in &metrics {
+            out.push_str(&format!("xquant_submission_queue_latency_ms{{priority=\"{}\",stat=\"avg\"}} {}\n", m.priority.name(), m.avg_latency_ms));
+            out.push_str(&format!("xquant_submission_queue_latency_ms{{priority=\"{}\",stat=\"max\"}} {}\n", m.priority.name(), m.max_latency_ms));
+        }
+        out
+    }
+}
+
+impl Default for SubmissionQueue {
+    fn default() -> Self {
+        SubmissionQueue::new(SubmissionQueueConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::dec;
+    use crate::models::order::{OrderSide, OrderType};
+
+    fn push(queue: &SubmissionQueue, order: Order) {
+        let priority = SubmissionPriority::classify(&order);
+        queue.enqueue(priority, SubmissionKind::Submit { strategy: None, order: Box::new(order), reply: oneshot::channel().0 }).unwrap();
+    }
+
+    #[test]
+    fn test_priority_order_and_starvation() {
+        let queue = SubmissionQueue::new(SubmissionQueueConfig { max_per_second: 5.0, starvation_ms: 1_000, max_pending: 0 });
+        let entry = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100));
+        push(&queue, entry.clone());
+        push(&queue, entry.clone().with_vwap_params(60_000, None));
+        push(&queue, entry.clone().with_reduce_only(true));
+        queue.enqueue(SubmissionPriority::RiskReducing, SubmissionKind::Cancel { order_id: OrderId("1".into()), reply: oneshot::channel().0 }).unwrap();
+
+        let now = Instant::now();
+        let order: Vec<_> = std::iter::from_fn(|| queue.pop_at(now)).map(|item| item.priority).collect();
+        assert_eq!(order, vec![
+            SubmissionPriority::RiskReducing,
+            SubmissionPriority::RiskReducing,
+            SubmissionPriority::Execution,
+            SubmissionPriority::Entry,
+        ]);
+
+        // 오래 기다린 신규 진입은 나중에 들어온 위험 감소 요청보다 먼저
+        push(&queue, entry.clone());
+        std::thread::sleep(Duration::from_millis(5));
+        push(&queue, entry.clone().with_reduce_only(true));
+        let later = Instant::now() + Duration::from_millis(1_000);
+        assert_eq!(queue.pop_at(later).unwrap().priority, SubmissionPriority::Entry);
+        assert_eq!(queue.pop_at(later).unwrap().priority, SubmissionPriority::RiskReducing);
+
+        let metrics = queue.metrics();
+        assert_eq!((metrics[2].dispatched, metrics[2].promoted, metrics[2].pending), (2, 1, 0));
+        assert_eq!(metrics[0].dispatched, 3);
+        assert_eq!(queue.min_interval(), Some(Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_backpressure_and_latency() {
+        let queue = SubmissionQueue::new(SubmissionQueueConfig { max_per_second: 0.0, starvation_ms: 5_000, max_pending: 2 });
+        let entry = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100));
+        let _first = queue.enqueue_submit(SubmissionPriority::Entry, None, entry.clone()).unwrap();
+        let _second = queue.enqueue_submit(SubmissionPriority::Execution, None, entry.clone()).unwrap();
+        // 가득 차면 신규 진입은 거부, 위험 감소 요청은 받음
+        assert!(matches!(queue.enqueue_submit(SubmissionPriority::Entry, None, entry.clone()), Err(TradingError::QueueFull(_))));
+        assert!(queue.enqueue_submit(SubmissionPriority::RiskReducing, None, entry.with_reduce_only(true)).is_ok());
+        assert_eq!((queue.pending(), queue.max_depth(), queue.capacity()), (3, 3, 2));
+
+        let item = queue.pop().unwrap();
+        assert_eq!(item.priority, SubmissionPriority::RiskReducing);
+        queue.record_completion(item.priority, item.enqueued_at() - Duration::from_millis(40));
+        let metrics = queue.metrics();
+        assert_eq!((metrics[2].rejected, metrics[2].pending), (1, 1));
+        assert_eq!(metrics[0].completed, 1);
+        assert!(metrics[0].max_latency_ms >= 40 && metrics[0].avg_latency_ms >= 40.0);
+        assert!(queue.render_prometheus().contains("xquant_submission_queue_rejected_total{priority=\"entry\"} 1"));
+    }
+
+    #[test]
+    fn test_burst_rejects_only_excess_orders() {
+        let queue = SubmissionQueue::new(SubmissionQueueConfig { max_per_second: 0.0, starvation_ms: 5_000, max_pending: 3 });
+        let entry = Order::new("BTCUSDT", OrderSide::Buy, OrderType::Limit, dec!(1), dec!(100));
+        let exit = entry.clone().with_reduce_only(true);
+        // 진입 2건 + 청산 1건으로 가득 찬 뒤의 진입만 거부, 청산은 한도를 넘어도 받음
+        let burst = [&entry, &entry, &exit, &entry, &entry, &exit, &entry];
+        let accepted: Vec<bool> = burst.iter()
+            .map(|order| queue.enqueue_submit(SubmissionPriority::classify(order), None, (*order).clone()).is_ok())
+            .collect();
+        assert_eq!(accepted, vec![true, true, true, false, false, true, false]);
+        let metrics = queue.metrics();
+        assert_eq!((metrics[2].rejected, metrics[0].rejected, queue.pending()), (3, 0, 4));
+    }
+}
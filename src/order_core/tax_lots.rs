@@ -0,0 +1,320 @@
+//! 세무용 취득 단위(tax lot) 추적
+//!
+//! 체결 기록(`FillRecord`)을 시간순으로 재생하여 매수는 취득 단위로 쌓고, 매도는 선택한 방식
+//! (FIFO/LIFO/HIFO)에 따라 취득 단위를 소진하며 실현 손익을 기록한다. 수수료는 매수 시 취득가에
+//! 더하고 매도 시 처분가에서 뺀다. 결과는 건별 실현 내역과 자산/연도별 요약으로 CSV 내보내기 가능.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::to_f64;
+use crate::models::order::OrderSide;
+use crate::order_core::reconcile::FillRecord;
+
+const QTY_EPSILON: f64 = 1e-9;
+const MS_PER_DAY: i64 = 86_400_000;
+/// 장기 보유 기준 (일)
+const LONG_TERM_DAYS: i64 = 365;
+/// 심볼에서 기초 자산을 분리할 때 인식하는 결제 통화
+const QUOTE_ASSETS: [&str; 8] = ["USDT", "USDC", "BUSD", "FDUSD", "USD", "EUR", "BTC", "ETH"];
+
+/// 매도 시 취득 단위 소진 순서
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LotMethod {
+    /// 먼저 산 것부터
+    #[default]
+    Fifo,
+    /// 나중에 산 것부터
+    Lifo,
+    /// 취득 단가가 높은 것부터 (실현 이익 최소화)
+    Hifo,
+}
+
+impl LotMethod {
+    pub fn parse(s: &str) -> Result<Self, TradingError> {
+        match s.to_ascii_lowercase().as_str() {
+            "fifo" => Ok(LotMethod::Fifo),
+            "lifo" => Ok(LotMethod::Lifo),
+            "hifo" => Ok(LotMethod::Hifo),
+            other => Err(TradingError::InvalidParameter(format!("Unsupported lot method: {}", other))),
+        }
+    }
+}
+
+/// "BTCUSDT", "BTC/USDT", "BTC-USDT" -> "BTC"
+pub fn base_asset(symbol: &str) -> String {
+    if let Some((base, _)) = symbol.split_once(['/', '-']) {
+        return base.to_ascii_uppercase();
+    }
+    let upper = symbol.to_ascii_uppercase();
+    QUOTE_ASSETS.iter()
+        .find_map(|q| upper.strip_suffix(q).filter(|b| !b.is_empty()))
+        .unwrap_or(&upper)
+        .to_string()
+}
+
+/// 보유 중인 취득 단위
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxLot {
+    pub asset: String,
+    pub quantity: f64,
+    /// 수수료 포함 단위당 취득가
+    pub unit_cost: f64,
+    pub acquired_at: i64,
+    pub order_id: String,
+}
+
+/// 처분 1건 (취득 단위별로 나뉨)
+#[derive(Debug, Clone, Serialize)]
+pub struct RealizedGain {
+    pub asset: String,
+    pub year: i32,
+    pub quantity: f64,
+    /// 취득 단위 없이 매도된 수량은 None (취득가 0 으로 계산)
+    pub acquired_at: Option<i64>,
+    pub disposed_at: i64,
+    pub holding_days: i64,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub gain: f64,
+    pub long_term: bool,
+    pub order_id: String,
+}
+
+/// 자산/연도별 실현 손익 요약
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GainSummary {
+    pub asset: String,
+    pub year: i32,
+    pub disposals: usize,
+    pub quantity: f64,
+    pub proceeds: f64,
+    pub cost_basis: f64,
+    pub gain: f64,
+    pub short_term_gain: f64,
+    pub long_term_gain: f64,
+}
+
+/// 취득 단위 원장
+#[derive(Debug, Clone, Default)]
+pub struct TaxLotLedger {
+    method: LotMethod,
+    lots: HashMap<String, Vec<TaxLot>>,
+    realized: Vec<RealizedGain>,
+}
+
+impl TaxLotLedger {
+    pub fn new(method: LotMethod) -> Self {
+        TaxLotLedger { method, ..Default::default() }
+    }
+
+    /// 체결 목록을 시간순으로 재생하여 원장 생성
+    pub fn from_fills(fills: &[FillRecord], method: LotMethod) -> Self {
+        let mut ledger = Self::new(method);
+        let mut sorted: Vec<&FillRecord> = fills.iter().collect();
+        sorted.sort_by_key(|f| f.timestamp);
+        for fill in sorted {
+            ledger.record(fill);
+        }
+        ledger
+    }
+
+    pub fn method(&self) -> LotMethod {
+        self.method
+    }
+
+    /// 체결 1건 반영 (시간순으로 호출해야 함)
+    pub fn record(&mut self, fill: &FillRecord) {
+        let quantity = to_f64(fill.quantity);
+        if quantity <= QTY_EPSILON {
+            return;
+        }
+        let asset = base_asset(&fill.symbol);
+        match fill.side {
+            OrderSide::Buy => {
+                self.lots.entry(asset.clone()).or_default().push(TaxLot {
+                    asset,
+                    quantity,
+                    unit_cost: to_f64(fill.price * fill.quantity + fill.fee) / quantity,
+                    acquired_at: fill.timestamp,
+                    order_id: fill.order_id.clone(),
+                });
+            }
+            OrderSide::Sell => self.dispose(asset, fill),
+        }
+    }
+
+    fn dispose(&mut self, asset: String, fill: &FillRecord) {
+        let unit_proceeds = to_f64(fill.price * fill.quantity - fill.fee) / to_f64(fill.quantity);
+        let year = Utc.timestamp_millis_opt(fill.timestamp).single().map(|t| t.year()).unwrap_or(1970);
+        let lots = self.lots.entry(asset.clone()).or_default();
+        let mut remaining = to_f64(fill.quantity);
+
+        while remaining > QTY_EPSILON {
+            let index = match self.method {
+                LotMethod::Fifo => (!lots.is_empty()).then_some(0),
+                LotMethod::Lifo => lots.len().checked_sub(1),
+                LotMethod::Hifo => lots.iter().enumerate()
+                    .max_by(|a, b| a.1.unit_cost.partial_cmp(&b.1.unit_cost).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(i, _)| i),
+            };
+            let (quantity, unit_cost, acquired_at) = match index {
+                Some(i) => {
+                    let lot = &mut lots[i];
+                    let take = remaining.min(lot.quantity);
+                    let acquired = (take, lot.unit_cost, Some(lot.acquired_at));
+                    lot.quantity -= take;
+                    if lot.quantity <= QTY_EPSILON {
+                        lots.remove(i);
+                    }
+                    acquired
+                }
+                None => {
+                    log::warn!("tax lots: {} {} sold without matching purchase, cost basis 0", remaining, asset);
+                    (remaining, 0.0, None)
+                }
+            };
+            remaining -= quantity;
+
+            let holding_days = acquired_at.map(|a| (fill.timestamp - a) / MS_PER_DAY).unwrap_or(0);
+            let proceeds = unit_proceeds * quantity;
+            let cost_basis = unit_cost * quantity;
+            self.realized.push(RealizedGain {
+                asset: asset.clone(),
+                year,
+                quantity,
+                acquired_at,
+                disposed_at: fill.timestamp,
+                holding_days,
+                proceeds,
+                cost_basis,
+                gain: proceeds - cost_basis,
+                long_term: holding_days > LONG_TERM_DAYS,
+                order_id: fill.order_id.clone(),
+            });
+        }
+    }
+
+    /// 처분 내역 (처분 시각순)
+    pub fn realized(&self) -> &[RealizedGain] {
+        &self.realized
+    }
+
+    /// 특정 과세 연도의 처분 내역만 남김 (취득 단위 매칭은 전체 이력 기준으로 이미 끝난 상태)
+    pub fn retain_year(&mut self, year: i32) {
+        self.realized.retain(|r| r.year == year);
+    }
+
+    /// 아직 처분되지 않은 취득 단위
+    pub fn open_lots(&self) -> Vec<&TaxLot> {
+        let mut lots: Vec<&TaxLot> = self.lots.values().flatten().collect();
+        lots.sort_by(|a, b| a.asset.cmp(&b.asset).then(a.acquired_at.cmp(&b.acquired_at)));
+        lots
+    }
+
+    /// 자산/연도별 요약 (자산, 연도순)
+    pub fn summary(&self) -> Vec<GainSummary> {
+        let mut by_key: BTreeMap<(String, i32), GainSummary> = BTreeMap::new();
+        for r in &self.realized {
+            let s = by_key.entry((r.asset.clone(), r.year)).or_insert_with(|| GainSummary {
+                asset: r.asset.clone(),
+                year: r.year,
+                ..Default::default()
+            });
+            s.disposals += 1;
+            s.quantity += r.quantity;
+            s.proceeds += r.proceeds;
+            s.cost_basis += r.cost_basis;
+            s.gain += r.gain;
+            if r.long_term {
+                s.long_term_gain += r.gain;
+            } else {
+                s.short_term_gain += r.gain;
+            }
+        }
+        by_key.into_values().collect()
+    }
+
+    /// 건별 실현 내역 CSV
+    pub fn export_realized_csv<W: Write>(&self, writer: W) -> Result<(), TradingError> {
+        write_csv(&self.realized, writer)
+    }
+
+    /// 자산/연도별 요약 CSV
+    pub fn export_summary_csv<W: Write>(&self, writer: W) -> Result<(), TradingError> {
+        write_csv(&self.summary(), writer)
+    }
+}
+
+fn write_csv<T: Serialize, W: Write>(rows: &[T], writer: W) -> Result<(), TradingError> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    for row in rows {
+        wtr.serialize(row).map_err(|e| TradingError::ParseError(e.to_string()))?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::to_decimal;
+
+    fn fill(side: OrderSide, price: f64, quantity: f64, fee: f64, timestamp: i64) -> FillRecord {
+        let (price, quantity, fee) = (to_decimal(price), to_decimal(quantity), to_decimal(fee));
+        FillRecord { order_id: format!("o-{}", timestamp), symbol: "BTC/USDT".into(), side, price, quantity, fee, timestamp }
+    }
+
+    #[test]
+    fn test_tax_lot_methods() {
+        assert_eq!(base_asset("ETHUSDT"), "ETH");
+        assert_eq!(base_asset("BTC-USD"), "BTC");
+        assert_eq!(LotMethod::parse("HIFO").unwrap(), LotMethod::Hifo);
+
+        // 2023-01-01 에 100, 2023-09-01 에 300, 2024-06-01 에 200 매수 후 2024-07-01 에 250 으로 1.5 매도
+        let jan23 = 1_672_531_200_000;
+        let sep23 = 1_693_526_400_000;
+        let jun24 = 1_717_200_000_000;
+        let jul24 = 1_719_792_000_000;
+        let fills = vec![
+            fill(OrderSide::Sell, 250.0, 1.5, 3.0, jul24),
+            fill(OrderSide::Buy, 100.0, 1.0, 1.0, jan23),
+            fill(OrderSide::Buy, 300.0, 1.0, 0.0, sep23),
+            fill(OrderSide::Buy, 200.0, 1.0, 0.0, jun24),
+        ];
+
+        // FIFO: 1 @ 101 (장기) + 0.5 @ 300, 처분가 (375 - 3) / 1.5 = 248
+        let fifo = TaxLotLedger::from_fills(&fills, LotMethod::Fifo);
+        let realized = fifo.realized();
+        assert_eq!(realized.len(), 2);
+        assert!((realized[0].gain - (248.0 - 101.0)).abs() < 1e-9);
+        assert!(realized[0].long_term);
+        assert!(!realized[1].long_term);
+        assert!((realized[1].gain - 0.5 * (248.0 - 300.0)).abs() < 1e-9);
+
+        let lifo = TaxLotLedger::from_fills(&fills, LotMethod::Lifo);
+        assert_eq!(lifo.realized()[0].acquired_at, Some(jun24));
+        let hifo = TaxLotLedger::from_fills(&fills, LotMethod::Hifo);
+        assert_eq!(hifo.realized()[0].acquired_at, Some(sep23));
+        assert!(hifo.summary()[0].gain < fifo.summary()[0].gain);
+
+        let summary = fifo.summary();
+        assert_eq!(summary.len(), 1);
+        assert_eq!((summary[0].asset.as_str(), summary[0].year, summary[0].disposals), ("BTC", 2024, 2));
+        assert!((summary[0].quantity - 1.5).abs() < 1e-9);
+        let open = fifo.open_lots();
+        assert_eq!(open.len(), 2);
+        assert!((open[0].quantity - 0.5).abs() < 1e-9);
+
+        let mut csv = Vec::new();
+        fifo.export_summary_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.starts_with("asset,year,disposals,quantity,proceeds,cost_basis,gain,short_term_gain,long_term_gain"));
+        assert!(csv.contains("BTC,2024,2,1.5,"));
+    }
+}
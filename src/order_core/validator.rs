@@ -1,7 +1,14 @@
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
 use crate::models::order::Order;
+use crate::models::symbol_info::SymbolInfo;
 
 /// 주문 검증기 인터페이스
 pub trait OrderValidator: Send + Sync {
@@ -11,12 +18,12 @@ pub trait OrderValidator: Send + Sync {
 
 /// 기본 주문 검증기
 pub struct BasicOrderValidator {
-    min_order_size: f64,
-    max_order_size: f64,
+    min_order_size: Decimal,
+    max_order_size: Decimal,
 }
 
 impl BasicOrderValidator {
-    pub fn new(min_order_size: f64, max_order_size: f64) -> Self {
+    pub fn new(min_order_size: Decimal, max_order_size: Decimal) -> Self {
         BasicOrderValidator {
             min_order_size,
             max_order_size,
@@ -27,7 +34,7 @@ impl BasicOrderValidator {
 impl OrderValidator for BasicOrderValidator {
     fn validate(&self, order: &Order) -> Result<(), TradingError> {
         // 주문 수량 검증
-        if order.quantity <= 0.0 {
+        if order.quantity <= Decimal::ZERO {
             return Err(TradingError::InvalidParameter("Order quantity must be positive".to_string()));
         }
 
@@ -44,7 +51,7 @@ impl OrderValidator for BasicOrderValidator {
         }
 
         // 가격 검증
-        if order.price < 0.0 {
+        if order.price < Decimal::ZERO {
             return Err(TradingError::InvalidParameter("Price cannot be negative".to_string()));
         }
 
@@ -54,12 +61,12 @@ impl OrderValidator for BasicOrderValidator {
 
 /// 리스크 기반 주문 검증기
 pub struct RiskOrderValidator {
-    max_position_size: f64,
-    max_notional_value: f64,
+    max_position_size: Decimal,
+    max_notional_value: Decimal,
 }
 
 impl RiskOrderValidator {
-    pub fn new(max_position_size: f64, max_notional_value: f64) -> Self {
+    pub fn new(max_position_size: Decimal, max_notional_value: Decimal) -> Self {
         RiskOrderValidator {
             max_position_size,
             max_notional_value,
@@ -86,4 +93,90 @@ impl OrderValidator for RiskOrderValidator {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// 거래소 기능 기반 주문 검증기 (지원하지 않는 주문 유형/TIF 사전 차단)
+pub struct CapabilityOrderValidator {
+    capabilities: ExchangeCapabilities,
+}
+
+impl CapabilityOrderValidator {
+    pub fn new(capabilities: ExchangeCapabilities) -> Self {
+        CapabilityOrderValidator { capabilities }
+    }
+}
+
+impl OrderValidator for CapabilityOrderValidator {
+    fn validate(&self, order: &Order) -> Result<(), TradingError> {
+        self.capabilities.check_order(order)
+    }
+}
+
+/// 거래소 심볼 규칙 검증기 - 가격/수량을 호가·수량 단위에 맞추고 최소 수량/주문 금액 미달 주문을 제출 전에 거부
+///
+/// 규칙은 거래소(venue)별로 처음 주문할 때 `Exchange::get_symbol_info` 로 받아 캐시한다.
+/// 규칙을 제공하지 않는 거래소(mock 등)의 주문은 그대로 통과한다.
+#[derive(Default)]
+pub struct SymbolRulesValidator {
+    rules: std::sync::RwLock<HashMap<(String, String), SymbolInfo>>,
+}
+
+impl SymbolRulesValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 규칙 직접 등록 (조회 없이 사용)
+    pub fn insert(&self, venue: &str, info: SymbolInfo) {
+        self.rules.write().unwrap_or_else(|e| e.into_inner()).insert((venue.to_string(), info.symbol.clone()), info);
+    }
+
+    pub fn get(&self, venue: &str, symbol: &str) -> Option<SymbolInfo> {
+        self.rules.read().unwrap_or_else(|e| e.into_inner()).get(&(venue.to_string(), symbol.to_string())).cloned()
+    }
+
+    /// 캐시에 없으면 거래소에서 규칙 조회 후 적용
+    pub async fn apply(&self, venue: &str, exchange: &Arc<RwLock<dyn Exchange>>, order: Order, reference_price: Option<Decimal>) -> Result<Order, TradingError> {
+        let info = match self.get(venue, &order.symbol) {
+            Some(info) => info,
+            None => match exchange.read().await.get_symbol_info(&order.symbol).await {
+                Ok(info) => {
+                    let info = SymbolInfo { symbol: order.symbol.clone(), ..info };
+                    self.insert(venue, info.clone());
+                    info
+                }
+                Err(e) => {
+                    log::debug!("symbol rules unavailable for {} on {}: {}", order.symbol, venue, e);
+                    return Ok(order);
+                }
+            },
+        };
+        round_to_rules(&info, order, reference_price)
+    }
+}
+
+/// 가격(지정가/스톱)과 수량을 단위에 맞춘 주문 반환, 최소 수량/주문 금액 미달이면 거부.
+/// 가격 없는 시장가는 `reference_price` 가 있을 때만 주문 금액을 검사
+pub fn round_to_rules(info: &SymbolInfo, mut order: Order, reference_price: Option<Decimal>) -> Result<Order, TradingError> {
+    if order.price > Decimal::ZERO {
+        order.price = info.round_price(order.price, &order.side);
+    }
+    if let Some(stop) = order.stop_price.filter(|s| *s > Decimal::ZERO) {
+        order.stop_price = Some(info.round_price(stop, &order.side));
+    }
+    order.quantity = info.round_quantity(order.quantity);
+    if order.quantity <= Decimal::ZERO || order.quantity < info.min_qty {
+        return Err(TradingError::InvalidParameter(format!(
+            "{} quantity {} below minimum {} (step {})", order.symbol, order.quantity, info.min_qty, info.step_size
+        )));
+    }
+    let price = if order.price > Decimal::ZERO { Some(order.price) } else { reference_price };
+    if let Some(price) = price {
+        if info.min_notional > Decimal::ZERO && order.quantity * price < info.min_notional {
+            return Err(TradingError::InvalidParameter(format!(
+                "{} notional {} below minimum {}", order.symbol, order.quantity * price, info.min_notional
+            )));
+        }
+    }
+    Ok(order)
+}
@@ -6,11 +6,20 @@
 **/
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use anyhow::Result;
+use crate::config::{FeedbackConfig, MicrostructureFeatureConfig, PredictionSignalFeed};
 use crate::error::TradingError;
+use crate::market_data::external::ExternalSeriesStore;
+use crate::market_data::microstructure::{MicrostructureFeatures, MicrostructureTracker};
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::order::{OrderSide, OrderStatus, OrderUpdate};
+use crate::models::timestamp::Timestamp;
+use crate::strategies::prediction::{confidence_series, signal_series};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketDataRequest {
@@ -25,6 +34,27 @@ pub struct SignalRequest {
     pub timeframe: String,
     pub strategy: String,
     pub lookback: i32,
+    /// 로컬에서 계산한 호가/체결 미시구조 피처 (전략별 설정 시에만 포함)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub features: Option<MicrostructureFeatures>,
+}
+
+impl SignalRequest {
+    pub fn new(symbol: impl Into<String>, timeframe: impl Into<String>, strategy: impl Into<String>, lookback: i32) -> Self {
+        SignalRequest {
+            symbol: symbol.into(),
+            timeframe: timeframe.into(),
+            strategy: strategy.into(),
+            lookback,
+            features: None,
+        }
+    }
+
+    /// 미시구조 피처 첨부
+    pub fn with_features(mut self, features: Option<MicrostructureFeatures>) -> Self {
+        self.features = features;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +117,183 @@ pub struct PredictionResponse {
     pub method: String,
 }
 
+/// 시그널 1건의 실제 실행 결과 (재학습용)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SignalOutcome {
+    pub strategy: String,
+    pub symbol: String,
+    /// 거부된 시그널은 None
+    pub order_id: Option<String>,
+    pub side: OrderSide,
+    pub quantity: f64,
+    /// 시그널 시점 시세
+    pub signal_price: f64,
+    pub signal_time: i64,
+    pub filled_quantity: f64,
+    /// 체결 평균가
+    pub fill_price: Option<f64>,
+    /// 이 시그널의 체결로 실현된 손익
+    pub realized_pnl: f64,
+    pub status: OrderStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reject_reason: Option<String>,
+}
+
+impl SignalOutcome {
+    /// 제출 직전 시그널
+    pub fn new(strategy: impl Into<String>, symbol: impl Into<String>, side: OrderSide, quantity: f64, signal_price: f64, signal_time: i64) -> Self {
+        SignalOutcome {
+            strategy: strategy.into(),
+            symbol: symbol.into(),
+            order_id: None,
+            side,
+            quantity,
+            signal_price,
+            signal_time,
+            filled_quantity: 0.0,
+            fill_price: None,
+            realized_pnl: 0.0,
+            status: OrderStatus::New,
+            reject_reason: None,
+        }
+    }
+
+    /// 더 이상 바뀌지 않는 상태인지
+    pub fn is_final(&self) -> bool {
+        !matches!(self.status, OrderStatus::New | OrderStatus::PartiallyFilled)
+    }
+
+    /// 시그널 시세 대비 체결가 차이 (bps, 불리한 방향이 양수)
+    pub fn slippage_bps(&self) -> Option<f64> {
+        let fill = self.fill_price?;
+        if self.signal_price <= 0.0 {
+            return None;
+        }
+        let diff = match self.side {
+            OrderSide::Buy => fill - self.signal_price,
+            OrderSide::Sell => self.signal_price - fill,
+        };
+        Some(diff / self.signal_price * 10_000.0)
+    }
+}
+
+/// `/feedback` 요청 본문
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackBatch {
+    pub sent_at: DateTime<Utc>,
+    pub outcomes: Vec<SignalOutcome>,
+}
+
+/// 전략 시그널 결과 수집기 - 런타임이 기록하고 주기 태스크가 `/feedback` 으로 전송
+#[derive(Clone, Default)]
+pub struct FeedbackCollector {
+    config: FeedbackConfig,
+    /// 주문 ID -> 결과 (거부 건은 "rejected-<n>" 키)
+    pending: Arc<std::sync::Mutex<HashMap<String, SignalOutcome>>>,
+}
+
+impl FeedbackCollector {
+    pub fn new(config: FeedbackConfig) -> Self {
+        FeedbackCollector {
+            config,
+            pending: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn config(&self) -> &FeedbackConfig {
+        &self.config
+    }
+
+    /// 제출된 시그널 기록
+    pub fn record_signal(&self, order_id: &str, mut outcome: SignalOutcome) {
+        if !self.config.reports(&outcome.strategy) {
+            return;
+        }
+        outcome.order_id = Some(order_id.to_string());
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(order_id.to_string(), outcome);
+        }
+    }
+
+    /// 리스크 한도 등으로 제출되지 않은 시그널 기록
+    pub fn record_rejection(&self, mut outcome: SignalOutcome, reason: &str) {
+        if !self.config.reports(&outcome.strategy) {
+            return;
+        }
+        outcome.status = OrderStatus::Rejected;
+        outcome.reject_reason = Some(reason.to_string());
+        if let Ok(mut pending) = self.pending.lock() {
+            let key = format!("rejected-{}-{}", outcome.signal_time, pending.len());
+            pending.insert(key, outcome);
+        }
+    }
+
+    /// 체결 반영 (평균 체결가 갱신, 실현 손익 누적)
+    pub fn record_fill(&self, order_id: &str, price: f64, quantity: f64, realized_pnl: f64) {
+        let Ok(mut pending) = self.pending.lock() else { return };
+        let Some(outcome) = pending.get_mut(order_id) else { return };
+        let filled = outcome.filled_quantity + quantity;
+        if filled > 0.0 {
+            let prev = outcome.fill_price.unwrap_or(0.0) * outcome.filled_quantity;
+            outcome.fill_price = Some((prev + price * quantity) / filled);
+        }
+        outcome.filled_quantity = filled;
+        outcome.realized_pnl += realized_pnl;
+        outcome.status = if filled + 1e-12 >= outcome.quantity { OrderStatus::Filled } else { OrderStatus::PartiallyFilled };
+    }
+
+    /// 체결로 실현된 전략 손익 반영
+    pub fn record_realized_pnl(&self, order_id: &str, realized_pnl: f64) {
+        let Ok(mut pending) = self.pending.lock() else { return };
+        if let Some(outcome) = pending.get_mut(order_id) {
+            outcome.realized_pnl += realized_pnl;
+        }
+    }
+
+    /// 사용자 데이터 스트림 주문 이벤트 반영 (체결 및 최종 상태)
+    pub fn apply_order_update(&self, update: &OrderUpdate) {
+        if update.is_fill() {
+            // 런타임이 시세로 추정 기록한 체결이 있으면 거래소 체결로 대체
+            if let Ok(mut pending) = self.pending.lock() {
+                if let Some(outcome) = pending.get_mut(&update.order_id.0) {
+                    let before = to_f64((update.cumulative_quantity - update.last_fill_quantity).max(Decimal::ZERO));
+                    if outcome.filled_quantity > before + 1e-12 {
+                        outcome.filled_quantity = before;
+                        outcome.fill_price = if before > 0.0 { outcome.fill_price } else { None };
+                    }
+                }
+            }
+            self.record_fill(&update.order_id.0, to_f64(update.last_fill_price), to_f64(update.last_fill_quantity), 0.0);
+        }
+        if let Ok(mut pending) = self.pending.lock() {
+            if let Some(outcome) = pending.get_mut(&update.order_id.0) {
+                outcome.status = update.status.clone();
+            }
+        }
+    }
+
+    /// 전송할 결과 꺼내기 - 최종 상태이거나 max_pending_ms 를 넘긴 것
+    pub fn take_ready(&self, now: i64) -> Vec<SignalOutcome> {
+        let Ok(mut pending) = self.pending.lock() else { return Vec::new() };
+        let ready: Vec<String> = pending.iter()
+            .filter(|(_, o)| o.is_final() || now - o.signal_time >= self.config.max_pending_ms)
+            .map(|(k, _)| k.clone())
+            .collect();
+        let mut outcomes: Vec<SignalOutcome> = ready.iter().filter_map(|k| pending.remove(k)).collect();
+        outcomes.sort_by_key(|o| o.signal_time);
+        outcomes
+    }
+
+    /// 전송 실패분을 다시 대기열로
+    pub fn requeue(&self, outcomes: Vec<SignalOutcome>) {
+        let Ok(mut pending) = self.pending.lock() else { return };
+        for outcome in outcomes {
+            let key = outcome.order_id.clone().unwrap_or_else(|| format!("rejected-{}-{}", outcome.signal_time, pending.len()));
+            pending.entry(key).or_insert(outcome);
+        }
+    }
+}
+
 pub struct PredictionClient {
     client: Client,
     base_url: String,
@@ -159,6 +366,22 @@ impl PredictionClient {
         Ok(prediction)
     }
     
+    /// Report realized signal outcomes for model retraining
+    pub async fn send_feedback(&self, batch: &FeedbackBatch) -> Result<()> {
+        let url = format!("{}/feedback", self.base_url);
+        let response = self.client
+            .post(&url)
+            .json(batch)
+            .send()
+            .await?;
+        
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Failed to send feedback: {}", response.status()));
+        }
+        
+        Ok(())
+    }
+    
     /// Get list of available strategies
     pub async fn list_strategies(&self) -> Result<Vec<String>> {
         let url = format!("{}/strategies", self.base_url);
@@ -177,6 +400,30 @@ impl PredictionClient {
     }
 }
 
+/// 설정된 심볼의 예측 신호를 주기적으로 조회하여 외부 시계열(`prediction.<SYMBOL>.signal` / `.confidence`)로 기록
+pub fn spawn_signal_feeds(base_url: String, store: ExternalSeriesStore, feeds: Vec<PredictionSignalFeed>) {
+    for feed in feeds {
+        let client = PredictionClient::new(base_url.clone());
+        let store = store.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_millis(feed.poll_interval_ms.max(1_000)));
+            loop {
+                ticker.tick().await;
+                let request = SignalRequest::new(feed.symbol.clone(), feed.timeframe.clone(), feed.strategy.clone(), 100);
+                match client.get_signals(request).await {
+                    Ok(signal) => {
+                        let timestamp = Timestamp::from_millis(signal.timestamp.timestamp_millis());
+                        store.record(&signal_series(&feed.symbol), timestamp, signal.signal as f64);
+                        store.record(&confidence_series(&feed.symbol), timestamp, signal.confidence);
+                        log::debug!("prediction signal {} = {} (conf {:.2})", feed.symbol, signal.signal, signal.confidence);
+                    }
+                    Err(e) => log::warn!("prediction signal {} poll failed: {}", feed.symbol, e),
+                }
+            }
+        });
+    }
+}
+
 /// Integration with existing trading bot
 pub struct PredictionBasedBot {
     prediction_client: PredictionClient,
@@ -184,6 +431,7 @@ pub struct PredictionBasedBot {
     strategy: String,
     timeframe: String,
     current_position: f64,
+    microstructure: Option<(Arc<RwLock<MicrostructureTracker>>, MicrostructureFeatureConfig)>,
 }
 
 impl PredictionBasedBot {
@@ -199,18 +447,27 @@ impl PredictionBasedBot {
             strategy,
             timeframe,
             current_position: 0.0,
+            microstructure: None,
         }
     }
     
+    /// 시그널 요청에 호가/체결 피처를 포함하도록 설정
+    pub fn with_microstructure(mut self, tracker: Arc<RwLock<MicrostructureTracker>>, config: MicrostructureFeatureConfig) -> Self {
+        if config.enabled {
+            self.microstructure = Some((tracker, config));
+        }
+        self
+    }
+    
     /// Get next trading action from prediction service
     pub async fn get_next_action(&mut self) -> Result<TradingAction> {
         // Get signals from prediction service
-        let request = SignalRequest {
-            symbol: self.symbol.clone(),
-            timeframe: self.timeframe.clone(),
-            strategy: self.strategy.clone(),
-            lookback: 100,
+        let features = match &self.microstructure {
+            Some((tracker, config)) => tracker.read().await.features(&self.symbol, config),
+            None => None,
         };
+        let request = SignalRequest::new(self.symbol.clone(), self.timeframe.clone(), self.strategy.clone(), 100)
+            .with_features(features);
         
         let signal = self.prediction_client.get_signals(request).await?;
         
@@ -277,6 +534,32 @@ mod tests {
         assert!(health.is_ok() || health.is_err()); // Accept both for testing
     }
     
+    #[test]
+    fn test_feedback_collector() {
+        let config = FeedbackConfig { enabled: true, strategies: vec!["rsi".into()], interval_ms: 1_000, max_pending_ms: 10_000 };
+        let collector = FeedbackCollector::new(config);
+        collector.record_signal("1", SignalOutcome::new("rsi", "BTCUSDT", OrderSide::Buy, 2.0, 100.0, 0));
+        collector.record_signal("2", SignalOutcome::new("rsi", "BTCUSDT", OrderSide::Sell, 1.0, 100.0, 0));
+        collector.record_signal("3", SignalOutcome::new("ma", "BTCUSDT", OrderSide::Buy, 1.0, 100.0, 0));
+        collector.record_rejection(SignalOutcome::new("rsi", "BTCUSDT", OrderSide::Buy, 1.0, 100.0, 5), "daily loss");
+
+        collector.record_fill("1", 101.0, 1.0, 0.0);
+        collector.record_fill("1", 103.0, 1.0, 4.0);
+
+        let ready = collector.take_ready(100);
+        assert_eq!(ready.len(), 2);
+        assert_eq!((ready[0].fill_price, ready[0].status.clone(), ready[0].realized_pnl), (Some(102.0), OrderStatus::Filled, 4.0));
+        assert!((ready[0].slippage_bps().unwrap() - 200.0).abs() < 1e-9);
+        assert_eq!(ready[1].reject_reason.as_deref(), Some("daily loss"));
+
+        // 미체결 주문은 max_pending_ms 이후 그대로 전송, 실패 시 재대기
+        assert!(collector.take_ready(9_999).is_empty());
+        let stale = collector.take_ready(10_000);
+        assert_eq!(stale[0].order_id.as_deref(), Some("2"));
+        collector.requeue(stale);
+        assert_eq!(collector.take_ready(10_000).len(), 1);
+    }
+
     #[test]
     fn test_trading_action() {
         let mut bot = PredictionBasedBot::new(
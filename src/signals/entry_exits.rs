@@ -0,0 +1,137 @@
+/**
+* filename : entry_exits
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 진입 주문에 붙이는 손절/익절 가격 계산 (진입가 대비 %, ATR 배수, 고정 가격)
+**/
+
+use serde::{Serialize, Deserialize};
+use crate::error::TradingError;
+use crate::indicators::{Indicator, volatility::AverageTrueRange};
+use crate::models::decimal::{to_decimal, to_f64, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide};
+
+// 손절/익절 가격 규칙 - {"percent": 2.0} | {"atr": 1.5} | {"price": 61000.0}
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitRule {
+  // 진입가 대비 비율 (%)
+  Percent(f64),
+  // 진입가에서 ATR 의 배수만큼
+  Atr(f64),
+  // 고정 가격 (진입 방향과 맞지 않으면 브래킷 검증에서 거부)
+  Price(f64),
+}
+
+impl ExitRule {
+  // direction: 진입가에서 멀어지는 방향 (+1 위, -1 아래)
+  fn level(&self, entry: f64, atr: Option<f64>, direction: f64) -> Option<f64> {
+    let price = match *self {
+      ExitRule::Percent(pct) => entry * (1.0 + direction * pct / 100.0),
+      ExitRule::Atr(multiple) => entry + direction * multiple * atr?,
+      ExitRule::Price(price) => price,
+    };
+    (price > 0.0).then_some(price)
+  }
+
+  fn value(&self) -> f64 {
+    match *self {
+      ExitRule::Percent(v) | ExitRule::Atr(v) | ExitRule::Price(v) => v,
+    }
+  }
+}
+
+// 진입 주문 손절/익절 설정 (전략 배포의 `exits`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EntryExitsConfig {
+  pub stop_loss: ExitRule,
+  pub take_profit: ExitRule,
+  // ATR 규칙에 쓰는 기간
+  #[serde(default = "default_exit_atr_period")]
+  pub atr_period: usize,
+}
+
+fn default_exit_atr_period() -> usize { 14 }
+
+impl EntryExitsConfig {
+  pub fn validate(&self) -> Result<(), String> {
+    for rule in [self.stop_loss, self.take_profit] {
+      if !rule.value().is_finite() || rule.value() <= 0.0 {
+        return Err(format!("exit rule {:?} must be positive", rule));
+      }
+    }
+    if matches!(self.stop_loss, ExitRule::Percent(pct) if pct >= 100.0) {
+      return Err("stop_loss percent must be below 100".to_string());
+    }
+    if self.uses_atr() && self.atr_period == 0 {
+      return Err("exits atr_period must be positive".to_string());
+    }
+    Ok(())
+  }
+
+  fn uses_atr(&self) -> bool {
+    matches!(self.stop_loss, ExitRule::Atr(_)) || matches!(self.take_profit, ExitRule::Atr(_))
+  }
+}
+
+// 진입 주문에 손절/익절 가격을 붙이는 계산기 - 전략이 받는 캔들로 ATR 과 기준가를 갱신
+#[derive(Debug)]
+pub struct EntryExits {
+  config: EntryExitsConfig,
+  atr: Option<AverageTrueRange>,
+  // 가격 없는 시장가 진입의 기준가
+  last_close: Option<f64>,
+}
+
+impl EntryExits {
+  pub fn new(config: EntryExitsConfig) -> Result<Self, TradingError> {
+    config.validate().map_err(TradingError::InvalidParameter)?;
+    Ok(EntryExits {
+      atr: config.uses_atr().then(|| AverageTrueRange::new(config.atr_period)),
+      config,
+      last_close: None,
+    })
+  }
+
+  pub fn config(&self) -> &EntryExitsConfig {
+    &self.config
+  }
+
+  pub fn update(&mut self, market_data: &MarketData) {
+    if let Some(atr) = self.atr.as_mut() {
+      if let Err(e) = atr.update_ohlc(market_data.open_f64(), market_data.high_f64(), market_data.low_f64(), market_data.close_f64(), None) {
+        log::debug!("exit ATR update skipped for {}: {}", market_data.symbol, e);
+      }
+    }
+    self.last_close = Some(market_data.close_f64());
+  }
+
+  // (익절가, 손절가) - ATR 워밍업 중이면 None
+  pub fn levels(&self, side: &OrderSide, entry_price: f64) -> Option<(f64, f64)> {
+    let direction = match side {
+      OrderSide::Buy => 1.0,
+      OrderSide::Sell => -1.0,
+    };
+    let atr = self.atr.as_ref().and_then(|atr| atr.value());
+    let take_profit = self.config.take_profit.level(entry_price, atr, direction)?;
+    let stop_loss = self.config.stop_loss.level(entry_price, atr, -direction)?;
+    Some((take_profit, stop_loss))
+  }
+
+  // 진입 주문에 손절/익절 첨부 - 기준가나 ATR 이 아직 없으면 보호 없는 진입을 막기 위해 None
+  pub fn attach(&self, order: Order) -> Option<Order> {
+    let entry_price = if order.price > Decimal::ZERO {
+      to_f64(order.price)
+    } else {
+      self.last_close?
+    };
+    match self.levels(&order.side, entry_price) {
+      Some((take_profit, stop_loss)) => Some(order.with_attached_exits(to_decimal(take_profit), to_decimal(stop_loss))),
+      None => {
+        log::warn!("{} {:?} entry skipped: exit levels not ready (ATR warming up)", order.symbol, order.side);
+        None
+      }
+    }
+  }
+}
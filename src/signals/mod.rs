@@ -8,7 +8,11 @@
 pub mod signal_types;
 pub mod signal_analyzer;
 pub mod position_sizing;
+pub mod signal_aggregator;
+pub mod entry_exits;
 
 pub use signal_types::*;
 pub use signal_analyzer::*;
-pub use position_sizing::*;
\ No newline at end of file
+pub use position_sizing::*;
+pub use signal_aggregator::*;
+pub use entry_exits::*;
\ No newline at end of file
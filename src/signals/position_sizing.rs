@@ -5,6 +5,9 @@
 * description: 
 **/
 
+use crate::error::TradingError;
+use crate::indicators::{Indicator, volatility::AverageTrueRange};
+use crate::models::market_data::MarketData;
 use crate::models::position::Position;
 use super::signal_types::{SignalType, SignalWithMetadata};
 
@@ -88,4 +91,61 @@ impl PositionSizer for KellyPositionSizer {
     // 계산된 비율로 포지션 크기 결정
     available_capital * capped_fraction
   }
-}
\ No newline at end of file
+}
+
+// ATR 기반 사이징: 수량 = 거래당 위험 금액 / (ATR × 배수)
+// 손절폭을 ATR × 배수로 두었을 때 한 번의 손실이 risk_per_trade 가 되도록 함
+pub struct AtrPositionSizer {
+  atr: AverageTrueRange,
+  atr_multiplier: f64,
+  risk_per_trade: f64, // 거래당 위험 금액 (호가 통화)
+}
+
+impl AtrPositionSizer {
+  pub fn new(atr_period: usize, atr_multiplier: f64, risk_per_trade: f64) -> Self {
+    AtrPositionSizer {
+      atr: AverageTrueRange::new(atr_period),
+      atr_multiplier,
+      risk_per_trade,
+    }
+  }
+  
+  // 캔들로 ATR 업데이트
+  pub fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    self.atr.update_ohlc(market_data.open_f64(), market_data.high_f64(), market_data.low_f64(), market_data.close_f64(), Some(market_data.volume_f64()))
+  }
+  
+  pub fn atr(&self) -> Option<f64> {
+    self.atr.value()
+  }
+  
+  // 현재 ATR 기준 손절 거리
+  pub fn stop_distance(&self) -> Option<f64> {
+    self.atr.value().map(|atr| atr * self.atr_multiplier)
+  }
+}
+
+impl PositionSizer for AtrPositionSizer {
+  fn calculate_position_size(
+    &self,
+    _signal: &SignalWithMetadata,
+    available_capital: f64,
+    _current_position: Option<&Position>,
+    price: f64
+  ) -> f64 {
+    // ATR 이 준비되지 않았으면 주문하지 않음
+    let distance = match self.stop_distance() {
+      Some(d) if d > 0.0 => d,
+      _ => return 0.0,
+    };
+    
+    let size = self.risk_per_trade / distance;
+    
+    // 가격을 알면 가용 자본 이상으로 사지 않도록 제한
+    if price > 0.0 {
+      size.min(available_capital / price)
+    } else {
+      size
+    }
+  }
+}
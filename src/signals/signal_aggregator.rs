@@ -0,0 +1,215 @@
+/**
+* filename : signal_aggregator
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 설정 기반 신호 집계 (가중 투표 + 최소 합의 수 + 거부 규칙)
+**/
+
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+use crate::error::TradingError;
+use crate::indicators::IndicatorResult;
+use super::signal_types::{SignalType, SignalWithMetadata};
+
+// 거부 규칙이 막는 방향
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VetoSide {
+  Buy,
+  Sell,
+  Both,
+}
+
+// 거부 규칙 - 지표의 주 값(MACD 는 히스토그램, RSI 는 RSI 값 등)이 조건을 만족하면 해당 방향 신호를 막음
+// 예: {"indicator": "MACD", "below": 0.0, "blocks": "buy"} = MACD 히스토그램이 0 미만이면 매수하지 않음
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VetoRule {
+  // 지표 이름 접두어 (대소문자 무시, "MACD" 는 "MACD-12-26-9" 와 일치)
+  pub indicator: String,
+  #[serde(default)]
+  pub above: Option<f64>,
+  #[serde(default)]
+  pub below: Option<f64>,
+  pub blocks: VetoSide,
+}
+
+impl VetoRule {
+  fn applies_to(&self, buy: bool) -> bool {
+    match self.blocks {
+      VetoSide::Buy => buy,
+      VetoSide::Sell => !buy,
+      VetoSide::Both => true,
+    }
+  }
+
+  fn triggered(&self, indicator: &str, value: f64) -> bool {
+    has_prefix(indicator, &self.indicator)
+      && self.above.is_none_or(|above| value > above)
+      && self.below.is_none_or(|below| value < below)
+  }
+
+  fn describe(&self) -> String {
+    match (self.above, self.below) {
+      (Some(above), Some(below)) => format!("{} between {} and {}", self.indicator, above, below),
+      (Some(above), None) => format!("{} above {}", self.indicator, above),
+      (None, Some(below)) => format!("{} below {}", self.indicator, below),
+      (None, None) => self.indicator.clone(),
+    }
+  }
+}
+
+fn has_prefix(name: &str, prefix: &str) -> bool {
+  name.len() >= prefix.len() && name[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+// 집계 설정 (TradingBotConfig 의 "aggregation" 파라미터, 전략 명세의 `aggregation`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AggregationConfig {
+  // 신호 이름("Golden Cross") 또는 지표 이름 접두어("RSI")별 가중치 - 0 이면 해당 신호 무시
+  #[serde(default)]
+  pub weights: BTreeMap<String, f64>,
+  #[serde(default = "default_weight")]
+  pub default_weight: f64,
+  // 결정 방향과 같은 방향 신호의 최소 개수
+  #[serde(default = "default_min_agreement")]
+  pub min_agreement: usize,
+  // 가중 평균 강도의 최소 절댓값 (0 ~ 1)
+  #[serde(default)]
+  pub threshold: f64,
+  #[serde(default)]
+  pub vetoes: Vec<VetoRule>,
+}
+
+fn default_weight() -> f64 { 0.5 }
+fn default_min_agreement() -> usize { 1 }
+
+impl Default for AggregationConfig {
+  fn default() -> Self {
+    AggregationConfig {
+      weights: BTreeMap::new(),
+      default_weight: default_weight(),
+      min_agreement: default_min_agreement(),
+      threshold: 0.0,
+      vetoes: Vec::new(),
+    }
+  }
+}
+
+impl AggregationConfig {
+  pub fn validate(&self) -> Result<(), String> {
+    if let Some((name, _)) = self.weights.iter().find(|(_, w)| !w.is_finite() || **w < 0.0) {
+      return Err(format!("aggregation weight for '{}' must be a non-negative number", name));
+    }
+    if !self.default_weight.is_finite() || self.default_weight < 0.0 {
+      return Err("aggregation default_weight must not be negative".to_string());
+    }
+    if !(0.0..=1.0).contains(&self.threshold) {
+      return Err("aggregation threshold must be in [0, 1]".to_string());
+    }
+    for rule in &self.vetoes {
+      if rule.indicator.trim().is_empty() || (rule.above.is_none() && rule.below.is_none()) {
+        return Err("veto rules need an indicator and an above/below bound".to_string());
+      }
+    }
+    Ok(())
+  }
+}
+
+// 집계 결과
+#[derive(Debug, Clone)]
+pub struct Aggregation {
+  // 가중 평균 강도 (-1.0 ~ 1.0)
+  pub score: f64,
+  pub buy_votes: usize,
+  pub sell_votes: usize,
+  // 결정 방향을 막은 거부 규칙
+  pub vetoed_by: Option<String>,
+  // 최종 신호 (합의 부족/임계값 미달/거부 시 None)
+  pub signal: Option<SignalWithMetadata>,
+}
+
+impl Aggregation {
+  pub fn to_json(&self) -> serde_json::Value {
+    serde_json::json!({
+      "score": self.score,
+      "buy_votes": self.buy_votes,
+      "sell_votes": self.sell_votes,
+      "vetoed_by": self.vetoed_by,
+      "signal": self.signal.as_ref().map(|s| format!("{:?}", s.signal_type)),
+    })
+  }
+}
+
+pub struct SignalAggregator {
+  config: AggregationConfig,
+}
+
+impl SignalAggregator {
+  pub fn new(config: AggregationConfig) -> Result<Self, TradingError> {
+    config.validate().map_err(TradingError::ConfigError)?;
+    Ok(SignalAggregator { config })
+  }
+
+  pub fn config(&self) -> &AggregationConfig {
+    &self.config
+  }
+
+  // 신호 이름 → 가장 긴 지표 이름 접두어 → 기본 가중치 순으로 결정
+  fn weight(&self, indicator: &str, signal: &str) -> f64 {
+    if let Some(weight) = self.config.weights.get(signal) {
+      return *weight;
+    }
+    self.config.weights.iter()
+      .filter(|(prefix, _)| has_prefix(indicator, prefix))
+      .max_by_key(|(prefix, _)| prefix.len())
+      .map(|(_, weight)| *weight)
+      .unwrap_or(self.config.default_weight)
+  }
+
+  // (지표 이름, 결과) 목록의 신호를 가중 투표로 하나의 신호로 집계
+  pub fn aggregate(&self, results: &[(String, IndicatorResult)]) -> Aggregation {
+    let mut weighted = 0.0;
+    let mut total_weight = 0.0;
+    let (mut buy_votes, mut sell_votes) = (0, 0);
+
+    for (indicator, result) in results {
+      for signal in &result.signals {
+        let weight = self.weight(indicator, &signal.name);
+        if weight <= 0.0 || signal.strength == 0.0 {
+          continue;
+        }
+        weighted += signal.strength * weight;
+        total_weight += weight;
+        if signal.strength > 0.0 {
+          buy_votes += 1;
+        } else {
+          sell_votes += 1;
+        }
+      }
+    }
+
+    let score = if total_weight > 0.0 { (weighted / total_weight).clamp(-1.0, 1.0) } else { 0.0 };
+    let mut aggregation = Aggregation { score, buy_votes, sell_votes, vetoed_by: None, signal: None };
+    let buy = score > 0.0;
+    let agreeing = if buy { buy_votes } else { sell_votes };
+    if score == 0.0 || score.abs() < self.config.threshold || agreeing < self.config.min_agreement.max(1) {
+      return aggregation;
+    }
+
+    // 결정 방향을 막는 거부 규칙
+    let veto = self.config.vetoes.iter()
+      .find(|rule| rule.applies_to(buy) && results.iter().any(|(name, result)| rule.triggered(name, result.value)));
+    if let Some(rule) = veto {
+      aggregation.vetoed_by = Some(rule.describe());
+      return aggregation;
+    }
+
+    aggregation.signal = Some(
+      SignalWithMetadata::new(SignalType::from_strength(score), "Signal Aggregation".to_string(), score)
+        .with_confidence(agreeing as f64 / (buy_votes + sell_votes) as f64)
+        .add_info("buy_votes", &buy_votes.to_string())
+        .add_info("sell_votes", &sell_votes.to_string())
+    );
+    aggregation
+  }
+}
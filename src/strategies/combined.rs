@@ -1,8 +1,15 @@
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::indicators::IndicatorResult;
+use crate::market_data::external::ExternalInputs;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
+use crate::models::order_book::OrderBook;
 use crate::models::order::{Order, OrderSide};
+use crate::signals::signal_aggregator::{AggregationConfig, SignalAggregator};
 use crate::signals::signal_types::SignalType;
-use crate::strategies::Strategy;
+use crate::strategies::{ParamUpdate, Strategy};
+use super::prediction::{PredictionSignalStrategy, TaConfirmation};
 use super::technical::TechnicalStrategy;
 
 // TA 기반 신호 생성 + 알고리즘 실행 최적화를 결합한 전략
@@ -10,7 +17,13 @@ pub struct CombinedStrategy {
   name: String,
   symbol: String,
   signal_strategy: Box<dyn Strategy>, // 신호 생성 전략
-  execution_strategy: Box<dyn Strategy>, // 실행 최적화 전략
+  execution_strategy: Option<Box<dyn Strategy>>, // 실행 최적화 전략 (None 이면 신호 주문을 그대로 제출)
+  // 신호 전략 지표의 가중 투표/거부 규칙 - 집계 방향과 같은 신호 주문만 실행
+  aggregator: Option<SignalAggregator>,
+  last_aggregation: Option<serde_json::Value>,
+  // 신호 주문을 고전 지표 조건으로 확인 (예측 신호 + TA 확인)
+  confirmation: Option<TaConfirmation>,
+  last_rejection: Option<String>,
   is_active: bool,
 }
 
@@ -25,11 +38,37 @@ impl CombinedStrategy {
       name,
       symbol,
       signal_strategy,
-      execution_strategy,
+      execution_strategy: Some(execution_strategy),
+      aggregator: None,
+      last_aggregation: None,
+      confirmation: None,
+      last_rejection: None,
       is_active: true,
     }
   }
   
+  // 예측 신호 + TA 확인: 예측 서비스 신호 중 `confirmation` 조건을 만족하는 방향만 시장가로 제출
+  // 확인에 실패한 신호는 버려지고, 예측 방향이 바뀌거나 중립을 거쳐 다시 나올 때 재평가
+  pub fn prediction_confirmed(signal_strategy: PredictionSignalStrategy, confirmation: TaConfirmation) -> Self {
+    CombinedStrategy {
+      name: format!("{} + TA", signal_strategy.name()),
+      symbol: signal_strategy.symbol().to_string(),
+      signal_strategy: Box::new(signal_strategy),
+      execution_strategy: None,
+      aggregator: None,
+      last_aggregation: None,
+      confirmation: Some(confirmation),
+      last_rejection: None,
+      is_active: true,
+    }
+  }
+  
+  // 신호 집계 설정 (None 이면 신호 전략 주문을 그대로 실행)
+  pub fn with_aggregation(mut self, config: Option<AggregationConfig>) -> Result<Self, TradingError> {
+    self.aggregator = config.map(SignalAggregator::new).transpose()?;
+    Ok(self)
+  }
+  
   // 편의 생성자: RSI 신호 + TWAP 실행
   pub fn rsi_twap(
     symbol: String,
@@ -50,7 +89,7 @@ impl CombinedStrategy {
     let execution_strategy = crate::strategies::twap::TwapStrategy::new(
       symbol.clone(),
       OrderSide::Buy,
-      1.0,
+      Decimal::ONE,
       (twap_duration_minutes as i64) * 60_000,
       10,
     );
@@ -83,7 +122,7 @@ impl CombinedStrategy {
     let execution_strategy = crate::strategies::vwap::VwapStrategy::new(
       symbol.clone(),
       OrderSide::Sell,
-      vwap_participation_rate.max(0.01),
+      to_decimal(vwap_participation_rate.max(0.01)),
       3_600_000,
       20,
     );
@@ -114,9 +153,9 @@ impl CombinedStrategy {
     let execution_strategy = crate::strategies::iceberg::IcebergStrategy::new(
       symbol.clone(),
       OrderSide::Buy,
-      10.0,
-      0.0,
-      display_size.max(0.001),
+      Decimal::TEN,
+      Decimal::ZERO,
+      to_decimal(display_size.max(0.001)),
     );
     
     Ok(CombinedStrategy::new(
@@ -137,8 +176,16 @@ impl Strategy for CombinedStrategy {
     // 먼저 신호 전략 업데이트
     self.signal_strategy.update(market_data.clone())?;
     
+    if let Some(confirmation) = self.confirmation.as_mut() {
+      if market_data.symbol == self.symbol {
+        confirmation.update(&market_data)?;
+      }
+    }
+    
     // 실행 전략도 업데이트
-    self.execution_strategy.update(market_data)?;
+    if let Some(execution) = self.execution_strategy.as_mut() {
+      execution.update(market_data)?;
+    }
     
     Ok(())
   }
@@ -155,20 +202,91 @@ impl Strategy for CombinedStrategy {
       return Ok(vec![]);
     }
     
+    // 집계 엔진이 있으면 집계 방향과 맞는 신호 주문만 통과
+    let signal_orders = match &self.aggregator {
+      Some(aggregator) => {
+        let aggregation = aggregator.aggregate(&self.signal_strategy.indicator_results());
+        self.last_aggregation = Some(aggregation.to_json());
+        let direction = aggregation.signal.as_ref().map(|s| s.strength > 0.0);
+        signal_orders.into_iter()
+          .filter(|order| {
+            let agreed = direction == Some(order.side == OrderSide::Buy);
+            if !agreed {
+              log::debug!("{}: {:?} signal dropped by aggregation (score {:.2}, veto {:?})", self.name, order.side, aggregation.score, aggregation.vetoed_by);
+            }
+            agreed
+          })
+          .collect()
+      }
+      None => signal_orders,
+    };
+    
+    // TA 확인 조건을 통과한 신호 주문만
+    let signal_orders: Vec<Order> = match &self.confirmation {
+      Some(confirmation) => signal_orders.into_iter()
+        .filter(|order| match confirmation.reject_reason(&order.side) {
+          Some(reason) => {
+            log::info!("{}: {:?} signal not confirmed ({})", self.name, order.side, reason);
+            self.last_rejection = Some(reason);
+            false
+          }
+          None => {
+            self.last_rejection = None;
+            true
+          }
+        })
+        .collect(),
+      None => signal_orders,
+    };
+    
+    let Some(execution_strategy) = self.execution_strategy.as_mut() else {
+      return Ok(signal_orders);
+    };
+    
     // 신호 주문을 실행 전략으로 변환
     let mut execution_orders = Vec::new();
     
     for order in signal_orders {
       // 신호 주문을 실행 전략에 다시 통과시켜 실 주문 생성
       // 실행 전략은 내부 로직에 따라 Market/Limit + 보조 파라미터(`with_*`)를 설정
-      self.execution_strategy.update(market_data_from_order(&order))?;
-      let mut exec_orders = self.execution_strategy.get_orders()?;
+      execution_strategy.update(market_data_from_order(&order))?;
+      let mut exec_orders = execution_strategy.get_orders()?;
       execution_orders.append(&mut exec_orders);
     }
     
     Ok(execution_orders)
   }
   
+  fn status(&self) -> serde_json::Value {
+    let confirmation = self.confirmation.as_ref().map(|c| {
+      let mut status = c.status();
+      status["rejected"] = serde_json::json!(self.last_rejection);
+      status
+    });
+    serde_json::json!({
+      "signal": self.signal_strategy.status(),
+      "execution": self.execution_strategy.as_ref().map(|e| e.status()),
+      "aggregation": self.last_aggregation,
+      "confirmation": confirmation,
+    })
+  }
+  
+  // {"signal": {...}, "execution": {...}} 를 각 다리 전략에 전달 (신호 다리부터 적용)
+  fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+    let params = ParamUpdate::new(&self.name, params)?;
+    params.allow(&["signal", "execution"])?;
+    if let Some(signal) = params.get("signal") {
+      self.signal_strategy.update_params(signal.clone())?;
+    }
+    if let Some(execution) = params.get("execution") {
+      match self.execution_strategy.as_mut() {
+        Some(strategy) => strategy.update_params(execution.clone())?,
+        None => return Err(params.invalid("strategy has no execution leg")),
+      }
+    }
+    Ok(())
+  }
+  
   fn name(&self) -> &str {
     &self.name
   }
@@ -185,7 +303,44 @@ impl Strategy for CombinedStrategy {
   fn set_active(&mut self, active: bool) {
     self.is_active = active;
     self.signal_strategy.set_active(active);
-    self.execution_strategy.set_active(active);
+    if let Some(execution) = self.execution_strategy.as_mut() {
+      execution.set_active(active);
+    }
+  }
+
+  fn update_order_book(&mut self, book: &OrderBook) {
+    if let Some(execution) = self.execution_strategy.as_mut() {
+      execution.update_order_book(book);
+    }
+  }
+  
+  fn apply_capabilities(&mut self, capabilities: &ExchangeCapabilities) {
+    if let Some(execution) = self.execution_strategy.as_mut() {
+      execution.apply_capabilities(capabilities);
+    }
+  }
+  
+  fn update_external(&mut self, inputs: &ExternalInputs) {
+    self.signal_strategy.update_external(inputs);
+    if let Some(execution) = self.execution_strategy.as_mut() {
+      execution.update_external(inputs);
+    }
+  }
+  
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    self.signal_strategy.indicator_results()
+  }
+  
+  // 신호 전략은 시세로 다시 워밍업되므로 실행 전략 진행 상태만 보존
+  fn export_state(&self) -> Option<serde_json::Value> {
+    self.execution_strategy.as_ref().and_then(|e| e.export_state())
+  }
+  
+  fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+    match self.execution_strategy.as_mut() {
+      Some(execution) => execution.restore_state(state),
+      None => Ok(()),
+    }
   }
 }
 
@@ -193,7 +348,7 @@ impl Strategy for CombinedStrategy {
 fn market_data_from_order(order: &Order) -> crate::models::market_data::MarketData {
   crate::models::market_data::MarketData {
     symbol: order.symbol.clone(),
-    timestamp: chrono::Utc::now().timestamp_millis(),
+    timestamp: crate::models::timestamp::Timestamp::now(),
     open: order.price,
     high: order.price,
     low: order.price,
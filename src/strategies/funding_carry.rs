@@ -0,0 +1,349 @@
+//! 펀딩비 캐리 전략
+//!
+//! 무기한 선물의 예상 펀딩비가 임계치를 넘으면 펀딩을 받는 쪽으로 포지션을 잡는다.
+//! 현물 심볼을 지정하면 현물 매수 + 선물 매도의 델타 중립 포지션(양수 펀딩만),
+//! 지정하지 않으면 선물 단독 방향성 포지션(음수 펀딩이면 롱)으로 운용한다.
+//! 다음 정산 예상 펀딩비가 청산 임계치 아래로 내려가거나 부호가 바뀌면 정산 전에 청산한다.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::funding::FundingRate;
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+use crate::strategies::{ParamUpdate, Strategy};
+
+/// 캐리 포지션 방향 (선물 기준)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CarryPosition {
+    Flat,
+    /// 선물 숏 (양수 펀딩 수취)
+    ShortPerp,
+    /// 선물 롱 (음수 펀딩 수취, 선물 단독 모드만)
+    LongPerp,
+}
+
+impl CarryPosition {
+    /// 펀딩비 부호 대비 수취 방향 (+1 이면 양수 펀딩을 받음)
+    fn carry_sign(self) -> f64 {
+        match self {
+            CarryPosition::Flat => 0.0,
+            CarryPosition::ShortPerp => 1.0,
+            CarryPosition::LongPerp => -1.0,
+        }
+    }
+}
+
+/// 재시작 시 복원하는 캐리 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct CarryState {
+    position: CarryPosition,
+    collected_funding: f64,
+    next_funding_time: Option<i64>,
+    is_active: bool,
+}
+
+/// 펀딩비 캐리 전략
+pub struct FundingCarryStrategy {
+    /// 전략 이름
+    name: String,
+    /// 전략 설명
+    description: String,
+    /// 무기한 선물 심볼
+    symbol: String,
+    /// 헤지용 현물 심볼 (None 이면 선물 단독)
+    spot_symbol: Option<String>,
+    /// 다리별 주문 수량
+    quantity: Decimal,
+    /// 진입 펀딩비 임계치 (정산 1회 기준 비율, 예: 0.0005 = 0.05%)
+    entry_rate: f64,
+    /// 청산 펀딩비 임계치 (수취 방향 기준, 이보다 낮아지면 청산)
+    exit_rate: f64,
+    /// 선물/현물 최신 가격
+    perp_price: Option<Decimal>,
+    spot_price: Option<Decimal>,
+    /// 최근 펀딩 정보
+    funding: Option<FundingRate>,
+    position: CarryPosition,
+    /// 보유 중 정산된 펀딩 추정액 (견적 통화)
+    collected_funding: f64,
+    /// 보유 포지션이 다음에 받을 정산 시각
+    next_funding_time: Option<i64>,
+    /// 다음 get_orders 에서 반환할 주문
+    pending_orders: Vec<Order>,
+    /// 전략 활성 여부
+    is_active: bool,
+}
+
+impl FundingCarryStrategy {
+    /// 새 펀딩비 캐리 전략 생성
+    pub fn new(
+        symbol: impl Into<String>,
+        spot_symbol: Option<String>,
+        quantity: Decimal,
+        entry_rate: f64,
+        exit_rate: f64,
+    ) -> Result<Self, TradingError> {
+        let symbol = symbol.into();
+        if entry_rate <= 0.0 || exit_rate >= entry_rate {
+            return Err(TradingError::InvalidParameter("entry_rate must be positive and above exit_rate".to_string()));
+        }
+        if spot_symbol.as_deref() == Some(symbol.as_str()) {
+            return Err(TradingError::InvalidParameter("spot_symbol must differ from the perpetual symbol".to_string()));
+        }
+        let description = match &spot_symbol {
+            Some(spot) => format!("Delta-neutral funding carry: long {} spot / short {} perpetual", spot, symbol),
+            None => format!("Directional funding carry on the {} perpetual", symbol),
+        };
+
+        Ok(FundingCarryStrategy {
+            name: format!("FUNDING-{}", symbol),
+            description,
+            symbol,
+            spot_symbol,
+            quantity,
+            entry_rate,
+            exit_rate,
+            perp_price: None,
+            spot_price: None,
+            funding: None,
+            position: CarryPosition::Flat,
+            collected_funding: 0.0,
+            next_funding_time: None,
+            pending_orders: Vec::new(),
+            is_active: true,
+        })
+    }
+
+    /// 현재 캐리 포지션
+    pub fn position(&self) -> CarryPosition {
+        self.position
+    }
+
+    /// 보유 중 정산된 펀딩 추정액
+    pub fn collected_funding(&self) -> f64 {
+        self.collected_funding
+    }
+
+    /// 정산 시각이 지났으면 직전 예상 펀딩비로 수취액 누적
+    fn settle(&mut self, funding: &FundingRate) {
+        let (Some(previous), Some(due)) = (&self.funding, self.next_funding_time) else {
+            return;
+        };
+        if self.position != CarryPosition::Flat && funding.next_funding_time > due {
+            let amount = self.position.carry_sign() * previous.funding_rate * to_f64(self.quantity) * previous.mark_price;
+            self.collected_funding += amount;
+            log::info!("{}: funding settled {:.4} (total {:.4})", self.name, amount, self.collected_funding);
+        }
+    }
+
+    /// 예상 펀딩비로 진입/청산 판단
+    fn evaluate(&mut self) {
+        let (Some(funding), Some(perp_price)) = (&self.funding, self.perp_price) else {
+            return;
+        };
+        let rate = funding.funding_rate;
+        let next_funding_time = funding.next_funding_time;
+
+        match self.position {
+            CarryPosition::Flat => {
+                let position = if rate >= self.entry_rate {
+                    CarryPosition::ShortPerp
+                } else if rate <= -self.entry_rate && self.spot_symbol.is_none() {
+                    // 현물 공매도는 불가하므로 음수 펀딩은 선물 단독 모드에서만
+                    CarryPosition::LongPerp
+                } else {
+                    return;
+                };
+                let hedge = match (&self.spot_symbol, self.spot_price) {
+                    (Some(spot), Some(price)) => Some((spot.clone(), price)),
+                    (Some(spot), None) => {
+                        log::debug!("{}: waiting for {} spot price before entering", self.name, spot);
+                        return;
+                    }
+                    (None, _) => None,
+                };
+                let side = if position == CarryPosition::ShortPerp { OrderSide::Sell } else { OrderSide::Buy };
+                if let Some((spot, price)) = hedge {
+                    self.pending_orders.push(Order::new(spot, OrderSide::Buy, OrderType::Market, self.quantity, price));
+                }
+                self.pending_orders.push(Order::new(self.symbol.clone(), side, OrderType::Market, self.quantity, perp_price));
+                self.position = position;
+                self.next_funding_time = Some(next_funding_time);
+                log::info!("{}: enter {:?} at predicted funding {:.4}%", self.name, position, rate * 100.0);
+            }
+            position => {
+                self.next_funding_time = Some(next_funding_time);
+                // 다음 정산에서 받을 펀딩이 임계치 아래(부호 반전 포함)면 정산 전에 청산
+                if position.carry_sign() * rate >= self.exit_rate {
+                    return;
+                }
+                let side = if position == CarryPosition::ShortPerp { OrderSide::Buy } else { OrderSide::Sell };
+                let mut exit = Order::new(self.symbol.clone(), side, OrderType::Market, self.quantity, perp_price);
+                exit.reduce_only = Some(true);
+                self.pending_orders.push(exit);
+                if let (Some(spot), Some(price)) = (&self.spot_symbol, self.spot_price) {
+                    self.pending_orders.push(Order::new(spot.clone(), OrderSide::Sell, OrderType::Market, self.quantity, price));
+                }
+                self.position = CarryPosition::Flat;
+                self.next_funding_time = None;
+                log::info!("{}: unwind carry, predicted funding {:.4}% (collected {:.4})", self.name, rate * 100.0, self.collected_funding);
+            }
+        }
+    }
+}
+
+impl Strategy for FundingCarryStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        if market_data.symbol == self.symbol {
+            self.perp_price = Some(market_data.close);
+        } else if self.spot_symbol.as_deref() == Some(market_data.symbol.as_str()) {
+            self.spot_price = Some(market_data.close);
+        } else {
+            return Ok(());
+        }
+        if self.is_active {
+            self.evaluate();
+        }
+        Ok(())
+    }
+
+    fn update_funding(&mut self, funding: &FundingRate) {
+        if funding.symbol != self.symbol {
+            return;
+        }
+        self.settle(funding);
+        self.funding = Some(funding.clone());
+        if self.is_active {
+            self.evaluate();
+        }
+    }
+
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        Ok(std::mem::take(&mut self.pending_orders))
+    }
+
+    fn status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "position": self.position,
+            "funding_rate": self.funding.as_ref().map(|f| f.funding_rate),
+            "next_funding_time": self.next_funding_time,
+            "collected_funding": self.collected_funding,
+            "entry_rate": self.entry_rate,
+            "exit_rate": self.exit_rate,
+        })
+    }
+
+    /// 진입/청산 펀딩비 임계치 변경
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+        let params = ParamUpdate::new(&self.name, params)?;
+        params.allow(&["entry_rate", "exit_rate"])?;
+        let entry_rate = params.f64("entry_rate")?.unwrap_or(self.entry_rate);
+        let exit_rate = params.f64("exit_rate")?.unwrap_or(self.exit_rate);
+        if entry_rate <= 0.0 || exit_rate >= entry_rate {
+            return Err(params.invalid("entry_rate must be positive and above exit_rate"));
+        }
+
+        self.entry_rate = entry_rate;
+        self.exit_rate = exit_rate;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(CarryState {
+            position: self.position,
+            collected_funding: self.collected_funding,
+            next_funding_time: self.next_funding_time,
+            is_active: self.is_active,
+        }).ok()
+    }
+
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: CarryState = serde_json::from_value(state.clone())?;
+        self.position = state.position;
+        self.collected_funding = state.collected_funding;
+        self.next_funding_time = state.next_funding_time;
+        self.is_active = state.is_active;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    const HOUR: i64 = 3_600_000;
+
+    fn funding(rate: f64, next_funding_time: i64) -> FundingRate {
+        FundingRate {
+            symbol: "BTCUSDT".into(),
+            mark_price: 50_000.0,
+            index_price: 50_000.0,
+            funding_rate: rate,
+            next_funding_time,
+            timestamp: 0,
+        }
+    }
+
+    fn candle(symbol: &str, price: f64) -> MarketData {
+        MarketData::new(symbol, 0, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_funding_carry_hedged_round_trip() {
+        let mut strategy = FundingCarryStrategy::new("BTCUSDT", Some("BTCUSDT_SPOT".into()), dec!(0.1), 0.0005, 0.0001).unwrap();
+        strategy.update(candle("BTCUSDT", 50_000.0)).unwrap();
+        // 현물 가격 전에는 헤지 불가 -> 진입 보류
+        strategy.update_funding(&funding(0.001, 8 * HOUR));
+        assert!(strategy.get_orders().unwrap().is_empty());
+
+        strategy.update(candle("BTCUSDT_SPOT", 49_990.0)).unwrap();
+        let entry = strategy.get_orders().unwrap();
+        assert_eq!(entry.len(), 2);
+        assert_eq!((entry[0].symbol.as_str(), &entry[0].side), ("BTCUSDT_SPOT", &OrderSide::Buy));
+        assert_eq!((entry[1].symbol.as_str(), &entry[1].side), ("BTCUSDT", &OrderSide::Sell));
+        assert_eq!(strategy.position(), CarryPosition::ShortPerp);
+
+        // 정산 후 다음 주기로 넘어가면 직전 예상 펀딩비로 수취액 누적
+        strategy.update_funding(&funding(0.0004, 16 * HOUR));
+        assert!((strategy.collected_funding() - 0.001 * 0.1 * 50_000.0).abs() < 1e-9);
+        assert!(strategy.get_orders().unwrap().is_empty());
+
+        // 예상 펀딩비가 음수로 바뀌면 정산 전에 두 다리 청산
+        strategy.update_funding(&funding(-0.0002, 16 * HOUR));
+        let exit = strategy.get_orders().unwrap();
+        assert_eq!(exit.len(), 2);
+        assert_eq!((exit[0].side.clone(), exit[0].reduce_only), (OrderSide::Buy, Some(true)));
+        assert_eq!((exit[1].symbol.as_str(), &exit[1].side), ("BTCUSDT_SPOT", &OrderSide::Sell));
+        assert_eq!(strategy.position(), CarryPosition::Flat);
+
+        // 헤지 모드는 음수 펀딩에 진입하지 않음, 선물 단독은 롱
+        strategy.update_funding(&funding(-0.002, 24 * HOUR));
+        assert!(strategy.get_orders().unwrap().is_empty());
+        let mut directional = FundingCarryStrategy::new("BTCUSDT", None, dec!(0.1), 0.0005, 0.0).unwrap();
+        directional.update(candle("BTCUSDT", 50_000.0)).unwrap();
+        directional.update_funding(&funding(-0.002, 8 * HOUR));
+        let orders = directional.get_orders().unwrap();
+        assert_eq!((orders.len(), orders[0].side.clone()), (1, OrderSide::Buy));
+        assert_eq!(directional.position(), CarryPosition::LongPerp);
+    }
+}
@@ -2,10 +2,22 @@
 //!
 //! 대량 포지션을 시장에 드러나지 않게 구축하는 전략
 
+use serde::{Deserialize, Serialize};
+
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
+use crate::models::order_book::OrderBook;
 use crate::models::order::{Order, OrderSide, OrderType};
-use crate::strategies::Strategy;
+use crate::strategies::{execution_progress, ExecutionMode, ParamUpdate, Strategy, BOOK_DEPTH_LEVELS};
+
+/// 재시작 시 복원하는 Iceberg 진행 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct IcebergState {
+    executed_quantity: Decimal,
+    is_active: bool,
+}
 
 /// Iceberg 매매 전략
 pub struct IcebergStrategy {
@@ -18,19 +30,27 @@ pub struct IcebergStrategy {
     /// 매매 방향
     side: OrderSide,
     /// 목표 총 수량
-    total_quantity: f64,
+    total_quantity: Decimal,
     /// 지정가 가격
-    limit_price: f64,
+    limit_price: Decimal,
     /// 시장에 노출할 부분 수량
-    display_quantity: f64,
+    display_quantity: Decimal,
     /// 이미 실행한 수량
-    executed_quantity: f64,
+    executed_quantity: Decimal,
     /// 전략 활성 여부
     is_active: bool,
     /// 현재 시장 데이터
     current_market_data: Option<MarketData>,
     /// 가격 조건 충족 여부
     price_condition_met: bool,
+    /// 실행 방식 설정
+    execution_mode: ExecutionMode,
+    /// 거래소 네이티브 iceberg 사용 여부 (거래소 기능 적용 후 결정)
+    native: bool,
+    /// 최신 호가창 (있으면 가격 조건과 노출 수량에 사용)
+    order_book: Option<OrderBook>,
+    /// 노출 수량 상한 - 상대 호가 상위 단계 수량 대비 비율
+    book_participation: Option<f64>,
 }
 
 impl IcebergStrategy {
@@ -38,9 +58,9 @@ impl IcebergStrategy {
     pub fn new(
         symbol: impl Into<String>,
         side: OrderSide,
-        total_quantity: f64,
-        limit_price: f64,
-        display_quantity: f64,
+        total_quantity: Decimal,
+        limit_price: Decimal,
+        display_quantity: Decimal,
     ) -> Self {
         let symbol_str = symbol.into();
         let display_quantity = display_quantity.min(total_quantity);
@@ -53,16 +73,42 @@ impl IcebergStrategy {
             total_quantity,
             limit_price,
             display_quantity,
-            executed_quantity: 0.0,
+            executed_quantity: Decimal::ZERO,
             is_active: true,
             current_market_data: None,
             price_condition_met: false,
+            execution_mode: ExecutionMode::ClientSide,
+            native: false,
+            order_book: None,
+            book_participation: None,
         }
     }
+
+    /// 노출 수량을 상대 호가 상위 단계 수량의 rate 배 이하로 제한 (호가창 수신 시)
+    pub fn with_book_participation(mut self, rate: f64) -> Self {
+        self.book_participation = Some(rate);
+        self
+    }
+
+    /// 실행 방식 설정 (PreferNative 는 거래소 기능 적용 시 확정)
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+
+    /// 네이티브 실행 여부
+    pub fn is_native(&self) -> bool {
+        self.native
+    }
     
-    /// 가격 조건 충족 여부 확인
+    /// 가격 조건 충족 여부 확인 - 호가창이 있으면 체결 상대 최우선 호가 기준
     fn check_price_condition(&self) -> bool {
-        if let Some(data) = &self.current_market_data {
+        if let Some(touch) = self.order_book.as_ref().and_then(|b| b.opposite_levels(&self.side).first()) {
+            match self.side {
+                OrderSide::Buy => to_decimal(touch.price) <= self.limit_price,
+                OrderSide::Sell => to_decimal(touch.price) >= self.limit_price,
+            }
+        } else if let Some(data) = &self.current_market_data {
             match self.side {
                 OrderSide::Buy => data.close <= self.limit_price,
                 OrderSide::Sell => data.close >= self.limit_price,
@@ -101,13 +147,33 @@ impl Strategy for IcebergStrategy {
         // 남은 수량 계산
         let remaining = self.total_quantity - self.executed_quantity;
         
-        if remaining <= 0.0 {
+        if remaining <= Decimal::ZERO {
             self.is_active = false;
             return Ok(Vec::new());
         }
         
-        // 다음 주문 크기 계산
-        let next_quantity = self.display_quantity.min(remaining);
+        // 네이티브 iceberg: 남은 전체 수량을 노출 수량과 함께 한 번에 위임
+        if self.native {
+            let order = Order::new(
+                self.symbol.clone(),
+                self.side.clone(),
+                OrderType::Limit,
+                remaining,
+                self.limit_price,
+            ).with_iceberg_qty(self.display_quantity);
+            self.executed_quantity = self.total_quantity;
+            self.is_active = false;
+            return Ok(vec![order]);
+        }
+        
+        // 다음 주문 크기 계산 (호가창이 있으면 보이는 유동성 대비 상한 적용)
+        let mut next_quantity = self.display_quantity.min(remaining);
+        if let (Some(rate), Some(book)) = (self.book_participation, &self.order_book) {
+            let depth = to_decimal(book.depth(&self.side, BOOK_DEPTH_LEVELS));
+            if depth > Decimal::ZERO {
+                next_quantity = next_quantity.min(depth * to_decimal(rate));
+            }
+        }
         
         // 지정가 주문 생성
         let order = Order::new(
@@ -129,6 +195,38 @@ impl Strategy for IcebergStrategy {
         Ok(vec![order])
     }
     
+    fn status(&self) -> serde_json::Value {
+        let mut status = execution_progress(self.executed_quantity, self.total_quantity);
+        status["limit_price"] = serde_json::json!(self.limit_price);
+        status["display_quantity"] = serde_json::json!(self.display_quantity);
+        status["price_condition_met"] = serde_json::json!(self.price_condition_met);
+        status["native"] = serde_json::json!(self.native);
+        status
+    }
+
+    /// 지정가, 노출 수량(총 수량 이하로 제한), 참여율(null 이면 해제) 변경
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+        let params = ParamUpdate::new(&self.name, params)?;
+        params.allow(&["limit_price", "display_quantity", "book_participation"])?;
+        if self.native {
+            return Err(params.invalid("native iceberg order is already delegated to the exchange"));
+        }
+        let limit_price = params.decimal("limit_price")?;
+        let display_quantity = params.decimal("display_quantity")?;
+        let participation = params.participation("book_participation")?;
+
+        if let Some(price) = limit_price {
+            self.limit_price = price;
+        }
+        if let Some(quantity) = display_quantity {
+            self.display_quantity = quantity.min(self.total_quantity);
+        }
+        if let Some(rate) = participation {
+            self.book_participation = rate;
+        }
+        Ok(())
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -136,11 +234,41 @@ impl Strategy for IcebergStrategy {
     fn description(&self) -> &str {
         &self.description
     }
+    
+    fn update_order_book(&mut self, book: &OrderBook) {
+        if book.symbol == self.symbol {
+            self.order_book = Some(book.clone());
+            self.price_condition_met = self.check_price_condition();
+        }
+    }
+    
+    fn is_execution_strategy(&self) -> bool {
+        true
+    }
+
+    fn apply_capabilities(&mut self, capabilities: &ExchangeCapabilities) {
+        self.native = self.execution_mode.resolve(&self.name, OrderType::Iceberg, capabilities);
+    }
+    
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(IcebergState {
+            executed_quantity: self.executed_quantity,
+            is_active: self.is_active,
+        }).ok()
+    }
+    
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: IcebergState = serde_json::from_value(state.clone())?;
+        self.executed_quantity = state.executed_quantity;
+        self.is_active = state.is_active;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::decimal::dec;
     
     #[test]
     fn test_iceberg_strategy() {
@@ -148,20 +276,20 @@ mod tests {
         let mut strategy = IcebergStrategy::new(
             "BTCUSDT",
             OrderSide::Buy,
-            10.0,
-            50000.0,
-            1.0,
+            dec!(10),
+            dec!(50000),
+            dec!(1),
         );
         
         // 가격 조건 충족하는 시장 데이터 추가
         let market_data = MarketData {
             symbol: "BTCUSDT".to_string(),
-            timestamp: 1000,
-            open: 50100.0,
-            high: 50200.0,
-            low: 49900.0,
-            close: 49900.0, // 매수 조건 충족 (현재가 < 지정가)
-            volume: 10.0,
+            timestamp: 1000.into(),
+            open: dec!(50100),
+            high: dec!(50200),
+            low: dec!(49900),
+            close: dec!(49900), // 매수 조건 충족 (현재가 < 지정가)
+            volume: dec!(10),
         };
         
         let result = strategy.update(market_data);
@@ -176,23 +304,58 @@ mod tests {
         assert_eq!(first_order.symbol, "BTCUSDT");
         assert_eq!(first_order.side, OrderSide::Buy);
         assert_eq!(first_order.order_type, OrderType::Limit);
-        assert_eq!(first_order.quantity, 1.0);
-        assert_eq!(first_order.price, 50000.0);
-        assert_eq!(first_order.iceberg_qty, Some(1.0));
+        assert_eq!(first_order.quantity, dec!(1));
+        assert_eq!(first_order.price, dec!(50000));
+        assert_eq!(first_order.iceberg_qty, Some(dec!(1)));
         
         // 가격 조건 불충족 시 주문 생성 안함 확인
         let market_data2 = MarketData {
             symbol: "BTCUSDT".to_string(),
-            timestamp: 2000,
-            open: 50100.0,
-            high: 50200.0,
-            low: 50100.0,
-            close: 50100.0, // 매수 조건 불충족 (현재가 > 지정가)
-            volume: 15.0,
+            timestamp: 2000.into(),
+            open: dec!(50100),
+            high: dec!(50200),
+            low: dec!(50100),
+            close: dec!(50100), // 매수 조건 불충족 (현재가 > 지정가)
+            volume: dec!(15),
         };
         
         strategy.update(market_data2).unwrap();
         let orders2 = strategy.get_orders().unwrap();
         assert!(orders2.is_empty());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_native_iceberg_fallback() {
+        let market_data = MarketData {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: 1000.into(),
+            open: dec!(49900),
+            high: dec!(49900),
+            low: dec!(49900),
+            close: dec!(49900),
+            volume: dec!(10),
+        };
+        
+        // 네이티브 지원 거래소: 전체 수량을 단일 iceberg 주문으로 위임
+        let mut native = IcebergStrategy::new("BTCUSDT", OrderSide::Buy, dec!(10), dec!(50000), dec!(1))
+            .with_execution_mode(ExecutionMode::PreferNative);
+        native.apply_capabilities(&ExchangeCapabilities::default());
+        assert!(native.is_native());
+        native.update(market_data.clone()).unwrap();
+        let orders = native.get_orders().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_type, OrderType::Iceberg);
+        assert_eq!(orders[0].quantity, dec!(10));
+        assert_eq!(orders[0].iceberg_qty, Some(dec!(1)));
+        assert!(native.get_orders().unwrap().is_empty());
+        
+        // 미지원 거래소: 클라이언트 분할로 자동 대체
+        let caps = ExchangeCapabilities { native_iceberg: false, ..ExchangeCapabilities::default() };
+        let mut fallback = IcebergStrategy::new("BTCUSDT", OrderSide::Buy, dec!(10), dec!(50000), dec!(1))
+            .with_execution_mode(ExecutionMode::PreferNative);
+        fallback.apply_capabilities(&caps);
+        assert!(!fallback.is_native());
+        fallback.update(market_data).unwrap();
+        assert_eq!(fallback.get_orders().unwrap()[0].quantity, dec!(1));
+    }
+}
@@ -0,0 +1,276 @@
+//! Implementation Shortfall 전략
+//!
+//! 도착 가격(실행 시작 시점 가격) 대비 비용을 줄이도록 긴급도와 예상 시장 충격에 따라
+//! 앞쪽 분할에 더 많은 수량을 싣는 실행 전략
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::execution_analyzer::ExecutionAnalyzer;
+use crate::core::is_splitter::shortfall_schedule;
+use crate::error::TradingError;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderId, OrderSide, OrderType};
+use crate::models::trade::Trade;
+use crate::strategies::{execution_progress, Strategy};
+
+/// 재시작 시 복원하는 IS 진행 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct ShortfallState {
+    executed_quantity: Decimal,
+    schedule_start: Option<i64>,
+    arrival_price: Option<f64>,
+    last_slice_index: Option<usize>,
+    is_active: bool,
+}
+
+/// Implementation Shortfall 실행 전략
+pub struct ImplementationShortfallStrategy {
+    /// 전략 이름
+    name: String,
+    /// 전략 설명
+    description: String,
+    /// 거래 심볼
+    symbol: String,
+    /// 매매 방향
+    side: OrderSide,
+    /// 목표 총 수량
+    total_quantity: Decimal,
+    /// 분할 수
+    num_slices: usize,
+    /// 분할 간격 (밀리초)
+    slice_interval: i64,
+    /// 분할별 실행 비율 (긴급도/시장 충격으로 결정)
+    schedule: Vec<f64>,
+    /// 이미 실행한 수량
+    executed_quantity: Decimal,
+    /// 현재 시장 데이터
+    current_market_data: Option<MarketData>,
+    /// 전략 활성 여부
+    is_active: bool,
+    /// 스케줄 시작 시각 (첫 시세 수신 시점)
+    schedule_start: Option<i64>,
+    /// 마지막으로 처리한 분할 인덱스
+    last_slice_index: Option<usize>,
+    /// 도착 가격 대비 실행 성과
+    analyzer: ExecutionAnalyzer,
+}
+
+impl ImplementationShortfallStrategy {
+    /// 새 IS 전략 생성 (`urgency` 0 이면 균등 분할)
+    pub fn new(
+        symbol: impl Into<String>,
+        side: OrderSide,
+        total_quantity: Decimal,
+        execution_interval: i64,
+        num_slices: usize,
+        urgency: f64,
+    ) -> Self {
+        let symbol_str = symbol.into();
+        let num_slices = num_slices.max(1);
+
+        ImplementationShortfallStrategy {
+            name: format!("IS-{}", symbol_str),
+            description: "Implementation Shortfall (arrival price) execution strategy".to_string(),
+            analyzer: ExecutionAnalyzer::new(symbol_str.clone()),
+            symbol: symbol_str,
+            side,
+            total_quantity,
+            num_slices,
+            slice_interval: execution_interval / num_slices as i64,
+            schedule: shortfall_schedule(num_slices, urgency, 20.0, 10.0),
+            executed_quantity: Decimal::ZERO,
+            current_market_data: None,
+            is_active: true,
+            schedule_start: None,
+            last_slice_index: None,
+        }
+    }
+
+    /// 시장 모형으로 스케줄 재계산 (분할 구간당 변동성, 예상 시장 충격; bps)
+    pub fn with_market_model(mut self, urgency: f64, volatility_bps: f64, impact_bps: f64) -> Self {
+        self.schedule = shortfall_schedule(self.num_slices, urgency, volatility_bps, impact_bps);
+        self
+    }
+
+    /// 분할별 실행 비율
+    pub fn schedule(&self) -> &[f64] {
+        &self.schedule
+    }
+
+    /// 도착 가격 대비 실현 슬리피지 (bps, 불리한 방향이 양수)
+    pub fn arrival_slippage_bps(&self) -> Option<f64> {
+        self.analyzer.arrival_slippage_bps()
+    }
+}
+
+impl Strategy for ImplementationShortfallStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        if market_data.symbol != self.symbol {
+            return Ok(());
+        }
+
+        // 첫 시세가 도착 가격 / 스케줄 시작점
+        if self.schedule_start.is_none() {
+            self.schedule_start = Some(market_data.timestamp.as_millis());
+            self.analyzer.set_arrival_price(market_data.close_f64());
+        }
+        if self.is_active {
+            self.analyzer.add_market_data(market_data.clone());
+        }
+        self.current_market_data = Some(market_data);
+
+        Ok(())
+    }
+
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        if !self.is_active || self.executed_quantity >= self.total_quantity {
+            return Ok(Vec::new());
+        }
+        let (Some(market_data), Some(start)) = (&self.current_market_data, self.schedule_start) else {
+            return Ok(Vec::new());
+        };
+        let current_time = market_data.timestamp.as_millis();
+
+        // 현재 분할 인덱스 (실행 구간 종료 후에는 마지막 분할)
+        let index = (((current_time - start) / self.slice_interval.max(1)).max(0) as usize).min(self.num_slices - 1);
+        if self.last_slice_index.is_some_and(|last| index <= last) {
+            return Ok(Vec::new());
+        }
+        self.last_slice_index = Some(index);
+
+        // 누적 목표 대비 부족분 주문 (놓친 분할은 따라잡음, 마지막 분할은 잔량 전부)
+        let remaining = self.total_quantity - self.executed_quantity;
+        let target = if index == self.num_slices - 1 {
+            self.total_quantity
+        } else {
+            self.total_quantity * to_decimal(self.schedule[..=index].iter().sum::<f64>())
+        };
+        let quantity = (target - self.executed_quantity).min(remaining);
+        if quantity <= Decimal::ZERO {
+            return Ok(Vec::new());
+        }
+
+        let order = Order::new(
+            self.symbol.clone(),
+            self.side.clone(),
+            OrderType::Market,
+            quantity,
+            market_data.close,
+        );
+
+        // 시장가 분할은 현재가 체결로 성과에 기록
+        let fill_id = format!("{}-{}", self.name, index);
+        self.analyzer.add_trade(Trade::new(
+            fill_id.clone(),
+            self.symbol.clone(),
+            market_data.close,
+            quantity,
+            market_data.timestamp,
+            OrderId(fill_id),
+            self.side.clone(),
+        ));
+        self.executed_quantity += quantity;
+        if self.total_quantity - self.executed_quantity <= Decimal::ZERO {
+            self.is_active = false;
+            if let Some(bps) = self.analyzer.arrival_slippage_bps() {
+                log::info!("{} finished: {:.2}bps vs arrival", self.name, bps);
+            }
+        }
+
+        Ok(vec![order])
+    }
+
+    fn status(&self) -> serde_json::Value {
+        let mut status = execution_progress(self.executed_quantity, self.total_quantity);
+        status["slices"] = serde_json::json!(self.num_slices);
+        status["slice_index"] = serde_json::json!(self.last_slice_index);
+        status
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+
+    fn is_execution_strategy(&self) -> bool {
+        true
+    }
+
+    fn execution_report(&self) -> Option<HashMap<String, f64>> {
+        Some(self.analyzer.get_report())
+    }
+
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(ShortfallState {
+            executed_quantity: self.executed_quantity,
+            schedule_start: self.schedule_start,
+            arrival_price: self.analyzer.arrival_price(),
+            last_slice_index: self.last_slice_index,
+            is_active: self.is_active,
+        }).ok()
+    }
+
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: ShortfallState = serde_json::from_value(state.clone())?;
+        self.executed_quantity = state.executed_quantity;
+        self.schedule_start = state.schedule_start;
+        self.last_slice_index = state.last_slice_index;
+        self.is_active = state.is_active;
+        if let Some(price) = state.arrival_price {
+            self.analyzer.set_arrival_price(price);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+
+    fn candle(minute: i64, price: f64) -> MarketData {
+        MarketData::new("BTCUSDT", minute * 60_000, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_implementation_shortfall_front_loads() {
+        // 5분 동안 5분할, 긴급도가 높아 앞쪽 분할이 TWAP(0.2)보다 큼
+        let mut strategy = ImplementationShortfallStrategy::new("BTCUSDT", OrderSide::Buy, dec!(1), 5 * 60_000, 5, 0.01);
+        let uniform = ImplementationShortfallStrategy::new("BTCUSDT", OrderSide::Buy, dec!(1), 5 * 60_000, 5, 0.0);
+        assert!((uniform.schedule()[0] - 0.2).abs() < 1e-12);
+        assert!(strategy.schedule()[0] > 0.2);
+
+        // 매수 중 가격 상승 -> 도착가(100) 대비 비용 발생
+        let mut quantities = Vec::new();
+        for minute in 0..5 {
+            strategy.update(candle(minute, 100.0 + minute as f64)).unwrap();
+            quantities.push(strategy.get_orders().unwrap().iter().map(|o| o.quantity).sum::<Decimal>());
+            // 같은 분할 안에서는 추가 주문 없음
+            assert!(strategy.get_orders().unwrap().is_empty());
+        }
+        assert!(quantities.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(quantities.iter().sum::<Decimal>(), dec!(1));
+        assert!(!strategy.is_active());
+
+        let report = strategy.execution_report().unwrap();
+        assert_eq!(report["arrival_price"], 100.0);
+        let bps = strategy.arrival_slippage_bps().unwrap();
+        assert!(bps > 0.0 && (report["arrival_slippage_bps"] - bps).abs() < 1e-12);
+        // 앞쪽에 몰아 실행했으므로 같은 구간 균등 분할(평균 102)보다 비용이 작음
+        assert!(bps < 200.0);
+    }
+}
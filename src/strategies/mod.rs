@@ -2,14 +2,147 @@ pub mod vwap;
 pub mod iceberg;
 pub mod trailing_stop;
 pub mod twap;
+pub mod implementation_shortfall;
+pub mod pairs;
+pub mod funding_carry;
+pub mod prediction;
 pub mod combined;
 pub mod technical;
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 use crate::error::TradingError;
+use crate::indicators::IndicatorResult;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::market_data::external::ExternalInputs;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::funding::FundingRate;
 use crate::models::market_data::MarketData;
-use crate::models::order::Order;
+use crate::models::order_book::OrderBook;
+use crate::models::order::{Order, OrderType};
+use crate::signals::entry_exits::EntryExitsConfig;
+
+/// 호가창 기반 슬라이스 산정에 사용하는 상대 호가 단계 수
+pub const BOOK_DEPTH_LEVELS: usize = 5;
+
+/// 실행 전략의 주문 실행 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// 클라이언트에서 분할 주문
+    #[default]
+    ClientSide,
+    /// 거래소 네이티브 주문(icebergQty, TWAP/VWAP 알고 주문)에 위임, 미지원 시 클라이언트 분할로 대체
+    PreferNative,
+}
+
+impl ExecutionMode {
+    /// 거래소 기능에 따라 실제로 네이티브 실행을 사용할지 결정
+    pub fn resolve(self, strategy: &str, order_type: OrderType, capabilities: &ExchangeCapabilities) -> bool {
+        match self {
+            ExecutionMode::ClientSide => false,
+            ExecutionMode::PreferNative => {
+                let native = capabilities.supports_native(&order_type);
+                if !native {
+                    log::info!("{}: native {:?} not supported by exchange, falling back to client-side slicing", strategy, order_type);
+                }
+                native
+            }
+        }
+    }
+}
+
+/// 실행 전략 공통 진행 상태 - 각 전략이 자기 필드를 덧붙여 `status()` 로 보고
+pub fn execution_progress(executed: Decimal, total: Decimal) -> serde_json::Value {
+    let progress = if total > Decimal::ZERO { crate::models::decimal::to_f64(executed / total).min(1.0) } else { 0.0 };
+    serde_json::json!({
+        "executed_quantity": executed,
+        "total_quantity": total,
+        "remaining_quantity": (total - executed).max(Decimal::ZERO),
+        "progress": progress,
+    })
+}
+
+/// 실행 중 파라미터 변경 요청 (`PATCH /strategies/:name` 본문)
+///
+/// 전략마다 허용 키를 검사하고 값 형식을 읽어 준다. 구현은 모든 값을 검증한 뒤에 한꺼번에 반영해
+/// 실패 시 아무것도 바뀌지 않게 한다.
+pub struct ParamUpdate {
+    strategy: String,
+    params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ParamUpdate {
+    pub fn new(strategy: &str, params: serde_json::Value) -> Result<Self, TradingError> {
+        match params {
+            serde_json::Value::Object(params) if !params.is_empty() => Ok(ParamUpdate { strategy: strategy.to_string(), params }),
+            serde_json::Value::Object(_) => Err(TradingError::InvalidParameter(format!("{}: no parameters given", strategy))),
+            _ => Err(TradingError::InvalidParameter(format!("{}: parameters must be a JSON object", strategy))),
+        }
+    }
+
+    /// 허용되지 않은 키가 있으면 거부 (지표 기간처럼 전략 이름/누적 상태에 묶인 값은 재생성 필요)
+    pub fn allow(&self, allowed: &[&str]) -> Result<(), TradingError> {
+        match self.params.keys().find(|key| !allowed.contains(&key.as_str())) {
+            Some(key) => Err(self.invalid(format!("'{}' cannot be changed while running (tunable: {})", key, allowed.join(", ")))),
+            None => Ok(()),
+        }
+    }
+
+    /// 원본 값 (복합 전략의 다리별 파라미터 객체 등)
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.params.get(key)
+    }
+
+    pub fn f64(&self, key: &str) -> Result<Option<f64>, TradingError> {
+        match self.params.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_f64().filter(|v| v.is_finite()).map(Some).ok_or_else(|| self.invalid(format!("{} must be a number", key))),
+        }
+    }
+
+    /// 양수만 허용
+    pub fn positive(&self, key: &str) -> Result<Option<f64>, TradingError> {
+        match self.f64(key)? {
+            Some(v) if v <= 0.0 => Err(self.invalid(format!("{} must be positive", key))),
+            v => Ok(v),
+        }
+    }
+
+    pub fn decimal(&self, key: &str) -> Result<Option<Decimal>, TradingError> {
+        Ok(self.positive(key)?.map(to_decimal))
+    }
+
+    pub fn usize(&self, key: &str) -> Result<Option<usize>, TradingError> {
+        match self.params.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_u64().filter(|v| *v > 0).map(|v| Some(v as usize)).ok_or_else(|| self.invalid(format!("{} must be a positive integer", key))),
+        }
+    }
+
+    /// null 이면 해제 (`Some(None)`)
+    pub fn optional_f64(&self, key: &str) -> Result<Option<Option<f64>>, TradingError> {
+        match self.params.get(key) {
+            Some(serde_json::Value::Null) => Ok(Some(None)),
+            _ => self.positive(key).map(|v| v.map(Some)),
+        }
+    }
+
+    /// 호가 참여율 (0, 1], null 이면 해제
+    pub fn participation(&self, key: &str) -> Result<Option<Option<f64>>, TradingError> {
+        match self.optional_f64(key)? {
+            Some(Some(rate)) if rate > 1.0 => Err(self.invalid(format!("{} must be in (0, 1]", key))),
+            rate => Ok(rate),
+        }
+    }
+
+    pub fn invalid(&self, message: impl std::fmt::Display) -> TradingError {
+        TradingError::InvalidParameter(format!("{}: {}", self.strategy, message))
+    }
+}
 
 /// 트레이딩 전략 인터페이스
 pub trait Strategy: Send + Sync {
@@ -25,11 +158,69 @@ pub trait Strategy: Send + Sync {
     /// 전략 설명 가져오기
     fn description(&self) -> &str;
 
+    /// 여러 심볼을 함께 보는 전략의 대상 심볼 (비어 있으면 단일 심볼 전략으로 update 만 호출)
+    fn symbols(&self) -> Vec<String> { Vec::new() }
+
+    /// 대상 심볼 중 하나가 갱신될 때 심볼별 최신 시세 스냅샷으로 호출
+    fn update_multi(&mut self, _snapshot: &HashMap<String, MarketData>) -> Result<(), TradingError> { Ok(()) }
+
+    /// 전략 시작 - StrategyManager 에 등록될 때 한 번 호출 (오류 시 등록 거부)
+    fn on_start(&mut self) -> Result<(), TradingError> { Ok(()) }
+
+    /// 전략 정지 - 제거되거나 프로세스가 종료될 때 호출 (미체결 주문 취소는 호출 측에서 처리)
+    fn on_stop(&mut self) {}
+
     /// 활성화 여부
     fn is_active(&self) -> bool { true }
 
     /// 활성화 설정
     fn set_active(&mut self, _active: bool) {}
+
+    /// 호가창 갱신 (호가를 참조하지 않는 전략은 무시)
+    fn update_order_book(&mut self, _book: &OrderBook) {}
+
+    /// 무기한 선물 펀딩 정보 갱신 (펀딩을 참조하지 않는 전략은 무시)
+    fn update_funding(&mut self, _funding: &FundingRate) {}
+
+    /// 외부 시계열(심리 지수, 온체인 등) 갱신 - 시세 업데이트 직전에 해당 시각 기준 값으로 호출
+    fn update_external(&mut self, _inputs: &ExternalInputs) {}
+
+    /// 자본 예산 중 남은 금액 - 주문 생성 직전에 호출 (수량이 고정된 전략은 무시)
+    fn set_available_capital(&mut self, _capital: f64) {}
+
+    /// 거래소 기능 적용 (네이티브 실행 가능 여부 결정)
+    fn apply_capabilities(&mut self, _capabilities: &ExchangeCapabilities) {}
+
+    /// 실행 성과 (도착 가격/VWAP/TWAP 대비 슬리피지 등, 실행 전략만 제공)
+    fn execution_report(&self) -> Option<HashMap<String, f64>> { None }
+
+    /// 정해진 총 수량을 나눠 내는 실행 전략인지 (TWAP/VWAP/IS/아이스버그) - 분할 주문은 중복 신호로 보지 않음
+    fn is_execution_strategy(&self) -> bool { false }
+
+    /// 재시작 후 이어서 실행하기 위한 런타임 상태 (상태가 없는 전략은 None)
+    fn export_state(&self) -> Option<serde_json::Value> { None }
+
+    /// export_state 로 저장한 상태 복원
+    fn restore_state(&mut self, _state: &serde_json::Value) -> Result<(), TradingError> { Ok(()) }
+
+    /// 실행 상태 보고 (실행 수량, 지표 값, 트리거 가격 등 - `GET /strategies/:name/status`, 보고할 상태가 없으면 Null)
+    fn status(&self) -> serde_json::Value { serde_json::Value::Null }
+
+    /// 지표 기반 전략의 현재 (지표 이름, 결과) - 복합 전략의 신호 집계 입력 (지표가 없으면 빈 목록)
+    fn indicator_results(&self) -> Vec<(String, IndicatorResult)> { Vec::new() }
+
+    /// 진입 주문에 붙일 손절/익절 (진입 체결 후 OCO 브래킷으로 등록) - None 이면 해제, 지표 신호 전략만 지원
+    fn set_entry_exits(&mut self, config: Option<EntryExitsConfig>) -> Result<(), TradingError> {
+        match config {
+            Some(_) => Err(TradingError::InvalidParameter(format!("{}: entry exits are not supported", self.name()))),
+            None => Ok(()),
+        }
+    }
+
+    /// 실행 중 파라미터 변경 (임계치, 참여율, 트레일링 간격 등) - 진행 상태는 유지, 검증 실패 시 변경 없음
+    fn update_params(&mut self, _params: serde_json::Value) -> Result<(), TradingError> {
+        Err(TradingError::InvalidParameter(format!("{}: live parameter updates are not supported", self.name())))
+    }
 }
 
 /// 전략 팩토리 인터페이스
@@ -46,4 +237,78 @@ pub use vwap::VwapStrategy;
 pub use iceberg::IcebergStrategy;
 pub use trailing_stop::TrailingStopStrategy;
 pub use twap::TwapStrategy;
+pub use implementation_shortfall::ImplementationShortfallStrategy;
+pub use pairs::PairsTradingStrategy;
+pub use funding_carry::FundingCarryStrategy;
+pub use prediction::{PredictionSignalStrategy, TaConfirmation};
 pub use combined::CombinedStrategy;
+
+/// 설정의 전략 명세로 전략 인스턴스 생성
+pub fn build_strategy(spec: &crate::config::StrategySpec) -> Result<Box<dyn Strategy>, TradingError> {
+    use crate::config::StrategySpec;
+    use technical::TechnicalStrategy;
+
+    spec.validate().map_err(TradingError::InvalidStrategy)?;
+    let strategy: Box<dyn Strategy> = match spec.clone() {
+        StrategySpec::MaCrossover { symbol, fast_period, slow_period } =>
+            Box::new(TechnicalStrategy::ma_crossover(symbol, fast_period, slow_period)?),
+        StrategySpec::Rsi { symbol, period, oversold, overbought } =>
+            Box::new(TechnicalStrategy::rsi(symbol, period, oversold, overbought)?),
+        StrategySpec::Macd { symbol, fast_period, slow_period, signal_period } =>
+            Box::new(TechnicalStrategy::macd(symbol, fast_period, slow_period, signal_period)?),
+        StrategySpec::Bollinger { symbol, period, std_dev } =>
+            Box::new(TechnicalStrategy::bollinger(symbol, period, std_dev)?),
+        StrategySpec::Ichimoku { symbol, tenkan_period, kijun_period, senkou_b_period } =>
+            Box::new(TechnicalStrategy::ichimoku(symbol, tenkan_period, kijun_period, senkou_b_period)?),
+        StrategySpec::MultiIndicator { symbol, aggregation } =>
+            Box::new(TechnicalStrategy::multi_indicator(symbol, aggregation)?),
+        StrategySpec::RsiTwap { symbol, period, oversold, overbought, twap_minutes, aggregation } =>
+            Box::new(CombinedStrategy::rsi_twap(symbol, period, oversold, overbought, twap_minutes)?.with_aggregation(aggregation)?),
+        StrategySpec::MacdVwap { symbol, fast_period, slow_period, signal_period, participation_rate, aggregation } =>
+            Box::new(CombinedStrategy::macd_vwap(symbol, fast_period, slow_period, signal_period, participation_rate)?.with_aggregation(aggregation)?),
+        StrategySpec::MaCrossoverIceberg { symbol, fast_period, slow_period, display_size, aggregation } =>
+            Box::new(CombinedStrategy::ma_crossover_iceberg(symbol, fast_period, slow_period, display_size)?.with_aggregation(aggregation)?),
+        StrategySpec::Vwap { symbol, side, quantity, execution_interval_ms, vwap_window, execution_mode, book_participation } => {
+            let strategy = VwapStrategy::new(symbol, side, to_decimal(quantity), execution_interval_ms, vwap_window).with_execution_mode(execution_mode);
+            Box::new(match book_participation { Some(rate) => strategy.with_book_participation(rate), None => strategy })
+        }
+        StrategySpec::Twap { symbol, side, quantity, execution_interval_ms, slices, execution_mode } =>
+            Box::new(TwapStrategy::new(symbol, side, to_decimal(quantity), execution_interval_ms, slices).with_execution_mode(execution_mode)),
+        StrategySpec::ImplementationShortfall { symbol, side, quantity, execution_interval_ms, slices, urgency, volatility_bps, impact_bps } =>
+            Box::new(ImplementationShortfallStrategy::new(symbol, side, to_decimal(quantity), execution_interval_ms, slices, urgency)
+                .with_market_model(urgency, volatility_bps, impact_bps)),
+        StrategySpec::Pairs { symbol, pair_symbol, quantity, lookback, entry_z, exit_z } =>
+            Box::new(PairsTradingStrategy::new(symbol, pair_symbol, to_decimal(quantity), lookback, entry_z, exit_z)?),
+        StrategySpec::FundingCarry { symbol, spot_symbol, quantity, entry_rate, exit_rate } =>
+            Box::new(FundingCarryStrategy::new(symbol, spot_symbol, to_decimal(quantity), entry_rate, exit_rate)?),
+        StrategySpec::Iceberg { symbol, side, total_quantity, limit_price, display_quantity, execution_mode, book_participation } => {
+            let strategy = IcebergStrategy::new(symbol, side, to_decimal(total_quantity), to_decimal(limit_price), to_decimal(display_quantity)).with_execution_mode(execution_mode);
+            Box::new(match book_participation { Some(rate) => strategy.with_book_participation(rate), None => strategy })
+        }
+        StrategySpec::TrailingStop { symbol, side, quantity, trailing_delta, activation_price } =>
+            Box::new(TrailingStopStrategy::new(symbol, side, to_decimal(quantity), trailing_delta, activation_price.map(to_decimal))),
+        StrategySpec::MultiTimeframe { symbol, entry, trend, entry_timeframe, trend_timeframe } => {
+            let leg = |spec: StrategySpec| -> Result<TechnicalStrategy, TradingError> {
+                match spec {
+                    StrategySpec::MaCrossover { symbol, fast_period, slow_period } => TechnicalStrategy::ma_crossover(symbol, fast_period, slow_period),
+                    StrategySpec::Rsi { symbol, period, oversold, overbought } => TechnicalStrategy::rsi(symbol, period, oversold, overbought),
+                    StrategySpec::Macd { symbol, fast_period, slow_period, signal_period } => TechnicalStrategy::macd(symbol, fast_period, slow_period, signal_period),
+                    StrategySpec::Bollinger { symbol, period, std_dev } => TechnicalStrategy::bollinger(symbol, period, std_dev),
+                    StrategySpec::Ichimoku { symbol, tenkan_period, kijun_period, senkou_b_period } => TechnicalStrategy::ichimoku(symbol, tenkan_period, kijun_period, senkou_b_period),
+                    other => Err(TradingError::InvalidStrategy(format!("{:?} cannot be a timeframe leg", other))),
+                }
+            };
+            Box::new(TechnicalStrategy::multi_timeframe(
+                leg(entry.to_spec(&symbol))?,
+                leg(trend.to_spec(&symbol))?,
+                &entry_timeframe,
+                &trend_timeframe,
+            )?)
+        }
+        StrategySpec::PredictionTa { symbol, quantity, min_confidence, confirmation, aggregation } => {
+            let signal = PredictionSignalStrategy::new(symbol, to_decimal(quantity), min_confidence)?;
+            Box::new(CombinedStrategy::prediction_confirmed(signal, TaConfirmation::new(confirmation)?).with_aggregation(aggregation)?)
+        }
+    };
+    Ok(strategy)
+}
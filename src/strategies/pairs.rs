@@ -0,0 +1,379 @@
+//! 페어 트레이딩(통계적 차익거래) 전략
+//!
+//! 상관관계가 높은 두 심볼의 로그 가격 스프레드 `ln(A) - β·ln(B)` 를 추적하여, z-score 가
+//! 진입 임계치를 넘으면 스프레드가 평균으로 회귀하는 쪽에 베팅하고(A 매도/B 매수 또는 반대)
+//! 청산 임계치 안으로 돌아오면 두 다리를 함께 청산한다. 헤지 비율 β 는 관측 구간 OLS 로 추정.
+
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::TradingError;
+use crate::models::decimal::{to_decimal, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+use crate::strategies::{ParamUpdate, Strategy};
+
+/// 스프레드 포지션 방향
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpreadPosition {
+    Flat,
+    /// A 매수 / B 매도 (스프레드가 평균보다 낮음)
+    Long,
+    /// A 매도 / B 매수 (스프레드가 평균보다 높음)
+    Short,
+}
+
+/// 재시작 시 복원하는 페어 포지션 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct PairsState {
+    position: SpreadPosition,
+    quantity_a: Decimal,
+    quantity_b: Decimal,
+    is_active: bool,
+}
+
+/// 한 심볼 다리의 최신 시세
+#[derive(Default)]
+struct Leg {
+    latest: Option<MarketData>,
+    /// 마지막 표본 이후 새 캔들 수신 여부
+    fresh: bool,
+}
+
+impl Leg {
+    fn observe(&mut self, market_data: &MarketData) {
+        let newer = self.latest.as_ref()
+            .is_none_or(|last| market_data.timestamp > last.timestamp);
+        let same_bar = self.latest.as_ref()
+            .is_some_and(|last| market_data.timestamp == last.timestamp);
+        if newer {
+            self.fresh = true;
+        }
+        if newer || same_bar {
+            self.latest = Some(market_data.clone());
+        }
+    }
+}
+
+/// 두 심볼 스프레드의 z-score 기반 페어 트레이딩 전략
+pub struct PairsTradingStrategy {
+    /// 전략 이름
+    name: String,
+    /// 전략 설명
+    description: String,
+    /// 기준 심볼 (A)
+    symbol: String,
+    /// 헤지 심볼 (B)
+    pair_symbol: String,
+    /// A 다리 주문 수량 (B 다리는 헤지 비율과 가격으로 산정)
+    quantity: Decimal,
+    /// 헤지 비율/z-score 계산 구간 (표본 수)
+    lookback: usize,
+    /// 진입 z-score 임계치
+    entry_z: f64,
+    /// 청산 z-score 임계치
+    exit_z: f64,
+    leg_a: Leg,
+    leg_b: Leg,
+    /// 두 다리가 모두 갱신될 때마다 쌓는 로그 가격 표본
+    log_a: VecDeque<f64>,
+    log_b: VecDeque<f64>,
+    /// 최근 헤지 비율
+    hedge_ratio: Option<f64>,
+    /// 최근 z-score
+    zscore: Option<f64>,
+    position: SpreadPosition,
+    /// 보유 중인 다리별 수량 (청산 시 그대로 반대 주문)
+    quantity_a: Decimal,
+    quantity_b: Decimal,
+    /// 다음 get_orders 에서 반환할 주문
+    pending_orders: Vec<Order>,
+    /// 전략 활성 여부
+    is_active: bool,
+}
+
+impl PairsTradingStrategy {
+    /// 새 페어 트레이딩 전략 생성
+    pub fn new(
+        symbol: impl Into<String>,
+        pair_symbol: impl Into<String>,
+        quantity: Decimal,
+        lookback: usize,
+        entry_z: f64,
+        exit_z: f64,
+    ) -> Result<Self, TradingError> {
+        let symbol = symbol.into();
+        let pair_symbol = pair_symbol.into();
+        if symbol == pair_symbol {
+            return Err(TradingError::InvalidParameter("pair_symbol must differ from symbol".to_string()));
+        }
+        if lookback < 3 {
+            return Err(TradingError::InvalidParameter("lookback must be at least 3".to_string()));
+        }
+        if exit_z < 0.0 || entry_z <= exit_z {
+            return Err(TradingError::InvalidParameter("entry_z must exceed exit_z (>= 0)".to_string()));
+        }
+
+        Ok(PairsTradingStrategy {
+            name: format!("PAIRS-{}-{}", symbol, pair_symbol),
+            description: format!("Pairs trading on the {}/{} log price spread z-score", symbol, pair_symbol),
+            symbol,
+            pair_symbol,
+            quantity,
+            lookback,
+            entry_z,
+            exit_z,
+            leg_a: Leg::default(),
+            leg_b: Leg::default(),
+            log_a: VecDeque::with_capacity(lookback),
+            log_b: VecDeque::with_capacity(lookback),
+            hedge_ratio: None,
+            zscore: None,
+            position: SpreadPosition::Flat,
+            quantity_a: Decimal::ZERO,
+            quantity_b: Decimal::ZERO,
+            pending_orders: Vec::new(),
+            is_active: true,
+        })
+    }
+
+    /// 최근 헤지 비율 β
+    pub fn hedge_ratio(&self) -> Option<f64> {
+        self.hedge_ratio
+    }
+
+    /// 최근 스프레드 z-score
+    pub fn zscore(&self) -> Option<f64> {
+        self.zscore
+    }
+
+    /// 현재 스프레드 포지션
+    pub fn position(&self) -> SpreadPosition {
+        self.position
+    }
+
+    /// 두 다리가 모두 새 캔들을 받았을 때 표본 하나를 추가하고 신호 평가
+    fn sample(&mut self) {
+        let (Some(a), Some(b)) = (self.leg_a.latest.clone(), self.leg_b.latest.clone()) else {
+            return;
+        };
+        if !(self.leg_a.fresh && self.leg_b.fresh) || a.close <= Decimal::ZERO || b.close <= Decimal::ZERO {
+            return;
+        }
+        self.leg_a.fresh = false;
+        self.leg_b.fresh = false;
+
+        self.log_a.push_back(a.close_f64().ln());
+        self.log_b.push_back(b.close_f64().ln());
+        while self.log_a.len() > self.lookback {
+            self.log_a.pop_front();
+            self.log_b.pop_front();
+        }
+        if self.log_a.len() < self.lookback {
+            return;
+        }
+
+        // OLS 헤지 비율: β = cov(A, B) / var(B)
+        let n = self.log_a.len() as f64;
+        let mean_a = self.log_a.iter().sum::<f64>() / n;
+        let mean_b = self.log_b.iter().sum::<f64>() / n;
+        let (cov, var_b) = self.log_a.iter().zip(&self.log_b)
+            .fold((0.0, 0.0), |(cov, var), (x, y)| (cov + (x - mean_a) * (y - mean_b), var + (y - mean_b).powi(2)));
+        let beta = if var_b > f64::EPSILON { cov / var_b } else { 1.0 };
+
+        let spreads: Vec<f64> = self.log_a.iter().zip(&self.log_b).map(|(x, y)| x - beta * y).collect();
+        let mean = spreads.iter().sum::<f64>() / n;
+        let std = (spreads.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n).sqrt();
+        self.hedge_ratio = Some(beta);
+        if std <= f64::EPSILON {
+            self.zscore = None;
+            return;
+        }
+        let z = (spreads[spreads.len() - 1] - mean) / std;
+        self.zscore = Some(z);
+
+        match self.position {
+            SpreadPosition::Flat if z.abs() >= self.entry_z => {
+                if beta <= 0.0 {
+                    log::debug!("{}: non-positive hedge ratio {:.4}, skipping entry", self.name, beta);
+                    return;
+                }
+                // 금액 기준 헤지: B 수량 = A 수량 · β · P(A) / P(B)
+                let quantity_a = self.quantity;
+                let quantity_b = self.quantity * to_decimal(beta) * a.close / b.close;
+                let (position, side_a, side_b) = if z > 0.0 {
+                    (SpreadPosition::Short, OrderSide::Sell, OrderSide::Buy)
+                } else {
+                    (SpreadPosition::Long, OrderSide::Buy, OrderSide::Sell)
+                };
+                self.pending_orders.push(Order::new(self.symbol.clone(), side_a, OrderType::Market, quantity_a, a.close));
+                self.pending_orders.push(Order::new(self.pair_symbol.clone(), side_b, OrderType::Market, quantity_b, b.close));
+                self.position = position;
+                self.quantity_a = quantity_a;
+                self.quantity_b = quantity_b;
+                log::info!("{}: enter {:?} spread (z={:.2}, beta={:.4})", self.name, position, z, beta);
+            }
+            SpreadPosition::Long if z >= -self.exit_z => self.close_position(&a, &b, z),
+            SpreadPosition::Short if z <= self.exit_z => self.close_position(&a, &b, z),
+            _ => {}
+        }
+    }
+
+    /// 보유 수량 그대로 두 다리 반대 주문
+    fn close_position(&mut self, a: &MarketData, b: &MarketData, z: f64) {
+        let (side_a, side_b) = match self.position {
+            SpreadPosition::Long => (OrderSide::Sell, OrderSide::Buy),
+            SpreadPosition::Short => (OrderSide::Buy, OrderSide::Sell),
+            SpreadPosition::Flat => return,
+        };
+        self.pending_orders.push(Order::new(self.symbol.clone(), side_a, OrderType::Market, self.quantity_a, a.close));
+        self.pending_orders.push(Order::new(self.pair_symbol.clone(), side_b, OrderType::Market, self.quantity_b, b.close));
+        log::info!("{}: exit {:?} spread (z={:.2})", self.name, self.position, z);
+        self.position = SpreadPosition::Flat;
+        self.quantity_a = Decimal::ZERO;
+        self.quantity_b = Decimal::ZERO;
+    }
+}
+
+impl Strategy for PairsTradingStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        let snapshot = HashMap::from([(market_data.symbol.clone(), market_data)]);
+        self.update_multi(&snapshot)
+    }
+
+    fn symbols(&self) -> Vec<String> {
+        vec![self.symbol.clone(), self.pair_symbol.clone()]
+    }
+
+    fn update_multi(&mut self, snapshot: &HashMap<String, MarketData>) -> Result<(), TradingError> {
+        if let Some(a) = snapshot.get(&self.symbol) {
+            self.leg_a.observe(a);
+        }
+        if let Some(b) = snapshot.get(&self.pair_symbol) {
+            self.leg_b.observe(b);
+        }
+        if self.is_active {
+            self.sample();
+        }
+        Ok(())
+    }
+
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        Ok(std::mem::take(&mut self.pending_orders))
+    }
+
+    fn status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "position": self.position,
+            "hedge_ratio": self.hedge_ratio,
+            "zscore": self.zscore,
+            "entry_z": self.entry_z,
+            "exit_z": self.exit_z,
+            "samples": self.log_a.len(),
+            "quantity_a": self.quantity_a,
+            "quantity_b": self.quantity_b,
+        })
+    }
+
+    /// 진입/청산 z-score 임계치 변경 (보유 중인 스프레드 포지션은 새 청산 임계치로 판단)
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+        let params = ParamUpdate::new(&self.name, params)?;
+        params.allow(&["entry_z", "exit_z"])?;
+        let entry_z = params.f64("entry_z")?.unwrap_or(self.entry_z);
+        let exit_z = params.f64("exit_z")?.unwrap_or(self.exit_z);
+        if exit_z < 0.0 || entry_z <= exit_z {
+            return Err(params.invalid("entry_z must exceed exit_z (>= 0)"));
+        }
+
+        self.entry_z = entry_z;
+        self.exit_z = exit_z;
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(PairsState {
+            position: self.position,
+            quantity_a: self.quantity_a,
+            quantity_b: self.quantity_b,
+            is_active: self.is_active,
+        }).ok()
+    }
+
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: PairsState = serde_json::from_value(state.clone())?;
+        self.position = state.position;
+        self.quantity_a = state.quantity_a;
+        self.quantity_b = state.quantity_b;
+        self.is_active = state.is_active;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::core::strategy_manager::StrategyManager;
+
+    fn candle(symbol: &str, minute: i64, price: f64) -> MarketData {
+        MarketData::new(symbol, minute * 60_000, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    #[test]
+    fn test_pairs_trading_spread_round_trip() {
+        // 백테스트처럼 같은 시각의 두 심볼 캔들이 따로 도착
+        let mut manager = StrategyManager::new();
+        manager.add_strategy(Box::new(PairsTradingStrategy::new("ETHUSDT", "BTCUSDT", dec!(1), 10, 2.0, 0.5).unwrap())).unwrap();
+
+        let mut orders = Vec::new();
+        for minute in 0..30 {
+            let btc = 50_000.0 * (1.0 + 0.01 * minute as f64);
+            // ETH 는 BTC 의 5% 를 따라가다가 20분에 튀고 25분에 복귀
+            let noise = 0.0005 * ((minute * 7) % 3 - 1) as f64;
+            let eth = btc * 0.05 * (1.0 + noise + if minute == 20 { 0.03 } else { 0.0 });
+            manager.update_all(&candle("ETHUSDT", minute, eth)).unwrap();
+            // 한쪽 다리만 갱신된 상태에서는 신호 없음
+            assert!(manager.get_all_orders().unwrap().is_empty());
+            manager.update_all(&candle("BTCUSDT", minute, btc)).unwrap();
+            orders.push((minute, manager.get_all_orders().unwrap()));
+        }
+
+        let traded: Vec<_> = orders.iter().filter(|(_, o)| !o.is_empty()).collect();
+        assert_eq!(traded.len(), 2);
+        // 스프레드 상승 -> ETH 매도 / BTC 매수, 금액 기준 헤지
+        let (entry_minute, entry) = traded[0];
+        assert_eq!(*entry_minute, 20);
+        assert_eq!((entry[0].symbol.as_str(), &entry[0].side), ("ETHUSDT", &OrderSide::Sell));
+        assert_eq!((entry[1].symbol.as_str(), &entry[1].side), ("BTCUSDT", &OrderSide::Buy));
+        let notional_ratio = entry[1].quantity * entry[1].price / (entry[0].quantity * entry[0].price);
+        assert!(notional_ratio > Decimal::ZERO);
+        // 평균 회귀 시 같은 수량으로 청산
+        let (exit_minute, exit) = traded[1];
+        assert_eq!(*exit_minute, 21);
+        assert_eq!(exit[0].side, OrderSide::Buy);
+        assert_eq!(exit[1].side, OrderSide::Sell);
+        assert_eq!(exit[1].quantity, entry[1].quantity);
+
+        // 설정 검증
+        assert!(PairsTradingStrategy::new("ETHUSDT", "ETHUSDT", dec!(1), 10, 2.0, 0.5).is_err());
+        assert!(PairsTradingStrategy::new("ETHUSDT", "BTCUSDT", dec!(1), 10, 0.5, 1.0).is_err());
+    }
+}
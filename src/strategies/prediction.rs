@@ -0,0 +1,286 @@
+//! 예측 서비스 신호 전략과 TA 확인 필터
+//!
+//! 예측 서비스의 신호/신뢰도는 외부 시계열(`prediction.<SYMBOL>.signal`, `prediction.<SYMBOL>.confidence`)로
+//! 들어온다 - 실시간은 `prediction_api.signal_feeds` 폴링, 백테스트는 같은 이름의 CSV 시계열.
+//! 신뢰도가 기준 이상인 새 방향 신호만 주문으로 바꾸고, `TaConfirmation` 은 RSI/EMA 조건으로 그 주문을 거른다.
+
+use crate::config::TaConfirmationConfig;
+use crate::error::TradingError;
+use crate::indicators::{ExponentialMovingAverage, Indicator, IndicatorResult, IndicatorSignal, RelativeStrengthIndex};
+use crate::market_data::external::ExternalInputs;
+use crate::models::decimal::{to_f64, Decimal};
+use crate::models::market_data::MarketData;
+use crate::models::order::{Order, OrderSide, OrderType};
+use crate::strategies::{ParamUpdate, Strategy};
+
+/// 예측 신호 시계열 이름 (1: 매수, -1: 매도, 0: 중립)
+pub fn signal_series(symbol: &str) -> String {
+    format!("prediction.{}.signal", symbol)
+}
+
+/// 예측 신뢰도 시계열 이름 (0 ~ 1)
+pub fn confidence_series(symbol: &str) -> String {
+    format!("prediction.{}.confidence", symbol)
+}
+
+/// 예측 서비스 신호를 따르는 신호 전략
+pub struct PredictionSignalStrategy {
+    name: String,
+    symbol: String,
+    quantity: Decimal,
+    /// 주문으로 바꿀 최소 신뢰도
+    min_confidence: f64,
+    /// 최근 신호 방향 (-1, 0, 1)
+    signal: Option<i32>,
+    confidence: f64,
+    /// 마지막으로 주문을 낸 방향 - 같은 방향 신호가 유지되는 동안 반복 주문하지 않음
+    acted: i32,
+    last_price: Option<Decimal>,
+    is_active: bool,
+}
+
+impl PredictionSignalStrategy {
+    pub fn new(symbol: impl Into<String>, quantity: Decimal, min_confidence: f64) -> Result<Self, TradingError> {
+        let symbol = symbol.into();
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(TradingError::InvalidParameter("min_confidence must be in [0, 1]".to_string()));
+        }
+        Ok(PredictionSignalStrategy {
+            name: format!("PREDICTION-{}", symbol),
+            symbol,
+            quantity,
+            min_confidence,
+            signal: None,
+            confidence: 0.0,
+            acted: 0,
+            last_price: None,
+            is_active: true,
+        })
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// 신뢰도로 가중한 신호 강도 (-1.0 ~ 1.0)
+    fn strength(&self) -> f64 {
+        self.signal.unwrap_or(0) as f64 * self.confidence
+    }
+}
+
+impl Strategy for PredictionSignalStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        if market_data.symbol == self.symbol {
+            self.last_price = Some(market_data.close);
+        }
+        Ok(())
+    }
+
+    fn update_external(&mut self, inputs: &ExternalInputs) {
+        if let Some(signal) = inputs.get(&signal_series(&self.symbol)) {
+            self.signal = Some(signal.round().clamp(-1.0, 1.0) as i32);
+        }
+        if let Some(confidence) = inputs.get(&confidence_series(&self.symbol)) {
+            self.confidence = confidence.clamp(0.0, 1.0);
+        }
+    }
+
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        let (Some(signal), Some(price)) = (self.signal, self.last_price) else {
+            return Ok(vec![]);
+        };
+        // 중립 신호가 오면 다음 같은 방향 신호에 다시 진입
+        if signal == 0 {
+            self.acted = 0;
+            return Ok(vec![]);
+        }
+        if !self.is_active || signal == self.acted || self.confidence < self.min_confidence {
+            return Ok(vec![]);
+        }
+        self.acted = signal;
+        let side = if signal > 0 { OrderSide::Buy } else { OrderSide::Sell };
+        log::info!("{}: prediction {:?} signal (confidence {:.2})", self.name, side, self.confidence);
+        Ok(vec![Order::new(self.symbol.clone(), side, OrderType::Market, self.quantity, price)])
+    }
+
+    fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+        let Some(signal) = self.signal.filter(|s| *s != 0) else {
+            return Vec::new();
+        };
+        let name = if signal > 0 { "Prediction Buy" } else { "Prediction Sell" };
+        let result = IndicatorResult {
+            value: self.strength(),
+            signals: vec![IndicatorSignal {
+                name: name.to_string(),
+                strength: self.strength(),
+                message: format!("prediction service confidence {:.2}", self.confidence),
+            }],
+        };
+        vec![("Prediction".to_string(), result)]
+    }
+
+    fn status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "signal": self.signal,
+            "confidence": self.confidence,
+            "min_confidence": self.min_confidence,
+            "quantity": to_f64(self.quantity),
+        })
+    }
+
+    /// 최소 신뢰도 / 주문 수량 변경
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+        let params = ParamUpdate::new(&self.name, params)?;
+        params.allow(&["min_confidence", "quantity"])?;
+        if let Some(min_confidence) = params.f64("min_confidence")? {
+            if !(0.0..=1.0).contains(&min_confidence) {
+                return Err(params.invalid("min_confidence must be in [0, 1]"));
+            }
+            self.min_confidence = min_confidence;
+        }
+        if let Some(quantity) = params.decimal("quantity")? {
+            self.quantity = quantity;
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        "Prediction service signal above a confidence threshold"
+    }
+
+    fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    fn set_active(&mut self, active: bool) {
+        self.is_active = active;
+    }
+}
+
+/// 예측 신호 주문을 확인하는 고전 지표 조건 (예: RSI 50 미만 + 200 EMA 위에서만 매수)
+#[derive(Debug)]
+pub struct TaConfirmation {
+    config: TaConfirmationConfig,
+    rsi: RelativeStrengthIndex,
+    ema: Option<ExponentialMovingAverage>,
+    price: Option<f64>,
+}
+
+impl TaConfirmation {
+    pub fn new(config: TaConfirmationConfig) -> Result<Self, TradingError> {
+        config.validate().map_err(TradingError::InvalidParameter)?;
+        Ok(TaConfirmation {
+            rsi: RelativeStrengthIndex::new(config.rsi_period, None, None),
+            ema: config.trend_ema_period.map(ExponentialMovingAverage::new),
+            config,
+            price: None,
+        })
+    }
+
+    pub fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+        let close = to_f64(market_data.close);
+        self.rsi.update(close, None)?;
+        if let Some(ema) = self.ema.as_mut() {
+            ema.update(close, None)?;
+        }
+        self.price = Some(close);
+        Ok(())
+    }
+
+    /// 주문 방향을 확인하면 None, 아니면 거부 사유 (지표 워밍업 중에는 모두 거부)
+    pub fn reject_reason(&self, side: &OrderSide) -> Option<String> {
+        let Some(price) = self.price else {
+            return Some("no price yet".to_string());
+        };
+        let rsi = match self.rsi.calculate() {
+            Ok(result) => result.value,
+            Err(_) => return Some("RSI warming up".to_string()),
+        };
+        let ema = match &self.ema {
+            Some(ema) => match ema.calculate() {
+                Ok(result) => Some(result.value),
+                Err(_) => return Some("EMA warming up".to_string()),
+            },
+            None => None,
+        };
+        let buy = *side == OrderSide::Buy;
+        let rsi_bound = if buy { self.config.max_buy_rsi } else { self.config.min_sell_rsi };
+        if let Some(bound) = rsi_bound {
+            if (buy && rsi >= bound) || (!buy && rsi <= bound) {
+                return Some(format!("RSI {:.1} {} {}", rsi, if buy { ">=" } else { "<=" }, bound));
+            }
+        }
+        if let Some(ema) = ema {
+            if (buy && price <= ema) || (!buy && price >= ema) {
+                return Some(format!("price {:.2} {} EMA {:.2}", price, if buy { "<=" } else { ">=" }, ema));
+            }
+        }
+        None
+    }
+
+    pub fn status(&self) -> serde_json::Value {
+        serde_json::json!({
+            "rsi": self.rsi.calculate().ok().map(|r| r.value),
+            "ema": self.ema.as_ref().and_then(|e| e.calculate().ok()).map(|r| r.value),
+            "price": self.price,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::decimal::{dec, to_decimal};
+    use crate::strategies::CombinedStrategy;
+
+    fn candle(price: f64) -> MarketData {
+        MarketData::new("BTCUSDT", 0, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+    }
+
+    fn prediction(signal: f64, confidence: f64) -> ExternalInputs {
+        ExternalInputs {
+            values: [(signal_series("BTCUSDT"), signal), (confidence_series("BTCUSDT"), confidence)].into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_prediction_confirmed_by_ta() {
+        let config = TaConfirmationConfig { rsi_period: 3, max_buy_rsi: Some(50.0), min_sell_rsi: Some(50.0), trend_ema_period: Some(10) };
+        let signal = PredictionSignalStrategy::new("BTCUSDT", dec!(0.5), 0.6).unwrap();
+        let mut strategy = CombinedStrategy::prediction_confirmed(signal, TaConfirmation::new(config).unwrap());
+
+        // 상승 추세에서 눌림 (가격 > EMA, RSI < 50) 에만 매수 확인
+        for step in 0..16 {
+            strategy.update(candle(100.0 + 2.0 * step as f64)).unwrap();
+        }
+        strategy.update_external(&prediction(1.0, 0.9));
+        // RSI 과열 구간이라 매수 거부
+        assert!(strategy.get_orders().unwrap().is_empty());
+        assert!(strategy.status()["confirmation"]["rejected"].as_str().unwrap().starts_with("RSI"));
+
+        strategy.update_external(&prediction(0.0, 0.9));
+        assert!(strategy.get_orders().unwrap().is_empty());
+        for price in [128.0, 126.0, 125.0] {
+            strategy.update(candle(price)).unwrap();
+        }
+        // 신뢰도 미달은 무시
+        strategy.update_external(&prediction(1.0, 0.5));
+        assert!(strategy.get_orders().unwrap().is_empty());
+        strategy.update_external(&prediction(1.0, 0.8));
+        let orders = strategy.get_orders().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!((&orders[0].side, orders[0].quantity), (&OrderSide::Buy, dec!(0.5)));
+        // 같은 방향 신호가 유지되는 동안 반복 주문 없음
+        assert!(strategy.get_orders().unwrap().is_empty());
+
+        // 가격이 EMA 위라 매도 신호는 거부
+        strategy.update_external(&prediction(-1.0, 0.9));
+        assert!(strategy.get_orders().unwrap().is_empty());
+        assert!(TaConfirmation::new(TaConfirmationConfig { rsi_period: 0, ..TaConfirmationConfig::default() }).is_err());
+    }
+}
@@ -7,16 +7,43 @@
 
 use std::sync::{Arc, RwLock};
 use crate::error::TradingError;
+use crate::indicators::IndicatorResult;
 use crate::models::market_data::MarketData;
-use crate::models::order::Order;
+use crate::market_data::aggregator::CandleAggregator;
+use crate::market_data::external::ExternalInputs;
+use crate::models::order::{Order, OrderSide};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
+use crate::signals::signal_aggregator::AggregationConfig;
 use crate::trading_bots::{TradingBot, TradingBotConfig, bot_config};
-use crate::strategies::Strategy;
+use crate::strategies::{ParamUpdate, Strategy};
+
+// 상위 주기 추세 필터 (예: 1h 추세 + 5m 진입)
+struct TrendFilter {
+  trend_bot: Box<dyn TradingBot>,
+  trend_candles: CandleAggregator,
+  entry_candles: CandleAggregator,
+  // 상위 주기 지표의 마지막 방향
+  bias: Option<OrderSide>,
+  // 마지막 주문 조회 이후 진입 주기 캔들이 완성되었는지
+  entry_closed: bool,
+}
+
+// 외부 시계열 조건 (예: 공포탐욕지수 25 이하일 때만 주문)
+struct ExternalGate {
+  series: String,
+  min: Option<f64>,
+  max: Option<f64>,
+  // 마지막으로 받은 값 (아직 없으면 주문 차단)
+  value: Option<f64>,
+}
 
 // 기술적 분석 기반 전략
 pub struct TechnicalStrategy {
   bot: Box<dyn TradingBot>,
   name: String,
   is_active: bool,
+  trend_filter: Option<TrendFilter>,
+  external_gate: Option<ExternalGate>,
 }
 
 impl TechnicalStrategy {
@@ -25,7 +52,59 @@ impl TechnicalStrategy {
       bot,
       name,
       is_active: true,
+      trend_filter: None,
+      external_gate: None,
+    }
+  }
+  
+  // 외부 시계열 값이 [min, max] 범위일 때만 주문 통과
+  pub fn with_external_gate(mut self, series: impl Into<String>, min: Option<f64>, max: Option<f64>) -> Self {
+    self.external_gate = Some(ExternalGate { series: series.into(), min, max, value: None });
+    self
+  }
+  
+  // 다중 주기 전략: entry 지표는 entry_timeframe 캔들로, trend 지표는 trend_timeframe 캔들로 계산하고
+  // 두 방향이 일치할 때만 진입 주문 생성
+  pub fn multi_timeframe(entry: TechnicalStrategy, trend: TechnicalStrategy, entry_timeframe: &str, trend_timeframe: &str) -> Result<Self, TradingError> {
+    let entry_candles = CandleAggregator::from_timeframe(entry_timeframe)?;
+    let trend_candles = CandleAggregator::from_timeframe(trend_timeframe)?;
+    if trend_candles.timeframe_ms() <= entry_candles.timeframe_ms() {
+      return Err(TradingError::InvalidParameter(format!(
+        "Trend timeframe {} must be longer than entry timeframe {}", trend_timeframe, entry_timeframe
+      )));
+    }
+    
+    let name = format!("MTF {} {} + {} {}", trend_timeframe, trend.name, entry_timeframe, entry.name);
+    let mut strategy = TechnicalStrategy::new(entry.bot, name);
+    strategy.trend_filter = Some(TrendFilter {
+      trend_bot: trend.bot,
+      trend_candles,
+      entry_candles,
+      bias: None,
+      entry_closed: false,
+    });
+    Ok(strategy)
+  }
+  
+  // 현재 상위 주기 추세 방향 (다중 주기 전략이 아니면 None)
+  pub fn trend_bias(&self) -> Option<OrderSide> {
+    self.trend_filter.as_ref().and_then(|f| f.bias.clone())
+  }
+  
+  // 지표 주문 수집 (다중 주기 전략은 진입 캔들 완성 시점에 한 번, 추세 방향과 같은 주문만)
+  fn collect_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+    let Some(filter) = self.trend_filter.as_mut() else {
+      return self.bot.generate_orders();
+    };
+    
+    if !std::mem::take(&mut filter.entry_closed) {
+      return Ok(vec![]);
     }
+    let Some(bias) = filter.bias.clone() else {
+      return Ok(vec![]);
+    };
+    let orders = self.bot.generate_orders()?;
+    Ok(orders.into_iter().filter(|o| o.side == bias).collect())
   }
   
   // 편의 생성자: MA 크로스오버 전략
@@ -61,8 +140,30 @@ impl TechnicalStrategy {
     ))
   }
   
-  // 편의 생성자: 복합 지표 전략
-  pub fn multi_indicator(symbol: String) -> Result<Self, TradingError> {
+  // 편의 생성자: 볼린저 밴드 전략
+  pub fn bollinger(symbol: String, period: usize, std_dev: f64) -> Result<Self, TradingError> {
+    let config = bot_config::TradingBotConfig::bollinger_config(period, std_dev);
+    let bot = crate::trading_bots::bollinger_bot::BollingerBot::new(symbol.clone(), config)?;
+    
+    Ok(TechnicalStrategy::new(
+      Box::new(bot),
+      format!("Bollinger {}/{}", period, std_dev),
+    ))
+  }
+  
+  // 편의 생성자: 일목균형표 전략
+  pub fn ichimoku(symbol: String, tenkan_period: usize, kijun_period: usize, senkou_b_period: usize) -> Result<Self, TradingError> {
+    let config = bot_config::TradingBotConfig::ichimoku_config(tenkan_period, kijun_period, senkou_b_period);
+    let bot = crate::trading_bots::ichimoku_bot::IchimokuBot::new(symbol.clone(), config)?;
+    
+    Ok(TechnicalStrategy::new(
+      Box::new(bot),
+      format!("Ichimoku {}/{}/{}", tenkan_period, kijun_period, senkou_b_period),
+    ))
+  }
+  
+  // 편의 생성자: 복합 지표 전략 (`aggregation` 이 있으면 가중 투표/거부 규칙으로 신호 집계)
+  pub fn multi_indicator(symbol: String, aggregation: Option<AggregationConfig>) -> Result<Self, TradingError> {
     let mut config = TradingBotConfig::new()
       .with_name("Multi Indicator Strategy")
       .with_description("Combined strategy using multiple technical indicators");
@@ -77,6 +178,9 @@ impl TechnicalStrategy {
     config.set_param("macd_slow_period", 26u64);
     config.set_param("macd_signal_period", 9u64);
     config.set_param("base_position_size", 1.0);
+    if let Some(aggregation) = aggregation {
+      config.set_param("aggregation", serde_json::to_value(aggregation).map_err(|e| TradingError::ConfigError(e.to_string()))?);
+    }
     
     let bot = crate::trading_bots::multi_indicator_bot::MultiIndicatorBot::new(symbol.clone(), config)?;
     
@@ -93,7 +197,39 @@ impl Strategy for TechnicalStrategy {
       return Ok(());
     }
     
-    self.bot.update(&market_data)
+    let Some(filter) = self.trend_filter.as_mut() else {
+      return self.bot.update(&market_data);
+    };
+    
+    // 각 지표는 자기 주기의 완성된 캔들만 받음
+    if let Some(candle) = filter.trend_candles.push(&market_data) {
+      filter.trend_bot.update(&candle)?;
+      let signals = filter.trend_bot.evaluate_signals()?;
+      if let Some(signal) = signals.last() {
+        if signal.signal_type.is_buy() {
+          filter.bias = Some(OrderSide::Buy);
+        } else if signal.signal_type.is_sell() {
+          filter.bias = Some(OrderSide::Sell);
+        }
+      }
+    }
+    if let Some(candle) = filter.entry_candles.push(&market_data) {
+      self.bot.update(&candle)?;
+      filter.entry_closed = true;
+    }
+    Ok(())
+  }
+  
+  fn update_external(&mut self, inputs: &ExternalInputs) {
+    if let Some(gate) = self.external_gate.as_mut() {
+      if let Some(value) = inputs.get(&gate.series) {
+        gate.value = Some(value);
+      }
+    }
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.bot.set_available_capital(capital);
   }
   
   fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
@@ -101,7 +237,44 @@ impl Strategy for TechnicalStrategy {
       return Ok(vec![]);
     }
     
-    self.bot.generate_orders()
+    let orders = self.collect_orders()?;
+    match &self.external_gate {
+      Some(gate) => {
+        let open = gate.value.is_some_and(|v| gate.min.is_none_or(|min| v >= min) && gate.max.is_none_or(|max| v <= max));
+        Ok(if open { orders } else { vec![] })
+      }
+      None => Ok(orders),
+    }
+  }
+  
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    self.bot.indicator_results()
+  }
+  
+  // 매수/매도 진입에 손절/익절 첨부 (다중 주기 전략은 진입 주기 캔들로 ATR 계산)
+  fn set_entry_exits(&mut self, config: Option<EntryExitsConfig>) -> Result<(), TradingError> {
+    self.bot.set_entry_exits(config.map(EntryExits::new).transpose()?)
+  }
+  
+  // 지표 봇 상태 + 상위 주기 추세/외부 시계열 조건
+  fn status(&self) -> serde_json::Value {
+    let mut status = self.bot.status();
+    if self.trend_filter.is_some() {
+      status["trend_bias"] = serde_json::json!(self.trend_bias().map(|side| format!("{:?}", side)));
+    }
+    if let Some(exits) = self.bot.entry_exits() {
+      status["exits"] = serde_json::json!(exits);
+    }
+    if let Some(gate) = &self.external_gate {
+      status["external"] = serde_json::json!({"series": gate.series, "value": gate.value, "min": gate.min, "max": gate.max});
+    }
+    status
+  }
+  
+  // 지표 봇 파라미터 변경 (다중 주기 전략은 진입 지표만)
+  fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+    let params = ParamUpdate::new(&self.name, params)?;
+    self.bot.update_params(&params)
   }
   
   fn name(&self) -> &str {
@@ -120,4 +293,193 @@ impl Strategy for TechnicalStrategy {
   fn set_active(&mut self, active: bool) {
     self.is_active = active;
   }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::decimal::{dec, to_decimal};
+  
+  fn tick(minute: i64, price: f64) -> MarketData {
+    MarketData::new("BTCUSDT", minute * 60_000, to_decimal(price), to_decimal(price), to_decimal(price), to_decimal(price), dec!(1))
+  }
+  
+  #[test]
+  fn test_multi_timeframe_requires_agreement() {
+    let entry = TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap();
+    let trend = TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap();
+    assert!(TechnicalStrategy::multi_timeframe(
+      TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap(),
+      TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap(),
+      "1h", "5m",
+    ).is_err());
+    let mut strategy = TechnicalStrategy::multi_timeframe(entry, trend, "1m", "5m").unwrap();
+    
+    // 하락 구간: 두 주기 모두 과매도 -> 매수 주문 통과
+    let mut buys = 0;
+    for minute in 0..30 {
+      strategy.update(tick(minute, 100.0 - minute as f64)).unwrap();
+      let orders = strategy.get_orders().unwrap();
+      assert!(orders.iter().all(|o| o.side == OrderSide::Buy));
+      buys += orders.len();
+    }
+    assert_eq!(strategy.trend_bias(), Some(OrderSide::Buy));
+    assert!(buys > 0);
+    // 새 진입 캔들이 없으면 주문 없음
+    assert!(strategy.get_orders().unwrap().is_empty());
+    
+    // 같은 상위 캔들 안에서 급반등: 진입 주기만 과매수 -> 매도 주문 차단
+    for (i, minute) in (30..34).enumerate() {
+      strategy.update(tick(minute, 80.0 + 5.0 * i as f64)).unwrap();
+      assert!(strategy.get_orders().unwrap().iter().all(|o| o.side == OrderSide::Buy));
+    }
+    assert!(strategy.bot.evaluate_signals().unwrap()[0].signal_type.is_sell());
+    assert_eq!(strategy.trend_bias(), Some(OrderSide::Buy));
+  }
+  
+  #[test]
+  fn test_bollinger_squeeze_breakout() {
+    let mut strategy = TechnicalStrategy::bollinger("BTCUSDT".to_string(), 20, 2.0).unwrap();
+    
+    // 출렁이다가 점점 잠잠해지는 구간 -> 밴드 폭 최저(스퀴즈)
+    for minute in 0..150 {
+      let swing = if minute < 120 { (minute as f64 * 0.7).sin() * 5.0 / (1.0 + minute as f64 / 10.0) } else { 0.01 * (minute % 2) as f64 };
+      strategy.update(tick(minute, 100.0 + swing)).unwrap();
+    }
+    assert!(strategy.bot.evaluate_signals().unwrap().is_empty());
+    
+    // 스퀴즈 직후 상단 이탈 -> 돌파 매수
+    strategy.update(tick(150, 103.0)).unwrap();
+    let signals = strategy.bot.evaluate_signals().unwrap();
+    assert!(signals[0].signal_type.is_buy());
+    assert!(strategy.get_orders().unwrap().iter().all(|o| o.side == OrderSide::Buy));
+  }
+  
+  #[test]
+  fn test_ichimoku_cloud_breakout() {
+    assert!(TechnicalStrategy::ichimoku("BTCUSDT".to_string(), 26, 9, 52).is_err());
+    let mut strategy = TechnicalStrategy::ichimoku("BTCUSDT".to_string(), 3, 6, 12).unwrap();
+    
+    // 횡보 구간: 구름이 형성될 때까지 신호 없음
+    for minute in 0..17 {
+      strategy.update(tick(minute, 100.0 + (minute % 2) as f64)).unwrap();
+      assert!(strategy.bot.evaluate_signals().unwrap().is_empty());
+    }
+    
+    // 상승 전환 -> 구름 상향 돌파/전환선 골든크로스 매수
+    let mut buys = 0;
+    for (i, minute) in (17..30).enumerate() {
+      strategy.update(tick(minute, 102.0 + 2.0 * i as f64)).unwrap();
+      assert!(strategy.bot.evaluate_signals().unwrap().iter().all(|s| s.signal_type.is_buy()));
+      buys += strategy.get_orders().unwrap().iter().filter(|o| o.side == OrderSide::Buy).count();
+    }
+    assert!(buys > 0);
+    
+    // 급락 -> 구름 하향 이탈 매도
+    let mut sold = false;
+    for (i, minute) in (30..45).enumerate() {
+      strategy.update(tick(minute, 125.0 - 4.0 * i as f64)).unwrap();
+      sold |= strategy.bot.evaluate_signals().unwrap().iter().any(|s| s.signal_type.is_sell());
+    }
+    assert!(sold);
+  }
+  
+  #[test]
+  fn test_external_gate_blocks_orders() {
+    let mut strategy = TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap()
+      .with_external_gate("fear_greed", None, Some(25.0));
+    let inputs = |value: f64| ExternalInputs { values: [("fear_greed".to_string(), value)].into(), ..Default::default() };
+    
+    // 하락 구간이라 RSI 매수 신호는 계속 나오지만 외부 값이 없으면 차단
+    for minute in 0..10 {
+      strategy.update(tick(minute, 100.0 - minute as f64)).unwrap();
+      assert!(strategy.get_orders().unwrap().is_empty());
+    }
+    assert!(strategy.bot.evaluate_signals().unwrap()[0].signal_type.is_buy());
+    
+    // 범위 밖(탐욕) -> 차단, 범위 안(공포) -> 통과
+    strategy.update_external(&inputs(40.0));
+    strategy.update(tick(10, 90.0)).unwrap();
+    assert!(strategy.get_orders().unwrap().is_empty());
+    strategy.update_external(&inputs(20.0));
+    strategy.update(tick(11, 89.0)).unwrap();
+    assert!(!strategy.get_orders().unwrap().is_empty());
+    
+    // 해당 시계열이 없는 입력은 마지막 값 유지
+    strategy.update_external(&ExternalInputs::default());
+    strategy.update(tick(12, 88.0)).unwrap();
+    assert!(!strategy.get_orders().unwrap().is_empty());
+  }
+  
+  #[test]
+  fn test_entry_orders_carry_exits() {
+    use crate::models::order::AttachedExits;
+    use crate::signals::entry_exits::ExitRule;
+    
+    let percent = EntryExitsConfig { stop_loss: ExitRule::Percent(2.0), take_profit: ExitRule::Percent(4.0), atr_period: 14 };
+    let mut strategy = TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap();
+    strategy.set_entry_exits(Some(percent.clone())).unwrap();
+    assert!(strategy.set_entry_exits(Some(EntryExitsConfig { stop_loss: ExitRule::Percent(120.0), ..percent.clone() })).is_err());
+    assert_eq!(strategy.status()["exits"]["take_profit"]["percent"], 4.0);
+    
+    // 하락 구간 매수 진입: 시장가 기준가(최근 종가) 대비 -2% 손절, +4% 익절
+    for minute in 0..6 {
+      strategy.update(tick(minute, 100.0 - minute as f64)).unwrap();
+    }
+    let orders = strategy.get_orders().unwrap();
+    assert_eq!(orders[0].side, OrderSide::Buy);
+    assert_eq!(orders[0].attached_exits, Some(AttachedExits { take_profit: dec!(98.8), stop_loss: dec!(93.1) }));
+    
+    // ATR 규칙은 워밍업 전 진입을 보호 없이 내지 않음
+    let atr = EntryExitsConfig { stop_loss: ExitRule::Atr(1.0), take_profit: ExitRule::Price(120.0), atr_period: 10 };
+    let mut strategy = TechnicalStrategy::rsi("BTCUSDT".to_string(), 3, 30.0, 70.0).unwrap();
+    strategy.set_entry_exits(Some(atr)).unwrap();
+    for minute in 0..6 {
+      strategy.update(tick(minute, 100.0 - minute as f64)).unwrap();
+    }
+    assert!(strategy.get_orders().unwrap().is_empty());
+    for minute in 6..12 {
+      strategy.update(tick(minute, 100.0 - minute as f64)).unwrap();
+    }
+    let exits = strategy.get_orders().unwrap()[0].attached_exits.unwrap();
+    // 종가 89 에서 ATR(약 1) 아래
+    assert_eq!(exits.take_profit, dec!(120));
+    assert!(exits.stop_loss > dec!(87.5) && exits.stop_loss < dec!(88.5));
+    
+    // 실행 전략은 손절/익절 미지원
+    let mut twap = crate::strategies::TwapStrategy::new("BTCUSDT", OrderSide::Buy, dec!(1), 60_000, 4);
+    assert!(twap.set_entry_exits(Some(percent)).is_err());
+    assert!(twap.set_entry_exits(None).is_ok());
+  }
+  
+  #[test]
+  fn test_multi_indicator_signal_aggregation() {
+    // RSI 만 투표 (다른 지표 가중치 0)
+    let strategy = |extra: serde_json::Value| {
+      let mut config = serde_json::json!({"weights": {"RSI": 1.0}, "default_weight": 0.0});
+      config.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+      TechnicalStrategy::multi_indicator("BTCUSDT".to_string(), Some(serde_json::from_value(config).unwrap())).unwrap()
+    };
+    let mut voting = strategy(serde_json::json!({}));
+    let mut vetoed = strategy(serde_json::json!({"vetoes": [{"indicator": "rsi", "below": 20.0, "blocks": "buy"}]}));
+    let mut quorum = strategy(serde_json::json!({"min_agreement": 2}));
+    assert!(TechnicalStrategy::multi_indicator("BTCUSDT".to_string(), Some(serde_json::from_value(serde_json::json!({"threshold": 2.0})).unwrap())).is_err());
+    
+    // 계속 하락 -> RSI 0, 과매도 매수
+    let mut orders = (Vec::new(), Vec::new(), Vec::new());
+    for minute in 0..40 {
+      for strategy in [&mut voting, &mut vetoed, &mut quorum] {
+        strategy.update(tick(minute, 200.0 - minute as f64)).unwrap();
+      }
+      orders.0.extend(voting.get_orders().unwrap());
+      orders.1.extend(vetoed.get_orders().unwrap());
+      orders.2.extend(quorum.get_orders().unwrap());
+    }
+    assert!(!orders.0.is_empty() && orders.0.iter().all(|o| o.side == OrderSide::Buy));
+    assert_eq!(voting.status()["aggregation"]["buy_votes"], 1);
+    // 거부 규칙(RSI 20 미만 매수 금지)과 합의 수 부족은 주문 없음
+    assert!(orders.1.is_empty() && orders.2.is_empty());
+    assert_eq!(vetoed.status()["aggregation"]["vetoed_by"], "rsi below 20");
+    assert!(quorum.status()["aggregation"]["signal"].is_null());
+  }
+}
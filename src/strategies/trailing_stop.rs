@@ -2,10 +2,24 @@
 //!
 //! 추세 추종과 자동 손절매를 위한 전략
 
+use serde::{Deserialize, Serialize};
+
 use crate::error::TradingError;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
 use crate::models::order::{Order, OrderSide, OrderType};
-use crate::strategies::Strategy;
+use crate::strategies::{ParamUpdate, Strategy};
+
+/// 재시작 시 복원하는 추적 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct TrailingStopState {
+  entry_price: Option<Decimal>,
+  highest_price: Decimal,
+  lowest_price: Decimal,
+  activated: bool,
+  executed: bool,
+  is_active: bool,
+}
 
 /// Trailing Stop 매매 전략
 pub struct TrailingStopStrategy {
@@ -18,17 +32,17 @@ pub struct TrailingStopStrategy {
   /// 매매 방향
   side: OrderSide,
   /// 주문 수량
-  quantity: f64,
+  quantity: Decimal,
   /// 포지션 진입 가격
-  entry_price: Option<f64>,
+  entry_price: Option<Decimal>,
   /// 트레일링 간격 (백분율)
   trailing_delta: f64,
   /// 활성화 가격 (선택사항)
-  activation_price: Option<f64>,
+  activation_price: Option<Decimal>,
   /// 최고 가격 (매수 추적용)
-  highest_price: f64,
+  highest_price: Decimal,
   /// 최저 가격 (매도 추적용)
-  lowest_price: f64,
+  lowest_price: Decimal,
   /// 현재 시장 데이터
   current_market_data: Option<MarketData>,
   /// 트레일링 스탑 활성화 여부
@@ -44,9 +58,9 @@ impl TrailingStopStrategy {
   pub fn new(
     symbol: impl Into<String>,
     side: OrderSide,
-    quantity: f64,
+    quantity: Decimal,
     trailing_delta: f64,
-    activation_price: Option<f64>,
+    activation_price: Option<Decimal>,
   ) -> Self {
     let symbol_str = symbol.into();
     
@@ -59,8 +73,8 @@ impl TrailingStopStrategy {
       entry_price: None,
       trailing_delta,
       activation_price,
-      highest_price: 0.0,
-      lowest_price: f64::MAX,
+      highest_price: Decimal::ZERO,
+      lowest_price: Decimal::MAX,
       current_market_data: None,
       activated: activation_price.is_none(),
       is_active: true,
@@ -69,24 +83,24 @@ impl TrailingStopStrategy {
   }
   
   /// 진입 가격 설정
-  pub fn set_entry_price(&mut self, price: f64) {
+  pub fn set_entry_price(&mut self, price: Decimal) {
     self.entry_price = Some(price);
     self.highest_price = price;
     self.lowest_price = price;
   }
   
   /// 트레일링 스탑 가격 계산
-  fn calculate_stop_price(&self) -> Option<f64> {
+  fn calculate_stop_price(&self) -> Option<Decimal> {
     if let Some(market_data) = &self.current_market_data {
       match self.side {
         OrderSide::Buy => {
           // 매수 트레일링 스탑: 최고가에서 델타% 하락 시 트리거
-          let delta_amount = self.highest_price * (self.trailing_delta / 100.0);
+          let delta_amount = self.highest_price * to_decimal(self.trailing_delta / 100.0);
           Some(self.highest_price - delta_amount)
         },
         OrderSide::Sell => {
           // 매도 트레일링 스탑: 최저가에서 델타% 상승 시 트리거
-          let delta_amount = self.lowest_price * (self.trailing_delta / 100.0);
+          let delta_amount = self.lowest_price * to_decimal(self.trailing_delta / 100.0);
           Some(self.lowest_price + delta_amount)
         }
       }
@@ -197,6 +211,44 @@ impl Strategy for TrailingStopStrategy {
     Ok(Vec::new())
   }
   
+  fn status(&self) -> serde_json::Value {
+    serde_json::json!({
+      "activated": self.activated,
+      "executed": self.executed,
+      "trailing_delta": self.trailing_delta,
+      "activation_price": self.activation_price,
+      "highest_price": self.highest_price,
+      "lowest_price": self.lowest_price,
+      "trigger_price": if self.activated && !self.executed { self.calculate_stop_price() } else { None },
+      "last_price": self.current_market_data.as_ref().map(|data| data.close),
+    })
+  }
+  
+  /// 트레일링 간격(%)은 추적 중인 최고/최저가를 유지한 채 변경, 활성화 가격은 활성화 전에만 변경
+  fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+    let params = ParamUpdate::new(&self.name, params)?;
+    params.allow(&["trailing_delta", "activation_price"])?;
+    if self.executed {
+      return Err(params.invalid("stop already executed"));
+    }
+    let trailing_delta = params.positive("trailing_delta")?;
+    if trailing_delta.is_some_and(|delta| delta >= 100.0) {
+      return Err(params.invalid("trailing_delta must be below 100"));
+    }
+    let activation_price = params.decimal("activation_price")?;
+    if activation_price.is_some() && self.activated {
+      return Err(params.invalid("trailing stop is already activated"));
+    }
+    
+    if let Some(delta) = trailing_delta {
+      self.trailing_delta = delta;
+    }
+    if activation_price.is_some() {
+      self.activation_price = activation_price;
+    }
+    Ok(())
+  }
+  
   fn name(&self) -> &str {
     &self.name
   }
@@ -204,11 +256,34 @@ impl Strategy for TrailingStopStrategy {
   fn description(&self) -> &str {
     &self.description
   }
+  
+  fn export_state(&self) -> Option<serde_json::Value> {
+    serde_json::to_value(TrailingStopState {
+      entry_price: self.entry_price,
+      highest_price: self.highest_price,
+      lowest_price: self.lowest_price,
+      activated: self.activated,
+      executed: self.executed,
+      is_active: self.is_active,
+    }).ok()
+  }
+  
+  fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+    let state: TrailingStopState = serde_json::from_value(state.clone())?;
+    self.entry_price = state.entry_price;
+    self.highest_price = state.highest_price;
+    self.lowest_price = state.lowest_price;
+    self.activated = state.activated;
+    self.executed = state.executed;
+    self.is_active = state.is_active;
+    Ok(())
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::models::decimal::dec;
   
   #[test]
   fn test_trailing_stop_strategy() {
@@ -216,23 +291,23 @@ mod tests {
     let mut strategy = TrailingStopStrategy::new(
       "BTCUSDT",
       OrderSide::Sell,
-      1.0,
+      dec!(1),
       2.0,  // 2% 트레일링 델타
       None, // 활성화 가격 없음 (즉시 활성화)
     );
     
     // 진입 가격 설정
-    strategy.set_entry_price(50000.0);
+    strategy.set_entry_price(dec!(50000));
     
     // 가격 하락 시 스탑 가격 이동 확인
     let market_data1 = MarketData {
       symbol: "BTCUSDT".to_string(),
-      timestamp: 1000,
-      open: 50000.0,
-      high: 50000.0,
-      low: 49500.0,
-      close: 49500.0,
-      volume: 10.0,
+      timestamp: 1000.into(),
+      open: dec!(50000),
+      high: dec!(50000),
+      low: dec!(49500),
+      close: dec!(49500),
+      volume: dec!(10),
     };
     
     strategy.update(market_data1).unwrap();
@@ -244,12 +319,12 @@ mod tests {
     // 가격 급등 시 스탑 조건 충족 확인
     let market_data2 = MarketData {
       symbol: "BTCUSDT".to_string(),
-      timestamp: 2000,
-      open: 49500.0,
-      high: 50500.0,
-      low: 49500.0,
-      close: 50500.0, // 가격 급등 (50500 > 49500 * 1.02)
-      volume: 15.0,
+      timestamp: 2000.into(),
+      open: dec!(49500),
+      high: dec!(50500),
+      low: dec!(49500),
+      close: dec!(50500), // 가격 급등 (50500 > 49500 * 1.02)
+      volume: dec!(15),
     };
     
     strategy.update(market_data2).unwrap();
@@ -263,6 +338,6 @@ mod tests {
     assert_eq!(order.symbol, "BTCUSDT");
     assert_eq!(order.side, OrderSide::Sell);
     assert_eq!(order.order_type, OrderType::Market);
-    assert_eq!(order.quantity, 1.0);
+    assert_eq!(order.quantity, dec!(1));
   }
 }
\ No newline at end of file
@@ -2,10 +2,91 @@
 //!
 //! 시간 가중 평균 가격 기반 주문 실행 전략
 
+use std::collections::HashMap;
+
+use chrono::{TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::core::execution_analyzer::ExecutionAnalyzer;
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
-use crate::models::order::{Order, OrderSide, OrderType};
-use crate::strategies::Strategy;
+use crate::models::order::{Order, OrderId, OrderSide, OrderType};
+use crate::models::trade::Trade;
+use crate::strategies::{execution_progress, ExecutionMode, Strategy};
+
+/// 시간대별 유동성 가중치 (UTC 기준 0~23시)
+#[derive(Debug, Clone)]
+pub struct LiquidityProfile {
+    /// 시간대별 가중치 (0이면 해당 시간대는 주문하지 않음)
+    hourly_weights: [f64; 24],
+}
+
+impl LiquidityProfile {
+    /// 모든 시간대 동일 가중치
+    pub fn uniform() -> Self {
+        LiquidityProfile { hourly_weights: [1.0; 24] }
+    }
+
+    /// 24개 시간대 가중치로 생성
+    pub fn from_weights(weights: &[f64]) -> Result<Self, TradingError> {
+        if weights.len() != 24 {
+            return Err(TradingError::InvalidParameter(
+                format!("Liquidity profile needs 24 hourly weights, got {}", weights.len())
+            ));
+        }
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(TradingError::InvalidParameter("Liquidity weights must be non-negative".to_string()));
+        }
+
+        let mut hourly_weights = [0.0; 24];
+        hourly_weights.copy_from_slice(weights);
+        Ok(LiquidityProfile { hourly_weights })
+    }
+
+    /// 거래하지 않을 시간대 지정 (가중치 0)
+    pub fn with_dead_hours(mut self, hours: &[u32]) -> Self {
+        for hour in hours {
+            if let Some(w) = self.hourly_weights.get_mut(*hour as usize) {
+                *w = 0.0;
+            }
+        }
+        self
+    }
+
+    /// 특정 시각(ms)의 가중치
+    pub fn weight_at(&self, timestamp: i64) -> f64 {
+        let hour = Utc.timestamp_millis_opt(timestamp).single().map(|t| t.hour()).unwrap_or(0);
+        self.hourly_weights[hour as usize]
+    }
+
+    /// 분할별 누적 목표 비율 (마지막 값은 1.0). 가중치 합이 0이면 균등 분할
+    fn cumulative_fractions(&self, start: i64, slice_interval: i64, num_slices: usize) -> Vec<f64> {
+        let weights: Vec<f64> = (0..num_slices)
+            .map(|i| self.weight_at(start + i as i64 * slice_interval))
+            .collect();
+        let total: f64 = weights.iter().sum();
+
+        let mut acc = 0.0;
+        weights.iter()
+            .map(|w| {
+                acc += if total > 0.0 { w / total } else { 1.0 / num_slices as f64 };
+                acc
+            })
+            .collect()
+    }
+}
+
+/// 재시작 시 복원하는 TWAP 진행 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct TwapState {
+    executed_quantity: Decimal,
+    last_order_time: i64,
+    schedule_start: Option<i64>,
+    last_slice_index: Option<usize>,
+    is_active: bool,
+}
 
 /// TWAP 매매 전략
 pub struct TwapStrategy {
@@ -18,13 +99,13 @@ pub struct TwapStrategy {
     /// 매매 방향
     side: OrderSide,
     /// 목표 총 수량
-    total_quantity: f64,
+    total_quantity: Decimal,
     /// 실행 간격 (밀리초)
     execution_interval: i64,
     /// 분할 수
     num_slices: usize,
     /// 이미 실행한 수량
-    executed_quantity: f64,
+    executed_quantity: Decimal,
     /// 현재 시장 데이터
     current_market_data: Option<MarketData>,
     /// 전략 활성 여부
@@ -33,6 +114,22 @@ pub struct TwapStrategy {
     last_order_time: i64,
     /// 주문 간격 (밀리초)
     slice_interval: i64,
+    /// 시간대별 유동성 가중치 (없으면 균등 분할)
+    liquidity_profile: Option<LiquidityProfile>,
+    /// 가중 스케줄 시작 시각
+    schedule_start: Option<i64>,
+    /// 분할별 누적 목표 비율
+    cumulative_targets: Vec<f64>,
+    /// 마지막으로 처리한 분할 인덱스
+    last_slice_index: Option<usize>,
+    /// 실행 방식 설정
+    execution_mode: ExecutionMode,
+    /// 거래소 네이티브 TWAP 사용 여부 (거래소 기능 적용 후 결정)
+    native: bool,
+    /// 도착 가격/TWAP 대비 실행 성과
+    analyzer: ExecutionAnalyzer,
+    /// 성과 기록용 분할 체결 번호
+    fill_count: usize,
 }
 
 impl TwapStrategy {
@@ -40,7 +137,7 @@ impl TwapStrategy {
     pub fn new(
         symbol: impl Into<String>,
         side: OrderSide,
-        total_quantity: f64,
+        total_quantity: Decimal,
         execution_interval: i64,
         num_slices: usize,
     ) -> Self {
@@ -49,40 +146,131 @@ impl TwapStrategy {
         
         TwapStrategy {
             name: format!("TWAP-{}", symbol_str),
+            analyzer: ExecutionAnalyzer::new(symbol_str.clone()),
             description: "Time Weighted Average Price based execution strategy".to_string(),
             symbol: symbol_str,
             side,
             total_quantity,
             execution_interval,
             num_slices,
-            executed_quantity: 0.0,
+            executed_quantity: Decimal::ZERO,
             current_market_data: None,
             is_active: true,
             last_order_time: 0,
             slice_interval,
+            liquidity_profile: None,
+            schedule_start: None,
+            cumulative_targets: Vec::new(),
+            last_slice_index: None,
+            execution_mode: ExecutionMode::ClientSide,
+            native: false,
+            fill_count: 0,
         }
     }
-}
 
-impl Strategy for TwapStrategy {
-    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
-        if market_data.symbol != self.symbol {
-            return Ok(());
+    /// 실행 방식 설정 (PreferNative 는 거래소 기능 적용 시 확정)
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+
+    /// 네이티브 실행 여부
+    pub fn is_native(&self) -> bool {
+        self.native
+    }
+
+    /// 네이티브 TWAP: 남은 전체 수량을 실행 기간과 함께 단일 부모 주문으로 위임
+    fn native_order(&mut self, market_data: &MarketData) -> Vec<Order> {
+        let remaining = self.total_quantity - self.executed_quantity;
+        self.is_active = false;
+        if remaining <= Decimal::ZERO {
+            return Vec::new();
         }
-        
-        // 시장 데이터 업데이트
-        self.current_market_data = Some(market_data);
-        
-        Ok(())
+
+        let order = Order::new(
+            self.symbol.clone(),
+            self.side.clone(),
+            OrderType::Market,
+            remaining,
+            market_data.close,
+        ).with_twap_params(self.execution_interval);
+
+        self.executed_quantity = self.total_quantity;
+        self.last_order_time = market_data.timestamp.as_millis();
+        vec![order]
+    }
+
+    /// 유동성 곡선에 따라 분할 수량을 가중 (가중치 0 시간대는 건너뜀)
+    pub fn with_liquidity_profile(mut self, profile: LiquidityProfile) -> Self {
+        self.liquidity_profile = Some(profile);
+        self
+    }
+
+    /// 유동성 가중 스케줄에 따른 주문 생성
+    fn weighted_orders(&mut self, market_data: &MarketData) -> Vec<Order> {
+        let Some(profile) = &self.liquidity_profile else { return Vec::new() };
+        let current_time = market_data.timestamp.as_millis();
+        let start = *self.schedule_start.get_or_insert(current_time);
+        if self.cumulative_targets.is_empty() {
+            self.cumulative_targets = profile.cumulative_fractions(start, self.slice_interval, self.num_slices);
+        }
+
+        // 현재 분할 인덱스 (실행 구간 종료 후에는 마지막 분할)
+        let index = (((current_time - start) / self.slice_interval.max(1)).max(0) as usize).min(self.num_slices - 1);
+        if self.last_slice_index.is_some_and(|last| index <= last) {
+            return Vec::new();
+        }
+        self.last_slice_index = Some(index);
+
+        // 누적 목표 대비 부족분만 주문 (비유동 시간대는 0)
+        let target = self.total_quantity * to_decimal(self.cumulative_targets[index]);
+        let remaining = self.total_quantity - self.executed_quantity;
+        let slice_quantity = (target - self.executed_quantity).min(remaining);
+        if slice_quantity <= Decimal::ZERO {
+            return Vec::new();
+        }
+
+        let order = Order::new(
+            self.symbol.clone(),
+            self.side.clone(),
+            OrderType::Market,
+            slice_quantity,
+            market_data.close,
+        ).with_twap_params(self.slice_interval);
+
+        self.executed_quantity += slice_quantity;
+        self.last_order_time = current_time;
+        if self.total_quantity - self.executed_quantity <= Decimal::ZERO {
+            self.is_active = false;
+        }
+
+        vec![order]
     }
     
-    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+    /// 현재 분할 주문 계산 (네이티브 실행이면 부모 주문 하나)
+    fn slice_orders(&mut self) -> Result<Vec<Order>, TradingError> {
         if !self.is_active || self.executed_quantity >= self.total_quantity {
             return Ok(Vec::new());
         }
         
+        if self.native {
+            let market_data = match self.current_market_data.clone() {
+                Some(md) => md,
+                None => return Ok(Vec::new()),
+            };
+            return Ok(self.native_order(&market_data));
+        }
+        
+        if self.liquidity_profile.is_some() {
+            let market_data = match self.current_market_data.clone() {
+                Some(md) => md,
+                None => return Ok(Vec::new()),
+            };
+            return Ok(self.weighted_orders(&market_data));
+        }
+        
         if let Some(market_data) = &self.current_market_data {
-            let current_time = market_data.timestamp;
+            let current_time = market_data.timestamp.as_millis();
             
             // 시간 간격 체크
             if self.last_order_time > 0 && current_time - self.last_order_time < self.slice_interval {
@@ -92,13 +280,13 @@ impl Strategy for TwapStrategy {
             // 남은 수량 계산
             let remaining = self.total_quantity - self.executed_quantity;
             
-            if remaining <= 0.0 {
+            if remaining <= Decimal::ZERO {
                 self.is_active = false;
                 return Ok(Vec::new());
             }
             
             // 분할 크기 계산
-            let slice_quantity = (self.total_quantity / self.num_slices as f64).min(remaining);
+            let slice_quantity = (self.total_quantity / Decimal::from(self.num_slices)).min(remaining);
             
             // 시장가 주문 생성
             let order = Order::new(
@@ -124,6 +312,64 @@ impl Strategy for TwapStrategy {
         Ok(Vec::new())
     }
     
+    /// 시장가 분할은 현재가 체결로 실행 성과에 기록
+    fn record_fills(&mut self, orders: &[Order]) {
+        for order in orders {
+            let fill_id = format!("{}-{}", self.name, self.fill_count);
+            self.fill_count += 1;
+            self.analyzer.add_trade(Trade::new(
+                fill_id.clone(),
+                order.symbol.clone(),
+                order.price,
+                order.quantity,
+                self.current_market_data.as_ref().map_or(order.created_at, |md| md.timestamp),
+                OrderId(fill_id),
+                order.side.clone(),
+            ));
+        }
+    }
+
+    /// 실행 성과 분석기에 시세 반영 (첫 시세가 도착 가격)
+    fn track_market_data(&mut self, market_data: &MarketData) {
+        if self.analyzer.arrival_price().is_none() {
+            self.analyzer.set_arrival_price(market_data.close_f64());
+        }
+        if self.is_active {
+            self.analyzer.add_market_data(market_data.clone());
+        }
+    }
+}
+
+impl Strategy for TwapStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        if market_data.symbol != self.symbol {
+            return Ok(());
+        }
+        
+        // 시장 데이터 업데이트
+        self.track_market_data(&market_data);
+        self.current_market_data = Some(market_data);
+        
+        Ok(())
+    }
+    
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        let orders = self.slice_orders()?;
+        if !self.native {
+            self.record_fills(&orders);
+        }
+        Ok(orders)
+    }
+    
+    fn status(&self) -> serde_json::Value {
+        let mut status = execution_progress(self.executed_quantity, self.total_quantity);
+        status["slices"] = serde_json::json!(self.num_slices);
+        status["slice_index"] = serde_json::json!(self.last_slice_index);
+        status["next_slice_at"] = serde_json::json!((self.last_order_time > 0).then(|| self.last_order_time + self.slice_interval));
+        status["native"] = serde_json::json!(self.native);
+        status
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -131,11 +377,45 @@ impl Strategy for TwapStrategy {
     fn description(&self) -> &str {
         &self.description
     }
+    
+    fn is_execution_strategy(&self) -> bool {
+        true
+    }
+
+    fn apply_capabilities(&mut self, capabilities: &ExchangeCapabilities) {
+        self.native = self.execution_mode.resolve(&self.name, OrderType::TWAP, capabilities);
+    }
+    
+    fn execution_report(&self) -> Option<HashMap<String, f64>> {
+        Some(self.analyzer.get_report())
+    }
+    
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(TwapState {
+            executed_quantity: self.executed_quantity,
+            last_order_time: self.last_order_time,
+            schedule_start: self.schedule_start,
+            last_slice_index: self.last_slice_index,
+            is_active: self.is_active,
+        }).ok()
+    }
+    
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: TwapState = serde_json::from_value(state.clone())?;
+        self.executed_quantity = state.executed_quantity;
+        self.last_order_time = state.last_order_time;
+        self.schedule_start = state.schedule_start;
+        self.last_slice_index = state.last_slice_index;
+        self.is_active = state.is_active;
+        self.cumulative_targets.clear();
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::decimal::{dec, to_f64};
     
     #[test]
     fn test_twap_strategy() {
@@ -143,7 +423,7 @@ mod tests {
         let mut strategy = TwapStrategy::new(
             "BTCUSDT",
             OrderSide::Buy,
-            1.0,
+            dec!(1),
             3600000, // 1시간 (밀리초)
             5,       // 5개 분할
         );
@@ -151,12 +431,12 @@ mod tests {
         // 시장 데이터 추가
         let market_data = MarketData {
             symbol: "BTCUSDT".to_string(),
-            timestamp: 1000,
-            open: 50000.0,
-            high: 50100.0,
-            low: 49900.0,
-            close: 50000.0,
-            volume: 10.0,
+            timestamp: 1000.into(),
+            open: dec!(50000),
+            high: dec!(50100),
+            low: dec!(49900),
+            close: dec!(50000),
+            volume: dec!(10),
         };
         
         let result = strategy.update(market_data);
@@ -171,18 +451,18 @@ mod tests {
         assert_eq!(first_order.symbol, "BTCUSDT");
         assert_eq!(first_order.side, OrderSide::Buy);
         assert_eq!(first_order.order_type, OrderType::Market);
-        assert_eq!(first_order.quantity, 0.2); // 1.0/5
-        assert_eq!(first_order.price, 50000.0);
+        assert_eq!(first_order.quantity, dec!(0.2)); // 1.0/5
+        assert_eq!(first_order.price, dec!(50000));
         
         // 다음 interval 내에는 주문 생성 안함 확인
         let market_data2 = MarketData {
             symbol: "BTCUSDT".to_string(),
-            timestamp: 1001, // 같은 interval 내
-            open: 50050.0,
-            high: 50150.0,
-            low: 49950.0,
-            close: 50050.0,
-            volume: 12.0,
+            timestamp: 1001.into(), // 같은 interval 내
+            open: dec!(50050),
+            high: dec!(50150),
+            low: dec!(49950),
+            close: dec!(50050),
+            volume: dec!(12),
         };
         
         strategy.update(market_data2).unwrap();
@@ -192,16 +472,45 @@ mod tests {
         // 다음 interval에는 주문 생성 확인
         let market_data3 = MarketData {
             symbol: "BTCUSDT".to_string(),
-            timestamp: 1000 + strategy.slice_interval, // 다음 interval
-            open: 50100.0,
-            high: 50200.0,
-            low: 50000.0,
-            close: 50100.0,
-            volume: 15.0,
+            timestamp: (1000 + strategy.slice_interval).into(), // 다음 interval
+            open: dec!(50100),
+            high: dec!(50200),
+            low: dec!(50000),
+            close: dec!(50100),
+            volume: dec!(15),
         };
         
         strategy.update(market_data3).unwrap();
         let orders3 = strategy.get_orders().unwrap();
         assert!(!orders3.is_empty());
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_twap_liquidity_profile() {
+        // 4시간 동안 4분할, 01시는 비유동 시간대
+        let profile = LiquidityProfile::uniform().with_dead_hours(&[1]);
+        let mut strategy = TwapStrategy::new("BTCUSDT", OrderSide::Buy, dec!(3), 4 * 3_600_000, 4)
+            .with_liquidity_profile(profile);
+        
+        let mut quantities = Vec::new();
+        for hour in 0..4 {
+            strategy.update(MarketData {
+                symbol: "BTCUSDT".to_string(),
+                timestamp: (hour * 3_600_000).into(),
+                open: dec!(50000),
+                high: dec!(50000),
+                low: dec!(50000),
+                close: dec!(50000),
+                volume: dec!(1),
+            }).unwrap();
+            let orders = strategy.get_orders().unwrap();
+            quantities.push(orders.iter().map(|o| to_f64(o.quantity)).sum::<f64>());
+        }
+        
+        assert!((quantities[0] - 1.0).abs() < 1e-9);
+        assert_eq!(quantities[1], 0.0);
+        assert!((quantities[2] - 1.0).abs() < 1e-9);
+        assert!((quantities[3] - 1.0).abs() < 1e-9);
+        assert!(strategy.get_orders().unwrap().is_empty());
+    }
+}
@@ -2,10 +2,27 @@
 //!
 //! 거래량 가중 평균 가격을 기준으로 매매 신호를 생성하는 전략
 
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::execution_analyzer::ExecutionAnalyzer;
 use crate::error::TradingError;
+use crate::exchange::capabilities::ExchangeCapabilities;
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
-use crate::models::order::{Order, OrderSide, OrderType};
-use crate::strategies::Strategy;
+use crate::models::order_book::OrderBook;
+use crate::models::order::{Order, OrderId, OrderSide, OrderType};
+use crate::models::trade::Trade;
+use crate::strategies::{execution_progress, ExecutionMode, ParamUpdate, Strategy, BOOK_DEPTH_LEVELS};
+
+/// 재시작 시 복원하는 VWAP 진행 상태
+#[derive(Debug, Serialize, Deserialize)]
+struct VwapState {
+    executed_quantity: Decimal,
+    last_order_time: i64,
+    is_active: bool,
+}
 
 /// VWAP 매매 전략
 pub struct VwapStrategy {
@@ -18,7 +35,7 @@ pub struct VwapStrategy {
     /// 매매 방향
     side: OrderSide,
     /// 목표 총 수량
-    target_quantity: f64,
+    target_quantity: Decimal,
     /// 실행 간격 (밀리초)
     execution_interval: i64,
     /// VWAP 계산 윈도우 크기
@@ -26,13 +43,25 @@ pub struct VwapStrategy {
     /// 가격 데이터 히스토리
     price_data: Vec<MarketData>,
     /// 이미 실행한 수량
-    executed_quantity: f64,
+    executed_quantity: Decimal,
     /// 전략 활성 여부
     is_active: bool,
     /// 마지막 주문 시간
     last_order_time: i64,
     /// 주문 간격 (밀리초)
     order_interval: i64,
+    /// 실행 방식 설정
+    execution_mode: ExecutionMode,
+    /// 거래소 네이티브 VWAP 사용 여부 (거래소 기능 적용 후 결정)
+    native: bool,
+    /// 최신 호가창
+    order_book: Option<OrderBook>,
+    /// 슬라이스 상한 - 상대 호가 상위 단계 수량 대비 비율
+    book_participation: Option<f64>,
+    /// 도착 가격/TWAP 대비 실행 성과
+    analyzer: ExecutionAnalyzer,
+    /// 성과 기록용 분할 체결 번호
+    fill_count: usize,
 }
 
 impl VwapStrategy {
@@ -40,7 +69,7 @@ impl VwapStrategy {
     pub fn new(
         symbol: impl Into<String>,
         side: OrderSide,
-        target_quantity: f64,
+        target_quantity: Decimal,
         execution_interval: i64,
         vwap_window: usize,
     ) -> Self {
@@ -48,6 +77,7 @@ impl VwapStrategy {
         
         VwapStrategy {
             name: format!("VWAP-{}", symbol_str),
+            analyzer: ExecutionAnalyzer::new(symbol_str.clone()),
             description: "Volume Weighted Average Price based execution strategy".to_string(),
             symbol: symbol_str,
             side,
@@ -55,15 +85,37 @@ impl VwapStrategy {
             execution_interval,
             vwap_window,
             price_data: Vec::new(),
-            executed_quantity: 0.0,
+            executed_quantity: Decimal::ZERO,
             is_active: true,
             last_order_time: 0,
             order_interval: execution_interval / 10, // 10개 분할 주문
+            execution_mode: ExecutionMode::ClientSide,
+            native: false,
+            order_book: None,
+            book_participation: None,
+            fill_count: 0,
         }
     }
+
+    /// 슬라이스를 상대 호가 상위 단계 수량의 rate 배 이하로 제한 (호가창 수신 시)
+    pub fn with_book_participation(mut self, rate: f64) -> Self {
+        self.book_participation = Some(rate);
+        self
+    }
+
+    /// 실행 방식 설정 (PreferNative 는 거래소 기능 적용 시 확정)
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.execution_mode = mode;
+        self
+    }
+
+    /// 네이티브 실행 여부
+    pub fn is_native(&self) -> bool {
+        self.native
+    }
     
     /// VWAP 계산
-    fn calculate_vwap(&self) -> Option<f64> {
+    fn calculate_vwap(&self) -> Option<Decimal> {
         if self.price_data.is_empty() {
             return None;
         }
@@ -74,50 +126,50 @@ impl VwapStrategy {
             &self.price_data[self.price_data.len() - self.vwap_window..]
         };
         
-        let volume_price_sum: f64 = window_data.iter()
+        let volume_price_sum: Decimal = window_data.iter()
           .map(|data| data.volume * data.close)
           .sum();
         
-        let volume_sum: f64 = window_data.iter()
+        let volume_sum: Decimal = window_data.iter()
           .map(|data| data.volume)
           .sum();
         
-        if volume_sum > 0.0 {
+        if volume_sum > Decimal::ZERO {
             Some(volume_price_sum / volume_sum)
         } else {
             None
         }
     }
-}
-
-impl Strategy for VwapStrategy {
-    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
-        if market_data.symbol != self.symbol {
-            return Ok(());
-        }
-        
-        // 시장 데이터 저장
-        self.price_data.push(market_data);
-        
-        // 윈도우 크기 유지
-        if self.price_data.len() > self.vwap_window * 2 {
-            self.price_data.remove(0);
-        }
-        
-        Ok(())
-    }
     
-    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+    /// 현재 분할 주문 계산 (네이티브 실행이면 부모 주문 하나)
+    fn slice_orders(&mut self) -> Result<Vec<Order>, TradingError> {
         if !self.is_active || self.executed_quantity >= self.target_quantity {
             return Ok(Vec::new());
         }
         
         let current_time = if let Some(last_data) = self.price_data.last() {
-            last_data.timestamp
+            last_data.timestamp.as_millis()
         } else {
             return Ok(Vec::new());
         };
         
+        // 네이티브 VWAP: 남은 전체 수량을 실행 기간과 함께 단일 부모 주문으로 위임
+        if self.native {
+            let remaining = self.target_quantity - self.executed_quantity;
+            let reference_price = self.price_data.last().map(|d| d.close).unwrap_or_default();
+            let order = Order::new(
+                self.symbol.clone(),
+                self.side.clone(),
+                OrderType::Market,
+                remaining,
+                reference_price,
+            ).with_vwap_params(self.execution_interval, None);
+            self.executed_quantity = self.target_quantity;
+            self.last_order_time = current_time;
+            self.is_active = false;
+            return Ok(vec![order]);
+        }
+        
         // 주문 간격 체크
         if current_time - self.last_order_time < self.order_interval {
             return Ok(Vec::new());
@@ -132,10 +184,16 @@ impl Strategy for VwapStrategy {
         // 남은 수량 계산
         let remaining = self.target_quantity - self.executed_quantity;
         
-        // 다음 주문 크기 계산
-        let slice_size = (self.target_quantity / 10.0).min(remaining);
+        // 다음 주문 크기 계산 (호가창이 있으면 보이는 유동성 대비 상한 적용)
+        let mut slice_size = (self.target_quantity / Decimal::TEN).min(remaining);
+        if let (Some(rate), Some(book)) = (self.book_participation, &self.order_book) {
+            let depth = to_decimal(book.depth(&self.side, BOOK_DEPTH_LEVELS));
+            if depth > Decimal::ZERO {
+                slice_size = slice_size.min(depth * to_decimal(rate));
+            }
+        }
         
-        if slice_size > 0.0 {
+        if slice_size > Decimal::ZERO {
             // 주문 생성
             let order = Order::new(
                 self.symbol.clone(),
@@ -160,6 +218,95 @@ impl Strategy for VwapStrategy {
         }
     }
     
+    /// 시장가 분할은 현재가 체결로 실행 성과에 기록
+    fn record_fills(&mut self, orders: &[Order]) {
+        for order in orders {
+            let fill_id = format!("{}-{}", self.name, self.fill_count);
+            self.fill_count += 1;
+            self.analyzer.add_trade(Trade::new(
+                fill_id.clone(),
+                order.symbol.clone(),
+                order.price,
+                order.quantity,
+                self.price_data.last().map_or(order.created_at, |md| md.timestamp),
+                OrderId(fill_id),
+                order.side.clone(),
+            ));
+        }
+    }
+
+    /// 실행 성과 분석기에 시세 반영 (첫 시세가 도착 가격)
+    fn track_market_data(&mut self, market_data: &MarketData) {
+        if self.analyzer.arrival_price().is_none() {
+            self.analyzer.set_arrival_price(market_data.close_f64());
+        }
+        if self.is_active {
+            self.analyzer.add_market_data(market_data.clone());
+        }
+    }
+}
+
+impl Strategy for VwapStrategy {
+    fn update(&mut self, market_data: MarketData) -> Result<(), TradingError> {
+        if market_data.symbol != self.symbol {
+            return Ok(());
+        }
+        
+        // 시장 데이터 저장
+        self.track_market_data(&market_data);
+        self.price_data.push(market_data);
+        
+        // 윈도우 크기 유지
+        if self.price_data.len() > self.vwap_window * 2 {
+            self.price_data.remove(0);
+        }
+        
+        Ok(())
+    }
+    
+    fn get_orders(&mut self) -> Result<Vec<Order>, TradingError> {
+        let orders = self.slice_orders()?;
+        if !self.native {
+            self.record_fills(&orders);
+        }
+        Ok(orders)
+    }
+    
+    fn status(&self) -> serde_json::Value {
+        let mut status = execution_progress(self.executed_quantity, self.target_quantity);
+        status["vwap"] = serde_json::json!(self.calculate_vwap());
+        status["vwap_window"] = serde_json::json!(self.vwap_window);
+        status["book_participation"] = serde_json::json!(self.book_participation);
+        status["native"] = serde_json::json!(self.native);
+        status
+    }
+
+    /// 참여율(null 이면 해제), VWAP 윈도우, 목표 수량(실행한 수량 이상) 변경
+    fn update_params(&mut self, params: serde_json::Value) -> Result<(), TradingError> {
+        let params = ParamUpdate::new(&self.name, params)?;
+        params.allow(&["book_participation", "vwap_window", "quantity"])?;
+        if self.native {
+            return Err(params.invalid("native VWAP order is already delegated to the exchange"));
+        }
+        let participation = params.participation("book_participation")?;
+        let window = params.usize("vwap_window")?;
+        let quantity = params.decimal("quantity")?;
+        if quantity.is_some_and(|q| q < self.executed_quantity) {
+            return Err(params.invalid(format!("quantity is below the executed {}", self.executed_quantity)));
+        }
+
+        if let Some(rate) = participation {
+            self.book_participation = rate;
+        }
+        if let Some(window) = window {
+            self.vwap_window = window;
+        }
+        if let Some(quantity) = quantity {
+            self.target_quantity = quantity;
+        }
+        Ok(())
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -167,11 +314,46 @@ impl Strategy for VwapStrategy {
     fn description(&self) -> &str {
         &self.description
     }
+    
+    fn update_order_book(&mut self, book: &OrderBook) {
+        if book.symbol == self.symbol {
+            self.order_book = Some(book.clone());
+        }
+    }
+    
+    fn is_execution_strategy(&self) -> bool {
+        true
+    }
+
+    fn apply_capabilities(&mut self, capabilities: &ExchangeCapabilities) {
+        self.native = self.execution_mode.resolve(&self.name, OrderType::VWAP, capabilities);
+    }
+    
+    fn execution_report(&self) -> Option<HashMap<String, f64>> {
+        Some(self.analyzer.get_report())
+    }
+    
+    fn export_state(&self) -> Option<serde_json::Value> {
+        serde_json::to_value(VwapState {
+            executed_quantity: self.executed_quantity,
+            last_order_time: self.last_order_time,
+            is_active: self.is_active,
+        }).ok()
+    }
+    
+    fn restore_state(&mut self, state: &serde_json::Value) -> Result<(), TradingError> {
+        let state: VwapState = serde_json::from_value(state.clone())?;
+        self.executed_quantity = state.executed_quantity;
+        self.last_order_time = state.last_order_time;
+        self.is_active = state.is_active;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::decimal::{dec, to_decimal};
     
     #[test]
     fn test_vwap_strategy() {
@@ -179,7 +361,7 @@ mod tests {
         let mut strategy = VwapStrategy::new(
             "BTCUSDT",
             OrderSide::Buy,
-            1.0,
+            dec!(1),
             3600000,
             10,
         );
@@ -188,12 +370,12 @@ mod tests {
         for i in 0..15 {
             let market_data = MarketData {
                 symbol: "BTCUSDT".to_string(),
-                timestamp: 1000 + i * 60000,
-                open: 50000.0 + i as f64 * 10.0,
-                high: 50100.0 + i as f64 * 10.0,
-                low: 49900.0 + i as f64 * 10.0,
-                close: 50000.0 + i as f64 * 10.0,
-                volume: 10.0 + i as f64,
+                timestamp: (1000 + i * 60000).into(),
+                open: to_decimal(50000.0 + i as f64 * 10.0),
+                high: to_decimal(50100.0 + i as f64 * 10.0),
+                low: to_decimal(49900.0 + i as f64 * 10.0),
+                close: to_decimal(50000.0 + i as f64 * 10.0),
+                volume: to_decimal(10.0 + i as f64),
             };
             
             let result = strategy.update(market_data);
@@ -209,7 +391,28 @@ mod tests {
         assert_eq!(first_order.symbol, "BTCUSDT");
         assert_eq!(first_order.side, OrderSide::Buy);
         assert_eq!(first_order.order_type, OrderType::Limit);
-        assert!(first_order.quantity > 0.0);
-        assert!(first_order.price > 0.0);
+        assert!(first_order.quantity > Decimal::ZERO);
+        assert!(first_order.price > Decimal::ZERO);
+    }
+    
+    #[test]
+    fn test_vwap_slice_capped_by_book_depth() {
+        use crate::models::order_book::PriceLevel;
+        
+        let mut strategy = VwapStrategy::new("BTCUSDT", OrderSide::Buy, dec!(10), 3600000, 10)
+            .with_book_participation(0.5);
+        strategy.update(MarketData::new("BTCUSDT", 1_000_000, dec!(100), dec!(100), dec!(100), dec!(100), dec!(5))).unwrap();
+        // 다른 심볼 호가는 무시
+        strategy.update_order_book(&OrderBook::new("ETHUSDT", 1000, vec![], vec![PriceLevel::new(10.0, 0.1)]));
+        strategy.update_order_book(&OrderBook::new(
+            "BTCUSDT",
+            1000,
+            vec![PriceLevel::new(99.9, 5.0)],
+            vec![PriceLevel::new(100.1, 0.4), PriceLevel::new(100.2, 0.6)],
+        ));
+        
+        // 기본 슬라이스 1.0 -> 상대 호가 1.0 의 50%
+        let orders = strategy.get_orders().unwrap();
+        assert_eq!(orders[0].quantity, dec!(0.5));
     }
 }
\ No newline at end of file
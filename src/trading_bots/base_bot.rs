@@ -7,11 +7,22 @@
 
 use std::sync::Arc;
 use crate::error::TradingError;
+use crate::indicators::{Indicator, IndicatorResult};
+use crate::models::decimal::{to_decimal, Decimal};
 use crate::models::market_data::MarketData;
 use crate::models::order::{Order, OrderSide, OrderType};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
+use crate::signals::position_sizing::FixedSizePositionSizer;
 use crate::signals::signal_types::{SignalType, SignalWithMetadata};
+use crate::strategies::ParamUpdate;
 use super::bot_config::TradingBotConfig;
 
+// 자본 예산이 배정되기 전의 기본 가용 자본
+pub const DEFAULT_AVAILABLE_CAPITAL: f64 = 10000.0;
+
+// 실행 중 바꿀 수 있는 포지션 크기 파라미터 (지표 기간은 전략 이름과 지표 이력에 묶여 있어 제외)
+pub const SIZING_PARAMS: [&str; 2] = ["base_position_size", "strength_multiplier"];
+
 pub trait TradingBot: Send + Sync {
   // 시장 데이터로 봇 상태 업데이트
   fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError>;
@@ -28,6 +39,38 @@ pub trait TradingBot: Send + Sync {
   // 봇 설정 업데이트
   fn update_config(&mut self, config: TradingBotConfig) -> Result<(), TradingError>;
   
+  // 포지션 크기 계산에 사용할 가용 자본 설정 (전략 자본 예산의 남은 금액)
+  fn set_available_capital(&mut self, _capital: f64) {}
+  
+  // 진입 주문에 붙일 손절/익절 설정 - None 이면 해제 (기본: 지원 안 함)
+  fn set_entry_exits(&mut self, _exits: Option<EntryExits>) -> Result<(), TradingError> {
+    Err(TradingError::InvalidParameter(format!("{}: entry exits are not supported", self.name())))
+  }
+  
+  // 현재 손절/익절 설정
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    None
+  }
+  
+  // 실행 상태 보고 (기본: 마지막 신호)
+  fn status(&self) -> serde_json::Value {
+    let signal = self.evaluate_signals().ok().and_then(|signals| signals.last().cloned());
+    serde_json::json!({
+      "signal": signal.as_ref().map(|s| format!("{:?}", s.signal_type)),
+      "signal_strength": signal.as_ref().map(|s| s.strength),
+    })
+  }
+  
+  // 현재 지표 결과 (지표 이름, 결과) - 신호 집계 엔진 입력 (기본: 없음)
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    Vec::new()
+  }
+  
+  // 실행 중 파라미터 변경 - 지표 이력은 유지 (기본: 지원 안 함)
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    Err(params.invalid("live parameter updates are not supported"))
+  }
+  
   // 봇 상태 리셋
   fn reset(&mut self);
   
@@ -59,19 +102,55 @@ pub fn create_bot(symbol: &str, config: TradingBotConfig) -> Result<Box<dyn Trad
   }
 }
 
-// 신호와 포지션을 기반으로 주문 생성 헬퍼 함수
+// 포지션 크기 파라미터를 반영한 (설정, 사이저) - 검증만 하고 봇 반영은 호출 측에서
+pub fn tuned_sizing(config: &TradingBotConfig, params: &ParamUpdate) -> Result<(TradingBotConfig, FixedSizePositionSizer), TradingError> {
+  let mut config = config.clone();
+  if let Some(size) = params.positive("base_position_size")? {
+    config.set_param("base_position_size", size);
+  }
+  if let Some(multiplier) = params.f64("strength_multiplier")? {
+    if multiplier < 0.0 {
+      return Err(params.invalid("strength_multiplier must not be negative"));
+    }
+    config.set_param("strength_multiplier", multiplier);
+  }
+  let sizer = FixedSizePositionSizer::new(
+    config.get_f64("base_position_size").unwrap_or(1.0),
+    config.get_f64("strength_multiplier").unwrap_or(0.5),
+  );
+  Ok((config, sizer))
+}
+
+// 계산 가능한 지표의 (이름, 결과)
+pub fn ready_result(indicator: &dyn Indicator) -> Option<(String, IndicatorResult)> {
+  if !indicator.is_ready() {
+    return None;
+  }
+  indicator.calculate().ok().map(|result| (indicator.name().to_string(), result))
+}
+
+// 진입 주문에 손절/익절 첨부 (설정이 없으면 그대로)
+fn with_exits(order: Order, exits: Option<&EntryExits>) -> Option<Order> {
+  match exits {
+    Some(exits) => exits.attach(order),
+    None => Some(order),
+  }
+}
+
+// 신호와 포지션을 기반으로 주문 생성 헬퍼 함수 (exits 가 있으면 매수/매도 진입에 손절/익절 첨부)
 pub fn create_order_from_signal(
   symbol: &str,
   signal: &SignalWithMetadata,
   position_size: f64,
   current_position: f64,
+  exits: Option<&EntryExits>,
 ) -> Option<Order> {
   // 신호 유형에 따른 주문 생성
   match signal.signal_type {
     SignalType::Buy | SignalType::StrongBuy => {
       // 현재 롱 포지션이 없거나 숏 포지션인 경우만 매수
       if current_position <= 0.0 {
-        Some(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, position_size, 0.0))
+        with_exits(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, to_decimal(position_size), Decimal::ZERO), exits)
       } else {
         None
       }
@@ -80,7 +159,7 @@ pub fn create_order_from_signal(
     SignalType::Sell | SignalType::StrongSell => {
       // 현재 숏 포지션이 없거나 롱 포지션인 경우만 매도
       if current_position >= 0.0 {
-        Some(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, position_size, 0.0))
+        with_exits(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, to_decimal(position_size), Decimal::ZERO), exits)
       } else {
         None
       }
@@ -91,7 +170,7 @@ pub fn create_order_from_signal(
       if current_position > 0.0 {
         let reduce_size = (current_position * 0.5).min(position_size);
         if reduce_size > 0.0 {
-          Some(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, reduce_size, 0.0))
+          Some(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, to_decimal(reduce_size), Decimal::ZERO))
         } else {
           None
         }
@@ -105,7 +184,7 @@ pub fn create_order_from_signal(
       if current_position < 0.0 {
         let reduce_size = (current_position.abs() * 0.5).min(position_size);
         if reduce_size > 0.0 {
-          Some(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, reduce_size, 0.0))
+          Some(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, to_decimal(reduce_size), Decimal::ZERO))
         } else {
           None
         }
@@ -117,7 +196,7 @@ pub fn create_order_from_signal(
     SignalType::CloseLong => {
       // 롱 포지션 전체 청산
       if current_position > 0.0 {
-        Some(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, current_position, 0.0))
+        Some(Order::new(symbol.to_string(), OrderSide::Sell, OrderType::Market, to_decimal(current_position), Decimal::ZERO))
       } else {
         None
       }
@@ -126,7 +205,7 @@ pub fn create_order_from_signal(
     SignalType::CloseShort => {
       // 숏 포지션 전체 청산
       if current_position < 0.0 {
-        Some(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, current_position.abs(), 0.0))
+        Some(Order::new(symbol.to_string(), OrderSide::Buy, OrderType::Market, to_decimal(current_position.abs()), Decimal::ZERO))
       } else {
         None
       }
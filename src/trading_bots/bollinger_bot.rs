@@ -0,0 +1,151 @@
+/**
+* filename : bollinger_bot
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 볼린저 밴드 터치/스퀴즈 돌파 신호 기반 봇
+**/
+
+use crate::error::TradingError;
+use crate::models::market_data::MarketData;
+use crate::models::order::Order;
+use crate::indicators::{Indicator, IndicatorResult, volatility::BollingerBands};
+use crate::signals::signal_types::SignalWithMetadata;
+use crate::signals::position_sizing::{PositionSizer, FixedSizePositionSizer};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
+use super::bot_config::TradingBotConfig;
+use super::base_bot::{TradingBot, create_order_from_signal, ready_result, tuned_sizing, DEFAULT_AVAILABLE_CAPITAL, SIZING_PARAMS};
+use crate::strategies::ParamUpdate;
+
+pub struct BollingerBot {
+  symbol: String,
+  config: TradingBotConfig,
+  bands: BollingerBands,
+  position_sizer: FixedSizePositionSizer,
+  last_signal: Option<SignalWithMetadata>,
+  current_position: f64,
+  available_capital: f64,
+  // 진입 주문 손절/익절
+  exits: Option<EntryExits>,
+}
+
+impl BollingerBot {
+  pub fn new(symbol: String, config: TradingBotConfig) -> Result<Self, TradingError> {
+    let (bands, position_sizer) = Self::build(&config)?;
+
+    Ok(BollingerBot {
+      symbol,
+      config,
+      bands,
+      position_sizer,
+      last_signal: None,
+      current_position: 0.0,
+      available_capital: DEFAULT_AVAILABLE_CAPITAL,
+      exits: None,
+    })
+  }
+
+  // 설정에서 지표와 포지션 사이저 구성
+  fn build(config: &TradingBotConfig) -> Result<(BollingerBands, FixedSizePositionSizer), TradingError> {
+    let period = config.get_usize("period")?;
+    let std_dev = config.get_f64("std_dev").unwrap_or(2.0);
+    if period < 2 || std_dev <= 0.0 {
+      return Err(TradingError::InvalidParameter(format!("Invalid Bollinger parameters {}/{}", period, std_dev)));
+    }
+
+    let base_position_size = config.get_f64("base_position_size").unwrap_or(1.0);
+    let strength_multiplier = config.get_f64("strength_multiplier").unwrap_or(0.5);
+    Ok((
+      BollingerBands::new(period, std_dev),
+      FixedSizePositionSizer::new(base_position_size, strength_multiplier),
+    ))
+  }
+}
+
+impl TradingBot for BollingerBot {
+  fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if let Some(exits) = self.exits.as_mut() {
+      exits.update(market_data);
+    }
+    self.bands.update(market_data.close_f64(), Some(market_data.volume_f64()))?;
+
+    if self.bands.is_ready() {
+      let result = self.bands.calculate()?;
+
+      // 방향이 없는 스퀴즈 알림은 주문 신호에서 제외
+      self.last_signal = result.signals.iter()
+        .filter(|s| s.strength != 0.0)
+        .max_by(|a, b| a.strength.abs().partial_cmp(&b.strength.abs()).unwrap())
+        .map(SignalWithMetadata::from_indicator_signal);
+    }
+
+    Ok(())
+  }
+
+  fn evaluate_signals(&self) -> Result<Vec<SignalWithMetadata>, TradingError> {
+    Ok(self.last_signal.iter().cloned().collect())
+  }
+
+  fn generate_orders(&self) -> Result<Vec<Order>, TradingError> {
+    if let Some(signal) = &self.last_signal {
+      let position_size = self.position_sizer.calculate_position_size(
+        signal,
+        self.available_capital,
+        None,
+        0.0, // 예시 가격
+      );
+
+      if let Some(order) = create_order_from_signal(
+        &self.symbol,
+        signal,
+        position_size,
+        self.current_position,
+        self.exits.as_ref(),
+      ) {
+        return Ok(vec![order]);
+      }
+    }
+
+    Ok(vec![])
+  }
+
+  fn config(&self) -> &TradingBotConfig {
+    &self.config
+  }
+
+  fn update_config(&mut self, config: TradingBotConfig) -> Result<(), TradingError> {
+    let (bands, position_sizer) = Self::build(&config)?;
+    self.config = config;
+    self.bands = bands;
+    self.position_sizer = position_sizer;
+
+    Ok(())
+  }
+
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    params.allow(&SIZING_PARAMS)?;
+    (self.config, self.position_sizer) = tuned_sizing(&self.config, params)?;
+    Ok(())
+  }
+
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    ready_result(&self.bands).into_iter().collect()
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.available_capital = capital;
+  }
+  
+  fn set_entry_exits(&mut self, exits: Option<EntryExits>) -> Result<(), TradingError> {
+    self.exits = exits;
+    Ok(())
+  }
+  
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    self.exits.as_ref().map(|e| e.config())
+  }
+  
+  fn reset(&mut self) {
+    self.bands.reset();
+    self.last_signal = None;
+  }
+}
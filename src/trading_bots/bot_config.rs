@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use serde::{Serialize, Deserialize};
 use serde_json::{Value, json};
 use crate::error::TradingError;
+use crate::signals::signal_aggregator::AggregationConfig;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingBotConfig {
@@ -106,6 +107,16 @@ impl TradingBotConfig {
     }
   }
   
+  // 신호 집계 설정 ("aggregation" 파라미터, 없으면 None)
+  pub fn get_aggregation(&self) -> Result<Option<AggregationConfig>, TradingError> {
+    match self.get_param("aggregation") {
+      None | Some(Value::Null) => Ok(None),
+      Some(value) => serde_json::from_value(value.clone()).map(Some).map_err(|e| TradingError::ConfigError(
+        format!("Parameter 'aggregation' is invalid: {}", e)
+      )),
+    }
+  }
+  
   // 기본 컨피그 생성 헬퍼
   pub fn ma_crossover_config(fast_period: usize, slow_period: usize) -> Self {
     let mut config = TradingBotConfig::new()
@@ -141,6 +152,39 @@ impl TradingBotConfig {
     config
   }
   
+  pub fn bollinger_config(period: usize, std_dev: f64) -> Self {
+    let mut config = TradingBotConfig::new()
+      .with_name(&format!("Bollinger {}/{}", period, std_dev))
+      .with_description(&format!(
+        "Bollinger Bands Strategy with {} period, {} standard deviations",
+        period, std_dev
+      ));
+    
+    config.set_param("period", period as u64);
+    config.set_param("std_dev", std_dev);
+    config.set_param("base_position_size", 1.0);
+    config.set_param("strength_multiplier", 0.5);
+    
+    config
+  }
+  
+  pub fn ichimoku_config(tenkan_period: usize, kijun_period: usize, senkou_b_period: usize) -> Self {
+    let mut config = TradingBotConfig::new()
+      .with_name(&format!("Ichimoku {}/{}/{}", tenkan_period, kijun_period, senkou_b_period))
+      .with_description(&format!(
+        "Ichimoku Cloud Strategy with tenkan {}, kijun {}, senkou B {} periods",
+        tenkan_period, kijun_period, senkou_b_period
+      ));
+    
+    config.set_param("tenkan_period", tenkan_period as u64);
+    config.set_param("kijun_period", kijun_period as u64);
+    config.set_param("senkou_b_period", senkou_b_period as u64);
+    config.set_param("base_position_size", 1.0);
+    config.set_param("strength_multiplier", 0.5);
+    
+    config
+  }
+  
   pub fn macd_config(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
     let mut config = TradingBotConfig::new()
       .with_name(&format!("MACD {}/{}/{}", fast_period, slow_period, signal_period))
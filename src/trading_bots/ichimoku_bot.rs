@@ -0,0 +1,158 @@
+/**
+* filename : ichimoku_bot
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 일목균형표 구름 돌파/전환선·기준선 교차 신호 기반 봇
+**/
+
+use crate::error::TradingError;
+use crate::models::market_data::MarketData;
+use crate::models::order::Order;
+use crate::indicators::{Indicator, IndicatorResult, trend::Ichimoku};
+use crate::signals::signal_types::SignalWithMetadata;
+use crate::signals::position_sizing::{PositionSizer, FixedSizePositionSizer};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
+use super::bot_config::TradingBotConfig;
+use super::base_bot::{TradingBot, create_order_from_signal, ready_result, tuned_sizing, DEFAULT_AVAILABLE_CAPITAL, SIZING_PARAMS};
+use crate::strategies::ParamUpdate;
+
+pub struct IchimokuBot {
+  symbol: String,
+  config: TradingBotConfig,
+  ichimoku: Ichimoku,
+  position_sizer: FixedSizePositionSizer,
+  last_signal: Option<SignalWithMetadata>,
+  current_position: f64,
+  available_capital: f64,
+  // 진입 주문 손절/익절
+  exits: Option<EntryExits>,
+}
+
+impl IchimokuBot {
+  pub fn new(symbol: String, config: TradingBotConfig) -> Result<Self, TradingError> {
+    let (ichimoku, position_sizer) = Self::build(&config)?;
+
+    Ok(IchimokuBot {
+      symbol,
+      config,
+      ichimoku,
+      position_sizer,
+      last_signal: None,
+      current_position: 0.0,
+      available_capital: DEFAULT_AVAILABLE_CAPITAL,
+      exits: None,
+    })
+  }
+
+  // 설정에서 지표와 포지션 사이저 구성
+  fn build(config: &TradingBotConfig) -> Result<(Ichimoku, FixedSizePositionSizer), TradingError> {
+    let tenkan_period = config.get_usize("tenkan_period")?;
+    let kijun_period = config.get_usize("kijun_period")?;
+    let senkou_b_period = config.get_usize("senkou_b_period")?;
+    if tenkan_period == 0 || tenkan_period >= kijun_period || kijun_period >= senkou_b_period {
+      return Err(TradingError::InvalidParameter(format!(
+        "Invalid Ichimoku parameters {}/{}/{}", tenkan_period, kijun_period, senkou_b_period
+      )));
+    }
+
+    let base_position_size = config.get_f64("base_position_size").unwrap_or(1.0);
+    let strength_multiplier = config.get_f64("strength_multiplier").unwrap_or(0.5);
+    Ok((
+      Ichimoku::new(tenkan_period, kijun_period, senkou_b_period),
+      FixedSizePositionSizer::new(base_position_size, strength_multiplier),
+    ))
+  }
+}
+
+impl TradingBot for IchimokuBot {
+  fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if let Some(exits) = self.exits.as_mut() {
+      exits.update(market_data);
+    }
+    self.ichimoku.update_ohlc(
+      market_data.open_f64(),
+      market_data.high_f64(),
+      market_data.low_f64(),
+      market_data.close_f64(),
+      Some(market_data.volume_f64()),
+    )?;
+
+    if self.ichimoku.is_ready() {
+      let result = self.ichimoku.calculate()?;
+
+      self.last_signal = result.signals.iter()
+        .max_by(|a, b| a.strength.abs().partial_cmp(&b.strength.abs()).unwrap())
+        .map(SignalWithMetadata::from_indicator_signal);
+    }
+
+    Ok(())
+  }
+
+  fn evaluate_signals(&self) -> Result<Vec<SignalWithMetadata>, TradingError> {
+    Ok(self.last_signal.iter().cloned().collect())
+  }
+
+  fn generate_orders(&self) -> Result<Vec<Order>, TradingError> {
+    if let Some(signal) = &self.last_signal {
+      let position_size = self.position_sizer.calculate_position_size(
+        signal,
+        self.available_capital,
+        None,
+        0.0, // 예시 가격
+      );
+
+      if let Some(order) = create_order_from_signal(
+        &self.symbol,
+        signal,
+        position_size,
+        self.current_position,
+        self.exits.as_ref(),
+      ) {
+        return Ok(vec![order]);
+      }
+    }
+
+    Ok(vec![])
+  }
+
+  fn config(&self) -> &TradingBotConfig {
+    &self.config
+  }
+
+  fn update_config(&mut self, config: TradingBotConfig) -> Result<(), TradingError> {
+    let (ichimoku, position_sizer) = Self::build(&config)?;
+    self.config = config;
+    self.ichimoku = ichimoku;
+    self.position_sizer = position_sizer;
+
+    Ok(())
+  }
+
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    params.allow(&SIZING_PARAMS)?;
+    (self.config, self.position_sizer) = tuned_sizing(&self.config, params)?;
+    Ok(())
+  }
+
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    ready_result(&self.ichimoku).into_iter().collect()
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.available_capital = capital;
+  }
+  
+  fn set_entry_exits(&mut self, exits: Option<EntryExits>) -> Result<(), TradingError> {
+    self.exits = exits;
+    Ok(())
+  }
+  
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    self.exits.as_ref().map(|e| e.config())
+  }
+  
+  fn reset(&mut self) {
+    self.ichimoku.reset();
+    self.last_signal = None;
+  }
+}
@@ -11,8 +11,10 @@ use crate::models::order::Order;
 use crate::indicators::{Indicator, IndicatorResult, trend::MACD};
 use crate::signals::signal_types::{SignalType, SignalWithMetadata};
 use crate::signals::position_sizing::{PositionSizer, FixedSizePositionSizer};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
 use super::bot_config::TradingBotConfig;
-use super::base_bot::{TradingBot, create_order_from_signal};
+use super::base_bot::{TradingBot, create_order_from_signal, ready_result, tuned_sizing, DEFAULT_AVAILABLE_CAPITAL, SIZING_PARAMS};
+use crate::strategies::ParamUpdate;
 
 pub struct MACDBot {
   symbol: String,
@@ -21,6 +23,9 @@ pub struct MACDBot {
   position_sizer: FixedSizePositionSizer,
   last_signal: Option<SignalWithMetadata>,
   current_position: f64,
+  available_capital: f64,
+  // 진입 주문 손절/익절
+  exits: Option<EntryExits>,
 }
 
 impl MACDBot {
@@ -45,14 +50,19 @@ impl MACDBot {
       position_sizer,
       last_signal: None,
       current_position: 0.0,
+      available_capital: DEFAULT_AVAILABLE_CAPITAL,
+      exits: None,
     })
   }
 }
 
 impl TradingBot for MACDBot {
   fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if let Some(exits) = self.exits.as_mut() {
+      exits.update(market_data);
+    }
     // 현재 시장 데이터로 지표 업데이트
-    self.macd.update(market_data.close, Some(market_data.volume))?;
+    self.macd.update(market_data.close_f64(), Some(market_data.volume_f64()))?;
     
     // 신호 평가
     if self.macd.is_ready() {
@@ -93,7 +103,7 @@ impl TradingBot for MACDBot {
       // 포지션 크기 계산
       let position_size = self.position_sizer.calculate_position_size(
         signal,
-        10000.0, // 예시 가용 자본
+        self.available_capital,
         None,
         0.0, // 예시 가격
       );
@@ -104,6 +114,7 @@ impl TradingBot for MACDBot {
         signal,
         position_size,
         self.current_position,
+        self.exits.as_ref(),
       ) {
         return Ok(vec![order]);
       }
@@ -136,6 +147,29 @@ impl TradingBot for MACDBot {
     Ok(())
   }
   
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    params.allow(&SIZING_PARAMS)?;
+    (self.config, self.position_sizer) = tuned_sizing(&self.config, params)?;
+    Ok(())
+  }
+  
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    ready_result(&self.macd).into_iter().collect()
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.available_capital = capital;
+  }
+  
+  fn set_entry_exits(&mut self, exits: Option<EntryExits>) -> Result<(), TradingError> {
+    self.exits = exits;
+    Ok(())
+  }
+  
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    self.exits.as_ref().map(|e| e.config())
+  }
+  
   fn reset(&mut self) {
     self.macd.reset();
     self.last_signal = None;
@@ -10,6 +10,8 @@ pub mod base_bot;
 pub mod ma_crossover_bot;
 pub mod rsi_bot;
 pub mod macd_bot;
+pub mod bollinger_bot;
+pub mod ichimoku_bot;
 pub mod multi_indicator_bot;
 
 pub use bot_config::*;
@@ -17,4 +19,6 @@ pub use base_bot::*;
 pub use ma_crossover_bot::*;
 pub use rsi_bot::*;
 pub use macd_bot::*;
+pub use bollinger_bot::*;
+pub use ichimoku_bot::*;
 pub use multi_indicator_bot::*;
\ No newline at end of file
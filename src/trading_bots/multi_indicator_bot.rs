@@ -12,18 +12,28 @@ use crate::models::order::Order;
 use crate::indicators::{Indicator, IndicatorResult, moving_averages::MovingAverageCrossover, oscillators::RelativeStrengthIndex, trend::MACD};
 use crate::signals::signal_types::{SignalType, SignalWithMetadata};
 use crate::signals::signal_analyzer::SignalAnalyzer;
+use crate::signals::signal_aggregator::{Aggregation, SignalAggregator};
 use crate::signals::position_sizing::{PositionSizer, FixedSizePositionSizer};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
 use super::bot_config::TradingBotConfig;
-use super::base_bot::{TradingBot, create_order_from_signal};
+use super::base_bot::{TradingBot, create_order_from_signal, ready_result, tuned_sizing, DEFAULT_AVAILABLE_CAPITAL, SIZING_PARAMS};
+use crate::strategies::ParamUpdate;
 
 pub struct MultiIndicatorBot {
   symbol: String,
   config: TradingBotConfig,
   indicators: Vec<Box<dyn Indicator>>,
   signal_analyzer: SignalAnalyzer,
+  // 설정에 "aggregation" 이 있으면 고정 분석기 대신 가중 투표/거부 규칙으로 집계
+  aggregator: Option<SignalAggregator>,
+  last_results: Vec<(String, IndicatorResult)>,
+  last_aggregation: Option<Aggregation>,
   position_sizer: FixedSizePositionSizer,
   last_signals: Vec<SignalWithMetadata>,
   current_position: f64,
+  available_capital: f64,
+  // 진입 주문 손절/익절
+  exits: Option<EntryExits>,
 }
 
 impl MultiIndicatorBot {
@@ -61,6 +71,7 @@ impl MultiIndicatorBot {
     
     // 신호 분석기 생성
     let signal_analyzer = SignalAnalyzer::new();
+    let aggregator = config.get_aggregation()?.map(SignalAggregator::new).transpose()?;
     
     // 포지션 사이저 설정
     let base_position_size = config.get_f64("base_position_size").unwrap_or(1.0);
@@ -72,34 +83,46 @@ impl MultiIndicatorBot {
       config,
       indicators,
       signal_analyzer,
+      aggregator,
+      last_results: Vec::new(),
+      last_aggregation: None,
       position_sizer,
       last_signals: Vec::new(),
       current_position: 0.0,
+      available_capital: DEFAULT_AVAILABLE_CAPITAL,
+      exits: None,
     })
   }
 }
 
 impl TradingBot for MultiIndicatorBot {
   fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if let Some(exits) = self.exits.as_mut() {
+      exits.update(market_data);
+    }
     // 모든 지표 업데이트
     for indicator in &mut self.indicators {
-      indicator.update(market_data.close, Some(market_data.volume))?;
+      indicator.update(market_data.close_f64(), Some(market_data.volume_f64()))?;
     }
     
     // 각 지표의 결과 계산
-    let mut indicator_results = Vec::new();
+    self.last_results = self.indicators.iter()
+      .filter_map(|indicator| ready_result(indicator.as_ref()))
+      .collect();
     
-    for indicator in &self.indicators {
-      if indicator.is_ready() {
-        if let Ok(result) = indicator.calculate() {
-          indicator_results.push(result);
-        }
+    // 신호 분석
+    match &self.aggregator {
+      Some(aggregator) => {
+        let aggregation = aggregator.aggregate(&self.last_results);
+        self.last_signals = aggregation.signal.clone().into_iter().collect();
+        self.last_aggregation = Some(aggregation);
+      }
+      None => {
+        let results: Vec<IndicatorResult> = self.last_results.iter().map(|(_, result)| result.clone()).collect();
+        self.last_signals = self.signal_analyzer.analyze_indicator_results(&results);
       }
     }
     
-    // 신호 분석
-    self.last_signals = self.signal_analyzer.analyze_indicator_results(&indicator_results);
-    
     Ok(())
   }
   
@@ -124,7 +147,7 @@ impl TradingBot for MultiIndicatorBot {
     // 포지션 크기 계산
     let position_size = self.position_sizer.calculate_position_size(
       strongest_signal,
-      10000.0, // 예시 가용 자본
+      self.available_capital,
       None,
       0.0, // 예시 가격
     );
@@ -135,6 +158,7 @@ impl TradingBot for MultiIndicatorBot {
       strongest_signal,
       position_size,
       self.current_position,
+      self.exits.as_ref(),
     ) {
       return Ok(vec![order]);
     }
@@ -181,6 +205,8 @@ impl TradingBot for MultiIndicatorBot {
     }
     
     self.indicators = new_indicators;
+    self.aggregator = self.config.get_aggregation()?.map(SignalAggregator::new).transpose()?;
+    self.last_aggregation = None;
     
     // 포지션 사이저 재설정
     let base_position_size = self.config.get_f64("base_position_size").unwrap_or(1.0);
@@ -190,10 +216,44 @@ impl TradingBot for MultiIndicatorBot {
     Ok(())
   }
   
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    self.last_results.clone()
+  }
+  
+  fn status(&self) -> serde_json::Value {
+    let signal = self.last_signals.first();
+    serde_json::json!({
+      "signal": signal.map(|s| format!("{:?}", s.signal_type)),
+      "signal_strength": signal.map(|s| s.strength),
+      "aggregation": self.last_aggregation.as_ref().map(Aggregation::to_json),
+    })
+  }
+  
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    params.allow(&SIZING_PARAMS)?;
+    (self.config, self.position_sizer) = tuned_sizing(&self.config, params)?;
+    Ok(())
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.available_capital = capital;
+  }
+  
+  fn set_entry_exits(&mut self, exits: Option<EntryExits>) -> Result<(), TradingError> {
+    self.exits = exits;
+    Ok(())
+  }
+  
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    self.exits.as_ref().map(|e| e.config())
+  }
+  
   fn reset(&mut self) {
     for indicator in &mut self.indicators {
       indicator.reset();
     }
     self.last_signals.clear();
+    self.last_results.clear();
+    self.last_aggregation = None;
   }
 }
\ No newline at end of file
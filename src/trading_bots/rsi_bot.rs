@@ -11,8 +11,10 @@ use crate::models::order::Order;
 use crate::indicators::{Indicator, IndicatorResult, oscillators::RelativeStrengthIndex};
 use crate::signals::signal_types::{SignalType, SignalWithMetadata};
 use crate::signals::position_sizing::{PositionSizer, FixedSizePositionSizer};
+use crate::signals::entry_exits::{EntryExits, EntryExitsConfig};
 use super::bot_config::TradingBotConfig;
-use super::base_bot::{TradingBot, create_order_from_signal};
+use super::base_bot::{TradingBot, create_order_from_signal, ready_result, tuned_sizing, DEFAULT_AVAILABLE_CAPITAL, SIZING_PARAMS};
+use crate::strategies::ParamUpdate;
 
 pub struct RSIBot {
   symbol: String,
@@ -21,6 +23,9 @@ pub struct RSIBot {
   position_sizer: FixedSizePositionSizer,
   last_signal: Option<SignalWithMetadata>,
   current_position: f64,
+  available_capital: f64,
+  // 진입 주문 손절/익절
+  exits: Option<EntryExits>,
 }
 
 impl RSIBot {
@@ -45,14 +50,19 @@ impl RSIBot {
       position_sizer,
       last_signal: None,
       current_position: 0.0,
+      available_capital: DEFAULT_AVAILABLE_CAPITAL,
+      exits: None,
     })
   }
 }
 
 impl TradingBot for RSIBot {
   fn update(&mut self, market_data: &MarketData) -> Result<(), TradingError> {
+    if let Some(exits) = self.exits.as_mut() {
+      exits.update(market_data);
+    }
     // 현재 시장 데이터로 지표 업데이트
-    self.rsi.update(market_data.close, Some(market_data.volume))?;
+    self.rsi.update(market_data.close_f64(), Some(market_data.volume_f64()))?;
     
     // 신호 평가
     if self.rsi.is_ready() {
@@ -88,7 +98,7 @@ impl TradingBot for RSIBot {
       // 포지션 크기 계산
       let position_size = self.position_sizer.calculate_position_size(
         signal,
-        10000.0, // 예시 가용 자본
+        self.available_capital,
         None,
         0.0, // 예시 가격
       );
@@ -99,6 +109,7 @@ impl TradingBot for RSIBot {
         signal,
         position_size,
         self.current_position,
+        self.exits.as_ref(),
       ) {
         return Ok(vec![order]);
       }
@@ -131,6 +142,52 @@ impl TradingBot for RSIBot {
     Ok(())
   }
   
+  // 현재 RSI 값과 임계치 (기간이 차기 전에는 rsi 가 null)
+  fn status(&self) -> serde_json::Value {
+    serde_json::json!({
+      "rsi": self.rsi.calculate().ok().map(|result| result.value),
+      "oversold": self.config.get_f64("oversold").unwrap_or(30.0),
+      "overbought": self.config.get_f64("overbought").unwrap_or(70.0),
+      "signal": self.last_signal.as_ref().map(|s| format!("{:?}", s.signal_type)),
+      "signal_strength": self.last_signal.as_ref().map(|s| s.strength),
+    })
+  }
+  
+  // 과매수/과매도 임계치와 포지션 크기는 RSI 이력을 유지한 채 변경
+  fn update_params(&mut self, params: &ParamUpdate) -> Result<(), TradingError> {
+    params.allow(&["overbought", "oversold", SIZING_PARAMS[0], SIZING_PARAMS[1]])?;
+    let overbought = params.f64("overbought")?.unwrap_or(self.config.get_f64("overbought").unwrap_or(70.0));
+    let oversold = params.f64("oversold")?.unwrap_or(self.config.get_f64("oversold").unwrap_or(30.0));
+    if !(0.0..=100.0).contains(&oversold) || !(0.0..=100.0).contains(&overbought) || oversold >= overbought {
+      return Err(params.invalid(format!("invalid RSI bands {}/{}", oversold, overbought)));
+    }
+    
+    let (mut config, position_sizer) = tuned_sizing(&self.config, params)?;
+    config.set_param("overbought", overbought);
+    config.set_param("oversold", oversold);
+    self.rsi.set_thresholds(overbought, oversold);
+    self.config = config;
+    self.position_sizer = position_sizer;
+    Ok(())
+  }
+  
+  fn indicator_results(&self) -> Vec<(String, IndicatorResult)> {
+    ready_result(&self.rsi).into_iter().collect()
+  }
+  
+  fn set_available_capital(&mut self, capital: f64) {
+    self.available_capital = capital;
+  }
+  
+  fn set_entry_exits(&mut self, exits: Option<EntryExits>) -> Result<(), TradingError> {
+    self.exits = exits;
+    Ok(())
+  }
+  
+  fn entry_exits(&self) -> Option<&EntryExitsConfig> {
+    self.exits.as_ref().map(|e| e.config())
+  }
+  
   fn reset(&mut self) {
     self.rsi.reset();
     self.last_signal = None;
@@ -0,0 +1,269 @@
+/**
+* filename : webhooks
+* author : HAMA
+* date: 2025. 5. 11.
+* description: 외부 시스템 연동용 아웃바운드 웹훅 (HMAC 서명 + 재시도)
+**/
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::sync::{mpsc, RwLock};
+
+use crate::config::WebhookConfig;
+use crate::exchange::telemetry::ExchangeTelemetry;
+use crate::exchange::traits::Exchange;
+use crate::models::decimal::Decimal;
+use crate::notifications::Notifier;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 웹훅 이벤트 종류
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    OrderFilled,
+    PositionOpened,
+    PositionClosed,
+    RiskBreach,
+    StrategyToggled,
+    StrategyAnomaly,
+    StrategySignal,
+    DeadManTriggered,
+    DegradedModeEntered,
+    DegradedModeRecovered,
+    TradingHalted,
+    TradingResumed,
+    ConnectorDisconnected,
+    ConnectorReconnected,
+    MarginAlert,
+}
+
+impl WebhookEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventKind::OrderFilled => "order_filled",
+            WebhookEventKind::PositionOpened => "position_opened",
+            WebhookEventKind::PositionClosed => "position_closed",
+            WebhookEventKind::RiskBreach => "risk_breach",
+            WebhookEventKind::StrategyToggled => "strategy_toggled",
+            WebhookEventKind::StrategyAnomaly => "strategy_anomaly",
+            WebhookEventKind::StrategySignal => "strategy_signal",
+            WebhookEventKind::DeadManTriggered => "dead_man_triggered",
+            WebhookEventKind::DegradedModeEntered => "degraded_mode_entered",
+            WebhookEventKind::DegradedModeRecovered => "degraded_mode_recovered",
+            WebhookEventKind::TradingHalted => "trading_halted",
+            WebhookEventKind::TradingResumed => "trading_resumed",
+            WebhookEventKind::ConnectorDisconnected => "connector_disconnected",
+            WebhookEventKind::ConnectorReconnected => "connector_reconnected",
+            WebhookEventKind::MarginAlert => "margin_alert",
+        }
+    }
+}
+
+/// 웹훅으로 전송되는 이벤트 본문
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub event: WebhookEventKind,
+    pub timestamp: i64,
+    pub data: serde_json::Value,
+}
+
+/// 내부 이벤트 버스 - 이벤트를 큐에 넣고 백그라운드 워커가 웹훅으로 전송하며, 알림 채널에도 전달한다
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    sender: Option<mpsc::UnboundedSender<WebhookEvent>>,
+    notifier: Notifier,
+}
+
+impl WebhookDispatcher {
+    /// 엔드포인트 없이 동작하는 발송기 (모든 이벤트 무시)
+    pub fn disabled() -> Self {
+        WebhookDispatcher { sender: None, notifier: Notifier::disabled() }
+    }
+
+    /// 알림 채널 연결 (Telegram/Discord/이메일 등)
+    pub fn with_notifier(mut self, notifier: Notifier) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// 설정된 엔드포인트로 전송하는 워커 시작
+    pub fn spawn(endpoints: Vec<WebhookConfig>) -> Self {
+        if endpoints.is_empty() {
+            return Self::disabled();
+        }
+
+        let (sender, mut receiver) = mpsc::unbounded_channel::<WebhookEvent>();
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                let body = match serde_json::to_string(&event) {
+                    Ok(b) => b,
+                    Err(e) => {
+                        log::warn!("webhook serialize failed: {}", e);
+                        continue;
+                    }
+                };
+                for endpoint in endpoints.iter().filter(|ep| ep.accepts(event.event.as_str())) {
+                    // 느린 엔드포인트가 다른 엔드포인트를 막지 않도록 개별 태스크로 전송
+                    let http = http.clone();
+                    let endpoint = endpoint.clone();
+                    let body = body.clone();
+                    let timestamp = event.timestamp;
+                    tokio::spawn(async move {
+                        deliver(&http, &endpoint, &body, timestamp).await;
+                    });
+                }
+            }
+        });
+
+        WebhookDispatcher { sender: Some(sender), notifier: Notifier::disabled() }
+    }
+
+    /// 이벤트 발행 (비동기 전송, 호출자를 막지 않음)
+    pub fn emit(&self, kind: WebhookEventKind, data: serde_json::Value) {
+        if !self.is_enabled() {
+            return;
+        }
+        let event = WebhookEvent {
+            event: kind,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            data,
+        };
+        self.notifier.notify(&event);
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some() || self.notifier.is_enabled()
+    }
+}
+
+/// 본문 서명: hex(HMAC-SHA256(secret, "{timestamp}.{body}"))
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 단일 엔드포인트 전송 (지수 백오프 재시도)
+async fn deliver(http: &reqwest::Client, endpoint: &WebhookConfig, body: &str, timestamp: i64) {
+    let mut attempt = 0u32;
+    loop {
+        let mut req = http
+            .post(&endpoint.url)
+            .header("Content-Type", "application/json")
+            .header("X-XQuant-Timestamp", timestamp.to_string());
+        if let Some(secret) = &endpoint.secret {
+            req = req.header("X-XQuant-Signature", sign_payload(secret, timestamp, body));
+        }
+
+        match req.body(body.to_string()).send().await {
+            Ok(res) if res.status().is_success() => return,
+            Ok(res) => log::warn!("webhook {} responded {} (attempt {})", endpoint.url, res.status(), attempt + 1),
+            Err(e) => log::warn!("webhook {} failed: {} (attempt {})", endpoint.url, e, attempt + 1),
+        }
+
+        if attempt >= endpoint.max_retries {
+            log::error!("webhook {} dropped after {} attempts", endpoint.url, attempt + 1);
+            return;
+        }
+        let backoff_ms = 500u64.saturating_mul(2u64.pow(attempt));
+        tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+        attempt += 1;
+    }
+}
+
+/// 커넥터 연결 끊김/복구를 이벤트로 발행
+pub fn spawn_connection_watcher(telemetry: &ExchangeTelemetry, dispatcher: WebhookDispatcher) {
+    if !dispatcher.is_enabled() {
+        return;
+    }
+
+    let mut events = telemetry.subscribe_connections();
+    tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let kind = if event.connected { WebhookEventKind::ConnectorReconnected } else { WebhookEventKind::ConnectorDisconnected };
+                    dispatcher.emit(kind, serde_json::to_value(&event).unwrap_or_default());
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => log::warn!("connection watcher lagged: {} events skipped", n),
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    });
+}
+
+/// 거래소 포지션을 주기적으로 비교하여 포지션 오픈/청산 이벤트 발행
+pub fn spawn_position_watcher(
+    exchange: Arc<RwLock<dyn Exchange>>,
+    dispatcher: WebhookDispatcher,
+    interval: Duration,
+) {
+    if !dispatcher.is_enabled() {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut previous: HashMap<String, Decimal> = HashMap::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let positions = {
+                let ex = exchange.read().await;
+                match ex.get_positions().await {
+                    Ok(p) => p,
+                    Err(_) => continue,
+                }
+            };
+
+            let mut current: HashMap<String, Decimal> = HashMap::new();
+            for p in positions.iter().filter(|p| !p.quantity.is_zero()) {
+                current.insert(p.symbol.clone(), p.quantity);
+                if !previous.contains_key(&p.symbol) {
+                    dispatcher.emit(WebhookEventKind::PositionOpened, serde_json::to_value(p).unwrap_or_default());
+                }
+            }
+            for (symbol, quantity) in &previous {
+                if !current.contains_key(symbol) {
+                    dispatcher.emit(WebhookEventKind::PositionClosed, serde_json::json!({"symbol": symbol, "quantity": quantity}));
+                }
+            }
+            previous = current;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_payload_and_filter() {
+        let sig = sign_payload("secret", 1700000000000, "{\"a\":1}");
+        assert_eq!(sig.len(), 64);
+        assert_eq!(sig, sign_payload("secret", 1700000000000, "{\"a\":1}"));
+        assert_ne!(sig, sign_payload("other", 1700000000000, "{\"a\":1}"));
+
+        let endpoint = WebhookConfig {
+            url: "http://localhost/hook".to_string(),
+            secret: None,
+            events: vec!["order_filled".to_string()],
+            max_retries: 3,
+        };
+        assert!(endpoint.accepts("order_filled"));
+        assert!(!endpoint.accepts("risk_breach"));
+    }
+}